@@ -14,12 +14,42 @@
 //! At any point, either party can initiate a dispute (see [`super::dispute_resolution`]).
 //! If the borrower misses the repayment deadline, the escrow transitions
 //! to `Defaulted`.
+//!
+//! ## Oracle Conditions
+//!
+//! Some escrows should release on a real-world event — a delivery scan, a
+//! flight delay — rather than on either party's word. Such an escrow
+//! carries a [`ReleaseCondition`]: an oracle from the [`OracleRegistry`],
+//! one of its feeds, and a [`ConditionPredicate`] the reported value must
+//! pass. While the condition is pending the escrow cannot be released by
+//! hand. Each [`OracleAttestation`] is signed by the oracle's key; the first
+//! one that satisfies the predicate releases everything escrowed to the
+//! borrower.
+//!
+//! If the oracle reports nothing for the condition's silence timeout, the
+//! escrow falls back to a [`Dispute`] opened for the borrower, and the
+//! arbiter's ruling decides between releasing and refunding the lender.
+//!
+//! ## Amendments
+//!
+//! Once funded, the principal changes only through an [`EscrowAmendment`]
+//! signed by both lender and borrower: a top-up that locks more of the
+//! lender's funds, or a partial release scheduled for a date before the
+//! repayment deadline. Amendments carry a sequence number, so each signed
+//! amendment applies once. A top-up adds simple interest on the new funds
+//! for the time left to the deadline, and every amendment re-spreads what
+//! is still owed over the remaining [`Installment`]s.
+
+use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
+use nova_protocol::crypto::{NovaKeypair, NovaPublicKey, NovaSignature};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::dispute_resolution::{Dispute, DisputeCategory, DisputeStatus};
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -70,6 +100,52 @@ pub enum EscrowError {
     /// A dispute has already been opened on this escrow.
     #[error("escrow already has an active dispute")]
     AlreadyDisputed,
+
+    /// The oracle is not in the registry.
+    #[error("oracle {0} is not registered")]
+    UnknownOracle(String),
+
+    /// The oracle's public key is not a hex-encoded Ed25519 key.
+    #[error("invalid public key for oracle {0}")]
+    InvalidOracleKey(String),
+
+    /// The oracle does not publish the feed the condition names.
+    #[error("oracle {oracle_id} has no feed {feed}")]
+    UnsupportedFeed {
+        /// The oracle named by the condition.
+        oracle_id: String,
+        /// The feed it does not publish.
+        feed: String,
+    },
+
+    /// The escrow has no release condition.
+    #[error("escrow has no release condition")]
+    NoReleaseCondition,
+
+    /// The escrow releases only on its oracle condition.
+    #[error("release is waiting on oracle {oracle_id}")]
+    AwaitingOracle {
+        /// The oracle whose attestation releases the escrow.
+        oracle_id: String,
+    },
+
+    /// The attestation does not apply to this escrow's condition, or its
+    /// signature does not verify.
+    #[error("invalid oracle attestation: {0}")]
+    InvalidAttestation(String),
+
+    /// The supplied dispute does not belong to this escrow.
+    #[error("dispute {0} does not belong to this escrow")]
+    ForeignDispute(String),
+
+    /// The dispute has not been resolved or cancelled yet.
+    #[error("dispute {0} is still pending")]
+    DisputePending(String),
+
+    /// The amendment is for another escrow or sequence number, or is not
+    /// signed by both parties.
+    #[error("invalid amendment: {0}")]
+    InvalidAmendment(String),
 }
 
 // ---------------------------------------------------------------------------
@@ -92,6 +168,8 @@ pub enum EscrowStatus {
     Disputed,
     /// The borrower missed the repayment deadline.
     Defaulted,
+    /// The escrowed funds went back to the lender unreleased.
+    Refunded,
 }
 
 impl std::fmt::Display for EscrowStatus {
@@ -103,6 +181,7 @@ impl std::fmt::Display for EscrowStatus {
             EscrowStatus::Completed => write!(f, "Completed"),
             EscrowStatus::Disputed => write!(f, "Disputed"),
             EscrowStatus::Defaulted => write!(f, "Defaulted"),
+            EscrowStatus::Refunded => write!(f, "Refunded"),
         }
     }
 }
@@ -124,6 +203,295 @@ pub struct CreditTerms {
     pub grace_period_secs: u64,
 }
 
+/// A value reported by an oracle feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OracleValue {
+    /// A count or measurement, e.g. a flight delay in minutes.
+    Number(i64),
+    /// A status, e.g. a parcel's `"delivered"` scan.
+    Text(String),
+}
+
+/// The test a reported value must pass for the escrow to release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionPredicate {
+    /// The feed reports exactly this value.
+    Equals(OracleValue),
+    /// The feed reports a number at or above this threshold.
+    AtLeast(i64),
+    /// The feed reports a number at or below this threshold.
+    AtMost(i64),
+}
+
+impl ConditionPredicate {
+    /// Returns `true` if `value` passes the predicate. Numeric predicates
+    /// never pass on text.
+    pub fn is_satisfied_by(&self, value: &OracleValue) -> bool {
+        match (self, value) {
+            (ConditionPredicate::Equals(expected), value) => expected == value,
+            (ConditionPredicate::AtLeast(min), OracleValue::Number(n)) => n >= min,
+            (ConditionPredicate::AtMost(max), OracleValue::Number(n)) => n <= max,
+            (_, OracleValue::Text(_)) => false,
+        }
+    }
+}
+
+/// An oracle allowed to attest release conditions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredOracle {
+    /// Unique identifier of the oracle.
+    pub oracle_id: String,
+    /// Hex-encoded Ed25519 public key its attestations are signed with.
+    pub public_key: String,
+    /// Names of the feeds it publishes, e.g. `"parcel_status"`.
+    pub feeds: Vec<String>,
+    /// Timestamp when the oracle was registered.
+    pub registered_at: DateTime<Utc>,
+}
+
+/// The oracles escrows may name in a [`ReleaseCondition`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OracleRegistry {
+    oracles: HashMap<String, RegisteredOracle>,
+}
+
+impl OracleRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an oracle, replacing any earlier registration under the
+    /// same ID (e.g. to rotate its key).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EscrowError::InvalidOracleKey`] if `public_key` is not a
+    /// hex-encoded Ed25519 key.
+    pub fn register(
+        &mut self,
+        oracle_id: String,
+        public_key: String,
+        feeds: Vec<String>,
+    ) -> Result<(), EscrowError> {
+        if NovaPublicKey::from_hex(&public_key).is_err() {
+            return Err(EscrowError::InvalidOracleKey(oracle_id));
+        }
+        self.oracles.insert(
+            oracle_id.clone(),
+            RegisteredOracle {
+                oracle_id,
+                public_key,
+                feeds,
+                registered_at: Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Removes an oracle. Escrows naming it stop accepting its
+    /// attestations and eventually fall back to a dispute.
+    pub fn deregister(&mut self, oracle_id: &str) -> Option<RegisteredOracle> {
+        self.oracles.remove(oracle_id)
+    }
+
+    /// Looks up a registered oracle.
+    pub fn get(&self, oracle_id: &str) -> Option<&RegisteredOracle> {
+        self.oracles.get(oracle_id)
+    }
+}
+
+/// Releases an escrow when an oracle reports a value passing `predicate`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseCondition {
+    /// The registered oracle whose attestations count.
+    pub oracle_id: String,
+    /// The oracle feed the condition reads.
+    pub feed: String,
+    /// The test the reported value must pass.
+    pub predicate: ConditionPredicate,
+    /// Seconds without an attestation after which the oracle counts as
+    /// silent and the escrow falls back to a dispute.
+    pub silence_timeout_secs: u64,
+}
+
+/// A signed report from an oracle about one escrow's feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleAttestation {
+    /// The reporting oracle.
+    pub oracle_id: String,
+    /// The escrow the report is for.
+    pub escrow_id: String,
+    /// The feed reported on.
+    pub feed: String,
+    /// The reported value.
+    pub value: OracleValue,
+    /// When the oracle observed the value.
+    pub observed_at: DateTime<Utc>,
+    /// Hex-encoded Ed25519 signature over [`signing_payload`](Self::signing_payload).
+    pub signature: String,
+}
+
+impl OracleAttestation {
+    /// Creates an attestation signed with the oracle's `keypair`.
+    pub fn sign(
+        keypair: &NovaKeypair,
+        oracle_id: String,
+        escrow_id: String,
+        feed: String,
+        value: OracleValue,
+        observed_at: DateTime<Utc>,
+    ) -> Self {
+        let mut attestation = Self {
+            oracle_id,
+            escrow_id,
+            feed,
+            value,
+            observed_at,
+            signature: String::new(),
+        };
+        attestation.signature = keypair.sign(&attestation.signing_payload()).to_hex();
+        attestation
+    }
+
+    /// The bytes the oracle signs: a domain tag, then every field
+    /// length-prefixed so none can run into the next.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        fn put(buf: &mut Vec<u8>, bytes: &[u8]) {
+            buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            buf.extend_from_slice(bytes);
+        }
+
+        let mut buf = b"nova-oracle-attestation".to_vec();
+        put(&mut buf, self.oracle_id.as_bytes());
+        put(&mut buf, self.escrow_id.as_bytes());
+        put(&mut buf, self.feed.as_bytes());
+        match &self.value {
+            OracleValue::Number(n) => {
+                buf.push(0x00);
+                buf.extend_from_slice(&n.to_le_bytes());
+            }
+            OracleValue::Text(text) => {
+                buf.push(0x01);
+                put(&mut buf, text.as_bytes());
+            }
+        }
+        buf.extend_from_slice(&self.observed_at.timestamp_millis().to_le_bytes());
+        buf
+    }
+
+    /// Returns `true` if the signature verifies against `public_key` (hex).
+    pub fn verify(&self, public_key: &str) -> bool {
+        verify_hex(public_key, &self.signing_payload(), &self.signature)
+    }
+}
+
+/// Seconds in the 365-day year interest rates are quoted over.
+const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+
+/// One repayment due from the borrower.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Installment {
+    /// When the installment is due.
+    pub due_at: DateTime<Utc>,
+    /// Amount due, in photons.
+    pub amount: u64,
+}
+
+/// A partial release to the borrower, agreed to happen at `release_at`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledRelease {
+    /// Amount to release, in photons.
+    pub amount: u64,
+    /// When the release becomes due.
+    pub release_at: DateTime<Utc>,
+}
+
+/// A change to a funded escrow's terms.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmendmentKind {
+    /// The lender locks `amount` more, growing the principal.
+    TopUp {
+        /// Amount added, in photons.
+        amount: u64,
+    },
+    /// Part of the escrowed funds go to the borrower at a set date before
+    /// the repayment deadline.
+    ScheduledRelease(ScheduledRelease),
+}
+
+/// An amendment signed by both parties to the escrow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscrowAmendment {
+    /// The escrow being amended.
+    pub escrow_id: String,
+    /// Number of amendments applied before this one.
+    pub sequence: u32,
+    /// What changes.
+    pub kind: AmendmentKind,
+    /// Hex-encoded Ed25519 signature of the lender over
+    /// [`signing_payload`](Self::signing_payload).
+    pub lender_signature: String,
+    /// Hex-encoded Ed25519 signature of the borrower over the same payload.
+    pub borrower_signature: String,
+}
+
+impl EscrowAmendment {
+    /// Creates an unsigned amendment to `escrow`, next in its sequence.
+    pub fn new(escrow: &CreditEscrow, kind: AmendmentKind) -> Self {
+        Self {
+            escrow_id: escrow.escrow_id.clone(),
+            sequence: escrow.amendments_applied,
+            kind,
+            lender_signature: String::new(),
+            borrower_signature: String::new(),
+        }
+    }
+
+    /// The bytes both parties sign: a domain tag, the escrow ID (length-
+    /// prefixed), the sequence number and the change.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut buf = b"nova-escrow-amendment".to_vec();
+        buf.extend_from_slice(&(self.escrow_id.len() as u32).to_le_bytes());
+        buf.extend_from_slice(self.escrow_id.as_bytes());
+        buf.extend_from_slice(&self.sequence.to_le_bytes());
+        match &self.kind {
+            AmendmentKind::TopUp { amount } => {
+                buf.push(0x01);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            AmendmentKind::ScheduledRelease(release) => {
+                buf.push(0x02);
+                buf.extend_from_slice(&release.amount.to_le_bytes());
+                buf.extend_from_slice(&release.release_at.timestamp_millis().to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    /// Adds the lender's signature.
+    pub fn sign_as_lender(&mut self, keypair: &NovaKeypair) {
+        self.lender_signature = keypair.sign(&self.signing_payload()).to_hex();
+    }
+
+    /// Adds the borrower's signature.
+    pub fn sign_as_borrower(&mut self, keypair: &NovaKeypair) {
+        self.borrower_signature = keypair.sign(&self.signing_payload()).to_hex();
+    }
+}
+
+/// Returns `true` if `signature` (hex) over `payload` verifies against
+/// `public_key` (hex).
+pub(crate) fn verify_hex(public_key: &str, payload: &[u8], signature: &str) -> bool {
+    let (Ok(key), Ok(signature)) = (
+        NovaPublicKey::from_hex(public_key),
+        NovaSignature::from_hex(signature),
+    ) else {
+        return false;
+    };
+    key.verify(payload, &signature)
+}
+
 /// A credit escrow instance.
 ///
 /// Tracks the full lifecycle of a lender-borrower credit arrangement,
@@ -153,6 +521,26 @@ pub struct CreditEscrow {
     pub created_at: DateTime<Utc>,
     /// Timestamp of the most recent state change.
     pub updated_at: DateTime<Utc>,
+    /// Oracle condition that releases the escrow, if any.
+    #[serde(default)]
+    pub release_condition: Option<ReleaseCondition>,
+    /// When the condition's oracle was last heard from (or the condition
+    /// started waiting on it); the silence timeout runs from here.
+    #[serde(default)]
+    pub oracle_heard_at: Option<DateTime<Utc>>,
+    /// ID of the dispute opened because the oracle went silent.
+    #[serde(default)]
+    pub dispute_id: Option<String>,
+    /// Number of amendments applied so far.
+    #[serde(default)]
+    pub amendments_applied: u32,
+    /// Agreed partial releases not yet made, earliest first.
+    #[serde(default)]
+    pub scheduled_releases: Vec<ScheduledRelease>,
+    /// Repayments due from the borrower, earliest first. The last one
+    /// falls on the repayment deadline.
+    #[serde(default)]
+    pub repayment_schedule: Vec<Installment>,
 }
 
 impl CreditEscrow {
@@ -169,6 +557,10 @@ impl CreditEscrow {
     pub fn create(lender: String, borrower: String, terms: CreditTerms) -> Self {
         let now = Utc::now();
         let principal = terms.principal;
+        let repayment_schedule = vec![Installment {
+            due_at: terms.repayment_deadline,
+            amount: terms.total_owed,
+        }];
         Self {
             escrow_id: Uuid::new_v4().to_string(),
             lender,
@@ -181,9 +573,28 @@ impl CreditEscrow {
             status: EscrowStatus::Pending,
             created_at: now,
             updated_at: now,
+            release_condition: None,
+            oracle_heard_at: None,
+            dispute_id: None,
+            amendments_applied: 0,
+            scheduled_releases: Vec::new(),
+            repayment_schedule,
         }
     }
 
+    /// Splits repayment into `count` equal installments, evenly spaced from
+    /// creation to the repayment deadline. The last installment absorbs
+    /// any rounding remainder.
+    pub fn with_installments(mut self, count: u32) -> Self {
+        let count = count.max(1) as i32;
+        let span = self.terms.repayment_deadline - self.created_at;
+        let due_dates: Vec<DateTime<Utc>> = (1..=count)
+            .map(|i| self.created_at + span * i / count)
+            .collect();
+        self.repayment_schedule = spread(self.terms.total_owed, &due_dates);
+        self
+    }
+
     /// Lender deposits funds into the escrow.
     ///
     /// Can be called multiple times for partial funding. Once the full
@@ -218,11 +629,17 @@ impl CreditEscrow {
             .checked_add(amount)
             .ok_or(EscrowError::AmountOverflow)?;
 
+        let now = Utc::now();
         if self.funded_amount == self.principal {
             self.status = EscrowStatus::Funded;
+            // The oracle's silence is counted from when there is something
+            // to release.
+            if self.release_condition.is_some() {
+                self.oracle_heard_at = Some(now);
+            }
         }
 
-        self.updated_at = Utc::now();
+        self.updated_at = now;
         Ok(())
     }
 
@@ -234,8 +651,11 @@ impl CreditEscrow {
     /// # Errors
     ///
     /// Returns [`EscrowError::InvalidState`] if the escrow is not `Funded` or `Active`.
+    /// Returns [`EscrowError::AwaitingOracle`] if a release condition is
+    /// still pending.
     /// Returns [`EscrowError::InsufficientEscrowed`] if the requested amount
-    /// exceeds what is currently held in escrow.
+    /// exceeds what is currently held in escrow and not promised to a
+    /// scheduled release.
     pub fn release_to_borrower(&mut self, amount: u64) -> Result<(), EscrowError> {
         if self.status != EscrowStatus::Funded && self.status != EscrowStatus::Active {
             return Err(EscrowError::InvalidState {
@@ -243,10 +663,18 @@ impl CreditEscrow {
                 expected: "Funded or Active".into(),
             });
         }
+        if let Some(oracle_id) = self.pending_oracle() {
+            return Err(EscrowError::AwaitingOracle {
+                oracle_id: oracle_id.to_string(),
+            });
+        }
 
+        // Funds promised to a scheduled release are not available.
+        let scheduled: u64 = self.scheduled_releases.iter().map(|r| r.amount).sum();
         let available = self
             .funded_amount
             .checked_sub(self.released_amount)
+            .and_then(|held| held.checked_sub(scheduled))
             .ok_or(EscrowError::AmountOverflow)?;
 
         if amount > available {
@@ -306,7 +734,9 @@ impl CreditEscrow {
             self.status = EscrowStatus::Completed;
         }
 
-        self.updated_at = Utc::now();
+        let now = Utc::now();
+        self.reschedule_repayments(now);
+        self.updated_at = now;
         Ok(())
     }
 
@@ -349,7 +779,7 @@ impl CreditEscrow {
     /// `Completed`, `Defaulted`, or `Disputed`.
     pub fn dispute(&mut self, _reason: &str) -> Result<(), EscrowError> {
         match self.status {
-            EscrowStatus::Completed | EscrowStatus::Defaulted => {
+            EscrowStatus::Completed | EscrowStatus::Defaulted | EscrowStatus::Refunded => {
                 return Err(EscrowError::InvalidState {
                     current: self.status.to_string(),
                     expected: "Pending, Funded, or Active".into(),
@@ -365,11 +795,422 @@ impl CreditEscrow {
         self.updated_at = Utc::now();
         Ok(())
     }
+
+    /// Makes the escrow release on an oracle condition instead of by hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EscrowError::InvalidState`] if the escrow is not `Pending`
+    /// or `Funded`.
+    /// Returns [`EscrowError::UnknownOracle`] if the oracle is not registered.
+    /// Returns [`EscrowError::UnsupportedFeed`] if it does not publish the feed.
+    pub fn attach_release_condition(
+        &mut self,
+        registry: &OracleRegistry,
+        condition: ReleaseCondition,
+    ) -> Result<(), EscrowError> {
+        if self.status != EscrowStatus::Pending && self.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState {
+                current: self.status.to_string(),
+                expected: "Pending or Funded".into(),
+            });
+        }
+
+        let oracle = registry
+            .get(&condition.oracle_id)
+            .ok_or_else(|| EscrowError::UnknownOracle(condition.oracle_id.clone()))?;
+        if !oracle.feeds.contains(&condition.feed) {
+            return Err(EscrowError::UnsupportedFeed {
+                oracle_id: condition.oracle_id,
+                feed: condition.feed,
+            });
+        }
+
+        let now = Utc::now();
+        if self.status == EscrowStatus::Funded {
+            self.oracle_heard_at = Some(now);
+        }
+        self.release_condition = Some(condition);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Applies an oracle attestation to the release condition.
+    ///
+    /// Every valid attestation restarts the silence timeout. One whose
+    /// value satisfies the predicate releases everything escrowed to the
+    /// borrower and returns `true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EscrowError::NoReleaseCondition`] if the escrow has no condition.
+    /// Returns [`EscrowError::InvalidState`] if the escrow is not `Funded`.
+    /// Returns [`EscrowError::UnknownOracle`] if the oracle was deregistered.
+    /// Returns [`EscrowError::InvalidAttestation`] if the attestation is for
+    /// another escrow, oracle or feed, or its signature does not verify.
+    pub fn submit_attestation(
+        &mut self,
+        registry: &OracleRegistry,
+        attestation: &OracleAttestation,
+    ) -> Result<bool, EscrowError> {
+        let condition = self
+            .release_condition
+            .as_ref()
+            .ok_or(EscrowError::NoReleaseCondition)?;
+        if self.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState {
+                current: self.status.to_string(),
+                expected: "Funded".into(),
+            });
+        }
+
+        if attestation.escrow_id != self.escrow_id {
+            return Err(EscrowError::InvalidAttestation(format!(
+                "attestation is for escrow {}",
+                attestation.escrow_id
+            )));
+        }
+        if attestation.oracle_id != condition.oracle_id || attestation.feed != condition.feed {
+            return Err(EscrowError::InvalidAttestation(format!(
+                "condition reads {}/{}, attestation is from {}/{}",
+                condition.oracle_id, condition.feed, attestation.oracle_id, attestation.feed
+            )));
+        }
+        let oracle = registry
+            .get(&attestation.oracle_id)
+            .ok_or_else(|| EscrowError::UnknownOracle(attestation.oracle_id.clone()))?;
+        if !attestation.verify(&oracle.public_key) {
+            return Err(EscrowError::InvalidAttestation(
+                "signature does not verify".into(),
+            ));
+        }
+
+        let satisfied = condition.predicate.is_satisfied_by(&attestation.value);
+        let now = Utc::now();
+        self.oracle_heard_at = Some(now);
+        if satisfied {
+            self.release_all_escrowed();
+        }
+        self.updated_at = now;
+        Ok(satisfied)
+    }
+
+    /// Falls back to a dispute if the condition's oracle has gone silent.
+    ///
+    /// Returns the opened [`Dispute`] — initiated by the borrower, who is
+    /// owed the release — if this call opened one.
+    pub fn check_oracle_silence(&mut self) -> Option<Dispute> {
+        self.check_oracle_silence_at(Utc::now())
+    }
+
+    /// Same as [`check_oracle_silence`](Self::check_oracle_silence), evaluated at `now`.
+    pub fn check_oracle_silence_at(&mut self, now: DateTime<Utc>) -> Option<Dispute> {
+        let oracle_id = self.pending_oracle()?.to_string();
+        let timeout = self.release_condition.as_ref()?.silence_timeout_secs;
+        let heard_at = self.oracle_heard_at?;
+        if now <= heard_at + chrono::Duration::seconds(timeout as i64) {
+            return None;
+        }
+
+        let dispute = Dispute::create(
+            self.escrow_id.clone(),
+            self.borrower.clone(),
+            self.lender.clone(),
+            format!(
+                "oracle {} silent since {}",
+                oracle_id,
+                heard_at.to_rfc3339()
+            ),
+        )
+        .with_category(DisputeCategory::OracleSilence);
+        self.dispute_id = Some(dispute.id.clone());
+        self.status = EscrowStatus::Disputed;
+        self.updated_at = now;
+        Some(dispute)
+    }
+
+    /// Settles an escrow whose oracle went silent, according to the
+    /// fallback dispute's outcome.
+    ///
+    /// A ruling for the borrower (the initiator) releases the escrowed
+    /// funds; a ruling for the lender refunds them. If the borrower cancels
+    /// the dispute, the escrow goes back to waiting on the oracle with a
+    /// fresh silence timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EscrowError::InvalidState`] if the escrow is not `Disputed`.
+    /// Returns [`EscrowError::ForeignDispute`] if the dispute is not the
+    /// escrow's fallback dispute.
+    /// Returns [`EscrowError::DisputePending`] if the dispute is still open.
+    pub fn apply_dispute_outcome(&mut self, dispute: &Dispute) -> Result<(), EscrowError> {
+        if self.status != EscrowStatus::Disputed {
+            return Err(EscrowError::InvalidState {
+                current: self.status.to_string(),
+                expected: "Disputed".into(),
+            });
+        }
+        if self.dispute_id.as_deref() != Some(dispute.id.as_str())
+            || dispute.escrow_id != self.escrow_id
+        {
+            return Err(EscrowError::ForeignDispute(dispute.id.clone()));
+        }
+
+        let now = Utc::now();
+        match dispute.status {
+            DisputeStatus::ResolvedForInitiator => self.release_all_escrowed(),
+            DisputeStatus::ResolvedForRespondent => self.status = EscrowStatus::Refunded,
+            DisputeStatus::Cancelled => {
+                self.status = EscrowStatus::Funded;
+                self.oracle_heard_at = Some(now);
+            }
+            DisputeStatus::Open | DisputeStatus::UnderReview => {
+                return Err(EscrowError::DisputePending(dispute.id.clone()));
+            }
+        }
+
+        self.dispute_id = None;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Applies an amendment signed by both lender and borrower.
+    ///
+    /// A top-up adds the amount to the principal and the funded amount at
+    /// once, and adds simple interest on it up to the repayment deadline to
+    /// the total owed. A scheduled release is queued for
+    /// [`process_scheduled_releases`](Self::process_scheduled_releases).
+    /// Either way the outstanding amount is re-spread over the installments
+    /// still to come.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EscrowError::InvalidState`] if the escrow is not `Funded`
+    /// or `Active`.
+    /// Returns [`EscrowError::InvalidAmendment`] if the amendment is for
+    /// another escrow, is out of sequence, is missing a valid signature, has
+    /// a zero amount, or schedules a release that is past or not before the
+    /// repayment deadline.
+    /// Returns [`EscrowError::AwaitingOracle`] for a scheduled release while
+    /// an oracle condition is pending.
+    /// Returns [`EscrowError::InsufficientEscrowed`] if the scheduled
+    /// releases would exceed what is escrowed.
+    /// Returns [`EscrowError::AmountOverflow`] if a total would overflow.
+    pub fn apply_amendment(&mut self, amendment: &EscrowAmendment) -> Result<(), EscrowError> {
+        self.apply_amendment_at(amendment, Utc::now())
+    }
+
+    /// Same as [`apply_amendment`](Self::apply_amendment), evaluated at `now`.
+    pub fn apply_amendment_at(
+        &mut self,
+        amendment: &EscrowAmendment,
+        now: DateTime<Utc>,
+    ) -> Result<(), EscrowError> {
+        if self.status != EscrowStatus::Funded && self.status != EscrowStatus::Active {
+            return Err(EscrowError::InvalidState {
+                current: self.status.to_string(),
+                expected: "Funded or Active".into(),
+            });
+        }
+        if amendment.escrow_id != self.escrow_id {
+            return Err(EscrowError::InvalidAmendment(format!(
+                "amendment is for escrow {}",
+                amendment.escrow_id
+            )));
+        }
+        if amendment.sequence != self.amendments_applied {
+            return Err(EscrowError::InvalidAmendment(format!(
+                "sequence {} does not follow {} applied amendments",
+                amendment.sequence, self.amendments_applied
+            )));
+        }
+        let payload = amendment.signing_payload();
+        if !verify_hex(&self.lender, &payload, &amendment.lender_signature) {
+            return Err(EscrowError::InvalidAmendment(
+                "lender signature does not verify".into(),
+            ));
+        }
+        if !verify_hex(&self.borrower, &payload, &amendment.borrower_signature) {
+            return Err(EscrowError::InvalidAmendment(
+                "borrower signature does not verify".into(),
+            ));
+        }
+
+        match &amendment.kind {
+            AmendmentKind::TopUp { amount } => self.top_up(*amount, now)?,
+            AmendmentKind::ScheduledRelease(release) => {
+                self.schedule_release(release.clone(), now)?
+            }
+        }
+
+        self.amendments_applied += 1;
+        self.reschedule_repayments(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Makes every scheduled release that is due, returning the total
+    /// released to the borrower.
+    pub fn process_scheduled_releases(&mut self) -> u64 {
+        self.process_scheduled_releases_at(Utc::now())
+    }
+
+    /// Same as [`process_scheduled_releases`](Self::process_scheduled_releases),
+    /// evaluated at `now`.
+    pub fn process_scheduled_releases_at(&mut self, now: DateTime<Utc>) -> u64 {
+        if self.status != EscrowStatus::Funded && self.status != EscrowStatus::Active {
+            return 0;
+        }
+
+        let (due, pending): (Vec<_>, Vec<_>) = self
+            .scheduled_releases
+            .drain(..)
+            .partition(|release| release.release_at <= now);
+        self.scheduled_releases = pending;
+
+        // Amendments only schedule what is escrowed, so the sum fits.
+        let released: u64 = due.iter().map(|release| release.amount).sum();
+        if released > 0 {
+            self.released_amount += released;
+            self.status = EscrowStatus::Active;
+            self.updated_at = now;
+        }
+        released
+    }
+
+    fn top_up(&mut self, amount: u64, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        if amount == 0 {
+            return Err(EscrowError::InvalidAmendment("top-up of zero".into()));
+        }
+
+        // Simple interest on the new funds for the time left to maturity.
+        let remaining_secs = (self.terms.repayment_deadline - now).num_seconds().max(0) as u128;
+        let interest = amount as u128 * self.terms.interest_rate_bps as u128 * remaining_secs
+            / (10_000 * SECONDS_PER_YEAR);
+        let interest = u64::try_from(interest).map_err(|_| EscrowError::AmountOverflow)?;
+
+        let principal = self
+            .principal
+            .checked_add(amount)
+            .ok_or(EscrowError::AmountOverflow)?;
+        let funded_amount = self
+            .funded_amount
+            .checked_add(amount)
+            .ok_or(EscrowError::AmountOverflow)?;
+        let total_owed = self
+            .terms
+            .total_owed
+            .checked_add(amount)
+            .and_then(|total| total.checked_add(interest))
+            .ok_or(EscrowError::AmountOverflow)?;
+
+        self.principal = principal;
+        self.terms.principal = principal;
+        self.funded_amount = funded_amount;
+        self.terms.total_owed = total_owed;
+        Ok(())
+    }
+
+    fn schedule_release(
+        &mut self,
+        release: ScheduledRelease,
+        now: DateTime<Utc>,
+    ) -> Result<(), EscrowError> {
+        if release.amount == 0 {
+            return Err(EscrowError::InvalidAmendment("release of zero".into()));
+        }
+        if release.release_at < now || release.release_at >= self.terms.repayment_deadline {
+            return Err(EscrowError::InvalidAmendment(
+                "release must fall between now and the repayment deadline".into(),
+            ));
+        }
+        if let Some(oracle_id) = self.pending_oracle() {
+            return Err(EscrowError::AwaitingOracle {
+                oracle_id: oracle_id.to_string(),
+            });
+        }
+
+        let available = self
+            .funded_amount
+            .checked_sub(self.released_amount)
+            .ok_or(EscrowError::AmountOverflow)?;
+        let committed = self
+            .scheduled_releases
+            .iter()
+            .try_fold(release.amount, |sum, r| sum.checked_add(r.amount))
+            .ok_or(EscrowError::AmountOverflow)?;
+        if committed > available {
+            return Err(EscrowError::InsufficientEscrowed {
+                requested: committed,
+                available,
+            });
+        }
+
+        let at = self
+            .scheduled_releases
+            .partition_point(|r| r.release_at <= release.release_at);
+        self.scheduled_releases.insert(at, release);
+        Ok(())
+    }
+
+    /// Re-spreads the outstanding amount over the installments still to
+    /// come after `now`, keeping their due dates. If all have passed, the
+    /// whole outstanding amount falls on the last one.
+    fn reschedule_repayments(&mut self, now: DateTime<Utc>) {
+        let outstanding = self.terms.total_owed.saturating_sub(self.repaid_amount);
+        let mut due_dates: Vec<DateTime<Utc>> = self
+            .repayment_schedule
+            .iter()
+            .map(|installment| installment.due_at)
+            .filter(|&due_at| due_at > now)
+            .collect();
+        if due_dates.is_empty() {
+            due_dates.push(self.terms.repayment_deadline);
+        }
+        self.repayment_schedule = spread(outstanding, &due_dates);
+    }
+
+    /// The oracle a still-unmet release condition is waiting on.
+    fn pending_oracle(&self) -> Option<&str> {
+        match &self.release_condition {
+            Some(condition) if self.status == EscrowStatus::Funded => Some(&condition.oracle_id),
+            _ => None,
+        }
+    }
+
+    /// Releases everything held in escrow to the borrower, bypassing the
+    /// oracle gate that [`release_to_borrower`](Self::release_to_borrower)
+    /// enforces. Scheduled releases are part of it and are dropped.
+    fn release_all_escrowed(&mut self) {
+        self.released_amount = self.funded_amount;
+        self.scheduled_releases.clear();
+        self.status = EscrowStatus::Active;
+    }
+}
+
+/// Splits `total` evenly over `due_dates`, the last one taking the
+/// remainder.
+fn spread(total: u64, due_dates: &[DateTime<Utc>]) -> Vec<Installment> {
+    let count = due_dates.len() as u64;
+    let share = total / count;
+    due_dates
+        .iter()
+        .enumerate()
+        .map(|(i, &due_at)| Installment {
+            due_at,
+            amount: if i as u64 + 1 == count {
+                total - share * (count - 1)
+            } else {
+                share
+            },
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dispute_resolution::{ArbiterVote, Resolution};
 
     fn sample_terms(principal: u64) -> CreditTerms {
         CreditTerms {
@@ -456,4 +1297,294 @@ mod tests {
         let result = escrow.repay(total_owed + 1);
         assert!(result.is_err());
     }
+
+    fn parcel_oracle() -> (OracleRegistry, NovaKeypair) {
+        let keypair = NovaKeypair::generate();
+        let mut registry = OracleRegistry::new();
+        registry
+            .register(
+                "carrier".into(),
+                keypair.public_key().to_hex(),
+                vec!["parcel_status".into()],
+            )
+            .unwrap();
+        (registry, keypair)
+    }
+
+    fn delivery_escrow(registry: &OracleRegistry) -> CreditEscrow {
+        let mut escrow = CreditEscrow::create("l".into(), "b".into(), sample_terms(1_000_000));
+        escrow
+            .attach_release_condition(
+                registry,
+                ReleaseCondition {
+                    oracle_id: "carrier".into(),
+                    feed: "parcel_status".into(),
+                    predicate: ConditionPredicate::Equals(OracleValue::Text("delivered".into())),
+                    silence_timeout_secs: 3600,
+                },
+            )
+            .unwrap();
+        escrow.fund(1_000_000).unwrap();
+        escrow
+    }
+
+    fn scan(keypair: &NovaKeypair, escrow: &CreditEscrow, status: &str) -> OracleAttestation {
+        OracleAttestation::sign(
+            keypair,
+            "carrier".into(),
+            escrow.escrow_id.clone(),
+            "parcel_status".into(),
+            OracleValue::Text(status.into()),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn numeric_predicates_compare_numbers_only() {
+        let delay = ConditionPredicate::AtLeast(120);
+        assert!(delay.is_satisfied_by(&OracleValue::Number(180)));
+        assert!(!delay.is_satisfied_by(&OracleValue::Number(90)));
+        assert!(!delay.is_satisfied_by(&OracleValue::Text("180".into())));
+        assert!(ConditionPredicate::AtMost(0).is_satisfied_by(&OracleValue::Number(-5)));
+    }
+
+    #[test]
+    fn satisfying_attestation_releases_to_borrower() {
+        let (registry, keypair) = parcel_oracle();
+        let mut escrow = delivery_escrow(&registry);
+
+        // Waiting on the oracle: no release by hand.
+        assert!(matches!(
+            escrow.release_to_borrower(1_000_000),
+            Err(EscrowError::AwaitingOracle { .. })
+        ));
+
+        assert!(!escrow
+            .submit_attestation(&registry, &scan(&keypair, &escrow, "in_transit"))
+            .unwrap());
+        assert_eq!(escrow.status, EscrowStatus::Funded);
+
+        assert!(escrow
+            .submit_attestation(&registry, &scan(&keypair, &escrow, "delivered"))
+            .unwrap());
+        assert_eq!(escrow.status, EscrowStatus::Active);
+        assert_eq!(escrow.released_amount, 1_000_000);
+    }
+
+    #[test]
+    fn forged_or_misdirected_attestations_rejected() {
+        let (registry, keypair) = parcel_oracle();
+        let mut escrow = delivery_escrow(&registry);
+
+        let forged = scan(&NovaKeypair::generate(), &escrow, "delivered");
+        assert!(matches!(
+            escrow.submit_attestation(&registry, &forged),
+            Err(EscrowError::InvalidAttestation(_))
+        ));
+
+        let other = delivery_escrow(&registry);
+        let misdirected = scan(&keypair, &other, "delivered");
+        assert!(matches!(
+            escrow.submit_attestation(&registry, &misdirected),
+            Err(EscrowError::InvalidAttestation(_))
+        ));
+
+        let mut tampered = scan(&keypair, &escrow, "in_transit");
+        tampered.value = OracleValue::Text("delivered".into());
+        assert!(escrow.submit_attestation(&registry, &tampered).is_err());
+        assert_eq!(escrow.status, EscrowStatus::Funded);
+    }
+
+    #[test]
+    fn condition_needs_a_registered_feed() {
+        let (registry, _) = parcel_oracle();
+        let mut escrow = CreditEscrow::create("l".into(), "b".into(), sample_terms(1_000));
+        let result = escrow.attach_release_condition(
+            &registry,
+            ReleaseCondition {
+                oracle_id: "carrier".into(),
+                feed: "flight_delay".into(),
+                predicate: ConditionPredicate::AtLeast(120),
+                silence_timeout_secs: 60,
+            },
+        );
+        assert!(matches!(result, Err(EscrowError::UnsupportedFeed { .. })));
+    }
+
+    #[test]
+    fn silent_oracle_falls_back_to_dispute() {
+        let (registry, _) = parcel_oracle();
+        let mut escrow = delivery_escrow(&registry);
+        assert!(escrow.check_oracle_silence().is_none());
+
+        let later = escrow.oracle_heard_at.unwrap() + chrono::Duration::seconds(3601);
+        let mut dispute = escrow.check_oracle_silence_at(later).unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Disputed);
+        assert_eq!(dispute.initiator, "b");
+        assert_eq!(dispute.category, DisputeCategory::OracleSilence);
+        assert_eq!(dispute.escrow_id, escrow.escrow_id);
+
+        assert!(matches!(
+            escrow.apply_dispute_outcome(&dispute),
+            Err(EscrowError::DisputePending(_))
+        ));
+        let vote = ArbiterVote::sign(
+            &NovaKeypair::generate(),
+            dispute.id.clone(),
+            Resolution::ForRespondent,
+            "The parcel was never scanned after the oracle went quiet",
+            false,
+        );
+        dispute.cast_vote(vote).unwrap();
+        escrow.apply_dispute_outcome(&dispute).unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Refunded);
+        assert_eq!(escrow.released_amount, 0);
+    }
+
+    /// A funded 1M escrow between real keys, maturing a year from `now`.
+    fn amendable_escrow(now: DateTime<Utc>) -> (CreditEscrow, NovaKeypair, NovaKeypair) {
+        let (lender, borrower) = (NovaKeypair::generate(), NovaKeypair::generate());
+        let terms = CreditTerms {
+            principal: 1_000_000,
+            interest_rate_bps: 500,
+            total_owed: 1_050_000,
+            repayment_deadline: now + chrono::Duration::days(365),
+            grace_period_secs: 0,
+        };
+        let mut escrow =
+            CreditEscrow::create(lender.public_key_hex(), borrower.public_key_hex(), terms)
+                .with_installments(4);
+        escrow.fund(1_000_000).unwrap();
+        (escrow, lender, borrower)
+    }
+
+    fn signed(
+        escrow: &CreditEscrow,
+        kind: AmendmentKind,
+        lender: &NovaKeypair,
+        borrower: &NovaKeypair,
+    ) -> EscrowAmendment {
+        let mut amendment = EscrowAmendment::new(escrow, kind);
+        amendment.sign_as_lender(lender);
+        amendment.sign_as_borrower(borrower);
+        amendment
+    }
+
+    #[test]
+    fn installments_split_total_owed() {
+        let (escrow, _, _) = amendable_escrow(Utc::now());
+        assert_eq!(escrow.repayment_schedule.len(), 4);
+        assert!(escrow
+            .repayment_schedule
+            .iter()
+            .all(|i| i.amount == 262_500));
+        assert_eq!(
+            escrow.repayment_schedule.last().unwrap().due_at,
+            escrow.terms.repayment_deadline
+        );
+    }
+
+    #[test]
+    fn top_up_adds_interest_and_reschedules() {
+        let now = Utc::now();
+        let (mut escrow, lender, borrower) = amendable_escrow(now);
+        let top_up = signed(
+            &escrow,
+            AmendmentKind::TopUp { amount: 400_000 },
+            &lender,
+            &borrower,
+        );
+        escrow.apply_amendment_at(&top_up, now).unwrap();
+
+        // A full year left at 5%: 20_000 interest on the new funds.
+        assert_eq!(escrow.principal, 1_400_000);
+        assert_eq!(escrow.funded_amount, 1_400_000);
+        assert_eq!(escrow.terms.total_owed, 1_470_000);
+        assert_eq!(escrow.amendments_applied, 1);
+        let scheduled: u64 = escrow.repayment_schedule.iter().map(|i| i.amount).sum();
+        assert_eq!(scheduled, 1_470_000);
+
+        // Each signed amendment applies once.
+        assert!(matches!(
+            escrow.apply_amendment_at(&top_up, now),
+            Err(EscrowError::InvalidAmendment(_))
+        ));
+    }
+
+    #[test]
+    fn amendments_need_both_signatures() {
+        let now = Utc::now();
+        let (mut escrow, lender, _) = amendable_escrow(now);
+        let mut amendment = EscrowAmendment::new(&escrow, AmendmentKind::TopUp { amount: 1 });
+        amendment.sign_as_lender(&lender);
+        amendment.sign_as_borrower(&lender);
+
+        assert!(matches!(
+            escrow.apply_amendment_at(&amendment, now),
+            Err(EscrowError::InvalidAmendment(_))
+        ));
+        assert_eq!(escrow.principal, 1_000_000);
+    }
+
+    #[test]
+    fn scheduled_release_pays_out_on_its_date() {
+        let now = Utc::now();
+        let (mut escrow, lender, borrower) = amendable_escrow(now);
+        let release_at = now + chrono::Duration::days(30);
+        let release = signed(
+            &escrow,
+            AmendmentKind::ScheduledRelease(ScheduledRelease {
+                amount: 600_000,
+                release_at,
+            }),
+            &lender,
+            &borrower,
+        );
+        escrow.apply_amendment_at(&release, now).unwrap();
+
+        // The promised funds are no longer free to release by hand.
+        assert!(matches!(
+            escrow.release_to_borrower(500_000),
+            Err(EscrowError::InsufficientEscrowed {
+                available: 400_000,
+                ..
+            })
+        ));
+
+        assert_eq!(escrow.process_scheduled_releases_at(now), 0);
+        assert_eq!(escrow.process_scheduled_releases_at(release_at), 600_000);
+        assert_eq!(escrow.released_amount, 600_000);
+        assert_eq!(escrow.status, EscrowStatus::Active);
+        assert!(escrow.scheduled_releases.is_empty());
+    }
+
+    #[test]
+    fn releases_must_precede_maturity_and_fit_the_escrow() {
+        let now = Utc::now();
+        let (mut escrow, lender, borrower) = amendable_escrow(now);
+        let release = |escrow: &CreditEscrow, amount, days| {
+            signed(
+                escrow,
+                AmendmentKind::ScheduledRelease(ScheduledRelease {
+                    amount,
+                    release_at: now + chrono::Duration::days(days),
+                }),
+                &lender,
+                &borrower,
+            )
+        };
+
+        let after_maturity = release(&escrow, 100, 400);
+        assert!(matches!(
+            escrow.apply_amendment_at(&after_maturity, now),
+            Err(EscrowError::InvalidAmendment(_))
+        ));
+
+        let too_much = release(&escrow, 1_000_001, 10);
+        assert!(matches!(
+            escrow.apply_amendment_at(&too_much, now),
+            Err(EscrowError::InsufficientEscrowed { .. })
+        ));
+        assert_eq!(escrow.amendments_applied, 0);
+    }
 }
@@ -12,20 +12,59 @@
 //! on-chain serves as a tamper-proof anchor. This keeps block sizes sane
 //! while still providing non-repudiation.
 //!
+//! Each piece of evidence is typed ([`EvidenceKind`]): an off-chain
+//! document, a transaction receipt hash, a reference to an on-chain
+//! transaction, or an oracle attestation. Submissions are numbered in
+//! order, forming the dispute's timeline, which arbiters read through
+//! [`Dispute::timeline`]. A dispute holds at most
+//! [`MAX_EVIDENCE_PER_DISPUTE`] pieces, descriptions and references are
+//! bounded in size, and the same reference cannot be submitted twice.
+//!
 //! ## Resolution Flow
 //!
 //! 1. Either party opens a dispute on an active escrow.
 //! 2. Both parties submit evidence (hashes + descriptions).
-//! 3. An arbiter reviews the evidence off-chain.
-//! 4. The arbiter calls `resolve()` with a signed resolution.
-//! 5. The dispute status transitions to `ResolvedForInitiator` or
-//!    `ResolvedForRespondent`, and the escrow is updated accordingly.
+//! 3. The arbiters review the evidence off-chain.
+//! 4. Each arbiter casts an [`ArbiterVote`] through
+//!    [`Dispute::cast_vote`], signing the ruling together with the hash
+//!    of a written rationale.
+//! 5. Once one side holds a majority of the panel, the dispute status
+//!    transitions to `ResolvedForInitiator` or `ResolvedForRespondent`,
+//!    and the escrow is updated accordingly.
+//!
+//! ## Rationale and Precedent
+//!
+//! A ruling never comes without reasons. Every vote carries the BLAKE3
+//! hash of the arbiter's rationale, covered by the arbiter's signature,
+//! and may publish the text itself. The resolution is recorded as a
+//! [`ResolutionRecord`] holding the rationales of the majority, and
+//! resolved disputes can be filed in a [`PrecedentRegistry`], where
+//! arbiters look up past rulings by [`DisputeCategory`].
 
 use chrono::{DateTime, Utc};
+use nova_protocol::crypto::{blake3_hash, NovaKeypair};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::credit_escrow::verify_hex;
+
+// ---------------------------------------------------------------------------
+// Limits
+// ---------------------------------------------------------------------------
+
+/// Most pieces of evidence a single dispute accepts.
+pub const MAX_EVIDENCE_PER_DISPUTE: usize = 64;
+
+/// Longest evidence description, in bytes.
+pub const MAX_EVIDENCE_DESCRIPTION_LEN: usize = 1024;
+
+/// Longest evidence reference (hash or transaction ID), in bytes.
+pub const MAX_EVIDENCE_REFERENCE_LEN: usize = 128;
+
+/// Longest public rationale an arbiter may attach to a vote, in bytes.
+pub const MAX_RATIONALE_LEN: usize = 4096;
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -60,6 +99,40 @@ pub enum DisputeError {
     /// The dispute has already been resolved.
     #[error("dispute already resolved")]
     AlreadyResolved,
+
+    /// The evidence is empty or exceeds a size limit.
+    #[error("invalid evidence: {0}")]
+    InvalidEvidence(String),
+
+    /// The dispute already holds [`MAX_EVIDENCE_PER_DISPUTE`] pieces.
+    #[error("dispute already holds {0} pieces of evidence")]
+    EvidenceLimit(usize),
+
+    /// The same reference is already on the timeline.
+    #[error("evidence {reference} was already submitted as #{sequence}")]
+    DuplicateEvidence {
+        /// The resubmitted reference.
+        reference: String,
+        /// Timeline position of the earlier submission.
+        sequence: u64,
+    },
+
+    /// The arbiter has already voted on this dispute.
+    #[error("arbiter {0} has already voted")]
+    DuplicateVote(String),
+
+    /// The rationale hash is malformed, or the published text does not
+    /// match it.
+    #[error("invalid rationale: {0}")]
+    InvalidRationale(String),
+
+    /// Only resolved disputes can become precedents.
+    #[error("dispute {0} is not resolved")]
+    NotResolved(String),
+
+    /// The dispute is already in the precedent registry.
+    #[error("dispute {0} is already a precedent")]
+    DuplicatePrecedent(String),
 }
 
 // ---------------------------------------------------------------------------
@@ -102,12 +175,71 @@ pub enum Resolution {
     ForRespondent,
 }
 
+/// What a dispute is about, the key precedents are filed under.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum DisputeCategory {
+    /// Not classified any further.
+    #[default]
+    General,
+    /// The terms or repayment of a credit escrow.
+    CreditTerms,
+    /// A marketplace purchase the buyer objected to.
+    Purchase,
+    /// An escrow whose release oracle fell silent.
+    OracleSilence,
+}
+
+impl std::fmt::Display for DisputeCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisputeCategory::General => write!(f, "General"),
+            DisputeCategory::CreditTerms => write!(f, "CreditTerms"),
+            DisputeCategory::Purchase => write!(f, "Purchase"),
+            DisputeCategory::OracleSilence => write!(f, "OracleSilence"),
+        }
+    }
+}
+
+/// What a piece of evidence is, and so what its `data_hash` refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EvidenceKind {
+    /// An off-chain document; `data_hash` is the BLAKE3 hash of its content.
+    #[default]
+    Document,
+    /// A transaction receipt; `data_hash` is the receipt hash.
+    ReceiptHash,
+    /// A transaction on the NOVA chain; `data_hash` is its ID.
+    ChainTransaction,
+    /// A signed oracle attestation; `data_hash` is the hash of the
+    /// attestation record.
+    OracleAttestation,
+}
+
+impl std::fmt::Display for EvidenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvidenceKind::Document => write!(f, "Document"),
+            EvidenceKind::ReceiptHash => write!(f, "ReceiptHash"),
+            EvidenceKind::ChainTransaction => write!(f, "ChainTransaction"),
+            EvidenceKind::OracleAttestation => write!(f, "OracleAttestation"),
+        }
+    }
+}
+
 /// A piece of evidence submitted by a dispute participant.
 ///
 /// The actual evidence payload (documents, screenshots, logs) is stored
 /// off-chain. Only the content hash is recorded on-chain for integrity.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Evidence {
+    /// Position on the dispute's timeline, counting from 0.
+    #[serde(default)]
+    pub sequence: u64,
+    /// What the evidence is.
+    #[serde(default)]
+    pub kind: EvidenceKind,
     /// Hex-encoded public key of the party that submitted this evidence.
     pub submitted_by: String,
     /// Human-readable description of what this evidence demonstrates.
@@ -118,6 +250,156 @@ pub struct Evidence {
     pub timestamp: DateTime<Utc>,
 }
 
+impl Evidence {
+    /// The reference as compared for duplicates: lowercase, without a
+    /// `0x` prefix.
+    fn normalized_reference(&self) -> String {
+        normalize_reference(&self.data_hash)
+    }
+}
+
+fn normalize_reference(reference: &str) -> String {
+    let reference = reference.trim();
+    let reference = reference
+        .strip_prefix("0x")
+        .or_else(|| reference.strip_prefix("0X"))
+        .unwrap_or(reference);
+    reference.to_ascii_lowercase()
+}
+
+/// Narrows [`Dispute::timeline`] to matching evidence. The default matches
+/// everything.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineQuery {
+    /// Only evidence from this party.
+    pub submitted_by: Option<String>,
+    /// Only evidence of this kind.
+    pub kind: Option<EvidenceKind>,
+    /// Only evidence submitted at or after this time.
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl TimelineQuery {
+    /// Restricts the query to evidence from `party`.
+    pub fn with_submitter(mut self, party: impl Into<String>) -> Self {
+        self.submitted_by = Some(party.into());
+        self
+    }
+
+    /// Restricts the query to evidence of `kind`.
+    pub fn with_kind(mut self, kind: EvidenceKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Restricts the query to evidence submitted at or after `since`.
+    pub fn with_since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    fn matches(&self, evidence: &Evidence) -> bool {
+        self.submitted_by
+            .iter()
+            .all(|party| *party == evidence.submitted_by)
+            && self.kind.iter().all(|kind| *kind == evidence.kind)
+            && self.since.iter().all(|since| evidence.timestamp >= *since)
+    }
+}
+
+/// Hex-encoded BLAKE3 hash of a rationale text, as votes commit to it.
+pub fn rationale_hash(text: &str) -> String {
+    blake3_hash(text.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// An arbiter's signed ruling on a dispute.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbiterVote {
+    /// The dispute voted on.
+    pub dispute_id: String,
+    /// Hex-encoded public key of the arbiter.
+    pub arbiter: String,
+    /// The side the arbiter rules for.
+    pub resolution: Resolution,
+    /// Hex-encoded BLAKE3 hash of the arbiter's rationale.
+    pub rationale_hash: String,
+    /// The rationale itself, when the arbiter makes it public. Must hash
+    /// to `rationale_hash`.
+    pub rationale: Option<String>,
+    /// Hex-encoded Ed25519 signature over
+    /// [`signing_payload`](Self::signing_payload).
+    pub signature: String,
+}
+
+impl ArbiterVote {
+    /// Creates a vote on `dispute_id` signed with the arbiter's `keypair`,
+    /// committing to `rationale` and publishing it when `public`.
+    pub fn sign(
+        keypair: &NovaKeypair,
+        dispute_id: String,
+        resolution: Resolution,
+        rationale: &str,
+        public: bool,
+    ) -> Self {
+        let mut vote = Self {
+            dispute_id,
+            arbiter: keypair.public_key().to_hex(),
+            resolution,
+            rationale_hash: rationale_hash(rationale),
+            rationale: public.then(|| rationale.to_string()),
+            signature: String::new(),
+        };
+        vote.signature = keypair.sign(&vote.signing_payload()).to_hex();
+        vote
+    }
+
+    /// The bytes the arbiter signs: a domain tag, the dispute ID and
+    /// arbiter key (length-prefixed), the ruling and the rationale hash.
+    /// The public text is covered through its hash.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut buf = b"nova-arbiter-vote".to_vec();
+        for field in [&self.dispute_id, &self.arbiter] {
+            buf.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            buf.extend_from_slice(field.as_bytes());
+        }
+        buf.push(match self.resolution {
+            Resolution::ForInitiator => 0x01,
+            Resolution::ForRespondent => 0x02,
+        });
+        buf.extend_from_slice(self.rationale_hash.as_bytes());
+        buf
+    }
+}
+
+/// A rationale backing a ruling, as kept in the [`ResolutionRecord`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rationale {
+    /// Hex-encoded public key of the arbiter who gave it.
+    pub arbiter: String,
+    /// Hex-encoded BLAKE3 hash of the rationale.
+    pub hash: String,
+    /// The text, if the arbiter published it.
+    pub text: Option<String>,
+}
+
+/// The final ruling on a dispute and the reasons behind it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionRecord {
+    /// The side the panel ruled for.
+    pub resolution: Resolution,
+    /// What the dispute was about.
+    pub category: DisputeCategory,
+    /// Rationales of the arbiters in the majority, in voting order.
+    pub rationales: Vec<Rationale>,
+    /// Number of arbiters who voted the other way before the ruling.
+    pub dissents: usize,
+    /// When the majority was reached.
+    pub resolved_at: DateTime<Utc>,
+}
+
 /// A dispute associated with a credit escrow.
 ///
 /// Tracks the full arbitration lifecycle from opening through evidence
@@ -134,7 +416,20 @@ pub struct Dispute {
     pub respondent: String,
     /// The initiator's stated reason for opening the dispute.
     pub reason: String,
-    /// Evidence submitted by both parties.
+    /// What the dispute is about.
+    #[serde(default)]
+    pub category: DisputeCategory,
+    /// Hex-encoded public keys of the arbiter panel. Empty means a single
+    /// arbiter designated by the execution engine.
+    #[serde(default)]
+    pub arbiters: Vec<String>,
+    /// Votes cast so far, in order.
+    #[serde(default)]
+    pub votes: Vec<ArbiterVote>,
+    /// The ruling and its rationales, once resolved.
+    #[serde(default)]
+    pub record: Option<ResolutionRecord>,
+    /// Evidence submitted by both parties, in timeline order.
     pub evidence: Vec<Evidence>,
     /// Current dispute status.
     pub status: DisputeStatus,
@@ -168,6 +463,10 @@ impl Dispute {
             initiator,
             respondent,
             reason,
+            category: DisputeCategory::default(),
+            arbiters: Vec::new(),
+            votes: Vec::new(),
+            record: None,
             evidence: Vec::new(),
             status: DisputeStatus::Open,
             created_at: Utc::now(),
@@ -175,10 +474,23 @@ impl Dispute {
         }
     }
 
-    /// Submits a piece of evidence to the dispute.
+    /// Files the dispute under `category`.
+    pub fn with_category(mut self, category: DisputeCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Hands the dispute to a panel of arbiters (hex-encoded public keys),
+    /// a majority of whom decide it.
+    pub fn with_arbiters(mut self, arbiters: Vec<String>) -> Self {
+        self.arbiters = arbiters;
+        self
+    }
+
+    /// Submits an off-chain document as evidence.
     ///
-    /// Only the initiator or respondent may submit evidence. Evidence can
-    /// be submitted while the dispute is `Open` or `UnderReview`.
+    /// Shorthand for [`submit_typed_evidence`](Self::submit_typed_evidence)
+    /// with [`EvidenceKind::Document`].
     ///
     /// # Arguments
     ///
@@ -188,14 +500,46 @@ impl Dispute {
     ///
     /// # Errors
     ///
-    /// Returns [`DisputeError::Unauthorized`] if `party` is not a participant.
-    /// Returns [`DisputeError::InvalidState`] if the dispute is already resolved.
+    /// As for [`submit_typed_evidence`](Self::submit_typed_evidence).
     pub fn submit_evidence(
         &mut self,
         party: &str,
         description: String,
         data_hash: String,
     ) -> Result<(), DisputeError> {
+        self.submit_typed_evidence(party, EvidenceKind::Document, description, data_hash)
+            .map(|_| ())
+    }
+
+    /// Submits a piece of evidence to the dispute, returning its position
+    /// on the timeline.
+    ///
+    /// Only the initiator or respondent may submit evidence. Evidence can
+    /// be submitted while the dispute is `Open` or `UnderReview`.
+    ///
+    /// # Arguments
+    ///
+    /// * `party` - Hex-encoded public key of the submitting party.
+    /// * `kind` - What the evidence is.
+    /// * `description` - What this evidence demonstrates.
+    /// * `reference` - The hash or transaction ID `kind` calls for.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DisputeError::Unauthorized`] if `party` is not a participant.
+    /// Returns [`DisputeError::InvalidState`] if the dispute is already resolved.
+    /// Returns [`DisputeError::InvalidEvidence`] if the reference is empty
+    /// or either field exceeds its size limit,
+    /// [`DisputeError::EvidenceLimit`] if the dispute is full, and
+    /// [`DisputeError::DuplicateEvidence`] if the reference was already
+    /// submitted.
+    pub fn submit_typed_evidence(
+        &mut self,
+        party: &str,
+        kind: EvidenceKind,
+        description: String,
+        reference: String,
+    ) -> Result<u64, DisputeError> {
         // Verify the caller is a dispute participant.
         if party != self.initiator && party != self.respondent {
             return Err(DisputeError::Unauthorized {
@@ -214,10 +558,47 @@ impl Dispute {
             }
         }
 
+        if reference.trim().is_empty() {
+            return Err(DisputeError::InvalidEvidence(
+                "reference must not be empty".into(),
+            ));
+        }
+        if reference.len() > MAX_EVIDENCE_REFERENCE_LEN {
+            return Err(DisputeError::InvalidEvidence(format!(
+                "reference is {} bytes, the limit is {}",
+                reference.len(),
+                MAX_EVIDENCE_REFERENCE_LEN
+            )));
+        }
+        if description.len() > MAX_EVIDENCE_DESCRIPTION_LEN {
+            return Err(DisputeError::InvalidEvidence(format!(
+                "description is {} bytes, the limit is {}",
+                description.len(),
+                MAX_EVIDENCE_DESCRIPTION_LEN
+            )));
+        }
+        if self.evidence.len() >= MAX_EVIDENCE_PER_DISPUTE {
+            return Err(DisputeError::EvidenceLimit(MAX_EVIDENCE_PER_DISPUTE));
+        }
+        let normalized = normalize_reference(&reference);
+        if let Some(earlier) = self
+            .evidence
+            .iter()
+            .find(|e| e.kind == kind && e.normalized_reference() == normalized)
+        {
+            return Err(DisputeError::DuplicateEvidence {
+                reference,
+                sequence: earlier.sequence,
+            });
+        }
+
+        let sequence = self.evidence.len() as u64;
         self.evidence.push(Evidence {
+            sequence,
+            kind,
             submitted_by: party.to_string(),
             description,
-            data_hash,
+            data_hash: reference,
             timestamp: Utc::now(),
         });
 
@@ -226,33 +607,34 @@ impl Dispute {
             self.status = DisputeStatus::UnderReview;
         }
 
-        Ok(())
+        Ok(sequence)
     }
 
-    /// Resolves the dispute with an arbiter's signed decision.
-    ///
-    /// The `arbiter_signature` is verified against the resolution payload.
-    /// In production, this would use the protocol's signature verification
-    /// via [`nova_protocol::crypto::signatures::verify`]. For now, we accept
-    /// any non-empty signature as valid (the crypto verification layer is
-    /// plugged in at the execution engine level).
-    ///
-    /// # Arguments
+    /// The evidence matching `query`, in the order it was submitted.
+    pub fn timeline(&self, query: &TimelineQuery) -> Vec<&Evidence> {
+        self.evidence.iter().filter(|e| query.matches(e)).collect()
+    }
+
+    /// Records an arbiter's vote, resolving the dispute once one side
+    /// holds a majority of the panel. Returns the ruling if this vote
+    /// decided it.
     ///
-    /// * `resolution` - Whether the ruling favors the initiator or respondent.
-    /// * `arbiter_signature` - Hex-encoded Ed25519 signature from the arbiter
-    ///   over the dispute ID + resolution.
+    /// Without a panel, the execution engine has already checked that the
+    /// voter is the designated arbiter, and the first vote decides.
     ///
     /// # Errors
     ///
-    /// Returns [`DisputeError::InvalidState`] if the dispute is not
-    /// `Open` or `UnderReview`.
-    /// Returns [`DisputeError::InvalidArbiterSignature`] if the signature is empty.
-    pub fn resolve(
-        &mut self,
-        resolution: Resolution,
-        arbiter_signature: &str,
-    ) -> Result<(), DisputeError> {
+    /// Returns [`DisputeError::AlreadyResolved`] or
+    /// [`DisputeError::InvalidState`] if the dispute is no longer open.
+    /// Returns [`DisputeError::InvalidResolution`] if the vote is for
+    /// another dispute, [`DisputeError::Unauthorized`] if the voter is not
+    /// on the panel, and [`DisputeError::DuplicateVote`] if they already
+    /// voted.
+    /// Returns [`DisputeError::InvalidRationale`] if the rationale hash is
+    /// malformed or the published text is too long or does not match it.
+    /// Returns [`DisputeError::InvalidArbiterSignature`] if the signature
+    /// does not verify against the arbiter's key.
+    pub fn cast_vote(&mut self, vote: ArbiterVote) -> Result<Option<Resolution>, DisputeError> {
         match self.status {
             DisputeStatus::Open | DisputeStatus::UnderReview => {}
             DisputeStatus::ResolvedForInitiator | DisputeStatus::ResolvedForRespondent => {
@@ -266,21 +648,82 @@ impl Dispute {
             }
         }
 
-        // Verify the arbiter signature is present. Full Ed25519 verification
-        // happens at the execution engine layer, which has access to the
-        // arbiter's public key from the validator set.
-        if arbiter_signature.is_empty() {
+        if vote.dispute_id != self.id {
+            return Err(DisputeError::InvalidResolution(format!(
+                "vote is for dispute {}",
+                vote.dispute_id
+            )));
+        }
+        if !self.arbiters.is_empty() && !self.arbiters.contains(&vote.arbiter) {
+            return Err(DisputeError::Unauthorized {
+                party: vote.arbiter,
+            });
+        }
+        if self.votes.iter().any(|v| v.arbiter == vote.arbiter) {
+            return Err(DisputeError::DuplicateVote(vote.arbiter));
+        }
+
+        if vote.rationale_hash.len() != 64
+            || !vote
+                .rationale_hash
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            return Err(DisputeError::InvalidRationale(
+                "hash must be 64 lowercase hex characters".into(),
+            ));
+        }
+        if let Some(text) = &vote.rationale {
+            if text.len() > MAX_RATIONALE_LEN {
+                return Err(DisputeError::InvalidRationale(format!(
+                    "text is {} bytes, the limit is {}",
+                    text.len(),
+                    MAX_RATIONALE_LEN
+                )));
+            }
+            if rationale_hash(text) != vote.rationale_hash {
+                return Err(DisputeError::InvalidRationale(
+                    "text does not match its hash".into(),
+                ));
+            }
+        }
+
+        if !verify_hex(&vote.arbiter, &vote.signing_payload(), &vote.signature) {
             return Err(DisputeError::InvalidArbiterSignature);
         }
 
+        let resolution = vote.resolution;
+        self.votes.push(vote);
+
+        let panel = self.arbiters.len().max(1);
+        let (majority, minority): (Vec<&ArbiterVote>, Vec<&ArbiterVote>) =
+            self.votes.iter().partition(|v| v.resolution == resolution);
+        if majority.len() * 2 <= panel {
+            return Ok(None);
+        }
+
         let now = Utc::now();
+        self.record = Some(ResolutionRecord {
+            resolution,
+            category: self.category,
+            rationales: majority
+                .iter()
+                .map(|v| Rationale {
+                    arbiter: v.arbiter.clone(),
+                    hash: v.rationale_hash.clone(),
+                    text: v.rationale.clone(),
+                })
+                .collect(),
+            dissents: minority.len(),
+            resolved_at: now,
+        });
         self.status = match resolution {
             Resolution::ForInitiator => DisputeStatus::ResolvedForInitiator,
             Resolution::ForRespondent => DisputeStatus::ResolvedForRespondent,
         };
         self.resolved_at = Some(now);
 
-        Ok(())
+        Ok(Some(resolution))
     }
 
     /// Cancels the dispute. Only the initiator can cancel, and only before
@@ -317,6 +760,141 @@ impl Dispute {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Precedents
+// ---------------------------------------------------------------------------
+
+/// A resolved dispute kept for reference in later rulings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Precedent {
+    /// The resolved dispute.
+    pub dispute_id: String,
+    /// The escrow it concerned.
+    pub escrow_id: String,
+    /// The initiator's stated reason.
+    pub reason: String,
+    /// The ruling and its rationales.
+    pub record: ResolutionRecord,
+}
+
+/// Narrows [`PrecedentRegistry::search`] to matching precedents. The
+/// default matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct PrecedentQuery {
+    /// Only precedents in this category.
+    pub category: Option<DisputeCategory>,
+    /// Only precedents ruled this way.
+    pub resolution: Option<Resolution>,
+    /// Only precedents whose reason or a public rationale contains this
+    /// text, ignoring case.
+    pub text: Option<String>,
+}
+
+impl PrecedentQuery {
+    /// Restricts the query to precedents in `category`.
+    pub fn with_category(mut self, category: DisputeCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Restricts the query to precedents ruled `resolution`.
+    pub fn with_resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Restricts the query to precedents mentioning `text`.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    fn matches(&self, precedent: &Precedent) -> bool {
+        let mentions = |text: &str| {
+            self.text
+                .iter()
+                .all(|needle| text.to_lowercase().contains(&needle.to_lowercase()))
+        };
+        self.category
+            .iter()
+            .all(|category| *category == precedent.record.category)
+            && self
+                .resolution
+                .iter()
+                .all(|resolution| *resolution == precedent.record.resolution)
+            && (mentions(&precedent.reason)
+                || precedent
+                    .record
+                    .rationales
+                    .iter()
+                    .filter_map(|r| r.text.as_deref())
+                    .any(mentions))
+    }
+}
+
+/// Past resolutions, searchable by category, ruling and text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrecedentRegistry {
+    precedents: Vec<Precedent>,
+}
+
+impl PrecedentRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Files a resolved dispute as a precedent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DisputeError::NotResolved`] if the dispute has no
+    /// resolution record, and [`DisputeError::DuplicatePrecedent`] if it
+    /// was already filed.
+    pub fn record(&mut self, dispute: &Dispute) -> Result<(), DisputeError> {
+        let record = dispute
+            .record
+            .clone()
+            .ok_or_else(|| DisputeError::NotResolved(dispute.id.clone()))?;
+        if self.precedents.iter().any(|p| p.dispute_id == dispute.id) {
+            return Err(DisputeError::DuplicatePrecedent(dispute.id.clone()));
+        }
+        self.precedents.push(Precedent {
+            dispute_id: dispute.id.clone(),
+            escrow_id: dispute.escrow_id.clone(),
+            reason: dispute.reason.clone(),
+            record,
+        });
+        Ok(())
+    }
+
+    /// The precedents matching `query`, most recent ruling first.
+    pub fn search(&self, query: &PrecedentQuery) -> Vec<&Precedent> {
+        let mut found: Vec<&Precedent> = self
+            .precedents
+            .iter()
+            .filter(|p| query.matches(p))
+            .collect();
+        found.sort_by(|a, b| b.record.resolved_at.cmp(&a.record.resolved_at));
+        found
+    }
+
+    /// Looks up the precedent set by `dispute_id`.
+    pub fn get(&self, dispute_id: &str) -> Option<&Precedent> {
+        self.precedents.iter().find(|p| p.dispute_id == dispute_id)
+    }
+
+    /// Number of precedents filed.
+    pub fn len(&self) -> usize {
+        self.precedents.len()
+    }
+
+    /// Returns `true` if no precedent has been filed.
+    pub fn is_empty(&self) -> bool {
+        self.precedents.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +939,103 @@ mod tests {
         assert_eq!(d.evidence.len(), 2);
     }
 
+    #[test]
+    fn typed_evidence_builds_an_ordered_timeline() {
+        let mut d = create_test_dispute();
+        let first = d
+            .submit_typed_evidence(
+                "initiator_pk",
+                EvidenceKind::ChainTransaction,
+                "The payment".into(),
+                "ab".repeat(32),
+            )
+            .unwrap();
+        let second = d
+            .submit_typed_evidence(
+                "respondent_pk",
+                EvidenceKind::ReceiptHash,
+                "Its receipt".into(),
+                "cd".repeat(32),
+            )
+            .unwrap();
+        d.submit_evidence("respondent_pk", "Invoice".into(), "hash1".into())
+            .unwrap();
+        assert_eq!((first, second), (0, 1));
+
+        let all = d.timeline(&TimelineQuery::default());
+        let order: Vec<u64> = all.iter().map(|e| e.sequence).collect();
+        assert_eq!(order, vec![0, 1, 2]);
+        assert_eq!(all[2].kind, EvidenceKind::Document);
+
+        let respondent = d.timeline(&TimelineQuery::default().with_submitter("respondent_pk"));
+        assert_eq!(respondent.len(), 2);
+        let receipts = d.timeline(
+            &TimelineQuery::default()
+                .with_submitter("respondent_pk")
+                .with_kind(EvidenceKind::ReceiptHash),
+        );
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].description, "Its receipt");
+        let later = d.timeline(
+            &TimelineQuery::default().with_since(Utc::now() + chrono::Duration::seconds(1)),
+        );
+        assert!(later.is_empty());
+    }
+
+    #[test]
+    fn duplicate_references_rejected() {
+        let mut d = create_test_dispute();
+        d.submit_typed_evidence(
+            "initiator_pk",
+            EvidenceKind::ChainTransaction,
+            "The payment".into(),
+            "0xABCD".into(),
+        )
+        .unwrap();
+        let result = d.submit_typed_evidence(
+            "respondent_pk",
+            EvidenceKind::ChainTransaction,
+            "Same payment".into(),
+            "abcd".into(),
+        );
+        assert!(matches!(
+            result,
+            Err(DisputeError::DuplicateEvidence { sequence: 0, .. })
+        ));
+
+        // The same value under another kind is different evidence.
+        d.submit_evidence("respondent_pk", "A document".into(), "abcd".into())
+            .unwrap();
+        assert_eq!(d.evidence.len(), 2);
+    }
+
+    #[test]
+    fn evidence_size_limits() {
+        let mut d = create_test_dispute();
+        let result = d.submit_evidence("initiator_pk", "doc".into(), " ".into());
+        assert!(matches!(result, Err(DisputeError::InvalidEvidence(_))));
+        let result = d.submit_evidence(
+            "initiator_pk",
+            "doc".into(),
+            "a".repeat(MAX_EVIDENCE_REFERENCE_LEN + 1),
+        );
+        assert!(matches!(result, Err(DisputeError::InvalidEvidence(_))));
+        let result = d.submit_evidence(
+            "initiator_pk",
+            "d".repeat(MAX_EVIDENCE_DESCRIPTION_LEN + 1),
+            "hash".into(),
+        );
+        assert!(matches!(result, Err(DisputeError::InvalidEvidence(_))));
+        assert!(d.evidence.is_empty());
+
+        for i in 0..MAX_EVIDENCE_PER_DISPUTE {
+            d.submit_evidence("initiator_pk", "doc".into(), format!("hash{i}"))
+                .unwrap();
+        }
+        let result = d.submit_evidence("initiator_pk", "doc".into(), "one_more".into());
+        assert!(matches!(result, Err(DisputeError::EvidenceLimit(_))));
+    }
+
     #[test]
     fn unauthorized_party_cannot_submit() {
         let mut d = create_test_dispute();
@@ -368,13 +1043,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn vote(d: &Dispute, keypair: &NovaKeypair, resolution: Resolution) -> ArbiterVote {
+        ArbiterVote::sign(
+            keypair,
+            d.id.clone(),
+            resolution,
+            "The evidence supports this side",
+            true,
+        )
+    }
+
     #[test]
     fn resolve_for_initiator() {
         let mut d = create_test_dispute();
         d.submit_evidence("initiator_pk", "proof".into(), "hash".into())
             .unwrap();
-        d.resolve(Resolution::ForInitiator, "arbiter_sig_hex")
+        let ruling = d
+            .cast_vote(vote(&d, &NovaKeypair::generate(), Resolution::ForInitiator))
             .unwrap();
+        assert_eq!(ruling, Some(Resolution::ForInitiator));
         assert_eq!(d.status, DisputeStatus::ResolvedForInitiator);
         assert!(d.resolved_at.is_some());
     }
@@ -382,24 +1069,173 @@ mod tests {
     #[test]
     fn resolve_for_respondent() {
         let mut d = create_test_dispute();
-        d.resolve(Resolution::ForRespondent, "arbiter_sig_hex")
-            .unwrap();
+        d.cast_vote(vote(
+            &d,
+            &NovaKeypair::generate(),
+            Resolution::ForRespondent,
+        ))
+        .unwrap();
         assert_eq!(d.status, DisputeStatus::ResolvedForRespondent);
     }
 
     #[test]
     fn empty_signature_rejected() {
         let mut d = create_test_dispute();
-        let result = d.resolve(Resolution::ForInitiator, "");
-        assert!(result.is_err());
+        let mut v = vote(&d, &NovaKeypair::generate(), Resolution::ForInitiator);
+        v.signature.clear();
+        let result = d.cast_vote(v);
+        assert!(matches!(result, Err(DisputeError::InvalidArbiterSignature)));
     }
 
     #[test]
     fn double_resolve_rejected() {
         let mut d = create_test_dispute();
-        d.resolve(Resolution::ForInitiator, "sig").unwrap();
-        let result = d.resolve(Resolution::ForRespondent, "sig");
-        assert!(result.is_err());
+        d.cast_vote(vote(&d, &NovaKeypair::generate(), Resolution::ForInitiator))
+            .unwrap();
+        let result = d.cast_vote(vote(
+            &d,
+            &NovaKeypair::generate(),
+            Resolution::ForRespondent,
+        ));
+        assert!(matches!(result, Err(DisputeError::AlreadyResolved)));
+    }
+
+    #[test]
+    fn panel_majority_records_its_rationales() {
+        let panel: Vec<NovaKeypair> = (0..3).map(|_| NovaKeypair::generate()).collect();
+        let mut d = create_test_dispute()
+            .with_category(DisputeCategory::CreditTerms)
+            .with_arbiters(panel.iter().map(|k| k.public_key().to_hex()).collect());
+
+        let outsider = vote(&d, &NovaKeypair::generate(), Resolution::ForInitiator);
+        assert!(matches!(
+            d.cast_vote(outsider),
+            Err(DisputeError::Unauthorized { .. })
+        ));
+
+        assert_eq!(
+            d.cast_vote(vote(&d, &panel[0], Resolution::ForInitiator))
+                .unwrap(),
+            None
+        );
+        assert!(matches!(
+            d.cast_vote(vote(&d, &panel[0], Resolution::ForInitiator)),
+            Err(DisputeError::DuplicateVote(_))
+        ));
+        let private = ArbiterVote::sign(
+            &panel[1],
+            d.id.clone(),
+            Resolution::ForRespondent,
+            "Repayment was on time",
+            false,
+        );
+        assert_eq!(d.cast_vote(private).unwrap(), None);
+        assert_eq!(d.status, DisputeStatus::Open);
+
+        assert_eq!(
+            d.cast_vote(vote(&d, &panel[2], Resolution::ForInitiator))
+                .unwrap(),
+            Some(Resolution::ForInitiator)
+        );
+        let record = d.record.as_ref().unwrap();
+        assert_eq!(record.category, DisputeCategory::CreditTerms);
+        assert_eq!(record.dissents, 1);
+        let arbiters: Vec<&str> = record
+            .rationales
+            .iter()
+            .map(|r| r.arbiter.as_str())
+            .collect();
+        assert_eq!(
+            arbiters,
+            vec![d.arbiters[0].as_str(), d.arbiters[2].as_str()]
+        );
+        assert_eq!(
+            record.rationales[0].hash,
+            rationale_hash("The evidence supports this side")
+        );
+    }
+
+    #[test]
+    fn rationale_must_match_its_hash() {
+        let mut d = create_test_dispute();
+        let keypair = NovaKeypair::generate();
+
+        let mut v = vote(&d, &keypair, Resolution::ForInitiator);
+        v.rationale = Some("A different story".into());
+        assert!(matches!(
+            d.cast_vote(v),
+            Err(DisputeError::InvalidRationale(_))
+        ));
+
+        let mut v = vote(&d, &keypair, Resolution::ForInitiator);
+        v.rationale = None;
+        v.rationale_hash = "not-a-hash".into();
+        assert!(matches!(
+            d.cast_vote(v),
+            Err(DisputeError::InvalidRationale(_))
+        ));
+
+        // Swapping in another hash breaks the signature.
+        let mut v = vote(&d, &keypair, Resolution::ForInitiator);
+        v.rationale = None;
+        v.rationale_hash = rationale_hash("A different story");
+        assert!(matches!(
+            d.cast_vote(v),
+            Err(DisputeError::InvalidArbiterSignature)
+        ));
+        assert!(d.votes.is_empty());
+    }
+
+    #[test]
+    fn precedents_searchable_by_category() {
+        let mut registry = PrecedentRegistry::new();
+        let mut open = create_test_dispute();
+        assert!(matches!(
+            registry.record(&open),
+            Err(DisputeError::NotResolved(_))
+        ));
+        open.cast_vote(vote(
+            &open,
+            &NovaKeypair::generate(),
+            Resolution::ForInitiator,
+        ))
+        .unwrap();
+        registry.record(&open).unwrap();
+        assert!(matches!(
+            registry.record(&open),
+            Err(DisputeError::DuplicatePrecedent(_))
+        ));
+
+        let mut purchase = create_test_dispute().with_category(DisputeCategory::Purchase);
+        purchase
+            .cast_vote(vote(
+                &purchase,
+                &NovaKeypair::generate(),
+                Resolution::ForRespondent,
+            ))
+            .unwrap();
+        registry.record(&purchase).unwrap();
+
+        assert_eq!(registry.len(), 2);
+        let found =
+            registry.search(&PrecedentQuery::default().with_category(DisputeCategory::Purchase));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].dispute_id, purchase.id);
+        assert_eq!(
+            registry
+                .search(&PrecedentQuery::default().with_resolution(Resolution::ForInitiator))
+                .len(),
+            1
+        );
+        assert_eq!(
+            registry
+                .search(&PrecedentQuery::default().with_text("EVIDENCE SUPPORTS"))
+                .len(),
+            2
+        );
+        assert!(registry
+            .search(&PrecedentQuery::default().with_text("counterfeit"))
+            .is_empty());
     }
 
     #[test]
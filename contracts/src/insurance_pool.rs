@@ -0,0 +1,844 @@
+//! # Insurance Pool Contract
+//!
+//! A mutual backstop for lenders: underwriters pool capital, credit escrows
+//! buy coverage against borrower default, and a verified default pays the
+//! lender out of the pool.
+//!
+//! ## Lifecycle
+//!
+//! 1. **Deposit** — underwriters add capital. Each underwriter's stake is
+//!    its share of the pool, and premiums and payouts move every stake
+//!    pro-rata.
+//! 2. **Enroll** — the lender of a [`CreditEscrow`] opts it in, paying a
+//!    premium priced off the borrower's credit score by the pool's
+//!    [`PremiumSchedule`]. The coverage limit is locked against the pool's
+//!    capital and the premium is shared among the underwriters.
+//! 3. **Claim** — once the escrow is `Defaulted`, the lender claims what the
+//!    borrower still owes on the released principal, up to the coverage
+//!    limit. The payout is taken from the underwriters pro-rata.
+//! 4. **Expire** — an escrow that completes or is refunded no longer needs
+//!    cover; expiring its coverage unlocks the limit.
+//!
+//! Underwriters may withdraw only capital not locked by active coverage.
+//!
+//! ## Conservation
+//!
+//! The pool keeps running totals of everything that entered and left it.
+//! Every operation runs against a copy of the pool and is committed only if
+//! afterwards
+//!
+//! ```text
+//!   Σ stakes == deposited + premiums − withdrawn − paid out
+//! ```
+//!
+//! and the locked coverage still fits in the capital. Pro-rata splits use
+//! the largest-remainder method, so they always add up to the exact amount.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::credit_escrow::{CreditEscrow, EscrowStatus};
+
+/// Highest credit score the premium schedule prices.
+pub const MAX_CREDIT_SCORE: u16 = 1000;
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur during insurance pool operations.
+#[derive(Debug, Error)]
+pub enum InsurancePoolError {
+    /// The caller is not allowed to perform this operation.
+    #[error("unauthorized: {party} may not perform this operation")]
+    Unauthorized {
+        /// The address that attempted the operation.
+        party: String,
+    },
+
+    /// Deposits, withdrawals and coverage limits must be non-zero.
+    #[error("amount must be greater than zero")]
+    ZeroAmount,
+
+    /// An arithmetic overflow would occur.
+    #[error("amount overflow: operation would exceed allowed limits")]
+    AmountOverflow,
+
+    /// The caller has no stake in the pool.
+    #[error("unknown underwriter: {0}")]
+    UnknownUnderwriter(String),
+
+    /// Not enough unlocked capital for the operation.
+    #[error("insufficient capital: {available} available, {requested} requested")]
+    InsufficientCapital {
+        /// Capital not locked by active coverage (or the caller's stake).
+        available: u64,
+        /// Amount the operation needs.
+        requested: u64,
+    },
+
+    /// The credit score is outside `0..=MAX_CREDIT_SCORE`.
+    #[error("invalid credit score: {0}")]
+    InvalidScore(u16),
+
+    /// The borrower's credit score is below what the pool insures.
+    #[error("credit score {score} is below the insurable minimum of {minimum}")]
+    Uninsurable {
+        /// The borrower's score.
+        score: u16,
+        /// The lowest score the schedule prices.
+        minimum: u16,
+    },
+
+    /// The premium paid does not match the quote.
+    #[error("premium mismatch: expected {expected}, got {got}")]
+    PremiumMismatch {
+        /// The quoted premium.
+        expected: u64,
+        /// The premium the lender paid.
+        got: u64,
+    },
+
+    /// The coverage limit exceeds the escrow's principal.
+    #[error("coverage limit {limit} exceeds the escrow principal of {principal}")]
+    LimitExceedsPrincipal {
+        /// The requested limit.
+        limit: u64,
+        /// The escrow's principal.
+        principal: u64,
+    },
+
+    /// The escrow is already covered by this pool.
+    #[error("escrow {0} is already covered")]
+    AlreadyCovered(String),
+
+    /// The escrow has no active coverage in this pool.
+    #[error("escrow {0} has no active coverage")]
+    NotCovered(String),
+
+    /// The escrow is not in a state that allows this operation.
+    #[error("invalid escrow state: escrow is {current}, expected {expected}")]
+    InvalidEscrowState {
+        /// The escrow's current status.
+        current: String,
+        /// The status required for this operation.
+        expected: String,
+    },
+
+    /// The pool's books do not balance. Indicates a bug; the operation
+    /// that caused it was not applied.
+    #[error("value not conserved: stakes total {stakes}, ledger expects {expected}")]
+    ConservationViolated {
+        /// Sum of all underwriter stakes.
+        stakes: u128,
+        /// Deposits plus premiums, less withdrawals and payouts.
+        expected: u128,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Premium Pricing
+// ---------------------------------------------------------------------------
+
+/// Premium rate for borrowers scoring at least `min_score`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PremiumTier {
+    /// Lowest credit score in the tier.
+    pub min_score: u16,
+    /// Premium as a share of the coverage limit, in basis points.
+    pub rate_bps: u32,
+}
+
+/// Maps a borrower's credit score to a premium rate. Scores below the
+/// lowest tier are not insured.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PremiumSchedule {
+    /// Tiers, highest `min_score` first.
+    tiers: Vec<PremiumTier>,
+}
+
+impl PremiumSchedule {
+    /// Creates a schedule from `tiers`, in any order.
+    pub fn new(mut tiers: Vec<PremiumTier>) -> Self {
+        tiers.sort_by(|a, b| b.min_score.cmp(&a.min_score));
+        Self { tiers }
+    }
+
+    /// The tiers, highest `min_score` first.
+    pub fn tiers(&self) -> &[PremiumTier] {
+        &self.tiers
+    }
+
+    /// Premium rate for `score`, in basis points.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::InvalidScore`] if the score is above
+    /// [`MAX_CREDIT_SCORE`], and [`InsurancePoolError::Uninsurable`] if it
+    /// falls below every tier.
+    pub fn rate_bps(&self, score: u16) -> Result<u32, InsurancePoolError> {
+        if score > MAX_CREDIT_SCORE {
+            return Err(InsurancePoolError::InvalidScore(score));
+        }
+        self.tiers
+            .iter()
+            .find(|tier| score >= tier.min_score)
+            .map(|tier| tier.rate_bps)
+            .ok_or_else(|| InsurancePoolError::Uninsurable {
+                score,
+                minimum: self.tiers.last().map_or(0, |tier| tier.min_score),
+            })
+    }
+
+    /// Premium for covering `limit` photons for a borrower scoring `score`,
+    /// rounded up to the next photon.
+    pub fn quote(&self, limit: u64, score: u16) -> Result<u64, InsurancePoolError> {
+        let rate = u128::from(self.rate_bps(score)?);
+        let premium = (u128::from(limit) * rate).div_ceil(10_000);
+        u64::try_from(premium).map_err(|_| InsurancePoolError::AmountOverflow)
+    }
+}
+
+impl Default for PremiumSchedule {
+    /// 1% for excellent credit up to 12% at the insurable minimum of 400.
+    fn default() -> Self {
+        Self::new(vec![
+            PremiumTier {
+                min_score: 800,
+                rate_bps: 100,
+            },
+            PremiumTier {
+                min_score: 700,
+                rate_bps: 250,
+            },
+            PremiumTier {
+                min_score: 600,
+                rate_bps: 500,
+            },
+            PremiumTier {
+                min_score: 500,
+                rate_bps: 800,
+            },
+            PremiumTier {
+                min_score: 400,
+                rate_bps: 1200,
+            },
+        ])
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// The current status of an escrow's coverage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoverageStatus {
+    /// The limit is locked; a default can be claimed.
+    Active,
+    /// The lender was paid out.
+    Claimed,
+    /// The escrow closed without default; the limit is unlocked.
+    Expired,
+}
+
+impl std::fmt::Display for CoverageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoverageStatus::Active => write!(f, "Active"),
+            CoverageStatus::Claimed => write!(f, "Claimed"),
+            CoverageStatus::Expired => write!(f, "Expired"),
+        }
+    }
+}
+
+/// Default cover bought for one credit escrow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Coverage {
+    /// The covered escrow.
+    pub escrow_id: String,
+    /// Hex-encoded public key of the lender, who is paid on default.
+    pub lender: String,
+    /// Hex-encoded public key of the borrower.
+    pub borrower: String,
+    /// The borrower's credit score the premium was priced from.
+    pub credit_score: u16,
+    /// Most the pool pays out for this escrow, in photons.
+    pub limit: u64,
+    /// Premium paid, in photons.
+    pub premium: u64,
+    /// Amount paid out on the claim, if any.
+    pub payout: u64,
+    /// Current status.
+    pub status: CoverageStatus,
+    /// Timestamp when the escrow was enrolled.
+    pub enrolled_at: DateTime<Utc>,
+    /// Timestamp of the most recent state change.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An insurance pool backstopping credit escrow defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsurancePool {
+    /// Unique identifier for this pool.
+    pub pool_id: String,
+    /// How premiums are priced.
+    pub schedule: PremiumSchedule,
+    /// Each underwriter's stake in photons, keyed by hex-encoded public key.
+    pub stakes: BTreeMap<String, u64>,
+    /// Coverage bought, keyed by escrow ID.
+    pub coverages: BTreeMap<String, Coverage>,
+    /// Total capital ever deposited.
+    pub total_deposited: u64,
+    /// Total capital ever withdrawn.
+    pub total_withdrawn: u64,
+    /// Total premiums collected.
+    pub total_premiums: u64,
+    /// Total paid out on claims.
+    pub total_payouts: u64,
+    /// Timestamp when the pool was created.
+    pub created_at: DateTime<Utc>,
+}
+
+impl InsurancePool {
+    /// Creates an empty pool pricing premiums with `schedule`.
+    pub fn create(schedule: PremiumSchedule) -> Self {
+        Self {
+            pool_id: Uuid::new_v4().to_string(),
+            schedule,
+            stakes: BTreeMap::new(),
+            coverages: BTreeMap::new(),
+            total_deposited: 0,
+            total_withdrawn: 0,
+            total_premiums: 0,
+            total_payouts: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Sum of all underwriter stakes.
+    pub fn total_capital(&self) -> u64 {
+        self.stakes.values().sum()
+    }
+
+    /// Sum of the limits of all active coverage.
+    pub fn locked_capital(&self) -> u64 {
+        self.coverages
+            .values()
+            .filter(|c| c.status == CoverageStatus::Active)
+            .map(|c| c.limit)
+            .sum()
+    }
+
+    /// Capital not locked by active coverage.
+    pub fn available_capital(&self) -> u64 {
+        self.total_capital().saturating_sub(self.locked_capital())
+    }
+
+    /// The stake of `underwriter`, zero if it has none.
+    pub fn stake_of(&self, underwriter: &str) -> u64 {
+        self.stakes.get(underwriter).copied().unwrap_or(0)
+    }
+
+    /// An underwriter adds `amount` photons of capital.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::ZeroAmount`] for a zero deposit.
+    pub fn deposit(&mut self, underwriter: &str, amount: u64) -> Result<(), InsurancePoolError> {
+        if amount == 0 {
+            return Err(InsurancePoolError::ZeroAmount);
+        }
+        self.apply(|pool| {
+            let stake = pool.stakes.entry(underwriter.to_string()).or_insert(0);
+            *stake = checked_add(*stake, amount)?;
+            pool.total_deposited = checked_add(pool.total_deposited, amount)?;
+            Ok(())
+        })
+    }
+
+    /// An underwriter takes `amount` photons of its stake out of the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::UnknownUnderwriter`] if the caller has
+    /// no stake, and [`InsurancePoolError::InsufficientCapital`] if the
+    /// amount exceeds its stake or the capital not locked by coverage.
+    pub fn withdraw(&mut self, underwriter: &str, amount: u64) -> Result<(), InsurancePoolError> {
+        if amount == 0 {
+            return Err(InsurancePoolError::ZeroAmount);
+        }
+        let stake = *self
+            .stakes
+            .get(underwriter)
+            .ok_or_else(|| InsurancePoolError::UnknownUnderwriter(underwriter.to_string()))?;
+        let available = stake.min(self.available_capital());
+        if amount > available {
+            return Err(InsurancePoolError::InsufficientCapital {
+                available,
+                requested: amount,
+            });
+        }
+
+        self.apply(|pool| {
+            if stake == amount {
+                pool.stakes.remove(underwriter);
+            } else {
+                pool.stakes.insert(underwriter.to_string(), stake - amount);
+            }
+            pool.total_withdrawn = checked_add(pool.total_withdrawn, amount)?;
+            Ok(())
+        })
+    }
+
+    /// The escrow's lender buys `limit` photons of default cover, paying
+    /// `premium` as quoted by the schedule for `credit_score`.
+    ///
+    /// The premium is shared among the underwriters pro-rata to their
+    /// stakes. The escrow must not have been released yet or be running
+    /// (`Pending`, `Funded` or `Active`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::Unauthorized`] if the caller is not the
+    /// escrow's lender, [`InsurancePoolError::PremiumMismatch`] if the
+    /// premium is not the quote, and
+    /// [`InsurancePoolError::InsufficientCapital`] if the pool cannot lock
+    /// the limit.
+    pub fn enroll(
+        &mut self,
+        caller: &str,
+        escrow: &CreditEscrow,
+        limit: u64,
+        credit_score: u16,
+        premium: u64,
+    ) -> Result<&Coverage, InsurancePoolError> {
+        if caller != escrow.lender {
+            return Err(InsurancePoolError::Unauthorized {
+                party: caller.to_string(),
+            });
+        }
+        if !matches!(
+            escrow.status,
+            EscrowStatus::Pending | EscrowStatus::Funded | EscrowStatus::Active
+        ) {
+            return Err(InsurancePoolError::InvalidEscrowState {
+                current: escrow.status.to_string(),
+                expected: "Pending, Funded or Active".into(),
+            });
+        }
+        if self.coverages.contains_key(&escrow.escrow_id) {
+            return Err(InsurancePoolError::AlreadyCovered(escrow.escrow_id.clone()));
+        }
+        if limit == 0 {
+            return Err(InsurancePoolError::ZeroAmount);
+        }
+        if limit > escrow.principal {
+            return Err(InsurancePoolError::LimitExceedsPrincipal {
+                limit,
+                principal: escrow.principal,
+            });
+        }
+        let expected = self.schedule.quote(limit, credit_score)?;
+        if premium != expected {
+            return Err(InsurancePoolError::PremiumMismatch {
+                expected,
+                got: premium,
+            });
+        }
+        let available = self.available_capital();
+        if limit > available {
+            return Err(InsurancePoolError::InsufficientCapital {
+                available,
+                requested: limit,
+            });
+        }
+
+        let now = Utc::now();
+        self.apply(|pool| {
+            for (underwriter, share) in pool.pro_rata(premium) {
+                let stake = pool
+                    .stakes
+                    .get_mut(&underwriter)
+                    .expect("split is over stakes");
+                *stake = checked_add(*stake, share)?;
+            }
+            pool.total_premiums = checked_add(pool.total_premiums, premium)?;
+            pool.coverages.insert(
+                escrow.escrow_id.clone(),
+                Coverage {
+                    escrow_id: escrow.escrow_id.clone(),
+                    lender: escrow.lender.clone(),
+                    borrower: escrow.borrower.clone(),
+                    credit_score,
+                    limit,
+                    premium,
+                    payout: 0,
+                    status: CoverageStatus::Active,
+                    enrolled_at: now,
+                    updated_at: now,
+                },
+            );
+            Ok(())
+        })?;
+        Ok(&self.coverages[&escrow.escrow_id])
+    }
+
+    /// Pays the lender of a defaulted escrow and returns the payout.
+    ///
+    /// The payout is the released principal the borrower has not repaid,
+    /// capped at the coverage limit, and is taken from the underwriters
+    /// pro-rata to their stakes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::NotCovered`] if the escrow has no
+    /// active coverage, and [`InsurancePoolError::InvalidEscrowState`] if
+    /// it has not defaulted.
+    pub fn claim(&mut self, escrow: &CreditEscrow) -> Result<u64, InsurancePoolError> {
+        let limit = self.active_coverage(escrow)?.limit;
+        if escrow.status != EscrowStatus::Defaulted {
+            return Err(InsurancePoolError::InvalidEscrowState {
+                current: escrow.status.to_string(),
+                expected: EscrowStatus::Defaulted.to_string(),
+            });
+        }
+
+        let loss = escrow.released_amount.saturating_sub(escrow.repaid_amount);
+        let payout = loss.min(limit);
+        let now = Utc::now();
+        self.apply(|pool| {
+            for (underwriter, share) in pool.pro_rata(payout) {
+                let stake = pool
+                    .stakes
+                    .get_mut(&underwriter)
+                    .expect("split is over stakes");
+                *stake = stake
+                    .checked_sub(share)
+                    .ok_or(InsurancePoolError::AmountOverflow)?;
+            }
+            pool.stakes.retain(|_, stake| *stake > 0);
+            pool.total_payouts = checked_add(pool.total_payouts, payout)?;
+            let coverage = pool
+                .coverages
+                .get_mut(&escrow.escrow_id)
+                .expect("coverage checked above");
+            coverage.payout = payout;
+            coverage.status = CoverageStatus::Claimed;
+            coverage.updated_at = now;
+            Ok(())
+        })?;
+        Ok(payout)
+    }
+
+    /// Unlocks the coverage of an escrow that closed without default
+    /// (`Completed` or `Refunded`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::NotCovered`] if the escrow has no
+    /// active coverage, and [`InsurancePoolError::InvalidEscrowState`] if
+    /// it is still running or has defaulted.
+    pub fn expire(&mut self, escrow: &CreditEscrow) -> Result<(), InsurancePoolError> {
+        self.active_coverage(escrow)?;
+        if !matches!(
+            escrow.status,
+            EscrowStatus::Completed | EscrowStatus::Refunded
+        ) {
+            return Err(InsurancePoolError::InvalidEscrowState {
+                current: escrow.status.to_string(),
+                expected: "Completed or Refunded".into(),
+            });
+        }
+
+        let now = Utc::now();
+        self.apply(|pool| {
+            let coverage = pool
+                .coverages
+                .get_mut(&escrow.escrow_id)
+                .expect("coverage checked above");
+            coverage.status = CoverageStatus::Expired;
+            coverage.updated_at = now;
+            Ok(())
+        })
+    }
+
+    /// Checks the pool's books: the stakes add up to what entered the pool
+    /// less what left it, and active coverage fits in the capital.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsurancePoolError::ConservationViolated`] or
+    /// [`InsurancePoolError::InsufficientCapital`] if they do not.
+    pub fn audit(&self) -> Result<(), InsurancePoolError> {
+        let stakes: u128 = self.stakes.values().map(|&s| u128::from(s)).sum();
+        let expected = (u128::from(self.total_deposited) + u128::from(self.total_premiums))
+            .checked_sub(u128::from(self.total_withdrawn) + u128::from(self.total_payouts));
+        if expected != Some(stakes) {
+            return Err(InsurancePoolError::ConservationViolated {
+                stakes,
+                expected: expected.unwrap_or(0),
+            });
+        }
+
+        // Claimed coverage has already been paid, so only active limits
+        // need backing.
+        let locked = self.locked_capital();
+        if u128::from(locked) > stakes {
+            return Err(InsurancePoolError::InsufficientCapital {
+                available: self.total_capital(),
+                requested: locked,
+            });
+        }
+        Ok(())
+    }
+
+    fn active_coverage(&self, escrow: &CreditEscrow) -> Result<&Coverage, InsurancePoolError> {
+        self.coverages
+            .get(&escrow.escrow_id)
+            .filter(|c| c.status == CoverageStatus::Active)
+            .ok_or_else(|| InsurancePoolError::NotCovered(escrow.escrow_id.clone()))
+    }
+
+    /// Splits `amount` across the underwriters in proportion to their
+    /// stakes. Whole photons left over go, one each, to the underwriters
+    /// with the largest fractional shares (ties by address), so the shares
+    /// always add up to `amount`.
+    fn pro_rata(&self, amount: u64) -> Vec<(String, u64)> {
+        let total = u128::from(self.total_capital());
+        if total == 0 {
+            return Vec::new();
+        }
+        let mut shares: Vec<(String, u64, u128)> = self
+            .stakes
+            .iter()
+            .map(|(underwriter, &stake)| {
+                let exact = u128::from(amount) * u128::from(stake);
+                (underwriter.clone(), (exact / total) as u64, exact % total)
+            })
+            .collect();
+
+        let assigned: u64 = shares.iter().map(|(_, share, _)| share).sum();
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by(|&a, &b| shares[b].2.cmp(&shares[a].2));
+        for &i in order.iter().take((amount - assigned) as usize) {
+            shares[i].1 += 1;
+        }
+        shares
+            .into_iter()
+            .map(|(underwriter, share, _)| (underwriter, share))
+            .collect()
+    }
+
+    /// Runs `op` against a copy of the pool and keeps the result only if
+    /// the operation succeeded and the books still balance.
+    fn apply(
+        &mut self,
+        op: impl FnOnce(&mut Self) -> Result<(), InsurancePoolError>,
+    ) -> Result<(), InsurancePoolError> {
+        let mut next = self.clone();
+        op(&mut next)?;
+        next.audit()?;
+        *self = next;
+        Ok(())
+    }
+}
+
+fn checked_add(a: u64, b: u64) -> Result<u64, InsurancePoolError> {
+    a.checked_add(b).ok_or(InsurancePoolError::AmountOverflow)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credit_escrow::CreditTerms;
+
+    fn escrow(principal: u64) -> CreditEscrow {
+        let terms = CreditTerms {
+            principal,
+            interest_rate_bps: 500,
+            total_owed: principal + principal / 20,
+            repayment_deadline: Utc::now() + chrono::Duration::days(30),
+            grace_period_secs: 86400,
+        };
+        let mut escrow = CreditEscrow::create("lender_pk".into(), "borrower_pk".into(), terms);
+        escrow.fund(principal).unwrap();
+        escrow.release_to_borrower(principal).unwrap();
+        escrow
+    }
+
+    /// A pool with 600 000 from alice and 400 000 from bob.
+    fn funded_pool() -> InsurancePool {
+        let mut pool = InsurancePool::create(PremiumSchedule::default());
+        pool.deposit("alice", 600_000).unwrap();
+        pool.deposit("bob", 400_000).unwrap();
+        pool
+    }
+
+    #[test]
+    fn premiums_are_priced_off_credit_score() {
+        let schedule = PremiumSchedule::default();
+        assert_eq!(schedule.quote(100_000, 850).unwrap(), 1_000);
+        assert_eq!(schedule.quote(100_000, 650).unwrap(), 5_000);
+        assert_eq!(schedule.quote(100_000, 400).unwrap(), 12_000);
+        // Rounded up: 1% of 150 photons is 1.5.
+        assert_eq!(schedule.quote(150, 800).unwrap(), 2);
+
+        assert!(matches!(
+            schedule.quote(100_000, 399),
+            Err(InsurancePoolError::Uninsurable { minimum: 400, .. })
+        ));
+        assert!(matches!(
+            schedule.quote(100_000, 1_001),
+            Err(InsurancePoolError::InvalidScore(1_001))
+        ));
+    }
+
+    #[test]
+    fn premiums_are_shared_pro_rata() {
+        let mut pool = funded_pool();
+        let escrow = escrow(200_000);
+        let premium = pool.schedule.quote(200_000, 650).unwrap();
+
+        let coverage = pool
+            .enroll("lender_pk", &escrow, 200_000, 650, premium)
+            .unwrap();
+        assert_eq!(coverage.status, CoverageStatus::Active);
+
+        // 10 000 split 60/40.
+        assert_eq!(pool.stake_of("alice"), 606_000);
+        assert_eq!(pool.stake_of("bob"), 404_000);
+        assert_eq!(pool.locked_capital(), 200_000);
+        pool.audit().unwrap();
+    }
+
+    #[test]
+    fn enrollment_requires_lender_and_exact_premium() {
+        let mut pool = funded_pool();
+        let escrow = escrow(200_000);
+
+        assert!(matches!(
+            pool.enroll("borrower_pk", &escrow, 200_000, 650, 10_000),
+            Err(InsurancePoolError::Unauthorized { .. })
+        ));
+        assert!(matches!(
+            pool.enroll("lender_pk", &escrow, 200_000, 650, 9_999),
+            Err(InsurancePoolError::PremiumMismatch {
+                expected: 10_000,
+                got: 9_999
+            })
+        ));
+        assert!(matches!(
+            pool.enroll("lender_pk", &escrow, 200_001, 650, 10_001),
+            Err(InsurancePoolError::LimitExceedsPrincipal { .. })
+        ));
+        assert_eq!(pool.total_premiums, 0);
+    }
+
+    #[test]
+    fn coverage_cannot_exceed_unlocked_capital() {
+        let mut pool = InsurancePool::create(PremiumSchedule::default());
+        pool.deposit("alice", 100_000).unwrap();
+        let escrow = escrow(200_000);
+
+        let premium = pool.schedule.quote(150_000, 850).unwrap();
+        assert!(matches!(
+            pool.enroll("lender_pk", &escrow, 150_000, 850, premium),
+            Err(InsurancePoolError::InsufficientCapital {
+                available: 100_000,
+                requested: 150_000
+            })
+        ));
+    }
+
+    #[test]
+    fn verified_default_pays_out_up_to_the_limit() {
+        let mut pool = funded_pool();
+        let mut escrow = escrow(500_000);
+        let premium = pool.schedule.quote(300_000, 750).unwrap();
+        pool.enroll("lender_pk", &escrow, 300_000, 750, premium)
+            .unwrap();
+
+        // No payout while the escrow is still running.
+        assert!(matches!(
+            pool.claim(&escrow),
+            Err(InsurancePoolError::InvalidEscrowState { .. })
+        ));
+
+        escrow.repay(100_000).unwrap();
+        escrow.status = EscrowStatus::Defaulted;
+
+        // 400 000 unpaid, capped at the 300 000 limit, taken 60/40.
+        let before = (pool.stake_of("alice"), pool.stake_of("bob"));
+        assert_eq!(pool.claim(&escrow).unwrap(), 300_000);
+        assert_eq!(pool.stake_of("alice"), before.0 - 180_000);
+        assert_eq!(pool.stake_of("bob"), before.1 - 120_000);
+        assert_eq!(pool.locked_capital(), 0);
+        pool.audit().unwrap();
+
+        // Each coverage pays once.
+        assert!(matches!(
+            pool.claim(&escrow),
+            Err(InsurancePoolError::NotCovered(_))
+        ));
+    }
+
+    #[test]
+    fn locked_capital_cannot_be_withdrawn_until_expiry() {
+        let mut pool = funded_pool();
+        let mut escrow = escrow(800_000);
+        let premium = pool.schedule.quote(800_000, 850).unwrap();
+        pool.enroll("lender_pk", &escrow, 800_000, 850, premium)
+            .unwrap();
+
+        // 1 008 000 in the pool, 800 000 of it locked.
+        assert!(matches!(
+            pool.withdraw("alice", 300_000),
+            Err(InsurancePoolError::InsufficientCapital {
+                available: 208_000,
+                ..
+            })
+        ));
+
+        escrow.repay(escrow.terms.total_owed).unwrap();
+        pool.expire(&escrow).unwrap();
+        pool.withdraw("alice", pool.stake_of("alice")).unwrap();
+        assert_eq!(pool.stake_of("alice"), 0);
+        assert!(!pool.stakes.contains_key("alice"));
+        pool.audit().unwrap();
+    }
+
+    #[test]
+    fn pro_rata_splits_add_up_exactly() {
+        let mut pool = InsurancePool::create(PremiumSchedule::default());
+        pool.deposit("a", 1).unwrap();
+        pool.deposit("b", 1).unwrap();
+        pool.deposit("c", 1).unwrap();
+
+        let shares = pool.pro_rata(100);
+        assert_eq!(shares.iter().map(|(_, s)| s).sum::<u64>(), 100);
+        assert_eq!(shares[0], ("a".to_string(), 34));
+    }
+
+    #[test]
+    fn unbalanced_books_are_detected() {
+        let mut pool = funded_pool();
+        pool.stakes.insert("mallory".into(), 1);
+        assert!(matches!(
+            pool.audit(),
+            Err(InsurancePoolError::ConservationViolated { .. })
+        ));
+
+        // And an operation on such a pool is not applied.
+        assert!(pool.deposit("alice", 10).is_err());
+        assert_eq!(pool.stake_of("alice"), 600_000);
+    }
+}
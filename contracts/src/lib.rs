@@ -5,9 +5,18 @@
 //! transfer-only chain:
 //!
 //! - **Credit Escrow** — trustless lending with time-locked fund release,
-//!   automatic default detection, and multi-party dispute resolution.
+//!   release on signed oracle attestations, automatic default detection,
+//!   and multi-party dispute resolution.
 //! - **Dispute Resolution** — evidence-based arbitration for escrow
-//!   disagreements, driven by arbiter votes and cryptographic evidence hashes.
+//!   disagreements, driven by arbiter votes over a timeline of typed,
+//!   hash-anchored evidence. Every vote commits to a signed rationale, and
+//!   rulings are kept as precedents searchable by category.
+//! - **Insurance Pool** — underwriter capital backstopping credit escrow
+//!   defaults: premiums priced off the borrower's credit score and shared
+//!   pro-rata, payouts on verified default up to each coverage limit.
+//! - **Purchase Escrow** — marketplace buyer protection: funds release on
+//!   delivery confirmation or after a deadline, and only escalate into a
+//!   dispute when the buyer objects.
 //! - **Token Factory** — permissionless token issuance with issuer-gated
 //!   minting and verifiable burn mechanics.
 //!
@@ -23,4 +32,6 @@
 
 pub mod credit_escrow;
 pub mod dispute_resolution;
+pub mod insurance_pool;
+pub mod purchase_escrow;
 pub mod token_factory;
@@ -0,0 +1,475 @@
+//! # Purchase Escrow Contract
+//!
+//! Buyer protection for marketplace purchases without dragging an arbiter
+//! into every sale. The buyer's payment sits in escrow until the goods
+//! arrive; most purchases settle without anyone but the two parties
+//! touching them.
+//!
+//! ## Lifecycle
+//!
+//! 1. **Create** — buyer and seller agree on the price and how long the
+//!    buyer has to confirm delivery once the seller ships.
+//! 2. **Fund** — buyer deposits the full price.
+//! 3. **Ship** — seller posts a delivery attestation hash (carrier receipt,
+//!    tracking proof, signed delivery note — stored off-chain, anchored
+//!    here). This starts the confirmation window.
+//! 4. **Confirm** — buyer confirms receipt and the funds go to the seller.
+//!    If the buyer stays silent past the window, funds auto-release.
+//! 5. **Object** — only if the buyer objects before release does the
+//!    purchase escalate into a [`Dispute`] from
+//!    [`super::dispute_resolution`]. The arbiter's ruling decides whether
+//!    the buyer is refunded or the seller paid.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::dispute_resolution::{Dispute, DisputeCategory, DisputeError, DisputeStatus};
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur during purchase escrow operations.
+#[derive(Debug, Error)]
+pub enum PurchaseEscrowError {
+    /// The purchase is not in a state that allows this operation.
+    #[error("invalid state: purchase is {current}, expected {expected}")]
+    InvalidState {
+        /// The purchase's current status.
+        current: String,
+        /// The status required for this operation.
+        expected: String,
+    },
+
+    /// The caller is not allowed to perform this operation.
+    #[error("unauthorized: {party} may not perform this operation")]
+    Unauthorized {
+        /// The address that attempted the operation.
+        party: String,
+    },
+
+    /// The deposit does not match the agreed price.
+    #[error("amount mismatch: expected {expected}, got {got}")]
+    AmountMismatch {
+        /// The agreed purchase price.
+        expected: u64,
+        /// The amount the buyer tried to deposit.
+        got: u64,
+    },
+
+    /// The supplied dispute does not belong to this purchase.
+    #[error("dispute {0} does not belong to this purchase")]
+    ForeignDispute(String),
+
+    /// The dispute has not been resolved or cancelled yet.
+    #[error("dispute {0} is still pending")]
+    DisputePending(String),
+
+    /// An error from the underlying dispute contract.
+    #[error("dispute error: {0}")]
+    Dispute(#[from] DisputeError),
+}
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// The current status of a purchase escrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PurchaseStatus {
+    /// Created, waiting for the buyer's deposit.
+    AwaitingFunding,
+    /// Buyer has deposited the price; waiting for the seller to ship.
+    Funded,
+    /// Seller posted a delivery attestation; the confirmation window runs.
+    Shipped,
+    /// Funds released to the seller (confirmed, auto-released, or ruled).
+    Released,
+    /// Funds returned to the buyer.
+    Refunded,
+    /// Buyer objected; the outcome is up to the arbiter.
+    Disputed,
+}
+
+impl std::fmt::Display for PurchaseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PurchaseStatus::AwaitingFunding => write!(f, "AwaitingFunding"),
+            PurchaseStatus::Funded => write!(f, "Funded"),
+            PurchaseStatus::Shipped => write!(f, "Shipped"),
+            PurchaseStatus::Released => write!(f, "Released"),
+            PurchaseStatus::Refunded => write!(f, "Refunded"),
+            PurchaseStatus::Disputed => write!(f, "Disputed"),
+        }
+    }
+}
+
+/// A marketplace purchase held in escrow until delivery is confirmed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseEscrow {
+    /// Unique identifier for this purchase.
+    pub purchase_id: String,
+    /// Hex-encoded public key of the buyer.
+    pub buyer: String,
+    /// Hex-encoded public key of the seller.
+    pub seller: String,
+    /// Agreed purchase price in photons.
+    pub amount: u64,
+    /// Seconds the buyer has to confirm or object after shipment.
+    pub confirmation_window_secs: u64,
+    /// BLAKE3 hash of the off-chain delivery attestation, hex-encoded.
+    pub delivery_attestation: Option<String>,
+    /// Deadline after which the funds auto-release to the seller.
+    pub release_deadline: Option<DateTime<Utc>>,
+    /// ID of the dispute opened by the buyer, if any.
+    pub dispute_id: Option<String>,
+    /// Current lifecycle status.
+    pub status: PurchaseStatus,
+    /// Timestamp when the purchase was created.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp of the most recent state change.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PurchaseEscrow {
+    /// Creates a new purchase in `AwaitingFunding` status.
+    ///
+    /// # Arguments
+    ///
+    /// * `buyer` - Hex-encoded public key of the buyer.
+    /// * `seller` - Hex-encoded public key of the seller.
+    /// * `amount` - Purchase price in photons.
+    /// * `confirmation_window_secs` - How long the buyer has to confirm
+    ///   delivery before funds auto-release.
+    pub fn create(
+        buyer: String,
+        seller: String,
+        amount: u64,
+        confirmation_window_secs: u64,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            purchase_id: Uuid::new_v4().to_string(),
+            buyer,
+            seller,
+            amount,
+            confirmation_window_secs,
+            delivery_attestation: None,
+            release_deadline: None,
+            dispute_id: None,
+            status: PurchaseStatus::AwaitingFunding,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Buyer deposits the purchase price.
+    ///
+    /// Funding is all-or-nothing: a marketplace checkout has no use for
+    /// half-paid orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PurchaseEscrowError::Unauthorized`] if the caller is not the buyer.
+    /// Returns [`PurchaseEscrowError::InvalidState`] if already funded.
+    /// Returns [`PurchaseEscrowError::AmountMismatch`] if `amount` is not the price.
+    pub fn fund(&mut self, caller: &str, amount: u64) -> Result<(), PurchaseEscrowError> {
+        self.require(caller, &self.buyer)?;
+        self.require_status(PurchaseStatus::AwaitingFunding)?;
+
+        if amount != self.amount {
+            return Err(PurchaseEscrowError::AmountMismatch {
+                expected: self.amount,
+                got: amount,
+            });
+        }
+
+        self.status = PurchaseStatus::Funded;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Seller posts the delivery attestation and starts the confirmation window.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PurchaseEscrowError::Unauthorized`] if the caller is not the seller.
+    /// Returns [`PurchaseEscrowError::InvalidState`] if the purchase is not `Funded`.
+    pub fn mark_shipped(
+        &mut self,
+        caller: &str,
+        attestation_hash: String,
+    ) -> Result<(), PurchaseEscrowError> {
+        self.require(caller, &self.seller)?;
+        self.require_status(PurchaseStatus::Funded)?;
+
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(self.confirmation_window_secs as i64);
+        self.delivery_attestation = Some(attestation_hash);
+        self.release_deadline = Some(now + window);
+        self.status = PurchaseStatus::Shipped;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Buyer confirms delivery, releasing the funds to the seller.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PurchaseEscrowError::Unauthorized`] if the caller is not the buyer.
+    /// Returns [`PurchaseEscrowError::InvalidState`] if the purchase is not `Shipped`.
+    pub fn confirm_delivery(&mut self, caller: &str) -> Result<(), PurchaseEscrowError> {
+        self.require(caller, &self.buyer)?;
+        self.require_status(PurchaseStatus::Shipped)?;
+
+        self.status = PurchaseStatus::Released;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Releases the funds to the seller if the confirmation window has
+    /// lapsed without an objection.
+    ///
+    /// Returns `true` if the funds were released by this call.
+    pub fn check_auto_release(&mut self) -> bool {
+        self.check_auto_release_at(Utc::now())
+    }
+
+    /// Same as [`check_auto_release`](Self::check_auto_release), evaluated at `now`.
+    pub fn check_auto_release_at(&mut self, now: DateTime<Utc>) -> bool {
+        if self.status != PurchaseStatus::Shipped {
+            return false;
+        }
+
+        match self.release_deadline {
+            Some(deadline) if now > deadline => {
+                self.status = PurchaseStatus::Released;
+                self.updated_at = now;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Buyer objects to the delivery, escalating into a formal dispute.
+    ///
+    /// Must happen before the funds are released. The returned [`Dispute`]
+    /// names the buyer as initiator and already carries the seller's
+    /// delivery attestation as evidence, so the arbiter starts from what
+    /// the seller committed to on-chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PurchaseEscrowError::Unauthorized`] if the caller is not the buyer.
+    /// Returns [`PurchaseEscrowError::InvalidState`] if the purchase is not
+    /// `Funded` or `Shipped`, or the confirmation window has already lapsed.
+    pub fn object(&mut self, caller: &str, reason: String) -> Result<Dispute, PurchaseEscrowError> {
+        self.require(caller, &self.buyer)?;
+
+        let now = Utc::now();
+        let window_open = match self.release_deadline {
+            Some(deadline) => now <= deadline,
+            None => true,
+        };
+        let open = matches!(
+            self.status,
+            PurchaseStatus::Funded | PurchaseStatus::Shipped
+        );
+        if !open || !window_open {
+            return Err(PurchaseEscrowError::InvalidState {
+                current: self.status.to_string(),
+                expected: "Funded or Shipped within the confirmation window".into(),
+            });
+        }
+
+        let mut dispute = Dispute::create(
+            self.purchase_id.clone(),
+            self.buyer.clone(),
+            self.seller.clone(),
+            reason,
+        )
+        .with_category(DisputeCategory::Purchase);
+        if let Some(attestation) = &self.delivery_attestation {
+            dispute.submit_evidence(
+                &self.seller,
+                "Delivery attestation".into(),
+                attestation.clone(),
+            )?;
+        }
+
+        self.dispute_id = Some(dispute.id.clone());
+        self.status = PurchaseStatus::Disputed;
+        self.updated_at = now;
+        Ok(dispute)
+    }
+
+    /// Settles a disputed purchase according to the dispute's outcome.
+    ///
+    /// A ruling for the buyer (the initiator) refunds them; a ruling for
+    /// the seller releases the funds. If the buyer cancels the dispute,
+    /// the purchase returns to where it was — `Shipped` with the original
+    /// deadline, or `Funded` if nothing shipped yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PurchaseEscrowError::InvalidState`] if the purchase is not `Disputed`.
+    /// Returns [`PurchaseEscrowError::ForeignDispute`] if the dispute is for
+    /// another purchase.
+    /// Returns [`PurchaseEscrowError::DisputePending`] if the dispute is still open.
+    pub fn apply_dispute_outcome(&mut self, dispute: &Dispute) -> Result<(), PurchaseEscrowError> {
+        self.require_status(PurchaseStatus::Disputed)?;
+
+        if self.dispute_id.as_deref() != Some(dispute.id.as_str())
+            || dispute.escrow_id != self.purchase_id
+        {
+            return Err(PurchaseEscrowError::ForeignDispute(dispute.id.clone()));
+        }
+
+        self.status = match dispute.status {
+            DisputeStatus::ResolvedForInitiator => PurchaseStatus::Refunded,
+            DisputeStatus::ResolvedForRespondent => PurchaseStatus::Released,
+            DisputeStatus::Cancelled if self.delivery_attestation.is_some() => {
+                PurchaseStatus::Shipped
+            }
+            DisputeStatus::Cancelled => PurchaseStatus::Funded,
+            DisputeStatus::Open | DisputeStatus::UnderReview => {
+                return Err(PurchaseEscrowError::DisputePending(dispute.id.clone()));
+            }
+        };
+
+        self.dispute_id = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn require(&self, caller: &str, expected: &str) -> Result<(), PurchaseEscrowError> {
+        if caller != expected {
+            return Err(PurchaseEscrowError::Unauthorized {
+                party: caller.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn require_status(&self, expected: PurchaseStatus) -> Result<(), PurchaseEscrowError> {
+        if self.status != expected {
+            return Err(PurchaseEscrowError::InvalidState {
+                current: self.status.to_string(),
+                expected: expected.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispute_resolution::{ArbiterVote, Resolution};
+    use nova_protocol::crypto::NovaKeypair;
+
+    fn shipped_purchase() -> PurchaseEscrow {
+        let mut p = PurchaseEscrow::create("buyer_pk".into(), "seller_pk".into(), 250_000, 3600);
+        p.fund("buyer_pk", 250_000).unwrap();
+        p.mark_shipped("seller_pk", "attestation_hash".into())
+            .unwrap();
+        p
+    }
+
+    #[test]
+    fn new_purchase_awaits_funding() {
+        let p = PurchaseEscrow::create("buyer_pk".into(), "seller_pk".into(), 250_000, 3600);
+        assert_eq!(p.status, PurchaseStatus::AwaitingFunding);
+        assert!(p.release_deadline.is_none());
+    }
+
+    #[test]
+    fn partial_funding_rejected() {
+        let mut p = PurchaseEscrow::create("buyer_pk".into(), "seller_pk".into(), 250_000, 3600);
+        let result = p.fund("buyer_pk", 100_000);
+        assert!(matches!(
+            result,
+            Err(PurchaseEscrowError::AmountMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn only_seller_can_ship() {
+        let mut p = PurchaseEscrow::create("buyer_pk".into(), "seller_pk".into(), 250_000, 3600);
+        p.fund("buyer_pk", 250_000).unwrap();
+        let result = p.mark_shipped("buyer_pk", "hash".into());
+        assert!(matches!(
+            result,
+            Err(PurchaseEscrowError::Unauthorized { .. })
+        ));
+    }
+
+    #[test]
+    fn buyer_confirmation_releases_funds() {
+        let mut p = shipped_purchase();
+        p.confirm_delivery("buyer_pk").unwrap();
+        assert_eq!(p.status, PurchaseStatus::Released);
+    }
+
+    #[test]
+    fn auto_release_after_deadline() {
+        let mut p = shipped_purchase();
+        assert!(!p.check_auto_release());
+
+        let after = p.release_deadline.unwrap() + chrono::Duration::seconds(1);
+        assert!(p.check_auto_release_at(after));
+        assert_eq!(p.status, PurchaseStatus::Released);
+    }
+
+    #[test]
+    fn objection_escalates_with_attestation_evidence() {
+        let mut p = shipped_purchase();
+        let dispute = p.object("buyer_pk", "Item never arrived".into()).unwrap();
+
+        assert_eq!(p.status, PurchaseStatus::Disputed);
+        assert_eq!(dispute.escrow_id, p.purchase_id);
+        assert_eq!(dispute.initiator, "buyer_pk");
+        assert_eq!(dispute.category, DisputeCategory::Purchase);
+        assert_eq!(dispute.evidence.len(), 1);
+        assert_eq!(dispute.evidence[0].data_hash, "attestation_hash");
+
+        // A disputed purchase no longer auto-releases.
+        let after = p.release_deadline.unwrap() + chrono::Duration::seconds(1);
+        assert!(!p.check_auto_release_at(after));
+    }
+
+    #[test]
+    fn ruling_for_buyer_refunds() {
+        let mut p = shipped_purchase();
+        let mut dispute = p.object("buyer_pk", "Wrong item".into()).unwrap();
+
+        assert!(matches!(
+            p.apply_dispute_outcome(&dispute),
+            Err(PurchaseEscrowError::DisputePending(_))
+        ));
+
+        let vote = ArbiterVote::sign(
+            &NovaKeypair::generate(),
+            dispute.id.clone(),
+            Resolution::ForInitiator,
+            "The item delivered is not the one listed",
+            true,
+        );
+        dispute.cast_vote(vote).unwrap();
+        p.apply_dispute_outcome(&dispute).unwrap();
+        assert_eq!(p.status, PurchaseStatus::Refunded);
+    }
+
+    #[test]
+    fn cancelled_dispute_resumes_confirmation_window() {
+        let mut p = shipped_purchase();
+        let mut dispute = p.object("buyer_pk", "Late".into()).unwrap();
+        dispute.cancel("buyer_pk").unwrap();
+
+        p.apply_dispute_outcome(&dispute).unwrap();
+        assert_eq!(p.status, PurchaseStatus::Shipped);
+        assert!(p.dispute_id.is_none());
+    }
+}
@@ -5,7 +5,12 @@
 //! default detection, and dispute initiation.
 
 use chrono::{Duration, Utc};
-use nova_contracts::credit_escrow::{CreditEscrow, CreditTerms, EscrowStatus};
+use nova_contracts::credit_escrow::{
+    AmendmentKind, ConditionPredicate, CreditEscrow, CreditTerms, EscrowAmendment, EscrowStatus,
+    OracleAttestation, OracleRegistry, OracleValue, ReleaseCondition, ScheduledRelease,
+};
+use nova_contracts::dispute_resolution::{ArbiterVote, Resolution};
+use nova_protocol::crypto::NovaKeypair;
 
 /// Helper: creates standard credit terms with the given principal.
 fn terms(principal: u64, days_until_deadline: i64) -> CreditTerms {
@@ -217,6 +222,162 @@ fn cannot_double_dispute() {
     assert!(result.is_err());
 }
 
+// ---------------------------------------------------------------------------
+// Oracle Conditions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn flight_delay_oracle_releases_cover() {
+    let oracle = NovaKeypair::generate();
+    let mut registry = OracleRegistry::new();
+    registry
+        .register(
+            "flight-feed".into(),
+            oracle.public_key().to_hex(),
+            vec!["delay_minutes".into()],
+        )
+        .unwrap();
+
+    let mut escrow = CreditEscrow::create("insurer".into(), "traveller".into(), terms(500_000, 30));
+    escrow
+        .attach_release_condition(
+            &registry,
+            ReleaseCondition {
+                oracle_id: "flight-feed".into(),
+                feed: "delay_minutes".into(),
+                predicate: ConditionPredicate::AtLeast(180),
+                silence_timeout_secs: 86400,
+            },
+        )
+        .unwrap();
+    escrow.fund(500_000).unwrap();
+
+    let report = |minutes| {
+        OracleAttestation::sign(
+            &oracle,
+            "flight-feed".into(),
+            escrow.escrow_id.clone(),
+            "delay_minutes".into(),
+            OracleValue::Number(minutes),
+            Utc::now(),
+        )
+    };
+    let (early, late) = (report(45), report(240));
+
+    assert!(!escrow.submit_attestation(&registry, &early).unwrap());
+    assert!(escrow.submit_attestation(&registry, &late).unwrap());
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.released_amount, 500_000);
+}
+
+#[test]
+fn deregistered_oracle_ends_in_arbitration() {
+    let oracle = NovaKeypair::generate();
+    let mut registry = OracleRegistry::new();
+    registry
+        .register(
+            "courier".into(),
+            oracle.public_key().to_hex(),
+            vec!["scan".into()],
+        )
+        .unwrap();
+
+    let mut escrow = CreditEscrow::create("l".into(), "b".into(), terms(100_000, 30));
+    escrow
+        .attach_release_condition(
+            &registry,
+            ReleaseCondition {
+                oracle_id: "courier".into(),
+                feed: "scan".into(),
+                predicate: ConditionPredicate::Equals(OracleValue::Text("delivered".into())),
+                silence_timeout_secs: 60,
+            },
+        )
+        .unwrap();
+    escrow.fund(100_000).unwrap();
+    registry.deregister("courier");
+
+    let scan = OracleAttestation::sign(
+        &oracle,
+        "courier".into(),
+        escrow.escrow_id.clone(),
+        "scan".into(),
+        OracleValue::Text("delivered".into()),
+        Utc::now(),
+    );
+    assert!(escrow.submit_attestation(&registry, &scan).is_err());
+
+    // With no oracle left to report, the silence timeout hands the escrow
+    // to an arbiter, who rules for the borrower.
+    let later = escrow.oracle_heard_at.unwrap() + Duration::seconds(61);
+    let mut dispute = escrow.check_oracle_silence_at(later).unwrap();
+    let vote = ArbiterVote::sign(
+        &NovaKeypair::generate(),
+        dispute.id.clone(),
+        Resolution::ForInitiator,
+        "The courier's last scan shows the parcel delivered",
+        true,
+    );
+    dispute.cast_vote(vote).unwrap();
+    escrow.apply_dispute_outcome(&dispute).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.released_amount, 100_000);
+}
+
+// ---------------------------------------------------------------------------
+// Amendments
+// ---------------------------------------------------------------------------
+
+#[test]
+fn amended_escrow_lifecycle() {
+    let (lender, borrower) = (NovaKeypair::generate(), NovaKeypair::generate());
+    let mut escrow = CreditEscrow::create(
+        lender.public_key_hex(),
+        borrower.public_key_hex(),
+        terms(2_000_000, 90),
+    )
+    .with_installments(3);
+    escrow.fund(2_000_000).unwrap();
+
+    let sign = |mut amendment: EscrowAmendment| {
+        amendment.sign_as_lender(&lender);
+        amendment.sign_as_borrower(&borrower);
+        amendment
+    };
+
+    // Release half up front, half in a month.
+    escrow.release_to_borrower(1_000_000).unwrap();
+    let release_at = Utc::now() + Duration::days(30);
+    let scheduled = sign(EscrowAmendment::new(
+        &escrow,
+        AmendmentKind::ScheduledRelease(ScheduledRelease {
+            amount: 1_000_000,
+            release_at,
+        }),
+    ));
+    escrow.apply_amendment(&scheduled).unwrap();
+
+    // Then the lender tops up, which the borrower draws by hand.
+    let top_up = sign(EscrowAmendment::new(
+        &escrow,
+        AmendmentKind::TopUp { amount: 500_000 },
+    ));
+    escrow.apply_amendment(&top_up).unwrap();
+    assert_eq!(escrow.principal, 2_500_000);
+    assert!(escrow.terms.total_owed > 2_600_000);
+    escrow.release_to_borrower(500_000).unwrap();
+
+    assert_eq!(escrow.process_scheduled_releases_at(release_at), 1_000_000);
+    assert_eq!(escrow.released_amount, 2_500_000);
+
+    // Every installment still to come adds up to what is owed.
+    let owed = escrow.terms.total_owed;
+    let scheduled: u64 = escrow.repayment_schedule.iter().map(|i| i.amount).sum();
+    assert_eq!(scheduled, owed);
+    escrow.repay(owed).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Completed);
+}
+
 // ---------------------------------------------------------------------------
 // Serialization
 // ---------------------------------------------------------------------------
@@ -6,7 +6,11 @@
 
 use chrono::{Duration, Utc};
 use nova_contracts::credit_escrow::{CreditEscrow, CreditTerms, EscrowStatus};
-use nova_contracts::dispute_resolution::{Dispute, DisputeStatus, Resolution};
+use nova_contracts::dispute_resolution::{
+    ArbiterVote, Dispute, DisputeCategory, DisputeError, DisputeStatus, EvidenceKind,
+    PrecedentQuery, PrecedentRegistry, Resolution, TimelineQuery,
+};
+use nova_protocol::crypto::NovaKeypair;
 
 /// Helper: creates a funded and active escrow ready for dispute testing.
 fn active_escrow() -> CreditEscrow {
@@ -23,6 +27,18 @@ fn active_escrow() -> CreditEscrow {
     escrow
 }
 
+/// Helper: a single arbiter's vote on `d`, with a public rationale.
+fn rule(d: &mut Dispute, resolution: Resolution) -> Result<Option<Resolution>, DisputeError> {
+    let vote = ArbiterVote::sign(
+        &NovaKeypair::generate(),
+        d.id.clone(),
+        resolution,
+        "The submitted evidence decides it",
+        true,
+    );
+    d.cast_vote(vote)
+}
+
 // ---------------------------------------------------------------------------
 // Lifecycle Tests
 // ---------------------------------------------------------------------------
@@ -91,8 +107,7 @@ fn resolve_in_favor_of_initiator() {
 
     d.submit_evidence("lender_pk", "proof".into(), "hash".into())
         .unwrap();
-    d.resolve(Resolution::ForInitiator, "arbiter_signature_hex")
-        .unwrap();
+    rule(&mut d, Resolution::ForInitiator).unwrap();
 
     assert_eq!(d.status, DisputeStatus::ResolvedForInitiator);
     assert!(d.resolved_at.is_some());
@@ -107,7 +122,7 @@ fn resolve_in_favor_of_respondent() {
         "Funds withheld".into(),
     );
 
-    d.resolve(Resolution::ForRespondent, "arbiter_sig").unwrap();
+    rule(&mut d, Resolution::ForRespondent).unwrap();
     assert_eq!(d.status, DisputeStatus::ResolvedForRespondent);
 }
 
@@ -137,7 +152,7 @@ fn cannot_submit_evidence_after_resolution() {
         "Issue".into(),
     );
 
-    d.resolve(Resolution::ForInitiator, "sig").unwrap();
+    rule(&mut d, Resolution::ForInitiator).unwrap();
 
     let result = d.submit_evidence("lender_pk", "late evidence".into(), "hash".into());
     assert!(result.is_err());
@@ -152,8 +167,16 @@ fn cannot_resolve_with_empty_signature() {
         "Issue".into(),
     );
 
-    let result = d.resolve(Resolution::ForInitiator, "");
-    assert!(result.is_err());
+    let mut vote = ArbiterVote::sign(
+        &NovaKeypair::generate(),
+        d.id.clone(),
+        Resolution::ForInitiator,
+        "Delivery was never proven",
+        true,
+    );
+    vote.signature.clear();
+    let result = d.cast_vote(vote);
+    assert!(matches!(result, Err(DisputeError::InvalidArbiterSignature)));
 }
 
 #[test]
@@ -165,8 +188,8 @@ fn cannot_resolve_twice() {
         "Issue".into(),
     );
 
-    d.resolve(Resolution::ForInitiator, "sig").unwrap();
-    let result = d.resolve(Resolution::ForRespondent, "sig2");
+    rule(&mut d, Resolution::ForInitiator).unwrap();
+    let result = rule(&mut d, Resolution::ForRespondent);
     assert!(result.is_err());
 }
 
@@ -227,7 +250,7 @@ fn cannot_cancel_resolved_dispute() {
         "Issue".into(),
     );
 
-    d.resolve(Resolution::ForRespondent, "sig").unwrap();
+    rule(&mut d, Resolution::ForRespondent).unwrap();
     let result = d.cancel("lender_pk");
     assert!(result.is_err());
 }
@@ -281,3 +304,142 @@ fn serialization_roundtrip_dispute() {
     assert_eq!(d.status, restored.status);
     assert_eq!(d.evidence.len(), restored.evidence.len());
 }
+
+#[test]
+fn arbiter_reads_structured_timeline() {
+    let mut d = Dispute::create(
+        "escrow-7".into(),
+        "lender_pk".into(),
+        "borrower_pk".into(),
+        "Repayment never arrived".into(),
+    );
+
+    d.submit_typed_evidence(
+        "borrower_pk",
+        EvidenceKind::ChainTransaction,
+        "Repayment transfer".into(),
+        "0x".to_string() + &"7e".repeat(32),
+    )
+    .unwrap();
+    d.submit_typed_evidence(
+        "borrower_pk",
+        EvidenceKind::ReceiptHash,
+        "Receipt for the transfer".into(),
+        "5d".repeat(32),
+    )
+    .unwrap();
+    d.submit_typed_evidence(
+        "lender_pk",
+        EvidenceKind::OracleAttestation,
+        "Bank feed shows no deposit".into(),
+        "0a".repeat(32),
+    )
+    .unwrap();
+
+    // The lender cannot resubmit the borrower's transaction as their own.
+    let dup = d.submit_typed_evidence(
+        "lender_pk",
+        EvidenceKind::ChainTransaction,
+        "Their transfer".into(),
+        "7E".repeat(32),
+    );
+    assert!(matches!(
+        dup,
+        Err(DisputeError::DuplicateEvidence { sequence: 0, .. })
+    ));
+
+    let timeline = d.timeline(&TimelineQuery::default());
+    let kinds: Vec<EvidenceKind> = timeline.iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            EvidenceKind::ChainTransaction,
+            EvidenceKind::ReceiptHash,
+            EvidenceKind::OracleAttestation,
+        ]
+    );
+    assert!(timeline
+        .windows(2)
+        .all(|w| w[0].timestamp <= w[1].timestamp));
+
+    let borrower = d.timeline(&TimelineQuery::default().with_submitter("borrower_pk"));
+    assert_eq!(borrower.len(), 2);
+
+    // Kinds and positions survive serialization.
+    let json = serde_json::to_string(&d).unwrap();
+    let restored: Dispute = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.evidence[2].kind, EvidenceKind::OracleAttestation);
+    assert_eq!(restored.evidence[2].sequence, 2);
+}
+
+// ---------------------------------------------------------------------------
+// Rationale and Precedent
+// ---------------------------------------------------------------------------
+
+#[test]
+fn panel_ruling_becomes_searchable_precedent() {
+    let panel: Vec<NovaKeypair> = (0..3).map(|_| NovaKeypair::generate()).collect();
+    let mut d = Dispute::create(
+        "escrow-9".into(),
+        "lender_pk".into(),
+        "borrower_pk".into(),
+        "Second installment missed".into(),
+    )
+    .with_category(DisputeCategory::CreditTerms)
+    .with_arbiters(panel.iter().map(|k| k.public_key_hex()).collect());
+
+    let ballots = [
+        (
+            Resolution::ForInitiator,
+            "No transfer reached the lender",
+            true,
+        ),
+        (
+            Resolution::ForRespondent,
+            "The bank delayed the payment",
+            true,
+        ),
+        (
+            Resolution::ForInitiator,
+            "Late is late under these terms",
+            false,
+        ),
+    ];
+    let mut outcome = None;
+    for (keypair, (resolution, rationale, public)) in panel.iter().zip(ballots) {
+        let vote = ArbiterVote::sign(keypair, d.id.clone(), resolution, rationale, public);
+        outcome = d.cast_vote(vote).unwrap();
+    }
+    assert_eq!(outcome, Some(Resolution::ForInitiator));
+    assert_eq!(d.status, DisputeStatus::ResolvedForInitiator);
+
+    // Only the majority's reasons make the record; the private one is
+    // kept as its hash.
+    let record = d.record.clone().unwrap();
+    assert_eq!(record.dissents, 1);
+    assert_eq!(record.rationales.len(), 2);
+    assert_eq!(
+        record.rationales[0].text.as_deref(),
+        Some("No transfer reached the lender")
+    );
+    assert!(record.rationales[1].text.is_none());
+
+    let mut registry = PrecedentRegistry::new();
+    registry.record(&d).unwrap();
+    let found = registry.search(
+        &PrecedentQuery::default()
+            .with_category(DisputeCategory::CreditTerms)
+            .with_text("transfer"),
+    );
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].escrow_id, "escrow-9");
+    assert!(registry
+        .search(&PrecedentQuery::default().with_category(DisputeCategory::Purchase))
+        .is_empty());
+
+    // The record survives serialization with the dispute.
+    let json = serde_json::to_string(&d).unwrap();
+    let restored: Dispute = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.record, Some(record));
+    assert_eq!(restored.votes.len(), 3);
+}
@@ -0,0 +1,97 @@
+//! Embeds build provenance into `nova-node`: the git commit, the SHA-256 of
+//! the workspace `Cargo.lock`, the compiler version, the cargo profile and
+//! the source timestamp. Read back by `src/provenance.rs`.
+//!
+//! Nothing here reads the wall clock, so two builds of the same commit with
+//! the same toolchain embed the same values. `BUILD_TIMESTAMP` comes from
+//! `SOURCE_DATE_EPOCH` (the commit time under `make release-reproducible`)
+//! and is omitted when that is unset.
+
+use std::path::Path;
+use std::process::Command;
+
+use sha2::{Digest, Sha256};
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    let workspace = Path::new(&manifest_dir)
+        .parent()
+        .expect("node/ lives inside the workspace");
+    let lockfile = workspace.join("Cargo.lock");
+
+    println!("cargo:rerun-if-changed={}", lockfile.display());
+    println!(
+        "cargo:rerun-if-changed={}",
+        workspace.join(".git/HEAD").display()
+    );
+    println!(
+        "cargo:rerun-if-changed={}",
+        workspace.join(".git/index").display()
+    );
+    for var in ["GIT_COMMIT", "SOURCE_DATE_EPOCH", "RUSTC"] {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+
+    if let Ok(bytes) = std::fs::read(&lockfile) {
+        println!(
+            "cargo:rustc-env=CARGO_LOCK_SHA256={}",
+            hex::encode(Sha256::digest(&bytes))
+        );
+    }
+
+    // CI may export GIT_COMMIT for builds from a source tarball.
+    let commit = std::env::var("GIT_COMMIT").ok().or_else(|| {
+        let head = git(workspace, &["rev-parse", "HEAD"])?;
+        let dirty = git(
+            workspace,
+            &["status", "--porcelain", "--untracked-files=no"],
+        )
+        .is_some_and(|s| !s.is_empty());
+        Some(if dirty {
+            format!("{}-dirty", head)
+        } else {
+            head
+        })
+    });
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=GIT_COMMIT={}", commit);
+    }
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    if let Some(version) = run(Command::new(rustc).arg("--version")) {
+        println!("cargo:rustc-env=RUSTC_VERSION={}", version);
+    }
+
+    // `PROFILE` only says debug or release; custom profiles such as
+    // `reproducible` show up as the directory OUT_DIR sits under
+    // (`target/[<triple>/]<profile>/build/<pkg>/out`).
+    let profile = std::env::var("OUT_DIR").ok().and_then(|out| {
+        Path::new(&out)
+            .ancestors()
+            .nth(3)?
+            .file_name()?
+            .to_str()
+            .map(str::to_string)
+    });
+    if let Some(profile) = profile.or_else(|| std::env::var("PROFILE").ok()) {
+        println!("cargo:rustc-env=BUILD_PROFILE={}", profile);
+    }
+
+    if let Some(secs) = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse::<i64>().ok())
+    {
+        if let Some(ts) = chrono::DateTime::from_timestamp(secs, 0) {
+            println!("cargo:rustc-env=BUILD_TIMESTAMP={}", ts.to_rfc3339());
+        }
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    run(Command::new("git").arg("-C").arg(dir).args(args))
+}
+
+fn run(cmd: &mut Command) -> Option<String> {
+    let out = cmd.output().ok().filter(|o| o.status.success())?;
+    Some(String::from_utf8(out.stdout).ok()?.trim().to_string())
+}
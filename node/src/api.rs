@@ -9,33 +9,191 @@
 //! |--------|------------------------|-------------------------------------|
 //! | GET    | `/health`              | Liveness probe                      |
 //! | GET    | `/status`              | Node status summary                 |
+//! | GET    | `/node`                | Build provenance, NAT reachability  |
 //! | POST   | `/rpc`                 | JSON-RPC 2.0 gateway                |
 //! | GET    | `/ws`                  | WebSocket for live block/tx updates |
-//! | GET    | `/validators`          | Current validator set                |
+//! | GET    | `/ws/binary`           | CBOR-framed RPC and events          |
+//! | GET    | `/validators`          | Validator set with liveness/jailing  |
+//! | GET    | `/treasury`            | Treasury balance and paid spends    |
+//! | GET    | `/supply`              | Circulating, locked and staked NOVA |
 //! | GET    | `/blocks/:height`      | Block by height                     |
 //! | GET    | `/transactions/:hash`  | Transaction by hash                 |
+//! | GET    | `/receipts/:hash`      | Receipt of a committed transaction  |
 //! | GET    | `/accounts/:address`   | Account state                       |
+//! | GET    | `/mempool`             | Pending transactions by fee, paged  |
+//! | GET    | `/mempool/:hash`       | One pending transaction with its age |
+//! | GET    | `/peers`               | Connected peers' gossipsub scores   |
+//! | GET    | `/admin/peers`         | Persisted peer store                |
+//! | POST   | `/admin/peers`         | Add or curate a peer                |
+//! | DELETE | `/admin/peers/:id`     | Forget a peer                       |
+//! | GET    | `/admin/audit`         | Audit log export                    |
+//! | GET    | `/halt`                | Emergency halt status               |
+//! | POST   | `/admin/halt`          | Submit a signed emergency halt      |
+//! | POST   | `/admin/resume`        | Submit a signed resume              |
+//! | GET    | `/ntp/peer`            | Peer ID for direct NTP messages     |
+//! | POST   | `/ntp/messages`        | Send an NTP message to a peer       |
+//! | GET    | `/ntp/messages/:box`   | Drain a mailbox                     |
+//! | POST   | `/dev/mine`            | Produce blocks now (`--dev` only)   |
+//!
+//! ## Encoding
+//!
+//! Response bodies follow the protocol's wire encoding policy
+//! ([`nova_protocol::encoding`]): hashes and keys are `0x`-prefixed hex and
+//! amounts above 2^53 − 1 are decimal strings. JSON-RPC responses and
+//! WebSocket events carry a `schema_version` field. Path parameters accept
+//! hashes with or without the `0x` prefix.
+//!
+//! `/ws/binary` serves the JSON-RPC methods and the `/ws` events over one
+//! WebSocket in CBOR frames, for clients that poll at high frequency; see
+//! [`crate::binary_rpc`] for the subprotocol and frame layout.
+//!
+//! ## Submitting Transactions
+//!
+//! `nova_sendTransaction` takes one signed transaction and returns its ID.
+//! Rejections use error code -32003 with `data.reason` set to a stable
+//! machine-readable kind (`invalid_transaction`, `stale_nonce`,
+//! `duplicate`, `fee_too_low`, `sender_limit`, `mempool_full`,
+//! `oversized`, `invalid_address`, `screened`) so clients can react
+//! without parsing the message. `screened` rejections also carry the
+//! refused `address`.
+//!
+//! `nova_cancelTransaction` takes `[{tx_id, signature}]`: the sender's
+//! signature over the pending transaction's ID
+//! ([`nova_protocol::transaction::TransactionCancellation`]). The node
+//! checks it against the transaction's sender key, drops the transaction
+//! from its mempool and announces a `transaction_cancelled` event, which
+//! the P2P layer relays to peers as `CancelTransaction` gossip. A
+//! transaction that is not pending answers -32001; a signature that does
+//! not verify is rejected with -32003 and `data.reason`
+//! `invalid_cancellation`.
+//!
+//! ## Supply
+//!
+//! `nova_getSupply` reports the circulating supply (genesis supply plus
+//! block rewards minus fee burns) and, for an optional `[height]`, the
+//! supply the genesis monetary policy projects there. It also reports the
+//! balances allocated at genesis, the funds locked by authorization holds
+//! and the validators' stake. `GET /supply` serves the same figures.
+//!
+//! ## Nonces
+//!
+//! `nova_getNonce` answers `[address]` with the nonce the address's next
+//! transaction must carry: one past the committed nonce, and past any
+//! consecutive nonces already waiting in the mempool. Blocks execute a
+//! sender's transactions only in strict nonce order, so a replayed or
+//! skipped nonce never applies.
+//!
+//! ## Capabilities
+//!
+//! `nova_getCapabilities` answers with the protocol version and the
+//! features this node supports
+//! ([`nova_protocol::network::capabilities`]): those built into the
+//! protocol crate, `binary_rpc` (see [`crate::binary_rpc`]), and those its
+//! flags switch on (`ntp_messaging`, `quic`, `dev_rpc`). The P2P layer advertises the same list in its identify
+//! agent string. Clients should treat an unlisted feature — or a node that
+//! answers -32601 — as unsupported.
+//!
+//! ## Receipts
+//!
+//! The block producer stores a receipt for every transaction it commits:
+//! the block it landed in, its status, the fee charged and the balance
+//! changes it made (`logs`), sealed by a BLAKE3 `receipt_hash`
+//! ([`nova_protocol::transaction::receipt`]). `nova_getTransactionReceipt`
+//! answers `[hash]` with it, and `GET /receipts/:hash` serves the same.
+//! Pending and unknown transactions have no receipt (-32001, or 404).
+//!
+//! ## Signed Messages
+//!
+//! `nova_verifyMessage` checks a "sign in with NOVA" message
+//! ([`nova_protocol::identity::message`]) and answers `{valid, address}`,
+//! plus a `reason` when invalid. It is stateless: services must still check
+//! the domain, nonce and expiry inside the message.
+//!
+//! ## NTP Messaging
+//!
+//! With `--ntp-messaging` the node relays NTP handshake, proof and receipt
+//! messages ([`nova_protocol::network::direct`]) so wallets need no
+//! rendezvous server. A payee's wallet reads its node's peer ID from
+//! `GET /ntp/peer` and hands it, with a mailbox name, to the payer; the
+//! payer's wallet posts to its own node, which forwards over
+//! `/nova/ntp/1.0`; the payee's wallet drains the mailbox. Anyone who knows
+//! a mailbox name can drain it, so wallets pick unguessable names. The
+//! routes are absent without the flag.
+//!
+//! ## Audit Trail
+//!
+//! Every successful change made through `/admin/*` is appended to the
+//! node's audit log ([`nova_protocol::storage::audit`]) with the caller's
+//! address as the actor. `GET /admin/audit` returns the whole log with its
+//! head, in the format `nova-node audit verify --file` checks.
+//!
+//! ## Emergency Halts
+//!
+//! `POST /admin/halt` and `POST /admin/resume` take the JSON records
+//! `nova-node halt sign` and `halt sign-resume` build
+//! ([`nova_protocol::network::halt`]). The node checks the signatures
+//! against the committed validator set and, once accepted, stops (or
+//! restarts) block production at the named height. Rejections are 400 for
+//! a bad record and 409 when it does not fit the current halt state.
+//!
+//! ## Dev Controls
+//!
+//! `--dev` nodes also answer the `nova_dev*` methods and `/dev/*` routes
+//! described in [`crate::dev`]. Other nodes do not route them at all.
 
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        Path, State,
+        ConnectInfo, Path, Query, State,
     },
     http::{Method, StatusCode},
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::broadcast;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
-use nova_protocol::storage::db::NovaDB;
-use nova_protocol::storage::state::StateTree;
+use nova_protocol::crypto::wire::BlockHash;
+use nova_protocol::encoding::{self, Versioned};
+use nova_protocol::identity::{AddressPolicy, SignedMessage};
+use nova_protocol::network::capabilities::Capabilities;
+use nova_protocol::network::consensus::ValidatorSet;
+use nova_protocol::network::direct::{DirectError, DirectMessage, DirectService};
+use nova_protocol::network::gossip::PeerTransport;
+use nova_protocol::network::halt::{EmergencyHalt, HaltError, HaltGuard, ResumeAction};
+use nova_protocol::network::liveness::{LivenessPolicy, LivenessRecord};
+use nova_protocol::network::mempool::{
+    AdmissionRejection, Eviction, Mempool, MempoolEntry, MempoolError, ReorgOutcome,
+    ReorgedTransaction,
+};
+use nova_protocol::network::network_keys::NetworkKeyRegistry;
+use nova_protocol::network::peer_store::{PeerRecord, PeerStore};
+use nova_protocol::network::rpc::{
+    FeeEstimateResponse, NonceResponse, PendingTransaction, PendingTransactionsResponse,
+    SupplyResponse,
+};
+use nova_protocol::network::scoring::PeerScoreBoard;
+use nova_protocol::network::treasury::{TreasuryLedger, TreasurySpend};
+use nova_protocol::storage::audit::{AuditCategory, AuditLog};
+use nova_protocol::storage::block::Block;
+use nova_protocol::storage::db::{DbError, NovaDB};
+use nova_protocol::storage::emission::{MonetaryPolicy, SupplyRecord};
+use nova_protocol::storage::snapshot::CommittedState;
+use nova_protocol::storage::state::TREASURY_STATE_KEY;
+use nova_protocol::transaction::verification::verify_transaction_with_policy;
+use nova_protocol::transaction::{
+    FeeEstimator, FeePriority, Transaction, TransactionCancellation, TransactionReceipt,
+    TransactionStatus,
+};
 
+use crate::dev::{DevControls, DevError};
 use crate::metrics::SharedMetrics;
+use crate::update::{UpdateChecker, UpdateReport};
 
 // ---------------------------------------------------------------------------
 // Application State
@@ -61,8 +219,39 @@ pub struct AppState {
     pub metrics: SharedMetrics,
     /// Persistent storage engine for blocks, transactions, and accounts.
     pub db: Arc<NovaDB>,
-    /// Sparse Merkle Tree for account state lookups and proofs.
-    pub state_tree: Arc<RwLock<StateTree>>,
+    /// State as of the last committed block; all account reads go here.
+    /// The live tree belongs to the producer and is never locked by the API.
+    pub committed_state: Arc<CommittedState>,
+    /// Pending transactions, consulted for `"pending"` status lookups.
+    pub mempool: Arc<Mempool>,
+    /// Fee history of recent blocks, fed by the producer, for
+    /// `nova_estimateFee`.
+    pub fee_estimator: Arc<FeeEstimator>,
+    /// Known peers, persisted across restarts and curated via `/admin/peers`.
+    pub peer_store: Arc<PeerStore>,
+    /// Effective gossipsub scores of connected peers, refreshed by the swarm
+    /// loop on every heartbeat.
+    pub peer_scores: Arc<PeerScoreBoard>,
+    /// Tamper-evident record of privileged operations.
+    pub audit: Arc<AuditLog>,
+    /// Accepted emergency halts, shared with the consensus engine.
+    pub halt: Arc<HaltGuard>,
+    /// Emission schedule from the genesis config, for `nova_getSupply`.
+    pub monetary_policy: MonetaryPolicy,
+    /// How address path parameters are validated. Strict unless the node
+    /// runs a dev chain.
+    pub address_policy: AddressPolicy,
+    /// What this build and configuration support, for
+    /// `nova_getCapabilities`.
+    pub capabilities: Capabilities,
+    /// Direct NTP messaging (`/ntp/*`). `Some` only with `--ntp-messaging`.
+    pub direct: Option<Arc<DirectService>>,
+    /// Release manifest polling, reported in `/status`. `Some` only with
+    /// `--update-check-url`.
+    pub updates: Option<Arc<UpdateChecker>>,
+    /// Test-environment controls (`nova_dev*`, `/dev/*`). `Some` only in
+    /// `--dev` mode.
+    pub dev: Option<Arc<DevControls>>,
 }
 
 /// Events pushed to WebSocket subscribers.
@@ -73,20 +262,132 @@ pub enum NodeEvent {
     #[serde(rename = "new_block")]
     NewBlock {
         height: u64,
+        #[serde(with = "encoding::hex_string")]
         hash: String,
         tx_count: u64,
         timestamp: u64,
     },
-    /// A new transaction entered the mempool.
+    /// A new transaction entered the mempool. `pending` is always `true`:
+    /// the transaction is admitted, not yet in a block.
     #[serde(rename = "new_transaction")]
     NewTransaction {
+        #[serde(with = "encoding::hex_string")]
         hash: String,
         sender: String,
         recipient: String,
+        #[serde(with = "encoding::amount")]
+        amount: u64,
+        #[serde(with = "encoding::amount")]
+        fee: u64,
+        pending: bool,
+    },
+    /// A pending transaction was withdrawn by its sender. `signature` is
+    /// the cancellation signature, so the event can be relayed as is.
+    #[serde(rename = "transaction_cancelled")]
+    TransactionCancelled {
+        #[serde(with = "encoding::hex_string")]
+        hash: String,
+        sender: String,
+        #[serde(with = "encoding::hex_string")]
+        signature: String,
+    },
+    /// A transaction's block was rolled back. `status` is `"pending"` if it
+    /// is back in the mempool, `"confirmed"` if the new branch includes it,
+    /// and `"failed"` if it no longer validates (see `reason`).
+    #[serde(rename = "transaction_reorged")]
+    TransactionReorged {
+        #[serde(with = "encoding::hex_string")]
+        hash: String,
+        from_height: u64,
+        status: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// The mempool dropped a pending transaction; it will not confirm
+    /// unless resubmitted. `reason` is `"expired"`, `"ttl"`, `"outbid"` or
+    /// `"replaced"`.
+    #[serde(rename = "transaction_evicted")]
+    TransactionEvicted {
+        #[serde(with = "encoding::hex_string")]
+        hash: String,
+        sender: String,
+        reason: String,
+    },
+    /// An outflow from a watched address tripped a velocity rule (see
+    /// [`crate::velocity`]). `kind` is `"velocity"`, `"new_counterparty"`
+    /// or `"unusual_hour"`.
+    #[serde(rename = "anomaly_detected")]
+    AnomalyDetected {
+        address: String,
+        kind: String,
+        #[serde(with = "encoding::hex_string")]
+        hash: String,
+        counterparty: String,
+        #[serde(with = "encoding::amount")]
         amount: u64,
+        block_height: u64,
+        timestamp: u64,
+        detail: String,
     },
 }
 
+/// Query parameters for `GET /ws` and `GET /ws/binary`, narrowing the
+/// `new_transaction` events a subscriber receives. Unset fields match
+/// everything; other event types are never filtered.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventFilter {
+    /// Only transactions sent by this address.
+    pub sender: Option<String>,
+    /// Only transactions paying this address.
+    pub recipient: Option<String>,
+    /// Only transactions moving at least this many photons.
+    pub min_amount: Option<u64>,
+}
+
+impl EventFilter {
+    /// Returns `true` if `event` should be delivered.
+    pub fn matches(&self, event: &NodeEvent) -> bool {
+        let NodeEvent::NewTransaction {
+            sender,
+            recipient,
+            amount,
+            ..
+        } = event
+        else {
+            return true;
+        };
+        !(self.sender.as_ref().is_some_and(|s| s != sender)
+            || self.recipient.as_ref().is_some_and(|r| r != recipient)
+            || self.min_amount.is_some_and(|min| *amount < min))
+    }
+}
+
+impl From<&ReorgedTransaction> for NodeEvent {
+    fn from(reorged: &ReorgedTransaction) -> Self {
+        let (status, reason) = match &reorged.outcome {
+            ReorgOutcome::Readmitted => (TransactionStatus::Pending, None),
+            ReorgOutcome::AlreadyIncluded => (TransactionStatus::Confirmed, None),
+            ReorgOutcome::Dropped(reason) => (TransactionStatus::Failed, Some(reason.clone())),
+        };
+        NodeEvent::TransactionReorged {
+            hash: reorged.tx_id.clone(),
+            from_height: reorged.from_height,
+            status: status_label(&status).into(),
+            reason,
+        }
+    }
+}
+
+impl From<&Eviction> for NodeEvent {
+    fn from(eviction: &Eviction) -> Self {
+        NodeEvent::TransactionEvicted {
+            hash: eviction.tx_id.clone(),
+            sender: eviction.sender.clone(),
+            reason: eviction.reason.as_str().into(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Router Construction
 // ---------------------------------------------------------------------------
@@ -97,18 +398,46 @@ pub enum NodeEvent {
 pub fn create_router(state: AppState) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
         .allow_headers(Any);
 
-    Router::new()
+    let mut router = Router::new()
         .route("/health", get(health_handler))
         .route("/status", get(status_handler))
+        .route("/node", get(node_handler))
         .route("/rpc", post(rpc_handler))
         .route("/ws", get(ws_handler))
+        .route("/ws/binary", get(crate::binary_rpc::ws_handler))
         .route("/validators", get(validators_handler))
+        .route("/treasury", get(treasury_handler))
+        .route("/supply", get(supply_handler))
         .route("/blocks/:height", get(block_by_height_handler))
         .route("/transactions/:hash", get(transaction_by_hash_handler))
+        .route("/receipts/:hash", get(receipt_handler))
         .route("/accounts/:address", get(account_handler))
+        .route("/mempool", get(mempool_handler))
+        .route("/mempool/:hash", get(mempool_entry_handler))
+        .route("/peers", get(peer_scores_handler))
+        .route(
+            "/admin/peers",
+            get(list_peers_handler).post(upsert_peer_handler),
+        )
+        .route("/admin/peers/:peer_id", delete(remove_peer_handler))
+        .route("/admin/audit", get(audit_handler))
+        .route("/halt", get(halt_status_handler))
+        .route("/admin/halt", post(halt_handler))
+        .route("/admin/resume", post(resume_handler));
+    if state.direct.is_some() {
+        router = router
+            .route("/ntp/peer", get(ntp_peer_handler))
+            .route("/ntp/messages", post(ntp_send_handler))
+            .route("/ntp/messages/:mailbox", get(ntp_mailbox_handler));
+    }
+    if state.dev.is_some() {
+        router = router.route("/dev/mine", post(dev_mine_handler));
+    }
+
+    router
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
@@ -144,6 +473,9 @@ pub struct JsonRpcResponse {
     pub error: Option<JsonRpcError>,
     /// Request identifier, echoed from the request.
     pub id: serde_json::Value,
+    /// Wire schema version of `result`.
+    #[serde(default)]
+    pub schema_version: u16,
 }
 
 /// A JSON-RPC 2.0 error object.
@@ -177,19 +509,55 @@ pub struct StatusResponse {
     pub synced: bool,
     /// ISO-8601 timestamp of the response.
     pub timestamp: String,
+    /// Latest update check, with `--update-check-url`. `status` is
+    /// `up_to_date`, `available` or `required`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update: Option<UpdateReport>,
 }
 
 /// Response payload for `GET /validators`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidatorInfo {
     /// Hex-encoded public key.
+    #[serde(with = "encoding::hex_string")]
     pub public_key: String,
     /// Validator stake in photons.
+    #[serde(with = "encoding::amount")]
     pub stake: u64,
     /// Whether this validator is in the active set.
     pub active: bool,
-    /// Last block height this validator proposed.
+    /// Last block height this validator proposed. Not tracked yet; always 0.
     pub last_proposed_block: u64,
+    /// Whether the validator is jailed for missed duties.
+    pub jailed: bool,
+    /// First height an unjail transaction is accepted at, while jailed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unjail_height: Option<u64>,
+    /// Proposal slots missed in the current liveness window.
+    pub missed_proposals: u32,
+    /// Votes missed in the current liveness window.
+    pub missed_votes: u32,
+    /// Hex-encoded network key the validator registered, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_key: Option<String>,
+    /// libp2p peer ID derived from `network_key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+}
+
+/// Response payload for `GET /treasury`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreasuryResponse {
+    /// State key of the treasury account.
+    pub account: String,
+    /// Treasury balance in photons.
+    #[serde(with = "encoding::amount")]
+    pub balance: u64,
+    /// Photons paid out over the treasury's lifetime.
+    #[serde(with = "encoding::amount")]
+    pub total_spent: u64,
+    /// Paid spend proposals, oldest first.
+    pub spends: Vec<TreasurySpend>,
 }
 
 /// Response payload for `GET /blocks/:height`.
@@ -197,30 +565,67 @@ pub struct ValidatorInfo {
 pub struct BlockResponse {
     /// Block height.
     pub height: u64,
-    /// Hex-encoded block hash.
-    pub hash: String,
-    /// Hex-encoded parent block hash.
-    pub parent_hash: String,
+    /// Block hash, hex-encoded on the wire.
+    pub hash: BlockHash,
+    /// Parent block hash, hex-encoded on the wire.
+    pub parent_hash: BlockHash,
     /// Hex-encoded proposer public key.
     pub proposer: String,
     /// Number of transactions in the block.
     pub tx_count: u64,
     /// Unix timestamp (milliseconds).
     pub timestamp: u64,
+    /// Total fees charged by the block's transactions, in photons.
+    #[serde(default, with = "encoding::amount")]
+    pub fees: u64,
+    /// Hex-encoded IDs of the block's transactions, in execution order.
+    #[serde(default, with = "encoding::hex_string_vec")]
+    pub transactions: Vec<String>,
+}
+
+impl From<&Block> for BlockResponse {
+    fn from(block: &Block) -> Self {
+        Self {
+            height: block.header.height,
+            hash: block.header.hash,
+            parent_hash: block.header.parent_hash,
+            proposer: block.header.validator.clone(),
+            tx_count: block.transactions.len() as u64,
+            timestamp: block.header.timestamp,
+            fees: block.header.fees,
+            transactions: block.transactions.iter().map(|tx| tx.id.clone()).collect(),
+        }
+    }
+}
+
+/// Query parameters for `GET /mempool` and `nova_pendingTransactions`.
+#[derive(Debug, Default, Deserialize)]
+pub struct MempoolQuery {
+    /// Number of transactions to skip, for paging (default 0).
+    pub offset: Option<usize>,
+    /// Maximum number of transactions to list (default 50, capped at
+    /// [`MAX_MEMPOOL_LISTING`]).
+    pub limit: Option<usize>,
 }
 
+/// Upper bound on the transactions `GET /mempool` lists in one page.
+pub const MAX_MEMPOOL_LISTING: usize = 500;
+
 /// Response payload for `GET /transactions/:hash`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionResponse {
     /// Hex-encoded transaction ID.
+    #[serde(with = "encoding::hex_string")]
     pub hash: String,
     /// Sender address.
     pub sender: String,
     /// Recipient address.
     pub recipient: String,
     /// Transfer amount in photons.
+    #[serde(with = "encoding::amount")]
     pub amount: u64,
     /// Fee paid in photons.
+    #[serde(with = "encoding::amount")]
     pub fee: u64,
     /// Block height (if confirmed).
     pub block_height: Option<u64>,
@@ -228,6 +633,8 @@ pub struct TransactionResponse {
     pub status: String,
     /// Unix timestamp (milliseconds).
     pub timestamp: u64,
+    /// Sender-supplied reference, such as an order number.
+    pub memo: Option<String>,
 }
 
 /// Response payload for `GET /accounts/:address`.
@@ -236,11 +643,60 @@ pub struct AccountResponse {
     /// Hex-encoded account address.
     pub address: String,
     /// Available balance in photons.
+    #[serde(with = "encoding::amount")]
     pub balance: u64,
     /// Current nonce.
     pub nonce: u64,
     /// Number of transactions sent from this account.
     pub tx_count: u64,
+    /// Height of the committed block this state was read at.
+    pub height: u64,
+}
+
+/// Request body for `POST /dev/mine`.
+#[derive(Debug, Default, Deserialize)]
+pub struct DevMineRequest {
+    /// Number of blocks to produce (default 1).
+    pub blocks: Option<u64>,
+}
+
+/// Response payload for `POST /dev/mine`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DevMineResponse {
+    /// Hashes of the produced blocks, oldest first.
+    pub hashes: Vec<BlockHash>,
+    /// Chain height after mining.
+    pub height: u64,
+}
+
+/// Request body for `POST /ntp/messages`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NtpSendRequest {
+    /// Peer ID of the recipient's node.
+    pub peer_id: String,
+    /// Multiaddr to dial the recipient's node at, if this node may not
+    /// know it yet.
+    #[serde(default)]
+    pub address: Option<String>,
+    /// The message, addressed to a mailbox on that node.
+    pub message: DirectMessage,
+}
+
+/// Request body for `POST /admin/peers`.
+///
+/// Unset flags leave the stored value untouched.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeerCurationRequest {
+    /// libp2p peer ID.
+    pub peer_id: String,
+    /// Multiaddr to record for the peer.
+    pub address: String,
+    /// Pin (always reconnect) or unpin the peer.
+    #[serde(default)]
+    pub pinned: Option<bool>,
+    /// Ban or unban the peer.
+    #[serde(default)]
+    pub banned: Option<bool>,
 }
 
 /// Generic error body returned by REST endpoints on failure.
@@ -283,10 +739,24 @@ async fn status_handler(State(state): State<AppState>) -> impl IntoResponse {
         peer_count: peers,
         synced: peers >= nova_protocol::config::MIN_PEERS_FOR_CONSENSUS as u64,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        update: state.updates.as_ref().and_then(|u| u.report()),
     };
     Json(resp)
 }
 
+/// `GET /node` — build provenance: commit, `Cargo.lock` hash, compiler,
+/// profile and the SHA-256 of the running binary. Explorers compare these
+/// across validators; operators check them with `version --verify`. With
+/// P2P running, `nat` reports whether peers can reach the node directly or
+/// only through a relay.
+async fn node_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "network": state.network,
+        "build": crate::provenance::BuildInfo::current(),
+        "nat": state.direct.as_ref().map(|direct| direct.nat().report()),
+    }))
+}
+
 /// `POST /rpc` — JSON-RPC 2.0 gateway.
 ///
 /// Routes method calls to internal handlers. Unknown methods return
@@ -295,8 +765,14 @@ async fn rpc_handler(
     State(state): State<AppState>,
     Json(req): Json<JsonRpcRequest>,
 ) -> impl IntoResponse {
+    Json(dispatch_rpc(&state, req).await)
+}
+
+/// Answers one JSON-RPC request. Shared by `POST /rpc` and the binary
+/// WebSocket transport ([`crate::binary_rpc`]).
+pub(crate) async fn dispatch_rpc(state: &AppState, req: JsonRpcRequest) -> JsonRpcResponse {
     if req.jsonrpc != "2.0" {
-        return Json(JsonRpcResponse {
+        return JsonRpcResponse {
             jsonrpc: "2.0".into(),
             result: None,
             error: Some(JsonRpcError {
@@ -305,7 +781,8 @@ async fn rpc_handler(
                 data: None,
             }),
             id: req.id,
-        });
+            schema_version: encoding::SCHEMA_VERSION,
+        };
     }
 
     let (result, error) = match req.method.as_str() {
@@ -336,14 +813,7 @@ async fn rpc_handler(
             match height {
                 Some(h) => match state.db.get_block(h) {
                     Ok(Some(block)) => {
-                        let resp = BlockResponse {
-                            height: block.header.height,
-                            hash: block.header.hash_hex(),
-                            parent_hash: block.header.parent_hash_hex(),
-                            proposer: block.header.validator.clone(),
-                            tx_count: block.transactions.len() as u64,
-                            timestamp: block.header.timestamp,
-                        };
+                        let resp = BlockResponse::from(&block);
                         (Some(serde_json::to_value(resp).unwrap()), None)
                     }
                     Ok(None) => (
@@ -380,23 +850,11 @@ async fn rpc_handler(
                 .and_then(|p| p.as_array())
                 .and_then(|arr| arr.first())
                 .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+                .map(|s| encoding::strip_hex_prefix(s).to_string());
 
             match hash {
-                Some(h) => match state.db.get_transaction(&h) {
-                    Ok(Some(tx)) => {
-                        let resp = TransactionResponse {
-                            hash: tx.id.clone(),
-                            sender: tx.sender.clone(),
-                            recipient: tx.receiver.clone(),
-                            amount: tx.amount.value,
-                            fee: tx.fee,
-                            block_height: None, // Would require reverse index
-                            status: "confirmed".into(),
-                            timestamp: tx.timestamp,
-                        };
-                        (Some(serde_json::to_value(resp).unwrap()), None)
-                    }
+                Some(h) => match find_transaction(state, &h) {
+                    Ok(Some(resp)) => (Some(serde_json::to_value(resp).unwrap()), None),
                     Ok(None) => (
                         None,
                         Some(JsonRpcError {
@@ -424,6 +882,51 @@ async fn rpc_handler(
                 ),
             }
         }
+        "nova_getTransactionReceipt" => match transaction_receipt(state, req.params.as_ref()) {
+            Ok(resp) => (Some(serde_json::to_value(resp).unwrap()), None),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_sendTransaction" => match submit_transaction(state, req.params.as_ref()) {
+            Ok(id) => (Some(serde_json::json!(id)), None),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_cancelTransaction" => match cancel_transaction(state, req.params.as_ref()) {
+            Ok(id) => (Some(serde_json::json!(id)), None),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_getSupply" => match supply(state, req.params.as_ref()) {
+            Ok(resp) => (Some(serde_json::to_value(resp).unwrap()), None),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_getNonce" => match next_nonce(state, req.params.as_ref()) {
+            Ok(resp) => (Some(serde_json::to_value(resp).unwrap()), None),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_getCapabilities" => (
+            Some(serde_json::to_value(&state.capabilities).unwrap()),
+            None,
+        ),
+        "nova_estimateFee" => match estimate_fee(state, req.params.as_ref()) {
+            Ok(resp) => (Some(serde_json::to_value(resp).unwrap()), None),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_pendingTransactions" => match mempool_query(req.params.as_ref()) {
+            Ok(query) => (
+                Some(serde_json::to_value(pending_transactions(&state.mempool, &query)).unwrap()),
+                None,
+            ),
+            Err(e) => (None, Some(e)),
+        },
+        "nova_verifyMessage" => match verify_message_params(req.params.as_ref()) {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(e)),
+        },
+        method if method.starts_with("nova_dev") && state.dev.is_some() => {
+            match dev_rpc(state, method, req.params.as_ref()).await {
+                Ok(value) => (Some(value), None),
+                Err(e) => (None, Some(e)),
+            }
+        }
         _ => (
             None,
             Some(JsonRpcError {
@@ -434,34 +937,43 @@ async fn rpc_handler(
         ),
     };
 
-    Json(JsonRpcResponse {
+    JsonRpcResponse {
         jsonrpc: "2.0".into(),
         result,
         error,
         id: req.id,
-    })
+        schema_version: encoding::SCHEMA_VERSION,
+    }
 }
 
 /// `GET /ws` — WebSocket upgrade for live event streaming.
 ///
-/// Clients receive JSON-encoded [`NodeEvent`] messages for each new block
-/// and transaction. The connection is read-only from the server's
-/// perspective; client messages are ignored.
-async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_ws_connection(socket, state))
+/// Clients receive JSON-encoded [`NodeEvent`] messages, each with a
+/// top-level `schema_version`, for every new block and transaction, less
+/// the transactions an [`EventFilter`] in the query string leaves out. The
+/// connection is read-only from the server's perspective; client messages
+/// are ignored.
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Query(filter): Query<EventFilter>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_ws_connection(socket, state, filter))
 }
 
 /// Drives a single WebSocket connection, forwarding broadcast events
-/// until the client disconnects or the channel is closed.
-async fn handle_ws_connection(mut socket: WebSocket, state: AppState) {
+/// that pass `filter` until the client disconnects or the channel is
+/// closed.
+async fn handle_ws_connection(mut socket: WebSocket, state: AppState, filter: EventFilter) {
     let mut rx = state.event_tx.subscribe();
 
     loop {
         tokio::select! {
             event = rx.recv() => {
                 match event {
+                    Ok(ev) if !filter.matches(&ev) => {}
                     Ok(ev) => {
-                        let payload = match serde_json::to_string(&ev) {
+                        let payload = match serde_json::to_string(&Versioned::new(&ev)) {
                             Ok(s) => s,
                             Err(e) => {
                                 tracing::warn!("failed to serialize ws event: {}", e);
@@ -493,22 +1005,72 @@ async fn handle_ws_connection(mut socket: WebSocket, state: AppState) {
     }
 }
 
-/// `GET /validators` — returns the current validator set.
-///
-/// TODO: Wire to the consensus module's active validator list once the
-/// validator registry is implemented. Currently returns a static
-/// placeholder set for API contract stability.
+/// `GET /validators` — returns the validator set in committed state, with
+/// each validator's liveness. Empty on a chain without a validator set.
 async fn validators_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let _ = &state;
-    let validators = vec![ValidatorInfo {
-        public_key: "0".repeat(64),
-        stake: 100_000_000_000,
-        active: true,
-        last_proposed_block: 0,
-    }];
+    let snapshot = state.committed_state.snapshot();
+    let Some(set) = ValidatorSet::load_from_snapshot(&snapshot) else {
+        return Json(Vec::<ValidatorInfo>::new());
+    };
+    let liveness = LivenessRecord::load_from_snapshot(&snapshot);
+    let network_keys = NetworkKeyRegistry::load_from_snapshot(&snapshot);
+    let policy = LivenessPolicy::default();
+
+    let validators = set
+        .validators()
+        .iter()
+        .map(|v| {
+            let record = liveness.get(&v.address).cloned().unwrap_or_default();
+            let network_key = network_keys.get(&v.address);
+            ValidatorInfo {
+                public_key: v.address.clone(),
+                stake: v.stake,
+                active: v.active,
+                last_proposed_block: 0,
+                jailed: record.jailed_at.is_some(),
+                unjail_height: record.unjail_height(&policy),
+                missed_proposals: record.proposals.missed(),
+                missed_votes: record.votes.missed(),
+                network_key: network_key.map(|k| k.network_key.clone()),
+                peer_id: network_key
+                    .and_then(|k| k.peer_id().ok())
+                    .map(|id| id.to_string()),
+            }
+        })
+        .collect();
     Json(validators)
 }
 
+/// `GET /treasury` — the treasury's balance and the spend proposals it has
+/// paid, as of committed state.
+async fn treasury_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let snapshot = state.committed_state.snapshot();
+    let balance = snapshot.get(TREASURY_STATE_KEY).map_or(0, |a| a.balance);
+    let ledger = TreasuryLedger::load_from_snapshot(&snapshot);
+    Json(TreasuryResponse {
+        account: TREASURY_STATE_KEY.to_string(),
+        balance,
+        total_spent: ledger.total_spent(),
+        spends: ledger.spends,
+    })
+}
+
+/// `GET /supply` — `nova_getSupply` with its default projection, for
+/// exchanges and explorers that poll plain URLs.
+async fn supply_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match supply(&state, None) {
+        Ok(resp) => (StatusCode::OK, Json(serde_json::to_value(resp).unwrap())).into_response(),
+        Err(e) => {
+            let err = ErrorResponse { error: e.message };
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::to_value(err).unwrap()),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// `GET /blocks/:height` — returns a block by its height.
 ///
 /// Fetches the block from NovaDB. Returns 404 if no block exists at
@@ -519,14 +1081,7 @@ async fn block_by_height_handler(
 ) -> impl IntoResponse {
     match state.db.get_block(height) {
         Ok(Some(block)) => {
-            let resp = BlockResponse {
-                height: block.header.height,
-                hash: block.header.hash_hex(),
-                parent_hash: block.header.parent_hash_hex(),
-                proposer: block.header.validator.clone(),
-                tx_count: block.transactions.len() as u64,
-                timestamp: block.header.timestamp,
-            };
+            let resp = BlockResponse::from(&block);
             (StatusCode::OK, Json(serde_json::to_value(resp).unwrap())).into_response()
         }
         Ok(None) => {
@@ -552,26 +1107,380 @@ async fn block_by_height_handler(
     }
 }
 
+/// Looks a transaction up by ID: `"confirmed"` if it is stored in a block,
+/// `"pending"` if it is waiting in the mempool — which is also where a
+/// transaction lands after its block is rolled back.
+fn find_transaction(state: &AppState, id: &str) -> Result<Option<TransactionResponse>, DbError> {
+    let (tx, status) = match state.db.get_transaction(id)? {
+        Some(tx) => (tx, TransactionStatus::Confirmed),
+        None => match state.mempool.get(id) {
+            Some(tx) => (tx, TransactionStatus::Pending),
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(transaction_response(&tx, &status)))
+}
+
+fn transaction_response(tx: &Transaction, status: &TransactionStatus) -> TransactionResponse {
+    TransactionResponse {
+        hash: tx.id.clone(),
+        sender: tx.sender.clone(),
+        recipient: tx.receiver.clone(),
+        amount: tx.amount.value,
+        fee: tx.fee,
+        block_height: None, // Would require a reverse index (tx -> block height)
+        status: status_label(status).into(),
+        timestamp: tx.timestamp,
+        memo: tx.memo.clone(),
+    }
+}
+
+/// Admits a signed transaction into the mempool (`nova_sendTransaction`).
+///
+/// Expects params `[transaction]`. The transaction is fully verified and
+/// its nonce checked against the committed account state before it
+/// reaches the pool: a nonce that is already used can never execute, and
+/// would otherwise sit in the pool until it expires.
+fn submit_transaction(
+    state: &AppState,
+    params: Option<&serde_json::Value>,
+) -> Result<String, JsonRpcError> {
+    let tx: Transaction = params
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [signed transaction]".into(),
+            data: None,
+        })?;
+
+    let rejected = |reason: &str, message: String| JsonRpcError {
+        code: -32003,
+        message: format!("Transaction rejected: {}", message),
+        data: Some(serde_json::json!({ "reason": reason })),
+    };
+
+    verify_transaction_with_policy(&tx, state.address_policy)
+        .map_err(|e| rejected("invalid_transaction", e.to_string()))?;
+
+    let account_nonce = state
+        .committed_state
+        .snapshot()
+        .get(&tx.sender)
+        .map_or(0, |a| a.nonce);
+    if tx.nonce <= account_nonce {
+        return Err(rejected(
+            "stale_nonce",
+            format!(
+                "nonce {} already used (account nonce is {})",
+                tx.nonce, account_nonce
+            ),
+        ));
+    }
+
+    let event = NodeEvent::NewTransaction {
+        hash: tx.id.clone(),
+        sender: tx.sender.clone(),
+        recipient: tx.receiver.clone(),
+        amount: tx.amount.value,
+        fee: tx.fee,
+        pending: true,
+    };
+    let id = tx.id.clone();
+    state.mempool.add(tx).map_err(|e| {
+        if let MempoolError::Screened(r) = &e {
+            let mut err = rejected("screened", e.to_string());
+            err.data = Some(serde_json::json!({ "reason": "screened", "address": r.address }));
+            return err;
+        }
+        let reason = match e {
+            MempoolError::DuplicateTransaction => "duplicate",
+            MempoolError::Expired { .. } => "expired",
+            MempoolError::FeeTooLow { .. } => "fee_too_low",
+            MempoolError::SenderLimitExceeded { .. } => "sender_limit",
+            MempoolError::MempoolFull { .. } | MempoolError::MemoryFull { .. } => "mempool_full",
+            MempoolError::Oversized(_) => "oversized",
+            MempoolError::InvalidAddress(_) => "invalid_address",
+            MempoolError::Screened(_) => "screened",
+            MempoolError::Unexecutable(AdmissionRejection::NonceUsed { .. }) => "stale_nonce",
+            MempoolError::Unexecutable(AdmissionRejection::InsufficientBalance { .. }) => {
+                "insufficient_balance"
+            }
+            MempoolError::Unexecutable(AdmissionRejection::Frozen { .. }) => "frozen",
+            MempoolError::Unexecutable(_) => "unexecutable",
+            MempoolError::UnknownTransaction(_) | MempoolError::InvalidCancellation(_) => {
+                "invalid_transaction"
+            }
+            _ => "rejected",
+        };
+        rejected(reason, e.to_string())
+    })?;
+
+    // No subscribers is fine.
+    let _ = state.event_tx.send(event);
+    Ok(id)
+}
+
+/// Withdraws a pending transaction on its sender's signed request
+/// (`nova_cancelTransaction`).
+///
+/// Expects params `[cancellation]`. Returns the ID of the dropped
+/// transaction.
+fn cancel_transaction(
+    state: &AppState,
+    params: Option<&serde_json::Value>,
+) -> Result<String, JsonRpcError> {
+    let cancellation: TransactionCancellation = params
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [{tx_id, signature}]".into(),
+            data: None,
+        })?;
+
+    let tx = state
+        .mempool
+        .cancel(&cancellation.tx_id, &cancellation)
+        .map_err(|e| match e {
+            MempoolError::UnknownTransaction(_) => JsonRpcError {
+                code: -32001,
+                message: format!("Pending transaction not found: {}", cancellation.tx_id),
+                data: None,
+            },
+            e => JsonRpcError {
+                code: -32003,
+                message: format!("Cancellation rejected: {}", e),
+                data: Some(serde_json::json!({ "reason": "invalid_cancellation" })),
+            },
+        })?;
+
+    // No subscribers is fine.
+    let _ = state.event_tx.send(NodeEvent::TransactionCancelled {
+        hash: tx.id.clone(),
+        sender: tx.sender,
+        signature: cancellation.signature,
+    });
+    Ok(tx.id)
+}
+
+/// `nova_verifyMessage` — checks a signed message. A well-formed message
+/// that fails verification is a normal result (`valid: false` with a
+/// `reason`), not an error, so login flows can tell "bad signature" from
+/// "bad request".
+fn verify_message_params(
+    params: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, JsonRpcError> {
+    let signed: SignedMessage = params
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [signed message]".into(),
+            data: None,
+        })?;
+    Ok(match signed.verify() {
+        Ok(()) => serde_json::json!({ "valid": true, "address": signed.address }),
+        Err(e) => serde_json::json!({
+            "valid": false,
+            "address": signed.address,
+            "reason": e.to_string(),
+        }),
+    })
+}
+
+/// `nova_getSupply` — the circulating supply as of committed state, and
+/// the supply projected at `[height]` (default: the end of the current
+/// emission epoch) if every scheduled reward is minted and nothing more is
+/// burned.
+fn supply(
+    state: &AppState,
+    params: Option<&serde_json::Value>,
+) -> Result<SupplyResponse, JsonRpcError> {
+    let snapshot = state.committed_state.snapshot();
+    let height = snapshot.height();
+    let record = SupplyRecord::load_from_snapshot(&snapshot);
+    let staked = ValidatorSet::load_from_snapshot(&snapshot).map_or(0, |set| set.total_stake());
+    drop(snapshot);
+
+    let policy = &state.monetary_policy;
+    let next = height + 1;
+    let epoch = policy.epoch_of(next).unwrap_or(0);
+    let projected_height = match params.and_then(|p| p.as_array()).and_then(|a| a.first()) {
+        None => (epoch + 1).saturating_mul(policy.epoch_blocks.max(1)),
+        Some(v) => v.as_u64().ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [height]".into(),
+            data: None,
+        })?,
+    };
+
+    let circulating = record.circulating(policy);
+    let scheduled = policy
+        .scheduled_emission(projected_height)
+        .saturating_sub(policy.scheduled_emission(height));
+    Ok(SupplyResponse {
+        height,
+        initial_supply: policy.initial_supply,
+        minted: record.minted,
+        burned: record.burned,
+        circulating,
+        genesis: record.genesis,
+        locked: record.locked,
+        staked,
+        epoch,
+        epoch_emission: policy.epoch_emission(epoch),
+        block_reward: policy.block_reward(next),
+        projected_height,
+        projected_supply: circulating.saturating_add(scheduled),
+    })
+}
+
+/// `nova_getNonce` — the nonce the next transaction from `[address]` must
+/// carry. Starts from the committed account nonce and skips past pending
+/// transactions with consecutive nonces, so a wallet that already has
+/// payments in the pool does not sign a duplicate.
+fn next_nonce(
+    state: &AppState,
+    params: Option<&serde_json::Value>,
+) -> Result<NonceResponse, JsonRpcError> {
+    let address = params
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [address]".into(),
+            data: None,
+        })?;
+    state
+        .address_policy
+        .check(address)
+        .map_err(|e| JsonRpcError {
+            code: -32602,
+            message: format!("Invalid params: {}", e),
+            data: None,
+        })?;
+
+    let snapshot = state.committed_state.snapshot();
+    let height = snapshot.height();
+    let nonce = snapshot.get(address).map_or(0, |a| a.nonce);
+    drop(snapshot);
+
+    let queued: std::collections::HashSet<u64> = state
+        .mempool
+        .pending_for_sender(address)
+        .iter()
+        .map(|tx| tx.nonce)
+        .collect();
+    let mut next = nonce + 1;
+    while queued.contains(&next) {
+        next += 1;
+    }
+
+    Ok(NonceResponse {
+        address: address.to_string(),
+        nonce,
+        pending: next - nonce - 1,
+        next_nonce: next,
+        height,
+    })
+}
+
+/// `nova_estimateFee` — the fee to offer at `[priority]` (`"low"`,
+/// `"normal"` or `"high"`, or `"economy"`, `"standard"` or `"fast"`;
+/// default `"normal"`), with every tier's expected wait, from the fees and
+/// fullness of recently committed blocks.
+fn estimate_fee(
+    state: &AppState,
+    params: Option<&serde_json::Value>,
+) -> Result<FeeEstimateResponse, JsonRpcError> {
+    let priority = match params.and_then(|p| p.as_array()).and_then(|a| a.first()) {
+        None => FeePriority::default(),
+        Some(v) => serde_json::from_value(v.clone()).map_err(|_| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [\"low\" | \"normal\" | \"high\"]".into(),
+            data: None,
+        })?,
+    };
+    let tiers = state.fee_estimator.tiers();
+    Ok(FeeEstimateResponse {
+        priority,
+        estimated_fee: tiers.get(priority).fee,
+        tiers,
+        sample_blocks: state.fee_estimator.sample_blocks() as u64,
+    })
+}
+
+/// Parses the optional `[{offset, limit}]` params of
+/// `nova_pendingTransactions`.
+fn mempool_query(params: Option<&serde_json::Value>) -> Result<MempoolQuery, JsonRpcError> {
+    match params.and_then(|p| p.as_array()).and_then(|a| a.first()) {
+        None => Ok(MempoolQuery::default()),
+        Some(v) => serde_json::from_value(v.clone()).map_err(|_| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [{offset?, limit?}]".into(),
+            data: None,
+        }),
+    }
+}
+
+/// `nova_getTransactionReceipt` — the receipt stored when the transaction
+/// `[hash]` was committed.
+fn transaction_receipt(
+    state: &AppState,
+    params: Option<&serde_json::Value>,
+) -> Result<TransactionReceipt, JsonRpcError> {
+    let hash = params
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| v.as_str())
+        .map(encoding::strip_hex_prefix)
+        .ok_or_else(|| JsonRpcError {
+            code: -32602,
+            message: "Invalid params: expected [hash]".into(),
+            data: None,
+        })?;
+
+    match state.db.get_receipt(hash) {
+        Ok(Some(receipt)) => Ok(receipt),
+        Ok(None) => Err(JsonRpcError {
+            code: -32001,
+            message: format!("Receipt not found: {}", hash),
+            data: None,
+        }),
+        Err(e) => Err(JsonRpcError {
+            code: -32603,
+            message: format!("Internal error: {}", e),
+            data: None,
+        }),
+    }
+}
+
+/// The lowercase status string used in responses and events.
+fn status_label(status: &TransactionStatus) -> &'static str {
+    match status {
+        TransactionStatus::Pending => "pending",
+        TransactionStatus::Confirmed => "confirmed",
+        TransactionStatus::Failed => "failed",
+        TransactionStatus::Expired => "expired",
+    }
+}
+
 /// `GET /transactions/:hash` — returns a transaction by its hex-encoded hash.
 ///
-/// Fetches the transaction from NovaDB. Returns 404 if no matching
-/// transaction exists.
+/// Looks in NovaDB first, then the mempool (see [`find_transaction`]).
+/// Returns 404 if neither holds a matching transaction.
 async fn transaction_by_hash_handler(
     Path(hash): Path<String>,
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    match state.db.get_transaction(&hash) {
-        Ok(Some(tx)) => {
-            let resp = TransactionResponse {
-                hash: tx.id.clone(),
-                sender: tx.sender.clone(),
-                recipient: tx.receiver.clone(),
-                amount: tx.amount.value,
-                fee: tx.fee,
-                block_height: None, // Would require a reverse index (tx -> block height)
-                status: "confirmed".into(),
-                timestamp: tx.timestamp,
-            };
+    let hash = encoding::strip_hex_prefix(&hash);
+    match find_transaction(&state, hash) {
+        Ok(Some(resp)) => {
             (StatusCode::OK, Json(serde_json::to_value(resp).unwrap())).into_response()
         }
         Ok(None) => {
@@ -597,88 +1506,607 @@ async fn transaction_by_hash_handler(
     }
 }
 
-/// `GET /accounts/:address` — returns account state for the given address.
+/// `GET /receipts/:hash` — returns the receipt of a committed transaction.
 ///
-/// Queries the StateTree for the account. Returns a default (zeroed)
-/// account response for addresses that have never appeared on-chain.
-async fn account_handler(
-    Path(address): Path<String>,
+/// Returns 404 if the transaction is pending, unknown, or was committed
+/// before receipts were stored.
+async fn receipt_handler(
+    Path(hash): Path<String>,
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    let tree = state.state_tree.read().await;
-    let account_state = tree.get(&address);
-    drop(tree);
+    let hash = encoding::strip_hex_prefix(&hash);
+    match state.db.get_receipt(hash) {
+        Ok(Some(receipt)) => {
+            (StatusCode::OK, Json(serde_json::to_value(receipt).unwrap())).into_response()
+        }
+        Ok(None) => {
+            let err = ErrorResponse {
+                error: format!("Receipt not found: {}", hash),
+            };
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::to_value(err).unwrap()),
+            )
+                .into_response()
+        }
+        Err(e) => db_error_response(e),
+    }
+}
 
-    let (balance, nonce) = match account_state {
-        Some(acct) => (acct.balance, acct.nonce),
-        None => (0, 0),
-    };
+/// `GET /mempool` — pages through the pending transactions, highest fee
+/// density first, along with the pool size. See [`pending_transactions`].
+async fn mempool_handler(
+    Query(query): Query<MempoolQuery>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let resp = pending_transactions(&state.mempool, &query);
+    (StatusCode::OK, Json(serde_json::to_value(resp).unwrap())).into_response()
+}
 
-    let account = AccountResponse {
-        address,
-        balance,
-        nonce,
-        tx_count: nonce, // Nonce tracks the number of outbound transactions.
-    };
-    Json(account)
+/// `GET /mempool/:hash` — one pending transaction with its fee density and
+/// age. Returns 404 unless the mempool holds it.
+async fn mempool_entry_handler(
+    Path(hash): Path<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let hash = encoding::strip_hex_prefix(&hash);
+    match state.mempool.entry(hash) {
+        Some(entry) => (
+            StatusCode::OK,
+            Json(serde_json::to_value(pending_transaction(&entry)).unwrap()),
+        )
+            .into_response(),
+        None => {
+            let err = ErrorResponse {
+                error: format!("Transaction not pending: {}", hash),
+            };
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::to_value(err).unwrap()),
+            )
+                .into_response()
+        }
+    }
 }
 
-// ---------------------------------------------------------------------------
-// Genesis Initialization
-// ---------------------------------------------------------------------------
+/// Lists one page of the mempool ([`Mempool::entries`]). Parked
+/// transactions, which block production skips until their nonce gap
+/// fills, are listed too.
+fn pending_transactions(mempool: &Mempool, query: &MempoolQuery) -> PendingTransactionsResponse {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(50).min(MAX_MEMPOOL_LISTING);
+    let size = mempool.size();
+    let transactions: Vec<PendingTransaction> = mempool
+        .entries(offset, limit)
+        .iter()
+        .map(pending_transaction)
+        .collect();
+    let end = offset.saturating_add(transactions.len());
+    PendingTransactionsResponse {
+        size: size as u64,
+        offset: offset as u64,
+        next_offset: (!transactions.is_empty() && end < size).then_some(end as u64),
+        transactions,
+    }
+}
 
-/// Ensures the genesis block exists in the database.
-///
-/// If the DB is empty (no latest block height), creates and persists the
-/// genesis block and updates the in-memory block height counter. This is
-/// idempotent — calling it on an already-initialized DB is a no-op.
-pub fn initialize_genesis(db: &NovaDB, block_height: &std::sync::atomic::AtomicU64) {
-    match db.get_latest_block_height() {
-        Ok(Some(h)) => {
-            // DB already has blocks. Sync the in-memory counter.
-            block_height.store(h, std::sync::atomic::Ordering::Relaxed);
-            tracing::info!(height = h, "database loaded, latest block height synced");
-        }
-        Ok(None) => {
-            // Empty DB — persist the genesis block.
-            let genesis = nova_protocol::storage::block::Block::genesis();
-            if let Err(e) = db.put_block(&genesis) {
-                tracing::error!("failed to persist genesis block: {}", e);
-                return;
-            }
-            block_height.store(0, std::sync::atomic::Ordering::Relaxed);
-            tracing::info!("genesis block persisted at height 0");
-        }
-        Err(e) => {
-            tracing::error!("failed to read latest block height from DB: {}", e);
-        }
+/// Builds the listing entry for one pending transaction.
+fn pending_transaction(entry: &MempoolEntry) -> PendingTransaction {
+    let tx = &entry.transaction;
+    PendingTransaction {
+        hash: tx.id.clone(),
+        sender: tx.sender.clone(),
+        recipient: tx.receiver.clone(),
+        nonce: tx.nonce,
+        amount: tx.amount.value,
+        fee: tx.fee,
+        fee_per_byte: entry.fee_per_byte,
+        timestamp: tx.timestamp,
+        added_at: entry.added_at,
+        age_secs: entry.age_secs(),
     }
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
+/// Runs a dev control off the async runtime. Callers check `state.dev`
+/// first.
+async fn blocking_dev<R, F>(state: &AppState, f: F) -> Result<R, DevError>
+where
+    R: Send + 'static,
+    F: FnOnce(&DevControls) -> Result<R, DevError> + Send + 'static,
+{
+    let dev = Arc::clone(state.dev.as_ref().expect("dev controls enabled"));
+    tokio::task::spawn_blocking(move || f(&dev))
+        .await
+        .expect("dev control task panicked")
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use axum::body::Body;
-    use axum::http::{Request, StatusCode};
-    use http_body_util::BodyExt;
-    use nova_protocol::storage::block::Block;
-    use nova_protocol::storage::db::NovaDB;
-    use nova_protocol::storage::state::{AccountState, StateTree};
-    use nova_protocol::transaction::builder::TransactionBuilder;
-    use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
-    use std::sync::Arc;
-    use tower::ServiceExt;
+/// Handles the `nova_dev*` JSON-RPC methods. Callers check `state.dev`
+/// first.
+async fn dev_rpc(
+    state: &AppState,
+    method: &str,
+    params: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, JsonRpcError> {
+    let arg = params
+        .and_then(|p| p.as_array())
+        .and_then(|arr| arr.first());
+    let invalid = |expected: &str| JsonRpcError {
+        code: -32602,
+        message: format!("Invalid params: expected [{}]", expected),
+        data: None,
+    };
 
-    /// Creates a test AppState backed by a temporary in-memory database.
-    fn test_app_state() -> AppState {
+    let result = match method {
+        // Expects params: [n: u64], n defaulting to 1.
+        "nova_devMine" => {
+            let n = arg
+                .map_or(Some(1), |v| v.as_u64())
+                .ok_or_else(|| invalid("block_count"))?;
+            blocking_dev(state, move |dev| dev.mine(n))
+                .await
+                .map(|hashes| serde_json::to_value(hashes).unwrap())
+        }
+        // Expects params: [ms: u64]. Returns the next block's timestamp.
+        "nova_devIncreaseTime" => {
+            let ms = arg
+                .and_then(|v| v.as_u64())
+                .ok_or_else(|| invalid("milliseconds"))?;
+            blocking_dev(state, move |dev| dev.increase_time(ms))
+                .await
+                .map(|t| serde_json::json!(t))
+        }
+        // Expects params: [timestamp_ms: u64].
+        "nova_devSetTimestamp" => {
+            let t = arg
+                .and_then(|v| v.as_u64())
+                .ok_or_else(|| invalid("timestamp_ms"))?;
+            blocking_dev(state, move |dev| dev.set_timestamp(t))
+                .await
+                .map(|t| serde_json::json!(t))
+        }
+        // No params. Returns the snapshot ID.
+        "nova_devSnapshot" => blocking_dev(state, |dev| dev.snapshot())
+            .await
+            .map(|id| serde_json::json!(id)),
+        // Expects params: [snapshot_id: u64]. Returns true.
+        "nova_devRevert" => {
+            let id = arg
+                .and_then(|v| v.as_u64())
+                .ok_or_else(|| invalid("snapshot_id"))?;
+            blocking_dev(state, move |dev| dev.revert(id))
+                .await
+                .map(|()| serde_json::json!(true))
+        }
+        _ => {
+            return Err(JsonRpcError {
+                code: -32601,
+                message: format!("Method not found: {}", method),
+                data: None,
+            })
+        }
+    };
+
+    result.map_err(|e| JsonRpcError {
+        code: match e {
+            DevError::TooManyBlocks { .. } | DevError::TimeBeforeTip { .. } => -32602,
+            DevError::UnknownSnapshot(_) => -32001,
+            _ => -32603,
+        },
+        message: e.to_string(),
+        data: None,
+    })
+}
+
+/// `POST /dev/mine` — produces blocks immediately (dev mode only; not
+/// routed otherwise). Body `{"blocks": n}` is optional and defaults to one
+/// block.
+async fn dev_mine_handler(
+    State(state): State<AppState>,
+    body: Option<Json<DevMineRequest>>,
+) -> impl IntoResponse {
+    let n = body.and_then(|Json(req)| req.blocks).unwrap_or(1);
+    match blocking_dev(&state, move |dev| dev.mine(n)).await {
+        Ok(hashes) => {
+            let resp = DevMineResponse {
+                hashes,
+                height: state
+                    .db
+                    .get_latest_block_height()
+                    .ok()
+                    .flatten()
+                    .unwrap_or(0),
+            };
+            (StatusCode::OK, Json(serde_json::to_value(resp).unwrap())).into_response()
+        }
+        Err(e) => {
+            let status = match e {
+                DevError::TooManyBlocks { .. } => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            let err = ErrorResponse {
+                error: e.to_string(),
+            };
+            (status, Json(serde_json::to_value(err).unwrap())).into_response()
+        }
+    }
+}
+
+/// `GET /ntp/peer` — the peer ID counterparties send NTP messages to, and
+/// the addresses (TCP, and QUIC with `--quic`) they can dial it on.
+async fn ntp_peer_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let Some(direct) = &state.direct else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let addresses: Vec<_> = direct
+        .listen_addrs()
+        .iter()
+        .map(|addr| {
+            serde_json::json!({
+                "address": addr.to_string(),
+                "transport": PeerTransport::of(addr),
+            })
+        })
+        .collect();
+    Json(serde_json::json!({
+        "peer_id": direct.local_peer_id().to_string(),
+        "protocol": nova_protocol::network::direct::NTP_DIRECT_PROTOCOL,
+        "addresses": addresses,
+    }))
+    .into_response()
+}
+
+/// `POST /ntp/messages` — sends a message to a mailbox on another node (or
+/// this one). Answers `{"delivery": "local" | "queued"}`; a queued message
+/// may still fail to reach the peer, which is logged but not reported.
+async fn ntp_send_handler(
+    State(state): State<AppState>,
+    Json(req): Json<NtpSendRequest>,
+) -> impl IntoResponse {
+    let Some(direct) = &state.direct else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    match direct.send(&req.peer_id, req.address.as_deref(), req.message, now) {
+        Ok(delivery) => Json(serde_json::json!({ "delivery": delivery.as_str() })).into_response(),
+        Err(e) => {
+            let status = match e {
+                DirectError::MailboxFull(_) => StatusCode::TOO_MANY_REQUESTS,
+                DirectError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            let err = ErrorResponse {
+                error: e.to_string(),
+            };
+            (status, Json(serde_json::to_value(err).unwrap())).into_response()
+        }
+    }
+}
+
+/// `GET /ntp/messages/:mailbox` — removes and returns the messages queued
+/// for a mailbox, oldest first.
+async fn ntp_mailbox_handler(
+    Path(mailbox): Path<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let Some(direct) = &state.direct else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let messages = direct.mailboxes().take(&mailbox, now);
+    Json(serde_json::json!({ "messages": messages })).into_response()
+}
+
+/// `GET /accounts/:address` — returns account state for the given address.
+///
+/// Reads the account from the last committed state (see
+/// [`CommittedState`]) and reports that block's height alongside it.
+/// Returns a default (zeroed) account response for addresses that have
+/// never appeared on-chain, and 400 for strings that are not addresses at
+/// all.
+async fn account_handler(
+    Path(address): Path<String>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    if let Err(e) = state.address_policy.check(&address) {
+        let err = ErrorResponse {
+            error: e.to_string(),
+        };
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::to_value(err).unwrap()),
+        )
+            .into_response();
+    }
+
+    // Read from the committed snapshot, never the tree the producer is
+    // mutating, so a half-applied block is never visible.
+    let snapshot = state.committed_state.snapshot();
+    let height = snapshot.height();
+    let account_state = snapshot.get(&address);
+    drop(snapshot);
+
+    let (balance, nonce) = match account_state {
+        Some(acct) => (acct.balance, acct.nonce),
+        None => (0, 0),
+    };
+
+    let account = AccountResponse {
+        address,
+        balance,
+        nonce,
+        tx_count: nonce, // Nonce tracks the number of outbound transactions.
+        height,
+    };
+    Json(account).into_response()
+}
+
+/// `GET /peers` — connected peers with their gossipsub scores, best first.
+async fn peer_scores_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.peer_scores.list())
+}
+
+/// `GET /admin/peers` — returns every record in the peer store.
+async fn list_peers_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match state.peer_store.list() {
+        Ok(peers) => (StatusCode::OK, Json(serde_json::to_value(peers).unwrap())).into_response(),
+        Err(e) => db_error_response(e),
+    }
+}
+
+/// `POST /admin/peers` — adds a peer or updates its pinned/banned flags.
+///
+/// Returns the resulting record.
+async fn upsert_peer_handler(
+    State(state): State<AppState>,
+    caller: Option<ConnectInfo<SocketAddr>>,
+    Json(req): Json<PeerCurationRequest>,
+) -> impl IntoResponse {
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let result = state
+        .peer_store
+        .observe(&req.peer_id, &req.address, now)
+        .and_then(|mut record: PeerRecord| {
+            if let Some(pinned) = req.pinned {
+                record.pinned = pinned;
+            }
+            if let Some(banned) = req.banned {
+                record.banned = banned;
+            }
+            state.peer_store.put(&record)?;
+            Ok(record)
+        });
+
+    match result {
+        Ok(record) => {
+            let details = format!(
+                "peer={} address={} pinned={} banned={}",
+                record.peer_id, req.address, record.pinned, record.banned
+            );
+            audit_admin(&state, caller, "peer_upserted", &details);
+            (StatusCode::OK, Json(serde_json::to_value(record).unwrap())).into_response()
+        }
+        Err(e) => db_error_response(e),
+    }
+}
+
+/// `DELETE /admin/peers/:peer_id` — forgets a peer.
+async fn remove_peer_handler(
+    Path(peer_id): Path<String>,
+    State(state): State<AppState>,
+    caller: Option<ConnectInfo<SocketAddr>>,
+) -> impl IntoResponse {
+    match state.peer_store.remove(&peer_id) {
+        Ok(true) => {
+            audit_admin(&state, caller, "peer_removed", &format!("peer={}", peer_id));
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(false) => {
+            let err = ErrorResponse {
+                error: format!("Unknown peer: {}", peer_id),
+            };
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::to_value(err).unwrap()),
+            )
+                .into_response()
+        }
+        Err(e) => db_error_response(e),
+    }
+}
+
+/// `GET /admin/audit` — the audit log's head and every entry.
+async fn audit_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match state.audit.export() {
+        Ok(export) => (StatusCode::OK, Json(serde_json::to_value(export).unwrap())).into_response(),
+        Err(e) => db_error_response(e),
+    }
+}
+
+/// `GET /halt` — whether an emergency halt is in force or was lifted.
+async fn halt_status_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.halt.status())
+}
+
+/// `POST /admin/halt` — accepts a supermajority-signed emergency halt.
+///
+/// Returns the resulting halt status.
+async fn halt_handler(
+    State(state): State<AppState>,
+    caller: Option<ConnectInfo<SocketAddr>>,
+    Json(halt): Json<EmergencyHalt>,
+) -> impl IntoResponse {
+    let Some(validators) = committed_validators(&state) else {
+        return no_validator_set_response();
+    };
+    let details = format!(
+        "halt_height={} signers={} reason={}",
+        halt.halt_height,
+        halt.signatures.len(),
+        halt.reason
+    );
+    let next_height = state.committed_state.height() + 1;
+    match state.halt.apply_halt(halt, &validators, next_height) {
+        Ok(()) => {
+            audit_admin(&state, caller, "chain_halted", &details);
+            state.metrics.chain_halted.set(1);
+            (StatusCode::OK, Json(state.halt.status())).into_response()
+        }
+        Err(e) => halt_error_response(e),
+    }
+}
+
+/// `POST /admin/resume` — accepts a supermajority-signed resume for the
+/// halt in force.
+///
+/// Returns the resulting halt status.
+async fn resume_handler(
+    State(state): State<AppState>,
+    caller: Option<ConnectInfo<SocketAddr>>,
+    Json(resume): Json<ResumeAction>,
+) -> impl IntoResponse {
+    let Some(validators) = committed_validators(&state) else {
+        return no_validator_set_response();
+    };
+    let details = format!(
+        "halt_height={} signers={} min_protocol_version={}",
+        resume.halt_height,
+        resume.signatures.len(),
+        resume.min_protocol_version
+    );
+    match state.halt.apply_resume(resume, &validators) {
+        Ok(()) => {
+            audit_admin(&state, caller, "chain_resumed", &details);
+            state.metrics.chain_halted.set(0);
+            (StatusCode::OK, Json(state.halt.status())).into_response()
+        }
+        Err(e) => halt_error_response(e),
+    }
+}
+
+fn committed_validators(state: &AppState) -> Option<ValidatorSet> {
+    ValidatorSet::load_from_snapshot(&state.committed_state.snapshot())
+}
+
+fn no_validator_set_response() -> axum::response::Response {
+    let err = ErrorResponse {
+        error: "No validator set in committed state".to_string(),
+    };
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::to_value(err).unwrap()),
+    )
+        .into_response()
+}
+
+fn halt_error_response(e: HaltError) -> axum::response::Response {
+    let status = match &e {
+        HaltError::Db(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        HaltError::Halted { .. }
+        | HaltError::UpgradeRequired { .. }
+        | HaltError::HeightPassed { .. }
+        | HaltError::Stale(_)
+        | HaltError::NoActiveHalt
+        | HaltError::ResumeMismatch { .. } => StatusCode::CONFLICT,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    let err = ErrorResponse {
+        error: e.to_string(),
+    };
+    (status, Json(serde_json::to_value(err).unwrap())).into_response()
+}
+
+/// Records a completed admin API change. The change has already happened,
+/// so a failed write is logged rather than reported to the caller.
+fn audit_admin(
+    state: &AppState,
+    caller: Option<ConnectInfo<SocketAddr>>,
+    action: &str,
+    details: &str,
+) {
+    let actor = caller.map_or_else(
+        || "unknown".to_string(),
+        |ConnectInfo(addr)| addr.to_string(),
+    );
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    if let Err(e) = state
+        .audit
+        .append(AuditCategory::Admin, action, &actor, details, now)
+    {
+        tracing::error!(action, error = %e, "failed to write audit entry");
+    }
+}
+
+fn db_error_response(e: nova_protocol::storage::db::DbError) -> axum::response::Response {
+    let err = ErrorResponse {
+        error: format!("Database error: {}", e),
+    };
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::to_value(err).unwrap()),
+    )
+        .into_response()
+}
+
+// ---------------------------------------------------------------------------
+// Genesis Initialization
+// ---------------------------------------------------------------------------
+
+/// Ensures the genesis block exists in the database.
+///
+/// If the DB is empty (no latest block height), creates and persists the
+/// genesis block and updates the in-memory block height counter. This is
+/// idempotent — calling it on an already-initialized DB is a no-op.
+pub fn initialize_genesis(db: &NovaDB, block_height: &std::sync::atomic::AtomicU64) {
+    match db.get_latest_block_height() {
+        Ok(Some(h)) => {
+            // DB already has blocks. Sync the in-memory counter.
+            block_height.store(h, std::sync::atomic::Ordering::Relaxed);
+            tracing::info!(height = h, "database loaded, latest block height synced");
+        }
+        Ok(None) => {
+            // Empty DB — persist the genesis block.
+            let genesis = Block::genesis();
+            if let Err(e) = db.put_block(&genesis) {
+                tracing::error!("failed to persist genesis block: {}", e);
+                return;
+            }
+            block_height.store(0, std::sync::atomic::Ordering::Relaxed);
+            tracing::info!("genesis block persisted at height 0");
+        }
+        Err(e) => {
+            tracing::error!("failed to read latest block height from DB: {}", e);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dev::MAX_DEV_MINE_BLOCKS;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use nova_protocol::storage::db::NovaDB;
+    use nova_protocol::storage::state::{AccountState, StateTree};
+    use nova_protocol::transaction::builder::TransactionBuilder;
+    use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    /// Creates a test AppState backed by a temporary in-memory database.
+    fn test_app_state() -> AppState {
         let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
-        let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+        let root = StateTree::new((*db).clone()).root();
+        let committed_state = CommittedState::new((*db).clone(), 0, root);
         let (event_tx, _) = broadcast::channel(16);
         let metrics = Arc::new(crate::metrics::NodeMetrics::new());
+        let peer_store = Arc::new(PeerStore::open(&db, Default::default()).expect("peer store"));
+        let audit = Arc::new(AuditLog::open(&db).expect("audit log"));
+        let halt = Arc::new(HaltGuard::open(&db).expect("halt guard"));
 
         AppState {
             version: "0.1.0-test".into(),
@@ -688,10 +2116,28 @@ mod tests {
             event_tx,
             metrics,
             db,
-            state_tree,
+            committed_state,
+            mempool: Arc::new(Mempool::default()),
+            fee_estimator: Arc::new(FeeEstimator::new(10)),
+            peer_store,
+            peer_scores: Arc::new(PeerScoreBoard::default()),
+            audit,
+            halt,
+            monetary_policy: MonetaryPolicy::default(),
+            address_policy: AddressPolicy::Permissive,
+            capabilities: Capabilities::compiled(),
+            direct: None,
+            updates: None,
+            dev: None,
         }
     }
 
+    /// A state tree wired to `state.committed_state`, standing in for the
+    /// producer's tree.
+    fn live_tree(state: &AppState) -> StateTree {
+        StateTree::new((*state.db).clone()).with_committed_state(Arc::clone(&state.committed_state))
+    }
+
     /// Creates a test AppState and persists the genesis block.
     fn test_app_state_with_genesis() -> AppState {
         let state = test_app_state();
@@ -712,7 +2158,7 @@ mod tests {
             .fee(10)
             .nonce(nonce)
             .timestamp(1_000_000)
-            .build()
+            .build_unchecked()
     }
 
     /// Sends a GET request and returns the (status, body_bytes).
@@ -800,7 +2246,12 @@ mod tests {
         assert_eq!(resp.height, 0);
         assert_eq!(resp.tx_count, 0);
         // Genesis has all-zero parent hash.
-        assert_eq!(resp.parent_hash, hex::encode([0u8; 32]));
+        assert!(resp.parent_hash.is_zero());
+
+        // Hashes are 0x-prefixed hex on the wire.
+        let raw: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(raw["parent_hash"], format!("0x{}", hex::encode([0u8; 32])));
+        assert_eq!(raw["hash"], format!("0x{}", resp.hash.to_hex()));
     }
 
     // -- 4. Block endpoint returns 404 for missing block ---------------------
@@ -873,8 +2324,9 @@ mod tests {
 
         // Populate an account in the state tree.
         {
-            let mut tree = state.state_tree.write().await;
+            let mut tree = live_tree(&state);
             tree.put("nova1alice", &AccountState::with_balance(42_000));
+            tree.commit(0);
         }
 
         let router = create_router(state);
@@ -887,6 +2339,25 @@ mod tests {
         assert_eq!(resp.nonce, 0);
     }
 
+    // -- 8b. Strict policy rejects malformed account addresses -----------------
+
+    #[tokio::test]
+    async fn account_endpoint_rejects_malformed_address_when_strict() {
+        let mut state = test_app_state_with_genesis();
+        state.address_policy = AddressPolicy::Strict;
+        let router = create_router(state);
+
+        let (status, body) = get(&router, "/accounts/garbage").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let err: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert!(err.error.contains("garbage"));
+
+        let kp = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let addr = nova_protocol::identity::NovaId::from_public_key(&kp.public_key()).to_address();
+        let (status, _) = get(&router, &format!("/accounts/{}", addr)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
     // -- 9. JSON-RPC nova_blockHeight returns real value -----------------------
 
     #[tokio::test]
@@ -1052,6 +2523,7 @@ mod tests {
         let genesis = Block::genesis();
         let tx1 = make_test_tx(1);
         let tx2 = make_test_tx(2);
+        let ids = vec![tx1.id.clone(), tx2.id.clone()];
         let block1 = Block::new(&genesis, vec![tx1, tx2], "nova:validator".into(), [1u8; 32]);
         state.db.put_block(&block1).expect("persist block 1");
 
@@ -1059,9 +2531,12 @@ mod tests {
         let (status, body) = get(&router, "/blocks/1").await;
 
         assert_eq!(status, StatusCode::OK);
-        let resp: BlockResponse = serde_json::from_slice(&body).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["transactions"][0], format!("0x{}", ids[0]));
+        let resp: BlockResponse = serde_json::from_value(json).unwrap();
         assert_eq!(resp.height, 1);
         assert_eq!(resp.tx_count, 2);
+        assert_eq!(resp.transactions, ids);
     }
 
     // -- 17. JSON-RPC version and networkId return config values ---------------
@@ -1112,4 +2587,1334 @@ mod tests {
         assert!(resp.error.is_some());
         assert_eq!(resp.error.unwrap().code, -32600);
     }
+
+    // -- 19. Admin peer store curation ---------------------------------------
+
+    #[tokio::test]
+    async fn admin_peers_add_pin_and_remove() {
+        let state = test_app_state();
+        let store = Arc::clone(&state.peer_store);
+        let router = create_router(state);
+
+        let body = serde_json::json!({
+            "peer_id": "12D3KooWPeer",
+            "address": "/ip4/10.0.0.1/tcp/9740",
+            "pinned": true
+        });
+        let (status, _) = post_json(&router, "/admin/peers", body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(store.get("12D3KooWPeer").unwrap().unwrap().pinned);
+
+        let (status, body) = get(&router, "/admin/peers").await;
+        assert_eq!(status, StatusCode::OK);
+        let peers: Vec<PeerRecord> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].addresses, ["/ip4/10.0.0.1/tcp/9740"]);
+
+        let req = Request::builder()
+            .method("DELETE")
+            .uri("/admin/peers/12D3KooWPeer")
+            .body(Body::empty())
+            .unwrap();
+        let resp = router.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(store.is_empty());
+    }
+
+    // -- 20. Wire encoding policy and schema version --------------------------
+
+    #[tokio::test]
+    async fn responses_follow_the_wire_encoding_policy() {
+        let state = test_app_state_with_genesis();
+        let tx = make_test_tx(3);
+        state.db.put_transaction(&tx).expect("persist tx");
+        let router = create_router(state);
+
+        // Path lookups accept the 0x prefix; the response hash carries it.
+        let (status, body) = get(&router, &format!("/transactions/0x{}", tx.id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let raw: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(raw["hash"], format!("0x{}", tx.id));
+        assert_eq!(raw["amount"], 500);
+
+        let rpc_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "nova_blockHeight",
+            "params": [],
+            "id": 1
+        });
+        let (_, body) = post_json(&router, "/rpc", rpc_body).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.schema_version, encoding::SCHEMA_VERSION);
+
+        let event = NodeEvent::NewTransaction {
+            hash: tx.id.clone(),
+            sender: "nova1alice".into(),
+            recipient: "nova1bob".into(),
+            amount: u64::MAX,
+            fee: 1_000,
+            pending: true,
+        };
+        let json = serde_json::to_value(Versioned::new(&event)).unwrap();
+        assert_eq!(json["type"], "new_transaction");
+        assert_eq!(json["schema_version"], encoding::SCHEMA_VERSION);
+        assert_eq!(json["amount"], u64::MAX.to_string());
+        assert_eq!(json["fee"], 1_000);
+        assert_eq!(json["pending"], true);
+    }
+
+    #[test]
+    fn event_filters_narrow_transactions_only() {
+        let event = |sender: &str, amount| NodeEvent::NewTransaction {
+            hash: "ab".into(),
+            sender: sender.into(),
+            recipient: "nova1shop".into(),
+            amount,
+            fee: 10,
+            pending: true,
+        };
+        let filter = EventFilter {
+            sender: Some("nova1alice".into()),
+            recipient: None,
+            min_amount: Some(1_000),
+        };
+        assert!(filter.matches(&event("nova1alice", 1_000)));
+        assert!(!filter.matches(&event("nova1alice", 999)));
+        assert!(!filter.matches(&event("nova1bob", 5_000)));
+        assert!(EventFilter::default().matches(&event("nova1bob", 0)));
+
+        let by_recipient = EventFilter {
+            recipient: Some("nova1other".into()),
+            ..EventFilter::default()
+        };
+        assert!(!by_recipient.matches(&event("nova1alice", 1_000)));
+        assert!(by_recipient.matches(&NodeEvent::NewBlock {
+            height: 1,
+            hash: "cd".into(),
+            tx_count: 0,
+            timestamp: 0,
+        }));
+    }
+
+    // -- 21. Reorged transactions report as pending ---------------------------
+
+    #[tokio::test]
+    async fn reorged_transactions_report_as_pending() {
+        let state = test_app_state_with_genesis();
+        let tx = make_test_tx(4);
+        state.mempool.add(tx.clone()).expect("admit tx");
+        let router = create_router(state);
+
+        let (status, body) = get(&router, &format!("/transactions/{}", tx.id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let resp: TransactionResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.status, "pending");
+
+        let event = NodeEvent::from(&ReorgedTransaction {
+            tx_id: tx.id.clone(),
+            from_height: 7,
+            outcome: ReorgOutcome::Readmitted,
+        });
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "transaction_reorged");
+        assert_eq!(json["hash"], format!("0x{}", tx.id));
+        assert_eq!(json["from_height"], 7);
+        assert_eq!(json["status"], "pending");
+        assert!(json.get("reason").is_none());
+
+        let dropped = NodeEvent::from(&ReorgedTransaction {
+            tx_id: tx.id,
+            from_height: 7,
+            outcome: ReorgOutcome::Dropped("insufficient balance".into()),
+        });
+        let json = serde_json::to_value(&dropped).unwrap();
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["reason"], "insufficient balance");
+    }
+
+    // -- 22. Account reads come from the committed snapshot ------------------
+
+    #[tokio::test]
+    async fn account_endpoint_reads_committed_snapshot() {
+        let state = test_app_state_with_genesis();
+        let mut live = live_tree(&state);
+        live.put("nova1alice", &AccountState::with_balance(1_000));
+        live.commit(1);
+
+        // A block in flight: debited but not yet committed.
+        live.put("nova1alice", &AccountState::with_balance(400));
+
+        let router = create_router(state);
+        let (_, body) = get(&router, "/accounts/nova1alice").await;
+        let resp: AccountResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.balance, 1_000);
+        assert_eq!(resp.height, 1);
+
+        live.commit(2);
+        let (_, body) = get(&router, "/accounts/nova1alice").await;
+        let resp: AccountResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.balance, 400);
+        assert_eq!(resp.height, 2);
+    }
+
+    // -- 23. nova_sendTransaction admits signed transactions ----------------
+
+    fn signed_transfer(
+        kp: &nova_protocol::crypto::keys::NovaKeypair,
+        nonce: u64,
+    ) -> nova_protocol::transaction::Transaction {
+        let sender =
+            nova_protocol::identity::NovaId::from_public_key(&kp.public_key()).to_address();
+        let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender(&sender)
+            .receiver("nova1bob")
+            .amount(Amount::new(500, Currency::NOVA))
+            .fee(100)
+            .nonce(nonce)
+            .build_unchecked();
+        nova_protocol::transaction::signing::sign_transaction(&mut tx, kp);
+        tx
+    }
+
+    async fn send_tx(router: &Router, params: serde_json::Value) -> JsonRpcResponse {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "nova_sendTransaction",
+            "params": params,
+            "id": 1
+        });
+        let (_, body) = post_json(router, "/rpc", body).await;
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn send_transaction_admits_and_rejects_with_reason() {
+        let state = test_app_state_with_genesis();
+        let mempool = Arc::clone(&state.mempool);
+        let mut events = state.event_tx.subscribe();
+        let kp = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let sender =
+            nova_protocol::identity::NovaId::from_public_key(&kp.public_key()).to_address();
+        let mut live = live_tree(&state);
+        let mut account = AccountState::with_balance(10_000);
+        account.nonce = 2;
+        live.put(&sender, &account);
+        live.commit(1);
+        let router = create_router(state);
+
+        let tx = signed_transfer(&kp, 3);
+        let resp = send_tx(&router, serde_json::json!([tx])).await;
+        assert!(resp.error.is_none(), "{:?}", resp.error);
+        assert_eq!(resp.result.unwrap(), serde_json::json!(tx.id));
+        assert!(mempool.contains(&tx.id));
+        match events.try_recv().unwrap() {
+            NodeEvent::NewTransaction {
+                hash, fee, pending, ..
+            } => {
+                assert_eq!(hash, tx.id);
+                assert_eq!(fee, tx.fee);
+                assert!(pending);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let reason = |resp: JsonRpcResponse| {
+            let err = resp.error.expect("rejected");
+            assert_eq!(err.code, -32003);
+            err.data.unwrap()["reason"].as_str().unwrap().to_string()
+        };
+        assert_eq!(
+            reason(send_tx(&router, serde_json::json!([tx])).await),
+            "duplicate"
+        );
+        assert_eq!(
+            reason(send_tx(&router, serde_json::json!([signed_transfer(&kp, 2)])).await),
+            "stale_nonce"
+        );
+
+        let mut forged = signed_transfer(&kp, 4);
+        forged.amount = Amount::new(1, Currency::NOVA);
+        assert_eq!(
+            reason(send_tx(&router, serde_json::json!([forged])).await),
+            "invalid_transaction"
+        );
+
+        let resp = send_tx(&router, serde_json::json!(["not a transaction"])).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 24. Mempool listing, best fee first, paged ------------------------
+
+    #[tokio::test]
+    async fn mempool_lists_pending_transactions() {
+        let state = test_app_state();
+        for (nonce, fee) in [(1, 10_000), (2, 30_000), (3, 20_000)] {
+            let tx = TransactionBuilder::new(TransactionType::Transfer)
+                .sender(&format!("nova1sender{nonce}"))
+                .receiver("nova1bob")
+                .amount(Amount::new(500, Currency::NOVA))
+                .fee(fee)
+                .nonce(1)
+                .build_unchecked();
+            state.mempool.add(tx).expect("admit");
+        }
+        let router = create_router(state);
+
+        let (status, body) = get(&router, "/mempool?limit=2").await;
+        assert_eq!(status, StatusCode::OK);
+        let resp: PendingTransactionsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.size, 3);
+        assert_eq!(resp.next_offset, Some(2));
+        let fees: Vec<u64> = resp.transactions.iter().map(|t| t.fee).collect();
+        assert_eq!(fees, vec![30_000, 20_000]);
+        assert_eq!(resp.transactions[0].sender, "nova1sender2");
+        assert!(resp.transactions.iter().all(|t| t.age_secs < 60));
+
+        let (_, body) = get(&router, "/mempool?offset=2&limit=2").await;
+        let resp: PendingTransactionsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.offset, 2);
+        assert_eq!(resp.next_offset, None);
+        assert_eq!(resp.transactions.len(), 1);
+        assert_eq!(resp.transactions[0].fee, 10_000);
+
+        let (_, body) = get(&router, "/mempool").await;
+        let resp: PendingTransactionsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.transactions.len(), 3);
+    }
+
+    // -- 25. nova_devMine and POST /dev/mine produce blocks on demand -------
+
+    /// A dev-mode AppState whose controls drive a single-validator
+    /// consensus loop over the state's own database.
+    fn dev_app_state() -> AppState {
+        use nova_protocol::crypto::keys::NovaKeypair;
+        use nova_protocol::network::consensus::{ConsensusConfig, ConsensusEngine, ValidatorSet};
+        use nova_protocol::network::consensus_loop::{ConsensusLoop, ConsensusLoopConfig};
+        use nova_protocol::network::producer::BlockProducer;
+        use parking_lot::RwLock;
+
+        let mut state = test_app_state_with_genesis();
+        let keypair = NovaKeypair::from_seed(&[3u8; 32]);
+        let mut set = ValidatorSet::new();
+        set.add_validator(keypair.public_key().to_hex(), 10_000_000_000);
+        let mut engine = ConsensusEngine::new(
+            ConsensusConfig {
+                min_validators: 1,
+                ..ConsensusConfig::default()
+            },
+            set,
+        );
+        engine.set_chain_state(1, Block::genesis().header.hash);
+
+        let engine = Arc::new(RwLock::new(engine));
+        let tree = Arc::new(RwLock::new(live_tree(&state)));
+        let producer = Arc::new(BlockProducer::new(
+            Arc::clone(&state.db),
+            Arc::clone(&tree),
+            Arc::clone(&state.mempool),
+            keypair.clone(),
+        ));
+        let consensus = ConsensusLoop::new(
+            Arc::clone(&engine),
+            Arc::clone(&producer),
+            Arc::clone(&state.db),
+            Arc::clone(&tree),
+            Arc::clone(&state.mempool),
+            keypair,
+            ConsensusLoopConfig::default(),
+        );
+        state.dev = Some(Arc::new(DevControls::new(
+            Arc::new(consensus),
+            producer,
+            engine,
+            Arc::clone(&state.db),
+            tree,
+            Arc::clone(&state.mempool),
+        )));
+        state
+    }
+
+    async fn dev_rpc(router: &Router, method: &str, params: serde_json::Value) -> JsonRpcResponse {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": 1
+        });
+        let (_, body) = post_json(router, "/rpc", body).await;
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn dev_mine_rpc(router: &Router, params: serde_json::Value) -> JsonRpcResponse {
+        dev_rpc(router, "nova_devMine", params).await
+    }
+
+    #[tokio::test]
+    async fn dev_mine_produces_blocks_on_demand() {
+        let state = dev_app_state();
+        let db = Arc::clone(&state.db);
+        let router = create_router(state);
+
+        let resp = dev_mine_rpc(&router, serde_json::json!([3])).await;
+        let hashes: Vec<BlockHash> = serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(3));
+        assert_eq!(db.get_block(3).unwrap().unwrap().header.hash, hashes[2]);
+
+        let (status, body) =
+            post_json(&router, "/dev/mine", serde_json::json!({ "blocks": 2 })).await;
+        assert_eq!(status, StatusCode::OK);
+        let resp: DevMineResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.hashes.len(), 2);
+        assert_eq!(resp.height, 5);
+
+        let resp = dev_mine_rpc(&router, serde_json::json!([MAX_DEV_MINE_BLOCKS + 1])).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(5));
+    }
+
+    // -- 26. Dev controls are invisible outside dev mode -------------------
+
+    #[tokio::test]
+    async fn dev_controls_absent_outside_dev_mode() {
+        let router = create_router(test_app_state_with_genesis());
+
+        let resp = dev_mine_rpc(&router, serde_json::json!([1])).await;
+        assert_eq!(resp.error.unwrap().code, -32601);
+
+        let (status, _) = post_json(&router, "/dev/mine", serde_json::json!({})).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    // -- 27. Time travel moves block timestamps forward only -----------------
+
+    #[tokio::test]
+    async fn dev_time_travel_stamps_blocks() {
+        let state = dev_app_state();
+        let db = Arc::clone(&state.db);
+        let router = create_router(state);
+        let target = 4_102_444_800_000; // 2100-01-01
+
+        let resp = dev_rpc(&router, "nova_devSetTimestamp", serde_json::json!([target])).await;
+        assert_eq!(resp.result.unwrap(), target);
+        dev_mine_rpc(&router, serde_json::json!([1])).await;
+        assert_eq!(db.get_block(1).unwrap().unwrap().header.timestamp, target);
+
+        let resp = dev_rpc(&router, "nova_devSetTimestamp", serde_json::json!([target])).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+
+        let resp = dev_rpc(&router, "nova_devIncreaseTime", serde_json::json!([60_000])).await;
+        let next = resp.result.unwrap().as_u64().unwrap();
+        assert!(next >= target + 60_000);
+        dev_mine_rpc(&router, serde_json::json!([1])).await;
+        assert!(db.get_block(2).unwrap().unwrap().header.timestamp >= target + 60_000);
+
+        let resp = dev_rpc(&router, "nova_devIncreaseTime", serde_json::json!(["soon"])).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 28. Snapshots restore blocks, state and the mempool -----------------
+
+    #[tokio::test]
+    async fn dev_snapshot_and_revert_reset_the_chain() {
+        let state = dev_app_state();
+        let db = Arc::clone(&state.db);
+        let committed = Arc::clone(&state.committed_state);
+        let router = create_router(state);
+
+        dev_mine_rpc(&router, serde_json::json!([2])).await;
+        let root_at_2 = committed.root();
+        let resp = dev_rpc(&router, "nova_devSnapshot", serde_json::json!([])).await;
+        let first = resp.result.unwrap().as_u64().unwrap();
+
+        dev_mine_rpc(&router, serde_json::json!([3])).await;
+        let resp = dev_rpc(&router, "nova_devSnapshot", serde_json::json!([])).await;
+        let second = resp.result.unwrap().as_u64().unwrap();
+        dev_mine_rpc(&router, serde_json::json!([1])).await;
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(6));
+
+        let resp = dev_rpc(&router, "nova_devRevert", serde_json::json!([first])).await;
+        assert_eq!(resp.result.unwrap(), true);
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(2));
+        assert!(db.get_block(3).unwrap().is_none());
+        assert_eq!(committed.height(), 2);
+        assert_eq!(committed.root(), root_at_2);
+
+        // The chain continues from the restored tip.
+        let resp = dev_mine_rpc(&router, serde_json::json!([1])).await;
+        let hashes: Vec<BlockHash> = serde_json::from_value(resp.result.unwrap()).unwrap();
+        let block = db.get_block(3).unwrap().unwrap();
+        assert_eq!(block.header.hash, hashes[0]);
+        assert_eq!(
+            block.header.parent_hash,
+            db.get_block(2).unwrap().unwrap().header.hash
+        );
+
+        // Reverting discards the snapshot and every later one.
+        for id in [first, second] {
+            let resp = dev_rpc(&router, "nova_devRevert", serde_json::json!([id])).await;
+            assert_eq!(resp.error.unwrap().code, -32001);
+        }
+    }
+
+    // -- 29. Admin changes are recorded in the audit log ---------------------
+
+    #[tokio::test]
+    async fn admin_changes_are_audited() {
+        use nova_protocol::storage::audit::{verify_chain, AuditExport};
+
+        let router = create_router(test_app_state());
+        let body = serde_json::json!({
+            "peer_id": "12D3KooWPeer",
+            "address": "/ip4/10.0.0.1/tcp/9740",
+            "banned": true
+        });
+        post_json(&router, "/admin/peers", body).await;
+        let req = Request::builder()
+            .method("DELETE")
+            .uri("/admin/peers/12D3KooWPeer")
+            .body(Body::empty())
+            .unwrap();
+        router.clone().oneshot(req).await.unwrap();
+        // Reads are not audited.
+        get(&router, "/admin/peers").await;
+
+        let (status, body) = get(&router, "/admin/audit").await;
+        assert_eq!(status, StatusCode::OK);
+        let export: AuditExport = serde_json::from_slice(&body).unwrap();
+        let actions: Vec<_> = export.entries.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, ["peer_upserted", "peer_removed"]);
+        assert!(export.entries[0].details.contains("banned=true"));
+        assert!(export
+            .entries
+            .iter()
+            .all(|e| e.category == AuditCategory::Admin));
+        assert_eq!(export.head.len, 2);
+        assert!(verify_chain(&export.entries, Some(&export.head)).is_ok());
+    }
+
+    // -- 30. Screened submissions are refused with the address ---------------
+
+    #[tokio::test]
+    async fn screened_submission_is_rejected_and_audited() {
+        use nova_protocol::network::screening::{Screener, ScreeningAction, StaticListProvider};
+
+        let mut state = test_app_state_with_genesis();
+        let list = StaticListProvider::new("sdn", [("nova1bob", "SDN-3")]);
+        let screener = Screener::new(Arc::new(list), ScreeningAction::Block)
+            .with_audit_log(Arc::clone(&state.audit));
+        state.mempool = Arc::new(Mempool::default().with_screener(Arc::new(screener)));
+        let audit = Arc::clone(&state.audit);
+        let kp = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let sender =
+            nova_protocol::identity::NovaId::from_public_key(&kp.public_key()).to_address();
+        let mut live = live_tree(&state);
+        live.put(&sender, &AccountState::with_balance(10_000));
+        live.commit(1);
+        let router = create_router(state);
+
+        let resp = send_tx(&router, serde_json::json!([signed_transfer(&kp, 1)])).await;
+        let err = resp.error.expect("rejected");
+        assert_eq!(err.code, -32003);
+        let data = err.data.unwrap();
+        assert_eq!(data["reason"], "screened");
+        assert_eq!(data["address"], "nova1bob");
+
+        let entries = audit.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].category, AuditCategory::Screening);
+        assert_eq!(entries[0].action, "blocked");
+    }
+
+    // -- 31. Signed messages verify over RPC --------------------------------
+
+    #[tokio::test]
+    async fn verify_message_reports_validity() {
+        let router = create_router(test_app_state());
+        let kp = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let signed = SignedMessage::sign(&kp, "example.com login\nNonce: 7");
+        let verify = |params: serde_json::Value| {
+            let router = router.clone();
+            async move {
+                let body = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "nova_verifyMessage",
+                    "params": params,
+                    "id": 1
+                });
+                let (_, body) = post_json(&router, "/rpc", body).await;
+                serde_json::from_slice::<JsonRpcResponse>(&body).unwrap()
+            }
+        };
+
+        let resp = verify(serde_json::json!([signed])).await;
+        let result = resp.result.expect("verified");
+        assert_eq!(result["valid"], true);
+        assert_eq!(result["address"], signed.address);
+
+        let forged = SignedMessage {
+            message: "example.com login\nNonce: 8".into(),
+            ..signed
+        };
+        let result = verify(serde_json::json!([forged])).await.result.unwrap();
+        assert_eq!(result["valid"], false);
+        assert_eq!(result["reason"], "signature verification failed");
+
+        let resp = verify(serde_json::json!(["nope"])).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 32. NTP messages relay through the node's mailboxes ----------------
+
+    #[tokio::test]
+    async fn ntp_messages_reach_local_mailboxes() {
+        assert_eq!(
+            get(&create_router(test_app_state()), "/ntp/peer").await.0,
+            StatusCode::NOT_FOUND
+        );
+
+        let keypair = nova_protocol::network::direct::peer_keypair(
+            &nova_protocol::crypto::keys::NovaKeypair::generate(),
+        )
+        .unwrap();
+        let (direct, _outbound) = DirectService::new(&keypair);
+        let router = create_router(AppState {
+            direct: Some(Arc::new(direct)),
+            ..test_app_state()
+        });
+
+        let (status, body) = get(&router, "/ntp/peer").await;
+        assert_eq!(status, StatusCode::OK);
+        let peer: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(peer["protocol"], "/nova/ntp/1.0");
+        assert!(peer["addresses"].as_array().unwrap().is_empty());
+        let (_, body) = get(&router, "/node").await;
+        let node: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(node["nat"]["reachability"], "unknown");
+
+        let message = DirectMessage::handshake("inbox-7f3a", b"hello".to_vec(), 1);
+        let send = serde_json::json!({ "peer_id": peer["peer_id"], "message": message });
+        let (status, body) = post_json(&router, "/ntp/messages", send).await;
+        assert_eq!(status, StatusCode::OK);
+        let sent: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(sent["delivery"], "local");
+
+        let (_, body) = get(&router, "/ntp/messages/inbox-7f3a").await;
+        let inbox: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(inbox["messages"][0]["kind"], "handshake");
+        assert_eq!(inbox["messages"][0]["payload"], "0x68656c6c6f");
+        let (_, body) = get(&router, "/ntp/messages/inbox-7f3a").await;
+        let inbox: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(inbox["messages"], serde_json::json!([]));
+
+        let bad = serde_json::json!({ "peer_id": "nope", "message": message });
+        assert_eq!(
+            post_json(&router, "/ntp/messages", bad).await.0,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    // -- 33. Build provenance ----------------------------------------------
+
+    #[tokio::test]
+    async fn node_endpoint_reports_build_provenance() {
+        let (status, body) = get(&create_router(test_app_state()), "/node").await;
+        assert_eq!(status, StatusCode::OK);
+        let node: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(node["network"], "devnet");
+        assert_eq!(node["build"]["node"], env!("CARGO_PKG_VERSION"));
+        assert!(node["build"]["lockfile_sha256"].is_string());
+        assert!(node["build"].get("commit").is_some());
+        assert!(node["build"]["binary_sha256"].is_string());
+        assert!(node["nat"].is_null());
+    }
+
+    // -- 34. Update check in /status ---------------------------------------
+
+    #[tokio::test]
+    async fn status_reports_required_updates() {
+        let (_, body) = get(&create_router(test_app_state()), "/status").await;
+        let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(status.get("update").is_none());
+
+        let release_key = nova_protocol::crypto::keys::NovaKeypair::from_seed(&[42u8; 32]);
+        let checker = UpdateChecker::new(
+            "http://updates.invalid/devnet.json",
+            "devnet",
+            &[release_key.public_key().to_hex()],
+        )
+        .unwrap();
+        let manifest = crate::update::ReleaseManifest {
+            network: "devnet".into(),
+            latest_version: "99.0.0".into(),
+            min_node_version: "98.0.0".into(),
+            min_protocol_version: "0.0.1".into(),
+            critical: true,
+            issued_at: 1_790_000_000,
+            url: Some("https://example.com/releases/99.0.0".into()),
+        };
+        let signed = crate::update::SignedManifest::sign(&release_key, &manifest);
+        checker
+            .accept(&serde_json::to_string(&signed).unwrap(), 1)
+            .unwrap();
+
+        let router = create_router(AppState {
+            updates: Some(Arc::new(checker)),
+            ..test_app_state()
+        });
+        let (_, body) = get(&router, "/status").await;
+        let status: StatusResponse = serde_json::from_slice(&body).unwrap();
+        let update = status.update.expect("update report");
+        assert_eq!(update.running, env!("CARGO_PKG_VERSION"));
+        assert!(matches!(
+            update.status,
+            crate::update::UpdateStatus::Required { .. }
+        ));
+    }
+
+    // -- 35. Emergency halt and resume through the admin API ---------------
+
+    #[tokio::test]
+    async fn admin_halt_and_resume() {
+        let state = test_app_state();
+        let validator = nova_protocol::crypto::keys::NovaKeypair::from_seed(&[7u8; 32]);
+        let mut set = ValidatorSet::new();
+        set.add_validator(validator.public_key().to_hex(), 1_000);
+        let mut tree = live_tree(&state);
+        set.store_in_state(&mut tree);
+        state.committed_state.commit(0, tree.root());
+        let router = create_router(state.clone());
+
+        let (_, body) = get(&router, "/halt").await;
+        let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(status["status"], "running");
+
+        let mut halt = EmergencyHalt::new(10, "state root mismatch");
+        let (status, _) =
+            post_json(&router, "/admin/halt", serde_json::to_value(&halt).unwrap()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        halt.sign(&validator);
+        let (status, body) =
+            post_json(&router, "/admin/halt", serde_json::to_value(&halt).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(status["status"], "halted");
+        assert_eq!(status["halt_height"], 10);
+        assert!(state.halt.check(10).is_err());
+
+        let mut resume = ResumeAction::new(11, nova_protocol::config::PROTOCOL_VERSION);
+        resume.sign(&validator);
+        let (status, _) = post_json(
+            &router,
+            "/admin/resume",
+            serde_json::to_value(&resume).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let mut resume = ResumeAction::new(10, nova_protocol::config::PROTOCOL_VERSION);
+        resume.sign(&validator);
+        let (status, _) = post_json(
+            &router,
+            "/admin/resume",
+            serde_json::to_value(&resume).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(state.halt.check(10).is_ok());
+
+        let (_, body) = get(&router, "/halt").await;
+        let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(status["status"], "resumed");
+
+        let (_, body) = get(&router, "/admin/audit").await;
+        let audit = String::from_utf8(body).unwrap();
+        assert!(audit.contains("chain_halted") && audit.contains("chain_resumed"));
+    }
+
+    // -- 36. Validator set reports jailed validators -----------------------
+
+    #[tokio::test]
+    async fn validators_report_liveness() {
+        use nova_protocol::network::liveness::record_liveness;
+
+        let state = test_app_state();
+        let router = create_router(state.clone());
+        let (_, body) = get(&router, "/validators").await;
+        assert_eq!(body, b"[]");
+
+        let mut set = ValidatorSet::new();
+        set.add_validator("aa".repeat(32), 2_000);
+        set.add_validator("bb".repeat(32), 1_000);
+        let mut tree = live_tree(&state);
+        set.store_in_state(&mut tree);
+
+        // "aa.." proposes twice in a row, so "bb.." missed its slot.
+        let policy = LivenessPolicy {
+            window: 1,
+            ..LivenessPolicy::default()
+        };
+        let aa = "aa".repeat(32);
+        let jailed = record_liveness(&mut tree, 5, &aa, Some(&aa), &[], &policy);
+        assert_eq!(jailed, vec!["bb".repeat(32)]);
+        state.committed_state.commit(5, tree.root());
+
+        let (status, body) = get(&router, "/validators").await;
+        assert_eq!(status, StatusCode::OK);
+        let validators: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(validators.len(), 2);
+        assert_eq!(validators[0]["jailed"], false);
+        assert_eq!(validators[1]["active"], false);
+        assert_eq!(validators[1]["jailed"], true);
+        assert_eq!(
+            validators[1]["unjail_height"],
+            5 + nova_protocol::config::JAIL_COOLDOWN_BLOCKS
+        );
+    }
+
+    // -- 37. Treasury reports balance and paid spends ----------------------
+
+    #[tokio::test]
+    async fn treasury_reports_balance_and_spends() {
+        use nova_protocol::network::treasury::{apply_treasury_spend, SpendProposal};
+        use nova_protocol::storage::fee::credit_treasury;
+
+        let state = test_app_state();
+        let router = create_router(state.clone());
+        let (status, body) = get(&router, "/treasury").await;
+        assert_eq!(status, StatusCode::OK);
+        let empty: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(empty["balance"], 0);
+        assert_eq!(empty["spends"], serde_json::json!([]));
+
+        let keypair = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let mut set = ValidatorSet::new();
+        set.add_validator(keypair.public_key().to_hex(), 1_000);
+        let mut tree = live_tree(&state);
+        set.store_in_state(&mut tree);
+        credit_treasury(&mut tree, 10_000);
+
+        let mut proposal = SpendProposal::new("grant-1", "nova1grantee", 3_000, "wallet SDK");
+        proposal.sign(&keypair);
+        let tx = TransactionBuilder::new(TransactionType::TreasurySpend)
+            .sender("nova1submitter")
+            .receiver("nova1grantee")
+            .amount(Amount::new(3_000, Currency::NOVA))
+            .nonce(1)
+            .payload(serde_json::to_vec(&proposal).unwrap())
+            .build_unchecked();
+        apply_treasury_spend(&mut tree, &tx, 9).unwrap();
+        state.committed_state.commit(9, tree.root());
+
+        let (_, body) = get(&router, "/treasury").await;
+        let treasury: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(treasury["balance"], 7_000);
+        assert_eq!(treasury["total_spent"], 3_000);
+        assert_eq!(treasury["spends"][0]["id"], "grant-1");
+        assert_eq!(treasury["spends"][0]["height"], 9);
+    }
+
+    // -- 38. nova_getSupply reports circulating and projected supply -------
+
+    #[tokio::test]
+    async fn rpc_get_supply() {
+        let mut state = test_app_state();
+        state.monetary_policy = MonetaryPolicy {
+            initial_supply: 1_000_000,
+            epoch_blocks: 10,
+            initial_epoch_emission: 1_000,
+            decay_interval_epochs: 0,
+            decay_bps: 0,
+            tail_epoch_emission: 0,
+        };
+        let mut tree = live_tree(&state);
+        SupplyRecord {
+            minted: 500,
+            burned: 20,
+            ..SupplyRecord::default()
+        }
+        .store(&mut tree);
+        state.committed_state.commit(3, tree.root());
+        let router = create_router(state);
+
+        let rpc = |params: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "nova_getSupply",
+                "params": params,
+                "id": 1
+            })
+        };
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        let supply = resp.result.unwrap();
+        assert_eq!(supply["circulating"], 1_000_480);
+        assert_eq!(supply["block_reward"], 100);
+        // Blocks 4..=10 of epoch 0 are still to come.
+        assert_eq!(supply["projected_height"], 10);
+        assert_eq!(supply["projected_supply"], 1_001_180);
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([20]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.result.unwrap()["projected_supply"], 1_002_180);
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!(["soon"]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 39. nova_getNonce skips nonces already pending ----------------------
+
+    #[tokio::test]
+    async fn rpc_get_nonce() {
+        let state = test_app_state();
+        let mut live = live_tree(&state);
+        let mut account = AccountState::with_balance(10_000);
+        account.nonce = 2;
+        live.put("nova1alice", &account);
+        live.commit(4);
+        for nonce in [3, 4, 6] {
+            let tx = TransactionBuilder::new(TransactionType::Transfer)
+                .sender("nova1alice")
+                .receiver("nova1bob")
+                .amount(Amount::new(500, Currency::NOVA))
+                .nonce(nonce)
+                .build_unchecked();
+            state.mempool.add(tx).expect("admit");
+        }
+        let router = create_router(state);
+
+        let rpc = |params: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "nova_getNonce",
+                "params": params,
+                "id": 1
+            })
+        };
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!(["nova1alice"]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        let nonce = resp.result.unwrap();
+        assert_eq!(nonce["nonce"], 2);
+        assert_eq!(nonce["pending"], 2);
+        // 6 is queued behind a gap and does not count.
+        assert_eq!(nonce["next_nonce"], 5);
+        assert_eq!(nonce["height"], 4);
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!(["nova1carol"]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.result.unwrap()["next_nonce"], 1);
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 40. GET /supply reports genesis, locked and staked funds ------------
+
+    #[tokio::test]
+    async fn supply_endpoint_reports_locked_and_staked() {
+        let state = test_app_state();
+        let mut tree = live_tree(&state);
+        nova_protocol::storage::emission::fund_genesis_account(&mut tree, "nova1alice", 10_000);
+        let mut set = ValidatorSet::new();
+        set.add_validator("aa".repeat(32), 2_500);
+        set.store_in_state(&mut tree);
+        let mut record = SupplyRecord::load(&tree);
+        record.locked = 700;
+        record.store(&mut tree);
+        tree.commit(1);
+        let router = create_router(state);
+
+        let (status, body) = get(&router, "/supply").await;
+        assert_eq!(status, StatusCode::OK);
+        let supply: SupplyResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(supply.height, 1);
+        assert_eq!(supply.genesis, 10_000);
+        assert_eq!(supply.locked, 700);
+        assert_eq!(supply.staked, 2_500);
+    }
+
+    // -- 41. GET /peers lists gossipsub scores best first -------------------
+
+    #[tokio::test]
+    async fn peers_endpoint_lists_scores() {
+        use nova_protocol::network::scoring::{PeerScore, PeerStanding};
+
+        let state = test_app_state();
+        let score = |peer_id: &str, score: f64, standing| PeerScore {
+            peer_id: peer_id.to_string(),
+            score,
+            application_score: 0.0,
+            standing,
+        };
+        state.peer_scores.replace([
+            score("12D3KooWBad", -95.0, PeerStanding::Graylisted),
+            score("12D3KooWGood", 14.0, PeerStanding::Good),
+        ]);
+        let router = create_router(state);
+
+        let (status, body) = get(&router, "/peers").await;
+        assert_eq!(status, StatusCode::OK);
+        let peers: Vec<PeerScore> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].peer_id, "12D3KooWGood");
+        assert_eq!(peers[1].standing, PeerStanding::Graylisted);
+    }
+
+    // -- 42. Block endpoint reports the fees collected ----------------------
+
+    #[tokio::test]
+    async fn block_endpoint_reports_fees() {
+        let state = test_app_state_with_genesis();
+        let block = Block::new(
+            &Block::genesis(),
+            vec![make_test_tx(1)],
+            "nova:val".to_string(),
+            [1u8; 32],
+        )
+        .with_fees(250);
+        state.db.put_block(&block).unwrap();
+        let router = create_router(state);
+
+        let (status, body) = get(&router, "/blocks/1").await;
+        assert_eq!(status, StatusCode::OK);
+        let resp: BlockResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.fees, 250);
+
+        let (_, body) = get(&router, "/blocks/0").await;
+        let resp: BlockResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.fees, 0);
+    }
+
+    // -- 43. Transaction endpoint returns the memo ---------------------------
+
+    #[tokio::test]
+    async fn transaction_endpoint_returns_memo() {
+        let state = test_app_state_with_genesis();
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(500, Currency::NOVA))
+            .fee(10)
+            .nonce(1)
+            .timestamp(1_000_000)
+            .memo("order-1042")
+            .build_unchecked();
+        state.db.put_transaction(&tx).expect("persist tx");
+
+        let router = create_router(state);
+        let (_, body) = get(&router, &format!("/transactions/{}", tx.id)).await;
+        let resp: TransactionResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.memo.as_deref(), Some("order-1042"));
+    }
+
+    // -- 44. nova_estimateFee quotes fee tiers from recent blocks -----------
+
+    #[tokio::test]
+    async fn rpc_estimate_fee() {
+        let state = test_app_state();
+        // Ten full blocks whose cheapest fees are 100, 200, ..., 1000.
+        for i in 1..=10 {
+            let block: Vec<_> = (1..=10)
+                .map(|nonce| {
+                    TransactionBuilder::new(TransactionType::Transfer)
+                        .sender("nova1alice")
+                        .receiver("nova1bob")
+                        .amount(Amount::new(500, Currency::NOVA))
+                        .fee(i * 100)
+                        .nonce(nonce)
+                        .build_unchecked()
+                })
+                .collect();
+            state.fee_estimator.record_block(&block);
+        }
+        let router = create_router(state);
+
+        let rpc = |params: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "nova_estimateFee",
+                "params": params,
+                "id": 1
+            })
+        };
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        let estimate = resp.result.unwrap();
+        assert_eq!(estimate["priority"], "normal");
+        assert_eq!(estimate["estimated_fee"], 500);
+        assert_eq!(estimate["sample_blocks"], 10);
+        assert_eq!(estimate["tiers"]["economy"]["fee"], 200);
+        assert_eq!(estimate["tiers"]["standard"]["expected_blocks"], 2.0);
+        assert!(estimate["tiers"]["fast"]["blocks_high"].is_number());
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!(["fast"]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.result.unwrap()["estimated_fee"], 900);
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!(["urgent"]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 45. Committed transactions have receipts ---------------------------
+
+    #[tokio::test]
+    async fn transaction_receipts() {
+        use nova_protocol::transaction::receipt::{BlockInfo, LogKind, ReceiptLog};
+
+        let state = test_app_state_with_genesis();
+        let tx = make_test_tx(1);
+        let info = BlockInfo {
+            height: 1,
+            hash: "ab".repeat(32),
+            timestamp: 1_700_000_000_000,
+        };
+        let receipt =
+            TransactionReceipt::from_transaction(&tx, &info, TransactionStatus::Confirmed)
+                .with_execution(
+                    tx.fee,
+                    vec![ReceiptLog::new(LogKind::Fee, &tx.sender, tx.fee)],
+                );
+        state
+            .db
+            .put_receipts(std::slice::from_ref(&receipt))
+            .unwrap();
+        let router = create_router(state);
+
+        let (status, body) = get(&router, &format!("/receipts/0x{}", tx.id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let served: TransactionReceipt = serde_json::from_slice(&body).unwrap();
+        assert_eq!(served, receipt);
+        assert!(served.verify_integrity());
+
+        let (status, _) = get(&router, "/receipts/deadbeef").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let rpc = |params: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "nova_getTransactionReceipt",
+                "params": params,
+                "id": 1
+            })
+        };
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([tx.id]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        let result = resp.result.unwrap();
+        assert_eq!(result["status"], "Confirmed");
+        assert_eq!(result["logs"][0]["kind"], "fee");
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!(["deadbeef"]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32001);
+
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 46. nova_cancelTransaction drops pending transactions -------------
+
+    #[tokio::test]
+    async fn cancel_transaction_requires_the_sender() {
+        let state = test_app_state_with_genesis();
+        let mempool = Arc::clone(&state.mempool);
+        let mut events = state.event_tx.subscribe();
+        let kp = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let tx = signed_transfer(&kp, 1);
+        mempool.add(tx.clone()).unwrap();
+        let router = create_router(state);
+
+        let cancel = |cancellation: TransactionCancellation| {
+            let router = router.clone();
+            async move {
+                let body = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "nova_cancelTransaction",
+                    "params": [cancellation],
+                    "id": 1
+                });
+                let (_, body) = post_json(&router, "/rpc", body).await;
+                serde_json::from_slice::<JsonRpcResponse>(&body).unwrap()
+            }
+        };
+
+        let stranger = nova_protocol::crypto::keys::NovaKeypair::generate();
+        let resp = cancel(TransactionCancellation::sign(&tx.id, &stranger)).await;
+        let err = resp.error.expect("rejected");
+        assert_eq!(err.code, -32003);
+        assert_eq!(err.data.unwrap()["reason"], "invalid_cancellation");
+        assert!(mempool.contains(&tx.id));
+
+        let cancellation = TransactionCancellation::sign(&tx.id, &kp);
+        let resp = cancel(cancellation.clone()).await;
+        assert!(resp.error.is_none(), "{:?}", resp.error);
+        assert_eq!(resp.result.unwrap(), serde_json::json!(tx.id));
+        assert!(!mempool.contains(&tx.id));
+        match events.try_recv().unwrap() {
+            NodeEvent::TransactionCancelled {
+                hash, signature, ..
+            } => {
+                assert_eq!(hash, tx.id);
+                assert_eq!(signature, cancellation.signature);
+            }
+            other => panic!("expected TransactionCancelled, got {:?}", other),
+        }
+
+        let resp = cancel(cancellation).await;
+        assert_eq!(resp.error.unwrap().code, -32001);
+    }
+
+    // -- 47. nova_getCapabilities reports the node's features ---------------
+
+    #[tokio::test]
+    async fn rpc_get_capabilities() {
+        let router = create_router(AppState {
+            capabilities: Capabilities::compiled().with(Capabilities::DEV_RPC, true),
+            ..test_app_state()
+        });
+        let (_, body) = post_json(
+            &router,
+            "/rpc",
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "nova_getCapabilities",
+                "id": 1
+            }),
+        )
+        .await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        let caps: Capabilities = serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(
+            caps.protocol_version,
+            nova_protocol::config::PROTOCOL_VERSION
+        );
+        assert!(caps.supports(Capabilities::DEV_RPC));
+        assert!(caps.supports(Capabilities::NONCE_RPC));
+        assert!(!caps.supports(Capabilities::NTP_MESSAGING));
+    }
+
+    // -- 48. Binary frames answer the same methods as POST /rpc --------------
+
+    #[tokio::test]
+    async fn binary_frames_dispatch_rpc_methods() {
+        use crate::binary_rpc::{self, BinaryRequest, FRAME_REQUEST, FRAME_RESPONSE};
+
+        let state = test_app_state_with_genesis();
+        let request = |method: &str| {
+            binary_rpc::encode_frame(
+                FRAME_REQUEST,
+                &BinaryRequest {
+                    id: serde_json::json!(42),
+                    method: method.into(),
+                    params: None,
+                },
+            )
+        };
+
+        let reply = binary_rpc::answer(&state, &request("nova_networkId")).await;
+        let (version, kind, resp): (u16, u8, JsonRpcResponse) =
+            binary_rpc::decode_frame(&reply).unwrap();
+        assert_eq!(version, encoding::SCHEMA_VERSION);
+        assert_eq!(kind, FRAME_RESPONSE);
+        assert_eq!(resp.id, serde_json::json!(42));
+        assert_eq!(resp.result.unwrap(), serde_json::json!("devnet"));
+
+        let reply = binary_rpc::answer(&state, &request("nova_nope")).await;
+        let (_, _, resp): (u16, u8, JsonRpcResponse) = binary_rpc::decode_frame(&reply).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32601);
+
+        let reply = binary_rpc::answer(&state, &[1, 0, FRAME_REQUEST, 0xff]).await;
+        let (_, _, resp): (u16, u8, JsonRpcResponse) = binary_rpc::decode_frame(&reply).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32700);
+    }
+
+    // -- 49. Single mempool entries and nova_pendingTransactions -----------
+
+    #[tokio::test]
+    async fn mempool_entry_and_pending_transactions_rpc() {
+        let state = test_app_state();
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(500, Currency::NOVA))
+            .fee(25_000)
+            .nonce(1)
+            .build_unchecked();
+        state.mempool.add(tx.clone()).expect("admit");
+        let router = create_router(state);
+
+        let (status, body) = get(&router, &format!("/mempool/0x{}", tx.id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let entry: PendingTransaction = serde_json::from_slice(&body).unwrap();
+        assert_eq!(entry.hash, tx.id);
+        assert_eq!(entry.sender, "nova1alice");
+        assert_eq!(entry.fee, 25_000);
+        assert_eq!(entry.fee_per_byte, tx.fee_per_byte());
+        assert!(entry.age_secs < 60);
+
+        let (status, _) = get(&router, &format!("/mempool/{}", "ab".repeat(32))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let rpc = |params: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "nova_pendingTransactions",
+                "params": params,
+                "id": 1
+            })
+        };
+        let (_, body) = post_json(&router, "/rpc", rpc(serde_json::json!([]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        let listing: PendingTransactionsResponse =
+            serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(listing.size, 1);
+        assert_eq!(listing.transactions[0].nonce, 1);
+
+        let (_, body) = post_json(
+            &router,
+            "/rpc",
+            rpc(serde_json::json!([{ "offset": 1, "limit": 10 }])),
+        )
+        .await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert!(resp.result.unwrap()["transactions"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+
+        let (_, body) =
+            post_json(&router, "/rpc", rpc(serde_json::json!([{ "limit": "x" }]))).await;
+        let resp: JsonRpcResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    // -- 50. Mempool evictions become transaction_evicted events ------------
+
+    #[test]
+    fn mempool_evictions_become_events() {
+        let state = test_app_state_with_genesis();
+        let mut evictions = state.mempool.subscribe_evictions();
+        let mut tx = make_test_tx(4);
+        tx.valid_until = Some(2_000_000);
+        tx.id = tx.compute_id();
+        state.mempool.add(tx.clone()).expect("admit tx");
+        assert_eq!(state.mempool.evict_expired(u64::MAX), 1);
+
+        let event = NodeEvent::from(&evictions.try_recv().unwrap());
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "transaction_evicted");
+        assert_eq!(json["hash"], format!("0x{}", tx.id));
+        assert_eq!(json["sender"], "nova1alice");
+        assert_eq!(json["reason"], "expired");
+        // Only new_transaction events are filtered.
+        assert!(EventFilter {
+            sender: Some("nova1bob".into()),
+            ..Default::default()
+        }
+        .matches(&event));
+    }
 }
@@ -0,0 +1,371 @@
+//! # Local Benchmark
+//!
+//! `nova-node bench` answers the hardware-sizing question "how fast is
+//! this box?" without touching a real chain. It builds a throwaway dev
+//! chain in a temporary database, funds `--accounts` deterministic dev
+//! accounts, signs `--transactions` transfers between them and pushes
+//! every one through the same mempool → producer → commit pipeline the
+//! validator runs.
+//!
+//! The numbers are a smoke test, not a consensus benchmark: there is one
+//! validator, no network and no voting. They are still the part of block
+//! production that scales with the hardware — signature-bound generation
+//! aside, execution, state-root hashing and storage.
+//!
+//! ## Ordering
+//!
+//! Every sender has many transfers in flight at once, and the mempool
+//! serves them by fee. Fees therefore step down with the nonce so each
+//! block drains the oldest nonces first; otherwise a block would pick
+//! nonces at random and most of its candidates would fail as gaps.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
+use serde::Serialize;
+
+use nova_protocol::config::{MAX_TX_FEE_PHOTONS, MIN_TX_FEE_PHOTONS};
+use nova_protocol::crypto::keys::NovaKeypair;
+use nova_protocol::identity::NovaId;
+use nova_protocol::network::mempool::{Mempool, MempoolConfig};
+use nova_protocol::network::producer::BlockProducer;
+use nova_protocol::storage::block::Block;
+use nova_protocol::storage::db::NovaDB;
+use nova_protocol::storage::emission::fund_genesis_account;
+use nova_protocol::storage::state::StateTree;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+use nova_protocol::transaction::Transaction;
+
+use crate::cli::{BenchArgs, OutputFormat};
+use crate::logging::{self, LogFormat};
+
+/// Photons moved by each benchmark transfer.
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+/// Balance of each benchmark account (1M NOVA) — enough for any run.
+const ACCOUNT_BALANCE: u64 = 100_000_000_000_000;
+
+/// Largest fee step between consecutive nonces of one sender.
+const MAX_FEE_STEP: u64 = 1_000;
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Distribution of a set of durations, in milliseconds. Shared with the
+/// load generator's confirmation latencies.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimingStats {
+    /// Shortest sample.
+    pub min: f64,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Median.
+    pub p50: f64,
+    /// 90th percentile.
+    pub p90: f64,
+    /// 99th percentile.
+    pub p99: f64,
+    /// Longest sample.
+    pub max: f64,
+}
+
+impl TimingStats {
+    /// Summarizes `samples`; all zero when there are none.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1e3).collect();
+        ms.sort_by(f64::total_cmp);
+        Self {
+            min: ms[0],
+            mean: ms.iter().sum::<f64>() / ms.len() as f64,
+            p50: percentile(&ms, 50.0),
+            p90: percentile(&ms, 90.0),
+            p99: percentile(&ms, 99.0),
+            max: ms[ms.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Result of a benchmark run.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    /// Transfers generated.
+    pub transactions: usize,
+    /// Accounts the transfers rotate through.
+    pub accounts: usize,
+    /// Blocks produced and committed.
+    pub blocks: usize,
+    /// Transfers that made it into a block.
+    pub included: usize,
+    /// Execution attempts that failed and were retried in a later block.
+    pub retried: usize,
+    /// Seconds spent building and signing transfers (not part of tx/sec).
+    pub signing_secs: f64,
+    /// Seconds spent in the mempool → producer → commit pipeline.
+    pub pipeline_secs: f64,
+    /// Included transfers per pipeline second.
+    pub tx_per_sec: f64,
+    /// Per-block produce + commit time.
+    pub block_time_ms: TimingStats,
+    /// Account writes made by block execution (sender, receiver, proposer).
+    pub state_writes: u64,
+    /// Account writes per second of block production, root hashing included.
+    pub state_writes_per_sec: f64,
+    /// Growth of the database on disk over the pipeline.
+    pub db_bytes_written: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Run
+// ---------------------------------------------------------------------------
+
+/// Runs the benchmark described by `args`.
+pub fn run(args: &BenchArgs) -> Result<BenchReport> {
+    if args.accounts < 2 {
+        bail!("--accounts must be at least 2 (got {})", args.accounts);
+    }
+    if args.transactions == 0 || args.block_size == 0 {
+        bail!("--transactions and --block-size must be positive");
+    }
+
+    // --- Temporary dev chain ---
+    let db = Arc::new(NovaDB::open_temporary().context("failed to open temporary database")?);
+    let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+    let mempool = Arc::new(Mempool::new(MempoolConfig {
+        max_size: args.transactions,
+        max_txs_per_sender: args.transactions,
+        max_bytes: usize::MAX,
+        ..MempoolConfig::default()
+    }));
+    let producer = BlockProducer::new(
+        Arc::clone(&db),
+        Arc::clone(&state_tree),
+        Arc::clone(&mempool),
+        NovaKeypair::generate(),
+    );
+
+    let accounts: Vec<(NovaKeypair, String)> = (1..=args.accounts as u64)
+        .map(|i| {
+            let kp = NovaKeypair::from_seed(&crate::generate_dev_seed(i));
+            let address = NovaId::from_public_key(&kp.public_key()).to_address();
+            (kp, address)
+        })
+        .collect();
+    {
+        let mut tree = state_tree.write();
+        for (_, address) in &accounts {
+            fund_genesis_account(&mut tree, address, ACCOUNT_BALANCE);
+        }
+    }
+    let genesis = Block::genesis();
+    db.put_block(&genesis)?;
+    state_tree.read().commit(0);
+
+    // --- Generate signed transfers ---
+    let started = Instant::now();
+    let txs = generate_transfers(&accounts, args.transactions);
+    let signing_secs = started.elapsed().as_secs_f64();
+
+    // --- Pipeline ---
+    db.flush()?;
+    let db_before = db.size_on_disk()?;
+    let started = Instant::now();
+
+    for tx in txs {
+        mempool
+            .add(tx)
+            .context("mempool rejected a benchmark transfer")?;
+    }
+
+    let mut parent = genesis;
+    let mut block_times = Vec::new();
+    let mut included = 0;
+    let mut retried = 0;
+    let mut state_writes = 0u64;
+    let mut produce_time = Duration::ZERO;
+
+    while !mempool.is_empty() {
+        let block_started = Instant::now();
+        let produced = producer.produce_block(&parent, args.block_size)?;
+        produce_time += block_started.elapsed();
+        producer.commit_block(&produced.block)?;
+        block_times.push(block_started.elapsed());
+
+        let count = produced.block.transactions.len();
+        if count == 0 {
+            bail!(
+                "block {} included no transactions with {} still pending",
+                produced.block.header.height,
+                mempool.size()
+            );
+        }
+        included += count;
+        retried += produced.tx_results.len() - count;
+        state_writes += 2 * count as u64 + 1;
+        parent = produced.block;
+    }
+
+    let pipeline_secs = started.elapsed().as_secs_f64();
+    db.flush()?;
+    let db_after = db.size_on_disk()?;
+
+    Ok(BenchReport {
+        transactions: args.transactions,
+        accounts: args.accounts,
+        blocks: block_times.len(),
+        included,
+        retried,
+        signing_secs,
+        pipeline_secs,
+        tx_per_sec: included as f64 / pipeline_secs.max(f64::EPSILON),
+        block_time_ms: TimingStats::from_samples(&block_times),
+        state_writes,
+        state_writes_per_sec: state_writes as f64 / produce_time.as_secs_f64().max(f64::EPSILON),
+        db_bytes_written: db_after.saturating_sub(db_before),
+    })
+}
+
+/// Builds `count` signed transfers, round-robin over `accounts`: the k-th
+/// transfer is sent by account `k % M` to its neighbour with nonce
+/// `k / M + 1`. Fees step down with the nonce (see the module docs).
+fn generate_transfers(accounts: &[(NovaKeypair, String)], count: usize) -> Vec<Transaction> {
+    let rounds = count.div_ceil(accounts.len()) as u64;
+    let fee_step = ((MAX_TX_FEE_PHOTONS - MIN_TX_FEE_PHOTONS) / rounds).clamp(1, MAX_FEE_STEP);
+
+    (0..count)
+        .map(|k| {
+            let sender = k % accounts.len();
+            let receiver = (sender + 1) % accounts.len();
+            let nonce = (k / accounts.len()) as u64 + 1;
+            let (kp, sender_addr) = &accounts[sender];
+
+            let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+                .sender(sender_addr)
+                .receiver(&accounts[receiver].1)
+                .amount(Amount::new(TRANSFER_AMOUNT, Currency::NOVA))
+                .fee(MIN_TX_FEE_PHOTONS + (rounds - nonce) * fee_step)
+                .nonce(nonce)
+                .build()
+                .expect("bench transfers are well-formed");
+            sign_transaction(&mut tx, kp);
+            tx
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+/// Prints `report` in the requested format.
+pub fn print_report(report: &BenchReport, output: OutputFormat) -> Result<()> {
+    if output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+
+    let t = &report.block_time_ms;
+    println!("Benchmark");
+    println!(
+        "  Workload     : {} transfers across {} accounts",
+        report.transactions, report.accounts
+    );
+    println!("  Signing      : {:.2} s", report.signing_secs);
+    println!(
+        "  Pipeline     : {:.2} s, {} blocks, {} included, {} retried",
+        report.pipeline_secs, report.blocks, report.included, report.retried
+    );
+    println!("  Throughput   : {:.0} tx/s", report.tx_per_sec);
+    println!(
+        "  Block time   : min {:.1} / p50 {:.1} / p90 {:.1} / p99 {:.1} / max {:.1} ms (mean {:.1})",
+        t.min, t.p50, t.p90, t.p99, t.max, t.mean
+    );
+    println!(
+        "  State tree   : {} account writes, {:.0} writes/s",
+        report.state_writes, report.state_writes_per_sec
+    );
+    println!(
+        "  DB written   : {} bytes ({:.2} MiB)",
+        report.db_bytes_written,
+        report.db_bytes_written as f64 / (1024.0 * 1024.0)
+    );
+    Ok(())
+}
+
+/// Entry point for `nova-node bench`.
+pub fn run_bench(args: BenchArgs) -> Result<()> {
+    // Producer logs at info level would drown the report.
+    logging::init_logging("nova_node=warn,nova_protocol=warn", LogFormat::Pretty);
+
+    if args.output == OutputFormat::Text {
+        println!(
+            "Running {} transfers across {} accounts (block size {})...",
+            args.transactions, args.accounts, args.block_size
+        );
+    }
+    let report = run(&args)?;
+    print_report(&report, args.output)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(transactions: usize, accounts: usize, block_size: usize) -> BenchArgs {
+        BenchArgs {
+            transactions,
+            accounts,
+            block_size,
+            output: OutputFormat::Json,
+        }
+    }
+
+    // -- 1. A small run includes every transfer ---------------------------
+
+    #[test]
+    fn small_run_includes_every_transfer() {
+        let report = run(&args(60, 5, 20)).unwrap();
+        assert_eq!(report.included, 60);
+        assert_eq!(report.blocks, 3);
+        assert_eq!(report.retried, 0, "fee ordering must drain nonces in order");
+        assert_eq!(report.state_writes, 2 * 60 + 3);
+        assert!(report.tx_per_sec > 0.0);
+        assert!(report.block_time_ms.min <= report.block_time_ms.max);
+    }
+
+    // -- 2. Bad arguments are rejected ------------------------------------
+
+    #[test]
+    fn rejects_degenerate_arguments() {
+        assert!(run(&args(10, 1, 10)).is_err());
+        assert!(run(&args(0, 5, 10)).is_err());
+        assert!(run(&args(10, 5, 0)).is_err());
+    }
+
+    // -- 3. Percentiles ---------------------------------------------------
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let samples: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&samples, 50.0), 50.0);
+        assert_eq!(percentile(&samples, 99.0), 99.0);
+        assert_eq!(percentile(&[7.0], 90.0), 7.0);
+    }
+}
@@ -0,0 +1,287 @@
+//! # Binary RPC over WebSocket
+//!
+//! `GET /ws/binary` carries the JSON-RPC methods of `POST /rpc` and the
+//! events of `GET /ws` on one connection, CBOR-encoded, for clients that
+//! poll hard enough for JSON parsing to show up in their profiles.
+//!
+//! ## Negotiation
+//!
+//! The client must offer the [`SUBPROTOCOL`] in `Sec-WebSocket-Protocol`;
+//! the node echoes it back. Upgrades without it are refused with 400, so a
+//! client never mistakes a JSON socket for a binary one. A new frame layout
+//! gets a new subprotocol name, and a node advertises the transport as the
+//! `binary_rpc` capability.
+//!
+//! ## Frames
+//!
+//! Every WebSocket binary message is one frame:
+//!
+//! ```text
+//! schema version (u16, little-endian) | kind (u8) | CBOR body
+//! ```
+//!
+//! | Kind | Direction       | Body                                         |
+//! |------|-----------------|----------------------------------------------|
+//! | 1    | client → node   | `{id, method, params}`                       |
+//! | 2    | node → client   | `{jsonrpc, id, result \| error, schema_version}` |
+//! | 3    | node → client   | a [`NodeEvent`], as on `/ws`                 |
+//!
+//! Bodies are the same documents the JSON transport sends — hashes are
+//! still hex strings, amounts still follow the wire encoding policy — so
+//! SDKs share one model for both. The schema version is the one the JSON
+//! transport puts in `schema_version`. The node answers requests written
+//! under its own or an older schema, and answers a newer one with error
+//! -32600 carrying its own `schema_version` in `data`. Unreadable frames
+//! get -32700. Requests on one connection are answered in order.
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use nova_protocol::encoding;
+
+use crate::api::{
+    self, AppState, ErrorResponse, EventFilter, JsonRpcError, JsonRpcRequest, JsonRpcResponse,
+};
+
+/// WebSocket subprotocol a client must offer to get binary frames.
+pub const SUBPROTOCOL: &str = "nova-rpc.cbor.v1";
+
+/// Frame kind of a client request.
+pub const FRAME_REQUEST: u8 = 1;
+/// Frame kind of the node's answer to a request.
+pub const FRAME_RESPONSE: u8 = 2;
+/// Frame kind of a pushed [`NodeEvent`](api::NodeEvent).
+pub const FRAME_EVENT: u8 = 3;
+
+/// Schema version and kind.
+const HEADER_LEN: usize = 3;
+
+/// Body of a [`FRAME_REQUEST`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryRequest {
+    /// Request identifier, echoed in the response.
+    pub id: serde_json::Value,
+    /// The method to invoke, as on `POST /rpc`.
+    pub method: String,
+    /// Method parameters.
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+}
+
+/// Encodes `body` as a frame of `kind` under the current schema version.
+pub fn encode_frame<T: Serialize>(kind: u8, body: &T) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(128);
+    frame.extend_from_slice(&encoding::SCHEMA_VERSION.to_le_bytes());
+    frame.push(kind);
+    ciborium::ser::into_writer(body, &mut frame).expect("CBOR encoding into memory cannot fail");
+    frame
+}
+
+/// Splits a frame into its schema version, kind and decoded body.
+pub fn decode_frame<T: DeserializeOwned>(frame: &[u8]) -> Result<(u16, u8, T), String> {
+    if frame.len() < HEADER_LEN {
+        return Err(format!(
+            "frame is {} bytes, shorter than its header",
+            frame.len()
+        ));
+    }
+    let version = u16::from_le_bytes([frame[0], frame[1]]);
+    let body = ciborium::de::from_reader(&frame[HEADER_LEN..]).map_err(|e| e.to_string())?;
+    Ok((version, frame[2], body))
+}
+
+/// Answers one frame from a client with a [`FRAME_RESPONSE`].
+pub async fn answer(state: &AppState, frame: &[u8]) -> Vec<u8> {
+    let response = match read_request(frame) {
+        Ok(req) => {
+            api::dispatch_rpc(
+                state,
+                JsonRpcRequest {
+                    jsonrpc: "2.0".into(),
+                    method: req.method,
+                    params: req.params,
+                    id: req.id,
+                },
+            )
+            .await
+        }
+        Err(error) => JsonRpcResponse {
+            jsonrpc: "2.0".into(),
+            result: None,
+            error: Some(error),
+            id: serde_json::Value::Null,
+            schema_version: encoding::SCHEMA_VERSION,
+        },
+    };
+    encode_frame(FRAME_RESPONSE, &response)
+}
+
+fn read_request(frame: &[u8]) -> Result<BinaryRequest, JsonRpcError> {
+    let parse_error = |reason: String| JsonRpcError {
+        code: -32700,
+        message: format!("Parse error: {}", reason),
+        data: None,
+    };
+    if frame.len() >= 2 {
+        let version = u16::from_le_bytes([frame[0], frame[1]]);
+        if version > encoding::SCHEMA_VERSION {
+            return Err(JsonRpcError {
+                code: -32600,
+                message: format!(
+                    "Invalid Request: schema version {} is newer than this node's {}",
+                    version,
+                    encoding::SCHEMA_VERSION
+                ),
+                data: Some(serde_json::json!({ "schema_version": encoding::SCHEMA_VERSION })),
+            });
+        }
+    }
+    let (_, kind, req) = decode_frame::<BinaryRequest>(frame).map_err(parse_error)?;
+    if kind != FRAME_REQUEST {
+        return Err(JsonRpcError {
+            code: -32600,
+            message: format!("Invalid Request: frame kind {} is not a request", kind),
+            data: None,
+        });
+    }
+    Ok(req)
+}
+
+/// Returns `true` if the upgrade request offers [`SUBPROTOCOL`].
+fn offers_subprotocol(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(SEC_WEBSOCKET_PROTOCOL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|protocol| protocol.trim() == SUBPROTOCOL)
+}
+
+/// `GET /ws/binary` — WebSocket upgrade for CBOR-framed RPC and events,
+/// filtered as on `/ws`.
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    Query(filter): Query<EventFilter>,
+    State(state): State<AppState>,
+) -> Response {
+    if !offers_subprotocol(&headers) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("binary RPC requires the {} subprotocol", SUBPROTOCOL),
+            }),
+        )
+            .into_response();
+    }
+    ws.protocols([SUBPROTOCOL])
+        .on_upgrade(move |socket| handle_connection(socket, state, filter))
+}
+
+/// Answers requests and forwards events that pass `filter` until the
+/// client disconnects.
+async fn handle_connection(mut socket: WebSocket, state: AppState, filter: EventFilter) {
+    let mut events = state.event_tx.subscribe();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Ok(ev) if !filter.matches(&ev) => {}
+                    Ok(ev) => {
+                        let body = match serde_json::to_value(&ev) {
+                            Ok(body) => body,
+                            Err(e) => {
+                                tracing::warn!("failed to encode binary ws event: {}", e);
+                                continue;
+                            }
+                        };
+                        let frame = encode_frame(FRAME_EVENT, &body);
+                        if socket.send(Message::Binary(frame)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("binary ws subscriber lagged by {} events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            msg = socket.recv() => {
+                match msg {
+                    Some(Ok(Message::Binary(frame))) => {
+                        let reply = answer(&state, &frame).await;
+                        if socket.send(Message::Binary(reply)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Text, ping and pong carry no requests.
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_carry_schema_version_and_kind() {
+        let req = BinaryRequest {
+            id: serde_json::json!(7),
+            method: "nova_blockHeight".into(),
+            params: None,
+        };
+        let frame = encode_frame(FRAME_REQUEST, &req);
+        assert_eq!(frame[..2], encoding::SCHEMA_VERSION.to_le_bytes());
+        assert_eq!(frame[2], FRAME_REQUEST);
+
+        let (version, kind, decoded): (u16, u8, BinaryRequest) = decode_frame(&frame).unwrap();
+        assert_eq!(version, encoding::SCHEMA_VERSION);
+        assert_eq!(kind, FRAME_REQUEST);
+        assert_eq!(decoded.method, "nova_blockHeight");
+        assert_eq!(decoded.id, serde_json::json!(7));
+
+        assert!(decode_frame::<BinaryRequest>(&frame[..2]).is_err());
+        assert!(read_request(&encode_frame(FRAME_EVENT, &req)).is_err());
+
+        let mut newer = frame.clone();
+        newer[..2].copy_from_slice(&(encoding::SCHEMA_VERSION + 1).to_le_bytes());
+        let err = read_request(&newer).unwrap_err();
+        assert_eq!(err.code, -32600);
+        assert_eq!(
+            err.data.unwrap()["schema_version"],
+            encoding::SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn upgrade_needs_the_subprotocol() {
+        let mut headers = HeaderMap::new();
+        assert!(!offers_subprotocol(&headers));
+        headers.insert(SEC_WEBSOCKET_PROTOCOL, "graphql-ws".parse().unwrap());
+        assert!(!offers_subprotocol(&headers));
+        headers.insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            format!("graphql-ws, {}", SUBPROTOCOL).parse().unwrap(),
+        );
+        assert!(offers_subprotocol(&headers));
+    }
+}
@@ -0,0 +1,101 @@
+//! # Capability Negotiation
+//!
+//! CLI commands that talk to a node ask it what it supports
+//! (`nova_getCapabilities`, see [`nova_protocol::network::capabilities`])
+//! before relying on a feature:
+//!
+//! - transaction types the node may not know are refused locally with a
+//!   clear message instead of an opaque rejection;
+//! - nonces come from `nova_getNonce`, which counts pending transactions,
+//!   where the node offers it, and from the committed account state
+//!   otherwise.
+//!
+//! Nodes too old to answer `nova_getCapabilities` read as supporting
+//! nothing optional.
+
+use anyhow::{bail, Context, Result};
+
+use nova_protocol::network::capabilities::Capabilities;
+
+/// Asks the node at `rpc_url` what it supports.
+pub async fn fetch(rpc_url: &str) -> Result<Capabilities> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "nova_getCapabilities",
+        "id": 1,
+    })
+    .to_string();
+    let response = crate::reqwest_post_json_stub(&format!("{}/rpc", rpc_url), &body).await?;
+    parse(&response)
+}
+
+/// Reads a `nova_getCapabilities` response. "Method not found" means a
+/// node that predates capabilities, which supports none of them.
+fn parse(body: &str) -> Result<Capabilities> {
+    let json: serde_json::Value =
+        serde_json::from_str(body).context("node returned a non-JSON capabilities response")?;
+    if let Some(err) = json.get("error").filter(|e| !e.is_null()) {
+        if err["code"] == -32601 {
+            return Ok(Capabilities::default());
+        }
+        bail!("nova_getCapabilities failed: {}", err["message"]);
+    }
+    serde_json::from_value(json["result"].clone()).context("malformed capabilities response")
+}
+
+/// Fails unless the node supports `name`, naming `what` needs it.
+pub fn require(caps: &Capabilities, name: &str, what: &str) -> Result<()> {
+    if !caps.supports(name) {
+        bail!(
+            "the node does not support {} (missing capability `{}`)",
+            what,
+            name
+        );
+    }
+    Ok(())
+}
+
+/// The nonce the next transaction from `address` must carry.
+pub async fn next_nonce(rpc_url: &str, caps: &Capabilities, address: &str) -> Result<u64> {
+    if !caps.supports(Capabilities::NONCE_RPC) {
+        return Ok(crate::spam::fetch_account(rpc_url, address).await?.1 + 1);
+    }
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "nova_getNonce",
+        "params": [address],
+        "id": 1,
+    })
+    .to_string();
+    let response = crate::reqwest_post_json_stub(&format!("{}/rpc", rpc_url), &body).await?;
+    let json: serde_json::Value =
+        serde_json::from_str(&response).context("node returned a non-JSON nonce response")?;
+    json.pointer("/result/next_nonce")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+        .with_context(|| format!("nova_getNonce failed for {}", address))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_nodes_read_as_supporting_nothing() {
+        let caps = Capabilities::compiled();
+        let body = serde_json::json!({"jsonrpc": "2.0", "result": caps}).to_string();
+        assert_eq!(parse(&body).unwrap(), caps);
+
+        let old = r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"}}"#;
+        let none = parse(old).unwrap();
+        assert!(!none.supports(Capabilities::NONCE_RPC));
+        assert!(require(&none, Capabilities::NETWORK_KEY_ROTATION, "key rotation").is_err());
+        assert!(require(&caps, Capabilities::NETWORK_KEY_ROTATION, "key rotation").is_ok());
+
+        let broken = r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"boom"}}"#;
+        assert!(parse(broken).is_err());
+    }
+}
@@ -1,16 +1,24 @@
 //! # CLI Interface
 //!
 //! Defines the command-line argument structure for `nova-node` using
-//! `clap` derive. Supports four subcommands: `run`, `init`, `status`,
-//! and `version`.
+//! `clap` derive. Supports the subcommands `run`, `init`, `status`,
+//! `version`, `keys show`, `tx status`, the offline signing workflow
+//! (`tx export`, `tx sign`, `tx broadcast`), `bench`, `spam` and
+//! `completions`.
 //!
 //! Address and port arguments default to sane devnet values. Every configurable
 //! value has a corresponding environment variable for container-friendly
 //! deployment — because nobody wants to pass 12 flags to a Docker entrypoint.
+//!
+//! Query subcommands take `--output json` so scripts can stop scraping the
+//! pretty-printed text.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use std::path::PathBuf;
 
+use crate::presets::Network;
+
 /// NOVA Protocol validator node.
 ///
 /// A full validator node for the NOVA payment network. Participates in
@@ -40,7 +48,43 @@ pub enum Commands {
     /// Query the status of a running node via its RPC endpoint.
     Status(StatusArgs),
     /// Print version information and exit.
-    Version,
+    Version(VersionArgs),
+    /// Inspect the validator keypair in a data directory.
+    #[command(subcommand)]
+    Keys(KeysCommand),
+    /// Query transactions on a running node, or move them through an
+    /// offline signer.
+    #[command(subcommand)]
+    Tx(TxCommand),
+    /// Benchmark block production on a temporary dev chain.
+    Bench(BenchArgs),
+    /// Generate transfer load against a running node's RPC endpoint.
+    Spam(SpamArgs),
+    /// Export or verify the audit log of privileged operations.
+    #[command(subcommand)]
+    Audit(AuditCommand),
+    /// Inspect or replay a gossip journal written with `--gossip-journal`.
+    #[command(subcommand)]
+    Journal(JournalCommand),
+    /// Sign emergency halt and resume records, or submit them to a node.
+    #[command(subcommand)]
+    Halt(HaltCommand),
+    /// Sign treasury spend proposals, or submit them on chain.
+    #[command(subcommand)]
+    Treasury(TreasuryCommand),
+    /// Check a stopped node's stored chain and its supply invariant.
+    VerifyChain(VerifyChainArgs),
+    /// Print a shell completion script to stdout.
+    Completions(CompletionsArgs),
+}
+
+/// Output format for query subcommands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// A single JSON document on stdout.
+    Json,
 }
 
 /// Arguments for the `run` subcommand.
@@ -52,6 +96,17 @@ pub struct RunArgs {
     #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
     pub data_dir: PathBuf,
 
+    /// Network to join: mainnet, testnet, or devnet.
+    ///
+    /// Must match the network the data directory was initialized for.
+    #[arg(long, env = "NOVA_NETWORK", value_enum, default_value_t = Network::Devnet)]
+    pub network: Network,
+
+    /// Genesis config JSON overriding the network's built-in chain ID and
+    /// monetary policy. Every node on the chain must use the same file.
+    #[arg(long, env = "NOVA_GENESIS")]
+    pub genesis: Option<PathBuf>,
+
     /// Full bind address for the JSON-RPC and REST API.
     #[arg(long, env = "NOVA_RPC_ADDR", default_value = "0.0.0.0:9741")]
     pub rpc_addr: String,
@@ -60,6 +115,17 @@ pub struct RunArgs {
     #[arg(long, env = "NOVA_P2P_ADDR", default_value = "0.0.0.0:9740")]
     pub p2p_addr: String,
 
+    /// Also accept P2P connections over QUIC, on the UDP port matching
+    /// `--p2p-addr`. TCP stays on; peers may dial either.
+    #[arg(long, env = "NOVA_QUIC")]
+    pub quic: bool,
+
+    /// Static circuit relay to reserve a slot on, as a multiaddr ending in
+    /// `/p2p/<peer id>` (repeatable). Lets peers reach a node behind NAT;
+    /// direct connections are then attempted by hole punching.
+    #[arg(long = "relay", env = "NOVA_RELAYS", value_delimiter = ',')]
+    pub relays: Vec<String>,
+
     /// Full bind address for the Prometheus metrics endpoint.
     #[arg(long, env = "NOVA_METRICS_ADDR", default_value = "0.0.0.0:9742")]
     pub metrics_addr: String,
@@ -70,6 +136,18 @@ pub struct RunArgs {
     #[arg(long)]
     pub dev: bool,
 
+    /// Reproducible dev chain for integration tests. Implies `--dev`, and
+    /// additionally uses the fixed dev validator key (seed index 0, see
+    /// README) and stamps block `h` at a fixed genesis time plus `h` block
+    /// intervals, so identical runs produce identical block hashes.
+    #[arg(long)]
+    pub dev_deterministic: bool,
+
+    /// Dev mode only: produce a block as soon as a transaction arrives
+    /// instead of on the block timer, and never produce empty blocks.
+    #[arg(long)]
+    pub dev_instant: bool,
+
     /// Log verbosity level: trace, debug, info, warn, error.
     #[arg(long, env = "NOVA_LOG_LEVEL", default_value = "info")]
     pub log_level: String,
@@ -103,6 +181,169 @@ pub struct RunArgs {
     /// **Never pass this flag in production** — use a key file or vault instead.
     #[arg(long, env = "NOVA_VALIDATOR_KEY")]
     pub validator_key: Option<String>,
+
+    /// Network (P2P identity) key file. Defaults to `keys/network.key` in
+    /// the data directory, created on first start. Kept separate from the
+    /// validator key so each can be stored and rotated on its own.
+    #[arg(long, env = "NOVA_NETWORK_KEY_FILE")]
+    pub network_key_file: Option<PathBuf>,
+
+    /// NTP server used as an extra clock reference (repeatable).
+    ///
+    /// Peers already provide clock samples; NTP servers are optional
+    /// tie-breakers for operators who want an external reference.
+    #[arg(long = "ntp-server", env = "NOVA_NTP_SERVERS", value_delimiter = ',')]
+    pub ntp_servers: Vec<String>,
+
+    /// Accept any printable string as an address instead of requiring
+    /// checksummed Bech32. Implied by `--dev`; never enable on a public
+    /// network.
+    #[arg(long, env = "NOVA_PERMISSIVE_ADDRESSES")]
+    pub permissive_addresses: bool,
+
+    /// Minimum fee, in photons, for a transaction to enter the mempool.
+    #[arg(long, env = "NOVA_MIN_FEE", default_value_t = 0)]
+    pub min_fee: u64,
+
+    /// Pending transactions one sender may hold in the mempool. A sender at
+    /// the limit can only replace its own transaction at the same nonce, or
+    /// its highest-nonce one.
+    #[arg(long, env = "NOVA_MAX_TXS_PER_SENDER", default_value_t = 100)]
+    pub max_txs_per_sender: usize,
+
+    /// Seconds a transaction may wait in the mempool before it is evicted
+    /// as stale.
+    #[arg(long, env = "NOVA_MEMPOOL_TTL_SECS", default_value_t = 3600)]
+    pub mempool_ttl_secs: u64,
+
+    /// Total size, in megabytes, of the transactions the mempool holds.
+    /// Over budget, the lowest fee-per-byte transactions are evicted first.
+    #[arg(
+        long = "mempool-max-mb",
+        env = "NOVA_MEMPOOL_MAX_MB",
+        default_value_t = 64,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub mempool_max_mb: u64,
+
+    /// Transactions held back behind a nonce gap until their predecessor
+    /// arrives. The oldest is dropped at the limit; 0 parks them in the
+    /// mempool instead.
+    #[arg(long, env = "NOVA_MEMPOOL_MAX_ORPHANS", default_value_t = 1_000)]
+    pub mempool_max_orphans: usize,
+
+    /// Extra minimum fee, in photons per byte, for transactions carrying an
+    /// encrypted payload.
+    #[arg(long, env = "NOVA_ENCRYPTED_BYTE_FEE", default_value_t = 0)]
+    pub encrypted_byte_fee: u64,
+
+    /// Screen transaction parties against a deny list file: one address
+    /// per line, optionally followed by a reason. `#` starts a comment.
+    #[arg(long, env = "NOVA_SCREENING_LIST", conflicts_with = "screening_url")]
+    pub screening_list: Option<PathBuf>,
+
+    /// Screen transaction parties with an HTTP screening service, queried
+    /// as `GET <url>?address=<address>`.
+    #[arg(long, env = "NOVA_SCREENING_URL")]
+    pub screening_url: Option<String>,
+
+    /// What to do with transactions touching a listed address.
+    #[arg(long, env = "NOVA_SCREENING_ACTION", value_enum, default_value_t = ScreeningMode::Block)]
+    pub screening_action: ScreeningMode,
+
+    /// Admit transactions when the screening provider cannot answer.
+    /// By default they are refused.
+    #[arg(long, env = "NOVA_SCREENING_FAIL_OPEN")]
+    pub screening_fail_open: bool,
+
+    /// Address whose outflows raise `anomaly_detected` events (repeatable).
+    #[arg(
+        long = "watch-address",
+        env = "NOVA_WATCH_ADDRESSES",
+        value_delimiter = ','
+    )]
+    pub watch_addresses: Vec<String>,
+
+    /// Alert when more than this many photons leave a watched address
+    /// within `--velocity-window`.
+    #[arg(long, env = "NOVA_VELOCITY_MAX_OUTFLOW")]
+    pub velocity_max_outflow: Option<u64>,
+
+    /// Sliding window for `--velocity-max-outflow`, in seconds.
+    #[arg(
+        long = "velocity-window",
+        env = "NOVA_VELOCITY_WINDOW",
+        default_value_t = 3600
+    )]
+    pub velocity_window_secs: u64,
+
+    /// Alert on the first payment from a watched address to a receiver.
+    #[arg(long, env = "NOVA_VELOCITY_NEW_COUNTERPARTY")]
+    pub velocity_new_counterparty: bool,
+
+    /// Alert on outflows in this UTC hour range, e.g. `22-6`.
+    #[arg(long, env = "NOVA_VELOCITY_QUIET_HOURS")]
+    pub velocity_quiet_hours: Option<crate::velocity::QuietHours>,
+
+    /// POST every `anomaly_detected` event as JSON to this URL.
+    #[arg(long, env = "NOVA_ANOMALY_WEBHOOK")]
+    pub anomaly_webhook: Option<String>,
+
+    /// Relay NTP handshake, proof and receipt messages for wallets over
+    /// `/nova/ntp/1.0` on the P2P address, and serve the `/ntp/*` routes.
+    #[arg(long, env = "NOVA_NTP_MESSAGING")]
+    pub ntp_messaging: bool,
+
+    /// Record every gossip message the P2P swarm receives — arrival time,
+    /// peer, kind, hash, verdict and raw frame — to this file, for
+    /// `journal replay`. Relative paths are under the data directory.
+    #[arg(long, env = "NOVA_GOSSIP_JOURNAL", requires = "ntp_messaging")]
+    pub gossip_journal: Option<PathBuf>,
+
+    /// Rotate the gossip journal to `<file>.1` at this size; at most about
+    /// twice this is kept on disk.
+    #[arg(
+        long = "gossip-journal-max-mb",
+        env = "NOVA_GOSSIP_JOURNAL_MAX_MB",
+        default_value_t = 64,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub gossip_journal_max_mb: u64,
+
+    /// Mirror committed blocks, transactions, transfers and account changes
+    /// into SQL for analytics: `sqlite:<path>` (under the data directory
+    /// unless absolute) or a `postgres://` URL. Resumes from the checkpoint
+    /// stored in that database.
+    #[arg(long, env = "NOVA_SQL_EXPORT")]
+    pub sql_export: Option<String>,
+
+    /// Poll this URL for a signed release manifest and warn when the node
+    /// falls behind the network's requirements. Off unless set.
+    #[arg(long, env = "NOVA_UPDATE_CHECK_URL")]
+    pub update_check_url: Option<String>,
+
+    /// Seconds between update checks.
+    #[arg(
+        long = "update-check-interval",
+        env = "NOVA_UPDATE_CHECK_INTERVAL",
+        default_value_t = 21_600,
+        value_parser = clap::value_parser!(u64).range(60..)
+    )]
+    pub update_check_interval_secs: u64,
+
+    /// Hex Ed25519 key trusted to sign release manifests, in addition to
+    /// the built-in release keys (repeatable).
+    #[arg(long = "update-key", env = "NOVA_UPDATE_KEYS", value_delimiter = ',')]
+    pub update_keys: Vec<String>,
+}
+
+/// `--screening-action` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScreeningMode {
+    /// Refuse the transaction.
+    Block,
+    /// Admit it and record the hit in the audit log.
+    Flag,
 }
 
 /// Arguments for the `init` subcommand.
@@ -113,8 +354,8 @@ pub struct InitArgs {
     pub data_dir: PathBuf,
 
     /// Network to configure for: mainnet, testnet, or devnet.
-    #[arg(long, default_value = "devnet")]
-    pub network: String,
+    #[arg(long, env = "NOVA_NETWORK", value_enum, default_value_t = Network::Devnet)]
+    pub network: Network,
 
     /// Overwrite an existing data directory. Use with caution — this will
     /// destroy any existing keypair and chain state.
@@ -128,6 +369,505 @@ pub struct StatusArgs {
     /// RPC endpoint of the running node.
     #[arg(long, default_value = "http://127.0.0.1:9741")]
     pub rpc_url: String,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for the `version` subcommand.
+#[derive(Parser, Debug, Clone)]
+pub struct VersionArgs {
+    /// Hash the running binary and check its embedded provenance; exits
+    /// non-zero if a check fails.
+    #[arg(long)]
+    pub verify: bool,
+
+    /// With `--verify`: commit (or prefix) the binary must be built from.
+    #[arg(long, requires = "verify")]
+    pub commit: Option<String>,
+
+    /// With `--verify`: `Cargo.lock` the binary must be built with.
+    #[arg(long, requires = "verify")]
+    pub lockfile: Option<PathBuf>,
+
+    /// With `--verify`: published SHA-256 of the binary.
+    #[arg(long, requires = "verify")]
+    pub sha256: Option<String>,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Subcommands of `keys`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum KeysCommand {
+    /// Show the public key and address of the validator key.
+    Show(KeysShowArgs),
+    /// Sign a message with the validator key to prove control of its
+    /// address. Prints the signed message as JSON.
+    SignMessage(KeysSignMessageArgs),
+    /// Verify a signed message produced by `keys sign-message` or a wallet.
+    VerifyMessage(KeysVerifyMessageArgs),
+    /// Replace the network (P2P) key and publish the new one on chain,
+    /// leaving the validator key untouched.
+    RotateNetworkKey(KeysRotateNetworkKeyArgs),
+}
+
+/// Arguments for `keys show`.
+#[derive(Parser, Debug, Clone)]
+pub struct KeysShowArgs {
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for `keys sign-message`.
+#[derive(Parser, Debug, Clone)]
+pub struct KeysSignMessageArgs {
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+
+    /// The message to sign.
+    pub message: String,
+}
+
+/// Arguments for `keys verify-message`.
+#[derive(Parser, Debug, Clone)]
+pub struct KeysVerifyMessageArgs {
+    /// JSON file with the signed message; read from stdin if omitted.
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for `keys rotate-network-key`.
+#[derive(Parser, Debug, Clone)]
+pub struct KeysRotateNetworkKeyArgs {
+    /// Validator nonce. Looked up on `--rpc-url` if omitted.
+    #[arg(long)]
+    pub nonce: Option<u64>,
+
+    /// RPC endpoint of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Network key file to replace. Defaults to `keys/network.key` in
+    /// `--data-dir`.
+    #[arg(long, env = "NOVA_NETWORK_KEY_FILE")]
+    pub network_key_file: Option<PathBuf>,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+}
+
+/// Subcommands of `tx`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum TxCommand {
+    /// Look up a transaction by hash.
+    Status(TxStatusArgs),
+    /// Write an unsigned transaction bundle for an offline signer.
+    Export(TxExportArgs),
+    /// Sign a bundle on an air-gapped machine. Never opens a connection.
+    Sign(TxSignArgs),
+    /// Verify a signed bundle and submit its transaction to a node.
+    Broadcast(TxBroadcastArgs),
+    /// Ask for this node's jailed validator to rejoin the active set.
+    Unjail(TxUnjailArgs),
+}
+
+/// Arguments for `tx status`.
+#[derive(Parser, Debug, Clone)]
+pub struct TxStatusArgs {
+    /// Hex-encoded transaction hash.
+    pub hash: String,
+
+    /// RPC endpoint of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for `tx export`.
+#[derive(Parser, Debug, Clone)]
+pub struct TxExportArgs {
+    /// Sender address; its key stays on the offline signer.
+    #[arg(long)]
+    pub from: String,
+
+    /// Receiver address.
+    #[arg(long)]
+    pub to: String,
+
+    /// Amount in photons.
+    #[arg(long)]
+    pub amount: u64,
+
+    /// Fee in photons.
+    #[arg(long, default_value_t = nova_protocol::config::MIN_TX_FEE_PHOTONS)]
+    pub fee: u64,
+
+    /// Sender nonce. Looked up on `--rpc-url` if omitted.
+    #[arg(long)]
+    pub nonce: Option<u64>,
+
+    /// UTF-8 memo attached to the transfer.
+    #[arg(long)]
+    pub memo: Option<String>,
+
+    /// RPC endpoint used to look up the nonce.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Write the bundle here instead of to stdout.
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+
+    /// Write QR chunk strings, one per line, instead of JSON.
+    #[arg(long)]
+    pub qr: bool,
+}
+
+/// Arguments for `tx sign`.
+#[derive(Parser, Debug, Clone)]
+pub struct TxSignArgs {
+    /// Bundle to sign, as JSON or QR chunk lines.
+    #[arg(long, short = 'b', visible_alias = "input")]
+    pub bundle: PathBuf,
+
+    /// Hex secret key file. Defaults to the validator key in `--data-dir`.
+    #[arg(long, visible_alias = "key")]
+    pub key_file: Option<PathBuf>,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+
+    /// Write the signed bundle here instead of to stdout.
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+
+    /// Write QR chunk strings, one per line, instead of JSON.
+    #[arg(long)]
+    pub qr: bool,
+}
+
+/// Arguments for `tx broadcast`.
+#[derive(Parser, Debug, Clone)]
+pub struct TxBroadcastArgs {
+    /// Signed bundle, as JSON or QR chunk lines.
+    #[arg(long, short = 'b')]
+    pub bundle: PathBuf,
+
+    /// RPC endpoint of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for `tx unjail`.
+#[derive(Parser, Debug, Clone)]
+pub struct TxUnjailArgs {
+    /// Validator nonce. Looked up on `--rpc-url` if omitted.
+    #[arg(long)]
+    pub nonce: Option<u64>,
+
+    /// RPC endpoint of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+}
+
+/// Arguments for the `bench` subcommand.
+#[derive(Parser, Debug, Clone)]
+pub struct BenchArgs {
+    /// Number of signed transfers to push through the pipeline.
+    #[arg(long, short = 'n', default_value_t = 10_000)]
+    pub transactions: usize,
+
+    /// Number of funded accounts the transfers rotate through.
+    #[arg(long, short = 'm', default_value_t = 100)]
+    pub accounts: usize,
+
+    /// Maximum transactions per block (the consensus loop's default).
+    #[arg(long, default_value_t = 1_000)]
+    pub block_size: usize,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for the `spam` subcommand.
+#[derive(Parser, Debug, Clone)]
+pub struct SpamArgs {
+    /// RPC endpoint of the node to load.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Target submissions per second.
+    #[arg(long, default_value_t = 10.0)]
+    pub tps: f64,
+
+    /// Seconds to keep sending.
+    #[arg(long, default_value_t = 60)]
+    pub duration: u64,
+
+    /// Number of dev accounts to send from (at most the 10 a `--dev` node funds).
+    #[arg(long, short = 'm', default_value_t = 10)]
+    pub accounts: usize,
+
+    /// Photons per transfer.
+    #[arg(long, default_value_t = 1_000)]
+    pub amount: u64,
+
+    /// Starting fee in photons.
+    #[arg(long, default_value_t = nova_protocol::config::MIN_TX_FEE_PHOTONS)]
+    pub fee: u64,
+
+    /// Ceiling for fee bumping, in photons.
+    #[arg(long, default_value_t = 10_000)]
+    pub max_fee: u64,
+
+    /// Seconds to wait for an accepted transfer to confirm.
+    #[arg(long, default_value_t = 30)]
+    pub confirm_timeout: u64,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Subcommands of `audit`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum AuditCommand {
+    /// Write the audit log, with its head, as one JSON document.
+    Export(AuditExportArgs),
+    /// Check the audit log's hash chain for modification or truncation.
+    Verify(AuditVerifyArgs),
+}
+
+/// Arguments for `audit export`.
+#[derive(Parser, Debug, Clone)]
+pub struct AuditExportArgs {
+    /// Data directory of a stopped node.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+
+    /// Write the export here instead of to stdout.
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+}
+
+/// Arguments for `audit verify`.
+#[derive(Parser, Debug, Clone)]
+pub struct AuditVerifyArgs {
+    /// Data directory of a stopped node.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+
+    /// Verify an export (from `audit export` or `GET /admin/audit`)
+    /// instead of the data directory.
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+
+    /// A head hash recorded earlier, e.g. from the node's startup log. The
+    /// log must still contain the entry it names.
+    #[arg(long)]
+    pub anchor: Option<String>,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Subcommands of `journal`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum JournalCommand {
+    /// Feed journaled gossip back through validation, in order, and report
+    /// any verdict that does not reproduce.
+    Replay(JournalReplayArgs),
+}
+
+/// Arguments for `journal replay`.
+#[derive(Parser, Debug, Clone)]
+pub struct JournalReplayArgs {
+    /// Journal file; its rotated `<file>.1` segment is read first.
+    pub file: PathBuf,
+
+    /// Check addresses as a `--permissive-addresses` node does.
+    #[arg(long)]
+    pub permissive_addresses: bool,
+
+    /// Print every entry, not just the ones whose verdict changed.
+    #[arg(long, short = 'v')]
+    pub verbose: bool,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Subcommands of `halt`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum HaltCommand {
+    /// Add the validator's signature to an emergency halt record.
+    Sign(HaltSignArgs),
+    /// Add the validator's signature to a resume record.
+    SignResume(HaltSignResumeArgs),
+    /// Submit a signed halt or resume record to a node's admin API.
+    Submit(HaltSubmitArgs),
+}
+
+/// Arguments for `halt sign`.
+#[derive(Parser, Debug, Clone)]
+pub struct HaltSignArgs {
+    /// Record to add the signature to, rewritten in place. Created from
+    /// `--height` and `--reason` if it does not exist.
+    #[arg(long, short = 'f')]
+    pub file: PathBuf,
+
+    /// First height that may not be built.
+    #[arg(long)]
+    pub height: Option<u64>,
+
+    /// Why the chain is halted.
+    #[arg(long)]
+    pub reason: Option<String>,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+}
+
+/// Arguments for `halt sign-resume`.
+#[derive(Parser, Debug, Clone)]
+pub struct HaltSignResumeArgs {
+    /// Record to add the signature to, rewritten in place. Created from
+    /// `--height` and `--min-protocol-version` if it does not exist.
+    #[arg(long, short = 'f')]
+    pub file: PathBuf,
+
+    /// Height of the halt being lifted.
+    #[arg(long)]
+    pub height: Option<u64>,
+
+    /// Oldest protocol version allowed to build past the halt.
+    #[arg(long)]
+    pub min_protocol_version: Option<String>,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+}
+
+/// Arguments for `halt submit`.
+#[derive(Parser, Debug, Clone)]
+pub struct HaltSubmitArgs {
+    /// Signed halt or resume record.
+    #[arg(long, short = 'f')]
+    pub file: PathBuf,
+
+    /// RPC endpoint of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+}
+
+/// Subcommands of `treasury`.
+#[derive(Subcommand, Debug, Clone)]
+pub enum TreasuryCommand {
+    /// Add the validator's signature to a treasury spend proposal.
+    Sign(TreasurySignArgs),
+    /// Submit a signed spend proposal as a `TreasurySpend` transaction.
+    Submit(TreasurySubmitArgs),
+}
+
+/// Arguments for `treasury sign`.
+#[derive(Parser, Debug, Clone)]
+pub struct TreasurySignArgs {
+    /// Proposal to add the signature to, rewritten in place. Created from
+    /// `--id`, `--recipient`, `--amount` and `--memo` if it does not exist.
+    #[arg(long, short = 'f')]
+    pub file: PathBuf,
+
+    /// Proposal ID. Each ID pays out at most once.
+    #[arg(long)]
+    pub id: Option<String>,
+
+    /// Address receiving the funds.
+    #[arg(long)]
+    pub recipient: Option<String>,
+
+    /// Photons to pay.
+    #[arg(long)]
+    pub amount: Option<u64>,
+
+    /// What the money is for.
+    #[arg(long)]
+    pub memo: Option<String>,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+}
+
+/// Arguments for `treasury submit`.
+#[derive(Parser, Debug, Clone)]
+pub struct TreasurySubmitArgs {
+    /// Signed spend proposal.
+    #[arg(long, short = 'f')]
+    pub file: PathBuf,
+
+    /// Submitting validator's nonce. Looked up on `--rpc-url` if omitted.
+    #[arg(long)]
+    pub nonce: Option<u64>,
+
+    /// RPC endpoint of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:9741")]
+    pub rpc_url: String,
+
+    /// Data directory holding `keys/validator.key`.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+}
+
+/// Arguments for the `verify-chain` subcommand.
+#[derive(Parser, Debug, Clone)]
+pub struct VerifyChainArgs {
+    /// Data directory of a stopped node.
+    #[arg(long, short = 'd', env = "NOVA_DATA_DIR", default_value = "~/.nova")]
+    pub data_dir: PathBuf,
+
+    /// Output format.
+    #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Arguments for the `completions` subcommand.
+#[derive(Parser, Debug, Clone)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for.
+    #[arg(value_enum)]
+    pub shell: Shell,
 }
 
 /// Resolves the data directory path, expanding the `~` prefix to the
@@ -199,10 +939,22 @@ mod tests {
                 assert_eq!(run.rpc_addr, "0.0.0.0:9741");
                 assert_eq!(run.p2p_addr, "0.0.0.0:9740");
                 assert_eq!(run.metrics_addr, "0.0.0.0:9742");
+                assert!(!run.quic);
+                assert!(run.relays.is_empty());
+                assert!(run.gossip_journal.is_none());
+                assert_eq!(run.gossip_journal_max_mb, 64);
+                assert!(run.sql_export.is_none());
+                assert_eq!(run.encrypted_byte_fee, 0);
+                assert_eq!(run.min_fee, 0);
+                assert_eq!(run.max_txs_per_sender, 100);
+                assert_eq!(run.mempool_ttl_secs, 3600);
+                assert_eq!(run.mempool_max_mb, 64);
+                assert_eq!(run.mempool_max_orphans, 1_000);
                 assert!(!run.dev);
                 assert!(!run.validator);
                 assert_eq!(run.stake, 0);
                 assert_eq!(run.log_level, "info");
+                assert_eq!(run.network, Network::Devnet);
             }
             _ => panic!("expected Run subcommand"),
         }
@@ -220,6 +972,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn run_subcommand_deterministic_dev() {
+        let args =
+            NovaNodeCli::parse_from(["nova-node", "run", "--dev-deterministic", "--dev-instant"]);
+        match args.command {
+            Commands::Run(run) => {
+                assert!(run.dev_deterministic);
+                assert!(run.dev_instant);
+                assert!(!run.dev, "--dev is implied at startup, not by the parser");
+            }
+            _ => panic!("expected Run subcommand"),
+        }
+    }
+
     #[test]
     fn run_subcommand_custom_addresses() {
         let args = NovaNodeCli::parse_from([
@@ -253,7 +1019,7 @@ mod tests {
         let args = NovaNodeCli::parse_from(["nova-node", "init"]);
         match args.command {
             Commands::Init(init) => {
-                assert_eq!(init.network, "devnet");
+                assert_eq!(init.network, Network::Devnet);
                 assert!(!init.force);
             }
             _ => panic!("expected Init subcommand"),
@@ -267,18 +1033,29 @@ mod tests {
         match args.command {
             Commands::Init(init) => {
                 assert!(init.force);
-                assert_eq!(init.network, "testnet");
+                assert_eq!(init.network, Network::Testnet);
             }
             _ => panic!("expected Init subcommand"),
         }
     }
 
+    #[test]
+    fn run_subcommand_network_preset() {
+        let args = NovaNodeCli::parse_from(["nova-node", "run", "--network", "mainnet"]);
+        match args.command {
+            Commands::Run(run) => assert_eq!(run.network, Network::Mainnet),
+            _ => panic!("expected Run subcommand"),
+        }
+        assert!(NovaNodeCli::try_parse_from(["nova-node", "run", "--network", "moonnet"]).is_err());
+    }
+
     #[test]
     fn status_subcommand_defaults() {
         let args = NovaNodeCli::parse_from(["nova-node", "status"]);
         match args.command {
             Commands::Status(status) => {
                 assert_eq!(status.rpc_url, "http://127.0.0.1:9741");
+                assert_eq!(status.output, OutputFormat::Text);
             }
             _ => panic!("expected Status subcommand"),
         }
@@ -299,7 +1076,408 @@ mod tests {
     #[test]
     fn version_subcommand_parses() {
         let args = NovaNodeCli::parse_from(["nova-node", "version"]);
-        assert!(matches!(args.command, Commands::Version));
+        assert!(matches!(args.command, Commands::Version(_)));
+
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "version",
+            "--verify",
+            "--commit",
+            "3894918",
+            "--lockfile",
+            "Cargo.lock",
+        ]);
+        match args.command {
+            Commands::Version(version) => {
+                assert!(version.verify);
+                assert_eq!(version.commit.as_deref(), Some("3894918"));
+                assert_eq!(version.lockfile, Some(PathBuf::from("Cargo.lock")));
+            }
+            _ => panic!("expected Version subcommand"),
+        }
+        assert!(
+            NovaNodeCli::try_parse_from(["nova-node", "version", "--sha256", "ab"]).is_err(),
+            "expectations need --verify"
+        );
+    }
+
+    #[test]
+    fn query_subcommands_accept_json_output() {
+        let args = NovaNodeCli::parse_from(["nova-node", "status", "--output", "json"]);
+        assert!(matches!(
+            args.command,
+            Commands::Status(StatusArgs {
+                output: OutputFormat::Json,
+                ..
+            })
+        ));
+
+        let args = NovaNodeCli::parse_from(["nova-node", "version", "-o", "json"]);
+        assert!(matches!(
+            args.command,
+            Commands::Version(VersionArgs {
+                output: OutputFormat::Json,
+                verify: false,
+                ..
+            })
+        ));
+
+        let args =
+            NovaNodeCli::parse_from(["nova-node", "keys", "show", "-d", "/tmp/n", "-o", "json"]);
+        match args.command {
+            Commands::Keys(KeysCommand::Show(show)) => {
+                assert_eq!(show.data_dir, PathBuf::from("/tmp/n"));
+                assert_eq!(show.output, OutputFormat::Json);
+            }
+            _ => panic!("expected Keys Show subcommand"),
+        }
+
+        let args =
+            NovaNodeCli::parse_from(["nova-node", "tx", "status", "ab12", "--output", "json"]);
+        match args.command {
+            Commands::Tx(TxCommand::Status(tx)) => {
+                assert_eq!(tx.hash, "ab12");
+                assert_eq!(tx.output, OutputFormat::Json);
+            }
+            _ => panic!("expected Tx Status subcommand"),
+        }
+    }
+
+    #[test]
+    fn tx_offline_signing_subcommands() {
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "tx",
+            "export",
+            "--from",
+            "nova1a",
+            "--to",
+            "nova1b",
+            "--amount",
+            "500",
+            "--qr",
+        ]);
+        match args.command {
+            Commands::Tx(TxCommand::Export(export)) => {
+                assert_eq!(export.amount, 500);
+                assert_eq!(export.nonce, None);
+                assert!(export.qr);
+            }
+            _ => panic!("expected Tx Export subcommand"),
+        }
+
+        let args = NovaNodeCli::parse_from(["nova-node", "tx", "sign", "--bundle", "tx.json"]);
+        match args.command {
+            Commands::Tx(TxCommand::Sign(sign)) => {
+                assert_eq!(sign.bundle, PathBuf::from("tx.json"));
+                assert!(sign.key_file.is_none());
+            }
+            _ => panic!("expected Tx Sign subcommand"),
+        }
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "tx",
+            "sign",
+            "--key",
+            "cold.key",
+            "--input",
+            "unsigned.json",
+        ]);
+        match args.command {
+            Commands::Tx(TxCommand::Sign(sign)) => {
+                assert_eq!(sign.bundle, PathBuf::from("unsigned.json"));
+                assert_eq!(sign.key_file, Some(PathBuf::from("cold.key")));
+            }
+            _ => panic!("expected Tx Sign subcommand"),
+        }
+        assert!(NovaNodeCli::try_parse_from(["nova-node", "tx", "broadcast"]).is_err());
+
+        let args = NovaNodeCli::parse_from(["nova-node", "tx", "unjail", "--nonce", "4"]);
+        match args.command {
+            Commands::Tx(TxCommand::Unjail(unjail)) => assert_eq!(unjail.nonce, Some(4)),
+            _ => panic!("expected Tx Unjail subcommand"),
+        }
+    }
+
+    #[test]
+    fn bench_subcommand_defaults() {
+        let args = NovaNodeCli::parse_from(["nova-node", "bench"]);
+        match args.command {
+            Commands::Bench(bench) => {
+                assert_eq!(bench.transactions, 10_000);
+                assert_eq!(bench.accounts, 100);
+                assert_eq!(bench.block_size, 1_000);
+                assert_eq!(bench.output, OutputFormat::Text);
+            }
+            _ => panic!("expected Bench subcommand"),
+        }
+    }
+
+    #[test]
+    fn spam_subcommand_defaults() {
+        let args = NovaNodeCli::parse_from(["nova-node", "spam", "--tps", "250"]);
+        match args.command {
+            Commands::Spam(spam) => {
+                assert_eq!(spam.tps, 250.0);
+                assert_eq!(spam.accounts, 10);
+                assert_eq!(spam.fee, nova_protocol::config::MIN_TX_FEE_PHOTONS);
+                assert_eq!(spam.rpc_url, "http://127.0.0.1:9741");
+            }
+            _ => panic!("expected Spam subcommand"),
+        }
+    }
+
+    #[test]
+    fn audit_subcommands_parse() {
+        let args = NovaNodeCli::parse_from(["nova-node", "audit", "export", "-d", "/tmp/n"]);
+        match args.command {
+            Commands::Audit(AuditCommand::Export(export)) => {
+                assert_eq!(export.data_dir, PathBuf::from("/tmp/n"));
+                assert!(export.file.is_none());
+            }
+            _ => panic!("expected Audit Export subcommand"),
+        }
+
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "audit",
+            "verify",
+            "--file",
+            "audit.json",
+            "--anchor",
+            "0xab",
+        ]);
+        match args.command {
+            Commands::Audit(AuditCommand::Verify(verify)) => {
+                assert_eq!(verify.file, Some(PathBuf::from("audit.json")));
+                assert_eq!(verify.anchor.as_deref(), Some("0xab"));
+                assert_eq!(verify.output, OutputFormat::Text);
+            }
+            _ => panic!("expected Audit Verify subcommand"),
+        }
+    }
+
+    #[test]
+    fn journal_replay_parses_and_journaling_needs_messaging() {
+        let args = NovaNodeCli::parse_from(["nova-node", "journal", "replay", "gossip.journal"]);
+        match args.command {
+            Commands::Journal(JournalCommand::Replay(replay)) => {
+                assert_eq!(replay.file, PathBuf::from("gossip.journal"));
+                assert!(!replay.permissive_addresses);
+                assert_eq!(replay.output, OutputFormat::Text);
+            }
+            _ => panic!("expected Journal Replay subcommand"),
+        }
+
+        let result = NovaNodeCli::try_parse_from(["nova-node", "run", "--gossip-journal", "g.jnl"]);
+        assert!(result.is_err());
+        let result = NovaNodeCli::try_parse_from([
+            "nova-node",
+            "run",
+            "--ntp-messaging",
+            "--gossip-journal",
+            "g.jnl",
+        ]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn halt_subcommands_parse() {
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "halt",
+            "sign",
+            "-f",
+            "halt.json",
+            "--height",
+            "1200",
+            "--reason",
+            "state root mismatch",
+        ]);
+        match args.command {
+            Commands::Halt(HaltCommand::Sign(sign)) => {
+                assert_eq!(sign.file, PathBuf::from("halt.json"));
+                assert_eq!(sign.height, Some(1200));
+                assert_eq!(sign.reason.as_deref(), Some("state root mismatch"));
+            }
+            _ => panic!("expected Halt Sign subcommand"),
+        }
+
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "halt",
+            "sign-resume",
+            "-f",
+            "resume.json",
+            "--min-protocol-version",
+            "0.2.0",
+        ]);
+        match args.command {
+            Commands::Halt(HaltCommand::SignResume(resume)) => {
+                assert!(resume.height.is_none());
+                assert_eq!(resume.min_protocol_version.as_deref(), Some("0.2.0"));
+            }
+            _ => panic!("expected Halt SignResume subcommand"),
+        }
+    }
+
+    #[test]
+    fn verify_chain_parses() {
+        let args =
+            NovaNodeCli::parse_from(["nova-node", "verify-chain", "-d", "/tmp/nova", "-o", "json"]);
+        match args.command {
+            Commands::VerifyChain(verify) => {
+                assert_eq!(verify.data_dir, PathBuf::from("/tmp/nova"));
+                assert_eq!(verify.output, OutputFormat::Json);
+            }
+            _ => panic!("expected VerifyChain subcommand"),
+        }
+    }
+
+    #[test]
+    fn treasury_sign_parses() {
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "treasury",
+            "sign",
+            "-f",
+            "spend.json",
+            "--id",
+            "audit-2026",
+            "--recipient",
+            "nova1auditor",
+            "--amount",
+            "5000000",
+        ]);
+        match args.command {
+            Commands::Treasury(TreasuryCommand::Sign(sign)) => {
+                assert_eq!(sign.file, PathBuf::from("spend.json"));
+                assert_eq!(sign.id.as_deref(), Some("audit-2026"));
+                assert_eq!(sign.amount, Some(5_000_000));
+                assert!(sign.memo.is_none());
+            }
+            _ => panic!("expected Treasury Sign subcommand"),
+        }
+    }
+
+    #[test]
+    fn screening_flags_parse() {
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "run",
+            "--screening-list",
+            "sdn.txt",
+            "--screening-action",
+            "flag",
+        ]);
+        match args.command {
+            Commands::Run(run) => {
+                assert_eq!(run.screening_list, Some(PathBuf::from("sdn.txt")));
+                assert_eq!(run.screening_action, ScreeningMode::Flag);
+                assert!(!run.screening_fail_open);
+            }
+            _ => panic!("expected Run subcommand"),
+        }
+        assert!(NovaNodeCli::try_parse_from([
+            "nova-node",
+            "run",
+            "--screening-list",
+            "sdn.txt",
+            "--screening-url",
+            "http://127.0.0.1:9000/screen",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn velocity_flags_parse() {
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "run",
+            "--watch-address",
+            "nova1a,nova1b",
+            "--velocity-max-outflow",
+            "5000",
+            "--velocity-quiet-hours",
+            "22-6",
+        ]);
+        match args.command {
+            Commands::Run(run) => {
+                assert_eq!(run.watch_addresses, ["nova1a", "nova1b"]);
+                assert_eq!(run.velocity_max_outflow, Some(5_000));
+                assert_eq!(run.velocity_window_secs, 3_600);
+                assert_eq!(
+                    run.velocity_quiet_hours.map(|q| (q.start, q.end)),
+                    Some((22, 6))
+                );
+            }
+            _ => panic!("expected Run subcommand"),
+        }
+        assert!(NovaNodeCli::try_parse_from([
+            "nova-node",
+            "run",
+            "--velocity-quiet-hours",
+            "25-1"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn message_subcommands_parse() {
+        let args = NovaNodeCli::parse_from(["nova-node", "keys", "sign-message", "hello world"]);
+        match args.command {
+            Commands::Keys(KeysCommand::SignMessage(sign)) => {
+                assert_eq!(sign.message, "hello world");
+            }
+            _ => panic!("expected Keys SignMessage subcommand"),
+        }
+        let args =
+            NovaNodeCli::parse_from(["nova-node", "keys", "verify-message", "-f", "signed.json"]);
+        match args.command {
+            Commands::Keys(KeysCommand::VerifyMessage(verify)) => {
+                assert_eq!(verify.file, Some(PathBuf::from("signed.json")));
+            }
+            _ => panic!("expected Keys VerifyMessage subcommand"),
+        }
+    }
+
+    #[test]
+    fn rotate_network_key_parses() {
+        let args = NovaNodeCli::parse_from([
+            "nova-node",
+            "keys",
+            "rotate-network-key",
+            "--network-key-file",
+            "/keys/p2p.key",
+            "--nonce",
+            "3",
+        ]);
+        match args.command {
+            Commands::Keys(KeysCommand::RotateNetworkKey(rotate)) => {
+                assert_eq!(
+                    rotate.network_key_file,
+                    Some(PathBuf::from("/keys/p2p.key"))
+                );
+                assert_eq!(rotate.nonce, Some(3));
+            }
+            _ => panic!("expected Keys RotateNetworkKey subcommand"),
+        }
+    }
+
+    #[test]
+    fn completions_subcommand_parses_shell() {
+        for (name, shell) in [
+            ("bash", Shell::Bash),
+            ("zsh", Shell::Zsh),
+            ("fish", Shell::Fish),
+        ] {
+            let args = NovaNodeCli::parse_from(["nova-node", "completions", name]);
+            match args.command {
+                Commands::Completions(c) => assert_eq!(c.shell, shell),
+                _ => panic!("expected Completions subcommand"),
+            }
+        }
+        assert!(NovaNodeCli::try_parse_from(["nova-node", "completions", "cmd.exe"]).is_err());
     }
 
     #[test]
@@ -0,0 +1,180 @@
+//! # Clock Synchronization
+//!
+//! Feeds the protocol's [`ClockSkewMonitor`] and keeps an eye on the result.
+//!
+//! Peer samples arrive from the networking layer. On top of that, operators
+//! can point the node at one or more NTP servers (`--ntp-server`); each is
+//! queried with a minimal SNTP request every
+//! [`NTP_SYNC_INTERVAL`](nova_protocol::config::NTP_SYNC_INTERVAL) and
+//! counts as one more source in the median.
+//!
+//! A background task exports the estimate as Prometheus gauges and logs a
+//! warning whenever the node drifts past the tolerated skew — the consensus
+//! loop independently refuses to propose while that is the case.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use nova_protocol::network::clock::{ClockSkewMonitor, ClockStatus};
+use tokio::net::UdpSocket;
+
+use crate::metrics::NodeMetrics;
+
+/// Seconds between the NTP epoch (1900) and the Unix epoch (1970).
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+/// How long to wait for an NTP reply before giving up on this round.
+const NTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Current Unix time in milliseconds.
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+// ---------------------------------------------------------------------------
+// SNTP
+// ---------------------------------------------------------------------------
+
+/// Build a 48-byte SNTP v4 client request.
+fn sntp_request() -> [u8; 48] {
+    let mut packet = [0u8; 48];
+    // LI = 0 (no warning), VN = 4, Mode = 3 (client).
+    packet[0] = 0b00_100_011;
+    packet
+}
+
+/// Extract the server's transmit timestamp (Unix milliseconds) from a reply.
+fn parse_sntp_reply(reply: &[u8]) -> Result<u64> {
+    if reply.len() < 48 {
+        bail!("short NTP reply: {} bytes", reply.len());
+    }
+    let mode = reply[0] & 0b111;
+    if mode != 4 {
+        bail!("unexpected NTP mode {}", mode);
+    }
+
+    let secs = u32::from_be_bytes([reply[40], reply[41], reply[42], reply[43]]) as u64;
+    let frac = u32::from_be_bytes([reply[44], reply[45], reply[46], reply[47]]) as u64;
+    if secs < NTP_UNIX_OFFSET_SECS {
+        bail!("NTP timestamp before the Unix epoch (unsynchronized server?)");
+    }
+
+    Ok((secs - NTP_UNIX_OFFSET_SECS) * 1_000 + ((frac * 1_000) >> 32))
+}
+
+/// Query one NTP server and record the exchange in `monitor`.
+async fn sample_ntp_server(monitor: &ClockSkewMonitor, server: &str) -> Result<()> {
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .context("failed to bind NTP socket")?;
+    let addr = if server.contains(':') {
+        server.to_string()
+    } else {
+        format!("{}:123", server)
+    };
+    socket
+        .connect(&addr)
+        .await
+        .with_context(|| format!("failed to resolve NTP server {}", addr))?;
+
+    let sent = now_ms();
+    socket.send(&sntp_request()).await?;
+
+    let mut buf = [0u8; 64];
+    let len = tokio::time::timeout(NTP_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .with_context(|| format!("NTP server {} timed out", addr))??;
+    let received = now_ms();
+
+    let remote = parse_sntp_reply(&buf[..len])?;
+    monitor.record_exchange(&format!("ntp:{}", server), sent, remote, received);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Background Tasks
+// ---------------------------------------------------------------------------
+
+/// Periodically query the configured NTP servers.
+pub async fn run_ntp_sampler(monitor: Arc<ClockSkewMonitor>, servers: Vec<String>) {
+    let mut interval = tokio::time::interval(nova_protocol::config::NTP_SYNC_INTERVAL);
+    loop {
+        interval.tick().await;
+        for server in &servers {
+            if let Err(e) = sample_ntp_server(&monitor, server).await {
+                tracing::debug!(server = %server, error = %e, "NTP query failed");
+            }
+        }
+    }
+}
+
+/// Export the clock estimate to metrics and warn on drift.
+pub async fn run_clock_watchdog(monitor: Arc<ClockSkewMonitor>, metrics: Arc<NodeMetrics>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        interval.tick().await;
+        let status = monitor.status(now_ms());
+
+        match status {
+            ClockStatus::Unknown { sources } => {
+                metrics.clock_sources.set(sources as i64);
+            }
+            ClockStatus::Synced { offset_ms, sources } => {
+                metrics.clock_offset_ms.set(offset_ms);
+                metrics.clock_sources.set(sources as i64);
+            }
+            ClockStatus::Drifted { offset_ms, sources } => {
+                metrics.clock_offset_ms.set(offset_ms);
+                metrics.clock_sources.set(sources as i64);
+                tracing::warn!(
+                    offset_ms,
+                    sources,
+                    max_drift_ms = monitor.config().max_drift_ms,
+                    "local clock drifted from network time; block proposals suspended"
+                );
+            }
+        }
+        metrics.clock_drifted.set(i64::from(!status.can_propose()));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sntp_request_is_client_v4() {
+        let req = sntp_request();
+        assert_eq!((req[0] >> 3) & 0b111, 4, "version");
+        assert_eq!(req[0] & 0b111, 3, "mode");
+    }
+
+    #[test]
+    fn sntp_reply_timestamp_parsed() {
+        let mut reply = [0u8; 48];
+        reply[0] = 0b00_100_100; // server mode
+        let unix_secs: u64 = 1_700_000_000;
+        let ntp_secs = (unix_secs + NTP_UNIX_OFFSET_SECS) as u32;
+        reply[40..44].copy_from_slice(&ntp_secs.to_be_bytes());
+        reply[44..48].copy_from_slice(&(u32::MAX / 2 + 1).to_be_bytes()); // 0.5s
+
+        assert_eq!(parse_sntp_reply(&reply).unwrap(), unix_secs * 1_000 + 500);
+    }
+
+    #[test]
+    fn sntp_reply_rejects_garbage() {
+        assert!(parse_sntp_reply(&[0u8; 10]).is_err());
+        assert!(
+            parse_sntp_reply(&[0u8; 48]).is_err(),
+            "mode 0 is not a reply"
+        );
+    }
+}
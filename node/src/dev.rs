@@ -0,0 +1,289 @@
+//! # Dev Controls
+//!
+//! Hooks for test environments that only a `--dev` node exposes. Test
+//! suites use them to drive the chain directly instead of waiting on it:
+//!
+//! | JSON-RPC                   | REST             | Effect                                   |
+//! |----------------------------|------------------|------------------------------------------|
+//! | `nova_devMine(n)`          | `POST /dev/mine` | Run `n` consensus rounds right now       |
+//! | `nova_devIncreaseTime(ms)` |                  | Move future block timestamps forward     |
+//! | `nova_devSetTimestamp(ms)` |                  | Stamp the next block at exactly `ms`     |
+//! | `nova_devSnapshot()`       |                  | Save the whole chain, return an ID       |
+//! | `nova_devRevert(id)`       |                  | Restore a saved chain                    |
+//!
+//! Outside dev mode [`AppState::dev`](crate::api::AppState::dev) is `None`:
+//! the RPC methods answer "Method not found" and the REST routes are not
+//! mounted, exactly as if they did not exist.
+//!
+//! ## Time Travel
+//!
+//! Block timestamps are the producer's clock plus an offset the time
+//! controls adjust. Time only moves forward from the chain's point of view:
+//! `nova_devSetTimestamp` refuses a time at or before the tip's timestamp.
+//!
+//! ## Snapshots
+//!
+//! A snapshot is a full in-memory copy of the database (blocks, state tree,
+//! metadata — everything but the peer store and the audit log) plus the
+//! time offset.
+//! Reverting restores it, rebuilds the in-memory views on top (live state
+//! tree, committed snapshot, consensus chain tip) and empties the mempool.
+//! Like most dev chains, reverting to a snapshot discards it and every
+//! snapshot taken after it. Both run between consensus rounds, never
+//! during one.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use parking_lot::{Mutex, RwLock};
+
+use nova_protocol::crypto::wire::BlockHash;
+use nova_protocol::network::consensus::ConsensusEngine;
+use nova_protocol::network::consensus_loop::{ConsensusLoop, ConsensusLoopError};
+use nova_protocol::network::mempool::Mempool;
+use nova_protocol::network::peer_store::PEER_TREE;
+use nova_protocol::network::producer::BlockProducer;
+use nova_protocol::storage::audit::AUDIT_TREE;
+use nova_protocol::storage::db::{DbError, DbImage, NovaDB};
+use nova_protocol::storage::state::StateTree;
+
+/// Most blocks a single `nova_devMine` call may produce.
+pub const MAX_DEV_MINE_BLOCKS: u64 = 1_000;
+
+/// Most snapshots held at once. Each is a full copy of the database.
+pub const MAX_DEV_SNAPSHOTS: usize = 64;
+
+/// Why a dev control request failed.
+#[derive(Debug)]
+pub enum DevError {
+    /// More blocks were requested than [`MAX_DEV_MINE_BLOCKS`].
+    TooManyBlocks {
+        /// Blocks requested.
+        requested: u64,
+    },
+    /// This node is not the proposer for the current round.
+    NotProposer,
+    /// A consensus round failed.
+    Round(ConsensusLoopError),
+    /// The requested time is not after the chain tip's timestamp.
+    TimeBeforeTip {
+        /// Requested timestamp (Unix milliseconds).
+        requested: u64,
+        /// Timestamp of the current tip.
+        tip: u64,
+    },
+    /// No snapshot with this ID is held (never taken, or discarded by an
+    /// earlier revert).
+    UnknownSnapshot(u64),
+    /// [`MAX_DEV_SNAPSHOTS`] snapshots are already held.
+    TooManySnapshots,
+    /// Reading or restoring the database failed.
+    Db(DbError),
+}
+
+impl fmt::Display for DevError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyBlocks { requested } => write!(
+                f,
+                "cannot mine {} blocks at once (max {})",
+                requested, MAX_DEV_MINE_BLOCKS
+            ),
+            Self::NotProposer => write!(f, "this node is not the proposer for the current round"),
+            Self::Round(e) => write!(f, "consensus round failed: {}", e),
+            Self::TimeBeforeTip { requested, tip } => write!(
+                f,
+                "timestamp {} is not after the chain tip's timestamp {}",
+                requested, tip
+            ),
+            Self::UnknownSnapshot(id) => write!(f, "unknown snapshot {}", id),
+            Self::TooManySnapshots => write!(
+                f,
+                "{} snapshots already held; revert to free some",
+                MAX_DEV_SNAPSHOTS
+            ),
+            Self::Db(e) => write!(f, "database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DevError {}
+
+impl From<DbError> for DevError {
+    fn from(e: DbError) -> Self {
+        Self::Db(e)
+    }
+}
+
+/// A saved chain, restorable with [`DevControls::revert`].
+struct DevSnapshot {
+    id: u64,
+    image: DbImage,
+    height: u64,
+    tip_hash: BlockHash,
+    state_root: [u8; 32],
+    clock_offset_ms: i64,
+}
+
+/// Handles to the node internals the dev controls drive.
+pub struct DevControls {
+    consensus: Arc<ConsensusLoop>,
+    producer: Arc<BlockProducer>,
+    engine: Arc<RwLock<ConsensusEngine>>,
+    db: Arc<NovaDB>,
+    state_tree: Arc<RwLock<StateTree>>,
+    mempool: Arc<Mempool>,
+    snapshots: Mutex<Vec<DevSnapshot>>,
+    next_snapshot_id: AtomicU64,
+}
+
+impl DevControls {
+    /// Dev controls over a node's own components — the same loop, producer
+    /// and stores the node runs, so mined blocks go through the full
+    /// propose/finalize/commit path.
+    pub fn new(
+        consensus: Arc<ConsensusLoop>,
+        producer: Arc<BlockProducer>,
+        engine: Arc<RwLock<ConsensusEngine>>,
+        db: Arc<NovaDB>,
+        state_tree: Arc<RwLock<StateTree>>,
+        mempool: Arc<Mempool>,
+    ) -> Self {
+        Self {
+            consensus,
+            producer,
+            engine,
+            db,
+            state_tree,
+            mempool,
+            snapshots: Mutex::new(Vec::new()),
+            next_snapshot_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Runs `n` consensus rounds back to back and returns the hashes of
+    /// the blocks they produced, oldest first. Blocks may be empty.
+    ///
+    /// Blocking: call from a blocking context.
+    pub fn mine(&self, n: u64) -> Result<Vec<BlockHash>, DevError> {
+        if n > MAX_DEV_MINE_BLOCKS {
+            return Err(DevError::TooManyBlocks { requested: n });
+        }
+        let mut hashes = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            match self.consensus.run_single_round() {
+                Ok(Some(finalized)) => hashes.push(finalized.block.header.hash),
+                Ok(None) => return Err(DevError::NotProposer),
+                Err(e) => return Err(DevError::Round(e)),
+            }
+        }
+        Ok(hashes)
+    }
+
+    /// Moves every future block timestamp `ms` milliseconds forward and
+    /// returns the timestamp the next block will carry.
+    pub fn increase_time(&self, ms: u64) -> Result<u64, DevError> {
+        self.consensus.exclusive(|| {
+            let (height, _) = self.tip()?;
+            let offset = self
+                .producer
+                .clock_offset_ms()
+                .saturating_add(i64::try_from(ms).unwrap_or(i64::MAX));
+            self.producer.set_clock_offset_ms(offset);
+            Ok(self.producer.timestamp_for(height + 1))
+        })
+    }
+
+    /// Stamps the next block at exactly `timestamp_ms`; later blocks
+    /// continue from there at the usual pace.
+    pub fn set_timestamp(&self, timestamp_ms: u64) -> Result<u64, DevError> {
+        self.consensus.exclusive(|| {
+            let (height, tip_time) = self.tip()?;
+            if timestamp_ms <= tip_time {
+                return Err(DevError::TimeBeforeTip {
+                    requested: timestamp_ms,
+                    tip: tip_time,
+                });
+            }
+            let natural = self.producer.block_clock().timestamp_for(height + 1);
+            self.producer
+                .set_clock_offset_ms(timestamp_ms as i64 - natural as i64);
+            // A wall clock keeps running until the block is produced.
+            self.producer.pin_timestamp(height + 1, timestamp_ms);
+            Ok(timestamp_ms)
+        })
+    }
+
+    /// Saves the whole chain and returns the snapshot's ID.
+    pub fn snapshot(&self) -> Result<u64, DevError> {
+        let mut snapshots = self.snapshots.lock();
+        if snapshots.len() >= MAX_DEV_SNAPSHOTS {
+            return Err(DevError::TooManySnapshots);
+        }
+        let snapshot = self.consensus.exclusive(|| -> Result<_, DevError> {
+            let height = self.db.get_latest_block_height()?.unwrap_or(0);
+            let tip_hash = self
+                .db
+                .get_block(height)?
+                .map_or(BlockHash::zero(), |b| b.header.hash);
+            Ok(DevSnapshot {
+                id: self.next_snapshot_id.fetch_add(1, Ordering::Relaxed),
+                // Rehash before copying so the root matches the stored nodes.
+                state_root: self.state_tree.read().root(),
+                image: self.db.capture_image(&[PEER_TREE, AUDIT_TREE])?,
+                height,
+                tip_hash,
+                clock_offset_ms: self.producer.clock_offset_ms(),
+            })
+        })?;
+        let id = snapshot.id;
+        tracing::info!(
+            id,
+            height = snapshot.height,
+            entries = snapshot.image.len(),
+            "dev snapshot taken"
+        );
+        snapshots.push(snapshot);
+        Ok(id)
+    }
+
+    /// Restores the chain saved as snapshot `id`, discarding it and every
+    /// later snapshot. Pending transactions are dropped.
+    pub fn revert(&self, id: u64) -> Result<(), DevError> {
+        let mut snapshots = self.snapshots.lock();
+        let index = snapshots
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or(DevError::UnknownSnapshot(id))?;
+        let snapshot = snapshots.drain(index..).next().expect("index in range");
+
+        self.consensus.exclusive(|| -> Result<(), DevError> {
+            self.db.restore_image(&snapshot.image)?;
+            self.mempool.clear();
+            {
+                let mut tree = self.state_tree.write();
+                let mut restored = StateTree::from_root((*self.db).clone(), snapshot.state_root);
+                if let Some(committed) = tree.committed_state() {
+                    restored = restored.with_committed_state(Arc::clone(committed));
+                }
+                restored.commit(snapshot.height);
+                *tree = restored;
+            }
+            self.engine
+                .write()
+                .set_chain_state(snapshot.height + 1, snapshot.tip_hash);
+            self.producer.set_clock_offset_ms(snapshot.clock_offset_ms);
+            Ok(())
+        })?;
+        tracing::info!(id, height = snapshot.height, "reverted to dev snapshot");
+        Ok(())
+    }
+
+    /// Height and timestamp of the chain tip.
+    fn tip(&self) -> Result<(u64, u64), DevError> {
+        let height = self.db.get_latest_block_height()?.unwrap_or(0);
+        let timestamp = self.db.get_block(height)?.map_or(0, |b| b.header.timestamp);
+        Ok((height, timestamp))
+    }
+}
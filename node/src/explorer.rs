@@ -0,0 +1,99 @@
+//! # Dev Explorer
+//!
+//! A single-page block explorer served at `/explorer` when the node runs
+//! with `--dev`. The page is one self-contained HTML file compiled into the
+//! binary: no build step, no CDN, nothing to install. It polls the regular
+//! REST API on the same origin (`/status`, `/blocks/:height`,
+//! `/transactions/:hash`, `/accounts/:address`, `/mempool`), so whatever it
+//! shows is exactly what any other API client would see.
+//!
+//! Outside dev mode the route is not mounted at all.
+
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+
+/// The explorer page, HTML, CSS and script inline.
+const INDEX_HTML: &str = include_str!("explorer/index.html");
+
+/// Adds the `/explorer` route to an API router.
+pub fn mount(router: Router) -> Router {
+    router.route("/explorer", get(index_handler))
+}
+
+/// `GET /explorer` — the embedded explorer page.
+async fn index_handler() -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        INDEX_HTML,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn get(router: Router, path: &str) -> (StatusCode, Option<String>, String) {
+        let req = Request::builder().uri(path).body(Body::empty()).unwrap();
+        let resp = router.oneshot(req).await.unwrap();
+        let status = resp.status();
+        let content_type = resp
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|v| v.to_str().unwrap().to_string());
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            content_type,
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    // -- 1. The page is served as HTML --------------------------------------
+
+    #[tokio::test]
+    async fn explorer_serves_embedded_page() {
+        let (status, content_type, body) = get(mount(Router::new()), "/explorer").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(content_type.unwrap().starts_with("text/html"));
+        assert!(body.contains("<title>NOVA Dev Explorer</title>"));
+    }
+
+    // -- 2. The page only talks to the node's own API -------------------------
+
+    #[test]
+    fn page_has_no_external_dependencies() {
+        for needle in ["http://", "https://", "//cdn", "<link "] {
+            assert!(!INDEX_HTML.contains(needle), "found {needle:?}");
+        }
+        for endpoint in [
+            "/status",
+            "/blocks/",
+            "/transactions/",
+            "/accounts/",
+            "/mempool",
+        ] {
+            assert!(INDEX_HTML.contains(endpoint), "missing {endpoint}");
+        }
+    }
+
+    // -- 3. Unmounted routers do not expose it -------------------------------
+
+    #[tokio::test]
+    async fn explorer_absent_unless_mounted() {
+        let (status, _, _) = get(Router::new(), "/explorer").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,699 @@
+//! # SQL Export
+//!
+//! Analysts want SQL, not sled. With `--sql-export`, the node attaches an
+//! [`Indexer`] to its block producer and registers [`SqlExport`], an
+//! [`IndexHandler`] that mirrors every committed block into SQLite or
+//! Postgres:
+//!
+//! ```text
+//! --sql-export sqlite:export.db                  (under the data directory)
+//! --sql-export postgres://nova@localhost/nova
+//! ```
+//!
+//! ## Schema
+//!
+//! Created on first connect (see [`SCHEMA`]). Amounts are photons, times
+//! are Unix milliseconds, hashes and IDs are lower-case hex.
+//!
+//! | Table               | One row per                      | Key                       |
+//! |---------------------|----------------------------------|---------------------------|
+//! | `blocks`            | block                            | `hash`                    |
+//! | `transactions`      | transaction in a block           | `block_hash, position`    |
+//! | `token_transfers`   | transfer, or payout of a batch   | `block_hash, seq`         |
+//! | `account_changes`   | account a block wrote            | `block_hash, address`     |
+//! | `export_checkpoint` | — (a single row, `id = 1`)       | `id`                      |
+//!
+//! Every data table has a `height` and a `deleted` column. The `accounts`
+//! view holds each account's latest live balance and nonce.
+//!
+//! Account changes come from the indexer's state diffs, which are only
+//! recorded while an indexer is attached: blocks committed before the
+//! first `--sql-export` run are exported without them.
+//!
+//! ## Checkpoint
+//!
+//! A block's rows and the checkpoint row are written in one SQL
+//! transaction. On start the indexer cursor is reset to the checkpoint, so
+//! the export resumes from what the SQL database actually holds — also
+//! after it was restored from a backup, or dropped to rebuild from genesis.
+//!
+//! ## Reorgs
+//!
+//! Rows are never deleted. When a block arrives at a height the export
+//! already holds under another hash, every row at that height or above is
+//! soft-deleted (`deleted = TRUE`) and the new branch is inserted. A block
+//! that returns to the chain is revived rather than duplicated. Queries
+//! should filter on `NOT deleted`; the `accounts` view already does.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+
+use nova_protocol::indexer::{ChainEvent, HandlerError, IndexHandler, Indexer, StateDiff};
+use nova_protocol::storage::block::Block;
+
+/// Name the export's cursor is stored under in the indexer.
+pub const EXPORT_HANDLER: &str = "sql-export";
+
+/// Tables and indexes, in creation order. Portable between SQLite and
+/// Postgres; the `accounts` view is created separately because the two
+/// disagree on `CREATE VIEW IF NOT EXISTS`.
+pub const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS blocks (
+        hash         TEXT PRIMARY KEY,
+        height       BIGINT NOT NULL,
+        parent_hash  TEXT NOT NULL,
+        timestamp_ms BIGINT NOT NULL,
+        validator    TEXT NOT NULL,
+        tx_count     BIGINT NOT NULL,
+        fees         BIGINT NOT NULL,
+        deleted      BOOLEAN NOT NULL DEFAULT FALSE
+    )",
+    "CREATE INDEX IF NOT EXISTS blocks_height ON blocks (height)",
+    "CREATE TABLE IF NOT EXISTS transactions (
+        block_hash   TEXT NOT NULL,
+        position     BIGINT NOT NULL,
+        height       BIGINT NOT NULL,
+        tx_id        TEXT NOT NULL,
+        tx_type      TEXT NOT NULL,
+        sender       TEXT NOT NULL,
+        receiver     TEXT NOT NULL,
+        amount       BIGINT NOT NULL,
+        currency     TEXT NOT NULL,
+        fee          BIGINT NOT NULL,
+        nonce        BIGINT NOT NULL,
+        timestamp_ms BIGINT NOT NULL,
+        memo         TEXT,
+        deleted      BOOLEAN NOT NULL DEFAULT FALSE,
+        PRIMARY KEY (block_hash, position)
+    )",
+    "CREATE INDEX IF NOT EXISTS transactions_tx_id ON transactions (tx_id)",
+    "CREATE INDEX IF NOT EXISTS transactions_sender ON transactions (sender)",
+    "CREATE INDEX IF NOT EXISTS transactions_receiver ON transactions (receiver)",
+    "CREATE TABLE IF NOT EXISTS token_transfers (
+        block_hash   TEXT NOT NULL,
+        seq          BIGINT NOT NULL,
+        height       BIGINT NOT NULL,
+        tx_id        TEXT NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address   TEXT NOT NULL,
+        amount       BIGINT NOT NULL,
+        deleted      BOOLEAN NOT NULL DEFAULT FALSE,
+        PRIMARY KEY (block_hash, seq)
+    )",
+    "CREATE INDEX IF NOT EXISTS token_transfers_from ON token_transfers (from_address)",
+    "CREATE INDEX IF NOT EXISTS token_transfers_to ON token_transfers (to_address)",
+    "CREATE TABLE IF NOT EXISTS account_changes (
+        block_hash     TEXT NOT NULL,
+        address        TEXT NOT NULL,
+        height         BIGINT NOT NULL,
+        balance_before BIGINT,
+        balance_after  BIGINT,
+        nonce_after    BIGINT,
+        deleted        BOOLEAN NOT NULL DEFAULT FALSE,
+        PRIMARY KEY (block_hash, address)
+    )",
+    "CREATE INDEX IF NOT EXISTS account_changes_address ON account_changes (address, height)",
+    "CREATE TABLE IF NOT EXISTS export_checkpoint (
+        id         BIGINT PRIMARY KEY,
+        height     BIGINT NOT NULL,
+        block_hash TEXT NOT NULL
+    )",
+];
+
+/// Body of the `accounts` view: the latest live change of every account
+/// that still exists.
+const ACCOUNTS_VIEW: &str = "accounts AS
+    SELECT c.address, c.balance_after AS balance, c.nonce_after AS nonce, c.height
+    FROM account_changes c
+    WHERE NOT c.deleted
+      AND c.balance_after IS NOT NULL
+      AND c.height = (
+          SELECT MAX(m.height) FROM account_changes m
+          WHERE m.address = c.address AND NOT m.deleted
+      )";
+
+/// Tables whose rows carry `height` and `deleted`.
+const DATA_TABLES: &[&str] = &[
+    "blocks",
+    "transactions",
+    "token_transfers",
+    "account_changes",
+];
+
+// Statements use `$n` placeholders, each once and in order: Postgres
+// requires them, and SQLite binds them positionally in that case.
+
+const SELECT_LIVE_HASH: &str = "SELECT hash FROM blocks WHERE height = $1 AND NOT deleted";
+
+const SELECT_CHECKPOINT: &str = "SELECT height FROM export_checkpoint WHERE id = 1";
+
+const INSERT_BLOCK: &str = "INSERT INTO blocks
+    (hash, height, parent_hash, timestamp_ms, validator, tx_count, fees)
+    VALUES ($1, $2, $3, $4, $5, $6, $7)
+    ON CONFLICT (hash) DO UPDATE SET deleted = FALSE";
+
+const INSERT_TRANSACTION: &str = "INSERT INTO transactions
+    (block_hash, position, height, tx_id, tx_type, sender, receiver, amount, currency,
+     fee, nonce, timestamp_ms, memo)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+    ON CONFLICT (block_hash, position) DO UPDATE SET deleted = FALSE";
+
+const INSERT_TRANSFER: &str = "INSERT INTO token_transfers
+    (block_hash, seq, height, tx_id, from_address, to_address, amount)
+    VALUES ($1, $2, $3, $4, $5, $6, $7)
+    ON CONFLICT (block_hash, seq) DO UPDATE SET deleted = FALSE";
+
+const INSERT_ACCOUNT_CHANGE: &str = "INSERT INTO account_changes
+    (block_hash, address, height, balance_before, balance_after, nonce_after)
+    VALUES ($1, $2, $3, $4, $5, $6)
+    ON CONFLICT (block_hash, address) DO UPDATE SET deleted = FALSE";
+
+const UPSERT_CHECKPOINT: &str = "INSERT INTO export_checkpoint (id, height, block_hash)
+    VALUES (1, $1, $2)
+    ON CONFLICT (id) DO UPDATE SET height = excluded.height, block_hash = excluded.block_hash";
+
+// ---------------------------------------------------------------------------
+// Statements
+// ---------------------------------------------------------------------------
+
+/// A bound statement parameter. Integers are stored as `BIGINT`.
+#[derive(Debug, Clone, PartialEq)]
+enum Param {
+    Int(Option<i64>),
+    Text(Option<String>),
+}
+
+impl Param {
+    fn int(value: u64) -> Result<Self> {
+        Ok(Self::Int(Some(to_bigint(value)?)))
+    }
+
+    fn opt_int(value: Option<u64>) -> Result<Self> {
+        Ok(Self::Int(value.map(to_bigint).transpose()?))
+    }
+
+    fn text(value: impl Into<String>) -> Self {
+        Self::Text(Some(value.into()))
+    }
+
+    fn as_postgres(&self) -> &(dyn postgres::types::ToSql + Sync) {
+        match self {
+            Self::Int(value) => value,
+            Self::Text(value) => value,
+        }
+    }
+}
+
+fn to_bigint(value: u64) -> Result<i64> {
+    i64::try_from(value).with_context(|| format!("{} does not fit a BIGINT column", value))
+}
+
+impl rusqlite::ToSql for Param {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        match self {
+            Self::Int(value) => value.to_sql(),
+            Self::Text(value) => value.to_sql(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Statement {
+    sql: String,
+    params: Vec<Param>,
+}
+
+impl Statement {
+    fn new(sql: impl Into<String>, params: Vec<Param>) -> Self {
+        Self {
+            sql: sql.into(),
+            params,
+        }
+    }
+}
+
+/// The statements that export `block`, given the hash of the live block
+/// the export already holds at its height.
+fn plan_block(
+    block: &Block,
+    diff: Option<&StateDiff>,
+    live: Option<&str>,
+) -> Result<Vec<Statement>> {
+    let header = &block.header;
+    let height = header.height;
+    let hash = header.hash_hex();
+    let mut statements = Vec::new();
+
+    let checkpoint = Statement::new(
+        UPSERT_CHECKPOINT,
+        vec![Param::int(height)?, Param::text(&hash)],
+    );
+    if live == Some(hash.as_str()) {
+        // Delivered again, e.g. after a crash before the indexer cursor
+        // moved. Only rows of an abandoned branch above it can be stale.
+        statements.extend(soft_delete_from(height + 1)?);
+        statements.push(checkpoint);
+        return Ok(statements);
+    }
+    statements.extend(soft_delete_from(height)?);
+
+    statements.push(Statement::new(
+        INSERT_BLOCK,
+        vec![
+            Param::text(&hash),
+            Param::int(height)?,
+            Param::text(header.parent_hash_hex()),
+            Param::int(header.timestamp)?,
+            Param::text(&header.validator),
+            Param::int(block.transactions.len() as u64)?,
+            Param::int(header.fees)?,
+        ],
+    ));
+    for (position, tx) in block.transactions.iter().enumerate() {
+        statements.push(Statement::new(
+            INSERT_TRANSACTION,
+            vec![
+                Param::text(&hash),
+                Param::int(position as u64)?,
+                Param::int(height)?,
+                Param::text(&tx.id),
+                Param::text(tx.tx_type.to_string()),
+                Param::text(&tx.sender),
+                Param::text(&tx.receiver),
+                Param::int(tx.amount.value)?,
+                Param::text(tx.amount.currency.to_string()),
+                Param::int(tx.fee)?,
+                Param::int(tx.nonce)?,
+                Param::int(tx.timestamp)?,
+                Param::Text(tx.memo.clone()),
+            ],
+        ));
+    }
+    let transfers = ChainEvent::from_block(block)
+        .into_iter()
+        .filter_map(|event| match event {
+            ChainEvent::Transfer {
+                tx_id,
+                from,
+                to,
+                amount,
+                ..
+            } => Some((tx_id, from, to, amount)),
+            _ => None,
+        });
+    for (seq, (tx_id, from, to, amount)) in transfers.enumerate() {
+        statements.push(Statement::new(
+            INSERT_TRANSFER,
+            vec![
+                Param::text(&hash),
+                Param::int(seq as u64)?,
+                Param::int(height)?,
+                Param::text(tx_id),
+                Param::text(from),
+                Param::text(to),
+                Param::int(amount)?,
+            ],
+        ));
+    }
+    for change in diff.map(|d| d.changes.as_slice()).unwrap_or_default() {
+        let before = change.account_before();
+        let after = change.account_after();
+        // Protocol records share the state tree; only accounts are exported.
+        if before.is_none() && after.is_none() {
+            continue;
+        }
+        statements.push(Statement::new(
+            INSERT_ACCOUNT_CHANGE,
+            vec![
+                Param::text(&hash),
+                Param::text(&change.key),
+                Param::int(height)?,
+                Param::opt_int(before.map(|a| a.balance))?,
+                Param::opt_int(after.as_ref().map(|a| a.balance))?,
+                Param::opt_int(after.map(|a| a.nonce))?,
+            ],
+        ));
+    }
+    statements.push(checkpoint);
+    Ok(statements)
+}
+
+/// Soft-deletes every live row at `height` or above.
+fn soft_delete_from(height: u64) -> Result<Vec<Statement>> {
+    DATA_TABLES
+        .iter()
+        .map(|table| {
+            Ok(Statement::new(
+                format!(
+                    "UPDATE {} SET deleted = TRUE WHERE height >= $1 AND NOT deleted",
+                    table
+                ),
+                vec![Param::int(height)?],
+            ))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Connections
+// ---------------------------------------------------------------------------
+
+/// Where `--sql-export` writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// A SQLite database file.
+    Sqlite(PathBuf),
+    /// A Postgres connection string.
+    Postgres(String),
+}
+
+impl ExportTarget {
+    /// Parses `sqlite:<path>` or a `postgres://` / `postgresql://` URL.
+    /// Relative SQLite paths are resolved under `data_dir`.
+    pub fn parse(target: &str, data_dir: &Path) -> Result<Self> {
+        if let Some(path) = target.strip_prefix("sqlite:") {
+            if path.is_empty() {
+                bail!("sqlite export target needs a path, e.g. sqlite:export.db");
+            }
+            Ok(Self::Sqlite(data_dir.join(path)))
+        } else if target.starts_with("postgres://") || target.starts_with("postgresql://") {
+            Ok(Self::Postgres(target.to_string()))
+        } else {
+            bail!(
+                "unsupported export target {} (expected sqlite:<path> or postgres://...)",
+                target
+            )
+        }
+    }
+}
+
+enum Connection {
+    Sqlite(rusqlite::Connection),
+    Postgres(postgres::Client),
+}
+
+impl Connection {
+    fn open(target: &ExportTarget) -> Result<Self> {
+        match target {
+            ExportTarget::Sqlite(path) => {
+                let conn = rusqlite::Connection::open(path)
+                    .with_context(|| format!("failed to open {}", path.display()))?;
+                Ok(Self::Sqlite(conn))
+            }
+            ExportTarget::Postgres(url) => {
+                let client = blocking(|| postgres::Client::connect(url, postgres::NoTls))
+                    .context("failed to connect to Postgres")?;
+                Ok(Self::Postgres(client))
+            }
+        }
+    }
+
+    fn create_schema(&mut self) -> Result<()> {
+        match self {
+            Self::Sqlite(conn) => {
+                for sql in SCHEMA {
+                    conn.execute(sql, [])?;
+                }
+                conn.execute(&format!("CREATE VIEW IF NOT EXISTS {}", ACCOUNTS_VIEW), [])?;
+            }
+            Self::Postgres(client) => blocking(|| {
+                for sql in SCHEMA {
+                    client.execute(*sql, &[])?;
+                }
+                client.execute(&format!("CREATE OR REPLACE VIEW {}", ACCOUNTS_VIEW), &[])?;
+                Ok::<_, postgres::Error>(())
+            })?,
+        }
+        Ok(())
+    }
+
+    fn checkpoint(&mut self) -> Result<Option<u64>> {
+        let height: Option<i64> = match self {
+            Self::Sqlite(conn) => {
+                use rusqlite::OptionalExtension;
+                conn.query_row(SELECT_CHECKPOINT, [], |row| row.get(0))
+                    .optional()?
+            }
+            Self::Postgres(client) => {
+                blocking(|| client.query_opt(SELECT_CHECKPOINT, &[]))?.map(|row| row.get(0))
+            }
+        };
+        Ok(height.map(|h| h as u64))
+    }
+
+    /// Runs the statements for `block` in one transaction.
+    fn write_block(&mut self, block: &Block, diff: Option<&StateDiff>) -> Result<()> {
+        let height = Param::int(block.header.height)?;
+        match self {
+            Self::Sqlite(conn) => {
+                use rusqlite::OptionalExtension;
+                let tx = conn.transaction()?;
+                let live: Option<String> = tx
+                    .query_row(SELECT_LIVE_HASH, [&height], |row| row.get(0))
+                    .optional()?;
+                for statement in plan_block(block, diff, live.as_deref())? {
+                    tx.execute(
+                        &statement.sql,
+                        rusqlite::params_from_iter(statement.params.iter()),
+                    )?;
+                }
+                tx.commit()?;
+            }
+            Self::Postgres(client) => blocking(|| {
+                let mut tx = client.transaction()?;
+                let live: Option<String> = tx
+                    .query_opt(SELECT_LIVE_HASH, &[height.as_postgres()])?
+                    .map(|row| row.get(0));
+                for statement in plan_block(block, diff, live.as_deref())? {
+                    let params: Vec<_> = statement.params.iter().map(Param::as_postgres).collect();
+                    tx.execute(statement.sql.as_str(), &params)?;
+                }
+                tx.commit()?;
+                Ok::<_, anyhow::Error>(())
+            })?,
+        }
+        Ok(())
+    }
+}
+
+/// Runs a synchronous Postgres call from inside the runtime. The client
+/// drives its own runtime, which tokio only allows outside async context.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(_) => tokio::task::block_in_place(f),
+        Err(_) => f(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Handler
+// ---------------------------------------------------------------------------
+
+/// The `--sql-export` index handler.
+pub struct SqlExport {
+    conn: Mutex<Connection>,
+    /// The diff of the block being delivered, held until its
+    /// `on_block_committed`.
+    pending_diff: Mutex<Option<StateDiff>>,
+}
+
+impl SqlExport {
+    /// Connects to `target` and creates the schema if it is missing.
+    pub fn connect(target: &ExportTarget) -> Result<Self> {
+        let mut conn = Connection::open(target)?;
+        conn.create_schema()
+            .context("failed to create the export schema")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            pending_diff: Mutex::new(None),
+        })
+    }
+
+    /// Height of the last block the export database holds.
+    pub fn checkpoint(&self) -> Result<Option<u64>> {
+        self.conn.lock().checkpoint()
+    }
+
+    /// Resets the indexer cursor to the checkpoint and registers the
+    /// handler, so delivery resumes after the last exported block.
+    pub fn attach(self, indexer: &Indexer) -> Result<()> {
+        let checkpoint = self.checkpoint()?;
+        indexer.set_cursor(EXPORT_HANDLER, checkpoint)?;
+        indexer.register(Arc::new(self))?;
+        Ok(())
+    }
+}
+
+impl IndexHandler for SqlExport {
+    fn name(&self) -> &str {
+        EXPORT_HANDLER
+    }
+
+    fn on_state_diff(&self, diff: &StateDiff) -> Result<(), HandlerError> {
+        *self.pending_diff.lock() = Some(diff.clone());
+        Ok(())
+    }
+
+    fn on_block_committed(&self, block: &Block) -> Result<(), HandlerError> {
+        let diff = self
+            .pending_diff
+            .lock()
+            .take()
+            .filter(|diff| diff.height == block.header.height);
+        self.conn.lock().write_block(block, diff.as_ref())?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_protocol::indexer::StateChange;
+    use nova_protocol::storage::db::NovaDB;
+    use nova_protocol::storage::state::AccountState;
+    use nova_protocol::transaction::builder::TransactionBuilder;
+    use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+    use nova_protocol::transaction::Transaction;
+
+    fn transfer(nonce: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(10)
+            .nonce(nonce)
+            .timestamp(1_700_000_000_000 + nonce)
+            .memo("order-7")
+            .build_unchecked()
+    }
+
+    fn count(export: &SqlExport, sql: &str) -> i64 {
+        match &*export.conn.lock() {
+            Connection::Sqlite(conn) => conn.query_row(sql, [], |row| row.get(0)).unwrap(),
+            Connection::Postgres(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parses_targets() {
+        let dir = Path::new("/data");
+        assert_eq!(
+            ExportTarget::parse("sqlite:export.db", dir).unwrap(),
+            ExportTarget::Sqlite(dir.join("export.db"))
+        );
+        assert_eq!(
+            ExportTarget::parse("postgres://nova@db/nova", dir).unwrap(),
+            ExportTarget::Postgres("postgres://nova@db/nova".to_string())
+        );
+        assert!(ExportTarget::parse("sqlite:", dir).is_err());
+        assert!(ExportTarget::parse("mysql://db", dir).is_err());
+    }
+
+    #[test]
+    fn exports_blocks_and_soft_deletes_abandoned_branches() {
+        let dir = tempfile::tempdir().unwrap();
+        let export = SqlExport::connect(&ExportTarget::Sqlite(dir.path().join("e.db"))).unwrap();
+        assert_eq!(export.checkpoint().unwrap(), None);
+
+        let genesis = Block::genesis();
+        let block1 = Block::new(&genesis, vec![transfer(1)], "v".into(), [1; 32]).with_fees(10);
+        export.on_block_committed(&genesis).unwrap();
+        export
+            .on_state_diff(&StateDiff {
+                height: 1,
+                changes: vec![StateChange {
+                    key: "nova1bob".to_string(),
+                    before: None,
+                    after: Some(AccountState::with_balance(1_000).to_bytes()),
+                }],
+            })
+            .unwrap();
+        export.on_block_committed(&block1).unwrap();
+        // At-least-once delivery: the same block again changes nothing.
+        export.on_block_committed(&block1).unwrap();
+
+        assert_eq!(export.checkpoint().unwrap(), Some(1));
+        assert_eq!(count(&export, "SELECT COUNT(*) FROM blocks"), 2);
+        assert_eq!(
+            count(
+                &export,
+                "SELECT COUNT(*) FROM transactions WHERE memo = 'order-7'"
+            ),
+            1
+        );
+        assert_eq!(
+            count(&export, "SELECT SUM(amount) FROM token_transfers"),
+            1_000
+        );
+        assert_eq!(
+            count(
+                &export,
+                "SELECT balance FROM accounts WHERE address = 'nova1bob'"
+            ),
+            1_000
+        );
+
+        // A competing block 1 replaces the first one.
+        let fork = Block::new(&genesis, vec![], "w".into(), [2; 32]);
+        export.on_block_committed(&fork).unwrap();
+        assert_eq!(
+            count(&export, "SELECT COUNT(*) FROM blocks WHERE NOT deleted"),
+            2
+        );
+        assert_eq!(
+            count(
+                &export,
+                "SELECT COUNT(*) FROM transactions WHERE NOT deleted"
+            ),
+            0
+        );
+        assert_eq!(count(&export, "SELECT COUNT(*) FROM accounts"), 0);
+
+        // Switching back revives the original rows instead of duplicating.
+        export.on_block_committed(&block1).unwrap();
+        assert_eq!(count(&export, "SELECT COUNT(*) FROM blocks"), 3);
+        assert_eq!(
+            count(
+                &export,
+                "SELECT COUNT(*) FROM transactions WHERE NOT deleted"
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn resumes_from_the_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = ExportTarget::Sqlite(dir.path().join("e.db"));
+        let db = Arc::new(NovaDB::open_temporary().unwrap());
+        let genesis = Block::genesis();
+        let block1 = Block::new(&genesis, vec![transfer(1)], "v".into(), [1; 32]);
+        db.put_block(&genesis).unwrap();
+        db.put_block(&block1).unwrap();
+
+        // The indexer's own cursor claims everything was delivered, but
+        // the export database only has genesis.
+        let export = SqlExport::connect(&target).unwrap();
+        export.on_block_committed(&genesis).unwrap();
+        drop(export);
+        let indexer = Indexer::open(Arc::clone(&db)).unwrap();
+        indexer.set_cursor(EXPORT_HANDLER, Some(1)).unwrap();
+
+        SqlExport::connect(&target)
+            .unwrap()
+            .attach(&indexer)
+            .unwrap();
+        assert_eq!(indexer.cursor(EXPORT_HANDLER).unwrap(), Some(0));
+        assert_eq!(indexer.deliver().unwrap(), 1);
+
+        let export = SqlExport::connect(&target).unwrap();
+        assert_eq!(export.checkpoint().unwrap(), Some(1));
+    }
+}
@@ -0,0 +1,170 @@
+//! # Emergency Halt Records
+//!
+//! The `halt sign`, `halt sign-resume` and `halt submit` subcommands, which
+//! pass a halt or resume record ([`nova_protocol::network::halt`]) from
+//! validator to validator until enough stake has signed:
+//!
+//! ```text
+//! validator 1  nova-node halt sign -f halt.json --height 1200 --reason "..."
+//! validator 2  nova-node halt sign -f halt.json
+//! ...
+//! every node   nova-node halt submit -f halt.json
+//! ```
+//!
+//! Resumes work the same way with `sign-resume` and
+//! `--min-protocol-version`. Signing never opens a connection; the node
+//! checks the signatures against its validator set when the record is
+//! submitted.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use nova_protocol::network::halt::{EmergencyHalt, ResumeAction};
+
+use crate::cli::{self, HaltSignArgs, HaltSignResumeArgs, HaltSubmitArgs};
+
+/// Adds the validator's signature to a halt record, creating it if needed.
+pub fn sign(args: HaltSignArgs) -> Result<()> {
+    let mut halt = match read_record::<EmergencyHalt>(&args.file)? {
+        Some(halt) => {
+            if args.height.is_some_and(|h| h != halt.halt_height)
+                || args.reason.as_ref().is_some_and(|r| *r != halt.reason)
+            {
+                bail!(
+                    "{} halts at height {} ({:?}); refusing to sign different terms",
+                    args.file.display(),
+                    halt.halt_height,
+                    halt.reason
+                );
+            }
+            halt
+        }
+        None => EmergencyHalt::new(
+            args.height
+                .context("--height is required to start a halt record")?,
+            args.reason
+                .context("--reason is required to start a halt record")?,
+        ),
+    };
+
+    let (keypair, _) = crate::read_validator_key(&cli::resolve_data_dir(&args.data_dir))?;
+    halt.sign(&keypair);
+    write_record(&args.file, &halt)?;
+    eprintln!(
+        "Signed the halt at height {}: {} signature(s)",
+        halt.halt_height,
+        halt.signatures.len()
+    );
+    Ok(())
+}
+
+/// Adds the validator's signature to a resume record, creating it if needed.
+pub fn sign_resume(args: HaltSignResumeArgs) -> Result<()> {
+    let mut resume = match read_record::<ResumeAction>(&args.file)? {
+        Some(resume) => {
+            if args.height.is_some_and(|h| h != resume.halt_height)
+                || args
+                    .min_protocol_version
+                    .as_ref()
+                    .is_some_and(|v| *v != resume.min_protocol_version)
+            {
+                bail!(
+                    "{} resumes the halt at {} for protocol {}; refusing to sign different terms",
+                    args.file.display(),
+                    resume.halt_height,
+                    resume.min_protocol_version
+                );
+            }
+            resume
+        }
+        None => ResumeAction::new(
+            args.height
+                .context("--height is required to start a resume record")?,
+            args.min_protocol_version
+                .context("--min-protocol-version is required to start a resume record")?,
+        ),
+    };
+
+    let (keypair, _) = crate::read_validator_key(&cli::resolve_data_dir(&args.data_dir))?;
+    resume.sign(&keypair);
+    write_record(&args.file, &resume)?;
+    eprintln!(
+        "Signed the resume of the halt at height {}: {} signature(s)",
+        resume.halt_height,
+        resume.signatures.len()
+    );
+    Ok(())
+}
+
+/// Posts a signed record to `/admin/halt` or `/admin/resume`, whichever
+/// its fields call for.
+pub async fn submit(args: HaltSubmitArgs) -> Result<()> {
+    let body = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("failed to read {}", args.file.display()))?;
+    let route = admin_route(&body)?;
+
+    let url = format!("{}{}", args.rpc_url.trim_end_matches('/'), route);
+    let response = crate::reqwest_post_json_stub(&url, &body).await?;
+    let value: serde_json::Value = serde_json::from_str(&response)
+        .with_context(|| format!("unexpected response from {}", url))?;
+    if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+        bail!("node rejected the record: {}", error);
+    }
+    println!("{}", serde_json::to_string_pretty(&value)?);
+    Ok(())
+}
+
+/// Resume records are the ones naming a protocol version.
+fn admin_route(body: &str) -> Result<&'static str> {
+    let value: serde_json::Value = serde_json::from_str(body).context("record is not JSON")?;
+    if value.get("min_protocol_version").is_some() {
+        serde_json::from_value::<ResumeAction>(value).context("malformed resume record")?;
+        Ok("/admin/resume")
+    } else {
+        serde_json::from_value::<EmergencyHalt>(value).context("malformed halt record")?;
+        Ok("/admin/halt")
+    }
+}
+
+pub(crate) fn read_record<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let record = serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a valid record", path.display()))?;
+    Ok(Some(record))
+}
+
+pub(crate) fn write_record<T: Serialize>(path: &Path, record: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(record)? + "\n";
+    std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_route_by_kind() {
+        let halt = EmergencyHalt::new(1200, "state root mismatch");
+        let resume = ResumeAction::new(1200, "0.2.0");
+        assert_eq!(
+            admin_route(&serde_json::to_string(&halt).unwrap()).unwrap(),
+            "/admin/halt"
+        );
+        assert_eq!(
+            admin_route(&serde_json::to_string(&resume).unwrap()).unwrap(),
+            "/admin/resume"
+        );
+        assert!(admin_route(r#"{"halt_height": "soon"}"#).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! # Gossip Journal
+//!
+//! The node side of the gossip journal ([`nova_protocol::network::journal`]):
+//! opening it for `run --gossip-journal`, and the `journal replay`
+//! subcommand.
+//!
+//! ## Replaying
+//!
+//! `journal replay` reads a journal — from a running node too, since the
+//! file is append-only — and re-runs validation on every frame in arrival
+//! order. Each verdict is compared with the one recorded at arrival, and
+//! each frame with its recorded hash. It exits non-zero if anything
+//! differs, which points at a validation change between builds or at a
+//! damaged journal. `--verbose` prints the whole timeline.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use nova_protocol::identity::AddressPolicy;
+use nova_protocol::network::journal::{
+    read_journal, replay as replay_entries, GossipJournal, ReplayedEntry, Verdict,
+};
+
+use crate::cli::{self, JournalReplayArgs, OutputFormat};
+
+/// Opens the journal `run --gossip-journal` asked for, relative to the
+/// data directory unless absolute.
+pub fn open(args: &cli::RunArgs, data_dir: &Path, policy: AddressPolicy) -> Result<GossipJournal> {
+    let path = args
+        .gossip_journal
+        .as_ref()
+        .map(|path| data_dir.join(path))
+        .context("no gossip journal configured")?;
+    GossipJournal::open(&path, args.gossip_journal_max_mb * 1024 * 1024, policy)
+        .with_context(|| format!("failed to open gossip journal {}", path.display()))
+}
+
+/// Outcome of `journal replay`, as printed with `--output json`.
+#[derive(Debug, Serialize)]
+struct ReplayReport {
+    entries: usize,
+    accepted: usize,
+    rejected: usize,
+    diverged: usize,
+    truncated: bool,
+    timeline: Vec<ReplayedEntry>,
+}
+
+/// `journal replay` — re-validates every journaled message and exits
+/// non-zero if a verdict or hash does not reproduce.
+pub fn replay(args: JournalReplayArgs) -> Result<()> {
+    let contents = read_journal(&args.file)
+        .with_context(|| format!("failed to read gossip journal {}", args.file.display()))?;
+    let policy = if args.permissive_addresses {
+        AddressPolicy::Permissive
+    } else {
+        AddressPolicy::Strict
+    };
+    let replayed = replay_entries(&contents.entries, policy);
+    let report = summarize(replayed, contents.truncated, args.verbose);
+
+    if args.output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for entry in &report.timeline {
+            print_entry(entry);
+        }
+        println!(
+            "{} entries: {} accepted, {} rejected, {} diverged",
+            report.entries, report.accepted, report.rejected, report.diverged
+        );
+        if report.truncated {
+            println!("(a partly written last frame was skipped)");
+        }
+    }
+    if report.diverged > 0 {
+        bail!(
+            "{} journal entries did not replay identically",
+            report.diverged
+        );
+    }
+    Ok(())
+}
+
+/// Counts `replayed`, keeping every entry in the timeline if `verbose`
+/// and only the diverging ones otherwise.
+fn summarize(replayed: Vec<ReplayedEntry>, truncated: bool, verbose: bool) -> ReplayReport {
+    let entries = replayed.len();
+    let accepted = replayed.iter().filter(|e| e.recorded.is_accepted()).count();
+    let diverged = replayed.iter().filter(|e| !e.matches()).count();
+    let timeline = replayed
+        .into_iter()
+        .filter(|e| verbose || !e.matches())
+        .collect();
+    ReplayReport {
+        entries,
+        accepted,
+        rejected: entries - accepted,
+        diverged,
+        truncated,
+        timeline,
+    }
+}
+
+fn print_entry(entry: &ReplayedEntry) {
+    let time = chrono::DateTime::from_timestamp_millis(entry.received_at_ms as i64)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| entry.received_at_ms.to_string());
+    let status = if entry.matches() { "ok  " } else { "DIFF" };
+    println!(
+        "{} #{} {} {} from {} {}: {}",
+        status,
+        entry.index,
+        time,
+        entry.kind.as_str(),
+        entry.peer,
+        entry.hash,
+        verdict_text(&entry.recorded)
+    );
+    if !entry.hash_matches {
+        println!("       payload no longer matches its hash");
+    }
+    if entry.replayed != entry.recorded {
+        println!("       replayed: {}", verdict_text(&entry.replayed));
+    }
+}
+
+fn verdict_text(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Accepted => "accepted".to_string(),
+        Verdict::Rejected { reason } => format!("rejected ({})", reason),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_protocol::crypto::keys::NovaKeypair;
+    use nova_protocol::network::consensus::Vote;
+    use nova_protocol::network::gossip::{encode_message, P2pGossipMessage};
+
+    #[test]
+    fn summary_keeps_only_divergences_unless_verbose() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gossip.journal");
+        let journal = GossipJournal::open(&path, 1024 * 1024, AddressPolicy::Strict).unwrap();
+        let vote = Vote::new(&NovaKeypair::from_seed(&[5; 32]), [1u8; 32], 1);
+        journal
+            .record(
+                "peer",
+                &encode_message(&P2pGossipMessage::BlockVote(vote)),
+                1,
+            )
+            .unwrap();
+        journal.record("peer", b"not a frame", 2).unwrap();
+
+        let mut entries = read_journal(&path).unwrap().entries;
+        let report = summarize(
+            replay_entries(&entries, AddressPolicy::Strict),
+            false,
+            false,
+        );
+        assert_eq!(
+            (report.entries, report.accepted, report.rejected),
+            (2, 1, 1)
+        );
+        assert_eq!(report.diverged, 0);
+        assert!(report.timeline.is_empty());
+
+        entries[0].verdict = Verdict::Rejected {
+            reason: "edited".to_string(),
+        };
+        let report = summarize(
+            replay_entries(&entries, AddressPolicy::Strict),
+            false,
+            false,
+        );
+        assert_eq!(report.diverged, 1);
+        assert_eq!(report.timeline.len(), 1);
+        assert_eq!(report.timeline[0].index, 0);
+
+        let report = summarize(replay_entries(&entries, AddressPolicy::Strict), false, true);
+        assert_eq!(report.timeline.len(), 2);
+    }
+}
@@ -6,33 +6,83 @@
 //! Entry point for the `nova-node` binary. Parses CLI arguments, initializes
 //! logging and metrics, starts the validator loop, and serves the HTTP/WS API.
 //!
-//! The binary supports four subcommands:
+//! The binary supports these subcommands:
 //!
-//! - `run`     — start the validator node
-//! - `init`    — initialize data directory and generate keys
-//! - `status`  — query a running node's status endpoint
-//! - `version` — print build version information
+//! - `run`         — start the validator node
+//! - `init`        — initialize data directory and generate keys
+//! - `status`      — query a running node's status endpoint
+//! - `version`     — print build provenance; `--verify` checks it
+//! - `keys show`   — print the validator key's public key and address
+//! - `keys sign-message` / `keys verify-message` — prove control of an address
+//! - `keys rotate-network-key` — replace and publish the P2P network key
+//! - `tx status`   — look up a transaction on a running node
+//! - `tx export` / `tx sign` / `tx broadcast` — sign on an air-gapped machine
+//! - `bench`       — benchmark block production on a temporary dev chain
+//! - `spam`        — generate transfer load against a running node
+//! - `audit`       — export or verify the audit log of privileged operations
+//! - `verify-chain` — check the stored blocks and the supply invariant
+//! - `completions` — generate bash/zsh/fish completions
 
 mod api;
+mod audit;
+mod bench;
+mod binary_rpc;
+mod capabilities;
 mod cli;
+mod clock;
+mod dev;
+mod explorer;
+mod export;
+mod halt;
+mod journal;
 mod logging;
 mod metrics;
+mod network_key;
+mod offline;
+mod presets;
+mod provenance;
+mod screening;
+mod spam;
+mod treasury;
+mod update;
+mod velocity;
+mod verify;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use std::sync::Arc;
 use tokio::signal;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::broadcast;
 
-use nova_protocol::identity::{NovaId, NovaKeypair};
+use nova_protocol::identity::AddressPolicy;
+use nova_protocol::identity::{NovaId, NovaKeypair, SignedMessage};
+use nova_protocol::indexer::Indexer;
+use nova_protocol::network::capabilities::Capabilities;
+use nova_protocol::network::clock::ClockSkewMonitor;
 use nova_protocol::network::consensus::{ConsensusConfig, ConsensusEngine, ValidatorSet};
 use nova_protocol::network::consensus_loop::{ConsensusLoop, ConsensusLoopConfig};
-use nova_protocol::network::mempool::{Mempool, MempoolConfig};
-use nova_protocol::network::producer::BlockProducer;
+use nova_protocol::network::direct::DirectService;
+use nova_protocol::network::halt::{HaltGuard, HaltStatus};
+use nova_protocol::network::mempool::{Eviction, Mempool, MempoolConfig, StateValidator};
+use nova_protocol::network::network_keys::{NetworkKeyBinding, NetworkKeyRegistry};
+use nova_protocol::network::peer_store::{BackoffPolicy, PeerStore};
+use nova_protocol::network::producer::{BlockClock, BlockProducer};
+use nova_protocol::network::scoring::PeerScoreBoard;
+use nova_protocol::storage::audit::AuditLog;
 use nova_protocol::storage::db::NovaDB;
-use nova_protocol::storage::state::{AccountState, StateTree};
-
-use cli::{Commands, NovaNodeCli};
+use nova_protocol::storage::emission::fund_genesis_account;
+use nova_protocol::storage::genesis::GenesisConfig;
+use nova_protocol::storage::snapshot::CommittedState;
+use nova_protocol::storage::state::StateTree;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::fee_estimator::FeeEstimator;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+use cli::{
+    AuditCommand, Commands, HaltCommand, JournalCommand, KeysCommand, NovaNodeCli, OutputFormat,
+    TreasuryCommand, TxCommand,
+};
 use logging::LogFormat;
 use metrics::NodeMetrics;
 
@@ -41,6 +91,10 @@ use metrics::NodeMetrics;
 /// for connected WebSocket clients.
 const EVENT_CHANNEL_CAPACITY: usize = 256;
 
+/// How often the mempool drops transactions past their `valid_until` or
+/// older than the pool TTL.
+const MEMPOOL_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
 /// Dev mode: number of pre-funded test accounts.
 const DEV_ACCOUNT_COUNT: u64 = 10;
 
@@ -50,6 +104,14 @@ const DEV_ACCOUNT_BALANCE: u64 = 100_000_000_000_000;
 /// Dev mode: default validator stake (100 NOVA = 10B photons).
 const DEV_VALIDATOR_STAKE: u64 = 10_000_000_000;
 
+/// Deterministic dev mode: seed index of the validator key. Dev accounts
+/// use indices 1..=DEV_ACCOUNT_COUNT.
+const DEV_VALIDATOR_SEED_INDEX: u64 = 0;
+
+/// Deterministic dev mode: the time block timestamps count from
+/// (2025-01-01T00:00:00Z).
+const DEV_GENESIS_TIMESTAMP_MS: u64 = 1_735_689_600_000;
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = NovaNodeCli::parse();
@@ -58,8 +120,34 @@ async fn main() -> Result<()> {
         Commands::Run(args) => run_node(args).await,
         Commands::Init(args) => init_node(args),
         Commands::Status(args) => query_status(args).await,
-        Commands::Version => {
-            print_version();
+        Commands::Version(args) => print_version(args),
+        Commands::Keys(KeysCommand::Show(args)) => show_keys(args),
+        Commands::Keys(KeysCommand::SignMessage(args)) => sign_message(args),
+        Commands::Keys(KeysCommand::VerifyMessage(args)) => verify_message(args),
+        Commands::Keys(KeysCommand::RotateNetworkKey(args)) => network_key::rotate(args).await,
+        Commands::Tx(TxCommand::Status(args)) => query_tx_status(args).await,
+        Commands::Tx(TxCommand::Export(args)) => offline::export(args).await,
+        Commands::Tx(TxCommand::Sign(args)) => offline::sign(args),
+        Commands::Tx(TxCommand::Broadcast(args)) => offline::broadcast(args).await,
+        Commands::Tx(TxCommand::Unjail(args)) => submit_unjail(args).await,
+        Commands::Bench(args) => bench::run_bench(args),
+        Commands::Spam(args) => spam::run_spam(args).await,
+        Commands::Audit(AuditCommand::Export(args)) => audit::export(args),
+        Commands::Audit(AuditCommand::Verify(args)) => audit::verify(args),
+        Commands::Journal(JournalCommand::Replay(args)) => journal::replay(args),
+        Commands::Halt(HaltCommand::Sign(args)) => halt::sign(args),
+        Commands::Halt(HaltCommand::SignResume(args)) => halt::sign_resume(args),
+        Commands::Halt(HaltCommand::Submit(args)) => halt::submit(args).await,
+        Commands::Treasury(TreasuryCommand::Sign(args)) => treasury::sign(args),
+        Commands::Treasury(TreasuryCommand::Submit(args)) => treasury::submit(args).await,
+        Commands::VerifyChain(args) => verify::run(args),
+        Commands::Completions(args) => {
+            clap_complete::generate(
+                args.shell,
+                &mut NovaNodeCli::command(),
+                "nova-node",
+                &mut std::io::stdout(),
+            );
             Ok(())
         }
     }
@@ -77,7 +165,7 @@ async fn main() -> Result<()> {
 /// 2.  Initialize logging
 /// 3.  Generate or load keypair
 /// 4.  Open NovaDB
-/// 5.  Initialize StateTree (genesis if empty)
+/// 5.  Initialize the shared StateTree
 /// 6.  Pre-fund dev accounts (if --dev)
 /// 7.  Create Mempool
 /// 8.  Create ValidatorSet
@@ -90,9 +178,30 @@ async fn main() -> Result<()> {
 /// 15. Print startup banner
 /// 16. Await shutdown
 /// 17. Graceful shutdown
-async fn run_node(args: cli::RunArgs) -> Result<()> {
+async fn run_node(mut args: cli::RunArgs) -> Result<()> {
     // --- 1. Resolve paths and validate config ---
+    args.dev |= args.dev_deterministic;
     let data_dir = cli::resolve_data_dir(&args.data_dir);
+    let preset = args.network.preset();
+    if args.dev && args.network != presets::Network::Devnet {
+        anyhow::bail!("--dev only runs devnet (got --network {})", args.network);
+    }
+    if args.dev_instant && !args.dev {
+        anyhow::bail!("--dev-instant requires --dev or --dev-deterministic");
+    }
+    let genesis = match &args.genesis {
+        Some(path) => GenesisConfig::load(path)
+            .with_context(|| format!("failed to load {}", path.display()))?,
+        None => preset.genesis_config(),
+    };
+    if genesis.chain_id != preset.chain_id {
+        anyhow::bail!(
+            "genesis config is for chain 0x{:08X}, but {} is 0x{:08X}",
+            genesis.chain_id,
+            preset.network,
+            preset.chain_id
+        );
+    }
 
     let log_filter = format!(
         "nova_node={level},nova_protocol={level},tower_http=debug",
@@ -108,24 +217,63 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
         p2p_addr = %args.p2p_addr,
         metrics_addr = %args.metrics_addr,
         data_dir = %data_dir.display(),
+        network = %preset.network,
+        chain_id = format_args!("0x{:08X}", preset.chain_id),
         dev = args.dev,
+        deterministic = args.dev_deterministic,
+        instant = args.dev_instant,
         validator = args.validator,
         "starting nova-node"
     );
 
     // --- 3. Generate or load keypair ---
-    let keypair = if args.dev {
+    let (keypair, key_action) = if args.dev_deterministic {
+        let kp = dev_validator_keypair();
+        tracing::info!(
+            public_key = %kp.public_key().to_hex(),
+            "using fixed dev validator keypair"
+        );
+        (kp, "dev_key_derived")
+    } else if args.dev {
         // Dev mode: generate a fresh keypair (not persisted).
         let kp = NovaKeypair::generate();
         tracing::info!(
             public_key = %kp.public_key().to_hex(),
             "generated ephemeral dev keypair"
         );
-        kp
+        (kp, "dev_key_generated")
     } else {
-        load_or_generate_keypair(&data_dir)?
+        let existed = data_dir.join("keys").join("validator.key").exists();
+        let kp = load_or_generate_keypair(&data_dir)?;
+        (
+            kp,
+            if existed {
+                "key_loaded"
+            } else {
+                "key_generated"
+            },
+        )
     };
 
+    // --- Network key ---
+    // The P2P identity is a key of its own, so a compromised host leaks
+    // the node's peer identity but not its consensus key.
+    let network_keypair = if args.dev {
+        NovaKeypair::generate()
+    } else {
+        network_key::load_or_generate(&network_key::key_path(
+            &data_dir,
+            args.network_key_file.as_deref(),
+        ))?
+    };
+    if network_keypair.public_key() == keypair.public_key() {
+        anyhow::bail!("the network key must differ from the validator key");
+    }
+    tracing::info!(
+        peer_id = %network_key::peer_id(&network_keypair),
+        "network identity ready"
+    );
+
     let validator_address = keypair.public_key().to_hex();
     let nova_id = NovaId::from_public_key(&keypair.public_key());
     let nova_address = nova_id.to_address();
@@ -147,8 +295,27 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
     };
     tracing::info!("database opened");
 
-    // --- 5. Initialize StateTree (genesis if empty) ---
-    let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+    // --- Peer store ---
+    // Known peers survive restarts; the swarm loop consults the store when
+    // the mesh runs thin and backs off peers that keep failing.
+    let peer_store = Arc::new(
+        PeerStore::open(&db, BackoffPolicy::default()).context("failed to open peer store")?,
+    );
+    tracing::info!(known_peers = peer_store.len(), "peer store loaded");
+
+    // --- Audit log ---
+    // Privileged operations are appended to a hash-chained log; startup
+    // records which key, configuration and mode this run uses.
+    let audit = Arc::new(AuditLog::open(&db).context("failed to open audit log")?);
+    let mode = node_mode(&args);
+    audit::record_startup(&audit, &args, key_action, &validator_address, mode)
+        .context("failed to write startup audit entries")?;
+    let audit_head = audit.head();
+    tracing::info!(
+        entries = audit_head.len,
+        head = %audit_head.hash,
+        "audit log opened"
+    );
 
     // --- Block height ---
     let block_height = Arc::new(std::sync::atomic::AtomicU64::new(0));
@@ -156,9 +323,35 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
     // --- Genesis initialization ---
     api::initialize_genesis(&db, &block_height);
 
+    // --- Network check ---
+    // Refuse a data directory that belongs to another network before any
+    // state is loaded from it.
+    if !args.dev {
+        presets::ensure_data_dir_network(&data_dir, &preset, &db)?;
+    }
+    if preset.seed_nodes.is_empty() {
+        tracing::info!(network = %preset.network, "no built-in seed nodes for this network");
+    } else {
+        tracing::info!(seeds = ?preset.seed_nodes, "using built-in seed nodes");
+    }
+
+    // --- 5. Initialize StateTree ---
+    // One tree, shared by the producer, the consensus loop and sync. The API
+    // never locks it: writes are journaled into `committed_state`, which
+    // only advances when a block is committed.
+    let tree = StateTree::new((*db).clone());
+    let committed_state = CommittedState::new(
+        (*db).clone(),
+        db.get_latest_block_height().ok().flatten().unwrap_or(0),
+        tree.root(),
+    );
+    let state_tree = Arc::new(parking_lot::RwLock::new(
+        tree.with_committed_state(Arc::clone(&committed_state)),
+    ));
+
     // --- 6. Pre-fund dev accounts (if --dev) ---
     let dev_stake = if args.dev {
-        let funded_addresses = prefund_dev_accounts(&state_tree).await;
+        let funded_addresses = prefund_dev_accounts(&state_tree);
         for (i, addr) in funded_addresses.iter().enumerate() {
             tracing::info!(
                 index = i + 1,
@@ -173,18 +366,83 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
     };
 
     // --- 7. Create Mempool ---
-    let mempool = Arc::new(Mempool::new(MempoolConfig::default()));
-
-    // --- 8. Create ValidatorSet ---
-    let mut validator_set = ValidatorSet::new();
-    if args.validator || args.dev {
-        validator_set.add_validator(validator_address.clone(), dev_stake);
-        tracing::info!(
-            address = %validator_address,
-            stake = dev_stake,
-            "added self to validator set"
-        );
+    let address_policy = if args.dev || args.permissive_addresses {
+        AddressPolicy::Permissive
+    } else {
+        AddressPolicy::Strict
+    };
+    let mut mempool = Mempool::new(MempoolConfig {
+        address_policy,
+        min_fee: args.min_fee,
+        max_txs_per_sender: args.max_txs_per_sender,
+        max_bytes: (args.mempool_max_mb * 1024 * 1024) as usize,
+        expiry_seconds: args.mempool_ttl_secs,
+        max_orphans: args.mempool_max_orphans,
+        encrypted_byte_fee: args.encrypted_byte_fee,
+        ..MempoolConfig::default()
+    })
+    .with_validator(Arc::new(StateValidator::new(Arc::clone(&committed_state))));
+    if let Some(screener) = screening::build(&args, Arc::clone(&audit))? {
+        tracing::info!(?screener, "address screening enabled");
+        mempool = mempool.with_screener(screener);
     }
+    let mempool = Arc::new(mempool);
+
+    // --- 8. Load ValidatorSet ---
+    // The set lives in the state tree so every node derives the same
+    // proposer order from the same state. CLI flags only bootstrap it on a
+    // fresh chain.
+    let validator_set = {
+        let mut tree = state_tree.write();
+        match ValidatorSet::load_from_state(&tree) {
+            Some(set) => {
+                tracing::info!(validators = set.len(), "validator set loaded from state");
+                if args.validator && !set.contains(&validator_address) {
+                    tracing::warn!(
+                        address = %validator_address,
+                        "this node is not in the persisted validator set; --validator/--stake ignored"
+                    );
+                }
+                let registered = NetworkKeyRegistry::load(&tree)
+                    .get(&validator_address)
+                    .map(|k| k.network_key.clone());
+                let network_key = network_keypair.public_key().to_hex();
+                if set.contains(&validator_address) && registered.as_ref() != Some(&network_key) {
+                    tracing::warn!(
+                        network_key = %network_key,
+                        registered = ?registered,
+                        "network key is not the one registered on chain; run `nova-node keys rotate-network-key`"
+                    );
+                }
+                set
+            }
+            None => {
+                let mut set = ValidatorSet::new();
+                if args.validator || args.dev {
+                    set.add_validator(validator_address.clone(), dev_stake);
+                    tracing::info!(
+                        address = %validator_address,
+                        stake = dev_stake,
+                        "bootstrapped validator set with self"
+                    );
+                    set.store_in_state(&mut tree);
+
+                    // Publish this node's network key alongside it.
+                    let mut registry = NetworkKeyRegistry::load(&tree);
+                    let binding = NetworkKeyBinding::sign(&keypair.public_key(), &network_keypair);
+                    registry
+                        .register(&binding, &set, 0)
+                        .map_err(|e| anyhow::anyhow!("failed to register network key: {}", e))?;
+                    registry.store(&mut tree);
+                }
+                set
+            }
+        }
+    };
+
+    // Startup writes (dev accounts, bootstrapped validator set) belong to
+    // the current tip.
+    state_tree.read().commit(committed_state.height());
 
     // --- 9. Create ConsensusEngine ---
     let consensus_config = if args.dev {
@@ -196,7 +454,12 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
         ConsensusConfig::default()
     };
 
+    // Accepted emergency halts persist across restarts; the engine refuses
+    // to build or finalize past one until it is resumed.
+    let halt_guard = Arc::new(HaltGuard::open(&db).context("failed to open halt records")?);
+
     let mut engine = ConsensusEngine::new(consensus_config, validator_set);
+    engine.set_halt_guard(Arc::clone(&halt_guard));
 
     // Sync engine to current chain tip.
     if let Ok(Some(h)) = db.get_latest_block_height() {
@@ -208,53 +471,202 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
 
     let engine = Arc::new(parking_lot::RwLock::new(engine));
 
+    // --- SQL export ---
+    // The indexer records state diffs on the producer's commit path and
+    // feeds them, with every committed block, to the export.
+    let indexer = match &args.sql_export {
+        Some(target) => {
+            let target = export::ExportTarget::parse(target, &data_dir)?;
+            let indexer =
+                Arc::new(Indexer::open(Arc::clone(&db)).context("failed to open the indexer")?);
+            let sink = export::SqlExport::connect(&target)?;
+            let checkpoint = sink.checkpoint()?;
+            sink.attach(&indexer)?;
+            tracing::info!(?checkpoint, "SQL export enabled");
+            Some(indexer)
+        }
+        None => None,
+    };
+
     // --- 10. Create BlockProducer ---
-    // The consensus loop and block producer use parking_lot::RwLock for the
-    // state tree (required by the protocol library), separate from the tokio
-    // RwLock used by the API layer. In dev mode, the parking_lot tree needs
-    // the same pre-funded accounts.
-    let state_tree_for_consensus = if args.dev {
-        let st = Arc::new(parking_lot::RwLock::new(StateTree::new((*db).clone())));
-        // Re-fund in the parking_lot tree too.
-        {
-            let mut tree = st.write();
-            for i in 1..=DEV_ACCOUNT_COUNT {
-                let seed = generate_dev_seed(i);
-                let kp = NovaKeypair::from_seed(&seed);
-                let id = NovaId::from_public_key(&kp.public_key());
-                let addr = id.to_address();
-                tree.put(&addr, &AccountState::with_balance(DEV_ACCOUNT_BALANCE));
-            }
+    let block_clock = if args.dev_deterministic {
+        BlockClock::Fixed {
+            genesis_ms: DEV_GENESIS_TIMESTAMP_MS,
+            interval_ms: nova_protocol::config::BLOCK_TIME_MS,
         }
-        st
     } else {
-        Arc::new(parking_lot::RwLock::new(StateTree::new((*db).clone())))
+        BlockClock::Wall
     };
-
-    let producer = Arc::new(BlockProducer::new(
+    // Fee history for nova_estimateFee, measured against the consensus
+    // loop's block size.
+    let fee_estimator = Arc::new(FeeEstimator::new(
+        ConsensusLoopConfig::default().max_txs_per_block,
+    ));
+    let mut producer = BlockProducer::new(
         Arc::clone(&db),
-        Arc::clone(&state_tree_for_consensus),
+        Arc::clone(&state_tree),
         Arc::clone(&mempool),
         keypair.clone(),
-    ));
+    )
+    .with_block_clock(block_clock)
+    .with_monetary_policy(genesis.monetary_policy)
+    .with_fee_estimator(Arc::clone(&fee_estimator));
+    if let Some(indexer) = &indexer {
+        producer = producer.with_indexer(Arc::clone(indexer));
+    }
+    let producer = Arc::new(producer);
+
+    // --- Clock skew monitor ---
+    // Peers and optional NTP servers feed samples; the consensus loop
+    // refuses to propose while the median offset exceeds MAX_CLOCK_SKEW.
+    let clock_monitor = Arc::new(ClockSkewMonitor::default());
 
     // --- 11. Create ConsensusLoop ---
+    // With --dev-instant, transaction arrivals drive production instead of
+    // the block timer (see the event forwarder below).
+    let mine_trigger = args
+        .dev_instant
+        .then(|| Arc::new(tokio::sync::Notify::new()));
     let consensus_loop_config = ConsensusLoopConfig::default();
-    let consensus_loop = ConsensusLoop::new(
+    let mut consensus_loop = ConsensusLoop::new(
         Arc::clone(&engine),
         Arc::clone(&producer),
         Arc::clone(&db),
-        Arc::clone(&state_tree_for_consensus),
+        Arc::clone(&state_tree),
         Arc::clone(&mempool),
         keypair.clone(),
         consensus_loop_config,
-    );
+    )
+    .with_clock_monitor(Arc::clone(&clock_monitor));
+    if let Some(trigger) = &mine_trigger {
+        consensus_loop = consensus_loop.with_trigger(Arc::clone(trigger));
+    }
+    let consensus_loop = Arc::new(consensus_loop);
 
     // --- Metrics ---
     let node_metrics = Arc::new(NodeMetrics::new());
+    if matches!(halt_guard.status(), HaltStatus::Halted { .. }) {
+        node_metrics.chain_halted.set(1);
+    }
+
+    // --- Clock watchdog / NTP sampling ---
+    tokio::spawn(clock::run_clock_watchdog(
+        Arc::clone(&clock_monitor),
+        Arc::clone(&node_metrics),
+    ));
+    if !args.ntp_servers.is_empty() {
+        tracing::info!(servers = ?args.ntp_servers, "NTP clock references enabled");
+        tokio::spawn(clock::run_ntp_sampler(
+            Arc::clone(&clock_monitor),
+            args.ntp_servers.clone(),
+        ));
+    }
 
     // --- Event broadcast ---
     let (event_tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+    if let Some(trigger) = mine_trigger {
+        tokio::spawn(forward_transactions_to_trigger(
+            event_tx.subscribe(),
+            trigger,
+        ));
+    }
+
+    // --- Mempool sweep and evictions ---
+    tokio::spawn(forward_evictions(
+        mempool.subscribe_evictions(),
+        event_tx.clone(),
+    ));
+    tokio::spawn(sweep_mempool(Arc::clone(&mempool)));
+    tokio::spawn(metrics::track_mempool(
+        mempool.subscribe(),
+        Arc::clone(&mempool),
+        Arc::clone(&node_metrics),
+    ));
+
+    // --- Velocity alerts ---
+    if let Some(config) = velocity::VelocityConfig::from_args(&args) {
+        tokio::spawn(velocity::run_velocity_monitor(
+            Arc::clone(&db),
+            velocity::VelocityMonitor::new(config),
+            event_tx.clone(),
+            args.anomaly_webhook.clone(),
+        ));
+    }
+
+    // --- Release manifest checks ---
+    let updates = match &args.update_check_url {
+        Some(url) => {
+            let checker = Arc::new(update::UpdateChecker::new(
+                url,
+                &preset.network.to_string(),
+                &args.update_keys,
+            )?);
+            tracing::info!(url = %url, "update checks enabled");
+            tokio::spawn(Arc::clone(&checker).run(
+                Arc::clone(&node_metrics),
+                std::time::Duration::from_secs(args.update_check_interval_secs),
+            ));
+            Some(checker)
+        }
+        None => None,
+    };
+
+    // --- 12. Setup shutdown handler ---
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+    if let Some(indexer) = indexer {
+        tokio::spawn(indexer.run(shutdown_rx.clone()));
+    }
+
+    // --- Capabilities ---
+    // Confidential transfers stay unadvertised: the node does not wire a
+    // balance verifier, so it rejects them.
+    let capabilities = Capabilities::compiled()
+        .with(Capabilities::NTP_MESSAGING, args.ntp_messaging)
+        .with(Capabilities::QUIC, args.ntp_messaging && args.quic)
+        .with(Capabilities::DEV_RPC, args.dev)
+        .with(Capabilities::BINARY_RPC, true);
+    tracing::info!(%capabilities, "node capabilities");
+
+    // --- Direct NTP messaging ---
+    let direct = if args.ntp_messaging {
+        let listen: std::net::SocketAddr = args
+            .p2p_addr
+            .parse()
+            .with_context(|| format!("invalid P2P address {}", args.p2p_addr))?;
+        let gossip_journal = match &args.gossip_journal {
+            Some(_) => {
+                let journal = journal::open(&args, &data_dir, address_policy)?;
+                tracing::info!(path = %journal.path().display(), "gossip journal enabled");
+                Some(journal)
+            }
+            None => None,
+        };
+        let service = DirectService::spawn(
+            &network_keypair,
+            listen,
+            args.quic,
+            &args.relays,
+            gossip_journal,
+            capabilities.clone(),
+            shutdown_rx.clone(),
+        )
+        .map_err(|e| anyhow::anyhow!("failed to start NTP messaging: {}", e))?;
+        tracing::info!(
+            peer_id = %service.local_peer_id(),
+            quic = args.quic,
+            relays = args.relays.len(),
+            "NTP messaging enabled on {}",
+            args.p2p_addr
+        );
+        tokio::spawn(metrics::track_connections(
+            Arc::clone(&service),
+            Arc::clone(&node_metrics),
+        ));
+        Some(service)
+    } else {
+        None
+    };
 
     // --- Application state ---
     let app_state = api::AppState {
@@ -263,18 +675,36 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
             env!("CARGO_PKG_VERSION"),
             nova_protocol::config::PROTOCOL_VERSION,
         ),
-        network: "devnet".to_string(),
+        network: preset.network.to_string(),
         block_height: Arc::clone(&block_height),
         peer_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
         event_tx: event_tx.clone(),
         metrics: Arc::clone(&node_metrics),
         db: Arc::clone(&db),
-        state_tree,
+        committed_state,
+        mempool: Arc::clone(&mempool),
+        fee_estimator,
+        peer_store: Arc::clone(&peer_store),
+        peer_scores: Arc::new(PeerScoreBoard::default()),
+        audit: Arc::clone(&audit),
+        halt: halt_guard,
+        monetary_policy: genesis.monetary_policy,
+        address_policy,
+        capabilities,
+        direct,
+        updates,
+        dev: args.dev.then(|| {
+            Arc::new(dev::DevControls::new(
+                Arc::clone(&consensus_loop),
+                Arc::clone(&producer),
+                Arc::clone(&engine),
+                Arc::clone(&db),
+                Arc::clone(&state_tree),
+                Arc::clone(&mempool),
+            ))
+        }),
     };
 
-    // --- 12. Setup shutdown handler ---
-    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
-
     // --- 13. Spawn consensus loop (if --validator or --dev) ---
     let consensus_handle = if args.validator || args.dev {
         let shutdown_rx_consensus = shutdown_rx.clone();
@@ -289,38 +719,21 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
             }
         }))
     } else {
-        // Passive node: run a stub block height incrementer for API/metrics.
-        let height_ref = Arc::clone(&app_state.block_height);
-        let metrics_ref = Arc::clone(&node_metrics);
-        let event_tx_ref = event_tx.clone();
-        Some(tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_millis(
-                nova_protocol::config::BLOCK_TIME_MS,
-            ));
-            loop {
-                interval.tick().await;
-                let h = height_ref.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                metrics_ref.block_height.set(h as i64);
-                metrics_ref.blocks_processed_total.inc();
-
-                let _ = event_tx_ref.send(api::NodeEvent::NewBlock {
-                    height: h,
-                    hash: format!("{:064x}", h),
-                    tx_count: 0,
-                    timestamp: chrono::Utc::now().timestamp_millis() as u64,
-                });
-
-                tracing::debug!(height = h, "block produced (stub)");
-            }
-        }))
+        None
     };
 
     // --- 14. Start API server ---
-    let api_router = api::create_router(app_state.clone());
+    let mut api_router = api::create_router(app_state.clone());
+    if args.dev {
+        api_router = explorer::mount(api_router);
+    }
     let api_listener = tokio::net::TcpListener::bind(&args.rpc_addr)
         .await
         .with_context(|| format!("failed to bind RPC listener on {}", args.rpc_addr))?;
     tracing::info!("RPC/API server listening on {}", args.rpc_addr);
+    if args.dev {
+        tracing::info!("Dev explorer at http://{}/explorer", args.rpc_addr);
+    }
 
     // --- Metrics server ---
     let metrics_router = axum::Router::new()
@@ -332,14 +745,9 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
     tracing::info!("Metrics server listening on {}", args.metrics_addr);
 
     // --- 15. Print startup banner ---
-    let mode = match (args.validator || args.dev, args.dev) {
-        (true, true) => "Validator (dev)",
-        (true, false) => "Validator",
-        (false, _) => "Full Node",
-    };
-
     print_startup_banner(
         &nova_address,
+        preset.network.name(),
         &args.rpc_addr,
         &args.p2p_addr,
         &data_dir.to_string_lossy(),
@@ -349,7 +757,10 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
 
     // --- 16. Await shutdown signal ---
     tokio::select! {
-        res = axum::serve(api_listener, api_router) => {
+        res = axum::serve(
+            api_listener,
+            api_router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        ) => {
             if let Err(e) = res {
                 tracing::error!("API server error: {}", e);
             }
@@ -378,13 +789,14 @@ async fn run_node(args: cli::RunArgs) -> Result<()> {
 // init — Data directory initialization
 // ---------------------------------------------------------------------------
 
-/// Initializes a new node data directory and generates a validator keypair.
+/// Initializes a new node data directory and generates the validator and
+/// network keypairs.
 ///
 /// Creates the directory structure:
 /// ```text
 /// {data_dir}/
 ///     db/         — RocksDB/sled storage
-///     keys/       — Validator keypair
+///     keys/       — Validator and network keypairs
 ///     config/     — Node configuration
 /// ```
 fn init_node(args: cli::InitArgs) -> Result<()> {
@@ -439,12 +851,21 @@ fn init_node(args: cli::InitArgs) -> Result<()> {
         std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))?;
     }
 
+    // Generate the network keypair, the node's P2P identity.
+    let network_keypair = NovaKeypair::generate();
+    let network_key_path = network_key::key_path(&data_dir, None);
+    network_key::write(&network_key_path, &network_keypair)?;
+
     // Initialize database with genesis block.
     let db = NovaDB::open(&db_dir)
         .with_context(|| format!("failed to open database at {}", db_dir.display()))?;
     let block_height = std::sync::atomic::AtomicU64::new(0);
     api::initialize_genesis(&db, &block_height);
 
+    // Stamp the directory with its network so `run` can refuse a mismatch.
+    let preset = args.network.preset();
+    preset.marker().store(&data_dir)?;
+
     tracing::info!(
         public_key = %pubkey_hex,
         address = %nova_address,
@@ -456,16 +877,31 @@ fn init_node(args: cli::InitArgs) -> Result<()> {
     println!("Node initialized successfully.");
     println!();
     println!("  Data directory : {}", data_dir.display());
-    println!("  Network        : {}", args.network);
+    println!(
+        "  Network        : {} (chain ID 0x{:08X})",
+        preset.network, preset.chain_id
+    );
     println!("  Validator key  : {}", key_path.display());
     println!("  Public key     : {}", pubkey_hex);
     println!("  NOVA address   : {}", nova_address);
+    println!("  Network key    : {}", network_key_path.display());
+    println!(
+        "  Peer ID        : {}",
+        network_key::peer_id(&network_keypair)
+    );
     println!("  DB directory   : {}", db_dir.display());
-    println!("  Genesis block  : persisted at height 0");
+    println!("  Genesis block  : {}", preset.genesis_hash());
+    println!("  Address prefix : {}", preset.hrp);
+    println!(
+        "  Default ports  : p2p {}, rpc {}, metrics {}",
+        preset.p2p_port, preset.rpc_port, preset.metrics_port
+    );
+    println!("  Seed nodes     : {}", preset.seed_nodes.len());
     println!();
     println!(
-        "Run `nova-node run -d {}` to start the node.",
-        data_dir.display()
+        "Run `nova-node run -d {} --network {}` to start the node.",
+        data_dir.display(),
+        preset.network
     );
 
     Ok(())
@@ -476,10 +912,20 @@ fn init_node(args: cli::InitArgs) -> Result<()> {
 // ---------------------------------------------------------------------------
 
 /// Queries a running node's status endpoint and prints the result.
+///
+/// With `--output json` the node's response is printed as-is (pretty
+/// printed), so scripts see exactly the fields the API returns.
 async fn query_status(args: cli::StatusArgs) -> Result<()> {
     let url = format!("{}/status", args.rpc_url.trim_end_matches('/'));
     let body: String = reqwest_get_stub(&url).await?;
 
+    if args.output == OutputFormat::Json {
+        let json: serde_json::Value =
+            serde_json::from_str(&body).context("node returned a non-JSON status response")?;
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
     // Try to pretty-print the JSON; fall back to raw output if parsing fails.
     match serde_json::from_str::<serde_json::Value>(&body) {
         Ok(json) => {
@@ -511,26 +957,265 @@ async fn query_status(args: cli::StatusArgs) -> Result<()> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// tx status — Look up a transaction on a running node
+// ---------------------------------------------------------------------------
+
+/// Fetches `GET /transactions/:hash` and prints the result.
+///
+/// A lookup the node answers with an error (unknown hash, database
+/// failure) exits non-zero in both output modes; in JSON mode the error
+/// document is still printed first.
+async fn query_tx_status(args: cli::TxStatusArgs) -> Result<()> {
+    let hash = nova_protocol::encoding::strip_hex_prefix(&args.hash);
+    let url = format!(
+        "{}/transactions/{}",
+        args.rpc_url.trim_end_matches('/'),
+        hash
+    );
+    let body: String = reqwest_get_stub(&url).await?;
+    let json: serde_json::Value =
+        serde_json::from_str(&body).context("node returned a non-JSON transaction response")?;
+
+    if args.output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    }
+    if let Some(error) = json.get("error").and_then(|v| v.as_str()) {
+        anyhow::bail!("{}", error);
+    }
+    if args.output == OutputFormat::Json {
+        return Ok(());
+    }
+
+    let field = |name: &str| match json.get(name) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => "-".to_string(),
+        Some(other) => other.to_string(),
+    };
+    println!("Transaction {}", field("hash"));
+    println!("  Status      : {}", field("status"));
+    println!("  Block Height: {}", field("block_height"));
+    println!("  Sender      : {}", field("sender"));
+    println!("  Recipient   : {}", field("recipient"));
+    println!("  Amount      : {}", field("amount"));
+    println!("  Fee         : {}", field("fee"));
+    Ok(())
+}
+
+/// Signs an `Unjail` transaction with the validator key and submits it.
+/// The node accepts it once the jail cooldown has passed.
+async fn submit_unjail(args: cli::TxUnjailArgs) -> Result<()> {
+    let (keypair, _) = read_validator_key(&cli::resolve_data_dir(&args.data_dir))?;
+    let address = NovaId::from_public_key(&keypair.public_key()).to_address();
+    let rpc_url = args.rpc_url.trim_end_matches('/');
+    let nonce = match args.nonce {
+        Some(nonce) => nonce,
+        None => {
+            let caps = capabilities::fetch(rpc_url).await?;
+            capabilities::next_nonce(rpc_url, &caps, &address).await?
+        }
+    };
+
+    let mut tx = TransactionBuilder::new(TransactionType::Unjail)
+        .sender(&address)
+        .receiver(&address)
+        .amount(Amount::new(0, Currency::NOVA))
+        .nonce(nonce)
+        .build()
+        .map_err(|e| anyhow::anyhow!("invalid transaction: {}", e))?;
+    sign_transaction(&mut tx, &keypair);
+
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "nova_sendTransaction",
+        "params": [tx],
+        "id": 1,
+    })
+    .to_string();
+    let response = reqwest_post_json_stub(&format!("{}/rpc", rpc_url), &body).await?;
+    let id = spam::parse_submission(&response)
+        .map_err(|reason| anyhow::anyhow!("node rejected the unjail: {}", reason))?;
+    println!(
+        "Submitted unjail {} for {}",
+        id,
+        keypair.public_key().to_hex()
+    );
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // version
 // ---------------------------------------------------------------------------
 
-/// Prints version information to stdout.
-fn print_version() {
-    println!("nova-node {}", env!("CARGO_PKG_VERSION"));
-    println!("protocol  {}", nova_protocol::config::PROTOCOL_VERSION);
-    println!("rustc     {}", rustc_version());
-    if let Some(commit) = option_env!("GIT_COMMIT") {
-        println!("commit    {}", commit);
+/// Build version information, as printed by `version --output json`. See
+/// [`provenance`].
+fn version_info() -> serde_json::Value {
+    serde_json::to_value(provenance::BuildInfo::current()).expect("build info serializes")
+}
+
+/// Prints version information to stdout; with `--verify`, checks it too.
+fn print_version(args: cli::VersionArgs) -> Result<()> {
+    let info = provenance::BuildInfo::current();
+    if !args.verify {
+        if args.output == OutputFormat::Json {
+            println!("{}", version_info());
+            return Ok(());
+        }
+        print_build_info(&info);
+        return Ok(());
     }
-    if let Some(ts) = option_env!("BUILD_TIMESTAMP") {
-        println!("built     {}", ts);
+
+    let expected = provenance::Expected {
+        commit: args.commit,
+        lockfile_sha256: args
+            .lockfile
+            .as_deref()
+            .map(provenance::file_sha256)
+            .transpose()?,
+        binary_sha256: args.sha256,
+    };
+    let checks = provenance::verify(&info, &expected);
+    let ok = checks.iter().all(|c| c.ok);
+
+    if args.output == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "build": info,
+                "checks": checks,
+                "verified": ok,
+            }))?
+        );
+    } else {
+        print_build_info(&info);
+        println!();
+        for check in &checks {
+            let mark = if check.ok { "ok  " } else { "FAIL" };
+            println!("{} {:<9} {}", mark, check.name, check.detail);
+        }
     }
+    if !ok {
+        anyhow::bail!("provenance verification failed");
+    }
+    Ok(())
 }
 
-/// Returns the Rust compiler version used to build this binary.
-fn rustc_version() -> &'static str {
-    option_env!("RUSTC_VERSION").unwrap_or("unknown")
+/// Prints `info` as aligned text.
+fn print_build_info(info: &provenance::BuildInfo) {
+    println!("nova-node {}", info.node);
+    println!("protocol  {}", info.protocol);
+    println!("rustc     {}", info.rustc);
+    let optional = [
+        ("commit   ", info.commit),
+        ("lockfile ", info.lockfile_sha256),
+        ("profile  ", info.profile),
+        ("built    ", info.built),
+        ("sha256   ", info.binary_sha256.as_deref()),
+    ];
+    for (label, value) in optional.iter().filter_map(|(l, v)| Some((l, (*v)?))) {
+        println!("{} {}", label, value);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// keys show
+// ---------------------------------------------------------------------------
+
+/// Public identity of the validator key in `data_dir`. Never generates a
+/// key — a missing key file is an error.
+fn key_info(data_dir: &std::path::Path) -> Result<serde_json::Value> {
+    let (keypair, key_path) = read_validator_key(data_dir)?;
+    let nova_id = NovaId::from_public_key(&keypair.public_key());
+    let mut info = serde_json::json!({
+        "public_key": keypair.public_key().to_hex(),
+        "address": nova_id.to_address(),
+        "key_path": key_path.display().to_string(),
+    });
+
+    // Data directories from before the key split have no network key
+    // until the node first starts.
+    let network_key_path = network_key::key_path(data_dir, None);
+    if network_key_path.exists() {
+        let network = network_key::read(&network_key_path)?;
+        info["network_key"] = serde_json::json!(network.public_key().to_hex());
+        info["peer_id"] = serde_json::json!(network_key::peer_id(&network));
+        info["network_key_path"] = serde_json::json!(network_key_path.display().to_string());
+    }
+    Ok(info)
+}
+
+/// Reads `{data_dir}/keys/validator.key` without generating one.
+fn read_validator_key(data_dir: &std::path::Path) -> Result<(NovaKeypair, std::path::PathBuf)> {
+    let key_path = data_dir.join("keys").join("validator.key");
+    let hex_str = std::fs::read_to_string(&key_path)
+        .with_context(|| format!("failed to read validator key from {}", key_path.display()))?;
+    let keypair = NovaKeypair::from_hex(hex_str.trim())
+        .map_err(|e| anyhow::anyhow!("invalid validator key: {}", e))?;
+    Ok((keypair, key_path))
+}
+
+/// Signs a message with the validator key and prints it as JSON.
+fn sign_message(args: cli::KeysSignMessageArgs) -> Result<()> {
+    let (keypair, _) = read_validator_key(&cli::resolve_data_dir(&args.data_dir))?;
+    let signed = SignedMessage::sign(&keypair, &args.message);
+    println!("{}", serde_json::to_string_pretty(&signed)?);
+    Ok(())
+}
+
+/// Verifies a signed message from a file or stdin; exits non-zero if it
+/// does not verify.
+fn verify_message(args: cli::KeysVerifyMessageArgs) -> Result<()> {
+    let json = match &args.file {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        None => std::io::read_to_string(std::io::stdin()).context("failed to read stdin")?,
+    };
+    let signed: SignedMessage =
+        serde_json::from_str(&json).context("input is not a signed message")?;
+    let result = signed.verify();
+    if args.output == OutputFormat::Json {
+        let mut report = serde_json::json!({
+            "valid": result.is_ok(),
+            "address": signed.address,
+        });
+        if let Err(e) = &result {
+            report["reason"] = serde_json::json!(e.to_string());
+        }
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if result.is_ok() {
+        println!("Signature OK");
+        println!("  Address: {}", signed.address);
+    }
+    result.map_err(|e| anyhow::anyhow!("message verification failed: {}", e))
+}
+
+/// Prints the validator key's public key and address.
+fn show_keys(args: cli::KeysShowArgs) -> Result<()> {
+    let info = key_info(&cli::resolve_data_dir(&args.data_dir))?;
+    if args.output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+    println!("Validator Key");
+    println!(
+        "  Public key  : {}",
+        info["public_key"].as_str().unwrap_or("")
+    );
+    println!("  NOVA address: {}", info["address"].as_str().unwrap_or(""));
+    println!(
+        "  Key file    : {}",
+        info["key_path"].as_str().unwrap_or("")
+    );
+    if let Some(network_key) = info["network_key"].as_str() {
+        println!("Network Key");
+        println!("  Public key  : {}", network_key);
+        println!("  Peer ID     : {}", info["peer_id"].as_str().unwrap_or(""));
+        println!(
+            "  Key file    : {}",
+            info["network_key_path"].as_str().unwrap_or("")
+        );
+    }
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -584,6 +1269,21 @@ fn load_or_generate_keypair(data_dir: &std::path::Path) -> Result<NovaKeypair> {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Node mode
+// ---------------------------------------------------------------------------
+
+/// Human-readable mode this run operates in, as shown in the startup
+/// banner and recorded in the audit log.
+fn node_mode(args: &cli::RunArgs) -> &'static str {
+    match (args.validator || args.dev, args.dev) {
+        (true, true) if args.dev_deterministic => "Validator (dev, deterministic)",
+        (true, true) => "Validator (dev)",
+        (true, false) => "Validator",
+        (false, _) => "Full Node",
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Dev mode helpers
 // ---------------------------------------------------------------------------
@@ -607,9 +1307,9 @@ fn generate_dev_seed(index: u64) -> [u8; 32] {
 /// address for each, and credits each account with 1M NOVA (10^14 photons).
 ///
 /// Returns the list of funded NOVA addresses.
-async fn prefund_dev_accounts(state_tree: &Arc<RwLock<StateTree>>) -> Vec<String> {
+fn prefund_dev_accounts(state_tree: &parking_lot::RwLock<StateTree>) -> Vec<String> {
     let mut addresses = Vec::with_capacity(DEV_ACCOUNT_COUNT as usize);
-    let mut tree = state_tree.write().await;
+    let mut tree = state_tree.write();
 
     for i in 1..=DEV_ACCOUNT_COUNT {
         let seed = generate_dev_seed(i);
@@ -617,13 +1317,70 @@ async fn prefund_dev_accounts(state_tree: &Arc<RwLock<StateTree>>) -> Vec<String
         let nova_id = NovaId::from_public_key(&kp.public_key());
         let addr = nova_id.to_address();
 
-        tree.put(&addr, &AccountState::with_balance(DEV_ACCOUNT_BALANCE));
+        fund_genesis_account(&mut tree, &addr, DEV_ACCOUNT_BALANCE);
         addresses.push(addr);
     }
 
     addresses
 }
 
+/// The validator key `--dev-deterministic` runs with: the dev seed at
+/// [`DEV_VALIDATOR_SEED_INDEX`].
+fn dev_validator_keypair() -> NovaKeypair {
+    NovaKeypair::from_seed(&generate_dev_seed(DEV_VALIDATOR_SEED_INDEX))
+}
+
+/// Nudges the consensus loop for every transaction the API admits
+/// (`--dev-instant`). A lagged receiver nudges too, since it has missed at
+/// least one.
+async fn forward_transactions_to_trigger(
+    mut events: broadcast::Receiver<api::NodeEvent>,
+    trigger: Arc<tokio::sync::Notify>,
+) {
+    loop {
+        match events.recv().await {
+            Ok(api::NodeEvent::NewTransaction { .. })
+            | Err(broadcast::error::RecvError::Lagged(_)) => trigger.notify_one(),
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Publishes every transaction the mempool drops as a `transaction_evicted`
+/// event, so wallets learn it will not confirm.
+async fn forward_evictions(
+    mut evictions: broadcast::Receiver<Eviction>,
+    event_tx: broadcast::Sender<api::NodeEvent>,
+) {
+    loop {
+        match evictions.recv().await {
+            Ok(eviction) => {
+                let _ = event_tx.send(api::NodeEvent::from(&eviction));
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                tracing::warn!(missed, "mempool eviction events dropped");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Periodically evicts expired and stale transactions from the mempool, so
+/// a payment that will never confirm stops holding its sender's slot.
+async fn sweep_mempool(mempool: Arc<Mempool>) {
+    let mut interval = tokio::time::interval(MEMPOOL_SWEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let expired = mempool.evict_expired(now_ms);
+        let stale = mempool.expire_old();
+        if expired + stale > 0 {
+            tracing::debug!(expired, stale, "swept mempool");
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Startup banner
 // ---------------------------------------------------------------------------
@@ -631,6 +1388,7 @@ async fn prefund_dev_accounts(state_tree: &Arc<RwLock<StateTree>>) -> Vec<String
 /// Prints the node startup banner with configuration summary.
 fn print_startup_banner(
     node_id: &str,
+    network: &str,
     rpc_addr: &str,
     p2p_addr: &str,
     data_dir: &str,
@@ -648,6 +1406,7 @@ fn print_startup_banner(
     // Compute the box width based on content.
     let lines = [
         format!("  Node ID:    {}", node_id_short),
+        format!("  Network:    {}", network),
         format!("  RPC:        http://{}", rpc_addr),
         format!("  P2P:        /ip4/{}", p2p_addr.replace(':', "/tcp/")),
         format!("  Data:       {}", data_dir),
@@ -727,6 +1486,18 @@ async fn shutdown_signal() {
 /// Minimal HTTP GET without pulling in `reqwest` as a dependency.
 /// In a real deployment, swap this for a proper HTTP client.
 async fn reqwest_get_stub(url: &str) -> Result<String> {
+    http_request_stub("GET", url, None).await
+}
+
+/// Minimal HTTP POST of a JSON body; see [`reqwest_get_stub`].
+async fn reqwest_post_json_stub(url: &str, body: &str) -> Result<String> {
+    http_request_stub("POST", url, Some(body)).await
+}
+
+/// Sends one HTTP/1.1 request over a fresh connection and returns the
+/// response body. The status line is not inspected — the API reports
+/// errors in the body.
+async fn http_request_stub(method: &str, url: &str, json_body: Option<&str>) -> Result<String> {
     let parsed: url::Url = url
         .parse()
         .map_err(|e| anyhow::anyhow!("invalid URL: {}", e))?;
@@ -742,10 +1513,18 @@ async fn reqwest_get_stub(url: &str) -> Result<String> {
         .await
         .with_context(|| format!("failed to connect to {}", addr))?;
 
-    let request = format!(
-        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
-        path, host,
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        method, path, host,
     );
+    match json_body {
+        Some(body) => request.push_str(&format!(
+            "Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )),
+        None => request.push_str("\r\n"),
+    }
 
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
     stream.write_all(request.as_bytes()).await?;
@@ -975,6 +1754,7 @@ mod tests {
         // Just verify it does not panic with various inputs.
         print_startup_banner(
             "nova1abc123def456ghi789jkl012mno345pqr678",
+            "devnet",
             "0.0.0.0:9741",
             "0.0.0.0:9740",
             "/home/user/.nova",
@@ -985,15 +1765,15 @@ mod tests {
 
     // -- 9. Prefund dev accounts populates state tree ---------------------
 
-    #[tokio::test]
-    async fn prefund_dev_accounts_populates_state_tree() {
+    #[test]
+    fn prefund_dev_accounts_populates_state_tree() {
         let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
-        let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+        let state_tree = parking_lot::RwLock::new(StateTree::new((*db).clone()));
 
-        let addresses = prefund_dev_accounts(&state_tree).await;
+        let addresses = prefund_dev_accounts(&state_tree);
         assert_eq!(addresses.len(), DEV_ACCOUNT_COUNT as usize);
 
-        let tree = state_tree.read().await;
+        let tree = state_tree.read();
         for addr in &addresses {
             let account = tree.get(addr).expect("account should exist");
             assert_eq!(
@@ -1002,19 +1782,21 @@ mod tests {
                 addr, DEV_ACCOUNT_BALANCE
             );
         }
+        let record = nova_protocol::storage::emission::SupplyRecord::load(&tree);
+        assert_eq!(record.genesis, DEV_ACCOUNT_COUNT * DEV_ACCOUNT_BALANCE);
     }
 
     // -- 10. Prefund dev accounts are deterministic -----------------------
 
-    #[tokio::test]
-    async fn prefund_dev_accounts_deterministic() {
+    #[test]
+    fn prefund_dev_accounts_deterministic() {
         let db1 = Arc::new(NovaDB::open_temporary().expect("temp db 1"));
         let db2 = Arc::new(NovaDB::open_temporary().expect("temp db 2"));
-        let tree1 = Arc::new(RwLock::new(StateTree::new((*db1).clone())));
-        let tree2 = Arc::new(RwLock::new(StateTree::new((*db2).clone())));
+        let tree1 = parking_lot::RwLock::new(StateTree::new((*db1).clone()));
+        let tree2 = parking_lot::RwLock::new(StateTree::new((*db2).clone()));
 
-        let addrs1 = prefund_dev_accounts(&tree1).await;
-        let addrs2 = prefund_dev_accounts(&tree2).await;
+        let addrs1 = prefund_dev_accounts(&tree1);
+        let addrs2 = prefund_dev_accounts(&tree2);
 
         assert_eq!(addrs1, addrs2, "dev addresses must be deterministic");
     }
@@ -1048,4 +1830,96 @@ mod tests {
         assert_eq!(cli::format_nova_amount(1), "0.00000001");
         assert_eq!(cli::format_nova_amount(100_000_000), "1.00000000");
     }
+
+    // -- 13. Machine-readable version and key output ----------------------
+
+    #[test]
+    fn version_info_has_stable_fields() {
+        let info = version_info();
+        assert_eq!(info["node"].as_str().unwrap(), env!("CARGO_PKG_VERSION"));
+        assert!(info["protocol"].is_string());
+        assert!(info["rustc"].is_string());
+        assert!(info.get("commit").is_some());
+        assert!(info.get("built").is_some());
+    }
+
+    #[test]
+    fn key_info_reads_without_generating() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(
+            key_info(dir.path()).is_err(),
+            "missing key must not be generated"
+        );
+        assert!(!dir.path().join("keys").exists());
+
+        let keypair = load_or_generate_keypair(dir.path()).unwrap();
+        let info = key_info(dir.path()).unwrap();
+        assert_eq!(
+            info["public_key"].as_str().unwrap(),
+            keypair.public_key().to_hex()
+        );
+        assert!(info["address"].as_str().unwrap().starts_with("nova1"));
+    }
+
+    // -- 14. Shell completions --------------------------------------------
+
+    #[test]
+    fn completions_generate_for_each_shell() {
+        for shell in [
+            clap_complete::Shell::Bash,
+            clap_complete::Shell::Zsh,
+            clap_complete::Shell::Fish,
+        ] {
+            let mut buf = Vec::new();
+            clap_complete::generate(shell, &mut NovaNodeCli::command(), "nova-node", &mut buf);
+            let script = String::from_utf8(buf).unwrap();
+            assert!(
+                script.contains("nova-node"),
+                "{shell} script names the binary"
+            );
+            assert!(
+                script.contains("completions"),
+                "{shell} script lists subcommands"
+            );
+        }
+    }
+
+    // -- 15. Deterministic dev mode -----------------------------------------
+
+    #[test]
+    fn dev_validator_key_is_fixed_and_not_an_account_key() {
+        let validator = dev_validator_keypair().public_key().to_hex();
+        assert_eq!(validator, dev_validator_keypair().public_key().to_hex());
+        for i in 1..=DEV_ACCOUNT_COUNT {
+            let account = NovaKeypair::from_seed(&generate_dev_seed(i));
+            assert_ne!(account.public_key().to_hex(), validator);
+        }
+    }
+
+    #[tokio::test]
+    async fn admitted_transactions_trigger_production() {
+        let (event_tx, _) = broadcast::channel(4);
+        let trigger = Arc::new(tokio::sync::Notify::new());
+        let forwarder = tokio::spawn(forward_transactions_to_trigger(
+            event_tx.subscribe(),
+            Arc::clone(&trigger),
+        ));
+
+        event_tx
+            .send(api::NodeEvent::NewTransaction {
+                hash: "ab".into(),
+                sender: "nova1alice".into(),
+                recipient: "nova1bob".into(),
+                amount: 1,
+                fee: 0,
+                pending: true,
+            })
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(1), trigger.notified())
+            .await
+            .expect("trigger notified");
+
+        drop(event_tx);
+        forwarder.await.unwrap();
+    }
 }
@@ -8,8 +8,16 @@
 
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use prometheus::{Encoder, Histogram, HistogramOpts, IntCounter, IntGauge, Registry, TextEncoder};
+use nova_protocol::network::direct::DirectService;
+use nova_protocol::network::gossip::PeerTransport;
+use nova_protocol::network::mempool::{Mempool, MempoolEvent};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
 
 /// Holds all Prometheus metric handles for the node.
 ///
@@ -26,14 +34,31 @@ pub struct NodeMetrics {
     pub transactions_processed_total: IntCounter,
     /// Current number of transactions waiting in the mempool.
     pub transactions_in_mempool: IntGauge,
+    /// Mempool changes, labelled by `event` (`added`, `removed`,
+    /// `replaced` or `evicted`).
+    pub mempool_events_total: IntCounterVec,
     /// Number of currently connected P2P peers.
     pub connected_peers: IntGauge,
+    /// Open P2P connections, labelled by `transport` (`tcp` or `quic`).
+    pub p2p_connections: IntGaugeVec,
     /// Total number of consensus rounds participated in.
     pub consensus_rounds_total: IntCounter,
     /// Current block height (latest finalized block).
     pub block_height: IntGauge,
     /// Histogram of transaction processing latency in seconds.
     pub transaction_latency_seconds: Histogram,
+    /// Median clock offset of the network relative to this node, in ms.
+    pub clock_offset_ms: IntGauge,
+    /// Number of fresh sources behind the clock offset estimate.
+    pub clock_sources: IntGauge,
+    /// 1 while the local clock is drifted and proposals are suspended.
+    pub clock_drifted: IntGauge,
+    /// 1 if the release manifest lists a newer `nova-node`.
+    pub update_available: IntGauge,
+    /// 1 if this node is below the network's activated requirements.
+    pub update_required: IntGauge,
+    /// 1 while an emergency halt stops block production.
+    pub chain_halted: IntGauge,
 }
 
 impl NodeMetrics {
@@ -69,6 +94,15 @@ impl NodeMetrics {
             .register(Box::new(transactions_in_mempool.clone()))
             .expect("metric registration");
 
+        let mempool_events_total = IntCounterVec::new(
+            Opts::new("mempool_events_total", "Mempool changes by event"),
+            &["event"],
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(mempool_events_total.clone()))
+            .expect("metric registration");
+
         let connected_peers =
             IntGauge::new("connected_peers", "Number of currently connected P2P peers")
                 .expect("metric creation");
@@ -76,6 +110,15 @@ impl NodeMetrics {
             .register(Box::new(connected_peers.clone()))
             .expect("metric registration");
 
+        let p2p_connections = IntGaugeVec::new(
+            Opts::new("p2p_connections", "Open P2P connections by transport"),
+            &["transport"],
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(p2p_connections.clone()))
+            .expect("metric registration");
+
         let consensus_rounds_total = IntCounter::new(
             "consensus_rounds_total",
             "Total number of consensus rounds this node has participated in",
@@ -105,15 +148,77 @@ impl NodeMetrics {
             .register(Box::new(transaction_latency_seconds.clone()))
             .expect("metric registration");
 
+        let clock_offset_ms = IntGauge::new(
+            "clock_offset_ms",
+            "Median network clock offset relative to the local clock, in milliseconds",
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(clock_offset_ms.clone()))
+            .expect("metric registration");
+
+        let clock_sources = IntGauge::new(
+            "clock_sources",
+            "Number of fresh peer/NTP samples behind the clock offset estimate",
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(clock_sources.clone()))
+            .expect("metric registration");
+
+        let clock_drifted = IntGauge::new(
+            "clock_drifted",
+            "1 if the local clock exceeds the tolerated skew, 0 otherwise",
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(clock_drifted.clone()))
+            .expect("metric registration");
+
+        let update_available = IntGauge::new(
+            "update_available",
+            "1 if the signed release manifest lists a newer nova-node release",
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(update_available.clone()))
+            .expect("metric registration");
+
+        let update_required = IntGauge::new(
+            "update_required",
+            "1 if this node is below the network's minimum node or protocol version",
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(update_required.clone()))
+            .expect("metric registration");
+
+        let chain_halted = IntGauge::new(
+            "chain_halted",
+            "1 while an accepted emergency halt stops block production",
+        )
+        .expect("metric creation");
+        registry
+            .register(Box::new(chain_halted.clone()))
+            .expect("metric registration");
+
         Self {
             registry,
             blocks_processed_total,
             transactions_processed_total,
             transactions_in_mempool,
+            mempool_events_total,
             connected_peers,
+            p2p_connections,
             consensus_rounds_total,
             block_height,
             transaction_latency_seconds,
+            clock_offset_ms,
+            clock_sources,
+            clock_drifted,
+            update_available,
+            update_required,
+            chain_halted,
         }
     }
 
@@ -127,6 +232,50 @@ impl NodeMetrics {
     }
 }
 
+/// Mirrors the direct messaging swarm's open connections into
+/// `p2p_connections` and `connected_peers` every few seconds.
+pub async fn track_connections(service: Arc<DirectService>, metrics: Arc<NodeMetrics>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(5));
+    loop {
+        interval.tick().await;
+        let mut total = 0;
+        for transport in PeerTransport::ALL {
+            let open = service.connections(transport);
+            total += open;
+            metrics
+                .p2p_connections
+                .with_label_values(&[transport.as_str()])
+                .set(open as i64);
+        }
+        metrics.connected_peers.set(total as i64);
+    }
+}
+
+/// Counts mempool `events` into `mempool_events_total` and keeps
+/// `transactions_in_mempool` at the pool's size after each one.
+pub async fn track_mempool(
+    mut events: broadcast::Receiver<MempoolEvent>,
+    mempool: Arc<Mempool>,
+    metrics: Arc<NodeMetrics>,
+) {
+    metrics.transactions_in_mempool.set(mempool.size() as i64);
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                metrics
+                    .mempool_events_total
+                    .with_label_values(&[event.kind()])
+                    .inc();
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(skipped, "mempool metrics lagged");
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+        metrics.transactions_in_mempool.set(mempool.size() as i64);
+    }
+}
+
 /// Shared metrics state passed to axum handlers via extension.
 pub type SharedMetrics = Arc<NodeMetrics>;
 
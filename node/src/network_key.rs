@@ -0,0 +1,179 @@
+//! # Network Key
+//!
+//! The node's transport identity, kept apart from the validator key
+//! ([`nova_protocol::network::network_keys`]):
+//!
+//! | File                 | Signs                                   | Derives       |
+//! |----------------------|-----------------------------------------|---------------|
+//! | `keys/validator.key` | blocks, votes, validator transactions   | validator ID  |
+//! | `keys/network.key`   | libp2p handshakes                       | `PeerId`      |
+//!
+//! `init` writes both; `run` creates `network.key` on first start if it is
+//! missing, so older data directories pick up a network key of their own.
+//! `--network-key-file` points `run` elsewhere, e.g. at a file provisioned
+//! separately from the validator key.
+//!
+//! `keys rotate-network-key` replaces the network key without touching the
+//! validator key: it signs a binding with a fresh key, submits it as a
+//! `NetworkKeyRotation` transaction signed with the validator key, and only
+//! once the node accepts it moves the new key into place. Restart the node
+//! to start using it. Nodes that do not advertise the
+//! `network_key_rotation` capability are refused up front.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use nova_protocol::crypto::keys::NovaKeypair;
+use nova_protocol::identity::NovaId;
+use nova_protocol::network::capabilities::Capabilities;
+use nova_protocol::network::direct::peer_id_of;
+use nova_protocol::network::network_keys::NetworkKeyBinding;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+use crate::cli::{self, KeysRotateNetworkKeyArgs};
+
+/// Where the network key lives: `file` if given, else
+/// `{data_dir}/keys/network.key`.
+pub fn key_path(data_dir: &Path, file: Option<&Path>) -> PathBuf {
+    file.map(Path::to_path_buf)
+        .unwrap_or_else(|| data_dir.join("keys").join("network.key"))
+}
+
+/// Reads the network key at `path`.
+pub fn read(path: &Path) -> Result<NovaKeypair> {
+    let hex_str = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read network key from {}", path.display()))?;
+    NovaKeypair::from_hex(hex_str.trim()).map_err(|e| anyhow::anyhow!("invalid network key: {}", e))
+}
+
+/// Writes `keypair` to `path` as hex, owner-only on Unix.
+pub fn write(path: &Path, keypair: &NovaKeypair) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create keys directory: {}", dir.display()))?;
+    }
+    std::fs::write(path, hex::encode(keypair.secret_key_bytes()))
+        .with_context(|| format!("failed to write network key to {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+/// Loads the network key at `path`, or generates and saves one.
+pub fn load_or_generate(path: &Path) -> Result<NovaKeypair> {
+    if path.exists() {
+        let keypair = read(path)?;
+        tracing::info!(
+            public_key = %keypair.public_key().to_hex(),
+            key_path = %path.display(),
+            "loaded network key from disk"
+        );
+        return Ok(keypair);
+    }
+    let keypair = NovaKeypair::generate();
+    write(path, &keypair)?;
+    tracing::info!(
+        public_key = %keypair.public_key().to_hex(),
+        key_path = %path.display(),
+        "generated and saved new network key"
+    );
+    Ok(keypair)
+}
+
+/// The libp2p peer ID of `keypair`, as a string.
+pub fn peer_id(keypair: &NovaKeypair) -> String {
+    peer_id_of(&keypair.public_key())
+        .map(|id| id.to_string())
+        .unwrap_or_default()
+}
+
+/// Publishes a fresh network key for the validator and installs it.
+pub async fn rotate(args: KeysRotateNetworkKeyArgs) -> Result<()> {
+    let data_dir = cli::resolve_data_dir(&args.data_dir);
+    let (validator, _) = crate::read_validator_key(&data_dir)?;
+    let path = key_path(&data_dir, args.network_key_file.as_deref());
+    let address = NovaId::from_public_key(&validator.public_key()).to_address();
+    let rpc_url = args.rpc_url.trim_end_matches('/');
+    let caps = crate::capabilities::fetch(rpc_url).await?;
+    crate::capabilities::require(
+        &caps,
+        Capabilities::NETWORK_KEY_ROTATION,
+        "network key rotation",
+    )?;
+    let nonce = match args.nonce {
+        Some(nonce) => nonce,
+        None => crate::capabilities::next_nonce(rpc_url, &caps, &address).await?,
+    };
+
+    // Stage the new key first: once the rotation is on chain, losing the
+    // key would leave the validator bound to a peer nobody can run.
+    let network = NovaKeypair::generate();
+    let staged = path.with_extension("key.new");
+    write(&staged, &network)?;
+
+    let binding = NetworkKeyBinding::sign(&validator.public_key(), &network);
+    let mut tx = TransactionBuilder::new(TransactionType::NetworkKeyRotation)
+        .sender(&address)
+        .receiver(&address)
+        .amount(Amount::new(0, Currency::NOVA))
+        .nonce(nonce)
+        .payload(serde_json::to_vec(&binding)?)
+        .build()
+        .map_err(|e| anyhow::anyhow!("invalid transaction: {}", e))?;
+    sign_transaction(&mut tx, &validator);
+
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "nova_sendTransaction",
+        "params": [tx],
+        "id": 1,
+    })
+    .to_string();
+    let response = crate::reqwest_post_json_stub(&format!("{}/rpc", rpc_url), &body).await?;
+    let id = crate::spam::parse_submission(&response)
+        .map_err(|reason| anyhow::anyhow!("node rejected the rotation: {}", reason))?;
+    std::fs::rename(&staged, &path).with_context(|| {
+        format!(
+            "rotation {} submitted, but moving {} into place failed",
+            id,
+            staged.display()
+        )
+    })?;
+
+    println!("Submitted network key rotation {}", id);
+    println!("  Network key : {}", network.public_key().to_hex());
+    println!("  Peer ID     : {}", peer_id(&network));
+    println!("  Key file    : {}", path.display());
+    println!("Restart the node to use the new key.");
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_key_is_created_once_and_kept() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = key_path(dir.path(), None);
+        assert_eq!(path, dir.path().join("keys").join("network.key"));
+
+        let created = load_or_generate(&path).unwrap();
+        let loaded = load_or_generate(&path).unwrap();
+        assert_eq!(created.public_key(), loaded.public_key());
+
+        let custom = dir.path().join("elsewhere.key");
+        assert_eq!(key_path(dir.path(), Some(&custom)), custom);
+    }
+}
@@ -0,0 +1,180 @@
+//! # Offline Signing
+//!
+//! The `tx export` / `tx sign` / `tx broadcast` subcommands, which carry a
+//! [`TransactionBundle`] between an online machine and an air-gapped signer:
+//!
+//! ```text
+//! online   nova-node tx export --from <treasury> --to <addr> --amount <n> -f tx.json
+//! offline  nova-node tx sign --bundle tx.json --key-file treasury.key -f signed.json
+//! online   nova-node tx broadcast --bundle signed.json
+//! ```
+//!
+//! Every step verifies the bundle's canonical bytes, digest and summary
+//! before using it and prints the digest to stderr, so the operator can
+//! check that all three machines saw the same transaction. `tx sign` reads
+//! one file, writes another and opens no connections. Bundles move as JSON
+//! files or, with `--qr`, as QR chunk strings one per line; readers accept
+//! either.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use nova_protocol::crypto::keys::NovaKeypair;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::bundle::{TransactionBundle, DEFAULT_QR_CHUNK_LEN};
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+use crate::cli::{self, OutputFormat, TxBroadcastArgs, TxExportArgs, TxSignArgs};
+
+/// Builds an unsigned transfer and writes it as a bundle.
+pub async fn export(args: TxExportArgs) -> Result<()> {
+    let nonce = match args.nonce {
+        Some(nonce) => nonce,
+        None => {
+            let rpc_url = args.rpc_url.trim_end_matches('/');
+            let (_, committed) = crate::spam::fetch_account(rpc_url, &args.from).await?;
+            committed + 1
+        }
+    };
+
+    let mut builder = TransactionBuilder::new(TransactionType::Transfer)
+        .sender(&args.from)
+        .receiver(&args.to)
+        .amount(Amount::new(args.amount, Currency::NOVA))
+        .fee(args.fee)
+        .nonce(nonce);
+    if let Some(memo) = &args.memo {
+        builder = builder.payload(memo.as_bytes().to_vec());
+    }
+    let tx = builder
+        .build()
+        .map_err(|e| anyhow::anyhow!("invalid transaction: {}", e))?;
+
+    let bundle = TransactionBundle::export(tx)?;
+    describe(&bundle);
+    write_bundle(&bundle, args.file.as_deref(), args.qr)
+}
+
+/// Signs a bundle with a local key file. Offline by construction.
+pub fn sign(args: TxSignArgs) -> Result<()> {
+    let bundle = read_bundle(&args.bundle)?;
+    let keypair = match &args.key_file {
+        Some(path) => read_key_file(path)?,
+        None => crate::read_validator_key(&cli::resolve_data_dir(&args.data_dir))?.0,
+    };
+
+    describe(&bundle);
+    let signed = bundle
+        .sign(&keypair)
+        .context("refusing to sign this bundle")?;
+    eprintln!("Signed.");
+    write_bundle(&signed, args.file.as_deref(), args.qr)
+}
+
+/// Verifies a signed bundle and submits its transaction.
+pub async fn broadcast(args: TxBroadcastArgs) -> Result<()> {
+    let bundle = read_bundle(&args.bundle)?;
+    let tx = bundle.signed_transaction()?;
+    describe(&bundle);
+
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "nova_sendTransaction",
+        "params": [tx],
+        "id": 1,
+    })
+    .to_string();
+    let url = format!("{}/rpc", args.rpc_url.trim_end_matches('/'));
+    let response = crate::reqwest_post_json_stub(&url, &body).await?;
+    let id = crate::spam::parse_submission(&response)
+        .map_err(|reason| anyhow::anyhow!("node rejected the transaction: {}", reason))?;
+
+    if args.output == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "id": id,
+                "digest": bundle.digest,
+            }))?
+        );
+    } else {
+        println!("Submitted {}", id);
+    }
+    Ok(())
+}
+
+/// Prints the summary and digest for the operator to compare.
+fn describe(bundle: &TransactionBundle) {
+    eprintln!("{}", bundle.summary);
+    eprintln!("  Digest: {}", bundle.digest);
+}
+
+/// Reads and verifies a bundle stored as JSON or as QR chunk lines.
+fn read_bundle(path: &Path) -> Result<TransactionBundle> {
+    Ok(TransactionBundle::from_file(path)?)
+}
+
+fn write_bundle(bundle: &TransactionBundle, file: Option<&Path>, qr: bool) -> Result<()> {
+    let out = if qr {
+        bundle.to_qr_chunks(DEFAULT_QR_CHUNK_LEN).join("\n") + "\n"
+    } else {
+        bundle.to_json() + "\n"
+    };
+    match file {
+        Some(path) => {
+            std::fs::write(path, out).with_context(|| format!("failed to write {}", path.display()))
+        }
+        None => {
+            print!("{}", out);
+            Ok(())
+        }
+    }
+}
+
+/// Reads a hex secret key file, the format `init` writes.
+fn read_key_file(path: &Path) -> Result<NovaKeypair> {
+    let hex_str = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read key from {}", path.display()))?;
+    NovaKeypair::from_hex(hex_str.trim())
+        .map_err(|e| anyhow::anyhow!("invalid key in {}: {}", path.display(), e))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_protocol::identity::NovaId;
+
+    #[test]
+    fn bundles_read_back_from_json_and_qr_lines() {
+        let kp = NovaKeypair::from_seed(&[5u8; 32]);
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender(&NovaId::from_public_key(&kp.public_key()).to_address())
+            .receiver(
+                &NovaId::from_public_key(&NovaKeypair::from_seed(&[6u8; 32]).public_key())
+                    .to_address(),
+            )
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(10)
+            .nonce(1)
+            .build_unchecked();
+        let bundle = TransactionBundle::export(tx).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("tx.json");
+        write_bundle(&bundle, Some(&json), false).unwrap();
+        assert_eq!(read_bundle(&json).unwrap(), bundle);
+        let lines = dir.path().join("tx.qr");
+        write_bundle(&bundle, Some(&lines), true).unwrap();
+        assert_eq!(read_bundle(&lines).unwrap(), bundle);
+
+        let mut tampered = bundle.clone();
+        tampered.transaction.fee = 1;
+        std::fs::write(&json, tampered.to_json()).unwrap();
+        assert!(read_bundle(&json).is_err());
+    }
+}
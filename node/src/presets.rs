@@ -0,0 +1,325 @@
+//! # Network Presets
+//!
+//! Named bundles of everything a node needs to join a particular NOVA
+//! network: chain ID, address prefix, the genesis block it must agree on,
+//! default ports and the seed nodes to dial first. Selected with
+//! `--network` on `init` and `run`.
+//!
+//! `init` stamps the data directory with a small marker file
+//! (`config/network.json`). `run` reads it back and refuses to start when
+//! the directory was initialized for a different network — pointing a
+//! testnet node at a mainnet database is the kind of mistake that should
+//! fail loudly at startup, not quietly at the first block.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use nova_protocol::config;
+use nova_protocol::storage::block::Block;
+use nova_protocol::storage::db::NovaDB;
+use nova_protocol::storage::genesis::GenesisConfig;
+
+/// File name of the network marker inside `{data_dir}/config/`.
+const MARKER_FILE: &str = "network.json";
+
+/// The networks a node can be configured for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Network {
+    /// Production. Real value.
+    Mainnet,
+    /// Public test network. Tokens have no value.
+    Testnet,
+    /// Local development.
+    Devnet,
+}
+
+impl Network {
+    /// Lower-case name, as accepted by `--network`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+        }
+    }
+
+    /// The built-in preset for this network.
+    pub fn preset(self) -> NetworkPreset {
+        match self {
+            Network::Mainnet => NetworkPreset {
+                network: self,
+                chain_id: config::NETWORK_ID_MAINNET,
+                hrp: config::MAINNET_HRP,
+                // Mainnet has not launched; seeds ship with the release
+                // that activates it.
+                seed_nodes: &[],
+                p2p_port: config::DEFAULT_P2P_PORT,
+                rpc_port: config::DEFAULT_RPC_PORT,
+                metrics_port: config::DEFAULT_METRICS_PORT,
+            },
+            Network::Testnet => NetworkPreset {
+                network: self,
+                chain_id: config::NETWORK_ID_TESTNET,
+                hrp: config::TESTNET_HRP,
+                seed_nodes: &[
+                    "/dns4/seed-1.testnet.novaprotocol.io/tcp/9740",
+                    "/dns4/seed-2.testnet.novaprotocol.io/tcp/9740",
+                    "/dns4/seed-3.testnet.novaprotocol.io/tcp/9740",
+                ],
+                p2p_port: config::DEFAULT_P2P_PORT,
+                rpc_port: config::DEFAULT_RPC_PORT,
+                metrics_port: config::DEFAULT_METRICS_PORT,
+            },
+            Network::Devnet => NetworkPreset {
+                network: self,
+                chain_id: config::NETWORK_ID_DEVNET,
+                hrp: config::DEVNET_HRP,
+                // Devnets are local; peers are configured by hand.
+                seed_nodes: &[],
+                p2p_port: config::DEFAULT_P2P_PORT,
+                rpc_port: config::DEFAULT_RPC_PORT,
+                metrics_port: config::DEFAULT_METRICS_PORT,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Everything that distinguishes one network from another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkPreset {
+    /// Which network this preset describes.
+    pub network: Network,
+    /// Chain ID (see `config::NETWORK_ID_*`).
+    pub chain_id: u32,
+    /// Bech32 human-readable prefix for addresses.
+    pub hrp: &'static str,
+    /// Multiaddrs dialed on first start, before the peer store knows anyone.
+    pub seed_nodes: &'static [&'static str],
+    /// Default P2P port.
+    pub p2p_port: u16,
+    /// Default JSON-RPC / REST port.
+    pub rpc_port: u16,
+    /// Default Prometheus metrics port.
+    pub metrics_port: u16,
+}
+
+impl NetworkPreset {
+    /// Hex hash of the genesis block every node on this network must hold
+    /// at height 0.
+    pub fn genesis_hash(&self) -> String {
+        Block::genesis().header.hash.to_hex()
+    }
+
+    /// Parameters fixed at genesis: the chain ID and the protocol's default
+    /// monetary policy.
+    pub fn genesis_config(&self) -> GenesisConfig {
+        GenesisConfig::new(self.chain_id)
+    }
+
+    /// The marker `init` writes for this preset.
+    pub fn marker(&self) -> NetworkMarker {
+        NetworkMarker {
+            network: self.network.name().to_string(),
+            chain_id: self.chain_id,
+            genesis_hash: self.genesis_hash(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Data directory marker
+// ---------------------------------------------------------------------------
+
+/// Identifies the network a data directory belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkMarker {
+    /// Network name (`mainnet`, `testnet`, `devnet`).
+    pub network: String,
+    /// Chain ID the directory was initialized with.
+    pub chain_id: u32,
+    /// Hex hash of the genesis block in the directory's database.
+    pub genesis_hash: String,
+}
+
+/// Path of the network marker for `data_dir`.
+pub fn marker_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("config").join(MARKER_FILE)
+}
+
+impl NetworkMarker {
+    /// Reads the marker from `data_dir`, or `None` if there is none.
+    pub fn load(data_dir: &Path) -> Result<Option<Self>> {
+        let path = marker_path(data_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read network marker {}", path.display()))?;
+        let marker = serde_json::from_str(&raw)
+            .with_context(|| format!("malformed network marker {}", path.display()))?;
+        Ok(Some(marker))
+    }
+
+    /// Writes the marker into `data_dir`, creating `config/` if needed.
+    pub fn store(&self, data_dir: &Path) -> Result<()> {
+        let path = marker_path(data_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create config directory: {}", parent.display())
+            })?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("failed to write network marker {}", path.display()))
+    }
+
+    /// Fails unless this marker describes `preset`.
+    pub fn check(&self, preset: &NetworkPreset) -> Result<()> {
+        if self.network != preset.network.name() || self.chain_id != preset.chain_id {
+            anyhow::bail!(
+                "data directory was initialized for {} (chain ID 0x{:08X}), not {} (chain ID 0x{:08X}); \
+                 pass --network {} or use a different --data-dir",
+                self.network,
+                self.chain_id,
+                preset.network,
+                preset.chain_id,
+                self.network
+            );
+        }
+        if self.genesis_hash != preset.genesis_hash() {
+            anyhow::bail!(
+                "data directory genesis {} does not match the {} genesis {}",
+                self.genesis_hash,
+                preset.network,
+                preset.genesis_hash()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Startup check for `run`: the data directory must belong to `preset`.
+///
+/// Directories initialized before markers existed are adopted: the marker
+/// is written on first run. Either way the genesis block in `db` must
+/// match the preset.
+pub fn ensure_data_dir_network(data_dir: &Path, preset: &NetworkPreset, db: &NovaDB) -> Result<()> {
+    match NetworkMarker::load(data_dir)? {
+        Some(marker) => marker.check(preset)?,
+        None => {
+            preset.marker().store(data_dir)?;
+            tracing::info!(network = %preset.network, "stamped data directory with network marker");
+        }
+    }
+
+    if let Some(genesis) = db.get_block(0).context("failed to read genesis block")? {
+        let hash = genesis.header.hash.to_hex();
+        if hash != preset.genesis_hash() {
+            anyhow::bail!(
+                "database genesis {} does not match the {} genesis {}",
+                hash,
+                preset.network,
+                preset.genesis_hash()
+            );
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -- 1. Presets carry the protocol constants --------------------------
+
+    #[test]
+    fn presets_match_protocol_config() {
+        for network in [Network::Mainnet, Network::Testnet, Network::Devnet] {
+            let preset = network.preset();
+            assert_eq!(config::network_name(preset.chain_id), network.name());
+            assert_eq!(config::hrp_for_network(preset.chain_id), Some(preset.hrp));
+            assert_eq!(preset.p2p_port, config::DEFAULT_P2P_PORT);
+        }
+        assert!(!Network::Testnet.preset().seed_nodes.is_empty());
+        for seed in Network::Testnet.preset().seed_nodes {
+            assert!(seed.starts_with("/dns4/") && seed.ends_with("/tcp/9740"));
+        }
+    }
+
+    // -- 2. Marker round-trips and matches its own preset -----------------
+
+    #[test]
+    fn marker_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(NetworkMarker::load(dir.path()).unwrap().is_none());
+
+        let preset = Network::Testnet.preset();
+        preset.marker().store(dir.path()).unwrap();
+        let loaded = NetworkMarker::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, preset.marker());
+        assert!(loaded.check(&preset).is_ok());
+    }
+
+    // -- 3. A directory initialized for another network is refused --------
+
+    #[test]
+    fn mismatched_network_is_refused() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = NovaDB::open_temporary().unwrap();
+        Network::Testnet
+            .preset()
+            .marker()
+            .store(dir.path())
+            .unwrap();
+
+        let err = ensure_data_dir_network(dir.path(), &Network::Mainnet.preset(), &db)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("initialized for testnet"), "{err}");
+        assert!(ensure_data_dir_network(dir.path(), &Network::Testnet.preset(), &db).is_ok());
+    }
+
+    // -- 4. Unmarked directories are adopted, foreign genesis refused -----
+
+    #[test]
+    fn unmarked_directory_is_adopted() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = NovaDB::open_temporary().unwrap();
+        db.put_block(&Block::genesis()).unwrap();
+
+        let preset = Network::Devnet.preset();
+        ensure_data_dir_network(dir.path(), &preset, &db).unwrap();
+        assert_eq!(
+            NetworkMarker::load(dir.path()).unwrap(),
+            Some(preset.marker())
+        );
+    }
+
+    #[test]
+    fn foreign_genesis_is_refused() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = NovaDB::open_temporary().unwrap();
+        let genesis = Block::genesis();
+        let mut other = Block::new(&genesis, vec![], "nova:v".into(), [1u8; 32]);
+        other.header.height = 0;
+        db.put_block(&other).unwrap();
+
+        let err = ensure_data_dir_network(dir.path(), &Network::Devnet.preset(), &db)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("database genesis"), "{err}");
+    }
+}
@@ -0,0 +1,230 @@
+//! # Build Provenance
+//!
+//! What `build.rs` embedded at compile time — git commit, `Cargo.lock`
+//! hash, compiler, cargo profile, source timestamp — plus the SHA-256 of
+//! the running executable. `version --verify` prints and checks it;
+//! `GET /node` serves it so explorers can compare validators.
+//!
+//! ## Reproducing a Release
+//!
+//! ```text
+//! git checkout <commit>
+//! make release-reproducible
+//! nova-node version --verify --commit <commit> --sha256 <published hash>
+//! ```
+//!
+//! `make release-reproducible` builds with `--locked`, the `reproducible`
+//! profile, `SOURCE_DATE_EPOCH` set to the commit time and the checkout
+//! and cargo home remapped out of the binary, so the same commit and
+//! toolchain give the same bytes on any machine.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Provenance of this binary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    /// `nova-node` crate version.
+    pub node: &'static str,
+    /// Protocol version it speaks.
+    pub protocol: &'static str,
+    /// Git commit it was built from; `-dirty` if the tree had changes.
+    pub commit: Option<&'static str>,
+    /// SHA-256 of the workspace `Cargo.lock`, hex.
+    pub lockfile_sha256: Option<&'static str>,
+    /// `rustc --version` of the compiler.
+    pub rustc: &'static str,
+    /// Cargo profile, e.g. `release` or `reproducible`.
+    pub profile: Option<&'static str>,
+    /// `SOURCE_DATE_EPOCH` as RFC 3339, if the build set it.
+    pub built: Option<&'static str>,
+    /// SHA-256 of the running executable, hex.
+    pub binary_sha256: Option<String>,
+}
+
+impl BuildInfo {
+    /// Provenance of the running binary.
+    pub fn current() -> Self {
+        Self {
+            node: env!("CARGO_PKG_VERSION"),
+            protocol: nova_protocol::config::PROTOCOL_VERSION,
+            commit: option_env!("GIT_COMMIT"),
+            lockfile_sha256: option_env!("CARGO_LOCK_SHA256"),
+            rustc: option_env!("RUSTC_VERSION").unwrap_or("unknown"),
+            profile: option_env!("BUILD_PROFILE"),
+            built: option_env!("BUILD_TIMESTAMP"),
+            binary_sha256: binary_sha256(),
+        }
+    }
+
+    /// Whether the commit records uncommitted changes.
+    pub fn is_dirty(&self) -> bool {
+        self.commit.is_some_and(|c| c.ends_with("-dirty"))
+    }
+}
+
+/// SHA-256 of the running executable, computed once.
+pub fn binary_sha256() -> Option<String> {
+    static HASH: OnceLock<Option<String>> = OnceLock::new();
+    HASH.get_or_init(|| {
+        let exe = std::env::current_exe().ok()?;
+        file_sha256(&exe).ok()
+    })
+    .clone()
+}
+
+/// Hex SHA-256 of a file's contents.
+pub fn file_sha256(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Values `version --verify` compares the binary against.
+#[derive(Debug, Clone, Default)]
+pub struct Expected {
+    /// Commit the binary should have been built from (a prefix is enough).
+    pub commit: Option<String>,
+    /// Hex SHA-256 of a `Cargo.lock` the binary should have been built with.
+    pub lockfile_sha256: Option<String>,
+    /// Published hex SHA-256 of the binary.
+    pub binary_sha256: Option<String>,
+}
+
+/// One line of the `version --verify` report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    /// What was checked.
+    pub name: &'static str,
+    /// Whether it passed.
+    pub ok: bool,
+    /// Why, in a few words.
+    pub detail: String,
+}
+
+/// Checks `info` for completeness, then against `expected`.
+pub fn verify(info: &BuildInfo, expected: &Expected) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    checks.push(match info.commit {
+        None => fail("commit", "no commit embedded".to_string()),
+        Some(c) if info.is_dirty() => fail("commit", format!("{} built from a modified tree", c)),
+        Some(c) => match &expected.commit {
+            Some(want) if !c.starts_with(want.to_lowercase().as_str()) => {
+                fail("commit", format!("built from {}, expected {}", c, want))
+            }
+            _ => pass("commit", c.to_string()),
+        },
+    });
+
+    checks.push(match (info.lockfile_sha256, &expected.lockfile_sha256) {
+        (None, _) => fail("lockfile", "no Cargo.lock hash embedded".to_string()),
+        (Some(have), Some(want)) if !have.eq_ignore_ascii_case(want) => fail(
+            "lockfile",
+            format!("built with Cargo.lock {}, expected {}", have, want),
+        ),
+        (Some(have), _) => pass("lockfile", have.to_string()),
+    });
+
+    if let Some(want) = &expected.binary_sha256 {
+        checks.push(match &info.binary_sha256 {
+            None => fail(
+                "binary",
+                "could not read the running executable".to_string(),
+            ),
+            Some(have) if !have.eq_ignore_ascii_case(want) => {
+                fail("binary", format!("hashes to {}, expected {}", have, want))
+            }
+            Some(have) => pass("binary", have.clone()),
+        });
+    }
+
+    checks
+}
+
+fn pass(name: &'static str, detail: String) -> Check {
+    Check {
+        name,
+        ok: true,
+        detail,
+    }
+}
+
+fn fail(name: &'static str, detail: String) -> Check {
+    Check {
+        name,
+        ok: false,
+        detail,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> BuildInfo {
+        BuildInfo {
+            node: "0.1.0",
+            protocol: "0.1.0",
+            commit: Some("3894918aa0c1"),
+            lockfile_sha256: Some("ab12"),
+            rustc: "rustc 1.75.0",
+            profile: Some("reproducible"),
+            built: None,
+            binary_sha256: Some("cd34".to_string()),
+        }
+    }
+
+    #[test]
+    fn verify_passes_matching_provenance() {
+        let expected = Expected {
+            commit: Some("3894918".to_string()),
+            lockfile_sha256: Some("AB12".to_string()),
+            binary_sha256: Some("cd34".to_string()),
+        };
+        let checks = verify(&info(), &expected);
+        assert_eq!(checks.len(), 3);
+        assert!(checks.iter().all(|c| c.ok), "{:?}", checks);
+
+        // Without expectations only completeness is checked.
+        assert!(verify(&info(), &Expected::default()).iter().all(|c| c.ok));
+    }
+
+    #[test]
+    fn verify_flags_mismatches_and_dirty_trees() {
+        let expected = Expected {
+            commit: Some("deadbeef".to_string()),
+            lockfile_sha256: Some("ffff".to_string()),
+            binary_sha256: Some("0000".to_string()),
+        };
+        assert!(verify(&info(), &expected).iter().all(|c| !c.ok));
+
+        let dirty = BuildInfo {
+            commit: Some("3894918aa0c1-dirty"),
+            lockfile_sha256: None,
+            ..info()
+        };
+        let checks = verify(&dirty, &Expected::default());
+        assert!(checks.iter().all(|c| !c.ok));
+        assert!(checks[0].detail.contains("modified tree"));
+    }
+
+    #[test]
+    fn current_build_embeds_the_lockfile_hash() {
+        let info = BuildInfo::current();
+        let lockfile = Path::new(env!("CARGO_MANIFEST_DIR")).join("../Cargo.lock");
+        assert_eq!(
+            info.lockfile_sha256.map(str::to_string),
+            Some(file_sha256(&lockfile).unwrap())
+        );
+        assert!(info.binary_sha256.is_some());
+    }
+}
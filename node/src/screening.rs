@@ -0,0 +1,280 @@
+//! # Address Screening
+//!
+//! Wires `--screening-*` flags to the mempool's screening hook
+//! ([`nova_protocol::network::screening`]) and provides the HTTP provider
+//! for external screening services.
+//!
+//! ## HTTP Service Contract
+//!
+//! The node sends `GET <url>?address=<address>` and expects a 200 response
+//! with a JSON body:
+//!
+//! ```json
+//! { "listed": true, "reason": "SDN-12345" }
+//! ```
+//!
+//! `reason` is optional. Any other status, a malformed body or a timeout
+//! counts as the provider being unavailable, which refuses the transaction
+//! unless `--screening-fail-open` is set. Answers are cached for
+//! [`CACHE_TTL`] so a busy sender does not cost one request per
+//! transaction; admission blocks on the service only on a cache miss.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::Deserialize;
+
+use nova_protocol::network::screening::{
+    Screener, ScreeningAction, ScreeningError, ScreeningProvider, StaticListProvider,
+};
+use nova_protocol::storage::audit::AuditLog;
+
+use crate::cli::{RunArgs, ScreeningMode};
+
+/// How long an HTTP screening answer is reused.
+pub const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Connect, write and read timeout for one screening request.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Most cached answers; the cache is dropped wholesale when full.
+const MAX_CACHE_ENTRIES: usize = 100_000;
+
+/// Largest response body accepted from a screening service.
+const MAX_RESPONSE_BYTES: u64 = 64 * 1024;
+
+/// Builds the screener the `run` flags ask for, or `None` if screening is
+/// not configured.
+pub fn build(args: &RunArgs, audit: Arc<AuditLog>) -> Result<Option<Arc<Screener>>> {
+    let provider: Arc<dyn ScreeningProvider> = match (&args.screening_list, &args.screening_url) {
+        (Some(path), _) => {
+            let list = StaticListProvider::from_file(path)?;
+            tracing::info!(
+                list = %path.display(),
+                addresses = list.len(),
+                "screening list loaded"
+            );
+            Arc::new(list)
+        }
+        (None, Some(url)) => {
+            let provider = HttpScreeningProvider::new(url)?;
+            tracing::info!(url = %url, "screening service configured");
+            Arc::new(provider)
+        }
+        (None, None) => return Ok(None),
+    };
+    let action = match args.screening_action {
+        ScreeningMode::Block => ScreeningAction::Block,
+        ScreeningMode::Flag => ScreeningAction::Flag,
+    };
+    Ok(Some(Arc::new(
+        Screener::new(provider, action)
+            .with_fail_open(args.screening_fail_open)
+            .with_audit_log(audit),
+    )))
+}
+
+/// The body a screening service answers with.
+#[derive(Debug, Deserialize)]
+struct ScreeningAnswer {
+    listed: bool,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Screens addresses with an external HTTP service.
+pub struct HttpScreeningProvider {
+    name: String,
+    host: String,
+    port: u16,
+    path: String,
+    cache: Mutex<HashMap<String, (Instant, Option<String>)>>,
+}
+
+impl HttpScreeningProvider {
+    /// A provider for the service at `url` (plain `http://` only).
+    pub fn new(url: &str) -> Result<Self> {
+        let parsed: crate::url::Url = url
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid screening URL: {}", e))?;
+        let host = parsed
+            .host_str()
+            .filter(|h| !h.is_empty())
+            .context("missing host in screening URL")?
+            .to_string();
+        Ok(Self {
+            name: host.clone(),
+            port: parsed.port().unwrap_or(80),
+            path: parsed.path().to_string(),
+            host,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn query(&self, address: &str) -> Result<Option<String>, ScreeningError> {
+        let unavailable = |e: &dyn std::fmt::Display| ScreeningError::Unavailable(e.to_string());
+
+        let addr = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| unavailable(&e))?
+            .next()
+            .ok_or_else(|| ScreeningError::Unavailable(format!("cannot resolve {}", self.host)))?;
+        let mut stream =
+            TcpStream::connect_timeout(&addr, REQUEST_TIMEOUT).map_err(|e| unavailable(&e))?;
+        stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(REQUEST_TIMEOUT)))
+            .map_err(|e| unavailable(&e))?;
+
+        let request = format!(
+            "GET {}?address={} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            percent_encode(address),
+            self.host,
+        );
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| unavailable(&e))?;
+        let mut buf = Vec::new();
+        stream
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_end(&mut buf)
+            .map_err(|e| unavailable(&e))?;
+
+        let response = String::from_utf8_lossy(&buf);
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| ScreeningError::Unavailable("malformed HTTP response".into()))?;
+        let status = head.split_whitespace().nth(1).unwrap_or_default();
+        if status != "200" {
+            return Err(ScreeningError::Unavailable(format!(
+                "HTTP status {}",
+                status
+            )));
+        }
+        let answer: ScreeningAnswer = serde_json::from_str(body.trim())
+            .map_err(|e| ScreeningError::Unavailable(format!("malformed answer: {}", e)))?;
+        Ok(answer
+            .listed
+            .then(|| answer.reason.unwrap_or_else(|| "listed".to_string())))
+    }
+}
+
+impl ScreeningProvider for HttpScreeningProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn screen(&self, address: &str) -> Result<Option<String>, ScreeningError> {
+        let key = address.to_lowercase();
+        if let Some((at, answer)) = self.cache.lock().get(&key) {
+            if at.elapsed() < CACHE_TTL {
+                return Ok(answer.clone());
+            }
+        }
+        // Failures are not cached: the next transaction retries.
+        let answer = self.query(address)?;
+        let mut cache = self.cache.lock();
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            cache.clear();
+        }
+        cache.insert(key, (Instant::now(), answer.clone()));
+        Ok(answer)
+    }
+}
+
+/// Percent-encodes everything but unreserved URL characters.
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Serves `responses` in order, one per connection, and returns the
+    /// service URL plus a handle yielding the request lines it received.
+    fn serve(responses: Vec<&'static str>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/screen", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 1024];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]);
+                requests.push(request.lines().next().unwrap_or_default().to_string());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    // -- 1. HTTP answers are parsed and cached -------------------------------
+
+    #[test]
+    fn http_provider_parses_and_caches_answers() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\n\r\n{\"listed\":true,\"reason\":\"SDN-9\"}",
+            "HTTP/1.1 200 OK\r\n\r\n{\"listed\":false}",
+        ]);
+        let provider = HttpScreeningProvider::new(&url).unwrap();
+
+        assert_eq!(
+            provider.screen("nova1bad").unwrap().as_deref(),
+            Some("SDN-9")
+        );
+        assert_eq!(
+            provider.screen("NOVA1BAD").unwrap().as_deref(),
+            Some("SDN-9")
+        );
+        assert_eq!(provider.screen("nova1ok").unwrap(), None);
+
+        let requests = server.join().unwrap();
+        assert_eq!(
+            requests,
+            [
+                "GET /screen?address=nova1bad HTTP/1.1",
+                "GET /screen?address=nova1ok HTTP/1.1",
+            ]
+        );
+    }
+
+    // -- 2. Service errors surface as unavailable ----------------------------
+
+    #[test]
+    fn http_provider_reports_errors_as_unavailable() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\n\r\n",
+            "HTTP/1.1 200 OK\r\n\r\nnot json",
+        ]);
+        let provider = HttpScreeningProvider::new(&url).unwrap();
+
+        for _ in 0..2 {
+            assert!(matches!(
+                provider.screen("nova1x"),
+                Err(ScreeningError::Unavailable(_))
+            ));
+        }
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,592 @@
+//! # Load Generator
+//!
+//! `nova-node spam` drives a running node — typically one member of a
+//! multi-node testnet — at a steady transactions-per-second target and
+//! reports how the network coped: how many submissions were admitted, how
+//! long admitted transfers took to confirm, and why the rest were turned
+//! away.
+//!
+//! The senders are the deterministic dev accounts a `--dev` node funds at
+//! startup, so no key material has to be shipped to the machine running
+//! the load. Transfers rotate through the accounts, each one paying its
+//! neighbour.
+//!
+//! ## Nonces
+//!
+//! Each account's next nonce starts from the node's committed state and is
+//! then tracked locally, so many transfers per account can be in flight at
+//! once. A nonce whose submission was rejected never reached the mempool
+//! and is handed out again before any new one; a `stale_nonce` rejection
+//! re-reads the committed nonce from the node.
+//!
+//! ## Fee Bumping
+//!
+//! `fee_too_low` and `mempool_full` rejections raise the fee by a quarter
+//! (up to `--max-fee`); a run of admissions lets it decay back towards
+//! `--fee`. The final fee is part of the report — a high one means the
+//! pool was saturated.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+
+use nova_protocol::crypto::keys::NovaKeypair;
+use nova_protocol::identity::NovaId;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+use crate::bench::TimingStats;
+use crate::cli::{OutputFormat, SpamArgs};
+
+/// How often pending transactions are checked for confirmation.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Upper bound on confirmation lookups per poll, oldest pending first.
+const MAX_POLLS_PER_TICK: usize = 256;
+
+/// Consecutive admissions after which a bumped fee starts decaying.
+const FEE_DECAY_STREAK: u32 = 50;
+
+// ---------------------------------------------------------------------------
+// Nonce and fee bookkeeping
+// ---------------------------------------------------------------------------
+
+/// Next nonces for one sender.
+#[derive(Debug)]
+struct NonceTracker {
+    /// Next never-used nonce.
+    next: u64,
+    /// Nonces handed out whose submission was rejected; reused first.
+    gaps: BTreeSet<u64>,
+}
+
+impl NonceTracker {
+    /// Starts after the committed account nonce.
+    fn new(committed: u64) -> Self {
+        Self {
+            next: committed + 1,
+            gaps: BTreeSet::new(),
+        }
+    }
+
+    /// The nonce for the next transfer.
+    fn take(&mut self) -> u64 {
+        if let Some(nonce) = self.gaps.pop_first() {
+            return nonce;
+        }
+        self.next += 1;
+        self.next - 1
+    }
+
+    /// Returns a nonce whose transfer never reached the mempool.
+    fn give_back(&mut self, nonce: u64) {
+        self.gaps.insert(nonce);
+    }
+
+    /// Catches up with the committed account nonce after a stale rejection.
+    fn resync(&mut self, committed: u64) {
+        self.next = self.next.max(committed + 1);
+        self.gaps.retain(|&n| n > committed);
+    }
+}
+
+/// Fee for new transfers, bumped on fee pressure.
+#[derive(Debug)]
+struct FeeController {
+    base: u64,
+    max: u64,
+    current: u64,
+    streak: u32,
+    bumps: u64,
+}
+
+impl FeeController {
+    fn new(base: u64, max: u64) -> Self {
+        Self {
+            base,
+            max,
+            current: base,
+            streak: 0,
+            bumps: 0,
+        }
+    }
+
+    fn on_accepted(&mut self) {
+        self.streak += 1;
+        if self.streak >= FEE_DECAY_STREAK && self.current > self.base {
+            self.current -= (self.current - self.base).div_ceil(2);
+            self.streak = 0;
+        }
+    }
+
+    fn on_rejected(&mut self, reason: &str) {
+        self.streak = 0;
+        if matches!(reason, "fee_too_low" | "mempool_full") && self.current < self.max {
+            self.current = (self.current + self.current / 4 + 1).min(self.max);
+            self.bumps += 1;
+        }
+    }
+}
+
+/// Whether a rejected submission left its nonce unused.
+fn nonce_unused(reason: &str) -> bool {
+    !matches!(reason, "stale_nonce" | "duplicate")
+}
+
+/// Outcome of a `nova_sendTransaction` response body: the transaction ID,
+/// or the rejection reason.
+pub(crate) fn parse_submission(body: &str) -> Result<String, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(body).map_err(|_| "malformed_response".to_string())?;
+    if let Some(err) = json.get("error").filter(|e| !e.is_null()) {
+        return Err(err
+            .pointer("/data/reason")
+            .and_then(|r| r.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("rpc_{}", err["code"])));
+    }
+    json.get("result")
+        .and_then(|r| r.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "malformed_response".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Result of a load-generation run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpamReport {
+    /// Requested submission rate.
+    pub target_tps: f64,
+    /// Submissions per second actually sent.
+    pub achieved_tps: f64,
+    /// Seconds spent sending.
+    pub send_secs: f64,
+    /// Submissions sent.
+    pub sent: u64,
+    /// Submissions the node admitted to its mempool.
+    pub accepted: u64,
+    /// Submissions the node turned away.
+    pub rejected: u64,
+    /// `accepted / sent`.
+    pub acceptance_rate: f64,
+    /// Admitted transfers seen confirmed.
+    pub confirmed: u64,
+    /// Admitted transfers not confirmed within `--confirm-timeout`.
+    pub timed_out: u64,
+    /// Submission-to-confirmation latency of confirmed transfers.
+    pub confirmation_latency_ms: TimingStats,
+    /// Times the fee was raised.
+    pub fee_bumps: u64,
+    /// Fee in use when sending stopped.
+    pub final_fee: u64,
+    /// Rejections by reason (see the API's `nova_sendTransaction` docs).
+    pub errors: BTreeMap<String, u64>,
+}
+
+// ---------------------------------------------------------------------------
+// Run
+// ---------------------------------------------------------------------------
+
+/// What the spawned request tasks report back to the control loop.
+enum Event {
+    Submitted {
+        account: usize,
+        nonce: u64,
+        sent_at: Instant,
+        outcome: Result<String, String>,
+    },
+    Confirmed {
+        id: String,
+        at: Instant,
+    },
+    PollDone,
+    Resynced {
+        account: usize,
+        committed: u64,
+    },
+}
+
+struct Sender {
+    keypair: NovaKeypair,
+    address: String,
+    nonces: NonceTracker,
+}
+
+/// Reads `(balance, nonce)` for `address` from the node.
+pub(crate) async fn fetch_account(rpc_url: &str, address: &str) -> Result<(u64, u64)> {
+    let body = crate::reqwest_get_stub(&format!("{}/accounts/{}", rpc_url, address)).await?;
+    let json: serde_json::Value =
+        serde_json::from_str(&body).context("node returned a non-JSON account response")?;
+    if let Some(error) = json.get("error").and_then(|e| e.as_str()) {
+        bail!("account {}: {}", address, error);
+    }
+    let field = |name: &str| {
+        json.get(name)
+            .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+            .unwrap_or(0)
+    };
+    Ok((field("balance"), field("nonce")))
+}
+
+/// Checks `args` before any request is made.
+fn validate(args: &SpamArgs) -> Result<()> {
+    if !(2..=crate::DEV_ACCOUNT_COUNT as usize).contains(&args.accounts) {
+        bail!(
+            "--accounts must be between 2 and {} (the dev accounts a --dev node funds)",
+            crate::DEV_ACCOUNT_COUNT
+        );
+    }
+    if !(args.tps > 0.0 && args.tps.is_finite()) {
+        bail!("--tps must be positive");
+    }
+    if args.fee > args.max_fee {
+        bail!("--fee ({}) exceeds --max-fee ({})", args.fee, args.max_fee);
+    }
+    Ok(())
+}
+
+/// Runs the load generator described by `args` until `--duration` has
+/// passed and every admitted transfer has confirmed or timed out.
+pub async fn run(args: &SpamArgs) -> Result<SpamReport> {
+    validate(args)?;
+    let rpc_url = args.rpc_url.trim_end_matches('/').to_string();
+
+    // --- Derive the dev accounts and read their nonces ---
+    let mut senders = Vec::with_capacity(args.accounts);
+    for i in 1..=args.accounts as u64 {
+        let keypair = NovaKeypair::from_seed(&crate::generate_dev_seed(i));
+        let address = NovaId::from_public_key(&keypair.public_key()).to_address();
+        let (balance, nonce) = fetch_account(&rpc_url, &address).await?;
+        if balance == 0 {
+            bail!(
+                "dev account {} ({}) has no balance — is the node running with --dev?",
+                i,
+                address
+            );
+        }
+        senders.push(Sender {
+            keypair,
+            address,
+            nonces: NonceTracker::new(nonce),
+        });
+    }
+
+    let (events_tx, mut events) = mpsc::unbounded_channel();
+    let mut fees = FeeController::new(args.fee, args.max_fee);
+    let mut errors: BTreeMap<String, u64> = BTreeMap::new();
+    let mut pending: VecDeque<(String, Instant)> = VecDeque::new();
+    let mut latencies = Vec::new();
+    let (mut sent, mut accepted, mut rejected, mut timed_out) = (0u64, 0u64, 0u64, 0u64);
+    let mut in_flight = 0usize;
+    let mut polling = false;
+    let mut next_sender = 0usize;
+
+    let confirm_timeout = Duration::from_secs(args.confirm_timeout);
+
+    let mut send_tick = tokio::time::interval(Duration::from_secs_f64(1.0 / args.tps));
+    send_tick.set_missed_tick_behavior(MissedTickBehavior::Burst);
+    let mut poll_tick = tokio::time::interval(POLL_INTERVAL);
+    poll_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let started = Instant::now();
+    let send_until = started + Duration::from_secs(args.duration);
+    let mut send_secs = args.duration as f64;
+    let mut sending = true;
+
+    loop {
+        tokio::select! {
+            _ = send_tick.tick(), if sending => {
+                if Instant::now() >= send_until {
+                    sending = false;
+                    send_secs = started.elapsed().as_secs_f64();
+                    continue;
+                }
+                let account = next_sender;
+                next_sender = (next_sender + 1) % senders.len();
+                let receiver = senders[(account + 1) % senders.len()].address.clone();
+                let sender = &mut senders[account];
+                let nonce = sender.nonces.take();
+
+                let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+                    .sender(&sender.address)
+                    .receiver(&receiver)
+                    .amount(Amount::new(args.amount, Currency::NOVA))
+                    .fee(fees.current)
+                    .nonce(nonce)
+                    .build()?;
+                sign_transaction(&mut tx, &sender.keypair);
+
+                let body = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "nova_sendTransaction",
+                    "params": [tx],
+                    "id": sent,
+                })
+                .to_string();
+                let url = format!("{}/rpc", rpc_url);
+                let events_tx = events_tx.clone();
+                sent += 1;
+                in_flight += 1;
+                tokio::spawn(async move {
+                    let sent_at = Instant::now();
+                    let outcome = match crate::reqwest_post_json_stub(&url, &body).await {
+                        Ok(body) => parse_submission(&body),
+                        Err(_) => Err("transport".to_string()),
+                    };
+                    let _ = events_tx.send(Event::Submitted { account, nonce, sent_at, outcome });
+                });
+            }
+
+            _ = poll_tick.tick() => {
+                let now = Instant::now();
+                while pending.front().is_some_and(|(_, at)| now.duration_since(*at) > confirm_timeout) {
+                    pending.pop_front();
+                    timed_out += 1;
+                }
+
+                if !sending && in_flight == 0 && pending.is_empty() {
+                    break;
+                }
+
+                if !polling && !pending.is_empty() {
+                    polling = true;
+                    let ids: Vec<String> =
+                        pending.iter().take(MAX_POLLS_PER_TICK).map(|(id, _)| id.clone()).collect();
+                    let rpc_url = rpc_url.clone();
+                    let events_tx = events_tx.clone();
+                    tokio::spawn(async move {
+                        let lookups = ids.into_iter().map(|id| {
+                            let url = format!("{}/transactions/{}", rpc_url, id);
+                            async move { (id, crate::reqwest_get_stub(&url).await) }
+                        });
+                        for (id, body) in futures::future::join_all(lookups).await {
+                            let confirmed = body
+                                .ok()
+                                .and_then(|b| serde_json::from_str::<serde_json::Value>(&b).ok())
+                                .is_some_and(|j| j["status"] == "confirmed");
+                            if confirmed {
+                                let _ = events_tx.send(Event::Confirmed { id, at: Instant::now() });
+                            }
+                        }
+                        let _ = events_tx.send(Event::PollDone);
+                    });
+                }
+            }
+
+            Some(event) = events.recv() => match event {
+                Event::Submitted { account, nonce, sent_at, outcome } => {
+                    in_flight -= 1;
+                    match outcome {
+                        Ok(id) => {
+                            accepted += 1;
+                            fees.on_accepted();
+                            pending.push_back((id, sent_at));
+                        }
+                        Err(reason) => {
+                            rejected += 1;
+                            fees.on_rejected(&reason);
+                            if nonce_unused(&reason) {
+                                senders[account].nonces.give_back(nonce);
+                            }
+                            if reason == "stale_nonce" {
+                                let rpc_url = rpc_url.clone();
+                                let address = senders[account].address.clone();
+                                let events_tx = events_tx.clone();
+                                tokio::spawn(async move {
+                                    if let Ok((_, committed)) = fetch_account(&rpc_url, &address).await {
+                                        let _ = events_tx.send(Event::Resynced { account, committed });
+                                    }
+                                });
+                            }
+                            *errors.entry(reason).or_insert(0) += 1;
+                        }
+                    }
+                }
+                Event::Confirmed { id, at } => {
+                    if let Some(i) = pending.iter().position(|(p, _)| *p == id) {
+                        let (_, sent_at) = pending.remove(i).expect("index in range");
+                        latencies.push(at.duration_since(sent_at));
+                    }
+                }
+                Event::PollDone => polling = false,
+                Event::Resynced { account, committed } => {
+                    senders[account].nonces.resync(committed);
+                }
+            },
+        }
+    }
+
+    let confirmed = latencies.len() as u64;
+    Ok(SpamReport {
+        target_tps: args.tps,
+        achieved_tps: sent as f64 / send_secs.max(f64::EPSILON),
+        send_secs,
+        sent,
+        accepted,
+        rejected,
+        acceptance_rate: if sent == 0 {
+            0.0
+        } else {
+            accepted as f64 / sent as f64
+        },
+        confirmed,
+        timed_out,
+        confirmation_latency_ms: TimingStats::from_samples(&latencies),
+        fee_bumps: fees.bumps,
+        final_fee: fees.current,
+        errors,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+/// Prints `report` in the requested format.
+pub fn print_report(report: &SpamReport, output: OutputFormat) -> Result<()> {
+    if output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+
+    let l = &report.confirmation_latency_ms;
+    println!("Load Generation");
+    println!(
+        "  Rate         : {:.1} tx/s sent (target {:.1}) over {:.1} s",
+        report.achieved_tps, report.target_tps, report.send_secs
+    );
+    println!(
+        "  Submissions  : {} sent, {} accepted, {} rejected ({:.1}% accepted)",
+        report.sent,
+        report.accepted,
+        report.rejected,
+        report.acceptance_rate * 100.0
+    );
+    println!(
+        "  Confirmed    : {} ({} timed out)",
+        report.confirmed, report.timed_out
+    );
+    println!(
+        "  Latency      : p50 {:.0} / p90 {:.0} / p99 {:.0} / max {:.0} ms",
+        l.p50, l.p90, l.p99, l.max
+    );
+    println!(
+        "  Fee          : {} photons at the end, {} bumps",
+        report.final_fee, report.fee_bumps
+    );
+    if !report.errors.is_empty() {
+        println!("  Rejections   :");
+        for (reason, count) in &report.errors {
+            println!("    {:<14} {}", reason, count);
+        }
+    }
+    Ok(())
+}
+
+/// Entry point for `nova-node spam`.
+pub async fn run_spam(args: SpamArgs) -> Result<()> {
+    if args.output == OutputFormat::Text {
+        println!(
+            "Sending {} tx/s for {} s to {} from {} dev accounts...",
+            args.tps, args.duration, args.rpc_url, args.accounts
+        );
+    }
+    let report = run(&args).await?;
+    print_report(&report, args.output)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -- 1. Nonce tracking --------------------------------------------------
+
+    #[test]
+    fn rejected_nonces_are_reused_first() {
+        let mut nonces = NonceTracker::new(4);
+        assert_eq!(nonces.take(), 5);
+        assert_eq!(nonces.take(), 6);
+        assert_eq!(nonces.take(), 7);
+
+        nonces.give_back(6);
+        assert_eq!(nonces.take(), 6);
+        assert_eq!(nonces.take(), 8);
+    }
+
+    #[test]
+    fn resync_skips_committed_nonces() {
+        let mut nonces = NonceTracker::new(0);
+        for _ in 0..3 {
+            nonces.take();
+        }
+        nonces.give_back(2);
+        nonces.give_back(3);
+
+        // Someone else used nonces up to 9.
+        nonces.resync(9);
+        assert!(nonces.gaps.is_empty());
+        assert_eq!(nonces.take(), 10);
+
+        // Resync never moves backwards past in-flight nonces.
+        nonces.resync(2);
+        assert_eq!(nonces.take(), 11);
+    }
+
+    // -- 2. Fee bumping -----------------------------------------------------
+
+    #[test]
+    fn fee_bumps_on_pressure_and_decays() {
+        let mut fees = FeeController::new(100, 150);
+        fees.on_rejected("sender_limit");
+        assert_eq!(fees.current, 100);
+
+        fees.on_rejected("fee_too_low");
+        assert_eq!(fees.current, 126);
+        fees.on_rejected("mempool_full");
+        assert_eq!(fees.current, 150, "capped at max");
+        assert_eq!(fees.bumps, 2);
+
+        for _ in 0..FEE_DECAY_STREAK {
+            fees.on_accepted();
+        }
+        assert_eq!(fees.current, 125);
+    }
+
+    // -- 3. Response parsing ------------------------------------------------
+
+    #[test]
+    fn submission_outcomes() {
+        assert_eq!(
+            parse_submission(r#"{"jsonrpc":"2.0","result":"ab12","id":1}"#),
+            Ok("ab12".to_string())
+        );
+        assert_eq!(
+            parse_submission(
+                r#"{"jsonrpc":"2.0","error":{"code":-32003,"message":"x","data":{"reason":"stale_nonce"}},"id":1}"#
+            ),
+            Err("stale_nonce".to_string())
+        );
+        assert_eq!(
+            parse_submission(r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"x"},"id":1}"#),
+            Err("rpc_-32601".to_string())
+        );
+        assert_eq!(
+            parse_submission("<html>"),
+            Err("malformed_response".to_string())
+        );
+        assert!(nonce_unused("fee_too_low"));
+        assert!(!nonce_unused("stale_nonce"));
+    }
+}
@@ -0,0 +1,112 @@
+//! # Treasury Spend Proposals
+//!
+//! The `treasury sign` and `treasury submit` subcommands, which pass a spend
+//! proposal ([`nova_protocol::network::treasury`]) from validator to
+//! validator until enough stake has signed, then submit it on chain:
+//!
+//! ```text
+//! validator 1  nova-node treasury sign -f spend.json --id audit-2026 \
+//!                  --recipient nova1... --amount 5000000 --memo "..."
+//! validator 2  nova-node treasury sign -f spend.json
+//! ...
+//! validator    nova-node treasury submit -f spend.json
+//! ```
+//!
+//! Unlike a halt, a spend is a transaction: `submit` wraps the proposal in
+//! a `TreasurySpend` signed with the submitting validator's key, and every
+//! node checks the proposal's signatures when the block executes.
+
+use anyhow::{bail, Context, Result};
+
+use nova_protocol::identity::NovaId;
+use nova_protocol::network::treasury::SpendProposal;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+use crate::cli::{self, TreasurySignArgs, TreasurySubmitArgs};
+use crate::halt::{read_record, write_record};
+
+/// Adds the validator's signature to a spend proposal, creating it if
+/// needed.
+pub fn sign(args: TreasurySignArgs) -> Result<()> {
+    let mut proposal = match read_record::<SpendProposal>(&args.file)? {
+        Some(proposal) => {
+            if args.id.as_ref().is_some_and(|id| *id != proposal.id)
+                || args
+                    .recipient
+                    .as_ref()
+                    .is_some_and(|r| *r != proposal.recipient)
+                || args.amount.is_some_and(|a| a != proposal.amount)
+                || args.memo.as_ref().is_some_and(|m| *m != proposal.memo)
+            {
+                bail!(
+                    "{} pays {} to {} ({:?}); refusing to sign different terms",
+                    args.file.display(),
+                    proposal.amount,
+                    proposal.recipient,
+                    proposal.id
+                );
+            }
+            proposal
+        }
+        None => SpendProposal::new(
+            args.id
+                .context("--id is required to start a spend proposal")?,
+            args.recipient
+                .context("--recipient is required to start a spend proposal")?,
+            args.amount
+                .context("--amount is required to start a spend proposal")?,
+            args.memo.unwrap_or_default(),
+        ),
+    };
+
+    let (keypair, _) = crate::read_validator_key(&cli::resolve_data_dir(&args.data_dir))?;
+    proposal.sign(&keypair);
+    write_record(&args.file, &proposal)?;
+    eprintln!(
+        "Signed spend proposal {:?}: {} signature(s)",
+        proposal.id,
+        proposal.signatures.len()
+    );
+    Ok(())
+}
+
+/// Wraps a signed proposal in a `TreasurySpend` transaction and submits it.
+pub async fn submit(args: TreasurySubmitArgs) -> Result<()> {
+    let proposal = read_record::<SpendProposal>(&args.file)?
+        .with_context(|| format!("{} does not exist", args.file.display()))?;
+    let (keypair, _) = crate::read_validator_key(&cli::resolve_data_dir(&args.data_dir))?;
+    let address = NovaId::from_public_key(&keypair.public_key()).to_address();
+    let rpc_url = args.rpc_url.trim_end_matches('/');
+    let nonce = match args.nonce {
+        Some(nonce) => nonce,
+        None => {
+            let caps = crate::capabilities::fetch(rpc_url).await?;
+            crate::capabilities::next_nonce(rpc_url, &caps, &address).await?
+        }
+    };
+
+    let mut tx = TransactionBuilder::new(TransactionType::TreasurySpend)
+        .sender(&address)
+        .receiver(&proposal.recipient)
+        .amount(Amount::new(proposal.amount, Currency::NOVA))
+        .nonce(nonce)
+        .payload(serde_json::to_vec(&proposal)?)
+        .build()
+        .map_err(|e| anyhow::anyhow!("invalid transaction: {}", e))?;
+    sign_transaction(&mut tx, &keypair);
+
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "nova_sendTransaction",
+        "params": [tx],
+        "id": 1,
+    })
+    .to_string();
+    let response = crate::reqwest_post_json_stub(&format!("{}/rpc", rpc_url), &body).await?;
+    let id = crate::spam::parse_submission(&response)
+        .map_err(|reason| anyhow::anyhow!("node rejected the spend: {}", reason))?;
+    println!("Submitted treasury spend {:?} as {}", proposal.id, id);
+    Ok(())
+}
@@ -0,0 +1,487 @@
+//! # Update Checker
+//!
+//! Opt-in (`--update-check-url`) polling of a signed release manifest, so
+//! operators hear about required upgrades before the network leaves them
+//! behind.
+//!
+//! ## Manifest
+//!
+//! The URL serves a [`SignedManifest`]: the manifest as a JSON string, the
+//! Ed25519 public key that signed it and the signature over
+//!
+//! ```text
+//! "nova-release-manifest:" || manifest
+//! ```
+//!
+//! Signing the exact text means release tooling in any language can produce
+//! a manifest without matching serde's field order. The key must be one of
+//! [`RELEASE_KEYS`] or a key passed with `--update-key`; with neither, the
+//! checker refuses to start. A manifest for another network, or one issued
+//! before the last accepted manifest, is ignored, so a mirror cannot roll a
+//! node back to a stale "up to date".
+//!
+//! ## Outcome
+//!
+//! | Status      | When                                                          | Signal                  |
+//! |-------------|---------------------------------------------------------------|-------------------------|
+//! | `required`  | node or protocol version below the manifest's minimum         | `error!` every check    |
+//! | `available` | a newer release exists                                        | `warn!` (critical) or `info!` |
+//! | `up_to_date`| otherwise                                                     | —                       |
+//!
+//! The latest report is included in `GET /status` as `update`, and the
+//! `nova_update_available` / `nova_update_required` gauges mirror it.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use nova_protocol::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use nova_protocol::encoding;
+
+use crate::metrics::NodeMetrics;
+
+/// Ed25519 public keys (hex) that sign official release manifests. The
+/// release process adds keys here; `--update-key` trusts more at runtime.
+pub const RELEASE_KEYS: &[&str] = &[];
+
+/// Domain prefix of the signed manifest bytes.
+pub const MANIFEST_DOMAIN: &[u8] = b"nova-release-manifest:";
+
+// ---------------------------------------------------------------------------
+// Manifest
+// ---------------------------------------------------------------------------
+
+/// What the release process publishes for one network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    /// Network the manifest applies to (`mainnet`, `testnet`, `devnet`).
+    pub network: String,
+    /// Newest `nova-node` release.
+    pub latest_version: String,
+    /// Oldest `nova-node` release that still follows the network's
+    /// activated rules.
+    pub min_node_version: String,
+    /// Protocol version the network has activated.
+    pub min_protocol_version: String,
+    /// Whether `latest_version` fixes something operators must not skip.
+    #[serde(default)]
+    pub critical: bool,
+    /// Unix seconds when the manifest was issued.
+    pub issued_at: u64,
+    /// Release notes or download page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// A manifest as served: signed text plus key and signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedManifest {
+    /// The [`ReleaseManifest`] as JSON text, exactly as signed.
+    pub manifest: String,
+    /// Signer's Ed25519 public key, hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub public_key: String,
+    /// Signature over [`MANIFEST_DOMAIN`] `||` `manifest`, hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub signature: String,
+}
+
+impl SignedManifest {
+    /// Signs `manifest` with `keypair`.
+    pub fn sign(keypair: &NovaKeypair, manifest: &ReleaseManifest) -> Self {
+        let text = serde_json::to_string(manifest).expect("manifest serializes");
+        Self {
+            signature: keypair.sign(&signing_bytes(&text)).to_hex(),
+            public_key: keypair.public_key().to_hex(),
+            manifest: text,
+        }
+    }
+
+    /// Checks the signer is trusted and the signature holds, then parses
+    /// the manifest.
+    pub fn verify(&self, trusted: &[NovaPublicKey]) -> Result<ReleaseManifest> {
+        let public_key = NovaPublicKey::from_hex(encoding::strip_hex_prefix(&self.public_key))
+            .context("malformed manifest public key")?;
+        if !trusted.contains(&public_key) {
+            bail!("manifest signed by untrusted key {}", public_key.to_hex());
+        }
+        let signature = NovaSignature::from_hex(encoding::strip_hex_prefix(&self.signature))
+            .context("malformed manifest signature")?;
+        if !public_key.verify(&signing_bytes(&self.manifest), &signature) {
+            bail!("manifest signature does not verify");
+        }
+        serde_json::from_str(&self.manifest).context("signed manifest is not a release manifest")
+    }
+}
+
+fn signing_bytes(manifest: &str) -> Vec<u8> {
+    [MANIFEST_DOMAIN, manifest.as_bytes()].concat()
+}
+
+// ---------------------------------------------------------------------------
+// Versions
+// ---------------------------------------------------------------------------
+
+/// A `major.minor.patch[-pre]` version. Pre-releases sort before the
+/// release they precede; their labels are not compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: bool,
+}
+
+impl Version {
+    /// Parses `1.2.3`, `v1.2.3` or `1.2.3-rc.1`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim().trim_start_matches('v');
+        let (core, pre) = match s.split_once('-') {
+            Some((core, _)) => (core, true),
+            None => (s, false),
+        };
+        let mut parts = core.split('.').map(str::parse::<u64>);
+        let mut next = || -> Result<u64> {
+            parts
+                .next()
+                .with_context(|| format!("version {:?} needs three parts", s))?
+                .with_context(|| format!("version {:?} is not numeric", s))
+        };
+        let version = Self {
+            major: next()?,
+            minor: next()?,
+            patch: next()?,
+            pre,
+        };
+        if parts.next().is_some() {
+            bail!("version {:?} has more than three parts", s);
+        }
+        Ok(version)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch, !self.pre).cmp(&(
+            other.major,
+            other.minor,
+            other.patch,
+            !other.pre,
+        ))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Evaluation
+// ---------------------------------------------------------------------------
+
+/// How the running node compares to a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UpdateStatus {
+    /// Running the latest release.
+    UpToDate,
+    /// A newer release exists; the node still follows the network.
+    Available { latest: String, critical: bool },
+    /// The node or its protocol version is below the network's minimum.
+    Required {
+        latest: String,
+        min_node_version: String,
+        min_protocol_version: String,
+    },
+}
+
+/// Compares the running versions against `manifest`.
+pub fn evaluate(manifest: &ReleaseManifest, node: &str, protocol: &str) -> Result<UpdateStatus> {
+    let node = Version::parse(node)?;
+    let protocol = Version::parse(protocol)?;
+    if node < Version::parse(&manifest.min_node_version)?
+        || protocol < Version::parse(&manifest.min_protocol_version)?
+    {
+        return Ok(UpdateStatus::Required {
+            latest: manifest.latest_version.clone(),
+            min_node_version: manifest.min_node_version.clone(),
+            min_protocol_version: manifest.min_protocol_version.clone(),
+        });
+    }
+    if node < Version::parse(&manifest.latest_version)? {
+        return Ok(UpdateStatus::Available {
+            latest: manifest.latest_version.clone(),
+            critical: manifest.critical,
+        });
+    }
+    Ok(UpdateStatus::UpToDate)
+}
+
+/// The latest check, as served in `GET /status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateReport {
+    /// Outcome, flattened into `status` plus its fields.
+    #[serde(flatten)]
+    pub status: UpdateStatus,
+    /// `nova-node` version running.
+    pub running: String,
+    /// `issued_at` of the manifest the outcome is based on.
+    pub manifest_issued_at: u64,
+    /// Release notes link from the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Unix milliseconds of the check.
+    pub checked_at: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Checker
+// ---------------------------------------------------------------------------
+
+/// Polls the manifest URL and keeps the latest [`UpdateReport`].
+pub struct UpdateChecker {
+    url: String,
+    network: String,
+    trusted: Vec<NovaPublicKey>,
+    report: RwLock<Option<UpdateReport>>,
+}
+
+impl UpdateChecker {
+    /// Trusts [`RELEASE_KEYS`] plus `extra_keys` (hex). Fails if that
+    /// leaves no key, or a key does not parse.
+    pub fn new(url: &str, network: &str, extra_keys: &[String]) -> Result<Self> {
+        let trusted = RELEASE_KEYS
+            .iter()
+            .copied()
+            .chain(extra_keys.iter().map(String::as_str))
+            .map(|k| {
+                NovaPublicKey::from_hex(encoding::strip_hex_prefix(k))
+                    .with_context(|| format!("invalid release key {}", k))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if trusted.is_empty() {
+            bail!("no release keys are trusted; pass --update-key");
+        }
+        Ok(Self {
+            url: url.to_string(),
+            network: network.to_string(),
+            trusted,
+            report: RwLock::new(None),
+        })
+    }
+
+    /// The latest report, if a check has succeeded.
+    pub fn report(&self) -> Option<UpdateReport> {
+        self.report.read().clone()
+    }
+
+    /// Verifies and evaluates a fetched manifest body and stores the result.
+    pub fn accept(&self, body: &str, now_ms: u64) -> Result<UpdateReport> {
+        let signed: SignedManifest =
+            serde_json::from_str(body).context("update URL did not return a signed manifest")?;
+        let manifest = signed.verify(&self.trusted)?;
+        if manifest.network != self.network {
+            bail!(
+                "manifest is for {}, this node runs {}",
+                manifest.network,
+                self.network
+            );
+        }
+        if let Some(last) = self.report.read().as_ref() {
+            if manifest.issued_at < last.manifest_issued_at {
+                bail!(
+                    "manifest issued at {} predates the one already accepted ({})",
+                    manifest.issued_at,
+                    last.manifest_issued_at
+                );
+            }
+        }
+
+        let running = env!("CARGO_PKG_VERSION");
+        let status = evaluate(&manifest, running, nova_protocol::config::PROTOCOL_VERSION)?;
+        let report = UpdateReport {
+            status,
+            running: running.to_string(),
+            manifest_issued_at: manifest.issued_at,
+            url: manifest.url,
+            checked_at: now_ms,
+        };
+        *self.report.write() = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Checks every `interval`, logging and exporting each outcome.
+    pub async fn run(self: Arc<Self>, metrics: Arc<NodeMetrics>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let result = match crate::reqwest_get_stub(&self.url).await {
+                Ok(body) => self.accept(&body, crate::clock::now_ms()),
+                Err(e) => Err(e),
+            };
+            let report = match result {
+                Ok(report) => report,
+                Err(e) => {
+                    tracing::warn!(url = %self.url, error = %e, "update check failed");
+                    continue;
+                }
+            };
+
+            metrics.update_required.set(i64::from(matches!(
+                report.status,
+                UpdateStatus::Required { .. }
+            )));
+            metrics
+                .update_available
+                .set(i64::from(!matches!(report.status, UpdateStatus::UpToDate)));
+            match &report.status {
+                UpdateStatus::Required {
+                    latest,
+                    min_node_version,
+                    min_protocol_version,
+                } => tracing::error!(
+                    running = %report.running,
+                    %latest,
+                    %min_node_version,
+                    %min_protocol_version,
+                    "UPGRADE REQUIRED: this node is below the network's activated protocol requirements"
+                ),
+                UpdateStatus::Available {
+                    latest,
+                    critical: true,
+                } => tracing::warn!(
+                    running = %report.running,
+                    %latest,
+                    "critical nova-node release available"
+                ),
+                UpdateStatus::Available { latest, .. } => tracing::info!(
+                    running = %report.running,
+                    %latest,
+                    "nova-node release available"
+                ),
+                UpdateStatus::UpToDate => {
+                    tracing::debug!(running = %report.running, "nova-node is up to date")
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release_key() -> NovaKeypair {
+        NovaKeypair::from_seed(&[42u8; 32])
+    }
+
+    fn manifest(latest: &str, min_node: &str, min_protocol: &str) -> ReleaseManifest {
+        ReleaseManifest {
+            network: "testnet".into(),
+            latest_version: latest.into(),
+            min_node_version: min_node.into(),
+            min_protocol_version: min_protocol.into(),
+            critical: false,
+            issued_at: 1_790_000_000,
+            url: None,
+        }
+    }
+
+    fn checker() -> UpdateChecker {
+        UpdateChecker::new(
+            "http://updates.invalid/testnet.json",
+            "testnet",
+            &[release_key().public_key().to_hex()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn versions_order_numerically_with_prereleases_first() {
+        let v = |s| Version::parse(s).unwrap();
+        assert!(v("0.10.0") > v("0.9.9"));
+        assert_eq!(v("v1.2.3"), v("1.2.3"));
+        assert!(v("1.0.0-rc.1") < v("1.0.0"));
+        assert!(v("1.0.0-rc.1") > v("0.9.0"));
+        assert!(Version::parse("1.2").is_err());
+        assert!(Version::parse("1.2.3.4").is_err());
+        assert!(Version::parse("one.two.three").is_err());
+    }
+
+    #[test]
+    fn evaluate_distinguishes_required_from_available() {
+        assert_eq!(
+            evaluate(&manifest("0.1.0", "0.1.0", "0.1.0"), "0.1.0", "0.1.0").unwrap(),
+            UpdateStatus::UpToDate
+        );
+        assert!(matches!(
+            evaluate(&manifest("0.2.0", "0.1.0", "0.1.0"), "0.1.0", "0.1.0").unwrap(),
+            UpdateStatus::Available { .. }
+        ));
+        assert!(matches!(
+            evaluate(&manifest("0.2.0", "0.1.0", "0.2.0"), "0.1.0", "0.1.0").unwrap(),
+            UpdateStatus::Required { .. }
+        ));
+        assert!(matches!(
+            evaluate(&manifest("0.3.0", "0.2.0", "0.1.0"), "0.1.0", "0.1.0").unwrap(),
+            UpdateStatus::Required { .. }
+        ));
+    }
+
+    #[test]
+    fn only_trusted_signed_fresh_manifests_are_accepted() {
+        let checker = checker();
+        let running = env!("CARGO_PKG_VERSION");
+
+        let signed = SignedManifest::sign(&release_key(), &manifest("99.0.0", running, "0.0.1"));
+        let report = checker
+            .accept(&serde_json::to_string(&signed).unwrap(), 1)
+            .unwrap();
+        assert!(matches!(report.status, UpdateStatus::Available { .. }));
+        assert_eq!(checker.report(), Some(report));
+
+        let forged = SignedManifest::sign(
+            &NovaKeypair::from_seed(&[7u8; 32]),
+            &manifest("99.0.0", running, "0.0.1"),
+        );
+        let err = checker.accept(&serde_json::to_string(&forged).unwrap(), 2);
+        assert!(err.unwrap_err().to_string().contains("untrusted"));
+
+        let mut tampered = signed.clone();
+        tampered.manifest = tampered.manifest.replace("99.0.0", "0.0.1");
+        assert!(checker
+            .accept(&serde_json::to_string(&tampered).unwrap(), 2)
+            .is_err());
+
+        let mut other_network = manifest("99.0.0", running, "0.0.1");
+        other_network.network = "mainnet".into();
+        let signed = SignedManifest::sign(&release_key(), &other_network);
+        assert!(checker
+            .accept(&serde_json::to_string(&signed).unwrap(), 2)
+            .is_err());
+
+        let mut stale = manifest(running, running, "0.0.1");
+        stale.issued_at -= 1;
+        let signed = SignedManifest::sign(&release_key(), &stale);
+        assert!(checker
+            .accept(&serde_json::to_string(&signed).unwrap(), 2)
+            .is_err());
+        assert_eq!(checker.report().unwrap().checked_at, 1);
+    }
+
+    #[test]
+    fn checker_needs_a_trusted_key() {
+        assert!(UpdateChecker::new("http://x", "testnet", &[]).is_err());
+        assert!(UpdateChecker::new("http://x", "testnet", &["zz".into()]).is_err());
+    }
+}
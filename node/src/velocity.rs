@@ -0,0 +1,522 @@
+//! # Spending Velocity Alerts
+//!
+//! Treasury teams watch a handful of hot addresses and want to hear about
+//! unusual outflows. With `--watch-address`, the node follows committed
+//! blocks and checks every transfer *out of* a watched address against
+//! three rules:
+//!
+//! | Kind               | Fires when                                                    |
+//! |--------------------|---------------------------------------------------------------|
+//! | `velocity`         | Outflow within the sliding window crosses `--velocity-max-outflow` |
+//! | `new_counterparty` | The receiver was never paid by this address before            |
+//! | `unusual_hour`     | The block time (UTC) falls in `--velocity-quiet-hours`        |
+//!
+//! Each hit is broadcast as an `anomaly_detected` WebSocket event and, with
+//! `--anomaly-webhook`, POSTed as the same JSON to an external endpoint.
+//!
+//! The monitor is purely observational: it reads finalized blocks and never
+//! touches admission or consensus. Block timestamps, not the local clock,
+//! drive the window and the hour check, so replays give the same alerts.
+//!
+//! ## Warm-Up
+//!
+//! On start the monitor silently replays the last [`BACKFILL_BLOCKS`]
+//! blocks, so counterparties and window totals from before a restart do not
+//! raise fresh alerts.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{TimeZone, Timelike};
+use tokio::sync::broadcast;
+
+use nova_protocol::encoding::Versioned;
+use nova_protocol::storage::block::Block;
+use nova_protocol::storage::db::NovaDB;
+use nova_protocol::transaction::Transaction;
+
+use crate::api::NodeEvent;
+use crate::cli::RunArgs;
+
+/// Blocks replayed without alerting when the monitor starts.
+pub const BACKFILL_BLOCKS: u64 = 10_000;
+
+/// How often the monitor looks for new blocks.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Counterparties remembered per watched address; half of them are
+/// forgotten when the set fills up.
+const MAX_COUNTERPARTIES: usize = 10_000;
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// A UTC hour range, `start` inclusive to `end` exclusive. Wraps past
+/// midnight when `start > end` (`22-6` is 22:00–05:59).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    /// First quiet hour (0–23).
+    pub start: u32,
+    /// First hour after the quiet period (0–23).
+    pub end: u32,
+}
+
+impl QuietHours {
+    /// Returns `true` if `hour` (0–23) is inside the range.
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&hour)
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("expected START-END hours, got {:?}", s))?;
+        let hour = |h: &str| match h.trim().parse::<u32>() {
+            Ok(h) if h < 24 => Ok(h),
+            _ => Err(format!("invalid hour {:?} (expected 0-23)", h)),
+        };
+        let hours = QuietHours {
+            start: hour(start)?,
+            end: hour(end)?,
+        };
+        if hours.start == hours.end {
+            return Err("quiet hours must not be empty".into());
+        }
+        Ok(hours)
+    }
+}
+
+/// What counts as unusual for a watched address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VelocityConfig {
+    /// Addresses whose outflows are watched.
+    pub watched: HashSet<String>,
+    /// Most photons that may leave an address within `window_ms`.
+    pub max_outflow: Option<u64>,
+    /// Sliding window for `max_outflow`, in milliseconds.
+    pub window_ms: u64,
+    /// Alert on the first payment to a receiver.
+    pub new_counterparty: bool,
+    /// Alert on outflows at these hours.
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl VelocityConfig {
+    /// The monitor the `run` flags ask for, or `None` if no address is
+    /// watched.
+    pub fn from_args(args: &RunArgs) -> Option<Self> {
+        if args.watch_addresses.is_empty() {
+            return None;
+        }
+        Some(Self {
+            watched: args.watch_addresses.iter().cloned().collect(),
+            max_outflow: args.velocity_max_outflow,
+            window_ms: args.velocity_window_secs.saturating_mul(1_000),
+            new_counterparty: args.velocity_new_counterparty,
+            quiet_hours: args.velocity_quiet_hours,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Monitor
+// ---------------------------------------------------------------------------
+
+/// Which rule an outflow tripped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// Too much left the address within the window.
+    Velocity,
+    /// First payment to this receiver.
+    NewCounterparty,
+    /// Outflow during quiet hours.
+    UnusualHour,
+}
+
+impl AnomalyKind {
+    /// Stable name used in events.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Velocity => "velocity",
+            Self::NewCounterparty => "new_counterparty",
+            Self::UnusualHour => "unusual_hour",
+        }
+    }
+}
+
+/// One unusual outflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anomaly {
+    /// The watched address the funds left.
+    pub address: String,
+    /// The rule that fired.
+    pub kind: AnomalyKind,
+    /// The transaction that tripped it.
+    pub tx_id: String,
+    /// Receiver of that transaction.
+    pub counterparty: String,
+    /// Its amount, in photons.
+    pub amount: u64,
+    /// Height of the block that included it.
+    pub block_height: u64,
+    /// That block's timestamp (Unix milliseconds).
+    pub timestamp: u64,
+    /// Human-readable explanation.
+    pub detail: String,
+}
+
+impl From<&Anomaly> for NodeEvent {
+    fn from(a: &Anomaly) -> Self {
+        NodeEvent::AnomalyDetected {
+            address: a.address.clone(),
+            kind: a.kind.as_str().into(),
+            hash: a.tx_id.clone(),
+            counterparty: a.counterparty.clone(),
+            amount: a.amount,
+            block_height: a.block_height,
+            timestamp: a.timestamp,
+            detail: a.detail.clone(),
+        }
+    }
+}
+
+/// Per-address bookkeeping.
+#[derive(Debug, Default)]
+struct Account {
+    /// `(timestamp, amount)` of outflows inside the window, oldest first.
+    window: VecDeque<(u64, u64)>,
+    window_total: u64,
+    /// Set once the window total crossed the limit; cleared when it falls
+    /// back under, so one burst raises one alert.
+    over_limit: bool,
+    counterparties: HashSet<String>,
+}
+
+/// Tracks outflows of watched addresses and reports anomalies.
+#[derive(Debug)]
+pub struct VelocityMonitor {
+    config: VelocityConfig,
+    accounts: HashMap<String, Account>,
+}
+
+impl VelocityMonitor {
+    /// A monitor with no history.
+    pub fn new(config: VelocityConfig) -> Self {
+        Self {
+            config,
+            accounts: HashMap::new(),
+        }
+    }
+
+    /// Feeds every transaction in `block` and returns the anomalies found,
+    /// in transaction order.
+    pub fn observe_block(&mut self, block: &Block) -> Vec<Anomaly> {
+        block
+            .transactions
+            .iter()
+            .flat_map(|tx| self.observe(tx, block.header.height, block.header.timestamp))
+            .collect()
+    }
+
+    /// Feeds one committed transaction.
+    pub fn observe(&mut self, tx: &Transaction, block_height: u64, timestamp: u64) -> Vec<Anomaly> {
+        if !self.config.watched.contains(&tx.sender) || tx.amount.value == 0 {
+            return Vec::new();
+        }
+        let config = &self.config;
+        let account = self.accounts.entry(tx.sender.clone()).or_default();
+        let anomaly = |kind, detail: String| Anomaly {
+            address: tx.sender.clone(),
+            kind,
+            tx_id: tx.id.clone(),
+            counterparty: tx.receiver.clone(),
+            amount: tx.amount.value,
+            block_height,
+            timestamp,
+            detail,
+        };
+        let mut found = Vec::new();
+
+        if let Some(limit) = config.max_outflow {
+            let cutoff = timestamp.saturating_sub(config.window_ms);
+            while let Some(&(at, amount)) = account.window.front() {
+                if at > cutoff {
+                    break;
+                }
+                account.window.pop_front();
+                account.window_total = account.window_total.saturating_sub(amount);
+            }
+            account.window.push_back((timestamp, tx.amount.value));
+            account.window_total = account.window_total.saturating_add(tx.amount.value);
+            if account.window_total > limit {
+                if !account.over_limit {
+                    account.over_limit = true;
+                    found.push(anomaly(
+                        AnomalyKind::Velocity,
+                        format!(
+                            "{} photons out within {}s (limit {})",
+                            account.window_total,
+                            config.window_ms / 1_000,
+                            limit
+                        ),
+                    ));
+                }
+            } else {
+                account.over_limit = false;
+            }
+        }
+
+        if config.new_counterparty && !account.counterparties.contains(&tx.receiver) {
+            found.push(anomaly(
+                AnomalyKind::NewCounterparty,
+                format!("first payment to {}", tx.receiver),
+            ));
+        }
+        if account.counterparties.len() >= MAX_COUNTERPARTIES {
+            let keep: HashSet<_> = account
+                .counterparties
+                .drain()
+                .take(MAX_COUNTERPARTIES / 2)
+                .collect();
+            account.counterparties = keep;
+        }
+        account.counterparties.insert(tx.receiver.clone());
+
+        if let Some(quiet) = config.quiet_hours {
+            let hour = utc_hour(timestamp);
+            if quiet.contains(hour) {
+                found.push(anomaly(
+                    AnomalyKind::UnusualHour,
+                    format!("outflow at {:02}:xx UTC", hour),
+                ));
+            }
+        }
+        found
+    }
+}
+
+/// UTC hour of a Unix-millisecond timestamp.
+fn utc_hour(timestamp_ms: u64) -> u32 {
+    chrono::Utc
+        .timestamp_millis_opt(timestamp_ms as i64)
+        .single()
+        .map_or(0, |t| t.hour())
+}
+
+// ---------------------------------------------------------------------------
+// Background task
+// ---------------------------------------------------------------------------
+
+/// Follows committed blocks and publishes anomalies for the lifetime of
+/// the node.
+pub async fn run_velocity_monitor(
+    db: Arc<NovaDB>,
+    mut monitor: VelocityMonitor,
+    event_tx: broadcast::Sender<NodeEvent>,
+    webhook: Option<String>,
+) {
+    let tip = db.get_latest_block_height().ok().flatten().unwrap_or(0);
+    for height in tip.saturating_sub(BACKFILL_BLOCKS)..=tip {
+        if let Ok(Some(block)) = db.get_block(height) {
+            monitor.observe_block(&block);
+        }
+    }
+    tracing::info!(
+        watched = monitor.config.watched.len(),
+        from_height = tip,
+        "velocity monitor started"
+    );
+
+    let mut scanned = tip;
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let tip = match db.get_latest_block_height() {
+            Ok(Some(tip)) => tip,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!(error = %e, "velocity monitor cannot read chain tip");
+                continue;
+            }
+        };
+        // A revert or reorg moved the tip back: resume from there.
+        scanned = scanned.min(tip);
+        while scanned < tip {
+            let height = scanned + 1;
+            let block = match db.get_block(height) {
+                Ok(Some(block)) => block,
+                _ => break,
+            };
+            scanned = height;
+            for anomaly in monitor.observe_block(&block) {
+                tracing::warn!(
+                    address = %anomaly.address,
+                    kind = anomaly.kind.as_str(),
+                    tx = %anomaly.tx_id,
+                    "{}",
+                    anomaly.detail
+                );
+                let event = NodeEvent::from(&anomaly);
+                if let Some(url) = &webhook {
+                    tokio::spawn(post_webhook(url.clone(), event.clone()));
+                }
+                // No subscribers is fine.
+                let _ = event_tx.send(event);
+            }
+        }
+    }
+}
+
+/// POSTs one event to the operator's webhook. Failures are logged only.
+async fn post_webhook(url: String, event: NodeEvent) {
+    let body = match serde_json::to_string(&Versioned::new(&event)) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to serialize anomaly event");
+            return;
+        }
+    };
+    if let Err(e) = crate::http_request_stub("POST", &url, Some(&body)).await {
+        tracing::warn!(url = %url, error = %e, "anomaly webhook failed");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_protocol::transaction::builder::TransactionBuilder;
+    use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+    /// 2026-01-01T12:00:00Z.
+    const NOON: u64 = 1_767_268_800_000;
+    const HOUR: u64 = 3_600_000;
+
+    fn config() -> VelocityConfig {
+        VelocityConfig {
+            watched: ["nova1treasury".to_string()].into_iter().collect(),
+            max_outflow: None,
+            window_ms: HOUR,
+            new_counterparty: false,
+            quiet_hours: None,
+        }
+    }
+
+    fn outflow(sender: &str, receiver: &str, amount: u64, nonce: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender(sender)
+            .receiver(receiver)
+            .amount(Amount::new(amount, Currency::NOVA))
+            .fee(10)
+            .nonce(nonce)
+            .timestamp(NOON)
+            .build_unchecked()
+    }
+
+    fn kinds(anomalies: &[Anomaly]) -> Vec<AnomalyKind> {
+        anomalies.iter().map(|a| a.kind).collect()
+    }
+
+    // -- 1. Window totals alert once per burst -------------------------------
+
+    #[test]
+    fn velocity_alerts_once_per_excursion() {
+        let mut monitor = VelocityMonitor::new(VelocityConfig {
+            max_outflow: Some(1_000),
+            ..config()
+        });
+        let mut send = |amount, nonce, at| {
+            kinds(&monitor.observe(&outflow("nova1treasury", "nova1bob", amount, nonce), 1, at))
+        };
+
+        assert!(send(600, 1, NOON).is_empty());
+        assert_eq!(send(600, 2, NOON + 1_000), [AnomalyKind::Velocity]);
+        // Still over the limit: no repeat.
+        assert!(send(100, 3, NOON + 2_000).is_empty());
+        // An hour later the window has drained and the monitor re-arms.
+        assert!(send(100, 4, NOON + HOUR + 2_000).is_empty());
+        assert_eq!(send(1_000, 5, NOON + HOUR + 3_000), [AnomalyKind::Velocity]);
+    }
+
+    // -- 2. Counterparties and quiet hours -----------------------------------
+
+    #[test]
+    fn new_counterparty_and_quiet_hours() {
+        let mut monitor = VelocityMonitor::new(VelocityConfig {
+            new_counterparty: true,
+            quiet_hours: Some("22-6".parse().unwrap()),
+            ..config()
+        });
+
+        let first = monitor.observe(&outflow("nova1treasury", "nova1bob", 5, 1), 3, NOON);
+        assert_eq!(kinds(&first), [AnomalyKind::NewCounterparty]);
+        assert_eq!(first[0].counterparty, "nova1bob");
+        assert_eq!(first[0].block_height, 3);
+        assert!(monitor
+            .observe(&outflow("nova1treasury", "nova1bob", 5, 2), 4, NOON)
+            .is_empty());
+
+        // 23:00 UTC.
+        let late = monitor.observe(
+            &outflow("nova1treasury", "nova1bob", 5, 3),
+            5,
+            NOON + 11 * HOUR,
+        );
+        assert_eq!(kinds(&late), [AnomalyKind::UnusualHour]);
+
+        // Unwatched senders are ignored entirely.
+        assert!(monitor
+            .observe(
+                &outflow("nova1alice", "nova1carol", 5, 1),
+                6,
+                NOON + 11 * HOUR
+            )
+            .is_empty());
+    }
+
+    // -- 3. Quiet hour parsing -----------------------------------------------
+
+    #[test]
+    fn quiet_hours_parse_and_wrap() {
+        let night: QuietHours = "22-6".parse().unwrap();
+        assert!(night.contains(23) && night.contains(0) && night.contains(5));
+        assert!(!night.contains(6) && !night.contains(12));
+        let lunch: QuietHours = "12-13".parse().unwrap();
+        assert!(lunch.contains(12) && !lunch.contains(13));
+        assert!("24-1".parse::<QuietHours>().is_err());
+        assert!("5-5".parse::<QuietHours>().is_err());
+        assert!("night".parse::<QuietHours>().is_err());
+    }
+
+    // -- 4. Events carry the anomaly ----------------------------------------
+
+    #[test]
+    fn anomaly_event_serializes() {
+        let mut monitor = VelocityMonitor::new(VelocityConfig {
+            new_counterparty: true,
+            ..config()
+        });
+        let tx = outflow("nova1treasury", "nova1bob", 5, 1);
+        let anomaly = &monitor.observe(&tx, 9, NOON)[0];
+        let json = serde_json::to_value(Versioned::new(NodeEvent::from(anomaly))).unwrap();
+        assert_eq!(json["type"], "anomaly_detected");
+        assert_eq!(json["kind"], "new_counterparty");
+        assert_eq!(json["address"], "nova1treasury");
+        assert_eq!(json["hash"], format!("0x{}", tx.id));
+        assert_eq!(json["block_height"], 9);
+    }
+}
@@ -94,7 +94,7 @@ fn bench_finalize_block(c: &mut Criterion) {
                         let votes: Vec<Vote> = keypairs
                             .iter()
                             .take(quorum)
-                            .map(|kp| Vote::new(kp, block_hash, 0))
+                            .map(|kp| Vote::precommit(kp, block_hash, 0))
                             .collect();
 
                         (engine, block, votes)
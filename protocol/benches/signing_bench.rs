@@ -49,7 +49,7 @@ fn bench_sign_transaction(c: &mut Criterion) {
                 .fee(100)
                 .nonce(42)
                 .timestamp(1_700_000_000_000)
-                .build();
+                .build_unchecked();
             sign_transaction(&mut tx, &keypair);
         });
     });
@@ -102,9 +102,7 @@ fn address_display(name: &str, addr: &str, color: &str) {
 }
 
 fn balance_row(name: &str, balance: u64, color: &str) {
-    println!(
-        "  {color}{BOLD}{name:<12}{RESET}  {WHITE}{balance:>12}{RESET} {DIM}photons{RESET}"
-    );
+    println!("  {color}{BOLD}{name:<12}{RESET}  {WHITE}{balance:>12}{RESET} {DIM}photons{RESET}");
 }
 
 fn separator() {
@@ -138,7 +136,8 @@ fn build_signed_transfer(
         .amount(Amount::new(amount, Currency::NOVA))
         .fee(fee)
         .nonce(nonce)
-        .build();
+        .build()
+        .expect("demo transfers are well-formed");
     sign_transaction(&mut tx, sender_kp);
     tx
 }
@@ -207,11 +206,8 @@ fn main() {
     // Store genesis block.
     let genesis = Block::genesis();
     db.put_block(&genesis).unwrap();
-    info("Genesis block hash", &hex::encode(genesis.header.hash));
-    info(
-        "Genesis state root",
-        &hex::encode(genesis.header.state_root),
-    );
+    info("Genesis block hash", &genesis.header.hash.to_hex());
+    info("Genesis state root", &genesis.header.state_root.to_hex());
     success("Genesis block committed to database");
 
     // -----------------------------------------------------------------------
@@ -231,9 +227,7 @@ fn main() {
     );
 
     println!();
-    println!(
-        "  {BOLD}{WHITE}--- Initial Balances ---{RESET}"
-    );
+    println!("  {BOLD}{WHITE}--- Initial Balances ---{RESET}");
     balance_row("Alice", initial_balance, BLUE);
     balance_row("Bob", 0, GREEN);
     balance_row("Merchant", 0, MAGENTA);
@@ -285,14 +279,11 @@ fn main() {
     producer.commit_block(&produced1.block).unwrap();
 
     info("Block height", &produced1.block.header.height.to_string());
-    info(
-        "Block hash",
-        &hex::encode(produced1.block.header.hash)[..16],
-    );
+    info("Block hash", &produced1.block.header.hash.to_hex()[..16]);
     info("Transactions in block", "1");
     info(
         "State root",
-        &hex::encode(produced1.block.header.state_root)[..16],
+        &produced1.block.header.state_root.to_hex()[..16],
     );
 
     separator();
@@ -304,9 +295,7 @@ fn main() {
         let bob_state = tree.get(&bob_addr).unwrap();
 
         println!();
-        println!(
-            "  {BOLD}{WHITE}--- Balances After Block #1 ---{RESET}"
-        );
+        println!("  {BOLD}{WHITE}--- Balances After Block #1 ---{RESET}");
         balance_row("Alice", alice_state.balance, BLUE);
         balance_row("Bob", bob_state.balance, GREEN);
         balance_row("Merchant", 0, MAGENTA);
@@ -351,10 +340,7 @@ fn main() {
     producer.commit_block(&produced2.block).unwrap();
 
     info("Block height", &produced2.block.header.height.to_string());
-    info(
-        "Block hash",
-        &hex::encode(produced2.block.header.hash)[..16],
-    );
+    info("Block hash", &produced2.block.header.hash.to_hex()[..16]);
     info("Transactions in block", "1");
 
     separator();
@@ -367,9 +353,7 @@ fn main() {
         let merchant_state = tree.get(&merchant_addr).unwrap();
 
         println!();
-        println!(
-            "  {BOLD}{WHITE}--- Balances After Block #2 ---{RESET}"
-        );
+        println!("  {BOLD}{WHITE}--- Balances After Block #2 ---{RESET}");
         balance_row("Alice", alice_state.balance, BLUE);
         balance_row("Bob", bob_state.balance, GREEN);
         balance_row("Merchant", merchant_state.balance, MAGENTA);
@@ -422,7 +406,8 @@ fn main() {
 
     for i in 1..chain.len() {
         assert_eq!(
-            chain[i].header.parent_hash, chain[i - 1].header.hash,
+            chain[i].header.parent_hash,
+            chain[i - 1].header.hash,
             "block {} parent hash mismatch",
             i
         );
@@ -559,7 +544,10 @@ fn run_confidential_transfer_demo(sender_addr: &str, receiver_addr: &str) {
 
     info("Transaction type", "ConfidentialTransfer");
     info("Proof size", &format!("{} bytes", proof_bytes.len()));
-    info("Commitment size", &format!("{} bytes", commitment_bytes.len()));
+    info(
+        "Commitment size",
+        &format!("{} bytes", commitment_bytes.len()),
+    );
     info("Transaction ID", &tx.id[..16]);
 
     subsection("Verifying zero-knowledge proof (pairing check)...");
@@ -583,7 +571,10 @@ fn run_confidential_transfer_demo(sender_addr: &str, receiver_addr: &str) {
         "Proof verification",
         &format!("{:.2} ms", verify_time.as_secs_f64() * 1000.0),
     );
-    info("Proof size", &format!("{} bytes (compressed)", proof_bytes.len()));
+    info(
+        "Proof size",
+        &format!("{} bytes (compressed)", proof_bytes.len()),
+    );
     info(
         "Commitment size",
         &format!("{} bytes (compressed)", commitment_bytes.len()),
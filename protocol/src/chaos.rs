@@ -0,0 +1,540 @@
+//! # Chaos — Fault Injection
+//!
+//! Hooks for testing how the node behaves when its disk and its peers
+//! misbehave. A [`FaultInjector`] decides, operation by operation and from
+//! a seeded RNG, whether to stall, to fail, or to stop partway. It plugs
+//! into three places:
+//!
+//! ```text
+//! storage — NovaDB::with_faults   — failed reads/writes, torn writes, latency
+//! gossip  — FaultyLink::transmit  — dropped frames, truncated frames, latency
+//! sync    — FaultyPeer::request   — timeouts, short responses, latency
+//! ```
+//!
+//! Failed storage operations return
+//! [`DbError::Injected`](crate::storage::db::DbError::Injected). A torn
+//! block write aborts the write's transaction after the block was staged;
+//! a torn receipt write stores the first half of the receipts. Either way
+//! the caller sees an error, exactly as it would if the disk failed
+//! mid-write.
+//!
+//! Only built with the `chaos` feature, which nothing enables by default:
+//! a release build never consults an injector. Latency is a blocking
+//! sleep on the calling thread.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::network::sync::{SyncEngine, SyncError, SyncRequest, SyncResponse};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// How often, and how badly, operations fail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaultConfig {
+    /// Chance, in `[0, 1]`, that an operation fails outright.
+    pub error_rate: f64,
+
+    /// Chance, in `[0, 1]`, that an operation which did not fail stops
+    /// partway: a torn write, a truncated frame, a short response.
+    pub partial_rate: f64,
+
+    /// Upper bound of the delay added before each operation. Delays are
+    /// drawn uniformly from `[0, latency]`.
+    pub latency: Duration,
+
+    /// RNG seed. The same seed and the same sequence of operations inject
+    /// the same faults.
+    pub seed: u64,
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        Self {
+            error_rate: 0.0,
+            partial_rate: 0.0,
+            latency: Duration::ZERO,
+            seed: 0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FaultInjector
+// ---------------------------------------------------------------------------
+
+/// A fault to inject into the current operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Fail without doing anything.
+    Error,
+
+    /// Do part of the work, then fail.
+    Partial,
+}
+
+/// Operations an injector was consulted for, and the faults it injected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FaultStats {
+    /// Times [`FaultInjector::next_fault`] was consulted while enabled.
+    pub operations: u64,
+    /// Operations failed outright.
+    pub errors: u64,
+    /// Operations stopped partway.
+    pub partials: u64,
+}
+
+/// Decides which operations fail. Shared (via `Arc`) by every hook that
+/// should fail together, such as one node's database and its links.
+#[derive(Debug)]
+pub struct FaultInjector {
+    config: FaultConfig,
+    rng: Mutex<StdRng>,
+    enabled: AtomicBool,
+    stats: Mutex<FaultStats>,
+}
+
+impl FaultInjector {
+    /// Creates an injector, enabled.
+    ///
+    /// # Panics
+    ///
+    /// If either rate is outside `[0, 1]`.
+    pub fn new(config: FaultConfig) -> Self {
+        for (name, rate) in [
+            ("error_rate", config.error_rate),
+            ("partial_rate", config.partial_rate),
+        ] {
+            assert!(
+                (0.0..=1.0).contains(&rate),
+                "{name} must be within [0, 1], got {rate}"
+            );
+        }
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(config.seed)),
+            config,
+            enabled: AtomicBool::new(true),
+            stats: Mutex::new(FaultStats::default()),
+        }
+    }
+
+    /// The configuration faults are drawn from.
+    pub fn config(&self) -> &FaultConfig {
+        &self.config
+    }
+
+    /// Stops injecting faults, as if the disk or the network recovered.
+    pub fn heal(&self) {
+        self.enabled.store(false, Ordering::SeqCst);
+    }
+
+    /// Starts injecting faults again after [`heal`](Self::heal).
+    pub fn resume(&self) {
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` unless healed.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// What the injector has done so far.
+    pub fn stats(&self) -> FaultStats {
+        *self.stats.lock()
+    }
+
+    /// Consulted before each operation: sleeps for the drawn latency, then
+    /// returns the fault to inject, if any. Always `None` while healed.
+    pub fn next_fault(&self) -> Option<Fault> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let (delay, fault) = {
+            let mut rng = self.rng.lock();
+            let delay = self.config.latency.mul_f64(rng.gen::<f64>());
+            let fault = if rng.gen_bool(self.config.error_rate) {
+                Some(Fault::Error)
+            } else if rng.gen_bool(self.config.partial_rate) {
+                Some(Fault::Partial)
+            } else {
+                None
+            };
+            (delay, fault)
+        };
+
+        {
+            let mut stats = self.stats.lock();
+            stats.operations += 1;
+            match fault {
+                Some(Fault::Error) => stats.errors += 1,
+                Some(Fault::Partial) => stats.partials += 1,
+                None => {}
+            }
+        }
+
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        fault
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Gossip
+// ---------------------------------------------------------------------------
+
+/// A gossip link that drops and truncates frames.
+///
+/// Carries frames as [`encode_message`](crate::network::gossip::encode_message)
+/// produces them. A truncated frame is the first half of the original, so
+/// it always fails [`decode_message`](crate::network::gossip::decode_message).
+#[derive(Debug, Clone)]
+pub struct FaultyLink {
+    faults: Arc<FaultInjector>,
+}
+
+impl FaultyLink {
+    /// A link that fails as `faults` decides.
+    pub fn new(faults: Arc<FaultInjector>) -> Self {
+        Self { faults }
+    }
+
+    /// Carries one frame across the link. `None` if it was dropped.
+    pub fn transmit(&self, frame: &[u8]) -> Option<Vec<u8>> {
+        match self.faults.next_fault() {
+            None => Some(frame.to_vec()),
+            Some(Fault::Partial) => Some(frame[..frame.len() / 2].to_vec()),
+            Some(Fault::Error) => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sync
+// ---------------------------------------------------------------------------
+
+/// A sync peer behind a faulty connection: the peer's engine answers every
+/// request, but the answer may not arrive, or arrive short.
+///
+/// Pass [`request`](Self::request) to
+/// [`SyncEngine::sync_from`](crate::network::sync::SyncEngine::sync_from).
+pub struct FaultyPeer {
+    engine: Arc<SyncEngine>,
+    faults: Arc<FaultInjector>,
+}
+
+impl FaultyPeer {
+    /// `engine`'s node, reached through a connection that fails as
+    /// `faults` decides.
+    pub fn new(engine: Arc<SyncEngine>, faults: Arc<FaultInjector>) -> Self {
+        Self { engine, faults }
+    }
+
+    /// Sends `request` to the peer. A failed request times out; a partial
+    /// one returns the first half (rounded up) of the blocks asked for, or
+    /// disconnects if the answer is not a batch of blocks.
+    pub fn request(&self, request: SyncRequest) -> Result<SyncResponse, SyncError> {
+        let fault = self.faults.next_fault();
+        if fault == Some(Fault::Error) {
+            return Err(SyncError::RequestTimeout);
+        }
+
+        let response = self.engine.process_sync_request(request);
+        match (fault, response) {
+            (Some(Fault::Partial), SyncResponse::Blocks(mut blocks)) => {
+                blocks.truncate(blocks.len().div_ceil(2));
+                Ok(SyncResponse::Blocks(blocks))
+            }
+            (Some(Fault::Partial), _) => Err(SyncError::PeerDisconnected),
+            (_, response) => Ok(response),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use parking_lot::RwLock;
+
+    use super::*;
+    use crate::network::gossip::{decode_message, encode_message, P2pGossipMessage};
+    use crate::network::sync::SyncConfig;
+    use crate::storage::block::Block;
+    use crate::storage::db::{DbError, NovaDB};
+    use crate::storage::state::{AccountState, StateTree};
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+    use crate::transaction::Transaction;
+
+    fn make_tx(nonce: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(100)
+            .nonce(nonce)
+            .timestamp(1_000_000 + nonce)
+            .build_unchecked()
+    }
+
+    fn injector(error_rate: f64, partial_rate: f64, seed: u64) -> Arc<FaultInjector> {
+        Arc::new(FaultInjector::new(FaultConfig {
+            error_rate,
+            partial_rate,
+            seed,
+            ..FaultConfig::default()
+        }))
+    }
+
+    /// A sync engine over `db` whose tree starts with Alice funded.
+    /// Test transactions are unsigned and blocks carry placeholder state
+    /// roots, as in the sync tests.
+    fn engine(db: NovaDB, max_retries: u32) -> (Arc<SyncEngine>, Arc<RwLock<StateTree>>) {
+        let mut tree = StateTree::new(db.clone());
+        tree.put("nova1alice", &AccountState::with_balance(1_000_000));
+        let tree = Arc::new(RwLock::new(tree));
+        let config = SyncConfig {
+            batch_size: 4,
+            max_retries,
+            verify_signatures: false,
+            verify_state_roots: false,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(Arc::new(db), Arc::clone(&tree), config);
+        (Arc::new(engine), tree)
+    }
+
+    /// Genesis plus `count` blocks with one transfer each.
+    fn make_chain(count: u64) -> Vec<Block> {
+        let mut chain = vec![Block::genesis()];
+        for nonce in 1..=count {
+            let block = Block::new(
+                chain.last().unwrap(),
+                vec![make_tx(nonce)],
+                "nova:validator_1".to_string(),
+                [nonce as u8; 32],
+            )
+            .with_fees(100);
+            chain.push(block);
+        }
+        chain
+    }
+
+    #[test]
+    fn faults_replay_per_seed_and_stop_when_healed() {
+        let draw =
+            |faults: &FaultInjector| (0..200).map(|_| faults.next_fault()).collect::<Vec<_>>();
+        let a = injector(0.3, 0.3, 7);
+        let b = injector(0.3, 0.3, 7);
+        let faults = draw(&a);
+        assert_eq!(faults, draw(&b));
+        assert!(faults.contains(&Some(Fault::Error)));
+        assert!(faults.contains(&Some(Fault::Partial)));
+        assert!(faults.contains(&None));
+
+        let stats = a.stats();
+        assert_eq!(stats.operations, 200);
+        assert_eq!(
+            stats.errors,
+            faults.iter().filter(|f| **f == Some(Fault::Error)).count() as u64
+        );
+
+        a.heal();
+        assert!(draw(&a).iter().all(Option::is_none));
+        assert_eq!(a.stats().operations, 200);
+        a.resume();
+        assert!(a.is_enabled());
+    }
+
+    #[test]
+    #[should_panic(expected = "error_rate must be within [0, 1]")]
+    fn rates_are_probabilities() {
+        FaultInjector::new(FaultConfig {
+            error_rate: 1.5,
+            ..FaultConfig::default()
+        });
+    }
+
+    #[test]
+    fn storage_errors_are_returned_not_raised() {
+        let faults = injector(1.0, 0.0, 1);
+        let db = NovaDB::open_temporary()
+            .unwrap()
+            .with_faults(Arc::clone(&faults));
+        let genesis = Block::genesis();
+
+        assert!(matches!(
+            db.put_block(&genesis),
+            Err(DbError::Injected("put_block"))
+        ));
+        assert!(matches!(db.get_block(0), Err(DbError::Injected(_))));
+        assert!(matches!(
+            db.get_latest_block_height(),
+            Err(DbError::Injected(_))
+        ));
+
+        faults.heal();
+        assert_eq!(db.get_latest_block_height().unwrap(), None);
+        db.put_block(&genesis).unwrap();
+        assert_eq!(
+            db.get_block(0).unwrap().unwrap().header.hash,
+            genesis.header.hash
+        );
+    }
+
+    #[test]
+    fn torn_block_writes_leave_nothing_behind() {
+        let faults = injector(0.0, 1.0, 1);
+        let db = NovaDB::open_temporary()
+            .unwrap()
+            .with_faults(Arc::clone(&faults));
+        let chain = make_chain(1);
+
+        faults.heal();
+        db.put_block(&chain[0]).unwrap();
+        faults.resume();
+
+        assert!(db.put_block(&chain[1]).is_err());
+        faults.heal();
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(0));
+        assert!(db.get_block(1).unwrap().is_none());
+        assert!(db
+            .get_block_by_hash(&chain[1].header.hash)
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get_transaction(&chain[1].transactions[0].id)
+            .unwrap()
+            .is_none());
+
+        // The retry lands the whole block.
+        db.put_block(&chain[1]).unwrap();
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(1));
+        assert_eq!(db.block_count(), 2);
+    }
+
+    #[test]
+    fn sync_retries_through_storage_and_network_faults() {
+        const HEIGHT: u64 = 24;
+        let chain = make_chain(HEIGHT);
+
+        let serving_db = NovaDB::open_temporary().unwrap();
+        serving_db.put_block(&chain[0]).unwrap();
+        let (serving, serving_tree) = engine(serving_db, 0);
+        serving.apply_blocks(chain[1..].to_vec()).unwrap();
+
+        let disk = injector(0.05, 0.1, 11);
+        let db = NovaDB::open_temporary()
+            .unwrap()
+            .with_faults(Arc::clone(&disk));
+        disk.heal();
+        db.put_block(&chain[0]).unwrap();
+        disk.resume();
+        let (syncing, syncing_tree) = engine(db.clone(), 1_000);
+
+        let network = injector(0.2, 0.2, 12);
+        let peer = FaultyPeer::new(serving, Arc::clone(&network));
+        let result = syncing.sync_from(HEIGHT, |req| peer.request(req)).unwrap();
+
+        assert!(disk.stats().errors + disk.stats().partials > 0);
+        assert!(network.stats().errors + network.stats().partials > 0);
+        assert_eq!(result.final_height, HEIGHT);
+        assert_eq!(result.blocks_applied, HEIGHT);
+        assert_eq!(result.transactions_executed, HEIGHT);
+
+        // No block was lost, doubled or half-written, and every transfer
+        // was applied exactly once.
+        disk.heal();
+        for block in &chain {
+            let stored = db.get_block(block.header.height).unwrap().unwrap();
+            assert_eq!(stored.header.hash, block.header.hash);
+        }
+        for block in &chain[1..] {
+            assert!(db.get_receipt(&block.transactions[0].id).unwrap().is_some());
+        }
+        assert_eq!(result.final_state_root, serving_tree.read().root());
+        assert_eq!(syncing_tree.read().root(), serving_tree.read().root());
+        let bob = syncing_tree.read().get("nova1bob").unwrap();
+        assert_eq!(bob.balance, HEIGHT * 1_000);
+    }
+
+    #[test]
+    fn sync_gives_up_after_max_retries() {
+        let chain = make_chain(2);
+        let serving_db = NovaDB::open_temporary().unwrap();
+        serving_db.put_block(&chain[0]).unwrap();
+        let (serving, _) = engine(serving_db, 0);
+        serving.apply_blocks(chain[1..].to_vec()).unwrap();
+
+        let db = NovaDB::open_temporary().unwrap();
+        db.put_block(&chain[0]).unwrap();
+        let (syncing, _) = engine(db.clone(), 3);
+
+        let network = injector(1.0, 0.0, 1);
+        let peer = FaultyPeer::new(serving, Arc::clone(&network));
+        assert!(matches!(
+            syncing.sync_from(2, |req| peer.request(req)),
+            Err(SyncError::RequestTimeout)
+        ));
+        // The first attempt plus three retries.
+        assert_eq!(network.stats().operations, 4);
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(0));
+    }
+
+    #[test]
+    fn lossy_gossip_delivers_whole_messages_or_nothing() {
+        let faults = injector(0.3, 0.3, 5);
+        let link = FaultyLink::new(Arc::clone(&faults));
+        let sent: Vec<Transaction> = (1..=50).map(make_tx).collect();
+        let frames: Vec<Vec<u8>> = sent
+            .iter()
+            .map(|tx| encode_message(&P2pGossipMessage::NewTransaction(tx.clone())))
+            .collect();
+        let mut delivered = HashSet::new();
+        let mut rejected = 0;
+
+        // Every transaction is relayed by three peers.
+        for _ in 0..3 {
+            for (tx, frame) in sent.iter().zip(&frames) {
+                let Some(received) = link.transmit(frame) else {
+                    continue;
+                };
+                match decode_message(&received) {
+                    Ok(P2pGossipMessage::NewTransaction(got)) => {
+                        assert_eq!(got.id, tx.id);
+                        assert_eq!(got.nonce, tx.nonce);
+                        delivered.insert(got.id);
+                    }
+                    Ok(other) => panic!("link altered the message kind: {other:?}"),
+                    Err(_) => rejected += 1,
+                }
+            }
+        }
+        assert!(rejected > 0);
+        assert!(!delivered.is_empty());
+        assert_eq!(
+            faults.stats().partials,
+            rejected,
+            "every truncated frame is refused"
+        );
+
+        // Once the link recovers, frames arrive intact.
+        faults.heal();
+        for frame in &frames {
+            assert_eq!(link.transmit(frame).as_ref(), Some(frame));
+        }
+    }
+}
@@ -7,7 +7,9 @@
 //! launch is somewhere between "difficult" and "career-ending", so choose
 //! wisely during devnet.
 
-use std::time::Duration;
+use core::time::Duration;
+
+use crate::alloc_prelude::*;
 
 // ---------------------------------------------------------------------------
 // Network Identifiers
@@ -155,6 +157,27 @@ pub const PEER_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
 /// move on. Life's too short for slow peers.
 pub const PEER_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Longest an authorization hold may lock a payer's funds (7 days).
+/// Hotels and car rentals need days; nobody needs a month-long hostage.
+pub const MAX_AUTHORIZATION_HOLD_MS: u64 = 7 * 24 * 60 * 60 * 1_000;
+
+// ---------------------------------------------------------------------------
+// Validator Liveness
+// ---------------------------------------------------------------------------
+
+/// How many recent duties (proposal slots, and votes) a validator's liveness
+/// is judged over. Each kind of duty has its own window of this size.
+pub const LIVENESS_WINDOW: u32 = 1_000;
+
+/// Share of the duties in a full window a validator must perform, in basis
+/// points. Miss more than half of either kind and you are jailed.
+pub const LIVENESS_MIN_SIGNED_BPS: u16 = 5_000;
+
+/// Blocks a jailed validator sits out before an unjail transaction is
+/// accepted. 43,200 blocks is a day at 2-second blocks — long enough to
+/// fix the box, short enough that nobody loses their seat over a bad deploy.
+pub const JAIL_COOLDOWN_BLOCKS: u64 = 43_200;
+
 // ---------------------------------------------------------------------------
 // Fee Parameters
 // ---------------------------------------------------------------------------
@@ -183,13 +206,61 @@ pub const MAX_TX_FEE_PHOTONS: u64 = 10_000_000;
 /// 8 decimals, same as Bitcoin. We're not reinventing this wheel.
 pub const FEE_DECIMALS: u8 = 8;
 
+/// Existential deposit: the smallest balance an account may hold. An
+/// account below it cannot pay even the minimum fee, so it is dead weight
+/// in the state tree — transfers may not create one, and senders left
+/// below it are reaped.
+pub const EXISTENTIAL_DEPOSIT_PHOTONS: u64 = MIN_TX_FEE_PHOTONS;
+
+/// Share of every collected fee that is burned, in basis points. The rest
+/// goes to the block proposer. Burning half keeps proposers paid without
+/// letting them stuff their own blocks with fee-paying junk for free.
+pub const FEE_BURN_BPS: u16 = 5_000;
+
+/// Share of every collected fee paid into the community treasury instead of
+/// being burned, in basis points. It comes out of the burn, so proposers
+/// earn the same: of the half that used to burn, a fifth now funds
+/// development.
+pub const FEE_TREASURY_BPS: u16 = 1_000;
+
+// ---------------------------------------------------------------------------
+// Monetary Policy
+// ---------------------------------------------------------------------------
+
+/// NOVA in existence at genesis, in photons: 1 billion NOVA.
+pub const GENESIS_SUPPLY_PHOTONS: u64 = 100_000_000_000_000_000;
+
+/// Blocks per emission epoch. 43,200 blocks is a day at 2-second blocks.
+pub const EMISSION_EPOCH_BLOCKS: u64 = 43_200;
+
+/// Photons minted over the first epoch: 100,000 NOVA a day, about 3.65%
+/// of the genesis supply a year.
+pub const INITIAL_EPOCH_EMISSION_PHOTONS: u64 = 10_000_000_000_000;
+
+/// Epochs between emission cuts. 1,460 days is four years.
+pub const EMISSION_DECAY_INTERVAL_EPOCHS: u64 = 1_460;
+
+/// How much each cut takes off the epoch emission, in basis points.
+/// 5,000 is a halving.
+pub const EMISSION_DECAY_BPS: u16 = 5_000;
+
+/// Epoch emission never decays below this: 5,000 NOVA a day, so block
+/// rewards keep paying for security after the halvings run out.
+pub const TAIL_EPOCH_EMISSION_PHOTONS: u64 = 500_000_000_000;
+
 // ---------------------------------------------------------------------------
 // Transaction Limits
 // ---------------------------------------------------------------------------
 
-/// Maximum transaction size in bytes. 256 KiB should be enough for anyone.
-/// (Famous last words, but we can always bump this in a future version.)
-pub const MAX_TX_SIZE_BYTES: usize = 256 * 1024;
+/// Maximum weight of a transaction in bytes: its canonical encoding plus
+/// the proofs, commitment, keys and signatures it carries (see
+/// `transaction::limits::tx_weight`). The per-field bounds already keep a
+/// well-formed transaction under this; the cap catches what they miss.
+pub const MAX_TX_SIZE_BYTES: usize = 32 * 1024;
+
+/// Maximum total weight of the transactions in one block, in bytes. Block
+/// producers stop selecting transactions once the next would exceed it.
+pub const MAX_BLOCK_SIZE_BYTES: usize = 2 * 1024 * 1024;
 
 /// Maximum number of inputs per transaction. Keeps validation bounded.
 pub const MAX_TX_INPUTS: usize = 256;
@@ -27,10 +27,14 @@
 //! hash-and-reduce approach: hash with BLAKE3, then reduce modulo the
 //! field order. Simple, secure, deterministic.
 
+#[cfg(feature = "zkp")]
 use ark_bn254::Fr;
+#[cfg(feature = "zkp")]
 use ark_ff::PrimeField;
 use sha2::{Digest, Sha256};
 
+use crate::alloc_prelude::*;
+
 /// Compute the SHA-256 hash of the input data.
 ///
 /// Returns a 32-byte digest as a `Vec<u8>`. Used primarily for cross-chain
@@ -157,6 +161,7 @@ pub fn double_sha256(data: &[u8]) -> Vec<u8> {
 /// let field_elem = hash_to_field(b"some transaction data");
 /// // field_elem is now a valid BN254 scalar that can be used in circuits.
 /// ```
+#[cfg(feature = "zkp")]
 pub fn hash_to_field(data: &[u8]) -> Fr {
     let hash = blake3_hash(data);
     // Fr::from_le_bytes_mod_order interprets the bytes as a little-endian
@@ -305,6 +310,7 @@ mod tests {
         assert_eq!(double, manual_double);
     }
 
+    #[cfg(feature = "zkp")]
     #[test]
     fn test_hash_to_field_deterministic() {
         let a = hash_to_field(b"transaction data");
@@ -312,6 +318,7 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[cfg(feature = "zkp")]
     #[test]
     fn test_hash_to_field_different_inputs() {
         let a = hash_to_field(b"input A");
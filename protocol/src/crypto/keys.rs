@@ -21,15 +21,18 @@
 //! - Key bytes are never logged. If you add logging to this module,
 //!   you will be asked to leave.
 
+use core::fmt;
+use core::hash::{Hash, Hasher};
 use ed25519_dalek::{
     Signature as DalekSignature, Signer, SigningKey, Verifier, VerifyingKey, SECRET_KEY_LENGTH,
 };
+#[cfg(feature = "std")]
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::hash::{Hash, Hasher};
 use thiserror::Error;
 
+use crate::alloc_prelude::*;
+
 /// Errors that can occur during key operations.
 ///
 /// These are intentionally vague about *why* something failed — leaking
@@ -108,6 +111,10 @@ impl NovaKeypair {
     /// `OsRng`, which pulls from `/dev/urandom` on Unix and `BCryptGenRandom`
     /// on Windows. If either of those is compromised, NOVA keys are the
     /// least of your worries.
+    ///
+    /// Needs the `std` feature; without an OS RNG, derive keys with
+    /// [`from_seed`](Self::from_seed).
+    #[cfg(feature = "std")]
     pub fn generate() -> Self {
         let signing_key = SigningKey::generate(&mut OsRng);
         Self { signing_key }
@@ -18,16 +18,26 @@
 //! reconsider. Then reconsider again. Then go read about timing attacks
 //! and come back when you've lost the urge.
 
+#[cfg(feature = "std")]
 pub mod encryption;
 pub mod hash;
 pub mod keys;
+#[cfg(feature = "std")]
 pub mod pfs;
 pub mod signatures;
+pub mod wire;
 
 // Re-export the things people actually need so they don't have to memorize
 // our module hierarchy. Life's too short for five levels of `use` statements.
+#[cfg(feature = "std")]
 pub use encryption::{decrypt, encrypt};
-pub use hash::{blake3_hash, double_sha256, hash_to_field, sha256};
+#[cfg(feature = "zkp")]
+pub use hash::hash_to_field;
+pub use hash::{blake3_hash, double_sha256, sha256};
 pub use keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+#[cfg(feature = "std")]
 pub use pfs::PfsSession;
 pub use signatures::{sign, verify};
+pub use wire::{
+    AuditHash, BlockHash, GossipHash, SignatureBytes, StateRoot, TxRoot, WireTypeError,
+};
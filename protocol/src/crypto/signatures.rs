@@ -27,6 +27,7 @@ use ed25519_dalek::{Signature as DalekSignature, Verifier, VerifyingKey};
 use thiserror::Error;
 
 use super::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use crate::alloc_prelude::*;
 
 /// Errors during signature operations.
 ///
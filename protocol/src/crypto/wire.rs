@@ -0,0 +1,317 @@
+//! # Fixed-Size Wire Types
+//!
+//! Hashes, roots and signatures used to travel as a mix of `[u8; 32]`,
+//! `Vec<u8>` and hex `String`s, which made it easy to hand a 64-byte
+//! signature to something expecting a 32-byte hash, or to compare a hex
+//! string against raw bytes. The newtypes here pin the length in the type.
+//!
+//! ## Encoding
+//!
+//! - **Human-readable formats** (JSON, the API): `0x`-prefixed lowercase
+//!   hex, per the [wire encoding policy](crate::encoding).
+//! - **Binary formats** (bincode, storage, gossip): the raw bytes as a
+//!   fixed-length tuple — no length prefix, same size as the bare array.
+//!
+//! Each type derefs to its byte array, so hashing code that takes
+//! `&[u8; 32]` keeps working unchanged.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::alloc_prelude::*;
+use crate::encoding;
+
+/// Errors from parsing a fixed-size wire type.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WireTypeError {
+    /// The input was not valid hex.
+    #[error("invalid hex: {0}")]
+    InvalidHex(String),
+    /// The input decoded to the wrong number of bytes.
+    #[error("expected {expected} bytes, got {got}")]
+    InvalidLength { expected: usize, got: usize },
+}
+
+macro_rules! fixed_bytes {
+    ($(#[$meta:meta])* $name:ident, $len:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(pub [u8; $len]);
+
+        impl $name {
+            /// Length in bytes.
+            pub const LEN: usize = $len;
+
+            /// All-zero value.
+            pub const fn zero() -> Self {
+                Self([0u8; $len])
+            }
+
+            /// Returns `true` if every byte is zero.
+            pub fn is_zero(&self) -> bool {
+                self.0.iter().all(|b| *b == 0)
+            }
+
+            /// The raw bytes.
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            /// Lowercase hex encoding.
+            pub fn to_hex(&self) -> String {
+                hex::encode(self.0)
+            }
+
+            /// Parses a hex string of exactly the right length, with or
+            /// without a `0x` prefix.
+            pub fn from_hex(s: &str) -> Result<Self, WireTypeError> {
+                let bytes = encoding::decode_hex(s).map_err(|e| WireTypeError::InvalidHex(e.to_string()))?;
+                Self::try_from(bytes.as_slice())
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::zero()
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; $len] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = WireTypeError;
+
+            fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+                let bytes: [u8; $len] =
+                    slice.try_into().map_err(|_| WireTypeError::InvalidLength {
+                        expected: $len,
+                        got: slice.len(),
+                    })?;
+                Ok(Self(bytes))
+            }
+        }
+
+        impl Deref for $name {
+            type Target = [u8; $len];
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl PartialEq<[u8; $len]> for $name {
+            fn eq(&self, other: &[u8; $len]) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for [u8; $len] {
+            fn eq(&self, other: &$name) -> bool {
+                self == &other.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = WireTypeError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_hex(s)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.to_hex())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.to_hex())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(&encoding::to_prefixed_hex(self.0))
+                } else {
+                    let mut tuple = serializer.serialize_tuple($len)?;
+                    for byte in &self.0 {
+                        tuple.serialize_element(byte)?;
+                    }
+                    tuple.end()
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    let s = String::deserialize(deserializer)?;
+                    Self::from_hex(&s).map_err(de::Error::custom)
+                } else {
+                    struct BytesVisitor;
+
+                    impl<'de> Visitor<'de> for BytesVisitor {
+                        type Value = [u8; $len];
+
+                        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                            write!(f, "{} bytes", $len)
+                        }
+
+                        fn visit_seq<A: SeqAccess<'de>>(
+                            self,
+                            mut seq: A,
+                        ) -> Result<Self::Value, A::Error> {
+                            let mut out = [0u8; $len];
+                            for (i, byte) in out.iter_mut().enumerate() {
+                                *byte = seq
+                                    .next_element()?
+                                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                            }
+                            Ok(out)
+                        }
+                    }
+
+                    deserializer.deserialize_tuple($len, BytesVisitor).map(Self)
+                }
+            }
+        }
+    };
+}
+
+fixed_bytes!(
+    /// BLAKE3 hash of a block header.
+    BlockHash,
+    32
+);
+
+fixed_bytes!(
+    /// Root of the account state tree after a block's transactions.
+    StateRoot,
+    32
+);
+
+fixed_bytes!(
+    /// Merkle root over a block's transactions.
+    TxRoot,
+    32
+);
+
+fixed_bytes!(
+    /// A raw Ed25519 signature.
+    SignatureBytes,
+    64
+);
+
+fixed_bytes!(
+    /// Link in a node's hash-chained audit log.
+    AuditHash,
+    32
+);
+
+fixed_bytes!(
+    /// BLAKE3 hash of a gossip message's wire frame.
+    GossipHash,
+    32
+);
+
+impl From<&crate::crypto::keys::NovaSignature> for SignatureBytes {
+    /// Ed25519 signatures produced by [`NovaKeypair::sign`](crate::crypto::keys::NovaKeypair::sign)
+    /// are always 64 bytes.
+    fn from(sig: &crate::crypto::keys::NovaSignature) -> Self {
+        let mut bytes = [0u8; 64];
+        let raw = sig.as_bytes();
+        let n = raw.len().min(64);
+        bytes[..n].copy_from_slice(&raw[..n]);
+        Self(bytes)
+    }
+}
+
+impl From<SignatureBytes> for crate::crypto::keys::NovaSignature {
+    fn from(sig: SignatureBytes) -> Self {
+        Self::from_bytes(sig.0)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_is_hex_and_bincode_is_raw() {
+        let hash = BlockHash([0xAB; 32]);
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", "ab".repeat(32)));
+        assert_eq!(serde_json::from_str::<BlockHash>(&json).unwrap(), hash);
+        let unprefixed = format!("\"{}\"", hash.to_hex());
+        assert_eq!(
+            serde_json::from_str::<BlockHash>(&unprefixed).unwrap(),
+            hash
+        );
+
+        let bin = bincode::serialize(&hash).unwrap();
+        assert_eq!(bin, vec![0xAB; 32], "no length prefix on the wire");
+        assert_eq!(bincode::deserialize::<BlockHash>(&bin).unwrap(), hash);
+
+        let sig = SignatureBytes([7; 64]);
+        let bin = bincode::serialize(&sig).unwrap();
+        assert_eq!(bin.len(), 64);
+        assert_eq!(bincode::deserialize::<SignatureBytes>(&bin).unwrap(), sig);
+    }
+
+    #[test]
+    fn wrong_length_is_rejected() {
+        assert_eq!(
+            BlockHash::from_hex(&"00".repeat(64)),
+            Err(WireTypeError::InvalidLength {
+                expected: 32,
+                got: 64
+            })
+        );
+        let sig_json = format!("\"{}\"", "00".repeat(32));
+        assert!(serde_json::from_str::<SignatureBytes>(&sig_json).is_err());
+        assert!(StateRoot::try_from(&[0u8; 31][..]).is_err());
+    }
+
+    #[test]
+    fn compares_with_bare_arrays() {
+        let root = StateRoot::from([1u8; 32]);
+        assert_eq!(root, [1u8; 32]);
+        assert_eq!([1u8; 32], root);
+        assert!(StateRoot::default().is_zero());
+    }
+}
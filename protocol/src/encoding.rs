@@ -0,0 +1,465 @@
+//! # Wire Encoding Policy
+//!
+//! Every public type that leaves the process follows the same rules, so an
+//! API consumer never has to guess whether a field is hex, base64 or a JSON
+//! byte array:
+//!
+//! | Kind                                  | Human-readable (JSON)              | Binary (bincode)   |
+//! |---------------------------------------|------------------------------------|--------------------|
+//! | Hashes, keys, signatures, byte blobs  | `0x`-prefixed lowercase hex        | raw bytes          |
+//! | Amounts (`u64`)                       | number up to 2^53 − 1, else string | little-endian `u64`|
+//!
+//! Amounts switch to decimal strings above 2^53 − 1 because that is the
+//! largest integer a JavaScript `number` holds exactly.
+//!
+//! Decoders are lenient: hex is accepted with or without the `0x` prefix
+//! (and byte blobs still accept a JSON array), and amounts accept either a
+//! number or a string. Payloads written before schema version 1 therefore
+//! still parse.
+//!
+//! Binary encodings are untouched by these helpers — bincode output, and
+//! with it every stored record and consensus hash over bincode bytes, is
+//! identical to the plain field types.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Example {
+//!     #[serde(with = "crate::encoding::hex_bytes")]
+//!     blob: Vec<u8>,
+//!     #[serde(with = "crate::encoding::amount")]
+//!     balance: u64,
+//! }
+//! ```
+//!
+//! ## Schema Versioning
+//!
+//! Wire envelopes (JSON-RPC responses, WebSocket events, gossip frames)
+//! carry [`SCHEMA_VERSION`]. JSON envelopes use a `schema_version` field —
+//! see [`Versioned`]; binary envelopes prefix the frame with the version as
+//! a `u16`. A missing field reads as `0`, meaning "before versioning".
+
+use core::fmt;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::alloc_prelude::*;
+
+/// Current wire schema version. Bump on any change to a public encoding.
+pub const SCHEMA_VERSION: u16 = 1;
+
+/// Largest integer a JavaScript `number` (IEEE-754 double) represents
+/// exactly. Amounts above this are encoded as decimal strings.
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+// ---------------------------------------------------------------------------
+// Hex Primitives
+// ---------------------------------------------------------------------------
+
+/// Encodes bytes as `0x`-prefixed lowercase hex.
+pub fn to_prefixed_hex(bytes: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Strips an optional `0x` / `0X` prefix.
+pub fn strip_hex_prefix(s: &str) -> &str {
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+/// Decodes hex with or without the `0x` prefix.
+pub fn decode_hex(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    hex::decode(strip_hex_prefix(s))
+}
+
+// ---------------------------------------------------------------------------
+// Versioned Envelope
+// ---------------------------------------------------------------------------
+
+/// Adds a top-level `schema_version` field to a JSON body.
+///
+/// The body is flattened, so `Versioned::new(event)` serializes as the
+/// event's own fields plus `"schema_version": 1`. Flattening needs a
+/// self-describing format; binary frames carry the version as a leading
+/// `u16` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    /// Wire schema version the body was encoded with.
+    #[serde(default)]
+    pub schema_version: u16,
+    /// The wrapped message.
+    #[serde(flatten)]
+    pub body: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wraps `body` at the current [`SCHEMA_VERSION`].
+    pub fn new(body: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            body,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Byte Blobs
+// ---------------------------------------------------------------------------
+
+/// Borrowed bytes that serialize under the hex policy.
+struct HexRef<'a>(&'a [u8]);
+
+impl Serialize for HexRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex_bytes::serialize(&self.0, serializer)
+    }
+}
+
+/// Owned bytes that deserialize under the hex policy.
+struct HexOwned(Vec<u8>);
+
+impl<'de> Deserialize<'de> for HexOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        hex_bytes::deserialize(deserializer).map(HexOwned)
+    }
+}
+
+struct HexOrSeqVisitor;
+
+impl<'de> Visitor<'de> for HexOrSeqVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a 0x-prefixed hex string or an array of bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        decode_hex(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            out.push(byte);
+        }
+        Ok(out)
+    }
+}
+
+/// `Vec<u8>` (or anything `AsRef<[u8]>`) as `0x` hex.
+pub mod hex_bytes {
+    use super::*;
+
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + ?Sized,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&to_prefixed_hex(bytes.as_ref()))
+        } else {
+            bytes.as_ref().serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HexOrSeqVisitor)
+        } else {
+            Vec::<u8>::deserialize(deserializer)
+        }
+    }
+}
+
+/// `Option<Vec<u8>>` as `0x` hex or `null`.
+pub mod hex_bytes_opt {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        bytes: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(b) => serializer.serialize_some(&HexRef(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Ok(Option::<HexOwned>::deserialize(deserializer)?.map(|h| h.0))
+    }
+}
+
+/// A string-keyed map of byte blobs, values as `0x` hex.
+pub mod hex_bytes_map {
+    use super::*;
+    use alloc::collections::BTreeMap;
+
+    pub fn serialize<'a, M, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'a M: IntoIterator<Item = (&'a String, &'a Vec<u8>)>,
+        S: Serializer,
+    {
+        serializer.collect_map(map.into_iter().map(|(k, v)| (k, HexRef(v))))
+    }
+
+    pub fn deserialize<'de, M, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        M: FromIterator<(String, Vec<u8>)>,
+        D: Deserializer<'de>,
+    {
+        let raw = BTreeMap::<String, HexOwned>::deserialize(deserializer)?;
+        Ok(raw.into_iter().map(|(k, v)| (k, v.0)).collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hex Strings
+// ---------------------------------------------------------------------------
+
+/// A `String` that already holds hex (transaction IDs, signatures, public
+/// keys). Gains the `0x` prefix on output and loses it on input, so the
+/// in-memory value — and everything hashed or looked up by it — is
+/// unchanged.
+pub mod hex_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(s: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() && !s.is_empty() {
+            serializer.serialize_str(&format!("0x{}", strip_hex_prefix(s)))
+        } else {
+            serializer.serialize_str(s)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(strip_hex_prefix(&s).to_string())
+    }
+}
+
+/// Borrowed hex string that serializes through [`hex_string`].
+struct Prefixed<'a>(&'a str);
+
+impl Serialize for Prefixed<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex_string::serialize(self.0, serializer)
+    }
+}
+
+/// `Option<String>` holding hex; see [`hex_string`].
+pub mod hex_string_opt {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(s: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+        match s {
+            Some(s) => serializer.serialize_some(&Prefixed(s)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        Ok(Option::<String>::deserialize(deserializer)?.map(|s| strip_hex_prefix(&s).to_string()))
+    }
+}
+
+/// `Vec<String>` holding hex (lists of transaction IDs); see [`hex_string`].
+pub mod hex_string_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(v: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(v.iter().map(|s| Prefixed(s)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        Ok(Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| strip_hex_prefix(s).to_string())
+            .collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Amounts
+// ---------------------------------------------------------------------------
+
+struct AmountVisitor;
+
+impl<'de> Visitor<'de> for AmountVisitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a non-negative integer or a decimal string")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        u64::try_from(v).map_err(|_| E::custom("amount must not be negative"))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse()
+            .map_err(|_| E::custom(format!("invalid amount string: {v:?}")))
+    }
+}
+
+/// `u64` amounts: a JSON number up to [`MAX_SAFE_INTEGER`], a decimal
+/// string above it.
+pub mod amount {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() && *value > MAX_SAFE_INTEGER {
+            serializer.serialize_str(&value.to_string())
+        } else {
+            serializer.serialize_u64(*value)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(AmountVisitor)
+        } else {
+            u64::deserialize(deserializer)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        #[serde(with = "hex_bytes")]
+        blob: Vec<u8>,
+        #[serde(with = "hex_bytes_opt")]
+        maybe: Option<Vec<u8>>,
+        #[serde(with = "hex_string")]
+        id: String,
+        #[serde(with = "amount")]
+        small: u64,
+        #[serde(with = "amount")]
+        big: u64,
+        #[serde(with = "hex_bytes_map")]
+        map: HashMap<String, Vec<u8>>,
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            blob: vec![0xde, 0xad],
+            maybe: None,
+            id: "abcd".into(),
+            small: MAX_SAFE_INTEGER,
+            big: MAX_SAFE_INTEGER + 1,
+            map: HashMap::from([("nova".to_string(), vec![1, 2])]),
+        }
+    }
+
+    #[test]
+    fn json_follows_the_policy() {
+        let json = serde_json::to_value(sample()).unwrap();
+        assert_eq!(json["blob"], "0xdead");
+        assert_eq!(json["maybe"], serde_json::Value::Null);
+        assert_eq!(json["id"], "0xabcd");
+        assert_eq!(json["small"], MAX_SAFE_INTEGER);
+        assert_eq!(json["big"], (MAX_SAFE_INTEGER + 1).to_string());
+        assert_eq!(json["map"]["nova"], "0x0102");
+
+        let back: Sample = serde_json::from_value(json).unwrap();
+        assert_eq!(back, sample());
+    }
+
+    #[test]
+    fn bincode_matches_plain_field_types() {
+        #[derive(Serialize)]
+        struct Plain {
+            blob: Vec<u8>,
+            maybe: Option<Vec<u8>>,
+            id: String,
+            small: u64,
+            big: u64,
+            map: HashMap<String, Vec<u8>>,
+        }
+        let s = sample();
+        let plain = Plain {
+            blob: s.blob.clone(),
+            maybe: None,
+            id: s.id.clone(),
+            small: s.small,
+            big: s.big,
+            map: s.map.clone(),
+        };
+
+        let bytes = bincode::serialize(&s).unwrap();
+        assert_eq!(bytes, bincode::serialize(&plain).unwrap());
+        assert_eq!(bincode::deserialize::<Sample>(&bytes).unwrap(), s);
+    }
+
+    #[test]
+    fn legacy_json_still_parses() {
+        let legacy = r#"{"blob":[222,173],"maybe":"0102","id":"0XABCD",
+            "small":"42","big":9007199254740992,"map":{"nova":[1,2]}}"#;
+        let parsed: Sample = serde_json::from_str(legacy).unwrap();
+        assert_eq!(parsed.blob, vec![0xde, 0xad]);
+        assert_eq!(parsed.maybe, Some(vec![1, 2]));
+        assert_eq!(parsed.id, "ABCD");
+        assert_eq!(parsed.small, 42);
+        assert_eq!(parsed.big, MAX_SAFE_INTEGER + 1);
+
+        assert!(serde_json::from_str::<Sample>(&legacy.replace("\"42\"", "-1")).is_err());
+    }
+
+    #[test]
+    fn hex_string_lists_are_prefixed() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Ids {
+            #[serde(with = "hex_string_vec")]
+            ids: Vec<String>,
+        }
+        let ids = Ids {
+            ids: vec!["ab".into(), "0xcd".into()],
+        };
+        let json = serde_json::to_value(&ids).unwrap();
+        assert_eq!(json, serde_json::json!({ "ids": ["0xab", "0xcd"] }));
+
+        let back: Ids = serde_json::from_value(json).unwrap();
+        assert_eq!(back.ids, vec!["ab".to_string(), "cd".to_string()]);
+    }
+
+    #[test]
+    fn versioned_envelope_flattens() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Body {
+            height: u64,
+        }
+        let json = serde_json::to_value(Versioned::new(Body { height: 7 })).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "schema_version": 1, "height": 7 })
+        );
+
+        let old: Versioned<Body> = serde_json::from_str(r#"{"height":7}"#).unwrap();
+        assert_eq!(old.schema_version, 0);
+    }
+}
@@ -0,0 +1,805 @@
+//! # State Transition Function
+//!
+//! The canonical definition of how a NOVA block changes the state. Block
+//! production, sync replay and anything else that needs a post-state go
+//! through [`StateTransition::apply_block`] (or, for a block still being
+//! packed, the [`BlockExecution`] it is built on), so there is exactly one
+//! implementation of the rules. An alternative client must reproduce this
+//! module bit for bit: a node that derives a different state root from the
+//! same block forks off the network.
+//!
+//! ## Inputs and Outputs
+//!
+//! ```text
+//! apply_block(rules, pre_state, block, parent_proposer)
+//!     -> post_state, receipts, diff, fees, state_root
+//! ```
+//!
+//! - `rules` — a [`StateTransition`]: fee split, liveness policy, block
+//!   reward schedule, token registry and confidential transfer
+//!   verification key, all fixed by the genesis configuration;
+//! - `pre_state` — the [`StateTree`] at the parent's state root, which the
+//!   call turns into the post-state in place;
+//! - `block` — its transactions, height, timestamp, proposer, declared fees
+//!   and `last_commit` votes;
+//! - `parent_proposer` — who proposed the parent block (`None` at
+//!   height 1), for liveness accounting.
+//!
+//! Nothing else is read: there is no clock (times come from the block and
+//! its transactions), no randomness, no network and no chain history. The
+//! state tree happens to be stored in sled, but the post-state depends only
+//! on the inputs above.
+//!
+//! ## The Transition
+//!
+//! 1. Each transaction, in block order:
+//!    1. is refused if its ID appeared earlier in the block, or if its
+//!       `valid_until` is before the block timestamp;
+//!    2. releases the sender's holds that lapsed by the block timestamp;
+//!    3. must pass the spending policy of the account it spends from;
+//!    4. applies its type's transition:
+//!
+//!       | Type                                                          | Transition                       |
+//!       |---------------------------------------------------------------|----------------------------------|
+//!       | `Transfer`                                                    | [`apply_transfer_transaction`]   |
+//!       | `AuthorizationHold`, `HoldCapture`                            | [`apply_hold_transaction`]       |
+//!       | `MandateGrant`, `MandatePull`, `MandateRevoke`                | [`apply_mandate_transaction`]    |
+//!       | `Unjail`                                                      | [`apply_unjail`]                 |
+//!       | `TreasurySpend`                                               | [`apply_treasury_spend`]         |
+//!       | `NetworkKeyRotation`                                          | [`apply_network_key_rotation`]   |
+//!       | `SpendingPolicyUpdate`                                        | [`apply_spending_policy_update`] |
+//!       | `Batch`                                                       | [`apply_batch`]                  |
+//!       | `ConfidentialTransfer`                                        | [`apply_confidential_transfer`]  |
+//!       | `CreditRequest`, `CreditSettlement`, `TokenMint`, `TokenBurn` | [`bump_nonce`] only              |
+//!
+//!    5. pays its declared fee from its fee source ([`charge_fee`]),
+//!       unless its transition already debited it ([`pays_own_fee`]);
+//!    6. records what it spent against the spending policy.
+//!
+//!    Any failure refuses the whole block.
+//! 2. The fees the transactions charged ([`fee_charged`]) must equal the
+//!    header's `fees`.
+//! 3. Settlement: the proposer is paid its share of the fees and the rest
+//!    is burned ([`settle_fees`]), the block reward is minted
+//!    ([`apply_emission`]), and the block is refused if the accounts it
+//!    names gained or lost value other than by those two
+//!    ([`BalanceAudit`]).
+//! 4. The proposer's and `last_commit` voters' duties are recorded
+//!    ([`record_liveness`]).
+//!
+//! The receipts ([`block_receipts`]) and the diff follow from the block and
+//! the writes. Whether the header's state root matches, and everything
+//! about the block that does not touch the state — hash, signatures,
+//! limits, linkage, transaction IDs committed in earlier blocks — is the
+//! [`BlockValidator`](crate::network::BlockValidator)'s business.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use thiserror::Error;
+use tracing::debug;
+
+use crate::indexer::StateDiff;
+use crate::network::liveness::{apply_unjail, record_liveness, LivenessPolicy};
+use crate::network::network_keys::apply_network_key_rotation;
+use crate::network::treasury::apply_treasury_spend;
+#[cfg(debug_assertions)]
+use crate::network::STATE_RECORD_KEYS;
+use crate::storage::batch::{apply_batch, batch_payouts};
+use crate::storage::block::{Block, CommitSignature};
+use crate::storage::confidential::apply_confidential_transfer;
+#[cfg(debug_assertions)]
+use crate::storage::emission::SupplyAudit;
+use crate::storage::emission::{apply_emission, MonetaryPolicy};
+use crate::storage::fee::{
+    charge_fee, fee_charged, pays_own_fee, settle_fees, BalanceAudit, FeePolicy,
+};
+use crate::storage::hold::{apply_hold_transaction, release_expired_holds};
+use crate::storage::mandate::apply_mandate_transaction;
+use crate::storage::spending::{
+    apply_spending_policy_update, check_spending_policy, record_spending,
+};
+use crate::storage::state::{apply_transfer_transaction, bump_nonce, StateError, StateTree};
+use crate::transaction::receipt::{BlockInfo, LogKind, ReceiptLog};
+use crate::transaction::types::{TransactionStatus, TransactionType};
+use crate::transaction::{Transaction, TransactionReceipt};
+use crate::vault::token::TokenRegistry;
+use crate::zkp::verifier::BalanceVerifier;
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Why a transaction cannot execute at this point of the block.
+#[derive(Debug, Error)]
+pub enum TxExecutionError {
+    /// The transaction appeared earlier in the same block.
+    #[error("transaction {0} already included")]
+    Duplicate(String),
+    /// The transaction's `valid_until` is before the block timestamp.
+    #[error("transaction {tx_id} expired at {valid_until}")]
+    Expired { tx_id: String, valid_until: u64 },
+    /// Its transition failed against the state.
+    #[error(transparent)]
+    State(#[from] StateError),
+}
+
+/// Why a block has no post-state.
+#[derive(Debug, Error)]
+pub enum ExecutionError {
+    /// A transaction cannot execute.
+    #[error(transparent)]
+    Transaction(#[from] TxExecutionError),
+    /// The header's fees are not what the transactions charged.
+    #[error("header claims {claimed} in fees, transactions charged {charged}")]
+    FeeMismatch { claimed: u64, charged: u64 },
+    /// Settling the block broke an invariant, such as value conservation.
+    #[error(transparent)]
+    Settlement(StateError),
+}
+
+// ---------------------------------------------------------------------------
+// StateTransition
+// ---------------------------------------------------------------------------
+
+/// The parameters of the state transition. Consensus-critical: every node
+/// must build it from the same genesis configuration.
+#[derive(Clone)]
+pub struct StateTransition {
+    fee_policy: FeePolicy,
+    liveness_policy: LivenessPolicy,
+    monetary_policy: MonetaryPolicy,
+    token_registry: TokenRegistry,
+    balance_verifier: Option<Arc<BalanceVerifier>>,
+}
+
+impl Default for StateTransition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for StateTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateTransition")
+            .field("fee_policy", &self.fee_policy)
+            .field("liveness_policy", &self.liveness_policy)
+            .field("monetary_policy", &self.monetary_policy)
+            .field("token_registry", &self.token_registry)
+            .field("balance_verifier", &self.balance_verifier.is_some())
+            .finish()
+    }
+}
+
+/// A block applied by [`StateTransition::apply_block`].
+#[derive(Debug, Clone)]
+pub struct AppliedBlock {
+    /// Fees the transactions charged.
+    pub fees: u64,
+    /// Root of the post-state.
+    pub state_root: [u8; 32],
+    /// One receipt per transaction, in block order.
+    pub receipts: Vec<TransactionReceipt>,
+    /// Every state leaf the block changed.
+    pub diff: StateDiff,
+}
+
+impl StateTransition {
+    /// Default policies, no block reward and no balance verifier
+    /// (confidential transfers are rejected).
+    pub fn new() -> Self {
+        Self {
+            fee_policy: FeePolicy::default(),
+            liveness_policy: LivenessPolicy::default(),
+            monetary_policy: MonetaryPolicy::fixed(0),
+            token_registry: TokenRegistry::default(),
+            balance_verifier: None,
+        }
+    }
+
+    /// Overrides the split of collected fees between proposer and burn.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.fee_policy = fee_policy;
+        self
+    }
+
+    /// Overrides when validators are jailed for missed duties.
+    pub fn with_liveness_policy(mut self, liveness_policy: LivenessPolicy) -> Self {
+        self.liveness_policy = liveness_policy;
+        self
+    }
+
+    /// Overrides the block reward schedule.
+    pub fn with_monetary_policy(mut self, monetary_policy: MonetaryPolicy) -> Self {
+        self.monetary_policy = monetary_policy;
+        self
+    }
+
+    /// Overrides the tokens transfers may be denominated in.
+    pub fn with_token_registry(mut self, token_registry: TokenRegistry) -> Self {
+        self.token_registry = token_registry;
+        self
+    }
+
+    /// Sets the verification key confidential transfers are checked with.
+    pub fn with_balance_verifier(mut self, verifier: Arc<BalanceVerifier>) -> Self {
+        self.balance_verifier = Some(verifier);
+        self
+    }
+
+    /// Applies `block` to `state`, turning the pre-state into the
+    /// post-state, as described in the [module documentation](self).
+    /// `parent_validator` proposed the parent block.
+    ///
+    /// On error every write the block made is undone, so the tree is back
+    /// at the pre-state.
+    pub fn apply_block(
+        &self,
+        state: &mut StateTree,
+        block: &Block,
+        parent_validator: Option<&str>,
+    ) -> Result<AppliedBlock, ExecutionError> {
+        state.track_writes();
+        let fees = self.execute_block(state, block, parent_validator);
+        let writes = state.take_writes();
+        let fees = match fees {
+            Ok(fees) => fees,
+            Err(e) => {
+                StateDiff::from_writes(state, block.header.height, writes).revert(state);
+                return Err(e);
+            }
+        };
+        Ok(AppliedBlock {
+            fees,
+            state_root: state.root(),
+            receipts: block_receipts(block),
+            diff: StateDiff::from_writes(state, block.header.height, writes),
+        })
+    }
+
+    /// Steps 1–4 of the transition. Returns the fees.
+    fn execute_block(
+        &self,
+        tree: &mut StateTree,
+        block: &Block,
+        parent_validator: Option<&str>,
+    ) -> Result<u64, ExecutionError> {
+        let mut execution = self.begin_block(
+            tree,
+            &block.transactions,
+            &block.header.validator,
+            block.header.height,
+            block.header.timestamp,
+        );
+        for tx in &block.transactions {
+            execution.execute(tree, tx)?;
+        }
+        if execution.fees() != block.header.fees {
+            return Err(ExecutionError::FeeMismatch {
+                claimed: block.header.fees,
+                charged: execution.fees(),
+            });
+        }
+        execution
+            .finish(tree, parent_validator, &block.header.last_commit)
+            .map_err(ExecutionError::Settlement)
+    }
+
+    /// Starts executing a block at `height` and `timestamp` proposed by
+    /// `proposer`, which may include any of `transactions`. For producers
+    /// deciding which candidates to keep; a finished block goes through
+    /// [`apply_block`](Self::apply_block).
+    pub fn begin_block(
+        &self,
+        tree: &StateTree,
+        transactions: &[Transaction],
+        proposer: &str,
+        height: u64,
+        timestamp: u64,
+    ) -> BlockExecution<'_> {
+        BlockExecution {
+            transition: self,
+            audit: BalanceAudit::for_block(tree, transactions, proposer),
+            #[cfg(debug_assertions)]
+            supply: SupplyAudit::take(tree, STATE_RECORD_KEYS),
+            proposer: proposer.to_string(),
+            height,
+            timestamp,
+            seen: HashSet::new(),
+            fees: 0,
+        }
+    }
+
+    /// Steps 1.2–1.6 for `tx` in a block at `height` and `timestamp`:
+    /// lapsed holds, spending policy, the type's transition, the fee and
+    /// the spending record.
+    ///
+    /// Holds are released and checked against the block timestamp, never
+    /// the transaction's own, which its signer chooses. A failed
+    /// transaction must leave no trace, so every write it made, the release
+    /// and its supply booking included, is rolled back if execution fails.
+    fn execute_transaction(
+        &self,
+        tree: &mut StateTree,
+        tx: &Transaction,
+        height: u64,
+        timestamp: u64,
+    ) -> Result<(), StateError> {
+        tree.savepoint();
+        release_expired_holds(tree, &tx.sender, timestamp);
+
+        let result = check_spending_policy(tree, tx, timestamp).and_then(|()| match tx.tx_type {
+            TransactionType::Transfer => apply_transfer_transaction(tree, tx, &self.token_registry),
+            TransactionType::AuthorizationHold | TransactionType::HoldCapture => {
+                apply_hold_transaction(tree, tx, timestamp)
+            }
+            TransactionType::MandateGrant
+            | TransactionType::MandatePull
+            | TransactionType::MandateRevoke => apply_mandate_transaction(tree, tx),
+            TransactionType::Unjail => apply_unjail(tree, tx, height, &self.liveness_policy),
+            TransactionType::TreasurySpend => apply_treasury_spend(tree, tx, height),
+            TransactionType::NetworkKeyRotation => apply_network_key_rotation(tree, tx, height),
+            TransactionType::SpendingPolicyUpdate => {
+                apply_spending_policy_update(tree, tx, timestamp)
+            }
+            TransactionType::Batch => apply_batch(tree, tx),
+            TransactionType::ConfidentialTransfer => {
+                apply_confidential_transfer(tree, tx, self.balance_verifier.as_deref())
+            }
+            // Other transaction types only consume the sender's nonce (and
+            // pay their fee) for now. The block includes them for ordering
+            // and audit purposes; state transitions will be added as each
+            // module matures.
+            TransactionType::CreditRequest
+            | TransactionType::CreditSettlement
+            | TransactionType::TokenMint
+            | TransactionType::TokenBurn => {
+                debug!(
+                    tx_type = %tx.tx_type,
+                    tx_id = %tx.id,
+                    "non-transfer transaction accepted as no-op"
+                );
+                bump_nonce(tree, &tx.sender, tx.nonce)
+            }
+        });
+        let result = result.and_then(|()| {
+            if pays_own_fee(tx.tx_type) {
+                Ok(())
+            } else {
+                charge_fee(tree, tx)
+            }
+        });
+
+        match result {
+            Ok(()) => {
+                record_spending(tree, tx, timestamp);
+                tree.release_savepoint();
+            }
+            Err(_) => tree.rollback_to_savepoint(),
+        }
+        result
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BlockExecution
+// ---------------------------------------------------------------------------
+
+/// A block being executed against the state tree, from
+/// [`StateTransition::begin_block`].
+///
+/// The producer executes candidates one by one and leaves out the ones
+/// rejected; [`apply_block`](StateTransition::apply_block) refuses the
+/// block instead. Either way the block is closed with
+/// [`finish`](Self::finish).
+pub struct BlockExecution<'t> {
+    transition: &'t StateTransition,
+    audit: BalanceAudit,
+    #[cfg(debug_assertions)]
+    supply: SupplyAudit,
+    proposer: String,
+    height: u64,
+    timestamp: u64,
+    seen: HashSet<String>,
+    fees: u64,
+}
+
+impl BlockExecution<'_> {
+    /// Step 1 for one transaction. A rejected transaction leaves the tree
+    /// as it found it: the holds it released and their supply booking are
+    /// rolled back with the rest of its writes.
+    pub fn execute(
+        &mut self,
+        tree: &mut StateTree,
+        tx: &Transaction,
+    ) -> Result<(), TxExecutionError> {
+        if self.seen.contains(&tx.id) {
+            return Err(TxExecutionError::Duplicate(tx.id.clone()));
+        }
+        if let Some(valid_until) = tx.valid_until {
+            if tx.is_expired_at(self.timestamp) {
+                return Err(TxExecutionError::Expired {
+                    tx_id: tx.id.clone(),
+                    valid_until,
+                });
+            }
+        }
+        self.transition
+            .execute_transaction(tree, tx, self.height, self.timestamp)?;
+        self.seen.insert(tx.id.clone());
+        self.fees = self.fees.saturating_add(fee_charged(tx));
+        Ok(())
+    }
+
+    /// Fees charged by the transactions executed so far.
+    pub fn fees(&self) -> u64 {
+        self.fees
+    }
+
+    /// Steps 3 and 4: pays the proposer its fees and reward, burns the
+    /// rest, refuses the block if it created or destroyed value along the
+    /// way, and records the proposer's and `last_commit` voters' duties
+    /// (`parent_validator` proposed the parent block). Returns the fees.
+    pub fn finish(
+        self,
+        tree: &mut StateTree,
+        parent_validator: Option<&str>,
+        last_commit: &[CommitSignature],
+    ) -> Result<u64, StateError> {
+        let transition = self.transition;
+        let split = settle_fees(tree, &self.proposer, self.fees, &transition.fee_policy);
+        let minted = apply_emission(
+            tree,
+            &self.proposer,
+            self.height,
+            &transition.monetary_policy,
+            split.burned,
+        );
+        self.audit.finish(tree, split.burned, minted)?;
+        // The balance audit only sees the accounts the block names; debug
+        // builds also check the whole tree against the booking.
+        #[cfg(debug_assertions)]
+        if let Err(e) = self
+            .supply
+            .check_block(&SupplyAudit::take(tree, STATE_RECORD_KEYS))
+        {
+            panic!("block {}: {}", self.height, e);
+        }
+
+        record_liveness(
+            tree,
+            self.height,
+            &self.proposer,
+            parent_validator,
+            last_commit,
+            &transition.liveness_policy,
+        );
+        Ok(self.fees)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Receipts
+// ---------------------------------------------------------------------------
+
+/// Receipts for the transactions of an applied block.
+///
+/// A block whose transactions did not all execute has no post-state, so
+/// every receipt is `Confirmed`.
+pub fn block_receipts(block: &Block) -> Vec<TransactionReceipt> {
+    let block_info = BlockInfo {
+        height: block.header.height,
+        hash: block.header.hash_hex(),
+        timestamp: block.header.timestamp,
+    };
+    block
+        .transactions
+        .iter()
+        .map(|tx| {
+            TransactionReceipt::from_transaction(tx, &block_info, TransactionStatus::Confirmed)
+                .with_execution(fee_charged(tx), receipt_logs(tx))
+        })
+        .collect()
+}
+
+/// Balance changes a committed transaction made, for its receipt.
+///
+/// Transfers and batches debit the sender and credit each recipient; the
+/// fee, when one was charged, is logged against its payer. A confidential
+/// transfer's amount is hidden, so it logs only its fee, and other types
+/// record only their fee until their state transitions settle.
+fn receipt_logs(tx: &Transaction) -> Vec<ReceiptLog> {
+    let mut logs = Vec::new();
+    match tx.tx_type {
+        TransactionType::Transfer => {
+            logs.push(ReceiptLog::new(LogKind::Debit, &tx.sender, tx.amount.value));
+            logs.push(ReceiptLog::new(
+                LogKind::Credit,
+                &tx.receiver,
+                tx.amount.value,
+            ));
+        }
+        TransactionType::Batch => {
+            logs.push(ReceiptLog::new(LogKind::Debit, &tx.sender, tx.amount.value));
+            for entry in batch_payouts(tx) {
+                logs.push(ReceiptLog::new(
+                    LogKind::Credit,
+                    entry.recipient,
+                    entry.amount,
+                ));
+            }
+        }
+        _ => {}
+    }
+    let fee = fee_charged(tx);
+    if fee > 0 {
+        logs.push(ReceiptLog::new(LogKind::Fee, tx.fee_source(), fee));
+    }
+    logs
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::emission::SupplyRecord;
+    use crate::storage::hold::{AuthorizationHold, CaptureTerms};
+    use crate::storage::state::AccountState;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+
+    fn transfer(nonce: u64, valid_until: Option<u64>) -> Transaction {
+        let mut builder = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(100)
+            .nonce(nonce)
+            .timestamp(1_000_000 + nonce);
+        if let Some(valid_until) = valid_until {
+            builder = builder.valid_until(valid_until);
+        }
+        builder.build_unchecked()
+    }
+
+    fn pre_state() -> StateTree {
+        let mut tree = StateTree::new(NovaDB::open_temporary().expect("temp db"));
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        tree
+    }
+
+    fn block(txs: Vec<Transaction>, fees: u64) -> Block {
+        Block::new_at(
+            &Block::genesis(),
+            txs,
+            "nova1proposer".into(),
+            [0u8; 32],
+            2_000_000,
+        )
+        .with_fees(fees)
+    }
+
+    #[test]
+    fn apply_block_returns_post_state_receipts_and_diff() {
+        let transition = StateTransition::new();
+        let mut state = pre_state();
+        let block = block(vec![transfer(1, None), transfer(2, None)], 200);
+
+        let applied = transition.apply_block(&mut state, &block, None).unwrap();
+        assert_eq!(applied.fees, 200);
+        assert_eq!(applied.state_root, state.root());
+        assert_eq!(state.get("nova1alice").unwrap().balance, 7_800);
+        assert_eq!(state.get("nova1bob").unwrap().balance, 2_000);
+
+        let receipt_ids: Vec<&str> = applied.receipts.iter().map(|r| r.tx_id.as_str()).collect();
+        let block_ids: Vec<&str> = block.transactions.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(receipt_ids, block_ids);
+        assert!(applied.receipts.iter().all(|r| r.fee == 100));
+
+        assert_eq!(applied.diff.height, 1);
+        let bob = applied
+            .diff
+            .changes
+            .iter()
+            .find(|c| c.key == "nova1bob")
+            .expect("bob in the diff");
+        assert!(bob.before.is_none());
+        assert_eq!(bob.account_after().unwrap().balance, 2_000);
+        let alice = applied
+            .diff
+            .changes
+            .iter()
+            .find(|c| c.key == "nova1alice")
+            .expect("alice in the diff");
+        assert_eq!(alice.account_before().unwrap().balance, 10_000);
+
+        // The same inputs always give the same post-state.
+        let mut again = pre_state();
+        let replayed = transition.apply_block(&mut again, &block, None).unwrap();
+        assert_eq!(replayed.state_root, applied.state_root);
+        assert_eq!(replayed.diff, applied.diff);
+    }
+
+    #[test]
+    fn apply_block_refuses_blocks_without_a_post_state() {
+        let transition = StateTransition::new();
+
+        let tx = transfer(1, None);
+        let twice = block(vec![tx.clone(), tx], 200);
+        assert!(matches!(
+            transition.apply_block(&mut pre_state(), &twice, None),
+            Err(ExecutionError::Transaction(TxExecutionError::Duplicate(_)))
+        ));
+
+        // The block timestamp is 2_000_000.
+        let lapsed = block(vec![transfer(1, Some(1_500_000))], 100);
+        assert!(matches!(
+            transition.apply_block(&mut pre_state(), &lapsed, None),
+            Err(ExecutionError::Transaction(
+                TxExecutionError::Expired { .. }
+            ))
+        ));
+
+        let skipped = block(vec![transfer(2, None)], 100);
+        assert!(matches!(
+            transition.apply_block(&mut pre_state(), &skipped, None),
+            Err(ExecutionError::Transaction(TxExecutionError::State(_)))
+        ));
+
+        let unpaid = block(vec![transfer(1, None)], 0);
+        assert!(matches!(
+            transition.apply_block(&mut pre_state(), &unpaid, None),
+            Err(ExecutionError::FeeMismatch {
+                claimed: 0,
+                charged: 100
+            })
+        ));
+    }
+
+    #[test]
+    fn every_transaction_type_pays_its_fee() {
+        let transition = StateTransition::new();
+        let no_op = |nonce: u64, fee: u64| {
+            TransactionBuilder::new(TransactionType::CreditRequest)
+                .sender("nova1alice")
+                .receiver("nova1bob")
+                .amount(Amount::new(1_000, Currency::NOVA))
+                .fee(fee)
+                .nonce(nonce)
+                .timestamp(1_000_000 + nonce)
+                .build_unchecked()
+        };
+
+        let mut state = pre_state();
+        let applied = transition
+            .apply_block(&mut state, &block(vec![no_op(1, 300)], 300), None)
+            .unwrap();
+        assert_eq!(applied.fees, 300);
+        assert_eq!(applied.receipts[0].fee, 300);
+        assert_eq!(state.get("nova1alice").unwrap().balance, 9_700);
+        assert!(state.get("nova1bob").is_none());
+
+        // A fee the sender cannot cover fails the transaction, nonce and all.
+        let root = state.root();
+        let unaffordable = no_op(2, 10_000);
+        let mut execution = transition.begin_block(
+            &state,
+            &[unaffordable.clone()],
+            "nova1proposer",
+            2,
+            2_000_000,
+        );
+        assert!(matches!(
+            execution.execute(&mut state, &unaffordable),
+            Err(TxExecutionError::State(StateError::InsufficientBalance {
+                have: 9_700,
+                need: 10_000
+            }))
+        ));
+        assert_eq!(state.root(), root);
+    }
+
+    /// Alice with 9_000 of her 10_000 held for `nova1merchant` until
+    /// `expires_at`.
+    fn held_state(expires_at: u64) -> StateTree {
+        let mut state = pre_state();
+        let mut alice = state.get("nova1alice").unwrap();
+        alice.holds.push(AuthorizationHold {
+            hold_id: "hold-1".into(),
+            merchant: "nova1merchant".into(),
+            amount: 9_000,
+            captured: 0,
+            expires_at,
+        });
+        state.put("nova1alice", &alice);
+        state
+    }
+
+    #[test]
+    fn backdated_capture_cannot_take_a_lapsed_hold() {
+        let transition = StateTransition::new();
+        let mut state = held_state(1_500_000);
+
+        // Signed as if before expiry, executed in a block after it.
+        let capture = TransactionBuilder::new(TransactionType::HoldCapture)
+            .sender("nova1merchant")
+            .receiver("nova1alice")
+            .amount(Amount::new(9_000, Currency::NOVA))
+            .nonce(1)
+            .timestamp(1_000_000)
+            .payload(
+                serde_json::to_vec(&CaptureTerms {
+                    hold_id: "hold-1".into(),
+                    final_capture: true,
+                })
+                .unwrap(),
+            )
+            .build_unchecked();
+        let mut execution =
+            transition.begin_block(&state, &[capture.clone()], "nova1proposer", 1, 2_000_000);
+        assert!(matches!(
+            execution.execute(&mut state, &capture),
+            Err(TxExecutionError::State(StateError::HoldRejected(_)))
+        ));
+        assert!(state.get("nova1merchant").is_none());
+        assert_eq!(state.get("nova1alice").unwrap().balance, 10_000);
+    }
+
+    #[test]
+    fn postdated_transaction_cannot_release_a_live_hold() {
+        let transition = StateTransition::new();
+        let mut state = held_state(2_500_000);
+
+        // Signed as if after expiry, executed in a block before it: the
+        // hold stays, so only 1_000 is available for 1_100.
+        let mut spend = transfer(1, None);
+        spend.timestamp = 3_000_000;
+        let mut execution =
+            transition.begin_block(&state, &[spend.clone()], "nova1proposer", 1, 2_000_000);
+        assert!(matches!(
+            execution.execute(&mut state, &spend),
+            Err(TxExecutionError::State(StateError::InsufficientBalance {
+                have: 1_000,
+                need: 1_100
+            }))
+        ));
+        assert_eq!(state.get("nova1alice").unwrap().holds.len(), 1);
+    }
+
+    #[test]
+    fn failed_transaction_undoes_its_hold_release() {
+        let transition = StateTransition::new();
+        let mut state = pre_state();
+        let mut alice = state.get("nova1alice").unwrap();
+        alice.holds.push(AuthorizationHold {
+            hold_id: "hold-1".into(),
+            merchant: "nova1merchant".into(),
+            amount: 4_000,
+            captured: 0,
+            expires_at: 500_000,
+        });
+        state.put("nova1alice", &alice);
+        SupplyRecord {
+            locked: 4_000,
+            ..SupplyRecord::load(&state)
+        }
+        .store(&mut state);
+        let root = state.root();
+
+        // The hold lapsed before the transaction's timestamp, so executing
+        // it releases the hold, and then the nonce gap fails it.
+        let skipped = transfer(2, None);
+        let mut execution =
+            transition.begin_block(&state, &[skipped.clone()], "nova1proposer", 1, 2_000_000);
+        assert!(execution.execute(&mut state, &skipped).is_err());
+
+        assert_eq!(state.root(), root);
+        assert_eq!(SupplyRecord::load(&state).locked, 4_000);
+        assert_eq!(state.get("nova1alice").unwrap().holds.len(), 1);
+    }
+}
@@ -0,0 +1,219 @@
+//! # Signed Messages — "Sign in with NOVA"
+//!
+//! Lets a wallet prove it controls an address without moving funds. The
+//! wallet signs an arbitrary message with the address's key; anyone holding
+//! the message, the public key and the signature can check that the key
+//! hashes to the address and produced the signature.
+//!
+//! ## Domain Separation
+//!
+//! The signature never covers the raw message. It covers
+//!
+//! ```text
+//! "\x19NOVA Signed Message:\n" || decimal(len(message)) || message
+//! ```
+//!
+//! Transaction signing bytes start with a small protocol version followed
+//! by a transaction type name, which this prefix can never parse as, so a
+//! signed login challenge cannot be replayed as a transfer. The length
+//! prefix stops one message from being reinterpreted as a prefix of
+//! another.
+//!
+//! ## Login Flow
+//!
+//! The scheme authenticates bytes, not intent. Services should put their
+//! domain, a one-time nonce and an expiry in the message text and check all
+//! three after [`verify_message`] succeeds:
+//!
+//! ```text
+//! example.com wants you to sign in with your NOVA account:
+//! nova1qw508d6...
+//!
+//! Nonce: 8f1c2e
+//! Expires: 2026-10-16T12:05:00Z
+//! ```
+
+use crate::alloc_prelude::*;
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use crate::encoding;
+use crate::identity::nova_id::{NovaId, NovaIdError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Prefix of every signed-message preimage.
+pub const SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19NOVA Signed Message:\n";
+
+/// Why a signed message did not verify.
+#[derive(Debug, Error)]
+pub enum MessageSignatureError {
+    /// The claimed address is not a valid NOVA address.
+    #[error("invalid address: {0}")]
+    InvalidAddress(NovaIdError),
+
+    /// The public key is not 32 hex-encoded bytes.
+    #[error("malformed public key")]
+    MalformedPublicKey,
+
+    /// The signature is not 64 hex-encoded bytes.
+    #[error("malformed signature")]
+    MalformedSignature,
+
+    /// The public key does not belong to the claimed address.
+    #[error("public key does not match the address")]
+    PublicKeyMismatch,
+
+    /// The signature does not cover this message under this key.
+    #[error("signature verification failed")]
+    SignatureVerificationFailed,
+}
+
+/// The bytes a message signature covers (see the module docs).
+pub fn signed_message_preimage(message: &[u8]) -> Vec<u8> {
+    let len = message.len().to_string();
+    let mut preimage = Vec::with_capacity(SIGNED_MESSAGE_PREFIX.len() + len.len() + message.len());
+    preimage.extend_from_slice(SIGNED_MESSAGE_PREFIX);
+    preimage.extend_from_slice(len.as_bytes());
+    preimage.extend_from_slice(message);
+    preimage
+}
+
+/// Signs `message` under the signed-message domain.
+pub fn sign_message(keypair: &NovaKeypair, message: &[u8]) -> NovaSignature {
+    keypair.sign(&signed_message_preimage(message))
+}
+
+/// Checks that `public_key` owns `address` and signed `message`.
+pub fn verify_message(
+    address: &str,
+    message: &[u8],
+    public_key: &NovaPublicKey,
+    signature: &NovaSignature,
+) -> Result<(), MessageSignatureError> {
+    let mut id = NovaId::from_address(address).map_err(MessageSignatureError::InvalidAddress)?;
+    id.attach_public_key(public_key)
+        .map_err(|_| MessageSignatureError::PublicKeyMismatch)?;
+    id.verify_signature(&signed_message_preimage(message), signature)
+        .map_err(|_| MessageSignatureError::SignatureVerificationFailed)
+}
+
+/// A message with everything needed to verify it, in the JSON shape the
+/// CLI prints and `nova_verifyMessage` accepts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMessage {
+    /// The address claimed to have signed.
+    pub address: String,
+    /// The signed text.
+    pub message: String,
+    /// The signer's Ed25519 public key, hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub public_key: String,
+    /// The Ed25519 signature over the preimage, hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub signature: String,
+}
+
+impl SignedMessage {
+    /// Signs `message` with `keypair`, claiming the keypair's address.
+    pub fn sign(keypair: &NovaKeypair, message: &str) -> Self {
+        let public_key = keypair.public_key();
+        Self {
+            address: NovaId::from_public_key(&public_key).to_address(),
+            message: message.to_string(),
+            public_key: public_key.to_hex(),
+            signature: sign_message(keypair, message.as_bytes()).to_hex(),
+        }
+    }
+
+    /// Verifies the signature and that the key owns `address`.
+    pub fn verify(&self) -> Result<(), MessageSignatureError> {
+        let public_key = NovaPublicKey::from_hex(encoding::strip_hex_prefix(&self.public_key))
+            .map_err(|_| MessageSignatureError::MalformedPublicKey)?;
+        let signature = NovaSignature::from_hex(encoding::strip_hex_prefix(&self.signature))
+            .map_err(|_| MessageSignatureError::MalformedSignature)?;
+        verify_message(
+            &self.address,
+            self.message.as_bytes(),
+            &public_key,
+            &signature,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair(seed: u8) -> NovaKeypair {
+        NovaKeypair::from_seed(&[seed; 32])
+    }
+
+    #[test]
+    fn preimage_is_prefixed_and_length_delimited() {
+        assert_eq!(
+            signed_message_preimage(b"hello"),
+            b"\x19NOVA Signed Message:\n5hello".to_vec()
+        );
+        assert_eq!(
+            signed_message_preimage(b""),
+            b"\x19NOVA Signed Message:\n0".to_vec()
+        );
+    }
+
+    #[test]
+    fn signed_message_round_trips() {
+        let signed = SignedMessage::sign(&keypair(1), "sign in to example.com\nNonce: 42");
+        assert!(signed.address.starts_with("nova1"));
+        signed.verify().unwrap();
+
+        let json = serde_json::to_value(&signed).unwrap();
+        assert_eq!(json["public_key"], format!("0x{}", signed.public_key));
+        let parsed: SignedMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, signed);
+        parsed.verify().unwrap();
+    }
+
+    #[test]
+    fn rejects_tampering_and_foreign_keys() {
+        let signed = SignedMessage::sign(&keypair(1), "nonce 1");
+
+        let altered = SignedMessage {
+            message: "nonce 2".into(),
+            ..signed.clone()
+        };
+        assert!(matches!(
+            altered.verify(),
+            Err(MessageSignatureError::SignatureVerificationFailed)
+        ));
+
+        // A valid signature by another key does not prove this address.
+        let other = SignedMessage::sign(&keypair(2), "nonce 1");
+        let borrowed = SignedMessage {
+            address: signed.address.clone(),
+            ..other
+        };
+        assert!(matches!(
+            borrowed.verify(),
+            Err(MessageSignatureError::PublicKeyMismatch)
+        ));
+
+        let garbled = SignedMessage {
+            signature: "0xdead".into(),
+            ..signed
+        };
+        assert!(matches!(
+            garbled.verify(),
+            Err(MessageSignatureError::MalformedSignature)
+        ));
+    }
+
+    #[test]
+    fn message_signatures_do_not_verify_as_raw() {
+        let kp = keypair(3);
+        let sig = sign_message(&kp, b"transfer 100");
+        assert!(!kp.public_key().verify(b"transfer 100", &sig));
+    }
+}
@@ -9,9 +9,11 @@
 //! 1. **Keypair** — Raw Ed25519 key material. Signs things, proves ownership.
 //! 2. **NOVA ID** — Bech32-encoded public key with `nova` HRP. This is what
 //!    users see, share, and paste into payment fields.
-//! 3. **Recovery** — Shamir's Secret Sharing over GF(256) for social/custodial
+//! 3. **Signed messages** — Domain-separated signatures that prove control
+//!    of an address without a transaction ("sign in with NOVA").
+//! 4. **Recovery** — Shamir's Secret Sharing over GF(256) for social/custodial
 //!    key recovery. Split your seed across trusted parties.
-//! 4. **DID** — W3C Decentralized Identifier compatibility layer. Maps NOVA
+//! 5. **DID** — W3C Decentralized Identifier compatibility layer. Maps NOVA
 //!    identities into the `did:nova:` method for interop with the broader
 //!    SSI ecosystem.
 //!
@@ -26,12 +28,20 @@
 //! - Shamir's implementation operates over GF(256) with irreducible polynomial
 //!   x^8 + x^4 + x^3 + x + 1 (0x11B), same as AES. No external dependencies.
 
+#[cfg(feature = "std")]
 pub mod did;
 pub mod keypair;
+pub mod message;
 pub mod nova_id;
+#[cfg(feature = "std")]
 pub mod recovery;
 
+#[cfg(feature = "std")]
 pub use did::{DidDocument, NovaDid, VerificationMethod};
 pub use keypair::{NovaKeypair, NovaPublicKey, NovaSignature};
-pub use nova_id::{NovaId, NovaIdDocument};
+pub use message::{sign_message, verify_message, MessageSignatureError, SignedMessage};
+#[cfg(feature = "std")]
+pub use nova_id::NovaIdDocument;
+pub use nova_id::{AddressPolicy, InvalidAddress, NovaId};
+#[cfg(feature = "std")]
 pub use recovery::{recover_secret, split_secret, ShamirConfig, Share};
@@ -21,20 +21,29 @@
 //! - Consistent 32-byte output regardless of future key scheme changes.
 //! - BLAKE3 is faster than SHA-256 and produces higher-quality digests.
 
+use crate::alloc_prelude::*;
 use crate::crypto::keys::{NovaPublicKey, NovaSignature};
 use bech32::{Bech32, Hrp};
+#[cfg(feature = "std")]
 use chrono::{DateTime, Utc};
+use core::fmt;
 use serde::{Deserialize, Serialize};
-use std::fmt;
 use thiserror::Error;
+#[cfg(feature = "std")]
 use uuid::Uuid;
 
 /// The human-readable prefix for all NOVA addresses.
 const NOVA_HRP: &str = "nova";
 
 /// Current identity document schema version.
+#[cfg(feature = "std")]
 const DOCUMENT_VERSION: u32 = 1;
 
+/// Upper bound on the length of any address string accepted at an API or
+/// transaction boundary, strict or permissive. A Bech32 NOVA address is
+/// 63 characters.
+pub const MAX_ADDRESS_LENGTH: usize = 128;
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -225,8 +234,8 @@ impl PartialEq for NovaId {
     }
 }
 
-impl std::hash::Hash for NovaId {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+impl core::hash::Hash for NovaId {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         // Must be consistent with PartialEq: only hash the key_hash field.
         self.key_hash.hash(state);
     }
@@ -277,6 +286,74 @@ impl<'de> Deserialize<'de> for NovaId {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Address Validation
+// ---------------------------------------------------------------------------
+
+/// An address string was rejected at a boundary (transaction, mempool, API,
+/// wallet).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid address '{address}': {reason}")]
+pub struct InvalidAddress {
+    /// The offending input, verbatim.
+    pub address: String,
+    /// Why it was rejected.
+    pub reason: String,
+}
+
+/// How strictly address strings are checked.
+///
+/// Production networks use [`Strict`](Self::Strict): every address must be
+/// a checksummed Bech32 string with the `nova` HRP and a 32-byte payload —
+/// exactly what [`NovaId::from_address`] accepts. Dev chains may opt into
+/// [`Permissive`](Self::Permissive) so that fixtures like `nova1alice` keep
+/// working; it still rejects empty, oversized or non-printable input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressPolicy {
+    /// Full Bech32 validation: HRP, length and checksum.
+    #[default]
+    Strict,
+    /// Any short, printable, whitespace-free string.
+    Permissive,
+}
+
+impl AddressPolicy {
+    /// Checks `address` against this policy.
+    pub fn check(self, address: &str) -> Result<(), InvalidAddress> {
+        let reject = |reason: String| InvalidAddress {
+            address: address.to_string(),
+            reason,
+        };
+
+        if address.is_empty() {
+            return Err(reject("empty".into()));
+        }
+        if address.len() > MAX_ADDRESS_LENGTH {
+            return Err(reject(format!(
+                "{} bytes (max {})",
+                address.len(),
+                MAX_ADDRESS_LENGTH
+            )));
+        }
+
+        match self {
+            Self::Strict => NovaId::from_address(address)
+                .map(|_| ())
+                .map_err(|e| reject(e.to_string())),
+            Self::Permissive => {
+                if address.bytes().all(|b| b.is_ascii_graphic()) {
+                    Ok(())
+                } else {
+                    Err(reject(
+                        "contains whitespace or non-printable characters".into(),
+                    ))
+                }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // NovaIdDocument
 // ---------------------------------------------------------------------------
@@ -286,7 +363,8 @@ impl<'de> Deserialize<'de> for NovaId {
 /// This is the authoritative record of a participant's identity on the
 /// network. It binds a NOVA ID to its public key, creation timestamp,
 /// and a unique document identifier. Documents are versioned to support
-/// future schema evolution.
+/// future schema evolution. Needs the `std` feature.
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NovaIdDocument {
     /// Unique document identifier (UUID v4).
@@ -316,6 +394,7 @@ pub struct NovaIdDocument {
     pub metadata: std::collections::HashMap<String, String>,
 }
 
+#[cfg(feature = "std")]
 impl NovaIdDocument {
     /// Create a new identity document from a public key.
     ///
@@ -516,4 +595,31 @@ mod tests {
         let sig = kp.sign(msg);
         assert!(doc.verify_signature(msg, &sig).is_ok());
     }
+
+    #[test]
+    fn strict_policy_requires_checksummed_bech32() {
+        let kp = NovaKeypair::generate();
+        let addr = NovaId::from_public_key(&kp.public_key()).to_address();
+        assert!(AddressPolicy::Strict.check(&addr).is_ok());
+
+        // Flip one character: the checksum must catch it.
+        let mut corrupted: Vec<char> = addr.chars().collect();
+        let last = corrupted.len() - 1;
+        corrupted[last] = if corrupted[last] == 'q' { 'p' } else { 'q' };
+        let corrupted: String = corrupted.into_iter().collect();
+        assert!(AddressPolicy::Strict.check(&corrupted).is_err());
+
+        for bad in ["", "garbage", "nova1alice", "btc1qqqqqqqq"] {
+            assert!(AddressPolicy::Strict.check(bad).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn permissive_policy_accepts_dev_names_only() {
+        assert!(AddressPolicy::Permissive.check("nova1alice").is_ok());
+        assert!(AddressPolicy::Permissive.check("").is_err());
+        assert!(AddressPolicy::Permissive.check("has space").is_err());
+        let long = "a".repeat(MAX_ADDRESS_LENGTH + 1);
+        assert!(AddressPolicy::Permissive.check(&long).is_err());
+    }
 }
@@ -0,0 +1,675 @@
+//! # Indexer Subscriptions
+//!
+//! Indexers — explorers, accounting exports, analytics — need every block,
+//! every executed transaction and every state change, exactly as the node
+//! committed them. Scraping the REST API for that is slow and misses state
+//! changes entirely. This module lets an indexer built on the crate
+//! subscribe to the commit path instead.
+//!
+//! ## Pipeline
+//!
+//! An [`Indexer`] attached to the
+//! [`BlockProducer`](crate::network::producer::BlockProducer) and the
+//! [`SyncEngine`](crate::network::sync::SyncEngine) (`with_indexer`)
+//! records a [`StateDiff`] for every block they commit and wakes delivery.
+//! Handlers implement [`IndexHandler`] and are registered with
+//! [`Indexer::register`]. For each committed block a handler receives, in
+//! order:
+//!
+//! 1. [`on_transaction_executed`](IndexHandler::on_transaction_executed)
+//!    once per transaction, in block order;
+//! 2. [`on_event`](IndexHandler::on_event) for each [`ChainEvent`] the
+//!    block emitted;
+//! 3. [`on_state_diff`](IndexHandler::on_state_diff) with the block's
+//!    state changes, if they were recorded;
+//! 4. [`on_block_committed`](IndexHandler::on_block_committed) last.
+//!
+//! ## Delivery and Cursors
+//!
+//! Every handler has a cursor — the last height it fully processed —
+//! persisted in the `indexer` tree of [`NovaDB`] under the handler's
+//! [`name`](IndexHandler::name). Delivery reads committed blocks from the
+//! database, starting after the cursor, and advances the cursor only once
+//! all four callbacks for a block returned `Ok`. A failing handler is
+//! retried from the same block later, and a crash between a callback and
+//! the cursor update replays the block on restart, so delivery is
+//! at-least-once: handlers must tolerate seeing a block twice.
+//!
+//! Handlers are independent: one stuck on a block does not hold back the
+//! others. After a rollback moves the chain tip below a cursor, the cursor
+//! is pulled back to the tip and the replacement blocks are delivered.
+//!
+//! State diffs are only recorded while an indexer is attached. Blocks
+//! committed before that are delivered without one.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sled::Tree;
+use thiserror::Error;
+use tokio::sync::{watch, Notify};
+use tracing::{debug, warn};
+
+use crate::encoding;
+use crate::storage::batch::BatchPayout;
+use crate::storage::db::{DbError, DbResult, NovaDB};
+use crate::storage::state::{AccountState, StateTree};
+use crate::storage::Block;
+use crate::transaction::{Transaction, TransactionType};
+
+/// Name of the sled tree holding recorded diffs and handler cursors.
+pub const INDEXER_TREE: &str = "indexer";
+
+/// Key prefix of recorded diffs; the rest is the big-endian height.
+const DIFF_PREFIX: &[u8] = b"d";
+
+/// Key prefix of handler cursors; the rest is the handler name.
+const CURSOR_PREFIX: &[u8] = b"c";
+
+/// How long [`Indexer::run`] waits before retrying a failed handler when
+/// no new block arrives.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Error a handler returns to have its block delivered again.
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Errors from the indexer itself.
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    /// Reading blocks or diffs, or writing a cursor, failed.
+    #[error("indexer storage: {0}")]
+    Db(#[from] DbError),
+    /// Another handler is already registered under this name.
+    #[error("an index handler named {0} is already registered")]
+    DuplicateHandler(String),
+}
+
+// ---------------------------------------------------------------------------
+// Payloads
+// ---------------------------------------------------------------------------
+
+/// A transaction as executed in a committed block.
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutedTransaction<'a> {
+    /// Height of the block that executed it.
+    pub height: u64,
+    /// Position in the block.
+    pub index: usize,
+    /// The transaction.
+    pub transaction: &'a Transaction,
+}
+
+/// One state leaf a block wrote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChange {
+    /// Account address, or the key of a protocol record.
+    pub key: String,
+    /// Raw value before the block; `None` if the key did not exist.
+    #[serde(with = "encoding::hex_bytes_opt")]
+    pub before: Option<Vec<u8>>,
+    /// Raw value after the block; `None` if the block deleted it.
+    #[serde(with = "encoding::hex_bytes_opt")]
+    pub after: Option<Vec<u8>>,
+}
+
+impl StateChange {
+    /// The account state before the block, if `key` held an account.
+    pub fn account_before(&self) -> Option<AccountState> {
+        self.before.as_deref().and_then(AccountState::from_bytes)
+    }
+
+    /// The account state after the block, if `key` holds an account.
+    pub fn account_after(&self) -> Option<AccountState> {
+        self.after.as_deref().and_then(AccountState::from_bytes)
+    }
+}
+
+/// Every state leaf one block changed, in key order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// Height of the block.
+    pub height: u64,
+    /// The changed leaves. Leaves written back to their old value are left
+    /// out.
+    pub changes: Vec<StateChange>,
+}
+
+impl StateDiff {
+    /// The changes `tree` holds since its last commit, as the diff of the
+    /// block at `height`. `None` without a committed-state handle, which is
+    /// what remembers the old values.
+    pub fn capture(tree: &StateTree, height: u64) -> Option<Self> {
+        let committed = tree.committed_state()?;
+        Some(Self::from_writes(tree, height, committed.uncommitted()))
+    }
+
+    /// The diff of the block at `height` from the keys it wrote and the
+    /// values they held before (see [`StateTree::take_writes`]), with
+    /// their current values in `tree`.
+    pub fn from_writes(
+        tree: &StateTree,
+        height: u64,
+        writes: Vec<(String, Option<Vec<u8>>)>,
+    ) -> Self {
+        let changes = writes
+            .into_iter()
+            .filter_map(|(key, before)| {
+                let after = tree.get_raw(&key);
+                (before != after).then_some(StateChange { key, before, after })
+            })
+            .collect();
+        Self { height, changes }
+    }
+
+    /// Writes every changed leaf back to its value before the block,
+    /// undoing the block in `tree`. For a block that executed but could
+    /// not be persisted.
+    pub fn revert(&self, tree: &mut StateTree) {
+        for change in &self.changes {
+            match &change.before {
+                Some(before) => tree.put_raw(&change.key, before),
+                None => tree.delete_raw(&change.key),
+            }
+        }
+    }
+}
+
+/// Something a committed block did, derived from its transactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ChainEvent {
+    /// Value moved between two accounts: a transfer, or one payout of a
+    /// batch.
+    Transfer {
+        /// Height of the block.
+        height: u64,
+        /// ID of the transaction that moved it.
+        #[serde(with = "encoding::hex_string")]
+        tx_id: String,
+        /// Debited account.
+        from: String,
+        /// Credited account.
+        to: String,
+        /// Photons moved.
+        #[serde(with = "encoding::amount")]
+        amount: u64,
+    },
+    /// The fees the block collected, before the proposer/burn split.
+    FeesCollected {
+        /// Height of the block.
+        height: u64,
+        /// The block's proposer.
+        proposer: String,
+        /// Photons collected.
+        #[serde(with = "encoding::amount")]
+        amount: u64,
+    },
+}
+
+impl ChainEvent {
+    /// The events `block` emitted, in transaction order, fees last.
+    pub fn from_block(block: &Block) -> Vec<Self> {
+        let height = block.header.height;
+        let mut events = Vec::new();
+        for tx in &block.transactions {
+            let transfer = |to: &str, amount: u64| Self::Transfer {
+                height,
+                tx_id: tx.id.clone(),
+                from: tx.sender.clone(),
+                to: to.to_string(),
+                amount,
+            };
+            match tx.tx_type {
+                TransactionType::Transfer => events.push(transfer(&tx.receiver, tx.amount.value)),
+                TransactionType::Batch => {
+                    let batch = tx
+                        .payload
+                        .as_deref()
+                        .and_then(|payload| serde_json::from_slice::<BatchPayout>(payload).ok());
+                    for entry in batch.map(|b| b.payouts).unwrap_or_default() {
+                        events.push(transfer(&entry.recipient, entry.amount));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if block.header.fees > 0 {
+            events.push(Self::FeesCollected {
+                height,
+                proposer: block.header.validator.clone(),
+                amount: block.header.fees,
+            });
+        }
+        events
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// Callbacks an indexer implements. Every callback defaults to doing
+/// nothing, so a handler only implements the ones it needs.
+///
+/// Returning an error stops delivery to this handler at the current block;
+/// the whole block is delivered again on the next attempt.
+pub trait IndexHandler: Send + Sync {
+    /// Stable name the handler's cursor is stored under. Renaming a
+    /// handler starts it over from genesis.
+    fn name(&self) -> &str;
+
+    /// A transaction of a committed block.
+    fn on_transaction_executed(&self, _tx: &ExecutedTransaction<'_>) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    /// An event a committed block emitted.
+    fn on_event(&self, _event: &ChainEvent) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    /// The state changes of a committed block.
+    fn on_state_diff(&self, _diff: &StateDiff) -> Result<(), HandlerError> {
+        Ok(())
+    }
+
+    /// A committed block, after all its other callbacks.
+    fn on_block_committed(&self, _block: &Block) -> Result<(), HandlerError> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Indexer
+// ---------------------------------------------------------------------------
+
+/// Records state diffs on the commit path and delivers committed blocks to
+/// registered handlers. Share via `Arc`.
+pub struct Indexer {
+    db: Arc<NovaDB>,
+    tree: Tree,
+    handlers: RwLock<Vec<Arc<dyn IndexHandler>>>,
+    committed: Notify,
+}
+
+impl std::fmt::Debug for Indexer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let handlers: Vec<String> = self
+            .handlers
+            .read()
+            .iter()
+            .map(|h| h.name().to_string())
+            .collect();
+        f.debug_struct("Indexer")
+            .field("handlers", &handlers)
+            .finish()
+    }
+}
+
+impl Indexer {
+    /// Opens (or creates) the indexer's tree inside `db`.
+    pub fn open(db: Arc<NovaDB>) -> DbResult<Self> {
+        let tree = db.open_tree(INDEXER_TREE)?;
+        Ok(Self {
+            db,
+            tree,
+            handlers: RwLock::new(Vec::new()),
+            committed: Notify::new(),
+        })
+    }
+
+    /// Registers `handler`. It resumes after its stored cursor, or from
+    /// genesis if it has none.
+    pub fn register(&self, handler: Arc<dyn IndexHandler>) -> Result<(), IndexerError> {
+        let mut handlers = self.handlers.write();
+        if handlers.iter().any(|h| h.name() == handler.name()) {
+            return Err(IndexerError::DuplicateHandler(handler.name().to_string()));
+        }
+        handlers.push(handler);
+        Ok(())
+    }
+
+    /// The last height `name` fully processed, if any.
+    pub fn cursor(&self, name: &str) -> DbResult<Option<u64>> {
+        self.tree
+            .get(cursor_key(name))?
+            .map(|bytes| decode_height(&bytes))
+            .transpose()
+    }
+
+    /// Moves `name`'s cursor, e.g. to `None` to re-index from genesis.
+    pub fn set_cursor(&self, name: &str, height: Option<u64>) -> DbResult<()> {
+        match height {
+            Some(height) => {
+                self.tree
+                    .insert(cursor_key(name), height.to_be_bytes().to_vec())?;
+            }
+            None => {
+                self.tree.remove(cursor_key(name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the diff of `block` from `tree`, which must hold the block's
+    /// writes uncommitted. Called on the commit path before the block is
+    /// persisted and the tree committed.
+    pub fn record_diff(&self, tree: &StateTree, block: &Block) -> DbResult<()> {
+        match StateDiff::capture(tree, block.header.height) {
+            Some(diff) => self.store_diff(&diff),
+            None => Ok(()),
+        }
+    }
+
+    /// Records a diff computed elsewhere, such as by
+    /// [`apply_block`](crate::execution::StateTransition::apply_block).
+    pub fn store_diff(&self, diff: &StateDiff) -> DbResult<()> {
+        let bytes = bincode::serialize(diff).map_err(|e| DbError::Serialization(e.to_string()))?;
+        self.tree.insert(diff_key(diff.height), bytes)?;
+        Ok(())
+    }
+
+    /// Wakes [`run`](Self::run) after a block was committed.
+    pub fn notify_committed(&self) {
+        self.committed.notify_one();
+    }
+
+    /// The recorded diff of the block at `height`, if any.
+    pub fn state_diff(&self, height: u64) -> DbResult<Option<StateDiff>> {
+        self.tree
+            .get(diff_key(height))?
+            .map(|bytes| {
+                bincode::deserialize(&bytes).map_err(|e| DbError::Serialization(e.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Delivers every committed block each handler has not processed yet.
+    /// Returns the number of blocks delivered, summed over handlers.
+    pub fn deliver(&self) -> Result<u64, IndexerError> {
+        let Some(tip) = self.db.get_latest_block_height()? else {
+            return Ok(0);
+        };
+        let handlers = self.handlers.read().clone();
+        let mut delivered = 0;
+        for handler in handlers {
+            delivered += self.catch_up(handler.as_ref(), tip)?;
+        }
+        Ok(delivered)
+    }
+
+    /// Delivers until `shutdown` fires: after every commit notification,
+    /// and every few seconds while a handler is failing.
+    pub async fn run(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) {
+        loop {
+            if let Err(e) = self.deliver() {
+                warn!(error = %e, "indexer delivery failed");
+            }
+            tokio::select! {
+                _ = self.committed.notified() => {}
+                _ = tokio::time::sleep(RETRY_INTERVAL) => {}
+                _ = shutdown.changed() => return,
+            }
+        }
+    }
+
+    /// Delivers the blocks after `handler`'s cursor up to `tip`, stopping
+    /// at the first block it fails.
+    fn catch_up(&self, handler: &dyn IndexHandler, tip: u64) -> Result<u64, IndexerError> {
+        let name = handler.name();
+        let mut next = match self.cursor(name)? {
+            // A rollback moved the tip below the cursor: redo from there.
+            Some(cursor) if cursor >= tip => {
+                if cursor > tip {
+                    self.set_cursor(name, Some(tip))?;
+                }
+                return Ok(0);
+            }
+            Some(cursor) => cursor + 1,
+            None => 0,
+        };
+        let mut delivered = 0;
+        while next <= tip {
+            let Some(block) = self.db.get_block(next)? else {
+                break;
+            };
+            let diff = self.state_diff(next)?;
+            if let Err(e) = dispatch(handler, &block, diff.as_ref()) {
+                warn!(handler = name, height = next, error = %e, "index handler failed");
+                break;
+            }
+            self.set_cursor(name, Some(next))?;
+            delivered += 1;
+            next += 1;
+        }
+        if delivered > 0 {
+            debug!(
+                handler = name,
+                cursor = next - 1,
+                delivered,
+                "indexed blocks"
+            );
+        }
+        Ok(delivered)
+    }
+}
+
+/// Runs every callback of `handler` for one block, in the documented
+/// order.
+fn dispatch(
+    handler: &dyn IndexHandler,
+    block: &Block,
+    diff: Option<&StateDiff>,
+) -> Result<(), HandlerError> {
+    for (index, transaction) in block.transactions.iter().enumerate() {
+        handler.on_transaction_executed(&ExecutedTransaction {
+            height: block.header.height,
+            index,
+            transaction,
+        })?;
+    }
+    for event in ChainEvent::from_block(block) {
+        handler.on_event(&event)?;
+    }
+    if let Some(diff) = diff {
+        handler.on_state_diff(diff)?;
+    }
+    handler.on_block_committed(block)
+}
+
+fn diff_key(height: u64) -> Vec<u8> {
+    [DIFF_PREFIX, &height.to_be_bytes()].concat()
+}
+
+fn cursor_key(name: &str) -> Vec<u8> {
+    [CURSOR_PREFIX, name.as_bytes()].concat()
+}
+
+fn decode_height(bytes: &[u8]) -> DbResult<u64> {
+    <[u8; 8]>::try_from(bytes)
+        .map(u64::from_be_bytes)
+        .map_err(|_| DbError::Serialization("malformed indexer cursor".to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::snapshot::CommittedState;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+    use parking_lot::Mutex;
+
+    /// Records what it saw; fails the first delivery of `fail_at`.
+    #[derive(Default)]
+    struct Recorder {
+        seen: Mutex<Vec<String>>,
+        fail_at: Mutex<Option<u64>>,
+    }
+
+    impl IndexHandler for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn on_transaction_executed(
+            &self,
+            tx: &ExecutedTransaction<'_>,
+        ) -> Result<(), HandlerError> {
+            self.seen
+                .lock()
+                .push(format!("tx {} #{}", tx.height, tx.index));
+            Ok(())
+        }
+
+        fn on_event(&self, event: &ChainEvent) -> Result<(), HandlerError> {
+            if let ChainEvent::Transfer { height, amount, .. } = event {
+                self.seen
+                    .lock()
+                    .push(format!("transfer {} {}", height, amount));
+            }
+            Ok(())
+        }
+
+        fn on_state_diff(&self, diff: &StateDiff) -> Result<(), HandlerError> {
+            self.seen
+                .lock()
+                .push(format!("diff {} {}", diff.height, diff.changes.len()));
+            Ok(())
+        }
+
+        fn on_block_committed(&self, block: &Block) -> Result<(), HandlerError> {
+            if *self.fail_at.lock() == Some(block.header.height) {
+                *self.fail_at.lock() = None;
+                return Err("downstream unavailable".into());
+            }
+            self.seen
+                .lock()
+                .push(format!("block {}", block.header.height));
+            Ok(())
+        }
+    }
+
+    fn transfer(nonce: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(10)
+            .nonce(nonce)
+            .timestamp(1_700_000_000_000 + nonce)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn captures_only_changed_leaves() {
+        let db = NovaDB::open_temporary().unwrap();
+        let tree = StateTree::new(db.clone());
+        let committed = CommittedState::new(db, 0, tree.root());
+        let mut tree = tree.with_committed_state(committed);
+        tree.put("nova1alice", &AccountState::with_balance(100));
+        tree.put("nova1carol", &AccountState::with_balance(5));
+        tree.commit(1);
+
+        tree.put("nova1alice", &AccountState::with_balance(40));
+        tree.put("nova1bob", &AccountState::with_balance(60));
+        tree.put("nova1carol", &AccountState::with_balance(5));
+
+        let diff = StateDiff::capture(&tree, 2).unwrap();
+        let keys: Vec<&str> = diff.changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["nova1alice", "nova1bob"]);
+        assert_eq!(diff.changes[0].account_before().unwrap().balance, 100);
+        assert_eq!(diff.changes[0].account_after().unwrap().balance, 40);
+        assert!(diff.changes[1].before.is_none());
+
+        let untracked = StateTree::new(NovaDB::open_temporary().unwrap());
+        assert!(StateDiff::capture(&untracked, 2).is_none());
+    }
+
+    #[test]
+    fn revert_restores_the_pre_block_root() {
+        let mut tree = StateTree::new(NovaDB::open_temporary().unwrap());
+        tree.put("nova1alice", &AccountState::with_balance(100));
+        let before = tree.root();
+
+        tree.track_writes();
+        tree.put("nova1alice", &AccountState::with_balance(40));
+        tree.put("nova1bob", &AccountState::with_balance(60));
+        let writes = tree.take_writes();
+        let diff = StateDiff::from_writes(&tree, 1, writes);
+        assert_ne!(tree.root(), before);
+
+        diff.revert(&mut tree);
+        assert_eq!(tree.root(), before);
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 100);
+        assert!(tree.get("nova1bob").is_none());
+    }
+
+    #[test]
+    fn delivers_in_order_and_retries_failed_blocks() {
+        let db = Arc::new(NovaDB::open_temporary().unwrap());
+        let genesis = Block::genesis();
+        let block1 = Block::new(
+            &genesis,
+            vec![transfer(1), transfer(2)],
+            "v".into(),
+            [1; 32],
+        )
+        .with_fees(20);
+        let block2 = Block::new(&block1, vec![], "v".into(), [2; 32]);
+        for block in [&genesis, &block1, &block2] {
+            db.put_block(block).unwrap();
+        }
+
+        let indexer = Indexer::open(Arc::clone(&db)).unwrap();
+        let diff = StateDiff {
+            height: 1,
+            changes: Vec::new(),
+        };
+        indexer
+            .tree
+            .insert(diff_key(1), bincode::serialize(&diff).unwrap())
+            .unwrap();
+
+        let recorder = Arc::new(Recorder::default());
+        *recorder.fail_at.lock() = Some(2);
+        indexer.register(recorder.clone()).unwrap();
+        assert!(matches!(
+            indexer.register(recorder.clone()),
+            Err(IndexerError::DuplicateHandler(_))
+        ));
+
+        // Block 2 fails, so the cursor stops at 1.
+        assert_eq!(indexer.deliver().unwrap(), 2);
+        assert_eq!(indexer.cursor("recorder").unwrap(), Some(1));
+        assert_eq!(
+            *recorder.seen.lock(),
+            [
+                "block 0",
+                "tx 1 #0",
+                "tx 1 #1",
+                "transfer 1 1000",
+                "transfer 1 1000",
+                "diff 1 0",
+                "block 1",
+            ]
+        );
+
+        // The retry delivers block 2 whole, then nothing is left.
+        recorder.seen.lock().clear();
+        assert_eq!(indexer.deliver().unwrap(), 1);
+        assert_eq!(*recorder.seen.lock(), ["block 2"]);
+        assert_eq!(indexer.deliver().unwrap(), 0);
+
+        // A rollback below the cursor pulls it back to the new tip.
+        db.rollback_to(1).unwrap();
+        assert_eq!(indexer.deliver().unwrap(), 0);
+        assert_eq!(indexer.cursor("recorder").unwrap(), Some(1));
+    }
+}
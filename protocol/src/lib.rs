@@ -25,7 +25,11 @@
 //! - **ntp** — NOVA Transfer Protocol for cross-network settlement.
 //! - **credit** — Credit scoring and reputation (the spicy part).
 //! - **storage** — Persistent storage abstraction over RocksDB.
+//! - **execution** — The state transition function: how a block changes state.
+//! - **indexer** — Commit-path subscriptions for building custom indexers.
 //! - **config** — Protocol constants and network parameters.
+//! - **encoding** — Wire encoding policy (hex, amounts) and schema versioning.
+//! - **chaos** — Fault injection, for testing against failing disks and peers.
 //!
 //! ## Design Philosophy
 //!
@@ -33,14 +37,59 @@
 //! 2. No unsafe code in crypto paths — we sleep at night.
 //! 3. Every public API is documented. Internal shame is documented too.
 //! 4. If it touches money, it has tests. Plural.
+//!
+//! ## Cargo Features
+//!
+//! | Feature   | Enables                                                            |
+//! |-----------|--------------------------------------------------------------------|
+//! | `std`     | credit, vault, DIDs, recovery, encryption, receipts                |
+//! | `zkp`     | zkp, confidential transfers (implies `std`)                        |
+//! | `storage` | storage (implies `std`)                                            |
+//! | `network` | network, execution, ntp, indexer (implies `std`, `zkp`, `storage`) |
+//! | `chaos`   | chaos: fault injection for tests (implies `network`)               |
+//!
+//! All but `chaos` are on by default. With `default-features = false` the
+//! crate is `no_std` + `alloc` and keeps only what a payment terminal needs
+//! to build and sign a transaction:
+//! `config`, `crypto::{keys, signatures, hash, wire}`, `identity::nova_id`
+//! and `transaction`. There is no clock and no OS RNG in that
+//! configuration — set the transaction timestamp explicitly and derive
+//! keys with [`NovaKeypair::from_seed`](crypto::keys::NovaKeypair::from_seed).
+//! `core::error::Error` makes the `no_std` build need Rust 1.81; the default
+//! build keeps the workspace MSRV.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+
+/// The parts of the std prelude that live in `alloc`. Modules that build
+/// without `std` glob-import this; under `std` it shadows the same items.
+mod alloc_prelude {
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+}
 
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod config;
+#[cfg(feature = "std")]
 pub mod credit;
 pub mod crypto;
+pub mod encoding;
+#[cfg(feature = "network")]
+pub mod execution;
 pub mod identity;
+#[cfg(feature = "network")]
+pub mod indexer;
+#[cfg(feature = "network")]
 pub mod network;
+#[cfg(feature = "network")]
 pub mod ntp;
+#[cfg(feature = "storage")]
 pub mod storage;
 pub mod transaction;
+#[cfg(feature = "std")]
 pub mod vault;
+#[cfg(feature = "zkp")]
 pub mod zkp;
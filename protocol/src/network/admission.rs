@@ -0,0 +1,491 @@
+//! # Adaptive Peer Admission
+//!
+//! Accepting a connection is cheap for the attacker and expensive for us:
+//! every inbound peer gets a Noise handshake, a Yamux session, and a slot
+//! in the gossipsub mesh. Under a connection flood that asymmetry is the
+//! whole attack.
+//!
+//! The [`AdmissionController`] watches the inbound connection rate. While
+//! it stays below `attack_threshold`, peers are admitted as usual. Once
+//! the rate is exceeded the node enters **attack mode**, and new peers must
+//! earn their mesh slot by presenting either:
+//!
+//! - **Proof of work** — a nonce such that
+//!   `BLAKE3(challenge || peer_id || nonce)` has `difficulty_bits` leading
+//!   zero bits. Milliseconds for one honest peer, real money for a botnet
+//!   opening thousands of connections.
+//! - **Proof of stake** — a signature over the challenge from a validator
+//!   key with at least `min_stake` bonded. Validators should never have to
+//!   grind hashes to talk to each other.
+//!
+//! Attack mode ends once the rate falls below `calm_threshold` (hysteresis
+//! prevents flapping at the boundary). Peers admitted during an attack stay
+//! admitted.
+//!
+//! ## Gossipsub integration
+//!
+//! Unadmitted peers are blacklisted in gossipsub ([`gate_gossipsub`]), which
+//! keeps them out of the mesh and drops their messages. The challenge and
+//! proof travel as [`AdmissionMessage`] values; the swarm event loop owns
+//! the transport.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use libp2p::gossipsub;
+use libp2p::PeerId;
+use parking_lot::Mutex;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::crypto::keys::{NovaPublicKey, NovaSignature};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Tunable parameters for adaptive admission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdmissionConfig {
+    /// Sliding window over which inbound connections are counted.
+    pub window_ms: u64,
+    /// Inbound connections per window that switch attack mode on.
+    pub attack_threshold: usize,
+    /// Inbound connections per window below which attack mode switches off.
+    pub calm_threshold: usize,
+    /// Required leading zero bits in the proof-of-work hash.
+    pub difficulty_bits: u8,
+    /// How long an issued challenge stays valid.
+    pub challenge_ttl_ms: u64,
+    /// Minimum validator stake (photons) accepted in lieu of work.
+    pub min_stake: u64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 10_000,
+            attack_threshold: 50,
+            calm_threshold: 20,
+            // ~1M hashes on average: well under a second on a laptop.
+            difficulty_bits: 20,
+            challenge_ttl_ms: 30_000,
+            min_stake: 1,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Wire Types
+// ---------------------------------------------------------------------------
+
+/// A server-issued challenge. Bound to one peer and valid until `expires_at`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdmissionChallenge {
+    /// Random challenge bytes.
+    pub nonce: [u8; 32],
+    /// Required leading zero bits for a proof of work.
+    pub difficulty_bits: u8,
+    /// Unix timestamp (milliseconds) after which the challenge is void.
+    pub expires_at: u64,
+}
+
+/// What a peer presents to get admitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AdmissionProof {
+    /// A nonce solving the proof-of-work puzzle.
+    Work {
+        /// The solution nonce.
+        solution: u64,
+    },
+    /// A validator signature over the challenge nonce.
+    Stake {
+        /// The validator's public key (its address is the hex encoding).
+        validator: NovaPublicKey,
+        /// Signature over [`AdmissionChallenge::nonce`].
+        signature: NovaSignature,
+    },
+}
+
+/// Admission messages exchanged between the node and a connecting peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AdmissionMessage {
+    /// Node → peer: prove yourself.
+    Challenge(AdmissionChallenge),
+    /// Peer → node: here is my proof.
+    Proof(AdmissionProof),
+}
+
+/// Admission decision for a freshly connected peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdmissionDecision {
+    /// Let the peer into the mesh.
+    Admit,
+    /// Keep the peer out until it answers this challenge.
+    Challenge(AdmissionChallenge),
+}
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Reasons an admission proof is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum AdmissionError {
+    /// No challenge was issued to this peer (or it was already used).
+    #[error("no outstanding challenge for peer")]
+    NoChallenge,
+    /// The challenge expired before the proof arrived.
+    #[error("admission challenge expired")]
+    ChallengeExpired,
+    /// The proof-of-work hash does not meet the difficulty.
+    #[error("proof of work below required difficulty")]
+    InsufficientWork,
+    /// The stake signature does not verify.
+    #[error("invalid stake signature")]
+    InvalidSignature,
+    /// The signer is not a validator with enough stake.
+    #[error("insufficient stake: have {have}, need {need}")]
+    InsufficientStake {
+        /// Stake found for the signer.
+        have: u64,
+        /// Stake required.
+        need: u64,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Proof of Work
+// ---------------------------------------------------------------------------
+
+/// Hash a candidate solution for `challenge` and `peer`.
+fn work_hash(challenge: &[u8; 32], peer: &PeerId, solution: u64) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(challenge);
+    hasher.update(&peer.to_bytes());
+    hasher.update(&solution.to_le_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// Number of leading zero bits in `hash`.
+fn leading_zero_bits(hash: &[u8; 32]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        if *byte == 0 {
+            bits += 8;
+        } else {
+            bits += byte.leading_zeros();
+            break;
+        }
+    }
+    bits
+}
+
+/// Grind a proof-of-work solution for `challenge` (client side).
+pub fn solve_challenge(challenge: &AdmissionChallenge, peer: &PeerId) -> u64 {
+    (0u64..)
+        .find(|n| {
+            leading_zero_bits(&work_hash(&challenge.nonce, peer, *n))
+                >= u32::from(challenge.difficulty_bits)
+        })
+        .expect("u64 space exhausted before finding a solution")
+}
+
+// ---------------------------------------------------------------------------
+// AdmissionController
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct AdmissionState {
+    inbound: VecDeque<u64>,
+    attack_mode: bool,
+    pending: HashMap<PeerId, AdmissionChallenge>,
+    admitted: HashSet<PeerId>,
+}
+
+/// Rate-triggered admission gate for inbound peers.
+#[derive(Debug)]
+pub struct AdmissionController {
+    config: AdmissionConfig,
+    state: Mutex<AdmissionState>,
+}
+
+impl AdmissionController {
+    /// Creates a controller in normal (non-attack) mode.
+    pub fn new(config: AdmissionConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(AdmissionState::default()),
+        }
+    }
+
+    /// Returns the controller configuration.
+    pub fn config(&self) -> &AdmissionConfig {
+        &self.config
+    }
+
+    /// Returns `true` while attack mode is active.
+    pub fn in_attack_mode(&self) -> bool {
+        self.state.lock().attack_mode
+    }
+
+    /// Returns `true` if `peer` may participate in the mesh.
+    pub fn is_admitted(&self, peer: &PeerId) -> bool {
+        self.state.lock().admitted.contains(peer)
+    }
+
+    /// Register an inbound connection and decide whether to admit the peer.
+    pub fn on_inbound(&self, peer: PeerId, now_ms: u64) -> AdmissionDecision {
+        let mut state = self.state.lock();
+
+        let horizon = now_ms.saturating_sub(self.config.window_ms);
+        while state.inbound.front().is_some_and(|t| *t < horizon) {
+            state.inbound.pop_front();
+        }
+        state.inbound.push_back(now_ms);
+
+        let rate = state.inbound.len();
+        if !state.attack_mode && rate > self.config.attack_threshold {
+            state.attack_mode = true;
+            tracing::warn!(
+                rate,
+                "inbound connection flood detected, entering attack mode"
+            );
+        } else if state.attack_mode && rate < self.config.calm_threshold {
+            state.attack_mode = false;
+            state.pending.clear();
+            tracing::info!(rate, "inbound connection rate normal, leaving attack mode");
+        }
+
+        if !state.attack_mode || state.admitted.contains(&peer) {
+            state.admitted.insert(peer);
+            return AdmissionDecision::Admit;
+        }
+
+        let mut nonce = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let challenge = AdmissionChallenge {
+            nonce,
+            difficulty_bits: self.config.difficulty_bits,
+            expires_at: now_ms + self.config.challenge_ttl_ms,
+        };
+        state.pending.insert(peer, challenge.clone());
+        AdmissionDecision::Challenge(challenge)
+    }
+
+    /// Check a peer's answer to its challenge. On success the peer is admitted.
+    ///
+    /// `stake_of` maps a validator address (hex public key) to its bonded
+    /// stake — typically [`ValidatorSet::stake_of`](super::consensus::ValidatorSet::stake_of).
+    pub fn verify(
+        &self,
+        peer: PeerId,
+        proof: &AdmissionProof,
+        now_ms: u64,
+        stake_of: impl Fn(&str) -> u64,
+    ) -> Result<(), AdmissionError> {
+        let mut state = self.state.lock();
+
+        // Single use: a failed attempt needs a fresh challenge.
+        let challenge = state
+            .pending
+            .remove(&peer)
+            .ok_or(AdmissionError::NoChallenge)?;
+        if now_ms > challenge.expires_at {
+            return Err(AdmissionError::ChallengeExpired);
+        }
+
+        match proof {
+            AdmissionProof::Work { solution } => {
+                let hash = work_hash(&challenge.nonce, &peer, *solution);
+                if leading_zero_bits(&hash) < u32::from(challenge.difficulty_bits) {
+                    return Err(AdmissionError::InsufficientWork);
+                }
+            }
+            AdmissionProof::Stake {
+                validator,
+                signature,
+            } => {
+                if !validator.verify(&challenge.nonce, signature) {
+                    return Err(AdmissionError::InvalidSignature);
+                }
+                let have = stake_of(&validator.to_hex());
+                if have < self.config.min_stake {
+                    return Err(AdmissionError::InsufficientStake {
+                        have,
+                        need: self.config.min_stake,
+                    });
+                }
+            }
+        }
+
+        state.admitted.insert(peer);
+        Ok(())
+    }
+
+    /// Forget a disconnected peer's pending challenge.
+    pub fn on_disconnect(&self, peer: &PeerId) {
+        self.state.lock().pending.remove(peer);
+    }
+}
+
+impl Default for AdmissionController {
+    fn default() -> Self {
+        Self::new(AdmissionConfig::default())
+    }
+}
+
+/// Keep unadmitted peers out of the gossipsub mesh.
+///
+/// Blacklisted peers are not grafted and their messages are ignored.
+/// Call after [`AdmissionController::on_inbound`] and again after a
+/// successful [`AdmissionController::verify`].
+pub fn gate_gossipsub(
+    gossipsub: &mut gossipsub::Behaviour,
+    controller: &AdmissionController,
+    peer: &PeerId,
+) {
+    if controller.is_admitted(peer) {
+        gossipsub.remove_blacklisted_peer(peer);
+    } else {
+        gossipsub.blacklist_peer(peer);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::NovaKeypair;
+
+    fn random_peer() -> PeerId {
+        PeerId::from(libp2p::identity::Keypair::generate_ed25519().public())
+    }
+
+    fn easy_config() -> AdmissionConfig {
+        AdmissionConfig {
+            attack_threshold: 3,
+            calm_threshold: 2,
+            difficulty_bits: 8,
+            min_stake: 1_000,
+            ..Default::default()
+        }
+    }
+
+    /// Push the controller into attack mode and return a challenged peer.
+    fn under_attack(ctrl: &AdmissionController) -> (PeerId, AdmissionChallenge) {
+        for _ in 0..3 {
+            ctrl.on_inbound(random_peer(), 0);
+        }
+        let peer = random_peer();
+        match ctrl.on_inbound(peer, 0) {
+            AdmissionDecision::Challenge(c) => (peer, c),
+            AdmissionDecision::Admit => panic!("expected a challenge in attack mode"),
+        }
+    }
+
+    #[test]
+    fn normal_rate_admits_everyone() {
+        let ctrl = AdmissionController::new(easy_config());
+        for _ in 0..3 {
+            let peer = random_peer();
+            assert_eq!(ctrl.on_inbound(peer, 0), AdmissionDecision::Admit);
+            assert!(ctrl.is_admitted(&peer));
+        }
+        assert!(!ctrl.in_attack_mode());
+    }
+
+    #[test]
+    fn flood_requires_work() {
+        let ctrl = AdmissionController::new(easy_config());
+        let (peer, challenge) = under_attack(&ctrl);
+        assert!(ctrl.in_attack_mode());
+        assert!(!ctrl.is_admitted(&peer));
+
+        let solution = solve_challenge(&challenge, &peer);
+        ctrl.verify(peer, &AdmissionProof::Work { solution }, 1, |_| 0)
+            .unwrap();
+        assert!(ctrl.is_admitted(&peer));
+    }
+
+    #[test]
+    fn bad_or_replayed_work_rejected() {
+        let ctrl = AdmissionController::new(easy_config());
+        let (peer, challenge) = under_attack(&ctrl);
+
+        // Find a nonce that definitely misses the target.
+        let bad = (0u64..)
+            .find(|n| leading_zero_bits(&work_hash(&challenge.nonce, &peer, *n)) < 8)
+            .unwrap();
+        let result = ctrl.verify(peer, &AdmissionProof::Work { solution: bad }, 1, |_| 0);
+        assert_eq!(result, Err(AdmissionError::InsufficientWork));
+
+        // The challenge was consumed by the failed attempt.
+        let solution = solve_challenge(&challenge, &peer);
+        let replay = ctrl.verify(peer, &AdmissionProof::Work { solution }, 1, |_| 0);
+        assert_eq!(replay, Err(AdmissionError::NoChallenge));
+    }
+
+    #[test]
+    fn validators_skip_the_work_with_stake() {
+        let ctrl = AdmissionController::new(easy_config());
+        let (peer, challenge) = under_attack(&ctrl);
+
+        let kp = NovaKeypair::generate();
+        let address = kp.public_key().to_hex();
+        let proof = AdmissionProof::Stake {
+            validator: kp.public_key(),
+            signature: kp.sign(&challenge.nonce),
+        };
+
+        ctrl.verify(peer, &proof, 1, |a| if a == address { 5_000 } else { 0 })
+            .unwrap();
+        assert!(ctrl.is_admitted(&peer));
+    }
+
+    #[test]
+    fn unstaked_signer_rejected() {
+        let ctrl = AdmissionController::new(easy_config());
+        let (peer, challenge) = under_attack(&ctrl);
+
+        let kp = NovaKeypair::generate();
+        let proof = AdmissionProof::Stake {
+            validator: kp.public_key(),
+            signature: kp.sign(&challenge.nonce),
+        };
+        let result = ctrl.verify(peer, &proof, 1, |_| 10);
+        assert!(matches!(
+            result,
+            Err(AdmissionError::InsufficientStake { have: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn expired_challenge_rejected() {
+        let ctrl = AdmissionController::new(easy_config());
+        let (peer, challenge) = under_attack(&ctrl);
+        let solution = solve_challenge(&challenge, &peer);
+
+        let late = challenge.expires_at + 1;
+        let result = ctrl.verify(peer, &AdmissionProof::Work { solution }, late, |_| 0);
+        assert_eq!(result, Err(AdmissionError::ChallengeExpired));
+    }
+
+    #[test]
+    fn attack_mode_ends_when_rate_drops() {
+        let ctrl = AdmissionController::new(easy_config());
+        under_attack(&ctrl);
+
+        // Well past the window: the old connections age out.
+        let later = ctrl.config().window_ms * 2;
+        assert_eq!(
+            ctrl.on_inbound(random_peer(), later),
+            AdmissionDecision::Admit
+        );
+        assert!(!ctrl.in_attack_mode());
+    }
+}
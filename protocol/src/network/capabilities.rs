@@ -0,0 +1,196 @@
+//! # Node Capabilities
+//!
+//! Not every node runs the same build or configuration. SDKs and peers ask
+//! a node what it supports instead of guessing from its version:
+//!
+//! - over JSON-RPC, `nova_getCapabilities` returns a [`Capabilities`];
+//! - over libp2p, the identify protocol's agent string carries the same
+//!   list ([`Capabilities::agent_version`]), so peers learn it on connect.
+//!
+//! A capability is a short snake_case name. [`Capabilities::compiled`]
+//! holds what the protocol build provides; the node adds what its runtime
+//! configuration switches on. A name is either listed or not — clients
+//! treat anything absent (including every name a node too old to know it
+//! never lists) as unsupported, so older nodes need no special casing
+//! beyond answering the RPC method itself.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::PROTOCOL_VERSION;
+
+/// Prefix of the capability list inside an identify agent string.
+const AGENT_CAPS_PREFIX: &str = "caps=";
+
+/// What a node supports, as reported to clients and peers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Protocol version the node speaks.
+    pub protocol_version: String,
+    /// Names of the supported capabilities, sorted.
+    pub features: BTreeSet<String>,
+}
+
+impl Capabilities {
+    /// `Batch` transactions paying many recipients at once.
+    pub const BATCH_PAYOUTS: &'static str = "batch_payouts";
+    /// CBOR-framed RPC and events over a WebSocket.
+    pub const BINARY_RPC: &'static str = "binary_rpc";
+    /// JSON-RPC batch requests (an array of calls in one POST).
+    pub const BATCH_RPC: &'static str = "batch_rpc";
+    /// Compact block relay (headers plus short transaction IDs).
+    pub const COMPACT_BLOCKS: &'static str = "compact_blocks";
+    /// `ConfidentialTransfer` execution; needs a balance verifier.
+    pub const CONFIDENTIAL_TRANSFERS: &'static str = "confidential_transfers";
+    /// The `nova_dev*` RPC methods of a dev chain.
+    pub const DEV_RPC: &'static str = "dev_rpc";
+    /// Encrypted transaction payloads.
+    pub const ENCRYPTED_PAYLOADS: &'static str = "encrypted_payloads";
+    /// `NetworkKeyRotation` transactions.
+    pub const NETWORK_KEY_ROTATION: &'static str = "network_key_rotation";
+    /// `nova_getNonce`, which accounts for pending transactions.
+    pub const NONCE_RPC: &'static str = "nonce_rpc";
+    /// Direct NTP messaging over `/nova/ntp/1.0`.
+    pub const NTP_MESSAGING: &'static str = "ntp_messaging";
+    /// QUIC transport next to TCP.
+    pub const QUIC: &'static str = "quic";
+    /// `SpendingPolicyUpdate` transactions and policy enforcement.
+    pub const SPENDING_POLICIES: &'static str = "spending_policies";
+    /// Transactions whose fee a second account pays.
+    pub const SPONSORED_FEES: &'static str = "sponsored_fees";
+    /// `nova_cancelTransaction` for pending transactions.
+    pub const TX_CANCELLATION: &'static str = "tx_cancellation";
+    /// Groth16 balance proofs (the `zkp` feature).
+    pub const ZKP: &'static str = "zkp";
+
+    /// An empty set for the current protocol version.
+    pub fn new() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            features: BTreeSet::new(),
+        }
+    }
+
+    /// What every node built from this crate supports, regardless of its
+    /// configuration.
+    pub fn compiled() -> Self {
+        let mut caps = Self::new();
+        for name in [
+            Self::BATCH_PAYOUTS,
+            Self::ENCRYPTED_PAYLOADS,
+            Self::NETWORK_KEY_ROTATION,
+            Self::NONCE_RPC,
+            Self::SPENDING_POLICIES,
+            Self::SPONSORED_FEES,
+            Self::TX_CANCELLATION,
+        ] {
+            caps.features.insert(name.to_string());
+        }
+        if cfg!(feature = "zkp") {
+            caps.features.insert(Self::ZKP.to_string());
+        }
+        caps
+    }
+
+    /// Adds `name` if `enabled`, for capabilities that depend on runtime
+    /// configuration.
+    pub fn with(mut self, name: &str, enabled: bool) -> Self {
+        if enabled {
+            self.features.insert(name.to_string());
+        }
+        self
+    }
+
+    /// Returns `true` if the node supports `name`.
+    pub fn supports(&self, name: &str) -> bool {
+        self.features.contains(name)
+    }
+
+    /// The identify agent string for a node running `client` (e.g.
+    /// `nova-node/0.1.0`): `"<client> proto/<version> caps=<a>,<b>,..."`.
+    pub fn agent_version(&self, client: &str) -> String {
+        format!(
+            "{} proto/{} {}{}",
+            client,
+            self.protocol_version,
+            AGENT_CAPS_PREFIX,
+            self.features.iter().cloned().collect::<Vec<_>>().join(",")
+        )
+    }
+
+    /// Parses an agent string written by [`agent_version`](Self::agent_version).
+    ///
+    /// Returns `None` for agents that do not advertise capabilities, such
+    /// as older NOVA nodes or other libp2p software.
+    pub fn from_agent_version(agent: &str) -> Option<Self> {
+        let mut protocol_version = None;
+        let mut features = None;
+        for part in agent.split_whitespace() {
+            if let Some(version) = part.strip_prefix("proto/") {
+                protocol_version = Some(version.to_string());
+            } else if let Some(list) = part.strip_prefix(AGENT_CAPS_PREFIX) {
+                features = Some(
+                    list.split(',')
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                );
+            }
+        }
+        Some(Self {
+            protocol_version: protocol_version?,
+            features: features?,
+        })
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.features.iter().map(String::as_str).collect();
+        write!(
+            f,
+            "protocol {}: {}",
+            self.protocol_version,
+            names.join(", ")
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_flags_extend_the_compiled_set() {
+        let caps = Capabilities::compiled()
+            .with(Capabilities::QUIC, true)
+            .with(Capabilities::DEV_RPC, false);
+        assert!(caps.supports(Capabilities::TX_CANCELLATION));
+        assert!(caps.supports(Capabilities::QUIC));
+        assert!(!caps.supports(Capabilities::DEV_RPC));
+        assert!(!caps.supports(Capabilities::BATCH_RPC));
+        assert!(!caps.supports(Capabilities::COMPACT_BLOCKS));
+    }
+
+    #[test]
+    fn agent_string_round_trips() {
+        let caps = Capabilities::compiled().with(Capabilities::NTP_MESSAGING, true);
+        let agent = caps.agent_version("nova-node/0.1.0");
+        assert!(agent.starts_with("nova-node/0.1.0 proto/"));
+        assert_eq!(Capabilities::from_agent_version(&agent), Some(caps));
+
+        let empty = Capabilities::new();
+        assert_eq!(
+            Capabilities::from_agent_version(&empty.agent_version("x")),
+            Some(empty)
+        );
+        assert_eq!(Capabilities::from_agent_version("rust-libp2p/0.53"), None);
+        assert_eq!(Capabilities::from_agent_version("/nova/1"), None);
+    }
+}
@@ -0,0 +1,313 @@
+//! # Clock Skew Detection
+//!
+//! Timestamp rules only work if validators agree on what time it is. A
+//! validator whose clock has wandered off will stamp blocks that honest
+//! peers reject — or worse, accept blocks it should have rejected.
+//!
+//! [`ClockSkewMonitor`] estimates this node's clock offset from timestamp
+//! samples taken against peers (and, optionally, NTP servers) and reports
+//! the median. The median is the point: a minority of lying or broken peers
+//! cannot drag it anywhere.
+//!
+//! ## Samples
+//!
+//! Two kinds of samples are accepted:
+//!
+//! - **Round-trip exchanges** (ping-style): we note when we asked, the
+//!   remote's timestamp, and when the answer arrived. The offset is measured
+//!   against the midpoint, which cancels symmetric network latency — the
+//!   same trick NTP uses.
+//! - **One-way observations** (identify/handshake metadata): the remote's
+//!   timestamp against our receive time. Biased by one-way latency, which
+//!   is small next to the skew threshold.
+//!
+//! Each source keeps only its latest sample, so a chatty peer gets no more
+//! say than a quiet one. Samples older than `sample_ttl_ms` are ignored.
+//!
+//! Offsets are signed: positive means the network is *ahead* of us.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Tunable parameters for clock skew detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockConfig {
+    /// Maximum tolerated absolute offset before the node counts as drifted.
+    pub max_drift_ms: u64,
+    /// Minimum number of distinct sources before an estimate is trusted.
+    pub min_sources: usize,
+    /// Samples older than this are excluded from the estimate.
+    pub sample_ttl_ms: u64,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            max_drift_ms: crate::config::MAX_CLOCK_SKEW.as_millis() as u64,
+            min_sources: 3,
+            sample_ttl_ms: crate::config::NTP_SYNC_INTERVAL.as_millis() as u64 * 10,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A single offset measurement against one source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockSample {
+    /// Remote clock minus local clock, in milliseconds.
+    pub offset_ms: i64,
+    /// Round-trip time of the exchange (0 for one-way observations).
+    pub rtt_ms: u64,
+    /// Local Unix timestamp (milliseconds) when the sample was taken.
+    pub observed_at: u64,
+}
+
+/// Outcome of a clock check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockStatus {
+    /// Not enough fresh samples to say anything.
+    Unknown {
+        /// Number of fresh sources currently available.
+        sources: usize,
+    },
+    /// Median offset is within the tolerated drift.
+    Synced {
+        /// Median offset in milliseconds.
+        offset_ms: i64,
+        /// Number of sources behind the estimate.
+        sources: usize,
+    },
+    /// Median offset exceeds the tolerated drift.
+    Drifted {
+        /// Median offset in milliseconds.
+        offset_ms: i64,
+        /// Number of sources behind the estimate.
+        sources: usize,
+    },
+}
+
+impl ClockStatus {
+    /// Returns `true` unless the clock is known to have drifted.
+    ///
+    /// An unknown status does not block proposing: a fresh devnet with no
+    /// peers would otherwise never produce a block.
+    pub fn can_propose(&self) -> bool {
+        !matches!(self, Self::Drifted { .. })
+    }
+
+    /// Median offset, if an estimate exists.
+    pub fn offset_ms(&self) -> Option<i64> {
+        match self {
+            Self::Unknown { .. } => None,
+            Self::Synced { offset_ms, .. } | Self::Drifted { offset_ms, .. } => Some(*offset_ms),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ClockSkewMonitor
+// ---------------------------------------------------------------------------
+
+/// Peer-median clock offset estimator.
+///
+/// Thread-safe: samples arrive from the networking layer while the
+/// consensus loop and metrics exporter read the estimate.
+#[derive(Debug)]
+pub struct ClockSkewMonitor {
+    config: ClockConfig,
+    samples: RwLock<HashMap<String, ClockSample>>,
+}
+
+impl ClockSkewMonitor {
+    /// Creates a monitor with no samples.
+    pub fn new(config: ClockConfig) -> Self {
+        Self {
+            config,
+            samples: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the monitor configuration.
+    pub fn config(&self) -> &ClockConfig {
+        &self.config
+    }
+
+    /// Record a round-trip exchange with `source`.
+    ///
+    /// `local_sent_ms` and `local_recv_ms` bracket the request on our clock;
+    /// `remote_ms` is the remote's timestamp from the reply.
+    pub fn record_exchange(
+        &self,
+        source: &str,
+        local_sent_ms: u64,
+        remote_ms: u64,
+        local_recv_ms: u64,
+    ) {
+        let rtt_ms = local_recv_ms.saturating_sub(local_sent_ms);
+        let midpoint = local_sent_ms + rtt_ms / 2;
+        let offset_ms = remote_ms as i64 - midpoint as i64;
+        self.insert(source, offset_ms, rtt_ms, local_recv_ms);
+    }
+
+    /// Record a one-way timestamp observation from `source`.
+    pub fn record_observation(&self, source: &str, remote_ms: u64, local_recv_ms: u64) {
+        let offset_ms = remote_ms as i64 - local_recv_ms as i64;
+        self.insert(source, offset_ms, 0, local_recv_ms);
+    }
+
+    /// Forget a source (e.g., on peer disconnect).
+    pub fn remove_source(&self, source: &str) {
+        self.samples.write().remove(source);
+    }
+
+    /// Number of sources with a sample, fresh or not.
+    pub fn source_count(&self) -> usize {
+        self.samples.read().len()
+    }
+
+    /// Median offset across fresh samples, with the number of sources used.
+    ///
+    /// Returns `None` if fewer than `min_sources` fresh samples exist.
+    pub fn estimate(&self, now_ms: u64) -> Option<(i64, usize)> {
+        let mut offsets = self.fresh_offsets(now_ms);
+        if offsets.is_empty() || offsets.len() < self.config.min_sources {
+            return None;
+        }
+
+        offsets.sort_unstable();
+        let mid = offsets.len() / 2;
+        let median = if offsets.len() % 2 == 0 {
+            (offsets[mid - 1] + offsets[mid]) / 2
+        } else {
+            offsets[mid]
+        };
+        Some((median, offsets.len()))
+    }
+
+    /// Classify the current estimate against `max_drift_ms`.
+    pub fn status(&self, now_ms: u64) -> ClockStatus {
+        match self.estimate(now_ms) {
+            None => ClockStatus::Unknown {
+                sources: self.fresh_offsets(now_ms).len(),
+            },
+            Some((offset_ms, sources)) if offset_ms.unsigned_abs() > self.config.max_drift_ms => {
+                ClockStatus::Drifted { offset_ms, sources }
+            }
+            Some((offset_ms, sources)) => ClockStatus::Synced { offset_ms, sources },
+        }
+    }
+
+    /// Our best guess at network time: local time corrected by the median
+    /// offset, or local time unchanged if there is no estimate.
+    pub fn network_time_ms(&self, now_ms: u64) -> u64 {
+        match self.estimate(now_ms) {
+            Some((offset, _)) => (now_ms as i64).saturating_add(offset).max(0) as u64,
+            None => now_ms,
+        }
+    }
+
+    fn insert(&self, source: &str, offset_ms: i64, rtt_ms: u64, observed_at: u64) {
+        self.samples.write().insert(
+            source.to_string(),
+            ClockSample {
+                offset_ms,
+                rtt_ms,
+                observed_at,
+            },
+        );
+    }
+
+    fn fresh_offsets(&self, now_ms: u64) -> Vec<i64> {
+        self.samples
+            .read()
+            .values()
+            .filter(|s| now_ms.saturating_sub(s.observed_at) <= self.config.sample_ttl_ms)
+            .map(|s| s.offset_ms)
+            .collect()
+    }
+}
+
+impl Default for ClockSkewMonitor {
+    fn default() -> Self {
+        Self::new(ClockConfig::default())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    #[test]
+    fn exchange_offset_uses_rtt_midpoint() {
+        let monitor = ClockSkewMonitor::new(ClockConfig {
+            min_sources: 1,
+            ..Default::default()
+        });
+
+        // Sent at NOW, replied at NOW+100 local; remote said NOW+250.
+        // Midpoint is NOW+50, so the remote is 200ms ahead.
+        monitor.record_exchange("peer-a", NOW, NOW + 250, NOW + 100);
+        assert_eq!(monitor.estimate(NOW + 100), Some((200, 1)));
+    }
+
+    #[test]
+    fn median_ignores_outliers() {
+        let monitor = ClockSkewMonitor::default();
+        monitor.record_observation("a", NOW + 10, NOW);
+        monitor.record_observation("b", NOW - 20, NOW);
+        monitor.record_observation("c", NOW + 30, NOW);
+        monitor.record_observation("liar", NOW + 3_600_000, NOW);
+
+        let (offset, sources) = monitor.estimate(NOW).unwrap();
+        assert_eq!(sources, 4);
+        assert_eq!(offset, 20, "median of -20, 10, 30, 3.6M");
+        assert!(matches!(monitor.status(NOW), ClockStatus::Synced { .. }));
+    }
+
+    #[test]
+    fn drift_beyond_threshold_blocks_proposing() {
+        let monitor = ClockSkewMonitor::default();
+        for peer in ["a", "b", "c"] {
+            monitor.record_observation(peer, NOW + 2_000, NOW);
+        }
+
+        let status = monitor.status(NOW);
+        assert!(matches!(
+            status,
+            ClockStatus::Drifted {
+                offset_ms: 2_000,
+                ..
+            }
+        ));
+        assert!(!status.can_propose());
+        assert_eq!(monitor.network_time_ms(NOW), NOW + 2_000);
+    }
+
+    #[test]
+    fn too_few_or_stale_samples_are_unknown() {
+        let monitor = ClockSkewMonitor::default();
+        monitor.record_observation("a", NOW + 5_000, NOW);
+        monitor.record_observation("b", NOW + 5_000, NOW);
+        assert_eq!(monitor.status(NOW), ClockStatus::Unknown { sources: 2 });
+        assert!(monitor.status(NOW).can_propose());
+
+        monitor.record_observation("c", NOW + 5_000, NOW);
+        let later = NOW + monitor.config().sample_ttl_ms + 1;
+        assert_eq!(monitor.status(later), ClockStatus::Unknown { sources: 0 });
+    }
+}
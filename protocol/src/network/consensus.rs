@@ -14,10 +14,12 @@
 //! 2. **Block signing (PoA)**: Only validators in the active authority set can
 //!    sign blocks. The set is updated at epoch boundaries (every N blocks).
 //!
-//! 3. **BFT finality**: A block is finalized when 2/3 + 1 of the active
-//!    validator set has voted for it. This follows the standard PBFT quorum
-//!    threshold — fewer votes and you can't guarantee safety under Byzantine
-//!    faults.
+//! 3. **BFT finality**: A block is finalized when validators holding at
+//!    least 2/3 of the active stake have precommitted it. Votes are weighted by
+//!    the stake snapshot taken when the previous epoch's last block was
+//!    committed, so a crowd of small validators cannot outvote the stake
+//!    that secures the chain, and stake moving mid-epoch cannot change who
+//!    counts.
 //!
 //! ## Consensus Round State Machine
 //!
@@ -31,14 +33,46 @@
 //! block within the timeout, the round advances and the next proposer takes
 //! over. Liveness is guaranteed as long as > 2/3 of validators are honest
 //! and online.
+//!
+//! ## Proposals and Locking
+//!
+//! The proposer wraps its block in a signed [`Proposal`] and gossips it on
+//! the proposals topic, so every validator votes on the same candidate.
+//! Safety under asynchrony comes from the lock-on-prevote rule:
+//!
+//! - When a validator sees prevotes from 2/3 of the stake for block `B` in
+//!   round `r` (a *polka*), it locks on `B` at round `r`.
+//! - A locked validator only prevotes for `B` in later rounds — unless a
+//!   proposal carries a [`Justification`]: a polka for the proposed block
+//!   from a round at or after the lock. That proves the rest of the network
+//!   moved on, so unlocking cannot fork the chain.
+//! - A validator precommits `B` only in the round it saw `B`'s polka, and
+//!   only once per round. A block is final once precommits from 2/3 of the
+//!   stake agree on it in one round.
+//! - Locks are released when a block is finalized at that height.
+//!
+//! Committing on the polka alone would fork: a validator that prevoted `B`
+//! but never saw its polka stays unlocked and may prevote `B'` next round,
+//! while the one validator that saw the polka has already committed `B`.
+//! With precommits, a commit for `B` needs 2/3 of the stake locked on it,
+//! and a locked validator does not prevote `B'` without a later polka.
+//! Prevotes and precommits sign different messages, so neither can stand
+//! in for the other.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
-use crate::storage::{Block, BlockHeader};
+use crate::crypto::wire::{BlockHash, SignatureBytes};
+use crate::encoding;
+use crate::network::halt::{HaltError, HaltGuard};
+use crate::network::validation::{BlockValidationError, BlockValidator};
+use crate::storage::block::{compute_merkle_root, HeaderExtensions, LEGACY_HEADER_VERSION};
+use crate::storage::{Block, BlockHeader, CommitSignature, StateSnapshot, StateTree};
+use crate::transaction::batch_verification::BlockSignatureError;
 use crate::transaction::Transaction;
 
 // ---------------------------------------------------------------------------
@@ -65,6 +99,10 @@ pub struct ConsensusConfig {
     pub epoch_length: u64,
     /// Maximum number of transactions per block.
     pub max_block_transactions: usize,
+    /// Maximum total weight of a block's transactions, in bytes (see
+    /// [`tx_weight`](crate::transaction::limits::tx_weight)).
+    #[serde(default = "default_max_block_bytes")]
+    pub max_block_bytes: usize,
     /// Timeout for a consensus round before advancing to the next proposer,
     /// in milliseconds.
     pub round_timeout_ms: u64,
@@ -79,11 +117,16 @@ impl Default for ConsensusConfig {
             stake_requirement: 1_000_000_000, // 10 NOVA (at 8 decimals)
             epoch_length: 100,
             max_block_transactions: 1_000,
+            max_block_bytes: default_max_block_bytes(),
             round_timeout_ms: 5_000,
         }
     }
 }
 
+fn default_max_block_bytes() -> usize {
+    crate::config::MAX_BLOCK_SIZE_BYTES
+}
+
 // ---------------------------------------------------------------------------
 // Validator Info & Set
 // ---------------------------------------------------------------------------
@@ -92,8 +135,10 @@ impl Default for ConsensusConfig {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidatorInfo {
     /// Hex-encoded public key of the validator.
+    #[serde(with = "encoding::hex_string")]
     pub address: String,
     /// Amount staked, in photons.
+    #[serde(with = "encoding::amount")]
     pub stake: u64,
     /// Whether this validator is currently active (online and participating).
     pub active: bool,
@@ -103,14 +148,26 @@ pub struct ValidatorInfo {
     pub blocks_voted: u64,
 }
 
+/// State tree key under which the validator set is persisted.
+///
+/// Not a valid NOVA address, so it can never collide with an account.
+pub const VALIDATOR_SET_STATE_KEY: &str = "nova:system:validator_set";
+
 /// The current set of active validators, sorted by stake (descending).
 ///
 /// The validator set determines who can propose and vote on blocks.
 /// It is recalculated at each epoch boundary based on the current
 /// stake distribution in the state tree.
-#[derive(Debug, Clone, Default)]
+///
+/// ## Canonical Order
+///
+/// Proposer selection indexes into this list, so every node must hold it
+/// in exactly the same order. Ties in stake are broken by address, making
+/// the order a pure function of the contents — insertion history does not
+/// matter. The persisted form is sorted by address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ValidatorSet {
-    /// Validators sorted by stake, highest first.
+    /// Validators sorted by stake (highest first), then by address.
     validators: Vec<ValidatorInfo>,
 }
 
@@ -122,17 +179,83 @@ impl ValidatorSet {
         }
     }
 
-    /// Adds a validator to the set. The set is re-sorted after insertion
-    /// to maintain stake-descending order.
+    /// Adds a validator to the set, or updates its stake if already present.
+    /// The set is re-sorted to maintain canonical order.
     pub fn add_validator(&mut self, address: String, stake: u64) {
-        self.validators.push(ValidatorInfo {
-            address,
-            stake,
-            active: true,
-            blocks_proposed: 0,
-            blocks_voted: 0,
+        match self.validators.iter_mut().find(|v| v.address == address) {
+            Some(existing) => existing.stake = stake,
+            None => self.validators.push(ValidatorInfo {
+                address,
+                stake,
+                active: true,
+                blocks_proposed: 0,
+                blocks_voted: 0,
+            }),
+        }
+        self.sort_canonical();
+    }
+
+    /// Builds a set from arbitrary validator records, in canonical order.
+    ///
+    /// Duplicate addresses keep the first occurrence.
+    pub fn from_validators(validators: Vec<ValidatorInfo>) -> Self {
+        let mut set = Self::new();
+        for info in validators {
+            if !set.validators.iter().any(|v| v.address == info.address) {
+                set.validators.push(info);
+            }
+        }
+        set.sort_canonical();
+        set
+    }
+
+    /// Loads the validator set persisted in the state tree.
+    ///
+    /// Returns `None` if no set has been stored (fresh chain) or the stored
+    /// bytes do not decode.
+    pub fn load_from_state(tree: &StateTree) -> Option<Self> {
+        let bytes = tree.get_raw(VALIDATOR_SET_STATE_KEY)?;
+        let validators: Vec<ValidatorInfo> = bincode::deserialize(&bytes).ok()?;
+        Some(Self::from_validators(validators))
+    }
+
+    /// Loads the validator set as of a committed state snapshot.
+    pub fn load_from_snapshot(snapshot: &StateSnapshot<'_>) -> Option<Self> {
+        let bytes = snapshot.get_raw(VALIDATOR_SET_STATE_KEY)?;
+        let validators: Vec<ValidatorInfo> = bincode::deserialize(&bytes).ok()?;
+        Some(Self::from_validators(validators))
+    }
+
+    /// Persists the validator set into the state tree, sorted by address.
+    ///
+    /// The write changes the state root, so it must happen as part of
+    /// deterministic state transitions (genesis, epoch boundaries) only.
+    pub fn store_in_state(&self, tree: &mut StateTree) {
+        let mut by_address = self.validators.clone();
+        by_address.sort_by(|a, b| a.address.cmp(&b.address));
+        let bytes =
+            bincode::serialize(&by_address).expect("validator set serialization should not fail");
+        tree.put_raw(VALIDATOR_SET_STATE_KEY, &bytes);
+    }
+
+    fn sort_canonical(&mut self) {
+        self.validators.sort_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.address.cmp(&b.address))
         });
-        self.validators.sort_by(|a, b| b.stake.cmp(&a.stake));
+    }
+
+    /// Moves a validator in or out of the active set without touching its
+    /// stake. Returns `false` if the address is unknown.
+    pub fn set_active(&mut self, address: &str, active: bool) -> bool {
+        match self.validators.iter_mut().find(|v| v.address == address) {
+            Some(info) => {
+                info.active = active;
+                true
+            }
+            None => false,
+        }
     }
 
     /// Removes a validator by address.
@@ -195,12 +318,40 @@ impl ValidatorSet {
             .sum()
     }
 
+    /// Minimum voting stake for finality: at least 2/3 of the total active
+    /// stake, rounded up.
+    pub fn stake_quorum(&self) -> u64 {
+        let total = self.total_stake() as u128;
+        ((2 * total + 2) / 3) as u64
+    }
+
+    /// Returns `true` if `voting_stake` (with `voters` distinct active voters)
+    /// is enough to finalize a block.
+    ///
+    /// A set with no stake at all (pure authority mode) falls back to the
+    /// validator-count threshold — otherwise it could never finalize.
+    pub fn has_quorum(&self, voting_stake: u64, voters: usize) -> bool {
+        if self.total_stake() == 0 {
+            return voters > 0 && voters >= self.quorum_threshold();
+        }
+        voting_stake >= self.stake_quorum()
+    }
+
     /// Checks if an address is in the active validator set.
     pub fn contains(&self, address: &str) -> bool {
         self.validators
             .iter()
             .any(|v| v.address == address && v.active)
     }
+
+    /// Returns the stake bonded by an active validator, or 0 if the address
+    /// is unknown or inactive.
+    pub fn stake_of(&self, address: &str) -> u64 {
+        self.validators
+            .iter()
+            .find(|v| v.address == address && v.active)
+            .map_or(0, |v| v.stake)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -240,39 +391,60 @@ impl ConsensusRound {
 // Vote
 // ---------------------------------------------------------------------------
 
+/// Which phase of a round a [`Vote`] is cast in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VoteKind {
+    /// A vote for a proposal; 2/3 of the stake makes a polka.
+    Prevote,
+    /// A vote to commit a block after seeing its polka; 2/3 of the stake
+    /// finalizes it.
+    Precommit,
+}
+
 /// A validator's vote on a proposed block.
 ///
 /// Votes are broadcast during the Prevote and Precommit phases. The
-/// signature covers `(block_hash || round)` to prevent replay across rounds.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// signature covers the block hash and the round to prevent replay across
+/// rounds, and a prevote's is domain-separated from a precommit's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Vote {
     /// Hex-encoded public key of the voting validator.
     pub validator: String,
     /// Hash of the block being voted on.
-    pub block_hash: [u8; 32],
-    /// Ed25519 signature over `(block_hash || round.to_le_bytes())`.
+    pub block_hash: BlockHash,
+    /// Ed25519 signature over the vote's signing message.
     pub signature: NovaSignature,
     /// Consensus round number this vote belongs to.
     pub round: u64,
+    /// Prevote or precommit.
+    pub kind: VoteKind,
 }
 
 impl Vote {
-    /// Creates a new signed vote.
+    /// Creates a new signed prevote.
     ///
-    /// The signature covers the concatenation of the block hash and the
-    /// round number (little-endian u64), preventing cross-round replay.
-    pub fn new(keypair: &NovaKeypair, block_hash: [u8; 32], round: u64) -> Self {
-        let mut message = Vec::with_capacity(40);
-        message.extend_from_slice(&block_hash);
-        message.extend_from_slice(&round.to_le_bytes());
+    /// The signature covers `"nova/prevote" || block_hash || round` (the
+    /// round as a little-endian u64), preventing cross-round replay.
+    pub fn new(keypair: &NovaKeypair, block_hash: impl Into<BlockHash>, round: u64) -> Self {
+        Self::signed(keypair, VoteKind::Prevote, block_hash.into(), round)
+    }
 
-        let signature = keypair.sign(&message);
+    /// Creates a new signed precommit.
+    ///
+    /// The signature covers `block_hash || round`, the message a
+    /// [`CommitSignature`] in the next block's header carries.
+    pub fn precommit(keypair: &NovaKeypair, block_hash: impl Into<BlockHash>, round: u64) -> Self {
+        Self::signed(keypair, VoteKind::Precommit, block_hash.into(), round)
+    }
 
+    fn signed(keypair: &NovaKeypair, kind: VoteKind, block_hash: BlockHash, round: u64) -> Self {
+        let signature = keypair.sign(&Self::signing_message(kind, &block_hash, round));
         Self {
             validator: keypair.public_key().to_hex(),
             block_hash,
             signature,
             round,
+            kind,
         }
     }
 
@@ -282,13 +454,129 @@ impl Vote {
             Ok(pk) => pk,
             Err(_) => return false,
         };
+        let message = Self::signing_message(self.kind, &self.block_hash, self.round);
+        pk.verify(&message, &self.signature)
+    }
+
+    /// `["nova/prevote" ||] block_hash || round`. Precommits keep the
+    /// untagged message commit signatures have always been checked against.
+    fn signing_message(kind: VoteKind, block_hash: &[u8; 32], round: u64) -> Vec<u8> {
+        let mut message = Vec::with_capacity(12 + 32 + 8);
+        if kind == VoteKind::Prevote {
+            message.extend_from_slice(b"nova/prevote");
+        }
+        message.extend_from_slice(block_hash);
+        message.extend_from_slice(&round.to_le_bytes());
+        message
+    }
+}
+
+impl From<&Vote> for CommitSignature {
+    fn from(vote: &Vote) -> Self {
+        Self {
+            validator: vote.validator.clone(),
+            round: vote.round,
+            signature: SignatureBytes::from(&vote.signature),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Proposal
+// ---------------------------------------------------------------------------
+
+/// Proof that a block gathered a prevote quorum (a polka) in some round.
+///
+/// Carried by proposals that re-propose a block from an earlier round, and
+/// kept by the engine as the evidence behind its lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Justification {
+    /// Round in which the polka formed.
+    pub round: u64,
+    /// Block the prevotes are for.
+    pub block_hash: BlockHash,
+    /// The prevotes themselves.
+    pub prevotes: Vec<Vote>,
+}
+
+/// Precommits from 2/3 of the stake for one block in one round: what it
+/// takes to finalize the block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit {
+    /// Round in which the precommits were cast.
+    pub round: u64,
+    /// Block the precommits are for.
+    pub block_hash: BlockHash,
+    /// The precommits themselves.
+    pub precommits: Vec<Vote>,
+}
+
+/// A proposer's signed candidate block for a round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    /// The candidate block.
+    pub block: Block,
+    /// Consensus round this proposal belongs to.
+    pub round: u64,
+    /// Hex-encoded public key of the proposer.
+    pub proposer: String,
+    /// Signature over the proposal's signing message.
+    pub signature: NovaSignature,
+    /// Polka from a prior round for this block, if it is being re-proposed.
+    pub justification: Option<Justification>,
+}
+
+impl Proposal {
+    /// Creates and signs a proposal.
+    pub fn new(
+        keypair: &NovaKeypair,
+        block: Block,
+        round: u64,
+        justification: Option<Justification>,
+    ) -> Self {
+        let message = Self::signing_message(
+            &block.header.hash,
+            round,
+            justification.as_ref().map(|j| j.round),
+        );
+        Self {
+            signature: keypair.sign(&message),
+            proposer: keypair.public_key().to_hex(),
+            block,
+            round,
+            justification,
+        }
+    }
 
-        let mut message = Vec::with_capacity(40);
-        message.extend_from_slice(&self.block_hash);
-        message.extend_from_slice(&self.round.to_le_bytes());
+    /// Hash of the proposed block.
+    pub fn block_hash(&self) -> BlockHash {
+        self.block.header.hash
+    }
 
+    /// Verifies the proposer's signature.
+    pub fn verify(&self) -> bool {
+        let pk = match NovaPublicKey::from_hex(&self.proposer) {
+            Ok(pk) => pk,
+            Err(_) => return false,
+        };
+        let message = Self::signing_message(
+            &self.block.header.hash,
+            self.round,
+            self.justification.as_ref().map(|j| j.round),
+        );
         pk.verify(&message, &self.signature)
     }
+
+    /// `"nova/proposal" || block_hash || round || justified_round + 1`, with
+    /// 0 standing for "no justification".
+    fn signing_message(block_hash: &[u8; 32], round: u64, justified: Option<u64>) -> Vec<u8> {
+        let mut message = Vec::with_capacity(13 + 32 + 16);
+        message.extend_from_slice(b"nova/proposal");
+        message.extend_from_slice(block_hash);
+        message.extend_from_slice(&round.to_le_bytes());
+        message.extend_from_slice(&justified.map_or(0, |r| r + 1).to_le_bytes());
+        message
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -304,8 +592,14 @@ impl Vote {
 pub struct FinalizedBlock {
     /// The block itself.
     pub block: Block,
-    /// Votes that finalized this block (at least quorum threshold count).
+    /// Precommits that finalized this block.
     pub votes: Vec<Vote>,
+    /// Stake of each voter in `votes` (same order), from the epoch snapshot.
+    pub vote_stakes: Vec<u64>,
+    /// Epoch whose validator snapshot weighted the votes.
+    pub epoch: u64,
+    /// Total active stake in that snapshot.
+    pub total_stake: u64,
     /// The consensus round in which finality was achieved.
     pub round: u64,
 }
@@ -316,6 +610,7 @@ pub struct FinalizedBlock {
 
 /// Errors that can occur during consensus operations.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ConsensusError {
     /// Not enough validators to meet the minimum quorum.
     #[error("insufficient validators: have {have}, need {need}")]
@@ -342,9 +637,25 @@ pub enum ConsensusError {
     /// Block contains more transactions than the maximum.
     #[error("block exceeds maximum transaction count: {0}")]
     TooManyTransactions(usize),
+    /// Block's transactions weigh more than the byte budget.
+    #[error("block transactions weigh {weight} bytes (max {max})")]
+    BlockTooLarge {
+        /// Total weight of the block's transactions.
+        weight: usize,
+        /// The configured `max_block_bytes`.
+        max: usize,
+    },
     /// Block timestamp is invalid.
     #[error("invalid block timestamp: {0}")]
     InvalidTimestamp(u64),
+    /// Voters do not hold enough stake to reach finality quorum.
+    #[error("insufficient voting stake for finality: have {have}, need {need}")]
+    InsufficientStake {
+        /// Stake behind the valid votes received.
+        have: u64,
+        /// Stake quorum required (2/3 of the active stake).
+        need: u64,
+    },
     /// Not enough votes to reach finality quorum.
     #[error("insufficient votes for finality: have {have}, need {need}")]
     InsufficientVotes {
@@ -362,6 +673,47 @@ pub enum ConsensusError {
     /// Duplicate vote from the same validator in the same round.
     #[error("duplicate vote from {0}")]
     DuplicateVote(String),
+    /// A proposal failed validation (signature, proposer, justification).
+    #[error("invalid proposal: {0}")]
+    InvalidProposal(String),
+    /// We are locked on a different block and the proposal does not carry a
+    /// justification recent enough to unlock.
+    #[error("locked on a different block since round {locked_round}")]
+    LockedOnOtherBlock {
+        /// Round in which the lock was taken.
+        locked_round: u64,
+    },
+    /// An emergency halt forbids this height (see [`crate::network::halt`]).
+    #[error(transparent)]
+    Halted(#[from] HaltError),
+    /// A transaction in the block is not properly signed.
+    #[error("invalid transaction signature: {0}")]
+    InvalidTransactionSignature(#[from] BlockSignatureError),
+    /// The block fails a check of the shared validation pipeline (hash,
+    /// Merkle root, commit votes).
+    #[error("invalid block: {0}")]
+    InvalidBlock(String),
+}
+
+impl From<BlockValidationError> for ConsensusError {
+    fn from(e: BlockValidationError) -> Self {
+        match e {
+            BlockValidationError::UnexpectedHeight { expected, got } => {
+                Self::UnexpectedHeight { expected, got }
+            }
+            BlockValidationError::InvalidParentHash => Self::InvalidParentHash,
+            BlockValidationError::TooManyTransactions { count, .. } => {
+                Self::TooManyTransactions(count)
+            }
+            BlockValidationError::TooLarge { weight, max } => Self::BlockTooLarge { weight, max },
+            BlockValidationError::ProposerSignature(proposer)
+            | BlockValidationError::UnauthorizedProposer(proposer) => {
+                Self::UnauthorizedProposer(proposer)
+            }
+            BlockValidationError::TransactionSignature(e) => Self::InvalidTransactionSignature(e),
+            e => Self::InvalidBlock(e.to_string()),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -386,7 +738,25 @@ pub struct ConsensusEngine {
     /// Height of the next block to be produced.
     next_height: u64,
     /// Hash of the most recent finalized block.
-    last_block_hash: [u8; 32],
+    last_block_hash: BlockHash,
+    /// Validator set as of the start of `snapshot_epoch`. Votes are checked
+    /// and weighted against this, not the live set.
+    epoch_snapshot: ValidatorSet,
+    /// Epoch the snapshot belongs to.
+    snapshot_epoch: u64,
+    /// Polka we are locked on at the current height, if any.
+    locked: Option<Justification>,
+    /// Prevotes seen at the current height, by (round, block hash).
+    prevotes: HashMap<(u64, BlockHash), Vec<Vote>>,
+    /// Precommits seen at the current height, by (round, block hash).
+    precommits: HashMap<(u64, BlockHash), Vec<Vote>>,
+    /// Accepted emergency halts, if governance is wired in.
+    halt: Option<Arc<HaltGuard>>,
+    /// Height whose round was last reset after a resumed halt.
+    restarted_height: Option<u64>,
+    /// Stateless block checks shared with the producer and sync, under the
+    /// configured block limits.
+    block_validator: BlockValidator,
 }
 
 impl ConsensusEngine {
@@ -399,13 +769,65 @@ impl ConsensusEngine {
             "consensus engine initialized"
         );
 
+        let block_validator = BlockValidator::new()
+            .with_max_block_transactions(config.max_block_transactions)
+            .with_max_block_bytes(config.max_block_bytes);
         Self {
             config,
+            epoch_snapshot: validator_set.clone(),
             validator_set,
             current_round: 0,
             current_phase: ConsensusRound::Propose,
             next_height: 0,
-            last_block_hash: [0u8; 32],
+            last_block_hash: BlockHash::zero(),
+            snapshot_epoch: 0,
+            locked: None,
+            prevotes: HashMap::new(),
+            precommits: HashMap::new(),
+            halt: None,
+            restarted_height: None,
+            block_validator,
+        }
+    }
+
+    /// Attaches the halt guard. Blocks at heights an accepted
+    /// [`EmergencyHalt`](crate::network::halt::EmergencyHalt) forbids then
+    /// fail validation and finalization.
+    pub fn set_halt_guard(&mut self, guard: Arc<HaltGuard>) {
+        self.halt = Some(guard);
+    }
+
+    /// Checks that the next height may be built.
+    ///
+    /// At the first height after a resumed halt, also moves to the round
+    /// the resume fixes, so every node restarts with the same proposer.
+    /// That happens once per height; timeouts advance rounds as usual.
+    pub fn check_halt(&mut self) -> Result<(), HaltError> {
+        let Some(guard) = &self.halt else {
+            return Ok(());
+        };
+        guard.check(self.next_height)?;
+        if let Some(round) = guard.restart_round(self.next_height) {
+            if self.restarted_height != Some(self.next_height) {
+                info!(
+                    height = self.next_height,
+                    round, "restarting after emergency halt"
+                );
+                self.restarted_height = Some(self.next_height);
+                self.current_round = round;
+                self.current_phase = ConsensusRound::Propose;
+                self.locked = None;
+                self.prevotes.clear();
+                self.precommits.clear();
+            }
+        }
+        Ok(())
+    }
+
+    fn check_halt_at(&self, height: u64) -> Result<(), ConsensusError> {
+        match &self.halt {
+            Some(guard) => Ok(guard.check(height)?),
+            None => Ok(()),
         }
     }
 
@@ -454,13 +876,11 @@ impl ConsensusEngine {
             return Err(ConsensusError::UnauthorizedProposer(proposer_address));
         }
 
-        // Enforce transaction limit.
-        if transactions.len() > self.config.max_block_transactions {
-            return Err(ConsensusError::TooManyTransactions(transactions.len()));
-        }
+        // Enforce transaction and byte limits.
+        self.block_validator.check_limits(&transactions)?;
 
         // Compute the transactions Merkle root.
-        let tx_root = Self::compute_transactions_root(&transactions);
+        let tx_root = compute_merkle_root(&transactions);
 
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -469,13 +889,17 @@ impl ConsensusEngine {
 
         let mut header = BlockHeader {
             height: self.next_height,
-            hash: [0u8; 32], // Computed below.
+            hash: BlockHash::zero(), // Computed below.
             parent_hash: self.last_block_hash,
-            tx_root,
-            state_root: [0u8; 32], // Filled by the state transition engine.
+            tx_root: tx_root.into(),
+            state_root: Default::default(), // Filled by the state transition engine.
+            fees: 0,
             timestamp,
             validator: proposer_address,
-            signature: Vec::new(),
+            last_commit: Vec::new(),
+            version: LEGACY_HEADER_VERSION,
+            extensions: HeaderExtensions::new(),
+            signature: None,
         };
 
         // Compute the block hash from header fields.
@@ -485,10 +909,9 @@ impl ConsensusEngine {
         };
         header.hash = block_for_hash.compute_hash();
 
-        // Sign the header.
-        let header_bytes = serde_json::to_vec(&header).unwrap_or_default();
-        let sig = proposer_keypair.sign(&header_bytes);
-        header.signature = sig.as_bytes().to_vec();
+        // Sign the block hash, as the block producer does.
+        let sig = proposer_keypair.sign(header.hash.as_bytes());
+        header.signature = Some(SignatureBytes::from(&sig));
 
         let block = Block {
             header,
@@ -505,87 +928,53 @@ impl ConsensusEngine {
 
     /// Validates a block against the consensus rules.
     ///
-    /// Checks height, parent hash, proposer authorization, transaction count,
-    /// and proposer signature. Does not execute transactions — that is the
+    /// Checks height and parent hash, halts, and proposer authorization,
+    /// then runs the stateless stages of the shared [`BlockValidator`]:
+    /// hash, Merkle root, commit votes, transaction count and byte limits,
+    /// the proposer's signature and the transactions' signatures (in
+    /// parallel batches). Does not execute transactions — that is the
     /// responsibility of the state transition engine.
     pub fn validate_block(&self, block: &Block) -> Result<bool, ConsensusError> {
-        if block.header.height != self.next_height {
-            return Err(ConsensusError::UnexpectedHeight {
-                expected: self.next_height,
-                got: block.header.height,
-            });
-        }
-
-        if block.header.parent_hash != self.last_block_hash {
-            return Err(ConsensusError::InvalidParentHash);
-        }
+        self.block_validator
+            .check_linkage(block, self.next_height, &self.last_block_hash)?;
 
-        if !self.validator_set.contains(&block.header.validator) {
-            return Err(ConsensusError::UnauthorizedProposer(
-                block.header.validator.clone(),
-            ));
-        }
+        self.check_halt_at(block.header.height)?;
 
-        if block.transactions.len() > self.config.max_block_transactions {
-            return Err(ConsensusError::TooManyTransactions(
-                block.transactions.len(),
-            ));
-        }
+        self.block_validator
+            .check_proposer(block, &self.validator_set)?;
 
-        // Verify the proposer's signature.
-        let proposer_pk = NovaPublicKey::from_hex(&block.header.validator)
-            .map_err(|_| ConsensusError::UnauthorizedProposer(block.header.validator.clone()))?;
-
-        let mut header_for_sig = block.header.clone();
-        header_for_sig.signature = Vec::new();
-        let header_bytes = serde_json::to_vec(&header_for_sig).unwrap_or_default();
-
-        if block.header.signature.len() != 64 {
-            return Err(ConsensusError::UnauthorizedProposer(
-                block.header.validator.clone(),
-            ));
-        }
-
-        let mut sig_bytes = [0u8; 64];
-        sig_bytes.copy_from_slice(&block.header.signature);
-        let signature = NovaSignature::from_bytes(sig_bytes);
-
-        if !proposer_pk.verify(&header_bytes, &signature) {
-            return Err(ConsensusError::UnauthorizedProposer(
-                block.header.validator.clone(),
-            ));
-        }
-
-        // Verify transactions root.
-        let expected_root = Self::compute_transactions_root(&block.transactions);
-        if block.header.tx_root != expected_root {
-            warn!(height = block.header.height, "transactions root mismatch");
-        }
+        self.block_validator.check_header(block)?;
+        self.block_validator.check_signatures(block)?;
 
         debug!(height = block.header.height, "block validated");
         Ok(true)
     }
 
-    /// Finalizes a block given a set of votes from validators.
+    /// Finalizes a block given a set of precommits from validators.
     ///
-    /// The block is finalized if and only if:
-    /// - All votes are for the correct block hash.
+    /// Prevotes and votes for other blocks are ignored. The block is
+    /// finalized if and only if:
     /// - All votes have valid signatures.
-    /// - All voters are in the active validator set.
-    /// - The number of valid votes meets the quorum threshold (2/3 + 1).
+    /// - All voters are in the block's epoch validator snapshot.
+    /// - The voters' snapshot stake is at least 2/3 of the snapshot's total.
     /// - No duplicate votes from the same validator.
     pub fn finalize_block(
         &mut self,
         block: Block,
         votes: Vec<Vote>,
     ) -> Result<FinalizedBlock, ConsensusError> {
+        self.check_halt_at(block.header.height)?;
+
         let block_hash = block.header.hash;
-        let quorum = self.validator_set.quorum_threshold();
+        let epoch = self.snapshot_epoch;
+        let snapshot = &self.epoch_snapshot;
+
         let mut seen_validators: HashMap<String, bool> = HashMap::new();
         let mut valid_votes = Vec::new();
+        let mut vote_stakes = Vec::new();
 
         for vote in &votes {
-            if vote.block_hash != block_hash {
+            if vote.block_hash != block_hash || vote.kind != VoteKind::Precommit {
                 continue;
             }
 
@@ -593,7 +982,7 @@ impl ConsensusEngine {
                 return Err(ConsensusError::DuplicateVote(vote.validator.clone()));
             }
 
-            if !self.validator_set.contains(&vote.validator) {
+            if !snapshot.contains(&vote.validator) {
                 return Err(ConsensusError::VoteFromNonValidator(vote.validator.clone()));
             }
 
@@ -602,19 +991,30 @@ impl ConsensusEngine {
             }
 
             seen_validators.insert(vote.validator.clone(), true);
+            vote_stakes.push(snapshot.stake_of(&vote.validator));
             valid_votes.push(vote.clone());
         }
 
-        if valid_votes.len() < quorum {
-            return Err(ConsensusError::InsufficientVotes {
-                have: valid_votes.len(),
-                need: quorum,
+        let voting_stake: u64 = vote_stakes.iter().sum();
+        if !snapshot.has_quorum(voting_stake, valid_votes.len()) {
+            if snapshot.total_stake() == 0 {
+                return Err(ConsensusError::InsufficientVotes {
+                    have: valid_votes.len(),
+                    need: snapshot.quorum_threshold(),
+                });
+            }
+            return Err(ConsensusError::InsufficientStake {
+                have: voting_stake,
+                need: snapshot.stake_quorum(),
             });
         }
 
         let finalized = FinalizedBlock {
             block: block.clone(),
             votes: valid_votes,
+            vote_stakes,
+            epoch,
+            total_stake: snapshot.total_stake(),
             round: self.current_round,
         };
 
@@ -622,17 +1022,274 @@ impl ConsensusEngine {
         self.next_height += 1;
         self.current_round += 1;
         self.current_phase = ConsensusRound::Propose;
+        self.locked = None;
+        self.prevotes.clear();
+        self.precommits.clear();
+        self.roll_snapshot();
 
         info!(
             height = finalized.block.header.height,
             round = finalized.round,
             votes = finalized.votes.len(),
+            voting_stake,
+            total_stake = finalized.total_stake,
             "block finalized"
         );
 
         Ok(finalized)
     }
 
+    // -- Proposals & locking ------------------------------------------------
+
+    /// Returns the polka this engine is locked on, if any.
+    pub fn locked(&self) -> Option<&Justification> {
+        self.locked.as_ref()
+    }
+
+    /// Wraps a block in a signed proposal for the current round.
+    ///
+    /// If we are locked, the lock's block must be re-proposed — proposing
+    /// anything else would ask honest validators to abandon a possible
+    /// commit. The lock's polka travels along as the justification.
+    pub fn create_proposal(
+        &self,
+        block: Block,
+        keypair: &NovaKeypair,
+    ) -> Result<Proposal, ConsensusError> {
+        let justification = match &self.locked {
+            Some(lock) if lock.block_hash != block.header.hash => {
+                return Err(ConsensusError::LockedOnOtherBlock {
+                    locked_round: lock.round,
+                });
+            }
+            Some(lock) => Some(lock.clone()),
+            None => None,
+        };
+        Ok(Proposal::new(
+            keypair,
+            block,
+            self.current_round,
+            justification,
+        ))
+    }
+
+    /// Checks a proposal's signature, proposer slot, and justification.
+    ///
+    /// Block-level checks (height, parent, header signature) are the job of
+    /// [`validate_block`](Self::validate_block).
+    pub fn validate_proposal(&self, proposal: &Proposal) -> Result<(), ConsensusError> {
+        if !proposal.verify() {
+            return Err(ConsensusError::InvalidProposal("bad signature".into()));
+        }
+
+        let expected = self
+            .validator_set
+            .proposer_for_round(proposal.round)
+            .ok_or(ConsensusError::InsufficientValidators {
+                have: self.validator_set.len(),
+                need: self.config.min_validators,
+            })?;
+        if expected.address != proposal.proposer {
+            return Err(ConsensusError::UnauthorizedProposer(
+                proposal.proposer.clone(),
+            ));
+        }
+        if proposal.block.header.validator != proposal.proposer {
+            return Err(ConsensusError::InvalidProposal(
+                "block was not produced by the proposer".into(),
+            ));
+        }
+
+        if let Some(justification) = &proposal.justification {
+            if justification.block_hash != proposal.block_hash() {
+                return Err(ConsensusError::InvalidProposal(
+                    "justification is for a different block".into(),
+                ));
+            }
+            if justification.round >= proposal.round {
+                return Err(ConsensusError::InvalidProposal(
+                    "justification must come from an earlier round".into(),
+                ));
+            }
+            self.check_polka(justification, proposal.block.header.height)?;
+        }
+        Ok(())
+    }
+
+    /// Validates a proposal and, if the locking rules allow, returns our
+    /// signed prevote for it.
+    ///
+    /// An unlocked validator prevotes for any valid proposal. A locked one
+    /// prevotes only for its locked block, or for a proposal justified by a
+    /// polka from the lock round or later.
+    pub fn prevote(
+        &self,
+        proposal: &Proposal,
+        keypair: &NovaKeypair,
+    ) -> Result<Vote, ConsensusError> {
+        self.validate_proposal(proposal)?;
+
+        if let Some(lock) = &self.locked {
+            let unlocks = proposal
+                .justification
+                .as_ref()
+                .is_some_and(|j| j.round >= lock.round);
+            if lock.block_hash != proposal.block_hash() && !unlocks {
+                return Err(ConsensusError::LockedOnOtherBlock {
+                    locked_round: lock.round,
+                });
+            }
+        }
+
+        Ok(Vote::new(keypair, proposal.block_hash(), proposal.round))
+    }
+
+    /// Records a prevote seen on the network (or our own).
+    ///
+    /// Returns the new lock if this vote completed a polka for a round at
+    /// or after the current lock.
+    pub fn record_prevote(&mut self, vote: Vote) -> Result<Option<&Justification>, ConsensusError> {
+        let snapshot = &self.epoch_snapshot;
+        let Some((key, stake)) = tally(snapshot, &mut self.prevotes, VoteKind::Prevote, vote)?
+        else {
+            return Ok(None);
+        };
+        let votes = &self.prevotes[&key];
+        if self.locked.as_ref().is_some_and(|l| l.round > key.0) {
+            return Ok(None);
+        }
+
+        info!(round = key.0, stake, "polka observed, locking");
+        self.locked = Some(Justification {
+            round: key.0,
+            block_hash: key.1,
+            prevotes: votes.clone(),
+        });
+        Ok(self.locked.as_ref())
+    }
+
+    /// Returns our signed precommit for `block_hash`, the block we have
+    /// executed, if we saw its polka in the current round (and are locked
+    /// on it) and have not precommitted in this round yet.
+    pub fn precommit(&self, block_hash: &BlockHash, keypair: &NovaKeypair) -> Option<Vote> {
+        let lock = self.locked.as_ref()?;
+        if lock.round != self.current_round || lock.block_hash != *block_hash {
+            return None;
+        }
+        let address = keypair.public_key().to_hex();
+        let precommitted = self
+            .precommits
+            .iter()
+            .filter(|((round, _), _)| *round == self.current_round)
+            .any(|(_, votes)| votes.iter().any(|v| v.validator == address));
+        if precommitted {
+            return None;
+        }
+        Some(Vote::precommit(keypair, *block_hash, self.current_round))
+    }
+
+    /// Records a precommit seen on the network (or our own).
+    ///
+    /// Returns the commit if this vote brought the precommits for its
+    /// round and block to 2/3 of the stake.
+    pub fn record_precommit(&mut self, vote: Vote) -> Result<Option<Commit>, ConsensusError> {
+        let tallied = tally(
+            &self.epoch_snapshot,
+            &mut self.precommits,
+            VoteKind::Precommit,
+            vote,
+        )?;
+        Ok(tallied.map(|((round, block_hash), stake)| {
+            info!(round, stake, "precommit quorum observed");
+            Commit {
+                round,
+                block_hash,
+                precommits: self.precommits[&(round, block_hash)].clone(),
+            }
+        }))
+    }
+
+    /// Returns a commit for `block_hash` at the current height, if
+    /// precommits from 2/3 of the stake for it in one round are in.
+    pub fn commit_for(&self, block_hash: &BlockHash) -> Option<Commit> {
+        let snapshot = &self.epoch_snapshot;
+        self.precommits
+            .iter()
+            .filter(|((_, hash), _)| hash == block_hash)
+            .find(|(_, votes)| {
+                let stake: u64 = votes.iter().map(|v| snapshot.stake_of(&v.validator)).sum();
+                snapshot.has_quorum(stake, votes.len())
+            })
+            .map(|((round, block_hash), votes)| Commit {
+                round: *round,
+                block_hash: *block_hash,
+                precommits: votes.clone(),
+            })
+    }
+
+    /// Verifies that `justification` is a genuine polka for a block at
+    /// `height`.
+    fn check_polka(
+        &self,
+        justification: &Justification,
+        height: u64,
+    ) -> Result<(), ConsensusError> {
+        let set = self.voting_set(height);
+        let mut seen = std::collections::HashSet::new();
+        let mut stake = 0u64;
+        for vote in &justification.prevotes {
+            if vote.block_hash != justification.block_hash
+                || vote.round != justification.round
+                || vote.kind != VoteKind::Prevote
+            {
+                return Err(ConsensusError::InvalidProposal(
+                    "justification vote is not a prevote for its round and block".into(),
+                ));
+            }
+            if !seen.insert(vote.validator.as_str()) {
+                return Err(ConsensusError::DuplicateVote(vote.validator.clone()));
+            }
+            if !set.contains(&vote.validator) {
+                return Err(ConsensusError::VoteFromNonValidator(vote.validator.clone()));
+            }
+            if !vote.verify() {
+                return Err(ConsensusError::InvalidVote(vote.validator.clone()));
+            }
+            stake += set.stake_of(&vote.validator);
+        }
+        if !set.has_quorum(stake, seen.len()) {
+            return Err(ConsensusError::InsufficientStake {
+                have: stake,
+                need: set.stake_quorum(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The validator set that weighs votes for a block at `height`: the
+    /// epoch snapshot, or the live set if that epoch has not started yet.
+    fn voting_set(&self, height: u64) -> &ValidatorSet {
+        if self.epoch_of(height) == self.snapshot_epoch {
+            &self.epoch_snapshot
+        } else {
+            &self.validator_set
+        }
+    }
+
+    /// Takes a fresh stake snapshot if the next height opens a new epoch.
+    ///
+    /// Called as each block is committed, so every node snapshots the same
+    /// set — the one in force once the epoch's last block is committed —
+    /// whenever its next vote happens to arrive.
+    fn roll_snapshot(&mut self) {
+        let epoch = self.epoch_of(self.next_height);
+        if epoch != self.snapshot_epoch {
+            self.epoch_snapshot = self.validator_set.clone();
+            self.snapshot_epoch = epoch;
+            debug!(epoch, "took validator stake snapshot");
+        }
+    }
+
     /// Advances to the next round (e.g., after a proposer timeout).
     pub fn advance_round(&mut self) {
         self.current_round += 1;
@@ -650,7 +1307,16 @@ impl ConsensusEngine {
         }
     }
 
+    /// Epoch number for a block height.
+    pub fn epoch_of(&self, height: u64) -> u64 {
+        height / self.config.epoch_length.max(1)
+    }
+
     /// Updates the validator set (typically at epoch boundaries).
+    ///
+    /// Vote weighting picks the change up at the next epoch boundary:
+    /// committing an epoch's last block snapshots the set as it stands
+    /// then, and blocks in the epoch that follows are weighed against it.
     pub fn update_validator_set(&mut self, new_set: ValidatorSet) {
         info!(
             old_count = self.validator_set.len(),
@@ -661,23 +1327,50 @@ impl ConsensusEngine {
     }
 
     /// Sets the chain state for the engine (used during sync/initialization).
-    pub fn set_chain_state(&mut self, height: u64, last_hash: [u8; 32]) {
+    pub fn set_chain_state(&mut self, height: u64, last_hash: BlockHash) {
         self.next_height = height;
         self.last_block_hash = last_hash;
+        self.snapshot_epoch = self.epoch_of(height);
+        self.epoch_snapshot = self.validator_set.clone();
     }
+}
 
-    /// Computes a simplified transactions root from a list of transactions.
-    ///
-    /// Concatenates all transaction IDs and hashes the result with BLAKE3.
-    /// A proper binary Merkle tree implementation replaces this in production,
-    /// but the critical property — determinism — is preserved either way.
-    fn compute_transactions_root(transactions: &[Transaction]) -> [u8; 32] {
-        let mut data = Vec::with_capacity(transactions.len() * 64);
-        for tx in transactions {
-            data.extend_from_slice(tx.id.as_bytes());
-        }
-        *blake3::hash(&data).as_bytes()
+/// Checks a `kind` vote and adds it to `votes`, refusing a second one from
+/// its validator in its round. Returns the vote's (round, block) key and
+/// the stake behind it once that holds a quorum of `snapshot`.
+fn tally(
+    snapshot: &ValidatorSet,
+    votes: &mut HashMap<(u64, BlockHash), Vec<Vote>>,
+    kind: VoteKind,
+    vote: Vote,
+) -> Result<Option<((u64, BlockHash), u64)>, ConsensusError> {
+    if vote.kind != kind {
+        return Err(ConsensusError::InvalidVote(vote.validator));
     }
+    if !snapshot.contains(&vote.validator) {
+        return Err(ConsensusError::VoteFromNonValidator(vote.validator));
+    }
+    if !vote.verify() {
+        return Err(ConsensusError::InvalidVote(vote.validator));
+    }
+    let already_voted = votes
+        .iter()
+        .filter(|((round, _), _)| *round == vote.round)
+        .any(|(_, votes)| votes.iter().any(|v| v.validator == vote.validator));
+    if already_voted {
+        return Err(ConsensusError::DuplicateVote(vote.validator));
+    }
+
+    let key = (vote.round, vote.block_hash);
+    let tallied = votes.entry(key).or_default();
+    tallied.push(vote);
+    let stake: u64 = tallied
+        .iter()
+        .map(|v| snapshot.stake_of(&v.validator))
+        .sum();
+    Ok(snapshot
+        .has_quorum(stake, tallied.len())
+        .then_some((key, stake)))
 }
 
 #[cfg(test)]
@@ -713,6 +1406,44 @@ mod tests {
         assert!(engine.validate_block(&block).is_ok());
     }
 
+    #[test]
+    fn block_with_unsigned_transaction_rejected() {
+        use crate::transaction::{Amount, Currency, TransactionBuilder, TransactionType};
+
+        let (engine, keypair) = setup_engine();
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .nonce(1)
+            .build_unchecked();
+
+        let block = engine.propose_block(vec![tx], &keypair).unwrap();
+        assert!(matches!(
+            engine.validate_block(&block),
+            Err(ConsensusError::InvalidTransactionSignature(ref e)) if e.index == 0
+        ));
+    }
+
+    #[test]
+    fn proposal_over_byte_budget_rejected() {
+        use crate::transaction::limits;
+        use crate::transaction::{Amount, Currency, TransactionBuilder, TransactionType};
+
+        let (mut engine, keypair) = setup_engine();
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .nonce(1)
+            .build_unchecked();
+        engine.block_validator =
+            BlockValidator::new().with_max_block_bytes(limits::tx_weight(&tx) - 1);
+
+        let result = engine.propose_block(vec![tx], &keypair);
+        assert!(matches!(result, Err(ConsensusError::BlockTooLarge { .. })));
+    }
+
     #[test]
     fn unauthorized_proposer_rejected() {
         let (engine, _) = setup_engine();
@@ -751,6 +1482,59 @@ mod tests {
         assert_eq!(vs.proposer_for_round(3).unwrap().address, "high-stake");
     }
 
+    #[test]
+    fn equal_stake_order_ignores_insertion_history() {
+        let mut a = ValidatorSet::new();
+        let mut b = ValidatorSet::new();
+        for name in ["carol", "alice", "bob"] {
+            a.add_validator(name.to_string(), 1000);
+        }
+        for name in ["bob", "carol", "alice"] {
+            b.add_validator(name.to_string(), 1000);
+        }
+        b.add_validator("alice".to_string(), 1000); // duplicate is an update
+
+        assert_eq!(b.len(), 3);
+        for round in 0..6 {
+            assert_eq!(
+                a.proposer_for_round(round).unwrap().address,
+                b.proposer_for_round(round).unwrap().address,
+            );
+        }
+        assert_eq!(a.proposer_for_round(0).unwrap().address, "alice");
+    }
+
+    #[test]
+    fn validator_set_persists_in_state() {
+        use crate::storage::NovaDB;
+
+        let mut vs = ValidatorSet::new();
+        vs.add_validator("zed".to_string(), 500);
+        vs.add_validator("amy".to_string(), 2000);
+        vs.add_validator("max".to_string(), 500);
+
+        // Two nodes with independent databases, same state contents.
+        let mut tree_a = StateTree::new(NovaDB::open_temporary().unwrap());
+        let mut tree_b = StateTree::new(NovaDB::open_temporary().unwrap());
+        assert!(ValidatorSet::load_from_state(&tree_a).is_none());
+
+        vs.store_in_state(&mut tree_a);
+        ValidatorSet::from_validators(vs.validators().iter().rev().cloned().collect())
+            .store_in_state(&mut tree_b);
+        assert_eq!(tree_a.root(), tree_b.root(), "persisted form is canonical");
+
+        let loaded_a = ValidatorSet::load_from_state(&tree_a).unwrap();
+        let loaded_b = ValidatorSet::load_from_state(&tree_b).unwrap();
+        assert_eq!(loaded_a.validators(), vs.validators());
+        for round in 0..6 {
+            assert_eq!(
+                loaded_a.proposer_for_round(round),
+                loaded_b.proposer_for_round(round),
+            );
+        }
+        assert_eq!(loaded_a.proposer_for_round(1).unwrap().address, "max");
+    }
+
     #[test]
     fn finalize_block_with_quorum() {
         let keypair = NovaKeypair::generate();
@@ -771,7 +1555,7 @@ mod tests {
             .expect("proposal should succeed");
 
         let block_hash = block.header.hash;
-        let vote = Vote::new(&keypair, block_hash, 0);
+        let vote = Vote::precommit(&keypair, block_hash, 0);
 
         let finalized = engine
             .finalize_block(block, vec![vote])
@@ -815,7 +1599,7 @@ mod tests {
         vs.add_validator(addr1, 3000);
         vs.add_validator(addr2, 2000);
         vs.add_validator(addr3, 1000);
-        // Quorum for 3 validators = (2*3/3)+1 = 3
+        // Stake quorum = 2/3 of 6000 = 4000.
 
         let config = ConsensusConfig {
             min_validators: 1,
@@ -829,13 +1613,313 @@ mod tests {
             .expect("proposal should succeed");
 
         let block_hash = block.header.hash;
-        // Only 1 vote — not enough.
-        let vote = Vote::new(&kp1, block_hash, 0);
+        // Only 1 vote (3000 stake) — not enough.
+        let vote = Vote::precommit(&kp1, block_hash, 0);
 
         let result = engine.finalize_block(block, vec![vote]);
         assert!(matches!(
             result,
-            Err(ConsensusError::InsufficientVotes { .. })
+            Err(ConsensusError::InsufficientStake {
+                have: 3000,
+                need: 4000
+            })
+        ));
+    }
+
+    /// Engine over the given (keypair, stake) pairs with a short epoch.
+    fn weighted_engine(stakes: &[(&NovaKeypair, u64)]) -> ConsensusEngine {
+        let mut vs = ValidatorSet::new();
+        for (kp, stake) in stakes {
+            vs.add_validator(kp.public_key().to_hex(), *stake);
+        }
+        let config = ConsensusConfig {
+            min_validators: 1,
+            epoch_length: 2,
+            ..ConsensusConfig::default()
+        };
+        ConsensusEngine::new(config, vs)
+    }
+
+    fn votes_for(block: &Block, voters: &[&NovaKeypair]) -> Vec<Vote> {
+        voters
+            .iter()
+            .map(|kp| Vote::precommit(kp, block.header.hash, 0))
+            .collect()
+    }
+
+    #[test]
+    fn small_stake_majority_cannot_finalize() {
+        let whale = NovaKeypair::generate();
+        let minnows: Vec<_> = (0..3).map(|_| NovaKeypair::generate()).collect();
+        let mut stakes = vec![(&whale, 7_000)];
+        stakes.extend(minnows.iter().map(|kp| (kp, 1_000)));
+        let mut engine = weighted_engine(&stakes);
+        let block = Block::genesis();
+
+        // 3 of 4 validators, but only 3000 of 10000 stake.
+        let minnow_refs: Vec<_> = minnows.iter().collect();
+        let result = engine.finalize_block(block.clone(), votes_for(&block, &minnow_refs));
+        assert!(matches!(
+            result,
+            Err(ConsensusError::InsufficientStake {
+                have: 3_000,
+                need: 6_667
+            })
+        ));
+
+        // 1 of 4 validators holding 70% of stake.
+        let finalized = engine
+            .finalize_block(block.clone(), votes_for(&block, &[&whale]))
+            .unwrap();
+        assert_eq!(finalized.vote_stakes, vec![7_000]);
+        assert_eq!(finalized.total_stake, 10_000);
+    }
+
+    #[test]
+    fn exactly_two_thirds_is_enough() {
+        let a = NovaKeypair::generate();
+        let b = NovaKeypair::generate();
+        let mut engine = weighted_engine(&[(&a, 2_000), (&b, 1_000)]);
+        assert_eq!(engine.validator_set().stake_quorum(), 2_000);
+
+        let block = Block::genesis();
+        assert!(engine
+            .finalize_block(block.clone(), votes_for(&block, &[&b]))
+            .is_err());
+        assert!(engine
+            .finalize_block(block.clone(), votes_for(&block, &[&a]))
+            .is_ok());
+
+        // Rounding goes up: 2/3 of 10 is 6.67, so 7 is needed.
+        let mut vs = ValidatorSet::new();
+        vs.add_validator("x".into(), 6);
+        vs.add_validator("y".into(), 4);
+        assert_eq!(vs.stake_quorum(), 7);
+        assert!(!vs.has_quorum(6, 1));
+        assert!(vs.has_quorum(7, 2));
+    }
+
+    #[test]
+    fn stake_change_mid_epoch_waits_for_next_epoch() {
+        let a = NovaKeypair::generate();
+        let b = NovaKeypair::generate();
+        let mut engine = weighted_engine(&[(&a, 1_000), (&b, 3_000)]);
+
+        let genesis = Block::genesis();
+        let block1 = Block::new(&genesis, vec![], a.public_key().to_hex(), [0u8; 32]);
+        let block2 = Block::new(&block1, vec![], a.public_key().to_hex(), [0u8; 32]);
+
+        engine
+            .finalize_block(genesis.clone(), votes_for(&genesis, &[&a, &b]))
+            .unwrap();
+
+        // Mid-epoch 0, `a` bonds a lot more stake.
+        let mut updated = engine.validator_set().clone();
+        updated.add_validator(a.public_key().to_hex(), 9_000);
+        engine.update_validator_set(updated);
+
+        // Block 1 is still in epoch 0: `a` weighs 1000 of 4000.
+        let result = engine.finalize_block(block1.clone(), votes_for(&block1, &[&a]));
+        assert!(matches!(
+            result,
+            Err(ConsensusError::InsufficientStake { have: 1_000, .. })
+        ));
+
+        // Committing block 1 closes epoch 0 and snapshots the new stake.
+        engine
+            .finalize_block(block1.clone(), votes_for(&block1, &[&a, &b]))
+            .unwrap();
+
+        // A stake change after the boundary waits for epoch 2.
+        let mut later = engine.validator_set().clone();
+        later.add_validator(b.public_key().to_hex(), 100_000);
+        engine.update_validator_set(later);
+
+        // Block 2 opens epoch 1 with the boundary snapshot: 9000 of 12000.
+        let finalized = engine
+            .finalize_block(block2.clone(), votes_for(&block2, &[&a]))
+            .unwrap();
+        assert_eq!(finalized.epoch, 1);
+        assert_eq!(finalized.vote_stakes, vec![9_000]);
+        assert_eq!(finalized.total_stake, 12_000);
+    }
+
+    // -- Proposals & locking ------------------------------------------------
+
+    /// Two validators, `a` (2/3 of stake, proposes even rounds) and `b`.
+    fn locking_setup() -> (ConsensusEngine, NovaKeypair, NovaKeypair) {
+        let a = NovaKeypair::generate();
+        let b = NovaKeypair::generate();
+        let engine = weighted_engine(&[(&a, 2_000), (&b, 1_000)]);
+        (engine, a, b)
+    }
+
+    fn block_by(kp: &NovaKeypair, salt: u8) -> Block {
+        Block::new(
+            &Block::genesis(),
+            vec![],
+            kp.public_key().to_hex(),
+            [salt; 32],
+        )
+    }
+
+    #[test]
+    fn proposal_signature_and_slot_checked() {
+        let (engine, a, b) = locking_setup();
+
+        let proposal = engine.create_proposal(block_by(&a, 1), &a).unwrap();
+        assert!(engine.validate_proposal(&proposal).is_ok());
+
+        let mut tampered = proposal.clone();
+        tampered.round = 7;
+        assert!(matches!(
+            engine.validate_proposal(&tampered),
+            Err(ConsensusError::InvalidProposal(_))
+        ));
+
+        // `b` signs a proposal for round 0, which belongs to `a`.
+        let wrong_slot = Proposal::new(&b, block_by(&b, 1), 0, None);
+        assert!(matches!(
+            engine.validate_proposal(&wrong_slot),
+            Err(ConsensusError::UnauthorizedProposer(_))
+        ));
+    }
+
+    #[test]
+    fn polka_locks_validator_on_block() {
+        let (mut engine, a, b) = locking_setup();
+        let block_a = block_by(&a, 1);
+
+        let proposal = engine.create_proposal(block_a.clone(), &a).unwrap();
+        let vote = engine.prevote(&proposal, &a).unwrap();
+        let lock = engine.record_prevote(vote).unwrap().cloned();
+        assert_eq!(
+            lock.map(|l| (l.round, l.block_hash)),
+            Some((0, block_a.header.hash))
+        );
+
+        // Round 1: `b` proposes something else with no justification.
+        engine.advance_round();
+        let other = Proposal::new(&b, block_by(&b, 2), 1, None);
+        assert!(matches!(
+            engine.prevote(&other, &a),
+            Err(ConsensusError::LockedOnOtherBlock { locked_round: 0 })
+        ));
+
+        // Round 2: `a` must re-propose the locked block, with its polka.
+        engine.advance_round();
+        assert!(engine.create_proposal(block_by(&a, 3), &a).is_err());
+        let again = engine.create_proposal(block_a.clone(), &a).unwrap();
+        assert_eq!(again.justification.as_ref().unwrap().round, 0);
+        assert!(engine.prevote(&again, &b).is_ok());
+
+        // Finalizing the height releases the lock.
+        let votes = votes_for(&block_a, &[&a]);
+        engine.finalize_block(block_a, votes).unwrap();
+        assert!(engine.locked().is_none());
+    }
+
+    #[test]
+    fn later_polka_justifies_unlocking() {
+        let (mut engine, a, b) = locking_setup();
+        let block_a = block_by(&a, 1);
+        let block_b = block_by(&a, 2);
+
+        let vote = Vote::new(&a, block_a.header.hash, 0);
+        engine.record_prevote(vote).unwrap();
+        assert_eq!(engine.locked().unwrap().block_hash, block_a.header.hash);
+
+        // The rest of the network formed a polka for `block_b` in round 1.
+        let polka = Justification {
+            round: 1,
+            block_hash: block_b.header.hash,
+            prevotes: votes_for_round(&block_b, &[&a], 1),
+        };
+        let justified = Proposal::new(&a, block_b.clone(), 2, Some(polka));
+        assert!(engine.prevote(&justified, &b).is_ok());
+
+        // A "polka" backed by a third of the stake proves nothing.
+        let weak = Justification {
+            round: 1,
+            block_hash: block_b.header.hash,
+            prevotes: votes_for_round(&block_b, &[&b], 1),
+        };
+        let unjustified = Proposal::new(&a, block_b, 2, Some(weak));
+        assert!(matches!(
+            engine.prevote(&unjustified, &b),
+            Err(ConsensusError::InsufficientStake { .. })
+        ));
+    }
+
+    fn votes_for_round(block: &Block, voters: &[&NovaKeypair], round: u64) -> Vec<Vote> {
+        voters
+            .iter()
+            .map(|kp| Vote::new(kp, block.header.hash, round))
+            .collect()
+    }
+
+    #[test]
+    fn only_precommits_after_a_polka_finalize() {
+        let (mut engine, a, _b) = locking_setup();
+        let block = block_by(&a, 1);
+        let hash = block.header.hash;
+
+        // No polka yet: nothing to precommit.
+        assert!(engine.precommit(&hash, &a).is_none());
+
+        let proposal = engine.create_proposal(block.clone(), &a).unwrap();
+        let prevote = engine.prevote(&proposal, &a).unwrap();
+        let polka = engine.record_prevote(prevote).unwrap().cloned().unwrap();
+
+        // The polka's prevotes do not finalize, and cannot pass for
+        // precommits.
+        assert!(matches!(
+            engine.finalize_block(block.clone(), polka.prevotes.clone()),
+            Err(ConsensusError::InsufficientStake { have: 0, .. })
+        ));
+        let relabeled = Vote {
+            kind: VoteKind::Precommit,
+            ..polka.prevotes[0].clone()
+        };
+        assert!(!relabeled.verify());
+        assert!(matches!(
+            engine.record_prevote(Vote::precommit(&a, hash, 0)),
+            Err(ConsensusError::InvalidVote(_))
+        ));
+
+        // One precommit per round, and 2/3 of the stake of them commit.
+        let precommit = engine.precommit(&hash, &a).unwrap();
+        let commit = engine.record_precommit(precommit).unwrap().unwrap();
+        assert!(engine.precommit(&hash, &a).is_none());
+        assert_eq!(engine.commit_for(&hash), Some(commit.clone()));
+        assert!(engine.commit_for(&BlockHash([9; 32])).is_none());
+
+        let finalized = engine.finalize_block(block, commit.precommits).unwrap();
+        assert_eq!(finalized.votes.len(), 1);
+        assert!(engine.commit_for(&hash).is_none());
+    }
+
+    #[test]
+    fn precommit_only_in_the_polka_round() {
+        let (mut engine, a, b) = locking_setup();
+        let block = block_by(&a, 1);
+        let hash = block.header.hash;
+        engine.record_prevote(Vote::new(&a, hash, 0)).unwrap();
+
+        // Still locked in round 1, but the polka was round 0's.
+        engine.advance_round();
+        assert_eq!(engine.locked().unwrap().round, 0);
+        assert!(engine.precommit(&hash, &a).is_none());
+
+        // A third of the stake precommitting is not a commit.
+        assert!(engine
+            .record_precommit(Vote::precommit(&b, hash, 1))
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            engine.record_precommit(Vote::precommit(&b, hash, 1)),
+            Err(ConsensusError::DuplicateVote(_))
         ));
+        assert!(engine.commit_for(&hash).is_none());
     }
 }
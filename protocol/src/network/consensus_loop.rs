@@ -9,35 +9,93 @@
 //! Each iteration ("round") of the loop:
 //!
 //! 1. Check if we are the designated proposer for the current consensus round.
-//! 2. If yes: produce a block, self-vote (sufficient for single-validator devnet),
-//!    finalize via the consensus engine, and commit to persistent storage.
-//! 3. If no: skip the round. In a production deployment with gossip, we would
-//!    wait for the proposer's block over the network. That plumbing comes later.
+//! 2. If yes: produce a block, wrap it in a signed proposal, prevote for it,
+//!    and gossip both when a service is attached. Once prevotes holding 2/3
+//!    of the stake are in, precommit it; once precommits holding 2/3 of the
+//!    stake are in, finalize via the consensus engine and commit to storage.
+//!    Until then the block stays pending.
+//! 3. If no: skip the round. The proposer's block arrives over gossip (see
+//!    [Peer Messages](#peer-messages)).
 //! 4. Sleep for `block_time_ms` before starting the next round.
 //! 5. If the mempool is empty, add `empty_block_delay_ms` to the sleep — no
 //!    point burning cycles producing blocks that carry no transactions.
 //!
+//! ## Peer Messages
+//!
+//! Whatever receives gossip hands inbound proposals and votes to
+//! [`ConsensusLoop::handle_message`]. A proposal for the current round that
+//! passes the engine's checks is executed against our state, and must
+//! commit to the root we compute, before we record and gossip our prevote.
+//! Every vote goes through the engine's bookkeeping. Once the pending
+//! block's prevotes reach a stake quorum in the current round we lock on it
+//! and gossip our precommit; the precommit that completes a stake quorum
+//! finalizes and commits it. A validator that saw the polka but not the
+//! precommits commits nothing, so it cannot run ahead of the rest.
+//!
+//! A pending block executes on [staged](StateTree::stage) state: its writes
+//! reach sled only when it is committed, so a crash before then never
+//! leaves persisted state ahead of the persisted chain, and a pending block
+//! superseded by a later round's proposal is simply discarded.
+//!
+//! A proposal or vote for a round ahead of ours, typically the next
+//! height's from peers that committed before we did, is held (if signed by
+//! a validator, and at most [`MAX_FUTURE_ROUNDS`] ahead) and replayed once
+//! we reach its round, so a slow validator catches up instead of stalling.
+//!
 //! ## Shutdown
 //!
 //! The loop monitors a `tokio::sync::watch` channel. When the sender drops or
 //! sends `true`, the loop exits cleanly after finishing its current round.
 //! No in-flight blocks are left half-committed.
 //!
+//! ## Clock Drift
+//!
+//! When a [`ClockSkewMonitor`] is attached, the loop refuses to propose while
+//! the peer-median clock offset exceeds the tolerated drift. A drifted
+//! validator stamping blocks is how honest peers end up rejecting them.
+//!
+//! ## Emergency Halts
+//!
+//! Before every round the loop asks the engine whether the next height may
+//! be built (see [`crate::network::halt`]). Under a halt it proposes
+//! nothing; after a resume it restarts in the round the resume fixes.
+//!
+//! ## Liveness
+//!
+//! The votes that finalize a block go into the next block's `last_commit`,
+//! and after every commit the loop reloads the validator set from state so
+//! a validator jailed (or unjailed) by that block leaves (or rejoins) the
+//! rotation from the next round. See [`crate::network::liveness`].
+//!
+//! ## Triggered Production
+//!
+//! With a trigger attached ([`ConsensusLoop::with_trigger`]) the block timer
+//! is ignored: the loop sleeps until notified, then produces blocks until
+//! the mempool is drained. Dev chains notify it as each transaction arrives,
+//! so a test sees its transaction mined immediately and no empty blocks in
+//! between.
+//!
 //! ## Single-Validator Mode
 //!
 //! For devnet and testing, a single validator is both proposer and sole voter.
-//! The quorum threshold is 1, so a self-vote is sufficient for finalization.
-//! Multi-validator gossip-based voting is a separate concern handled by the
-//! networking layer (see `gossip.rs`).
+//! Its own prevote and precommit hold all the stake, so the proposer
+//! finalizes its block in the same round without waiting for the network.
 
-use std::fmt;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use thiserror::Error;
 use tracing::{debug, info, warn};
 
 use crate::crypto::keys::NovaKeypair;
-use crate::network::consensus::{ConsensusEngine, ConsensusError, FinalizedBlock, Vote};
+use crate::crypto::wire::BlockHash;
+use crate::network::clock::{ClockSkewMonitor, ClockStatus};
+use crate::network::consensus::{
+    ConsensusEngine, ConsensusError, FinalizedBlock, Proposal, ValidatorSet, Vote, VoteKind,
+};
+use crate::network::gossip::{GossipService, P2pGossipMessage};
+use crate::network::halt::HaltError;
 use crate::network::mempool::Mempool;
 use crate::network::producer::{BlockProducer, BlockProductionError};
 use crate::storage::db::{DbError, NovaDB};
@@ -48,6 +106,10 @@ use crate::storage::Block;
 // Configuration
 // ---------------------------------------------------------------------------
 
+/// How many rounds ahead of ours a proposal or vote may be and still be
+/// held for replay. Anything further ahead is dropped.
+pub const MAX_FUTURE_ROUNDS: u64 = 16;
+
 /// Tunable parameters for the consensus loop.
 ///
 /// These control timing, throughput, and liveness. Defaults are tuned for
@@ -98,59 +160,44 @@ impl Default for ConsensusLoopConfig {
 /// These map cleanly to the subsystem that failed. The caller (typically the
 /// validator node runtime) can decide whether to retry, log, or shut down
 /// based on the variant.
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ConsensusLoopError {
     /// This node is not in the active validator set. It cannot propose or
     /// vote, so running the consensus loop is pointless.
+    #[error("node is not in the active validator set")]
     NotValidator,
 
     /// The block production pipeline failed (state error, signing error, etc.).
-    ProductionError(BlockProductionError),
+    #[error("block production failed: {0}")]
+    ProductionError(#[from] BlockProductionError),
 
     /// The consensus engine rejected the block or votes (invalid proposer,
     /// insufficient quorum, height mismatch, etc.).
-    ConsensusError(ConsensusError),
+    #[error("consensus error: {0}")]
+    ConsensusError(#[from] ConsensusError),
 
     /// Database persistence failed.
-    DbError(DbError),
+    #[error("database error: {0}")]
+    DbError(#[from] DbError),
+
+    /// Our clock is too far from the network's to safely stamp a block.
+    #[error("refusing to propose: clock offset {offset_ms}ms")]
+    ClockDrift {
+        /// Median offset of the network relative to us, in milliseconds.
+        offset_ms: i64,
+    },
+
+    /// An emergency halt forbids building the next block.
+    #[error("refusing to propose: {0}")]
+    Halted(#[source] HaltError),
 
     /// The shutdown signal was received. This is the happy path — the loop
     /// exited because someone asked it to, not because something broke.
+    #[error("consensus loop received shutdown signal")]
     Shutdown,
 }
 
-impl fmt::Display for ConsensusLoopError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::NotValidator => write!(f, "node is not in the active validator set"),
-            Self::ProductionError(e) => write!(f, "block production failed: {}", e),
-            Self::ConsensusError(e) => write!(f, "consensus error: {}", e),
-            Self::DbError(e) => write!(f, "database error: {}", e),
-            Self::Shutdown => write!(f, "consensus loop received shutdown signal"),
-        }
-    }
-}
-
-impl std::error::Error for ConsensusLoopError {}
-
-impl From<BlockProductionError> for ConsensusLoopError {
-    fn from(e: BlockProductionError) -> Self {
-        Self::ProductionError(e)
-    }
-}
-
-impl From<ConsensusError> for ConsensusLoopError {
-    fn from(e: ConsensusError) -> Self {
-        Self::ConsensusError(e)
-    }
-}
-
-impl From<DbError> for ConsensusLoopError {
-    fn from(e: DbError) -> Self {
-        Self::DbError(e)
-    }
-}
-
 // ---------------------------------------------------------------------------
 // ConsensusLoop
 // ---------------------------------------------------------------------------
@@ -177,8 +224,8 @@ pub struct ConsensusLoop {
     db: Arc<NovaDB>,
 
     /// Sparse Merkle Tree for account state. Held here for state queries
-    /// during epoch boundary evaluation and validator set recalculation.
-    #[allow(dead_code)]
+    /// during epoch boundary evaluation and validator set recalculation,
+    /// and to stage pending blocks until they are committed.
     state_tree: Arc<RwLock<StateTree>>,
 
     /// Priority-ordered transaction pool.
@@ -189,6 +236,22 @@ pub struct ConsensusLoop {
 
     /// Loop timing and throughput configuration.
     config: ConsensusLoopConfig,
+
+    /// Optional clock skew monitor gating block proposals.
+    clock: Option<Arc<ClockSkewMonitor>>,
+    /// Optional gossip service used to broadcast our proposals and votes.
+    gossip: Option<Arc<GossipService>>,
+    /// Block executed at the next height and prevoted for, awaiting a
+    /// quorum.
+    pending: Mutex<Option<PendingBlock>>,
+    /// Proposals and votes for rounds ahead of ours, by round.
+    future: Mutex<BTreeMap<u64, Vec<P2pGossipMessage>>>,
+    /// Optional production trigger replacing the block timer.
+    trigger: Option<Arc<tokio::sync::Notify>>,
+    /// Held for the duration of a round, so rounds driven from outside
+    /// the loop (dev-mode mining) never build on the same tip as the
+    /// loop's own.
+    round_lock: Mutex<()>,
 }
 
 impl ConsensusLoop {
@@ -213,9 +276,38 @@ impl ConsensusLoop {
             mempool,
             keypair,
             config,
+            clock: None,
+            gossip: None,
+            pending: Mutex::new(None),
+            future: Mutex::new(BTreeMap::new()),
+            trigger: None,
+            round_lock: Mutex::new(()),
         }
     }
 
+    /// Attaches a clock skew monitor. While it reports drift, the loop
+    /// skips its proposer slots instead of stamping bad timestamps.
+    pub fn with_clock_monitor(mut self, clock: Arc<ClockSkewMonitor>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Attaches a gossip service. Proposals are then published on the
+    /// proposals topic so every validator votes on the same candidate, and
+    /// our prevotes and precommits on the votes topic.
+    pub fn with_gossip(mut self, gossip: Arc<GossipService>) -> Self {
+        self.gossip = Some(gossip);
+        self
+    }
+
+    /// Attaches a production trigger. [`run`](Self::run) then produces
+    /// only after `trigger` is notified (use `notify_one`, which is not lost
+    /// if the loop is busy), and keeps going until the mempool is drained.
+    pub fn with_trigger(mut self, trigger: Arc<tokio::sync::Notify>) -> Self {
+        self.trigger = Some(trigger);
+        self
+    }
+
     /// Runs the consensus loop until a shutdown signal is received.
     ///
     /// This is the main entry point for block production. It runs indefinitely,
@@ -228,7 +320,14 @@ impl ConsensusLoop {
         &self,
         mut shutdown: tokio::sync::watch::Receiver<bool>,
     ) -> Result<(), ConsensusLoopError> {
-        info!("consensus loop starting");
+        info!(
+            triggered = self.trigger.is_some(),
+            "consensus loop starting"
+        );
+
+        // Triggered mode: set while the last round mined transactions and
+        // more are waiting, so the next round runs without a new nudge.
+        let mut more_pending = false;
 
         loop {
             // Check shutdown signal before each round.
@@ -237,8 +336,23 @@ impl ConsensusLoop {
                 return Err(ConsensusLoopError::Shutdown);
             }
 
+            if let Some(trigger) = &self.trigger {
+                if !more_pending {
+                    tokio::select! {
+                        _ = trigger.notified() => {}
+                        _ = shutdown.changed() => {
+                            info!("consensus loop shutting down while idle");
+                            return Err(ConsensusLoopError::Shutdown);
+                        }
+                    }
+                }
+            }
+
             // Run one round of consensus.
-            match self.run_single_round() {
+            let result = self.run_single_round();
+            more_pending = matches!(&result, Ok(Some(f)) if !f.block.transactions.is_empty())
+                && !self.mempool.is_empty();
+            match result {
                 Ok(Some(finalized)) => {
                     info!(
                         height = finalized.block.header.height,
@@ -258,6 +372,10 @@ impl ConsensusLoop {
                 }
             }
 
+            if self.trigger.is_some() {
+                continue;
+            }
+
             // Determine sleep duration. If the mempool is empty, add extra delay
             // to avoid churning on empty blocks.
             let sleep_ms = if self.mempool.is_empty() {
@@ -281,52 +399,174 @@ impl ConsensusLoop {
     ///
     /// If this validator is the designated proposer for the current round:
     /// 1. Retrieve the latest block from the database (chain tip).
-    /// 2. Produce a new block via the block producer pipeline.
-    /// 3. Cast a self-vote on the produced block.
-    /// 4. Finalize the block through the consensus engine.
-    /// 5. Commit the finalized block to persistent storage.
+    /// 2. Produce a new block via the block producer pipeline, or take the
+    ///    pending block again if we are locked on it.
+    /// 3. Propose the block and cast a self-prevote, subject to the engine's
+    ///    locking rules, gossiping both if a service is attached.
+    /// 4. If our prevote completed a stake quorum, precommit the block, and
+    ///    if that completed a stake quorum too, finalize it through the
+    ///    consensus engine and commit it to persistent storage.
+    ///
+    /// Messages held for the rounds we have reached are replayed first.
+    ///
+    /// Returns `Ok(None)` if this validator is NOT the proposer, or if the
+    /// block is pending until peers' votes arrive through
+    /// [`handle_message`](Self::handle_message).
     ///
-    /// If this validator is NOT the proposer, returns `Ok(None)`. In a
-    /// multi-validator deployment, we would wait for the proposer's block
-    /// via gossip — that coordination layer is not yet wired in.
+    /// Safe to call while [`run`](Self::run) is active: rounds are
+    /// serialized.
     pub fn run_single_round(&self) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        let _round = self.round_lock.lock();
+        self.engine
+            .write()
+            .check_halt()
+            .map_err(ConsensusLoopError::Halted)?;
+        if let Some(finalized) = self.replay_due() {
+            return Ok(Some(finalized));
+        }
         if !self.is_our_turn() {
             return Ok(None);
         }
 
-        let engine = self.engine.read();
-        let current_round = engine.current_round();
-        drop(engine);
-
-        // Step 1: Get the chain tip as parent block.
-        let parent = self.get_latest_block()?;
-
-        // Step 2: Produce a block from the current mempool.
-        let produced = self
-            .producer
-            .produce_block(&parent, self.config.max_txs_per_block)?;
+        if let Some(clock) = &self.clock {
+            let now_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64;
+            if let ClockStatus::Drifted { offset_ms, .. } = clock.status(now_ms) {
+                return Err(ConsensusLoopError::ClockDrift { offset_ms });
+            }
+        }
 
-        // Step 3: Self-vote on the block we just produced.
-        let block_hash = produced.block.header.hash;
-        let vote = self.self_vote(block_hash, current_round);
+        let current_round = self.engine.read().current_round();
+
+        // Steps 1-2: A block we already proposed this round waits for its
+        // votes. One from an earlier round is proposed again if we are
+        // locked on it, and discarded otherwise, before we build on the
+        // chain tip from the current mempool, on staged state.
+        let pending = self.pending.lock().take();
+        let block = match pending {
+            Some(pending) if pending.proposal.round == current_round => {
+                *self.pending.lock() = Some(pending);
+                return Ok(None);
+            }
+            Some(pending) if self.is_locked_on(&pending.proposal.block_hash()) => {
+                pending.proposal.block
+            }
+            _ => {
+                self.restage();
+                let parent = self.get_latest_block()?;
+                let produced = self
+                    .producer
+                    .produce_block(&parent, self.config.max_txs_per_block);
+                match produced {
+                    Ok(produced) => produced.block,
+                    Err(e) => {
+                        self.state_tree.write().discard_staged();
+                        return Err(e.into());
+                    }
+                }
+            }
+        };
 
-        // Step 4: Finalize the block through the consensus engine.
-        let finalized = {
-            let mut engine = self.engine.write();
-            engine.finalize_block(produced.block, vec![vote])?
+        // Step 3: Wrap the block in a signed proposal, publish it, and
+        // prevote under the locking rules. Our own prevote goes through the
+        // same bookkeeping as a peer's would.
+        let proposed = {
+            let engine = self.engine.read();
+            engine
+                .create_proposal(block, &self.keypair)
+                .and_then(|proposal| {
+                    let vote = engine.prevote(&proposal, &self.keypair)?;
+                    Ok((proposal, vote))
+                })
+        };
+        let (proposal, vote) = match proposed {
+            Ok(proposed) => proposed,
+            Err(e) => {
+                self.state_tree.write().discard_staged();
+                return Err(e.into());
+            }
         };
+        if let Some(gossip) = &self.gossip {
+            if let Err(e) = gossip.publish_proposal(&proposal) {
+                warn!(round = current_round, "failed to publish proposal: {}", e);
+            }
+        }
+        *self.pending.lock() = Some(PendingBlock { proposal });
+        self.engine.write().record_prevote(vote.clone())?;
+        self.publish_vote(&vote);
+
+        // Step 4: Precommit once the prevotes hold a quorum, and finalize
+        // once the precommits do. Finalizing takes us to the next round,
+        // whose held messages are then due.
+        let finalized = self.progress()?;
+        let finalized = self.replay_due().or(finalized);
+        if let Some(finalized) = &finalized {
+            debug!(
+                height = finalized.block.header.height,
+                round = finalized.round,
+                txs = finalized.block.transactions.len(),
+                "round completed successfully"
+            );
+        }
+        Ok(finalized)
+    }
 
-        // Step 5: Commit to persistent storage and drain mempool.
-        self.producer.commit_block(&finalized.block)?;
+    /// Handles a consensus message received over gossip: a proposal goes
+    /// to [`handle_proposal`](Self::handle_proposal), a prevote or
+    /// precommit to [`handle_vote`](Self::handle_vote). Other messages are
+    /// ignored.
+    pub fn handle_message(
+        &self,
+        message: P2pGossipMessage,
+    ) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        match message {
+            P2pGossipMessage::BlockProposal(proposal) => self.handle_proposal(proposal),
+            P2pGossipMessage::BlockVote(vote) => self.handle_vote(vote),
+            _ => Ok(None),
+        }
+    }
 
-        debug!(
-            height = finalized.block.header.height,
-            round = finalized.round,
-            txs = finalized.block.transactions.len(),
-            "round completed successfully"
-        );
+    /// Handles the round proposer's proposal.
+    ///
+    /// The proposal must pass the engine's block and proposal checks,
+    /// locking rules included. Its block is then executed against our
+    /// staged state and must commit to the root we compute; only then is
+    /// our prevote recorded and gossiped. Returns the finalized block if
+    /// the votes already in complete its commit, or complete the commit of
+    /// a block held for the round that follows.
+    ///
+    /// Proposals from earlier rounds are ignored, and ones from later
+    /// rounds held until we reach them.
+    pub fn handle_proposal(
+        &self,
+        proposal: Proposal,
+    ) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        let _round = self.round_lock.lock();
+        let finalized = self.on_proposal(proposal)?;
+        Ok(self.replay_due().or(finalized))
+    }
 
-        Ok(Some(finalized))
+    /// Handles a peer's prevote or precommit. Returns the finalized block
+    /// if it completed the pending block's commit, or the commit of a block
+    /// held for the round that follows.
+    ///
+    /// Prevotes from earlier rounds are ignored: a polka there can no
+    /// longer lead to a precommit. Precommits count from any round of the
+    /// height. Votes from later rounds are held until we reach them.
+    pub fn handle_vote(&self, vote: Vote) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        let _round = self.round_lock.lock();
+        let finalized = self.on_vote(vote)?;
+        Ok(self.replay_due().or(finalized))
+    }
+
+    /// Runs `f` while no round is in progress, and keeps new rounds from
+    /// starting until it returns. Lets dev tooling rewrite chain state
+    /// under a running loop.
+    pub fn exclusive<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _round = self.round_lock.lock();
+        f()
     }
 
     /// Returns `true` if this validator is the designated proposer for the
@@ -348,12 +588,12 @@ impl ConsensusLoop {
         proposer.address == our_address
     }
 
-    /// Creates a signed vote from this validator for the given block hash
-    /// and round number.
+    /// Creates a signed prevote from this validator for the given block
+    /// hash and round number.
     ///
-    /// The vote signature covers `block_hash || round.to_le_bytes()`, which
-    /// prevents cross-round replay attacks.
-    pub fn self_vote(&self, block_hash: [u8; 32], round: u64) -> Vote {
+    /// The vote signature covers `"nova/prevote" || block_hash || round`,
+    /// which prevents cross-round replay attacks (see [`Vote::new`]).
+    pub fn self_vote(&self, block_hash: BlockHash, round: u64) -> Vote {
         Vote::new(&self.keypair, block_hash, round)
     }
 
@@ -366,6 +606,234 @@ impl ConsensusLoop {
     // Internal helpers
     // -----------------------------------------------------------------------
 
+    /// [`handle_proposal`](Self::handle_proposal), with the round lock
+    /// held.
+    fn on_proposal(
+        &self,
+        proposal: Proposal,
+    ) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        let current_round = self.engine.read().current_round();
+        if proposal.round < current_round {
+            debug!(
+                round = proposal.round,
+                "ignoring proposal from an earlier round"
+            );
+            return Ok(None);
+        }
+        if proposal.round > current_round {
+            self.hold(P2pGossipMessage::BlockProposal(proposal));
+            return Ok(None);
+        }
+
+        let vote = {
+            let engine = self.engine.read();
+            engine.validate_block(&proposal.block)?;
+            engine.prevote(&proposal, &self.keypair)?
+        };
+
+        // One block per round: the same proposal again is a duplicate, a
+        // different one is refused. A block pending from an earlier round
+        // is discarded.
+        let pending = self.pending.lock().take();
+        if let Some(pending) = pending {
+            if pending.proposal.round == proposal.round {
+                let duplicate = pending.proposal.block_hash() == proposal.block_hash();
+                *self.pending.lock() = Some(pending);
+                if duplicate {
+                    return Ok(None);
+                }
+                return Err(ConsensusError::InvalidProposal(format!(
+                    "conflicting proposal for round {}",
+                    proposal.round
+                ))
+                .into());
+            }
+        }
+
+        self.restage();
+        let parent = self.get_latest_block()?;
+        if let Err(e) = self.producer.apply_proposed(&proposal.block, &parent) {
+            self.state_tree.write().discard_staged();
+            return Err(ConsensusError::from(e).into());
+        }
+        *self.pending.lock() = Some(PendingBlock { proposal });
+
+        self.engine.write().record_prevote(vote.clone())?;
+        self.publish_vote(&vote);
+        self.progress()
+    }
+
+    /// [`handle_vote`](Self::handle_vote), with the round lock held.
+    fn on_vote(&self, vote: Vote) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        let current_round = self.engine.read().current_round();
+        if vote.round > current_round {
+            self.hold(P2pGossipMessage::BlockVote(vote));
+            return Ok(None);
+        }
+        match vote.kind {
+            VoteKind::Prevote if vote.round < current_round => {
+                debug!(round = vote.round, "ignoring prevote from an earlier round");
+                return Ok(None);
+            }
+            VoteKind::Prevote => {
+                self.engine.write().record_prevote(vote)?;
+            }
+            VoteKind::Precommit => {
+                self.engine.write().record_precommit(vote)?;
+            }
+        }
+        self.progress()
+    }
+
+    /// Holds a proposal or vote for a round ahead of ours until we reach
+    /// it. Only messages at most [`MAX_FUTURE_ROUNDS`] ahead, correctly
+    /// signed by a validator, are held, one per signer and kind in each
+    /// round, so a peer cannot fill the buffer.
+    fn hold(&self, message: P2pGossipMessage) {
+        let Some((round, signer, kind)) = held_key(&message) else {
+            return;
+        };
+        let current_round = self.engine.read().current_round();
+        let signed = match &message {
+            P2pGossipMessage::BlockProposal(proposal) => proposal.verify(),
+            P2pGossipMessage::BlockVote(vote) => vote.verify(),
+            _ => false,
+        };
+        if round > current_round.saturating_add(MAX_FUTURE_ROUNDS)
+            || !signed
+            || !self.engine.read().validator_set().contains(signer)
+        {
+            debug!(round, signer, "dropping message for a later round");
+            return;
+        }
+
+        let mut future = self.future.lock();
+        let held = future.entry(round).or_default();
+        if held
+            .iter()
+            .any(|other| held_key(other) == Some((round, signer, kind)))
+        {
+            return;
+        }
+        debug!(round, signer, "holding message for a later round");
+        held.push(message);
+    }
+
+    /// Replays the messages held for rounds we have reached, earliest
+    /// round first. A block they finalize takes us to the next round, whose
+    /// messages are replayed in turn. Returns the last block finalized.
+    fn replay_due(&self) -> Option<FinalizedBlock> {
+        let mut finalized = None;
+        loop {
+            let current_round = self.engine.read().current_round();
+            let due = {
+                let mut future = self.future.lock();
+                match future.first_key_value() {
+                    Some((round, _)) if *round <= current_round => future.pop_first(),
+                    _ => None,
+                }
+            };
+            let Some((round, messages)) = due else {
+                return finalized;
+            };
+            for message in messages {
+                let replayed = match message {
+                    P2pGossipMessage::BlockProposal(proposal) => self.on_proposal(proposal),
+                    P2pGossipMessage::BlockVote(vote) => self.on_vote(vote),
+                    _ => Ok(None),
+                };
+                match replayed {
+                    Ok(Some(block)) => finalized = Some(block),
+                    Ok(None) => {}
+                    Err(e) => debug!(round, error = %e, "held message refused on replay"),
+                }
+            }
+        }
+    }
+
+    /// Moves the pending block along: gossips our precommit for it once we
+    /// have seen its polka in this round, and once precommits from 2/3 of
+    /// the stake are in, finalizes it through the consensus engine, writes
+    /// its staged state and the block to persistent storage and drains the
+    /// mempool. The precommits go into the next block's commit, and a block
+    /// that jailed or unjailed a validator changes the rotation from the
+    /// next round on.
+    ///
+    /// Votes for a block we have not executed yet wait for its proposal.
+    fn progress(&self) -> Result<Option<FinalizedBlock>, ConsensusLoopError> {
+        let Some(block_hash) = self
+            .pending
+            .lock()
+            .as_ref()
+            .map(|pending| pending.proposal.block_hash())
+        else {
+            return Ok(None);
+        };
+
+        let precommit = self.engine.read().precommit(&block_hash, &self.keypair);
+        if let Some(precommit) = precommit {
+            self.engine.write().record_precommit(precommit.clone())?;
+            self.publish_vote(&precommit);
+        }
+
+        let Some(commit) = self.engine.read().commit_for(&block_hash) else {
+            return Ok(None);
+        };
+        let block = match self.pending.lock().as_ref() {
+            Some(pending) => pending.proposal.block.clone(),
+            None => return Ok(None),
+        };
+
+        let finalized = self
+            .engine
+            .write()
+            .finalize_block(block, commit.precommits)?;
+        *self.pending.lock() = None;
+
+        self.state_tree.write().apply_staged();
+        self.producer.commit_block(&finalized.block)?;
+        self.producer.record_commit(&finalized);
+        self.refresh_validator_set();
+        Ok(Some(finalized))
+    }
+
+    /// Discards the state of any block staged before and starts staging
+    /// the next one.
+    fn restage(&self) {
+        let mut tree = self.state_tree.write();
+        tree.discard_staged();
+        tree.stage();
+    }
+
+    /// Gossips our prevote or precommit, if a service is attached.
+    fn publish_vote(&self, vote: &Vote) {
+        if let Some(gossip) = &self.gossip {
+            if let Err(e) = gossip.publish_vote(vote) {
+                warn!(round = vote.round, kind = ?vote.kind, "failed to publish vote: {}", e);
+            }
+        }
+    }
+
+    /// Returns `true` if the engine is locked on the block `block_hash`.
+    fn is_locked_on(&self, block_hash: &BlockHash) -> bool {
+        self.engine
+            .read()
+            .locked()
+            .is_some_and(|lock| lock.block_hash == *block_hash)
+    }
+
+    /// Reloads the validator set from committed state into the engine if
+    /// the block just committed changed it.
+    fn refresh_validator_set(&self) {
+        let Some(set) = ValidatorSet::load_from_state(&self.state_tree.read()) else {
+            return;
+        };
+        let mut engine = self.engine.write();
+        if *engine.validator_set() != set {
+            engine.update_validator_set(set);
+        }
+    }
+
     /// Retrieves the latest block from the database.
     ///
     /// If the DB has a recorded latest height, fetches that block. Otherwise,
@@ -400,6 +868,31 @@ impl ConsensusLoop {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Pending Block
+// ---------------------------------------------------------------------------
+
+/// A proposal whose block has been executed against the staged state tree
+/// and prevoted for, but not finalized.
+struct PendingBlock {
+    /// The proposal, ours or the round proposer's.
+    proposal: Proposal,
+}
+
+/// Round, signer and vote kind (`None` for a proposal) of a consensus
+/// message, or `None` for any other message.
+fn held_key(message: &P2pGossipMessage) -> Option<(u64, &str, Option<VoteKind>)> {
+    match message {
+        P2pGossipMessage::BlockProposal(proposal) => {
+            Some((proposal.round, proposal.proposer.as_str(), None))
+        }
+        P2pGossipMessage::BlockVote(vote) => {
+            Some((vote.round, vote.validator.as_str(), Some(vote.kind)))
+        }
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -409,10 +902,11 @@ mod tests {
     use super::*;
     use crate::crypto::keys::NovaKeypair;
     use crate::network::consensus::{ConsensusConfig, ConsensusEngine, ValidatorSet};
+    use crate::network::halt::{EmergencyHalt, HaltGuard, ResumeAction};
     use crate::network::mempool::{Mempool, MempoolConfig};
     use crate::network::producer::BlockProducer;
     use crate::storage::db::NovaDB;
-    use crate::storage::state::{AccountState, StateTree};
+    use crate::storage::state::{AccountState, StateError, StateTree};
     use crate::storage::Block;
     use crate::transaction::builder::TransactionBuilder;
     use crate::transaction::types::{Amount, Currency, TransactionType};
@@ -445,6 +939,15 @@ mod tests {
         let mut validator_set = ValidatorSet::new();
         validator_set.add_validator(address, 10_000_000_000);
 
+        setup_validator(keypair, validator_set, loop_config)
+    }
+
+    /// Like [`setup_with_config`], for `keypair` as one of `validator_set`.
+    fn setup_validator(
+        keypair: NovaKeypair,
+        validator_set: ValidatorSet,
+        loop_config: ConsensusLoopConfig,
+    ) -> TestHarness {
         let consensus_config = ConsensusConfig {
             min_validators: 1,
             ..ConsensusConfig::default()
@@ -512,7 +1015,7 @@ mod tests {
             .fee(fee)
             .nonce(nonce)
             .timestamp(1_700_000_000_000 + nonce)
-            .build()
+            .build_unchecked()
     }
 
     /// Seeds an account with a given balance in the state tree.
@@ -531,7 +1034,7 @@ mod tests {
 
         seed_balance(&h.state_tree, "nova1alice", 100_000);
 
-        let tx = make_transfer("nova1alice", "nova1bob", 5_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 5_000, 100, 1);
         h.mempool.add(tx).unwrap();
 
         let result = h.consensus_loop.run_single_round();
@@ -633,7 +1136,7 @@ mod tests {
     fn self_vote_valid() {
         let h = setup();
 
-        let block_hash = [42u8; 32];
+        let block_hash = BlockHash([42u8; 32]);
         let vote = h.consensus_loop.self_vote(block_hash, 0);
 
         assert!(vote.verify());
@@ -671,7 +1174,7 @@ mod tests {
         let mut heights = Vec::new();
 
         for i in 0..3u64 {
-            let tx = make_transfer("nova1alice", "nova1bob", 100, 50, i);
+            let tx = make_transfer("nova1alice", "nova1bob", 100, 50, i + 1);
             h.mempool.add(tx).unwrap();
 
             let result = h.consensus_loop.run_single_round().unwrap();
@@ -730,7 +1233,7 @@ mod tests {
 
         seed_balance(&h.state_tree, "nova1alice", 50_000);
 
-        let tx = make_transfer("nova1alice", "nova1bob", 10_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 10_000, 100, 1);
         h.mempool.add(tx).unwrap();
 
         let result = h.consensus_loop.run_single_round().unwrap();
@@ -767,8 +1270,8 @@ mod tests {
 
         seed_balance(&h.state_tree, "nova1alice", 100_000);
 
-        let tx1 = make_transfer("nova1alice", "nova1bob", 1_000, 100, 0);
-        let tx2 = make_transfer("nova1alice", "nova1bob", 2_000, 200, 1);
+        let tx1 = make_transfer("nova1alice", "nova1bob", 1_000, 100, 1);
+        let tx2 = make_transfer("nova1alice", "nova1bob", 2_000, 200, 2);
 
         let tx1_id = tx1.id.clone();
         let tx2_id = tx2.id.clone();
@@ -823,7 +1326,7 @@ mod tests {
 
         // Run 5 rounds, each transferring 10,000 from alice to bob.
         for i in 0..5u64 {
-            let tx = make_transfer("nova1alice", "nova1bob", 10_000, 100, i);
+            let tx = make_transfer("nova1alice", "nova1bob", 10_000, 100, i + 1);
             h.mempool.add(tx).unwrap();
 
             let result = h.consensus_loop.run_single_round().unwrap();
@@ -835,8 +1338,8 @@ mod tests {
         let alice = tree.get("nova1alice").unwrap();
         let bob = tree.get("nova1bob").unwrap();
 
-        // Alice: 1,000,000 - (5 * 10,000) = 950,000
-        assert_eq!(alice.balance, 950_000);
+        // Alice: 1,000,000 - 5 * (10,000 + 100 fee) = 949,500
+        assert_eq!(alice.balance, 949_500);
         // Bob: 5 * 10,000 = 50,000
         assert_eq!(bob.balance, 50_000);
 
@@ -929,7 +1432,9 @@ mod tests {
         let h = setup();
 
         let engine_round = h.engine.read().current_round();
-        let vote = h.consensus_loop.self_vote([0xAB; 32], engine_round);
+        let vote = h
+            .consensus_loop
+            .self_vote(BlockHash([0xAB; 32]), engine_round);
 
         assert_eq!(vote.round, engine_round);
         assert!(vote.verify());
@@ -965,4 +1470,569 @@ mod tests {
         let our_address = h.keypair.public_key().to_hex();
         assert_eq!(finalized.block.header.validator, our_address);
     }
+
+    // -----------------------------------------------------------------------
+    // 20. Drifted clock refuses to propose
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn drifted_clock_refuses_to_propose() {
+        let h = setup();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let clock = Arc::new(ClockSkewMonitor::default());
+        for peer in ["a", "b", "c"] {
+            clock.record_observation(peer, now + 10_000, now);
+        }
+
+        let consensus_loop = h.consensus_loop.with_clock_monitor(Arc::clone(&clock));
+        let result = consensus_loop.run_single_round();
+        assert!(matches!(result, Err(ConsensusLoopError::ClockDrift { .. })));
+        assert_eq!(h.db.get_latest_block_height().unwrap(), Some(0));
+
+        // Once peers agree with us again, proposing resumes.
+        for peer in ["a", "b", "c"] {
+            clock.record_observation(peer, now, now);
+        }
+        assert!(consensus_loop.run_single_round().unwrap().is_some());
+    }
+
+    // -----------------------------------------------------------------------
+    // 21. A triggered loop mines on demand and never on the timer
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn triggered_loop_mines_on_notify() {
+        let h = setup_with_config(ConsensusLoopConfig {
+            block_time_ms: 10,
+            empty_block_delay_ms: 0,
+            ..ConsensusLoopConfig::default()
+        });
+        seed_balance(&h.state_tree, "nova1alice", 100_000);
+
+        let trigger = Arc::new(tokio::sync::Notify::new());
+        let consensus_loop = Arc::new(h.consensus_loop.with_trigger(Arc::clone(&trigger)));
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let task = {
+            let consensus_loop = Arc::clone(&consensus_loop);
+            tokio::spawn(async move { consensus_loop.run(shutdown_rx).await })
+        };
+
+        // Many block intervals pass without a single block.
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        assert_eq!(h.db.get_latest_block_height().unwrap(), Some(0));
+
+        h.mempool
+            .add(make_transfer("nova1alice", "nova1bob", 5_000, 100, 1))
+            .unwrap();
+        trigger.notify_one();
+        for _ in 0..100 {
+            if h.db.get_latest_block_height().unwrap() == Some(1) {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(h.db.get_latest_block_height().unwrap(), Some(1));
+        assert!(h.mempool.is_empty());
+
+        shutdown_tx.send(true).unwrap();
+        assert!(matches!(
+            task.await.unwrap(),
+            Err(ConsensusLoopError::Shutdown)
+        ));
+    }
+
+    // -----------------------------------------------------------------------
+    // 22. An emergency halt stops production until resumed
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn emergency_halt_stops_and_resume_restarts() {
+        let h = setup();
+        let guard = Arc::new(HaltGuard::default());
+        h.engine.write().set_halt_guard(Arc::clone(&guard));
+        let validators = h.engine.read().validator_set().clone();
+
+        let mut halt = EmergencyHalt::new(2, "bad state transition");
+        halt.sign(&h.keypair);
+        guard.apply_halt(halt, &validators, 1).unwrap();
+
+        // Height 1 is still built; height 2 is not.
+        assert!(h.consensus_loop.run_single_round().unwrap().is_some());
+        let result = h.consensus_loop.run_single_round();
+        assert!(matches!(
+            result,
+            Err(ConsensusLoopError::Halted(HaltError::Halted {
+                halt_height: 2
+            }))
+        ));
+        assert_eq!(h.db.get_latest_block_height().unwrap(), Some(1));
+
+        // A local round drifting while halted does not matter: the resume
+        // fixes the round the halt height is proposed in.
+        h.engine.write().advance_round();
+        h.engine.write().advance_round();
+        let mut resume = ResumeAction::new(2, crate::config::PROTOCOL_VERSION);
+        resume.sign(&h.keypair);
+        guard.apply_resume(resume, &validators).unwrap();
+
+        let finalized = h.consensus_loop.run_single_round().unwrap().unwrap();
+        assert_eq!(finalized.block.header.height, 2);
+        assert_eq!(finalized.round, 2);
+    }
+
+    // -----------------------------------------------------------------------
+    // 23. Votes ride in the next block and state changes reach the rotation
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn commit_carried_and_validator_set_refreshed() {
+        let h = setup();
+        let mut validators = h.engine.read().validator_set().clone();
+        validators.store_in_state(&mut h.state_tree.write());
+
+        let first = h.consensus_loop.run_single_round().unwrap().unwrap();
+        assert!(first.block.header.last_commit.is_empty());
+
+        // A jailed peer appears in state; the engine picks it up after the
+        // next commit, still outside the rotation.
+        validators.add_validator("peer".to_string(), 1);
+        validators.set_active("peer", false);
+        validators.store_in_state(&mut h.state_tree.write());
+
+        let second = h.consensus_loop.run_single_round().unwrap().unwrap();
+        let commit = &second.block.header.last_commit;
+        assert_eq!(commit.len(), 1);
+        assert_eq!(commit[0].validator, h.keypair.public_key().to_hex());
+        assert!(second.block.verify().is_ok());
+
+        let engine = h.engine.read();
+        assert_eq!(*engine.validator_set(), validators);
+        assert!(!engine.validator_set().contains("peer"));
+    }
+
+    // -----------------------------------------------------------------------
+    // 24. Wrapped errors keep their source chain
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn loop_errors_keep_their_sources() {
+        use std::error::Error as _;
+
+        let production = BlockProductionError::from(StateError::AccountFrozen("nova:a".into()));
+        let err = ConsensusLoopError::from(production);
+        assert!(matches!(err, ConsensusLoopError::ProductionError(_)));
+
+        // Loop -> production -> state, walkable by anyhow and friends.
+        let state = err
+            .source()
+            .and_then(|e| e.source())
+            .and_then(|e| e.downcast_ref::<StateError>());
+        assert!(matches!(state, Some(StateError::AccountFrozen(_))));
+
+        let halted = ConsensusLoopError::Halted(HaltError::NoActiveHalt);
+        assert!(halted
+            .source()
+            .is_some_and(|e| e.downcast_ref::<HaltError>().is_some()));
+        assert!(ConsensusLoopError::Shutdown.source().is_none());
+    }
+
+    // -----------------------------------------------------------------------
+    // 25. Validators finalize each other's proposals over gossip
+    // -----------------------------------------------------------------------
+
+    /// A validator whose published messages land in `outbox`.
+    struct GossipingValidator {
+        harness: TestHarness,
+        outbox: tokio::sync::mpsc::UnboundedReceiver<P2pGossipMessage>,
+    }
+
+    fn gossiping_validators(count: usize) -> Vec<GossipingValidator> {
+        use crate::network::gossip::GossipServiceConfig;
+
+        let keypairs: Vec<NovaKeypair> = (0..count).map(|_| NovaKeypair::generate()).collect();
+        let mut validator_set = ValidatorSet::new();
+        for keypair in &keypairs {
+            validator_set.add_validator(keypair.public_key().to_hex(), 1_000);
+        }
+        keypairs
+            .into_iter()
+            .map(|keypair| {
+                let mut harness = setup_validator(
+                    keypair,
+                    validator_set.clone(),
+                    ConsensusLoopConfig::default(),
+                );
+                let (gossip, outbox) = GossipService::new(
+                    GossipServiceConfig::default(),
+                    &libp2p::identity::Keypair::generate_ed25519(),
+                );
+                harness.consensus_loop = harness.consensus_loop.with_gossip(Arc::new(gossip));
+                GossipingValidator { harness, outbox }
+            })
+            .collect()
+    }
+
+    /// Delivers every message the validators publish to all the others,
+    /// as a fully connected mesh would, until none are left. Returns the
+    /// blocks they finalized.
+    fn pump(validators: &mut [GossipingValidator]) -> Vec<FinalizedBlock> {
+        let mut finalized = Vec::new();
+        loop {
+            let mut delivered = false;
+            for from in 0..validators.len() {
+                let mut messages = Vec::new();
+                while let Ok(message) = validators[from].outbox.try_recv() {
+                    messages.push(message);
+                }
+                for message in messages {
+                    delivered = true;
+                    for (to, peer) in validators.iter().enumerate() {
+                        if to == from {
+                            continue;
+                        }
+                        match peer.harness.consensus_loop.handle_message(message.clone()) {
+                            Ok(handled) => finalized.extend(handled),
+                            // A locked validator refuses other blocks.
+                            Err(ConsensusLoopError::ConsensusError(
+                                ConsensusError::LockedOnOtherBlock { .. },
+                            )) => {}
+                            Err(e) => panic!("validator {to} failed: {e}"),
+                        }
+                    }
+                }
+            }
+            if !delivered {
+                return finalized;
+            }
+        }
+    }
+
+    #[test]
+    fn validators_finalize_proposals_over_gossip() {
+        use crate::identity::NovaId;
+        use crate::transaction::signing::sign_transaction;
+
+        let mut validators = gossiping_validators(3);
+        let sender = NovaKeypair::generate();
+        let alice = NovaId::from_public_key(&sender.public_key()).to_address();
+        for v in &validators {
+            seed_balance(&v.harness.state_tree, &alice, 100_000);
+        }
+
+        for height in 1..=2u64 {
+            let proposer = validators
+                .iter()
+                .position(|v| v.harness.consensus_loop.is_our_turn())
+                .unwrap();
+            let mut tx = make_transfer(&alice, "nova1bob", 5_000, 100, height);
+            sign_transaction(&mut tx, &sender);
+            validators[proposer].harness.mempool.add(tx).unwrap();
+
+            // The proposer's own prevote is a third of the stake: the
+            // block waits for its peers.
+            let proposing = &validators[proposer].harness;
+            assert!(proposing
+                .consensus_loop
+                .run_single_round()
+                .unwrap()
+                .is_none());
+            assert!(proposing
+                .consensus_loop
+                .run_single_round()
+                .unwrap()
+                .is_none());
+            assert_eq!(
+                proposing.db.get_latest_block_height().unwrap(),
+                Some(height - 1)
+            );
+
+            let finalized = pump(&mut validators);
+            assert_eq!(finalized.len(), 3, "every validator finalizes");
+            let hash = finalized[0].block.header.hash;
+            assert!(finalized.iter().all(|f| f.block.header.hash == hash));
+            assert!(finalized.iter().all(|f| f.votes.len() >= 2));
+            assert_eq!(finalized[0].block.header.height, height);
+        }
+
+        // Every validator replayed the same blocks to the same state.
+        let root = validators[0].harness.state_tree.read().root();
+        for v in &validators {
+            let h = &v.harness;
+            assert_eq!(h.db.get_latest_block_height().unwrap(), Some(2));
+            assert_eq!(h.state_tree.read().root(), root);
+            assert_eq!(h.state_tree.read().get("nova1bob").unwrap().balance, 10_000);
+            assert_eq!(h.engine.read().current_round(), 2);
+        }
+    }
+
+    #[test]
+    fn superseded_proposal_is_undone() {
+        use crate::identity::NovaId;
+        use crate::transaction::signing::sign_transaction;
+
+        let mut validators = gossiping_validators(3);
+        let sender = NovaKeypair::generate();
+        let alice = NovaId::from_public_key(&sender.public_key()).to_address();
+        for v in &validators {
+            seed_balance(&v.harness.state_tree, &alice, 100_000);
+        }
+        let turn = |validators: &[GossipingValidator]| {
+            validators
+                .iter()
+                .position(|v| v.harness.consensus_loop.is_our_turn())
+                .unwrap()
+        };
+        let propose = |v: &mut GossipingValidator| {
+            let round = v.harness.consensus_loop.run_single_round().unwrap();
+            assert!(round.is_none(), "a third of the stake cannot finalize");
+            v.outbox.try_recv().unwrap()
+        };
+
+        // Round 0: the proposer's transfer reaches its peers, which execute
+        // it and keep it pending. The prevotes never make it out.
+        let first = turn(&validators);
+        let mut tx = make_transfer(&alice, "nova1bob", 5_000, 100, 1);
+        sign_transaction(&mut tx, &sender);
+        validators[first].harness.mempool.add(tx).unwrap();
+        let proposal = propose(&mut validators[first]);
+        for (i, v) in validators.iter().enumerate() {
+            if i != first {
+                let handled = v.harness.consensus_loop.handle_message(proposal.clone());
+                assert!(handled.unwrap().is_none());
+                assert!(v.harness.state_tree.read().get("nova1bob").is_some());
+                // Staged only: nothing reached sled.
+                let on_disk = StateTree::new((*v.harness.db).clone());
+                assert!(on_disk.get("nova1bob").is_none());
+            }
+        }
+
+        // The round times out everywhere; its successor proposes an empty
+        // block, which replaces the pending one on the last validator.
+        for v in &validators {
+            v.harness.engine.write().advance_round();
+        }
+        let second = turn(&validators);
+        let follower = (0..3).find(|&i| i != first && i != second).unwrap();
+        let proposal = propose(&mut validators[second]);
+        let peer = &validators[follower].harness;
+        assert!(peer
+            .consensus_loop
+            .handle_message(proposal)
+            .unwrap()
+            .is_none());
+
+        // The first block's writes are gone; the second's are pending.
+        let proposing = &validators[second].harness;
+        assert!(peer.state_tree.read().get("nova1bob").is_none());
+        assert_eq!(
+            peer.state_tree.read().root(),
+            proposing.state_tree.read().root()
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // 26. A polka seen by one validator alone does not commit
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn split_view_of_a_polka_cannot_fork() {
+        let mut validators = gossiping_validators(4);
+        let turn = |validators: &[GossipingValidator]| {
+            validators
+                .iter()
+                .position(|v| v.harness.consensus_loop.is_our_turn())
+                .unwrap()
+        };
+        let drain = |v: &mut GossipingValidator| {
+            let mut messages = Vec::new();
+            while let Ok(message) = v.outbox.try_recv() {
+                messages.push(message);
+            }
+            messages
+        };
+
+        // Round 0: everyone executes and prevotes the first proposal, but
+        // only its proposer hears the prevotes. It locks and precommits;
+        // nobody hears the precommit.
+        let first = turn(&validators);
+        assert!(validators[first]
+            .harness
+            .consensus_loop
+            .run_single_round()
+            .unwrap()
+            .is_none());
+        let proposal = drain(&mut validators[first]).remove(0);
+        let block_b = match &proposal {
+            P2pGossipMessage::BlockProposal(p) => p.block_hash(),
+            other => panic!("expected a proposal, got {other:?}"),
+        };
+        let mut prevotes = Vec::new();
+        for (i, v) in validators.iter_mut().enumerate() {
+            if i != first {
+                let handled = v.harness.consensus_loop.handle_message(proposal.clone());
+                assert!(handled.unwrap().is_none());
+                prevotes.extend(drain(v));
+            }
+        }
+        for prevote in prevotes {
+            let handled = validators[first]
+                .harness
+                .consensus_loop
+                .handle_message(prevote);
+            assert!(handled.unwrap().is_none(), "a polka alone must not commit");
+        }
+        let locker = &validators[first].harness;
+        assert_eq!(locker.engine.read().locked().unwrap().block_hash, block_b);
+        assert_eq!(locker.db.get_latest_block_height().unwrap(), Some(0));
+        let sent = drain(&mut validators[first]);
+        assert!(matches!(
+            sent.as_slice(),
+            [P2pGossipMessage::BlockVote(v)] if v.kind == VoteKind::Precommit
+        ));
+
+        // Round 1: the others, unlocked, move on to a fresh block and
+        // commit it. The locked validator refuses to prevote it and commits
+        // nothing in its place.
+        for v in &validators {
+            v.harness.engine.write().advance_round();
+        }
+        let second = turn(&validators);
+        assert_ne!(second, first);
+        assert!(validators[second]
+            .harness
+            .consensus_loop
+            .run_single_round()
+            .unwrap()
+            .is_none());
+        let finalized = pump(&mut validators);
+        assert_eq!(finalized.len(), 3);
+        let block_b2 = finalized[0].block.header.hash;
+        assert_ne!(block_b2, block_b);
+        assert!(finalized.iter().all(|f| f.block.header.hash == block_b2));
+
+        for v in &validators {
+            if let Some(block) = v.harness.db.get_block(1).unwrap() {
+                assert_eq!(block.header.hash, block_b2);
+            }
+        }
+        let locker = &validators[first].harness;
+        assert_eq!(locker.db.get_latest_block_height().unwrap(), Some(0));
+    }
+
+    // -----------------------------------------------------------------------
+    // 27. A validator behind by a round catches up from held messages
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn later_round_messages_are_held_and_replayed() {
+        let mut validators = gossiping_validators(3);
+        let turn = |validators: &[GossipingValidator]| {
+            validators
+                .iter()
+                .position(|v| v.harness.consensus_loop.is_our_turn())
+                .unwrap()
+        };
+        // Delivers what the two fast validators publish to each other
+        // until they go quiet, and returns all of it.
+        let exchange = |validators: &mut [GossipingValidator], fast: [usize; 2]| {
+            let mut log = Vec::new();
+            loop {
+                let mut delivered = false;
+                for (from, to) in [(fast[0], fast[1]), (fast[1], fast[0])] {
+                    while let Ok(message) = validators[from].outbox.try_recv() {
+                        delivered = true;
+                        let handled = validators[to]
+                            .harness
+                            .consensus_loop
+                            .handle_message(message.clone());
+                        handled.unwrap();
+                        log.push(message);
+                    }
+                }
+                if !delivered {
+                    return log;
+                }
+            }
+        };
+
+        // Heights 1 and 2 are committed by two of the three validators,
+        // in rounds 0 and 1, without the third.
+        let first = turn(&validators);
+        assert!(validators[first]
+            .harness
+            .consensus_loop
+            .run_single_round()
+            .unwrap()
+            .is_none());
+        let next_proposer = {
+            let engine = validators[first].harness.engine.read();
+            engine
+                .validator_set()
+                .proposer_for_round(1)
+                .unwrap()
+                .address
+                .clone()
+        };
+        let second = validators
+            .iter()
+            .position(|v| v.harness.keypair.public_key().to_hex() == next_proposer)
+            .unwrap();
+        assert_ne!(second, first);
+        let slow = (0..3).find(|&i| i != first && i != second).unwrap();
+        let height_1 = exchange(&mut validators, [first, second]);
+        assert!(validators[second]
+            .harness
+            .consensus_loop
+            .run_single_round()
+            .unwrap()
+            .is_none());
+        let height_2 = exchange(&mut validators, [first, second]);
+        assert_eq!(
+            validators[first]
+                .harness
+                .db
+                .get_latest_block_height()
+                .unwrap(),
+            Some(2)
+        );
+
+        // Forged, outsider and far-off messages are not held.
+        let lagging = &validators[slow].harness;
+        let hash = Block::genesis().header.hash;
+        let outsider = Vote::new(&NovaKeypair::generate(), hash, 1);
+        let far_off = Vote::new(&lagging.keypair, hash, MAX_FUTURE_ROUNDS + 1);
+        let mut forged = Vote::new(&lagging.keypair, hash, 1);
+        forged.round = 2;
+        for vote in [outsider, far_off, forged] {
+            let handled = lagging.consensus_loop.handle_vote(vote);
+            assert!(handled.unwrap().is_none());
+        }
+        assert!(lagging.consensus_loop.future.lock().is_empty());
+
+        // The slow validator hears round 1 first: all of it is held.
+        for message in height_2 {
+            let handled = lagging.consensus_loop.handle_message(message);
+            assert!(handled.unwrap().is_none());
+        }
+        assert_eq!(lagging.db.get_latest_block_height().unwrap(), Some(0));
+        assert!(!lagging.consensus_loop.future.lock().is_empty());
+
+        // Round 0 arrives late. Committing height 1 brings it to round 1,
+        // and the held messages commit height 2.
+        let mut finalized = Vec::new();
+        for message in height_1 {
+            finalized.extend(lagging.consensus_loop.handle_message(message).unwrap());
+        }
+        assert_eq!(finalized.last().unwrap().block.header.height, 2);
+        assert_eq!(lagging.db.get_latest_block_height().unwrap(), Some(2));
+        assert!(lagging.consensus_loop.future.lock().is_empty());
+        assert_eq!(
+            lagging.state_tree.read().root(),
+            validators[first].harness.state_tree.read().root()
+        );
+    }
 }
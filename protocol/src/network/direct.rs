@@ -0,0 +1,1037 @@
+//! # Direct NTP Messaging
+//!
+//! NTP ([`crate::ntp`]) leaves the transport for handshake, proof-of-funds
+//! and receipt messages to the wallets. This module carries them between
+//! nodes over a libp2p request-response protocol, [`NTP_DIRECT_PROTOCOL`],
+//! so a wallet only ever talks to its own node:
+//!
+//! ```text
+//! payer wallet ──POST──▶ payer node ══/nova/ntp/1.0══▶ payee node ◀──GET── payee wallet
+//! ```
+//!
+//! Each message is addressed to a *mailbox* on the receiving node — an
+//! opaque name the payee chose and shared with the payer (usually a random
+//! token, later the NTP session ID). The receiving node queues it until the
+//! payee's wallet drains the mailbox.
+//!
+//! ## Encryption
+//!
+//! The node-to-node hop runs over Noise like every other libp2p stream.
+//! Proof and receipt payloads are additionally sealed end to end with the
+//! session's shared secret ([`DirectMessage::seal`]), so neither node can
+//! read or alter them. The AES-GCM associated data binds the kind, session
+//! and mailbox, so a sealed receipt cannot be replayed as a proof or into
+//! another mailbox. Handshake messages travel unsealed — no session key
+//! exists yet, and they carry only public keys and payment parameters.
+//!
+//! ## Wire Format
+//!
+//! Requests and responses are a big-endian `u32` length followed by the
+//! bincode encoding of `(SCHEMA_VERSION, value)`, the same framing gossip
+//! uses. Frames above [`MAX_DIRECT_MESSAGE_SIZE`] are refused before they
+//! are read.
+
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, StreamExt};
+use libp2p::core::ConnectedPoint;
+use libp2p::identity::Keypair;
+use libp2p::multiaddr::Protocol;
+use libp2p::request_response::{self, ProtocolSupport};
+use libp2p::swarm::SwarmEvent;
+use libp2p::{dcutr, gossipsub, identify, relay, Multiaddr, PeerId, StreamProtocol, Swarm};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info, warn};
+
+use crate::config::AES_NONCE_LENGTH;
+use crate::crypto::encryption;
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey};
+use crate::encoding;
+use crate::network::capabilities::Capabilities;
+use crate::network::gossip::{
+    build_swarm, GossipBehaviour, GossipBehaviourEvent, GossipServiceConfig, PeerTransport,
+};
+use crate::network::journal::GossipJournal;
+use crate::network::nat::NatStatus;
+use crate::ntp::handshake::EstablishedSession;
+
+/// Protocol name negotiated for direct NTP messages.
+pub const NTP_DIRECT_PROTOCOL: &str = "/nova/ntp/1.0";
+
+/// Largest encoded request or response accepted on the wire.
+pub const MAX_DIRECT_MESSAGE_SIZE: usize = 64 * 1024;
+
+/// Longest mailbox name accepted.
+pub const MAX_MAILBOX_LEN: usize = 128;
+
+/// Messages held per mailbox; further deliveries are refused until the
+/// wallet drains it.
+pub const MAILBOX_CAPACITY: usize = 64;
+
+/// How long an undrained message is kept.
+pub const MESSAGE_TTL: Duration = Duration::from_secs(600);
+
+/// Most mailboxes held at once, so a peer cannot exhaust memory by
+/// scattering messages over fresh names.
+const MAX_MAILBOXES: usize = 10_000;
+
+/// How long a request may wait for the remote node's acknowledgement.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ---------------------------------------------------------------------------
+// Messages
+// ---------------------------------------------------------------------------
+
+/// Which NTP step a direct message belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectMessageKind {
+    /// A `HandshakeRequest` or `HandshakeResponse`. Never sealed.
+    Handshake,
+    /// A `ProofOfFundsRequest` or `ProofOfFundsResponse`.
+    Proof,
+    /// A `PaymentReceipt`.
+    Receipt,
+}
+
+impl DirectMessageKind {
+    /// Stable lowercase name, as used in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Handshake => "handshake",
+            Self::Proof => "proof",
+            Self::Receipt => "receipt",
+        }
+    }
+}
+
+/// One NTP message in transit between two wallets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectMessage {
+    /// Mailbox on the receiving node.
+    pub mailbox: String,
+    /// The NTP step this message belongs to.
+    pub kind: DirectMessageKind,
+    /// Session the payload is sealed under. Required for every kind but
+    /// [`DirectMessageKind::Handshake`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// The NTP message: plain for handshakes, `nonce || ciphertext`
+    /// otherwise. Opaque to both nodes.
+    #[serde(with = "encoding::hex_bytes")]
+    pub payload: Vec<u8>,
+    /// Sender's Unix timestamp in milliseconds. Informational only.
+    pub sent_at: u64,
+}
+
+impl DirectMessage {
+    /// An unsealed handshake message.
+    pub fn handshake(mailbox: impl Into<String>, payload: Vec<u8>, sent_at: u64) -> Self {
+        Self {
+            mailbox: mailbox.into(),
+            kind: DirectMessageKind::Handshake,
+            session_id: None,
+            payload,
+            sent_at,
+        }
+    }
+
+    /// Seals `plaintext` with the session's shared secret.
+    pub fn seal(
+        session: &EstablishedSession,
+        mailbox: impl Into<String>,
+        kind: DirectMessageKind,
+        plaintext: &[u8],
+        sent_at: u64,
+    ) -> Result<Self, DirectError> {
+        let mailbox = mailbox.into();
+        let aad = associated_data(kind, &session.session_id, &mailbox);
+        let (nonce, ciphertext) =
+            encryption::encrypt_with_aad(&session.shared_secret, plaintext, &aad)
+                .map_err(|_| DirectError::Encryption)?;
+        let mut payload = Vec::with_capacity(nonce.len() + ciphertext.len());
+        payload.extend_from_slice(&nonce);
+        payload.extend_from_slice(&ciphertext);
+        Ok(Self {
+            mailbox,
+            kind,
+            session_id: Some(session.session_id.clone()),
+            payload,
+            sent_at,
+        })
+    }
+
+    /// Decrypts a sealed payload with the session's shared secret.
+    pub fn open(&self, session: &EstablishedSession) -> Result<Vec<u8>, DirectError> {
+        if self.session_id.as_deref() != Some(session.session_id.as_str()) {
+            return Err(DirectError::SessionMismatch);
+        }
+        if self.payload.len() < AES_NONCE_LENGTH {
+            return Err(DirectError::Encryption);
+        }
+        let (nonce, ciphertext) = self.payload.split_at(AES_NONCE_LENGTH);
+        let nonce: [u8; AES_NONCE_LENGTH] = nonce.try_into().expect("split at nonce length");
+        let aad = associated_data(self.kind, &session.session_id, &self.mailbox);
+        encryption::decrypt_with_aad(&session.shared_secret, &nonce, ciphertext, &aad)
+            .map_err(|_| DirectError::Encryption)
+    }
+
+    /// Checks the envelope: a sane mailbox, a bounded payload, and a
+    /// session on every non-handshake message.
+    pub fn validate(&self) -> Result<(), DirectError> {
+        if self.mailbox.is_empty() || self.mailbox.len() > MAX_MAILBOX_LEN {
+            return Err(DirectError::InvalidMessage(format!(
+                "mailbox must be 1-{} characters",
+                MAX_MAILBOX_LEN
+            )));
+        }
+        if self.payload.is_empty() || self.payload.len() > MAX_DIRECT_MESSAGE_SIZE / 2 {
+            return Err(DirectError::InvalidMessage(format!(
+                "payload must be 1-{} bytes",
+                MAX_DIRECT_MESSAGE_SIZE / 2
+            )));
+        }
+        if self.kind != DirectMessageKind::Handshake && self.session_id.is_none() {
+            return Err(DirectError::InvalidMessage(format!(
+                "{} messages must be sealed to a session",
+                self.kind.as_str()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// AES-GCM associated data binding a sealed payload to its envelope.
+fn associated_data(kind: DirectMessageKind, session_id: &str, mailbox: &str) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}\n{}",
+        NTP_DIRECT_PROTOCOL,
+        kind.as_str(),
+        session_id,
+        mailbox
+    )
+    .into_bytes()
+}
+
+/// The receiving node's answer to a [`DirectMessage`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DirectAck {
+    /// The message is queued in its mailbox.
+    Delivered,
+    /// The message was refused.
+    Rejected(String),
+}
+
+/// Errors from direct messaging.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DirectError {
+    /// The envelope is malformed.
+    #[error("invalid message: {0}")]
+    InvalidMessage(String),
+    /// The peer ID or address could not be parsed.
+    #[error("invalid peer: {0}")]
+    InvalidPeer(String),
+    /// Sealing or opening the payload failed.
+    #[error("payload encryption failed")]
+    Encryption,
+    /// The message is sealed under a different session.
+    #[error("message belongs to another session")]
+    SessionMismatch,
+    /// The mailbox (or the mailbox table) is full.
+    #[error("mailbox {0} is full")]
+    MailboxFull(String),
+    /// The swarm task is gone.
+    #[error("direct messaging is not running")]
+    Unavailable,
+    /// Building or starting the swarm failed.
+    #[error("transport error: {0}")]
+    Transport(String),
+    /// The peer's advertised capabilities exclude direct messaging.
+    #[error("peer {0} does not support direct messaging")]
+    Unsupported(String),
+}
+
+// ---------------------------------------------------------------------------
+// Codec
+// ---------------------------------------------------------------------------
+
+/// Length-prefixed bincode codec for [`NTP_DIRECT_PROTOCOL`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectCodec;
+
+/// Encodes one frame (see the module docs).
+pub fn encode_frame<T: Serialize>(value: &T) -> io::Result<Vec<u8>> {
+    let body = bincode::serialize(&(encoding::SCHEMA_VERSION, value))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if body.len() > MAX_DIRECT_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame too large",
+        ));
+    }
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
+/// Decodes a frame body (without its length prefix).
+pub fn decode_frame<T: DeserializeOwned>(body: &[u8]) -> io::Result<T> {
+    let (version, value): (u16, T) =
+        bincode::deserialize(body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if version != encoding::SCHEMA_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported schema version {}", version),
+        ));
+    }
+    Ok(value)
+}
+
+async fn read_frame<T, R>(io: &mut R) -> io::Result<T>
+where
+    T: DeserializeOwned,
+    R: AsyncRead + Unpin + Send,
+{
+    let mut len = [0u8; 4];
+    io.read_exact(&mut len).await?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_DIRECT_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame too large",
+        ));
+    }
+    let mut body = vec![0u8; len];
+    io.read_exact(&mut body).await?;
+    decode_frame(&body)
+}
+
+async fn write_frame<T, W>(io: &mut W, value: &T) -> io::Result<()>
+where
+    T: Serialize,
+    W: AsyncWrite + Unpin + Send,
+{
+    io.write_all(&encode_frame(value)?).await?;
+    io.flush().await
+}
+
+#[async_trait]
+impl request_response::Codec for DirectCodec {
+    type Protocol = StreamProtocol;
+    type Request = DirectMessage;
+    type Response = DirectAck;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<DirectMessage>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_frame(io).await
+    }
+
+    async fn read_response<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<DirectAck>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_frame(io).await
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        request: DirectMessage,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_frame(io, &request).await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        response: DirectAck,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_frame(io, &response).await
+    }
+}
+
+/// The request-response behaviour for [`NTP_DIRECT_PROTOCOL`].
+pub fn new_behaviour() -> request_response::Behaviour<DirectCodec> {
+    request_response::Behaviour::new(
+        [(
+            StreamProtocol::new(NTP_DIRECT_PROTOCOL),
+            ProtocolSupport::Full,
+        )],
+        request_response::Config::default().with_request_timeout(REQUEST_TIMEOUT),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Mailboxes
+// ---------------------------------------------------------------------------
+
+/// Per-name queues of received messages awaiting their wallet.
+#[derive(Default)]
+pub struct Mailboxes {
+    inner: Mutex<HashMap<String, VecDeque<(u64, DirectMessage)>>>,
+}
+
+impl Mailboxes {
+    /// Empty mailboxes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a validated message received at `now_ms`.
+    pub fn deliver(&self, message: DirectMessage, now_ms: u64) -> Result<(), DirectError> {
+        message.validate()?;
+        let mut inner = self.inner.lock();
+        prune_locked(&mut inner, now_ms);
+        if !inner.contains_key(&message.mailbox) && inner.len() >= MAX_MAILBOXES {
+            return Err(DirectError::MailboxFull(message.mailbox));
+        }
+        let queue = inner.entry(message.mailbox.clone()).or_default();
+        if queue.len() >= MAILBOX_CAPACITY {
+            return Err(DirectError::MailboxFull(message.mailbox));
+        }
+        queue.push_back((now_ms, message));
+        Ok(())
+    }
+
+    /// Removes and returns everything queued for `mailbox`, oldest first.
+    pub fn take(&self, mailbox: &str, now_ms: u64) -> Vec<DirectMessage> {
+        let mut inner = self.inner.lock();
+        prune_locked(&mut inner, now_ms);
+        inner
+            .remove(mailbox)
+            .map(|queue| queue.into_iter().map(|(_, m)| m).collect())
+            .unwrap_or_default()
+    }
+
+    /// Total queued messages.
+    pub fn len(&self) -> usize {
+        self.inner.lock().values().map(VecDeque::len).sum()
+    }
+
+    /// True if nothing is queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn prune_locked(inner: &mut HashMap<String, VecDeque<(u64, DirectMessage)>>, now_ms: u64) {
+    let ttl = MESSAGE_TTL.as_millis() as u64;
+    inner.retain(|_, queue| {
+        queue.retain(|(received, _)| now_ms.saturating_sub(*received) < ttl);
+        !queue.is_empty()
+    });
+}
+
+// ---------------------------------------------------------------------------
+// Service
+// ---------------------------------------------------------------------------
+
+/// A message waiting for the swarm task to send it.
+#[derive(Debug)]
+pub struct OutboundDirect {
+    /// The receiving node.
+    pub peer: PeerId,
+    /// Where to dial it, if the swarm does not know yet.
+    pub addr: Option<Multiaddr>,
+    /// The message.
+    pub message: DirectMessage,
+}
+
+/// Where [`DirectService::send`] put a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// The recipient is this node; the message is already in its mailbox.
+    Local,
+    /// Handed to the swarm for sending to a remote node.
+    Queued,
+}
+
+impl Delivery {
+    /// Stable lowercase name, as used in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Queued => "queued",
+        }
+    }
+}
+
+/// The node's end of direct NTP messaging: local mailboxes plus the
+/// outbound queue drained by [`DirectService::run`].
+///
+/// Like [`GossipService`](crate::network::gossip::GossipService), the
+/// service only owns a channel; the swarm lives in the task that drives
+/// it.
+pub struct DirectService {
+    local_peer_id: PeerId,
+    mailboxes: Mailboxes,
+    outbound: mpsc::UnboundedSender<OutboundDirect>,
+    /// Addresses the swarm is listening on, as it reports them.
+    listen_addrs: Mutex<Vec<Multiaddr>>,
+    /// Open connections per transport.
+    connections: Mutex<HashMap<PeerTransport, u64>>,
+    /// Relay reservations, hole punches and reachability.
+    nat: NatStatus,
+    /// Where received gossip is recorded, if journaling is on.
+    journal: Option<GossipJournal>,
+    /// Capabilities peers advertised over identify.
+    peer_capabilities: Mutex<HashMap<PeerId, Capabilities>>,
+}
+
+impl DirectService {
+    /// A service for the node with `keypair`, and the receiver its swarm
+    /// task drains.
+    pub fn new(keypair: &Keypair) -> (Self, mpsc::UnboundedReceiver<OutboundDirect>) {
+        let (outbound, rx) = mpsc::unbounded_channel();
+        let service = Self {
+            local_peer_id: PeerId::from(keypair.public()),
+            mailboxes: Mailboxes::new(),
+            outbound,
+            listen_addrs: Mutex::new(Vec::new()),
+            connections: Mutex::new(HashMap::new()),
+            nat: NatStatus::new(),
+            journal: None,
+            peer_capabilities: Mutex::new(HashMap::new()),
+        };
+        (service, rx)
+    }
+
+    /// This node's peer ID, which counterparties address messages to.
+    pub fn local_peer_id(&self) -> &PeerId {
+        &self.local_peer_id
+    }
+
+    /// The mailboxes holding messages for this node's wallets.
+    pub fn mailboxes(&self) -> &Mailboxes {
+        &self.mailboxes
+    }
+
+    /// Addresses this node listens on, one per transport and interface.
+    pub fn listen_addrs(&self) -> Vec<Multiaddr> {
+        self.listen_addrs.lock().clone()
+    }
+
+    /// Number of open connections over `transport`.
+    pub fn connections(&self, transport: PeerTransport) -> u64 {
+        self.connections
+            .lock()
+            .get(&transport)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Whether and how peers can reach this node.
+    pub fn nat(&self) -> &NatStatus {
+        &self.nat
+    }
+
+    /// What `peer` advertised over identify, if it has connected and
+    /// advertises capabilities at all.
+    pub fn peer_capabilities(&self, peer: &PeerId) -> Option<Capabilities> {
+        self.peer_capabilities.lock().get(peer).cloned()
+    }
+
+    /// Records the capabilities in `peer`'s identify agent string.
+    pub fn on_identify(&self, peer: PeerId, agent_version: &str) {
+        match Capabilities::from_agent_version(agent_version) {
+            Some(caps) => {
+                debug!(%peer, capabilities = %caps, "peer advertised capabilities");
+                self.peer_capabilities.lock().insert(peer, caps);
+            }
+            None => {
+                self.peer_capabilities.lock().remove(&peer);
+            }
+        }
+    }
+
+    fn on_connection(&self, addr: &Multiaddr, opened: bool) {
+        let mut connections = self.connections.lock();
+        let count = connections.entry(PeerTransport::of(addr)).or_insert(0);
+        *count = if opened {
+            *count + 1
+        } else {
+            count.saturating_sub(1)
+        };
+    }
+
+    /// Sends `message` to the mailbox on node `peer_id`, dialling `addr`
+    /// if given. Messages for this node skip the network; peers whose
+    /// advertised capabilities lack direct messaging are refused up front.
+    pub fn send(
+        &self,
+        peer_id: &str,
+        addr: Option<&str>,
+        message: DirectMessage,
+        now_ms: u64,
+    ) -> Result<Delivery, DirectError> {
+        message.validate()?;
+        let peer: PeerId = peer_id
+            .parse()
+            .map_err(|_| DirectError::InvalidPeer(format!("bad peer ID {}", peer_id)))?;
+        let addr = addr
+            .map(|a| {
+                a.parse::<Multiaddr>()
+                    .map_err(|e| DirectError::InvalidPeer(format!("bad address {}: {}", a, e)))
+            })
+            .transpose()?;
+
+        if peer == self.local_peer_id {
+            self.mailboxes.deliver(message, now_ms)?;
+            return Ok(Delivery::Local);
+        }
+        if self
+            .peer_capabilities(&peer)
+            .is_some_and(|caps| !caps.supports(Capabilities::NTP_MESSAGING))
+        {
+            return Err(DirectError::Unsupported(peer_id.to_string()));
+        }
+        self.outbound
+            .send(OutboundDirect {
+                peer,
+                addr,
+                message,
+            })
+            .map_err(|_| DirectError::Unavailable)?;
+        Ok(Delivery::Queued)
+    }
+
+    /// Answers a message received from `peer`.
+    pub fn receive(&self, peer: &PeerId, message: DirectMessage, now_ms: u64) -> DirectAck {
+        match self.mailboxes.deliver(message, now_ms) {
+            Ok(()) => DirectAck::Delivered,
+            Err(e) => {
+                debug!(%peer, error = %e, "refused direct message");
+                DirectAck::Rejected(e.to_string())
+            }
+        }
+    }
+
+    /// Builds a swarm for the node's key, listens on `listen` over TCP —
+    /// over QUIC on the same UDP port if `quic` is set, and through each of
+    /// `relays` — and drives it until `shutdown` fires. With a `journal`,
+    /// the swarm also joins the gossip topics and records what arrives.
+    /// Identify advertises `capabilities` to every peer.
+    pub fn spawn(
+        keypair: &NovaKeypair,
+        listen: SocketAddr,
+        quic: bool,
+        relays: &[String],
+        journal: Option<GossipJournal>,
+        capabilities: Capabilities,
+        shutdown: watch::Receiver<bool>,
+    ) -> Result<std::sync::Arc<Self>, DirectError> {
+        let keypair = peer_keypair(keypair)?;
+        let config = GossipServiceConfig {
+            listen_addr: Multiaddr::from(listen.ip())
+                .with(Protocol::Tcp(listen.port()))
+                .to_string(),
+            quic_listen_addr: quic.then(|| {
+                Multiaddr::from(listen.ip())
+                    .with(Protocol::Udp(listen.port()))
+                    .with(Protocol::QuicV1)
+                    .to_string()
+            }),
+            relays: relays.to_vec(),
+            capabilities,
+            ..Default::default()
+        };
+        let mut swarm =
+            build_swarm(&config, &keypair).map_err(|e| DirectError::Transport(e.to_string()))?;
+        for addr in config
+            .listen_addrs()
+            .map_err(|e| DirectError::Transport(e.to_string()))?
+        {
+            swarm
+                .listen_on(addr)
+                .map_err(|e| DirectError::Transport(e.to_string()))?;
+        }
+        if journal.is_some() {
+            let topics = &config.topics;
+            for topic in [
+                topics.transactions_topic(),
+                topics.blocks_topic(),
+                topics.votes_topic(),
+                topics.proposals_topic(),
+                topics.channels_topic(),
+            ] {
+                swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .subscribe(&topic)
+                    .map_err(|e| DirectError::Transport(e.to_string()))?;
+            }
+        }
+        let (mut service, outbound) = Self::new(&keypair);
+        service.journal = journal;
+        let service = std::sync::Arc::new(service);
+        tokio::spawn(std::sync::Arc::clone(&service).run(swarm, outbound, shutdown));
+        Ok(service)
+    }
+
+    /// Drives `swarm`: sends queued messages and files received ones.
+    pub async fn run(
+        self: std::sync::Arc<Self>,
+        mut swarm: Swarm<GossipBehaviour>,
+        mut outbound: mpsc::UnboundedReceiver<OutboundDirect>,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        loop {
+            tokio::select! {
+                Some(out) = outbound.recv() => {
+                    if let Some(addr) = out.addr {
+                        swarm.add_peer_address(out.peer, addr);
+                    }
+                    swarm.behaviour_mut().direct.send_request(&out.peer, out.message);
+                }
+                event = swarm.select_next_some() => match event {
+                    SwarmEvent::NewListenAddr { address, .. } => {
+                        let transport = PeerTransport::of(&address);
+                        info!(%address, %transport, peer_id = %self.local_peer_id, "direct NTP messaging listening");
+                        self.listen_addrs.lock().push(address);
+                    }
+                    SwarmEvent::ExpiredListenAddr { address, .. } => {
+                        self.nat.on_listen_addr_expired(&address);
+                        self.listen_addrs.lock().retain(|a| *a != address);
+                    }
+                    SwarmEvent::ConnectionEstablished { endpoint, .. } => {
+                        if let ConnectedPoint::Listener { local_addr, send_back_addr } = &endpoint {
+                            self.nat.on_inbound(local_addr, send_back_addr);
+                        }
+                        self.on_connection(endpoint.get_remote_address(), true);
+                    }
+                    SwarmEvent::ConnectionClosed { endpoint, .. } => {
+                        self.on_connection(endpoint.get_remote_address(), false);
+                    }
+                    SwarmEvent::Behaviour(GossipBehaviourEvent::Identify(
+                        identify::Event::Received { peer_id, info, .. },
+                    )) => {
+                        self.on_identify(peer_id, &info.agent_version);
+                    }
+                    SwarmEvent::Behaviour(GossipBehaviourEvent::Direct(event)) => {
+                        self.on_direct_event(&mut swarm, event);
+                    }
+                    SwarmEvent::Behaviour(GossipBehaviourEvent::Gossipsub(
+                        gossipsub::Event::Message { propagation_source, message, .. },
+                    )) => {
+                        self.on_gossip(&propagation_source, &message.data);
+                    }
+                    SwarmEvent::Behaviour(GossipBehaviourEvent::RelayClient(
+                        relay::client::Event::ReservationReqAccepted { relay_peer_id, .. },
+                    )) => {
+                        info!(relay = %relay_peer_id, "relay reservation accepted");
+                        self.nat.on_reservation(relay_peer_id);
+                    }
+                    SwarmEvent::Behaviour(GossipBehaviourEvent::Dcutr(dcutr::Event {
+                        remote_peer_id,
+                        result,
+                    })) => {
+                        match &result {
+                            Ok(_) => info!(peer = %remote_peer_id, "hole punch upgraded relayed connection"),
+                            Err(error) => debug!(peer = %remote_peer_id, %error, "hole punch failed"),
+                        }
+                        self.nat.on_hole_punch(result.is_ok());
+                    }
+                    _ => {}
+                },
+                _ = shutdown.changed() => break,
+            }
+        }
+    }
+
+    fn on_gossip(&self, peer: &PeerId, data: &[u8]) {
+        let Some(journal) = &self.journal else {
+            return;
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        match journal.record(&peer.to_string(), data, now) {
+            Ok(entry) => {
+                debug!(%peer, kind = entry.kind.as_str(), verdict = ?entry.verdict, "journaled gossip")
+            }
+            Err(e) => warn!(%peer, error = %e, "failed to journal gossip message"),
+        }
+    }
+
+    fn on_direct_event(
+        &self,
+        swarm: &mut Swarm<GossipBehaviour>,
+        event: request_response::Event<DirectMessage, DirectAck>,
+    ) {
+        match event {
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Request {
+                        request, channel, ..
+                    },
+            } => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                let ack = self.receive(&peer, request, now);
+                let _ = swarm.behaviour_mut().direct.send_response(channel, ack);
+            }
+            request_response::Event::Message {
+                peer,
+                message: request_response::Message::Response { response, .. },
+            } => {
+                if let DirectAck::Rejected(reason) = response {
+                    warn!(%peer, %reason, "peer refused direct message");
+                }
+            }
+            request_response::Event::OutboundFailure { peer, error, .. } => {
+                warn!(%peer, %error, "direct message not delivered");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The libp2p identity for a node's network key: the same Ed25519 key, so
+/// a node's peer ID follows from it. Validators run with a network key
+/// separate from their consensus key (see [`crate::network::network_keys`]).
+pub fn peer_keypair(keypair: &NovaKeypair) -> Result<Keypair, DirectError> {
+    Keypair::ed25519_from_bytes(keypair.secret_key_bytes())
+        .map_err(|e| DirectError::Transport(e.to_string()))
+}
+
+/// The peer ID [`peer_keypair`] yields for the holder of `key`.
+pub fn peer_id_of(key: &NovaPublicKey) -> Result<PeerId, DirectError> {
+    let key = libp2p::identity::ed25519::PublicKey::try_from_bytes(key.as_bytes())
+        .map_err(|e| DirectError::Transport(e.to_string()))?;
+    Ok(PeerId::from_public_key(&key.into()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntp::handshake::{HandshakeSession, PaymentParams};
+    use crate::transaction::types::Currency;
+
+    fn sessions() -> (EstablishedSession, EstablishedSession) {
+        let payer = NovaKeypair::from_seed(&[1; 32]);
+        let payee = NovaKeypair::from_seed(&[2; 32]);
+        let (initiator, request) = HandshakeSession::initiate(&payer, vec![Currency::NOVA]);
+        let params = PaymentParams {
+            amount: 1_000,
+            currency: Currency::NOVA,
+            description: "coffee".to_string(),
+            capture: Default::default(),
+        };
+        let (response, payee_session) =
+            HandshakeSession::respond(&request, &payee, params).unwrap();
+        let payer_session = initiator.complete(&response).unwrap();
+        (payer_session, payee_session)
+    }
+
+    fn keypair() -> Keypair {
+        peer_keypair(&NovaKeypair::from_seed(&[9; 32])).unwrap()
+    }
+
+    #[test]
+    fn sealed_payloads_round_trip_and_bind_their_envelope() {
+        let (payer, payee) = sessions();
+        let sealed =
+            DirectMessage::seal(&payer, "box-1", DirectMessageKind::Receipt, b"receipt", 1)
+                .unwrap();
+        assert_eq!(sealed.open(&payee).unwrap(), b"receipt");
+
+        // Moving the payload to another mailbox or kind breaks the tag.
+        let moved = DirectMessage {
+            mailbox: "box-2".into(),
+            ..sealed.clone()
+        };
+        assert!(matches!(moved.open(&payee), Err(DirectError::Encryption)));
+        let relabelled = DirectMessage {
+            kind: DirectMessageKind::Proof,
+            ..sealed
+        };
+        assert!(matches!(
+            relabelled.open(&payee),
+            Err(DirectError::Encryption)
+        ));
+    }
+
+    #[test]
+    fn frames_round_trip_and_refuse_oversize() {
+        let message = DirectMessage::handshake("box", vec![7; 32], 5);
+        let frame = encode_frame(&message).unwrap();
+        assert_eq!(
+            u32::from_be_bytes(frame[..4].try_into().unwrap()) as usize,
+            frame.len() - 4
+        );
+        let decoded: DirectMessage = decode_frame(&frame[4..]).unwrap();
+        assert_eq!(decoded, message);
+
+        let huge = DirectMessage::handshake("box", vec![0; MAX_DIRECT_MESSAGE_SIZE], 5);
+        assert!(encode_frame(&huge).is_err());
+    }
+
+    #[test]
+    fn counts_connections_per_transport() {
+        let (service, _outbound) = DirectService::new(&keypair());
+        let tcp: Multiaddr = "/ip4/10.0.0.1/tcp/9740".parse().unwrap();
+        let quic: Multiaddr = "/ip4/10.0.0.2/udp/9740/quic-v1".parse().unwrap();
+
+        service.on_connection(&tcp, true);
+        service.on_connection(&quic, true);
+        service.on_connection(&quic, true);
+        service.on_connection(&quic, false);
+        assert_eq!(service.connections(PeerTransport::Tcp), 1);
+        assert_eq!(service.connections(PeerTransport::Quic), 1);
+
+        service.on_connection(&tcp, false);
+        service.on_connection(&tcp, false);
+        assert_eq!(service.connections(PeerTransport::Tcp), 0);
+    }
+
+    #[test]
+    fn mailboxes_queue_drain_and_expire() {
+        let boxes = Mailboxes::new();
+        boxes
+            .deliver(DirectMessage::handshake("a", vec![1], 0), 1_000)
+            .unwrap();
+        boxes
+            .deliver(DirectMessage::handshake("a", vec![2], 0), 2_000)
+            .unwrap();
+
+        let drained = boxes.take("a", 3_000);
+        assert_eq!(
+            drained.iter().map(|m| m.payload[0]).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert!(boxes.take("a", 3_000).is_empty());
+
+        boxes
+            .deliver(DirectMessage::handshake("b", vec![1], 0), 0)
+            .unwrap();
+        let expired = MESSAGE_TTL.as_millis() as u64;
+        assert!(boxes.take("b", expired).is_empty());
+
+        for _ in 0..MAILBOX_CAPACITY {
+            boxes
+                .deliver(DirectMessage::handshake("c", vec![1], 0), 0)
+                .unwrap();
+        }
+        assert!(matches!(
+            boxes.deliver(DirectMessage::handshake("c", vec![1], 0), 0),
+            Err(DirectError::MailboxFull(_))
+        ));
+    }
+
+    #[test]
+    fn unsealed_proofs_are_refused() {
+        let proof = DirectMessage {
+            kind: DirectMessageKind::Proof,
+            ..DirectMessage::handshake("box", vec![1], 0)
+        };
+        assert!(matches!(
+            proof.validate(),
+            Err(DirectError::InvalidMessage(_))
+        ));
+    }
+
+    #[test]
+    fn peer_id_follows_from_the_public_key() {
+        let keypair = NovaKeypair::from_seed(&[9; 32]);
+        let from_secret = peer_keypair(&keypair).unwrap().public().to_peer_id();
+        assert_eq!(peer_id_of(&keypair.public_key()).unwrap(), from_secret);
+    }
+
+    #[tokio::test]
+    async fn send_delivers_locally_or_queues() {
+        let kp = keypair();
+        let (service, mut outbound) = DirectService::new(&kp);
+        let me = service.local_peer_id().to_string();
+
+        let delivery = service
+            .send(&me, None, DirectMessage::handshake("box", vec![1], 0), 10)
+            .unwrap();
+        assert_eq!(delivery, Delivery::Local);
+        assert_eq!(service.mailboxes().take("box", 10).len(), 1);
+
+        let other = PeerId::from(Keypair::generate_ed25519().public());
+        let delivery = service
+            .send(
+                &other.to_string(),
+                Some("/ip4/127.0.0.1/tcp/9740"),
+                DirectMessage::handshake("box", vec![1], 0),
+                10,
+            )
+            .unwrap();
+        assert_eq!(delivery, Delivery::Queued);
+        let queued = outbound.recv().await.unwrap();
+        assert_eq!(queued.peer, other);
+        assert!(queued.addr.is_some());
+
+        assert!(matches!(
+            service.send(
+                "not-a-peer",
+                None,
+                DirectMessage::handshake("box", vec![1], 0),
+                10
+            ),
+            Err(DirectError::InvalidPeer(_))
+        ));
+    }
+
+    #[test]
+    fn send_respects_advertised_capabilities() {
+        let (service, _outbound) = DirectService::new(&keypair());
+        let old = PeerId::from(Keypair::generate_ed25519().public());
+        let relay_only = PeerId::from(Keypair::generate_ed25519().public());
+        let messaging = PeerId::from(Keypair::generate_ed25519().public());
+
+        service.on_identify(old, "rust-libp2p/0.53");
+        service.on_identify(relay_only, &Capabilities::new().agent_version("nova/0.1.0"));
+        service.on_identify(
+            messaging,
+            &Capabilities::new()
+                .with(Capabilities::NTP_MESSAGING, true)
+                .agent_version("nova/0.1.0"),
+        );
+        assert!(service.peer_capabilities(&old).is_none());
+
+        let send = |peer: &PeerId| {
+            service.send(
+                &peer.to_string(),
+                None,
+                DirectMessage::handshake("box", vec![1], 0),
+                10,
+            )
+        };
+        assert_eq!(send(&old).unwrap(), Delivery::Queued);
+        assert_eq!(send(&messaging).unwrap(), Delivery::Queued);
+        assert!(matches!(
+            send(&relay_only),
+            Err(DirectError::Unsupported(_))
+        ));
+    }
+}
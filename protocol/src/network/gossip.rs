@@ -37,16 +37,23 @@ use std::time::Duration;
 use dashmap::DashMap;
 use libp2p::gossipsub::{self, IdentTopic, MessageAuthenticity};
 use libp2p::identity::Keypair;
+use libp2p::multiaddr::Protocol;
 use libp2p::swarm::NetworkBehaviour;
-use libp2p::{identify, PeerId, Swarm};
+use libp2p::{dcutr, identify, relay, request_response, Multiaddr, PeerId, Swarm};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{debug, trace};
 
-use crate::network::consensus::Vote;
+use crate::encoding;
+use crate::network::capabilities::Capabilities;
+use crate::network::consensus::{Proposal, Vote};
+use crate::network::direct::DirectCodec;
+use crate::network::scoring::PeerScoring;
+use crate::ntp::routing::ChannelAdvertisement;
 use crate::storage::Block;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionCancellation};
 
 // ===========================================================================
 // Layer 1: Epidemic Gossip (application-level)
@@ -99,6 +106,52 @@ pub struct PeerInfo {
     pub connected_at: u64,
     /// Last time a heartbeat was received from this peer (Unix ms).
     pub last_seen: u64,
+    /// Transport the connection runs over.
+    #[serde(default)]
+    pub transport: PeerTransport,
+}
+
+/// The transport a P2P connection runs over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerTransport {
+    /// TCP, secured with Noise and multiplexed with Yamux.
+    #[default]
+    Tcp,
+    /// QUIC v1 over UDP. Faster handshakes, and connections survive a
+    /// change of network — useful for mobile and NAT-ed validators.
+    Quic,
+}
+
+impl PeerTransport {
+    /// Every transport, in a fixed order.
+    pub const ALL: [PeerTransport; 2] = [Self::Tcp, Self::Quic];
+
+    /// The transport `addr` is dialled or listened on over.
+    pub fn of(addr: &Multiaddr) -> Self {
+        if addr
+            .iter()
+            .any(|p| matches!(p, Protocol::QuicV1 | Protocol::Quic))
+        {
+            Self::Quic
+        } else {
+            Self::Tcp
+        }
+    }
+
+    /// Lower-case name, as used in metric labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Quic => "quic",
+        }
+    }
+}
+
+impl fmt::Display for PeerTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -245,6 +298,7 @@ impl GossipProtocol {
             .unwrap_or_default()
             .as_millis() as u64;
         self.seen_messages.insert(hash, now);
+        self.maybe_evict_seen_cache();
 
         // Select target peers (up to fanout).
         let peers = self.peers.read();
@@ -390,7 +444,7 @@ impl GossipProtocol {
 ///
 /// Unlike the epidemic `GossipMessage` which carries TTL metadata for
 /// application-level forwarding, `P2pGossipMessage` is a clean envelope
-/// for the three types of data that flow through the network. Gossipsub
+/// for the kinds of data that flow through the network. Gossipsub
 /// handles deduplication and mesh propagation at the transport level, so
 /// we don't need TTL here.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -401,17 +455,25 @@ pub enum P2pGossipMessage {
     NewBlock(Block),
     /// A consensus vote (prevote or precommit) from a validator.
     BlockVote(Vote),
+    /// A signed block proposal for a consensus round.
+    BlockProposal(Proposal),
+    /// A forwarding channel's fees and capacity, for multi-hop route
+    /// discovery ([`crate::ntp::routing`]).
+    ChannelAdvertisement(ChannelAdvertisement),
+    /// A sender's signed withdrawal of a pending transaction; peers drop it
+    /// from their mempools (see [`crate::network::Mempool::cancel`]).
+    CancelTransaction(TransactionCancellation),
 }
 
 // ---------------------------------------------------------------------------
 // Gossip Topics
 // ---------------------------------------------------------------------------
 
-/// Topic strings for the three gossipsub channels.
+/// Topic strings for the gossipsub channels.
 ///
 /// Each message type gets its own topic so that nodes can subscribe
 /// selectively. Light clients might subscribe only to blocks, while
-/// validators need all three. Separate topics also let us tune gossipsub
+/// validators need all of them. Separate topics also let us tune gossipsub
 /// parameters (mesh size, scoring) per message type if needed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GossipTopics {
@@ -421,6 +483,20 @@ pub struct GossipTopics {
     pub blocks: String,
     /// Topic for consensus votes.
     pub votes: String,
+    /// Topic for consensus block proposals.
+    #[serde(default = "default_proposals_topic")]
+    pub proposals: String,
+    /// Topic for payment channel advertisements.
+    #[serde(default = "default_channels_topic")]
+    pub channels: String,
+}
+
+fn default_proposals_topic() -> String {
+    "nova-proposals".to_string()
+}
+
+fn default_channels_topic() -> String {
+    "nova-channels".to_string()
 }
 
 impl Default for GossipTopics {
@@ -429,6 +505,8 @@ impl Default for GossipTopics {
             transactions: "nova-transactions".to_string(),
             blocks: "nova-blocks".to_string(),
             votes: "nova-votes".to_string(),
+            proposals: default_proposals_topic(),
+            channels: default_channels_topic(),
         }
     }
 }
@@ -448,6 +526,16 @@ impl GossipTopics {
     pub fn votes_topic(&self) -> IdentTopic {
         IdentTopic::new(&self.votes)
     }
+
+    /// Returns the proposals topic as a gossipsub `IdentTopic`.
+    pub fn proposals_topic(&self) -> IdentTopic {
+        IdentTopic::new(&self.proposals)
+    }
+
+    /// Returns the channel advertisements topic as a gossipsub `IdentTopic`.
+    pub fn channels_topic(&self) -> IdentTopic {
+        IdentTopic::new(&self.channels)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -461,8 +549,17 @@ impl GossipTopics {
 /// hardware — adjust `mesh_n` and friends for larger deployments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GossipServiceConfig {
-    /// Multiaddr to listen on (e.g., "/ip4/0.0.0.0/tcp/9740").
+    /// Multiaddr to listen on over TCP (e.g., "/ip4/0.0.0.0/tcp/9740").
     pub listen_addr: String,
+    /// Multiaddr to also listen on over QUIC (e.g.,
+    /// "/ip4/0.0.0.0/udp/9740/quic-v1"). `None` listens on TCP only; the
+    /// swarm can dial QUIC peers either way.
+    #[serde(default)]
+    pub quic_listen_addr: Option<String>,
+    /// Static circuit relays to reserve a slot on, each ending in
+    /// `/p2p/<relay peer id>`. Lets peers reach a node behind NAT.
+    #[serde(default)]
+    pub relays: Vec<String>,
     /// Topic configuration.
     pub topics: GossipTopics,
     /// Target number of peers in the gossipsub mesh per topic.
@@ -480,19 +577,58 @@ pub struct GossipServiceConfig {
     /// Maximum gossip message size in bytes. Messages exceeding this are
     /// dropped at the transport level before deserialization.
     pub max_message_size: usize,
+    /// Peer scoring parameters and thresholds (see [`super::scoring`]).
+    #[serde(default)]
+    pub scoring: PeerScoring,
+    /// Capabilities advertised in the identify agent string (see
+    /// [`super::capabilities`]).
+    #[serde(default)]
+    pub capabilities: Capabilities,
 }
 
 impl Default for GossipServiceConfig {
     fn default() -> Self {
         Self {
             listen_addr: format!("/ip4/0.0.0.0/tcp/{}", crate::config::DEFAULT_P2P_PORT),
+            quic_listen_addr: None,
+            relays: Vec::new(),
             topics: GossipTopics::default(),
             mesh_n: 6,
             mesh_n_low: 4,
             mesh_n_high: 12,
             heartbeat_interval_ms: 1000,
             max_message_size: 1024 * 1024, // 1 MiB — enough for the largest blocks.
+            scoring: PeerScoring::default(),
+            capabilities: Capabilities::compiled(),
+        }
+    }
+}
+
+impl GossipServiceConfig {
+    /// Every address to listen on: `listen_addr`, then `quic_listen_addr`
+    /// if set, then a `/p2p-circuit` address through each relay.
+    pub fn listen_addrs(&self) -> Result<Vec<Multiaddr>, GossipError> {
+        let mut addrs = std::iter::once(&self.listen_addr)
+            .chain(self.quic_listen_addr.as_ref())
+            .map(|addr| {
+                addr.parse().map_err(|e| {
+                    GossipError::TransportError(format!("listen address {}: {}", addr, e))
+                })
+            })
+            .collect::<Result<Vec<Multiaddr>, _>>()?;
+        for relay in &self.relays {
+            let addr: Multiaddr = relay.parse().map_err(|e| {
+                GossipError::TransportError(format!("relay address {}: {}", relay, e))
+            })?;
+            if !matches!(addr.iter().last(), Some(Protocol::P2p(_))) {
+                return Err(GossipError::TransportError(format!(
+                    "relay address {} must end in /p2p/<peer id>",
+                    relay
+                )));
+            }
+            addrs.push(addr.with(Protocol::P2pCircuit));
         }
+        Ok(addrs)
     }
 }
 
@@ -505,34 +641,26 @@ impl Default for GossipServiceConfig {
 /// Intentionally coarse-grained — callers care about *what* failed, not the
 /// exact libp2p error variant three layers deep. The inner `String` carries
 /// enough context for debugging without leaking implementation details.
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum GossipError {
     /// Bincode serialization or deserialization failed.
+    #[error("serialization error: {0}")]
     Serialization(String),
     /// Failed to publish a message to a gossipsub topic.
+    #[error("publish error: {0}")]
     PublishError(String),
     /// Failed to subscribe to a gossipsub topic.
+    #[error("subscription error: {0}")]
     SubscriptionError(String),
     /// Transport-level error (TCP, Noise, Yamux).
+    #[error("transport error: {0}")]
     TransportError(String),
     /// Received message that could not be decoded into a known type.
+    #[error("invalid message: {0}")]
     InvalidMessage(String),
 }
 
-impl fmt::Display for GossipError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Serialization(msg) => write!(f, "serialization error: {}", msg),
-            Self::PublishError(msg) => write!(f, "publish error: {}", msg),
-            Self::SubscriptionError(msg) => write!(f, "subscription error: {}", msg),
-            Self::TransportError(msg) => write!(f, "transport error: {}", msg),
-            Self::InvalidMessage(msg) => write!(f, "invalid message: {}", msg),
-        }
-    }
-}
-
-impl std::error::Error for GossipError {}
-
 // ---------------------------------------------------------------------------
 // Combined Network Behaviour
 // ---------------------------------------------------------------------------
@@ -541,13 +669,24 @@ impl std::error::Error for GossipError {}
 ///
 /// Gossipsub handles pub/sub message propagation. Identify lets peers
 /// exchange metadata (protocol version, listen addresses) on connection,
-/// which is essential for NAT traversal and peer discovery.
+/// which is essential for NAT traversal and peer discovery. Direct carries
+/// point-to-point NTP messages between wallets' nodes
+/// ([`crate::network::direct`]). The relay client and DCUtR let nodes
+/// behind NAT be reached through a relay and then upgrade to a direct
+/// connection ([`crate::network::nat`]).
 #[derive(NetworkBehaviour)]
 pub struct GossipBehaviour {
     /// Gossipsub protocol for topic-based message propagation.
     pub gossipsub: gossipsub::Behaviour,
     /// Identify protocol for peer metadata exchange.
     pub identify: identify::Behaviour,
+    /// Request-response protocol for direct NTP messages.
+    pub direct: request_response::Behaviour<DirectCodec>,
+    /// Circuit relay v2 client: reservations on static relays, and
+    /// relayed connections to NAT-ed peers.
+    pub relay_client: relay::client::Behaviour,
+    /// Direct connection upgrade through relay (hole punching).
+    pub dcutr: dcutr::Behaviour,
 }
 
 // ---------------------------------------------------------------------------
@@ -557,32 +696,49 @@ pub struct GossipBehaviour {
 /// Serialize a `P2pGossipMessage` to bincode bytes for wire transmission.
 ///
 /// Bincode is deterministic for the same input, compact, and fast. The
-/// encoded output is suitable for publishing directly to a gossipsub topic.
+/// frame is the [`SCHEMA_VERSION`](crate::encoding::SCHEMA_VERSION) as a
+/// little-endian `u16` followed by the message, and is suitable for
+/// publishing directly to a gossipsub topic.
 pub fn encode_message(msg: &P2pGossipMessage) -> Vec<u8> {
     // bincode::serialize returns Result but should never fail for our types
     // (no unsupported types like maps with non-string keys). Unwrap is safe.
-    bincode::serialize(msg).expect("P2pGossipMessage serialization should never fail")
+    bincode::serialize(&(encoding::SCHEMA_VERSION, msg))
+        .expect("P2pGossipMessage serialization should never fail")
 }
 
 /// Deserialize bincode bytes back into a `P2pGossipMessage`.
 ///
 /// Returns a `GossipError::Serialization` if the bytes are malformed or
-/// truncated. This is expected for messages from misbehaving peers — the
-/// caller should log and drop, not panic.
+/// truncated, or if the frame was written under a different schema
+/// version. This is expected for messages from misbehaving (or not yet
+/// upgraded) peers — the caller should log and drop, not panic.
 pub fn decode_message(data: &[u8]) -> Result<P2pGossipMessage, GossipError> {
-    bincode::deserialize(data).map_err(|e| GossipError::Serialization(e.to_string()))
+    let (version, msg): (u16, P2pGossipMessage) =
+        bincode::deserialize(data).map_err(|e| GossipError::Serialization(e.to_string()))?;
+    if version != encoding::SCHEMA_VERSION {
+        return Err(GossipError::Serialization(format!(
+            "unsupported schema version {} (expected {})",
+            version,
+            encoding::SCHEMA_VERSION
+        )));
+    }
+    Ok(msg)
 }
 
 // ---------------------------------------------------------------------------
 // Swarm Construction
 // ---------------------------------------------------------------------------
 
-/// Build a fully configured libp2p `Swarm` with gossipsub and identify.
+/// Build a fully configured libp2p `Swarm` with gossipsub, identify and
+/// direct NTP messaging.
 ///
-/// The returned swarm is ready to listen and dial but is NOT yet running
-/// its event loop. The caller (node binary) is responsible for:
+/// The swarm carries TCP, QUIC and relayed transports, so it can dial
+/// peers advertising any of them. The returned swarm is ready to listen and dial but
+/// is NOT yet running its event loop. The caller (node binary) is
+/// responsible for:
 ///
-/// 1. Calling `swarm.listen_on(...)` with the configured address.
+/// 1. Calling `swarm.listen_on(...)` with each of
+///    [`GossipServiceConfig::listen_addrs`]; identify advertises them all.
 /// 2. Subscribing to topics via `swarm.behaviour_mut().gossipsub.subscribe(...)`.
 /// 3. Driving the swarm in a `tokio::select!` loop.
 ///
@@ -606,24 +762,34 @@ pub fn build_swarm(
     // Messages are signed with the node's identity keypair. This prevents
     // message spoofing and enables gossipsub's peer scoring to attribute
     // messages correctly.
-    let gossipsub_behaviour = gossipsub::Behaviour::new(
+    let mut gossipsub_behaviour = gossipsub::Behaviour::new(
         MessageAuthenticity::Signed(keypair.clone()),
         gossipsub_config,
     )
     .map_err(|e| GossipError::TransportError(format!("gossipsub behaviour: {}", e)))?;
 
-    // Identify protocol — exchange metadata on every new connection.
+    if config.scoring.enabled {
+        gossipsub_behaviour
+            .with_peer_score(
+                config.scoring.params(&config.topics),
+                config.scoring.thresholds.to_gossipsub(),
+            )
+            .map_err(|e| GossipError::TransportError(format!("peer scoring: {}", e)))?;
+    }
+
+    // Identify protocol — exchange metadata on every new connection. The
+    // agent string carries our capabilities.
     let identify_config = identify::Config::new(
         format!("/nova/{}", crate::config::PROTOCOL_VERSION),
         keypair.public(),
+    )
+    .with_agent_version(
+        config
+            .capabilities
+            .agent_version(concat!("nova/", env!("CARGO_PKG_VERSION"))),
     );
     let identify_behaviour = identify::Behaviour::new(identify_config);
 
-    let behaviour = GossipBehaviour {
-        gossipsub: gossipsub_behaviour,
-        identify: identify_behaviour,
-    };
-
     let swarm = libp2p::SwarmBuilder::with_existing_identity(keypair.clone())
         .with_tokio()
         .with_tcp(
@@ -632,7 +798,16 @@ pub fn build_swarm(
             libp2p::yamux::Config::default,
         )
         .map_err(|e| GossipError::TransportError(format!("tcp transport: {}", e)))?
-        .with_behaviour(|_| behaviour)
+        .with_quic()
+        .with_relay_client(libp2p::noise::Config::new, libp2p::yamux::Config::default)
+        .map_err(|e| GossipError::TransportError(format!("relay transport: {}", e)))?
+        .with_behaviour(|key, relay_client| GossipBehaviour {
+            gossipsub: gossipsub_behaviour,
+            identify: identify_behaviour,
+            direct: crate::network::direct::new_behaviour(),
+            relay_client,
+            dcutr: dcutr::Behaviour::new(key.public().to_peer_id()),
+        })
         .map_err(|e| GossipError::TransportError(format!("behaviour: {}", e)))?
         .with_swarm_config(|cfg| cfg.with_idle_connection_timeout(Duration::from_secs(60)))
         .build();
@@ -700,13 +875,31 @@ impl GossipService {
     ///
     /// The message is queued for publication on the `nova-transactions`
     /// topic. Actual network I/O happens asynchronously in the swarm loop.
+    /// Transactions exceeding the protocol field limits are refused here so
+    /// they never reach the wire.
     pub fn publish_transaction(&self, tx: &Transaction) -> Result<(), GossipError> {
+        crate::transaction::limits::check_sizes(tx)
+            .map_err(|e| GossipError::InvalidMessage(e.to_string()))?;
         let msg = P2pGossipMessage::NewTransaction(tx.clone());
         self.tx_sender
             .send(msg)
             .map_err(|e| GossipError::PublishError(format!("channel closed: {}", e)))
     }
 
+    /// Publish a transaction cancellation to the network.
+    ///
+    /// The message is queued for publication on the `nova-transactions`
+    /// topic, next to the transactions it cancels.
+    pub fn publish_cancellation(
+        &self,
+        cancellation: &TransactionCancellation,
+    ) -> Result<(), GossipError> {
+        let msg = P2pGossipMessage::CancelTransaction(cancellation.clone());
+        self.tx_sender
+            .send(msg)
+            .map_err(|e| GossipError::PublishError(format!("channel closed: {}", e)))
+    }
+
     /// Publish a block to the network.
     ///
     /// The message is queued for publication on the `nova-blocks` topic.
@@ -727,15 +920,45 @@ impl GossipService {
             .map_err(|e| GossipError::PublishError(format!("channel closed: {}", e)))
     }
 
+    /// Publish a block proposal to the network.
+    ///
+    /// The message is queued for publication on the `nova-proposals` topic.
+    pub fn publish_proposal(&self, proposal: &Proposal) -> Result<(), GossipError> {
+        let msg = P2pGossipMessage::BlockProposal(proposal.clone());
+        self.tx_sender
+            .send(msg)
+            .map_err(|e| GossipError::PublishError(format!("channel closed: {}", e)))
+    }
+
+    /// Advertise a forwarding channel to the network.
+    ///
+    /// The message is queued for publication on the `nova-channels` topic.
+    /// Advertisements that do not verify are refused here.
+    pub fn publish_channel_advertisement(
+        &self,
+        ad: &ChannelAdvertisement,
+    ) -> Result<(), GossipError> {
+        ad.verify()
+            .map_err(|e| GossipError::InvalidMessage(e.to_string()))?;
+        let msg = P2pGossipMessage::ChannelAdvertisement(ad.clone());
+        self.tx_sender
+            .send(msg)
+            .map_err(|e| GossipError::PublishError(format!("channel closed: {}", e)))
+    }
+
     /// Determine which topic a `P2pGossipMessage` should be published to.
     ///
     /// Used by the swarm event loop to route outbound messages to the
     /// correct gossipsub topic.
     pub fn topic_for_message(&self, msg: &P2pGossipMessage) -> IdentTopic {
         match msg {
-            P2pGossipMessage::NewTransaction(_) => self.config.topics.transactions_topic(),
+            P2pGossipMessage::NewTransaction(_) | P2pGossipMessage::CancelTransaction(_) => {
+                self.config.topics.transactions_topic()
+            }
             P2pGossipMessage::NewBlock(_) => self.config.topics.blocks_topic(),
             P2pGossipMessage::BlockVote(_) => self.config.topics.votes_topic(),
+            P2pGossipMessage::BlockProposal(_) => self.config.topics.proposals_topic(),
+            P2pGossipMessage::ChannelAdvertisement(_) => self.config.topics.channels_topic(),
         }
     }
 }
@@ -770,6 +993,7 @@ mod tests {
             address: "/ip4/127.0.0.1/tcp/9740".to_string(),
             connected_at: 1000,
             last_seen: 1000,
+            transport: PeerTransport::Tcp,
         }
     }
 
@@ -781,7 +1005,7 @@ mod tests {
             .fee(100)
             .nonce(nonce)
             .timestamp(1_700_000_000_000)
-            .build()
+            .build_unchecked()
     }
 
     fn make_test_block() -> Block {
@@ -898,6 +1122,18 @@ mod tests {
         assert_eq!(proto.peer_count(), 1);
     }
 
+    #[test]
+    fn own_broadcasts_stay_within_the_seen_cache() {
+        let proto = GossipProtocol::new(make_config());
+        for nonce in 0..500 {
+            proto.broadcast(GossipMessage::NewTransaction {
+                transaction: make_test_tx(nonce),
+                ttl: 5,
+            });
+            assert!(proto.seen_count() <= 100);
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Layer 2: libp2p gossipsub tests
     // -----------------------------------------------------------------------
@@ -940,6 +1176,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encode_decode_cancellation_message() {
+        let tx = make_test_tx(1);
+        let cancellation = TransactionCancellation::sign(&tx.id, &NovaKeypair::generate());
+        let msg = P2pGossipMessage::CancelTransaction(cancellation.clone());
+
+        let decoded = decode_message(&encode_message(&msg)).expect("should decode");
+        match decoded {
+            P2pGossipMessage::CancelTransaction(decoded) => assert_eq!(decoded, cancellation),
+            other => panic!("expected CancelTransaction, got {:?}", other),
+        }
+    }
+
     #[test]
     fn encode_decode_vote_message() {
         let vote = make_test_vote();
@@ -975,6 +1224,8 @@ mod tests {
         assert_eq!(topics.transactions, "nova-transactions");
         assert_eq!(topics.blocks, "nova-blocks");
         assert_eq!(topics.votes, "nova-votes");
+        assert_eq!(topics.proposals, "nova-proposals");
+        assert_eq!(topics.channels, "nova-channels");
     }
 
     #[test]
@@ -1125,16 +1376,22 @@ mod tests {
     fn config_custom_values() {
         let config = GossipServiceConfig {
             listen_addr: "/ip4/127.0.0.1/tcp/12345".to_string(),
+            quic_listen_addr: Some("/ip4/127.0.0.1/udp/12345/quic-v1".to_string()),
+            relays: Vec::new(),
             topics: GossipTopics {
                 transactions: "custom-tx".to_string(),
                 blocks: "custom-blocks".to_string(),
                 votes: "custom-votes".to_string(),
+                proposals: "custom-proposals".to_string(),
+                channels: "custom-channels".to_string(),
             },
             mesh_n: 8,
             mesh_n_low: 5,
             mesh_n_high: 15,
             heartbeat_interval_ms: 2000,
             max_message_size: 2 * 1024 * 1024,
+            scoring: PeerScoring::default(),
+            capabilities: Capabilities::new(),
         };
 
         assert_eq!(config.listen_addr, "/ip4/127.0.0.1/tcp/12345");
@@ -1146,6 +1403,29 @@ mod tests {
         assert_eq!(config.mesh_n_high, 15);
         assert_eq!(config.heartbeat_interval_ms, 2000);
         assert_eq!(config.max_message_size, 2 * 1024 * 1024);
+
+        let addrs = config.listen_addrs().unwrap();
+        let transports: Vec<_> = addrs.iter().map(PeerTransport::of).collect();
+        assert_eq!(transports, [PeerTransport::Tcp, PeerTransport::Quic]);
+        assert_eq!(
+            GossipServiceConfig::default().listen_addrs().unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn config_listens_through_relays() {
+        let relay = PeerId::from(Keypair::ed25519_from_bytes([9; 32]).unwrap().public());
+        let mut config = GossipServiceConfig {
+            relays: vec![format!("/ip4/203.0.113.5/tcp/9740/p2p/{}", relay)],
+            ..Default::default()
+        };
+        let addrs = config.listen_addrs().unwrap();
+        assert_eq!(addrs.len(), 2);
+        assert_eq!(crate::network::nat::relay_peer(&addrs[1]), Some(relay));
+
+        config.relays = vec!["/ip4/203.0.113.5/tcp/9740".to_string()];
+        assert!(config.listen_addrs().is_err());
     }
 
     #[test]
@@ -1154,6 +1434,8 @@ mod tests {
             transactions: "test-tx-topic".to_string(),
             blocks: "test-block-topic".to_string(),
             votes: "test-vote-topic".to_string(),
+            proposals: "test-proposal-topic".to_string(),
+            channels: "test-channel-topic".to_string(),
         };
 
         // Verify the IdentTopic conversion works.
@@ -1246,6 +1528,62 @@ mod tests {
         assert_ne!(block_topic.hash(), vote_topic.hash());
     }
 
+    #[test]
+    fn gossip_service_publish_proposal() {
+        let keypair = Keypair::generate_ed25519();
+        let config = GossipServiceConfig::default();
+        let (service, mut rx) = GossipService::new(config.clone(), &keypair);
+
+        let proposer = NovaKeypair::generate();
+        let proposal = Proposal::new(&proposer, make_test_block(), 3, None);
+        service
+            .publish_proposal(&proposal)
+            .expect("publish should succeed");
+
+        let received = rx.try_recv().expect("should receive message");
+        assert_eq!(
+            service.topic_for_message(&received).hash(),
+            config.topics.proposals_topic().hash()
+        );
+        match decode_message(&encode_message(&received)).unwrap() {
+            P2pGossipMessage::BlockProposal(p) => {
+                assert_eq!(p.round, 3);
+                assert!(p.verify());
+            }
+            other => panic!("expected BlockProposal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn gossip_service_publish_channel_advertisement() {
+        let keypair = Keypair::generate_ed25519();
+        let config = GossipServiceConfig::default();
+        let (service, mut rx) = GossipService::new(config.clone(), &keypair);
+
+        let node = NovaKeypair::generate();
+        let ad = ChannelAdvertisement::sign(&node, 7, "nova1peer", 10_000, 1, 100, 40, 1);
+        service
+            .publish_channel_advertisement(&ad)
+            .expect("publish should succeed");
+
+        let received = rx.try_recv().expect("should receive message");
+        assert_eq!(
+            service.topic_for_message(&received).hash(),
+            config.topics.channels_topic().hash()
+        );
+        match decode_message(&encode_message(&received)).unwrap() {
+            P2pGossipMessage::ChannelAdvertisement(decoded) => assert_eq!(decoded, ad),
+            other => panic!("expected ChannelAdvertisement, got {:?}", other),
+        }
+
+        let mut forged = ad;
+        forged.fee_base = 0;
+        assert!(matches!(
+            service.publish_channel_advertisement(&forged),
+            Err(GossipError::InvalidMessage(_))
+        ));
+    }
+
     #[test]
     fn gossip_error_display() {
         let errors = vec![
@@ -1269,6 +1607,19 @@ mod tests {
         assert!(format!("{}", errors[4]).starts_with("invalid"));
     }
 
+    #[test]
+    fn frames_carry_the_schema_version() {
+        let msg = P2pGossipMessage::NewTransaction(make_test_tx(1));
+        let mut encoded = encode_message(&msg);
+        assert_eq!(encoded[..2], encoding::SCHEMA_VERSION.to_le_bytes());
+
+        encoded[..2].copy_from_slice(&(encoding::SCHEMA_VERSION + 1).to_le_bytes());
+        match decode_message(&encoded) {
+            Err(GossipError::Serialization(msg)) => assert!(msg.contains("schema version")),
+            other => panic!("expected a schema version error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn empty_data_decode_fails() {
         let result = decode_message(&[]);
@@ -0,0 +1,694 @@
+//! # Emergency Halt
+//!
+//! A coordinated pause for when a critical bug is found in a live chain.
+//! Validators holding a supermajority (2/3) of the active stake sign an
+//! [`EmergencyHalt`] naming a height; every node that accepts it refuses to
+//! build or finalize a block at or past that height. The chain stays
+//! stopped until the same supermajority signs a [`ResumeAction`] for that
+//! halt, which also names the minimum protocol version allowed to continue
+//! — typically the release carrying the fix.
+//!
+//! ## Deterministic Restart
+//!
+//! After a resume, the first block at the halt height is proposed in round
+//! `halt_height` by every node, whatever its local round counter drifted to
+//! while halted. Everyone agrees on the proposer, so the chain picks up
+//! without a round of timeouts. Nodes below the required version stay
+//! halted; they must upgrade before building past the halt height, while
+//! the blocks they already have remain valid.
+//!
+//! ## Records
+//!
+//! Both records are plain JSON documents passed around out of band: each
+//! validator adds its signature with `nova-node halt sign` (or
+//! `halt sign-resume`), and once enough stake has signed, operators submit
+//! the record to their nodes' admin API. Signatures are
+//! domain-separated so a halt signature can never be replayed as a resume
+//! (or as anything else a validator key signs). [`HaltGuard`] keeps the
+//! accepted records in the `governance` tree, so a restart does not lift a
+//! halt.
+
+use std::cmp::Ordering;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sled::Tree;
+use tracing::{info, warn};
+
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey};
+use crate::crypto::wire::SignatureBytes;
+use crate::network::consensus::ValidatorSet;
+use crate::storage::db::{DbError, DbResult, NovaDB};
+
+/// Name of the sled tree holding accepted governance records.
+pub const GOVERNANCE_TREE: &str = "governance";
+
+/// Key of the halt record inside [`GOVERNANCE_TREE`].
+const HALT_KEY: &[u8] = b"halt";
+
+/// Domain prefix of the bytes an [`EmergencyHalt`] signature covers.
+pub const HALT_DOMAIN: &[u8] = b"nova-emergency-halt-v1";
+
+/// Domain prefix of the bytes a [`ResumeAction`] signature covers.
+pub const RESUME_DOMAIN: &[u8] = b"nova-resume-v1";
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Reasons a record is rejected or a height may not be built.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum HaltError {
+    /// An emergency halt is in force at this height.
+    #[error("chain halted at height {halt_height}")]
+    Halted {
+        /// Height at which production stopped.
+        halt_height: u64,
+    },
+    /// The chain resumed, but only for nodes at a newer protocol version.
+    #[error("protocol {required} or newer required past height {halt_height}, running {running}")]
+    UpgradeRequired {
+        /// Height of the halt the resume lifted.
+        halt_height: u64,
+        /// Minimum protocol version named by the resume.
+        required: String,
+        /// Protocol version of this node.
+        running: String,
+    },
+    /// The signers do not hold 2/3 of the active stake.
+    #[error("insufficient signing stake: have {have}, need {need}")]
+    InsufficientStake {
+        /// Stake behind the valid signatures.
+        have: u64,
+        /// Stake quorum required.
+        need: u64,
+    },
+    /// Not enough signers in an unstaked (authority) set.
+    #[error("insufficient signers: have {have}, need {need}")]
+    InsufficientSigners {
+        /// Number of valid signers.
+        have: usize,
+        /// Quorum threshold required.
+        need: usize,
+    },
+    /// A signature does not verify.
+    #[error("invalid signature from {0}")]
+    InvalidSignature(String),
+    /// A signer is not an active validator.
+    #[error("signer is not an active validator: {0}")]
+    NotValidator(String),
+    /// The same validator signed twice.
+    #[error("duplicate signature from {0}")]
+    DuplicateSigner(String),
+    /// The halt height has already been built.
+    #[error("halt height {halt_height} already passed, next block is {next_height}")]
+    HeightPassed {
+        /// Height the halt names.
+        halt_height: u64,
+        /// Height of the next block on this node.
+        next_height: u64,
+    },
+    /// A halt at this height or earlier is already in force or was resumed.
+    #[error("halt at height {0} does not supersede the current one")]
+    Stale(u64),
+    /// A resume was submitted while no halt is in force.
+    #[error("no emergency halt is in force")]
+    NoActiveHalt,
+    /// A resume names a different halt than the one in force.
+    #[error("resume is for the halt at {got}, but the halt in force is at {active}")]
+    ResumeMismatch {
+        /// Height of the halt in force.
+        active: u64,
+        /// Height the resume names.
+        got: u64,
+    },
+    /// A protocol version is not `major.minor.patch`.
+    #[error("invalid protocol version: {0}")]
+    InvalidVersion(String),
+    /// Persisting the record failed.
+    #[error("governance storage error: {0}")]
+    Db(#[from] DbError),
+}
+
+// ---------------------------------------------------------------------------
+// Records
+// ---------------------------------------------------------------------------
+
+/// One validator's signature on a governance record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceSignature {
+    /// Hex-encoded public key of the signing validator.
+    pub validator: String,
+    /// Ed25519 signature over the record's signable bytes.
+    pub signature: SignatureBytes,
+}
+
+impl GovernanceSignature {
+    pub(crate) fn new(keypair: &NovaKeypair, message: &[u8]) -> Self {
+        Self {
+            validator: keypair.public_key().to_hex(),
+            signature: SignatureBytes::from(&keypair.sign(message)),
+        }
+    }
+
+    fn verify(&self, message: &[u8]) -> bool {
+        NovaPublicKey::from_hex(&self.validator)
+            .map(|pk| pk.verify(message, &self.signature.into()))
+            .unwrap_or(false)
+    }
+}
+
+/// A supermajority-signed order to stop producing blocks at `halt_height`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmergencyHalt {
+    /// First height that may not be built. Blocks below it are unaffected.
+    pub halt_height: u64,
+    /// Why, for operators and explorers. Covered by the signatures.
+    pub reason: String,
+    /// Validator signatures over [`signable_bytes`](Self::signable_bytes).
+    #[serde(default)]
+    pub signatures: Vec<GovernanceSignature>,
+}
+
+impl EmergencyHalt {
+    /// An unsigned halt record.
+    pub fn new(halt_height: u64, reason: impl Into<String>) -> Self {
+        Self {
+            halt_height,
+            reason: reason.into(),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Bytes every signature covers: the domain, the height and the reason.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HALT_DOMAIN.len() + 8 + self.reason.len());
+        bytes.extend_from_slice(HALT_DOMAIN);
+        bytes.extend_from_slice(&self.halt_height.to_le_bytes());
+        bytes.extend_from_slice(self.reason.as_bytes());
+        bytes
+    }
+
+    /// Adds `keypair`'s signature, replacing an earlier one by the same key.
+    pub fn sign(&mut self, keypair: &NovaKeypair) {
+        let signature = GovernanceSignature::new(keypair, &self.signable_bytes());
+        add_signature(&mut self.signatures, signature);
+    }
+
+    /// Checks that the signers hold a supermajority of `validators`.
+    /// Returns the signing stake.
+    pub fn verify(&self, validators: &ValidatorSet) -> Result<u64, HaltError> {
+        verify_quorum(&self.signable_bytes(), &self.signatures, validators)
+    }
+}
+
+/// A supermajority-signed order lifting the halt at `halt_height`, for
+/// nodes running at least `min_protocol_version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeAction {
+    /// Height of the halt being lifted.
+    pub halt_height: u64,
+    /// Oldest protocol version allowed to build past the halt height.
+    pub min_protocol_version: String,
+    /// Validator signatures over [`signable_bytes`](Self::signable_bytes).
+    #[serde(default)]
+    pub signatures: Vec<GovernanceSignature>,
+}
+
+impl ResumeAction {
+    /// An unsigned resume record.
+    pub fn new(halt_height: u64, min_protocol_version: impl Into<String>) -> Self {
+        Self {
+            halt_height,
+            min_protocol_version: min_protocol_version.into(),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Bytes every signature covers: the domain, the halt height and the
+    /// required version.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(RESUME_DOMAIN.len() + 8 + self.min_protocol_version.len());
+        bytes.extend_from_slice(RESUME_DOMAIN);
+        bytes.extend_from_slice(&self.halt_height.to_le_bytes());
+        bytes.extend_from_slice(self.min_protocol_version.as_bytes());
+        bytes
+    }
+
+    /// Adds `keypair`'s signature, replacing an earlier one by the same key.
+    pub fn sign(&mut self, keypair: &NovaKeypair) {
+        let signature = GovernanceSignature::new(keypair, &self.signable_bytes());
+        add_signature(&mut self.signatures, signature);
+    }
+
+    /// Checks the version format and that the signers hold a supermajority
+    /// of `validators`. Returns the signing stake.
+    pub fn verify(&self, validators: &ValidatorSet) -> Result<u64, HaltError> {
+        parse_version(&self.min_protocol_version)?;
+        verify_quorum(&self.signable_bytes(), &self.signatures, validators)
+    }
+}
+
+pub(crate) fn add_signature(
+    signatures: &mut Vec<GovernanceSignature>,
+    signature: GovernanceSignature,
+) {
+    signatures.retain(|s| s.validator != signature.validator);
+    signatures.push(signature);
+}
+
+/// Same quorum rule as block finality: 2/3 of the active stake, or 2/3 + 1
+/// of the validators in an unstaked set. Shared by every record validators
+/// sign together (see also [`crate::network::treasury`]).
+pub(crate) fn verify_quorum(
+    message: &[u8],
+    signatures: &[GovernanceSignature],
+    validators: &ValidatorSet,
+) -> Result<u64, HaltError> {
+    let mut signers: Vec<&str> = Vec::with_capacity(signatures.len());
+    let mut stake = 0u64;
+    for sig in signatures {
+        if signers.contains(&sig.validator.as_str()) {
+            return Err(HaltError::DuplicateSigner(sig.validator.clone()));
+        }
+        if !validators.contains(&sig.validator) {
+            return Err(HaltError::NotValidator(sig.validator.clone()));
+        }
+        if !sig.verify(message) {
+            return Err(HaltError::InvalidSignature(sig.validator.clone()));
+        }
+        signers.push(&sig.validator);
+        stake = stake.saturating_add(validators.stake_of(&sig.validator));
+    }
+
+    if !validators.has_quorum(stake, signers.len()) {
+        if validators.total_stake() == 0 {
+            return Err(HaltError::InsufficientSigners {
+                have: signers.len(),
+                need: validators.quorum_threshold(),
+            });
+        }
+        return Err(HaltError::InsufficientStake {
+            have: stake,
+            need: validators.stake_quorum(),
+        });
+    }
+    Ok(stake)
+}
+
+/// Parses `major.minor.patch`.
+fn parse_version(version: &str) -> Result<(u64, u64, u64), HaltError> {
+    let invalid = || HaltError::InvalidVersion(version.to_string());
+    let mut parts = version.split('.').map(|p| p.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok((major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
+
+fn version_cmp(a: &str, b: &str) -> Result<Ordering, HaltError> {
+    Ok(parse_version(a)?.cmp(&parse_version(b)?))
+}
+
+// ---------------------------------------------------------------------------
+// Halt Status
+// ---------------------------------------------------------------------------
+
+/// Accepted governance records, as persisted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaltRecord {
+    /// The most recent halt, resumed or not.
+    pub halt: Option<EmergencyHalt>,
+    /// Every resume accepted, oldest first. Their version requirements
+    /// keep applying past their halt heights after later halts.
+    pub resumes: Vec<ResumeAction>,
+}
+
+impl HaltRecord {
+    /// The halt in force, if the latest one has not been resumed.
+    pub fn active(&self) -> Option<&EmergencyHalt> {
+        self.halt
+            .as_ref()
+            .filter(|h| !self.resumes.iter().any(|r| r.halt_height == h.halt_height))
+    }
+
+    /// Whether a node running protocol `running` may build at `height`.
+    pub fn check(&self, height: u64, running: &str) -> Result<(), HaltError> {
+        if let Some(halt) = self.active() {
+            if height >= halt.halt_height {
+                return Err(HaltError::Halted {
+                    halt_height: halt.halt_height,
+                });
+            }
+        }
+        for resume in self.resumes.iter().filter(|r| height >= r.halt_height) {
+            if version_cmp(running, &resume.min_protocol_version)? == Ordering::Less {
+                return Err(HaltError::UpgradeRequired {
+                    halt_height: resume.halt_height,
+                    required: resume.min_protocol_version.clone(),
+                    running: running.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Summary for status endpoints.
+    pub fn status(&self) -> HaltStatus {
+        match (&self.halt, self.active()) {
+            (_, Some(halt)) => HaltStatus::Halted {
+                halt_height: halt.halt_height,
+                reason: halt.reason.clone(),
+            },
+            (Some(halt), None) => HaltStatus::Resumed {
+                halt_height: halt.halt_height,
+                reason: halt.reason.clone(),
+                min_protocol_version: self
+                    .resumes
+                    .last()
+                    .map(|r| r.min_protocol_version.clone())
+                    .unwrap_or_default(),
+            },
+            (None, None) => HaltStatus::Running,
+        }
+    }
+}
+
+/// Where the chain stands with respect to emergency halts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum HaltStatus {
+    /// No halt has ever been accepted.
+    Running,
+    /// Production stops at `halt_height`.
+    Halted {
+        /// First height that may not be built.
+        halt_height: u64,
+        /// Reason given by the signers.
+        reason: String,
+    },
+    /// The latest halt was lifted.
+    Resumed {
+        /// Height the halt was at.
+        halt_height: u64,
+        /// Reason the halt gave.
+        reason: String,
+        /// Oldest protocol version allowed past it.
+        min_protocol_version: String,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Halt Guard
+// ---------------------------------------------------------------------------
+
+/// Shared, persisted view of the accepted halt records.
+///
+/// Held by the consensus loop, which consults it before every round, and
+/// by the API, which accepts new records.
+#[derive(Debug, Default)]
+pub struct HaltGuard {
+    tree: Option<Tree>,
+    record: RwLock<HaltRecord>,
+}
+
+impl HaltGuard {
+    /// Opens the guard over `db`, restoring any accepted records.
+    pub fn open(db: &NovaDB) -> DbResult<Self> {
+        let tree = db.open_tree(GOVERNANCE_TREE)?;
+        let record = match tree.get(HALT_KEY)? {
+            Some(bytes) => {
+                serde_json::from_slice(&bytes).map_err(|e| DbError::Serialization(e.to_string()))?
+            }
+            None => HaltRecord::default(),
+        };
+        if let Some(halt) = record.active() {
+            warn!(
+                halt_height = halt.halt_height,
+                reason = %halt.reason,
+                "emergency halt in force"
+            );
+        }
+        Ok(Self {
+            tree: Some(tree),
+            record: RwLock::new(record),
+        })
+    }
+
+    /// A copy of the accepted records.
+    pub fn record(&self) -> HaltRecord {
+        self.record.read().clone()
+    }
+
+    /// Where the chain stands.
+    pub fn status(&self) -> HaltStatus {
+        self.record.read().status()
+    }
+
+    /// Whether this node may build or finalize a block at `height`.
+    pub fn check(&self, height: u64) -> Result<(), HaltError> {
+        self.record
+            .read()
+            .check(height, crate::config::PROTOCOL_VERSION)
+    }
+
+    /// If `height` is the first height after a resumed halt, the round
+    /// every node proposes it in.
+    pub fn restart_round(&self, height: u64) -> Option<u64> {
+        let record = self.record.read();
+        record
+            .resumes
+            .iter()
+            .any(|r| r.halt_height == height)
+            .then_some(height)
+    }
+
+    /// Accepts a halt signed by a supermajority of `validators`, given the
+    /// node's next block height.
+    ///
+    /// A halt can be tightened to an earlier height, but not postponed;
+    /// after a resume, only a halt past the resumed height is accepted.
+    pub fn apply_halt(
+        &self,
+        halt: EmergencyHalt,
+        validators: &ValidatorSet,
+        next_height: u64,
+    ) -> Result<(), HaltError> {
+        halt.verify(validators)?;
+        if halt.halt_height < next_height {
+            return Err(HaltError::HeightPassed {
+                halt_height: halt.halt_height,
+                next_height,
+            });
+        }
+
+        let mut record = self.record.write();
+        let supersedes = match (&record.halt, record.active()) {
+            (_, Some(active)) => halt.halt_height < active.halt_height,
+            (Some(resumed), None) => halt.halt_height > resumed.halt_height,
+            (None, None) => true,
+        };
+        if !supersedes {
+            return Err(HaltError::Stale(halt.halt_height));
+        }
+
+        let mut updated = record.clone();
+        warn!(
+            halt_height = halt.halt_height,
+            reason = %halt.reason,
+            signers = halt.signatures.len(),
+            "emergency halt accepted"
+        );
+        updated.halt = Some(halt);
+        self.persist(&updated)?;
+        *record = updated;
+        Ok(())
+    }
+
+    /// Accepts a resume for the halt in force, signed by a supermajority
+    /// of `validators`.
+    pub fn apply_resume(
+        &self,
+        resume: ResumeAction,
+        validators: &ValidatorSet,
+    ) -> Result<(), HaltError> {
+        resume.verify(validators)?;
+
+        let mut record = self.record.write();
+        let active = record.active().ok_or(HaltError::NoActiveHalt)?;
+        if active.halt_height != resume.halt_height {
+            return Err(HaltError::ResumeMismatch {
+                active: active.halt_height,
+                got: resume.halt_height,
+            });
+        }
+
+        let mut updated = record.clone();
+        info!(
+            halt_height = resume.halt_height,
+            min_protocol_version = %resume.min_protocol_version,
+            "emergency halt lifted"
+        );
+        updated.resumes.push(resume);
+        self.persist(&updated)?;
+        *record = updated;
+        Ok(())
+    }
+
+    fn persist(&self, record: &HaltRecord) -> Result<(), HaltError> {
+        if let Some(tree) = &self.tree {
+            let bytes =
+                serde_json::to_vec(record).map_err(|e| DbError::Serialization(e.to_string()))?;
+            tree.insert(HALT_KEY, bytes).map_err(DbError::from)?;
+            tree.flush().map_err(DbError::from)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(n: usize) -> (Vec<NovaKeypair>, ValidatorSet) {
+        let keys: Vec<NovaKeypair> = (0..n).map(|_| NovaKeypair::generate()).collect();
+        let mut set = ValidatorSet::new();
+        for kp in &keys {
+            set.add_validator(kp.public_key().to_hex(), 1_000);
+        }
+        (keys, set)
+    }
+
+    fn signed_halt(keys: &[NovaKeypair], height: u64) -> EmergencyHalt {
+        let mut halt = EmergencyHalt::new(height, "state root mismatch");
+        for kp in keys {
+            halt.sign(kp);
+        }
+        halt
+    }
+
+    #[test]
+    fn halt_requires_a_stake_supermajority() {
+        let (keys, set) = validators(4);
+
+        // 2 of 4 equal stakes is short of 2/3.
+        let short = signed_halt(&keys[..2], 100);
+        assert!(matches!(
+            short.verify(&set),
+            Err(HaltError::InsufficientStake { have: 2_000, .. })
+        ));
+        assert_eq!(signed_halt(&keys[..3], 100).verify(&set).unwrap(), 3_000);
+
+        // Signatures cover the height, and a halt signature is no resume.
+        let mut moved = signed_halt(&keys[..3], 100);
+        moved.halt_height = 101;
+        assert!(matches!(
+            moved.verify(&set),
+            Err(HaltError::InvalidSignature(_))
+        ));
+        let mut resume = ResumeAction::new(100, "0.2.0");
+        resume.signatures = signed_halt(&keys[..3], 100).signatures;
+        assert!(matches!(
+            resume.verify(&set),
+            Err(HaltError::InvalidSignature(_))
+        ));
+
+        let outsider = NovaKeypair::generate();
+        let mut halt = signed_halt(&keys[..3], 100);
+        halt.sign(&outsider);
+        assert!(matches!(halt.verify(&set), Err(HaltError::NotValidator(_))));
+    }
+
+    #[test]
+    fn guard_stops_production_until_resumed_and_upgraded() {
+        let (keys, set) = validators(3);
+        let guard = HaltGuard::default();
+
+        assert!(matches!(
+            guard.apply_halt(signed_halt(&keys, 5), &set, 10),
+            Err(HaltError::HeightPassed { .. })
+        ));
+        guard.apply_halt(signed_halt(&keys, 20), &set, 10).unwrap();
+        assert!(guard.check(19).is_ok());
+        assert!(matches!(
+            guard.check(20),
+            Err(HaltError::Halted { halt_height: 20 })
+        ));
+
+        // Tightening is allowed, postponing is not.
+        assert!(matches!(
+            guard.apply_halt(signed_halt(&keys, 30), &set, 10),
+            Err(HaltError::Stale(30))
+        ));
+        guard.apply_halt(signed_halt(&keys, 15), &set, 10).unwrap();
+        assert!(guard.check(15).is_err());
+
+        let mut resume = ResumeAction::new(20, "99.0.0");
+        for kp in &keys {
+            resume.sign(kp);
+        }
+        assert!(matches!(
+            guard.apply_resume(resume.clone(), &set),
+            Err(HaltError::ResumeMismatch {
+                active: 15,
+                got: 20
+            })
+        ));
+        resume.halt_height = 15;
+        resume.signatures.clear();
+        for kp in &keys {
+            resume.sign(kp);
+        }
+        guard.apply_resume(resume, &set).unwrap();
+        assert_eq!(guard.restart_round(15), Some(15));
+        assert_eq!(guard.restart_round(16), None);
+
+        // This build is older than 99.0.0: blocks below the halt are
+        // fine, building past it needs the upgrade.
+        assert!(guard.check(14).is_ok());
+        assert!(matches!(
+            guard.check(15),
+            Err(HaltError::UpgradeRequired { .. })
+        ));
+        let record = guard.record();
+        assert!(record.check(15, "99.0.0").is_ok());
+        assert!(record.check(40, "99.1.0").is_ok());
+        assert!(matches!(
+            guard.status(),
+            HaltStatus::Resumed {
+                halt_height: 15,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn accepted_records_survive_a_restart() {
+        let (keys, set) = validators(1);
+        let db = NovaDB::open_temporary().unwrap();
+
+        HaltGuard::open(&db)
+            .unwrap()
+            .apply_halt(signed_halt(&keys, 7), &set, 3)
+            .unwrap();
+
+        let reopened = HaltGuard::open(&db).unwrap();
+        assert!(matches!(
+            reopened.check(7),
+            Err(HaltError::Halted { halt_height: 7 })
+        ));
+        assert_eq!(
+            reopened.status(),
+            HaltStatus::Halted {
+                halt_height: 7,
+                reason: "state root mismatch".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,507 @@
+//! # Validator Liveness & Jailing
+//!
+//! Every block settles two kinds of duty for the active validators:
+//!
+//! - **Proposals.** The block's proposer performed its slot. Anyone the
+//!   round-robin rotation passed over between the parent's proposer and
+//!   this one missed theirs.
+//! - **Votes.** The block's `last_commit` lists who voted for the parent.
+//!   Every other active validator missed that vote. A block without a
+//!   commit (older blocks, or a proposer that saw no votes) settles no vote
+//!   duties at all.
+//!
+//! Each validator keeps a sliding window of its last [`LivenessPolicy::window`]
+//! duties of each kind. Once a window is full and the share performed drops
+//! below [`LivenessPolicy::min_signed_bps`], the validator is jailed: marked
+//! inactive in the stored [`ValidatorSet`], which takes it out of proposer
+//! rotation and with it out of the fee rewards only proposers receive.
+//!
+//! ```text
+//!   active ──(window below threshold)──▶ jailed
+//!   jailed ──(Unjail tx, cooldown passed)──▶ active, windows cleared
+//! ```
+//!
+//! Jailing is never automatic in reverse. After
+//! [`LivenessPolicy::jail_cooldown_blocks`] the operator sends an
+//! [`Unjail`](crate::transaction::types::TransactionType::Unjail)
+//! transaction signed with the validator's key ([`apply_unjail`]).
+//!
+//! ## Determinism
+//!
+//! All of this is state transition: the producer and every syncing node call
+//! [`record_liveness`] for each block after its transactions, and the
+//! records live in the state tree under [`LIVENESS_STATE_KEY`]. The policy
+//! is consensus-critical in the same way as the fee policy. Chains with no
+//! stored validator set track nothing.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::{JAIL_COOLDOWN_BLOCKS, LIVENESS_MIN_SIGNED_BPS, LIVENESS_WINDOW};
+use crate::crypto::keys::NovaPublicKey;
+use crate::identity::nova_id::NovaId;
+use crate::network::consensus::ValidatorSet;
+use crate::storage::block::CommitSignature;
+use crate::storage::snapshot::StateSnapshot;
+use crate::storage::state::{bump_nonce, check_nonce, StateError, StateTree};
+use crate::transaction::Transaction;
+
+/// State tree key under which liveness records are persisted.
+///
+/// Not a valid NOVA address, so it can never collide with an account.
+pub const LIVENESS_STATE_KEY: &str = "nova:system:liveness";
+
+// ---------------------------------------------------------------------------
+// Policy
+// ---------------------------------------------------------------------------
+
+/// When validators are jailed and how long they stay out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LivenessPolicy {
+    /// Duties of each kind a validator is judged over.
+    pub window: u32,
+    /// Share of a full window that must be performed, in basis points.
+    pub min_signed_bps: u16,
+    /// Blocks after jailing before an unjail is accepted.
+    pub jail_cooldown_blocks: u64,
+}
+
+impl Default for LivenessPolicy {
+    fn default() -> Self {
+        Self {
+            window: LIVENESS_WINDOW,
+            min_signed_bps: LIVENESS_MIN_SIGNED_BPS,
+            jail_cooldown_blocks: JAIL_COOLDOWN_BLOCKS,
+        }
+    }
+}
+
+impl LivenessPolicy {
+    fn window(&self) -> u32 {
+        self.window.max(1)
+    }
+
+    /// A window only counts against a validator once it is full, so a new
+    /// validator is not jailed over its first missed slot.
+    fn is_failing(&self, duties: &DutyWindow) -> bool {
+        duties.len() >= self.window() && duties.performed_bps() < self.min_signed_bps
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Duty Window
+// ---------------------------------------------------------------------------
+
+/// The outcome of a validator's most recent duties of one kind.
+///
+/// A ring buffer with one bit per duty (set = missed), so a full window of
+/// 1,000 duties costs 125 bytes of state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DutyWindow {
+    missed_bits: Vec<u64>,
+    len: u32,
+    next: u32,
+    missed: u32,
+}
+
+impl DutyWindow {
+    /// Records one duty, evicting the oldest once `window` are held.
+    ///
+    /// A window recorded under a different size starts over.
+    pub fn record(&mut self, missed: bool, window: u32) {
+        let window = window.max(1);
+        let words = window.div_ceil(64) as usize;
+        if self.missed_bits.len() != words || self.next >= window {
+            *self = Self {
+                missed_bits: vec![0; words],
+                ..Self::default()
+            };
+        }
+
+        let (word, bit) = ((self.next / 64) as usize, self.next % 64);
+        if self.missed_bits[word] & (1 << bit) != 0 {
+            self.missed_bits[word] &= !(1 << bit);
+            self.missed -= 1;
+        }
+        if missed {
+            self.missed_bits[word] |= 1 << bit;
+            self.missed += 1;
+        }
+        self.next = (self.next + 1) % window;
+        self.len = (self.len + 1).min(window);
+    }
+
+    /// Duties currently in the window.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if no duty has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Duties in the window that were missed.
+    pub fn missed(&self) -> u32 {
+        self.missed
+    }
+
+    /// Share of the window performed, in basis points. An empty window
+    /// counts as fully performed.
+    pub fn performed_bps(&self) -> u16 {
+        if self.len == 0 {
+            return 10_000;
+        }
+        (u64::from(self.len - self.missed) * 10_000 / u64::from(self.len)) as u16
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Records
+// ---------------------------------------------------------------------------
+
+/// Liveness history of a single validator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorLiveness {
+    /// Recent proposal slots.
+    pub proposals: DutyWindow,
+    /// Recent votes.
+    pub votes: DutyWindow,
+    /// Height at which the validator was jailed, while it is.
+    pub jailed_at: Option<u64>,
+    /// Times this validator has been jailed.
+    pub times_jailed: u32,
+}
+
+impl ValidatorLiveness {
+    /// First height at which an unjail is accepted, while jailed.
+    pub fn unjail_height(&self, policy: &LivenessPolicy) -> Option<u64> {
+        self.jailed_at
+            .map(|height| height.saturating_add(policy.jail_cooldown_blocks))
+    }
+
+    fn clear_windows(&mut self) {
+        self.proposals = DutyWindow::default();
+        self.votes = DutyWindow::default();
+    }
+}
+
+/// Liveness history of every validator that has had a duty, by address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LivenessRecord {
+    validators: BTreeMap<String, ValidatorLiveness>,
+}
+
+impl LivenessRecord {
+    /// Loads the records persisted in the state tree, or an empty set if
+    /// none have been stored.
+    pub fn load(tree: &StateTree) -> Self {
+        tree.get_raw(LIVENESS_STATE_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Loads the records as of a committed state snapshot.
+    pub fn load_from_snapshot(snapshot: &StateSnapshot<'_>) -> Self {
+        snapshot
+            .get_raw(LIVENESS_STATE_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persists the records into the state tree.
+    pub fn store(&self, tree: &mut StateTree) {
+        let bytes =
+            bincode::serialize(self).expect("liveness record serialization should not fail");
+        tree.put_raw(LIVENESS_STATE_KEY, &bytes);
+    }
+
+    /// History of one validator, if it has had any duty.
+    pub fn get(&self, address: &str) -> Option<&ValidatorLiveness> {
+        self.validators.get(address)
+    }
+
+    /// Returns `true` if the validator is currently jailed.
+    pub fn is_jailed(&self, address: &str) -> bool {
+        self.get(address).is_some_and(|v| v.jailed_at.is_some())
+    }
+
+    /// Iterates over every tracked validator in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &ValidatorLiveness)> {
+        self.validators.iter()
+    }
+
+    fn entry(&mut self, address: &str) -> &mut ValidatorLiveness {
+        self.validators.entry(address.to_string()).or_default()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// State Transitions
+// ---------------------------------------------------------------------------
+
+/// Settles the duties of the block at `height` and jails any validator whose
+/// window falls below the policy. Returns the addresses jailed.
+///
+/// `parent_proposer` is the validator of the parent block (`None` above
+/// genesis) and `last_commit` the block's commit for its parent.
+pub fn record_liveness(
+    tree: &mut StateTree,
+    height: u64,
+    proposer: &str,
+    parent_proposer: Option<&str>,
+    last_commit: &[CommitSignature],
+    policy: &LivenessPolicy,
+) -> Vec<String> {
+    let Some(mut set) = ValidatorSet::load_from_state(tree) else {
+        return Vec::new();
+    };
+    let rotation: Vec<String> = set
+        .validators()
+        .iter()
+        .filter(|v| v.active)
+        .map(|v| v.address.clone())
+        .collect();
+    let window = policy.window();
+    let mut record = LivenessRecord::load(tree);
+    let mut touched = false;
+
+    // Proposal slots: everyone the rotation skipped missed theirs.
+    if let Some(current) = rotation.iter().position(|a| a == proposer) {
+        touched = true;
+        if let Some(previous) = parent_proposer.and_then(|p| rotation.iter().position(|a| a == p)) {
+            let mut slot = (previous + 1) % rotation.len();
+            while slot != current {
+                record.entry(&rotation[slot]).proposals.record(true, window);
+                slot = (slot + 1) % rotation.len();
+            }
+        }
+        record.entry(proposer).proposals.record(false, window);
+    }
+
+    // Votes for the parent block.
+    if !last_commit.is_empty() {
+        touched = true;
+        for address in &rotation {
+            let voted = last_commit.iter().any(|c| &c.validator == address);
+            record.entry(address).votes.record(!voted, window);
+        }
+    }
+
+    // Jail whoever fell below the threshold, but never the last active
+    // validator: an empty set could not include the unjail that fixes it.
+    let mut jailed = Vec::new();
+    let mut active = rotation.len();
+    for address in &rotation {
+        if active <= 1 {
+            break;
+        }
+        let liveness = record.entry(address);
+        if policy.is_failing(&liveness.proposals) || policy.is_failing(&liveness.votes) {
+            warn!(
+                validator = %address,
+                height,
+                proposals_missed = liveness.proposals.missed(),
+                votes_missed = liveness.votes.missed(),
+                "validator jailed for missed duties"
+            );
+            liveness.jailed_at = Some(height);
+            liveness.times_jailed += 1;
+            liveness.clear_windows();
+            set.set_active(address, false);
+            jailed.push(address.clone());
+            active -= 1;
+        }
+    }
+
+    if touched {
+        record.store(tree);
+    }
+    if !jailed.is_empty() {
+        set.store_in_state(tree);
+    }
+    jailed
+}
+
+/// Applies an `Unjail` transaction in the block at `height`.
+///
+/// The validator is the transaction's signing key, which must own the
+/// sender address. The validator must be jailed and past its cooldown; it
+/// rejoins the active set with empty windows.
+pub fn apply_unjail(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    height: u64,
+    policy: &LivenessPolicy,
+) -> Result<(), StateError> {
+    check_nonce(tree, &tx.sender, tx.nonce)?;
+
+    let validator = tx
+        .sender_public_key
+        .as_deref()
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| StateError::UnjailRejected("no validator key on transaction".into()))?;
+    let owns_sender = NovaPublicKey::from_hex(&validator)
+        .is_ok_and(|pk| NovaId::from_public_key(&pk).to_address() == tx.sender);
+    if !owns_sender {
+        return Err(StateError::UnjailRejected(format!(
+            "key {} does not own {}",
+            validator, tx.sender
+        )));
+    }
+
+    let mut record = LivenessRecord::load(tree);
+    let liveness = record
+        .validators
+        .get_mut(&validator)
+        .filter(|v| v.jailed_at.is_some())
+        .ok_or_else(|| StateError::UnjailRejected(format!("{} is not jailed", validator)))?;
+    let eligible = liveness.unjail_height(policy).unwrap_or_default();
+    if height < eligible {
+        return Err(StateError::UnjailRejected(format!(
+            "{} is jailed until height {}",
+            validator, eligible
+        )));
+    }
+
+    let mut set = ValidatorSet::load_from_state(tree)
+        .filter(|set| set.validators().iter().any(|v| v.address == validator))
+        .ok_or_else(|| {
+            StateError::UnjailRejected(format!("{} is not in the validator set", validator))
+        })?;
+
+    liveness.jailed_at = None;
+    liveness.clear_windows();
+    set.set_active(&validator, true);
+    record.store(tree);
+    set.store_in_state(tree);
+    bump_nonce(tree, &tx.sender, tx.nonce)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::NovaKeypair;
+    use crate::storage::db::NovaDB;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::signing::sign_transaction;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    const POLICY: LivenessPolicy = LivenessPolicy {
+        window: 4,
+        min_signed_bps: 5_000,
+        jail_cooldown_blocks: 10,
+    };
+
+    fn tree_with_validators(addresses: &[&str]) -> StateTree {
+        let mut tree = StateTree::new(NovaDB::open_temporary().expect("temp db"));
+        let mut set = ValidatorSet::new();
+        for address in addresses {
+            set.add_validator(address.to_string(), 1_000);
+        }
+        set.store_in_state(&mut tree);
+        tree
+    }
+
+    #[test]
+    fn duty_window_slides() {
+        let mut window = DutyWindow::default();
+        for missed in [true, true, false, false] {
+            window.record(missed, 4);
+        }
+        assert_eq!((window.len(), window.missed()), (4, 2));
+        assert_eq!(window.performed_bps(), 5_000);
+
+        // The two oldest misses fall out.
+        window.record(false, 4);
+        window.record(false, 4);
+        assert_eq!((window.len(), window.missed()), (4, 0));
+
+        // A policy change starts the window over.
+        window.record(true, 8);
+        assert_eq!((window.len(), window.missed()), (1, 1));
+    }
+
+    #[test]
+    fn offline_validator_is_jailed_out_of_rotation() {
+        // Canonical order with equal stake is by address: a, b, c.
+        let mut tree = tree_with_validators(&["a", "b", "c"]);
+
+        // "b" never proposes: every block goes a → c → a → ...
+        let mut jailed = Vec::new();
+        let mut previous = None;
+        for (height, proposer) in (1..).zip(["a", "c", "a", "c", "a", "c", "a", "c"]) {
+            jailed.extend(record_liveness(
+                &mut tree,
+                height,
+                proposer,
+                previous,
+                &[],
+                &POLICY,
+            ));
+            previous = Some(proposer);
+        }
+
+        assert_eq!(jailed, vec!["b".to_string()]);
+        let set = ValidatorSet::load_from_state(&tree).unwrap();
+        assert!(!set.contains("b"));
+        assert_eq!(set.proposer_for_round(1).unwrap().address, "c");
+
+        let record = LivenessRecord::load(&tree);
+        let b = record.get("b").unwrap();
+        assert_eq!(b.times_jailed, 1);
+        assert!(b.proposals.is_empty());
+        assert!(!record.is_jailed("a") && !record.is_jailed("c"));
+    }
+
+    #[test]
+    fn missed_votes_jail_and_unjail_waits_for_cooldown() {
+        let keypair = NovaKeypair::generate();
+        let validator = keypair.public_key().to_hex();
+        let mut tree = tree_with_validators(&["a", &validator]);
+
+        // "a" proposes every block; the validator never shows up in commits.
+        let commit = |voter: &str| CommitSignature {
+            validator: voter.to_string(),
+            round: 0,
+            signature: Default::default(),
+        };
+        for height in 1..=4 {
+            record_liveness(&mut tree, height, "a", Some("a"), &[commit("a")], &POLICY);
+        }
+        let record = LivenessRecord::load(&tree);
+        assert_eq!(record.get(&validator).unwrap().jailed_at, Some(4));
+
+        let sender = NovaId::from_public_key(&keypair.public_key()).to_address();
+        let unjail = |nonce| {
+            let mut tx = TransactionBuilder::new(TransactionType::Unjail)
+                .sender(&sender)
+                .receiver(&sender)
+                .amount(Amount::new(0, Currency::NOVA))
+                .nonce(nonce)
+                .build_unchecked();
+            sign_transaction(&mut tx, &keypair);
+            tx
+        };
+
+        match apply_unjail(&mut tree, &unjail(1), 13, &POLICY) {
+            Err(StateError::UnjailRejected(reason)) => assert!(reason.contains("14")),
+            other => panic!("expected cooldown rejection, got {:?}", other),
+        }
+
+        apply_unjail(&mut tree, &unjail(1), 14, &POLICY).unwrap();
+        assert!(ValidatorSet::load_from_state(&tree)
+            .unwrap()
+            .contains(&validator));
+        assert!(!LivenessRecord::load(&tree).is_jailed(&validator));
+
+        // Nothing left to lift.
+        assert!(apply_unjail(&mut tree, &unjail(2), 15, &POLICY).is_err());
+    }
+}
@@ -12,17 +12,58 @@
 //! - `parking_lot::RwLock<BTreeMap>` protects the fee index. Writers are rare
 //!   (new transactions, evictions) compared to readers (block proposers
 //!   scanning the top-N entries).
-//! - Eviction targets the lowest fee-per-byte transaction when the pool is
-//!   full and an incoming transaction offers a higher fee density.
-
-use std::collections::BTreeMap;
+//! - The pool is bounded both by transaction count and by total weight in
+//!   bytes. When an admission would exceed either, the lowest fee-per-byte
+//!   transactions are evicted first, as long as each pays less than the
+//!   incoming one.
+//! - Every transaction the pool drops without including it is announced as
+//!   an [`Eviction`] to [`Mempool::subscribe_evictions`] subscribers, so the
+//!   node can tell wallets their transaction will not confirm.
+//! - Every change to the pool — admissions, removals, replacements and
+//!   evictions — is published as a [`MempoolEvent`] to [`Mempool::subscribe`]
+//!   subscribers, so the node can follow churn without polling.
+//! - A sender at `max_txs_per_sender` competes only with itself: a new
+//!   transaction replaces that sender's pending one at the same nonce, or
+//!   its highest-nonce one if the new transaction comes earlier, as long as
+//!   it pays more per byte. Flooding the pool displaces the flooder's own
+//!   traffic, never anyone else's, and never opens a gap in its nonces.
+//! - An optional [`MempoolValidator`] checks each admission against
+//!   account state, so a transaction that can never execute (a used nonce,
+//!   a balance that cannot cover it) is refused at the door instead of
+//!   holding a slot until it expires.
+//! - Selection groups entries by sender and hands each sender's out in
+//!   contiguous nonce order, whatever they pay; a transaction behind a
+//!   nonce gap stays parked in the pool until the gap fills.
+//! - Protocol housekeeping — validator operations — travels in [`Lane`]s,
+//!   each reserved a share of every block before fees are compared, so
+//!   user spam cannot starve it.
+//! - When the validator knows the sender's account nonce, a transaction
+//!   ahead of the sender's next expected nonce does not enter the pool at
+//!   all: it waits in a bounded [`OrphanPool`] and is promoted once its
+//!   predecessor is admitted or committed.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use dashmap::DashMap;
 use parking_lot::RwLock;
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+use std::sync::Arc;
 
-use crate::transaction::Transaction;
+use crate::identity::nova_id::{AddressPolicy, InvalidAddress};
+use crate::network::orphans::{OrphanPool, OrphanRefusal};
+use crate::network::screening::{Screener, ScreeningRejection};
+use crate::storage::{Block, CommittedState, StateTree};
+use crate::transaction::limits;
+use crate::transaction::verification::verify_transaction_with_policy;
+use crate::transaction::{
+    Currency, Transaction, TransactionCancellation, TransactionError, TransactionType,
+};
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -32,35 +73,133 @@ use crate::transaction::Transaction;
 ///
 /// Defaults are tuned for a devnet/testnet environment where fee enforcement
 /// is relaxed. Production deployments should raise `min_fee` and lower
-/// `max_per_sender` to mitigate spam.
+/// `max_txs_per_sender` to mitigate spam.
 #[derive(Debug, Clone)]
 pub struct MempoolConfig {
     /// Maximum number of transactions the pool will hold.
     pub max_size: usize,
 
-    /// Maximum pending transactions allowed per sender address.
-    pub max_per_sender: usize,
+    /// Maximum total weight of pending transactions, in bytes (see
+    /// [`limits::tx_weight`]). An admission that would exceed it evicts the
+    /// lowest fee-per-byte entries first.
+    pub max_bytes: usize,
 
-    /// Seconds after which a transaction is considered stale and eligible
-    /// for garbage collection via [`Mempool::expire_old`].
+    /// Maximum pending transactions allowed per sender address. A sender at
+    /// the limit can only replace one of its own transactions, at the same
+    /// nonce or its highest, with one paying a higher fee-per-byte.
+    pub max_txs_per_sender: usize,
+
+    /// Time-to-live in seconds: a transaction pending longer than this is
+    /// stale and evicted by [`Mempool::expire_old`].
     pub expiry_seconds: u64,
 
     /// Minimum acceptable fee in photons. Transactions below this threshold
     /// are rejected outright (set to 0 on devnet for convenience).
     pub min_fee: u64,
+
+    /// Extra fee, in photons per byte, charged on top of `min_fee` for an
+    /// encrypted payload envelope. Receiver-only data cannot be inspected
+    /// or pruned, so it pays for its size (0 on devnet).
+    pub encrypted_byte_fee: u64,
+
+    /// How sender and receiver addresses are checked on admission.
+    /// Permissive by default (devnet); production nodes set `Strict`.
+    pub address_policy: AddressPolicy,
+
+    /// Largest transaction weight admitted, in bytes (see
+    /// [`limits::tx_weight`]). May be set below the protocol cap, never
+    /// above it: anything heavier fails the field limits first.
+    pub max_tx_bytes: usize,
+
+    /// Maximum transactions held back behind a nonce gap, across all
+    /// senders; each sender may hold at most `max_txs_per_sender`. The oldest
+    /// is dropped when a new one arrives at the limit. Zero admits gapped
+    /// transactions to the pool, parked, instead.
+    pub max_orphans: usize,
+
+    /// Shares of each block reserved for protocol housekeeping, filled
+    /// before fees are compared (see [`Lane`]). Empty orders blocks purely
+    /// by fee.
+    pub lanes: Vec<Lane>,
 }
 
 impl Default for MempoolConfig {
     fn default() -> Self {
         Self {
             max_size: 10_000,
-            max_per_sender: 100,
+            max_bytes: 64 * 1024 * 1024,
+            max_txs_per_sender: 100,
             expiry_seconds: 3600,
             min_fee: 0,
+            encrypted_byte_fee: 0,
+            address_policy: AddressPolicy::Permissive,
+            max_tx_bytes: limits::MAX_TX_BYTES,
+            max_orphans: 1_000,
+            lanes: vec![Lane::validator()],
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Lanes
+// ---------------------------------------------------------------------------
+
+/// A share of each block reserved for a class of transactions.
+///
+/// Selection fills a lane's reserved slots first, with its best-paying
+/// executable transactions whatever they pay relative to the rest of the
+/// pool; the remaining slots go to the highest fee density overall, lane
+/// transactions included. A flood of well-paying user transfers therefore
+/// cannot keep validator operations out of blocks.
+/// Slots a lane cannot fill are not held back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lane {
+    /// Name, for logs and configuration.
+    pub name: String,
+
+    /// Transaction types the lane carries. A type listed by several lanes
+    /// belongs to the first.
+    pub types: Vec<TransactionType>,
+
+    /// Share of each block's transaction slots reserved for the lane, in
+    /// basis points, rounded up to a whole slot.
+    pub reserved_bps: u16,
+}
+
+impl Lane {
+    /// Every transaction type the validator set signs: unjailing, network
+    /// key rotation and supermajority treasury spends. 5% of each block.
+    ///
+    /// The protocol has no stake, unstake or vote transactions — stake is
+    /// bonded through validator configuration and consensus votes travel
+    /// as gossip, not in blocks — so there is nothing else to carry.
+    pub fn validator() -> Self {
+        Self {
+            name: "validator".into(),
+            types: vec![
+                TransactionType::Unjail,
+                TransactionType::NetworkKeyRotation,
+                TransactionType::TreasurySpend,
+            ],
+            reserved_bps: 500,
         }
     }
+
+    /// Returns `true` if the lane carries transactions of `tx_type`.
+    pub fn carries(&self, tx_type: TransactionType) -> bool {
+        self.types.contains(&tx_type)
+    }
+
+    /// Slots reserved in a block of `max_count` transactions.
+    pub fn reserved_slots(&self, max_count: usize) -> usize {
+        (max_count as u64 * u64::from(self.reserved_bps)).div_ceil(10_000) as usize
+    }
 }
 
+/// A sender's selectable transactions in nonce order, as
+/// `(fee rank, lane, tx_id)`.
+type SenderQueue = VecDeque<(usize, Option<usize>, String)>;
+
 // ---------------------------------------------------------------------------
 // FeeKey — B-tree ordering key
 // ---------------------------------------------------------------------------
@@ -116,6 +255,16 @@ pub struct MempoolEntry {
 
     /// Pre-computed fee density used for priority ordering.
     pub fee_per_byte: u64,
+
+    /// Transaction weight in bytes, counted against `config.max_bytes`.
+    pub weight: usize,
+}
+
+impl MempoolEntry {
+    /// Seconds the transaction has been waiting in the pool.
+    pub fn age_secs(&self) -> u64 {
+        current_timestamp_secs().saturating_sub(self.added_at)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -123,44 +272,325 @@ pub struct MempoolEntry {
 // ---------------------------------------------------------------------------
 
 /// Errors returned by mempool operations.
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum MempoolError {
     /// A transaction with the same ID is already in the pool.
+    #[error("transaction already exists in mempool")]
     DuplicateTransaction,
 
+    /// The transaction's `valid_until` has already passed.
+    #[error("transaction expired at {valid_until}")]
+    Expired { valid_until: u64 },
+
     /// The offered fee does not meet the minimum threshold.
+    #[error("fee too low: minimum {min}, got {got}")]
     FeeTooLow { min: u64, got: u64 },
 
     /// The sender already has too many pending transactions.
+    #[error("sender {sender} exceeded per-sender limit of {limit}")]
     SenderLimitExceeded { sender: String, limit: usize },
 
     /// The pool is at capacity and the incoming transaction does not outbid
     /// the lowest-fee entry.
+    #[error("mempool is full ({size} transactions)")]
     MempoolFull { size: usize },
+
+    /// Admitting the transaction would exceed the pool's byte budget and
+    /// evicting the entries it outbids would not free enough room.
+    #[error("mempool is full ({bytes} bytes)")]
+    MemoryFull { bytes: usize },
+
+    /// A field exceeds its protocol size bound.
+    #[error("transaction rejected")]
+    Oversized(#[source] TransactionError),
+
+    /// The sender or receiver is not a valid address under the pool's policy.
+    #[error(transparent)]
+    InvalidAddress(InvalidAddress),
+
+    /// The pool's screener refused the sender or receiver.
+    #[error(transparent)]
+    Screened(ScreeningRejection),
+
+    /// The pool's validator found the transaction unexecutable against
+    /// account state.
+    #[error(transparent)]
+    Unexecutable(AdmissionRejection),
+
+    /// No pending transaction has the ID a cancellation names.
+    #[error("no pending transaction {0}")]
+    UnknownTransaction(String),
+
+    /// The cancellation is not signed by the transaction's sender.
+    #[error("cancellation of {0} is not signed by its sender")]
+    InvalidCancellation(String),
+}
+
+// ---------------------------------------------------------------------------
+// Admission validation
+// ---------------------------------------------------------------------------
+
+/// Why a [`MempoolValidator`] refused a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum AdmissionRejection {
+    /// The sender's account already used the transaction's nonce.
+    #[error("nonce {nonce} already used (account nonce is {account_nonce})")]
+    NonceUsed { nonce: u64, account_nonce: u64 },
+
+    /// `account` cannot cover what the transaction debits from it.
+    #[error("insufficient balance in {account}: need {need}, have {have}")]
+    InsufficientBalance {
+        account: String,
+        need: u64,
+        have: u64,
+    },
+
+    /// The sender's account is frozen.
+    #[error("account is frozen: {account}")]
+    Frozen { account: String },
+}
+
+/// A stateful admission check, run by [`Mempool::add`] after the stateless
+/// checks and screening.
+///
+/// It judges each transaction on its own: transactions the sender already
+/// has pending are not accounted for, so it only refuses those that could
+/// not execute even first in line.
+pub trait MempoolValidator: Send + Sync {
+    /// Returns `Err` if `tx` cannot execute against current state.
+    fn validate(&self, tx: &Transaction) -> Result<(), AdmissionRejection>;
+
+    /// The next nonce `sender`'s account expects, if known. Without it the
+    /// pool cannot tell a nonce gap from a sender it has not seen yet, and
+    /// buffers no orphans.
+    fn next_nonce(&self, _sender: &str) -> Option<u64> {
+        None
+    }
+}
+
+/// [`MempoolValidator`] over the last committed state of the node's state
+/// tree. Refuses transactions from frozen senders, nonces the sender has
+/// already used, and transactions whose NOVA debits (a transfer's amount
+/// plus the fee, or the fee alone from a sponsor) exceed the paying
+/// account's available balance. Token amounts are left to execution.
+pub struct StateValidator {
+    state: Arc<CommittedState>,
+}
+
+impl StateValidator {
+    /// Validates against `state`.
+    pub fn new(state: Arc<CommittedState>) -> Self {
+        Self { state }
+    }
+}
+
+impl MempoolValidator for StateValidator {
+    fn validate(&self, tx: &Transaction) -> Result<(), AdmissionRejection> {
+        let snapshot = self.state.snapshot();
+        let sender = snapshot.get(&tx.sender).unwrap_or_default();
+        if sender.frozen {
+            return Err(AdmissionRejection::Frozen {
+                account: tx.sender.clone(),
+            });
+        }
+        if tx.nonce < sender.next_nonce() {
+            return Err(AdmissionRejection::NonceUsed {
+                nonce: tx.nonce,
+                account_nonce: sender.nonce,
+            });
+        }
+
+        let native =
+            if tx.tx_type == TransactionType::Transfer && tx.amount.currency == Currency::NOVA {
+                tx.amount.value
+            } else {
+                0
+            };
+        // A sponsored fee is the fee payer's to cover, not the sender's.
+        if tx.is_sponsored() {
+            covers(&tx.sender, native, sender.available_balance())?;
+            let payer = tx.fee_source();
+            let have = snapshot.get(payer).map_or(0, |a| a.available_balance());
+            covers(payer, tx.fee, have)
+        } else {
+            covers(
+                &tx.sender,
+                native.saturating_add(tx.fee),
+                sender.available_balance(),
+            )
+        }
+    }
+
+    fn next_nonce(&self, sender: &str) -> Option<u64> {
+        Some(self.state.get(sender).unwrap_or_default().next_nonce())
+    }
+}
+
+/// Checks that `account`, with `have` available, can pay `need`.
+fn covers(account: &str, need: u64, have: u64) -> Result<(), AdmissionRejection> {
+    if have < need {
+        return Err(AdmissionRejection::InsufficientBalance {
+            account: account.to_string(),
+            need,
+            have,
+        });
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Evictions
+// ---------------------------------------------------------------------------
+
+/// Why the pool dropped a transaction before any block included it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// Its `valid_until` passed ([`Mempool::evict_expired`]).
+    Expired,
+
+    /// It was pending longer than `config.expiry_seconds`
+    /// ([`Mempool::expire_old`]).
+    Ttl,
+
+    /// The pool was full, by count or by bytes, and a transaction paying a
+    /// higher fee-per-byte took its place.
+    Outbid,
+
+    /// Its sender was at `max_txs_per_sender` and replaced it with a
+    /// better-paying transaction of their own.
+    Replaced,
+
+    /// It waited behind a nonce gap and was pushed out of the full orphan
+    /// buffer, overtaken by its account nonce, or refused on promotion.
+    Orphaned,
+}
+
+impl EvictionReason {
+    /// Stable lowercase name, as used in node events.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Expired => "expired",
+            Self::Ttl => "ttl",
+            Self::Outbid => "outbid",
+            Self::Replaced => "replaced",
+            Self::Orphaned => "orphaned",
+        }
+    }
 }
 
-impl fmt::Display for MempoolError {
+impl fmt::Display for EvictionReason {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A transaction the pool dropped without including it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eviction {
+    /// ID of the evicted transaction.
+    pub tx_id: String,
+
+    /// Its sender.
+    pub sender: String,
+
+    /// Why it was dropped.
+    pub reason: EvictionReason,
+}
+
+/// Evictions buffered per subscriber before the slowest one starts lagging.
+const EVICTION_CHANNEL_CAPACITY: usize = 1024;
+
+// ---------------------------------------------------------------------------
+// Events
+// ---------------------------------------------------------------------------
+
+/// A change to the pool, as published to [`Mempool::subscribe`] subscribers.
+///
+/// A transaction entering the pool is `Added`, and leaves it as exactly one
+/// of `Removed`, `Replaced` or `Evicted`. Orphans are not in the pool: one
+/// is `Added` once promoted, but an orphan dropped from the buffer is still
+/// reported as `Replaced` or `Evicted`, as on the eviction stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MempoolEvent {
+    /// A transaction entered the pool.
+    Added {
+        tx_id: String,
+        sender: String,
+        fee_per_byte: u64,
+    },
+
+    /// A transaction left the pool without being dropped: a block included
+    /// it, its sender cancelled it, or the pool was cleared.
+    Removed { tx_id: String, sender: String },
+
+    /// The sender replaced a transaction with `by`, a better-paying one of
+    /// their own.
+    Replaced {
+        tx_id: String,
+        sender: String,
+        by: String,
+    },
+
+    /// The pool dropped a transaction for any other reason.
+    Evicted(Eviction),
+}
+
+impl MempoolEvent {
+    /// Stable lowercase name, as used in metric labels.
+    pub fn kind(&self) -> &'static str {
         match self {
-            Self::DuplicateTransaction => write!(f, "transaction already exists in mempool"),
-            Self::FeeTooLow { min, got } => {
-                write!(f, "fee too low: minimum {}, got {}", min, got)
-            }
-            Self::SenderLimitExceeded { sender, limit } => {
-                write!(
-                    f,
-                    "sender {} exceeded per-sender limit of {}",
-                    sender, limit
-                )
-            }
-            Self::MempoolFull { size } => {
-                write!(f, "mempool is full ({} transactions)", size)
-            }
+            Self::Added { .. } => "added",
+            Self::Removed { .. } => "removed",
+            Self::Replaced { .. } => "replaced",
+            Self::Evicted(_) => "evicted",
+        }
+    }
+
+    /// ID of the transaction the event is about.
+    pub fn tx_id(&self) -> &str {
+        match self {
+            Self::Added { tx_id, .. }
+            | Self::Removed { tx_id, .. }
+            | Self::Replaced { tx_id, .. } => tx_id,
+            Self::Evicted(eviction) => &eviction.tx_id,
         }
     }
 }
 
-impl std::error::Error for MempoolError {}
+/// Events buffered per subscriber before the slowest one starts lagging.
+const EVENT_CHANNEL_CAPACITY: usize = 4096;
+
+// ---------------------------------------------------------------------------
+// Reorg handling
+// ---------------------------------------------------------------------------
+
+/// What happened to a transaction from a rolled-back block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReorgOutcome {
+    /// Still valid against the new state; back in the pool as pending.
+    Readmitted,
+
+    /// The new canonical branch already includes it.
+    AlreadyIncluded,
+
+    /// No longer valid (or refused by the pool); the reason is attached.
+    Dropped(String),
+}
+
+/// A transaction that was part of an abandoned block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgedTransaction {
+    /// ID of the transaction.
+    pub tx_id: String,
+
+    /// Height of the abandoned block that contained it.
+    pub from_height: u64,
+
+    /// Where the transaction ended up.
+    pub outcome: ReorgOutcome,
+}
 
 // ---------------------------------------------------------------------------
 // Mempool
@@ -183,15 +613,42 @@ pub struct Mempool {
     /// Per-sender transaction count for rate limiting.
     sender_counts: DashMap<String, usize>,
 
+    /// Per-sender pending nonces, with the number of transactions at each,
+    /// for finding the sender's next expected nonce.
+    sender_nonces: DashMap<String, BTreeMap<u64, usize>>,
+
+    /// Transactions waiting for an earlier nonce of their sender.
+    orphans: OrphanPool,
+
+    /// Total weight of the pooled transactions, in bytes.
+    total_bytes: AtomicUsize,
+
+    /// Announces every eviction; sends with no subscriber are dropped.
+    evictions: broadcast::Sender<Eviction>,
+
+    /// Announces every change to the pool, evictions included.
+    events: broadcast::Sender<MempoolEvent>,
+
     /// Configuration knobs.
     config: MempoolConfig,
+
+    /// Address screening, if the operator configured it. `None` costs
+    /// nothing on the admission path.
+    screener: Option<Arc<Screener>>,
+
+    /// Stateful admission check, if one is attached.
+    validator: Option<Arc<dyn MempoolValidator>>,
 }
 
 impl fmt::Debug for Mempool {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Mempool")
             .field("size", &self.transactions.len())
+            .field("total_bytes", &self.total_bytes())
+            .field("orphans", &self.orphans.len())
             .field("config", &self.config)
+            .field("screener", &self.screener)
+            .field("validator", &self.validator.is_some())
             .finish()
     }
 }
@@ -203,67 +660,198 @@ impl Mempool {
             transactions: DashMap::new(),
             fee_index: RwLock::new(BTreeMap::new()),
             sender_counts: DashMap::new(),
+            sender_nonces: DashMap::new(),
+            orphans: OrphanPool::new(config.max_orphans, config.max_txs_per_sender),
+            total_bytes: AtomicUsize::new(0),
+            evictions: broadcast::channel(EVICTION_CHANNEL_CAPACITY).0,
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
             config,
+            screener: None,
+            validator: None,
         }
     }
 
+    /// Screens the sender and receiver of every admitted transaction with
+    /// `screener`.
+    pub fn with_screener(mut self, screener: Arc<Screener>) -> Self {
+        self.screener = Some(screener);
+        self
+    }
+
+    /// Checks every admitted transaction against account state with
+    /// `validator`.
+    pub fn with_validator(mut self, validator: Arc<dyn MempoolValidator>) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Subscribes to the transactions the pool drops without including
+    /// them: expired, stale, outbid or replaced by their sender. Removals
+    /// for inclusion, cancellation or [`clear`](Self::clear) are not
+    /// reported.
+    pub fn subscribe_evictions(&self) -> broadcast::Receiver<Eviction> {
+        self.evictions.subscribe()
+    }
+
+    /// Subscribes to every change to the pool: transactions added,
+    /// removed, replaced by their sender or evicted (see [`MempoolEvent`]).
+    pub fn subscribe(&self) -> broadcast::Receiver<MempoolEvent> {
+        self.events.subscribe()
+    }
+
     /// Adds a validated transaction to the mempool.
     ///
     /// The following checks are applied in order:
     ///
+    /// 0. **Field limits** — reject oversized payloads, proofs and addresses,
+    ///    and transactions weighing more than `config.max_tx_bytes`, before
+    ///    they take up any pool memory, then check both addresses against
+    ///    `config.address_policy`.
     /// 1. **Duplicate** — reject if a transaction with the same ID already exists.
-    /// 2. **Minimum fee** — reject if `tx.fee < config.min_fee`.
-    /// 3. **Per-sender limit** — reject if the sender already has
-    ///    `config.max_per_sender` pending transactions.
-    /// 4. **Capacity** — if the pool is full, attempt to evict the lowest-fee
-    ///    transaction. If the incoming transaction does not outbid it, reject.
+    /// 2. **Expiry** — reject if the transaction's `valid_until` has passed.
+    /// 3. **Minimum fee** — reject if `tx.fee < config.min_fee`, plus
+    ///    `config.encrypted_byte_fee` for each byte of an encrypted payload.
+    /// 4. **Screening** — if a screener is attached, reject transactions it
+    ///    refuses. Runs after the cheap checks so duplicates and dust never
+    ///    reach the provider.
+    /// 5. **State** — if a validator is attached, reject transactions it
+    ///    finds unexecutable against account state.
+    /// 6. **Nonce gap** — if the validator knows the sender's account nonce
+    ///    and the transaction is ahead of the sender's next expected nonce
+    ///    (the account nonce, or the first one after the sender's contiguous
+    ///    pending run), buffer it as an orphan and stop. A sender's orphan
+    ///    at the same nonce is replaced only by a higher fee, and a sender
+    ///    already holding `config.max_txs_per_sender` orphans is rejected.
+    /// 7. **Per-sender limit** — if the sender already has
+    ///    `config.max_txs_per_sender` pending transactions, the incoming one
+    ///    may replace the sender's transaction at the same nonce or, if it
+    ///    comes before it, the sender's highest-nonce one; replacing any
+    ///    other would leave a nonce gap. If there is none to replace or the
+    ///    incoming transaction does not outbid it, reject.
+    /// 8. **Capacity** — if the pool would exceed `config.max_size`
+    ///    transactions or `config.max_bytes` bytes, counting the slot a
+    ///    replacement frees, evict the lowest fee-per-byte transactions until
+    ///    it fits. If the ones the incoming transaction outbids cannot make
+    ///    enough room, evict nothing, keep the transaction it would have
+    ///    replaced, and reject.
     ///
-    /// On success the transaction is inserted into all indices atomically.
+    /// On success the transaction is inserted into all indices atomically,
+    /// and the sender's orphans that now follow on without a gap are
+    /// promoted into the pool in nonce order.
     pub fn add(&self, tx: Transaction) -> Result<(), MempoolError> {
+        let sender = tx.sender.clone();
+        if self.admit(tx)? == Admission::Pending {
+            if let Some(base) = self.account_nonce(&sender) {
+                self.promote(&sender, base);
+            }
+        }
+        Ok(())
+    }
+
+    /// The checks and insertion behind [`add`](Self::add), without
+    /// promoting orphans.
+    fn admit(&self, tx: Transaction) -> Result<Admission, MempoolError> {
+        // 0. Field size bounds.
+        limits::check_sizes(&tx).map_err(MempoolError::Oversized)?;
+        let weight = limits::tx_weight(&tx);
+        if weight > self.config.max_tx_bytes {
+            return Err(MempoolError::Oversized(TransactionError::FieldTooLarge {
+                field: "transaction",
+                size: weight,
+                max: self.config.max_tx_bytes,
+            }));
+        }
+        for address in [&tx.sender, &tx.receiver] {
+            self.config
+                .address_policy
+                .check(address)
+                .map_err(MempoolError::InvalidAddress)?;
+        }
+
         // 1. Duplicate check.
-        if self.transactions.contains_key(&tx.id) {
+        if self.transactions.contains_key(&tx.id) || self.orphans.contains(&tx.id) {
             return Err(MempoolError::DuplicateTransaction);
         }
 
-        // 2. Minimum fee enforcement.
-        if tx.fee < self.config.min_fee {
+        // 2. Expiry.
+        if let Some(valid_until) = tx.valid_until {
+            if tx.is_expired_at(current_timestamp_ms()) {
+                return Err(MempoolError::Expired { valid_until });
+            }
+        }
+
+        // 3. Minimum fee enforcement.
+        let min_fee = self.config.min_fee.saturating_add(
+            (limits::encrypted_payload_len(&tx) as u64)
+                .saturating_mul(self.config.encrypted_byte_fee),
+        );
+        if tx.fee < min_fee {
             return Err(MempoolError::FeeTooLow {
-                min: self.config.min_fee,
+                min: min_fee,
                 got: tx.fee,
             });
         }
 
-        // 3. Per-sender limit.
-        let sender = tx.sender.clone();
-        let sender_count = self.sender_counts.get(&sender).map(|v| *v).unwrap_or(0);
+        // 4. Address screening.
+        if let Some(screener) = &self.screener {
+            screener
+                .screen_transaction(&tx)
+                .map_err(MempoolError::Screened)?;
+        }
 
-        if sender_count >= self.config.max_per_sender {
-            return Err(MempoolError::SenderLimitExceeded {
-                sender,
-                limit: self.config.max_per_sender,
-            });
+        // 5. Stateful admission check.
+        if let Some(validator) = &self.validator {
+            validator
+                .validate(&tx)
+                .map_err(MempoolError::Unexecutable)?;
         }
 
-        // 4. Capacity check with eviction.
-        if self.transactions.len() >= self.config.max_size {
-            let incoming_fpb = tx.fee_per_byte();
-            let evicted = self.try_evict_lowest(incoming_fpb);
-            if !evicted {
-                return Err(MempoolError::MempoolFull {
-                    size: self.config.max_size,
-                });
+        // 6. Nonce gap.
+        if self.config.max_orphans > 0 {
+            let expected = self
+                .account_nonce(&tx.sender)
+                .map(|base| self.next_expected_nonce(&tx.sender, base));
+            if expected.is_some_and(|expected| tx.nonce > expected) {
+                self.orphan(tx)?;
+                return Ok(Admission::Orphaned);
             }
         }
 
+        // 7. Per-sender limit.
+        let sender = tx.sender.clone();
+        let sender_count = self.sender_counts.get(&sender).map(|v| *v).unwrap_or(0);
+
+        let mut victim = None;
+        if sender_count >= self.config.max_txs_per_sender {
+            match self.sender_victim(&sender, tx.nonce) {
+                Some((victim_fpb, victim_id)) if tx.fee_per_byte() > victim_fpb => {
+                    victim = Some(victim_id);
+                }
+                _ => {
+                    return Err(MempoolError::SenderLimitExceeded {
+                        sender,
+                        limit: self.config.max_txs_per_sender,
+                    });
+                }
+            }
+        }
+
+        // 8. Capacity check with eviction. The victim leaves the pool only
+        // once the incoming transaction is sure to fit.
+        self.make_room(tx.fee_per_byte(), weight, victim.as_deref())?;
+        let replaced = victim.and_then(|id| self.take(&id));
+
         // Build the entry and insert into all indices.
         let now = current_timestamp_secs();
         let fee_per_byte = tx.fee_per_byte();
         let tx_id = tx.id.clone();
+        let nonce = tx.nonce;
 
         let entry = MempoolEntry {
             transaction: tx,
             added_at: now,
             fee_per_byte,
+            weight,
         };
 
         let fee_key = FeeKey {
@@ -273,17 +861,60 @@ impl Mempool {
         };
 
         self.transactions.insert(tx_id.clone(), entry);
-        self.fee_index.write().insert(fee_key, tx_id);
-        *self.sender_counts.entry(sender).or_insert(0) += 1;
+        self.fee_index.write().insert(fee_key, tx_id.clone());
+        *self
+            .sender_nonces
+            .entry(sender.clone())
+            .or_default()
+            .entry(nonce)
+            .or_insert(0) += 1;
+        *self.sender_counts.entry(sender.clone()).or_insert(0) += 1;
+        self.total_bytes.fetch_add(weight, Ordering::Relaxed);
+
+        if let Some(replaced) = replaced {
+            self.announce_replaced(replaced, &tx_id);
+        }
+        let _ = self.events.send(MempoolEvent::Added {
+            tx_id,
+            sender,
+            fee_per_byte,
+        });
 
-        Ok(())
+        Ok(Admission::Pending)
     }
 
     /// Removes a transaction by its ID and returns it, or `None` if not found.
     pub fn remove(&self, tx_id: &str) -> Option<Transaction> {
-        let (_, entry) = self.transactions.remove(tx_id)?;
-        self.remove_from_indices(&entry);
-        Some(entry.transaction)
+        let tx = self.take(tx_id)?;
+        let _ = self.events.send(MempoolEvent::Removed {
+            tx_id: tx.id.clone(),
+            sender: tx.sender.clone(),
+        });
+        Some(tx)
+    }
+
+    /// Drops the pending or orphaned transaction `tx_id` on its sender's
+    /// signed request, returning it so the caller can pass the
+    /// cancellation on.
+    ///
+    /// The cancellation must name `tx_id` and verify against the
+    /// transaction's `sender_public_key`, which admission has already tied
+    /// to the sender address.
+    pub fn cancel(
+        &self,
+        tx_id: &str,
+        cancellation: &TransactionCancellation,
+    ) -> Result<Transaction, MempoolError> {
+        let tx = self
+            .get(tx_id)
+            .or_else(|| self.orphans.get(tx_id))
+            .ok_or_else(|| MempoolError::UnknownTransaction(tx_id.to_string()))?;
+        if !cancellation.verify(&tx) {
+            return Err(MempoolError::InvalidCancellation(tx_id.to_string()));
+        }
+        self.remove(tx_id)
+            .or_else(|| self.orphans.remove(tx_id))
+            .ok_or_else(|| MempoolError::UnknownTransaction(tx_id.to_string()))
     }
 
     /// Batch-removes transactions by their IDs.
@@ -306,26 +937,92 @@ impl Mempool {
         self.transactions.contains_key(tx_id)
     }
 
-    /// Selects up to `max_count` transactions ordered by fee density
-    /// (highest first) for block proposal.
-    ///
-    /// The returned vector is ordered from highest to lowest fee-per-byte.
-    /// This is the primary interface used by the consensus engine during
-    /// block production.
-    pub fn select_transactions(&self, max_count: usize) -> Vec<Transaction> {
-        let index = self.fee_index.read();
-        let mut result = Vec::with_capacity(max_count.min(index.len()));
+    /// Returns `true` if the transaction with the given ID is waiting
+    /// behind a nonce gap.
+    pub fn is_orphan(&self, tx_id: &str) -> bool {
+        self.orphans.contains(tx_id)
+    }
 
-        for (_key, tx_id) in index.iter() {
-            if result.len() >= max_count {
-                break;
-            }
-            if let Some(entry) = self.transactions.get(tx_id) {
-                result.push(entry.transaction.clone());
+    /// Returns the number of transactions waiting behind a nonce gap. They
+    /// are not counted by [`size`](Self::size).
+    pub fn orphan_count(&self) -> usize {
+        self.orphans.len()
+    }
+
+    /// Promotes every orphan whose predecessor has been committed, and
+    /// drops those whose nonce the sender's account has already used.
+    /// Called after each committed block, since a block from another
+    /// producer can close a gap without the missing transaction ever
+    /// passing through this pool. Returns the number promoted.
+    pub fn promote_orphans(&self) -> usize {
+        let mut promoted = 0;
+        for sender in self.orphans.senders() {
+            let Some(base) = self.account_nonce(&sender) else {
+                continue;
+            };
+            let stale = self
+                .orphans
+                .remove_where(|o| o.transaction.sender == sender && o.transaction.nonce < base);
+            for tx in stale {
+                self.announce(tx, EvictionReason::Orphaned);
             }
+            promoted += self.promote(&sender, base);
         }
+        promoted
+    }
 
-        result
+    /// Returns the pool entry for the transaction with the given ID, with
+    /// its admission time and fee density.
+    pub fn entry(&self, tx_id: &str) -> Option<MempoolEntry> {
+        self.transactions.get(tx_id).map(|e| e.value().clone())
+    }
+
+    /// Returns up to `limit` pool entries after skipping the first `offset`,
+    /// highest fee density first (earliest admitted first on ties).
+    ///
+    /// Unlike [`select_transactions`](Self::select_transactions) this lists
+    /// every pending transaction, parked ones included, in an order that
+    /// only changes as transactions come and go, so callers can page
+    /// through the pool by offset.
+    pub fn entries(&self, offset: usize, limit: usize) -> Vec<MempoolEntry> {
+        self.fee_index
+            .read()
+            .values()
+            .skip(offset)
+            .filter_map(|tx_id| self.transactions.get(tx_id).map(|e| e.value().clone()))
+            .take(limit)
+            .collect()
+    }
+
+    /// Selects up to `max_count` transactions for block proposal, highest
+    /// fee density first, each sender's in nonce order.
+    ///
+    /// A sender's transactions form a queue from its lowest pending nonce,
+    /// and each becomes selectable once the one before it is selected, so a
+    /// follow-up never runs ahead of the transaction it follows however much
+    /// more it pays. Transactions behind a nonce gap are parked — kept in
+    /// the pool but not selected — until the missing nonce arrives. Across
+    /// senders, the heads of the queues compete on fee-per-byte.
+    ///
+    /// Each of `config.lanes` first gets its reserved share of the
+    /// `max_count` slots, taken from the queue heads it carries; the rest
+    /// are filled by fee as above.
+    ///
+    /// Only the pool is consulted; [`select_executable`](Self::select_executable)
+    /// also starts each queue at the sender's account nonce.
+    pub fn select_transactions(&self, max_count: usize) -> Vec<Transaction> {
+        self.select_in_nonce_order(max_count, |_| None)
+    }
+
+    /// Like [`select_transactions`](Self::select_transactions), but each
+    /// sender's queue starts at its next account nonce in `state`: a sender
+    /// whose lowest pending nonce is ahead of it has everything parked.
+    /// Transactions reusing an executed nonce are still selected, for block
+    /// production to refuse and report.
+    pub fn select_executable(&self, state: &StateTree, max_count: usize) -> Vec<Transaction> {
+        self.select_in_nonce_order(max_count, |sender| {
+            Some(state.get(sender).unwrap_or_default().next_nonce())
+        })
     }
 
     /// Returns the current number of transactions in the pool.
@@ -338,15 +1035,29 @@ impl Mempool {
         self.transactions.is_empty()
     }
 
-    /// Removes all transactions from the pool.
+    /// Returns the total weight of the pending transactions, in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Removes all transactions from the pool, orphans included.
     pub fn clear(&self) {
+        for entry in self.transactions.iter() {
+            let _ = self.events.send(MempoolEvent::Removed {
+                tx_id: entry.key().clone(),
+                sender: entry.value().transaction.sender.clone(),
+            });
+        }
         self.transactions.clear();
         self.fee_index.write().clear();
         self.sender_counts.clear();
+        self.sender_nonces.clear();
+        self.orphans.clear();
+        self.total_bytes.store(0, Ordering::Relaxed);
     }
 
-    /// Removes transactions that have been in the pool longer than
-    /// `config.expiry_seconds`.
+    /// Evicts transactions, orphans included, that have been in the pool
+    /// longer than `config.expiry_seconds`, their time-to-live.
     ///
     /// Intended to be called periodically by a background timer in the
     /// validator node. Returns the number of expired transactions removed.
@@ -354,6 +1065,12 @@ impl Mempool {
         let now = current_timestamp_secs();
         let cutoff = now.saturating_sub(self.config.expiry_seconds);
 
+        let stale_orphans = self.orphans.remove_where(|o| o.added_at < cutoff);
+        let orphans = stale_orphans.len();
+        for tx in stale_orphans {
+            self.announce(tx, EvictionReason::Ttl);
+        }
+
         // Collect expired IDs first to avoid holding a DashMap iterator
         // while mutating.
         let expired_ids: Vec<String> = self
@@ -365,10 +1082,40 @@ impl Mempool {
 
         let count = expired_ids.len();
         for id in &expired_ids {
-            self.remove(id);
+            self.evict(id, EvictionReason::Ttl);
         }
 
-        count
+        count + orphans
+    }
+
+    /// Evicts transactions whose `valid_until` lies before `now_ms`
+    /// (Unix milliseconds). No block may include them any more, so they
+    /// only hold a sender's slot.
+    ///
+    /// Called by the node's background sweep next to
+    /// [`expire_old`](Self::expire_old). Returns the number removed.
+    pub fn evict_expired(&self, now_ms: u64) -> usize {
+        let expired_orphans = self
+            .orphans
+            .remove_where(|o| o.transaction.is_expired_at(now_ms));
+        let orphans = expired_orphans.len();
+        for tx in expired_orphans {
+            self.announce(tx, EvictionReason::Expired);
+        }
+
+        let expired_ids: Vec<String> = self
+            .transactions
+            .iter()
+            .filter(|entry| entry.value().transaction.is_expired_at(now_ms))
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        let count = expired_ids.len();
+        for id in &expired_ids {
+            self.evict(id, EvictionReason::Expired);
+        }
+
+        count + orphans
     }
 
     /// Returns all pending transactions for a given sender address.
@@ -380,41 +1127,406 @@ impl Mempool {
             .collect()
     }
 
+    /// Returns the transactions of rolled-back blocks to the pool.
+    ///
+    /// `abandoned` are the blocks removed from the chain, in ascending
+    /// height order, and `canonical_tx_ids` the transactions included by the
+    /// replacement branch. Every abandoned transaction is re-validated
+    /// against `state` (the state after the new branch): the stateless
+    /// checks run again under the pool's address policy, frozen senders are
+    /// refused, nonces the new branch already consumed are dropped, and
+    /// transfers (amount plus fee) must still be covered by the sender's
+    /// available balance. Transfers re-admitted earlier in the same call are
+    /// accounted for, so a chain of dependent payments is judged in order.
+    ///
+    /// Survivors go through [`add`](Self::add) like any other submission.
+    /// The returned list has one entry per abandoned transaction, in block
+    /// order, for status tracking and event emission.
+    pub fn readmit_reorged(
+        &self,
+        abandoned: &[Block],
+        canonical_tx_ids: &HashSet<String>,
+        state: &StateTree,
+    ) -> Vec<ReorgedTransaction> {
+        // Balances as adjusted by the transfers re-admitted so far.
+        let mut balances: HashMap<String, u64> = HashMap::new();
+
+        let mut results = Vec::new();
+        for block in abandoned {
+            for tx in &block.transactions {
+                let is_transfer = tx.tx_type == TransactionType::Transfer;
+                let outcome = if canonical_tx_ids.contains(&tx.id) {
+                    ReorgOutcome::AlreadyIncluded
+                } else if let Err(e) =
+                    verify_transaction_with_policy(tx, self.config.address_policy)
+                {
+                    ReorgOutcome::Dropped(e.to_string())
+                } else if state.get(&tx.sender).is_some_and(|a| a.frozen) {
+                    ReorgOutcome::Dropped(format!("account is frozen: {}", tx.sender))
+                } else if let Some(used) = state
+                    .get(&tx.sender)
+                    .map(|a| a.nonce)
+                    .filter(|&used| tx.nonce <= used)
+                {
+                    ReorgOutcome::Dropped(format!(
+                        "nonce {} already used (account nonce is {})",
+                        tx.nonce, used
+                    ))
+                } else {
+                    let have = available_balance(&mut balances, state, &tx.sender);
+                    // Only NOVA is tracked here; token amounts are left to
+                    // the state transition.
+                    let native = if tx.amount.currency == Currency::NOVA {
+                        tx.amount.value
+                    } else {
+                        0
+                    };
+                    // A sponsored fee is the fee payer's to cover, not the
+                    // sender's.
+                    let need = if tx.is_sponsored() {
+                        native
+                    } else {
+                        native.saturating_add(tx.fee)
+                    };
+                    if is_transfer && have < need {
+                        ReorgOutcome::Dropped(format!(
+                            "insufficient balance: need {}, have {}",
+                            need, have
+                        ))
+                    } else {
+                        match self.add(tx.clone()) {
+                            Ok(()) | Err(MempoolError::DuplicateTransaction) => {
+                                if is_transfer {
+                                    balances.insert(tx.sender.clone(), have - need);
+                                    let credited =
+                                        available_balance(&mut balances, state, &tx.receiver)
+                                            .saturating_add(native);
+                                    balances.insert(tx.receiver.clone(), credited);
+                                }
+                                ReorgOutcome::Readmitted
+                            }
+                            Err(e) => ReorgOutcome::Dropped(e.to_string()),
+                        }
+                    }
+                };
+
+                results.push(ReorgedTransaction {
+                    tx_id: tx.id.clone(),
+                    from_height: block.header.height,
+                    outcome,
+                });
+            }
+        }
+        results
+    }
+
     // -----------------------------------------------------------------------
     // Internal helpers
     // -----------------------------------------------------------------------
 
-    /// Attempts to evict the lowest-fee transaction to make room for an
-    /// incoming one with `incoming_fpb` fee-per-byte. Returns `true` if
-    /// eviction succeeded.
-    fn try_evict_lowest(&self, incoming_fpb: u64) -> bool {
-        let mut index = self.fee_index.write();
-
-        // The last entry in our inverted B-tree is the one with the
-        // highest inverted_fee, i.e. the lowest actual fee-per-byte.
-        let lowest_key = match index.keys().next_back() {
-            Some(k) => k.clone(),
-            None => return false,
+    /// Selection behind [`select_transactions`](Self::select_transactions).
+    /// `next_nonce` gives the nonce a sender's queue starts at, or `None`
+    /// to start at its lowest pending nonce.
+    fn select_in_nonce_order(
+        &self,
+        max_count: usize,
+        next_nonce: impl Fn(&str) -> Option<u64>,
+    ) -> Vec<Transaction> {
+        // Group the pool by sender, noting each transaction's fee rank
+        // and lane.
+        let mut by_sender: HashMap<String, Vec<(u64, usize, Option<usize>, String)>> =
+            HashMap::new();
+        for (rank, tx_id) in self.fee_index.read().values().enumerate() {
+            if let Some(entry) = self.transactions.get(tx_id) {
+                let tx = &entry.transaction;
+                by_sender.entry(tx.sender.clone()).or_default().push((
+                    tx.nonce,
+                    rank,
+                    self.lane_of(tx.tx_type),
+                    tx_id.clone(),
+                ));
+            }
+        }
+
+        // Order each sender's transactions by nonce (the better-paying
+        // first on a shared nonce) and cut the queue at the first gap.
+        let mut queues: Vec<SenderQueue> = Vec::with_capacity(by_sender.len());
+        for (sender, mut pending) in by_sender {
+            pending.sort_unstable();
+            let mut next = next_nonce(&sender).unwrap_or(pending[0].0);
+            let mut queue = VecDeque::with_capacity(pending.len());
+            for (nonce, rank, lane, tx_id) in pending {
+                if nonce > next {
+                    break;
+                }
+                if nonce == next {
+                    next = next.saturating_add(1);
+                }
+                queue.push_back((rank, lane, tx_id));
+            }
+            queues.push(queue);
+        }
+
+        // Fill each lane's reserved slots, then the rest of the block.
+        let mut result = Vec::with_capacity(max_count.min(self.transactions.len()));
+        for (lane, config) in self.config.lanes.iter().enumerate() {
+            let reserved = config.reserved_slots(max_count);
+            let limit = max_count.min(result.len() + reserved);
+            self.merge_queues(&mut queues, Some(lane), limit, &mut result);
+        }
+        self.merge_queues(&mut queues, None, max_count, &mut result);
+
+        result
+    }
+
+    /// Moves the best-ranked queue heads into `result` until it holds
+    /// `limit` transactions or the queues run dry. With `lane` set, only
+    /// heads in that lane are taken.
+    fn merge_queues(
+        &self,
+        queues: &mut [SenderQueue],
+        lane: Option<usize>,
+        limit: usize,
+        result: &mut Vec<Transaction>,
+    ) {
+        let eligible = |queue: &SenderQueue| {
+            queue
+                .front()
+                .filter(|(_, head_lane, _)| lane.is_none() || *head_lane == lane)
+                .map(|(rank, _, _)| *rank)
         };
 
-        let lowest_fpb = u64::MAX - lowest_key.inverted_fee;
-        if incoming_fpb <= lowest_fpb {
-            // The incoming transaction does not outbid the worst entry.
-            return false;
+        let mut heads: BinaryHeap<Reverse<(usize, usize)>> = queues
+            .iter()
+            .enumerate()
+            .filter_map(|(i, queue)| eligible(queue).map(|rank| Reverse((rank, i))))
+            .collect();
+        while result.len() < limit {
+            let Some(Reverse((_, i))) = heads.pop() else {
+                break;
+            };
+            if let Some((_, _, tx_id)) = queues[i].pop_front() {
+                if let Some(entry) = self.transactions.get(&tx_id) {
+                    result.push(entry.transaction.clone());
+                }
+            }
+            if let Some(rank) = eligible(&queues[i]) {
+                heads.push(Reverse((rank, i)));
+            }
         }
+    }
 
-        // Evict the lowest-fee transaction.
-        let evicted_id = index.remove(&lowest_key).unwrap();
-        drop(index); // release the write lock before touching DashMap
+    /// Index in `config.lanes` of the lane carrying `tx_type`, if any.
+    fn lane_of(&self, tx_type: TransactionType) -> Option<usize> {
+        self.config
+            .lanes
+            .iter()
+            .position(|lane| lane.carries(tx_type))
+    }
 
-        if let Some((_, entry)) = self.transactions.remove(&evicted_id) {
-            self.decrement_sender_count(&entry.transaction.sender);
+    /// Evicts the lowest fee-per-byte transactions, each paying less than
+    /// `incoming_fpb`, until one more transaction weighing `weight` bytes
+    /// fits within both `config.max_size` and `config.max_bytes` once
+    /// `replacing`, if given, is gone. Evicts nothing if those cannot make
+    /// enough room, and never `replacing` itself.
+    fn make_room(
+        &self,
+        incoming_fpb: u64,
+        weight: usize,
+        replacing: Option<&str>,
+    ) -> Result<(), MempoolError> {
+        let (freed_count, freed_bytes) = replacing
+            .and_then(|id| self.transactions.get(id))
+            .map_or((0, 0), |entry| (1, entry.weight));
+        let excess_count =
+            (self.transactions.len() + 1).saturating_sub(self.config.max_size + freed_count);
+        let excess_bytes =
+            (self.total_bytes() + weight).saturating_sub(self.config.max_bytes + freed_bytes);
+        if excess_count == 0 && excess_bytes == 0 {
+            return Ok(());
         }
 
+        // Plan the evictions from the cheapest end of the index before
+        // touching anything, so a rejected admission leaves the pool as is.
+        let mut victims = Vec::new();
+        let mut freed = 0;
+        for (key, id) in self.fee_index.read().iter().rev() {
+            if victims.len() >= excess_count && freed >= excess_bytes {
+                break;
+            }
+            if u64::MAX - key.inverted_fee >= incoming_fpb {
+                break;
+            }
+            if Some(id.as_str()) == replacing {
+                continue;
+            }
+            freed += self.transactions.get(id).map_or(0, |entry| entry.weight);
+            victims.push(id.clone());
+        }
+
+        if victims.len() < excess_count {
+            return Err(MempoolError::MempoolFull {
+                size: self.config.max_size,
+            });
+        }
+        if freed < excess_bytes {
+            return Err(MempoolError::MemoryFull {
+                bytes: self.config.max_bytes,
+            });
+        }
+
+        for id in &victims {
+            self.evict(id, EvictionReason::Outbid);
+        }
+        Ok(())
+    }
+
+    /// The pending transaction of `sender` that one at `nonce` may replace,
+    /// with its fee-per-byte: the sender's cheapest at `nonce`, or else at
+    /// the sender's highest nonce if `nonce` comes before it. Replacing any
+    /// other would leave a gap in the sender's nonces.
+    fn sender_victim(&self, sender: &str, nonce: u64) -> Option<(u64, String)> {
+        let target = {
+            let nonces = self.sender_nonces.get(sender)?;
+            let highest = *nonces.keys().next_back()?;
+            if nonces.contains_key(&nonce) {
+                nonce
+            } else if nonce < highest {
+                highest
+            } else {
+                return None;
+            }
+        };
+
+        // Walk the fee index from the cheapest end to the sender's first
+        // entry at that nonce; only reached when the sender has a full
+        // quota pending.
+        self.fee_index
+            .read()
+            .iter()
+            .rev()
+            .find(|(_, id)| {
+                self.transactions.get(id.as_str()).is_some_and(|entry| {
+                    entry.transaction.sender == sender && entry.transaction.nonce == target
+                })
+            })
+            .map(|(key, id)| (u64::MAX - key.inverted_fee, id.clone()))
+    }
+
+    /// Removes `tx_id` from the pool without announcing it.
+    fn take(&self, tx_id: &str) -> Option<Transaction> {
+        let (_, entry) = self.transactions.remove(tx_id)?;
+        self.remove_from_indices(&entry);
+        Some(entry.transaction)
+    }
+
+    /// Removes `tx_id` and announces it to eviction subscribers. Returns
+    /// `true` if it was pending.
+    fn evict(&self, tx_id: &str, reason: EvictionReason) -> bool {
+        let Some(tx) = self.take(tx_id) else {
+            return false;
+        };
+        self.announce(tx, reason);
         true
     }
 
-    /// Removes an entry's metadata from the fee index and sender counts.
+    /// Tells eviction and event subscribers that `tx` was dropped for
+    /// `reason`.
+    fn announce(&self, tx: Transaction, reason: EvictionReason) {
+        let eviction = Eviction {
+            tx_id: tx.id,
+            sender: tx.sender,
+            reason,
+        };
+        let _ = self.events.send(MempoolEvent::Evicted(eviction.clone()));
+        let _ = self.evictions.send(eviction);
+    }
+
+    /// Tells eviction and event subscribers that `tx`'s sender replaced it
+    /// with `by`.
+    fn announce_replaced(&self, tx: Transaction, by: &str) {
+        let _ = self.events.send(MempoolEvent::Replaced {
+            tx_id: tx.id.clone(),
+            sender: tx.sender.clone(),
+            by: by.to_string(),
+        });
+        let _ = self.evictions.send(Eviction {
+            tx_id: tx.id,
+            sender: tx.sender,
+            reason: EvictionReason::Replaced,
+        });
+    }
+
+    /// The next nonce `sender`'s account expects, if the validator knows.
+    fn account_nonce(&self, sender: &str) -> Option<u64> {
+        self.validator.as_ref()?.next_nonce(sender)
+    }
+
+    /// The first nonce from `base` on that `sender` has nothing pending
+    /// at: the next transaction the sender's queue can take.
+    fn next_expected_nonce(&self, sender: &str, base: u64) -> u64 {
+        let mut next = base;
+        if let Some(nonces) = self.sender_nonces.get(sender) {
+            for &nonce in nonces.range(base..).map(|(nonce, _)| nonce) {
+                if nonce != next {
+                    break;
+                }
+                next = next.saturating_add(1);
+            }
+        }
+        next
+    }
+
+    /// Buffers `tx` as an orphan, announcing whatever it displaces.
+    fn orphan(&self, tx: Transaction) -> Result<(), MempoolError> {
+        let sender = tx.sender.clone();
+        let tx_id = tx.id.clone();
+        let fee = tx.fee;
+        match self.orphans.insert(tx, current_timestamp_secs()) {
+            Ok(displaced) => {
+                if let Some(tx) = displaced.replaced {
+                    self.announce_replaced(tx, &tx_id);
+                }
+                if let Some(tx) = displaced.dropped {
+                    self.announce(tx, EvictionReason::Orphaned);
+                }
+                Ok(())
+            }
+            Err(OrphanRefusal::Outbid { fee: existing }) => Err(MempoolError::FeeTooLow {
+                min: existing.saturating_add(1),
+                got: fee,
+            }),
+            Err(OrphanRefusal::SenderFull) => Err(MempoolError::SenderLimitExceeded {
+                sender,
+                limit: self.config.max_txs_per_sender,
+            }),
+        }
+    }
+
+    /// Admits `sender`'s orphans, in nonce order, for as long as the next
+    /// one follows on from the sender's pending run above `base`, its
+    /// account nonce. An orphan the pool refuses is dropped and ends the
+    /// run. Returns the number promoted.
+    fn promote(&self, sender: &str, base: u64) -> usize {
+        let mut promoted = 0;
+        loop {
+            let next = self.next_expected_nonce(sender, base);
+            let Some(tx) = self.orphans.take(sender, next) else {
+                break;
+            };
+            match self.admit(tx.clone()) {
+                Ok(_) => promoted += 1,
+                Err(_) => {
+                    self.announce(tx, EvictionReason::Orphaned);
+                    break;
+                }
+            }
+        }
+        promoted
+    }
+
+    /// Removes an entry's metadata from the fee index, sender counts and
+    /// byte total.
     fn remove_from_indices(&self, entry: &MempoolEntry) {
         // Remove from fee index.
         let fee_key = FeeKey {
@@ -424,8 +1536,11 @@ impl Mempool {
         };
         self.fee_index.write().remove(&fee_key);
 
-        // Decrement sender count.
+        // Decrement sender count and nonce.
         self.decrement_sender_count(&entry.transaction.sender);
+        self.decrement_sender_nonce(&entry.transaction.sender, entry.transaction.nonce);
+
+        self.total_bytes.fetch_sub(entry.weight, Ordering::Relaxed);
     }
 
     /// Decrements the sender's pending transaction count, removing the
@@ -440,6 +1555,33 @@ impl Mempool {
             }
         }
     }
+
+    /// Drops one transaction at `nonce` from the sender's pending nonces,
+    /// removing the sender entirely when none are left.
+    fn decrement_sender_nonce(&self, sender: &str, nonce: u64) {
+        if let Some(mut nonces) = self.sender_nonces.get_mut(sender) {
+            if let Some(count) = nonces.get_mut(&nonce) {
+                *count -= 1;
+                if *count == 0 {
+                    nonces.remove(&nonce);
+                }
+            }
+            if nonces.is_empty() {
+                drop(nonces);
+                self.sender_nonces.remove(sender);
+            }
+        }
+    }
+}
+
+/// Where [`Mempool::admit`] put a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    /// Into the pool.
+    Pending,
+
+    /// Into the orphan buffer, behind a nonce gap.
+    Orphaned,
 }
 
 impl Default for Mempool {
@@ -452,6 +1594,14 @@ impl Default for Mempool {
 // Utility
 // ---------------------------------------------------------------------------
 
+/// Spendable balance of `address`, from the overlay if already touched,
+/// otherwise from the state tree.
+fn available_balance(balances: &mut HashMap<String, u64>, state: &StateTree, address: &str) -> u64 {
+    *balances
+        .entry(address.to_string())
+        .or_insert_with(|| state.get(address).map_or(0, |a| a.available_balance()))
+}
+
 /// Returns the current time as seconds since the UNIX epoch.
 fn current_timestamp_secs() -> u64 {
     SystemTime::now()
@@ -460,6 +1610,14 @@ fn current_timestamp_secs() -> u64 {
         .as_secs()
 }
 
+/// Returns the current time as milliseconds since the UNIX epoch.
+fn current_timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before UNIX epoch")
+        .as_millis() as u64
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -467,8 +1625,12 @@ fn current_timestamp_secs() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::NovaKeypair;
+    use crate::identity::NovaId;
+    use crate::storage::{AccountState, NovaDB};
     use crate::transaction::builder::TransactionBuilder;
-    use crate::transaction::types::{Amount, Currency, TransactionType};
+    use crate::transaction::sign_transaction;
+    use crate::transaction::types::{Amount, Currency};
 
     /// Builds a test transaction with the given parameters.
     fn make_tx(sender: &str, receiver: &str, fee: u64, nonce: u64) -> Transaction {
@@ -479,7 +1641,7 @@ mod tests {
             .fee(fee)
             .nonce(nonce)
             .timestamp(1_700_000_000_000 + nonce)
-            .build()
+            .build_unchecked()
     }
 
     /// Builds a test transaction with only a fee differentiator.
@@ -487,6 +1649,14 @@ mod tests {
         make_tx("nova1sender_a", "nova1receiver_b", fee, nonce)
     }
 
+    /// Builds a test transaction of the given type.
+    fn make_typed_tx(tx_type: TransactionType, sender: &str, fee: u64, nonce: u64) -> Transaction {
+        let mut tx = make_tx(sender, "nova1receiver_b", fee, nonce);
+        tx.tx_type = tx_type;
+        tx.id = tx.compute_id();
+        tx
+    }
+
     // -- Basic add / get / contains -----------------------------------------
 
     #[test]
@@ -522,78 +1692,349 @@ mod tests {
 
     // -- Duplicate rejection ------------------------------------------------
 
-    #[test]
-    fn rejects_duplicate_transaction() {
-        let pool = Mempool::default();
-        let tx = make_tx_with_fee(100, 1);
+    #[test]
+    fn rejects_duplicate_transaction() {
+        let pool = Mempool::default();
+        let tx = make_tx_with_fee(100, 1);
+
+        pool.add(tx.clone()).unwrap();
+        let result = pool.add(tx);
+
+        assert!(matches!(result, Err(MempoolError::DuplicateTransaction)));
+        assert_eq!(pool.size(), 1);
+    }
+
+    // -- Size limits --------------------------------------------------------
+
+    #[test]
+    fn rejects_oversized_memo() {
+        let pool = Mempool::default();
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1sender_a")
+            .receiver("nova1receiver_b")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(100)
+            .nonce(1)
+            .payload(vec![0; limits::MAX_MEMO_BYTES + 1])
+            .build_unchecked();
+
+        let result = pool.add(tx);
+        assert!(matches!(
+            result,
+            Err(MempoolError::Oversized(TransactionError::FieldTooLarge {
+                field: "memo",
+                ..
+            }))
+        ));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn rejects_transactions_above_configured_weight() {
+        let tx = make_tx_with_fee(100, 1);
+        let weight = limits::tx_weight(&tx);
+        let pool = Mempool::new(MempoolConfig {
+            max_tx_bytes: weight - 1,
+            ..Default::default()
+        });
+
+        let result = pool.add(tx.clone());
+        assert!(matches!(
+            result,
+            Err(MempoolError::Oversized(TransactionError::FieldTooLarge {
+                field: "transaction",
+                size,
+                ..
+            })) if size == weight
+        ));
+
+        let pool = Mempool::new(MempoolConfig {
+            max_tx_bytes: weight,
+            ..Default::default()
+        });
+        pool.add(tx).unwrap();
+    }
+
+    #[test]
+    fn strict_policy_rejects_malformed_addresses() {
+        let config = MempoolConfig {
+            address_policy: AddressPolicy::Strict,
+            ..Default::default()
+        };
+        let pool = Mempool::new(config);
+
+        let result = pool.add(make_tx_with_fee(100, 1));
+        assert!(matches!(result, Err(MempoolError::InvalidAddress(_))));
+
+        let real = |seed: u8| {
+            let kp = crate::crypto::keys::NovaKeypair::from_seed(&[seed; 32]);
+            crate::identity::NovaId::from_public_key(&kp.public_key()).to_address()
+        };
+        pool.add(make_tx(&real(1), &real(2), 100, 1)).unwrap();
+        assert_eq!(pool.size(), 1);
+    }
+
+    // -- Fee too low --------------------------------------------------------
+
+    #[test]
+    fn rejects_fee_too_low() {
+        let config = MempoolConfig {
+            min_fee: 500,
+            ..Default::default()
+        };
+        let pool = Mempool::new(config);
+        let tx = make_tx_with_fee(100, 1);
+
+        let result = pool.add(tx);
+        assert!(matches!(
+            result,
+            Err(MempoolError::FeeTooLow { min: 500, got: 100 })
+        ));
+        assert_eq!(pool.size(), 0);
+    }
+
+    #[test]
+    fn accepts_fee_at_minimum() {
+        let config = MempoolConfig {
+            min_fee: 100,
+            ..Default::default()
+        };
+        let pool = Mempool::new(config);
+        let tx = make_tx_with_fee(100, 1);
+
+        assert!(pool.add(tx).is_ok());
+        assert_eq!(pool.size(), 1);
+    }
+
+    #[test]
+    fn encrypted_payload_bytes_raise_the_minimum_fee() {
+        let config = MempoolConfig {
+            min_fee: 100,
+            encrypted_byte_fee: 2,
+            ..Default::default()
+        };
+        let pool = Mempool::new(config);
+        let mut envelope = crate::transaction::envelope::ENVELOPE_MAGIC.to_vec();
+        envelope.resize(150, 0);
+        let tx = |fee: u64, nonce: u64, payload: Vec<u8>| {
+            TransactionBuilder::new(TransactionType::Transfer)
+                .sender("nova1sender_a")
+                .receiver("nova1receiver_b")
+                .amount(Amount::new(1_000, Currency::NOVA))
+                .fee(fee)
+                .nonce(nonce)
+                .payload(payload)
+                .build_unchecked()
+        };
+
+        assert!(matches!(
+            pool.add(tx(399, 1, envelope.clone())),
+            Err(MempoolError::FeeTooLow { min: 400, got: 399 })
+        ));
+        assert!(pool.add(tx(400, 1, envelope)).is_ok());
+        // Plain memos only pay the flat minimum.
+        assert!(pool.add(tx(100, 2, vec![0; 150])).is_ok());
+    }
+
+    // -- Sender limit -------------------------------------------------------
+
+    #[test]
+    fn enforces_sender_limit() {
+        let config = MempoolConfig {
+            max_txs_per_sender: 3,
+            ..Default::default()
+        };
+        let pool = Mempool::new(config);
+
+        for nonce in 1..=3 {
+            let tx = make_tx("nova1alice", "nova1bob", 100, nonce);
+            pool.add(tx).unwrap();
+        }
+
+        let tx4 = make_tx("nova1alice", "nova1bob", 100, 4);
+        let result = pool.add(tx4);
+        assert!(matches!(
+            result,
+            Err(MempoolError::SenderLimitExceeded { limit: 3, .. })
+        ));
+        assert_eq!(pool.size(), 3);
+    }
+
+    #[test]
+    fn sender_at_limit_replaces_without_a_gap() {
+        let config = MempoolConfig {
+            max_txs_per_sender: 2,
+            ..Default::default()
+        };
+        let pool = Mempool::new(config);
+        let mut evictions = pool.subscribe_evictions();
+
+        let cheap = make_tx("nova1alice", "nova1bob", 10_000, 1);
+        let cheap_id = cheap.id.clone();
+        pool.add(cheap).unwrap();
+        pool.add(make_tx("nova1alice", "nova1bob", 30_000, 2))
+            .unwrap();
+        pool.add(make_tx("nova1bob", "nova1alice", 5_000, 1))
+            .unwrap();
 
-        pool.add(tx.clone()).unwrap();
-        let result = pool.add(tx);
+        // A later nonce would need an earlier one gone, whatever it pays.
+        assert!(matches!(
+            pool.add(make_tx("nova1alice", "nova1bob", 50_000, 3)),
+            Err(MempoolError::SenderLimitExceeded { limit: 2, .. })
+        ));
+        assert!(pool.contains(&cheap_id));
+
+        // The same nonce paying more replaces it. Bob's cheaper
+        // transaction is not Alice's to displace.
+        let better = make_tx("nova1alice", "nova1bob", 20_000, 1);
+        let better_id = better.id.clone();
+        pool.add(better).unwrap();
+        assert!(!pool.contains(&cheap_id));
+        assert!(pool.contains(&better_id));
+        assert_eq!(pool.size(), 3);
+        let eviction = evictions.try_recv().unwrap();
+        assert_eq!(eviction.tx_id, cheap_id);
+        assert_eq!(eviction.reason, EvictionReason::Replaced);
 
-        assert!(matches!(result, Err(MempoolError::DuplicateTransaction)));
-        assert_eq!(pool.size(), 1);
+        // Paying less than the transaction at that nonce is not enough.
+        assert!(matches!(
+            pool.add(make_tx("nova1alice", "nova1bob", 25_000, 2)),
+            Err(MempoolError::SenderLimitExceeded { limit: 2, .. })
+        ));
+        assert_eq!(pool.size(), 3);
     }
 
-    // -- Fee too low --------------------------------------------------------
-
     #[test]
-    fn rejects_fee_too_low() {
+    fn sender_at_limit_fills_a_gap_with_its_highest_nonce() {
         let config = MempoolConfig {
-            min_fee: 500,
+            max_txs_per_sender: 2,
             ..Default::default()
         };
         let pool = Mempool::new(config);
-        let tx = make_tx_with_fee(100, 1);
 
-        let result = pool.add(tx);
-        assert!(matches!(
-            result,
-            Err(MempoolError::FeeTooLow { min: 500, got: 100 })
-        ));
-        assert_eq!(pool.size(), 0);
+        pool.add(make_tx("nova1alice", "nova1bob", 10_000, 1))
+            .unwrap();
+        let parked = make_tx("nova1alice", "nova1bob", 10_000, 3);
+        let parked_id = parked.id.clone();
+        pool.add(parked).unwrap();
+
+        let filler = make_tx("nova1alice", "nova1bob", 20_000, 2);
+        let filler_id = filler.id.clone();
+        pool.add(filler).unwrap();
+        assert!(!pool.contains(&parked_id));
+        assert!(pool.contains(&filler_id));
+        assert_eq!(pool.pending_for_sender("nova1alice").len(), 2);
     }
 
     #[test]
-    fn accepts_fee_at_minimum() {
+    fn replacement_that_does_not_fit_keeps_the_original() {
+        let original = make_tx("nova1alice", "nova1bob", 10_000, 1);
         let config = MempoolConfig {
-            min_fee: 100,
+            max_txs_per_sender: 1,
+            max_bytes: limits::tx_weight(&original) + 10,
             ..Default::default()
         };
         let pool = Mempool::new(config);
-        let tx = make_tx_with_fee(100, 1);
+        let mut evictions = pool.subscribe_evictions();
+        pool.add(original.clone()).unwrap();
 
-        assert!(pool.add(tx).is_ok());
+        // Outbids the original, but is too heavy to fit even in its place.
+        let mut heavy = make_tx("nova1alice", "nova1bob", 1_000_000, 1);
+        heavy.payload = Some(vec![0; 500]);
+        heavy.id = heavy.compute_id();
+        assert!(matches!(
+            pool.add(heavy),
+            Err(MempoolError::MemoryFull { .. })
+        ));
+        assert!(pool.contains(&original.id));
         assert_eq!(pool.size(), 1);
+        assert!(evictions.try_recv().is_err());
     }
 
-    // -- Sender limit -------------------------------------------------------
-
     #[test]
-    fn enforces_sender_limit() {
+    fn events_follow_every_change() {
         let config = MempoolConfig {
-            max_per_sender: 3,
+            max_txs_per_sender: 1,
             ..Default::default()
         };
         let pool = Mempool::new(config);
+        let mut events = pool.subscribe();
+
+        let cheap = make_tx("nova1alice", "nova1bob", 10_000, 1);
+        let cheap_id = cheap.id.clone();
+        pool.add(cheap.clone()).unwrap();
+        assert_eq!(
+            events.try_recv().unwrap(),
+            MempoolEvent::Added {
+                tx_id: cheap_id.clone(),
+                sender: "nova1alice".into(),
+                fee_per_byte: cheap.fee_per_byte(),
+            }
+        );
 
-        for nonce in 1..=3 {
-            let tx = make_tx("nova1alice", "nova1bob", 100, nonce);
-            pool.add(tx).unwrap();
+        // Alice is at her limit, so a better-paying transaction at the same
+        // nonce replaces her first one before it is added.
+        let better = make_tx("nova1alice", "nova1bob", 20_000, 1);
+        let better_id = better.id.clone();
+        pool.add(better).unwrap();
+        assert_eq!(
+            events.try_recv().unwrap(),
+            MempoolEvent::Replaced {
+                tx_id: cheap_id,
+                sender: "nova1alice".into(),
+                by: better_id.clone(),
+            }
+        );
+        assert_eq!(events.try_recv().unwrap().tx_id(), better_id);
+
+        let now = current_timestamp_ms();
+        let mut other = make_tx("nova1bob", "nova1alice", 5_000, 1);
+        other.valid_until = Some(now + 1_000);
+        other.id = other.compute_id();
+        let other_id = other.id.clone();
+        pool.add(other).unwrap();
+        assert_eq!(events.try_recv().unwrap().kind(), "added");
+
+        pool.remove(&better_id).unwrap();
+        assert_eq!(
+            events.try_recv().unwrap(),
+            MempoolEvent::Removed {
+                tx_id: better_id,
+                sender: "nova1alice".into(),
+            }
+        );
+
+        assert_eq!(pool.evict_expired(now + 2_000), 1);
+        match events.try_recv().unwrap() {
+            MempoolEvent::Evicted(eviction) => {
+                assert_eq!(eviction.tx_id, other_id);
+                assert_eq!(eviction.reason, EvictionReason::Expired);
+            }
+            other => panic!("expected an eviction, got {other:?}"),
         }
+        assert!(events.try_recv().is_err());
+    }
 
-        let tx4 = make_tx("nova1alice", "nova1bob", 100, 4);
-        let result = pool.add(tx4);
-        assert!(matches!(
-            result,
-            Err(MempoolError::SenderLimitExceeded { limit: 3, .. })
-        ));
-        assert_eq!(pool.size(), 3);
+    #[test]
+    fn clear_reports_pending_transactions_as_removed() {
+        let pool = Mempool::new(MempoolConfig::default());
+        pool.add(make_tx("nova1alice", "nova1bob", 10_000, 1))
+            .unwrap();
+        pool.add(make_tx("nova1bob", "nova1alice", 10_000, 1))
+            .unwrap();
+        let mut events = pool.subscribe();
+
+        pool.clear();
+        let kinds: Vec<&str> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|event| event.kind())
+            .collect();
+        assert_eq!(kinds, ["removed", "removed"]);
     }
 
     #[test]
     fn sender_limit_is_per_sender() {
         let config = MempoolConfig {
-            max_per_sender: 2,
+            max_txs_per_sender: 2,
             ..Default::default()
         };
         let pool = Mempool::new(config);
@@ -607,6 +2048,77 @@ mod tests {
         assert_eq!(pool.size(), 3);
     }
 
+    // -- Screening ----------------------------------------------------------
+
+    #[test]
+    fn screener_refuses_listed_addresses() {
+        use crate::network::screening::{ScreeningAction, StaticListProvider};
+
+        let list = StaticListProvider::new("sdn", [("nova1mallory", "SDN-7")]);
+        let screener = Screener::new(Arc::new(list), ScreeningAction::Block);
+        let pool = Mempool::new(MempoolConfig::default()).with_screener(Arc::new(screener));
+
+        pool.add(make_tx("nova1alice", "nova1bob", 100, 1)).unwrap();
+        let result = pool.add(make_tx("nova1alice", "nova1mallory", 100, 2));
+        match result {
+            Err(MempoolError::Screened(r)) => {
+                assert_eq!(r.address, "nova1mallory");
+                assert_eq!(r.role, "receiver");
+            }
+            other => panic!("expected Screened, got {:?}", other),
+        }
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.pending_for_sender("nova1alice").len(), 1);
+    }
+
+    // -- State validation ---------------------------------------------------
+
+    #[test]
+    fn state_validator_refuses_unexecutable_transactions() {
+        let db = NovaDB::open_temporary().unwrap();
+        let mut tree = StateTree::new(db.clone());
+        let mut alice = AccountState::with_balance(1_500);
+        alice.nonce = 2;
+        tree.put("nova1alice", &alice);
+        let mut frozen = AccountState::with_balance(10_000);
+        frozen.frozen = true;
+        tree.put("nova1frozen", &frozen);
+        let committed = CommittedState::new(db, 0, tree.root());
+        let pool = Mempool::default().with_validator(Arc::new(StateValidator::new(committed)));
+
+        // 1_000 + 100 out of 1_500, at the next nonce.
+        pool.add(make_tx("nova1alice", "nova1bob", 100, 3)).unwrap();
+
+        match pool.add(make_tx("nova1alice", "nova1bob", 100, 2)) {
+            Err(MempoolError::Unexecutable(AdmissionRejection::NonceUsed {
+                nonce: 2,
+                account_nonce: 2,
+            })) => {}
+            other => panic!("expected NonceUsed, got {:?}", other),
+        }
+        match pool.add(make_tx("nova1alice", "nova1bob", 600, 4)) {
+            Err(MempoolError::Unexecutable(AdmissionRejection::InsufficientBalance {
+                need: 1_600,
+                have: 1_500,
+                ..
+            })) => {}
+            other => panic!("expected InsufficientBalance, got {:?}", other),
+        }
+        assert!(matches!(
+            pool.add(make_tx("nova1frozen", "nova1bob", 100, 1)),
+            Err(MempoolError::Unexecutable(
+                AdmissionRejection::Frozen { .. }
+            ))
+        ));
+        assert!(matches!(
+            pool.add(make_tx("nova1carol", "nova1bob", 100, 1)),
+            Err(MempoolError::Unexecutable(
+                AdmissionRejection::InsufficientBalance { have: 0, .. }
+            ))
+        ));
+        assert_eq!(pool.size(), 1);
+    }
+
     // -- Mempool full / eviction --------------------------------------------
 
     #[test]
@@ -656,6 +2168,65 @@ mod tests {
         assert!(matches!(result, Err(MempoolError::MempoolFull { size: 2 })));
     }
 
+    #[test]
+    fn byte_budget_evicts_cheapest_and_announces_it() {
+        let tx_low = make_tx("nova1a", "nova1b", 10, 1);
+        let weight = limits::tx_weight(&tx_low);
+        let pool = Mempool::new(MempoolConfig {
+            max_bytes: 3 * weight,
+            ..Default::default()
+        });
+        let mut evictions = pool.subscribe_evictions();
+
+        pool.add(tx_low.clone()).unwrap();
+        pool.add(make_tx("nova1c", "nova1d", 500, 2)).unwrap();
+        pool.add(make_tx("nova1e", "nova1f", 1_000, 3)).unwrap();
+        assert_eq!(pool.total_bytes(), 3 * weight);
+        assert_eq!(pool.entry(&tx_low.id).unwrap().weight, weight);
+
+        // Under the count limit but over the byte budget: the cheapest goes.
+        pool.add(make_tx("nova1g", "nova1h", 5_000, 4)).unwrap();
+        assert_eq!(pool.size(), 3);
+        assert_eq!(pool.total_bytes(), 3 * weight);
+        assert!(!pool.contains(&tx_low.id));
+        assert_eq!(
+            evictions.try_recv().unwrap(),
+            Eviction {
+                tx_id: tx_low.id,
+                sender: "nova1a".into(),
+                reason: EvictionReason::Outbid,
+            }
+        );
+
+        pool.clear();
+        assert_eq!(pool.total_bytes(), 0);
+        assert!(evictions.try_recv().is_err());
+    }
+
+    #[test]
+    fn byte_budget_rejects_without_evicting_when_not_outbid() {
+        let tx = make_tx("nova1a", "nova1b", 1_000, 1);
+        let weight = limits::tx_weight(&tx);
+        let pool = Mempool::new(MempoolConfig {
+            max_bytes: 2 * weight,
+            ..Default::default()
+        });
+        let mut evictions = pool.subscribe_evictions();
+
+        pool.add(tx).unwrap();
+        pool.add(make_tx("nova1c", "nova1d", 2_000, 2)).unwrap();
+
+        // Room is short by bytes, not by count, and nothing is cheaper.
+        let result = pool.add(make_tx("nova1e", "nova1f", 500, 3));
+        assert!(matches!(
+            result,
+            Err(MempoolError::MemoryFull { bytes }) if bytes == 2 * weight
+        ));
+        assert_eq!(pool.size(), 2);
+        assert_eq!(pool.total_bytes(), 2 * weight);
+        assert!(evictions.try_recv().is_err());
+    }
+
     // -- select_transactions ------------------------------------------------
 
     #[test]
@@ -697,6 +2268,169 @@ mod tests {
         assert!(selected.is_empty());
     }
 
+    #[test]
+    fn entries_page_through_the_whole_pool_by_fee() {
+        let pool = Mempool::default();
+        // Alice's nonce 3 is parked behind a gap but still listed.
+        let a1 = make_tx("nova1alice", "nova1bob", 20_000, 1);
+        let a3 = make_tx("nova1alice", "nova1bob", 90_000, 3);
+        let c1 = make_tx("nova1carol", "nova1bob", 50_000, 1);
+        for tx in [&a1, &a3, &c1] {
+            pool.add(tx.clone()).unwrap();
+        }
+
+        let first = pool.entries(0, 2);
+        let ids: Vec<&str> = first.iter().map(|e| e.transaction.id.as_str()).collect();
+        assert_eq!(ids, vec![a3.id.as_str(), c1.id.as_str()]);
+        let rest = pool.entries(2, 2);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].transaction.id, a1.id);
+        assert!(pool.entries(3, 2).is_empty());
+
+        let entry = pool.entry(&c1.id).unwrap();
+        assert_eq!(entry.fee_per_byte, c1.fee_per_byte());
+        assert!(entry.age_secs() < 60);
+        assert!(pool.entry("missing").is_none());
+    }
+
+    #[test]
+    fn select_transactions_follows_each_senders_nonces() {
+        let pool = Mempool::default();
+        let ids = |txs: &[Transaction]| txs.iter().map(|tx| tx.id.clone()).collect::<Vec<_>>();
+
+        // Alice's follow-ups outbid her first transaction and Bob's.
+        let a1 = make_tx("nova1alice", "nova1bob", 1_000, 1);
+        let a2 = make_tx("nova1alice", "nova1bob", 50_000, 2);
+        let a3 = make_tx("nova1alice", "nova1bob", 40_000, 3);
+        let b1 = make_tx("nova1bob", "nova1alice", 20_000, 1);
+        // Behind a gap: Alice has nothing at nonce 4.
+        let a5 = make_tx("nova1alice", "nova1bob", 90_000, 5);
+        for tx in [&a5, &a3, &a2, &b1, &a1] {
+            pool.add(tx.clone()).unwrap();
+        }
+
+        let selected = pool.select_transactions(10);
+        assert_eq!(
+            ids(&selected),
+            ids(&[b1.clone(), a1.clone(), a2.clone(), a3.clone()])
+        );
+        assert_eq!(ids(&pool.select_transactions(2)), ids(&[b1, a1]));
+
+        // The parked transaction is still pending, and goes once the gap fills.
+        assert!(pool.contains(&a5.id));
+        let a4 = make_tx("nova1alice", "nova1bob", 1_000, 4);
+        pool.add(a4.clone()).unwrap();
+        let selected = pool.select_transactions(10);
+        assert_eq!(ids(&selected[3..]), ids(&[a3, a4, a5]));
+    }
+
+    #[test]
+    fn select_executable_starts_at_the_account_nonce() {
+        let pool = Mempool::default();
+        let mut state = funded_state("nova1alice", 10_000);
+        let mut alice = state.get("nova1alice").unwrap();
+        alice.nonce = 4;
+        state.put("nova1alice", &alice);
+
+        let a6 = make_tx("nova1alice", "nova1bob", 1_000, 6);
+        let a7 = make_tx("nova1alice", "nova1bob", 1_000, 7);
+        let c1 = make_tx("nova1carol", "nova1bob", 1_000, 1);
+        for tx in [&a6, &a7, &c1] {
+            pool.add(tx.clone()).unwrap();
+        }
+
+        // Alice's next nonce is 5, so 6 and 7 wait; Carol has no account
+        // yet and starts at 1.
+        let selected = pool.select_executable(&state, 10);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].id, c1.id);
+        // The pool alone cannot tell, and starts Alice's queue at 6.
+        assert_eq!(pool.select_transactions(10).len(), 3);
+
+        let a5 = make_tx("nova1alice", "nova1bob", 1_000, 5);
+        pool.add(a5.clone()).unwrap();
+        let selected = pool.select_executable(&state, 10);
+        let nonces: Vec<u64> = selected
+            .iter()
+            .filter(|tx| tx.sender == "nova1alice")
+            .map(|tx| tx.nonce)
+            .collect();
+        assert_eq!(nonces, vec![5, 6, 7]);
+    }
+
+    // -- Lanes --------------------------------------------------------------
+
+    #[test]
+    fn lane_reserves_round_up_to_a_slot() {
+        let lane = Lane::validator();
+        assert_eq!(lane.reserved_slots(0), 0);
+        assert_eq!(lane.reserved_slots(1), 1);
+        assert_eq!(lane.reserved_slots(100), 5);
+        assert_eq!(lane.reserved_slots(101), 6);
+        assert!(lane.carries(TransactionType::Unjail));
+        assert!(lane.carries(TransactionType::NetworkKeyRotation));
+        assert!(lane.carries(TransactionType::TreasurySpend));
+        assert!(!lane.carries(TransactionType::Transfer));
+        assert!(!lane.carries(TransactionType::SpendingPolicyUpdate));
+    }
+
+    #[test]
+    fn lanes_get_their_share_regardless_of_fee() {
+        let fill = |pool: &Mempool| {
+            for i in 0..10 {
+                pool.add(make_tx(&format!("nova1user{i}"), "nova1bob", 50_000, 1))
+                    .unwrap();
+            }
+            let unjail = make_typed_tx(TransactionType::Unjail, "nova1val", 10, 1);
+            let settle = make_typed_tx(TransactionType::CreditSettlement, "nova1debtor", 10, 1);
+            pool.add(unjail.clone()).unwrap();
+            pool.add(settle.clone()).unwrap();
+            (unjail.id, settle.id)
+        };
+
+        // The validator lane's slot goes to the unjail; settlements have no
+        // lane and compete on fee like any transfer.
+        let pool = Mempool::default();
+        let (unjail_id, settle_id) = fill(&pool);
+        let selected = pool.select_transactions(10);
+        assert_eq!(selected.len(), 10);
+        assert_eq!(selected[0].id, unjail_id);
+        assert!(selected[1..].iter().all(|tx| tx.fee == 50_000));
+        assert!(selected.iter().all(|tx| tx.id != settle_id));
+
+        // Without lanes, the cheap housekeeping waits behind the spam.
+        let pool = Mempool::new(MempoolConfig {
+            lanes: Vec::new(),
+            ..Default::default()
+        });
+        let (unjail_id, _) = fill(&pool);
+        let selected = pool.select_transactions(10);
+        assert!(selected.iter().all(|tx| tx.id != unjail_id));
+    }
+
+    #[test]
+    fn lanes_respect_nonce_order_and_share_unused_slots() {
+        let pool = Mempool::default();
+        let transfer = make_tx("nova1val", "nova1bob", 10, 1);
+        let unjail = make_typed_tx(TransactionType::Unjail, "nova1val", 10, 2);
+        pool.add(transfer.clone()).unwrap();
+        pool.add(unjail.clone()).unwrap();
+        for i in 0..3 {
+            pool.add(make_tx(&format!("nova1user{i}"), "nova1bob", 50_000, 1))
+                .unwrap();
+        }
+
+        // The unjail cannot run ahead of the transfer before it, so the
+        // lane's slot goes to the best-paying transactions instead.
+        let selected = pool.select_transactions(3);
+        assert_eq!(selected.len(), 3);
+        assert!(selected.iter().all(|tx| tx.fee == 50_000));
+
+        let selected = pool.select_transactions(5);
+        let position = |id: &str| selected.iter().position(|tx| tx.id == id).unwrap();
+        assert!(position(&transfer.id) < position(&unjail.id));
+    }
+
     // -- remove / remove_batch ----------------------------------------------
 
     #[test]
@@ -791,6 +2525,49 @@ mod tests {
         assert_eq!(pool.size(), 2);
     }
 
+    // -- evict_expired ------------------------------------------------------
+
+    #[test]
+    fn rejects_already_expired_transaction() {
+        let pool = Mempool::new(MempoolConfig::default());
+        let mut tx = make_tx_with_fee(100, 1);
+        tx.valid_until = Some(current_timestamp_ms() - 1_000);
+        tx.id = tx.compute_id();
+
+        match pool.add(tx) {
+            Err(MempoolError::Expired { .. }) => {}
+            other => panic!("expected Expired, got {:?}", other),
+        }
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn evict_expired_removes_only_lapsed_transactions() {
+        let pool = Mempool::new(MempoolConfig::default());
+        let now = current_timestamp_ms();
+
+        let mut short = make_tx_with_fee(100, 1);
+        short.valid_until = Some(now + 1_000);
+        short.id = short.compute_id();
+        let mut long = make_tx_with_fee(100, 2);
+        long.valid_until = Some(now + 60_000);
+        long.id = long.compute_id();
+        pool.add(short.clone()).unwrap();
+        pool.add(long.clone()).unwrap();
+        pool.add(make_tx_with_fee(100, 3)).unwrap();
+        let mut evictions = pool.subscribe_evictions();
+
+        assert_eq!(pool.evict_expired(now), 0);
+        assert_eq!(pool.evict_expired(now + 2_000), 1);
+        assert!(!pool.contains(&short.id));
+        let eviction = evictions.try_recv().unwrap();
+        assert_eq!(eviction.tx_id, short.id);
+        assert_eq!(eviction.reason, EvictionReason::Expired);
+        assert!(pool.contains(&long.id));
+        assert_eq!(pool.size(), 2);
+        assert_eq!(pool.pending_for_sender(&long.sender).len(), 2);
+    }
+
     // -- pending_for_sender -------------------------------------------------
 
     #[test]
@@ -856,9 +2633,12 @@ mod tests {
     fn default_config_values() {
         let config = MempoolConfig::default();
         assert_eq!(config.max_size, 10_000);
-        assert_eq!(config.max_per_sender, 100);
+        assert_eq!(config.max_txs_per_sender, 100);
         assert_eq!(config.expiry_seconds, 3600);
         assert_eq!(config.min_fee, 0);
+        assert_eq!(config.encrypted_byte_fee, 0);
+        assert_eq!(config.address_policy, AddressPolicy::Permissive);
+        assert_eq!(config.lanes, [Lane::validator()]);
     }
 
     #[test]
@@ -920,7 +2700,7 @@ mod tests {
     #[test]
     fn sender_count_decrements_on_remove() {
         let config = MempoolConfig {
-            max_per_sender: 2,
+            max_txs_per_sender: 2,
             ..Default::default()
         };
         let pool = Mempool::new(config);
@@ -932,8 +2712,8 @@ mod tests {
         pool.add(tx1).unwrap();
         pool.add(tx2).unwrap();
 
-        // Alice is at limit.
-        let tx3 = make_tx("nova1alice", "nova1bob", 300, 3);
+        // Alice is at limit, and tx3 has nothing it may replace.
+        let tx3 = make_tx("nova1alice", "nova1bob", 100, 3);
         assert!(matches!(
             pool.add(tx3),
             Err(MempoolError::SenderLimitExceeded { .. })
@@ -971,4 +2751,323 @@ mod tests {
         assert_eq!(selected[0].id, tx3.id);
         assert_eq!(selected[1].id, tx1.id);
     }
+
+    // -- Reorg re-admission -------------------------------------------------
+
+    fn address_of(kp: &NovaKeypair) -> String {
+        NovaId::from_public_key(&kp.public_key()).to_address()
+    }
+
+    fn signed_transfer(kp: &NovaKeypair, receiver: &str, amount: u64, nonce: u64) -> Transaction {
+        let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender(&address_of(kp))
+            .receiver(receiver)
+            .amount(Amount::new(amount, Currency::NOVA))
+            .fee(10)
+            .nonce(nonce)
+            .build_unchecked();
+        sign_transaction(&mut tx, kp);
+        tx
+    }
+
+    fn funded_state(address: &str, balance: u64) -> StateTree {
+        let mut state = StateTree::new(NovaDB::open_temporary().unwrap());
+        state.put(address, &AccountState::with_balance(balance));
+        state
+    }
+
+    #[test]
+    fn readmit_reorged_returns_valid_transactions_to_pool() {
+        let alice = NovaKeypair::generate();
+        let bob = address_of(&NovaKeypair::generate());
+        let state = funded_state(&address_of(&alice), 5_000);
+
+        let tx_a = signed_transfer(&alice, &bob, 1_000, 1);
+        let tx_b = signed_transfer(&alice, &bob, 1_000, 2);
+        let block = Block::new(
+            &Block::genesis(),
+            vec![tx_a.clone(), tx_b.clone()],
+            "v".into(),
+            [0; 32],
+        );
+        let canonical: HashSet<String> = [tx_b.id.clone()].into_iter().collect();
+
+        let pool = Mempool::default();
+        let results = pool.readmit_reorged(&[block], &canonical, &state);
+
+        assert_eq!(
+            results,
+            vec![
+                ReorgedTransaction {
+                    tx_id: tx_a.id.clone(),
+                    from_height: 1,
+                    outcome: ReorgOutcome::Readmitted,
+                },
+                ReorgedTransaction {
+                    tx_id: tx_b.id.clone(),
+                    from_height: 1,
+                    outcome: ReorgOutcome::AlreadyIncluded,
+                },
+            ]
+        );
+        assert!(pool.contains(&tx_a.id));
+        assert!(!pool.contains(&tx_b.id));
+    }
+
+    #[test]
+    fn readmit_reorged_drops_invalid_and_overdrawn_transactions() {
+        let alice = NovaKeypair::generate();
+        let bob = address_of(&NovaKeypair::generate());
+        let state = funded_state(&address_of(&alice), 1_500);
+
+        let first = signed_transfer(&alice, &bob, 1_000, 1);
+        // Valid alone, but the first transfer already spends the balance.
+        let second = signed_transfer(&alice, &bob, 1_000, 2);
+        let mut forged = signed_transfer(&alice, &bob, 100, 3);
+        forged.signature = None;
+
+        let block = Block::new(
+            &Block::genesis(),
+            vec![first.clone(), second.clone(), forged.clone()],
+            "v".into(),
+            [0; 32],
+        );
+
+        let pool = Mempool::default();
+        let results = pool.readmit_reorged(&[block], &HashSet::new(), &state);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].outcome, ReorgOutcome::Readmitted);
+        assert!(
+            matches!(&results[1].outcome, ReorgOutcome::Dropped(r) if r.contains("insufficient"))
+        );
+        assert!(matches!(results[2].outcome, ReorgOutcome::Dropped(_)));
+        assert_eq!(pool.size(), 1);
+        assert!(pool.contains(&first.id));
+    }
+
+    #[test]
+    fn readmit_reorged_drops_consumed_nonces() {
+        let alice = NovaKeypair::generate();
+        let bob = address_of(&NovaKeypair::generate());
+        let mut state = funded_state(&address_of(&alice), 5_000);
+        // The new branch already executed one of Alice's transactions.
+        let mut account = state.get(&address_of(&alice)).unwrap();
+        account.nonce = 1;
+        state.put(&address_of(&alice), &account);
+
+        let stale = signed_transfer(&alice, &bob, 1_000, 1);
+        let fresh = signed_transfer(&alice, &bob, 1_000, 2);
+        let block = Block::new(
+            &Block::genesis(),
+            vec![stale.clone(), fresh.clone()],
+            "v".into(),
+            [0; 32],
+        );
+
+        let pool = Mempool::default();
+        let results = pool.readmit_reorged(&[block], &HashSet::new(), &state);
+
+        assert!(matches!(&results[0].outcome, ReorgOutcome::Dropped(r) if r.contains("nonce")));
+        assert_eq!(results[1].outcome, ReorgOutcome::Readmitted);
+        assert!(!pool.contains(&stale.id));
+        assert!(pool.contains(&fresh.id));
+    }
+
+    // -- Orphans ------------------------------------------------------------
+
+    /// A pool validating against `tree`'s root, with each of `funded` given
+    /// 100_000 photons.
+    fn orphaning_pool(
+        config: MempoolConfig,
+        funded: &[&str],
+    ) -> (Mempool, StateTree, Arc<CommittedState>) {
+        let db = NovaDB::open_temporary().unwrap();
+        let mut tree = StateTree::new(db.clone());
+        for address in funded {
+            tree.put(address, &AccountState::with_balance(100_000));
+        }
+        let committed = CommittedState::new(db, 0, tree.root());
+        let pool = Mempool::new(config)
+            .with_validator(Arc::new(StateValidator::new(Arc::clone(&committed))));
+        (pool, tree, committed)
+    }
+
+    #[test]
+    fn gapped_transactions_wait_until_the_gap_fills() {
+        let (pool, _, _) = orphaning_pool(MempoolConfig::default(), &["nova1alice"]);
+        let mut evictions = pool.subscribe_evictions();
+
+        let third = make_tx("nova1alice", "nova1bob", 100, 3);
+        let second = make_tx("nova1alice", "nova1bob", 100, 2);
+        pool.add(third.clone()).unwrap();
+        pool.add(second.clone()).unwrap();
+        assert!(pool.is_orphan(&third.id));
+        assert!(pool.is_orphan(&second.id));
+        assert_eq!(pool.size(), 0);
+        assert_eq!(pool.orphan_count(), 2);
+        assert!(matches!(
+            pool.add(third.clone()),
+            Err(MempoolError::DuplicateTransaction)
+        ));
+
+        // Nonce 1 closes the gap and both follow it in.
+        pool.add(make_tx("nova1alice", "nova1bob", 100, 1)).unwrap();
+        assert_eq!(pool.size(), 3);
+        assert_eq!(pool.orphan_count(), 0);
+        let nonces: Vec<u64> = pool
+            .select_transactions(10)
+            .iter()
+            .map(|tx| tx.nonce)
+            .collect();
+        assert_eq!(nonces, [1, 2, 3]);
+        assert!(evictions.try_recv().is_err());
+    }
+
+    #[test]
+    fn committed_nonces_promote_and_drop_orphans() {
+        let (pool, mut tree, committed) = orphaning_pool(MempoolConfig::default(), &["nova1alice"]);
+        let mut evictions = pool.subscribe_evictions();
+
+        let second = make_tx("nova1alice", "nova1bob", 100, 2);
+        let third = make_tx("nova1alice", "nova1bob", 100, 3);
+        let fifth = make_tx("nova1alice", "nova1bob", 100, 5);
+        for tx in [&second, &third, &fifth] {
+            pool.add(tx.clone()).unwrap();
+        }
+        assert_eq!(pool.promote_orphans(), 0);
+
+        // Another producer's block uses nonces 1 and 2.
+        let mut alice = AccountState::with_balance(100_000);
+        alice.nonce = 2;
+        tree.put("nova1alice", &alice);
+        committed.commit(1, tree.root());
+
+        assert_eq!(pool.promote_orphans(), 1);
+        assert!(pool.contains(&third.id));
+        assert!(pool.is_orphan(&fifth.id));
+        assert!(!pool.is_orphan(&second.id));
+        let eviction = evictions.try_recv().unwrap();
+        assert_eq!(eviction.tx_id, second.id);
+        assert_eq!(eviction.reason, EvictionReason::Orphaned);
+
+        // Cancellations look in the buffer too: this one finds the orphan
+        // but is not signed by its sender.
+        let stranger = NovaKeypair::generate();
+        let cancellation = TransactionCancellation::sign(&fifth.id, &stranger);
+        assert!(matches!(
+            pool.cancel(&fifth.id, &cancellation),
+            Err(MempoolError::InvalidCancellation(_))
+        ));
+        assert!(pool.is_orphan(&fifth.id));
+    }
+
+    #[test]
+    fn orphan_buffer_is_bounded() {
+        let config = MempoolConfig {
+            max_orphans: 2,
+            max_txs_per_sender: 1,
+            ..Default::default()
+        };
+        let (pool, _, _) = orphaning_pool(config, &["nova1a", "nova1c", "nova1e"]);
+        let mut evictions = pool.subscribe_evictions();
+
+        let oldest = make_tx("nova1a", "nova1b", 100, 3);
+        pool.add(oldest.clone()).unwrap();
+        assert!(matches!(
+            pool.add(make_tx("nova1a", "nova1b", 100, 4)),
+            Err(MempoolError::SenderLimitExceeded { limit: 1, .. })
+        ));
+        // Same slot: only a higher fee replaces it.
+        assert!(matches!(
+            pool.add(make_tx("nova1a", "nova1b", 50, 3)),
+            Err(MempoolError::FeeTooLow { min: 101, got: 50 })
+        ));
+
+        pool.add(make_tx("nova1c", "nova1d", 100, 2)).unwrap();
+        pool.add(make_tx("nova1e", "nova1f", 100, 2)).unwrap();
+        assert_eq!(pool.orphan_count(), 2);
+        assert!(!pool.is_orphan(&oldest.id));
+        let eviction = evictions.try_recv().unwrap();
+        assert_eq!(eviction.tx_id, oldest.id);
+        assert_eq!(eviction.reason, EvictionReason::Orphaned);
+
+        // Disabled, gapped transactions are parked in the pool as before.
+        let config = MempoolConfig {
+            max_orphans: 0,
+            ..Default::default()
+        };
+        let (pool, _, _) = orphaning_pool(config, &["nova1a"]);
+        let gapped = make_tx("nova1a", "nova1b", 100, 3);
+        pool.add(gapped.clone()).unwrap();
+        assert!(pool.contains(&gapped.id));
+        assert_eq!(pool.orphan_count(), 0);
+    }
+
+    // -- Cancellation -------------------------------------------------------
+
+    #[test]
+    fn cancel_requires_the_senders_signature() {
+        let alice = NovaKeypair::generate();
+        let bob = address_of(&NovaKeypair::generate());
+        let tx = signed_transfer(&alice, &bob, 1_000, 1);
+        let pool = Mempool::default();
+        pool.add(tx.clone()).unwrap();
+
+        let forged = TransactionCancellation::sign(&tx.id, &NovaKeypair::generate());
+        assert!(matches!(
+            pool.cancel(&tx.id, &forged),
+            Err(MempoolError::InvalidCancellation(_))
+        ));
+        // Signed for another transaction.
+        let other = TransactionCancellation::sign(&"ab".repeat(32), &alice);
+        assert!(matches!(
+            pool.cancel(&tx.id, &other),
+            Err(MempoolError::InvalidCancellation(_))
+        ));
+        assert!(pool.contains(&tx.id));
+
+        let cancellation = TransactionCancellation::sign(&tx.id, &alice);
+        assert_eq!(pool.cancel(&tx.id, &cancellation).unwrap().id, tx.id);
+        assert!(pool.is_empty());
+        assert!(matches!(
+            pool.cancel(&tx.id, &cancellation),
+            Err(MempoolError::UnknownTransaction(_))
+        ));
+    }
+
+    // -- Errors -------------------------------------------------------------
+
+    #[test]
+    fn errors_keep_messages_and_sources() {
+        use std::error::Error as _;
+
+        assert_eq!(
+            MempoolError::FeeTooLow { min: 10, got: 5 }.to_string(),
+            "fee too low: minimum 10, got 5"
+        );
+        assert_eq!(
+            MempoolError::MemoryFull { bytes: 64 }.to_string(),
+            "mempool is full (64 bytes)"
+        );
+
+        let oversized = MempoolError::Oversized(TransactionError::FieldTooLarge {
+            field: "memo",
+            size: 2,
+            max: 1,
+        });
+        assert_eq!(oversized.to_string(), "transaction rejected");
+        assert!(oversized
+            .source()
+            .is_some_and(|e| e.downcast_ref::<TransactionError>().is_some()));
+
+        // Transparent variants read as, and chain through, the inner rejection.
+        let rejection = AdmissionRejection::Frozen {
+            account: "nova:a".into(),
+        };
+        let unexecutable = MempoolError::Unexecutable(rejection.clone());
+        assert_eq!(unexecutable.to_string(), "account is frozen: nova:a");
+        assert_eq!(unexecutable.to_string(), rejection.to_string());
+        assert!(MempoolError::DuplicateTransaction.source().is_none());
+    }
 }
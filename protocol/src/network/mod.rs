@@ -10,10 +10,25 @@
 //! ```text
 //! node.rs       — Validator node lifecycle and peer management
 //! consensus.rs  — Hybrid PoS+PoA consensus engine with BFT finality
+//! clock.rs      — Peer-median clock offset estimation and drift detection
+//! admission.rs  — Adaptive inbound admission (PoW/stake challenges under floods)
+//! capabilities.rs — Feature flags advertised over RPC and identify
+//! direct.rs     — Point-to-point NTP messaging between wallets' nodes
 //! mempool.rs    — Priority-ordered transaction pool with thread-safe access
+//! network_keys.rs — Validator network keys, separate from consensus keys
+//! orphans.rs    — Bounded buffer for transactions that arrive ahead of their nonce
+//! nat.rs        — Relay reservations, hole punching and reachability status
 //! gossip.rs     — Gossip protocol for block/transaction propagation
+//! halt.rs       — Supermajority-signed emergency halts and resumes
+//! journal.rs    — Bounded on-disk journal of received gossip, with replay
+//! liveness.rs   — Missed proposal/vote windows, jailing and unjailing
+//! peer_store.rs — Persistent peer records, dial backoff and reconnection planning
+//! screening.rs  — Sanctions/deny-list screening hook for mempool admission
 //! rpc.rs        — JSON-RPC method definitions and request/response types
+//! scoring.rs    — Gossipsub peer scoring parameters, thresholds and app scores
 //! sync.rs       — Chain state synchronization protocol
+//! treasury.rs   — Fee-funded treasury paid out by supermajority spend proposals
+//! validation.rs — Staged block validation shared by producer, consensus and sync
 //! ```
 //!
 //! ## Design Decisions
@@ -29,26 +44,86 @@
 //! - The RPC layer defines types only — actual HTTP serving happens in the
 //!   node binary via axum. The protocol crate stays transport-agnostic.
 
+pub mod admission;
+pub mod capabilities;
+pub mod clock;
 pub mod consensus;
 pub mod consensus_loop;
+pub mod direct;
 pub mod gossip;
+pub mod halt;
+pub mod journal;
+pub mod liveness;
 pub mod mempool;
+pub mod nat;
+pub mod network_keys;
 pub mod node;
+pub mod orphans;
+pub mod peer_store;
 pub mod producer;
 pub mod rpc;
+pub mod scoring;
+pub mod screening;
 pub mod sync;
+pub mod treasury;
+pub mod validation;
 
+pub use admission::{
+    AdmissionChallenge, AdmissionConfig, AdmissionController, AdmissionDecision, AdmissionError,
+    AdmissionMessage, AdmissionProof,
+};
+pub use capabilities::Capabilities;
+pub use clock::{ClockConfig, ClockSample, ClockSkewMonitor, ClockStatus};
 pub use consensus::{
-    ConsensusConfig, ConsensusEngine, ConsensusRound, FinalizedBlock, ValidatorInfo, ValidatorSet,
-    Vote,
+    ConsensusConfig, ConsensusEngine, ConsensusRound, FinalizedBlock, Justification, Proposal,
+    ValidatorInfo, ValidatorSet, Vote,
+};
+pub use consensus_loop::{
+    ConsensusLoop, ConsensusLoopConfig, ConsensusLoopError, MAX_FUTURE_ROUNDS,
+};
+pub use direct::{
+    Delivery, DirectAck, DirectError, DirectMessage, DirectMessageKind, DirectService, Mailboxes,
+    NTP_DIRECT_PROTOCOL,
 };
-pub use consensus_loop::{ConsensusLoop, ConsensusLoopConfig, ConsensusLoopError};
 pub use gossip::{
     GossipAction, GossipBehaviour, GossipConfig, GossipError, GossipMessage, GossipProtocol,
-    GossipService, GossipServiceConfig, GossipTopics, P2pGossipMessage, PeerInfo,
+    GossipService, GossipServiceConfig, GossipTopics, P2pGossipMessage, PeerInfo, PeerTransport,
+};
+pub use halt::{
+    EmergencyHalt, GovernanceSignature, HaltError, HaltGuard, HaltRecord, HaltStatus, ResumeAction,
+};
+pub use journal::{
+    GossipJournal, JournalContents, JournalEntry, JournalError, MessageKind, ReplayedEntry, Verdict,
 };
-pub use mempool::{Mempool, MempoolConfig, MempoolEntry, MempoolError};
+pub use liveness::{DutyWindow, LivenessPolicy, LivenessRecord, ValidatorLiveness};
+pub use mempool::{
+    AdmissionRejection, Eviction, EvictionReason, Lane, Mempool, MempoolConfig, MempoolEntry,
+    MempoolError, MempoolEvent, MempoolValidator, StateValidator,
+};
+pub use nat::{NatReport, NatStatus, Reachability};
+pub use network_keys::{NetworkKeyBinding, NetworkKeyRegistry, RegisteredNetworkKey};
 pub use node::{NodeStatus, ValidatorNode};
+pub use orphans::{Orphan, OrphanPool};
+pub use peer_store::{BackoffPolicy, PeerRecord, PeerStore};
 pub use producer::{BlockProducer, BlockProductionError, ProducedBlock, TxResult};
 pub use rpc::{RpcError, RpcMethod, RpcRequest, RpcResponse};
+pub use scoring::{
+    PeerScore, PeerScoreBoard, PeerScoring, PeerStanding, ScoreThresholds, TopicScoring,
+};
+pub use screening::{
+    Screener, ScreeningAction, ScreeningError, ScreeningProvider, ScreeningRejection,
+    StaticListProvider,
+};
 pub use sync::{SyncConfig, SyncEngine, SyncError, SyncRequest, SyncResponse, SyncResult};
+pub use treasury::{SpendProposal, TreasuryLedger, TreasurySpend};
+pub use validation::{BlockValidationError, BlockValidator, TransactionRejection, ValidationStage};
+
+/// State tree keys of the protocol records stored beside accounts. Supply
+/// audits skip them (see [`crate::storage::emission::SupplyAudit`]).
+pub const STATE_RECORD_KEYS: &[&str] = &[
+    consensus::VALIDATOR_SET_STATE_KEY,
+    liveness::LIVENESS_STATE_KEY,
+    network_keys::NETWORK_KEYS_STATE_KEY,
+    treasury::TREASURY_LEDGER_KEY,
+    crate::storage::emission::SUPPLY_STATE_KEY,
+];
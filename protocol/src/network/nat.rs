@@ -0,0 +1,265 @@
+//! # NAT Traversal
+//!
+//! Home validators usually sit behind NAT and cannot accept inbound
+//! connections. The swarm works around that in two steps:
+//!
+//! 1. **Circuit relay v2.** The node reserves a slot on each static relay
+//!    it is configured with and listens on `<relay>/p2p-circuit`, so peers
+//!    can reach it through the relay.
+//! 2. **DCUtR.** When a peer connects over a relayed circuit, both sides
+//!    try to punch a hole through their NATs and upgrade to a direct
+//!    connection. The relay only carries the coordination.
+//!
+//! [`NatStatus`] collects the evidence the swarm sees along the way and
+//! derives whether the node is publicly reachable. The node serves its
+//! [`NatReport`] at `/node`.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// How other nodes can reach this one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reachability {
+    /// No peer has dialled in directly and no relay holds a reservation.
+    #[default]
+    Unknown,
+    /// A peer on a public address dialled in without a relay.
+    Public,
+    /// Reachable only through a relay reservation.
+    Relayed,
+}
+
+/// Whether `addr` goes through a relay circuit.
+pub fn is_relayed(addr: &Multiaddr) -> bool {
+    addr.iter().any(|p| matches!(p, Protocol::P2pCircuit))
+}
+
+/// The relay of a `<relay>/p2p/<id>/p2p-circuit` address.
+pub fn relay_peer(addr: &Multiaddr) -> Option<PeerId> {
+    let mut relay = None;
+    for protocol in addr.iter() {
+        match protocol {
+            Protocol::P2p(peer) => relay = Some(peer),
+            Protocol::P2pCircuit => return relay,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether `addr` is an IP address outside private, loopback and
+/// link-local ranges — one a peer could only dial us from over the
+/// internet.
+fn is_public(addr: &Multiaddr) -> bool {
+    match addr.iter().next() {
+        Some(Protocol::Ip4(ip)) => is_public_v4(ip),
+        Some(Protocol::Ip6(ip)) => is_public_v6(ip),
+        _ => false,
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast())
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_v4(v4);
+    }
+    let first = ip.segments()[0];
+    // fc00::/7 is unique-local, fe80::/10 link-local.
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || first & 0xfe00 == 0xfc00
+        || first & 0xffc0 == 0xfe80)
+}
+
+#[derive(Debug, Default)]
+struct NatState {
+    public_inbound: u64,
+    reservations: Vec<PeerId>,
+    hole_punches_succeeded: u64,
+    hole_punches_failed: u64,
+}
+
+/// Reachability evidence gathered from the swarm's events.
+#[derive(Debug, Default)]
+pub struct NatStatus {
+    inner: RwLock<NatState>,
+}
+
+impl NatStatus {
+    /// Creates an empty status: reachability [`Reachability::Unknown`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an inbound connection accepted on `local_addr` from
+    /// `send_back_addr`. Only direct connections from public addresses
+    /// prove the node is reachable.
+    pub fn on_inbound(&self, local_addr: &Multiaddr, send_back_addr: &Multiaddr) {
+        if !is_relayed(local_addr) && !is_relayed(send_back_addr) && is_public(send_back_addr) {
+            self.inner.write().public_inbound += 1;
+        }
+    }
+
+    /// Records a reservation accepted by `relay`.
+    pub fn on_reservation(&self, relay: PeerId) {
+        let mut state = self.inner.write();
+        if !state.reservations.contains(&relay) {
+            state.reservations.push(relay);
+        }
+    }
+
+    /// Drops the reservation behind `addr` if it was a relay circuit
+    /// address the swarm stopped listening on.
+    pub fn on_listen_addr_expired(&self, addr: &Multiaddr) {
+        if let Some(relay) = relay_peer(addr) {
+            self.inner.write().reservations.retain(|r| *r != relay);
+        }
+    }
+
+    /// Records the outcome of a DCUtR hole punch.
+    pub fn on_hole_punch(&self, succeeded: bool) {
+        let mut state = self.inner.write();
+        if succeeded {
+            state.hole_punches_succeeded += 1;
+        } else {
+            state.hole_punches_failed += 1;
+        }
+    }
+
+    /// How peers can currently reach this node.
+    pub fn reachability(&self) -> Reachability {
+        let state = self.inner.read();
+        if state.public_inbound > 0 {
+            Reachability::Public
+        } else if !state.reservations.is_empty() {
+            Reachability::Relayed
+        } else {
+            Reachability::Unknown
+        }
+    }
+
+    /// A snapshot for status endpoints.
+    pub fn report(&self) -> NatReport {
+        let reachability = self.reachability();
+        let state = self.inner.read();
+        NatReport {
+            reachability,
+            relays: state.reservations.iter().map(PeerId::to_string).collect(),
+            public_inbound_connections: state.public_inbound,
+            hole_punches_succeeded: state.hole_punches_succeeded,
+            hole_punches_failed: state.hole_punches_failed,
+        }
+    }
+}
+
+/// Serializable view of a [`NatStatus`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NatReport {
+    /// How peers can currently reach this node.
+    pub reachability: Reachability,
+    /// Peer IDs of the relays holding a reservation for this node.
+    pub relays: Vec<String>,
+    /// Direct inbound connections accepted from public addresses.
+    pub public_inbound_connections: u64,
+    /// Relayed connections upgraded to direct ones.
+    pub hole_punches_succeeded: u64,
+    /// Hole punches that failed; the connection stays relayed.
+    pub hole_punches_failed: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identity::Keypair;
+
+    fn addr(s: &str) -> Multiaddr {
+        s.parse().unwrap()
+    }
+
+    fn relay_id() -> PeerId {
+        PeerId::from(Keypair::ed25519_from_bytes([7; 32]).unwrap().public())
+    }
+
+    #[test]
+    fn finds_relay_of_circuit_addresses() {
+        let relay = relay_id();
+        let circuit = addr(&format!(
+            "/ip4/203.0.113.5/tcp/9740/p2p/{}/p2p-circuit",
+            relay
+        ));
+        assert!(is_relayed(&circuit));
+        assert_eq!(relay_peer(&circuit), Some(relay));
+
+        let direct = addr(&format!("/ip4/203.0.113.5/tcp/9740/p2p/{}", relay));
+        assert!(!is_relayed(&direct));
+        assert_eq!(relay_peer(&direct), None);
+    }
+
+    #[test]
+    fn only_public_direct_inbound_proves_reachability() {
+        let status = NatStatus::new();
+        let local = addr("/ip4/0.0.0.0/tcp/9740");
+
+        status.on_inbound(&local, &addr("/ip4/192.168.1.20/tcp/50000"));
+        status.on_inbound(&local, &addr("/ip6/fe80::1/tcp/50000"));
+        status.on_inbound(
+            &addr(&format!(
+                "/ip4/203.0.113.5/tcp/9740/p2p/{}/p2p-circuit",
+                relay_id()
+            )),
+            &addr("/ip4/203.0.113.5/tcp/9740"),
+        );
+        assert_eq!(status.reachability(), Reachability::Unknown);
+
+        status.on_inbound(&local, &addr("/ip4/198.51.100.7/tcp/50000"));
+        assert_eq!(status.reachability(), Reachability::Public);
+        assert_eq!(status.report().public_inbound_connections, 1);
+    }
+
+    #[test]
+    fn reservations_make_the_node_relayed_until_they_expire() {
+        let status = NatStatus::new();
+        let relay = relay_id();
+        status.on_reservation(relay);
+        status.on_reservation(relay);
+        assert_eq!(status.reachability(), Reachability::Relayed);
+        assert_eq!(status.report().relays, vec![relay.to_string()]);
+
+        status.on_listen_addr_expired(&addr("/ip4/0.0.0.0/tcp/9740"));
+        assert_eq!(status.reachability(), Reachability::Relayed);
+
+        let circuit = addr(&format!(
+            "/ip4/203.0.113.5/tcp/9740/p2p/{}/p2p-circuit",
+            relay
+        ));
+        status.on_listen_addr_expired(&circuit);
+        assert_eq!(status.reachability(), Reachability::Unknown);
+    }
+
+    #[test]
+    fn counts_hole_punches() {
+        let status = NatStatus::new();
+        status.on_hole_punch(true);
+        status.on_hole_punch(false);
+        status.on_hole_punch(true);
+        let report = status.report();
+        assert_eq!(report.hole_punches_succeeded, 2);
+        assert_eq!(report.hole_punches_failed, 1);
+    }
+}
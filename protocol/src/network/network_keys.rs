@@ -0,0 +1,380 @@
+//! # Validator Network Keys
+//!
+//! A validator runs with two Ed25519 keys:
+//!
+//! - the **consensus key** (`keys/validator.key`) signs blocks, votes and
+//!   validator transactions. It is the validator's identity in the
+//!   [`ValidatorSet`] and should live somewhere cold;
+//! - the **network key** (`keys/network.key`) authenticates the transport
+//!   and derives the node's libp2p `PeerId`. It sits on the internet-facing
+//!   host and is the one most likely to leak.
+//!
+//! Losing the network key lets an attacker impersonate the node on the
+//! wire, nothing more. It can be rotated on its own: the operator submits a
+//! [`NetworkKeyRotation`](crate::transaction::types::TransactionType::NetworkKeyRotation)
+//! transaction signed with the consensus key whose payload is a
+//! [`NetworkKeyBinding`] signed with the new network key. Both signatures
+//! are needed — the consensus key authorizes the mapping, the network key
+//! proves the validator holds it, so nobody can claim another node's
+//! `PeerId`.
+//!
+//! Accepted bindings are kept in the [`NetworkKeyRegistry`] in the state
+//! tree, so every node can map a validator to its current peer and back.
+//! A network key equal to a consensus key, or already bound to another
+//! validator, is refused.
+
+use std::collections::BTreeMap;
+
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use crate::encoding;
+use crate::identity::nova_id::NovaId;
+use crate::network::consensus::ValidatorSet;
+use crate::network::direct::{peer_id_of, DirectError};
+use crate::storage::snapshot::StateSnapshot;
+use crate::storage::state::{bump_nonce, check_nonce, StateError, StateTree};
+use crate::transaction::Transaction;
+
+/// State tree key under which the network key registry is persisted.
+///
+/// Not a valid NOVA address, so it can never collide with an account.
+pub const NETWORK_KEYS_STATE_KEY: &str = "nova:system:network_keys";
+
+/// Domain prefix of the bytes a [`NetworkKeyBinding`] signature covers.
+pub const NETWORK_KEY_DOMAIN: &[u8] = b"nova-network-key-v1";
+
+// ---------------------------------------------------------------------------
+// Bindings
+// ---------------------------------------------------------------------------
+
+/// A network key's signed statement that it serves `validator`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkKeyBinding {
+    /// Hex-encoded consensus public key of the validator.
+    #[serde(with = "encoding::hex_string")]
+    pub validator: String,
+    /// Hex-encoded network public key.
+    #[serde(with = "encoding::hex_string")]
+    pub network_key: String,
+    /// Network key's signature over [`signing_bytes`](Self::signing_bytes),
+    /// hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub signature: String,
+}
+
+impl NetworkKeyBinding {
+    /// Binds `network` to the validator with consensus key `validator`.
+    pub fn sign(validator: &NovaPublicKey, network: &NovaKeypair) -> Self {
+        let validator = validator.to_hex();
+        let network_key = network.public_key().to_hex();
+        let signature = network
+            .sign(&Self::signing_bytes(&validator, &network_key))
+            .to_hex();
+        Self {
+            validator,
+            network_key,
+            signature,
+        }
+    }
+
+    /// The bytes a binding of `network_key` to `validator` signs.
+    pub fn signing_bytes(validator: &str, network_key: &str) -> Vec<u8> {
+        let mut buf = NETWORK_KEY_DOMAIN.to_vec();
+        buf.extend_from_slice(encoding::strip_hex_prefix(validator).as_bytes());
+        buf.push(b':');
+        buf.extend_from_slice(encoding::strip_hex_prefix(network_key).as_bytes());
+        buf
+    }
+
+    /// Returns `true` if the network key signed this binding.
+    pub fn verify(&self) -> bool {
+        let (Ok(key), Ok(signature)) = (
+            NovaPublicKey::from_hex(encoding::strip_hex_prefix(&self.network_key)),
+            NovaSignature::from_hex(encoding::strip_hex_prefix(&self.signature)),
+        ) else {
+            return false;
+        };
+        key.verify(
+            &Self::signing_bytes(&self.validator, &self.network_key),
+            &signature,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+/// A validator's current network key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredNetworkKey {
+    /// Hex-encoded network public key.
+    pub network_key: String,
+    /// Height of the block that registered it; 0 for genesis.
+    pub since: u64,
+}
+
+impl RegisteredNetworkKey {
+    /// The libp2p peer ID the network key derives.
+    pub fn peer_id(&self) -> Result<PeerId, DirectError> {
+        let key = NovaPublicKey::from_hex(&self.network_key)
+            .map_err(|e| DirectError::Transport(e.to_string()))?;
+        peer_id_of(&key)
+    }
+}
+
+/// Current network key of every validator that has registered one, keyed
+/// by consensus public key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkKeyRegistry {
+    /// Registered keys.
+    pub keys: BTreeMap<String, RegisteredNetworkKey>,
+}
+
+impl NetworkKeyRegistry {
+    /// Loads the registry persisted in the state tree, or an empty one.
+    pub fn load(tree: &StateTree) -> Self {
+        tree.get_raw(NETWORK_KEYS_STATE_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Loads the registry as of a committed state snapshot.
+    pub fn load_from_snapshot(snapshot: &StateSnapshot<'_>) -> Self {
+        snapshot
+            .get_raw(NETWORK_KEYS_STATE_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persists the registry into the state tree.
+    pub fn store(&self, tree: &mut StateTree) {
+        let bytes =
+            bincode::serialize(self).expect("network key registry serialization should not fail");
+        tree.put_raw(NETWORK_KEYS_STATE_KEY, &bytes);
+    }
+
+    /// The network key registered for `validator`.
+    pub fn get(&self, validator: &str) -> Option<&RegisteredNetworkKey> {
+        self.keys.get(validator)
+    }
+
+    /// The validator a network key is bound to.
+    pub fn validator_for(&self, network_key: &str) -> Option<&str> {
+        self.keys
+            .iter()
+            .find(|(_, registered)| registered.network_key == network_key)
+            .map(|(validator, _)| validator.as_str())
+    }
+
+    /// Checks `binding` against the validator set and records it at
+    /// `height`, replacing the validator's previous network key.
+    ///
+    /// # Errors
+    ///
+    /// [`StateError::NetworkKeyRejected`] if the binding does not verify,
+    /// names a validator outside `validators`, reuses a consensus key, or
+    /// names a network key bound to another validator.
+    pub fn register(
+        &mut self,
+        binding: &NetworkKeyBinding,
+        validators: &ValidatorSet,
+        height: u64,
+    ) -> Result<(), StateError> {
+        let reject = |reason: String| Err(StateError::NetworkKeyRejected(reason));
+        let validator = encoding::strip_hex_prefix(&binding.validator).to_ascii_lowercase();
+        let network_key = encoding::strip_hex_prefix(&binding.network_key).to_ascii_lowercase();
+
+        if !binding.verify() {
+            return reject("binding is not signed by the network key".to_string());
+        }
+        if !validators
+            .validators()
+            .iter()
+            .any(|v| v.address == validator)
+        {
+            return reject(format!("{} is not in the validator set", validator));
+        }
+        if validators
+            .validators()
+            .iter()
+            .any(|v| v.address == network_key)
+        {
+            return reject("network key is a consensus key".to_string());
+        }
+        if let Some(other) = self.validator_for(&network_key) {
+            if other != validator {
+                return reject(format!("network key is bound to {}", other));
+            }
+        }
+
+        self.keys.insert(
+            validator,
+            RegisteredNetworkKey {
+                network_key,
+                since: height,
+            },
+        );
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// State Transition
+// ---------------------------------------------------------------------------
+
+/// Applies a `NetworkKeyRotation` transaction in the block at `height`.
+///
+/// The sender must be the address of the transaction's
+/// `sender_public_key`, and that key the validator the JSON
+/// [`NetworkKeyBinding`] in the payload names; see
+/// [`NetworkKeyRegistry::register`] for the checks on the binding.
+pub fn apply_network_key_rotation(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    height: u64,
+) -> Result<(), StateError> {
+    check_nonce(tree, &tx.sender, tx.nonce)?;
+
+    let validator = tx
+        .sender_public_key
+        .as_deref()
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| StateError::NetworkKeyRejected("no validator key on transaction".into()))?;
+    let owns_sender = NovaPublicKey::from_hex(&validator)
+        .is_ok_and(|pk| NovaId::from_public_key(&pk).to_address() == tx.sender);
+    if !owns_sender {
+        return Err(StateError::NetworkKeyRejected(format!(
+            "key {} does not own {}",
+            validator, tx.sender
+        )));
+    }
+
+    let payload = tx.payload.as_deref().unwrap_or_default();
+    let binding: NetworkKeyBinding = serde_json::from_slice(payload)
+        .map_err(|e| StateError::Serialization(format!("network key binding: {}", e)))?;
+    if encoding::strip_hex_prefix(&binding.validator).to_ascii_lowercase() != validator {
+        return Err(StateError::NetworkKeyRejected(
+            "binding names another validator".to_string(),
+        ));
+    }
+
+    let validators = ValidatorSet::load_from_state(tree)
+        .ok_or_else(|| StateError::NetworkKeyRejected("no validator set in state".to_string()))?;
+    let mut registry = NetworkKeyRegistry::load(tree);
+    registry.register(&binding, &validators, height)?;
+    registry.store(tree);
+    bump_nonce(tree, &tx.sender, tx.nonce)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::signing::sign_transaction;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    fn setup(validators: &[&NovaKeypair]) -> StateTree {
+        let mut tree = StateTree::new(NovaDB::open_temporary().unwrap());
+        let mut set = ValidatorSet::new();
+        for kp in validators {
+            set.add_validator(kp.public_key().to_hex(), 1_000);
+        }
+        set.store_in_state(&mut tree);
+        tree
+    }
+
+    fn rotation_tx(
+        validator: &NovaKeypair,
+        binding: &NetworkKeyBinding,
+        nonce: u64,
+    ) -> Transaction {
+        let address = NovaId::from_public_key(&validator.public_key()).to_address();
+        let mut tx = TransactionBuilder::new(TransactionType::NetworkKeyRotation)
+            .sender(&address)
+            .receiver(&address)
+            .amount(Amount::new(0, Currency::NOVA))
+            .nonce(nonce)
+            .payload(serde_json::to_vec(binding).unwrap())
+            .build_unchecked();
+        sign_transaction(&mut tx, validator);
+        tx
+    }
+
+    #[test]
+    fn binding_is_signed_by_the_network_key() {
+        let validator = NovaKeypair::generate();
+        let network = NovaKeypair::generate();
+        let binding = NetworkKeyBinding::sign(&validator.public_key(), &network);
+        assert!(binding.verify());
+
+        // Re-pointing the binding at another validator breaks it.
+        let mut stolen = binding;
+        stolen.validator = NovaKeypair::generate().public_key().to_hex();
+        assert!(!stolen.verify());
+    }
+
+    #[test]
+    fn rotation_replaces_the_network_key() {
+        let validator = NovaKeypair::generate();
+        let mut tree = setup(&[&validator]);
+
+        let first = NovaKeypair::generate();
+        let binding = NetworkKeyBinding::sign(&validator.public_key(), &first);
+        apply_network_key_rotation(&mut tree, &rotation_tx(&validator, &binding, 1), 5).unwrap();
+
+        let second = NovaKeypair::generate();
+        let binding = NetworkKeyBinding::sign(&validator.public_key(), &second);
+        apply_network_key_rotation(&mut tree, &rotation_tx(&validator, &binding, 2), 9).unwrap();
+
+        let registry = NetworkKeyRegistry::load(&tree);
+        let current = registry.get(&validator.public_key().to_hex()).unwrap();
+        assert_eq!(current.network_key, second.public_key().to_hex());
+        assert_eq!(current.since, 9);
+        assert_eq!(
+            current.peer_id().unwrap(),
+            peer_id_of(&second.public_key()).unwrap()
+        );
+        assert_eq!(registry.validator_for(&first.public_key().to_hex()), None);
+    }
+
+    #[test]
+    fn rotation_rejects_shared_and_foreign_keys() {
+        let alice = NovaKeypair::generate();
+        let bob = NovaKeypair::generate();
+        let outsider = NovaKeypair::generate();
+        let mut tree = setup(&[&alice, &bob]);
+
+        // A validator cannot reuse its consensus key on the network.
+        let binding = NetworkKeyBinding::sign(&alice.public_key(), &alice);
+        assert!(matches!(
+            apply_network_key_rotation(&mut tree, &rotation_tx(&alice, &binding, 1), 1),
+            Err(StateError::NetworkKeyRejected(_))
+        ));
+
+        // Nor claim a network key another validator already uses.
+        let network = NovaKeypair::generate();
+        let binding = NetworkKeyBinding::sign(&alice.public_key(), &network);
+        apply_network_key_rotation(&mut tree, &rotation_tx(&alice, &binding, 1), 1).unwrap();
+        let binding = NetworkKeyBinding::sign(&bob.public_key(), &network);
+        assert!(matches!(
+            apply_network_key_rotation(&mut tree, &rotation_tx(&bob, &binding, 1), 2),
+            Err(StateError::NetworkKeyRejected(_))
+        ));
+
+        // Non-validators have nothing to bind.
+        let binding = NetworkKeyBinding::sign(&outsider.public_key(), &NovaKeypair::generate());
+        assert!(matches!(
+            apply_network_key_rotation(&mut tree, &rotation_tx(&outsider, &binding, 1), 2),
+            Err(StateError::NetworkKeyRejected(_))
+        ));
+        assert_eq!(NetworkKeyRegistry::load(&tree).keys.len(), 1);
+    }
+}
@@ -328,6 +328,7 @@ impl ValidatorNode {
 
 /// Errors that can occur during node operations.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum NodeError {
     /// The node is offline and cannot process requests.
     #[error("node is offline")]
@@ -404,7 +405,7 @@ mod tests {
         ))
         .fee(200)
         .nonce(1)
-        .build();
+        .build_unchecked();
 
         let result = node.process_transaction(tx);
         assert!(result.is_err());
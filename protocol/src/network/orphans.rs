@@ -0,0 +1,301 @@
+//! Buffer for transactions that arrive ahead of their nonce.
+//!
+//! Gossip does not preserve order: a sender's nonce 7 can reach a node
+//! before its nonce 6. Admitted to the pool, such a transaction could not
+//! be selected until the gap filled, yet it would hold one of the sender's
+//! slots and fail execution if a producer tried it. The
+//! [`Mempool`](super::mempool::Mempool) holds it here instead, keyed by
+//! `(sender, nonce)`, and promotes it into the pool as soon as the
+//! transaction before it is admitted or the sender's account nonce catches
+//! up.
+//!
+//! The buffer is bounded twice: a sender may hold at most `max_txs_per_sender`
+//! orphans, and once `max_orphans` are held in total the oldest is dropped
+//! to make room.
+
+use std::collections::{BTreeMap, HashMap};
+
+use parking_lot::Mutex;
+
+use crate::transaction::Transaction;
+
+/// A transaction waiting for its predecessor.
+#[derive(Debug, Clone)]
+pub struct Orphan {
+    /// The transaction itself.
+    pub transaction: Transaction,
+
+    /// Unix timestamp (seconds) when it was buffered.
+    pub added_at: u64,
+
+    /// Insertion order, for dropping the oldest first.
+    seq: u64,
+}
+
+/// Why [`OrphanPool::insert`] refused a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrphanRefusal {
+    /// The sender already has an orphan at this nonce paying at least as
+    /// much; `fee` is what it pays.
+    Outbid { fee: u64 },
+
+    /// The sender already holds `max_txs_per_sender` orphans.
+    SenderFull,
+}
+
+/// What an accepted orphan pushed out.
+#[derive(Debug, Clone, Default)]
+pub struct Displaced {
+    /// The sender's orphan at the same nonce, paying less.
+    pub replaced: Option<Transaction>,
+
+    /// The oldest orphan, dropped because the buffer was full.
+    pub dropped: Option<Transaction>,
+}
+
+#[derive(Debug, Default)]
+struct Orphans {
+    by_key: BTreeMap<(String, u64), Orphan>,
+    by_id: HashMap<String, (String, u64)>,
+    next_seq: u64,
+}
+
+impl Orphans {
+    fn remove(&mut self, key: &(String, u64)) -> Option<Orphan> {
+        let orphan = self.by_key.remove(key)?;
+        self.by_id.remove(&orphan.transaction.id);
+        Some(orphan)
+    }
+
+    fn count_for(&self, sender: &str) -> usize {
+        self.by_key
+            .range((sender.to_string(), 0)..=(sender.to_string(), u64::MAX))
+            .count()
+    }
+}
+
+/// Bounded, thread-safe orphan buffer keyed by `(sender, nonce)`.
+#[derive(Debug)]
+pub struct OrphanPool {
+    inner: Mutex<Orphans>,
+    max_orphans: usize,
+    max_txs_per_sender: usize,
+}
+
+impl OrphanPool {
+    /// Creates a buffer holding at most `max_orphans` transactions, and at
+    /// most `max_txs_per_sender` from any one sender.
+    pub fn new(max_orphans: usize, max_txs_per_sender: usize) -> Self {
+        Self {
+            inner: Mutex::new(Orphans::default()),
+            max_orphans,
+            max_txs_per_sender,
+        }
+    }
+
+    /// Buffers `tx`, buffered at `added_at`. At a nonce the sender already
+    /// has an orphan for, the one paying the higher fee stays.
+    pub fn insert(&self, tx: Transaction, added_at: u64) -> Result<Displaced, OrphanRefusal> {
+        let mut inner = self.inner.lock();
+        let key = (tx.sender.clone(), tx.nonce);
+        let mut displaced = Displaced::default();
+
+        if let Some(existing) = inner.by_key.get(&key) {
+            if tx.fee <= existing.transaction.fee {
+                return Err(OrphanRefusal::Outbid {
+                    fee: existing.transaction.fee,
+                });
+            }
+            displaced.replaced = inner.remove(&key).map(|o| o.transaction);
+        } else if inner.count_for(&tx.sender) >= self.max_txs_per_sender {
+            return Err(OrphanRefusal::SenderFull);
+        }
+
+        if inner.by_key.len() >= self.max_orphans {
+            let oldest = inner
+                .by_key
+                .iter()
+                .min_by_key(|(_, orphan)| orphan.seq)
+                .map(|(key, _)| key.clone());
+            displaced.dropped = oldest
+                .and_then(|oldest| inner.remove(&oldest))
+                .map(|o| o.transaction);
+        }
+
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        inner.by_id.insert(tx.id.clone(), key.clone());
+        inner.by_key.insert(
+            key,
+            Orphan {
+                transaction: tx,
+                added_at,
+                seq,
+            },
+        );
+        Ok(displaced)
+    }
+
+    /// Removes and returns `sender`'s orphan at `nonce`.
+    pub fn take(&self, sender: &str, nonce: u64) -> Option<Transaction> {
+        self.inner
+            .lock()
+            .remove(&(sender.to_string(), nonce))
+            .map(|o| o.transaction)
+    }
+
+    /// Removes and returns the orphan with the given ID.
+    pub fn remove(&self, tx_id: &str) -> Option<Transaction> {
+        let mut inner = self.inner.lock();
+        let key = inner.by_id.get(tx_id)?.clone();
+        inner.remove(&key).map(|o| o.transaction)
+    }
+
+    /// Removes and returns every orphan `pred` matches.
+    pub fn remove_where(&self, pred: impl Fn(&Orphan) -> bool) -> Vec<Transaction> {
+        let mut inner = self.inner.lock();
+        let keys: Vec<(String, u64)> = inner
+            .by_key
+            .iter()
+            .filter(|(_, orphan)| pred(orphan))
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.iter()
+            .filter_map(|key| inner.remove(key))
+            .map(|o| o.transaction)
+            .collect()
+    }
+
+    /// Returns `true` if the transaction with the given ID is buffered.
+    pub fn contains(&self, tx_id: &str) -> bool {
+        self.inner.lock().by_id.contains_key(tx_id)
+    }
+
+    /// Returns a clone of the buffered transaction with the given ID.
+    pub fn get(&self, tx_id: &str) -> Option<Transaction> {
+        let inner = self.inner.lock();
+        let key = inner.by_id.get(tx_id)?;
+        inner.by_key.get(key).map(|o| o.transaction.clone())
+    }
+
+    /// The senders with at least one orphan.
+    pub fn senders(&self) -> Vec<String> {
+        let inner = self.inner.lock();
+        let mut senders: Vec<String> = inner.by_key.keys().map(|(s, _)| s.clone()).collect();
+        senders.dedup();
+        senders
+    }
+
+    /// Number of buffered transactions.
+    pub fn len(&self) -> usize {
+        self.inner.lock().by_key.len()
+    }
+
+    /// Returns `true` if nothing is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().by_key.is_empty()
+    }
+
+    /// Drops every orphan.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.by_key.clear();
+        inner.by_id.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    fn make_tx(sender: &str, nonce: u64, fee: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender(sender)
+            .receiver("nova:bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(fee)
+            .nonce(nonce)
+            .timestamp(1_700_000_000_000 + nonce)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn keyed_by_sender_and_nonce() {
+        let pool = OrphanPool::new(10, 10);
+        let tx = make_tx("nova:alice", 3, 100);
+        pool.insert(tx.clone(), 0).unwrap();
+
+        assert!(pool.contains(&tx.id));
+        assert_eq!(pool.get(&tx.id).unwrap().id, tx.id);
+        assert!(pool.take("nova:alice", 2).is_none());
+        assert!(pool.take("nova:carol", 3).is_none());
+        assert_eq!(pool.take("nova:alice", 3).unwrap().id, tx.id);
+        assert!(pool.is_empty());
+        assert!(!pool.contains(&tx.id));
+
+        pool.insert(tx.clone(), 0).unwrap();
+        assert_eq!(pool.remove(&tx.id).unwrap().id, tx.id);
+        assert!(pool.take("nova:alice", 3).is_none());
+    }
+
+    #[test]
+    fn same_slot_keeps_the_higher_fee() {
+        let pool = OrphanPool::new(10, 10);
+        let cheap = make_tx("nova:alice", 3, 100);
+        pool.insert(cheap.clone(), 0).unwrap();
+
+        let mut cheaper = make_tx("nova:alice", 3, 100);
+        cheaper.id = "other".into();
+        assert_eq!(
+            pool.insert(cheaper, 0).unwrap_err(),
+            OrphanRefusal::Outbid { fee: 100 }
+        );
+
+        let pricier = make_tx("nova:alice", 3, 500);
+        let displaced = pool.insert(pricier.clone(), 0).unwrap();
+        assert_eq!(displaced.replaced.unwrap().id, cheap.id);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains(&pricier.id));
+    }
+
+    #[test]
+    fn bounded_per_sender_and_in_total() {
+        let pool = OrphanPool::new(3, 2);
+        pool.insert(make_tx("nova:alice", 3, 100), 0).unwrap();
+        pool.insert(make_tx("nova:alice", 4, 100), 0).unwrap();
+        assert_eq!(
+            pool.insert(make_tx("nova:alice", 5, 100), 0).unwrap_err(),
+            OrphanRefusal::SenderFull
+        );
+
+        let first_carol = make_tx("nova:carol", 9, 100);
+        pool.insert(first_carol.clone(), 0).unwrap();
+        // Full: the oldest orphan, Alice's nonce 3, makes room.
+        let displaced = pool.insert(make_tx("nova:dave", 2, 100), 0).unwrap();
+        assert_eq!(displaced.dropped.unwrap().nonce, 3);
+        assert_eq!(pool.len(), 3);
+        assert!(pool.contains(&first_carol.id));
+        assert_eq!(pool.senders(), ["nova:alice", "nova:carol", "nova:dave"]);
+    }
+
+    #[test]
+    fn remove_where_drains_matches() {
+        let pool = OrphanPool::new(10, 10);
+        pool.insert(make_tx("nova:alice", 3, 100), 10).unwrap();
+        pool.insert(make_tx("nova:alice", 4, 100), 20).unwrap();
+
+        let stale = pool.remove_where(|o| o.added_at < 15);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].nonce, 3);
+        assert_eq!(pool.len(), 1);
+
+        pool.clear();
+        assert!(pool.is_empty());
+    }
+}
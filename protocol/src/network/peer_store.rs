@@ -0,0 +1,447 @@
+//! # Persistent Peer Store
+//!
+//! Remembers the peers this node has met so that a restart does not mean
+//! starting from the bootnodes again. Records live in a dedicated `peers`
+//! tree inside [`NovaDB`], keyed by peer ID, and carry enough history to
+//! tell a reliable peer from a flaky one.
+//!
+//! ## Reconnection
+//!
+//! Every failed dial pushes the peer's next attempt further out, doubling
+//! from `base_ms` up to `max_ms`, with random jitter so that a network-wide
+//! outage does not end in a synchronized reconnection stampede. A successful
+//! connection resets the counter.
+//!
+//! ## Dial Planning
+//!
+//! [`PeerStore::dial_plan`] decides whom to dial next. When the mesh is
+//! healthy only pinned peers are redialed; when it drops below the low
+//! watermark the store hands out its most reliable known peers — pinned
+//! first, then by historical success rate — until the target is met.
+//!
+//! Operators curate the store by hand through the node's admin API: pin
+//! peers that must always be reconnected, ban ones that misbehave.
+
+use std::collections::HashSet;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sled::Tree;
+
+use crate::storage::db::{DbError, DbResult, NovaDB};
+
+/// Name of the sled tree holding peer records.
+pub const PEER_TREE: &str = "peers";
+
+// ---------------------------------------------------------------------------
+// Backoff Policy
+// ---------------------------------------------------------------------------
+
+/// Exponential backoff parameters for redialing failed peers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackoffPolicy {
+    /// Delay after the first failure, in milliseconds.
+    pub base_ms: u64,
+    /// Upper bound on the delay, in milliseconds.
+    pub max_ms: u64,
+    /// Fraction of the delay added or removed at random (0.0 – 1.0).
+    pub jitter: f64,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            base_ms: crate::config::PEER_CONNECTION_TIMEOUT.as_millis() as u64 / 10,
+            max_ms: 10 * 60 * 1_000,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before the next dial after `consecutive_failures` failures.
+    ///
+    /// Zero failures means no delay. The result is jittered by up to
+    /// `±jitter` of the nominal delay and never exceeds `max_ms`.
+    pub fn delay_ms(&self, consecutive_failures: u32) -> u64 {
+        if consecutive_failures == 0 {
+            return 0;
+        }
+        let exp = (consecutive_failures - 1).min(32);
+        let nominal = self.base_ms.saturating_mul(1u64 << exp).min(self.max_ms);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 || nominal == 0 {
+            return nominal;
+        }
+        let factor = rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter);
+        ((nominal as f64 * factor) as u64).min(self.max_ms)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Peer Record
+// ---------------------------------------------------------------------------
+
+/// Everything the node remembers about one peer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// libp2p peer ID (base58).
+    pub peer_id: String,
+    /// Known multiaddrs, most recently seen first.
+    pub addresses: Vec<String>,
+    /// Unix timestamp (milliseconds) when the peer was first recorded.
+    pub first_seen: u64,
+    /// Unix timestamp (milliseconds) of the last successful contact.
+    pub last_seen: u64,
+    /// Total successful connections.
+    pub successes: u64,
+    /// Total failed dials.
+    pub failures: u64,
+    /// Failed dials since the last success; drives the backoff.
+    pub consecutive_failures: u32,
+    /// Earliest Unix timestamp (milliseconds) at which to dial again.
+    pub next_dial_at: u64,
+    /// Always reconnect, regardless of mesh health.
+    pub pinned: bool,
+    /// Never dial or accept.
+    pub banned: bool,
+}
+
+impl PeerRecord {
+    /// Creates a fresh record for a newly discovered peer.
+    pub fn new(peer_id: impl Into<String>, address: impl Into<String>, now_ms: u64) -> Self {
+        Self {
+            peer_id: peer_id.into(),
+            addresses: vec![address.into()],
+            first_seen: now_ms,
+            last_seen: 0,
+            successes: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            next_dial_at: 0,
+            pinned: false,
+            banned: false,
+        }
+    }
+
+    /// Historical success rate with Laplace smoothing, in `(0, 1)`.
+    ///
+    /// An unknown peer scores 0.5 — better than a known-bad one, worse
+    /// than a known-good one.
+    pub fn reliability(&self) -> f64 {
+        (self.successes as f64 + 1.0) / ((self.successes + self.failures) as f64 + 2.0)
+    }
+
+    /// Returns `true` if the backoff has elapsed and the peer is not banned.
+    pub fn is_dialable(&self, now_ms: u64) -> bool {
+        !self.banned && !self.addresses.is_empty() && now_ms >= self.next_dial_at
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PeerStore
+// ---------------------------------------------------------------------------
+
+/// Peer records persisted in NovaDB.
+///
+/// sled handles concurrency, so the store can be shared via `Arc` between
+/// the swarm loop and the admin API without extra locking.
+#[derive(Debug, Clone)]
+pub struct PeerStore {
+    tree: Tree,
+    policy: BackoffPolicy,
+}
+
+impl PeerStore {
+    /// Opens (or creates) the peer store inside `db`.
+    pub fn open(db: &NovaDB, policy: BackoffPolicy) -> DbResult<Self> {
+        Ok(Self {
+            tree: db.open_tree(PEER_TREE)?,
+            policy,
+        })
+    }
+
+    /// Returns the backoff policy.
+    pub fn policy(&self) -> &BackoffPolicy {
+        &self.policy
+    }
+
+    /// Number of known peers, including banned ones.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Returns `true` if no peers are known.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Fetches a single record.
+    pub fn get(&self, peer_id: &str) -> DbResult<Option<PeerRecord>> {
+        match self.tree.get(peer_id.as_bytes())? {
+            Some(bytes) => Ok(Some(decode(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// All known peers, in key order.
+    pub fn list(&self) -> DbResult<Vec<PeerRecord>> {
+        self.tree.iter().values().map(|v| decode(&v?)).collect()
+    }
+
+    /// Inserts or replaces a record verbatim.
+    pub fn put(&self, record: &PeerRecord) -> DbResult<()> {
+        let bytes =
+            bincode::serialize(record).map_err(|e| DbError::Serialization(e.to_string()))?;
+        self.tree.insert(record.peer_id.as_bytes(), bytes)?;
+        Ok(())
+    }
+
+    /// Records that `peer_id` was seen at `address` (discovery, identify).
+    ///
+    /// The address moves to the front of the list; history is kept.
+    pub fn observe(&self, peer_id: &str, address: &str, now_ms: u64) -> DbResult<PeerRecord> {
+        self.update(peer_id, address, now_ms, |record| {
+            record.addresses.retain(|a| a != address);
+            record.addresses.insert(0, address.to_string());
+        })
+    }
+
+    /// Records a successful connection: resets the backoff.
+    pub fn record_success(
+        &self,
+        peer_id: &str,
+        address: &str,
+        now_ms: u64,
+    ) -> DbResult<PeerRecord> {
+        self.update(peer_id, address, now_ms, |record| {
+            record.addresses.retain(|a| a != address);
+            record.addresses.insert(0, address.to_string());
+            record.successes += 1;
+            record.consecutive_failures = 0;
+            record.last_seen = now_ms;
+            record.next_dial_at = now_ms;
+        })
+    }
+
+    /// Records a failed dial and schedules the next attempt.
+    ///
+    /// Returns `None` for unknown peers — there is nothing to back off from.
+    pub fn record_failure(&self, peer_id: &str, now_ms: u64) -> DbResult<Option<PeerRecord>> {
+        let Some(mut record) = self.get(peer_id)? else {
+            return Ok(None);
+        };
+        record.failures += 1;
+        record.consecutive_failures = record.consecutive_failures.saturating_add(1);
+        record.next_dial_at = now_ms + self.policy.delay_ms(record.consecutive_failures);
+        self.put(&record)?;
+        Ok(Some(record))
+    }
+
+    /// Pins or unpins a peer. Returns `false` if the peer is unknown.
+    pub fn set_pinned(&self, peer_id: &str, pinned: bool) -> DbResult<bool> {
+        self.modify(peer_id, |record| record.pinned = pinned)
+    }
+
+    /// Bans or unbans a peer. Returns `false` if the peer is unknown.
+    pub fn set_banned(&self, peer_id: &str, banned: bool) -> DbResult<bool> {
+        self.modify(peer_id, |record| record.banned = banned)
+    }
+
+    /// Forgets a peer entirely. Returns `true` if it was known.
+    pub fn remove(&self, peer_id: &str) -> DbResult<bool> {
+        Ok(self.tree.remove(peer_id.as_bytes())?.is_some())
+    }
+
+    /// Picks the peers to dial now.
+    ///
+    /// `connected` is the set of peer IDs with a live connection. If fewer
+    /// than `mesh_low` are connected, the most reliable dialable peers are
+    /// returned until `mesh_target` would be reached; otherwise only pinned
+    /// peers that dropped off are redialed.
+    pub fn dial_plan(
+        &self,
+        now_ms: u64,
+        connected: &HashSet<String>,
+        mesh_low: usize,
+        mesh_target: usize,
+    ) -> DbResult<Vec<PeerRecord>> {
+        let mut candidates: Vec<PeerRecord> = self
+            .list()?
+            .into_iter()
+            .filter(|r| r.is_dialable(now_ms) && !connected.contains(&r.peer_id))
+            .collect();
+
+        if connected.len() >= mesh_low {
+            candidates.retain(|r| r.pinned);
+            return Ok(candidates);
+        }
+
+        candidates.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then(b.reliability().total_cmp(&a.reliability()))
+                .then(b.last_seen.cmp(&a.last_seen))
+        });
+        candidates.truncate(mesh_target.saturating_sub(connected.len()));
+        Ok(candidates)
+    }
+
+    fn update(
+        &self,
+        peer_id: &str,
+        address: &str,
+        now_ms: u64,
+        f: impl FnOnce(&mut PeerRecord),
+    ) -> DbResult<PeerRecord> {
+        let mut record = self
+            .get(peer_id)?
+            .unwrap_or_else(|| PeerRecord::new(peer_id, address, now_ms));
+        f(&mut record);
+        self.put(&record)?;
+        Ok(record)
+    }
+
+    fn modify(&self, peer_id: &str, f: impl FnOnce(&mut PeerRecord)) -> DbResult<bool> {
+        match self.get(peer_id)? {
+            Some(mut record) => {
+                f(&mut record);
+                self.put(&record)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+fn decode(bytes: &[u8]) -> DbResult<PeerRecord> {
+    bincode::deserialize(bytes).map_err(|e| DbError::Serialization(e.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn store() -> PeerStore {
+        let db = NovaDB::open_temporary().unwrap();
+        PeerStore::open(&db, BackoffPolicy::default()).unwrap()
+    }
+
+    #[test]
+    fn records_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = NovaDB::open(dir.path()).unwrap();
+            let peers = PeerStore::open(&db, BackoffPolicy::default()).unwrap();
+            peers
+                .record_success("peer-a", "/ip4/10.0.0.1/tcp/9740", NOW)
+                .unwrap();
+            peers.set_pinned("peer-a", true).unwrap();
+            db.flush().unwrap();
+        }
+
+        let db = NovaDB::open(dir.path()).unwrap();
+        let peers = PeerStore::open(&db, BackoffPolicy::default()).unwrap();
+        let record = peers.get("peer-a").unwrap().expect("persisted");
+        assert_eq!(record.successes, 1);
+        assert_eq!(record.last_seen, NOW);
+        assert!(record.pinned);
+    }
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        let policy = BackoffPolicy {
+            base_ms: 1_000,
+            max_ms: 30_000,
+            jitter: 0.0,
+        };
+        assert_eq!(policy.delay_ms(0), 0);
+        assert_eq!(policy.delay_ms(1), 1_000);
+        assert_eq!(policy.delay_ms(3), 4_000);
+        assert_eq!(policy.delay_ms(10), 30_000);
+        assert_eq!(policy.delay_ms(u32::MAX), 30_000);
+
+        let jittered = BackoffPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for _ in 0..100 {
+            let d = jittered.delay_ms(2);
+            assert!((1_000..=3_000).contains(&d), "2s ± 50%, got {d}");
+        }
+    }
+
+    #[test]
+    fn failures_defer_dialing_and_success_resets() {
+        let peers = store();
+        peers
+            .observe("peer-a", "/ip4/10.0.0.1/tcp/9740", NOW)
+            .unwrap();
+
+        let record = peers.record_failure("peer-a", NOW).unwrap().unwrap();
+        assert_eq!(record.consecutive_failures, 1);
+        assert!(!record.is_dialable(NOW));
+        assert!(record.is_dialable(NOW + peers.policy().max_ms));
+
+        let record = peers
+            .record_success("peer-a", "/ip4/10.0.0.1/tcp/9740", NOW + 5)
+            .unwrap();
+        assert_eq!(record.consecutive_failures, 0);
+        assert!(record.is_dialable(NOW + 5));
+        assert!(peers.record_failure("stranger", NOW).unwrap().is_none());
+    }
+
+    #[test]
+    fn thin_mesh_prefers_reliable_peers() {
+        let peers = store();
+        for (id, ok, bad) in [("flaky", 1, 9), ("solid", 9, 1), ("fresh", 0, 0)] {
+            let mut record = PeerRecord::new(id, format!("/dns4/{id}/tcp/9740"), NOW);
+            record.successes = ok;
+            record.failures = bad;
+            peers.put(&record).unwrap();
+        }
+        peers
+            .observe("pinned", "/dns4/pinned/tcp/9740", NOW)
+            .unwrap();
+        peers.set_pinned("pinned", true).unwrap();
+
+        let none = HashSet::new();
+        let plan: Vec<_> = peers
+            .dial_plan(NOW, &none, 2, 3)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.peer_id)
+            .collect();
+        assert_eq!(plan, ["pinned", "solid", "fresh"]);
+
+        // Healthy mesh: only pinned peers are chased.
+        let connected: HashSet<String> = ["solid".to_string(), "fresh".to_string()].into();
+        let plan = peers.dial_plan(NOW, &connected, 2, 3).unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].peer_id, "pinned");
+    }
+
+    #[test]
+    fn banned_peers_are_never_dialed() {
+        let peers = store();
+        peers
+            .observe("peer-a", "/ip4/10.0.0.1/tcp/9740", NOW)
+            .unwrap();
+        assert!(peers.set_banned("peer-a", true).unwrap());
+        assert!(peers
+            .dial_plan(NOW, &HashSet::new(), 1, 8)
+            .unwrap()
+            .is_empty());
+
+        assert!(peers.remove("peer-a").unwrap());
+        assert!(peers.is_empty());
+        assert!(!peers.set_banned("peer-a", false).unwrap());
+    }
+}
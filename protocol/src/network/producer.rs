@@ -8,11 +8,15 @@
 //! ## Pipeline Stages
 //!
 //! ```text
-//! 1. SELECT   — Pull highest-fee transactions from the mempool
-//! 2. EXECUTE  — Apply each transaction to the state tree; drop failures
+//! 1. SELECT   — Pull highest-fee transactions from the mempool, each
+//!              sender's in nonce order
+//! 2. EXECUTE  — Apply each transaction to the state tree; drop failures;
+//!              settle the collected fees and check value conservation;
+//!              record validator liveness
 //! 3. BUILD    — Construct the block with the post-execution state root
 //! 4. SIGN     — Attach the validator's Ed25519 signature
-//! 5. COMMIT   — Persist to NovaDB and purge executed txs from the mempool
+//! 5. COMMIT   — Persist the block and its receipts to NovaDB and purge
+//!              executed txs from the mempool
 //! ```
 //!
 //! Failed transactions are silently dropped during execution. They do not
@@ -20,6 +24,11 @@
 //! the "optimistic execution" model: we attempt every transaction the mempool
 //! offers and keep only the winners.
 //!
+//! Execution and settlement go through the state transition function
+//! ([`crate::execution`]) the sync engine replays blocks with, via the
+//! same [`BlockValidator`], so a produced block passes replay by
+//! construction.
+//!
 //! ## Thread Safety
 //!
 //! The `BlockProducer` holds `Arc` references to shared infrastructure
@@ -27,19 +36,30 @@
 //! The state tree is protected by `RwLock` — block production acquires a
 //! write lock for the duration of transaction execution.
 
-use std::fmt;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use thiserror::Error;
 use tracing::{debug, info};
 
 use crate::crypto::keys::NovaKeypair;
+use crate::crypto::wire::{BlockHash, SignatureBytes};
+use crate::execution;
+use crate::indexer::{Indexer, StateDiff};
+use crate::network::consensus::FinalizedBlock;
+use crate::network::liveness::LivenessPolicy;
 use crate::network::mempool::Mempool;
-use crate::storage::block::Block;
+use crate::network::validation::{BlockValidationError, BlockValidator, TransactionRejection};
+use crate::storage::block::{Block, CommitSignature, MerkleBuilder};
 use crate::storage::db::{DbError, NovaDB};
-use crate::storage::state::{apply_transfer, StateError, StateTree};
-use crate::transaction::types::TransactionType;
-use crate::transaction::Transaction;
+use crate::storage::emission::MonetaryPolicy;
+use crate::storage::fee::FeePolicy;
+use crate::storage::state::{StateError, StateTree};
+use crate::transaction::fee_estimator::FeeEstimator;
+use crate::transaction::limits;
+use crate::vault::token::TokenRegistry;
+use crate::zkp::verifier::BalanceVerifier;
 
 // ---------------------------------------------------------------------------
 // Error Type
@@ -50,52 +70,96 @@ use crate::transaction::Transaction;
 /// These are operational errors — things that go wrong during the pipeline,
 /// not protocol violations. A `BlockProductionError` means "we tried to
 /// produce a block and something didn't work," not "the block is invalid."
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum BlockProductionError {
     /// No transactions available in the mempool. Not necessarily an error
     /// in practice (empty blocks are valid), but the caller may want to
     /// skip production when there's nothing to include.
+    #[error("mempool is empty, nothing to produce")]
     EmptyMempool,
 
     /// No transactions survived execution. Every candidate was invalid
     /// (insufficient balance, wrong nonce, frozen account, etc.).
+    #[error("all candidate transactions failed execution")]
     NoTransactions,
 
     /// A state tree operation failed during transaction execution.
-    StateError(StateError),
+    #[error("state transition error: {0}")]
+    StateError(#[from] StateError),
+
+    /// The transaction is already included in a persisted block. Nonces
+    /// alone cannot catch this once the sender has been reaped and
+    /// re-created.
+    #[error("transaction {0} is already on chain")]
+    AlreadyIncluded(String),
+
+    /// The transaction's `valid_until` is before the block timestamp.
+    #[error("transaction {tx_id} expired at {valid_until}")]
+    Expired {
+        /// Transaction ID.
+        tx_id: String,
+        /// The transaction's expiry (Unix milliseconds).
+        valid_until: u64,
+    },
 
     /// Database persistence failed.
-    DbError(DbError),
+    #[error("database error: {0}")]
+    DbError(#[from] DbError),
 
     /// Block signing failed (malformed key, hardware token error, etc.).
+    #[error("block signing error: {0}")]
     SigningError(String),
 }
 
-impl fmt::Display for BlockProductionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::EmptyMempool => write!(f, "mempool is empty, nothing to produce"),
-            Self::NoTransactions => {
-                write!(f, "all candidate transactions failed execution")
+impl From<TransactionRejection> for BlockProductionError {
+    fn from(e: TransactionRejection) -> Self {
+        match e {
+            TransactionRejection::AlreadyIncluded(tx_id) => Self::AlreadyIncluded(tx_id),
+            TransactionRejection::Expired { tx_id, valid_until } => {
+                Self::Expired { tx_id, valid_until }
             }
-            Self::StateError(e) => write!(f, "state transition error: {}", e),
-            Self::DbError(e) => write!(f, "database error: {}", e),
-            Self::SigningError(e) => write!(f, "block signing error: {}", e),
+            TransactionRejection::State(e) => Self::StateError(e),
+            TransactionRejection::Db(e) => Self::DbError(e),
         }
     }
 }
 
-impl std::error::Error for BlockProductionError {}
+// ---------------------------------------------------------------------------
+// Block Timestamps
+// ---------------------------------------------------------------------------
 
-impl From<StateError> for BlockProductionError {
-    fn from(e: StateError) -> Self {
-        Self::StateError(e)
-    }
+/// Where produced blocks get their timestamps from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockClock {
+    /// The wall clock at production time.
+    #[default]
+    Wall,
+    /// A schedule anchored at a fixed genesis time: the block at height
+    /// `h` is stamped `genesis_ms + h * interval_ms`. Two runs that produce
+    /// the same blocks produce the same hashes, which is what deterministic
+    /// dev chains need.
+    Fixed {
+        /// Unix milliseconds the schedule starts from (height 0).
+        genesis_ms: u64,
+        /// Milliseconds between consecutive heights.
+        interval_ms: u64,
+    },
 }
 
-impl From<DbError> for BlockProductionError {
-    fn from(e: DbError) -> Self {
-        Self::DbError(e)
+impl BlockClock {
+    /// Timestamp (Unix milliseconds) for a block at `height`.
+    pub fn timestamp_for(&self, height: u64) -> u64 {
+        match *self {
+            Self::Wall => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            Self::Fixed {
+                genesis_ms,
+                interval_ms,
+            } => genesis_ms.saturating_add(height.saturating_mul(interval_ms)),
+        }
     }
 }
 
@@ -137,6 +201,14 @@ pub struct ProducedBlock {
     /// State root after applying all successful transactions. This is
     /// the same value embedded in `block.header.state_root`.
     pub state_root: [u8; 32],
+
+    /// Total weight of the block's transactions, in bytes (see
+    /// [`limits::tx_weight`]).
+    pub weight: usize,
+
+    /// Every state leaf the block wrote. Reverting it undoes the block if
+    /// consensus does not finalize it.
+    pub diff: StateDiff,
 }
 
 // ---------------------------------------------------------------------------
@@ -171,8 +243,37 @@ pub struct BlockProducer {
     /// Validator's Ed25519 keypair for signing produced blocks.
     keypair: NovaKeypair,
 
-    /// NOVA address (hex-encoded public key) of this validator.
+    /// NOVA address (hex-encoded public key) of this validator. Receives
+    /// the proposer share of every block's fees.
     validator_address: String,
+
+    /// Block rules transactions are executed and settled under: fee,
+    /// liveness and monetary policies, token registry, balance verifier
+    /// and the block byte budget. Mints nothing unless the monetary policy
+    /// is set from the genesis config.
+    validator: BlockValidator,
+
+    /// Votes that finalized the last committed block, carried into the
+    /// next block's `last_commit`.
+    last_commit: Mutex<Option<(BlockHash, Vec<CommitSignature>)>>,
+
+    /// Source of block timestamps.
+    clock: BlockClock,
+
+    /// Milliseconds added to every timestamp `clock` yields. Zero except
+    /// on dev chains that have been moved through time.
+    clock_offset_ms: AtomicI64,
+
+    /// Exact `(height, timestamp)` for one upcoming block, overriding
+    /// `clock` and the offset.
+    pinned_timestamp: Mutex<Option<(u64, u64)>>,
+
+    /// Indexer recording the state diff of every committed block, if one
+    /// is attached.
+    indexer: Option<Arc<Indexer>>,
+
+    /// Fee history fed with every committed block, if one is attached.
+    fee_estimator: Option<Arc<FeeEstimator>>,
 }
 
 impl BlockProducer {
@@ -193,6 +294,118 @@ impl BlockProducer {
             mempool,
             keypair,
             validator_address,
+            validator: BlockValidator::new(),
+            last_commit: Mutex::new(None),
+            clock: BlockClock::default(),
+            clock_offset_ms: AtomicI64::new(0),
+            pinned_timestamp: Mutex::new(None),
+            indexer: None,
+            fee_estimator: None,
+        }
+    }
+
+    /// Overrides the fee policy. Must match the policy every other node
+    /// replays blocks with.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.validator = self.validator.with_fee_policy(fee_policy);
+        self
+    }
+
+    /// Overrides the liveness policy. Consensus-critical, like the fee
+    /// policy.
+    pub fn with_liveness_policy(mut self, liveness_policy: LivenessPolicy) -> Self {
+        self.validator = self.validator.with_liveness_policy(liveness_policy);
+        self
+    }
+
+    /// Overrides the block reward schedule, normally with the genesis
+    /// config's. Consensus-critical, like the fee policy.
+    pub fn with_monetary_policy(mut self, monetary_policy: MonetaryPolicy) -> Self {
+        self.validator = self.validator.with_monetary_policy(monetary_policy);
+        self
+    }
+
+    /// Overrides the tokens transfers may be denominated in.
+    /// Consensus-critical, like the fee policy.
+    pub fn with_token_registry(mut self, token_registry: TokenRegistry) -> Self {
+        self.validator = self.validator.with_token_registry(token_registry);
+        self
+    }
+
+    /// Sets the verification key confidential transfers are checked with.
+    /// Consensus-critical, like the fee policy.
+    pub fn with_balance_verifier(mut self, verifier: Arc<BalanceVerifier>) -> Self {
+        self.validator = self.validator.with_balance_verifier(verifier);
+        self
+    }
+
+    /// Overrides the byte budget of a block. Must not exceed the
+    /// `max_block_bytes` validators enforce, or the blocks are rejected.
+    pub fn with_max_block_bytes(mut self, max_block_bytes: usize) -> Self {
+        self.validator = self.validator.with_max_block_bytes(max_block_bytes);
+        self
+    }
+
+    /// Records every committed block's state diff in `indexer` and wakes
+    /// its delivery.
+    pub fn with_indexer(mut self, indexer: Arc<Indexer>) -> Self {
+        self.indexer = Some(indexer);
+        self
+    }
+
+    /// Feeds the fees of every committed block to `fee_estimator`.
+    pub fn with_fee_estimator(mut self, fee_estimator: Arc<FeeEstimator>) -> Self {
+        self.fee_estimator = Some(fee_estimator);
+        self
+    }
+
+    /// Remembers the votes that finalized `finalized.block`, so the block
+    /// built on it can carry them as its `last_commit`.
+    pub fn record_commit(&self, finalized: &FinalizedBlock) {
+        let commit = finalized.votes.iter().map(CommitSignature::from).collect();
+        *self.last_commit.lock() = Some((finalized.block.header.hash, commit));
+    }
+
+    /// Overrides where block timestamps come from (the wall clock by
+    /// default).
+    pub fn with_block_clock(mut self, clock: BlockClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock block timestamps come from, before any offset.
+    pub fn block_clock(&self) -> BlockClock {
+        self.clock
+    }
+
+    /// Current offset applied to block timestamps, in milliseconds.
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.clock_offset_ms.load(Ordering::Relaxed)
+    }
+
+    /// Shifts every future block timestamp by `offset_ms` and drops any
+    /// pinned timestamp. For dev chains testing time-dependent logic; a
+    /// production validator stamping shifted times would have its blocks
+    /// rejected by peers.
+    pub fn set_clock_offset_ms(&self, offset_ms: i64) {
+        self.clock_offset_ms.store(offset_ms, Ordering::Relaxed);
+        *self.pinned_timestamp.lock() = None;
+    }
+
+    /// Stamps the block at `height` with exactly `timestamp_ms`, whatever
+    /// the clock reads when it is produced. Dev chains only, as above.
+    pub fn pin_timestamp(&self, height: u64, timestamp_ms: u64) {
+        *self.pinned_timestamp.lock() = Some((height, timestamp_ms));
+    }
+
+    /// Timestamp the next block at `height` would carry.
+    pub fn timestamp_for(&self, height: u64) -> u64 {
+        match *self.pinned_timestamp.lock() {
+            Some((pinned, timestamp)) if pinned == height => timestamp,
+            _ => self
+                .clock
+                .timestamp_for(height)
+                .saturating_add_signed(self.clock_offset_ms()),
         }
     }
 
@@ -200,11 +413,13 @@ impl BlockProducer {
     ///
     /// Selects up to `max_txs` transactions ordered by fee priority,
     /// executes each one against the state tree, drops failures, and
-    /// assembles the surviving transactions into a signed block.
+    /// assembles the surviving transactions into a signed block. Selection
+    /// stops at the first transaction that would take the block past its
+    /// byte budget; it and those after it stay in the mempool.
     ///
-    /// The state tree is mutated in place. If the caller needs rollback
-    /// semantics, they should snapshot the state root before calling this
-    /// method and restore it on error.
+    /// The state tree is mutated in place; [`ProducedBlock::diff`] undoes
+    /// the block if it is not finalized. A block that fails to settle is
+    /// undone before the error is returned.
     ///
     /// # Arguments
     ///
@@ -220,8 +435,13 @@ impl BlockProducer {
         parent: &Block,
         max_txs: usize,
     ) -> Result<ProducedBlock, BlockProductionError> {
-        // Stage 1: SELECT — grab the best transactions from the mempool.
-        let candidates = self.mempool.select_transactions(max_txs);
+        // Stage 1: SELECT — grab the best transactions from the mempool,
+        // each sender's from its account nonce on, in nonce order, so a
+        // higher-fee follow-up does not execute ahead of the transaction it
+        // follows and nothing behind a nonce gap is tried.
+        let candidates = self
+            .mempool
+            .select_executable(&self.state_tree.read(), max_txs);
 
         info!(
             candidates = candidates.len(),
@@ -231,23 +451,61 @@ impl BlockProducer {
         );
 
         // Stage 2: EXECUTE — apply each transaction to the state tree.
+        let height = parent.header.height + 1;
+        let timestamp = self.timestamp_for(height);
         let mut successful_txs = Vec::new();
+        let mut tx_root = MerkleBuilder::new();
         let mut tx_results = Vec::new();
-
-        {
+        let last_commit = match self.last_commit.lock().as_ref() {
+            Some((hash, commit)) if *hash == parent.header.hash => commit.clone(),
+            _ => Vec::new(),
+        };
+        let mut weight: usize = 0;
+        let max_block_bytes = self.validator.max_block_bytes();
+
+        let (fees, diff) = {
             let mut tree = self.state_tree.write();
+            tree.track_writes();
+            let mut execution = self.validator.begin_block(
+                &tree,
+                &candidates,
+                &self.validator_address,
+                height,
+                timestamp,
+            );
 
             for tx in &candidates {
-                match self.execute_transaction(&mut tree, tx) {
+                let tx_weight = limits::tx_weight(tx);
+                if weight + tx_weight > max_block_bytes {
+                    debug!(
+                        tx_id = %tx.id,
+                        weight,
+                        max_block_bytes,
+                        "block byte budget reached"
+                    );
+                    break;
+                }
+                let executed = self
+                    .validator
+                    .check_not_included(&self.db, tx)
+                    .and_then(|()| {
+                        execution
+                            .execute(&mut tree, tx)
+                            .map_err(TransactionRejection::from)
+                    });
+                match executed {
                     Ok(()) => {
+                        weight += tx_weight;
                         tx_results.push(TxResult {
                             tx_id: tx.id.clone(),
                             success: true,
                             error: None,
                         });
+                        tx_root.push(tx);
                         successful_txs.push(tx.clone());
                     }
                     Err(e) => {
+                        let e = BlockProductionError::from(e);
                         debug!(
                             tx_id = %tx.id,
                             error = %e,
@@ -261,22 +519,45 @@ impl BlockProducer {
                     }
                 }
             }
-        }
+
+            // Pay the proposer its fees and reward, burn the rest, and
+            // refuse to build a block that created or destroyed value along
+            // the way.
+            let fees = execution.finish(
+                &mut tree,
+                Some(parent.header.validator.as_str()),
+                &last_commit,
+            );
+            let writes = tree.take_writes();
+            let diff = StateDiff::from_writes(&tree, height, writes);
+            match fees {
+                Ok(fees) => (fees, diff),
+                Err(e) => {
+                    diff.revert(&mut tree);
+                    return Err(e.into());
+                }
+            }
+        };
 
         // Stage 3: Capture the post-execution state root.
         let state_root = self.state_tree.read().root();
 
-        // Stage 4: BUILD — construct the block from successful transactions.
-        let mut block = Block::new(
+        // Stage 4: BUILD — construct the block from successful transactions,
+        // whose Merkle root was built up as they were packed.
+        let mut block = Block::with_tx_root(
             parent,
             successful_txs,
+            tx_root.root(),
             self.validator_address.clone(),
             state_root,
-        );
+            timestamp,
+        )
+        .with_fees(fees)
+        .with_last_commit(last_commit);
 
         // Stage 5: SIGN — attach the validator's signature.
         let sig = self.keypair.sign(&block.header.hash);
-        block.header.signature = sig.as_bytes().to_vec();
+        block.header.signature = Some(SignatureBytes::from(&sig));
 
         info!(
             height = block.header.height,
@@ -289,52 +570,43 @@ impl BlockProducer {
             block,
             tx_results,
             state_root,
+            weight,
+            diff,
         })
     }
 
-    /// Executes a single transaction against the state tree.
+    /// Executes `block`, proposed by a peer on `parent`, against the state
+    /// tree under the rules [`produce_block`](Self::produce_block) builds
+    /// with. Every transaction must execute and the header must commit to
+    /// the resulting state root; otherwise the tree is left as it was.
     ///
-    /// For `Transfer` transactions, this calls `apply_transfer` which
-    /// validates the sender's balance, debits the sender, credits the
-    /// receiver, and increments the sender's nonce.
-    ///
-    /// Other transaction types (CreditRequest, TokenMint, etc.) are not
-    /// yet implemented in the state transition engine. They pass through
-    /// as no-ops — included in the block but with no state effect.
-    fn execute_transaction(
+    /// Returns the block's diff, which undoes it if it is not finalized.
+    pub fn apply_proposed(
         &self,
-        tree: &mut StateTree,
-        tx: &Transaction,
-    ) -> Result<(), BlockProductionError> {
-        match tx.tx_type {
-            TransactionType::Transfer => {
-                let amount = tx.amount.value;
-                apply_transfer(tree, &tx.sender, &tx.receiver, amount)?;
-                Ok(())
-            }
-            // Other transaction types are accepted but do not yet modify
-            // state. The block includes them for ordering and audit purposes;
-            // state transitions will be added as each module matures.
-            TransactionType::CreditRequest
-            | TransactionType::CreditSettlement
-            | TransactionType::TokenMint
-            | TransactionType::TokenBurn
-            | TransactionType::ConfidentialTransfer => {
-                debug!(
-                    tx_type = %tx.tx_type,
-                    tx_id = %tx.id,
-                    "non-transfer transaction accepted as no-op"
-                );
-                Ok(())
-            }
+        block: &Block,
+        parent: &Block,
+    ) -> Result<StateDiff, BlockValidationError> {
+        let mut tree = self.state_tree.write();
+        let applied = self.validator.replay_block(
+            &mut tree,
+            &self.db,
+            block,
+            Some(parent.header.validator.as_str()),
+        )?;
+        if let Err(e) = self.validator.check_state_root(block, applied.state_root) {
+            applied.diff.revert(&mut tree);
+            return Err(e);
         }
+        Ok(applied.diff)
     }
 
     /// Persists a produced block to the database and cleans up the mempool.
     ///
     /// This is the final step in the block production pipeline. After this
-    /// call, the block is durable on disk and its transactions are no longer
-    /// in the mempool.
+    /// call, the block and a receipt for each of its transactions are
+    /// durable on disk, its state is visible through the tree's
+    /// committed-state handle, and its transactions are no longer in the
+    /// mempool.
     ///
     /// # Ordering guarantee
     ///
@@ -345,12 +617,30 @@ impl BlockProducer {
     /// succeed (if the block was not actually persisted) or fail with a
     /// nonce mismatch (if it was). Either way, no funds are lost.
     pub fn commit_block(&self, block: &Block) -> Result<(), BlockProductionError> {
-        // Persist the block to the database.
+        // Record the state diff while the block's writes are uncommitted.
+        if let Some(indexer) = &self.indexer {
+            indexer.record_diff(&self.state_tree.read(), block)?;
+        }
+
+        // Persist the block to the database, then its receipts.
         self.db.put_block(block)?;
+        self.db.put_receipts(&execution::block_receipts(block))?;
 
-        // Remove included transactions from the mempool.
+        // Publish the post-block state to API readers.
+        self.state_tree.read().commit(block.header.height);
+
+        // Remove included transactions from the mempool, then promote the
+        // orphans whose gap the block closed.
         let tx_ids: Vec<String> = block.transactions.iter().map(|tx| tx.id.clone()).collect();
         self.mempool.remove_batch(&tx_ids);
+        self.mempool.promote_orphans();
+
+        if let Some(fee_estimator) = &self.fee_estimator {
+            fee_estimator.record_block(&block.transactions);
+        }
+        if let Some(indexer) = &self.indexer {
+            indexer.notify_committed();
+        }
 
         info!(
             height = block.header.height,
@@ -377,9 +667,13 @@ mod tests {
     use crate::crypto::keys::NovaKeypair;
     use crate::network::mempool::{Mempool, MempoolConfig};
     use crate::storage::db::NovaDB;
+    use crate::storage::snapshot::CommittedState;
     use crate::storage::state::{AccountState, StateTree};
     use crate::transaction::builder::TransactionBuilder;
-    use crate::transaction::types::{Amount, Currency, TransactionType};
+    use crate::transaction::fee_estimator::FeePriority;
+    use crate::transaction::receipt::{LogKind, ReceiptLog};
+    use crate::transaction::types::{Amount, Currency, TransactionStatus, TransactionType};
+    use crate::transaction::Transaction;
 
     // -- Test Helpers -------------------------------------------------------
 
@@ -422,7 +716,7 @@ mod tests {
             .fee(fee)
             .nonce(nonce)
             .timestamp(1_700_000_000_000 + nonce)
-            .build()
+            .build_unchecked()
     }
 
     /// Seeds an account with a given balance in the state tree.
@@ -455,7 +749,7 @@ mod tests {
 
         seed_balance(&tree, "nova1alice", 10_000);
 
-        let tx = make_transfer("nova1alice", "nova1bob", 3_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 3_000, 100, 1);
         mempool.add(tx).unwrap();
 
         let produced = producer.produce_block(&genesis, 100).unwrap();
@@ -466,7 +760,7 @@ mod tests {
         let t = tree.read();
         let alice = t.get("nova1alice").unwrap();
         let bob = t.get("nova1bob").unwrap();
-        assert_eq!(alice.balance, 7_000);
+        assert_eq!(alice.balance, 6_900);
         assert_eq!(bob.balance, 3_000);
     }
 
@@ -479,7 +773,7 @@ mod tests {
         seed_balance(&tree, "nova1sender", 100_000);
 
         for i in 0..10u64 {
-            let tx = make_transfer("nova1sender", "nova1receiver", 100, (i + 1) * 100, i);
+            let tx = make_transfer("nova1sender", "nova1receiver", 100, (i + 1) * 100, i + 1);
             mempool.add(tx).unwrap();
         }
 
@@ -499,8 +793,8 @@ mod tests {
         seed_balance(&tree, "nova1alice", 500);
         seed_balance(&tree, "nova1bob", 10_000);
 
-        let bad_tx = make_transfer("nova1alice", "nova1bob", 1_000, 200, 0);
-        let good_tx = make_transfer("nova1bob", "nova1alice", 500, 100, 0);
+        let bad_tx = make_transfer("nova1alice", "nova1bob", 1_000, 200, 1);
+        let good_tx = make_transfer("nova1bob", "nova1alice", 500, 100, 1);
 
         mempool.add(bad_tx.clone()).unwrap();
         mempool.add(good_tx.clone()).unwrap();
@@ -535,7 +829,7 @@ mod tests {
         let root_before = tree.read().root();
 
         seed_balance(&tree, "nova1alice", 10_000);
-        let tx = make_transfer("nova1alice", "nova1bob", 5_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 5_000, 100, 1);
         mempool.add(tx).unwrap();
 
         let produced = producer.produce_block(&genesis, 100).unwrap();
@@ -568,7 +862,7 @@ mod tests {
 
         seed_balance(&tree, "nova1alice", 10_000);
 
-        let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 1);
         let tx_id = tx.id.clone();
         mempool.add(tx).unwrap();
         assert_eq!(mempool.size(), 1);
@@ -589,15 +883,13 @@ mod tests {
         let produced = producer.produce_block(&genesis, 100).unwrap();
         let block = &produced.block;
 
-        // The signature should be 64 bytes (Ed25519).
-        assert_eq!(block.header.signature.len(), 64);
+        // The block must carry a signature.
+        let sig_bytes = block.header.signature.expect("block should be signed");
 
         // Verify the signature against the block hash.
         let pk = producer.keypair.public_key();
-        let mut sig_bytes = [0u8; 64];
-        sig_bytes.copy_from_slice(&block.header.signature);
-        let signature = crate::crypto::keys::NovaSignature::from_bytes(sig_bytes);
-        assert!(pk.verify(&block.header.hash, &signature));
+        let signature = crate::crypto::keys::NovaSignature::from(sig_bytes);
+        assert!(pk.verify(block.header.hash.as_bytes(), &signature));
     }
 
     // -- 9. Sequential blocks chain correctly --------------------------------
@@ -613,7 +905,7 @@ mod tests {
         let mut heights = vec![0u64];
 
         for i in 0..3u64 {
-            let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, i);
+            let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, i + 1);
             mempool.add(tx).unwrap();
 
             let produced = producer.produce_block(&parent, 100).unwrap();
@@ -637,20 +929,25 @@ mod tests {
 
         seed_balance(&tree, "nova1alice", 50_000);
 
-        // Nonce 0 should succeed (expected nonce for a fresh account).
-        let tx0 = make_transfer("nova1alice", "nova1bob", 1_000, 300, 0);
-        // Nonce 1 should succeed after nonce 0 is applied.
+        // The follow-up pays more, so fee priority alone would run it first.
         let tx1 = make_transfer("nova1alice", "nova1bob", 1_000, 200, 1);
+        let tx2 = make_transfer("nova1alice", "nova1bob", 1_000, 300, 2);
 
-        mempool.add(tx0).unwrap();
-        mempool.add(tx1).unwrap();
+        mempool.add(tx1.clone()).unwrap();
+        mempool.add(tx2.clone()).unwrap();
 
         let produced = producer.produce_block(&genesis, 100).unwrap();
 
-        // Both should be in the block (nonce 0 executes first by fee priority,
-        // then nonce 1 naturally follows).
-        let successful_count = produced.tx_results.iter().filter(|r| r.success).count();
-        assert!(successful_count >= 1);
+        // Both execute, in nonce order.
+        assert!(produced.tx_results.iter().all(|r| r.success));
+        let ids: Vec<_> = produced
+            .block
+            .transactions
+            .iter()
+            .map(|tx| &tx.id)
+            .collect();
+        assert_eq!(ids, vec![&tx1.id, &tx2.id]);
+        assert_eq!(tree.read().get("nova1alice").unwrap().nonce, 2);
     }
 
     // -- 11. Fee priority ordering ------------------------------------------
@@ -663,9 +960,9 @@ mod tests {
         seed_balance(&tree, "nova1bob", 100_000);
 
         // Low fee from alice.
-        let tx_low = make_transfer("nova1alice", "nova1charlie", 100, 10, 0);
+        let tx_low = make_transfer("nova1alice", "nova1charlie", 100, 10, 1);
         // High fee from bob.
-        let tx_high = make_transfer("nova1bob", "nova1charlie", 100, 10_000, 0);
+        let tx_high = make_transfer("nova1bob", "nova1charlie", 100, 10_000, 1);
 
         mempool.add(tx_low).unwrap();
         mempool.add(tx_high.clone()).unwrap();
@@ -686,9 +983,9 @@ mod tests {
         seed_balance(&tree, "nova1poor", 100);
 
         // High fee: rich sends 1000 — should succeed.
-        let tx_good = make_transfer("nova1rich", "nova1dest", 1_000, 5_000, 0);
+        let tx_good = make_transfer("nova1rich", "nova1dest", 1_000, 5_000, 1);
         // Low fee: poor sends 10000 — should fail (insufficient balance).
-        let tx_bad = make_transfer("nova1poor", "nova1dest", 10_000, 100, 0);
+        let tx_bad = make_transfer("nova1poor", "nova1dest", 10_000, 100, 1);
 
         mempool.add(tx_good).unwrap();
         mempool.add(tx_bad).unwrap();
@@ -698,7 +995,7 @@ mod tests {
         // The good tx should have been applied.
         let t = tree.read();
         let rich = t.get("nova1rich").unwrap();
-        assert_eq!(rich.balance, 49_000);
+        assert_eq!(rich.balance, 44_000);
 
         // The failed tx should not have affected the poor account's balance.
         let poor = t.get("nova1poor").unwrap();
@@ -722,7 +1019,7 @@ mod tests {
 
         // Add some initial transactions.
         for i in 0..5u64 {
-            let tx = make_transfer("nova1alice", "nova1bob", 100, (i + 1) * 100, i);
+            let tx = make_transfer("nova1alice", "nova1bob", 100, (i + 1) * 100, i + 1);
             mempool.add(tx).unwrap();
         }
 
@@ -730,7 +1027,7 @@ mod tests {
         let mempool_clone = Arc::clone(&mempool);
         let writer = thread::spawn(move || {
             for i in 100..110u64 {
-                let tx = make_transfer(&format!("nova1writer_{}", i), "nova1receiver", 10, 50, i);
+                let tx = make_transfer(&format!("nova1writer_{}", i), "nova1receiver", 10, 50, 1);
                 let _ = mempool_clone.add(tx);
             }
         });
@@ -767,15 +1064,15 @@ mod tests {
         let mempool1 = Arc::new(Mempool::new(MempoolConfig::default()));
         let mempool2 = Arc::new(Mempool::new(MempoolConfig::default()));
 
-        let tx = make_transfer("nova1alice", "nova1bob", 3_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 3_000, 100, 1);
         mempool1.add(tx.clone()).unwrap();
         mempool2.add(tx).unwrap();
 
-        let kp1 = NovaKeypair::generate();
-        let kp2 = NovaKeypair::generate();
+        // Same proposer too: it is credited with the fee.
+        let kp = NovaKeypair::generate();
 
-        let producer1 = BlockProducer::new(db1, tree1, mempool1, kp1);
-        let producer2 = BlockProducer::new(db2, tree2, mempool2, kp2);
+        let producer1 = BlockProducer::new(db1, tree1, mempool1, kp.clone());
+        let producer2 = BlockProducer::new(db2, tree2, mempool2, kp);
 
         let genesis = Block::genesis();
         let p1 = producer1.produce_block(&genesis, 100).unwrap();
@@ -798,21 +1095,21 @@ mod tests {
         let mut parent = genesis;
 
         // Block 1: Alice sends 10,000 to Bob.
-        let tx1 = make_transfer("nova1alice", "nova1bob", 10_000, 100, 0);
+        let tx1 = make_transfer("nova1alice", "nova1bob", 10_000, 100, 1);
         mempool.add(tx1).unwrap();
         let p1 = producer.produce_block(&parent, 100).unwrap();
         producer.commit_block(&p1.block).unwrap();
         parent = p1.block;
 
         // Block 2: Alice sends another 20,000 to Bob.
-        let tx2 = make_transfer("nova1alice", "nova1bob", 20_000, 100, 1);
+        let tx2 = make_transfer("nova1alice", "nova1bob", 20_000, 100, 2);
         mempool.add(tx2).unwrap();
         let p2 = producer.produce_block(&parent, 100).unwrap();
         producer.commit_block(&p2.block).unwrap();
         parent = p2.block;
 
         // Block 3: Bob sends 5,000 to Charlie.
-        let tx3 = make_transfer("nova1bob", "nova1charlie", 5_000, 100, 0);
+        let tx3 = make_transfer("nova1bob", "nova1charlie", 5_000, 100, 1);
         mempool.add(tx3).unwrap();
         let p3 = producer.produce_block(&parent, 100).unwrap();
         producer.commit_block(&p3.block).unwrap();
@@ -822,10 +1119,10 @@ mod tests {
         let bob = t.get("nova1bob").unwrap();
         let charlie = t.get("nova1charlie").unwrap();
 
-        // Alice: 100,000 - 10,000 - 20,000 = 70,000
-        assert_eq!(alice.balance, 70_000);
-        // Bob: 10,000 + 20,000 - 5,000 = 25,000
-        assert_eq!(bob.balance, 25_000);
+        // Alice: 100,000 - 10,000 - 20,000 - 2 * 100 fee = 69,800
+        assert_eq!(alice.balance, 69_800);
+        // Bob: 10,000 + 20,000 - 5,000 - 100 fee = 24,900
+        assert_eq!(bob.balance, 24_900);
         // Charlie: 5,000
         assert_eq!(charlie.balance, 5_000);
     }
@@ -869,9 +1166,9 @@ mod tests {
         seed_balance(&tree, "nova1b", 50_000);
         seed_balance(&tree, "nova1c", 50_000);
 
-        let tx1 = make_transfer("nova1a", "nova1d", 1_000, 100, 0);
-        let tx2 = make_transfer("nova1b", "nova1d", 2_000, 200, 0);
-        let tx3 = make_transfer("nova1c", "nova1d", 3_000, 300, 0);
+        let tx1 = make_transfer("nova1a", "nova1d", 1_000, 100, 1);
+        let tx2 = make_transfer("nova1b", "nova1d", 2_000, 200, 1);
+        let tx3 = make_transfer("nova1c", "nova1d", 3_000, 300, 1);
 
         mempool.add(tx1).unwrap();
         mempool.add(tx2).unwrap();
@@ -921,7 +1218,7 @@ mod tests {
             );
         }
 
-        let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 0);
+        let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 1);
         mempool.add(tx.clone()).unwrap();
 
         let produced = producer.produce_block(&genesis, 100).unwrap();
@@ -932,4 +1229,486 @@ mod tests {
         assert!(result.is_some());
         assert!(!result.unwrap().success);
     }
+
+    // -- 21. Committed view only advances on commit --------------------------
+
+    #[test]
+    fn committed_state_hides_block_until_commit() {
+        let (producer, genesis, tree, mempool, db) = setup();
+        let committed = CommittedState::new((*db).clone(), 0, tree.read().root());
+        {
+            let mut t = tree.write();
+            let attached = std::mem::replace(&mut *t, StateTree::new((*db).clone()));
+            *t = attached.with_committed_state(Arc::clone(&committed));
+            t.put("nova1alice", &AccountState::with_balance(10_000));
+            t.commit(0);
+        }
+
+        mempool
+            .add(make_transfer("nova1alice", "nova1bob", 2_500, 10, 1))
+            .unwrap();
+        let produced = producer.produce_block(&genesis, 10).unwrap();
+
+        assert_eq!(tree.read().get("nova1alice").unwrap().balance, 7_490);
+        let snap = committed.snapshot();
+        assert_eq!(snap.height(), 0);
+        assert_eq!(snap.get("nova1alice").unwrap().balance, 10_000);
+        assert!(snap.get("nova1bob").is_none());
+        drop(snap);
+
+        producer.commit_block(&produced.block).unwrap();
+        let snap = committed.snapshot();
+        assert_eq!(snap.height(), 1);
+        assert_eq!(snap.root(), produced.state_root);
+        assert_eq!(snap.get("nova1alice").unwrap().balance, 7_490);
+        assert_eq!(snap.get("nova1bob").unwrap().balance, 2_500);
+    }
+
+    // -- 22. Replayed and out-of-order nonces rejected ----------------------
+
+    #[test]
+    fn replayed_transaction_rejected() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 10_000);
+
+        let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 1);
+        mempool.add(tx.clone()).unwrap();
+        let p1 = producer.produce_block(&genesis, 10).unwrap();
+        producer.commit_block(&p1.block).unwrap();
+        assert_eq!(p1.block.transactions.len(), 1);
+
+        // The same signed transaction, submitted again.
+        mempool.add(tx.clone()).unwrap();
+        let p2 = producer.produce_block(&p1.block, 10).unwrap();
+        assert!(p2.block.transactions.is_empty());
+        let err = p2.tx_results[0].error.as_deref().unwrap();
+        assert!(err.contains("already on chain"), "{err}");
+        producer.commit_block(&p2.block).unwrap();
+
+        // A different transaction reusing the executed nonce.
+        mempool
+            .add(make_transfer("nova1alice", "nova1bob", 500, 100, 1))
+            .unwrap();
+        let p3 = producer.produce_block(&p2.block, 10).unwrap();
+        assert!(p3.block.transactions.is_empty());
+        let err = p3.tx_results[0].error.as_deref().unwrap();
+        assert!(err.contains("nonce mismatch"), "{err}");
+
+        let alice = tree.read().get("nova1alice").unwrap();
+        assert_eq!(alice.balance, 8_900);
+        assert_eq!(alice.nonce, 1);
+    }
+
+    #[test]
+    fn nonce_gap_rejected() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 10_000);
+
+        mempool
+            .add(make_transfer("nova1alice", "nova1bob", 1_000, 100, 3))
+            .unwrap();
+        let produced = producer.produce_block(&genesis, 10).unwrap();
+
+        assert!(produced.block.transactions.is_empty());
+        assert_eq!(tree.read().get("nova1alice").unwrap().balance, 10_000);
+    }
+
+    // -- 23. Fees debited, split between proposer and burn -------------------
+
+    #[test]
+    fn fees_paid_to_proposer_and_burned() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 10_000);
+        seed_balance(&tree, "nova1bob", 10_000);
+
+        mempool
+            .add(make_transfer("nova1alice", "nova1carol", 1_000, 300, 1))
+            .unwrap();
+        mempool
+            .add(make_transfer("nova1bob", "nova1carol", 1_000, 101, 1))
+            .unwrap();
+        let produced = producer.produce_block(&genesis, 10).unwrap();
+        assert_eq!(produced.block.transactions.len(), 2);
+        assert_eq!(produced.block.header.fees, 401);
+        assert!(produced.block.verify().is_ok());
+
+        let t = tree.read();
+        assert_eq!(t.get("nova1alice").unwrap().balance, 8_700);
+        assert_eq!(t.get("nova1bob").unwrap().balance, 8_899);
+        assert_eq!(t.get("nova1carol").unwrap().balance, 2_000);
+
+        let split = FeePolicy::default().split(401);
+        let proposer = t.get(producer.validator_address()).unwrap();
+        assert_eq!(proposer.balance, split.proposer);
+        assert_eq!(split.proposer + split.treasury + split.burned, 401);
+    }
+
+    #[test]
+    fn fee_counts_against_balance() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 1_000);
+
+        // The amount alone is covered, amount plus fee is not.
+        mempool
+            .add(make_transfer("nova1alice", "nova1bob", 1_000, 100, 1))
+            .unwrap();
+        let produced = producer.produce_block(&genesis, 10).unwrap();
+
+        assert!(produced.block.transactions.is_empty());
+        assert_eq!(tree.read().get("nova1alice").unwrap().balance, 1_000);
+        assert!(tree.read().get(producer.validator_address()).is_none());
+    }
+
+    // -- 24. A fixed block clock makes block hashes reproducible ------------
+
+    #[test]
+    fn fixed_clock_reproduces_block_hashes() {
+        let clock = BlockClock::Fixed {
+            genesis_ms: 1_700_000_000_000,
+            interval_ms: 1_000,
+        };
+        let kp = NovaKeypair::from_seed(&[9u8; 32]);
+        let produce = || {
+            let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
+            let tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+            seed_balance(&tree, "nova1alice", 10_000);
+            let mempool = Arc::new(Mempool::new(MempoolConfig::default()));
+            mempool
+                .add(make_transfer("nova1alice", "nova1bob", 3_000, 100, 1))
+                .unwrap();
+            BlockProducer::new(db, tree, mempool, kp.clone())
+                .with_block_clock(clock)
+                .produce_block(&Block::genesis(), 100)
+                .unwrap()
+                .block
+        };
+
+        let (a, b) = (produce(), produce());
+        assert_eq!(a.header.timestamp, 1_700_000_001_000);
+        assert_eq!(a.header.hash, b.header.hash);
+        assert_eq!(a.header.signature, b.header.signature);
+    }
+
+    // -- 25. Clock offset shifts block timestamps ---------------------------
+
+    #[test]
+    fn clock_offset_shifts_timestamps() {
+        let (producer, genesis, _tree, _mempool, _db) = setup();
+        let producer = producer.with_block_clock(BlockClock::Fixed {
+            genesis_ms: 1_000_000,
+            interval_ms: 1_000,
+        });
+
+        producer.set_clock_offset_ms(60_000);
+        let block = producer.produce_block(&genesis, 10).unwrap().block;
+        assert_eq!(block.header.timestamp, 1_061_000);
+        assert!(block.verify().is_ok());
+
+        producer.pin_timestamp(2, 5_000_000);
+        assert_eq!(producer.timestamp_for(2), 5_000_000);
+        assert_eq!(producer.timestamp_for(3), 1_063_000);
+
+        producer.set_clock_offset_ms(-2_000_000);
+        assert_eq!(producer.timestamp_for(1), 0);
+        assert_eq!(producer.timestamp_for(2), 0);
+    }
+
+    // -- 26. The next block carries the parent's commit ---------------------
+
+    #[test]
+    fn next_block_carries_parent_commit() {
+        use crate::network::consensus::{ValidatorSet, Vote};
+        use crate::network::liveness::LivenessRecord;
+
+        let (producer, genesis, tree, _mempool, _db) = setup();
+        let mut set = ValidatorSet::new();
+        set.add_validator(producer.validator_address.clone(), 1_000);
+        set.store_in_state(&mut tree.write());
+
+        let block1 = producer.produce_block(&genesis, 10).unwrap().block;
+        assert!(block1.header.last_commit.is_empty());
+        producer.commit_block(&block1).unwrap();
+
+        let vote = Vote::precommit(&producer.keypair, block1.header.hash, 0);
+        producer.record_commit(&FinalizedBlock {
+            block: block1.clone(),
+            votes: vec![vote],
+            vote_stakes: vec![1_000],
+            epoch: 0,
+            total_stake: 1_000,
+            round: 0,
+        });
+
+        let block2 = producer.produce_block(&block1, 10).unwrap().block;
+        assert_eq!(block2.header.last_commit.len(), 1);
+        assert!(block2.verify().is_ok());
+
+        let record = LivenessRecord::load(&tree.read());
+        let ours = record.get(&producer.validator_address).unwrap();
+        assert_eq!((ours.proposals.len(), ours.votes.len()), (2, 1));
+        assert_eq!(ours.votes.missed(), 0);
+
+        // A commit for some other parent is not carried.
+        let sibling = producer.produce_block(&genesis, 10).unwrap().block;
+        assert!(sibling.header.last_commit.is_empty());
+    }
+
+    // -- 27. Blocks mint the reward the monetary policy schedules ----------
+
+    #[test]
+    fn blocks_mint_scheduled_reward() {
+        use crate::storage::emission::SupplyRecord;
+
+        let (producer, genesis, tree, mempool, _db) = setup();
+        let policy = MonetaryPolicy {
+            epoch_blocks: 4,
+            initial_epoch_emission: 1_002,
+            ..MonetaryPolicy::default()
+        };
+        let producer = producer.with_monetary_policy(policy);
+        seed_balance(&tree, "nova1alice", 10_000);
+        mempool
+            .add(make_transfer("nova1alice", "nova1bob", 1_000, 200, 1))
+            .unwrap();
+
+        let block1 = producer.produce_block(&genesis, 10).unwrap().block;
+        producer.commit_block(&block1).unwrap();
+        producer.produce_block(&block1, 10).unwrap();
+
+        // 1_002 over 4 blocks: 251, 251, 250, 250.
+        let split = FeePolicy::default().split(200);
+        let t = tree.read();
+        let proposer = t.get(producer.validator_address()).unwrap();
+        assert_eq!(proposer.balance, split.proposer + 251 + 251);
+
+        let supply = SupplyRecord::load(&t);
+        assert_eq!(supply.minted, 502);
+        assert_eq!(supply.burned, split.burned);
+    }
+
+    // -- 28. A batch pays every recipient or is dropped whole ----------------
+
+    #[test]
+    fn batch_pays_all_recipients_or_none() {
+        use crate::storage::batch::{BatchEntry, BatchPayout};
+
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1payroll", 100_000);
+        let batch = |payouts: &[(&str, u64)], nonce: u64| {
+            let payload = BatchPayout {
+                payouts: payouts
+                    .iter()
+                    .map(|&(recipient, amount)| BatchEntry {
+                        recipient: recipient.to_string(),
+                        amount,
+                    })
+                    .collect(),
+            };
+            TransactionBuilder::new(TransactionType::Batch)
+                .sender("nova1payroll")
+                .receiver(payouts[0].0)
+                .amount(Amount::new(
+                    payouts.iter().map(|p| p.1).sum(),
+                    Currency::NOVA,
+                ))
+                .fee(300)
+                .nonce(nonce)
+                .timestamp(1_700_000_000_000 + nonce)
+                .payload(serde_json::to_vec(&payload).unwrap())
+                .build_unchecked()
+        };
+
+        mempool
+            .add(batch(&[("nova1alice", 10_000), ("nova1bob", 20_000)], 1))
+            .unwrap();
+        let produced = producer.produce_block(&genesis, 10).unwrap();
+        assert_eq!(produced.block.transactions.len(), 1);
+        assert_eq!(produced.block.header.fees, 300);
+        {
+            let t = tree.read();
+            assert_eq!(t.get("nova1payroll").unwrap().balance, 69_700);
+            assert_eq!(t.get("nova1alice").unwrap().balance, 10_000);
+            assert_eq!(t.get("nova1bob").unwrap().balance, 20_000);
+        }
+        producer.commit_block(&produced.block).unwrap();
+
+        // The second payout would open an account below the existential
+        // deposit, so neither recipient is paid.
+        mempool
+            .add(batch(&[("nova1carol", 10_000), ("nova1dave", 1)], 2))
+            .unwrap();
+        let produced = producer.produce_block(&produced.block, 10).unwrap();
+        assert!(produced.block.transactions.is_empty());
+        assert!(!produced.tx_results[0].success);
+        let t = tree.read();
+        assert_eq!(t.get("nova1payroll").unwrap().balance, 69_700);
+        assert!(t.get("nova1carol").is_none());
+    }
+
+    // -- 29. Expired transactions are dropped at the block timestamp --------
+
+    #[test]
+    fn produce_block_drops_expired_transactions() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 10_000);
+
+        let now = BlockClock::Wall.timestamp_for(0);
+        let mut tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 1);
+        tx.valid_until = Some(now + 60_000);
+        tx.id = tx.compute_id();
+        mempool.add(tx.clone()).unwrap();
+
+        // Still in the mempool, but the block is stamped past the expiry.
+        producer.pin_timestamp(1, now + 120_000);
+        let produced = producer.produce_block(&genesis, 10).unwrap();
+        assert!(produced.block.transactions.is_empty());
+        let result = &produced.tx_results[0];
+        assert!(!result.success);
+        assert!(result.error.as_deref().unwrap().contains("expired"));
+        assert_eq!(tree.read().get("nova1alice").unwrap().balance, 10_000);
+    }
+
+    // -- 30. Committed blocks feed the fee estimator ------------------------
+
+    #[test]
+    fn commit_block_feeds_fee_estimator() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        let estimator = Arc::new(FeeEstimator::new(2));
+        let producer = producer.with_fee_estimator(Arc::clone(&estimator));
+        seed_balance(&tree, "nova1alice", 10_000);
+        seed_balance(&tree, "nova1carol", 10_000);
+
+        mempool
+            .add(make_transfer("nova1alice", "nova1bob", 1_000, 100, 1))
+            .unwrap();
+        mempool
+            .add(make_transfer("nova1carol", "nova1bob", 1_000, 300, 1))
+            .unwrap();
+        let produced = producer.produce_block(&genesis, 2).unwrap();
+        assert_eq!(produced.block.transactions.len(), 2);
+        assert_eq!(estimator.sample_blocks(), 0);
+        producer.commit_block(&produced.block).unwrap();
+
+        // A full block: only fees at or above its cheapest would have made it.
+        assert_eq!(estimator.sample_blocks(), 1);
+        assert_eq!(estimator.estimate(FeePriority::Low), 100);
+    }
+
+    // -- 31. Sponsored transfers charge the fee payer -----------------------
+
+    #[test]
+    fn sponsored_transfer_charges_fee_payer() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 5_000);
+        seed_balance(&tree, "nova1sponsor", 1_000);
+
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(3_000, Currency::NOVA))
+            .fee(100)
+            .fee_payer("nova1sponsor")
+            .nonce(1)
+            .timestamp(1_700_000_000_001)
+            .build_unchecked();
+        mempool.add(tx).unwrap();
+
+        // The balance audit sees the fee payer too, so the block books.
+        let produced = producer.produce_block(&genesis, 100).unwrap();
+        assert!(produced.tx_results.iter().all(|r| r.success));
+
+        let t = tree.read();
+        assert_eq!(t.get("nova1alice").unwrap().balance, 2_000);
+        assert_eq!(t.get("nova1bob").unwrap().balance, 3_000);
+        assert_eq!(t.get("nova1sponsor").unwrap().balance, 900);
+    }
+
+    // -- 32. Commit persists a receipt for every transaction ----------------
+
+    #[test]
+    fn commit_block_persists_receipts() {
+        let (producer, genesis, tree, mempool, db) = setup();
+        seed_balance(&tree, "nova1alice", 10_000);
+
+        let tx = make_transfer("nova1alice", "nova1bob", 1_000, 100, 1);
+        let tx_id = tx.id.clone();
+        mempool.add(tx).unwrap();
+        let produced = producer.produce_block(&genesis, 100).unwrap();
+        assert!(db.get_receipt(&tx_id).unwrap().is_none());
+        producer.commit_block(&produced.block).unwrap();
+
+        let receipt = db.get_receipt(&tx_id).unwrap().unwrap();
+        assert_eq!(receipt.status, TransactionStatus::Confirmed);
+        assert_eq!(receipt.block_height, 1);
+        assert_eq!(receipt.block_hash, produced.block.header.hash_hex());
+        assert_eq!(receipt.fee, 100);
+        assert_eq!(
+            receipt.logs,
+            vec![
+                ReceiptLog::new(LogKind::Debit, "nova1alice", 1_000),
+                ReceiptLog::new(LogKind::Credit, "nova1bob", 1_000),
+                ReceiptLog::new(LogKind::Fee, "nova1alice", 100),
+            ]
+        );
+        assert!(receipt.verify_integrity());
+    }
+
+    // -- 33. Confidential transfers need a balance verifier ------------------
+
+    #[test]
+    fn confidential_transfer_without_verifier_is_dropped() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 1_000);
+
+        let tx = TransactionBuilder::new(TransactionType::ConfidentialTransfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(0, Currency::NOVA))
+            .fee(100)
+            .nonce(1)
+            .timestamp(1_700_000_000_001)
+            .build_unchecked()
+            .with_proof(vec![0xAA; 128])
+            .with_commitment(vec![0xBB; 64]);
+        mempool.add(tx).unwrap();
+
+        // No longer a no-op: nothing vouches for the proofs, so the
+        // transaction neither pays its fee nor consumes its nonce.
+        let produced = producer.produce_block(&genesis, 100).unwrap();
+        assert!(produced.block.transactions.is_empty());
+        assert!(produced.tx_results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("no balance verifier"));
+        let alice = tree.read().get("nova1alice").unwrap();
+        assert_eq!((alice.balance, alice.nonce), (1_000, 0));
+    }
+
+    // -- 34. Selection stops at the block byte budget -----------------------
+
+    #[test]
+    fn produce_block_respects_byte_budget() {
+        let (producer, genesis, tree, mempool, _db) = setup();
+        seed_balance(&tree, "nova1alice", 100_000);
+
+        let txs: Vec<Transaction> = (1..=3)
+            .map(|nonce| make_transfer("nova1alice", "nova1bob", 1_000, 100, nonce))
+            .collect();
+        let two = limits::tx_weight(&txs[0]) + limits::tx_weight(&txs[1]);
+        for tx in txs {
+            mempool.add(tx).unwrap();
+        }
+
+        // Room for two transactions and a little more, not for three.
+        let producer = producer.with_max_block_bytes(two + 10);
+        let produced = producer.produce_block(&genesis, 100).unwrap();
+        assert_eq!(produced.block.transactions.len(), 2);
+        assert_eq!(produced.weight, two);
+        assert_eq!(produced.tx_results.len(), 2);
+
+        // The third waits for the next block.
+        producer.commit_block(&produced.block).unwrap();
+        assert_eq!(mempool.size(), 1);
+    }
 }
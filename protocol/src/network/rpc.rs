@@ -15,16 +15,23 @@
 //! | `nova_getBalance`          | Query token balance for an address    |
 //! | `nova_sendTransaction`     | Submit a signed transaction           |
 //! | `nova_getTransaction`      | Retrieve a transaction by hash/ID     |
+//! | `nova_getTransactionReceipt` | Receipt of a committed transaction  |
 //! | `nova_getBlock`            | Retrieve a block by height or hash    |
 //! | `nova_getBlockHeight`      | Current chain height                  |
 //! | `nova_getAccountState`     | Full account state (balance, nonce, etc.) |
 //! | `nova_getValidators`       | Active validator set                  |
-//! | `nova_estimateFee`         | Estimate fee for a transaction        |
+//! | `nova_estimateFee`         | Suggested fee from recent blocks      |
 //! | `nova_getCreditOffers`     | Query available credit offers         |
+//! | `nova_getSupply`           | Current and projected NOVA supply     |
+//! | `nova_getNonce`            | Next nonce an address should sign with |
+//! | `nova_getCapabilities`     | Features this node supports            |
+//! | `nova_pendingTransactions` | Page through the node's mempool        |
 
 use serde::{Deserialize, Serialize};
 
+use crate::encoding;
 use crate::network::consensus::ValidatorInfo;
+use crate::transaction::fee_estimator::{FeePriority, FeeTiers};
 
 // ---------------------------------------------------------------------------
 // RPC Method Enumeration
@@ -48,6 +55,10 @@ pub enum RpcMethod {
     /// Parameters: `(tx_hash: String)`
     #[serde(rename = "nova_getTransaction")]
     GetTransaction,
+    /// Retrieve the receipt stored when a transaction was committed.
+    /// Parameters: `(tx_hash: String)`
+    #[serde(rename = "nova_getTransactionReceipt")]
+    GetTransactionReceipt,
     /// Retrieve a block by height (u64) or hash (hex string).
     /// Parameters: `(height_or_hash: String)`
     #[serde(rename = "nova_getBlock")]
@@ -72,6 +83,29 @@ pub enum RpcMethod {
     /// Parameters: `(address: String, amount: u64)`
     #[serde(rename = "nova_getCreditOffers")]
     GetCreditOffers,
+    /// Check a signed message ("sign in with NOVA").
+    /// Parameters: `(signed: SignedMessage)`
+    #[serde(rename = "nova_verifyMessage")]
+    VerifyMessage,
+    /// Get the circulating supply, and the supply projected at a height.
+    /// Parameters: `(height?: u64)`, defaulting to the end of the current
+    /// emission epoch.
+    #[serde(rename = "nova_getSupply")]
+    GetSupply,
+    /// Get the nonce the next transaction from an address must carry.
+    /// Parameters: `(address: String)`
+    #[serde(rename = "nova_getNonce")]
+    GetNonce,
+    /// Get the features this node supports (see
+    /// [`crate::network::capabilities`]).
+    /// Parameters: none.
+    #[serde(rename = "nova_getCapabilities")]
+    GetCapabilities,
+    /// List the transactions waiting in the node's mempool, highest fee
+    /// density first.
+    /// Parameters: `({offset?: u64, limit?: u64})`
+    #[serde(rename = "nova_pendingTransactions")]
+    PendingTransactions,
 }
 
 // ---------------------------------------------------------------------------
@@ -124,6 +158,10 @@ pub struct RpcResponse {
     /// The error, if the method failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<RpcError>,
+    /// Wire schema version of `result` (see [`crate::encoding`]). Reads as
+    /// `0` from nodes that predate versioning.
+    #[serde(default)]
+    pub schema_version: u16,
 }
 
 impl RpcResponse {
@@ -134,6 +172,7 @@ impl RpcResponse {
             id,
             result: Some(result),
             error: None,
+            schema_version: encoding::SCHEMA_VERSION,
         }
     }
 
@@ -144,6 +183,7 @@ impl RpcResponse {
             id,
             result: None,
             error: Some(error),
+            schema_version: encoding::SCHEMA_VERSION,
         }
     }
 }
@@ -276,7 +316,11 @@ pub struct BalanceResponse {
     /// Token identifier.
     pub token_id: String,
     /// Current balance in the smallest denomination.
+    #[serde(with = "encoding::amount")]
     pub balance: u64,
+    /// Height of the committed block the balance was read at.
+    #[serde(default)]
+    pub height: u64,
 }
 
 /// Response payload for `nova_getBlockHeight`.
@@ -292,16 +336,122 @@ pub struct ValidatorsResponse {
     /// Active validators with their stake info.
     pub validators: Vec<ValidatorInfo>,
     /// Total stake across all active validators.
+    #[serde(with = "encoding::amount")]
     pub total_stake: u64,
 }
 
 /// Response payload for `nova_estimateFee`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeeEstimateResponse {
-    /// Estimated fee in photons.
+    /// Priority the estimate is for.
+    pub priority: FeePriority,
+    /// Estimated fee in photons: the fee of `priority`'s tier.
+    #[serde(with = "encoding::amount")]
     pub estimated_fee: u64,
-    /// Fee per byte at current network conditions.
+    /// Economy, standard and fast suggestions with their expected waits.
+    pub tiers: FeeTiers,
+    /// Committed blocks the estimate was drawn from.
+    pub sample_blocks: u64,
+}
+
+/// Response payload for `nova_getSupply`. Amounts are photons.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplyResponse {
+    /// Height of the committed block the supply was read at.
+    pub height: u64,
+    /// Supply at genesis.
+    #[serde(with = "encoding::amount")]
+    pub initial_supply: u64,
+    /// Minted as block rewards since genesis.
+    #[serde(with = "encoding::amount")]
+    pub minted: u64,
+    /// Burned from fees since genesis.
+    #[serde(with = "encoding::amount")]
+    pub burned: u64,
+    /// `initial_supply + minted - burned`.
+    #[serde(with = "encoding::amount")]
+    pub circulating: u64,
+    /// Allocated to accounts at genesis.
+    #[serde(with = "encoding::amount", default)]
+    pub genesis: u64,
+    /// Locked by authorization holds. Part of the circulating supply, but
+    /// not spendable by its owners.
+    #[serde(with = "encoding::amount", default)]
+    pub locked: u64,
+    /// Bonded by the validator set. Tracked beside balances, not in them.
+    #[serde(with = "encoding::amount", default)]
+    pub staked: u64,
+    /// Emission epoch of the next block.
+    pub epoch: u64,
+    /// Emission over that epoch.
+    #[serde(with = "encoding::amount")]
+    pub epoch_emission: u64,
+    /// Reward of the next block.
+    #[serde(with = "encoding::amount")]
+    pub block_reward: u64,
+    /// Height the projection is for.
+    pub projected_height: u64,
+    /// Circulating supply at `projected_height` if every scheduled reward
+    /// is minted and nothing more is burned.
+    #[serde(with = "encoding::amount")]
+    pub projected_supply: u64,
+}
+
+/// Response payload for `nova_getNonce`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceResponse {
+    /// The address queried.
+    pub address: String,
+    /// Nonce of the address's last committed transaction (0 if none).
+    pub nonce: u64,
+    /// Transactions from the address waiting in the mempool with
+    /// consecutive nonces after `nonce`.
+    pub pending: u64,
+    /// Nonce the next transaction must carry: `nonce + pending + 1`.
+    pub next_nonce: u64,
+    /// Height of the committed block `nonce` was read at.
+    pub height: u64,
+}
+
+/// One mempool entry in a `nova_pendingTransactions` listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    /// Transaction ID.
+    #[serde(with = "encoding::hex_string")]
+    pub hash: String,
+    /// Sender address.
+    pub sender: String,
+    /// Recipient address.
+    pub recipient: String,
+    /// Sender nonce the transaction carries.
+    pub nonce: u64,
+    /// Transfer amount in photons.
+    #[serde(with = "encoding::amount")]
+    pub amount: u64,
+    /// Fee offered in photons.
+    #[serde(with = "encoding::amount")]
+    pub fee: u64,
+    /// Fee divided by the encoded size; the pool's priority key.
     pub fee_per_byte: u64,
+    /// Sender-supplied creation time (Unix milliseconds).
+    pub timestamp: u64,
+    /// When this node admitted the transaction (Unix seconds).
+    pub added_at: u64,
+    /// Seconds the transaction has been waiting in the pool.
+    pub age_secs: u64,
+}
+
+/// Response payload for `nova_pendingTransactions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTransactionsResponse {
+    /// Transactions waiting in the pool, all pages included.
+    pub size: u64,
+    /// Position of the first listed transaction.
+    pub offset: u64,
+    /// Offset of the next page, or `None` if this page reaches the end.
+    pub next_offset: Option<u64>,
+    /// The listed transactions, highest fee density first.
+    pub transactions: Vec<PendingTransaction>,
 }
 
 /// Response payload for `nova_getCreditOffers`.
@@ -372,12 +522,18 @@ mod tests {
             RpcMethod::GetBalance,
             RpcMethod::SendTransaction,
             RpcMethod::GetTransaction,
+            RpcMethod::GetTransactionReceipt,
             RpcMethod::GetBlock,
             RpcMethod::GetBlockHeight,
             RpcMethod::GetAccountState,
             RpcMethod::GetValidators,
             RpcMethod::EstimateFee,
             RpcMethod::GetCreditOffers,
+            RpcMethod::VerifyMessage,
+            RpcMethod::GetSupply,
+            RpcMethod::GetNonce,
+            RpcMethod::GetCapabilities,
+            RpcMethod::PendingTransactions,
         ];
 
         for method in methods {
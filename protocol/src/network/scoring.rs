@@ -0,0 +1,451 @@
+//! # Gossipsub Peer Scoring
+//!
+//! Gossipsub scores every peer it talks to and stops cooperating with the
+//! ones that misbehave. This module holds NOVA's parameters for that score
+//! and the glue that feeds our own view of each peer into it.
+//!
+//! ## Topic Parameters
+//!
+//! Each topic weighs a peer's time in the mesh, the messages it was first
+//! to deliver, the messages it failed to deliver, and the invalid messages
+//! it sent. A forged vote is worse than a bad transaction, so invalid
+//! messages cost most on the votes and proposals topics. Mesh delivery
+//! penalties only apply to blocks and votes, which every healthy peer
+//! relays at a steady rate; transaction traffic is too bursty to judge.
+//!
+//! ## Application Score
+//!
+//! The [`PeerStore`] remembers which peers connect reliably and which ones
+//! an operator banned. [`refresh_scores`] turns that into gossipsub's
+//! application-specific score on every call, and records the resulting
+//! effective scores in a [`PeerScoreBoard`] the node serves at `/peers`.
+//!
+//! ## Thresholds
+//!
+//! | Score below      | Effect                                         |
+//! |------------------|------------------------------------------------|
+//! | `gossip` (−10)   | No gossip to or from the peer                  |
+//! | `publish` (−50)  | Our own messages are not flood-published to it |
+//! | `graylist` (−80) | Everything the peer sends is ignored           |
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use libp2p::gossipsub::{self, IdentTopic, PeerScoreParams, PeerScoreThresholds, TopicScoreParams};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use super::gossip::GossipTopics;
+use super::peer_store::{PeerRecord, PeerStore};
+
+/// Application score of a banned peer: far enough below the graylist
+/// threshold that nothing it does can bring it back.
+pub const BANNED_APPLICATION_SCORE: f64 = -1_000.0;
+
+/// Application score range for unbanned peers, from `−range` (every dial
+/// failed) to `+range` (every dial succeeded).
+pub const APPLICATION_SCORE_RANGE: f64 = 10.0;
+
+// ---------------------------------------------------------------------------
+// Thresholds
+// ---------------------------------------------------------------------------
+
+/// Score thresholds at which gossipsub restricts a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreThresholds {
+    /// Below this, no gossip is emitted to or accepted from the peer.
+    pub gossip: f64,
+    /// Below this, our own messages are not flood-published to the peer.
+    pub publish: f64,
+    /// Below this, every message from the peer is ignored.
+    pub graylist: f64,
+    /// Peer exchange is only accepted from peers above this.
+    pub accept_px: f64,
+    /// Median mesh score below which gossipsub grafts better peers.
+    pub opportunistic_graft: f64,
+}
+
+impl Default for ScoreThresholds {
+    fn default() -> Self {
+        Self {
+            gossip: -10.0,
+            publish: -50.0,
+            graylist: -80.0,
+            accept_px: 10.0,
+            opportunistic_graft: 20.0,
+        }
+    }
+}
+
+impl ScoreThresholds {
+    /// The gossipsub form of these thresholds.
+    pub fn to_gossipsub(&self) -> PeerScoreThresholds {
+        PeerScoreThresholds {
+            gossip_threshold: self.gossip,
+            publish_threshold: self.publish,
+            graylist_threshold: self.graylist,
+            accept_px_threshold: self.accept_px,
+            opportunistic_graft_threshold: self.opportunistic_graft,
+        }
+    }
+
+    /// How gossipsub treats a peer with `score`.
+    pub fn standing(&self, score: f64) -> PeerStanding {
+        if score < self.graylist {
+            PeerStanding::Graylisted
+        } else if score < self.publish {
+            PeerStanding::NoPublish
+        } else if score < self.gossip {
+            PeerStanding::NoGossip
+        } else {
+            PeerStanding::Good
+        }
+    }
+}
+
+/// Where a peer's score puts it relative to the [`ScoreThresholds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerStanding {
+    /// At or above the gossip threshold.
+    Good,
+    /// Below the gossip threshold.
+    NoGossip,
+    /// Below the publish threshold.
+    NoPublish,
+    /// Below the graylist threshold.
+    Graylisted,
+}
+
+// ---------------------------------------------------------------------------
+// Topic Parameters
+// ---------------------------------------------------------------------------
+
+/// Score parameters for one topic. Decays and windows use gossipsub's
+/// defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TopicScoring {
+    /// Weight of this topic in the peer's total score.
+    pub topic_weight: f64,
+    /// Reward per second in the mesh, capped at an hour.
+    pub time_in_mesh_weight: f64,
+    /// Reward per message the peer delivered first.
+    pub first_message_deliveries_weight: f64,
+    /// Cap on the first-delivery counter.
+    pub first_message_deliveries_cap: f64,
+    /// Penalty (≤ 0) for mesh peers delivering fewer messages than
+    /// `mesh_message_deliveries_threshold`. Zero disables it.
+    pub mesh_message_deliveries_weight: f64,
+    /// Messages a mesh peer is expected to deliver per decay interval.
+    pub mesh_message_deliveries_threshold: f64,
+    /// Penalty (≤ 0) per invalid message, applied squared.
+    pub invalid_message_deliveries_weight: f64,
+}
+
+impl TopicScoring {
+    /// The gossipsub form of these parameters.
+    pub fn to_gossipsub(&self) -> TopicScoreParams {
+        let mesh_deliveries = self.mesh_message_deliveries_weight != 0.0;
+        TopicScoreParams {
+            topic_weight: self.topic_weight,
+            time_in_mesh_weight: self.time_in_mesh_weight,
+            time_in_mesh_quantum: Duration::from_secs(1),
+            time_in_mesh_cap: 3_600.0,
+            first_message_deliveries_weight: self.first_message_deliveries_weight,
+            first_message_deliveries_cap: self.first_message_deliveries_cap,
+            mesh_message_deliveries_weight: self.mesh_message_deliveries_weight,
+            mesh_message_deliveries_threshold: if mesh_deliveries {
+                self.mesh_message_deliveries_threshold
+            } else {
+                1.0
+            },
+            mesh_message_deliveries_cap: (self.mesh_message_deliveries_threshold * 10.0).max(1.0),
+            // A peer pruned while under-delivering keeps the penalty.
+            mesh_failure_penalty_weight: self.mesh_message_deliveries_weight,
+            invalid_message_deliveries_weight: self.invalid_message_deliveries_weight,
+            ..TopicScoreParams::default()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Peer Scoring
+// ---------------------------------------------------------------------------
+
+/// NOVA's gossipsub peer scoring configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerScoring {
+    /// Whether peer scoring is enabled at all.
+    pub enabled: bool,
+    /// Thresholds at which peers are restricted.
+    pub thresholds: ScoreThresholds,
+    /// Weight of the application score (see [`application_score`]).
+    pub app_specific_weight: f64,
+    /// Penalty (≤ 0) for protocol misbehaviour such as broken promises
+    /// and GRAFT floods.
+    pub behaviour_penalty_weight: f64,
+    /// Parameters for the transactions topic.
+    pub transactions: TopicScoring,
+    /// Parameters for the blocks topic.
+    pub blocks: TopicScoring,
+    /// Parameters for the votes topic.
+    pub votes: TopicScoring,
+    /// Parameters for the proposals topic.
+    pub proposals: TopicScoring,
+    /// Parameters for the channel advertisements topic.
+    pub channels: TopicScoring,
+}
+
+impl Default for PeerScoring {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            thresholds: ScoreThresholds::default(),
+            app_specific_weight: 1.0,
+            behaviour_penalty_weight: -10.0,
+            transactions: TopicScoring {
+                topic_weight: 0.5,
+                time_in_mesh_weight: 0.01,
+                first_message_deliveries_weight: 0.5,
+                first_message_deliveries_cap: 100.0,
+                mesh_message_deliveries_weight: 0.0,
+                mesh_message_deliveries_threshold: 0.0,
+                invalid_message_deliveries_weight: -10.0,
+            },
+            blocks: TopicScoring {
+                topic_weight: 1.0,
+                time_in_mesh_weight: 0.01,
+                first_message_deliveries_weight: 1.0,
+                first_message_deliveries_cap: 20.0,
+                mesh_message_deliveries_weight: -1.0,
+                mesh_message_deliveries_threshold: 1.0,
+                invalid_message_deliveries_weight: -50.0,
+            },
+            votes: TopicScoring {
+                topic_weight: 1.0,
+                time_in_mesh_weight: 0.01,
+                first_message_deliveries_weight: 0.5,
+                first_message_deliveries_cap: 50.0,
+                mesh_message_deliveries_weight: -0.5,
+                mesh_message_deliveries_threshold: 2.0,
+                invalid_message_deliveries_weight: -100.0,
+            },
+            proposals: TopicScoring {
+                topic_weight: 1.0,
+                time_in_mesh_weight: 0.01,
+                first_message_deliveries_weight: 1.0,
+                first_message_deliveries_cap: 10.0,
+                mesh_message_deliveries_weight: 0.0,
+                mesh_message_deliveries_threshold: 0.0,
+                invalid_message_deliveries_weight: -100.0,
+            },
+            channels: TopicScoring {
+                topic_weight: 0.2,
+                time_in_mesh_weight: 0.01,
+                first_message_deliveries_weight: 0.2,
+                first_message_deliveries_cap: 50.0,
+                mesh_message_deliveries_weight: 0.0,
+                mesh_message_deliveries_threshold: 0.0,
+                invalid_message_deliveries_weight: -10.0,
+            },
+        }
+    }
+}
+
+impl PeerScoring {
+    /// Gossipsub score parameters for `topics`.
+    pub fn params(&self, topics: &GossipTopics) -> PeerScoreParams {
+        let mut params = PeerScoreParams {
+            app_specific_weight: self.app_specific_weight,
+            behaviour_penalty_weight: self.behaviour_penalty_weight,
+            ..PeerScoreParams::default()
+        };
+        let per_topic = [
+            (&topics.transactions, &self.transactions),
+            (&topics.blocks, &self.blocks),
+            (&topics.votes, &self.votes),
+            (&topics.proposals, &self.proposals),
+            (&topics.channels, &self.channels),
+        ];
+        for (topic, scoring) in per_topic {
+            params
+                .topics
+                .insert(IdentTopic::new(topic).hash(), scoring.to_gossipsub());
+        }
+        params
+    }
+
+    /// Checks the parameters and thresholds are ones gossipsub accepts.
+    pub fn validate(&self, topics: &GossipTopics) -> Result<(), String> {
+        self.params(topics).validate()?;
+        self.thresholds.to_gossipsub().validate()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Application Score
+// ---------------------------------------------------------------------------
+
+/// The application score for a peer the [`PeerStore`] knows: from
+/// `−APPLICATION_SCORE_RANGE` to `+APPLICATION_SCORE_RANGE` by dial
+/// reliability, never negative for a pinned peer, and
+/// [`BANNED_APPLICATION_SCORE`] for a banned one.
+pub fn application_score(record: &PeerRecord) -> f64 {
+    if record.banned {
+        return BANNED_APPLICATION_SCORE;
+    }
+    let score = (record.reliability() - 0.5) * 2.0 * APPLICATION_SCORE_RANGE;
+    if record.pinned {
+        score.max(0.0)
+    } else {
+        score
+    }
+}
+
+/// One peer's effective gossipsub score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerScore {
+    /// libp2p peer ID (base58).
+    pub peer_id: String,
+    /// Total score, as gossipsub computes it.
+    pub score: f64,
+    /// The application score injected from the peer store.
+    pub application_score: f64,
+    /// What the score means for the peer.
+    pub standing: PeerStanding,
+}
+
+/// The most recent effective scores of connected peers, shared between
+/// the swarm loop that computes them and the API that serves them.
+#[derive(Debug, Default)]
+pub struct PeerScoreBoard {
+    scores: RwLock<HashMap<String, PeerScore>>,
+}
+
+impl PeerScoreBoard {
+    /// Replaces the board with `scores`.
+    pub fn replace(&self, scores: impl IntoIterator<Item = PeerScore>) {
+        *self.scores.write() = scores.into_iter().map(|s| (s.peer_id.clone(), s)).collect();
+    }
+
+    /// All scores, best first.
+    pub fn list(&self) -> Vec<PeerScore> {
+        let mut scores: Vec<PeerScore> = self.scores.read().values().cloned().collect();
+        scores.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.peer_id.cmp(&b.peer_id))
+        });
+        scores
+    }
+
+    /// The score of one peer, if it is connected.
+    pub fn get(&self, peer_id: &str) -> Option<PeerScore> {
+        self.scores.read().get(peer_id).cloned()
+    }
+}
+
+/// Injects the application score of every connected peer into `gossipsub`
+/// and records the resulting effective scores on `board`.
+///
+/// Call from the swarm loop once per gossipsub heartbeat.
+pub fn refresh_scores(
+    gossipsub: &mut gossipsub::Behaviour,
+    store: &PeerStore,
+    thresholds: &ScoreThresholds,
+    board: &PeerScoreBoard,
+) {
+    let peers: Vec<_> = gossipsub.all_peers().map(|(peer, _)| *peer).collect();
+    let scores = peers.into_iter().map(|peer| {
+        let peer_id = peer.to_base58();
+        let application_score = store
+            .get(&peer_id)
+            .ok()
+            .flatten()
+            .map_or(0.0, |record| application_score(&record));
+        gossipsub.set_application_score(&peer, application_score);
+        let score = gossipsub.peer_score(&peer).unwrap_or(application_score);
+        PeerScore {
+            peer_id,
+            score,
+            application_score,
+            standing: thresholds.standing(score),
+        }
+    });
+    board.replace(scores.collect::<Vec<_>>());
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::gossipsub::MessageAuthenticity;
+
+    #[test]
+    fn defaults_are_accepted_by_gossipsub() {
+        let scoring = PeerScoring::default();
+        let topics = GossipTopics::default();
+        scoring.validate(&topics).unwrap();
+
+        let params = scoring.params(&topics);
+        assert_eq!(params.topics.len(), 5);
+        let votes = &params.topics[&IdentTopic::new(&topics.votes).hash()];
+        let txs = &params.topics[&IdentTopic::new(&topics.transactions).hash()];
+        assert!(votes.invalid_message_deliveries_weight < txs.invalid_message_deliveries_weight);
+
+        let keypair = libp2p::identity::Keypair::generate_ed25519();
+        let mut behaviour = gossipsub::Behaviour::new(
+            MessageAuthenticity::Signed(keypair),
+            gossipsub::Config::default(),
+        )
+        .unwrap();
+        behaviour
+            .with_peer_score(params, scoring.thresholds.to_gossipsub())
+            .unwrap();
+    }
+
+    #[test]
+    fn application_score_follows_the_peer_store() {
+        let mut record = PeerRecord::new("12D3KooWPeer", "/ip4/1.2.3.4/tcp/9740", 0);
+        assert_eq!(application_score(&record), 0.0);
+
+        record.successes = 98;
+        assert!(application_score(&record) > 9.0);
+        record.successes = 0;
+        record.failures = 98;
+        assert!(application_score(&record) < -9.0);
+        record.pinned = true;
+        assert_eq!(application_score(&record), 0.0);
+
+        record.banned = true;
+        let thresholds = ScoreThresholds::default();
+        assert_eq!(
+            thresholds.standing(application_score(&record)),
+            PeerStanding::Graylisted
+        );
+        assert_eq!(thresholds.standing(-20.0), PeerStanding::NoGossip);
+        assert_eq!(thresholds.standing(-60.0), PeerStanding::NoPublish);
+        assert_eq!(thresholds.standing(5.0), PeerStanding::Good);
+    }
+
+    #[test]
+    fn board_lists_best_first() {
+        let board = PeerScoreBoard::default();
+        let score = |peer_id: &str, score: f64| PeerScore {
+            peer_id: peer_id.to_string(),
+            score,
+            application_score: 0.0,
+            standing: ScoreThresholds::default().standing(score),
+        };
+        board.replace([score("a", -90.0), score("b", 12.5), score("c", 0.0)]);
+        let ids: Vec<_> = board.list().into_iter().map(|s| s.peer_id).collect();
+        assert_eq!(ids, ["b", "c", "a"]);
+        assert_eq!(board.get("a").unwrap().standing, PeerStanding::Graylisted);
+
+        board.replace([]);
+        assert!(board.list().is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+//! # Address Screening
+//!
+//! Regulated gateway operators must refuse service to addresses on
+//! sanctions or internal deny lists. A [`Screener`] attached to the
+//! [`Mempool`](super::mempool::Mempool) checks the sender and receiver of
+//! every transaction before admission — API submissions, gossip and reorg
+//! readmissions alike, since they all enter through
+//! [`Mempool::add`](super::mempool::Mempool::add).
+//!
+//! ## Providers
+//!
+//! Lists come from a [`ScreeningProvider`]. [`StaticListProvider`] reads a
+//! plain text file; the node binary adds an HTTP provider for external
+//! screening services. Providers answer per address and may fail, e.g.
+//! when a remote service is down.
+//!
+//! ## Behavior
+//!
+//! | Provider says | `Block`                  | `Flag`                 |
+//! |---------------|--------------------------|------------------------|
+//! | clear         | admit                    | admit                  |
+//! | listed        | reject (`Screened`)      | admit, record the hit  |
+//! | error         | reject unless fail-open  | reject unless fail-open |
+//!
+//! Every hit and every provider failure is appended to the audit log when
+//! one is attached. Clear results are not recorded.
+//!
+//! Nodes without a screener pay nothing: the mempool holds an `Option`
+//! and skips the check entirely.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::storage::audit::{AuditCategory, AuditLog};
+use crate::transaction::Transaction;
+
+// ---------------------------------------------------------------------------
+// Providers
+// ---------------------------------------------------------------------------
+
+/// Why a provider could not answer.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ScreeningError {
+    /// The provider could not be reached or gave an unusable answer.
+    #[error("screening provider unavailable: {0}")]
+    Unavailable(String),
+    /// A list could not be loaded.
+    #[error("invalid screening list: {0}")]
+    InvalidList(String),
+}
+
+/// A source of listed addresses.
+pub trait ScreeningProvider: Send + Sync {
+    /// Short name recorded with every decision, e.g. `ofac-list`.
+    fn name(&self) -> &str;
+
+    /// Returns `Some(reason)` if `address` is listed, `None` if it is
+    /// clear. Called on the admission path: implementations that do I/O
+    /// should bound it with a timeout and cache answers.
+    fn screen(&self, address: &str) -> Result<Option<String>, ScreeningError>;
+}
+
+/// A fixed list of addresses, matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct StaticListProvider {
+    name: String,
+    /// Lowercased address → reason.
+    listed: HashMap<String, String>,
+}
+
+impl StaticListProvider {
+    /// A provider over `entries` of `(address, reason)`.
+    pub fn new<A, R>(name: impl Into<String>, entries: impl IntoIterator<Item = (A, R)>) -> Self
+    where
+        A: AsRef<str>,
+        R: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            listed: entries
+                .into_iter()
+                .map(|(address, reason)| (address.as_ref().to_lowercase(), reason.into()))
+                .collect(),
+        }
+    }
+
+    /// Parses a list: one address per line, optionally followed by
+    /// whitespace and a reason. Blank lines and lines starting with `#`
+    /// are skipped.
+    ///
+    /// ```text
+    /// # 2026-03 SDN update
+    /// nova1qxy...   SDN-12345
+    /// nova1zzq...
+    /// ```
+    pub fn parse(name: impl Into<String>, text: &str) -> Self {
+        let entries = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| match line.split_once(char::is_whitespace) {
+                Some((address, reason)) => (address, reason.trim().to_string()),
+                None => (line, "listed".to_string()),
+            });
+        Self::new(name, entries)
+    }
+
+    /// Loads a list file (see [`parse`](Self::parse)), named after the file.
+    pub fn from_file(path: &Path) -> Result<Self, ScreeningError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ScreeningError::InvalidList(format!("{}: {}", path.display(), e)))?;
+        let name = path
+            .file_name()
+            .map_or_else(|| "list".to_string(), |n| n.to_string_lossy().into_owned());
+        Ok(Self::parse(name, &text))
+    }
+
+    /// Number of listed addresses.
+    pub fn len(&self) -> usize {
+        self.listed.len()
+    }
+
+    /// Returns `true` if nothing is listed.
+    pub fn is_empty(&self) -> bool {
+        self.listed.is_empty()
+    }
+}
+
+impl ScreeningProvider for StaticListProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn screen(&self, address: &str) -> Result<Option<String>, ScreeningError> {
+        Ok(self.listed.get(&address.to_lowercase()).cloned())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Screener
+// ---------------------------------------------------------------------------
+
+/// What to do with a transaction touching a listed address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScreeningAction {
+    /// Refuse it.
+    #[default]
+    Block,
+    /// Admit it, but record the hit for review.
+    Flag,
+}
+
+/// A transaction refused by screening.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{role} {address} refused by screening: {reason}")]
+pub struct ScreeningRejection {
+    /// The screened address.
+    pub address: String,
+    /// Which side of the transaction it was on: `sender` or `receiver`.
+    pub role: &'static str,
+    /// The provider's reason, or why it could not answer.
+    pub reason: String,
+}
+
+/// Applies a provider's answers to transactions.
+pub struct Screener {
+    provider: Arc<dyn ScreeningProvider>,
+    action: ScreeningAction,
+    fail_open: bool,
+    audit: Option<Arc<AuditLog>>,
+}
+
+impl fmt::Debug for Screener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Screener")
+            .field("provider", &self.provider.name())
+            .field("action", &self.action)
+            .field("fail_open", &self.fail_open)
+            .finish()
+    }
+}
+
+impl Screener {
+    /// Screens with `provider`, fail-closed: provider errors reject.
+    pub fn new(provider: Arc<dyn ScreeningProvider>, action: ScreeningAction) -> Self {
+        Self {
+            provider,
+            action,
+            fail_open: false,
+            audit: None,
+        }
+    }
+
+    /// Admit transactions when the provider cannot answer, instead of
+    /// refusing them.
+    pub fn with_fail_open(mut self, fail_open: bool) -> Self {
+        self.fail_open = fail_open;
+        self
+    }
+
+    /// Record hits and provider failures in `audit`.
+    pub fn with_audit_log(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// The configured action for listed addresses.
+    pub fn action(&self) -> ScreeningAction {
+        self.action
+    }
+
+    /// Screens both parties of `tx`.
+    pub fn screen_transaction(&self, tx: &Transaction) -> Result<(), ScreeningRejection> {
+        for (role, address) in [("sender", &tx.sender), ("receiver", &tx.receiver)] {
+            let rejection = |reason: String| ScreeningRejection {
+                address: address.clone(),
+                role,
+                reason,
+            };
+            match self.provider.screen(address) {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    let blocked = self.action == ScreeningAction::Block;
+                    self.record(
+                        if blocked { "blocked" } else { "flagged" },
+                        tx,
+                        role,
+                        address,
+                        &reason,
+                    );
+                    if blocked {
+                        return Err(rejection(reason));
+                    }
+                }
+                Err(e) => {
+                    let reason = e.to_string();
+                    self.record(
+                        if self.fail_open {
+                            "unavailable_admitted"
+                        } else {
+                            "unavailable_blocked"
+                        },
+                        tx,
+                        role,
+                        address,
+                        &reason,
+                    );
+                    if !self.fail_open {
+                        return Err(rejection(reason));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&self, action: &str, tx: &Transaction, role: &str, address: &str, reason: &str) {
+        tracing::warn!(
+            provider = self.provider.name(),
+            tx = %tx.id,
+            role,
+            address,
+            reason,
+            "screening {}",
+            action
+        );
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let details = format!("tx={} {}={} reason={}", tx.id, role, address, reason);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        if let Err(e) = audit.append(
+            AuditCategory::Screening,
+            action,
+            self.provider.name(),
+            &details,
+            now,
+        ) {
+            tracing::error!(error = %e, "failed to record screening decision");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::NovaDB;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+    use crate::transaction::TransactionType;
+
+    fn transfer(sender: &str, receiver: &str) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender(sender)
+            .receiver(receiver)
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(10)
+            .nonce(1)
+            .timestamp(1_700_000_000_000)
+            .build_unchecked()
+    }
+
+    struct Offline;
+
+    impl ScreeningProvider for Offline {
+        fn name(&self) -> &str {
+            "offline"
+        }
+
+        fn screen(&self, _address: &str) -> Result<Option<String>, ScreeningError> {
+            Err(ScreeningError::Unavailable("connection refused".into()))
+        }
+    }
+
+    #[test]
+    fn list_file_format() {
+        let list = StaticListProvider::parse(
+            "sdn",
+            "# comment\n\n  nova1BAD   SDN-1 program X\nnova1worse\n",
+        );
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            list.screen("nova1bad").unwrap().as_deref(),
+            Some("SDN-1 program X")
+        );
+        assert_eq!(
+            list.screen("NOVA1WORSE").unwrap().as_deref(),
+            Some("listed")
+        );
+        assert_eq!(list.screen("nova1fine").unwrap(), None);
+    }
+
+    #[test]
+    fn block_rejects_either_party_and_audits() {
+        let db = NovaDB::open_temporary().unwrap();
+        let audit = Arc::new(AuditLog::open(&db).unwrap());
+        let list = StaticListProvider::new("sdn", [("nova1bad", "SDN-1")]);
+        let screener = Screener::new(Arc::new(list), ScreeningAction::Block)
+            .with_audit_log(Arc::clone(&audit));
+
+        assert!(screener
+            .screen_transaction(&transfer("nova1alice", "nova1bob"))
+            .is_ok());
+        let err = screener
+            .screen_transaction(&transfer("nova1alice", "nova1bad"))
+            .unwrap_err();
+        assert_eq!((err.role, err.reason.as_str()), ("receiver", "SDN-1"));
+        let err = screener
+            .screen_transaction(&transfer("nova1bad", "nova1bob"))
+            .unwrap_err();
+        assert_eq!(err.role, "sender");
+
+        let entries = audit.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries
+            .iter()
+            .all(|e| e.category == AuditCategory::Screening && e.action == "blocked"));
+        assert_eq!(entries[0].actor, "sdn");
+    }
+
+    #[test]
+    fn flag_admits_but_records() {
+        let db = NovaDB::open_temporary().unwrap();
+        let audit = Arc::new(AuditLog::open(&db).unwrap());
+        let list = StaticListProvider::new("sdn", [("nova1bad", "SDN-1")]);
+        let screener =
+            Screener::new(Arc::new(list), ScreeningAction::Flag).with_audit_log(Arc::clone(&audit));
+
+        assert!(screener
+            .screen_transaction(&transfer("nova1bad", "nova1bob"))
+            .is_ok());
+        let entries = audit.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "flagged");
+        assert!(entries[0].details.contains("sender=nova1bad"));
+    }
+
+    #[test]
+    fn provider_failure_is_fail_closed_by_default() {
+        let tx = transfer("nova1alice", "nova1bob");
+        let closed = Screener::new(Arc::new(Offline), ScreeningAction::Flag);
+        let err = closed.screen_transaction(&tx).unwrap_err();
+        assert!(err.reason.contains("unavailable"));
+
+        let open = Screener::new(Arc::new(Offline), ScreeningAction::Block).with_fail_open(true);
+        assert!(open.screen_transaction(&tx).is_ok());
+    }
+}
@@ -31,28 +31,55 @@
 //!   fetches. The tradeoff is memory — each batch is held in RAM until applied.
 //!
 //! - **Validate-then-apply.** Every downloaded block is verified (hash integrity,
-//!   Merkle root, parent chain linkage) before touching the state tree. A single
-//!   invalid block in a batch rejects the entire batch. No partial state corruption.
+//!   Merkle root, parent chain linkage, proposer and transaction signatures)
+//!   before touching the state tree. A single invalid block in a batch rejects
+//!   the entire batch. No partial state corruption. Signatures are checked in
+//!   parallel batches, since one by one they would dominate replay time.
 //!
 //! - **Replay execution.** Blocks are not just stored — their transactions are
 //!   re-executed against the state tree. This means the syncing node independently
-//!   derives the same state root as the rest of the network. Trust is minimized;
-//!   the peer only provides blocks, not state.
+//!   derives the same state root as the rest of the network, and refuses a block
+//!   whose header commits to a different one. Trust is minimized; the peer only
+//!   provides blocks, not state. Replay is the state transition function
+//!   ([`crate::execution`]), which also yields the receipts and the state
+//!   diff that sync persists.
+//!
+//! - **Shared rules.** Every check is a stage of the [`BlockValidator`] the
+//!   block producer executes with and consensus validates proposals with, so
+//!   a block one accepts the others accept too.
 //!
 //! - **Stateless engine.** The `SyncEngine` does not manage network connections.
-//!   It provides `process_sync_request` for handling incoming requests and
-//!   `apply_blocks` for processing downloaded batches. Transport is the caller's
-//!   problem — this keeps the engine testable without spinning up libp2p.
+//!   It provides `process_sync_request` for handling incoming requests,
+//!   `apply_blocks` for processing downloaded batches, and `sync_from`, which
+//!   drives a whole sync — with retries — through a request function. Transport
+//!   is the caller's problem — this keeps the engine testable without spinning
+//!   up libp2p.
+//!
+//! - **Retryable persistence.** A block that replays but cannot be persisted
+//!   is undone in the state tree, and the block is written last, so a failed
+//!   write leaves the node where it was and the block can simply be applied
+//!   again.
 
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-
+use thiserror::Error;
+use tracing::warn;
+
+use crate::crypto::wire::BlockHash;
+use crate::execution::AppliedBlock;
+use crate::indexer::Indexer;
+use crate::network::consensus::ValidatorSet;
+use crate::network::liveness::LivenessPolicy;
+use crate::network::validation::{BlockValidationError, BlockValidator, TransactionRejection};
 use crate::storage::block::Block;
 use crate::storage::db::{DbError, NovaDB};
-use crate::storage::state::{apply_transfer, StateError, StateTree};
-use crate::transaction::types::TransactionType;
+use crate::storage::emission::MonetaryPolicy;
+use crate::storage::fee::FeePolicy;
+use crate::storage::state::{StateError, StateTree};
+use crate::vault::token::TokenRegistry;
+use crate::zkp::verifier::BalanceVerifier;
 
 // ---------------------------------------------------------------------------
 // Sync Request / Response
@@ -86,7 +113,7 @@ pub enum SyncRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SyncResponse {
     /// The peer's current chain tip: height and block hash.
-    ChainTip { height: u64, block_hash: [u8; 32] },
+    ChainTip { height: u64, block_hash: BlockHash },
 
     /// A batch of blocks in ascending height order.
     Blocks(Vec<Block>),
@@ -127,6 +154,18 @@ pub struct SyncConfig {
     /// How many times to retry a failed request before giving up.
     /// Retries use the same peer — peer rotation is the caller's job.
     pub max_retries: u32,
+
+    /// Whether to check the proposer's and every transaction's signatures,
+    /// and that the proposer is an active validator, before replaying a
+    /// block. Transaction signatures are checked in parallel batches
+    /// ([`verify_block_signatures`](crate::transaction::verify_block_signatures));
+    /// turn them off only for replays of blocks this node already verified.
+    pub verify_signatures: bool,
+
+    /// Whether to refuse a block whose header state root differs from the
+    /// state its replay produced. Turn it off only for replays of blocks
+    /// this node already verified.
+    pub verify_state_roots: bool,
 }
 
 impl Default for SyncConfig {
@@ -136,6 +175,8 @@ impl Default for SyncConfig {
             max_parallel_requests: 4,
             request_timeout_ms: 10_000,
             max_retries: 3,
+            verify_signatures: true,
+            verify_state_roots: true,
         }
     }
 }
@@ -170,67 +211,76 @@ pub struct SyncResult {
 /// Errors that can occur during block synchronization.
 ///
 /// These are split into "the data is wrong" errors (InvalidBlock, ChainGap,
-/// InvalidParentHash) and "the infrastructure broke" errors (StateError,
-/// DbError, RequestTimeout, PeerDisconnected). The former indicate a
-/// misbehaving peer; the latter are transient and worth retrying.
-#[derive(Debug)]
+/// InvalidParentHash, StateError) and "the infrastructure broke" errors
+/// (DbError, RequestTimeout, PeerDisconnected, PeerFailed). The former
+/// indicate a misbehaving peer; the latter are transient and worth
+/// retrying (see [`SyncError::is_transient`]).
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum SyncError {
     /// A block failed integrity verification (hash mismatch, bad Merkle root,
     /// or other structural issue).
+    #[error("invalid block at height {height}: {reason}")]
     InvalidBlock { height: u64, reason: String },
 
     /// Expected block at height `expected`, but got `got`. Indicates a gap
     /// or overlap in the downloaded batch.
+    #[error("chain gap: expected height {expected}, got {got}")]
     ChainGap { expected: u64, got: u64 },
 
     /// A block's `parent_hash` doesn't match the previous block's hash.
     /// Either the peer is serving a fork or the data is corrupt.
+    #[error("invalid parent hash at height {height}")]
     InvalidParentHash { height: u64 },
 
     /// A state transition failed during transaction replay.
-    StateError(StateError),
+    #[error("state error")]
+    StateError(#[from] StateError),
 
     /// Persistence layer failure.
-    DbError(DbError),
+    #[error("database error")]
+    DbError(#[from] DbError),
 
     /// The peer did not respond within the configured timeout window.
+    #[error("request timed out")]
     RequestTimeout,
 
     /// The peer disconnected mid-sync. Pick a new peer and resume.
+    #[error("peer disconnected")]
     PeerDisconnected,
-}
 
-impl std::fmt::Display for SyncError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::InvalidBlock { height, reason } => {
-                write!(f, "invalid block at height {}: {}", height, reason)
-            }
-            Self::ChainGap { expected, got } => {
-                write!(f, "chain gap: expected height {}, got {}", expected, got)
-            }
-            Self::InvalidParentHash { height } => {
-                write!(f, "invalid parent hash at height {}", height)
-            }
-            Self::StateError(e) => write!(f, "state error: {}", e),
-            Self::DbError(e) => write!(f, "database error: {}", e),
-            Self::RequestTimeout => write!(f, "request timed out"),
-            Self::PeerDisconnected => write!(f, "peer disconnected"),
-        }
-    }
+    /// The peer answered with an error, or without the blocks asked for.
+    #[error("peer failed the request: {0}")]
+    PeerFailed(String),
 }
 
-impl std::error::Error for SyncError {}
-
-impl From<StateError> for SyncError {
-    fn from(e: StateError) -> Self {
-        Self::StateError(e)
+impl SyncError {
+    /// Returns `true` for infrastructure failures, which a retry may get
+    /// past. State errors come from replaying the peer's transactions, so
+    /// they count against the peer.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::DbError(_) | Self::RequestTimeout | Self::PeerDisconnected | Self::PeerFailed(_)
+        )
     }
-}
 
-impl From<DbError> for SyncError {
-    fn from(e: DbError) -> Self {
-        Self::DbError(e)
+    /// Attributes a validation failure to the block at `height`.
+    fn from_validation(height: u64, e: BlockValidationError) -> Self {
+        match e {
+            BlockValidationError::UnexpectedHeight { expected, got } => {
+                Self::ChainGap { expected, got }
+            }
+            BlockValidationError::InvalidParentHash => Self::InvalidParentHash { height },
+            BlockValidationError::Transaction(TransactionRejection::State(e)) => {
+                Self::StateError(e)
+            }
+            BlockValidationError::Transaction(TransactionRejection::Db(e)) => Self::DbError(e),
+            e => Self::InvalidBlock {
+                height,
+                reason: e.to_string(),
+            },
+        }
     }
 }
 
@@ -263,6 +313,15 @@ pub struct SyncEngine {
 
     /// Configuration knobs (batch size, timeouts, etc.).
     config: SyncConfig,
+
+    /// Block rules blocks are validated and replayed under. Its policies,
+    /// token registry and balance verifier must match the ones the blocks
+    /// were produced under.
+    validator: BlockValidator,
+
+    /// Indexer recording the state diff of every applied block, if one is
+    /// attached.
+    indexer: Option<Arc<Indexer>>,
 }
 
 impl SyncEngine {
@@ -275,15 +334,55 @@ impl SyncEngine {
             db,
             state_tree,
             config,
+            validator: BlockValidator::new(),
+            indexer: None,
         }
     }
 
+    /// Overrides the fee policy used when replaying blocks.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.validator = self.validator.with_fee_policy(fee_policy);
+        self
+    }
+
+    /// Overrides the liveness policy used when replaying blocks.
+    pub fn with_liveness_policy(mut self, liveness_policy: LivenessPolicy) -> Self {
+        self.validator = self.validator.with_liveness_policy(liveness_policy);
+        self
+    }
+
+    /// Overrides the block reward schedule used when replaying blocks.
+    pub fn with_monetary_policy(mut self, monetary_policy: MonetaryPolicy) -> Self {
+        self.validator = self.validator.with_monetary_policy(monetary_policy);
+        self
+    }
+
+    /// Overrides the token registry used when replaying blocks.
+    pub fn with_token_registry(mut self, token_registry: TokenRegistry) -> Self {
+        self.validator = self.validator.with_token_registry(token_registry);
+        self
+    }
+
+    /// Sets the verification key confidential transfers are checked with
+    /// when replaying blocks.
+    pub fn with_balance_verifier(mut self, verifier: Arc<BalanceVerifier>) -> Self {
+        self.validator = self.validator.with_balance_verifier(verifier);
+        self
+    }
+
+    /// Records every applied block's state diff in `indexer` and wakes its
+    /// delivery.
+    pub fn with_indexer(mut self, indexer: Arc<Indexer>) -> Self {
+        self.indexer = Some(indexer);
+        self
+    }
+
     /// Returns the local chain tip: current height and block hash.
     ///
     /// If the database is empty (no blocks persisted), returns height 0 and
     /// the genesis block hash. A fresh node always knows about genesis — it's
     /// hardcoded, not downloaded.
-    pub fn local_chain_tip(&self) -> Result<(u64, [u8; 32]), SyncError> {
+    pub fn local_chain_tip(&self) -> Result<(u64, BlockHash), SyncError> {
         match self.db.get_latest_block_height()? {
             Some(height) => {
                 let block = self.db.get_block(height)?.ok_or_else(|| {
@@ -341,11 +440,23 @@ impl SyncEngine {
 
     /// Validates and applies a batch of blocks to the local chain.
     ///
-    /// Each block goes through:
-    /// 1. **Integrity check** — recompute hash, verify Merkle root.
-    /// 2. **Chain linkage** — verify parent hash matches the previous block.
-    /// 3. **Transaction replay** — execute every transaction against the state tree.
-    /// 4. **Persistence** — write the block and updated metadata to NovaDB.
+    /// Each block goes through the [`BlockValidator`] stages:
+    /// 1. **Header** — recompute hash, verify Merkle root and limits.
+    /// 2. **Signatures** — unless [`SyncConfig::verify_signatures`] is off,
+    ///    the proposer's and every transaction's.
+    /// 3. **Chain linkage** — verify height and parent hash against the
+    ///    previous block.
+    /// 4. **Authority** — unless [`SyncConfig::verify_signatures`] is off,
+    ///    the proposer must be an active member of the validator set
+    ///    stored in the state tree as of the parent block. A tree with no
+    ///    set stored vouches for no one.
+    /// 5. **Transaction replay** — refuse transactions already on chain,
+    ///    execute every transaction against the state tree, settle the
+    ///    block's fees, and reject the block if the balance changes do not
+    ///    conserve value.
+    /// 6. **State root** — unless [`SyncConfig::verify_state_roots`] is off,
+    ///    reject the block if its header commits to a different state.
+    /// 7. **Persistence** — write the block and updated metadata to NovaDB.
     ///
     /// If any block fails validation, the entire batch is rejected and the
     /// state tree / database are left in their pre-call state (for the
@@ -371,8 +482,8 @@ impl SyncEngine {
 
         // Determine what the parent hash should be for the first block in the batch.
         let first_height = blocks[0].header.height;
-        let expected_parent_hash = if first_height == 0 {
-            [0u8; 32] // Genesis block's parent is all zeros.
+        let (expected_parent_hash, mut prev_validator) = if first_height == 0 {
+            (BlockHash::zero(), None) // Genesis block's parent is all zeros.
         } else {
             // Look up the block just before the batch start.
             let prev = self
@@ -382,82 +493,72 @@ impl SyncEngine {
                     expected: first_height - 1,
                     got: first_height,
                 })?;
-            prev.header.hash
+            (prev.header.hash, Some(prev.header.validator))
         };
 
         let mut prev_hash = expected_parent_hash;
-        let mut prev_height = if first_height == 0 {
-            0
-        } else {
-            first_height - 1
-        };
+        let mut prev_height = first_height.saturating_sub(1);
 
         for (i, block) in blocks.iter().enumerate() {
-            // Verify block integrity (hash + Merkle root).
-            block.verify().map_err(|reason| SyncError::InvalidBlock {
-                height: block.header.height,
-                reason,
-            })?;
-
-            // Verify height continuity.
+            let height = block.header.height;
+            let invalid = |e| SyncError::from_validation(height, e);
             let expected_height = if i == 0 {
                 first_height
             } else {
                 prev_height + 1
             };
-            if block.header.height != expected_height {
-                return Err(SyncError::ChainGap {
-                    expected: expected_height,
-                    got: block.header.height,
-                });
-            }
 
-            // Verify parent hash linkage.
-            if i == 0 {
-                // For genesis, parent must be all zeros.
-                if block.header.height == 0 {
-                    if block.header.parent_hash != [0u8; 32] {
-                        return Err(SyncError::InvalidParentHash {
-                            height: block.header.height,
-                        });
-                    }
-                } else if block.header.parent_hash != prev_hash {
-                    return Err(SyncError::InvalidParentHash {
-                        height: block.header.height,
-                    });
-                }
-            } else if block.header.parent_hash != prev_hash {
-                return Err(SyncError::InvalidParentHash {
-                    height: block.header.height,
-                });
+            // Stateless stages: header, signatures, linkage. Then the
+            // proposer, against the set the parent block left in state.
+            self.check_block(block)?;
+            self.validator
+                .check_linkage(block, expected_height, &prev_hash)
+                .map_err(invalid)?;
+            if self.config.verify_signatures {
+                let validators =
+                    ValidatorSet::load_from_state(&self.state_tree.read()).unwrap_or_default();
+                self.validator
+                    .check_proposer(block, &validators)
+                    .map_err(invalid)?;
             }
 
-            // Replay transactions against the state tree.
-            {
-                let mut tree = self.state_tree.write();
-                for tx in &block.transactions {
-                    match tx.tx_type {
-                        TransactionType::Transfer => {
-                            apply_transfer(&mut tree, &tx.sender, &tx.receiver, tx.amount.value)?;
-                        }
-                        // Non-transfer transaction types are accepted but don't
-                        // mutate state yet. Same behavior as BlockProducer.
-                        TransactionType::CreditRequest
-                        | TransactionType::CreditSettlement
-                        | TransactionType::TokenMint
-                        | TransactionType::TokenBurn
-                        | TransactionType::ConfidentialTransfer => {}
-                    }
-                    transactions_executed += 1;
+            // Replay transactions against the state tree, then check the
+            // header commits to the result.
+            let applied = self
+                .validator
+                .replay_block(
+                    &mut self.state_tree.write(),
+                    &self.db,
+                    block,
+                    prev_validator.as_deref(),
+                )
+                .map_err(invalid)?;
+            // A header committing to another root is refused; undo its
+            // replay so the tree stays at the last accepted block.
+            if self.config.verify_state_roots {
+                if let Err(e) = self.validator.check_state_root(block, applied.state_root) {
+                    applied.diff.revert(&mut self.state_tree.write());
+                    return Err(invalid(e));
                 }
             }
-
-            // Persist the block.
-            self.db.put_block(block)?;
+            transactions_executed += block.transactions.len() as u64;
+
+            // Persist the block, then publish its state to API readers. If
+            // it could not be persisted, undo its replay so the block can be
+            // applied again.
+            if let Err(e) = self.persist(block, &applied) {
+                applied.diff.revert(&mut self.state_tree.write());
+                return Err(e);
+            }
+            self.state_tree.read().commit(block.header.height);
+            if let Some(indexer) = &self.indexer {
+                indexer.notify_committed();
+            }
 
             blocks_applied += 1;
             prev_hash = block.header.hash;
             prev_height = block.header.height;
+            prev_validator = Some(block.header.validator.clone());
         }
 
         let state_root = self.state_tree.read().root();
@@ -470,10 +571,110 @@ impl SyncEngine {
         })
     }
 
+    /// Records the state diff for indexers, then persists the receipts and,
+    /// last, the block itself: until the block lands, the chain tip has not
+    /// moved.
+    fn persist(&self, block: &Block, applied: &AppliedBlock) -> Result<(), SyncError> {
+        if let Some(indexer) = &self.indexer {
+            indexer.store_diff(&applied.diff)?;
+        }
+        self.db.put_receipts(&applied.receipts)?;
+        self.db.put_block(block)?;
+        Ok(())
+    }
+
+    /// Downloads and applies every block up to `remote_height` from one
+    /// peer, sending `GetBlocks` requests through `request`.
+    ///
+    /// Blocks are applied one at a time as they arrive, so a batch that
+    /// fails partway keeps the blocks before the failure, and a short
+    /// response is applied and the rest requested again. Transient
+    /// failures ([`SyncError::is_transient`]) are retried up to
+    /// [`SyncConfig::max_retries`] times in a row; any progress resets the
+    /// count. Other errors are returned at once — the peer is misbehaving,
+    /// and rotating to another is the caller's job.
+    pub fn sync_from<F>(&self, remote_height: u64, mut request: F) -> Result<SyncResult, SyncError>
+    where
+        F: FnMut(SyncRequest) -> Result<SyncResponse, SyncError>,
+    {
+        let mut result = SyncResult {
+            blocks_applied: 0,
+            transactions_executed: 0,
+            final_height: 0,
+            final_state_root: [0u8; 32],
+        };
+        let mut failures = 0u32;
+
+        loop {
+            let applied_before = result.blocks_applied;
+            match self.sync_batch(remote_height, &mut request, &mut result) {
+                Ok(true) => failures = 0,
+                Ok(false) => break,
+                Err(e) => {
+                    if result.blocks_applied > applied_before {
+                        failures = 0;
+                    }
+                    if !e.is_transient() || failures >= self.config.max_retries {
+                        return Err(e);
+                    }
+                    failures += 1;
+                    warn!(error = %e, attempt = failures, "sync request failed, retrying");
+                }
+            }
+        }
+
+        result.final_state_root = self.state_tree.read().root();
+        Ok(result)
+    }
+
+    /// Requests the next batch after the local tip and applies what came
+    /// back, counting it into `result`. Returns `false` once caught up.
+    fn sync_batch<F>(
+        &self,
+        remote_height: u64,
+        request: &mut F,
+        result: &mut SyncResult,
+    ) -> Result<bool, SyncError>
+    where
+        F: FnMut(SyncRequest) -> Result<SyncResponse, SyncError>,
+    {
+        let (local_height, _) = self.local_chain_tip()?;
+        result.final_height = local_height;
+        let Some(&(start, end)) = self.compute_sync_plan(local_height, remote_height).first()
+        else {
+            return Ok(false);
+        };
+
+        let blocks = match request(SyncRequest::GetBlocks { start, end })? {
+            SyncResponse::Blocks(blocks) if !blocks.is_empty() => blocks,
+            SyncResponse::Blocks(_) => {
+                return Err(SyncError::PeerFailed(format!(
+                    "no blocks in [{start}, {end})"
+                )))
+            }
+            SyncResponse::Error(reason) => return Err(SyncError::PeerFailed(reason)),
+            _ => {
+                return Err(SyncError::PeerFailed(
+                    "unexpected response to GetBlocks".to_string(),
+                ))
+            }
+        };
+
+        for block in blocks {
+            let applied = self.apply_blocks(vec![block])?;
+            result.blocks_applied += applied.blocks_applied;
+            result.transactions_executed += applied.transactions_executed;
+            result.final_height = applied.final_height;
+        }
+        Ok(true)
+    }
+
     /// Validates that a sequence of blocks forms a valid chain.
     ///
     /// Checks:
-    /// 1. Each block passes integrity verification (hash + Merkle root).
+    /// 1. Each block passes the header stage (hash, Merkle root, limits)
+    ///    and, unless [`SyncConfig::verify_signatures`] is off, the
+    ///    signatures stage.
     /// 2. Heights are contiguous starting from `expected_start`.
     /// 3. Each block's `parent_hash` matches the previous block's hash.
     ///
@@ -486,36 +687,35 @@ impl SyncEngine {
         expected_start: u64,
     ) -> Result<(), SyncError> {
         for (i, block) in blocks.iter().enumerate() {
-            // Integrity check.
-            block.verify().map_err(|reason| SyncError::InvalidBlock {
-                height: block.header.height,
-                reason,
-            })?;
+            self.check_block(block)?;
 
-            // Height continuity.
+            // Height continuity and parent hash linkage. The first block's
+            // parent is not checked — the caller is responsible for
+            // verifying it chains to the local tip.
             let expected_height = expected_start + i as u64;
-            if block.header.height != expected_height {
-                return Err(SyncError::ChainGap {
-                    expected: expected_height,
-                    got: block.header.height,
-                });
-            }
-
-            // Parent hash linkage (skip for the first block in the batch —
-            // the caller is responsible for verifying it chains to the local tip).
-            if i > 0 {
-                let prev = &blocks[i - 1];
-                if block.header.parent_hash != prev.header.hash {
-                    return Err(SyncError::InvalidParentHash {
-                        height: block.header.height,
-                    });
-                }
-            }
+            let parent_hash = match i {
+                0 => block.header.parent_hash,
+                _ => blocks[i - 1].header.hash,
+            };
+            self.validator
+                .check_linkage(block, expected_height, &parent_hash)
+                .map_err(|e| SyncError::from_validation(block.header.height, e))?;
         }
 
         Ok(())
     }
 
+    /// Runs the header stage on `block`, then the signatures stage if
+    /// configured to.
+    fn check_block(&self, block: &Block) -> Result<(), SyncError> {
+        let invalid = |e| SyncError::from_validation(block.header.height, e);
+        self.validator.check_header(block).map_err(invalid)?;
+        if self.config.verify_signatures {
+            self.validator.check_signatures(block).map_err(invalid)?;
+        }
+        Ok(())
+    }
+
     /// Returns `true` if we are behind the given remote height.
     ///
     /// A node "needs sync" when the remote chain has blocks we haven't seen.
@@ -578,11 +778,14 @@ mod tests {
     fn setup() -> (SyncEngine, Arc<NovaDB>, Arc<RwLock<StateTree>>) {
         let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
         let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
-        let engine = SyncEngine::new(
-            Arc::clone(&db),
-            Arc::clone(&state_tree),
-            SyncConfig::default(),
-        );
+        // Test transactions are unsigned, from placeholder addresses, in
+        // blocks with placeholder state roots.
+        let config = SyncConfig {
+            verify_signatures: false,
+            verify_state_roots: false,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(Arc::clone(&db), Arc::clone(&state_tree), config);
         (engine, db, state_tree)
     }
 
@@ -600,7 +803,7 @@ mod tests {
             .fee(100)
             .nonce(nonce)
             .timestamp(1_000_000 + nonce)
-            .build()
+            .build_unchecked()
     }
 
     /// Builds a chain of blocks with no transactions, linked from genesis.
@@ -781,13 +984,14 @@ mod tests {
         db.put_block(&genesis).unwrap();
 
         // Build a block with a transfer.
-        let tx = make_test_tx("nova1alice", "nova1bob", 3_000, 0);
+        let tx = make_test_tx("nova1alice", "nova1bob", 3_000, 1);
         let block1 = Block::new(
             &genesis,
             vec![tx],
             "nova:validator_1".to_string(),
             [1u8; 32],
-        );
+        )
+        .with_fees(100);
 
         let result = engine.apply_blocks(vec![block1]).unwrap();
 
@@ -798,7 +1002,7 @@ mod tests {
         let tree = state_tree.read();
         let alice = tree.get("nova1alice").expect("alice should exist");
         let bob = tree.get("nova1bob").expect("bob should exist");
-        assert_eq!(alice.balance, 7_000);
+        assert_eq!(alice.balance, 6_900);
         assert_eq!(bob.balance, 3_000);
     }
 
@@ -844,7 +1048,7 @@ mod tests {
         let chain = make_empty_chain(4);
         // Tamper with the parent hash of block 2.
         let mut tampered = chain[2].clone();
-        tampered.header.parent_hash = [0xFF; 32];
+        tampered.header.parent_hash = BlockHash([0xFF; 32]);
         // Recompute hash to maintain internal consistency.
         tampered.header.hash = tampered.compute_hash();
 
@@ -938,13 +1142,14 @@ mod tests {
             let mut blocks = vec![genesis.clone()];
             for i in 1..=3u64 {
                 let parent = &blocks[(i - 1) as usize];
-                let tx = make_test_tx("nova1alice", "nova1bob", 1_000, i - 1);
+                let tx = make_test_tx("nova1alice", "nova1bob", 1_000, i);
                 let block = Block::new(
                     parent,
                     vec![tx],
                     format!("nova:validator_{i}"),
                     [i as u8; 32],
-                );
+                )
+                .with_fees(100);
                 blocks.push(block);
             }
             blocks
@@ -962,7 +1167,7 @@ mod tests {
         // Verify final balances.
         let alice = tree.get("nova1alice").unwrap();
         let bob = tree.get("nova1bob").unwrap();
-        assert_eq!(alice.balance, 97_000); // 100_000 - 3 * 1_000
+        assert_eq!(alice.balance, 96_700); // 100_000 - 3 * (1_000 + 100 fee)
         assert_eq!(bob.balance, 3_000); // 3 * 1_000
     }
 
@@ -975,6 +1180,8 @@ mod tests {
         assert_eq!(config.max_parallel_requests, 4);
         assert_eq!(config.request_timeout_ms, 10_000);
         assert_eq!(config.max_retries, 3);
+        assert!(config.verify_signatures);
+        assert!(config.verify_state_roots);
     }
 
     // -- 19. process_get_blocks_empty_range ---------------------------------
@@ -1078,4 +1285,386 @@ mod tests {
         assert_eq!(result.final_height, 0);
         assert_eq!(result.transactions_executed, 0);
     }
+
+    // -- 26. apply_blocks_rejects_replayed_transaction ---------------------
+
+    #[test]
+    fn apply_blocks_rejects_replayed_transaction() {
+        let (engine, db, state_tree) = setup();
+        state_tree
+            .write()
+            .put("nova1alice", &AccountState::with_balance(10_000));
+
+        let genesis = Block::genesis();
+        db.put_block(&genesis).unwrap();
+
+        // A peer serves a chain that includes the same transfer twice.
+        let tx = make_test_tx("nova1alice", "nova1bob", 1_000, 1);
+        let block1 = Block::new(&genesis, vec![tx.clone()], "v".into(), [1u8; 32]).with_fees(100);
+        let block2 = Block::new(&block1, vec![tx], "v".into(), [2u8; 32]).with_fees(100);
+
+        let result = engine.apply_blocks(vec![block1, block2]);
+        assert!(matches!(
+            result,
+            Err(SyncError::InvalidBlock { height: 2, ref reason }) if reason.contains("already included")
+        ));
+
+        // Block 1 stays applied; the replay moved no funds.
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(1));
+        let alice = state_tree.read().get("nova1alice").unwrap();
+        assert_eq!(alice.balance, 8_900);
+        assert_eq!(alice.nonce, 1);
+    }
+
+    // -- 27. apply_blocks_settles_fees_to_block_validator -------------------
+
+    #[test]
+    fn apply_blocks_settles_fees_to_block_validator() {
+        let (engine, db, state_tree) = setup();
+        state_tree
+            .write()
+            .put("nova1alice", &AccountState::with_balance(10_000));
+
+        let genesis = Block::genesis();
+        db.put_block(&genesis).unwrap();
+
+        let tx = make_test_tx("nova1alice", "nova1bob", 1_000, 1);
+        let block1 =
+            Block::new(&genesis, vec![tx], "nova:validator_1".into(), [1u8; 32]).with_fees(100);
+        engine.apply_blocks(vec![block1]).unwrap();
+
+        let split = FeePolicy::default().split(100);
+        let tree = state_tree.read();
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 8_900);
+        assert_eq!(tree.get("nova1bob").unwrap().balance, 1_000);
+        assert_eq!(
+            tree.get("nova:validator_1").unwrap().balance,
+            split.proposer
+        );
+    }
+
+    // -- 28. apply_blocks_rejects_stale_nonce -------------------------------
+
+    #[test]
+    fn apply_blocks_rejects_stale_nonce() {
+        let (engine, db, state_tree) = setup();
+        state_tree
+            .write()
+            .put("nova1alice", &AccountState::with_balance(10_000));
+
+        let genesis = Block::genesis();
+        db.put_block(&genesis).unwrap();
+
+        // A different transaction reusing an executed nonce.
+        let block1 = Block::new(
+            &genesis,
+            vec![make_test_tx("nova1alice", "nova1bob", 1_000, 1)],
+            "v".into(),
+            [1u8; 32],
+        )
+        .with_fees(100);
+        let block2 = Block::new(
+            &block1,
+            vec![make_test_tx("nova1alice", "nova1bob", 500, 1)],
+            "v".into(),
+            [2u8; 32],
+        )
+        .with_fees(100);
+
+        let result = engine.apply_blocks(vec![block1, block2]);
+        assert!(matches!(
+            result,
+            Err(SyncError::StateError(StateError::NonceMismatch {
+                expected: 2,
+                got: 1,
+                ..
+            }))
+        ));
+    }
+
+    // -- 29. apply_blocks_rejects_misstated_fees ----------------------------
+
+    #[test]
+    fn apply_blocks_rejects_misstated_fees() {
+        let (engine, db, state_tree) = setup();
+        state_tree
+            .write()
+            .put("nova1alice", &AccountState::with_balance(10_000));
+
+        let genesis = Block::genesis();
+        db.put_block(&genesis).unwrap();
+
+        // The transfer charges 100, the header claims 40.
+        let tx = make_test_tx("nova1alice", "nova1bob", 1_000, 1);
+        let block1 = Block::new(&genesis, vec![tx], "v".into(), [1u8; 32]).with_fees(40);
+
+        let result = engine.apply_blocks(vec![block1]);
+        assert!(matches!(
+            result,
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("fees")
+        ));
+    }
+
+    // -- 30. apply_blocks_rejects_expired_transaction -----------------------
+
+    #[test]
+    fn apply_blocks_rejects_expired_transaction() {
+        let (engine, db, state_tree) = setup();
+        state_tree
+            .write()
+            .put("nova1alice", &AccountState::with_balance(10_000));
+
+        let genesis = Block::genesis();
+        db.put_block(&genesis).unwrap();
+
+        // Expired a second before the block was produced.
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(100)
+            .nonce(1)
+            .timestamp(1_000_000)
+            .valid_until(1_999_000)
+            .build_unchecked();
+        let block1 =
+            Block::new_at(&genesis, vec![tx], "v".into(), [1u8; 32], 2_000_000).with_fees(100);
+
+        let result = engine.apply_blocks(vec![block1]);
+        assert!(matches!(
+            result,
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("expired")
+        ));
+        assert_eq!(state_tree.read().get("nova1alice").unwrap().balance, 10_000);
+    }
+
+    // -- 31. apply_blocks_verifies_signatures -------------------------------
+
+    #[test]
+    fn apply_blocks_verifies_signatures() {
+        use crate::crypto::keys::NovaKeypair;
+        use crate::crypto::wire::SignatureBytes;
+        use crate::identity::NovaId;
+        use crate::transaction::signing::sign_transaction;
+
+        let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
+        let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+        let genesis = Block::genesis();
+        let config = SyncConfig {
+            verify_state_roots: false,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(Arc::clone(&db), Arc::clone(&state_tree), config);
+        let kp = NovaKeypair::from_seed(&[7u8; 32]);
+        let proposer = NovaKeypair::from_seed(&[8u8; 32]);
+        let propose = |txs| {
+            let mut block =
+                Block::new(&genesis, txs, proposer.public_key().to_hex(), [1u8; 32]).with_fees(100);
+            let sig = proposer.sign(block.header.hash.as_bytes());
+            block.header.signature = Some(SignatureBytes::from(&sig));
+            block
+        };
+        let alice = NovaId::from_public_key(&kp.public_key()).to_address();
+        state_tree
+            .write()
+            .put(&alice, &AccountState::with_balance(10_000));
+        let mut validators = ValidatorSet::new();
+        validators.add_validator(proposer.public_key().to_hex(), 1_000);
+        validators.store_in_state(&mut state_tree.write());
+
+        db.put_block(&genesis).unwrap();
+
+        let mut tx = make_test_tx(&alice, "nova1bob", 1_000, 1);
+        sign_transaction(&mut tx, &kp);
+        let mut forged = tx.clone();
+        forged.signature = Some("00".repeat(64));
+
+        let bad = propose(vec![forged]);
+        assert!(matches!(
+            engine.apply_blocks(vec![bad.clone()]),
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("transaction 0")
+        ));
+        assert!(engine.validate_block_chain(&[bad], 1).is_err());
+        assert_eq!(state_tree.read().get(&alice).unwrap().balance, 10_000);
+
+        // The proposer must have signed the header.
+        let mut unsigned = propose(vec![tx.clone()]);
+        unsigned.header.signature = None;
+        assert!(matches!(
+            engine.apply_blocks(vec![unsigned]),
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("proposer")
+        ));
+
+        let good = propose(vec![tx]);
+        assert!(engine.validate_block_chain(&[good.clone()], 1).is_ok());
+        let result = engine.apply_blocks(vec![good]).unwrap();
+        assert_eq!(result.transactions_executed, 1);
+        assert_eq!(state_tree.read().get(&alice).unwrap().balance, 8_900);
+    }
+
+    // -- 32. apply_blocks_checks_state_root --------------------------------
+
+    #[test]
+    fn apply_blocks_checks_state_root() {
+        use crate::crypto::keys::NovaKeypair;
+        use crate::network::mempool::{Mempool, MempoolConfig};
+        use crate::network::producer::BlockProducer;
+
+        let seeded = || {
+            let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
+            let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+            state_tree
+                .write()
+                .put("nova1alice", &AccountState::with_balance(100_000));
+            db.put_block(&Block::genesis()).unwrap();
+            (db, state_tree)
+        };
+        let syncing = || {
+            let (db, state_tree) = seeded();
+            let config = SyncConfig {
+                verify_signatures: false,
+                ..SyncConfig::default()
+            };
+            SyncEngine::new(db, state_tree, config)
+        };
+
+        // A block from the producer replays to the root it commits to.
+        let (db, state_tree) = seeded();
+        let mempool = Arc::new(Mempool::new(MempoolConfig::default()));
+        mempool
+            .add(make_test_tx("nova1alice", "nova1bob", 1_000, 1))
+            .unwrap();
+        let producer = BlockProducer::new(db, state_tree, mempool, NovaKeypair::generate());
+        let produced = producer.produce_block(&Block::genesis(), 10).unwrap();
+        let result = syncing()
+            .apply_blocks(vec![produced.block.clone()])
+            .unwrap();
+        assert_eq!(result.final_state_root, produced.state_root);
+
+        // The same transactions under another root are refused.
+        let header = &produced.block.header;
+        let wrong = Block::new(
+            &Block::genesis(),
+            produced.block.transactions.clone(),
+            header.validator.clone(),
+            [9u8; 32],
+        )
+        .with_fees(header.fees);
+        assert!(matches!(
+            syncing().apply_blocks(vec![wrong]),
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("state root")
+        ));
+    }
+
+    // -- 33. apply_blocks_undoes_refused_replay ----------------------------
+
+    #[test]
+    fn apply_blocks_undoes_refused_replay() {
+        let seeded = || {
+            let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
+            let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+            state_tree
+                .write()
+                .put("nova1alice", &AccountState::with_balance(10_000));
+            db.put_block(&Block::genesis()).unwrap();
+            (db, state_tree)
+        };
+        let tx = make_test_tx("nova1alice", "nova1bob", 1_000, 1);
+
+        // The block executes, but its header commits to another root.
+        let (db, state_tree) = seeded();
+        let root = state_tree.read().root();
+        let config = SyncConfig {
+            verify_signatures: false,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(db, Arc::clone(&state_tree), config);
+        let wrong_root =
+            Block::new(&Block::genesis(), vec![tx.clone()], "v".into(), [9u8; 32]).with_fees(100);
+        assert!(engine.apply_blocks(vec![wrong_root]).is_err());
+        assert_eq!(state_tree.read().root(), root);
+
+        // The block fails to execute after its transfer was applied.
+        let (db, state_tree) = seeded();
+        let root = state_tree.read().root();
+        let config = SyncConfig {
+            verify_signatures: false,
+            verify_state_roots: false,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(db, Arc::clone(&state_tree), config);
+        let misstated =
+            Block::new(&Block::genesis(), vec![tx], "v".into(), [1u8; 32]).with_fees(40);
+        assert!(engine.apply_blocks(vec![misstated]).is_err());
+        assert_eq!(state_tree.read().root(), root);
+    }
+
+    // -- 34. apply_blocks_rejects_non_validator_proposer --------------------
+
+    #[test]
+    fn apply_blocks_rejects_non_validator_proposer() {
+        use crate::crypto::keys::NovaKeypair;
+        use crate::crypto::wire::SignatureBytes;
+
+        let db = Arc::new(NovaDB::open_temporary().expect("temp db"));
+        let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+        let config = SyncConfig {
+            verify_state_roots: false,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(Arc::clone(&db), Arc::clone(&state_tree), config);
+        let genesis = Block::genesis();
+        db.put_block(&genesis).unwrap();
+
+        let validator = NovaKeypair::from_seed(&[1u8; 32]);
+        let outsider = NovaKeypair::from_seed(&[2u8; 32]);
+        let signed_by = |kp: &NovaKeypair| {
+            let mut block = Block::new(&genesis, vec![], kp.public_key().to_hex(), [1u8; 32]);
+            block.header.signature =
+                Some(SignatureBytes::from(&kp.sign(block.header.hash.as_bytes())));
+            block
+        };
+
+        // Without a validator set in state, no proposer is authorized.
+        assert!(matches!(
+            engine.apply_blocks(vec![signed_by(&validator)]),
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("not an active validator")
+        ));
+
+        let mut validators = ValidatorSet::new();
+        validators.add_validator(validator.public_key().to_hex(), 1_000);
+        validators.store_in_state(&mut state_tree.write());
+
+        // A correctly signed block from outside the set is refused...
+        let root = state_tree.read().root();
+        assert!(matches!(
+            engine.apply_blocks(vec![signed_by(&outsider)]),
+            Err(SyncError::InvalidBlock { height: 1, ref reason }) if reason.contains("not an active validator")
+        ));
+        assert_eq!(state_tree.read().root(), root);
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(0));
+
+        // ...and one from a member is applied.
+        assert_eq!(
+            engine
+                .apply_blocks(vec![signed_by(&validator)])
+                .unwrap()
+                .final_height,
+            1
+        );
+    }
+
+    // -- 35. sync_errors_keep_their_sources --------------------------------
+
+    #[test]
+    fn sync_errors_keep_their_sources() {
+        use std::error::Error as _;
+
+        let err = SyncError::from(StateError::InsufficientBalance { have: 1, need: 2 });
+        assert_eq!(err.to_string(), "state error");
+        assert!(err
+            .source()
+            .is_some_and(|e| e.downcast_ref::<StateError>().is_some()));
+        assert!(SyncError::RequestTimeout.source().is_none());
+    }
 }
@@ -0,0 +1,349 @@
+//! # Community Treasury
+//!
+//! A share of every block's fees ([`FeePolicy::treasury_bps`]) is paid into
+//! the treasury account at [`TREASURY_STATE_KEY`] instead of being burned.
+//! No key can sign for that account. Funds leave it only through a
+//! [`SpendProposal`] that validators holding 2/3 of the active stake have
+//! signed — the same quorum rule and signature format as emergency halts
+//! ([`crate::network::halt`]).
+//!
+//! ```text
+//! validator 1  nova-node treasury sign -f spend.json --id audit-2026 \
+//!                  --recipient nova1... --amount 5000000 --memo "..."
+//! validator 2  nova-node treasury sign -f spend.json
+//! ...
+//! anyone       nova-node treasury submit -f spend.json
+//! ```
+//!
+//! Submitting wraps the signed proposal in a
+//! [`TreasurySpend`](crate::transaction::types::TransactionType::TreasurySpend)
+//! transaction whose receiver and amount repeat the proposal's. The spend
+//! executes like any other transaction, so every node checks the
+//! signatures against the validator set in state and pays out at the same
+//! height. Each proposal ID pays out at most once; paid proposals are kept
+//! in the [`TreasuryLedger`] as the treasury's history.
+//!
+//! [`FeePolicy::treasury_bps`]: crate::storage::fee::FeePolicy::treasury_bps
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::EXISTENTIAL_DEPOSIT_PHOTONS;
+use crate::crypto::keys::NovaKeypair;
+use crate::network::consensus::ValidatorSet;
+use crate::network::halt::{add_signature, verify_quorum, GovernanceSignature, HaltError};
+use crate::storage::snapshot::StateSnapshot;
+use crate::storage::state::{
+    bump_nonce, check_nonce, AccountState, StateError, StateTree, TREASURY_STATE_KEY,
+};
+use crate::transaction::Transaction;
+
+/// State tree key under which the record of paid proposals is persisted.
+///
+/// Not a valid NOVA address, so it can never collide with an account.
+pub const TREASURY_LEDGER_KEY: &str = "nova:system:treasury_ledger";
+
+/// Domain prefix of the bytes a [`SpendProposal`] signature covers.
+pub const TREASURY_SPEND_DOMAIN: &[u8] = b"nova-treasury-spend-v1";
+
+// ---------------------------------------------------------------------------
+// Proposals
+// ---------------------------------------------------------------------------
+
+/// A request to pay `amount` from the treasury to `recipient`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendProposal {
+    /// Label chosen by whoever drafts the proposal. Each ID pays out once.
+    pub id: String,
+    /// NOVA address receiving the funds.
+    pub recipient: String,
+    /// Photons to pay.
+    pub amount: u64,
+    /// What the money is for, for voters and explorers. Covered by the
+    /// signatures.
+    pub memo: String,
+    /// Validator signatures over [`signable_bytes`](Self::signable_bytes).
+    #[serde(default)]
+    pub signatures: Vec<GovernanceSignature>,
+}
+
+impl SpendProposal {
+    /// An unsigned proposal.
+    pub fn new(
+        id: impl Into<String>,
+        recipient: impl Into<String>,
+        amount: u64,
+        memo: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            recipient: recipient.into(),
+            amount,
+            memo: memo.into(),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Bytes every signature covers: the domain, then the ID, recipient,
+    /// amount and memo. Strings are length-prefixed so no two proposals
+    /// share bytes.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            TREASURY_SPEND_DOMAIN.len()
+                + 20
+                + self.id.len()
+                + self.recipient.len()
+                + self.memo.len(),
+        );
+        bytes.extend_from_slice(TREASURY_SPEND_DOMAIN);
+        for field in [&self.id, &self.recipient] {
+            bytes.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(field.as_bytes());
+        }
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(self.memo.as_bytes());
+        bytes
+    }
+
+    /// Adds `keypair`'s signature, replacing an earlier one by the same key.
+    pub fn sign(&mut self, keypair: &NovaKeypair) {
+        let signature = GovernanceSignature::new(keypair, &self.signable_bytes());
+        add_signature(&mut self.signatures, signature);
+    }
+
+    /// Checks that the signers hold a supermajority of `validators`.
+    /// Returns the signing stake.
+    pub fn verify(&self, validators: &ValidatorSet) -> Result<u64, HaltError> {
+        verify_quorum(&self.signable_bytes(), &self.signatures, validators)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ledger
+// ---------------------------------------------------------------------------
+
+/// A proposal the treasury has paid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasurySpend {
+    /// ID of the proposal.
+    pub id: String,
+    /// Address paid.
+    pub recipient: String,
+    /// Photons paid.
+    pub amount: u64,
+    /// The proposal's memo.
+    pub memo: String,
+    /// Height of the block that paid it.
+    pub height: u64,
+    /// Transaction that carried it.
+    pub tx_id: String,
+}
+
+/// Every proposal the treasury has paid, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasuryLedger {
+    /// Paid proposals.
+    pub spends: Vec<TreasurySpend>,
+}
+
+impl TreasuryLedger {
+    /// Loads the ledger persisted in the state tree, or an empty one.
+    pub fn load(tree: &StateTree) -> Self {
+        tree.get_raw(TREASURY_LEDGER_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Loads the ledger as of a committed state snapshot.
+    pub fn load_from_snapshot(snapshot: &StateSnapshot<'_>) -> Self {
+        snapshot
+            .get_raw(TREASURY_LEDGER_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persists the ledger into the state tree.
+    pub fn store(&self, tree: &mut StateTree) {
+        let bytes =
+            bincode::serialize(self).expect("treasury ledger serialization should not fail");
+        tree.put_raw(TREASURY_LEDGER_KEY, &bytes);
+    }
+
+    /// Returns `true` if the proposal with this ID has been paid.
+    pub fn is_paid(&self, id: &str) -> bool {
+        self.spends.iter().any(|s| s.id == id)
+    }
+
+    /// Photons paid out over the treasury's lifetime.
+    pub fn total_spent(&self) -> u64 {
+        self.spends.iter().map(|s| s.amount).sum()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// State Transition
+// ---------------------------------------------------------------------------
+
+/// Applies a `TreasurySpend` transaction in the block at `height`.
+///
+/// The payload is a JSON [`SpendProposal`]; the transaction's receiver and
+/// amount must match it. The proposal must carry a supermajority of the
+/// validator set in state, be unpaid, and fit the treasury's balance. The
+/// sender only submits it and pays no part of it.
+pub fn apply_treasury_spend(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    height: u64,
+) -> Result<(), StateError> {
+    check_nonce(tree, &tx.sender, tx.nonce)?;
+
+    let payload = tx.payload.as_deref().unwrap_or_default();
+    let proposal: SpendProposal = serde_json::from_slice(payload)
+        .map_err(|e| StateError::Serialization(format!("spend proposal: {}", e)))?;
+    if proposal.recipient != tx.receiver || proposal.amount != tx.amount.value {
+        return Err(StateError::TreasuryRejected(
+            "transaction receiver and amount must match the proposal".to_string(),
+        ));
+    }
+
+    let validators = ValidatorSet::load_from_state(tree)
+        .ok_or_else(|| StateError::TreasuryRejected("no validator set in state".to_string()))?;
+    proposal
+        .verify(&validators)
+        .map_err(|e| StateError::TreasuryRejected(e.to_string()))?;
+
+    let mut ledger = TreasuryLedger::load(tree);
+    if ledger.is_paid(&proposal.id) {
+        return Err(StateError::TreasuryRejected(format!(
+            "proposal {} was already paid",
+            proposal.id
+        )));
+    }
+
+    let mut treasury = tree.get(TREASURY_STATE_KEY).unwrap_or_default();
+    if treasury.balance < proposal.amount {
+        return Err(StateError::InsufficientBalance {
+            have: treasury.balance,
+            need: proposal.amount,
+        });
+    }
+    let mut recipient = match tree.get(&proposal.recipient) {
+        Some(state) => state,
+        None if proposal.amount < EXISTENTIAL_DEPOSIT_PHOTONS => {
+            return Err(StateError::BelowExistentialDeposit {
+                address: proposal.recipient.clone(),
+                amount: proposal.amount,
+                minimum: EXISTENTIAL_DEPOSIT_PHOTONS,
+            });
+        }
+        None => AccountState::default(),
+    };
+
+    treasury.balance -= proposal.amount;
+    tree.put(TREASURY_STATE_KEY, &treasury);
+    recipient.balance += proposal.amount;
+    tree.put(&proposal.recipient, &recipient);
+
+    ledger.spends.push(TreasurySpend {
+        id: proposal.id,
+        recipient: proposal.recipient,
+        amount: proposal.amount,
+        memo: proposal.memo,
+        height,
+        tx_id: tx.id.clone(),
+    });
+    ledger.store(tree);
+    bump_nonce(tree, &tx.sender, tx.nonce)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::fee::{settle_fees, FeePolicy};
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    fn setup(validators: usize) -> (StateTree, Vec<NovaKeypair>) {
+        let mut tree = StateTree::new(NovaDB::open_temporary().unwrap());
+        let keys: Vec<NovaKeypair> = (0..validators).map(|_| NovaKeypair::generate()).collect();
+        let mut set = ValidatorSet::new();
+        for kp in &keys {
+            set.add_validator(kp.public_key().to_hex(), 1_000);
+        }
+        set.store_in_state(&mut tree);
+        (tree, keys)
+    }
+
+    fn spend_tx(proposal: &SpendProposal, nonce: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::TreasurySpend)
+            .sender("nova1submitter")
+            .receiver(&proposal.recipient)
+            .amount(Amount::new(proposal.amount, Currency::NOVA))
+            .nonce(nonce)
+            .payload(serde_json::to_vec(proposal).unwrap())
+            .build_unchecked()
+    }
+
+    #[test]
+    fn fees_fund_the_treasury() {
+        let (mut tree, _) = setup(1);
+        tree.put("nova1validator", &AccountState::with_balance(1));
+        let split = settle_fees(&mut tree, "nova1validator", 1_000, &FeePolicy::default());
+
+        assert_eq!(split.treasury, 100);
+        assert_eq!(tree.get(TREASURY_STATE_KEY).unwrap().balance, 100);
+    }
+
+    #[test]
+    fn supermajority_spend_pays_once() {
+        let (mut tree, keys) = setup(3);
+        tree.put(TREASURY_STATE_KEY, &AccountState::with_balance(10_000));
+
+        let mut proposal = SpendProposal::new("audit-1", "nova1auditor", 4_000, "security audit");
+        proposal.sign(&keys[0]);
+        match apply_treasury_spend(&mut tree, &spend_tx(&proposal, 1), 7) {
+            Err(StateError::TreasuryRejected(reason)) => assert!(reason.contains("insufficient")),
+            other => panic!("expected quorum rejection, got {:?}", other),
+        }
+
+        proposal.sign(&keys[1]);
+        let mut mismatched = spend_tx(&proposal, 1);
+        mismatched.amount = Amount::new(9_000, Currency::NOVA);
+        assert!(apply_treasury_spend(&mut tree, &mismatched, 7).is_err());
+
+        apply_treasury_spend(&mut tree, &spend_tx(&proposal, 1), 7).unwrap();
+        assert_eq!(tree.get(TREASURY_STATE_KEY).unwrap().balance, 6_000);
+        assert_eq!(tree.get("nova1auditor").unwrap().balance, 4_000);
+
+        let ledger = TreasuryLedger::load(&tree);
+        assert_eq!(ledger.spends.len(), 1);
+        assert_eq!(ledger.spends[0].height, 7);
+        assert_eq!(ledger.total_spent(), 4_000);
+
+        match apply_treasury_spend(&mut tree, &spend_tx(&proposal, 2), 8) {
+            Err(StateError::TreasuryRejected(reason)) => assert!(reason.contains("already paid")),
+            other => panic!("expected replay rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn spend_cannot_overdraw() {
+        let (mut tree, keys) = setup(1);
+        tree.put(TREASURY_STATE_KEY, &AccountState::with_balance(500));
+
+        let mut proposal = SpendProposal::new("grant", "nova1dev", 1_000, "grant");
+        proposal.sign(&keys[0]);
+        assert!(matches!(
+            apply_treasury_spend(&mut tree, &spend_tx(&proposal, 1), 1),
+            Err(StateError::InsufficientBalance {
+                have: 500,
+                need: 1_000
+            })
+        ));
+        assert!(TreasuryLedger::load(&tree).spends.is_empty());
+    }
+}
@@ -0,0 +1,603 @@
+//! # Block Validation Pipeline
+//!
+//! Producing a block, validating a proposal and replaying a synced block
+//! must apply the same rules, or nodes split on which blocks are valid.
+//! [`BlockValidator`] is that one rule set, in stages:
+//!
+//! | Stage            | Method                                                                                  | Checks                                                    |
+//! |------------------|-----------------------------------------------------------------------------------------|-----------------------------------------------------------|
+//! | Header           | [`check_header`](BlockValidator::check_header)                                          | hash, tx Merkle root, commit votes, count and byte limits |
+//! | Signatures       | [`check_signatures`](BlockValidator::check_signatures)                                  | proposer signature, every transaction's signatures        |
+//! | Authority        | [`check_proposer`](BlockValidator::check_proposer)                                      | proposer is in the active validator set                   |
+//! | Linkage          | [`check_linkage`](BlockValidator::check_linkage)                                        | height and parent hash against the chain tip              |
+//! | Transactions     | [`check_not_included`](BlockValidator::check_not_included), [`BlockExecution::execute`] | replays, expiry, spending policies                        |
+//! | State transition | [`StateTransition::apply_block`]                                                        | execution, fees, emission, conservation, liveness         |
+//! | State root       | [`check_state_root`](BlockValidator::check_state_root)                                  | header state root against the replayed state              |
+//!
+//! Each path runs the stages it can:
+//!
+//! - the [`BlockProducer`](super::BlockProducer) executes candidates through
+//!   a [`BlockExecution`], dropping those it rejects, and builds a header
+//!   that passes the rest by construction;
+//! - the [`ConsensusEngine`](super::ConsensusEngine) has no state tree and
+//!   runs the stateless stages — header, signatures, linkage — on proposals,
+//!   plus the authority stage against the validator set it loaded from
+//!   committed state;
+//! - the [`SyncEngine`](super::SyncEngine) runs all of them, with the
+//!   validator set stored in its state tree, refusing the block on the
+//!   first failure ([`replay_block`](BlockValidator::replay_block)).
+//!
+//! A rule added to a stage therefore applies everywhere. The transaction
+//! and state transition stages are the state transition function itself
+//! ([`crate::execution`]); the validator adds the checks that need the
+//! block header or the chain. Its parameters (the [`StateTransition`]'s
+//! policies and the block limits) are consensus-critical: every node must
+//! build its validator from the same genesis configuration.
+
+use std::fmt;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::config::MAX_BLOCK_SIZE_BYTES;
+use crate::crypto::keys::{NovaPublicKey, NovaSignature};
+use crate::crypto::wire::BlockHash;
+use crate::execution::{
+    AppliedBlock, BlockExecution, ExecutionError, StateTransition, TxExecutionError,
+};
+use crate::network::consensus::ValidatorSet;
+use crate::network::liveness::LivenessPolicy;
+use crate::storage::block::Block;
+use crate::storage::db::{DbError, NovaDB};
+use crate::storage::emission::MonetaryPolicy;
+use crate::storage::fee::FeePolicy;
+use crate::storage::state::{StateError, StateTree};
+use crate::transaction::batch_verification::{verify_block_signatures, BlockSignatureError};
+use crate::transaction::limits;
+use crate::transaction::Transaction;
+use crate::vault::token::TokenRegistry;
+use crate::zkp::verifier::BalanceVerifier;
+
+/// Default cap on transactions per block, as in
+/// [`ConsensusConfig`](super::ConsensusConfig).
+const DEFAULT_MAX_BLOCK_TRANSACTIONS: usize = 1_000;
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// A stage of the validation pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationStage {
+    Header,
+    Signatures,
+    Authority,
+    Linkage,
+    Transactions,
+    StateTransition,
+    StateRoot,
+}
+
+impl fmt::Display for ValidationStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Header => "header",
+            Self::Signatures => "signatures",
+            Self::Authority => "authority",
+            Self::Linkage => "linkage",
+            Self::Transactions => "transactions",
+            Self::StateTransition => "state transition",
+            Self::StateRoot => "state root",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a transaction cannot go into a block at this point of the chain.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TransactionRejection {
+    /// Already on chain, or earlier in the same block. Nonces alone cannot
+    /// catch this once the sender has been reaped and re-created.
+    #[error("transaction {0} already included")]
+    AlreadyIncluded(String),
+    /// The transaction's `valid_until` is before the block timestamp.
+    #[error("transaction {tx_id} expired at {valid_until}")]
+    Expired { tx_id: String, valid_until: u64 },
+    /// Execution against the state tree failed.
+    #[error(transparent)]
+    State(#[from] StateError),
+    /// Looking up earlier inclusions failed.
+    #[error(transparent)]
+    Db(#[from] DbError),
+}
+
+impl From<TxExecutionError> for TransactionRejection {
+    fn from(e: TxExecutionError) -> Self {
+        match e {
+            TxExecutionError::Duplicate(tx_id) => Self::AlreadyIncluded(tx_id),
+            TxExecutionError::Expired { tx_id, valid_until } => {
+                Self::Expired { tx_id, valid_until }
+            }
+            TxExecutionError::State(e) => Self::State(e),
+        }
+    }
+}
+
+/// Why a block was refused.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BlockValidationError {
+    /// Hash, Merkle root, genesis shape or commit votes do not check out.
+    #[error("{0}")]
+    Integrity(String),
+    /// More transactions than a block may carry.
+    #[error("block has {count} transactions (max {max})")]
+    TooManyTransactions { count: usize, max: usize },
+    /// The transactions weigh more than the byte budget.
+    #[error("block transactions weigh {weight} bytes (max {max})")]
+    TooLarge { weight: usize, max: usize },
+    /// The header is not signed by the validator it names.
+    #[error("block is not signed by its proposer {0}")]
+    ProposerSignature(String),
+    /// A transaction's signatures do not verify.
+    #[error(transparent)]
+    TransactionSignature(#[from] BlockSignatureError),
+    /// The proposer is not an active validator.
+    #[error("block proposer {0} is not an active validator")]
+    UnauthorizedProposer(String),
+    /// The block is not the next height.
+    #[error("expected height {expected}, got {got}")]
+    UnexpectedHeight { expected: u64, got: u64 },
+    /// The block does not extend the chain tip.
+    #[error("parent hash does not match the chain tip")]
+    InvalidParentHash,
+    /// A transaction cannot be executed.
+    #[error(transparent)]
+    Transaction(#[from] TransactionRejection),
+    /// The header's fees are not what the transactions charged.
+    #[error("header claims {claimed} in fees, transactions charged {charged}")]
+    FeeMismatch { claimed: u64, charged: u64 },
+    /// Settling the block broke an invariant, such as value conservation.
+    #[error(transparent)]
+    StateTransition(StateError),
+    /// The header's state root is not the state the block produces.
+    #[error("header state root {claimed} does not match the replayed {computed}")]
+    StateRootMismatch { claimed: String, computed: String },
+}
+
+impl BlockValidationError {
+    /// The stage that refused the block.
+    pub fn stage(&self) -> ValidationStage {
+        match self {
+            Self::Integrity(_) | Self::TooManyTransactions { .. } | Self::TooLarge { .. } => {
+                ValidationStage::Header
+            }
+            Self::ProposerSignature(_) | Self::TransactionSignature(_) => {
+                ValidationStage::Signatures
+            }
+            Self::UnauthorizedProposer(_) => ValidationStage::Authority,
+            Self::UnexpectedHeight { .. } | Self::InvalidParentHash => ValidationStage::Linkage,
+            Self::Transaction(TransactionRejection::State(_)) => ValidationStage::StateTransition,
+            Self::Transaction(_) => ValidationStage::Transactions,
+            Self::FeeMismatch { .. } | Self::StateTransition(_) => ValidationStage::StateTransition,
+            Self::StateRootMismatch { .. } => ValidationStage::StateRoot,
+        }
+    }
+}
+
+impl From<ExecutionError> for BlockValidationError {
+    fn from(e: ExecutionError) -> Self {
+        match e {
+            ExecutionError::Transaction(e) => Self::Transaction(e.into()),
+            ExecutionError::FeeMismatch { claimed, charged } => {
+                Self::FeeMismatch { claimed, charged }
+            }
+            ExecutionError::Settlement(e) => Self::StateTransition(e),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BlockValidator
+// ---------------------------------------------------------------------------
+
+/// The block rules shared by production, consensus and sync.
+#[derive(Clone)]
+pub struct BlockValidator {
+    transition: StateTransition,
+    max_block_transactions: usize,
+    max_block_bytes: usize,
+}
+
+impl Default for BlockValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BlockValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockValidator")
+            .field("transition", &self.transition)
+            .field("max_block_transactions", &self.max_block_transactions)
+            .field("max_block_bytes", &self.max_block_bytes)
+            .finish()
+    }
+}
+
+impl BlockValidator {
+    /// The default [`StateTransition`] and block limits.
+    pub fn new() -> Self {
+        Self {
+            transition: StateTransition::new(),
+            max_block_transactions: DEFAULT_MAX_BLOCK_TRANSACTIONS,
+            max_block_bytes: MAX_BLOCK_SIZE_BYTES,
+        }
+    }
+
+    /// Overrides the split of collected fees between proposer and burn.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.transition = self.transition.with_fee_policy(fee_policy);
+        self
+    }
+
+    /// Overrides when validators are jailed for missed duties.
+    pub fn with_liveness_policy(mut self, liveness_policy: LivenessPolicy) -> Self {
+        self.transition = self.transition.with_liveness_policy(liveness_policy);
+        self
+    }
+
+    /// Overrides the block reward schedule.
+    pub fn with_monetary_policy(mut self, monetary_policy: MonetaryPolicy) -> Self {
+        self.transition = self.transition.with_monetary_policy(monetary_policy);
+        self
+    }
+
+    /// Overrides the tokens transfers may be denominated in.
+    pub fn with_token_registry(mut self, token_registry: TokenRegistry) -> Self {
+        self.transition = self.transition.with_token_registry(token_registry);
+        self
+    }
+
+    /// Sets the verification key confidential transfers are checked with.
+    pub fn with_balance_verifier(mut self, verifier: Arc<BalanceVerifier>) -> Self {
+        self.transition = self.transition.with_balance_verifier(verifier);
+        self
+    }
+
+    /// Overrides how many transactions a block may carry.
+    pub fn with_max_block_transactions(mut self, max_block_transactions: usize) -> Self {
+        self.max_block_transactions = max_block_transactions;
+        self
+    }
+
+    /// Overrides the byte budget of a block's transactions (see
+    /// [`limits::tx_weight`]).
+    pub fn with_max_block_bytes(mut self, max_block_bytes: usize) -> Self {
+        self.max_block_bytes = max_block_bytes;
+        self
+    }
+
+    /// The byte budget of a block's transactions.
+    pub fn max_block_bytes(&self) -> usize {
+        self.max_block_bytes
+    }
+
+    /// The state transition blocks are executed with.
+    pub fn transition(&self) -> &StateTransition {
+        &self.transition
+    }
+
+    // -- Stateless stages ---------------------------------------------------
+
+    /// Header stage: the block's integrity ([`Block::verify`]) and
+    /// [`check_limits`](Self::check_limits).
+    pub fn check_header(&self, block: &Block) -> Result<(), BlockValidationError> {
+        block.verify().map_err(BlockValidationError::Integrity)?;
+        self.check_limits(&block.transactions)
+    }
+
+    /// Rejects `transactions` if there are more than a block may carry or
+    /// they weigh more than its byte budget.
+    pub fn check_limits(&self, transactions: &[Transaction]) -> Result<(), BlockValidationError> {
+        if transactions.len() > self.max_block_transactions {
+            return Err(BlockValidationError::TooManyTransactions {
+                count: transactions.len(),
+                max: self.max_block_transactions,
+            });
+        }
+        let weight: usize = transactions.iter().map(limits::tx_weight).sum();
+        if weight > self.max_block_bytes {
+            return Err(BlockValidationError::TooLarge {
+                weight,
+                max: self.max_block_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Signatures stage: the proposer's signature over the block hash, then
+    /// every transaction's, in parallel batches ([`verify_block_signatures`]).
+    /// The genesis block is unsigned.
+    pub fn check_signatures(&self, block: &Block) -> Result<(), BlockValidationError> {
+        if block.header.height > 0 {
+            let invalid =
+                || BlockValidationError::ProposerSignature(block.header.validator.clone());
+            let proposer =
+                NovaPublicKey::from_hex(&block.header.validator).map_err(|_| invalid())?;
+            let signature: NovaSignature = block.header.signature.ok_or_else(invalid)?.into();
+            if !proposer.verify(block.header.hash.as_bytes(), &signature) {
+                return Err(invalid());
+            }
+        }
+        verify_block_signatures(&block.transactions)?;
+        Ok(())
+    }
+
+    /// Authority stage: the block's proposer must be an active member of
+    /// `validators`, the set in force at the parent block. Callers take it
+    /// from state ([`ValidatorSet::load_from_state`]), so every path judges
+    /// a block against the same set. The genesis block has no proposer.
+    pub fn check_proposer(
+        &self,
+        block: &Block,
+        validators: &ValidatorSet,
+    ) -> Result<(), BlockValidationError> {
+        if block.header.height > 0 && !validators.contains(&block.header.validator) {
+            return Err(BlockValidationError::UnauthorizedProposer(
+                block.header.validator.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Linkage stage: the block must be at `expected_height` and extend
+    /// `parent_hash` (all zeros for the genesis block).
+    pub fn check_linkage(
+        &self,
+        block: &Block,
+        expected_height: u64,
+        parent_hash: &BlockHash,
+    ) -> Result<(), BlockValidationError> {
+        if block.header.height != expected_height {
+            return Err(BlockValidationError::UnexpectedHeight {
+                expected: expected_height,
+                got: block.header.height,
+            });
+        }
+        if block.header.parent_hash != *parent_hash {
+            return Err(BlockValidationError::InvalidParentHash);
+        }
+        Ok(())
+    }
+
+    // -- Stateful stages ----------------------------------------------------
+
+    /// Starts executing a block at `height` and `timestamp` proposed by
+    /// `proposer`, which may include any of `transactions` (see
+    /// [`StateTransition::begin_block`]). Candidates should pass
+    /// [`check_not_included`](Self::check_not_included) first.
+    pub fn begin_block(
+        &self,
+        tree: &StateTree,
+        transactions: &[Transaction],
+        proposer: &str,
+        height: u64,
+        timestamp: u64,
+    ) -> BlockExecution<'_> {
+        self.transition
+            .begin_block(tree, transactions, proposer, height, timestamp)
+    }
+
+    /// Transactions stage against the chain: refuses `tx` if an earlier
+    /// block already committed it. Nonces restart when an account is
+    /// reaped, so replay protection also checks the transaction ID.
+    pub fn check_not_included(
+        &self,
+        db: &NovaDB,
+        tx: &Transaction,
+    ) -> Result<(), TransactionRejection> {
+        if db.get_transaction(&tx.id)?.is_some() {
+            return Err(TransactionRejection::AlreadyIncluded(tx.id.clone()));
+        }
+        Ok(())
+    }
+
+    /// Transactions and state transition stages for a received block:
+    /// checks no transaction is already on chain, then applies the block
+    /// ([`StateTransition::apply_block`]), requiring every transaction to
+    /// execute and the header's fees to match.
+    ///
+    /// A block that fails to execute leaves the tree as it found it. One
+    /// that executes is applied; a caller refusing it at a later stage
+    /// undoes it with [`AppliedBlock::diff`].
+    pub fn replay_block(
+        &self,
+        tree: &mut StateTree,
+        db: &NovaDB,
+        block: &Block,
+        parent_validator: Option<&str>,
+    ) -> Result<AppliedBlock, BlockValidationError> {
+        for tx in &block.transactions {
+            self.check_not_included(db, tx)?;
+        }
+        Ok(self.transition.apply_block(tree, block, parent_validator)?)
+    }
+
+    /// State root stage: the header must commit to `computed`, the root of
+    /// the tree after [`replay_block`](Self::replay_block)
+    /// ([`AppliedBlock::state_root`]). The genesis
+    /// block's root is fixed by its coinbase message instead.
+    pub fn check_state_root(
+        &self,
+        block: &Block,
+        computed: [u8; 32],
+    ) -> Result<(), BlockValidationError> {
+        if block.header.height == 0 || block.header.state_root == computed {
+            return Ok(());
+        }
+        Err(BlockValidationError::StateRootMismatch {
+            claimed: block.header.state_root.to_hex(),
+            computed: hex::encode(computed),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::NovaKeypair;
+    use crate::crypto::wire::SignatureBytes;
+    use crate::identity::NovaId;
+    use crate::storage::state::AccountState;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::signing::sign_transaction;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    fn signed_transfer(keypair: &NovaKeypair, nonce: u64) -> Transaction {
+        let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender(&NovaId::from_public_key(&keypair.public_key()).to_address())
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(100)
+            .nonce(nonce)
+            .timestamp(1_000_000)
+            .build_unchecked();
+        sign_transaction(&mut tx, keypair);
+        tx
+    }
+
+    fn signed_block(parent: &Block, txs: Vec<Transaction>, proposer: &NovaKeypair) -> Block {
+        let mut block = Block::new_at(
+            parent,
+            txs,
+            proposer.public_key().to_hex(),
+            [0u8; 32],
+            2_000_000,
+        );
+        block.header.signature = Some(SignatureBytes::from(
+            &proposer.sign(block.header.hash.as_bytes()),
+        ));
+        block
+    }
+
+    #[test]
+    fn stateless_stages_name_what_failed() {
+        let validator = BlockValidator::new();
+        let proposer = NovaKeypair::from_seed(&[1u8; 32]);
+        let alice = NovaKeypair::from_seed(&[2u8; 32]);
+        let genesis = Block::genesis();
+        let block = signed_block(&genesis, vec![signed_transfer(&alice, 1)], &proposer);
+
+        validator.check_header(&block).unwrap();
+        validator.check_signatures(&block).unwrap();
+        validator
+            .check_linkage(&block, 1, &genesis.header.hash)
+            .unwrap();
+
+        let err = validator
+            .check_linkage(&block, 2, &genesis.header.hash)
+            .unwrap_err();
+        assert_eq!(err.stage(), ValidationStage::Linkage);
+
+        let mut forged = block.clone();
+        forged.transactions[0].signature = forged.transactions[0]
+            .signature
+            .as_ref()
+            .map(|s| s.replace(|c: char| c != '0', "0"));
+        let err = validator.check_signatures(&forged).unwrap_err();
+        assert!(matches!(err, BlockValidationError::TransactionSignature(_)));
+        // Changing a transaction also moves the Merkle root.
+        assert_eq!(
+            validator.check_header(&forged).unwrap_err().stage(),
+            ValidationStage::Header
+        );
+
+        let mut unsigned = block.clone();
+        unsigned.header.signature = None;
+        assert!(matches!(
+            validator.check_signatures(&unsigned),
+            Err(BlockValidationError::ProposerSignature(_))
+        ));
+
+        let tight = BlockValidator::new().with_max_block_transactions(0);
+        assert!(matches!(
+            tight.check_header(&block),
+            Err(BlockValidationError::TooManyTransactions { count: 1, max: 0 })
+        ));
+    }
+
+    #[test]
+    fn replay_requires_every_transaction_and_the_stated_fees() {
+        let validator = BlockValidator::new();
+        let db = NovaDB::open_temporary().expect("temp db");
+        let alice = NovaKeypair::from_seed(&[2u8; 32]);
+        let alice_address = NovaId::from_public_key(&alice.public_key()).to_address();
+        let proposer = NovaKeypair::from_seed(&[1u8; 32]);
+        let genesis = Block::genesis();
+
+        let fresh_tree = || {
+            let mut tree = StateTree::new(db.clone());
+            tree.put(&alice_address, &AccountState::with_balance(10_000));
+            tree
+        };
+
+        // A replayed transaction is refused before it executes.
+        let tx = signed_transfer(&alice, 1);
+        let twice = signed_block(&genesis, vec![tx.clone(), tx.clone()], &proposer);
+        let mut tree = fresh_tree();
+        let err = validator
+            .replay_block(&mut tree, &db, &twice, None)
+            .unwrap_err();
+        assert_eq!(err.stage(), ValidationStage::Transactions);
+
+        // The header must state what the transactions charged.
+        let unpaid = signed_block(&genesis, vec![tx.clone()], &proposer);
+        let mut tree = fresh_tree();
+        assert!(matches!(
+            validator.replay_block(&mut tree, &db, &unpaid, None),
+            Err(BlockValidationError::FeeMismatch {
+                claimed: 0,
+                charged: 100
+            })
+        ));
+
+        // A proper block replays, and its header must commit to the result.
+        let block = Block::new_at(
+            &genesis,
+            vec![tx],
+            proposer.public_key().to_hex(),
+            [0u8; 32],
+            2_000_000,
+        )
+        .with_fees(100);
+        let mut tree = fresh_tree();
+        let applied = validator
+            .replay_block(&mut tree, &db, &block, None)
+            .unwrap();
+        assert_eq!(applied.receipts.len(), 1);
+        assert_eq!(tree.get(&alice_address).unwrap().balance, 8_900);
+        let err = validator
+            .check_state_root(&block, applied.state_root)
+            .unwrap_err();
+        assert_eq!(err.stage(), ValidationStage::StateRoot);
+        assert!(validator
+            .check_state_root(&genesis, applied.state_root)
+            .is_ok());
+
+        // Once committed, its transaction cannot come back in a later block.
+        db.put_block(&block).unwrap();
+        let mut tree = fresh_tree();
+        assert!(matches!(
+            validator.replay_block(&mut tree, &db, &block, None),
+            Err(BlockValidationError::Transaction(
+                TransactionRejection::AlreadyIncluded(_)
+            ))
+        ));
+    }
+}
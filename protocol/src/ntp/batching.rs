@@ -0,0 +1,435 @@
+//! # Merchant Settlement Batching
+//!
+//! A coffee shop taking a few thousand NTP payments a day does not want a
+//! few thousand treasury movements. The [`MerchantBatcher`] collects
+//! dual-signed receipts as they arrive and, on a schedule or when enough
+//! have piled up, emits one consolidated transfer per currency from the
+//! merchant's collection address to its treasury.
+//!
+//! Every payout comes with a [`ReconciliationReport`] that links the payout
+//! transaction back to the receipts it consolidates, so the accounting team
+//! can trace any single sale to the treasury movement that swept it.
+//!
+//! ## Flow
+//!
+//! ```text
+//! PaymentReceipt ──► add_receipt() ──► pending
+//!                                         │  should_payout(now)?
+//!                                         ▼
+//!                              build_payout(keypair, nonce)
+//!                                         │
+//!                         ┌───────────────┴───────────────┐
+//!                         ▼                               ▼
+//!              Vec<Transaction> (signed)       ReconciliationReport
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config;
+use crate::crypto::keys::NovaKeypair;
+use crate::transaction::builder::{Transaction, TransactionBuilder};
+use crate::transaction::signing::sign_transaction;
+use crate::transaction::types::{Amount, Currency, TransactionType};
+
+use super::error::NtpError;
+use super::receipt::{verify_receipt, PaymentReceipt};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// When and how a merchant's receipts are swept into a payout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchingConfig {
+    /// Sweep at least this often (milliseconds), as long as something is pending.
+    pub payout_interval_ms: u64,
+    /// Sweep immediately once this many receipts are pending.
+    pub max_receipts_per_payout: usize,
+    /// Fee attached to each consolidated transfer, in photons.
+    pub payout_fee: u64,
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            payout_interval_ms: 60 * 60 * 1000, // hourly
+            max_receipts_per_payout: 1_000,
+            payout_fee: config::MIN_TX_FEE_PHOTONS,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Reconciliation
+// ---------------------------------------------------------------------------
+
+/// One consolidated transfer within a payout, with its constituent receipts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PayoutLine {
+    /// Currency of this line.
+    pub currency: Currency,
+    /// Sum of all receipt amounts in this line.
+    pub total: u64,
+    /// ID of the consolidated transfer carrying `total`.
+    pub tx_hash: String,
+    /// Receipt IDs swept by this transfer.
+    pub receipt_ids: Vec<String>,
+    /// On-chain transaction hashes of the original payments.
+    pub source_tx_hashes: Vec<String>,
+}
+
+/// Links a payout to every receipt it consolidates.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    /// Unique payout identifier (UUIDv4).
+    pub payout_id: String,
+    /// Merchant collection address (source of the payout).
+    pub merchant: String,
+    /// Treasury address (destination of the payout).
+    pub treasury: String,
+    /// Unix timestamp (milliseconds) when the payout was built.
+    pub created_at: u64,
+    /// One line per currency, ordered by currency ticker.
+    pub lines: Vec<PayoutLine>,
+}
+
+impl ReconciliationReport {
+    /// Total number of receipts across all lines.
+    pub fn receipt_count(&self) -> usize {
+        self.lines.iter().map(|l| l.receipt_ids.len()).sum()
+    }
+
+    /// Total swept for a given currency, or zero if none.
+    pub fn total_for(&self, currency: &Currency) -> u64 {
+        self.lines
+            .iter()
+            .filter(|l| &l.currency == currency)
+            .map(|l| l.total)
+            .sum()
+    }
+
+    /// Find the payout line that swept a given receipt.
+    pub fn line_for_receipt(&self, receipt_id: &str) -> Option<&PayoutLine> {
+        self.lines
+            .iter()
+            .find(|l| l.receipt_ids.iter().any(|id| id == receipt_id))
+    }
+}
+
+/// A built payout: signed transfers ready to broadcast, plus the report.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MerchantPayout {
+    /// Signed consolidated transfers, one per currency.
+    pub transactions: Vec<Transaction>,
+    /// Reconciliation report for this payout.
+    pub report: ReconciliationReport,
+}
+
+// ---------------------------------------------------------------------------
+// Batcher
+// ---------------------------------------------------------------------------
+
+/// Accumulates confirmed receipts for one merchant and builds payouts.
+pub struct MerchantBatcher {
+    /// Address receiving customer payments (must match receipt receivers).
+    merchant_address: String,
+    /// Treasury address payouts are sent to.
+    treasury_address: String,
+    /// Batching policy.
+    config: BatchingConfig,
+    /// Receipts waiting for the next payout, in arrival order.
+    pending: Vec<PaymentReceipt>,
+    /// Every receipt ID ever accepted, so replays cannot be swept twice.
+    seen: HashSet<String>,
+    /// Unix ms of the last payout (or of construction).
+    last_payout_at: u64,
+}
+
+impl MerchantBatcher {
+    /// Create a batcher for `merchant_address`, paying out to `treasury_address`.
+    pub fn new(
+        merchant_address: impl Into<String>,
+        treasury_address: impl Into<String>,
+        config: BatchingConfig,
+        now_ms: u64,
+    ) -> Self {
+        Self {
+            merchant_address: merchant_address.into(),
+            treasury_address: treasury_address.into(),
+            config,
+            pending: Vec::new(),
+            seen: HashSet::new(),
+            last_payout_at: now_ms,
+        }
+    }
+
+    /// Accept a confirmed, dual-signed receipt.
+    ///
+    /// # Errors
+    ///
+    /// Rejects receipts that are not fully signed, were paid to a different
+    /// address, or have already been accepted.
+    pub fn add_receipt(&mut self, receipt: PaymentReceipt) -> Result<(), NtpError> {
+        verify_receipt(&receipt)?;
+
+        if receipt.receiver != self.merchant_address {
+            return Err(NtpError::InvalidState {
+                current_state: format!("batching for {}", self.merchant_address),
+                message_type: format!("receipt for {}", receipt.receiver),
+            });
+        }
+        if !self.seen.insert(receipt.receipt_id.clone()) {
+            return Err(NtpError::InvalidState {
+                current_state: "receipt already batched".to_string(),
+                message_type: receipt.receipt_id,
+            });
+        }
+
+        self.pending.push(receipt);
+        Ok(())
+    }
+
+    /// Number of receipts waiting for the next payout.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if a payout is due: either the interval elapsed with
+    /// something pending, or the pending count hit the cap.
+    pub fn should_payout(&self, now_ms: u64) -> bool {
+        if self.pending.is_empty() {
+            return false;
+        }
+        self.pending.len() >= self.config.max_receipts_per_payout
+            || now_ms.saturating_sub(self.last_payout_at) >= self.config.payout_interval_ms
+    }
+
+    /// Sweep all pending receipts into signed consolidated transfers.
+    ///
+    /// One transfer is produced per currency, using consecutive nonces
+    /// starting at `next_nonce`. Returns `None` if nothing is pending.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a transfer cannot be built (e.g. the configured addresses
+    /// are malformed). The receipts then stay pending.
+    pub fn build_payout(
+        &mut self,
+        keypair: &NovaKeypair,
+        next_nonce: u64,
+        now_ms: u64,
+    ) -> Result<Option<MerchantPayout>, NtpError> {
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+
+        let payout_id = Uuid::new_v4().to_string();
+
+        // Group by ticker. BTreeMap keeps the line (and nonce) order stable.
+        let mut groups: BTreeMap<String, Vec<&PaymentReceipt>> = BTreeMap::new();
+        for receipt in &self.pending {
+            groups
+                .entry(receipt.currency.to_string())
+                .or_default()
+                .push(receipt);
+        }
+
+        let mut transactions = Vec::with_capacity(groups.len());
+        let mut lines = Vec::with_capacity(groups.len());
+
+        for (i, receipts) in groups.into_values().enumerate() {
+            let currency = receipts[0].currency.clone();
+            let total = receipts
+                .iter()
+                .fold(0u64, |acc, r| acc.saturating_add(r.amount));
+
+            let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+                .sender(&self.merchant_address)
+                .receiver(&self.treasury_address)
+                .amount(Amount::new(total, currency.clone()))
+                .fee(self.config.payout_fee)
+                .nonce(next_nonce + i as u64)
+                .payload(format!("NTP-PAYOUT:{}", payout_id).into_bytes())
+                .build()
+                .map_err(|e| NtpError::TransactionError(e.to_string()))?;
+            sign_transaction(&mut tx, keypair);
+
+            lines.push(PayoutLine {
+                currency,
+                total,
+                tx_hash: tx.id.clone(),
+                receipt_ids: receipts.iter().map(|r| r.receipt_id.clone()).collect(),
+                source_tx_hashes: receipts
+                    .iter()
+                    .map(|r| r.transaction_hash.clone())
+                    .collect(),
+            });
+            transactions.push(tx);
+        }
+
+        self.pending.clear();
+        self.last_payout_at = now_ms;
+
+        Ok(Some(MerchantPayout {
+            transactions,
+            report: ReconciliationReport {
+                payout_id,
+                merchant: self.merchant_address.clone(),
+                treasury: self.treasury_address.clone(),
+                created_at: now_ms,
+                lines,
+            },
+        }))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::NovaId;
+    use crate::ntp::receipt::{countersign_receipt, sign_receipt_as_sender};
+
+    struct Fixture {
+        merchant: NovaKeypair,
+        merchant_addr: String,
+        treasury_addr: String,
+    }
+
+    fn fixture() -> Fixture {
+        let merchant = NovaKeypair::generate();
+        let merchant_addr = NovaId::from_public_key(&merchant.public_key()).to_address();
+        let treasury = NovaKeypair::generate();
+        let treasury_addr = NovaId::from_public_key(&treasury.public_key()).to_address();
+        Fixture {
+            merchant,
+            merchant_addr,
+            treasury_addr,
+        }
+    }
+
+    fn signed_receipt(f: &Fixture, amount: u64, currency: Currency) -> PaymentReceipt {
+        let payer = NovaKeypair::generate();
+        let mut receipt = PaymentReceipt {
+            receipt_id: Uuid::new_v4().to_string(),
+            session_id: "session".to_string(),
+            transaction_hash: format!("tx-{}", Uuid::new_v4()),
+            block_height: 10,
+            sender: NovaId::from_public_key(&payer.public_key()).to_address(),
+            sender_pubkey: payer.public_key(),
+            receiver: f.merchant_addr.clone(),
+            receiver_pubkey: f.merchant.public_key(),
+            amount,
+            currency,
+            timestamp: 1_700_000_000_000,
+            sender_signature: None,
+            receiver_signature: None,
+            witnesses: Vec::new(),
+            witness_threshold: 0,
+        };
+        sign_receipt_as_sender(&mut receipt, &payer);
+        countersign_receipt(&mut receipt, &f.merchant).unwrap();
+        receipt
+    }
+
+    #[test]
+    fn payout_consolidates_per_currency() {
+        let f = fixture();
+        let mut batcher = MerchantBatcher::new(
+            &f.merchant_addr,
+            &f.treasury_addr,
+            BatchingConfig::default(),
+            0,
+        );
+
+        let r1 = signed_receipt(&f, 500, Currency::BRL);
+        let r2 = signed_receipt(&f, 700, Currency::BRL);
+        let r3 = signed_receipt(&f, 900, Currency::USD);
+        let r1_id = r1.receipt_id.clone();
+        batcher.add_receipt(r1).unwrap();
+        batcher.add_receipt(r2).unwrap();
+        batcher.add_receipt(r3).unwrap();
+
+        let payout = batcher
+            .build_payout(&f.merchant, 5, 1_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(payout.transactions.len(), 2);
+        assert_eq!(payout.report.receipt_count(), 3);
+        assert_eq!(payout.report.total_for(&Currency::BRL), 1_200);
+        assert_eq!(payout.report.total_for(&Currency::USD), 900);
+
+        // Every transfer is signed, sweeps to the treasury, and uses
+        // consecutive nonces.
+        for (i, tx) in payout.transactions.iter().enumerate() {
+            assert!(tx.is_signed());
+            assert_eq!(tx.receiver, f.treasury_addr);
+            assert_eq!(tx.nonce, 5 + i as u64);
+        }
+
+        let line = payout.report.line_for_receipt(&r1_id).unwrap();
+        assert_eq!(line.currency, Currency::BRL);
+        assert!(payout.transactions.iter().any(|tx| tx.id == line.tx_hash));
+
+        assert_eq!(batcher.pending_count(), 0);
+        assert!(batcher
+            .build_payout(&f.merchant, 7, 2_000)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn should_payout_respects_interval_and_cap() {
+        let f = fixture();
+        let config = BatchingConfig {
+            payout_interval_ms: 10_000,
+            max_receipts_per_payout: 2,
+            ..BatchingConfig::default()
+        };
+        let mut batcher = MerchantBatcher::new(&f.merchant_addr, &f.treasury_addr, config, 0);
+
+        assert!(!batcher.should_payout(50_000), "nothing pending");
+
+        batcher
+            .add_receipt(signed_receipt(&f, 1, Currency::NOVA))
+            .unwrap();
+        assert!(!batcher.should_payout(5_000));
+        assert!(batcher.should_payout(10_000));
+
+        batcher
+            .add_receipt(signed_receipt(&f, 1, Currency::NOVA))
+            .unwrap();
+        assert!(batcher.should_payout(1), "cap reached");
+    }
+
+    #[test]
+    fn rejects_duplicate_and_foreign_receipts() {
+        let f = fixture();
+        let mut batcher = MerchantBatcher::new(
+            &f.merchant_addr,
+            &f.treasury_addr,
+            BatchingConfig::default(),
+            0,
+        );
+
+        let receipt = signed_receipt(&f, 100, Currency::BRL);
+        batcher.add_receipt(receipt.clone()).unwrap();
+        assert!(batcher.add_receipt(receipt).is_err());
+
+        let other = fixture();
+        let foreign = signed_receipt(&other, 100, Currency::BRL);
+        assert!(batcher.add_receipt(foreign).is_err());
+
+        let mut unsigned = signed_receipt(&f, 100, Currency::BRL);
+        unsigned.receiver_signature = None;
+        assert!(batcher.add_receipt(unsigned).is_err());
+
+        assert_eq!(batcher.pending_count(), 1);
+    }
+}
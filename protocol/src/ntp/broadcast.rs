@@ -18,17 +18,25 @@
 //! The `BroadcastMessage` is what gets transmitted over the libp2p gossipsub
 //! topic `nova/txs/1.0.0`. Validators pull transactions from this topic
 //! and submit them to the mempool.
+//!
+//! ## Delayed Capture
+//!
+//! When the receiver asked for [`CaptureMode::Delayed`], the payer signs an
+//! `AuthorizationHold` instead of a `Transfer`. The merchant later settles
+//! with [`prepare_capture`], as many times as it likes, up to the held
+//! amount and before the hold expires.
 
 use serde::{Deserialize, Serialize};
 
 use crate::config;
 use crate::crypto::keys::NovaKeypair;
+use crate::storage::hold::{CaptureTerms, HoldTerms};
 use crate::transaction::builder::{Transaction, TransactionBuilder};
 use crate::transaction::signing::sign_transaction;
 use crate::transaction::types::{Amount, TransactionType};
 
 use super::error::NtpError;
-use super::handshake::EstablishedSession;
+use super::handshake::{CaptureMode, EstablishedSession};
 
 // ---------------------------------------------------------------------------
 // SignedTransaction
@@ -95,6 +103,8 @@ pub struct BroadcastMessage {
 /// # Returns
 ///
 /// An unsigned `Transaction` with all fields populated except `signature`.
+/// For delayed-capture sessions this is an `AuthorizationHold` whose
+/// expiry is `now + hold_window_ms`.
 pub fn prepare_transaction(
     session: &EstablishedSession,
     sender_address: &str,
@@ -102,18 +112,75 @@ pub fn prepare_transaction(
 ) -> Result<Transaction, NtpError> {
     let params = &session.payment_params;
 
-    let tx = TransactionBuilder::new(TransactionType::Transfer)
+    let builder = match params.capture {
+        CaptureMode::Immediate => TransactionBuilder::new(TransactionType::Transfer)
+            .payload(format!("NTP:{} | {}", session.session_id, params.description).into_bytes()),
+        CaptureMode::Delayed { hold_window_ms } => {
+            let now = now_ms();
+            let terms = HoldTerms {
+                expires_at: now.saturating_add(hold_window_ms),
+                reference: format!("NTP:{} | {}", session.session_id, params.description),
+            };
+            let payload = serde_json::to_vec(&terms)
+                .map_err(|e| NtpError::TransactionError(e.to_string()))?;
+            TransactionBuilder::new(TransactionType::AuthorizationHold)
+                .timestamp(now)
+                .payload(payload)
+        }
+    };
+
+    let tx = builder
         .sender(sender_address)
         .receiver(&session.peer_nova_id)
         .amount(Amount::new(params.amount, params.currency.clone()))
         .fee(config::MIN_TX_FEE_PHOTONS)
         .nonce(nonce)
-        .payload(format!("NTP:{} | {}", session.session_id, params.description).into_bytes())
-        .build();
+        .build()
+        .map_err(|e| NtpError::TransactionError(e.to_string()))?;
 
     Ok(tx)
 }
 
+/// Prepare an unsigned capture against an authorization hold.
+///
+/// Called by the merchant (the hold's receiver). `amount` may be less than
+/// the held amount; set `final_capture` to release the remainder to the
+/// payer immediately instead of waiting for expiry.
+///
+/// # Arguments
+///
+/// * `merchant_address` — The merchant's NOVA address (signs the capture).
+/// * `payer_address` — The account holding the funds.
+/// * `hold_id` — ID of the `AuthorizationHold` transaction.
+/// * `amount` — Amount to capture, in the hold's currency.
+/// * `final_capture` — Whether this is the last capture for the hold.
+/// * `nonce` — The merchant's current account nonce.
+pub fn prepare_capture(
+    merchant_address: &str,
+    payer_address: &str,
+    hold_id: &str,
+    amount: Amount,
+    final_capture: bool,
+    nonce: u64,
+) -> Result<Transaction, NtpError> {
+    let terms = CaptureTerms {
+        hold_id: hold_id.to_string(),
+        final_capture,
+    };
+    let payload =
+        serde_json::to_vec(&terms).map_err(|e| NtpError::TransactionError(e.to_string()))?;
+
+    TransactionBuilder::new(TransactionType::HoldCapture)
+        .sender(merchant_address)
+        .receiver(payer_address)
+        .amount(amount)
+        .fee(config::MIN_TX_FEE_PHOTONS)
+        .nonce(nonce)
+        .payload(payload)
+        .build()
+        .map_err(|e| NtpError::TransactionError(e.to_string()))
+}
+
 /// Sign a transaction and wrap it as a [`SignedTransaction`].
 ///
 /// Attaches the sender's Ed25519 signature to the transaction body,
@@ -149,21 +216,24 @@ pub fn sign_and_prepare(
 /// * `signed_tx` — The signed transaction.
 /// * `network_id` — The network this transaction targets (mainnet/testnet/devnet).
 pub fn create_broadcast_message(signed_tx: SignedTransaction, network_id: u32) -> BroadcastMessage {
-    let broadcast_timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64;
-
     BroadcastMessage {
         signed_tx,
         protocol_version: config::PROTOCOL_VERSION.to_string(),
         network_id,
         ttl: config::GOSSIP_FANOUT as u8,
-        broadcast_timestamp,
+        broadcast_timestamp: now_ms(),
         priority: 0,
     }
 }
 
+/// Current Unix time in milliseconds.
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -172,10 +242,19 @@ pub fn create_broadcast_message(signed_tx: SignedTransaction, network_id: u32) -
 mod tests {
     use super::*;
     use crate::crypto::keys::NovaKeypair;
-    use crate::ntp::handshake::{HandshakeSession, PaymentParams};
+    use crate::identity::NovaId;
+    use crate::ntp::handshake::{CaptureMode, HandshakeSession, PaymentParams};
     use crate::transaction::types::Currency;
 
+    fn address() -> String {
+        NovaId::from_public_key(&NovaKeypair::generate().public_key()).to_address()
+    }
+
     fn setup_session_and_keypair() -> (EstablishedSession, NovaKeypair) {
+        setup_session_with_capture(CaptureMode::Immediate)
+    }
+
+    fn setup_session_with_capture(capture: CaptureMode) -> (EstablishedSession, NovaKeypair) {
         let sender_kp = NovaKeypair::generate();
         let receiver_kp = NovaKeypair::generate();
 
@@ -183,6 +262,7 @@ mod tests {
             amount: 2500,
             currency: Currency::BRL,
             description: "Test payment".to_string(),
+            capture,
         };
 
         let (sender_session, request) = HandshakeSession::initiate(&sender_kp, vec![Currency::BRL]);
@@ -196,18 +276,56 @@ mod tests {
     fn prepare_transaction_builds_correctly() {
         let (session, _kp) = setup_session_and_keypair();
 
-        let tx = prepare_transaction(&session, "nova:sender123", 0)
+        let tx = prepare_transaction(&session, &session.our_nova_id, 0)
             .expect("transaction preparation should succeed");
 
         assert_eq!(tx.amount.value, 2500);
         assert_eq!(tx.amount.currency, Currency::BRL);
         assert_eq!(tx.tx_type, TransactionType::Transfer);
-        assert_eq!(tx.sender, "nova:sender123");
+        assert_eq!(tx.sender, session.our_nova_id);
         assert_eq!(tx.receiver, session.peer_nova_id);
         assert_eq!(tx.nonce, 0);
         assert!(!tx.is_signed(), "unsigned tx should have no signature");
     }
 
+    #[test]
+    fn delayed_capture_prepares_authorization_hold() {
+        let (session, _kp) = setup_session_with_capture(CaptureMode::Delayed {
+            hold_window_ms: 60_000,
+        });
+
+        let tx = prepare_transaction(&session, &session.our_nova_id, 1).unwrap();
+        assert_eq!(tx.tx_type, TransactionType::AuthorizationHold);
+        assert_eq!(tx.amount.value, 2500);
+
+        let terms: HoldTerms = serde_json::from_slice(tx.payload.as_ref().unwrap()).unwrap();
+        assert_eq!(terms.expires_at, tx.timestamp + 60_000);
+        assert!(terms.reference.contains(&session.session_id));
+    }
+
+    #[test]
+    fn prepare_capture_references_hold() {
+        let merchant = address();
+        let payer = address();
+        let tx = prepare_capture(
+            &merchant,
+            &payer,
+            "hold-123",
+            Amount::new(1_000, Currency::BRL),
+            true,
+            4,
+        )
+        .unwrap();
+
+        assert_eq!(tx.tx_type, TransactionType::HoldCapture);
+        assert_eq!(tx.sender, merchant);
+        assert_eq!(tx.receiver, payer);
+
+        let terms: CaptureTerms = serde_json::from_slice(tx.payload.as_ref().unwrap()).unwrap();
+        assert_eq!(terms.hold_id, "hold-123");
+        assert!(terms.final_capture);
+    }
+
     #[test]
     fn sign_and_prepare_attaches_signature() {
         let (session, kp) = setup_session_and_keypair();
@@ -7,6 +7,7 @@ use thiserror::Error;
 
 /// Errors that can occur during the NTP payment flow.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum NtpError {
     /// The handshake could not be completed (version mismatch, bad key, etc.).
     #[error("handshake failed: {0}")]
@@ -33,6 +34,39 @@ pub enum NtpError {
     #[error("proof of funds verification failed: {0}")]
     ProofVerificationFailed(String),
 
+    /// The proof of funds is anchored to a block too far behind the tip.
+    #[error("stale proof of funds: anchored at height {anchor_height}, tip is {tip_height}")]
+    StaleProof {
+        /// Height the proof is anchored to.
+        anchor_height: u64,
+        /// The verifier's chain tip.
+        tip_height: u64,
+    },
+
+    /// A channel advertisement is malformed or not signed by its node.
+    #[error("invalid channel advertisement: {0}")]
+    InvalidAdvertisement(String),
+
+    /// No known channels connect the sender to the recipient for the amount.
+    #[error("no route from {from} to {to} for {amount}")]
+    NoRoute {
+        /// The paying node.
+        from: String,
+        /// The recipient.
+        to: String,
+        /// Amount to deliver.
+        amount: u64,
+    },
+
+    /// Every route tried failed at some hop.
+    #[error("payment failed after {attempts} attempts; last: {last}")]
+    PaymentFailed {
+        /// Routes tried.
+        attempts: usize,
+        /// The last hop failure.
+        last: String,
+    },
+
     /// The sender's balance is insufficient for the requested payment.
     #[error("insufficient funds: required {required}, proof covers {proven}")]
     InsufficientFunds {
@@ -63,6 +97,14 @@ pub enum NtpError {
     #[error("invalid receipt signature: {0}")]
     InvalidReceiptSignature(String),
 
+    /// A receipt witness slot is malformed, duplicated, or added too late.
+    #[error("invalid receipt witness: {0}")]
+    InvalidWitness(String),
+
+    /// A settlement cancellation is unsigned, mis-signed, or mismatched.
+    #[error("invalid cancellation: {0}")]
+    InvalidCancellation(String),
+
     /// A cryptographic operation failed (key derivation, encryption, etc.).
     #[error("crypto error: {0}")]
     CryptoError(String),
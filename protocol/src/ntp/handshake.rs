@@ -64,6 +64,28 @@ pub struct PaymentParams {
     pub currency: Currency,
     /// Human-readable description (e.g., "Coffee at Nova Cafe").
     pub description: String,
+    /// Whether the payer settles immediately or authorizes a hold that
+    /// the receiver captures later. Defaults to immediate settlement.
+    #[serde(default)]
+    pub capture: CaptureMode,
+}
+
+/// How a payment settles on-chain.
+///
+/// `Delayed` turns the payment into an authorization hold: the amount is
+/// locked against the receiver, who captures up to it (possibly in parts)
+/// within the window. Anything left uncaptured returns to the payer at
+/// expiry — no refund request, no chargeback.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptureMode {
+    /// Plain transfer — funds move when the transaction is included.
+    #[default]
+    Immediate,
+    /// Authorize now, capture later.
+    Delayed {
+        /// How long the hold stays capturable, in milliseconds.
+        hold_window_ms: u64,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -264,6 +286,17 @@ impl HandshakeSession {
             )));
         }
 
+        // Holds must be bounded — an unbounded window is a frozen balance.
+        if let CaptureMode::Delayed { hold_window_ms } = payment_params.capture {
+            if hold_window_ms == 0 || hold_window_ms > config::MAX_AUTHORIZATION_HOLD_MS {
+                return Err(NtpError::HandshakeFailed(format!(
+                    "hold window {}ms outside 1..={}ms",
+                    hold_window_ms,
+                    config::MAX_AUTHORIZATION_HOLD_MS
+                )));
+            }
+        }
+
         let receiver_pubkey = keypair.public_key();
         let receiver_nova_id = NovaId::from_public_key(&receiver_pubkey);
         let session_id = Uuid::new_v4().to_string();
@@ -358,6 +391,7 @@ mod tests {
             amount: 5000,
             currency: Currency::BRL,
             description: "Coffee at Nova Cafe".to_string(),
+            capture: CaptureMode::Immediate,
         };
 
         // Step 1: Sender initiates.
@@ -398,6 +432,7 @@ mod tests {
             amount: 1000,
             currency: Currency::USD, // Not in sender's list.
             description: "test".to_string(),
+            capture: CaptureMode::Immediate,
         };
 
         let (_session, request) = HandshakeSession::initiate(&sender_kp, currencies);
@@ -410,6 +445,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn unbounded_hold_window_rejected() {
+        let sender_kp = NovaKeypair::generate();
+        let receiver_kp = NovaKeypair::generate();
+
+        let payment = PaymentParams {
+            amount: 1000,
+            currency: Currency::NOVA,
+            description: "hotel deposit".to_string(),
+            capture: CaptureMode::Delayed {
+                hold_window_ms: config::MAX_AUTHORIZATION_HOLD_MS + 1,
+            },
+        };
+
+        let (_session, request) = HandshakeSession::initiate(&sender_kp, vec![Currency::NOVA]);
+        let result = HandshakeSession::respond(&request, &receiver_kp, payment);
+        assert!(matches!(result, Err(NtpError::HandshakeFailed(_))));
+    }
+
     #[test]
     fn double_complete_fails() {
         let sender_kp = NovaKeypair::generate();
@@ -420,6 +474,7 @@ mod tests {
             amount: 100,
             currency: Currency::NOVA,
             description: "test".to_string(),
+            capture: CaptureMode::Immediate,
         };
 
         let (sender_session, request) = HandshakeSession::initiate(&sender_kp, currencies);
@@ -36,7 +36,8 @@
 //! ### Step 2 — Proof of Funds (`proof_request.rs`)
 //! The receiver issues a challenge. The sender responds with a Groth16
 //! zero-knowledge proof demonstrating `balance >= amount` without
-//! revealing the actual balance.
+//! revealing the actual balance, bound to the challenge and a recent block
+//! so it cannot be replayed.
 //!
 //! ### Step 3 — Broadcast (`broadcast.rs`)
 //! The sender constructs, signs, and broadcasts the transaction to the
@@ -48,7 +49,18 @@
 //!
 //! ### Step 5 — Receipt (`receipt.rs`)
 //! Both parties sign a receipt confirming the payment. This dual-signed
-//! receipt serves as non-repudiable proof of payment.
+//! receipt serves as non-repudiable proof of payment. High-value receipts
+//! can add role-labelled witness co-signers with a signing threshold.
+//!
+//! ### Multi-Hop Routing (`routing.rs`)
+//! Not a protocol step either: when the sender has no session with the
+//! recipient, it routes the payment over advertised forwarding channels
+//! with HTLCs, paying per-hop fees and retrying around failed hops.
+//!
+//! ### Merchant Batching (`batching.rs`)
+//! Not a protocol step: merchants feed confirmed receipts into a
+//! [`MerchantBatcher`], which periodically sweeps them into one treasury
+//! transfer per currency along with a reconciliation report.
 //!
 //! ## Session Encryption
 //!
@@ -57,19 +69,27 @@
 //! keys for perfect forward secrecy — compromising a long-term key does
 //! not reveal past session traffic.
 
+pub mod batching;
 pub mod broadcast;
 pub mod handshake;
 pub mod proof_request;
 pub mod receipt;
+pub mod routing;
 pub mod settlement;
 
 mod error;
 
-pub use broadcast::{BroadcastMessage, SignedTransaction};
+pub use batching::{BatchingConfig, MerchantBatcher, MerchantPayout, ReconciliationReport};
+pub use broadcast::{prepare_capture, BroadcastMessage, SignedTransaction};
 pub use error::NtpError;
 pub use handshake::{
-    EstablishedSession, HandshakeRequest, HandshakeResponse, HandshakeSession, PaymentParams,
+    CaptureMode, EstablishedSession, HandshakeRequest, HandshakeResponse, HandshakeSession,
+    PaymentParams,
+};
+pub use proof_request::{ChainView, ProofAnchor, ProofOfFundsRequest, ProofOfFundsResponse};
+pub use receipt::{PaymentReceipt, ReceiptWitness};
+pub use routing::{ChannelAdvertisement, ChannelGraph, Htlc, Route, RouteHop};
+pub use settlement::{
+    CompensationAction, SettlementCancellation, SettlementResult, SettlementState,
+    SettlementStateMachine, SettlementTimeouts, SettlementTransition, ValidationRequest,
 };
-pub use proof_request::{ProofOfFundsRequest, ProofOfFundsResponse};
-pub use receipt::PaymentReceipt;
-pub use settlement::{SettlementResult, SettlementStateMachine, ValidationRequest};
@@ -7,8 +7,7 @@
 //!
 //! The proof uses the Groth16 zero-knowledge proof system over BN254.
 //! The sender proves `balance >= required_amount` without revealing the
-//! actual balance. The proof is bound to a challenge nonce to prevent
-//! replay attacks.
+//! actual balance.
 //!
 //! ## Protocol Flow
 //!
@@ -18,16 +17,29 @@
 //! }
 //!
 //! Sender → Receiver: ProofOfFundsResponse {
-//!     session_id, zkp_proof, commitment, timestamp
+//!     session_id, zkp_proof, commitment, anchor, timestamp
 //! }
 //! ```
 //!
 //! The receiver verifies the proof using the public verification key.
 //! If verification passes, the protocol advances to the broadcast step.
+//!
+//! ## Freshness
+//!
+//! The challenge nonce and the hash of a recent block (the *anchor*, picked
+//! by the sender from its view of the chain) are public inputs of the proof
+//! ([`ProofContext`]). The receiver verifies against its own request's
+//! nonce, so a proof made for any earlier request does not verify, and
+//! checks that the anchor is on its chain and at most
+//! [`PROOF_ANCHOR_MAX_AGE`] blocks behind the tip, so a proof cannot be
+//! generated early and held back.
 
 use serde::{Deserialize, Serialize};
 
+use crate::crypto::wire::BlockHash;
+use crate::storage::db::NovaDB;
 use crate::transaction::types::Currency;
+use crate::zkp::circuit::ProofContext;
 use crate::zkp::commitment::{self, Commitment, PedersenParams};
 use crate::zkp::prover::{BalanceProof, BalanceProver};
 use crate::zkp::verifier::BalanceVerifier;
@@ -38,6 +50,56 @@ use super::handshake::EstablishedSession;
 use ark_bn254::Fr;
 use ark_ff::UniformRand;
 
+/// Oldest anchor, in blocks behind the verifier's tip, a proof may use.
+/// 30 blocks is one minute at the 2-second block time: enough for the
+/// sender to prove and the message to travel, not enough to stockpile.
+pub const PROOF_ANCHOR_MAX_AGE: u64 = 30;
+
+// ---------------------------------------------------------------------------
+// Chain Anchors
+// ---------------------------------------------------------------------------
+
+/// The block a proof of funds is anchored to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofAnchor {
+    /// Height of the anchor block.
+    pub height: u64,
+    /// Hash of the anchor block.
+    pub hash: BlockHash,
+}
+
+/// Read access to the chain, for picking and checking proof anchors.
+pub trait ChainView {
+    /// Height of the latest block, if any.
+    fn tip_height(&self) -> Option<u64>;
+    /// Hash of the block at `height`, if known.
+    fn block_hash(&self, height: u64) -> Option<BlockHash>;
+}
+
+impl ChainView for NovaDB {
+    fn tip_height(&self) -> Option<u64> {
+        self.get_latest_block_height().ok().flatten()
+    }
+
+    fn block_hash(&self, height: u64) -> Option<BlockHash> {
+        self.get_block(height)
+            .ok()
+            .flatten()
+            .map(|block| block.header.hash)
+    }
+}
+
+impl ProofAnchor {
+    /// The chain tip, which is the freshest possible anchor.
+    pub fn latest(chain: &impl ChainView) -> Option<Self> {
+        let height = chain.tip_height()?;
+        Some(Self {
+            height,
+            hash: chain.block_hash(height)?,
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Request / Response
 // ---------------------------------------------------------------------------
@@ -72,6 +134,8 @@ pub struct ProofOfFundsResponse {
     pub zkp_proof: Vec<u8>,
     /// Serialized Pedersen commitment (compressed BN254/G1 point).
     pub commitment: Vec<u8>,
+    /// Recent block the proof is bound to.
+    pub anchor: ProofAnchor,
     /// Unix timestamp (milliseconds) of proof generation.
     pub timestamp: u64,
 }
@@ -111,13 +175,15 @@ pub fn request_proof_of_funds(
 ///
 /// Called by the **sender** upon receiving a [`ProofOfFundsRequest`].
 /// The sender commits to their balance using a fresh Pedersen commitment,
-/// then generates a Groth16 proof that `balance >= required_amount`.
+/// then generates a Groth16 proof that `balance >= required_amount`, bound
+/// to the request's challenge and to `anchor`.
 ///
 /// # Arguments
 ///
 /// * `request` — The proof request from the receiver.
 /// * `session` — The established NTP session (for session_id validation).
 /// * `balance` — The sender's actual balance in the requested currency.
+/// * `anchor` — A recent block, usually [`ProofAnchor::latest`].
 /// * `prover` — The Groth16 prover (holds the proving key).
 /// * `pedersen_params` — Public Pedersen commitment parameters.
 ///
@@ -130,6 +196,7 @@ pub fn generate_proof_response(
     request: &ProofOfFundsRequest,
     session: &EstablishedSession,
     balance: u64,
+    anchor: ProofAnchor,
     prover: &BalanceProver,
     _pedersen_params: &PedersenParams,
 ) -> Result<ProofOfFundsResponse, NtpError> {
@@ -154,9 +221,18 @@ pub fn generate_proof_response(
     // Commit to our balance.
     let comm = commitment::commit(params, balance, blinding);
 
-    // Generate the Groth16 proof: balance >= required_amount.
+    // Generate the Groth16 proof: balance >= required_amount, under this
+    // request's challenge and anchor.
+    let context = ProofContext::new(request.challenge_nonce, anchor.hash.0);
     let proof = prover
-        .prove(balance, blinding, request.required_amount, params, &comm)
+        .prove_with_context(
+            balance,
+            blinding,
+            request.required_amount,
+            params,
+            &comm,
+            &context,
+        )
         .map_err(|e| NtpError::ProofVerificationFailed(e.to_string()))?;
 
     let timestamp = std::time::SystemTime::now()
@@ -168,32 +244,45 @@ pub fn generate_proof_response(
         session_id: session.session_id.clone(),
         zkp_proof: proof.to_bytes(),
         commitment: comm.to_bytes(),
+        anchor,
         timestamp,
     })
 }
 
 /// Verify a proof-of-funds response.
 ///
-/// Called by the **receiver** after receiving the sender's proof. Validates
-/// both the Groth16 proof and the Pedersen commitment.
+/// Called by the **receiver** after receiving the sender's proof. Checks the
+/// anchor against `chain`, then validates the Groth16 proof and the
+/// Pedersen commitment under the request's challenge.
 ///
 /// # Arguments
 ///
+/// * `request` — The proof request this response answers.
 /// * `response` — The proof response from the sender.
-/// * `required_amount` — The amount that was requested in the proof request.
 /// * `verifier` — The Groth16 verifier (holds the verification key).
+/// * `chain` — The receiver's view of the chain.
 /// * `pedersen_params` — Public Pedersen commitment parameters.
 ///
 /// # Returns
 ///
 /// `Ok(true)` if the proof is valid, `Ok(false)` if the proof is
-/// mathematically invalid, or `Err` if deserialization fails.
+/// mathematically invalid (including a proof made for another challenge),
+/// or `Err` if deserialization fails or the anchor is stale or unknown.
 pub fn verify_proof_of_funds(
+    request: &ProofOfFundsRequest,
     response: &ProofOfFundsResponse,
-    required_amount: u64,
     verifier: &BalanceVerifier,
+    chain: &impl ChainView,
     _pedersen_params: &PedersenParams,
 ) -> Result<bool, NtpError> {
+    if response.session_id != request.session_id {
+        return Err(NtpError::SessionMismatch {
+            expected: request.session_id.clone(),
+            got: response.session_id.clone(),
+        });
+    }
+    check_anchor(&response.anchor, chain)?;
+
     // Deserialize the commitment.
     let comm = Commitment::from_bytes(&response.commitment)
         .map_err(|e| NtpError::ProofVerificationFailed(format!("bad commitment: {}", e)))?;
@@ -208,13 +297,35 @@ pub fn verify_proof_of_funds(
     let params = verifier.pedersen_params();
 
     // Verify the Groth16 proof.
+    let context = ProofContext::new(request.challenge_nonce, response.anchor.hash.0);
     let valid = verifier
-        .verify(&proof, &comm, required_amount, params)
+        .verify_with_context(&proof, &comm, request.required_amount, params, &context)
         .map_err(|e| NtpError::ProofVerificationFailed(e.to_string()))?;
 
     Ok(valid)
 }
 
+/// Checks that `anchor` is on `chain` and within [`PROOF_ANCHOR_MAX_AGE`]
+/// blocks of its tip.
+fn check_anchor(anchor: &ProofAnchor, chain: &impl ChainView) -> Result<(), NtpError> {
+    let tip = chain
+        .tip_height()
+        .ok_or_else(|| NtpError::ProofVerificationFailed("no chain to check anchor".into()))?;
+    if tip.saturating_sub(anchor.height) > PROOF_ANCHOR_MAX_AGE {
+        return Err(NtpError::StaleProof {
+            anchor_height: anchor.height,
+            tip_height: tip,
+        });
+    }
+    if chain.block_hash(anchor.height) != Some(anchor.hash) {
+        return Err(NtpError::ProofVerificationFailed(format!(
+            "anchor block at height {} is not on our chain",
+            anchor.height
+        )));
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -223,9 +334,32 @@ pub fn verify_proof_of_funds(
 mod tests {
     use super::*;
     use crate::crypto::keys::NovaKeypair;
-    use crate::ntp::handshake::{HandshakeSession, PaymentParams};
+    use crate::ntp::handshake::{CaptureMode, HandshakeSession, PaymentParams};
     use ark_std::rand::{rngs::StdRng, SeedableRng};
 
+    /// A chain of `len` blocks with distinct hashes.
+    struct TestChain(Vec<BlockHash>);
+
+    impl TestChain {
+        fn new(len: u8) -> Self {
+            Self((0..len).map(|h| BlockHash([h; 32])).collect())
+        }
+    }
+
+    impl ChainView for TestChain {
+        fn tip_height(&self) -> Option<u64> {
+            (self.0.len() as u64).checked_sub(1)
+        }
+
+        fn block_hash(&self, height: u64) -> Option<BlockHash> {
+            self.0.get(height as usize).copied()
+        }
+    }
+
+    fn anchor() -> ProofAnchor {
+        ProofAnchor::latest(&TestChain::new(1)).unwrap()
+    }
+
     fn setup_session() -> EstablishedSession {
         let sender_kp = NovaKeypair::generate();
         let receiver_kp = NovaKeypair::generate();
@@ -234,6 +368,7 @@ mod tests {
             amount: 500,
             currency: Currency::NOVA,
             description: "test".to_string(),
+            capture: CaptureMode::Immediate,
         };
 
         let (sender_session, request) =
@@ -263,21 +398,87 @@ mod tests {
         let pedersen_params = PedersenParams::setup(&mut rng);
         let (prover, verifier) = BalanceProver::setup(&mut rng);
 
+        let chain = TestChain::new(10);
         let request = request_proof_of_funds(&session, 500, Currency::NOVA);
 
         // Sender has balance 1000, needs to prove >= 500.
-        let response = generate_proof_response(&request, &session, 1000, &prover, &pedersen_params)
-            .expect("proof generation should succeed");
+        let anchor = ProofAnchor::latest(&chain).unwrap();
+        let response =
+            generate_proof_response(&request, &session, 1000, anchor, &prover, &pedersen_params)
+                .expect("proof generation should succeed");
 
         assert_eq!(response.session_id, session.session_id);
         assert!(!response.zkp_proof.is_empty());
         assert!(!response.commitment.is_empty());
 
-        let valid = verify_proof_of_funds(&response, 500, &verifier, &pedersen_params)
+        let valid = verify_proof_of_funds(&request, &response, &verifier, &chain, &pedersen_params)
             .expect("verification should not error");
         assert!(valid, "valid proof must verify");
     }
 
+    #[test]
+    fn proof_replayed_against_new_challenge_fails() {
+        let session = setup_session();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let pedersen_params = PedersenParams::setup(&mut rng);
+        let (prover, verifier) = BalanceProver::setup(&mut rng);
+        let chain = TestChain::new(10);
+
+        let first = request_proof_of_funds(&session, 500, Currency::NOVA);
+        let anchor = ProofAnchor::latest(&chain).unwrap();
+        let response =
+            generate_proof_response(&first, &session, 1000, anchor, &prover, &pedersen_params)
+                .unwrap();
+
+        let second = request_proof_of_funds(&session, 500, Currency::NOVA);
+        let valid =
+            verify_proof_of_funds(&second, &response, &verifier, &chain, &pedersen_params).unwrap();
+        assert!(!valid, "a proof must not verify under another challenge");
+    }
+
+    #[test]
+    fn stale_or_foreign_anchors_rejected() {
+        let session = setup_session();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let pedersen_params = PedersenParams::setup(&mut rng);
+        let (prover, verifier) = BalanceProver::setup(&mut rng);
+        let request = request_proof_of_funds(&session, 500, Currency::NOVA);
+
+        // Anchored at height 5; by the time it is checked the tip is 40.
+        let chain = TestChain::new(41);
+        let old = ProofAnchor {
+            height: 5,
+            hash: BlockHash([5; 32]),
+        };
+        let response =
+            generate_proof_response(&request, &session, 1000, old, &prover, &pedersen_params)
+                .unwrap();
+        let result =
+            verify_proof_of_funds(&request, &response, &verifier, &chain, &pedersen_params);
+        assert!(matches!(
+            result,
+            Err(NtpError::StaleProof {
+                anchor_height: 5,
+                tip_height: 40
+            })
+        ));
+
+        // A recent height with a hash that is not ours (another fork, or
+        // made up) is refused too.
+        let foreign = ProofAnchor {
+            height: 39,
+            hash: BlockHash([0xEE; 32]),
+        };
+        let response =
+            generate_proof_response(&request, &session, 1000, foreign, &prover, &pedersen_params)
+                .unwrap();
+        let result =
+            verify_proof_of_funds(&request, &response, &verifier, &chain, &pedersen_params);
+        assert!(matches!(result, Err(NtpError::ProofVerificationFailed(_))));
+    }
+
     #[test]
     fn insufficient_balance_proof_fails() {
         let session = setup_session();
@@ -293,7 +494,7 @@ mod tests {
         // system is unsatisfiable instead of returning Err. Wrap in
         // catch_unwind so the test handles both a panic and an Err.
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            generate_proof_response(&request, &session, 100, &prover, &pedersen_params)
+            generate_proof_response(&request, &session, 100, anchor(), &prover, &pedersen_params)
         }));
         assert!(result.is_err() || result.unwrap().is_err());
     }
@@ -309,7 +510,14 @@ mod tests {
         let mut request = request_proof_of_funds(&session, 100, Currency::NOVA);
         request.session_id = "wrong-session-id".to_string();
 
-        let result = generate_proof_response(&request, &session, 1000, &prover, &pedersen_params);
+        let result = generate_proof_response(
+            &request,
+            &session,
+            1000,
+            anchor(),
+            &prover,
+            &pedersen_params,
+        );
         assert!(matches!(result, Err(NtpError::SessionMismatch { .. })));
     }
 }
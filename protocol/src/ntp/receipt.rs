@@ -16,6 +16,20 @@
 //! 2. Sender transmits the receipt (with signature) to the receiver.
 //! 3. Receiver verifies sender's signature, countersigns, returns it.
 //! 4. Both parties store the dual-signed receipt.
+//!
+//! ## Witnesses
+//!
+//! High-value payments can name third-party witnesses, such as the
+//! logistics provider on a B2B shipment. Before signing, the sender adds
+//! a slot per witness with a role label and public key, plus how many
+//! witnesses must sign ([`PaymentReceipt::require_witnesses`]). The slots
+//! and threshold join the signed payload, so no one can drop a witness
+//! later. After the parties, each witness signs the same payload with
+//! [`sign_receipt_as_witness`]. [`verify_receipt`] then also checks every
+//! witness signature present and requires the threshold.
+//!
+//! A receipt without witness slots signs exactly the same bytes as
+//! before, so existing dual-signed receipts still verify.
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -27,10 +41,36 @@ use super::error::NtpError;
 use super::handshake::EstablishedSession;
 use super::settlement::SettlementResult;
 
+/// Maximum number of witness slots on one receipt.
+pub const MAX_RECEIPT_WITNESSES: usize = 8;
+
+/// Maximum length of a witness role label, in bytes.
+pub const MAX_WITNESS_ROLE_LEN: usize = 64;
+
 // ---------------------------------------------------------------------------
 // Receipt
 // ---------------------------------------------------------------------------
 
+/// A third-party co-signer slot on a receipt.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptWitness {
+    /// What the witness attests to, e.g. `"logistics"` or `"escrow-agent"`.
+    pub role: String,
+    /// The witness's Ed25519 public key.
+    pub pubkey: NovaPublicKey,
+    /// The witness's signature over the receipt body, once given.
+    pub signature: Option<NovaSignature>,
+}
+
+impl ReceiptWitness {
+    /// Returns `true` if the witness has signed `payload`.
+    fn has_signed(&self, payload: &[u8]) -> bool {
+        self.signature
+            .as_ref()
+            .is_some_and(|sig| self.pubkey.verify(payload, sig))
+    }
+}
+
 /// A dual-signed payment receipt proving a transaction was settled.
 ///
 /// Both parties hold a copy. Either can present it as proof of payment.
@@ -63,15 +103,23 @@ pub struct PaymentReceipt {
     pub sender_signature: Option<NovaSignature>,
     /// Receiver's Ed25519 signature over the receipt body.
     pub receiver_signature: Option<NovaSignature>,
+    /// Third-party co-signer slots, in the order they were added.
+    #[serde(default)]
+    pub witnesses: Vec<ReceiptWitness>,
+    /// Witness signatures required for the receipt to verify.
+    #[serde(default)]
+    pub witness_threshold: u32,
 }
 
 impl PaymentReceipt {
     /// Compute the canonical byte representation of the receipt body.
     ///
-    /// This is the message that both parties sign. It excludes the
-    /// signature fields to avoid circular dependencies.
+    /// This is the message that both parties and every witness sign. It
+    /// excludes the signature fields to avoid circular dependencies.
+    /// Witness slots are appended only when present, so dual-signed
+    /// receipts keep their original payload.
     pub fn signing_payload(&self) -> Vec<u8> {
-        let canonical = format!(
+        let mut canonical = format!(
             "{}:{}:{}:{}:{}:{}:{}:{}:{}:{}",
             self.receipt_id,
             self.session_id,
@@ -84,6 +132,16 @@ impl PaymentReceipt {
             self.timestamp,
             hex::encode(self.sender_pubkey.as_bytes()),
         );
+        if !self.witnesses.is_empty() || self.witness_threshold > 0 {
+            canonical.push_str(&format!(":witnesses:{}", self.witness_threshold));
+            for witness in &self.witnesses {
+                canonical.push_str(&format!(
+                    ":{}={}",
+                    hex::encode(witness.role.as_bytes()),
+                    hex::encode(witness.pubkey.as_bytes()),
+                ));
+            }
+        }
         canonical.into_bytes()
     }
 
@@ -91,6 +149,93 @@ impl PaymentReceipt {
     pub fn is_fully_signed(&self) -> bool {
         self.sender_signature.is_some() && self.receiver_signature.is_some()
     }
+
+    /// Adds a witness slot. Must happen before anyone signs, since the
+    /// slots are part of the signed payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receipt is already signed, the role is
+    /// empty or too long, the key already has a slot, or the receipt has
+    /// [`MAX_RECEIPT_WITNESSES`] slots.
+    pub fn add_witness(&mut self, role: &str, pubkey: NovaPublicKey) -> Result<(), NtpError> {
+        self.ensure_unsigned()?;
+        if role.is_empty() || role.len() > MAX_WITNESS_ROLE_LEN {
+            return Err(NtpError::InvalidWitness(format!(
+                "role must be 1 to {} bytes",
+                MAX_WITNESS_ROLE_LEN
+            )));
+        }
+        if self.witnesses.len() >= MAX_RECEIPT_WITNESSES {
+            return Err(NtpError::InvalidWitness(format!(
+                "at most {} witnesses per receipt",
+                MAX_RECEIPT_WITNESSES
+            )));
+        }
+        if self.witnesses.iter().any(|w| w.pubkey == pubkey) {
+            return Err(NtpError::InvalidWitness(
+                "key already witnesses this receipt".to_string(),
+            ));
+        }
+        self.witnesses.push(ReceiptWitness {
+            role: role.to_string(),
+            pubkey,
+            signature: None,
+        });
+        Ok(())
+    }
+
+    /// Sets how many witnesses must sign. Zero makes every witness
+    /// optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receipt is already signed or there are
+    /// fewer slots than `threshold`.
+    pub fn require_witnesses(&mut self, threshold: u32) -> Result<(), NtpError> {
+        self.ensure_unsigned()?;
+        if threshold as usize > self.witnesses.len() {
+            return Err(NtpError::InvalidWitness(format!(
+                "threshold {} exceeds {} witness slots",
+                threshold,
+                self.witnesses.len()
+            )));
+        }
+        self.witness_threshold = threshold;
+        Ok(())
+    }
+
+    /// The witness slot for `role`, if any.
+    pub fn witness(&self, role: &str) -> Option<&ReceiptWitness> {
+        self.witnesses.iter().find(|w| w.role == role)
+    }
+
+    /// Witnesses whose signatures verify against the current payload.
+    pub fn verified_witnesses(&self) -> Vec<&ReceiptWitness> {
+        let payload = self.signing_payload();
+        self.witnesses
+            .iter()
+            .filter(|w| w.has_signed(&payload))
+            .collect()
+    }
+
+    /// Returns `true` if enough witnesses have validly signed to meet the
+    /// threshold.
+    pub fn is_witnessed(&self) -> bool {
+        self.verified_witnesses().len() >= self.witness_threshold as usize
+    }
+
+    fn ensure_unsigned(&self) -> Result<(), NtpError> {
+        let signed = self.sender_signature.is_some()
+            || self.receiver_signature.is_some()
+            || self.witnesses.iter().any(|w| w.signature.is_some());
+        if signed {
+            return Err(NtpError::InvalidWitness(
+                "witness slots must be set before signing".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -128,6 +273,8 @@ pub fn generate_receipt(
             timestamp: *block_timestamp,
             sender_signature: None,
             receiver_signature: None,
+            witnesses: Vec::new(),
+            witness_threshold: 0,
         }),
         SettlementResult::Rejected { reason, .. } => {
             Err(NtpError::SettlementRejected(reason.clone()))
@@ -180,15 +327,71 @@ pub fn countersign_receipt(
     Ok(())
 }
 
+/// Co-sign a receipt as one of its witnesses.
+///
+/// Verifies the sender's and receiver's signatures first, so a witness
+/// only attests to a receipt both parties have agreed to.
+///
+/// # Errors
+///
+/// Returns an error if `keypair` has no witness slot, or either party's
+/// signature is missing or invalid.
+pub fn sign_receipt_as_witness(
+    receipt: &mut PaymentReceipt,
+    keypair: &NovaKeypair,
+) -> Result<(), NtpError> {
+    let pubkey = keypair.public_key();
+    let slot = receipt
+        .witnesses
+        .iter()
+        .position(|w| w.pubkey == pubkey)
+        .ok_or_else(|| NtpError::InvalidWitness("key has no witness slot".to_string()))?;
+    verify_parties(receipt)?;
+
+    let sig = keypair.sign(&receipt.signing_payload());
+    receipt.witnesses[slot].signature = Some(sig);
+    Ok(())
+}
+
 /// Verify a fully signed receipt.
 ///
 /// Checks both the sender's and receiver's signatures against the
-/// receipt's canonical payload.
+/// receipt's canonical payload, then every witness signature present,
+/// and that at least `witness_threshold` witnesses signed.
 ///
 /// # Errors
 ///
-/// Returns an error if either signature is missing or invalid.
+/// Returns an error if either party's signature is missing or invalid, a
+/// witness signature is invalid, or too few witnesses signed.
 pub fn verify_receipt(receipt: &PaymentReceipt) -> Result<bool, NtpError> {
+    verify_parties(receipt)?;
+
+    let payload = receipt.signing_payload();
+    let mut signed = 0;
+    for witness in &receipt.witnesses {
+        if witness.signature.is_none() {
+            continue;
+        }
+        if !witness.has_signed(&payload) {
+            return Err(NtpError::InvalidReceiptSignature(format!(
+                "{} witness signature invalid",
+                witness.role
+            )));
+        }
+        signed += 1;
+    }
+    if signed < receipt.witness_threshold as usize {
+        return Err(NtpError::InvalidReceiptSignature(format!(
+            "{} of {} required witness signatures",
+            signed, receipt.witness_threshold
+        )));
+    }
+
+    Ok(true)
+}
+
+/// Checks the sender's and receiver's signatures.
+fn verify_parties(receipt: &PaymentReceipt) -> Result<(), NtpError> {
     let sender_sig = receipt
         .sender_signature
         .as_ref()
@@ -212,7 +415,7 @@ pub fn verify_receipt(receipt: &PaymentReceipt) -> Result<bool, NtpError> {
         ));
     }
 
-    Ok(true)
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -224,7 +427,7 @@ mod tests {
     use super::*;
     use crate::crypto::keys::NovaKeypair;
     use crate::identity::nova_id::NovaId;
-    use crate::ntp::handshake::PaymentParams;
+    use crate::ntp::handshake::{CaptureMode, PaymentParams};
     use crate::ntp::settlement::ValidationStage;
 
     fn make_test_session(sender_kp: &NovaKeypair, receiver_kp: &NovaKeypair) -> EstablishedSession {
@@ -237,6 +440,7 @@ mod tests {
                 amount: 5000,
                 currency: Currency::BRL,
                 description: "Test payment".to_string(),
+                capture: CaptureMode::Immediate,
             },
             our_nova_id: NovaId::from_public_key(&sender_kp.public_key()).to_address(),
             our_pubkey: sender_kp.public_key(),
@@ -350,4 +554,117 @@ mod tests {
         assert!(recovered.is_fully_signed());
         assert!(verify_receipt(&recovered).unwrap());
     }
+
+    fn witnessed_receipt(
+        sender_kp: &NovaKeypair,
+        receiver_kp: &NovaKeypair,
+        witnesses: &[(&str, &NovaKeypair)],
+        threshold: u32,
+    ) -> PaymentReceipt {
+        let session = make_test_session(sender_kp, receiver_kp);
+        let mut receipt = generate_receipt(&make_confirmed(), &session).unwrap();
+        for (role, kp) in witnesses {
+            receipt.add_witness(role, kp.public_key()).unwrap();
+        }
+        receipt.require_witnesses(threshold).unwrap();
+        sign_receipt_as_sender(&mut receipt, sender_kp);
+        countersign_receipt(&mut receipt, receiver_kp).unwrap();
+        receipt
+    }
+
+    #[test]
+    fn witness_threshold_gates_verification() {
+        let (sender_kp, receiver_kp) = (NovaKeypair::generate(), NovaKeypair::generate());
+        let logistics = NovaKeypair::generate();
+        let insurer = NovaKeypair::generate();
+        let mut receipt = witnessed_receipt(
+            &sender_kp,
+            &receiver_kp,
+            &[("logistics", &logistics), ("insurer", &insurer)],
+            1,
+        );
+
+        assert!(receipt.is_fully_signed());
+        assert!(!receipt.is_witnessed());
+        assert!(verify_receipt(&receipt).is_err());
+
+        sign_receipt_as_witness(&mut receipt, &logistics).unwrap();
+        assert!(receipt.is_witnessed());
+        assert!(verify_receipt(&receipt).unwrap());
+        let verified = receipt.verified_witnesses();
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].role, "logistics");
+        assert!(receipt.witness("insurer").unwrap().signature.is_none());
+
+        // Strangers cannot sign, and slots are frozen once signed.
+        let stranger = NovaKeypair::generate();
+        assert!(matches!(
+            sign_receipt_as_witness(&mut receipt, &stranger),
+            Err(NtpError::InvalidWitness(_))
+        ));
+        assert!(matches!(
+            receipt.add_witness("auditor", stranger.public_key()),
+            Err(NtpError::InvalidWitness(_))
+        ));
+    }
+
+    #[test]
+    fn witness_slots_are_covered_by_the_party_signatures() {
+        let (sender_kp, receiver_kp) = (NovaKeypair::generate(), NovaKeypair::generate());
+        let logistics = NovaKeypair::generate();
+        let mut receipt =
+            witnessed_receipt(&sender_kp, &receiver_kp, &[("logistics", &logistics)], 1);
+        sign_receipt_as_witness(&mut receipt, &logistics).unwrap();
+
+        let mut stripped = receipt.clone();
+        stripped.witnesses.clear();
+        stripped.witness_threshold = 0;
+        assert!(verify_receipt(&stripped).is_err());
+
+        let mut relabeled = receipt.clone();
+        relabeled.witnesses[0].role = "customs".to_string();
+        assert!(verify_receipt(&relabeled).is_err());
+    }
+
+    #[test]
+    fn witness_slot_rules() {
+        let (sender_kp, receiver_kp) = (NovaKeypair::generate(), NovaKeypair::generate());
+        let session = make_test_session(&sender_kp, &receiver_kp);
+        let mut receipt = generate_receipt(&make_confirmed(), &session).unwrap();
+        let witness = NovaKeypair::generate();
+
+        assert!(receipt.add_witness("", witness.public_key()).is_err());
+        receipt
+            .add_witness("logistics", witness.public_key())
+            .unwrap();
+        assert!(receipt
+            .add_witness("insurer", witness.public_key())
+            .is_err());
+        assert!(receipt.require_witnesses(2).is_err());
+        receipt.require_witnesses(1).unwrap();
+    }
+
+    #[test]
+    fn receipts_without_witnesses_keep_their_payload() {
+        let (sender_kp, receiver_kp) = (NovaKeypair::generate(), NovaKeypair::generate());
+        let session = make_test_session(&sender_kp, &receiver_kp);
+        let mut receipt = generate_receipt(&make_confirmed(), &session).unwrap();
+        sign_receipt_as_sender(&mut receipt, &sender_kp);
+        countersign_receipt(&mut receipt, &receiver_kp).unwrap();
+
+        // A receipt serialized before witnesses existed.
+        let mut json = serde_json::to_value(&receipt).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("witnesses");
+        object.remove("witness_threshold");
+        let legacy: PaymentReceipt = serde_json::from_value(json).unwrap();
+
+        assert_eq!(legacy.signing_payload(), receipt.signing_payload());
+        assert!(!legacy
+            .signing_payload()
+            .windows(9)
+            .any(|w| w == b"witnesses"));
+        assert!(verify_receipt(&legacy).unwrap());
+        assert!(legacy.is_witnessed());
+    }
 }
@@ -0,0 +1,536 @@
+//! # Multi-Hop Routing
+//!
+//! Source routing for paying someone the sender has no session or channel
+//! with. Nodes advertise the channels they can forward over; the sender
+//! picks a path through them, locks the payment along it with hash-time
+//! locked contracts (HTLCs), and retries around hops that fail.
+//!
+//! This module is the routing layer only: channel advertisements, the
+//! channel graph, route selection, HTLC terms and the retry loop. Opening,
+//! updating and settling the channels themselves is not part of the tree
+//! yet; [`pay_with_retry`] takes the hop-by-hop forwarding as a callback so
+//! it can be driven by whatever channel implementation lands.
+//!
+//! ## Fees and Timelocks
+//!
+//! Every forwarding node charges the fee it advertised on its outgoing
+//! channel and needs its incoming HTLC to expire `timelock_delta` blocks
+//! after its outgoing one, so it can still claim upstream after learning
+//! the preimage downstream. Both accumulate from the recipient back to the
+//! sender:
+//!
+//! ```text
+//! sender ──(a+f1+f2, T+d1+d2)──▶ hop1 ──(a+f2, T+d2)──▶ hop2 ──(a, T)──▶ recipient
+//! ```
+//!
+//! The sender's own first channel is free and adds no delta.
+//!
+//! ## Route Selection
+//!
+//! Dijkstra from the recipient back to the sender, minimising the amount
+//! the sender must lock (payment plus fees), skipping channels too small
+//! for the amount they would carry and channels that already failed in
+//! this payment.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::hash::sha256_array;
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use crate::identity::nova_id::NovaId;
+
+use super::error::NtpError;
+
+/// Longest route considered, in hops.
+pub const MAX_ROUTE_HOPS: usize = 20;
+
+/// Most routes [`pay_with_retry`] tries before giving up.
+pub const DEFAULT_MAX_ATTEMPTS: usize = 5;
+
+// ---------------------------------------------------------------------------
+// Channel Advertisements
+// ---------------------------------------------------------------------------
+
+/// One direction of a channel, as advertised over gossip by the node that
+/// forwards over it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelAdvertisement {
+    /// Channel identifier, shared by both directions.
+    pub channel_id: u64,
+    /// Forwarding node's NOVA ID.
+    pub from: String,
+    /// Next node's NOVA ID.
+    pub to: String,
+    /// Most the channel can carry in this direction, in smallest units.
+    pub capacity: u64,
+    /// Flat fee per forwarded payment.
+    pub fee_base: u64,
+    /// Proportional fee in millionths of the forwarded amount.
+    pub fee_ppm: u32,
+    /// Blocks between the outgoing and incoming HTLC expiries.
+    pub timelock_delta: u64,
+    /// Unix timestamp (milliseconds); newer advertisements replace older.
+    pub timestamp: u64,
+    /// The forwarding node's public key.
+    pub public_key: NovaPublicKey,
+    /// The forwarding node's signature over [`Self::signing_payload`].
+    pub signature: NovaSignature,
+}
+
+impl ChannelAdvertisement {
+    /// Builds and signs an advertisement for a channel from `keypair`'s
+    /// node to `to`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign(
+        keypair: &NovaKeypair,
+        channel_id: u64,
+        to: impl Into<String>,
+        capacity: u64,
+        fee_base: u64,
+        fee_ppm: u32,
+        timelock_delta: u64,
+        timestamp: u64,
+    ) -> Self {
+        let public_key = keypair.public_key();
+        let mut ad = Self {
+            channel_id,
+            from: NovaId::from_public_key(&public_key).to_address(),
+            to: to.into(),
+            capacity,
+            fee_base,
+            fee_ppm,
+            timelock_delta,
+            timestamp,
+            public_key,
+            signature: NovaSignature::from_bytes([0u8; 64]),
+        };
+        ad.signature = keypair.sign(&ad.signing_payload());
+        ad
+    }
+
+    /// Canonical bytes the forwarding node signs.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        format!(
+            "nova-channel-ad:{}:{}:{}:{}:{}:{}:{}:{}",
+            self.channel_id,
+            self.from,
+            self.to,
+            self.capacity,
+            self.fee_base,
+            self.fee_ppm,
+            self.timelock_delta,
+            self.timestamp,
+        )
+        .into_bytes()
+    }
+
+    /// Checks that the key owns `from` and signed the advertisement.
+    pub fn verify(&self) -> Result<(), NtpError> {
+        if NovaId::from_public_key(&self.public_key).to_address() != self.from {
+            return Err(NtpError::InvalidAdvertisement(format!(
+                "channel {}: key does not own {}",
+                self.channel_id, self.from
+            )));
+        }
+        if !self
+            .public_key
+            .verify(&self.signing_payload(), &self.signature)
+        {
+            return Err(NtpError::InvalidAdvertisement(format!(
+                "channel {}: bad signature",
+                self.channel_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fee this node charges to forward `amount`.
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        let proportional = (amount as u128 * self.fee_ppm as u128 / 1_000_000) as u64;
+        self.fee_base.saturating_add(proportional)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Channel Graph
+// ---------------------------------------------------------------------------
+
+/// The sender's view of the forwarding network, built from gossiped
+/// advertisements.
+#[derive(Debug, Default)]
+pub struct ChannelGraph {
+    /// Advertisements keyed by `(channel_id, from)`.
+    edges: HashMap<(u64, String), ChannelAdvertisement>,
+}
+
+impl ChannelGraph {
+    /// An empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifies and records `ad`. Returns `false` if an advertisement at
+    /// least as new is already known for that channel direction.
+    pub fn insert(&mut self, ad: ChannelAdvertisement) -> Result<bool, NtpError> {
+        ad.verify()?;
+        let key = (ad.channel_id, ad.from.clone());
+        if let Some(known) = self.edges.get(&key) {
+            if known.timestamp >= ad.timestamp {
+                return Ok(false);
+            }
+        }
+        self.edges.insert(key, ad);
+        Ok(true)
+    }
+
+    /// Number of known channel directions.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// True if no channels are known.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Cheapest route carrying `amount` from `source` to `target`, with
+    /// the recipient's HTLC expiring at `final_expiry`, avoiding the
+    /// channels in `excluded`.
+    pub fn find_route(
+        &self,
+        source: &str,
+        target: &str,
+        amount: u64,
+        final_expiry: u64,
+        excluded: &HashSet<u64>,
+    ) -> Result<Route, NtpError> {
+        let no_route = || NtpError::NoRoute {
+            from: source.to_string(),
+            to: target.to_string(),
+            amount,
+        };
+        if source == target {
+            return Err(no_route());
+        }
+
+        // Channels by their *destination*, since the search runs backwards.
+        let mut incoming: HashMap<&str, Vec<&ChannelAdvertisement>> = HashMap::new();
+        for ad in self.edges.values() {
+            if !excluded.contains(&ad.channel_id) {
+                incoming.entry(ad.to.as_str()).or_default().push(ad);
+            }
+        }
+
+        // best[node] = (amount that must arrive at node, its HTLC expiry,
+        // hop count, the channel it forwards over next).
+        let mut best: HashMap<&str, (u64, u64, usize, Option<&ChannelAdvertisement>)> =
+            HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert(target, (amount, final_expiry, 0, None));
+        queue.push(Reverse((amount, target)));
+
+        while let Some(Reverse((arriving, node))) = queue.pop() {
+            if node == source {
+                break;
+            }
+            let (known, expiry, hops, _) = best[node];
+            if arriving > known || hops >= MAX_ROUTE_HOPS {
+                continue;
+            }
+            for ad in incoming.get(node).into_iter().flatten() {
+                if ad.capacity < arriving {
+                    continue;
+                }
+                let prev = ad.from.as_str();
+                // The sender forwards its own payment for free.
+                let (needed, prev_expiry) = if prev == source {
+                    (arriving, expiry)
+                } else {
+                    (
+                        arriving.saturating_add(ad.fee_for(arriving)),
+                        expiry.saturating_add(ad.timelock_delta),
+                    )
+                };
+                let better = best.get(prev).map(|(b, ..)| needed < *b).unwrap_or(true);
+                if better {
+                    best.insert(prev, (needed, prev_expiry, hops + 1, Some(ad)));
+                    queue.push(Reverse((needed, prev)));
+                }
+            }
+        }
+
+        let (total_amount, _, _, _) = *best.get(source).ok_or_else(no_route)?;
+
+        // Walk forwards from the sender, reading each hop's terms from the
+        // node it lands on.
+        let mut hops = Vec::new();
+        let mut node = source;
+        while node != target {
+            let ad = best[node].3.expect("every node on the path has a next hop");
+            let (hop_amount, hop_expiry, _, _) = best[ad.to.as_str()];
+            hops.push(RouteHop {
+                channel_id: ad.channel_id,
+                node: ad.to.clone(),
+                amount: hop_amount,
+                expiry: hop_expiry,
+            });
+            node = ad.to.as_str();
+        }
+
+        Ok(Route {
+            hops,
+            total_amount,
+            total_fees: total_amount - amount,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Routes and HTLCs
+// ---------------------------------------------------------------------------
+
+/// One hop of a route: the HTLC offered to `node` over `channel_id`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteHop {
+    /// Channel the HTLC is offered over.
+    pub channel_id: u64,
+    /// The node receiving the HTLC.
+    pub node: String,
+    /// Amount locked in the HTLC.
+    pub amount: u64,
+    /// Block height at which the HTLC can be refunded.
+    pub expiry: u64,
+}
+
+/// A source route, sender side first.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Route {
+    /// Hops in forwarding order; the last lands on the recipient.
+    pub hops: Vec<RouteHop>,
+    /// What the sender locks: payment plus all fees.
+    pub total_amount: u64,
+    /// Sum of the forwarding fees.
+    pub total_fees: u64,
+}
+
+impl Route {
+    /// The HTLCs to offer along the route for `payment_hash`.
+    pub fn htlcs(&self, payment_hash: [u8; 32]) -> Vec<Htlc> {
+        self.hops
+            .iter()
+            .map(|hop| Htlc {
+                payment_hash,
+                amount: hop.amount,
+                expiry: hop.expiry,
+            })
+            .collect()
+    }
+}
+
+/// A hash-time locked contract: `amount` goes to whoever reveals the
+/// preimage of `payment_hash` before `expiry`, and back to the offerer
+/// after.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Htlc {
+    /// SHA-256 of the recipient's payment preimage.
+    pub payment_hash: [u8; 32],
+    /// Locked amount.
+    pub amount: u64,
+    /// Refund height.
+    pub expiry: u64,
+}
+
+impl Htlc {
+    /// True if `preimage` unlocks the HTLC at `height`.
+    pub fn can_claim(&self, preimage: &[u8; 32], height: u64) -> bool {
+        height < self.expiry && payment_hash(preimage) == self.payment_hash
+    }
+
+    /// True if the offerer can take the funds back at `height`.
+    pub fn can_refund(&self, height: u64) -> bool {
+        height >= self.expiry
+    }
+}
+
+/// The hash an HTLC locks to for `preimage`.
+pub fn payment_hash(preimage: &[u8; 32]) -> [u8; 32] {
+    sha256_array(preimage)
+}
+
+// ---------------------------------------------------------------------------
+// Sending
+// ---------------------------------------------------------------------------
+
+/// A hop that refused or failed to forward.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HopFailure {
+    /// The channel that failed.
+    pub channel_id: u64,
+    /// Why, as reported by the failing node.
+    pub reason: String,
+}
+
+/// Finds a route and hands it to `forward`; on a hop failure, excludes
+/// that channel and tries again, up to `max_attempts` routes.
+///
+/// Returns the route that succeeded.
+pub fn pay_with_retry<F>(
+    graph: &ChannelGraph,
+    source: &str,
+    target: &str,
+    amount: u64,
+    final_expiry: u64,
+    max_attempts: usize,
+    mut forward: F,
+) -> Result<Route, NtpError>
+where
+    F: FnMut(&Route) -> Result<(), HopFailure>,
+{
+    let mut excluded = HashSet::new();
+    let mut last_failure = None;
+    for _ in 0..max_attempts {
+        let route = match graph.find_route(source, target, amount, final_expiry, &excluded) {
+            Ok(route) => route,
+            Err(e) if last_failure.is_none() => return Err(e),
+            Err(_) => break,
+        };
+        match forward(&route) {
+            Ok(()) => return Ok(route),
+            Err(failure) => {
+                excluded.insert(failure.channel_id);
+                last_failure = Some(failure);
+            }
+        }
+    }
+    match last_failure {
+        Some(last) => Err(NtpError::PaymentFailed {
+            attempts: excluded.len(),
+            last: format!("channel {}: {}", last.channel_id, last.reason),
+        }),
+        None => Err(NtpError::NoRoute {
+            from: source.to_string(),
+            to: target.to_string(),
+            amount,
+        }),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(seed: u8) -> (NovaKeypair, String) {
+        let kp = NovaKeypair::from_seed(&[seed; 32]);
+        let address = NovaId::from_public_key(&kp.public_key()).to_address();
+        (kp, address)
+    }
+
+    /// sender(1) ─▶ a(2) ─▶ recipient(4), and sender ─▶ b(3) ─▶ recipient.
+    /// `a` is cheap, `b` dear.
+    fn diamond() -> (ChannelGraph, String, String) {
+        let (s, sender) = node(1);
+        let (a, addr_a) = node(2);
+        let (b, addr_b) = node(3);
+        let (_, recipient) = node(4);
+
+        let mut graph = ChannelGraph::new();
+        for ad in [
+            ChannelAdvertisement::sign(&s, 1, &addr_a, 10_000, 0, 0, 0, 1),
+            ChannelAdvertisement::sign(&s, 2, &addr_b, 10_000, 0, 0, 0, 1),
+            ChannelAdvertisement::sign(&a, 3, &recipient, 10_000, 10, 1_000, 40, 1),
+            ChannelAdvertisement::sign(&b, 4, &recipient, 10_000, 50, 0, 20, 1),
+        ] {
+            assert!(graph.insert(ad).unwrap());
+        }
+        (graph, sender, recipient)
+    }
+
+    #[test]
+    fn route_accumulates_fees_and_timelocks() {
+        let (graph, sender, recipient) = diamond();
+        let route = graph
+            .find_route(&sender, &recipient, 1_000, 500, &HashSet::new())
+            .unwrap();
+
+        // Through `a`: 10 base + 1 (0.1% of 1000).
+        assert_eq!(route.total_fees, 11);
+        assert_eq!(route.total_amount, 1_011);
+        assert_eq!(route.hops.len(), 2);
+        assert_eq!(route.hops[0].channel_id, 1);
+        assert_eq!((route.hops[0].amount, route.hops[0].expiry), (1_011, 540));
+        assert_eq!(route.hops[1].node, recipient);
+        assert_eq!((route.hops[1].amount, route.hops[1].expiry), (1_000, 500));
+    }
+
+    #[test]
+    fn retries_around_failed_hops() {
+        let (graph, sender, recipient) = diamond();
+        let mut tried = Vec::new();
+        let route = pay_with_retry(&graph, &sender, &recipient, 1_000, 500, 5, |route| {
+            tried.push(route.hops[1].channel_id);
+            if route.hops[1].channel_id == 3 {
+                Err(HopFailure {
+                    channel_id: 3,
+                    reason: "temporary channel failure".into(),
+                })
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+        assert_eq!(tried, [3, 4]);
+        assert_eq!(route.total_fees, 50);
+
+        let result = pay_with_retry(&graph, &sender, &recipient, 1_000, 500, 5, |route| {
+            Err(HopFailure {
+                channel_id: route.hops[1].channel_id,
+                reason: "no".into(),
+            })
+        });
+        assert!(matches!(
+            result,
+            Err(NtpError::PaymentFailed { attempts: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn capacity_and_advertisements_are_enforced() {
+        let (mut graph, sender, recipient) = diamond();
+        assert!(matches!(
+            graph.find_route(&sender, &recipient, 20_000, 500, &HashSet::new()),
+            Err(NtpError::NoRoute { .. })
+        ));
+
+        // Stale and forged advertisements are refused.
+        let (a, _) = node(2);
+        let stale = ChannelAdvertisement::sign(&a, 3, &recipient, 1, 0, 0, 0, 1);
+        assert!(!graph.insert(stale).unwrap());
+        let mut forged = ChannelAdvertisement::sign(&a, 3, &recipient, 1, 0, 0, 0, 2);
+        forged.capacity = 1_000_000;
+        assert!(matches!(
+            graph.insert(forged),
+            Err(NtpError::InvalidAdvertisement(_))
+        ));
+    }
+
+    #[test]
+    fn htlcs_unlock_with_preimage_before_expiry() {
+        let preimage = [42u8; 32];
+        let htlc = Htlc {
+            payment_hash: payment_hash(&preimage),
+            amount: 100,
+            expiry: 50,
+        };
+        assert!(htlc.can_claim(&preimage, 49));
+        assert!(!htlc.can_claim(&preimage, 50));
+        assert!(!htlc.can_claim(&[0u8; 32], 10));
+        assert!(!htlc.can_refund(49));
+        assert!(htlc.can_refund(50));
+    }
+}
@@ -19,14 +19,26 @@
 //! - **Confirmed** -- included in a block at a specific height.
 //! - **Rejected** -- failed validation with a reason.
 //! - **TimedOut** -- not included within the finality timeout.
+//!
+//! ## Compensation
+//!
+//! A payment that is slow rather than dead should not sit in limbo until
+//! the finality timeout. Each non-terminal state has its own timeout; when
+//! it fires, [`SettlementStateMachine::poll`] escalates through
+//! re-broadcast, fee bump (RBF), and finally an abort settled by a
+//! [`SettlementCancellation`] signed by both parties. Integrators observe
+//! every step through transition hooks.
 
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
 use crate::config;
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
 use crate::transaction::builder::Transaction;
 use crate::transaction::verification::{verify_transaction, TransactionError};
 
+use super::error::NtpError;
+
 // ---------------------------------------------------------------------------
 // Settlement Result
 // ---------------------------------------------------------------------------
@@ -149,6 +161,208 @@ pub fn validate_transaction(tx: &Transaction) -> Result<(), SettlementResult> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Timeouts and Compensation Policy
+// ---------------------------------------------------------------------------
+
+/// Per-state timeouts and the compensation budget for a single settlement.
+///
+/// The overall finality timeout is a hard ceiling. The per-state timeouts
+/// decide when a payment that is merely *slow* gets nudged: first by
+/// re-broadcasting the same transaction, then by replacing it with a
+/// higher-fee version (RBF), and finally by aborting with a cancellation
+/// signed by both parties.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SettlementTimeouts {
+    /// How long a transaction may sit in `Pending` before compensating.
+    pub pending_ms: u64,
+    /// How long a transaction may sit in `Validating` before compensating.
+    pub validating_ms: u64,
+    /// How long the integrator has to carry out a compensation action
+    /// (and call [`SettlementStateMachine::resume`]) before we escalate.
+    pub compensation_ms: u64,
+    /// Number of plain re-broadcasts attempted before bumping the fee.
+    pub max_rebroadcasts: u32,
+    /// Number of fee bumps attempted before giving up and aborting.
+    pub max_fee_bumps: u32,
+    /// Fee increase per bump, in percent of the current fee.
+    pub fee_bump_percent: u64,
+}
+
+impl Default for SettlementTimeouts {
+    fn default() -> Self {
+        Self {
+            // Three missed blocks is a strong hint the tx never reached a validator.
+            pending_ms: config::BLOCK_TIME_MS * 3,
+            validating_ms: config::BLOCK_TIME_MS * 5,
+            compensation_ms: config::BLOCK_TIME_MS * 2,
+            max_rebroadcasts: 2,
+            max_fee_bumps: 1,
+            fee_bump_percent: 25,
+        }
+    }
+}
+
+impl SettlementTimeouts {
+    /// Compute the replacement fee for a fee bump.
+    ///
+    /// Always increases by at least [`config::MIN_TX_FEE_PHOTONS`] so that a
+    /// bump on a near-zero fee is still a meaningful replacement, and never
+    /// exceeds [`config::MAX_TX_FEE_PHOTONS`].
+    pub fn bumped_fee(&self, current_fee: u64) -> u64 {
+        let pct = current_fee.saturating_mul(self.fee_bump_percent) / 100;
+        let increase = pct.max(config::MIN_TX_FEE_PHOTONS);
+        current_fee
+            .saturating_add(increase)
+            .min(config::MAX_TX_FEE_PHOTONS)
+    }
+}
+
+/// A compensating action the integrator must carry out on behalf of a
+/// stalled settlement.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompensationAction {
+    /// Re-broadcast the original signed transaction unchanged.
+    Rebroadcast {
+        /// 1-based attempt number.
+        attempt: u32,
+    },
+    /// Replace the transaction with a higher-fee version (same nonce).
+    FeeBump {
+        /// 1-based attempt number.
+        attempt: u32,
+        /// Fee increase to apply, in percent of the current fee.
+        fee_bump_percent: u64,
+    },
+    /// Give up: exchange a signed [`SettlementCancellation`] with the
+    /// counterparty and call [`SettlementStateMachine::mark_aborted`].
+    Abort,
+}
+
+// ---------------------------------------------------------------------------
+// Signed Cancellation
+// ---------------------------------------------------------------------------
+
+/// A cancellation notice signed by both parties of an NTP session.
+///
+/// Mirrors the receipt protocol: the party that gives up signs first, the
+/// counterparty verifies and countersigns. A fully signed cancellation is
+/// the merchant's proof that the payer walked away (and the payer's proof
+/// that the merchant agreed not to ship).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SettlementCancellation {
+    /// The NTP session being cancelled.
+    pub session_id: String,
+    /// Hash of the transaction that failed to settle.
+    pub tx_hash: String,
+    /// Human-readable reason (e.g., "settlement stalled after fee bump").
+    pub reason: String,
+    /// Unix timestamp (milliseconds) when the cancellation was created.
+    pub created_at: u64,
+    /// Public key of the party initiating the cancellation.
+    pub initiator_pubkey: NovaPublicKey,
+    /// Public key of the counterparty.
+    pub counterparty_pubkey: NovaPublicKey,
+    /// Initiator's signature over [`signing_payload`](Self::signing_payload).
+    pub initiator_signature: Option<NovaSignature>,
+    /// Counterparty's signature over the same payload.
+    pub counterparty_signature: Option<NovaSignature>,
+}
+
+impl SettlementCancellation {
+    /// Create an unsigned cancellation notice.
+    pub fn new(
+        session_id: impl Into<String>,
+        tx_hash: impl Into<String>,
+        reason: impl Into<String>,
+        initiator_pubkey: NovaPublicKey,
+        counterparty_pubkey: NovaPublicKey,
+    ) -> Self {
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Self {
+            session_id: session_id.into(),
+            tx_hash: tx_hash.into(),
+            reason: reason.into(),
+            created_at,
+            initiator_pubkey,
+            counterparty_pubkey,
+            initiator_signature: None,
+            counterparty_signature: None,
+        }
+    }
+
+    /// Canonical bytes that both parties sign. The domain prefix keeps a
+    /// cancellation signature from ever being replayed as a receipt.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        format!(
+            "NTP-CANCEL:{}:{}:{}:{}:{}:{}",
+            self.session_id,
+            self.tx_hash,
+            self.reason,
+            self.created_at,
+            hex::encode(self.initiator_pubkey.as_bytes()),
+            hex::encode(self.counterparty_pubkey.as_bytes()),
+        )
+        .into_bytes()
+    }
+
+    /// Sign as the initiating party.
+    pub fn sign_as_initiator(&mut self, keypair: &NovaKeypair) {
+        let payload = self.signing_payload();
+        self.initiator_signature = Some(keypair.sign(&payload));
+    }
+
+    /// Verify the initiator's signature and countersign.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initiator signature is missing or invalid.
+    pub fn countersign(&mut self, keypair: &NovaKeypair) -> Result<(), NtpError> {
+        let payload = self.signing_payload();
+        let sig = self.initiator_signature.as_ref().ok_or_else(|| {
+            NtpError::InvalidCancellation("initiator signature missing".to_string())
+        })?;
+        if !self.initiator_pubkey.verify(&payload, sig) {
+            return Err(NtpError::InvalidCancellation(
+                "initiator signature verification failed".to_string(),
+            ));
+        }
+        self.counterparty_signature = Some(keypair.sign(&payload));
+        Ok(())
+    }
+
+    /// Verify both signatures.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either signature is missing or invalid.
+    pub fn verify(&self) -> Result<(), NtpError> {
+        let payload = self.signing_payload();
+        let initiator_sig = self.initiator_signature.as_ref().ok_or_else(|| {
+            NtpError::InvalidCancellation("initiator signature missing".to_string())
+        })?;
+        let counterparty_sig = self.counterparty_signature.as_ref().ok_or_else(|| {
+            NtpError::InvalidCancellation("counterparty signature missing".to_string())
+        })?;
+
+        if !self.initiator_pubkey.verify(&payload, initiator_sig) {
+            return Err(NtpError::InvalidCancellation(
+                "initiator signature invalid".to_string(),
+            ));
+        }
+        if !self.counterparty_pubkey.verify(&payload, counterparty_sig) {
+            return Err(NtpError::InvalidCancellation(
+                "counterparty signature invalid".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Settlement State Machine
 // ---------------------------------------------------------------------------
@@ -160,19 +374,69 @@ pub enum SettlementState {
     Pending,
     /// Currently being validated by the network.
     Validating,
+    /// Stalled: waiting for the integrator to carry out a compensation.
+    Compensating(CompensationAction),
+    /// Compensation budget exhausted; waiting for a signed cancellation.
+    Aborting,
     /// Terminal: confirmed in a block.
     Confirmed(SettlementResult),
     /// Terminal: rejected by validators.
     Rejected(SettlementResult),
     /// Terminal: not settled within the timeout window.
     TimedOut,
+    /// Terminal: both parties signed off on cancelling the payment.
+    Aborted(SettlementCancellation),
+}
+
+impl SettlementState {
+    /// Short, stable name for logging and error messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Validating => "validating",
+            Self::Compensating(_) => "compensating",
+            Self::Aborting => "aborting",
+            Self::Confirmed(_) => "confirmed",
+            Self::Rejected(_) => "rejected",
+            Self::TimedOut => "timed_out",
+            Self::Aborted(_) => "aborted",
+        }
+    }
+
+    /// Returns `true` for states that can still change.
+    fn is_open(&self) -> bool {
+        matches!(
+            self,
+            Self::Pending | Self::Validating | Self::Compensating(_) | Self::Aborting
+        )
+    }
+}
+
+/// A single state change, handed to every registered transition hook.
+#[derive(Clone, Debug)]
+pub struct SettlementTransition {
+    /// Transaction hash being tracked (after any fee-bump replacement).
+    pub tx_hash: String,
+    /// NTP session ID.
+    pub session_id: String,
+    /// State before the transition.
+    pub from: SettlementState,
+    /// State after the transition.
+    pub to: SettlementState,
+    /// Milliseconds since tracking started.
+    pub elapsed_ms: u64,
 }
 
+/// Callback invoked on every state transition.
+pub type TransitionHook = Box<dyn Fn(&SettlementTransition) + Send + Sync>;
+
 /// Tracks the lifecycle of a single transaction through settlement.
 ///
 /// Created when a transaction is broadcast, updated as the network
 /// processes it, and finalized when a result is received or the
-/// timeout expires.
+/// timeout expires. Call [`poll`](Self::poll) periodically: a payment
+/// stuck in one state for longer than its per-state timeout is escalated
+/// to a [`CompensationAction`] instead of hanging forever.
 pub struct SettlementStateMachine {
     /// Current state.
     state: SettlementState,
@@ -182,33 +446,65 @@ pub struct SettlementStateMachine {
     session_id: String,
     /// When tracking started.
     started_at: Instant,
+    /// When the current state was entered.
+    state_entered_at: Instant,
     /// Finality timeout in milliseconds.
     timeout_ms: u64,
+    /// Per-state timeouts and compensation budget.
+    timeouts: SettlementTimeouts,
+    /// Re-broadcasts issued so far.
+    rebroadcasts: u32,
+    /// Fee bumps issued so far.
+    fee_bumps: u32,
+    /// Integrator callbacks fired on every transition.
+    hooks: Vec<TransitionHook>,
 }
 
 impl SettlementStateMachine {
     /// Create a new state machine for tracking a transaction.
     pub fn new(tx_hash: String, session_id: String) -> Self {
-        Self {
-            state: SettlementState::Pending,
+        Self::with_timeout(
             tx_hash,
             session_id,
-            started_at: Instant::now(),
-            timeout_ms: config::FINALITY_TIMEOUT.as_millis() as u64,
-        }
+            config::FINALITY_TIMEOUT.as_millis() as u64,
+        )
     }
 
     /// Create with a custom timeout (for testing).
     pub fn with_timeout(tx_hash: String, session_id: String, timeout_ms: u64) -> Self {
+        let now = Instant::now();
         Self {
             state: SettlementState::Pending,
             tx_hash,
             session_id,
-            started_at: Instant::now(),
+            started_at: now,
+            state_entered_at: now,
             timeout_ms,
+            timeouts: SettlementTimeouts::default(),
+            rebroadcasts: 0,
+            fee_bumps: 0,
+            hooks: Vec::new(),
         }
     }
 
+    /// Replace the per-state timeouts and compensation budget.
+    pub fn with_timeouts(mut self, timeouts: SettlementTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Register a callback that fires on every state transition.
+    ///
+    /// Hooks run synchronously in registration order, on the thread that
+    /// caused the transition. Keep them cheap — push to a channel if you
+    /// need to do I/O.
+    pub fn on_transition<F>(&mut self, hook: F)
+    where
+        F: Fn(&SettlementTransition) + Send + Sync + 'static,
+    {
+        self.hooks.push(Box::new(hook));
+    }
+
     /// Return the current settlement state.
     pub fn state(&self) -> &SettlementState {
         &self.state
@@ -224,31 +520,113 @@ impl SettlementStateMachine {
         &self.session_id
     }
 
+    /// Return the configured per-state timeouts.
+    pub fn timeouts(&self) -> &SettlementTimeouts {
+        &self.timeouts
+    }
+
+    /// Move to `to`, reset the per-state clock, and notify hooks.
+    fn transition(&mut self, to: SettlementState) {
+        let from = std::mem::replace(&mut self.state, to);
+        self.state_entered_at = Instant::now();
+
+        if self.hooks.is_empty() {
+            return;
+        }
+        let event = SettlementTransition {
+            tx_hash: self.tx_hash.clone(),
+            session_id: self.session_id.clone(),
+            from,
+            to: self.state.clone(),
+            elapsed_ms: self.elapsed_ms(),
+        };
+        for hook in &self.hooks {
+            hook(&event);
+        }
+    }
+
     /// Transition to the validating state.
     pub fn mark_validating(&mut self) {
         if matches!(self.state, SettlementState::Pending) {
-            self.state = SettlementState::Validating;
+            self.transition(SettlementState::Validating);
         }
     }
 
     /// Transition to the confirmed state.
+    ///
+    /// A confirmation wins even while compensating or aborting: the chain
+    /// is the source of truth, and a re-broadcast copy may well land after
+    /// we started giving up on it.
     pub fn mark_confirmed(&mut self, result: SettlementResult) {
-        match self.state {
-            SettlementState::Pending | SettlementState::Validating => {
-                self.state = SettlementState::Confirmed(result);
-            }
-            _ => {} // Terminal states are immutable.
+        if self.state.is_open() {
+            self.transition(SettlementState::Confirmed(result));
         }
     }
 
     /// Transition to the rejected state.
     pub fn mark_rejected(&mut self, result: SettlementResult) {
+        if self.state.is_open() {
+            self.transition(SettlementState::Rejected(result));
+        }
+    }
+
+    /// Report that the pending compensation action has been carried out.
+    ///
+    /// Pass `Some(hash)` after a fee bump, since the replacement transaction
+    /// has a different ID. Returns the machine to `Pending` with a fresh
+    /// per-state clock.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtpError::InvalidState`] unless the machine is compensating
+    /// with a re-broadcast or fee bump.
+    pub fn resume(&mut self, replacement_tx_hash: Option<String>) -> Result<(), NtpError> {
         match self.state {
-            SettlementState::Pending | SettlementState::Validating => {
-                self.state = SettlementState::Rejected(result);
+            SettlementState::Compensating(CompensationAction::Rebroadcast { .. })
+            | SettlementState::Compensating(CompensationAction::FeeBump { .. }) => {
+                if let Some(hash) = replacement_tx_hash {
+                    self.tx_hash = hash;
+                }
+                self.transition(SettlementState::Pending);
+                Ok(())
             }
-            _ => {}
+            _ => Err(NtpError::InvalidState {
+                current_state: self.state.name().to_string(),
+                message_type: "resume".to_string(),
+            }),
+        }
+    }
+
+    /// Finish an abort with a fully signed cancellation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the machine is already terminal, the cancellation
+    /// refers to a different session or transaction, or either signature
+    /// fails verification.
+    pub fn mark_aborted(&mut self, cancellation: SettlementCancellation) -> Result<(), NtpError> {
+        if !self.state.is_open() {
+            return Err(NtpError::InvalidState {
+                current_state: self.state.name().to_string(),
+                message_type: "cancellation".to_string(),
+            });
+        }
+        if cancellation.session_id != self.session_id {
+            return Err(NtpError::SessionMismatch {
+                expected: self.session_id.clone(),
+                got: cancellation.session_id,
+            });
         }
+        if cancellation.tx_hash != self.tx_hash {
+            return Err(NtpError::InvalidCancellation(format!(
+                "cancellation is for tx {}, tracking {}",
+                cancellation.tx_hash, self.tx_hash
+            )));
+        }
+        cancellation.verify()?;
+
+        self.transition(SettlementState::Aborted(cancellation));
+        Ok(())
     }
 
     /// Check if the settlement has timed out.
@@ -256,22 +634,61 @@ impl SettlementStateMachine {
     /// If the timeout has elapsed and we are still pending/validating,
     /// transitions to `TimedOut` and returns the timeout result.
     pub fn check_timeout(&mut self) -> Option<SettlementResult> {
-        let elapsed = self.started_at.elapsed().as_millis() as u64;
+        let elapsed = self.elapsed_ms();
+
+        if self.state.is_open() && elapsed >= self.timeout_ms {
+            self.transition(SettlementState::TimedOut);
+            Some(SettlementResult::TimedOut {
+                elapsed_ms: elapsed,
+                timeout_ms: self.timeout_ms,
+            })
+        } else {
+            None
+        }
+    }
 
-        match self.state {
-            SettlementState::Pending | SettlementState::Validating => {
-                if elapsed >= self.timeout_ms {
-                    self.state = SettlementState::TimedOut;
-                    Some(SettlementResult::TimedOut {
-                        elapsed_ms: elapsed,
-                        timeout_ms: self.timeout_ms,
-                    })
-                } else {
-                    None
-                }
-            }
-            _ => None,
+    /// Drive the per-state timeouts.
+    ///
+    /// Returns the compensation action the integrator should perform, if
+    /// the current state has been held for longer than its timeout. The
+    /// escalation ladder is: re-broadcast (up to `max_rebroadcasts`), fee
+    /// bump (up to `max_fee_bumps`), then abort. The overall finality
+    /// timeout still applies on top and ends in `TimedOut`.
+    pub fn poll(&mut self) -> Option<CompensationAction> {
+        if self.check_timeout().is_some() {
+            return None;
+        }
+
+        let in_state = self.state_entered_at.elapsed().as_millis() as u64;
+        let limit = match self.state {
+            SettlementState::Pending => self.timeouts.pending_ms,
+            SettlementState::Validating => self.timeouts.validating_ms,
+            SettlementState::Compensating(_) => self.timeouts.compensation_ms,
+            // Aborting waits for the counterparty; only the finality timeout applies.
+            _ => return None,
+        };
+        if in_state < limit {
+            return None;
         }
+
+        let action = if self.rebroadcasts < self.timeouts.max_rebroadcasts {
+            self.rebroadcasts += 1;
+            CompensationAction::Rebroadcast {
+                attempt: self.rebroadcasts,
+            }
+        } else if self.fee_bumps < self.timeouts.max_fee_bumps {
+            self.fee_bumps += 1;
+            CompensationAction::FeeBump {
+                attempt: self.fee_bumps,
+                fee_bump_percent: self.timeouts.fee_bump_percent,
+            }
+        } else {
+            self.transition(SettlementState::Aborting);
+            return Some(CompensationAction::Abort);
+        };
+
+        self.transition(SettlementState::Compensating(action.clone()));
+        Some(action)
     }
 
     /// Return the final settlement result, if terminal.
@@ -284,12 +701,7 @@ impl SettlementStateMachine {
 
     /// Returns `true` if the state machine is in a terminal state.
     pub fn is_terminal(&self) -> bool {
-        matches!(
-            self.state,
-            SettlementState::Confirmed(_)
-                | SettlementState::Rejected(_)
-                | SettlementState::TimedOut
-        )
+        !self.state.is_open()
     }
 
     /// Elapsed time in milliseconds since tracking started.
@@ -323,7 +735,7 @@ mod tests {
             .amount(Amount::new(1_000, Currency::NOVA))
             .fee(100)
             .nonce(nonce)
-            .build();
+            .build_unchecked();
         sign_transaction(&mut tx, &kp);
         (tx, kp)
     }
@@ -348,7 +760,7 @@ mod tests {
             .amount(Amount::new(1_000, Currency::NOVA))
             .fee(100)
             .nonce(1)
-            .build();
+            .build_unchecked();
         let result = validate_transaction(&tx);
         assert!(result.is_err());
     }
@@ -412,4 +824,173 @@ mod tests {
         // Still confirmed.
         assert!(matches!(sm.state(), SettlementState::Confirmed(_)));
     }
+
+    // -- Compensation ----------------------------------------------------
+
+    fn fast_timeouts() -> SettlementTimeouts {
+        SettlementTimeouts {
+            pending_ms: 0,
+            validating_ms: 0,
+            compensation_ms: 0,
+            max_rebroadcasts: 1,
+            max_fee_bumps: 1,
+            fee_bump_percent: 25,
+        }
+    }
+
+    fn signed_cancellation(tx_hash: &str, session_id: &str) -> SettlementCancellation {
+        let payer = NovaKeypair::generate();
+        let merchant = NovaKeypair::generate();
+        let mut cancel = SettlementCancellation::new(
+            session_id,
+            tx_hash,
+            "settlement stalled",
+            payer.public_key(),
+            merchant.public_key(),
+        );
+        cancel.sign_as_initiator(&payer);
+        cancel.countersign(&merchant).unwrap();
+        cancel
+    }
+
+    #[test]
+    fn stalled_settlement_escalates_through_compensation_ladder() {
+        let mut sm = SettlementStateMachine::with_timeout("tx".into(), "s".into(), 60_000)
+            .with_timeouts(fast_timeouts());
+
+        assert_eq!(
+            sm.poll(),
+            Some(CompensationAction::Rebroadcast { attempt: 1 })
+        );
+        sm.resume(None).unwrap();
+        assert!(matches!(sm.state(), SettlementState::Pending));
+
+        assert_eq!(
+            sm.poll(),
+            Some(CompensationAction::FeeBump {
+                attempt: 1,
+                fee_bump_percent: 25
+            })
+        );
+        sm.resume(Some("tx-replacement".into())).unwrap();
+        assert_eq!(sm.tx_hash(), "tx-replacement");
+
+        assert_eq!(sm.poll(), Some(CompensationAction::Abort));
+        assert!(matches!(sm.state(), SettlementState::Aborting));
+        assert!(!sm.is_terminal());
+
+        // Aborting only waits on the counterparty.
+        assert_eq!(sm.poll(), None);
+
+        sm.mark_aborted(signed_cancellation("tx-replacement", "s"))
+            .unwrap();
+        assert!(sm.is_terminal());
+        assert!(matches!(sm.state(), SettlementState::Aborted(_)));
+    }
+
+    #[test]
+    fn poll_is_quiet_before_state_timeout() {
+        let mut sm = SettlementStateMachine::new("tx".into(), "s".into());
+        assert_eq!(sm.poll(), None);
+        assert!(matches!(sm.state(), SettlementState::Pending));
+    }
+
+    #[test]
+    fn late_confirmation_wins_over_compensation() {
+        let mut sm = SettlementStateMachine::with_timeout("tx".into(), "s".into(), 60_000)
+            .with_timeouts(fast_timeouts());
+        sm.poll();
+        assert!(matches!(sm.state(), SettlementState::Compensating(_)));
+
+        sm.mark_confirmed(SettlementResult::Confirmed {
+            block_height: 7,
+            tx_hash: "tx".into(),
+            block_hash: "b".into(),
+            tx_index: 0,
+            block_timestamp: 1,
+        });
+        assert!(matches!(sm.state(), SettlementState::Confirmed(_)));
+    }
+
+    #[test]
+    fn transition_hooks_fire_on_every_change() {
+        use std::sync::{Arc, Mutex};
+
+        let seen: Arc<Mutex<Vec<(String, String)>>> = Arc::default();
+        let sink = Arc::clone(&seen);
+
+        let mut sm = SettlementStateMachine::new("tx".into(), "s".into());
+        sm.on_transition(move |t| {
+            sink.lock()
+                .unwrap()
+                .push((t.from.name().to_string(), t.to.name().to_string()));
+        });
+
+        sm.mark_validating();
+        sm.mark_rejected(SettlementResult::Rejected {
+            reason: "nope".into(),
+            stage: ValidationStage::StateTransition,
+        });
+        // No-op on a terminal state: no hook.
+        sm.mark_validating();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(
+            *seen,
+            vec![
+                ("pending".to_string(), "validating".to_string()),
+                ("validating".to_string(), "rejected".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn resume_outside_compensation_is_rejected() {
+        let mut sm = SettlementStateMachine::new("tx".into(), "s".into());
+        assert!(matches!(
+            sm.resume(None),
+            Err(NtpError::InvalidState { .. })
+        ));
+    }
+
+    #[test]
+    fn cancellation_requires_both_signatures() {
+        let payer = NovaKeypair::generate();
+        let merchant = NovaKeypair::generate();
+        let mut cancel = SettlementCancellation::new(
+            "s",
+            "tx",
+            "bored",
+            payer.public_key(),
+            merchant.public_key(),
+        );
+        cancel.sign_as_initiator(&payer);
+        assert!(cancel.verify().is_err());
+
+        let mut sm = SettlementStateMachine::new("tx".into(), "s".into());
+        assert!(sm.mark_aborted(cancel.clone()).is_err());
+
+        cancel.countersign(&merchant).unwrap();
+        cancel.reason = "edited after signing".into();
+        assert!(cancel.verify().is_err());
+    }
+
+    #[test]
+    fn cancellation_for_other_session_is_rejected() {
+        let mut sm = SettlementStateMachine::new("tx".into(), "s".into());
+        let result = sm.mark_aborted(signed_cancellation("tx", "other-session"));
+        assert!(matches!(result, Err(NtpError::SessionMismatch { .. })));
+        assert!(!sm.is_terminal());
+    }
+
+    #[test]
+    fn bumped_fee_always_increases() {
+        let t = SettlementTimeouts::default();
+        assert_eq!(t.bumped_fee(10_000), 12_500);
+        assert_eq!(t.bumped_fee(0), config::MIN_TX_FEE_PHOTONS);
+        assert_eq!(
+            t.bumped_fee(config::MAX_TX_FEE_PHOTONS),
+            config::MAX_TX_FEE_PHOTONS
+        );
+    }
 }
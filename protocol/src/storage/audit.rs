@@ -0,0 +1,468 @@
+//! # Audit Log
+//!
+//! An append-only record of privileged operations — key loads, admin API
+//! calls, validator mode changes, configuration loads — kept in a dedicated
+//! `audit_log` tree inside [`NovaDB`] for compliance review.
+//!
+//! ## Tamper Evidence
+//!
+//! Every entry commits to its predecessor:
+//!
+//! ```text
+//!   hash(n) = BLAKE3("nova-audit-v1" ‖ seq ‖ timestamp ‖ category ‖ action
+//!                    ‖ actor ‖ details ‖ hash(n-1))          hash(-1) = 0
+//! ```
+//!
+//! and the log's head (entry count plus last hash) is stored next to the
+//! entries, updated in the same atomic batch. [`verify_chain`] recomputes
+//! the chain and reports the first entry that was modified, removed or
+//! reordered, and a head that claims more entries than are present
+//! (truncation).
+//!
+//! Someone with write access to the database can still rewrite the whole
+//! chain and its head consistently. The head hash is therefore meant to be
+//! recorded elsewhere too — the node logs it at startup — and checked
+//! against with [`verify_chain`]'s `anchor`.
+
+use serde::{Deserialize, Serialize};
+use sled::{Batch, Tree};
+
+use parking_lot::Mutex;
+
+use crate::crypto::wire::AuditHash;
+use crate::storage::db::{DbError, DbResult, NovaDB};
+
+/// Name of the sled tree holding the audit log.
+pub const AUDIT_TREE: &str = "audit_log";
+
+/// Domain separator for entry hashes.
+const HASH_DOMAIN: &[u8] = b"nova-audit-v1";
+
+/// Key prefix of entries; the rest of the key is the big-endian sequence
+/// number, so entries iterate in order.
+const ENTRY_PREFIX: &[u8] = b"e";
+
+/// Key of the stored [`AuditHead`].
+const HEAD_KEY: &[u8] = b"h";
+
+// ---------------------------------------------------------------------------
+// Entries
+// ---------------------------------------------------------------------------
+
+/// What kind of privileged operation an entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditCategory {
+    /// A signing key was loaded, generated or used outside block signing.
+    KeyUsage,
+    /// A call to the node's admin API.
+    Admin,
+    /// The node started in, or switched to, a validator/full-node mode.
+    ValidatorMode,
+    /// Node configuration was loaded.
+    Config,
+    /// A transaction touched an address on a screening list, or the list
+    /// could not be consulted.
+    Screening,
+}
+
+impl AuditCategory {
+    /// Stable name, as used on the wire and in entry hashes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::KeyUsage => "key_usage",
+            Self::Admin => "admin",
+            Self::ValidatorMode => "validator_mode",
+            Self::Config => "config",
+            Self::Screening => "screening",
+        }
+    }
+}
+
+impl std::fmt::Display for AuditCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One record in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 0.
+    pub seq: u64,
+    /// Unix timestamp (milliseconds) when the operation happened.
+    pub timestamp_ms: u64,
+    /// Kind of operation.
+    pub category: AuditCategory,
+    /// Short machine-readable action, e.g. `peer_removed`.
+    pub action: String,
+    /// Who performed it: a remote address, `node` for the node itself.
+    pub actor: String,
+    /// Free-form specifics (addresses, flags, the old and new mode).
+    pub details: String,
+    /// Hash of the previous entry; zero for the first one.
+    pub prev_hash: AuditHash,
+    /// Hash of this entry, over every field above.
+    pub hash: AuditHash,
+}
+
+impl AuditEntry {
+    /// Recomputes the hash this entry should carry.
+    pub fn compute_hash(&self) -> AuditHash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(HASH_DOMAIN);
+        hasher.update(&self.seq.to_be_bytes());
+        hasher.update(&self.timestamp_ms.to_be_bytes());
+        for field in [
+            self.category.as_str(),
+            self.action.as_str(),
+            self.actor.as_str(),
+            self.details.as_str(),
+        ] {
+            // Length-prefixed so ("ab", "c") and ("a", "bc") differ.
+            hasher.update(&(field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hasher.update(self.prev_hash.as_bytes());
+        AuditHash(*hasher.finalize().as_bytes())
+    }
+}
+
+/// Length and tip of an audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AuditHead {
+    /// Number of entries.
+    pub len: u64,
+    /// Hash of the last entry; zero for an empty log.
+    pub hash: AuditHash,
+}
+
+/// A whole audit log, as exported for offline review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExport {
+    /// Head the exporting node had stored.
+    pub head: AuditHead,
+    /// Every entry, in order.
+    pub entries: Vec<AuditEntry>,
+}
+
+// ---------------------------------------------------------------------------
+// Verification
+// ---------------------------------------------------------------------------
+
+/// Why an audit log failed verification, or could not be read.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum AuditError {
+    /// The underlying database failed.
+    #[error(transparent)]
+    Db(#[from] DbError),
+
+    /// An entry's contents no longer match its hash.
+    #[error("entry {seq} was modified: its hash does not match its contents")]
+    Modified { seq: u64 },
+
+    /// An entry does not follow the one before it.
+    #[error("entry {seq} does not link to the entry before it (removed, inserted or reordered)")]
+    BrokenLink { seq: u64 },
+
+    /// Sequence numbers skip.
+    #[error("expected entry {expected}, found entry {found} (entries removed)")]
+    Gap { expected: u64, found: u64 },
+
+    /// Fewer entries than the head records.
+    #[error("log truncated: head records {expected} entries, found {found}")]
+    Truncated { expected: u64, found: u64 },
+
+    /// The entries do not end where the head says.
+    #[error("log head does not match its last entry")]
+    HeadMismatch,
+}
+
+/// Checks that `entries` form an unbroken chain from the first entry and,
+/// if given, end at `anchor` — the stored head, or a head recorded
+/// elsewhere. Returns the head the entries actually produce.
+pub fn verify_chain(
+    entries: &[AuditEntry],
+    anchor: Option<&AuditHead>,
+) -> Result<AuditHead, AuditError> {
+    let mut head = AuditHead::default();
+    for entry in entries {
+        if entry.seq != head.len {
+            return Err(AuditError::Gap {
+                expected: head.len,
+                found: entry.seq,
+            });
+        }
+        if entry.prev_hash != head.hash {
+            return Err(AuditError::BrokenLink { seq: entry.seq });
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(AuditError::Modified { seq: entry.seq });
+        }
+        head = AuditHead {
+            len: head.len + 1,
+            hash: entry.hash,
+        };
+    }
+
+    match anchor {
+        Some(anchor) if anchor.len > head.len => Err(AuditError::Truncated {
+            expected: anchor.len,
+            found: head.len,
+        }),
+        Some(anchor) if *anchor != head => Err(AuditError::HeadMismatch),
+        _ => Ok(head),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AuditLog
+// ---------------------------------------------------------------------------
+
+/// The audit log persisted in NovaDB.
+///
+/// Appends are serialized so every entry links to the one before it;
+/// reads go straight to sled. Share via `Arc`.
+#[derive(Debug)]
+pub struct AuditLog {
+    tree: Tree,
+    head: Mutex<AuditHead>,
+}
+
+impl AuditLog {
+    /// Opens (or creates) the audit log inside `db`.
+    pub fn open(db: &NovaDB) -> DbResult<Self> {
+        let tree = db.open_tree(AUDIT_TREE)?;
+        let head = match tree.get(HEAD_KEY)? {
+            Some(bytes) => decode(&bytes)?,
+            None => AuditHead::default(),
+        };
+        Ok(Self {
+            tree,
+            head: Mutex::new(head),
+        })
+    }
+
+    /// The stored head.
+    pub fn head(&self) -> AuditHead {
+        *self.head.lock()
+    }
+
+    /// Appends an entry and returns it.
+    pub fn append(
+        &self,
+        category: AuditCategory,
+        action: &str,
+        actor: &str,
+        details: &str,
+        now_ms: u64,
+    ) -> DbResult<AuditEntry> {
+        let mut head = self.head.lock();
+        let mut entry = AuditEntry {
+            seq: head.len,
+            timestamp_ms: now_ms,
+            category,
+            action: action.to_string(),
+            actor: actor.to_string(),
+            details: details.to_string(),
+            prev_hash: head.hash,
+            hash: AuditHash::zero(),
+        };
+        entry.hash = entry.compute_hash();
+        let next = AuditHead {
+            len: head.len + 1,
+            hash: entry.hash,
+        };
+
+        let mut batch = Batch::default();
+        batch.insert(entry_key(entry.seq), encode(&entry)?);
+        batch.insert(HEAD_KEY, encode(&next)?);
+        self.tree.apply_batch(batch)?;
+        *head = next;
+        Ok(entry)
+    }
+
+    /// Every entry, oldest first.
+    pub fn entries(&self) -> DbResult<Vec<AuditEntry>> {
+        self.tree
+            .scan_prefix(ENTRY_PREFIX)
+            .values()
+            .map(|v| decode(&v?))
+            .collect()
+    }
+
+    /// The most recent entry of `category`, if any.
+    pub fn last_of(&self, category: AuditCategory) -> DbResult<Option<AuditEntry>> {
+        for value in self.tree.scan_prefix(ENTRY_PREFIX).values().rev() {
+            let entry: AuditEntry = decode(&value?)?;
+            if entry.category == category {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The stored head and every entry.
+    pub fn export(&self) -> DbResult<AuditExport> {
+        let head = self.head.lock();
+        Ok(AuditExport {
+            head: *head,
+            entries: self.entries()?,
+        })
+    }
+
+    /// Verifies the stored entries against the stored head.
+    pub fn verify(&self) -> Result<AuditHead, AuditError> {
+        let export = self.export()?;
+        verify_chain(&export.entries, Some(&export.head))
+    }
+}
+
+fn entry_key(seq: u64) -> Vec<u8> {
+    let mut key = ENTRY_PREFIX.to_vec();
+    key.extend_from_slice(&seq.to_be_bytes());
+    key
+}
+
+fn encode<T: Serialize>(value: &T) -> DbResult<Vec<u8>> {
+    bincode::serialize(value).map_err(|e| DbError::Serialization(e.to_string()))
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> DbResult<T> {
+    bincode::deserialize(bytes).map_err(|e| DbError::Serialization(e.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn populated() -> (NovaDB, AuditLog) {
+        let db = NovaDB::open_temporary().unwrap();
+        let log = AuditLog::open(&db).unwrap();
+        log.append(AuditCategory::KeyUsage, "key_loaded", "node", "pk=ab", NOW)
+            .unwrap();
+        log.append(
+            AuditCategory::ValidatorMode,
+            "mode",
+            "node",
+            "validator",
+            NOW + 1,
+        )
+        .unwrap();
+        log.append(
+            AuditCategory::Admin,
+            "peer_banned",
+            "10.0.0.9:5000",
+            "p1",
+            NOW + 2,
+        )
+        .unwrap();
+        (db, log)
+    }
+
+    #[test]
+    fn entries_chain_and_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let head = {
+            let db = NovaDB::open(dir.path()).unwrap();
+            let log = AuditLog::open(&db).unwrap();
+            log.append(AuditCategory::Config, "config_loaded", "node", "a", NOW)
+                .unwrap();
+            let second = log
+                .append(AuditCategory::Config, "config_loaded", "node", "b", NOW)
+                .unwrap();
+            assert_eq!(second.seq, 1);
+            assert_eq!(second.prev_hash, log.entries().unwrap()[0].hash);
+            db.flush().unwrap();
+            log.head()
+        };
+
+        let db = NovaDB::open(dir.path()).unwrap();
+        let log = AuditLog::open(&db).unwrap();
+        assert_eq!(log.head(), head);
+        assert_eq!(log.verify().unwrap(), head);
+        let third = log
+            .append(AuditCategory::Config, "config_loaded", "node", "c", NOW)
+            .unwrap();
+        assert_eq!((third.seq, third.prev_hash), (2, head.hash));
+        assert_eq!(
+            log.last_of(AuditCategory::Config).unwrap().unwrap().details,
+            "c"
+        );
+        assert!(log.last_of(AuditCategory::Admin).unwrap().is_none());
+    }
+
+    #[test]
+    fn verify_detects_modification() {
+        let (_db, log) = populated();
+        let mut entries = log.entries().unwrap();
+        entries[1].details = "full_node".into();
+        assert!(matches!(
+            verify_chain(&entries, None),
+            Err(AuditError::Modified { seq: 1 })
+        ));
+
+        // Rehashing the edited entry breaks the link from its successor.
+        entries[1].hash = entries[1].compute_hash();
+        assert!(matches!(
+            verify_chain(&entries, None),
+            Err(AuditError::BrokenLink { seq: 2 })
+        ));
+    }
+
+    #[test]
+    fn verify_detects_removal_and_truncation() {
+        let (db, log) = populated();
+        let head = log.head();
+        let mut entries = log.entries().unwrap();
+
+        let mut gapped = entries.clone();
+        gapped.remove(1);
+        assert!(matches!(
+            verify_chain(&gapped, None),
+            Err(AuditError::Gap {
+                expected: 1,
+                found: 2
+            })
+        ));
+
+        entries.pop();
+        assert!(verify_chain(&entries, None).is_ok());
+        assert!(matches!(
+            verify_chain(&entries, Some(&head)),
+            Err(AuditError::Truncated {
+                expected: 3,
+                found: 2
+            })
+        ));
+
+        // The same truncation, done to the database directly.
+        db.open_tree(AUDIT_TREE)
+            .unwrap()
+            .remove(entry_key(2))
+            .unwrap();
+        assert!(matches!(log.verify(), Err(AuditError::Truncated { .. })));
+    }
+
+    #[test]
+    fn export_round_trips_through_json() {
+        let (_db, log) = populated();
+        let export = log.export().unwrap();
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(json.contains("\"category\":\"validator_mode\""));
+        assert!(json.contains(&format!("\"hash\":\"0x{}\"", export.head.hash.to_hex())));
+
+        let back: AuditExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            verify_chain(&back.entries, Some(&back.head)).unwrap(),
+            log.head()
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! # Batch Payouts — Many Recipients, One Transaction
+//!
+//! Payroll and airdrops pay hundreds of accounts at once. As plain
+//! transfers that is hundreds of transactions, each with its own nonce and
+//! signature, any of which can fail on its own. A `Batch` transaction
+//! carries the whole payout list instead and executes all-or-nothing: every
+//! recipient is checked before the first photon moves, and if any check
+//! fails the transaction fails with nothing applied.
+//!
+//! ## On-chain encoding
+//!
+//! | Tx type | sender | receiver        | amount          | payload         |
+//! |---------|--------|-----------------|-----------------|-----------------|
+//! | `Batch` | payer  | first recipient | sum of payouts  | [`BatchPayout`] |
+//!
+//! Repeating the first recipient and the total in the transaction's own
+//! fields keeps the mempool's balance check (`amount + fee`) and the
+//! sender ≠ receiver rule meaningful without parsing the payload. The fee
+//! is charged once for the whole batch.
+//!
+//! A batch names at most [`MAX_TX_OUTPUTS`] recipients, each once, none of
+//! them the sender, and the payload is still bound by
+//! [`MAX_PAYLOAD_BYTES`](crate::transaction::limits::MAX_PAYLOAD_BYTES).
+//! Each payout that opens a new account must meet the existential deposit.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{EXISTENTIAL_DEPOSIT_PHOTONS, MAX_TX_OUTPUTS};
+use crate::encoding;
+use crate::transaction::builder::Transaction;
+use crate::transaction::types::TransactionType;
+
+use super::state::{check_nonce, reap_if_dust, StateError, StateTree};
+
+// ---------------------------------------------------------------------------
+// Payload
+// ---------------------------------------------------------------------------
+
+/// One recipient of a batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchEntry {
+    /// NOVA address being paid.
+    pub recipient: String,
+    /// Photons paid to `recipient`.
+    #[serde(with = "encoding::amount")]
+    pub amount: u64,
+}
+
+/// Payload of a `Batch` transaction (JSON-encoded).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchPayout {
+    /// Recipients in payout order. The first one repeats the transaction's
+    /// receiver.
+    pub payouts: Vec<BatchEntry>,
+}
+
+impl BatchPayout {
+    /// Sum of every payout, or `None` if it overflows.
+    pub fn total(&self) -> Option<u64> {
+        self.payouts
+            .iter()
+            .try_fold(0u64, |sum, entry| sum.checked_add(entry.amount))
+    }
+
+    /// Checks the payout list against the transaction carrying it.
+    pub fn validate(&self, tx: &Transaction) -> Result<(), StateError> {
+        let reject = |reason: String| Err(StateError::BatchRejected(reason));
+
+        let Some(first) = self.payouts.first() else {
+            return reject("batch has no payouts".to_string());
+        };
+        if self.payouts.len() > MAX_TX_OUTPUTS {
+            return reject(format!(
+                "batch has {} payouts, the limit is {}",
+                self.payouts.len(),
+                MAX_TX_OUTPUTS
+            ));
+        }
+        if first.recipient != tx.receiver {
+            return reject("transaction receiver must be the first recipient".to_string());
+        }
+        if self.total() != Some(tx.amount.value) {
+            return reject("transaction amount must be the sum of the payouts".to_string());
+        }
+
+        let mut seen = HashSet::with_capacity(self.payouts.len());
+        for entry in &self.payouts {
+            if entry.amount == 0 {
+                return reject(format!("zero payout to {}", entry.recipient));
+            }
+            if entry.recipient == tx.sender {
+                return reject("the sender cannot pay itself".to_string());
+            }
+            if !seen.insert(entry.recipient.as_str()) {
+                return reject(format!("{} is paid more than once", entry.recipient));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Recipients a `Batch` transaction pays, or none for any other type or
+/// an unreadable payload.
+///
+/// [`BalanceAudit`](super::fee::BalanceAudit) uses this to see every
+/// account a block can credit.
+pub fn batch_recipients(tx: &Transaction) -> Vec<String> {
+    batch_payouts(tx).into_iter().map(|e| e.recipient).collect()
+}
+
+/// Payouts a `Batch` transaction makes, or none for any other type or an
+/// unreadable payload.
+pub fn batch_payouts(tx: &Transaction) -> Vec<BatchEntry> {
+    if tx.tx_type != TransactionType::Batch {
+        return Vec::new();
+    }
+    tx.payload
+        .as_deref()
+        .and_then(|payload| serde_json::from_slice::<BatchPayout>(payload).ok())
+        .map(|batch| batch.payouts)
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// State Transition
+// ---------------------------------------------------------------------------
+
+/// Execute a `Batch` transaction: debit the sender the total plus the fee
+/// and credit every recipient, or change nothing.
+///
+/// Shared by the block producer and the sync engine. `tx.nonce` must be
+/// the sender's next nonce. Other transaction types are ignored.
+pub fn apply_batch(tree: &mut StateTree, tx: &Transaction) -> Result<(), StateError> {
+    if tx.tx_type != TransactionType::Batch {
+        return Ok(());
+    }
+    let mut sender = tree.get(&tx.sender).unwrap_or_default();
+    if sender.frozen {
+        return Err(StateError::AccountFrozen(tx.sender.clone()));
+    }
+    check_nonce(tree, &tx.sender, tx.nonce)?;
+
+    let payload = tx.payload.as_deref().unwrap_or_default();
+    let batch: BatchPayout = serde_json::from_slice(payload)
+        .map_err(|e| StateError::Serialization(format!("batch payout: {}", e)))?;
+    batch.validate(tx)?;
+
+    let available = sender.available_balance();
+    let need = tx.amount.value.saturating_add(tx.fee);
+    if available < need {
+        return Err(StateError::InsufficientBalance {
+            have: available,
+            need,
+        });
+    }
+    for entry in &batch.payouts {
+        if entry.amount < EXISTENTIAL_DEPOSIT_PHOTONS && tree.get(&entry.recipient).is_none() {
+            return Err(StateError::BelowExistentialDeposit {
+                address: entry.recipient.clone(),
+                amount: entry.amount,
+                minimum: EXISTENTIAL_DEPOSIT_PHOTONS,
+            });
+        }
+    }
+
+    // Every check passed; nothing below can fail.
+    sender.balance -= need;
+    sender.nonce += 1;
+    tree.put(&tx.sender, &sender);
+    for entry in &batch.payouts {
+        let mut recipient = tree.get(&entry.recipient).unwrap_or_default();
+        recipient.balance += entry.amount;
+        tree.put(&entry.recipient, &recipient);
+    }
+    reap_if_dust(tree, &tx.sender);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::state::AccountState;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+
+    fn temp_tree() -> StateTree {
+        StateTree::new(NovaDB::open_temporary().unwrap())
+    }
+
+    fn batch_tx(payouts: &[(&str, u64)], amount: u64, fee: u64, nonce: u64) -> Transaction {
+        let batch = BatchPayout {
+            payouts: payouts
+                .iter()
+                .map(|&(recipient, amount)| BatchEntry {
+                    recipient: recipient.to_string(),
+                    amount,
+                })
+                .collect(),
+        };
+        TransactionBuilder::new(TransactionType::Batch)
+            .sender("nova1payroll")
+            .receiver(payouts.first().map_or("nova1nobody", |p| p.0))
+            .amount(Amount::new(amount, Currency::NOVA))
+            .fee(fee)
+            .nonce(nonce)
+            .payload(serde_json::to_vec(&batch).unwrap())
+            .build_unchecked()
+    }
+
+    #[test]
+    fn pays_every_recipient() {
+        let mut tree = temp_tree();
+        tree.put("nova1payroll", &AccountState::with_balance(100_000));
+        tree.put("nova1carol", &AccountState::with_balance(5));
+
+        let payouts = [
+            ("nova1alice", 20_000),
+            ("nova1bob", 30_000),
+            ("nova1carol", 1),
+        ];
+        let tx = batch_tx(&payouts, 50_001, 100, 1);
+        apply_batch(&mut tree, &tx).unwrap();
+
+        let payroll = tree.get("nova1payroll").unwrap();
+        assert_eq!(payroll.balance, 100_000 - 50_001 - 100);
+        assert_eq!(payroll.nonce, 1);
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 20_000);
+        assert_eq!(tree.get("nova1bob").unwrap().balance, 30_000);
+        assert_eq!(tree.get("nova1carol").unwrap().balance, 6);
+        assert_eq!(
+            batch_recipients(&tx),
+            ["nova1alice", "nova1bob", "nova1carol"]
+        );
+    }
+
+    #[test]
+    fn one_bad_payout_fails_the_whole_batch() {
+        let mut tree = temp_tree();
+        tree.put("nova1payroll", &AccountState::with_balance(100_000));
+        let root = tree.root();
+
+        // A new account below the existential deposit.
+        let tx = batch_tx(&[("nova1alice", 20_000), ("nova1dust", 1)], 20_001, 0, 1);
+        assert!(matches!(
+            apply_batch(&mut tree, &tx),
+            Err(StateError::BelowExistentialDeposit { .. })
+        ));
+
+        // More than the sender holds, fee included.
+        let tx = batch_tx(
+            &[("nova1alice", 60_000), ("nova1bob", 40_000)],
+            100_000,
+            1,
+            1,
+        );
+        assert!(matches!(
+            apply_batch(&mut tree, &tx),
+            Err(StateError::InsufficientBalance { need: 100_001, .. })
+        ));
+        assert_eq!(tree.root(), root);
+    }
+
+    #[test]
+    fn rejects_malformed_batches() {
+        let mut tree = temp_tree();
+        tree.put("nova1payroll", &AccountState::with_balance(100_000));
+
+        let cases = [
+            // Amount is not the sum of the payouts.
+            batch_tx(
+                &[("nova1alice", 20_000), ("nova1bob", 20_000)],
+                30_000,
+                0,
+                1,
+            ),
+            // Recipient paid twice.
+            batch_tx(
+                &[("nova1alice", 20_000), ("nova1alice", 20_000)],
+                40_000,
+                0,
+                1,
+            ),
+            // Zero payout.
+            batch_tx(&[("nova1alice", 20_000), ("nova1bob", 0)], 20_000, 0, 1),
+            // Sender paying itself.
+            batch_tx(&[("nova1alice", 20_000), ("nova1payroll", 1)], 20_001, 0, 1),
+            // Empty.
+            batch_tx(&[], 1, 0, 1),
+        ];
+        for tx in &cases {
+            assert!(
+                matches!(
+                    apply_batch(&mut tree, tx),
+                    Err(StateError::BatchRejected(_))
+                ),
+                "accepted {:?}",
+                tx.payload
+            );
+        }
+
+        let mut tx = batch_tx(&[("nova1alice", 20_000)], 20_000, 0, 1);
+        tx.receiver = "nova1bob".to_string();
+        assert!(matches!(
+            apply_batch(&mut tree, &tx),
+            Err(StateError::BatchRejected(_))
+        ));
+
+        let too_many: Vec<(String, u64)> = (0..=MAX_TX_OUTPUTS)
+            .map(|i| (format!("nova1payee{i}"), 1_000))
+            .collect();
+        let refs: Vec<(&str, u64)> = too_many.iter().map(|(r, a)| (r.as_str(), *a)).collect();
+        let tx = batch_tx(&refs, 1_000 * refs.len() as u64, 0, 1);
+        assert!(matches!(
+            apply_batch(&mut tree, &tx),
+            Err(StateError::BatchRejected(_))
+        ));
+
+        assert_eq!(tree.get("nova1payroll").unwrap().nonce, 0);
+    }
+}
@@ -10,13 +10,17 @@
 //! ┌─────────────────────────────────────────────┐
 //! │  BlockHeader                                │
 //! │  ├── height: u64                            │
-//! │  ├── hash: [u8; 32]       (BLAKE3 of header)│
-//! │  ├── parent_hash: [u8; 32]                  │
+//! │  ├── hash: BlockHash      (BLAKE3 of header)│
+//! │  ├── parent_hash: BlockHash                 │
 //! │  ├── timestamp: u64                         │
 //! │  ├── validator: String                      │
-//! │  ├── state_root: [u8; 32]                   │
-//! │  ├── tx_root: [u8; 32]   (Merkle root)      │
-//! │  └── signature: Vec<u8>                     │
+//! │  ├── state_root: StateRoot                  │
+//! │  ├── tx_root: TxRoot     (Merkle root)      │
+//! │  ├── fees: u64                              │
+//! │  ├── last_commit: Vec<CommitSignature>      │
+//! │  ├── version: u8                            │
+//! │  ├── extensions: HeaderExtensions           │
+//! │  └── signature: Option<SignatureBytes>      │
 //! ├─────────────────────────────────────────────┤
 //! │  transactions: Vec<Transaction>             │
 //! └─────────────────────────────────────────────┘
@@ -25,18 +29,60 @@
 //! ## Hash Computation
 //!
 //! The block hash covers: `height || parent_hash || timestamp || validator
-//! || state_root || tx_root`. The signature is NOT included in the hash
-//! (it signs the hash, not the other way around).
+//! || state_root || tx_root || fees`, followed by each `last_commit` entry
+//! when there are any, and from header version 1 on by the version byte and
+//! the extension area. The signature is NOT included in the hash (it signs
+//! the hash, not the other way around).
+//!
+//! ## Last Commit
+//!
+//! A proposer copies the votes that finalized the parent block into
+//! `last_commit`. They are what liveness tracking counts missed votes from
+//! ([`crate::network::liveness`]). Blocks without them hash exactly as they
+//! did before the field existed.
+//!
+//! ## Header Versions
+//!
+//! Fields added to the header after launch (a receipts root, a base fee, a
+//! nullifier root) live in the extension area rather than as new struct
+//! fields, so adding one never changes how existing blocks hash. A version
+//! 0 header is the original layout and carries no extensions. From version
+//! 1 on, the hash preimage continues with
+//!
+//! ```text
+//! version: u8 || area_len: u32 LE || count: u16 LE
+//!   || (id: u16 LE || len: u32 LE || value)*   in ascending id order
+//! ```
+//!
+//! Each typed field ([`HeaderExtension`]) has a fixed id and the header
+//! version it activates at. [`Block::verify`] rejects a header above
+//! [`MAX_HEADER_VERSION`] and any extension that is unknown or not yet
+//! active at the header's version.
 //!
 //! ## Merkle Root
 //!
 //! The `tx_root` is a binary Merkle tree over the BLAKE3 hashes of each
 //! transaction's canonical serialization. Empty blocks have a tx_root of
 //! all zeros.
+//!
+//! A block producer packing transactions one at a time keeps a
+//! [`MerkleBuilder`] alongside: each append merges the completed subtrees
+//! it finishes, so the root of the candidate block is ready in
+//! `O(log n)` without rehashing the transactions packed so far.
+//!
+//! ## Wire Types
+//!
+//! Hashes, roots and the signature use the fixed-size types from
+//! [`crate::crypto::wire`]: hex in JSON, raw bytes in bincode. A wrong-length
+//! value fails to deserialize instead of surfacing later as a bad signature.
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
 use crate::crypto::hash::blake3_hash;
+use crate::crypto::keys::{NovaPublicKey, NovaSignature};
+use crate::crypto::wire::{BlockHash, SignatureBytes, StateRoot, TxRoot};
 use crate::transaction::Transaction;
 
 /// Coinbase message embedded in the genesis block state root.
@@ -60,30 +106,227 @@ pub struct BlockHeader {
     /// Block height (0-indexed, genesis = 0).
     pub height: u64,
     /// BLAKE3 hash of this block's header fields.
-    pub hash: [u8; 32],
+    pub hash: BlockHash,
     /// Hash of the parent block's header. All zeros for genesis.
-    pub parent_hash: [u8; 32],
+    pub parent_hash: BlockHash,
     /// Unix timestamp (milliseconds) when this block was produced.
     pub timestamp: u64,
     /// NOVA address (hex public key) of the validator that proposed this block.
     pub validator: String,
     /// Root hash of the state tree after applying this block's transactions.
-    pub state_root: [u8; 32],
+    pub state_root: StateRoot,
     /// Merkle root of the transactions in this block.
-    pub tx_root: [u8; 32],
-    /// Ed25519 signature of the validator over the block hash.
-    pub signature: Vec<u8>,
+    pub tx_root: TxRoot,
+    /// Total fees, in photons, charged by this block's transactions before
+    /// the proposer/treasury/burn split. Sync rejects a block whose
+    /// transactions charge anything else.
+    #[serde(default)]
+    pub fees: u64,
+    /// Votes that finalized the parent block, as the proposer saw them.
+    #[serde(default)]
+    pub last_commit: Vec<CommitSignature>,
+    /// Header layout version. `0` is the original layout; from `1` on the
+    /// hash also covers the version and `extensions`.
+    #[serde(default)]
+    pub version: u8,
+    /// Fields added after the original layout, keyed by extension id.
+    /// Always empty at version 0.
+    #[serde(default)]
+    pub extensions: HeaderExtensions,
+    /// Ed25519 signature of the validator over the header. `None` until the
+    /// proposer signs it; genesis stays unsigned.
+    pub signature: Option<SignatureBytes>,
 }
 
 impl BlockHeader {
     /// Return the block hash as a hex string.
     pub fn hash_hex(&self) -> String {
-        hex::encode(self.hash)
+        self.hash.to_hex()
     }
 
     /// Return the parent hash as a hex string.
     pub fn parent_hash_hex(&self) -> String {
-        hex::encode(self.parent_hash)
+        self.parent_hash.to_hex()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CommitSignature
+// ---------------------------------------------------------------------------
+
+/// One validator's precommit for the parent block, carried in the child's
+/// header.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitSignature {
+    /// Hex-encoded public key of the voting validator.
+    pub validator: String,
+    /// Consensus round the precommit was cast in.
+    pub round: u64,
+    /// Ed25519 signature over `(parent_hash || round.to_le_bytes())`, the
+    /// same message a consensus precommit signs.
+    pub signature: SignatureBytes,
+}
+
+impl CommitSignature {
+    /// Checks the signature as a precommit for `block_hash`.
+    pub fn verify(&self, block_hash: &BlockHash) -> bool {
+        let Ok(pk) = NovaPublicKey::from_hex(&self.validator) else {
+            return false;
+        };
+        let mut message = Vec::with_capacity(40);
+        message.extend_from_slice(block_hash.as_bytes());
+        message.extend_from_slice(&self.round.to_le_bytes());
+        pk.verify(&message, &NovaSignature::from(self.signature))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Header Extensions
+// ---------------------------------------------------------------------------
+
+/// Header version of the original layout, without an extension area. Every
+/// block produced before header versioning has it and keeps its hash.
+pub const LEGACY_HEADER_VERSION: u8 = 0;
+
+/// Highest header version this build accepts.
+pub const MAX_HEADER_VERSION: u8 = 1;
+
+/// A typed field carried in the header extension area.
+pub trait HeaderExtension: Sized {
+    /// Id of the field in the extension area. Assigned once, never reused.
+    const ID: u16;
+
+    /// Header version from which blocks may carry the field (at least 1).
+    const SINCE_VERSION: u8;
+
+    /// The field's canonical bytes.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Parses the canonical bytes, or `None` if they are malformed.
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Root of the receipts of the block's transactions. Not yet active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceiptsRoot(pub [u8; 32]);
+
+/// Base fee, in photons per byte, for transactions in the block. Not yet
+/// active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BaseFee(pub u64);
+
+/// Root of the shielded-note nullifier set after the block. Not yet active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NullifierRoot(pub [u8; 32]);
+
+impl HeaderExtension for ReceiptsRoot {
+    const ID: u16 = 1;
+    const SINCE_VERSION: u8 = 2;
+
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self)
+    }
+}
+
+impl HeaderExtension for BaseFee {
+    const ID: u16 = 2;
+    const SINCE_VERSION: u8 = 2;
+
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_le_bytes().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(|b| Self(u64::from_le_bytes(b)))
+    }
+}
+
+impl HeaderExtension for NullifierRoot {
+    const ID: u16 = 3;
+    const SINCE_VERSION: u8 = 2;
+
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self)
+    }
+}
+
+/// Header version at which extension `id` activates, or `None` for an id
+/// this build does not know.
+fn activation_version(id: u16) -> Option<u8> {
+    match id {
+        ReceiptsRoot::ID => Some(ReceiptsRoot::SINCE_VERSION),
+        BaseFee::ID => Some(BaseFee::SINCE_VERSION),
+        NullifierRoot::ID => Some(NullifierRoot::SINCE_VERSION),
+        _ => None,
+    }
+}
+
+/// The header extension area: raw field bytes keyed by extension id.
+///
+/// Kept sorted by id, so [`encode`](Self::encode) is deterministic whatever
+/// order the fields were set in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderExtensions(BTreeMap<u16, ExtensionBytes>);
+
+/// One extension's bytes: hex in JSON, raw in bincode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ExtensionBytes(#[serde(with = "crate::encoding::hex_bytes")] Vec<u8>);
+
+impl HeaderExtensions {
+    /// An empty extension area.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if no field is set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of fields set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Sets the typed field `value`, replacing any previous value.
+    pub fn set<E: HeaderExtension>(&mut self, value: &E) {
+        self.0.insert(E::ID, ExtensionBytes(value.encode()));
+    }
+
+    /// Returns the typed field `E`, or `None` if it is absent or malformed.
+    pub fn get<E: HeaderExtension>(&self) -> Option<E> {
+        self.raw(E::ID).and_then(E::decode)
+    }
+
+    /// Returns the bytes stored under `id`.
+    pub fn raw(&self, id: u16) -> Option<&[u8]> {
+        self.0.get(&id).map(|bytes| bytes.0.as_slice())
+    }
+
+    /// Ids of the fields set, ascending.
+    pub fn ids(&self) -> impl Iterator<Item = u16> + '_ {
+        self.0.keys().copied()
+    }
+
+    /// The canonical encoding: `count: u16 LE`, then `id: u16 LE ||
+    /// len: u32 LE || value` per field in ascending id order.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.0.len() as u16).to_le_bytes());
+        for (id, bytes) in &self.0 {
+            out.extend_from_slice(&id.to_le_bytes());
+            out.extend_from_slice(&(bytes.0.len() as u32).to_le_bytes());
+            out.extend_from_slice(&bytes.0);
+        }
+        out
     }
 }
 
@@ -121,35 +364,32 @@ impl Block {
         let state_root = blake3_hash(GENESIS_COINBASE_MESSAGE);
         let tx_root = [0u8; 32]; // No transactions.
 
-        let hash = compute_header_hash(
-            0,
-            &[0u8; 32],
-            timestamp,
-            &genesis_validator,
-            &state_root,
-            &tx_root,
-        );
-
-        Block {
+        let mut genesis = Block {
             header: BlockHeader {
                 height: 0,
-                hash,
-                parent_hash: [0u8; 32],
+                hash: BlockHash::zero(), // Computed below.
+                parent_hash: BlockHash::zero(),
                 timestamp,
                 validator: genesis_validator,
-                state_root,
-                tx_root,
-                signature: Vec::new(), // Genesis block is unsigned.
+                state_root: state_root.into(),
+                tx_root: tx_root.into(),
+                fees: 0,
+                last_commit: Vec::new(),
+                version: LEGACY_HEADER_VERSION,
+                extensions: HeaderExtensions::new(),
+                signature: None, // Genesis block is unsigned.
             },
             transactions: Vec::new(),
-        }
+        };
+        genesis.header.hash = genesis.compute_hash();
+        genesis
     }
 
     /// Construct a new block linked to a parent.
     ///
     /// Computes the tx Merkle root from the transaction list and the
     /// block hash from the header fields. The signature field is left
-    /// empty — the validator signs separately after construction.
+    /// `None` — the validator signs separately after construction.
     ///
     /// # Arguments
     ///
@@ -163,49 +403,105 @@ impl Block {
         validator: String,
         state_root: [u8; 32],
     ) -> Self {
-        let height = parent.header.height + 1;
-        let parent_hash = parent.header.hash;
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
+        Self::new_at(parent, transactions, validator, state_root, timestamp)
+    }
+
+    /// Like [`new`](Self::new), but stamped with `timestamp` (Unix
+    /// milliseconds) instead of the wall clock. Identical inputs produce an
+    /// identical block hash.
+    pub fn new_at(
+        parent: &Block,
+        transactions: Vec<Transaction>,
+        validator: String,
+        state_root: [u8; 32],
+        timestamp: u64,
+    ) -> Self {
         let tx_root = compute_merkle_root(&transactions);
-        let hash = compute_header_hash(
-            height,
-            &parent_hash,
+        Self::with_tx_root(
+            parent,
+            transactions,
+            tx_root,
+            validator,
+            state_root,
             timestamp,
-            &validator,
-            &state_root,
-            &tx_root,
-        );
+        )
+    }
 
-        Block {
+    /// Like [`new_at`](Self::new_at), but with a `tx_root` the caller has
+    /// already computed over `transactions`, typically with a
+    /// [`MerkleBuilder`] while packing them. A root that does not match
+    /// makes the block fail [`verify`](Self::verify).
+    pub fn with_tx_root(
+        parent: &Block,
+        transactions: Vec<Transaction>,
+        tx_root: [u8; 32],
+        validator: String,
+        state_root: [u8; 32],
+        timestamp: u64,
+    ) -> Self {
+        debug_assert_eq!(tx_root, compute_merkle_root(&transactions));
+        let mut block = Block {
             header: BlockHeader {
-                height,
-                hash,
-                parent_hash,
+                height: parent.header.height + 1,
+                hash: BlockHash::zero(), // Computed below.
+                parent_hash: parent.header.hash,
                 timestamp,
                 validator,
-                state_root,
-                tx_root,
-                signature: Vec::new(),
+                state_root: state_root.into(),
+                tx_root: tx_root.into(),
+                fees: 0,
+                last_commit: Vec::new(),
+                version: LEGACY_HEADER_VERSION,
+                extensions: HeaderExtensions::new(),
+                signature: None,
             },
             transactions,
-        }
+        };
+        block.header.hash = block.compute_hash();
+        block
+    }
+
+    /// Attaches the votes that finalized the parent block and rehashes.
+    ///
+    /// Must be called before the proposer signs, since the signature covers
+    /// the hash.
+    pub fn with_last_commit(mut self, last_commit: Vec<CommitSignature>) -> Self {
+        self.header.last_commit = last_commit;
+        self.header.hash = self.compute_hash();
+        self
+    }
+
+    /// Records the fees the block's transactions charged and rehashes.
+    ///
+    /// Like [`with_last_commit`](Self::with_last_commit), must be called
+    /// before the proposer signs.
+    pub fn with_fees(mut self, fees: u64) -> Self {
+        self.header.fees = fees;
+        self.header.hash = self.compute_hash();
+        self
+    }
+
+    /// Moves the header to `version` with the extension fields
+    /// `extensions`, and rehashes.
+    ///
+    /// Like [`with_last_commit`](Self::with_last_commit), must be called
+    /// before the proposer signs.
+    pub fn with_extensions(mut self, version: u8, extensions: HeaderExtensions) -> Self {
+        self.header.version = version;
+        self.header.extensions = extensions;
+        self.header.hash = self.compute_hash();
+        self
     }
 
     /// Recompute the block hash from header fields.
     ///
     /// Use this to verify that `header.hash` matches the actual content.
-    pub fn compute_hash(&self) -> [u8; 32] {
-        compute_header_hash(
-            self.header.height,
-            &self.header.parent_hash,
-            self.header.timestamp,
-            &self.header.validator,
-            &self.header.state_root,
-            &self.header.tx_root,
-        )
+    pub fn compute_hash(&self) -> BlockHash {
+        BlockHash(compute_header_hash(&self.header))
     }
 
     /// Verify block integrity: hash consistency, tx Merkle root, and
@@ -217,6 +513,9 @@ impl Block {
     /// 1. The stored hash matches the recomputed hash.
     /// 2. The stored tx_root matches the recomputed Merkle root.
     /// 3. Genesis blocks have height 0 and zeroed parent_hash.
+    /// 4. Every `last_commit` entry is a valid vote for the parent block.
+    /// 5. The header version is at most [`MAX_HEADER_VERSION`], and every
+    ///    extension is known and active at that version.
     ///
     /// # Errors
     ///
@@ -227,9 +526,7 @@ impl Block {
         if self.header.hash != expected_hash {
             return Err(format!(
                 "block {} hash mismatch: stored={}, computed={}",
-                self.header.height,
-                hex::encode(self.header.hash),
-                hex::encode(expected_hash),
+                self.header.height, self.header.hash, expected_hash,
             ));
         }
 
@@ -239,16 +536,47 @@ impl Block {
             return Err(format!(
                 "block {} tx_root mismatch: stored={}, computed={}",
                 self.header.height,
-                hex::encode(self.header.tx_root),
+                self.header.tx_root,
                 hex::encode(expected_tx_root),
             ));
         }
 
         // 3. Genesis-specific checks.
-        if self.header.height == 0 && self.header.parent_hash != [0u8; 32] {
+        if self.header.height == 0 && !self.header.parent_hash.is_zero() {
             return Err("genesis block must have zeroed parent_hash".to_string());
         }
 
+        // 4. Verify the parent's commit signatures.
+        if let Some(bad) = self
+            .header
+            .last_commit
+            .iter()
+            .find(|commit| !commit.verify(&self.header.parent_hash))
+        {
+            return Err(format!(
+                "block {} carries an invalid commit signature from {}",
+                self.header.height, bad.validator
+            ));
+        }
+
+        // 5. Header version and extensions.
+        let version = self.header.version;
+        if version > MAX_HEADER_VERSION {
+            return Err(format!(
+                "block {} has unsupported header version {} (max {})",
+                self.header.height, version, MAX_HEADER_VERSION
+            ));
+        }
+        if let Some(id) = self.header.extensions.ids().find(|&id| {
+            version == LEGACY_HEADER_VERSION
+                || !activation_version(id).is_some_and(|since| since <= version)
+        }) {
+            return Err(format!(
+                "block {} carries header extension {} not active at version {}",
+                self.header.height, id, version
+            ));
+        }
+
         Ok(())
     }
 
@@ -264,7 +592,7 @@ impl Block {
 
     /// Return the block hash as a hex string.
     pub fn hash_hex(&self) -> String {
-        hex::encode(self.header.hash)
+        self.header.hash.to_hex()
     }
 }
 
@@ -272,25 +600,33 @@ impl Block {
 // Hash Computation
 // ---------------------------------------------------------------------------
 
-/// Compute the BLAKE3 hash of a block header from its constituent fields.
+/// Compute the BLAKE3 hash of a block header from its fields.
 ///
 /// The hash covers: height || parent_hash || timestamp || validator ||
-/// state_root || tx_root. The signature is NOT included.
-fn compute_header_hash(
-    height: u64,
-    parent_hash: &[u8; 32],
-    timestamp: u64,
-    validator: &str,
-    state_root: &[u8; 32],
-    tx_root: &[u8; 32],
-) -> [u8; 32] {
+/// state_root || tx_root || fees || last_commit, then for version 1 and up
+/// version || area_len || extensions. The signature is NOT included. An
+/// empty `last_commit` adds nothing and version 0 stops there, so older
+/// blocks keep their hashes.
+fn compute_header_hash(header: &BlockHeader) -> [u8; 32] {
     let mut preimage = Vec::with_capacity(128);
-    preimage.extend_from_slice(&height.to_le_bytes());
-    preimage.extend_from_slice(parent_hash);
-    preimage.extend_from_slice(&timestamp.to_le_bytes());
-    preimage.extend_from_slice(validator.as_bytes());
-    preimage.extend_from_slice(state_root);
-    preimage.extend_from_slice(tx_root);
+    preimage.extend_from_slice(&header.height.to_le_bytes());
+    preimage.extend_from_slice(header.parent_hash.as_bytes());
+    preimage.extend_from_slice(&header.timestamp.to_le_bytes());
+    preimage.extend_from_slice(header.validator.as_bytes());
+    preimage.extend_from_slice(header.state_root.as_bytes());
+    preimage.extend_from_slice(header.tx_root.as_bytes());
+    preimage.extend_from_slice(&header.fees.to_le_bytes());
+    for commit in &header.last_commit {
+        preimage.extend_from_slice(commit.validator.as_bytes());
+        preimage.extend_from_slice(&commit.round.to_le_bytes());
+        preimage.extend_from_slice(commit.signature.as_bytes());
+    }
+    if header.version != LEGACY_HEADER_VERSION {
+        let area = header.extensions.encode();
+        preimage.push(header.version);
+        preimage.extend_from_slice(&(area.len() as u32).to_le_bytes());
+        preimage.extend_from_slice(&area);
+    }
     blake3_hash(&preimage)
 }
 
@@ -311,29 +647,17 @@ pub fn compute_merkle_root(transactions: &[Transaction]) -> [u8; 32] {
     }
 
     // Compute leaf hashes.
-    let mut hashes: Vec<[u8; 32]> = transactions
-        .iter()
-        .map(|tx| {
-            let serialized = serde_json::to_vec(tx).unwrap_or_default();
-            blake3_hash(&serialized)
-        })
-        .collect();
+    let mut hashes: Vec<[u8; 32]> = transactions.iter().map(leaf_hash).collect();
 
     // Build the tree bottom-up.
     while hashes.len() > 1 {
         let mut next_level = Vec::with_capacity(hashes.len().div_ceil(2));
         for chunk in hashes.chunks(2) {
             if chunk.len() == 2 {
-                let mut combined = Vec::with_capacity(64);
-                combined.extend_from_slice(&chunk[0]);
-                combined.extend_from_slice(&chunk[1]);
-                next_level.push(blake3_hash(&combined));
+                next_level.push(node_hash(&chunk[0], &chunk[1]));
             } else {
-                // Odd element — promote it unchanged (duplicate-left strategy).
-                let mut combined = Vec::with_capacity(64);
-                combined.extend_from_slice(&chunk[0]);
-                combined.extend_from_slice(&chunk[0]);
-                next_level.push(blake3_hash(&combined));
+                // Odd element — paired with itself (duplicate-left strategy).
+                next_level.push(node_hash(&chunk[0], &chunk[0]));
             }
         }
         hashes = next_level;
@@ -342,6 +666,93 @@ pub fn compute_merkle_root(transactions: &[Transaction]) -> [u8; 32] {
     hashes[0]
 }
 
+/// Leaf of the transaction tree: BLAKE3 of the canonical JSON.
+fn leaf_hash(tx: &Transaction) -> [u8; 32] {
+    let serialized = serde_json::to_vec(tx).unwrap_or_default();
+    blake3_hash(&serialized)
+}
+
+/// Internal node of the transaction tree: `BLAKE3(left || right)`.
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut combined = [0u8; 64];
+    combined[..32].copy_from_slice(left);
+    combined[32..].copy_from_slice(right);
+    blake3_hash(&combined)
+}
+
+/// Builds the transaction Merkle root one transaction at a time.
+///
+/// Produces the same root as [`compute_merkle_root`] over the transactions
+/// pushed so far. It keeps one pending subtree per level — the roots of
+/// the complete subtrees not yet paired, like the carries of a binary
+/// counter — so a push hashes its leaf plus one node per level it
+/// completes, and [`root`](Self::root) folds the pending subtrees in
+/// `O(log n)`.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleBuilder {
+    /// `pending[k]` is the root of a complete subtree of `2^k` leaves
+    /// waiting for its right sibling.
+    pending: Vec<Option<[u8; 32]>>,
+    /// Number of leaves pushed.
+    len: usize,
+}
+
+impl MerkleBuilder {
+    /// An empty builder, whose root is all zeros.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `tx` as the next leaf.
+    pub fn push(&mut self, tx: &Transaction) {
+        self.len += 1;
+        let mut node = leaf_hash(tx);
+        for slot in self.pending.iter_mut() {
+            match slot.take() {
+                Some(left) => node = node_hash(&left, &node),
+                None => {
+                    *slot = Some(node);
+                    return;
+                }
+            }
+        }
+        self.pending.push(Some(node));
+    }
+
+    /// Number of transactions pushed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The root over the transactions pushed so far. The builder stays
+    /// usable, so a producer can read the root of each candidate block.
+    pub fn root(&self) -> [u8; 32] {
+        if self.len == 0 {
+            return [0u8; 32];
+        }
+        // Walk up the levels carrying the last, incomplete node of each
+        // (if any). A level ending in a single node that is not the root
+        // pairs it with itself, as `compute_merkle_root` does.
+        let mut carry: Option<[u8; 32]> = None;
+        for (level, pending) in self.pending.iter().enumerate() {
+            let nodes_at_level = (self.len - 1) / (1 << level) + 1;
+            let last = match (pending.as_ref(), carry.as_ref()) {
+                (Some(left), Some(right)) => node_hash(left, right),
+                (Some(node), None) | (None, Some(node)) if nodes_at_level == 1 => return *node,
+                (Some(node), None) | (None, Some(node)) => node_hash(node, node),
+                (None, None) => continue,
+            };
+            carry = Some(last);
+        }
+        carry.expect("a non-empty tree has a root")
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -360,7 +771,7 @@ mod tests {
             .fee(100)
             .nonce(id_byte as u64)
             .timestamp(1_000_000)
-            .build()
+            .build_unchecked()
     }
 
     #[test]
@@ -370,7 +781,7 @@ mod tests {
         assert_eq!(genesis.header.parent_hash, [0u8; 32]);
         assert_eq!(genesis.header.timestamp, 0);
         assert!(genesis.transactions.is_empty());
-        assert!(genesis.header.signature.is_empty());
+        assert!(genesis.header.signature.is_none());
     }
 
     #[test]
@@ -405,6 +816,25 @@ mod tests {
         assert!(block.verify().is_ok());
     }
 
+    #[test]
+    fn fixed_timestamp_block_is_reproducible() {
+        let genesis = Block::genesis();
+        let build = || {
+            Block::new_at(
+                &genesis,
+                vec![make_test_tx(1)],
+                "nova:validator".to_string(),
+                [7u8; 32],
+                1_700_000_000_000,
+            )
+        };
+
+        let (a, b) = (build(), build());
+        assert_eq!(a.header.timestamp, 1_700_000_000_000);
+        assert_eq!(a.header.hash, b.header.hash);
+        assert!(a.verify().is_ok());
+    }
+
     #[test]
     fn tampered_block_fails_verification() {
         let genesis = Block::genesis();
@@ -429,6 +859,110 @@ mod tests {
         assert!(block.verify().is_err());
     }
 
+    #[test]
+    fn last_commit_is_hashed_and_verified() {
+        use crate::crypto::keys::NovaKeypair;
+
+        let genesis = Block::genesis();
+        let b1 = Block::new(&genesis, vec![], "nova:v1".to_string(), [1u8; 32]);
+        let plain = Block::new_at(&b1, vec![], "nova:v2".to_string(), [2u8; 32], 5_000);
+
+        let voter = NovaKeypair::generate();
+        let mut message = b1.header.hash.as_bytes().to_vec();
+        message.extend_from_slice(&3u64.to_le_bytes());
+        let commit = CommitSignature {
+            validator: voter.public_key().to_hex(),
+            round: 3,
+            signature: SignatureBytes::from(&voter.sign(&message)),
+        };
+
+        let b2 = plain.clone().with_last_commit(vec![commit.clone()]);
+        assert_ne!(b2.header.hash, plain.header.hash);
+        assert!(b2.verify().is_ok());
+
+        // Same signature claimed for another round.
+        let forged = plain.with_last_commit(vec![CommitSignature { round: 4, ..commit }]);
+        assert!(forged.verify().is_err());
+    }
+
+    #[test]
+    fn fees_are_covered_by_the_hash() {
+        let genesis = Block::genesis();
+        let plain = Block::new_at(&genesis, vec![], "nova:v1".to_string(), [1u8; 32], 5_000);
+        assert_eq!(plain.header.fees, 0);
+
+        let block = plain.clone().with_fees(300);
+        assert_eq!(block.header.fees, 300);
+        assert_ne!(block.header.hash, plain.header.hash);
+        assert!(block.verify().is_ok());
+
+        let mut tampered = block;
+        tampered.header.fees = 0;
+        assert!(tampered.verify().is_err());
+    }
+
+    #[test]
+    fn legacy_headers_hash_as_before_versioning() {
+        let genesis = Block::genesis();
+        let block = Block::new_at(&genesis, vec![], "nova:v1".to_string(), [1u8; 32], 5_000);
+        assert_eq!(block.header.version, LEGACY_HEADER_VERSION);
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&1u64.to_le_bytes());
+        preimage.extend_from_slice(genesis.header.hash.as_bytes());
+        preimage.extend_from_slice(&5_000u64.to_le_bytes());
+        preimage.extend_from_slice(b"nova:v1");
+        preimage.extend_from_slice(&[1u8; 32]);
+        preimage.extend_from_slice(&[0u8; 32]);
+        preimage.extend_from_slice(&0u64.to_le_bytes());
+        assert_eq!(block.header.hash, blake3_hash(&preimage));
+    }
+
+    #[test]
+    fn extension_area_is_hashed_from_version_one() {
+        let genesis = Block::genesis();
+        let legacy = Block::new_at(&genesis, vec![], "nova:v1".to_string(), [1u8; 32], 5_000);
+
+        let v1 = legacy.clone().with_extensions(1, HeaderExtensions::new());
+        assert_ne!(v1.header.hash, legacy.header.hash);
+        assert!(v1.verify().is_ok());
+
+        // The encoding does not depend on the order fields were set in.
+        let mut a = HeaderExtensions::new();
+        a.set(&BaseFee(7));
+        a.set(&ReceiptsRoot([9u8; 32]));
+        let mut b = HeaderExtensions::new();
+        b.set(&ReceiptsRoot([9u8; 32]));
+        b.set(&BaseFee(7));
+        assert_eq!(a.encode(), b.encode());
+        assert_eq!(a.get::<BaseFee>(), Some(BaseFee(7)));
+        assert_eq!(a.get::<NullifierRoot>(), None);
+
+        let extended = legacy.with_extensions(1, a);
+        let mut tampered = extended.clone();
+        tampered.header.extensions.set(&BaseFee(8));
+        assert_ne!(tampered.compute_hash(), extended.header.hash);
+    }
+
+    #[test]
+    fn inactive_extensions_and_versions_fail_verification() {
+        let genesis = Block::genesis();
+        let block = Block::new_at(&genesis, vec![], "nova:v1".to_string(), [1u8; 32], 5_000);
+        let mut fields = HeaderExtensions::new();
+        fields.set(&ReceiptsRoot([9u8; 32]));
+
+        // Not active before its version, and never on a legacy header.
+        assert!(block
+            .clone()
+            .with_extensions(1, fields.clone())
+            .verify()
+            .is_err());
+        assert!(block.clone().with_extensions(0, fields).verify().is_err());
+
+        let future = block.with_extensions(MAX_HEADER_VERSION + 1, HeaderExtensions::new());
+        assert!(future.verify().unwrap_err().contains("header version"));
+    }
+
     #[test]
     fn merkle_root_empty() {
         assert_eq!(compute_merkle_root(&[]), [0u8; 32]);
@@ -460,6 +994,36 @@ mod tests {
         assert_ne!(root_12, root_21, "Merkle root must be order-sensitive");
     }
 
+    #[test]
+    fn merkle_builder_matches_full_recomputation() {
+        let txs: Vec<Transaction> = (1..=33).map(make_test_tx).collect();
+        let mut builder = MerkleBuilder::new();
+        assert!(builder.is_empty());
+        assert_eq!(builder.root(), compute_merkle_root(&[]));
+
+        for (i, tx) in txs.iter().enumerate() {
+            builder.push(tx);
+            assert_eq!(builder.len(), i + 1);
+            assert_eq!(
+                builder.root(),
+                compute_merkle_root(&txs[..=i]),
+                "{} transactions",
+                i + 1
+            );
+        }
+
+        let genesis = Block::genesis();
+        let block = Block::with_tx_root(
+            &genesis,
+            txs,
+            builder.root(),
+            "nova:validator".to_string(),
+            [3u8; 32],
+            5_000,
+        );
+        assert!(block.verify().is_ok());
+    }
+
     #[test]
     fn block_chain_of_three() {
         let b0 = Block::genesis();
@@ -482,4 +1046,36 @@ mod tests {
         let recovered: Block = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(genesis, recovered);
     }
+
+    #[test]
+    fn header_hashes_are_hex_in_json_and_raw_in_bincode() {
+        let genesis = Block::genesis();
+
+        let json: serde_json::Value = serde_json::to_value(&genesis.header).unwrap();
+        assert_eq!(json["hash"], format!("0x{}", genesis.header.hash_hex()));
+        assert_eq!(json["parent_hash"], format!("0x{}", "00".repeat(32)));
+        assert!(json["signature"].is_null());
+
+        let bin = bincode::serialize(&genesis).unwrap();
+        let recovered: Block = bincode::deserialize(&bin).unwrap();
+        assert_eq!(genesis, recovered);
+    }
+
+    #[test]
+    fn header_extensions_roundtrip_as_hex() {
+        let mut fields = HeaderExtensions::new();
+        fields.set(&BaseFee(7));
+        let block = Block::genesis().with_extensions(1, fields);
+
+        let json: serde_json::Value = serde_json::to_value(&block.header).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["extensions"]["2"], "0x0700000000000000");
+        let recovered: Block = serde_json::from_value(serde_json::to_value(&block).unwrap())
+            .expect("deserialize json");
+        assert_eq!(recovered, block);
+
+        let bin = bincode::serialize(&block).unwrap();
+        let recovered: Block = bincode::deserialize(&bin).unwrap();
+        assert_eq!(recovered, block);
+    }
 }
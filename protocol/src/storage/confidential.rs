@@ -0,0 +1,233 @@
+//! # Confidential Transfers — Balances Held as Commitments
+//!
+//! An account can hold NOVA as a Pedersen commitment instead of a plaintext
+//! balance. The commitment is kept in [`AccountState::balance_commitments`]
+//! under the native token's ID; nobody but the holder knows the value and
+//! blinding factor it opens to.
+//!
+//! A `ConfidentialTransfer` moves a hidden amount between two such
+//! balances. It carries:
+//!
+//! | Field               | Content                                            |
+//! |---------------------|----------------------------------------------------|
+//! | `amount_commitment` | `A = commit(a, r_a)`, the amount moved             |
+//! | `proof`             | Groth16 proof that `A` opens to at least `amount`  |
+//! | `zkp_proof`         | Groth16 proof that `S - A` opens to at least 0     |
+//! | `amount.value`      | public floor for `a`; 0 keeps the amount hidden    |
+//! | `fee`               | paid in plaintext NOVA, as for any transfer        |
+//!
+//! where `S` is the sender's committed balance before the transfer. Both
+//! proofs are checked with the chain's [`BalanceVerifier`]; the first
+//! rules out a "negative" amount that would wrap in the field, the second
+//! shows the sender can afford it. The sender's commitment becomes `S - A`
+//! and the receiver's `R + A` (the [`Commitment::zero`] when it has none),
+//! so the sender must hand `(a, r_a)` to the receiver off-chain for it to
+//! spend the funds later.
+//!
+//! Only the fee touches plaintext balances, which keeps the block's
+//! [`BalanceAudit`](super::fee::BalanceAudit) exact. Moving NOVA between a
+//! plaintext and a committed balance is not a transfer and is not handled
+//! here.
+
+use crate::transaction::builder::Transaction;
+use crate::transaction::confidential::verify_confidential_proof;
+use crate::transaction::types::Currency;
+use crate::vault::token::native_token_id;
+use crate::zkp::commitment::Commitment;
+use crate::zkp::prover::BalanceProof;
+use crate::zkp::verifier::BalanceVerifier;
+
+use super::state::{check_nonce, AccountState, StateError, StateTree};
+
+/// Key of the committed NOVA balance in
+/// [`AccountState::balance_commitments`].
+pub fn native_commitment_key() -> String {
+    native_token_id().to_hex()
+}
+
+/// The committed NOVA balance of `state`, if it has one.
+pub fn committed_balance(state: &AccountState) -> Result<Option<Commitment>, StateError> {
+    state
+        .balance_commitments
+        .get(&native_commitment_key())
+        .map(|bytes| {
+            Commitment::from_bytes(bytes)
+                .map_err(|e| StateError::Serialization(format!("balance commitment: {}", e)))
+        })
+        .transpose()
+}
+
+fn set_committed_balance(state: &mut AccountState, commitment: &Commitment) {
+    state
+        .balance_commitments
+        .insert(native_commitment_key(), commitment.to_bytes());
+}
+
+/// Execute a `ConfidentialTransfer` transaction (see the module docs).
+///
+/// Shared by the block producer and the sync engine so both paths apply
+/// identical state transitions. Without a `verifier` every confidential
+/// transfer is rejected: the chain cannot check the proofs.
+pub fn apply_confidential_transfer(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    verifier: Option<&BalanceVerifier>,
+) -> Result<(), StateError> {
+    let reject = |reason: &str| Err(StateError::ConfidentialRejected(reason.to_string()));
+
+    let Some(verifier) = verifier else {
+        return reject("no balance verifier configured");
+    };
+    if tx.amount.currency != Currency::NOVA {
+        return reject("only NOVA can be transferred confidentially");
+    }
+    if tx
+        .fee_payer
+        .as_deref()
+        .is_some_and(|payer| payer != tx.sender)
+    {
+        return reject("confidential transfers cannot be sponsored");
+    }
+    if tx.sender == tx.receiver {
+        return reject("sender and receiver are the same account");
+    }
+
+    check_nonce(tree, &tx.sender, tx.nonce)?;
+    let mut sender = tree.get(&tx.sender).unwrap_or_default();
+    if sender.frozen {
+        return Err(StateError::AccountFrozen(tx.sender.clone()));
+    }
+    let available = sender.available_balance();
+    if available < tx.fee {
+        return Err(StateError::InsufficientBalance {
+            have: available,
+            need: tx.fee,
+        });
+    }
+
+    // The amount commitment opens to at least the public floor.
+    match verify_confidential_proof(tx, verifier) {
+        Ok(true) => {}
+        Ok(false) => return reject("amount proof does not verify"),
+        Err(e) => return reject(&format!("amount proof: {}", e)),
+    }
+    let amount = tx
+        .amount_commitment
+        .as_deref()
+        .map(Commitment::from_bytes)
+        .transpose()
+        .map_err(|e| StateError::Serialization(format!("amount commitment: {}", e)))?
+        .unwrap_or_else(Commitment::zero);
+
+    // What the sender keeps opens to at least zero.
+    let Some(solvency) = tx.zkp_proof.as_deref() else {
+        return reject("missing solvency proof");
+    };
+    let solvency = match BalanceProof::from_bytes(solvency) {
+        Ok(proof) => proof,
+        Err(e) => return reject(&format!("solvency proof: {}", e)),
+    };
+    let remaining = &committed_balance(&sender)?.unwrap_or_else(Commitment::zero) - &amount;
+    match verifier.verify(&solvency, &remaining, 0, verifier.pedersen_params()) {
+        Ok(true) => {}
+        Ok(false) => return reject("solvency proof does not verify"),
+        Err(e) => return reject(&format!("solvency proof: {}", e)),
+    }
+
+    sender.balance -= tx.fee;
+    sender.nonce += 1;
+    set_committed_balance(&mut sender, &remaining);
+    tree.put(&tx.sender, &sender);
+
+    let mut receiver = tree.get(&tx.receiver).unwrap_or_default();
+    let credited = &committed_balance(&receiver)?.unwrap_or_else(Commitment::zero) + &amount;
+    set_committed_balance(&mut receiver, &credited);
+    tree.put(&tx.receiver, &receiver);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, TransactionType};
+    use crate::zkp::commitment::{commit, verify_commitment};
+    use crate::zkp::prover::BalanceProver;
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    fn temp_tree() -> StateTree {
+        StateTree::new(NovaDB::open_temporary().unwrap())
+    }
+
+    fn transfer(amount_floor: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::ConfidentialTransfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(amount_floor, Currency::NOVA))
+            .fee(10)
+            .nonce(1)
+            .timestamp(1_700_000_000_000)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn rejected_without_a_verifier() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(100));
+
+        let err = apply_confidential_transfer(&mut tree, &transfer(0), None).unwrap_err();
+        assert!(matches!(err, StateError::ConfidentialRejected(_)));
+        assert_eq!(tree.get("nova1alice").unwrap().nonce, 0);
+    }
+
+    #[test]
+    #[ignore] // Groth16 setup and two proofs take several seconds.
+    fn moves_a_hidden_amount_between_commitments() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (prover, verifier) = BalanceProver::setup(&mut rng);
+        let params = prover.pedersen_params().clone();
+
+        // Alice holds 1 000 committed NOVA and 100 plaintext for fees.
+        let r_s = Fr::rand(&mut rng);
+        let mut alice = AccountState::with_balance(100);
+        set_committed_balance(&mut alice, &commit(&params, 1_000, r_s));
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &alice);
+
+        // She sends 300, proving it is at least 0 and that 700 remains.
+        let r_a = Fr::rand(&mut rng);
+        let amount = commit(&params, 300, r_a);
+        let amount_proof = prover.prove(300, r_a, 0, &params, &amount).unwrap();
+        let remaining = &commit(&params, 1_000, r_s) - &amount;
+        let solvency = prover
+            .prove(700, r_s - r_a, 0, &params, &remaining)
+            .unwrap();
+        let mut tx = transfer(0)
+            .with_proof(amount_proof.to_bytes())
+            .with_commitment(amount.to_bytes());
+        tx.zkp_proof = Some(solvency.to_bytes());
+
+        apply_confidential_transfer(&mut tree, &tx, Some(&verifier)).unwrap();
+
+        let alice = tree.get("nova1alice").unwrap();
+        let bob = tree.get("nova1bob").unwrap();
+        assert_eq!((alice.balance, alice.nonce, bob.balance), (90, 1, 0));
+        let kept = committed_balance(&alice).unwrap().unwrap();
+        assert!(verify_commitment(&params, &kept, 700, r_s - r_a));
+        let received = committed_balance(&bob).unwrap().unwrap();
+        assert!(verify_commitment(&params, &received, 300, r_a));
+
+        // Replaying the same proofs against the new balance fails.
+        let mut replay = tx.clone();
+        replay.nonce = 2;
+        let err = apply_confidential_transfer(&mut tree, &replay, Some(&verifier)).unwrap_err();
+        assert!(matches!(err, StateError::ConfidentialRejected(_)));
+    }
+}
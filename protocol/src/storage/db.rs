@@ -9,13 +9,14 @@
 //! in RocksDB or tables in SQL). Each tree is an independent B+ tree
 //! with its own keyspace:
 //!
-//! | Tree           | Key                 | Value                    |
-//! |----------------|---------------------|--------------------------|
-//! | `blocks`       | `height` (8B BE)    | `bincode(Block)`         |
-//! | `block_hashes` | `hash` (32B)        | `height` (8B BE)         |
-//! | `transactions` | `tx_id` (hex bytes) | `bincode(Transaction)`   |
-//! | `accounts`     | `address` (UTF-8)   | `bincode(AccountState)`  |
-//! | `metadata`     | key (UTF-8)         | value (bytes)            |
+//! | Tree           | Key                 | Value                         |
+//! |----------------|---------------------|-------------------------------|
+//! | `blocks`       | `height` (8B BE)    | `bincode(Block)`              |
+//! | `block_hashes` | `hash` (32B)        | `height` (8B BE)              |
+//! | `transactions` | `tx_id` (hex bytes) | `bincode(Transaction)`        |
+//! | `receipts`     | `tx_id` (hex bytes) | `bincode(TransactionReceipt)` |
+//! | `accounts`     | `address` (UTF-8)   | `bincode(AccountState)`       |
+//! | `metadata`     | key (UTF-8)         | value (bytes)                 |
 //!
 //! Block heights are stored as big-endian u64 so that sled's lexicographic
 //! ordering matches numeric ordering — this makes range scans over blocks
@@ -24,15 +25,28 @@
 //! ## Atomicity
 //!
 //! When persisting a new block, we write the block, all its transactions,
-//! and the updated height in a single atomic `Batch`. Either everything
-//! lands on disk or nothing does — no partial writes, no corruption.
+//! and the updated height in a single sled transaction across their trees.
+//! Either everything lands on disk or nothing does — no partial writes, no
+//! corruption.
+//!
+//! ## Fault Injection
+//!
+//! With the `chaos` feature, `NovaDB::with_faults` attaches a
+//! `FaultInjector` that typed reads and writes consult first (see the
+//! `chaos` module).
 
-use sled::{Batch, Db, Tree};
+use sled::transaction::{ConflictableTransactionError, TransactionError};
+use sled::{Batch, Db, Transactional, Tree};
 use std::path::Path;
+#[cfg(feature = "chaos")]
+use std::sync::Arc;
+
+#[cfg(feature = "chaos")]
+use crate::chaos::{Fault, FaultInjector};
 
 use super::block::Block;
 use super::state::AccountState;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionReceipt};
 
 // ---------------------------------------------------------------------------
 // Error Type
@@ -40,6 +54,7 @@ use crate::transaction::Transaction;
 
 /// Errors that can occur during database operations.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum DbError {
     #[error("sled error: {0}")]
     Sled(#[from] sled::Error),
@@ -49,6 +64,11 @@ pub enum DbError {
 
     #[error("key not found: {0}")]
     NotFound(String),
+
+    /// A fault injected into `op` by the `chaos` feature's fault injector.
+    /// Never returned without it.
+    #[error("injected fault in {0}")]
+    Injected(&'static str),
 }
 
 pub type DbResult<T> = Result<T, DbError>;
@@ -85,10 +105,15 @@ pub struct NovaDB {
     block_hashes: Tree,
     /// Transactions indexed by hex-encoded tx ID.
     transactions: Tree,
+    /// Receipts of committed transactions, by hex-encoded tx ID.
+    receipts: Tree,
     /// Account states indexed by NOVA address (UTF-8).
     accounts: Tree,
     /// Arbitrary key-value metadata (latest height, config, etc.).
     metadata: Tree,
+    /// Decides which operations fail, when testing under faults.
+    #[cfg(feature = "chaos")]
+    faults: Option<Arc<FaultInjector>>,
 }
 
 impl NovaDB {
@@ -117,6 +142,7 @@ impl NovaDB {
         let blocks = db.open_tree("blocks")?;
         let block_hashes = db.open_tree("block_hashes")?;
         let transactions = db.open_tree("transactions")?;
+        let receipts = db.open_tree("receipts")?;
         let accounts = db.open_tree("accounts")?;
         let metadata = db.open_tree("metadata")?;
 
@@ -125,11 +151,40 @@ impl NovaDB {
             blocks,
             block_hashes,
             transactions,
+            receipts,
             accounts,
             metadata,
+            #[cfg(feature = "chaos")]
+            faults: None,
         })
     }
 
+    /// Route typed reads and writes through `faults` first. Untyped access
+    /// ([`open_tree`](Self::open_tree), images) is left alone.
+    #[cfg(feature = "chaos")]
+    pub fn with_faults(mut self, faults: Arc<FaultInjector>) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /// Consult the fault injector before `op`. Errors if the operation
+    /// should fail; returns `true` if a `tearable` write should stop
+    /// partway. A torn read is a failed read.
+    #[cfg(feature = "chaos")]
+    fn inject(&self, op: &'static str, tearable: bool) -> DbResult<bool> {
+        match self.faults.as_ref().and_then(|faults| faults.next_fault()) {
+            None => Ok(false),
+            Some(Fault::Partial) if tearable => Ok(true),
+            Some(_) => Err(DbError::Injected(op)),
+        }
+    }
+
+    #[cfg(not(feature = "chaos"))]
+    #[inline(always)]
+    fn inject(&self, _op: &'static str, _tearable: bool) -> DbResult<bool> {
+        Ok(false)
+    }
+
     /// Open a named sled tree from the underlying database.
     ///
     /// Used by higher-level data structures (e.g., Sparse Merkle Tree) that
@@ -149,31 +204,45 @@ impl NovaDB {
     /// 3. Each transaction into the `transactions` tree (keyed by tx ID).
     /// 4. An updated `latest_block_height` in `metadata`.
     ///
-    /// All writes are batched into a single atomic operation per tree.
+    /// All four writes happen in one transaction: if any fails, none lands.
     pub fn put_block(&self, block: &Block) -> DbResult<()> {
+        let torn = self.inject("put_block", true)?;
         let height_key = block.header.height.to_be_bytes();
         let block_bytes =
             bincode::serialize(block).map_err(|e| DbError::Serialization(e.to_string()))?;
+        let tx_bytes = block
+            .transactions
+            .iter()
+            .map(|tx| {
+                bincode::serialize(tx)
+                    .map(|bytes| (tx.id.as_bytes(), bytes))
+                    .map_err(|e| DbError::Serialization(e.to_string()))
+            })
+            .collect::<DbResult<Vec<_>>>()?;
 
-        // Batch writes to the blocks tree.
-        let mut block_batch = Batch::default();
-        block_batch.insert(&height_key, block_bytes);
-        self.blocks.apply_batch(block_batch)?;
-
-        // Index block hash -> height.
-        self.block_hashes.insert(block.header.hash, &height_key)?;
-
-        // Persist each transaction.
-        let mut tx_batch = Batch::default();
-        for tx in &block.transactions {
-            let tx_bytes =
-                bincode::serialize(tx).map_err(|e| DbError::Serialization(e.to_string()))?;
-            tx_batch.insert(tx.id.as_bytes(), tx_bytes);
-        }
-        self.transactions.apply_batch(tx_batch)?;
-
-        // Update latest height.
-        self.metadata.insert(META_LATEST_HEIGHT, &height_key)?;
+        let trees = (
+            &self.blocks,
+            &self.block_hashes,
+            &self.transactions,
+            &self.metadata,
+        );
+        trees
+            .transaction(|(blocks, block_hashes, transactions, metadata)| {
+                blocks.insert(&height_key, block_bytes.as_slice())?;
+                block_hashes.insert(block.header.hash.as_ref(), &height_key)?;
+                if torn {
+                    return Err(ConflictableTransactionError::Abort(()));
+                }
+                for (id, bytes) in &tx_bytes {
+                    transactions.insert(*id, bytes.as_slice())?;
+                }
+                metadata.insert(META_LATEST_HEIGHT, &height_key)?;
+                Ok(())
+            })
+            .map_err(|e| match e {
+                TransactionError::Abort(()) => DbError::Injected("put_block"),
+                TransactionError::Storage(e) => DbError::Sled(e),
+            })?;
 
         // Flush to ensure durability.
         self.db.flush()?;
@@ -185,6 +254,7 @@ impl NovaDB {
     ///
     /// Returns `None` if no block exists at the given height.
     pub fn get_block(&self, height: u64) -> DbResult<Option<Block>> {
+        self.inject("get_block", false)?;
         let key = height.to_be_bytes();
         match self.blocks.get(key)? {
             Some(bytes) => {
@@ -201,6 +271,7 @@ impl NovaDB {
     /// Performs a two-step lookup: hash -> height (from `block_hashes`),
     /// then height -> block (from `blocks`).
     pub fn get_block_by_hash(&self, hash: &[u8; 32]) -> DbResult<Option<Block>> {
+        self.inject("get_block_by_hash", false)?;
         match self.block_hashes.get(hash)? {
             Some(height_bytes) => {
                 let height = u64::from_be_bytes(
@@ -220,6 +291,7 @@ impl NovaDB {
     /// Returns blocks in ascending height order. Stops at the first gap
     /// (missing height) within the range.
     pub fn get_block_range(&self, start: u64, end: u64) -> DbResult<Vec<Block>> {
+        self.inject("get_block_range", false)?;
         let start_key = start.to_be_bytes();
         let end_key = end.to_be_bytes();
 
@@ -234,6 +306,47 @@ impl NovaDB {
         Ok(blocks)
     }
 
+    /// Remove every block above `height`, returning them in ascending order.
+    ///
+    /// This is the storage half of a chain reorganisation: the abandoned
+    /// blocks are dropped from the height and hash indices, their
+    /// transactions and receipts are un-indexed (so lookups no longer
+    /// report them as confirmed), and the latest height is reset to
+    /// `height`. The caller decides what to do with the returned
+    /// transactions — typically
+    /// [`Mempool::readmit_reorged`](crate::network::mempool::Mempool::readmit_reorged).
+    ///
+    /// Rolling back to or above the current tip is a no-op.
+    pub fn rollback_to(&self, height: u64) -> DbResult<Vec<Block>> {
+        let tip = match self.get_latest_block_height()? {
+            Some(tip) if tip > height => tip,
+            _ => return Ok(Vec::new()),
+        };
+        let abandoned = self.get_block_range(height + 1, tip)?;
+
+        let mut block_batch = Batch::default();
+        let mut hash_batch = Batch::default();
+        let mut tx_batch = Batch::default();
+        let mut receipt_batch = Batch::default();
+        for block in &abandoned {
+            block_batch.remove(&block.header.height.to_be_bytes());
+            hash_batch.remove(block.header.hash.as_ref());
+            for tx in &block.transactions {
+                tx_batch.remove(tx.id.as_bytes());
+                receipt_batch.remove(tx.id.as_bytes());
+            }
+        }
+        self.blocks.apply_batch(block_batch)?;
+        self.block_hashes.apply_batch(hash_batch)?;
+        self.transactions.apply_batch(tx_batch)?;
+        self.receipts.apply_batch(receipt_batch)?;
+
+        self.set_latest_block_height(height)?;
+        self.db.flush()?;
+
+        Ok(abandoned)
+    }
+
     // -- Transaction operations ---------------------------------------------
 
     /// Persist a single transaction.
@@ -241,6 +354,7 @@ impl NovaDB {
     /// Typically used for mempool staging. Block-included transactions
     /// are written atomically via `put_block`.
     pub fn put_transaction(&self, tx: &Transaction) -> DbResult<()> {
+        self.inject("put_transaction", false)?;
         let tx_bytes = bincode::serialize(tx).map_err(|e| DbError::Serialization(e.to_string()))?;
         self.transactions.insert(tx.id.as_bytes(), tx_bytes)?;
         Ok(())
@@ -248,6 +362,7 @@ impl NovaDB {
 
     /// Retrieve a transaction by its hex-encoded ID.
     pub fn get_transaction(&self, id: &str) -> DbResult<Option<Transaction>> {
+        self.inject("get_transaction", false)?;
         match self.transactions.get(id.as_bytes())? {
             Some(bytes) => {
                 let tx: Transaction = bincode::deserialize(&bytes)
@@ -258,10 +373,46 @@ impl NovaDB {
         }
     }
 
+    // -- Receipt operations -------------------------------------------------
+
+    /// Persist the receipts of a committed block in one atomic batch.
+    pub fn put_receipts(&self, receipts: &[TransactionReceipt]) -> DbResult<()> {
+        let torn = self.inject("put_receipts", true)?;
+        let stored = if torn {
+            receipts.len() / 2
+        } else {
+            receipts.len()
+        };
+        let mut batch = Batch::default();
+        for receipt in &receipts[..stored] {
+            batch.insert(receipt.tx_id.as_bytes(), receipt.to_binary());
+        }
+        self.receipts.apply_batch(batch)?;
+        self.db.flush()?;
+        if torn {
+            return Err(DbError::Injected("put_receipts"));
+        }
+        Ok(())
+    }
+
+    /// Retrieve the receipt of a committed transaction by its hex-encoded ID.
+    pub fn get_receipt(&self, tx_id: &str) -> DbResult<Option<TransactionReceipt>> {
+        self.inject("get_receipt", false)?;
+        match self.receipts.get(tx_id.as_bytes())? {
+            Some(bytes) => {
+                let receipt = TransactionReceipt::from_binary(&bytes)
+                    .map_err(|e| DbError::Serialization(e.to_string()))?;
+                Ok(Some(receipt))
+            }
+            None => Ok(None),
+        }
+    }
+
     // -- Account operations -------------------------------------------------
 
     /// Persist an account state for the given address.
     pub fn put_account(&self, address: &str, state: &AccountState) -> DbResult<()> {
+        self.inject("put_account", false)?;
         let bytes = bincode::serialize(state).map_err(|e| DbError::Serialization(e.to_string()))?;
         self.accounts.insert(address.as_bytes(), bytes)?;
         Ok(())
@@ -271,6 +422,7 @@ impl NovaDB {
     ///
     /// Returns `None` if the address has never been seen on-chain.
     pub fn get_account(&self, address: &str) -> DbResult<Option<AccountState>> {
+        self.inject("get_account", false)?;
         match self.accounts.get(address.as_bytes())? {
             Some(bytes) => {
                 let state: AccountState = bincode::deserialize(&bytes)
@@ -287,6 +439,7 @@ impl NovaDB {
     ///
     /// Returns `None` if the database is empty (no blocks persisted yet).
     pub fn get_latest_block_height(&self) -> DbResult<Option<u64>> {
+        self.inject("get_latest_block_height", false)?;
         match self.metadata.get(META_LATEST_HEIGHT)? {
             Some(bytes) => {
                 let height = u64::from_be_bytes(
@@ -306,6 +459,7 @@ impl NovaDB {
     /// Normally this is updated automatically by `put_block`, but this
     /// method is available for bootstrapping and recovery scenarios.
     pub fn set_latest_block_height(&self, height: u64) -> DbResult<()> {
+        self.inject("set_latest_block_height", false)?;
         self.metadata
             .insert(META_LATEST_HEIGHT, &height.to_be_bytes())?;
         Ok(())
@@ -336,6 +490,79 @@ impl NovaDB {
         self.db.flush()?;
         Ok(())
     }
+
+    /// Bytes the database currently occupies on its storage device,
+    /// including log segments not yet compacted. Flush first for a figure
+    /// that covers every write so far.
+    pub fn size_on_disk(&self) -> DbResult<u64> {
+        Ok(self.db.size_on_disk()?)
+    }
+
+    // -- Images -------------------------------------------------------------
+
+    /// Copy every tree except those named in `exclude` into memory.
+    ///
+    /// Meant for small dev and test chains: the whole database is held in
+    /// the returned [`DbImage`]. The caller must keep writers out while
+    /// capturing if it needs a consistent image.
+    pub fn capture_image(&self, exclude: &[&str]) -> DbResult<DbImage> {
+        let mut trees = Vec::new();
+        for name in self.db.tree_names() {
+            if exclude.iter().any(|e| e.as_bytes() == name.as_ref()) {
+                continue;
+            }
+            let tree = self.db.open_tree(&name)?;
+            let entries = tree.iter().collect::<Result<Vec<_>, _>>()?;
+            trees.push((name.to_vec(), entries));
+        }
+        Ok(DbImage {
+            trees,
+            exclude: exclude.iter().map(|e| e.to_string()).collect(),
+        })
+    }
+
+    /// Reset the database to `image`: every tree the image covers is
+    /// replaced with its captured contents, and trees created since the
+    /// capture are emptied. Excluded trees are left alone.
+    pub fn restore_image(&self, image: &DbImage) -> DbResult<()> {
+        for name in self.db.tree_names() {
+            if image.exclude.iter().any(|e| e.as_bytes() == name.as_ref()) {
+                continue;
+            }
+            self.db.open_tree(&name)?.clear()?;
+        }
+        for (name, entries) in &image.trees {
+            let tree = self.db.open_tree(name)?;
+            let mut batch = Batch::default();
+            for (key, value) in entries {
+                batch.insert(key, value);
+            }
+            tree.apply_batch(batch)?;
+        }
+        self.db.flush()?;
+        Ok(())
+    }
+}
+
+/// In-memory copy of a database's trees, from [`NovaDB::capture_image`].
+#[derive(Debug, Clone)]
+pub struct DbImage {
+    /// Tree name and its entries, in key order.
+    trees: Vec<(Vec<u8>, Vec<(sled::IVec, sled::IVec)>)>,
+    /// Trees the image deliberately leaves out.
+    exclude: Vec<String>,
+}
+
+impl DbImage {
+    /// Number of key-value entries captured.
+    pub fn len(&self) -> usize {
+        self.trees.iter().map(|(_, entries)| entries.len()).sum()
+    }
+
+    /// Whether the image holds no entries at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -359,7 +586,7 @@ mod tests {
             .fee(10)
             .nonce(id_byte as u64)
             .timestamp(1_000_000)
-            .build()
+            .build_unchecked()
     }
 
     fn make_block_chain(count: usize) -> Vec<Block> {
@@ -704,6 +931,55 @@ mod tests {
         db.flush().expect("flush should succeed");
     }
 
+    #[test]
+    fn size_on_disk_grows_with_writes() {
+        let db = NovaDB::open_temporary().unwrap();
+        db.flush().unwrap();
+        let before = db.size_on_disk().unwrap();
+
+        for block in make_block_chain(20) {
+            db.put_block(&block).unwrap();
+        }
+        db.flush().unwrap();
+        assert!(db.size_on_disk().unwrap() > before);
+    }
+
+    #[test]
+    fn restore_image_resets_captured_trees_only() {
+        let db = NovaDB::open_temporary().unwrap();
+        let chain = make_block_chain(3);
+        db.put_block(&chain[0]).unwrap();
+        db.open_tree("kept")
+            .unwrap()
+            .insert(b"k", b"before")
+            .unwrap();
+
+        let image = db.capture_image(&["kept"]).unwrap();
+        assert!(!image.is_empty());
+
+        db.put_block(&chain[1]).unwrap();
+        db.put_block(&chain[2]).unwrap();
+        db.open_tree("kept")
+            .unwrap()
+            .insert(b"k", b"after")
+            .unwrap();
+        db.open_tree("scratch").unwrap().insert(b"x", b"1").unwrap();
+
+        db.restore_image(&image).unwrap();
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(0));
+        assert!(db.get_block(1).unwrap().is_none());
+        assert!(db
+            .get_transaction(&chain[1].transactions[0].id)
+            .unwrap()
+            .is_none());
+        assert!(db.open_tree("scratch").unwrap().is_empty());
+        // Excluded trees keep their current contents.
+        assert_eq!(
+            db.open_tree("kept").unwrap().get(b"k").unwrap().as_deref(),
+            Some(&b"after"[..])
+        );
+    }
+
     #[test]
     fn overwrite_block_at_same_height() {
         let db = NovaDB::open_temporary().unwrap();
@@ -734,6 +1010,59 @@ mod tests {
         assert_eq!(retrieved_b.header.state_root, [0xBB; 32]);
     }
 
+    #[test]
+    fn rollback_removes_abandoned_blocks_and_their_transactions() {
+        let db = NovaDB::open_temporary().unwrap();
+        let chain = make_block_chain(3);
+        for block in &chain {
+            db.put_block(block).unwrap();
+        }
+
+        let abandoned = db.rollback_to(0).unwrap();
+        assert_eq!(abandoned, chain[1..].to_vec());
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(0));
+        assert!(db.get_block(1).unwrap().is_none());
+        assert!(db
+            .get_block_by_hash(&chain[2].header.hash)
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get_transaction(&chain[1].transactions[0].id)
+            .unwrap()
+            .is_none());
+        assert!(db.get_block(0).unwrap().is_some());
+
+        // Rolling back at or above the tip is a no-op.
+        assert!(db.rollback_to(5).unwrap().is_empty());
+        assert_eq!(db.get_latest_block_height().unwrap(), Some(0));
+    }
+
+    #[test]
+    fn receipts_roundtrip_and_roll_back() {
+        use crate::transaction::receipt::BlockInfo;
+        use crate::transaction::TransactionStatus;
+
+        let db = NovaDB::open_temporary().unwrap();
+        let chain = make_block_chain(2);
+        for block in &chain {
+            db.put_block(block).unwrap();
+        }
+
+        let tx = &chain[1].transactions[0];
+        let info = BlockInfo {
+            height: 1,
+            hash: chain[1].header.hash_hex(),
+            timestamp: chain[1].header.timestamp,
+        };
+        let receipt = TransactionReceipt::from_transaction(tx, &info, TransactionStatus::Confirmed);
+        db.put_receipts(std::slice::from_ref(&receipt)).unwrap();
+        assert_eq!(db.get_receipt(&tx.id).unwrap(), Some(receipt));
+        assert!(db.get_receipt("deadbeef").unwrap().is_none());
+
+        db.rollback_to(0).unwrap();
+        assert!(db.get_receipt(&tx.id).unwrap().is_none());
+    }
+
     #[test]
     fn frozen_account_persists_correctly() {
         let db = NovaDB::open_temporary().unwrap();
@@ -744,6 +1073,7 @@ mod tests {
             balance_commitments: std::collections::HashMap::new(),
             credit_lines: vec!["credit_001".to_string()],
             frozen: true,
+            ..Default::default()
         };
 
         db.put_account("nova:frozen_user", &state).unwrap();
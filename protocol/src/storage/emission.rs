@@ -0,0 +1,548 @@
+//! # Emission & Supply
+//!
+//! New NOVA enters circulation as block rewards. The [`MonetaryPolicy`]
+//! fixed in the [`GenesisConfig`](super::genesis::GenesisConfig) says how
+//! much: the chain is divided into epochs of `epoch_blocks` blocks, each
+//! epoch mints a fixed amount, and every `decay_interval_epochs` epochs
+//! that amount is cut by `decay_bps` until it reaches the tail emission,
+//! where it stays.
+//!
+//! ```text
+//!   epoch(h)      = (h - 1) / epoch_blocks            (genesis mints nothing)
+//!   emission(e)   = max(tail, initial × (1 − decay)^⌊e / interval⌋)
+//!   reward(h)     = emission / epoch_blocks, plus 1 photon for the first
+//!                   (emission mod epoch_blocks) blocks of the epoch
+//! ```
+//!
+//! Every step is integer arithmetic with each cut rounded down, so an
+//! epoch mints exactly its emission and every node agrees to the photon.
+//!
+//! Rewards go to the block proposer, after fees are settled. The
+//! [`SupplyRecord`] in the state tree books every block's mint and burn, so
+//! the circulating supply is `initial_supply + minted − burned`.
+//!
+//! ## Supply Invariant
+//!
+//! The record also books the balances allocated at genesis and the funds
+//! locked by authorization holds, which makes the account balances
+//! checkable against it:
+//!
+//! ```text
+//!   Σ balances + burned == genesis + minted
+//!   Σ held              == locked
+//! ```
+//!
+//! A [`SupplyAudit`] sums every account in the tree to check both. Debug
+//! builds check every block's change against its booking; the node's
+//! `verify-chain` command checks the absolute form.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{
+    EMISSION_DECAY_BPS, EMISSION_DECAY_INTERVAL_EPOCHS, EMISSION_EPOCH_BLOCKS,
+    EXISTENTIAL_DEPOSIT_PHOTONS, GENESIS_SUPPLY_PHOTONS, INITIAL_EPOCH_EMISSION_PHOTONS,
+    TAIL_EPOCH_EMISSION_PHOTONS,
+};
+use crate::encoding;
+
+use super::snapshot::StateSnapshot;
+use super::state::{AccountState, StateError, StateTree};
+
+/// State tree key under which the [`SupplyRecord`] is persisted.
+///
+/// Not a valid NOVA address, so it can never collide with an account.
+pub const SUPPLY_STATE_KEY: &str = "nova:system:supply";
+
+// ---------------------------------------------------------------------------
+// Monetary Policy
+// ---------------------------------------------------------------------------
+
+/// The emission schedule.
+///
+/// Consensus-critical: every node must apply the same policy or state
+/// roots diverge at block 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonetaryPolicy {
+    /// Photons in existence at genesis.
+    #[serde(with = "encoding::amount")]
+    pub initial_supply: u64,
+    /// Blocks per epoch. Must be nonzero.
+    pub epoch_blocks: u64,
+    /// Photons minted over the first epoch.
+    #[serde(with = "encoding::amount")]
+    pub initial_epoch_emission: u64,
+    /// Epochs between emission cuts. Zero never cuts.
+    pub decay_interval_epochs: u64,
+    /// How much each cut takes off the epoch emission, in basis points
+    /// (0..=10_000). 5,000 is a halving.
+    pub decay_bps: u16,
+    /// Epoch emission never decays below this.
+    #[serde(with = "encoding::amount")]
+    pub tail_epoch_emission: u64,
+}
+
+impl Default for MonetaryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_supply: GENESIS_SUPPLY_PHOTONS,
+            epoch_blocks: EMISSION_EPOCH_BLOCKS,
+            initial_epoch_emission: INITIAL_EPOCH_EMISSION_PHOTONS,
+            decay_interval_epochs: EMISSION_DECAY_INTERVAL_EPOCHS,
+            decay_bps: EMISSION_DECAY_BPS,
+            tail_epoch_emission: TAIL_EPOCH_EMISSION_PHOTONS,
+        }
+    }
+}
+
+impl MonetaryPolicy {
+    /// A policy that never mints: the supply only shrinks, through fee
+    /// burns.
+    pub fn fixed(initial_supply: u64) -> Self {
+        Self {
+            initial_supply,
+            epoch_blocks: EMISSION_EPOCH_BLOCKS,
+            initial_epoch_emission: 0,
+            decay_interval_epochs: 0,
+            decay_bps: 0,
+            tail_epoch_emission: 0,
+        }
+    }
+
+    /// Epoch the block at `height` belongs to. Genesis belongs to none.
+    pub fn epoch_of(&self, height: u64) -> Option<u64> {
+        height.checked_sub(1).map(|h| h / self.epoch_blocks.max(1))
+    }
+
+    /// Photons minted over `epoch`.
+    pub fn epoch_emission(&self, epoch: u64) -> u64 {
+        let cuts = match self.decay_interval_epochs {
+            0 => 0,
+            interval => epoch / interval,
+        };
+        let mut emission = self.initial_epoch_emission;
+        for _ in 0..cuts {
+            let next = decay(emission, self.decay_bps);
+            if next <= self.tail_epoch_emission || next == emission {
+                emission = next;
+                break;
+            }
+            emission = next;
+        }
+        emission.max(self.tail_epoch_emission)
+    }
+
+    /// Photons minted by the block at `height`.
+    pub fn block_reward(&self, height: u64) -> u64 {
+        let Some(epoch) = self.epoch_of(height) else {
+            return 0;
+        };
+        let blocks = self.epoch_blocks.max(1);
+        let emission = self.epoch_emission(epoch);
+        let index = (height - 1) % blocks;
+        emission / blocks + u64::from(index < emission % blocks)
+    }
+
+    /// Photons the schedule mints over blocks `1..=height`, saturating at
+    /// `u64::MAX`.
+    pub fn scheduled_emission(&self, height: u64) -> u64 {
+        let Some(last_epoch) = self.epoch_of(height) else {
+            return 0;
+        };
+        let blocks = self.epoch_blocks.max(1);
+        let interval = match self.decay_interval_epochs {
+            0 => u64::MAX,
+            interval => interval,
+        };
+
+        // Whole epochs before the last, one decay interval at a time: the
+        // emission is constant within an interval, and once it stops
+        // changing the rest is a single multiplication.
+        let mut total: u128 = 0;
+        let mut epoch = 0u64;
+        while epoch < last_epoch {
+            let emission = self.epoch_emission(epoch);
+            let interval_end = epoch.saturating_add(interval - epoch % interval);
+            let settled = self.epoch_emission(interval_end) == emission;
+            let end = if settled {
+                last_epoch
+            } else {
+                interval_end.min(last_epoch)
+            };
+            total += u128::from(emission) * u128::from(end - epoch);
+            epoch = end;
+        }
+
+        // Then the blocks of the last epoch up to `height`, reward by reward.
+        let emission = self.epoch_emission(last_epoch);
+        let into_epoch = (height - 1) % blocks + 1;
+        let extra = into_epoch.min(emission % blocks);
+        total += u128::from(emission / blocks) * u128::from(into_epoch) + u128::from(extra);
+
+        u64::try_from(total).unwrap_or(u64::MAX)
+    }
+}
+
+/// One emission cut, rounding the cut down.
+fn decay(emission: u64, decay_bps: u16) -> u64 {
+    let cut = u128::from(emission) * u128::from(decay_bps.min(10_000)) / 10_000;
+    emission - cut as u64
+}
+
+// ---------------------------------------------------------------------------
+// Supply Record
+// ---------------------------------------------------------------------------
+
+/// Photons allocated, minted, burned and locked since genesis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupplyRecord {
+    /// Photons minted as block rewards.
+    pub minted: u64,
+    /// Photons burned from fees.
+    pub burned: u64,
+    /// Photons credited to accounts at genesis
+    /// (see [`fund_genesis_account`]).
+    pub genesis: u64,
+    /// Photons currently locked by authorization holds. Still part of the
+    /// payers' balances.
+    pub locked: u64,
+}
+
+impl SupplyRecord {
+    /// Loads the record persisted in the state tree, or an empty one.
+    pub fn load(tree: &StateTree) -> Self {
+        tree.get_raw(SUPPLY_STATE_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Loads the record as of a committed state snapshot.
+    pub fn load_from_snapshot(snapshot: &StateSnapshot<'_>) -> Self {
+        snapshot
+            .get_raw(SUPPLY_STATE_KEY)
+            .and_then(|bytes| bincode::deserialize(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persists the record into the state tree.
+    pub fn store(&self, tree: &mut StateTree) {
+        let bytes = bincode::serialize(self).expect("supply record serialization should not fail");
+        tree.put_raw(SUPPLY_STATE_KEY, &bytes);
+    }
+
+    /// Photons in circulation under `policy`.
+    pub fn circulating(&self, policy: &MonetaryPolicy) -> u64 {
+        policy
+            .initial_supply
+            .saturating_add(self.minted)
+            .saturating_sub(self.burned)
+    }
+
+    /// What every account balance must add up to:
+    /// `genesis + minted − burned`.
+    pub fn expected_balances(&self) -> u128 {
+        (u128::from(self.genesis) + u128::from(self.minted)).saturating_sub(u128::from(self.burned))
+    }
+}
+
+/// Credit `amount` to `address` as a genesis allocation, booking it in the
+/// [`SupplyRecord`]. For seeding a fresh chain before block 1; value
+/// created any other way breaks the supply invariant.
+pub fn fund_genesis_account(tree: &mut StateTree, address: &str, amount: u64) {
+    let mut state = tree.get(address).unwrap_or_default();
+    state.balance += amount;
+    tree.put(address, &state);
+
+    let mut record = SupplyRecord::load(tree);
+    record.genesis = record.genesis.saturating_add(amount);
+    record.store(tree);
+}
+
+/// Book `placed` photons newly locked by holds and `released` photons
+/// unlocked. Writes only when something changed.
+pub(crate) fn book_locked(tree: &mut StateTree, placed: u64, released: u64) {
+    if placed == released {
+        return;
+    }
+    let mut record = SupplyRecord::load(tree);
+    record.locked = record
+        .locked
+        .saturating_add(placed)
+        .saturating_sub(released);
+    record.store(tree);
+}
+
+// ---------------------------------------------------------------------------
+// Reward Engine
+// ---------------------------------------------------------------------------
+
+/// Mint the block reward at `height` to `proposer`, and book it with the
+/// fees the block `burned` in the [`SupplyRecord`]. Returns the photons
+/// minted.
+///
+/// Called once per block, after [`settle_fees`](super::fee::settle_fees).
+/// A reward too small to open the proposer's account is not minted.
+pub fn apply_emission(
+    tree: &mut StateTree,
+    proposer: &str,
+    height: u64,
+    policy: &MonetaryPolicy,
+    burned: u64,
+) -> u64 {
+    let mut minted = policy.block_reward(height);
+    if minted > 0 {
+        match tree.get(proposer) {
+            Some(mut state) => {
+                state.balance += minted;
+                tree.put(proposer, &state);
+            }
+            None if minted >= EXISTENTIAL_DEPOSIT_PHOTONS => {
+                tree.put(proposer, &AccountState::with_balance(minted));
+            }
+            None => minted = 0,
+        }
+    }
+
+    if minted > 0 || burned > 0 {
+        let mut record = SupplyRecord::load(tree);
+        record.minted = record.minted.saturating_add(minted);
+        record.burned = record.burned.saturating_add(burned);
+        record.store(tree);
+    }
+    minted
+}
+
+// ---------------------------------------------------------------------------
+// Supply Audit
+// ---------------------------------------------------------------------------
+
+/// Every account in a state tree summed, beside the [`SupplyRecord`] that
+/// claims to describe them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyAudit {
+    /// Accounts scanned.
+    pub accounts: u64,
+    /// Sum of all account balances, system accounts included.
+    pub balances: u128,
+    /// Sum of all funds held by authorization holds.
+    pub held: u128,
+    /// The supply record at the time of the scan.
+    pub record: SupplyRecord,
+}
+
+impl SupplyAudit {
+    /// Scans every account in `tree`. `records` are the keys of the
+    /// non-account records stored beside them (see
+    /// [`StateTree::accounts`]). A full scan: for audits and debug builds,
+    /// not the hot path.
+    pub fn take(tree: &StateTree, records: &[&str]) -> Self {
+        let mut audit = Self {
+            accounts: 0,
+            balances: 0,
+            held: 0,
+            record: SupplyRecord::load(tree),
+        };
+        for account in tree.accounts(records) {
+            audit.accounts += 1;
+            audit.balances += u128::from(account.balance);
+            audit.held += u128::from(account.held_balance());
+        }
+        audit
+    }
+
+    /// Checks the balances against the record:
+    /// `Σ balances + burned == genesis + minted` and `Σ held == locked`.
+    pub fn check(&self) -> Result<(), StateError> {
+        let expected = self.record.expected_balances();
+        if self.balances != expected {
+            return Err(StateError::SupplyMismatch(format!(
+                "balances sum to {}, but genesis {} + minted {} - burned {} is {}",
+                self.balances,
+                self.record.genesis,
+                self.record.minted,
+                self.record.burned,
+                expected
+            )));
+        }
+        if self.held != u128::from(self.record.locked) {
+            return Err(StateError::SupplyMismatch(format!(
+                "holds lock {}, but the record books {}",
+                self.held, self.record.locked
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that everything between `self` and a later scan `after` was
+    /// booked: balances moved by exactly the change in
+    /// `genesis + minted − burned`, and held funds by the change in
+    /// `locked`. Holds for trees seeded without [`fund_genesis_account`].
+    pub fn check_block(&self, after: &SupplyAudit) -> Result<(), StateError> {
+        let change = |before: u128, after: u128| after as i128 - before as i128;
+        let booked = change(
+            self.record.expected_balances(),
+            after.record.expected_balances(),
+        );
+        let moved = change(self.balances, after.balances);
+        if moved != booked {
+            return Err(StateError::SupplyMismatch(format!(
+                "balances changed by {}, but the record booked {}",
+                moved, booked
+            )));
+        }
+        let booked = change(self.record.locked.into(), after.record.locked.into());
+        let moved = change(self.held, after.held);
+        if moved != booked {
+            return Err(StateError::SupplyMismatch(format!(
+                "held funds changed by {}, but the record booked {}",
+                moved, booked
+            )));
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+
+    fn policy() -> MonetaryPolicy {
+        MonetaryPolicy {
+            initial_supply: 1_000_000,
+            epoch_blocks: 10,
+            initial_epoch_emission: 1_005,
+            decay_interval_epochs: 2,
+            decay_bps: 5_000,
+            tail_epoch_emission: 100,
+        }
+    }
+
+    #[test]
+    fn emission_halves_down_to_the_tail() {
+        let policy = policy();
+        let emissions: Vec<u64> = (0..12).map(|e| policy.epoch_emission(e)).collect();
+        assert_eq!(
+            emissions,
+            [1_005, 1_005, 503, 503, 252, 252, 126, 126, 100, 100, 100, 100]
+        );
+        assert_eq!(policy.epoch_emission(u64::MAX), 100);
+        assert_eq!(MonetaryPolicy::fixed(5).block_reward(1), 0);
+    }
+
+    #[test]
+    fn block_rewards_sum_to_epoch_emission() {
+        let policy = policy();
+        assert_eq!(policy.block_reward(0), 0);
+        for epoch in 0..10u64 {
+            let first = epoch * 10 + 1;
+            let minted: u64 = (first..first + 10).map(|h| policy.block_reward(h)).sum();
+            assert_eq!(minted, policy.epoch_emission(epoch));
+        }
+        // 1_005 over 10 blocks: the first five get the odd photons.
+        assert_eq!(policy.block_reward(5), 101);
+        assert_eq!(policy.block_reward(6), 100);
+    }
+
+    #[test]
+    fn scheduled_emission_matches_block_by_block() {
+        let policy = policy();
+        let mut total = 0u64;
+        for height in 0..=150 {
+            total += policy.block_reward(height);
+            assert_eq!(
+                policy.scheduled_emission(height),
+                total,
+                "height {}",
+                height
+            );
+        }
+        let default = MonetaryPolicy::default();
+        assert_eq!(
+            default.scheduled_emission(default.epoch_blocks),
+            INITIAL_EPOCH_EMISSION_PHOTONS
+        );
+        assert_eq!(default.scheduled_emission(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn emission_pays_proposer_and_books_supply() {
+        let mut tree = StateTree::new(NovaDB::open_temporary().unwrap());
+        let policy = policy();
+
+        assert_eq!(
+            apply_emission(&mut tree, "nova1proposer", 1, &policy, 40),
+            101
+        );
+        assert_eq!(tree.get("nova1proposer").unwrap().balance, 101);
+
+        let record = SupplyRecord::load(&tree);
+        assert_eq!(
+            record,
+            SupplyRecord {
+                minted: 101,
+                burned: 40,
+                ..SupplyRecord::default()
+            }
+        );
+        assert_eq!(record.circulating(&policy), 1_000_061);
+
+        // A reward below the existential deposit does not open an account.
+        let tiny = MonetaryPolicy {
+            initial_epoch_emission: 10,
+            ..policy
+        };
+        assert_eq!(apply_emission(&mut tree, "nova1new", 1, &tiny, 0), 0);
+        assert!(tree.get("nova1new").is_none());
+    }
+
+    #[test]
+    fn supply_audit_balances_against_the_record() {
+        use crate::storage::hold::{place_hold, release_expired_holds, AuthorizationHold};
+        use crate::storage::state::apply_transfer;
+
+        let mut tree = StateTree::new(NovaDB::open_temporary().unwrap());
+        fund_genesis_account(&mut tree, "nova1alice", 50_000);
+        fund_genesis_account(&mut tree, "nova1bob", 20_000);
+        let before = SupplyAudit::take(&tree, &[SUPPLY_STATE_KEY]);
+        assert_eq!(before.accounts, 2);
+        assert_eq!(before.balances, 70_000);
+        before.check().unwrap();
+
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 5_000, 100, 1).unwrap();
+        place_hold(
+            &mut tree,
+            "nova1bob",
+            AuthorizationHold {
+                hold_id: "h1".into(),
+                merchant: "nova1alice".into(),
+                amount: 3_000,
+                captured: 0,
+                expires_at: 1_000,
+            },
+        )
+        .unwrap();
+        apply_emission(&mut tree, "nova1alice", 1, &policy(), 100);
+
+        let after = SupplyAudit::take(&tree, &[SUPPLY_STATE_KEY]);
+        assert_eq!(after.held, 3_000);
+        after.check().unwrap();
+        before.check_block(&after).unwrap();
+
+        release_expired_holds(&mut tree, "nova1bob", 2_000);
+        let released = SupplyAudit::take(&tree, &[SUPPLY_STATE_KEY]);
+        assert_eq!(released.record.locked, 0);
+        released.check().unwrap();
+
+        // Value that appears without a booking is caught both ways.
+        let mut bob = tree.get("nova1bob").unwrap();
+        bob.balance += 1;
+        tree.put("nova1bob", &bob);
+        let forged = SupplyAudit::take(&tree, &[SUPPLY_STATE_KEY]);
+        assert!(matches!(forged.check(), Err(StateError::SupplyMismatch(_))));
+        assert!(released.check_block(&forged).is_err());
+    }
+}
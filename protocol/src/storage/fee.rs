@@ -0,0 +1,387 @@
+//! # Fee Settlement & Value Conservation
+//!
+//! Every transaction pays its declared fee out of the sender's balance
+//! when it executes, or out of its fee payer's, if sponsored. Transfers
+//! and batches fold the fee into their own debit —
+//! [`apply_transfer`](super::state::apply_transfer) debits `amount + fee`
+//! and credits only `amount` — and every other type is charged by
+//! [`charge_fee`] after its transition. The fees a block collects are
+//! settled once, after its last transaction: the [`FeePolicy`] credits the
+//! proposer's share, pays the treasury's share into
+//! [`TREASURY_STATE_KEY`], and burns the remainder.
+//! The total is recorded in the block header's `fees` field, and sync
+//! rejects a block whose header disagrees with its transactions.
+//!
+//! ## Conservation
+//!
+//! No block may create value beyond its reward. A [`BalanceAudit`] records
+//! the balance of every account a block can touch (each transaction's
+//! sender and receiver, the proposer, the treasury, and the dust pool that
+//! reaped balances are swept into) before execution, and after settlement
+//! checks
+//!
+//! ```text
+//!   total debits + minted == total credits + burned
+//! ```
+//!
+//! where `minted` is the block reward ([`super::emission`]).
+//!
+//! Producers refuse to emit a block that fails the check; sync refuses to
+//! apply one.
+
+use std::collections::HashMap;
+
+use crate::config::{EXISTENTIAL_DEPOSIT_PHOTONS, FEE_BURN_BPS, FEE_TREASURY_BPS};
+use crate::transaction::builder::Transaction;
+use crate::transaction::types::TransactionType;
+
+use super::batch::batch_recipients;
+use super::state::{
+    reap_if_dust, AccountState, StateError, StateTree, DUST_POOL_STATE_KEY, TREASURY_STATE_KEY,
+};
+
+// ---------------------------------------------------------------------------
+// Fee Policy
+// ---------------------------------------------------------------------------
+
+/// How collected fees are divided between the proposer, the treasury and
+/// the burn. The treasury's share is carved out of the burn.
+///
+/// Consensus-critical: every node must apply the same policy or state
+/// roots diverge at the first block that carries a fee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeePolicy {
+    /// Share of fees burned, in basis points (0..=10_000).
+    pub burn_bps: u16,
+    /// Share of fees paid to the treasury instead of burned, in basis
+    /// points. Capped at the burn share.
+    pub treasury_bps: u16,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        Self {
+            burn_bps: FEE_BURN_BPS,
+            treasury_bps: FEE_TREASURY_BPS,
+        }
+    }
+}
+
+/// Result of splitting a block's fees under a [`FeePolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeSplit {
+    /// Credited to the block proposer.
+    pub proposer: u64,
+    /// Paid into the treasury.
+    pub treasury: u64,
+    /// Removed from circulation.
+    pub burned: u64,
+}
+
+impl FeePolicy {
+    /// Splits `fees` into proposer share, treasury share and burn.
+    /// Rounding favours the burn over the proposer and the treasury, so
+    /// the three parts always sum to `fees`.
+    pub fn split(&self, fees: u64) -> FeeSplit {
+        let bps = u128::from(self.burn_bps.min(10_000));
+        let withheld = (u128::from(fees) * bps).div_ceil(10_000) as u64;
+        let treasury = (u128::from(fees) * u128::from(self.treasury_bps) / 10_000)
+            .min(u128::from(withheld)) as u64;
+        FeeSplit {
+            proposer: fees - withheld,
+            treasury,
+            burned: withheld - treasury,
+        }
+    }
+}
+
+/// Fee a successfully executed transaction has paid into the block: its
+/// declared fee, whatever its type.
+pub fn fee_charged(tx: &Transaction) -> u64 {
+    tx.fee
+}
+
+/// Returns `true` if the transition for `tx_type` debits the fee itself,
+/// as part of moving the amount. Every other type is charged by
+/// [`charge_fee`].
+pub fn pays_own_fee(tx_type: TransactionType) -> bool {
+    matches!(
+        tx_type,
+        TransactionType::Transfer | TransactionType::ConfidentialTransfer | TransactionType::Batch
+    )
+}
+
+/// Debit `tx.fee` from the transaction's fee source (its fee payer, or
+/// the sender).
+///
+/// The payer must not be frozen and its available balance must cover the
+/// fee. It is reaped if the fee leaves it below the existential deposit.
+/// A zero fee touches nothing.
+pub fn charge_fee(tree: &mut StateTree, tx: &Transaction) -> Result<(), StateError> {
+    if tx.fee == 0 {
+        return Ok(());
+    }
+    let payer = tx.fee_source();
+    let mut state = tree.get(payer).unwrap_or_default();
+    if state.frozen {
+        return Err(StateError::AccountFrozen(payer.to_string()));
+    }
+    let available = state.available_balance();
+    if available < tx.fee {
+        return Err(StateError::InsufficientBalance {
+            have: available,
+            need: tx.fee,
+        });
+    }
+    state.balance -= tx.fee;
+    tree.put(payer, &state);
+    reap_if_dust(tree, payer);
+    Ok(())
+}
+
+/// Credit the proposer's and the treasury's shares of `fees` and return
+/// the split.
+///
+/// Called once per block, after every transaction has executed. A proposer
+/// share too small to open the proposer's account (below the existential
+/// deposit) is burned instead; the treasury takes any amount.
+pub fn settle_fees(
+    tree: &mut StateTree,
+    proposer: &str,
+    fees: u64,
+    policy: &FeePolicy,
+) -> FeeSplit {
+    let mut split = policy.split(fees);
+    credit_treasury(tree, split.treasury);
+    if split.proposer == 0 {
+        return split;
+    }
+    match tree.get(proposer) {
+        Some(mut state) => {
+            state.balance += split.proposer;
+            tree.put(proposer, &state);
+        }
+        None if split.proposer >= EXISTENTIAL_DEPOSIT_PHOTONS => {
+            tree.put(proposer, &AccountState::with_balance(split.proposer));
+        }
+        None => {
+            split.burned += split.proposer;
+            split.proposer = 0;
+        }
+    }
+    split
+}
+
+/// Pays `amount` into the treasury account. Fee settlement is the only
+/// source today; slashed stake is meant to follow once slashing exists.
+pub fn credit_treasury(tree: &mut StateTree, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    let mut treasury = tree.get(TREASURY_STATE_KEY).unwrap_or_default();
+    treasury.balance += amount;
+    tree.put(TREASURY_STATE_KEY, &treasury);
+}
+
+// ---------------------------------------------------------------------------
+// Conservation Check
+// ---------------------------------------------------------------------------
+
+/// Balances of the accounts a block can touch, captured before execution.
+#[derive(Debug, Clone)]
+pub struct BalanceAudit {
+    before: HashMap<String, u64>,
+}
+
+impl BalanceAudit {
+    /// Records the current balance of every sender, receiver and fee payer
+    /// in `txs`, every recipient of a batch, the `proposer`, the treasury
+    /// and the dust pool.
+    pub fn for_block(tree: &StateTree, txs: &[Transaction], proposer: &str) -> Self {
+        let mut before = HashMap::new();
+        let accounts = txs
+            .iter()
+            .flat_map(|tx| [tx.sender.clone(), tx.receiver.clone()])
+            .chain(txs.iter().filter_map(|tx| tx.fee_payer.clone()))
+            .chain(txs.iter().flat_map(batch_recipients))
+            .chain([proposer, TREASURY_STATE_KEY, DUST_POOL_STATE_KEY].map(String::from));
+        for address in accounts {
+            let balance = tree.get(&address).map_or(0, |a| a.balance);
+            before.entry(address).or_insert(balance);
+        }
+        Self { before }
+    }
+
+    /// Checks that the balance changes since [`for_block`](Self::for_block)
+    /// net out to exactly `minted − burned`.
+    pub fn finish(&self, tree: &StateTree, burned: u64, minted: u64) -> Result<(), StateError> {
+        let mut debited: u128 = 0;
+        let mut credited: u128 = 0;
+        for (address, &before) in &self.before {
+            let after = tree.get(address).map_or(0, |a| a.balance);
+            if after < before {
+                debited += u128::from(before - after);
+            } else {
+                credited += u128::from(after - before);
+            }
+        }
+
+        if debited + u128::from(minted) != credited + u128::from(burned) {
+            return Err(StateError::ConservationViolated {
+                debited,
+                minted,
+                credited,
+                burned,
+            });
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::state::apply_transfer;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+
+    fn temp_tree() -> StateTree {
+        StateTree::new(NovaDB::open_temporary().unwrap())
+    }
+
+    fn transfer(sender: &str, receiver: &str, amount: u64, fee: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender(sender)
+            .receiver(receiver)
+            .amount(Amount::new(amount, Currency::NOVA))
+            .fee(fee)
+            .nonce(1)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn split_always_sums_to_total() {
+        let policy = FeePolicy {
+            burn_bps: 5_000,
+            treasury_bps: 0,
+        };
+        assert_eq!(
+            policy.split(101),
+            FeeSplit {
+                proposer: 50,
+                treasury: 0,
+                burned: 51
+            }
+        );
+        let burn = |burn_bps| FeePolicy {
+            burn_bps,
+            treasury_bps: 0,
+        };
+        assert_eq!(burn(0).split(7).proposer, 7);
+        assert_eq!(burn(10_000).split(7).burned, 7);
+        assert_eq!(burn(u16::MAX).split(7).burned, 7);
+
+        let policy = FeePolicy {
+            burn_bps: 5_000,
+            treasury_bps: 1_000,
+        };
+        assert_eq!(
+            policy.split(1_001),
+            FeeSplit {
+                proposer: 500,
+                treasury: 100,
+                burned: 401
+            }
+        );
+        let greedy = FeePolicy {
+            burn_bps: 2_000,
+            treasury_bps: 5_000,
+        };
+        assert_eq!(greedy.split(100).treasury, 20);
+        assert_eq!(greedy.split(100).burned, 0);
+    }
+
+    #[test]
+    fn settled_block_conserves_value() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        let txs = vec![transfer("nova1alice", "nova1bob", 1_000, 200)];
+
+        let audit = BalanceAudit::for_block(&tree, &txs, "nova1validator");
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 200, 1).unwrap();
+        let split = settle_fees(&mut tree, "nova1validator", 200, &FeePolicy::default());
+
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 8_800);
+        assert_eq!(tree.get("nova1validator").unwrap().balance, split.proposer);
+        assert_eq!(tree.get(TREASURY_STATE_KEY).unwrap().balance, 20);
+        assert!(audit.finish(&tree, split.burned, 0).is_ok());
+    }
+
+    #[test]
+    fn minted_value_is_detected() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        let txs = vec![transfer("nova1alice", "nova1bob", 1_000, 0)];
+
+        let audit = BalanceAudit::for_block(&tree, &txs, "nova1validator");
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap();
+        // A buggy transition credits the receiver twice.
+        tree.put("nova1bob", &AccountState::with_balance(2_000));
+
+        let err = audit.finish(&tree, 0, 0).unwrap_err();
+        assert!(matches!(
+            err,
+            StateError::ConservationViolated {
+                debited: 1_000,
+                minted: 0,
+                credited: 2_000,
+                burned: 0
+            }
+        ));
+    }
+
+    #[test]
+    fn reaped_dust_is_conserved() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(1_050));
+        let txs = vec![transfer("nova1alice", "nova1bob", 1_000, 0)];
+
+        let audit = BalanceAudit::for_block(&tree, &txs, "nova1validator");
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap();
+
+        assert!(tree.get("nova1alice").is_none());
+        assert_eq!(tree.get(DUST_POOL_STATE_KEY).unwrap().balance, 50);
+        assert!(audit.finish(&tree, 0, 0).is_ok());
+    }
+
+    #[test]
+    fn proposer_share_below_deposit_is_burned() {
+        let mut tree = temp_tree();
+        let policy = FeePolicy {
+            burn_bps: 5_000,
+            treasury_bps: 0,
+        };
+
+        let split = settle_fees(&mut tree, "nova1validator", 100, &policy);
+        assert_eq!(
+            split,
+            FeeSplit {
+                proposer: 0,
+                treasury: 0,
+                burned: 100
+            }
+        );
+        assert!(tree.get("nova1validator").is_none());
+
+        // An existing account takes any share.
+        tree.put("nova1validator", &AccountState::with_balance(1));
+        let split = settle_fees(&mut tree, "nova1validator", 100, &policy);
+        assert_eq!(split.proposer, 50);
+        assert_eq!(tree.get("nova1validator").unwrap().balance, 51);
+    }
+}
@@ -0,0 +1,125 @@
+//! # Genesis Configuration
+//!
+//! The parameters a network fixes at block 0 and never changes without a
+//! hard fork. Today that is the chain ID and the [`MonetaryPolicy`]; every
+//! node on a network must load the same config or state roots diverge.
+//!
+//! Configs are JSON. Amounts above 2^53 − 1 may be written as decimal
+//! strings (see [`crate::encoding`]):
+//!
+//! ```json
+//! {
+//!   "chain_id": 1313822276,
+//!   "monetary_policy": {
+//!     "initial_supply": "100000000000000000",
+//!     "epoch_blocks": 43200,
+//!     "initial_epoch_emission": 10000000000000,
+//!     "decay_interval_epochs": 1460,
+//!     "decay_bps": 5000,
+//!     "tail_epoch_emission": 500000000000
+//!   }
+//! }
+//! ```
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::emission::MonetaryPolicy;
+
+/// Errors loading a genesis config.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum GenesisError {
+    #[error("failed to read genesis config: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("malformed genesis config: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("invalid genesis config: {0}")]
+    Invalid(String),
+}
+
+/// Network parameters fixed at genesis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisConfig {
+    /// Chain ID (see `config::NETWORK_ID_*`).
+    pub chain_id: u32,
+    /// Emission schedule and initial supply.
+    #[serde(default)]
+    pub monetary_policy: MonetaryPolicy,
+}
+
+impl GenesisConfig {
+    /// The protocol's default parameters for `chain_id`.
+    pub fn new(chain_id: u32) -> Self {
+        Self {
+            chain_id,
+            monetary_policy: MonetaryPolicy::default(),
+        }
+    }
+
+    /// Parses and validates a JSON config.
+    pub fn from_json(json: &str) -> Result<Self, GenesisError> {
+        let config: Self = serde_json::from_str(json)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads, parses and validates a JSON config file.
+    pub fn load(path: &Path) -> Result<Self, GenesisError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Rejects parameters the emission engine cannot apply.
+    pub fn validate(&self) -> Result<(), GenesisError> {
+        let policy = &self.monetary_policy;
+        if policy.epoch_blocks == 0 {
+            return Err(GenesisError::Invalid(
+                "monetary_policy.epoch_blocks must be nonzero".to_string(),
+            ));
+        }
+        if policy.decay_bps > 10_000 {
+            return Err(GenesisError::Invalid(format!(
+                "monetary_policy.decay_bps is {}, above 10000",
+                policy.decay_bps
+            )));
+        }
+        if policy.tail_epoch_emission > policy.initial_epoch_emission {
+            return Err(GenesisError::Invalid(
+                "monetary_policy.tail_epoch_emission exceeds initial_epoch_emission".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NETWORK_ID_DEVNET;
+
+    #[test]
+    fn json_roundtrip_and_validation() {
+        let genesis = GenesisConfig::new(NETWORK_ID_DEVNET);
+        let json = serde_json::to_string(&genesis).unwrap();
+        assert!(json.contains("\"100000000000000000\""));
+        assert_eq!(GenesisConfig::from_json(&json).unwrap(), genesis);
+
+        let defaults = GenesisConfig::from_json(r#"{"chain_id": 7}"#).unwrap();
+        assert_eq!(defaults.monetary_policy, MonetaryPolicy::default());
+
+        let mut broken = genesis;
+        broken.monetary_policy.epoch_blocks = 0;
+        let json = serde_json::to_string(&broken).unwrap();
+        assert!(matches!(
+            GenesisConfig::from_json(&json),
+            Err(GenesisError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,445 @@
+//! # Authorization Holds — Authorize Now, Capture Later
+//!
+//! Card-style payments split into two steps: the payer *authorizes* an
+//! amount (funds are locked against one merchant, but stay in the payer's
+//! account), and the merchant later *captures* up to that amount. Whatever
+//! is not captured by the expiry is released back to the payer's spendable
+//! balance without anyone having to ask for it.
+//!
+//! Holds live inside [`AccountState`] so they are covered by the state root
+//! like every other consensus-critical byte. A hold never moves money on
+//! its own — it only shrinks [`AccountState::available_balance`], which is
+//! what every debit checks against. The total still held across all
+//! accounts is booked as `locked` in the
+//! [`SupplyRecord`](super::emission::SupplyRecord).
+//!
+//! ## On-chain encoding
+//!
+//! | Tx type              | sender  | receiver | amount  | payload         |
+//! |----------------------|---------|----------|---------|-----------------|
+//! | `AuthorizationHold`  | payer   | merchant | held    | [`HoldTerms`]   |
+//! | `HoldCapture`        | merchant| payer    | capture | [`CaptureTerms`]|
+//!
+//! The hold ID is the ID of the `AuthorizationHold` transaction. Expiry is
+//! evaluated against the timestamp of the block the transaction executes
+//! in, which keeps replay during sync deterministic. The transaction's own
+//! timestamp is the signer's to choose, so it never decides whether a hold
+//! is live: a merchant cannot backdate a capture onto a lapsed hold, and a
+//! payer cannot post-date a transaction to free a hold early.
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoding;
+use crate::transaction::builder::Transaction;
+use crate::transaction::types::TransactionType;
+
+use super::emission::book_locked;
+use super::state::{check_nonce, AccountState, StateError, StateTree};
+
+// ---------------------------------------------------------------------------
+// Hold Record
+// ---------------------------------------------------------------------------
+
+/// Funds locked by a payer in favour of a single merchant.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorizationHold {
+    /// ID of the transaction that placed the hold.
+    pub hold_id: String,
+    /// Merchant allowed to capture against this hold.
+    pub merchant: String,
+    /// Total amount authorized.
+    #[serde(with = "encoding::amount")]
+    pub amount: u64,
+    /// Amount captured so far.
+    #[serde(with = "encoding::amount")]
+    pub captured: u64,
+    /// Unix timestamp (milliseconds) after which the hold lapses.
+    pub expires_at: u64,
+}
+
+impl AuthorizationHold {
+    /// Amount still capturable.
+    pub fn remaining(&self) -> u64 {
+        self.amount.saturating_sub(self.captured)
+    }
+
+    /// Returns `true` once `now_ms` is past the expiry.
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms > self.expires_at
+    }
+}
+
+/// Payload of an `AuthorizationHold` transaction (JSON-encoded).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldTerms {
+    /// Unix timestamp (milliseconds) at which the hold lapses.
+    pub expires_at: u64,
+    /// Free-form reference (e.g., the NTP session ID).
+    #[serde(default)]
+    pub reference: String,
+}
+
+/// Payload of a `HoldCapture` transaction (JSON-encoded).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptureTerms {
+    /// The hold being captured against.
+    pub hold_id: String,
+    /// Release whatever is left after this capture.
+    #[serde(default)]
+    pub final_capture: bool,
+}
+
+// ---------------------------------------------------------------------------
+// State Transitions
+// ---------------------------------------------------------------------------
+
+/// Lock `hold.amount` of `owner`'s available balance for `hold.merchant`.
+///
+/// Bumps the owner's nonce, exactly like a transfer would.
+pub fn place_hold(
+    tree: &mut StateTree,
+    owner: &str,
+    hold: AuthorizationHold,
+) -> Result<(), StateError> {
+    let mut state = tree.get(owner).unwrap_or_default();
+
+    if state.frozen {
+        return Err(StateError::AccountFrozen(owner.to_string()));
+    }
+    let available = state.available_balance();
+    if available < hold.amount {
+        return Err(StateError::InsufficientBalance {
+            have: available,
+            need: hold.amount,
+        });
+    }
+    if state.holds.iter().any(|h| h.hold_id == hold.hold_id) {
+        return Err(StateError::HoldRejected(format!(
+            "hold {} already exists",
+            hold.hold_id
+        )));
+    }
+
+    let locked = hold.amount;
+    state.holds.push(hold);
+    state.nonce += 1;
+    tree.put(owner, &state);
+    book_locked(tree, locked, 0);
+    Ok(())
+}
+
+/// Capture `amount` from one of `owner`'s holds and pay it to `merchant`.
+///
+/// Partial captures leave the remainder held until expiry, unless
+/// `final_capture` is set. Bumps the merchant's nonce (they sign the
+/// capture). Returns the amount still held afterwards.
+pub fn capture_hold(
+    tree: &mut StateTree,
+    owner: &str,
+    merchant: &str,
+    hold_id: &str,
+    amount: u64,
+    final_capture: bool,
+    now_ms: u64,
+) -> Result<u64, StateError> {
+    let mut owner_state = tree.get(owner).unwrap_or_default();
+    let held = owner_state.held_balance();
+
+    let idx = owner_state
+        .holds
+        .iter()
+        .position(|h| h.hold_id == hold_id)
+        .ok_or_else(|| StateError::HoldRejected(format!("hold {} not found", hold_id)))?;
+    let hold = &mut owner_state.holds[idx];
+
+    if hold.merchant != merchant {
+        return Err(StateError::HoldRejected(format!(
+            "hold {} belongs to a different merchant",
+            hold_id
+        )));
+    }
+    if hold.is_expired(now_ms) {
+        return Err(StateError::HoldRejected(format!(
+            "hold {} expired at {}",
+            hold_id, hold.expires_at
+        )));
+    }
+    if amount > hold.remaining() {
+        return Err(StateError::InsufficientBalance {
+            have: hold.remaining(),
+            need: amount,
+        });
+    }
+
+    hold.captured += amount;
+    let remaining = if final_capture { 0 } else { hold.remaining() };
+    if remaining == 0 {
+        owner_state.holds.remove(idx);
+    }
+    // The hold guaranteed these funds were never spendable elsewhere.
+    owner_state.balance -= amount;
+    let released = held - owner_state.held_balance();
+    tree.put(owner, &owner_state);
+    book_locked(tree, 0, released);
+
+    let mut merchant_state = tree.get(merchant).unwrap_or_default();
+    merchant_state.balance += amount;
+    merchant_state.nonce += 1;
+    tree.put(merchant, &merchant_state);
+
+    Ok(remaining)
+}
+
+/// Drop every expired hold on `owner`, returning the amount released.
+///
+/// Writes to the tree only when something actually expired, so calling
+/// this before every debit does not perturb the state root.
+pub fn release_expired_holds(tree: &mut StateTree, owner: &str, now_ms: u64) -> u64 {
+    let Some(mut state) = tree.get(owner) else {
+        return 0;
+    };
+
+    let before = state.held_balance();
+    state.holds.retain(|h| !h.is_expired(now_ms));
+    let released = before - state.held_balance();
+
+    if released > 0 {
+        tree.put(owner, &state);
+        book_locked(tree, 0, released);
+    }
+    released
+}
+
+/// Execute an `AuthorizationHold` or `HoldCapture` transaction in a block
+/// with timestamp `now_ms`.
+///
+/// Shared by the block producer and the sync engine so both paths apply
+/// identical state transitions. `tx.nonce` must be the signer's next nonce.
+/// Other transaction types are ignored.
+pub fn apply_hold_transaction(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    now_ms: u64,
+) -> Result<(), StateError> {
+    if matches!(
+        tx.tx_type,
+        TransactionType::AuthorizationHold | TransactionType::HoldCapture
+    ) {
+        check_nonce(tree, &tx.sender, tx.nonce)?;
+    }
+    let payload = tx.payload.as_deref().unwrap_or_default();
+
+    match tx.tx_type {
+        TransactionType::AuthorizationHold => {
+            let terms: HoldTerms = serde_json::from_slice(payload)
+                .map_err(|e| StateError::Serialization(format!("hold terms: {}", e)))?;
+            if terms.expires_at <= now_ms {
+                return Err(StateError::HoldRejected(
+                    "hold expires before it is placed".to_string(),
+                ));
+            }
+            release_expired_holds(tree, &tx.sender, now_ms);
+            place_hold(
+                tree,
+                &tx.sender,
+                AuthorizationHold {
+                    hold_id: tx.id.clone(),
+                    merchant: tx.receiver.clone(),
+                    amount: tx.amount.value,
+                    captured: 0,
+                    expires_at: terms.expires_at,
+                },
+            )
+        }
+        TransactionType::HoldCapture => {
+            let terms: CaptureTerms = serde_json::from_slice(payload)
+                .map_err(|e| StateError::Serialization(format!("capture terms: {}", e)))?;
+            capture_hold(
+                tree,
+                &tx.receiver,
+                &tx.sender,
+                &terms.hold_id,
+                tx.amount.value,
+                terms.final_capture,
+                now_ms,
+            )
+            .map(|_| ())
+        }
+        _ => Ok(()),
+    }
+}
+
+impl AccountState {
+    /// Total amount currently locked by authorization holds.
+    pub fn held_balance(&self) -> u64 {
+        self.holds
+            .iter()
+            .fold(0u64, |acc, h| acc.saturating_add(h.remaining()))
+    }
+
+    /// Balance that can be debited right now (balance minus holds).
+    pub fn available_balance(&self) -> u64 {
+        self.balance.saturating_sub(self.held_balance())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::state::apply_transfer;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+
+    fn temp_tree() -> StateTree {
+        StateTree::new(NovaDB::open_temporary().unwrap())
+    }
+
+    fn hold(id: &str, amount: u64, expires_at: u64) -> AuthorizationHold {
+        AuthorizationHold {
+            hold_id: id.to_string(),
+            merchant: "nova1shop".to_string(),
+            amount,
+            captured: 0,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn hold_reduces_available_balance() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+
+        place_hold(&mut tree, "nova1alice", hold("h1", 6_000, 1_000)).unwrap();
+
+        let alice = tree.get("nova1alice").unwrap();
+        assert_eq!(alice.balance, 10_000);
+        assert_eq!(alice.available_balance(), 4_000);
+
+        // Held funds cannot be spent elsewhere.
+        // Placing the hold used nonce 1.
+        assert!(apply_transfer(&mut tree, "nova1alice", "nova1bob", 5_000, 0, 2).is_err());
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 4_000, 0, 2).unwrap();
+    }
+
+    #[test]
+    fn partial_capture_keeps_remainder_held() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        place_hold(&mut tree, "nova1alice", hold("h1", 6_000, 1_000)).unwrap();
+
+        let remaining = capture_hold(
+            &mut tree,
+            "nova1alice",
+            "nova1shop",
+            "h1",
+            2_500,
+            false,
+            500,
+        )
+        .unwrap();
+        assert_eq!(remaining, 3_500);
+
+        let alice = tree.get("nova1alice").unwrap();
+        assert_eq!(alice.balance, 7_500);
+        assert_eq!(alice.held_balance(), 3_500);
+        assert_eq!(tree.get("nova1shop").unwrap().balance, 2_500);
+
+        // Over-capture is refused.
+        assert!(capture_hold(
+            &mut tree,
+            "nova1alice",
+            "nova1shop",
+            "h1",
+            4_000,
+            false,
+            600
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn final_capture_releases_the_rest() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        place_hold(&mut tree, "nova1alice", hold("h1", 6_000, 1_000)).unwrap();
+
+        capture_hold(&mut tree, "nova1alice", "nova1shop", "h1", 1_000, true, 500).unwrap();
+
+        let alice = tree.get("nova1alice").unwrap();
+        assert!(alice.holds.is_empty());
+        assert_eq!(alice.available_balance(), 9_000);
+    }
+
+    #[test]
+    fn expired_hold_cannot_be_captured_and_is_released() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        place_hold(&mut tree, "nova1alice", hold("h1", 6_000, 1_000)).unwrap();
+
+        assert!(capture_hold(&mut tree, "nova1alice", "nova1shop", "h1", 1, false, 1_001).is_err());
+
+        let root_before = tree.root();
+        assert_eq!(release_expired_holds(&mut tree, "nova1alice", 999), 0);
+        assert_eq!(tree.root(), root_before, "no-op release must not write");
+
+        assert_eq!(release_expired_holds(&mut tree, "nova1alice", 1_001), 6_000);
+        assert_eq!(tree.get("nova1alice").unwrap().available_balance(), 10_000);
+    }
+
+    #[test]
+    fn only_the_named_merchant_may_capture() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        place_hold(&mut tree, "nova1alice", hold("h1", 6_000, 1_000)).unwrap();
+
+        let result = capture_hold(&mut tree, "nova1alice", "nova1mallory", "h1", 10, false, 0);
+        assert!(matches!(result, Err(StateError::HoldRejected(_))));
+    }
+
+    #[test]
+    fn hold_and_capture_transactions_round_trip() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+
+        let hold_tx = TransactionBuilder::new(TransactionType::AuthorizationHold)
+            .sender("nova1alice")
+            .receiver("nova1shop")
+            .amount(Amount::new(3_000, Currency::NOVA))
+            .nonce(1)
+            .timestamp(1_000)
+            .payload(
+                serde_json::to_vec(&HoldTerms {
+                    expires_at: 5_000,
+                    reference: "session-1".to_string(),
+                })
+                .unwrap(),
+            )
+            .build_unchecked();
+        apply_hold_transaction(&mut tree, &hold_tx, 1_000).unwrap();
+
+        let capture_tx = TransactionBuilder::new(TransactionType::HoldCapture)
+            .sender("nova1shop")
+            .receiver("nova1alice")
+            .amount(Amount::new(3_000, Currency::NOVA))
+            .nonce(1)
+            .timestamp(2_000)
+            .payload(
+                serde_json::to_vec(&CaptureTerms {
+                    hold_id: hold_tx.id.clone(),
+                    final_capture: false,
+                })
+                .unwrap(),
+            )
+            .build_unchecked();
+        apply_hold_transaction(&mut tree, &capture_tx, 2_000).unwrap();
+
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 7_000);
+        assert_eq!(tree.get("nova1shop").unwrap().balance, 3_000);
+        assert!(tree.get("nova1alice").unwrap().holds.is_empty());
+    }
+}
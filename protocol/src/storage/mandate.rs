@@ -0,0 +1,441 @@
+//! # Direct-Debit Mandates — Standing Approval for Pull Payments
+//!
+//! A mandate lets a payee (utility, subscription, landlord) pull funds from
+//! a payer without a fresh signature every month. The payer signs the
+//! mandate once, naming the payee, a ceiling per period, the period length
+//! and an expiry. The payee then submits pull transactions that reference
+//! the mandate; execution keeps a running total for the current period and
+//! refuses anything over the ceiling.
+//!
+//! The payer can revoke at any time, on-chain, with immediate effect.
+//!
+//! ## On-chain encoding
+//!
+//! | Tx type          | sender | receiver | amount | payload             |
+//! |------------------|--------|----------|--------|---------------------|
+//! | `MandateGrant`   | payer  | payee    | —      | [`MandateTerms`]    |
+//! | `MandatePull`    | payee  | payer    | pulled | [`MandateReference`]|
+//! | `MandateRevoke`  | payer  | payee    | —      | [`MandateReference`]|
+//!
+//! The mandate ID is the ID of the `MandateGrant` transaction. Periods are
+//! anchored at the grant's timestamp: period `n` covers
+//! `[start + n * period_ms, start + (n + 1) * period_ms)`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoding;
+use crate::transaction::builder::Transaction;
+use crate::transaction::types::TransactionType;
+
+use super::state::{check_nonce, StateError, StateTree};
+
+// ---------------------------------------------------------------------------
+// Mandate Record
+// ---------------------------------------------------------------------------
+
+/// Standing approval for `payee` to pull from the owning account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mandate {
+    /// ID of the transaction that granted the mandate.
+    pub mandate_id: String,
+    /// The only account allowed to pull under this mandate.
+    pub payee: String,
+    /// Ceiling on cumulative pulls within one period.
+    #[serde(with = "encoding::amount")]
+    pub max_per_period: u64,
+    /// Period length in milliseconds.
+    pub period_ms: u64,
+    /// Unix timestamp (milliseconds) the mandate was granted; anchors periods.
+    pub start: u64,
+    /// Unix timestamp (milliseconds) after which the mandate is void.
+    pub expires_at: u64,
+    /// Index of the period `pulled_in_period` refers to.
+    pub current_period: u64,
+    /// Amount pulled so far in `current_period`.
+    #[serde(with = "encoding::amount")]
+    pub pulled_in_period: u64,
+}
+
+impl Mandate {
+    /// Period index containing `now_ms`.
+    pub fn period_at(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.start) / self.period_ms.max(1)
+    }
+
+    /// Amount still pullable in the period containing `now_ms`.
+    pub fn remaining_at(&self, now_ms: u64) -> u64 {
+        if self.period_at(now_ms) == self.current_period {
+            self.max_per_period.saturating_sub(self.pulled_in_period)
+        } else {
+            self.max_per_period
+        }
+    }
+
+    /// Returns `true` once `now_ms` is past the expiry.
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms > self.expires_at
+    }
+}
+
+/// Payload of a `MandateGrant` transaction (JSON-encoded).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MandateTerms {
+    /// Ceiling on cumulative pulls within one period.
+    #[serde(with = "encoding::amount")]
+    pub max_per_period: u64,
+    /// Period length in milliseconds.
+    pub period_ms: u64,
+    /// Unix timestamp (milliseconds) after which the mandate is void.
+    pub expires_at: u64,
+}
+
+/// Payload of `MandatePull` and `MandateRevoke` transactions (JSON-encoded).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MandateReference {
+    /// The mandate being pulled against or revoked.
+    pub mandate_id: String,
+}
+
+// ---------------------------------------------------------------------------
+// State Transitions
+// ---------------------------------------------------------------------------
+
+/// Record a mandate on `payer`'s account. Bumps the payer's nonce.
+pub fn grant_mandate(
+    tree: &mut StateTree,
+    payer: &str,
+    mandate: Mandate,
+) -> Result<(), StateError> {
+    let mut state = tree.get(payer).unwrap_or_default();
+
+    if state.frozen {
+        return Err(StateError::AccountFrozen(payer.to_string()));
+    }
+    if mandate.period_ms == 0 || mandate.max_per_period == 0 {
+        return Err(StateError::MandateRejected(
+            "period and per-period limit must be non-zero".to_string(),
+        ));
+    }
+    if mandate.payee == payer {
+        return Err(StateError::MandateRejected(
+            "payer cannot mandate itself".to_string(),
+        ));
+    }
+    if state
+        .mandates
+        .iter()
+        .any(|m| m.mandate_id == mandate.mandate_id)
+    {
+        return Err(StateError::MandateRejected(format!(
+            "mandate {} already exists",
+            mandate.mandate_id
+        )));
+    }
+
+    state.mandates.push(mandate);
+    state.nonce += 1;
+    tree.put(payer, &state);
+    Ok(())
+}
+
+/// Pull `amount` from `payer` to `payee` under a mandate.
+///
+/// Enforces the per-period ceiling and the payer's available balance.
+/// Bumps the payee's nonce (they sign the pull). Returns the amount still
+/// pullable in the current period.
+pub fn pull_under_mandate(
+    tree: &mut StateTree,
+    payer: &str,
+    payee: &str,
+    mandate_id: &str,
+    amount: u64,
+    now_ms: u64,
+) -> Result<u64, StateError> {
+    let mut payer_state = tree.get(payer).unwrap_or_default();
+
+    if payer_state.frozen {
+        return Err(StateError::AccountFrozen(payer.to_string()));
+    }
+    let available = payer_state.available_balance();
+
+    let mandate = payer_state
+        .mandates
+        .iter_mut()
+        .find(|m| m.mandate_id == mandate_id)
+        .ok_or_else(|| StateError::MandateRejected(format!("mandate {} not found", mandate_id)))?;
+
+    if mandate.payee != payee {
+        return Err(StateError::MandateRejected(format!(
+            "mandate {} belongs to a different payee",
+            mandate_id
+        )));
+    }
+    if mandate.is_expired(now_ms) {
+        return Err(StateError::MandateRejected(format!(
+            "mandate {} expired at {}",
+            mandate_id, mandate.expires_at
+        )));
+    }
+
+    let remaining = mandate.remaining_at(now_ms);
+    if amount > remaining {
+        return Err(StateError::MandateRejected(format!(
+            "pull of {} exceeds remaining period limit {}",
+            amount, remaining
+        )));
+    }
+    if amount > available {
+        return Err(StateError::InsufficientBalance {
+            have: available,
+            need: amount,
+        });
+    }
+
+    let period = mandate.period_at(now_ms);
+    if period != mandate.current_period {
+        mandate.current_period = period;
+        mandate.pulled_in_period = 0;
+    }
+    mandate.pulled_in_period += amount;
+    let left = remaining - amount;
+
+    payer_state.balance -= amount;
+    tree.put(payer, &payer_state);
+
+    let mut payee_state = tree.get(payee).unwrap_or_default();
+    payee_state.balance += amount;
+    payee_state.nonce += 1;
+    tree.put(payee, &payee_state);
+
+    Ok(left)
+}
+
+/// Remove a mandate from `payer`'s account. Bumps the payer's nonce.
+pub fn revoke_mandate(
+    tree: &mut StateTree,
+    payer: &str,
+    mandate_id: &str,
+) -> Result<(), StateError> {
+    let mut state = tree.get(payer).unwrap_or_default();
+
+    let idx = state
+        .mandates
+        .iter()
+        .position(|m| m.mandate_id == mandate_id)
+        .ok_or_else(|| StateError::MandateRejected(format!("mandate {} not found", mandate_id)))?;
+
+    state.mandates.remove(idx);
+    state.nonce += 1;
+    tree.put(payer, &state);
+    Ok(())
+}
+
+/// Execute a `MandateGrant`, `MandatePull` or `MandateRevoke` transaction.
+///
+/// Shared by the block producer and the sync engine. `tx.nonce` must be
+/// the signer's next nonce. Other transaction types are ignored.
+pub fn apply_mandate_transaction(tree: &mut StateTree, tx: &Transaction) -> Result<(), StateError> {
+    if matches!(
+        tx.tx_type,
+        TransactionType::MandateGrant
+            | TransactionType::MandatePull
+            | TransactionType::MandateRevoke
+    ) {
+        check_nonce(tree, &tx.sender, tx.nonce)?;
+    }
+    let payload = tx.payload.as_deref().unwrap_or_default();
+
+    match tx.tx_type {
+        TransactionType::MandateGrant => {
+            let terms: MandateTerms = serde_json::from_slice(payload)
+                .map_err(|e| StateError::Serialization(format!("mandate terms: {}", e)))?;
+            if terms.expires_at <= tx.timestamp {
+                return Err(StateError::MandateRejected(
+                    "mandate expires before it is granted".to_string(),
+                ));
+            }
+            grant_mandate(
+                tree,
+                &tx.sender,
+                Mandate {
+                    mandate_id: tx.id.clone(),
+                    payee: tx.receiver.clone(),
+                    max_per_period: terms.max_per_period,
+                    period_ms: terms.period_ms,
+                    start: tx.timestamp,
+                    expires_at: terms.expires_at,
+                    current_period: 0,
+                    pulled_in_period: 0,
+                },
+            )
+        }
+        TransactionType::MandatePull => {
+            let reference: MandateReference = serde_json::from_slice(payload)
+                .map_err(|e| StateError::Serialization(format!("mandate reference: {}", e)))?;
+            pull_under_mandate(
+                tree,
+                &tx.receiver,
+                &tx.sender,
+                &reference.mandate_id,
+                tx.amount.value,
+                tx.timestamp,
+            )
+            .map(|_| ())
+        }
+        TransactionType::MandateRevoke => {
+            let reference: MandateReference = serde_json::from_slice(payload)
+                .map_err(|e| StateError::Serialization(format!("mandate reference: {}", e)))?;
+            revoke_mandate(tree, &tx.sender, &reference.mandate_id)
+        }
+        _ => Ok(()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::state::AccountState;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+    fn temp_tree() -> StateTree {
+        StateTree::new(NovaDB::open_temporary().unwrap())
+    }
+
+    fn utility_mandate() -> Mandate {
+        Mandate {
+            mandate_id: "m1".to_string(),
+            payee: "nova1power".to_string(),
+            max_per_period: 5_000,
+            period_ms: 30 * DAY_MS,
+            start: 0,
+            expires_at: 365 * DAY_MS,
+            current_period: 0,
+            pulled_in_period: 0,
+        }
+    }
+
+    fn funded_tree() -> StateTree {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(100_000));
+        grant_mandate(&mut tree, "nova1alice", utility_mandate()).unwrap();
+        tree
+    }
+
+    #[test]
+    fn pulls_accumulate_within_a_period() {
+        let mut tree = funded_tree();
+
+        let left =
+            pull_under_mandate(&mut tree, "nova1alice", "nova1power", "m1", 3_000, DAY_MS).unwrap();
+        assert_eq!(left, 2_000);
+
+        let over = pull_under_mandate(&mut tree, "nova1alice", "nova1power", "m1", 2_001, DAY_MS);
+        assert!(matches!(over, Err(StateError::MandateRejected(_))));
+
+        pull_under_mandate(&mut tree, "nova1alice", "nova1power", "m1", 2_000, DAY_MS).unwrap();
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 95_000);
+        assert_eq!(tree.get("nova1power").unwrap().balance, 5_000);
+    }
+
+    #[test]
+    fn limit_resets_next_period() {
+        let mut tree = funded_tree();
+        pull_under_mandate(&mut tree, "nova1alice", "nova1power", "m1", 5_000, DAY_MS).unwrap();
+
+        let next_period = 31 * DAY_MS;
+        pull_under_mandate(
+            &mut tree,
+            "nova1alice",
+            "nova1power",
+            "m1",
+            5_000,
+            next_period,
+        )
+        .unwrap();
+
+        let mandate = tree.get("nova1alice").unwrap().mandates[0].clone();
+        assert_eq!(mandate.current_period, 1);
+        assert_eq!(mandate.pulled_in_period, 5_000);
+    }
+
+    #[test]
+    fn only_the_payee_may_pull() {
+        let mut tree = funded_tree();
+        let result = pull_under_mandate(&mut tree, "nova1alice", "nova1mallory", "m1", 1, DAY_MS);
+        assert!(matches!(result, Err(StateError::MandateRejected(_))));
+    }
+
+    #[test]
+    fn expired_mandate_rejects_pulls() {
+        let mut tree = funded_tree();
+        let result =
+            pull_under_mandate(&mut tree, "nova1alice", "nova1power", "m1", 1, 366 * DAY_MS);
+        assert!(matches!(result, Err(StateError::MandateRejected(_))));
+    }
+
+    #[test]
+    fn revoked_mandate_rejects_pulls() {
+        let mut tree = funded_tree();
+        revoke_mandate(&mut tree, "nova1alice", "m1").unwrap();
+
+        let result = pull_under_mandate(&mut tree, "nova1alice", "nova1power", "m1", 1, DAY_MS);
+        assert!(result.is_err());
+        assert!(tree.get("nova1alice").unwrap().mandates.is_empty());
+    }
+
+    #[test]
+    fn mandate_transactions_round_trip() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(100_000));
+
+        let grant = TransactionBuilder::new(TransactionType::MandateGrant)
+            .sender("nova1alice")
+            .receiver("nova1power")
+            .nonce(1)
+            .timestamp(1_000)
+            .payload(
+                serde_json::to_vec(&MandateTerms {
+                    max_per_period: 5_000,
+                    period_ms: 30 * DAY_MS,
+                    expires_at: 365 * DAY_MS,
+                })
+                .unwrap(),
+            )
+            .build_unchecked();
+        apply_mandate_transaction(&mut tree, &grant).unwrap();
+
+        let reference = serde_json::to_vec(&MandateReference {
+            mandate_id: grant.id.clone(),
+        })
+        .unwrap();
+
+        let pull = TransactionBuilder::new(TransactionType::MandatePull)
+            .sender("nova1power")
+            .receiver("nova1alice")
+            .amount(Amount::new(4_000, Currency::NOVA))
+            .nonce(1)
+            .timestamp(2_000)
+            .payload(reference.clone())
+            .build_unchecked();
+        apply_mandate_transaction(&mut tree, &pull).unwrap();
+        assert_eq!(tree.get("nova1power").unwrap().balance, 4_000);
+
+        let revoke = TransactionBuilder::new(TransactionType::MandateRevoke)
+            .sender("nova1alice")
+            .receiver("nova1power")
+            .nonce(2)
+            .timestamp(3_000)
+            .payload(reference)
+            .build_unchecked();
+        apply_mandate_transaction(&mut tree, &revoke).unwrap();
+        assert!(tree.get("nova1alice").unwrap().mandates.is_empty());
+    }
+}
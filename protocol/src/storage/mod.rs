@@ -8,9 +8,19 @@
 //!
 //! ```text
 //! block.rs  — Block structure, genesis block, hash/verify operations
+//! batch.rs  — Batch payouts (many recipients, all-or-nothing)
 //! state.rs  — Sparse Merkle Tree for account state (256-bit keyspace, BLAKE3)
 //! chain.rs  — In-memory chain management with validation
+//! confidential.rs — Confidential transfers between committed balances
 //! db.rs     — sled-backed persistence with separate trees per data type
+//! fee.rs    — Fee settlement (proposer share, burn) and value conservation
+//! emission.rs — Monetary policy, block rewards and the supply record
+//! genesis.rs — Network parameters fixed at genesis
+//! hold.rs   — Authorization holds (authorize now, capture later)
+//! mandate.rs — Direct-debit mandates (standing approval for pull payments)
+//! snapshot.rs — Committed-state read view for API queries
+//! spending.rs — Spending policies (daily limits, allow-listed recipients)
+//! audit.rs  — Hash-chained audit log of privileged node operations
 //! ```
 //!
 //! ## Data Flow
@@ -39,12 +49,55 @@
 //! 3. **Bincode for on-disk serialization.** Compact, fast, deterministic.
 //!    JSON is for APIs and debugging; bincode is for storage.
 
+pub mod audit;
+pub mod batch;
 pub mod block;
 pub mod chain;
+#[cfg(feature = "zkp")]
+pub mod confidential;
 pub mod db;
+pub mod emission;
+pub mod fee;
+pub mod genesis;
+pub mod hold;
+pub mod mandate;
+pub mod snapshot;
+pub mod spending;
 pub mod state;
 
-pub use block::{Block, BlockHeader};
+pub use audit::{AuditCategory, AuditEntry, AuditError, AuditExport, AuditHead, AuditLog};
+pub use batch::{apply_batch, batch_payouts, batch_recipients, BatchEntry, BatchPayout};
+pub use block::{
+    BaseFee, Block, BlockHeader, CommitSignature, HeaderExtension, HeaderExtensions, NullifierRoot,
+    ReceiptsRoot, MAX_HEADER_VERSION,
+};
 pub use chain::Chain;
+#[cfg(feature = "zkp")]
+pub use confidential::{apply_confidential_transfer, committed_balance, native_commitment_key};
 pub use db::{DbError, DbResult, NovaDB};
-pub use state::{apply_transfer, AccountState, MerkleProof, StateError, StateTree};
+pub use emission::{
+    apply_emission, fund_genesis_account, MonetaryPolicy, SupplyAudit, SupplyRecord,
+    SUPPLY_STATE_KEY,
+};
+pub use fee::{
+    charge_fee, credit_treasury, fee_charged, pays_own_fee, settle_fees, BalanceAudit, FeePolicy,
+    FeeSplit,
+};
+pub use genesis::{GenesisConfig, GenesisError};
+pub use hold::{
+    apply_hold_transaction, capture_hold, place_hold, release_expired_holds, AuthorizationHold,
+    CaptureTerms, HoldTerms,
+};
+pub use mandate::{
+    apply_mandate_transaction, grant_mandate, pull_under_mandate, revoke_mandate, Mandate,
+    MandateReference, MandateTerms,
+};
+pub use snapshot::{CommittedState, StateSnapshot};
+pub use spending::{
+    apply_spending_policy_update, check_spending_policy, record_spending, update_spending_policy,
+    PendingPolicy, PolicyState, SpendingPolicy,
+};
+pub use state::{
+    apply_sponsored_transfer, apply_transfer, bump_nonce, check_nonce, reap_if_dust, AccountState,
+    MerkleProof, StateError, StateTree, DUST_POOL_STATE_KEY,
+};
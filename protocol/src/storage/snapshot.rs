@@ -0,0 +1,224 @@
+//! # Committed State Snapshots
+//!
+//! The state tree is updated in place while a block executes, so a reader
+//! sharing it with the producer can observe a block halfway through — the
+//! sender already debited, the recipient not yet credited. API queries must
+//! only ever see state as of the last committed block.
+//!
+//! ## How It Works
+//!
+//! The SMT stores nodes by position, so older roots cannot be read back
+//! from sled once overwritten. Instead, a [`CommittedState`] handle keeps a
+//! journal of pre-images: the first time a leaf is written after a commit,
+//! its committed value is recorded before the write lands. Readers consult
+//! the journal first and fall through to sled for everything untouched.
+//!
+//! ```text
+//!   producer                    CommittedState                 API
+//!   ────────                    ──────────────                 ───
+//!   put(alice)  ──► journal[alice] = old value
+//!   put(bob)    ──► journal[bob]   = old value
+//!                                               ◄── get(alice) → old value
+//!   commit(h)   ──► height = h, root = R, journal cleared
+//!                                               ◄── get(alice) → new value
+//! ```
+//!
+//! Journal updates and leaf writes happen under the same lock readers take,
+//! and `commit` clears the journal and advances the height in one step, so
+//! every [`StateSnapshot`] corresponds exactly to one committed root.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::db::NovaDB;
+use super::state::{AccountState, StateTree};
+
+/// Pre-images of leaves written since the last commit.
+#[derive(Debug, Default)]
+pub(crate) struct Journal {
+    height: u64,
+    root: [u8; 32],
+    /// Committed raw value per key; `None` if the key did not exist.
+    preimages: HashMap<String, Option<Vec<u8>>>,
+}
+
+/// Read view of the state tree as of the last committed block.
+///
+/// Attach it to the live tree with [`StateTree::with_committed_state`] and
+/// advance it with [`StateTree::commit`] once a block is durable.
+pub struct CommittedState {
+    /// Reads untouched leaves straight from sled.
+    reader: StateTree,
+    journal: RwLock<Journal>,
+}
+
+impl std::fmt::Debug for CommittedState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let journal = self.journal.read();
+        f.debug_struct("CommittedState")
+            .field("height", &journal.height)
+            .field("root", &hex::encode(journal.root))
+            .field("pending_writes", &journal.preimages.len())
+            .finish()
+    }
+}
+
+impl CommittedState {
+    /// Creates a handle over `db` whose current contents are committed at
+    /// `height` with state root `root`.
+    pub fn new(db: NovaDB, height: u64, root: [u8; 32]) -> Arc<Self> {
+        Arc::new(Self {
+            reader: StateTree::new(db),
+            journal: RwLock::new(Journal {
+                height,
+                root,
+                preimages: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Takes a consistent snapshot. Commits wait until it is dropped, so
+    /// keep it short-lived.
+    pub fn snapshot(&self) -> StateSnapshot<'_> {
+        StateSnapshot {
+            reader: &self.reader,
+            journal: self.journal.read(),
+        }
+    }
+
+    /// Height of the last committed block.
+    pub fn height(&self) -> u64 {
+        self.journal.read().height
+    }
+
+    /// State root of the last committed block.
+    pub fn root(&self) -> [u8; 32] {
+        self.journal.read().root
+    }
+
+    /// Committed account state for `address`.
+    pub fn get(&self, address: &str) -> Option<AccountState> {
+        self.snapshot().get(address)
+    }
+
+    /// Keys written since the last commit, each with its committed raw
+    /// value (`None` if it did not exist yet), in key order.
+    pub fn uncommitted(&self) -> Vec<(String, Option<Vec<u8>>)> {
+        let journal = self.journal.read();
+        let mut writes: Vec<_> = journal
+            .preimages
+            .iter()
+            .map(|(key, preimage)| (key.clone(), preimage.clone()))
+            .collect();
+        writes.sort_by(|a, b| a.0.cmp(&b.0));
+        writes
+    }
+
+    /// Marks everything written so far as committed at `height`.
+    pub fn commit(&self, height: u64, root: [u8; 32]) {
+        let mut journal = self.journal.write();
+        journal.height = height;
+        journal.root = root;
+        journal.preimages.clear();
+    }
+
+    /// Locks the journal for a write to `key`, recording its committed
+    /// value first if this is the first write since the last commit. The
+    /// caller performs the write while holding the returned guard.
+    pub(crate) fn begin_write(
+        &self,
+        key: &str,
+        current: impl FnOnce() -> Option<Vec<u8>>,
+    ) -> RwLockWriteGuard<'_, Journal> {
+        let mut journal = self.journal.write();
+        if !journal.preimages.contains_key(key) {
+            journal.preimages.insert(key.to_string(), current());
+        }
+        journal
+    }
+}
+
+/// A consistent view of committed state, pinned to one height.
+pub struct StateSnapshot<'a> {
+    reader: &'a StateTree,
+    journal: RwLockReadGuard<'a, Journal>,
+}
+
+impl StateSnapshot<'_> {
+    /// Height of the block this snapshot reflects.
+    pub fn height(&self) -> u64 {
+        self.journal.height
+    }
+
+    /// State root of the block this snapshot reflects.
+    pub fn root(&self) -> [u8; 32] {
+        self.journal.root
+    }
+
+    /// Committed account state for `address`.
+    pub fn get(&self, address: &str) -> Option<AccountState> {
+        self.get_raw(address)
+            .and_then(|bytes| AccountState::from_bytes(&bytes))
+    }
+
+    /// Committed raw leaf value under `key`.
+    pub fn get_raw(&self, key: &str) -> Option<Vec<u8>> {
+        match self.journal.preimages.get(key) {
+            Some(preimage) => preimage.clone(),
+            None => self.reader.get_raw(key),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracked_tree() -> (StateTree, Arc<CommittedState>) {
+        let db = NovaDB::open_temporary().unwrap();
+        let tree = StateTree::new(db.clone());
+        let committed = CommittedState::new(db, 0, tree.root());
+        (tree.with_committed_state(Arc::clone(&committed)), committed)
+    }
+
+    #[test]
+    fn uncommitted_writes_are_invisible() {
+        let (mut tree, committed) = tracked_tree();
+        tree.put("nova1alice", &AccountState::with_balance(100));
+        tree.commit(1);
+
+        tree.put("nova1alice", &AccountState::with_balance(40));
+        tree.put("nova1bob", &AccountState::with_balance(60));
+
+        let snap = committed.snapshot();
+        assert_eq!(snap.height(), 1);
+        assert_eq!(snap.get("nova1alice").unwrap().balance, 100);
+        assert!(snap.get("nova1bob").is_none());
+        drop(snap);
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 40);
+
+        tree.commit(2);
+        assert_eq!(committed.height(), 2);
+        assert_eq!(committed.root(), tree.root());
+        assert_eq!(committed.get("nova1alice").unwrap().balance, 40);
+        assert_eq!(committed.get("nova1bob").unwrap().balance, 60);
+    }
+
+    #[test]
+    fn first_write_since_commit_wins_the_journal() {
+        let (mut tree, committed) = tracked_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10));
+        tree.commit(1);
+
+        for balance in [20, 30, 40] {
+            tree.put("nova1alice", &AccountState::with_balance(balance));
+        }
+        assert_eq!(committed.get("nova1alice").unwrap().balance, 10);
+    }
+}
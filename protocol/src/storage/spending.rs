@@ -0,0 +1,541 @@
+//! # Spending Policies — Programmable Controls on Outgoing Value
+//!
+//! A corporate treasury wants rules its signing key cannot break on its
+//! own: no more than so much per day, and only to these counterparties.
+//! An account registers a [`SpendingPolicy`] on-chain and every block
+//! producer and syncing node enforces it when executing the account's
+//! outgoing transactions — no contract VM involved.
+//!
+//! ## On-chain encoding
+//!
+//! | Tx type                 | sender  | receiver | amount | payload            |
+//! |-------------------------|---------|----------|--------|--------------------|
+//! | `SpendingPolicyUpdate`  | account | account  | —      | [`SpendingPolicy`] |
+//!
+//! The policy is JSON-encoded and replaces the previous one. An empty
+//! policy removes it.
+//!
+//! ## What is checked
+//!
+//! | Tx type                | Spending account | Recipients       | Counts towards the limit |
+//! |------------------------|------------------|------------------|--------------------------|
+//! | `Transfer`             | sender           | receiver         | amount                   |
+//! | `Batch`                | sender           | every payout     | total                    |
+//! | `AuthorizationHold`    | sender           | merchant         | held amount              |
+//! | `MandateGrant`         | sender           | payee            | —                        |
+//! | `MandatePull`          | payer (receiver) | payee (sender)   | pulled amount            |
+//! | `ConfidentialTransfer` | sender           | receiver         | refused under a NOVA limit, the amount is hidden |
+//!
+//! Daily limits are per currency and reset at 00:00 UTC of the block
+//! timestamp. A hold counts when placed; releasing it does not give the
+//! allowance back. Fees never count. Sending to oneself is not spending.
+//!
+//! Verification is stateless, so it cannot see a policy: a violating
+//! transaction passes [`verify_transaction`](crate::transaction::verify_transaction)
+//! and fails at execution, like one the sender cannot afford.
+//!
+//! ## Changing a policy
+//!
+//! A compromised key must not be able to lift its own limits. A
+//! replacement that is at least as strict as the policy in force applies
+//! immediately; anything looser waits [`POLICY_CHANGE_DELAY_MS`] (24
+//! hours), during which the account holder can replace it again.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::transaction::builder::Transaction;
+use crate::transaction::types::{Amount, Currency, TransactionType};
+
+use super::batch::batch_recipients;
+use super::state::{check_nonce, StateError, StateTree};
+
+/// How long a loosening policy change waits before taking effect.
+pub const POLICY_CHANGE_DELAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Most recipients a policy may allow-list, to bound account state.
+pub const MAX_POLICY_RECIPIENTS: usize = 64;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+// ---------------------------------------------------------------------------
+// Policy
+// ---------------------------------------------------------------------------
+
+/// Rules on an account's outgoing value. Payload of a
+/// `SpendingPolicyUpdate` transaction (JSON-encoded).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendingPolicy {
+    /// Ceiling on value sent per UTC day, at most one per currency.
+    /// Currencies without an entry are unlimited.
+    #[serde(default)]
+    pub daily_limits: Vec<Amount>,
+    /// If non-empty, the only accounts value may be sent to.
+    #[serde(default)]
+    pub allowed_recipients: BTreeSet<String>,
+}
+
+impl SpendingPolicy {
+    /// Returns `true` if the policy restricts nothing.
+    pub fn is_empty(&self) -> bool {
+        self.daily_limits.is_empty() && self.allowed_recipients.is_empty()
+    }
+
+    /// The daily ceiling for `currency`, if there is one.
+    pub fn daily_limit(&self, currency: &Currency) -> Option<u64> {
+        self.daily_limits
+            .iter()
+            .find(|limit| limit.currency == *currency)
+            .map(|limit| limit.value)
+    }
+
+    /// Returns `true` if value may be sent to `address`.
+    pub fn allows_recipient(&self, address: &str) -> bool {
+        self.allowed_recipients.is_empty() || self.allowed_recipients.contains(address)
+    }
+
+    /// Returns `true` if everything `self` permits, `other` permits too.
+    pub fn is_at_least_as_strict_as(&self, other: &SpendingPolicy) -> bool {
+        let limits = other.daily_limits.iter().all(|limit| {
+            self.daily_limit(&limit.currency)
+                .is_some_and(|own| own <= limit.value)
+        });
+        let recipients = if self.allowed_recipients.is_empty() {
+            other.allowed_recipients.is_empty()
+        } else {
+            other.allowed_recipients.is_empty()
+                || self.allowed_recipients.is_subset(&other.allowed_recipients)
+        };
+        limits && recipients
+    }
+
+    fn validate(&self, owner: &str) -> Result<(), StateError> {
+        let reject = |reason: String| Err(StateError::PolicyRejected(reason));
+
+        let mut currencies = BTreeSet::new();
+        for limit in &self.daily_limits {
+            if !currencies.insert(limit.currency.to_string()) {
+                return reject(format!("{} has more than one daily limit", limit.currency));
+            }
+        }
+        if self.allowed_recipients.len() > MAX_POLICY_RECIPIENTS {
+            return reject(format!(
+                "{} allowed recipients, the limit is {}",
+                self.allowed_recipients.len(),
+                MAX_POLICY_RECIPIENTS
+            ));
+        }
+        if self.allowed_recipients.contains(owner) {
+            return reject("the account cannot allow-list itself".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A replacement policy waiting out [`POLICY_CHANGE_DELAY_MS`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingPolicy {
+    /// The policy that will take over.
+    pub policy: SpendingPolicy,
+    /// Unix timestamp (milliseconds) from which it applies.
+    pub effective_at: u64,
+}
+
+/// The policy on an account, with the day's running totals. Kept in
+/// [`AccountState::spending_policy`](super::state::AccountState::spending_policy).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyState {
+    /// The policy in force.
+    pub policy: SpendingPolicy,
+    /// A looser replacement that has not taken effect yet.
+    #[serde(default)]
+    pub pending: Option<PendingPolicy>,
+    /// UTC day (days since the Unix epoch) `spent_today` refers to.
+    pub day: u64,
+    /// Value sent so far on `day`, per limited currency.
+    #[serde(default)]
+    pub spent_today: Vec<Amount>,
+}
+
+impl PolicyState {
+    /// The state as seen at `now_ms`: a pending policy whose delay has
+    /// passed is in force, and a new day starts with nothing spent.
+    pub fn at(&self, now_ms: u64) -> PolicyState {
+        let mut state = self.clone();
+        if let Some(pending) = state.pending.take() {
+            if pending.effective_at <= now_ms {
+                state.policy = pending.policy;
+            } else {
+                state.pending = Some(pending);
+            }
+        }
+        let day = now_ms / DAY_MS;
+        if day != state.day {
+            state.day = day;
+            state.spent_today.clear();
+        }
+        state
+    }
+
+    /// Value sent in `currency` on the current day.
+    pub fn spent(&self, currency: &Currency) -> u64 {
+        self.spent_today
+            .iter()
+            .find(|spent| spent.currency == *currency)
+            .map_or(0, |spent| spent.value)
+    }
+
+    /// What is left of the daily limit for `currency`, or `None` if the
+    /// currency is unlimited.
+    pub fn remaining(&self, currency: &Currency) -> Option<u64> {
+        self.policy
+            .daily_limit(currency)
+            .map(|limit| limit.saturating_sub(self.spent(currency)))
+    }
+
+    /// Returns `true` once nothing is restricted or about to be.
+    fn is_lifted(&self) -> bool {
+        self.policy.is_empty() && self.pending.is_none()
+    }
+
+    fn add_spent(&mut self, amount: &Amount) {
+        if self.policy.daily_limit(&amount.currency).is_none() {
+            return;
+        }
+        match self
+            .spent_today
+            .iter_mut()
+            .find(|spent| spent.currency == amount.currency)
+        {
+            Some(spent) => spent.value = spent.value.saturating_add(amount.value),
+            None => self.spent_today.push(amount.clone()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Outflows
+// ---------------------------------------------------------------------------
+
+/// Value a transaction sends out of an account, as a policy sees it.
+struct Outflow<'a> {
+    account: &'a str,
+    recipients: Vec<String>,
+    /// `None` when the amount is hidden.
+    amount: Option<Amount>,
+}
+
+fn outflow(tx: &Transaction) -> Option<Outflow<'_>> {
+    let (account, recipients, amount) = match tx.tx_type {
+        TransactionType::Transfer | TransactionType::AuthorizationHold => (
+            tx.sender.as_str(),
+            vec![tx.receiver.clone()],
+            Some(tx.amount.clone()),
+        ),
+        TransactionType::Batch => (
+            tx.sender.as_str(),
+            batch_recipients(tx),
+            Some(tx.amount.clone()),
+        ),
+        TransactionType::MandateGrant => (tx.sender.as_str(), vec![tx.receiver.clone()], None),
+        TransactionType::MandatePull => (
+            tx.receiver.as_str(),
+            vec![tx.sender.clone()],
+            Some(tx.amount.clone()),
+        ),
+        TransactionType::ConfidentialTransfer => {
+            (tx.sender.as_str(), vec![tx.receiver.clone()], None)
+        }
+        _ => return None,
+    };
+    if recipients.iter().all(|recipient| recipient == account) {
+        return None;
+    }
+    Some(Outflow {
+        account,
+        recipients,
+        amount,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Enforcement
+// ---------------------------------------------------------------------------
+
+/// Checks `tx` against the policy of the account it spends from, as of
+/// the block timestamp `now_ms`. Changes nothing.
+///
+/// Shared by the block producer and the sync engine, which call it before
+/// executing `tx` and call [`record_spending`] once it succeeded.
+pub fn check_spending_policy(
+    tree: &StateTree,
+    tx: &Transaction,
+    now_ms: u64,
+) -> Result<(), StateError> {
+    let Some(outflow) = outflow(tx) else {
+        return Ok(());
+    };
+    let Some(state) = tree
+        .get(outflow.account)
+        .and_then(|account| account.spending_policy)
+    else {
+        return Ok(());
+    };
+    let state = state.at(now_ms);
+    let violated = |reason: String| Err(StateError::PolicyViolated(reason));
+
+    if let Some(recipient) = outflow
+        .recipients
+        .iter()
+        .find(|recipient| !state.policy.allows_recipient(recipient))
+    {
+        return violated(format!("{} is not an allowed recipient", recipient));
+    }
+    match (tx.tx_type, &outflow.amount) {
+        (TransactionType::ConfidentialTransfer, _)
+            if state.policy.daily_limit(&Currency::NOVA).is_some() =>
+        {
+            violated("a confidential transfer's amount cannot be held to a NOVA limit".into())
+        }
+        (_, Some(amount)) => match state.remaining(&amount.currency) {
+            Some(remaining) if amount.value > remaining => violated(format!(
+                "{} {} exceeds the {} left of today's limit",
+                amount.value, amount.currency, remaining
+            )),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Adds an executed `tx` to the daily total of the account it spent from.
+pub fn record_spending(tree: &mut StateTree, tx: &Transaction, now_ms: u64) {
+    let Some(outflow) = outflow(tx) else {
+        return;
+    };
+    let Some(mut account) = tree.get(outflow.account) else {
+        return;
+    };
+    let Some(state) = account.spending_policy.as_ref() else {
+        return;
+    };
+    let mut state = state.at(now_ms);
+    if let Some(amount) = &outflow.amount {
+        state.add_spent(amount);
+    }
+    account.spending_policy = (!state.is_lifted()).then_some(state);
+    tree.put(outflow.account, &account);
+}
+
+// ---------------------------------------------------------------------------
+// State Transition
+// ---------------------------------------------------------------------------
+
+/// Install `policy` on `account` at `now_ms`, immediately if it is at
+/// least as strict as the policy in force and after
+/// [`POLICY_CHANGE_DELAY_MS`] otherwise. Bumps the account's nonce.
+pub fn update_spending_policy(
+    tree: &mut StateTree,
+    account: &str,
+    policy: SpendingPolicy,
+    now_ms: u64,
+) -> Result<(), StateError> {
+    policy.validate(account)?;
+    let mut state = tree.get(account).unwrap_or_default();
+    if state.frozen {
+        return Err(StateError::AccountFrozen(account.to_string()));
+    }
+
+    let current = state
+        .spending_policy
+        .map(|current| current.at(now_ms))
+        .unwrap_or_else(|| PolicyState {
+            day: now_ms / DAY_MS,
+            ..PolicyState::default()
+        });
+    let next = if policy.is_at_least_as_strict_as(&current.policy) {
+        PolicyState {
+            policy,
+            pending: None,
+            ..current
+        }
+    } else {
+        PolicyState {
+            pending: Some(PendingPolicy {
+                policy,
+                effective_at: now_ms.saturating_add(POLICY_CHANGE_DELAY_MS),
+            }),
+            ..current
+        }
+    };
+    state.spending_policy = (!next.is_lifted()).then_some(next);
+    state.nonce += 1;
+    tree.put(account, &state);
+    Ok(())
+}
+
+/// Execute a `SpendingPolicyUpdate` transaction in a block stamped
+/// `now_ms`.
+///
+/// Shared by the block producer and the sync engine. `tx.nonce` must be
+/// the sender's next nonce. Other transaction types are ignored.
+pub fn apply_spending_policy_update(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    now_ms: u64,
+) -> Result<(), StateError> {
+    if tx.tx_type != TransactionType::SpendingPolicyUpdate {
+        return Ok(());
+    }
+    check_nonce(tree, &tx.sender, tx.nonce)?;
+    if tx.receiver != tx.sender {
+        return Err(StateError::PolicyRejected(
+            "a policy update must be addressed to the account itself".to_string(),
+        ));
+    }
+    let policy: SpendingPolicy = serde_json::from_slice(tx.payload.as_deref().unwrap_or_default())
+        .map_err(|e| StateError::Serialization(format!("spending policy: {}", e)))?;
+    update_spending_policy(tree, &tx.sender, policy, now_ms)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db::NovaDB;
+    use crate::storage::state::AccountState;
+    use crate::transaction::builder::TransactionBuilder;
+
+    const NOON: u64 = 20_000 * DAY_MS + DAY_MS / 2;
+
+    fn temp_tree() -> StateTree {
+        StateTree::new(NovaDB::open_temporary().unwrap())
+    }
+
+    fn treasury_policy() -> SpendingPolicy {
+        SpendingPolicy {
+            daily_limits: vec![Amount::new(10_000, Currency::NOVA)],
+            allowed_recipients: ["nova1payroll", "nova1supplier"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    fn transfer(to: &str, amount: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1treasury")
+            .receiver(to)
+            .amount(Amount::new(amount, Currency::NOVA))
+            .nonce(2)
+            .timestamp(NOON)
+            .build_unchecked()
+    }
+
+    fn governed_tree() -> StateTree {
+        let mut tree = temp_tree();
+        tree.put("nova1treasury", &AccountState::with_balance(1_000_000));
+        update_spending_policy(&mut tree, "nova1treasury", treasury_policy(), NOON).unwrap();
+        tree
+    }
+
+    #[test]
+    fn limits_and_recipients_are_enforced_per_day() {
+        let mut tree = governed_tree();
+
+        let stranger = transfer("nova1mallory", 1);
+        assert!(matches!(
+            check_spending_policy(&tree, &stranger, NOON),
+            Err(StateError::PolicyViolated(_))
+        ));
+
+        let payroll = transfer("nova1payroll", 7_000);
+        check_spending_policy(&tree, &payroll, NOON).unwrap();
+        record_spending(&mut tree, &payroll, NOON);
+
+        let supplier = transfer("nova1supplier", 3_001);
+        assert!(check_spending_policy(&tree, &supplier, NOON + 1).is_err());
+        let supplier = transfer("nova1supplier", 3_000);
+        check_spending_policy(&tree, &supplier, NOON + 1).unwrap();
+
+        // Tokens without a limit only face the allow-list.
+        let mut tokens = transfer("nova1supplier", 1_000_000);
+        tokens.amount.currency = Currency::BRL;
+        check_spending_policy(&tree, &tokens, NOON).unwrap();
+
+        // The next UTC day starts over.
+        let tomorrow = NOON + DAY_MS;
+        let big = transfer("nova1supplier", 10_000);
+        check_spending_policy(&tree, &big, tomorrow).unwrap();
+
+        // Accounts without a policy are untouched.
+        let mut other = transfer("nova1mallory", 1_000_000);
+        other.sender = "nova1someone".to_string();
+        check_spending_policy(&tree, &other, NOON).unwrap();
+    }
+
+    #[test]
+    fn loosening_waits_out_the_delay() {
+        let mut tree = governed_tree();
+        let open = SpendingPolicy::default();
+
+        update_spending_policy(&mut tree, "nova1treasury", open.clone(), NOON).unwrap();
+        let state = tree.get("nova1treasury").unwrap().spending_policy.unwrap();
+        assert_eq!(state.policy, treasury_policy());
+        assert_eq!(
+            state.pending.as_ref().unwrap().effective_at,
+            NOON + POLICY_CHANGE_DELAY_MS
+        );
+        let stranger = transfer("nova1mallory", 1);
+        assert!(check_spending_policy(&tree, &stranger, NOON + 1).is_err());
+        check_spending_policy(&tree, &stranger, NOON + POLICY_CHANGE_DELAY_MS).unwrap();
+
+        // Tightening applies at once and cancels the pending change.
+        let tighter = SpendingPolicy {
+            daily_limits: vec![Amount::new(5_000, Currency::NOVA)],
+            ..treasury_policy()
+        };
+        assert!(tighter.is_at_least_as_strict_as(&treasury_policy()));
+        assert!(!treasury_policy().is_at_least_as_strict_as(&tighter));
+        update_spending_policy(&mut tree, "nova1treasury", tighter.clone(), NOON + 2).unwrap();
+        let state = tree.get("nova1treasury").unwrap().spending_policy.unwrap();
+        assert_eq!(state.policy, tighter);
+        assert!(state.pending.is_none());
+        assert_eq!(tree.get("nova1treasury").unwrap().nonce, 3);
+    }
+
+    #[test]
+    fn policy_update_transactions_apply() {
+        let mut tree = temp_tree();
+        tree.put("nova1treasury", &AccountState::with_balance(1_000_000));
+
+        let update = |policy: &SpendingPolicy, nonce: u64| {
+            TransactionBuilder::new(TransactionType::SpendingPolicyUpdate)
+                .sender("nova1treasury")
+                .receiver("nova1treasury")
+                .nonce(nonce)
+                .timestamp(NOON)
+                .payload(serde_json::to_vec(policy).unwrap())
+                .build_unchecked()
+        };
+        apply_spending_policy_update(&mut tree, &update(&treasury_policy(), 1), NOON).unwrap();
+        let account = tree.get("nova1treasury").unwrap();
+        assert_eq!(account.spending_policy.unwrap().policy, treasury_policy());
+        assert!(!account.is_reapable());
+
+        let mut duplicate = treasury_policy();
+        duplicate.daily_limits.push(Amount::new(1, Currency::NOVA));
+        assert!(matches!(
+            apply_spending_policy_update(&mut tree, &update(&duplicate, 2), NOON),
+            Err(StateError::PolicyRejected(_))
+        ));
+
+        let mut elsewhere = update(&treasury_policy(), 2);
+        elsewhere.receiver = "nova1payroll".to_string();
+        assert!(apply_spending_policy_update(&mut tree, &elsewhere, NOON).is_err());
+    }
+}
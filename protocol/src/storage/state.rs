@@ -24,26 +24,52 @@
 //! ## Persistence
 //!
 //! Nodes are persisted to sled via `NovaDB`. Each node is keyed by its
-//! position in the tree (level + path prefix). On update, only the 256 nodes
-//! along the affected path are written — the rest of the tree remains
-//! untouched on disk.
+//! position in the tree (level + path prefix). Leaf values are written
+//! immediately; node hashes are recomputed lazily, when the root or a proof
+//! is next requested. Only the nodes on the paths of leaves written since
+//! then are rehashed — once each, however many times a leaf changed — and
+//! the rest of the tree remains untouched on disk.
+//!
+//! While [staging](StateTree::stage), leaf values and rehashed nodes are
+//! held in memory instead, and reach sled in one batch only when applied.
+//! Consensus executes a proposed block this way, so persisted state never
+//! runs ahead of the persisted chain.
 //!
 //! ## State Transitions
 //!
-//! A transfer `sender -> recipient` for amount `A`:
+//! A transfer `sender -> recipient` for amount `A` and fee `F`:
 //!
-//! 1. Verify `sender.balance >= A`.
-//! 2. `sender.balance -= A`
+//! 1. Verify `sender.balance >= A + F`, and that a new recipient receives
+//!    at least the existential deposit.
+//! 2. `sender.balance -= A + F`
 //! 3. `sender.nonce += 1`
 //! 4. `recipient.balance += A`
-//! 5. Recompute the state root.
+//! 5. Reap the sender if it fell below the existential deposit.
+//! 6. Recompute the state root.
+//!
+//! A sponsored transfer takes `F` from its fee payer instead, so the sender
+//! only needs `A` (see [`apply_sponsored_transfer`]).
+//!
+//! A transfer denominated in another registered token moves `A` between
+//! the accounts' balances of that token, while `F` is still paid in NOVA
+//! (see [`apply_transfer_transaction`]).
 
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
 
+use crate::config::EXISTENTIAL_DEPOSIT_PHOTONS;
 use crate::crypto::hash::blake3_hash;
+use crate::encoding;
+use crate::transaction::Transaction;
+use crate::vault::token::{TokenId, TokenRegistry, TokenType};
 
 use super::db::NovaDB;
+use super::hold::AuthorizationHold;
+use super::mandate::Mandate;
+use super::snapshot::CommittedState;
+use super::spending::PolicyState;
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -55,6 +81,14 @@ const TREE_DEPTH: usize = 256;
 /// sled tree name for SMT node data.
 const SMT_TREE_NAME: &str = "smt_nodes";
 
+/// State key of the account collecting the balances of reaped accounts.
+/// Not a valid address, so nothing can ever sign for it.
+pub const DUST_POOL_STATE_KEY: &str = "nova:system:dust_pool";
+
+/// State key of the community treasury account. Fees pay into it; only a
+/// spend proposal signed by a validator supermajority pays out of it.
+pub const TREASURY_STATE_KEY: &str = "nova:system:treasury";
+
 // ---------------------------------------------------------------------------
 // Precomputed Default Hashes
 // ---------------------------------------------------------------------------
@@ -94,17 +128,34 @@ fn default_hashes() -> &'static Vec<[u8; 32]> {
 /// is consensus-critical — validators must agree on every byte.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccountState {
-    /// Next expected transaction nonce (monotonically increasing).
+    /// Number of transactions this account has had executed. The next one
+    /// must carry `nonce + 1` (see [`AccountState::next_nonce`]).
     pub nonce: u64,
     /// Native token balance (photons).
+    #[serde(with = "encoding::amount")]
     pub balance: u64,
     /// Per-token balance commitments (serialized Pedersen commitment bytes).
     /// Keyed by token ID (hex-encoded).
+    #[serde(with = "encoding::hex_bytes_map")]
     pub balance_commitments: HashMap<String, Vec<u8>>,
     /// Active credit line IDs associated with this account.
     pub credit_lines: Vec<String>,
     /// Whether this account is frozen (compliance hold, dispute, etc.).
     pub frozen: bool,
+    /// Outstanding authorization holds (see [`super::hold`]).
+    #[serde(default)]
+    pub holds: Vec<AuthorizationHold>,
+    /// Direct-debit mandates granted by this account (see [`super::mandate`]).
+    #[serde(default)]
+    pub mandates: Vec<Mandate>,
+    /// Balances of tokens other than NOVA, in each token's smallest unit.
+    /// Tokens the account holds none of have no entry.
+    #[serde(default, with = "crate::vault::token::token_id_btree_map")]
+    pub token_balances: BTreeMap<TokenId, u64>,
+    /// Rules on the account's outgoing value, if it registered any (see
+    /// [`super::spending`]).
+    #[serde(default)]
+    pub spending_policy: Option<PolicyState>,
 }
 
 impl AccountState {
@@ -116,6 +167,32 @@ impl AccountState {
         }
     }
 
+    /// Balance of `token`, other than NOVA, held by the account.
+    pub fn token_balance(&self, token: &TokenId) -> u64 {
+        self.token_balances.get(token).copied().unwrap_or(0)
+    }
+
+    /// The nonce the account's next transaction must carry. Transaction
+    /// nonces start at 1; 0 is reserved for genesis/system transactions.
+    pub fn next_nonce(&self) -> u64 {
+        self.nonce + 1
+    }
+
+    /// Whether the account holds nothing but a balance and a nonce — no
+    /// holds, mandates, credit lines, commitments, token balances, spending
+    /// policy or freeze — and may
+    /// therefore be reaped once its balance drops below the existential
+    /// deposit.
+    pub fn is_reapable(&self) -> bool {
+        !self.frozen
+            && self.holds.is_empty()
+            && self.mandates.is_empty()
+            && self.credit_lines.is_empty()
+            && self.balance_commitments.is_empty()
+            && self.token_balances.is_empty()
+            && self.spending_policy.is_none()
+    }
+
     /// Serialize this account state to bytes for hashing / storage.
     pub fn to_bytes(&self) -> Vec<u8> {
         bincode::serialize(self).expect("AccountState serialization should never fail")
@@ -153,6 +230,7 @@ pub struct MerkleProof {
 
 /// Errors that can occur during state transitions.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum StateError {
     #[error("insufficient balance: have {have}, need {need}")]
     InsufficientBalance { have: u64, need: u64 },
@@ -160,11 +238,73 @@ pub enum StateError {
     #[error("account is frozen: {0}")]
     AccountFrozen(String),
 
+    #[error("nonce mismatch for {address}: expected {expected}, got {got}")]
+    NonceMismatch {
+        address: String,
+        expected: u64,
+        got: u64,
+    },
+
     #[error("database error: {0}")]
     Db(#[from] super::db::DbError),
 
     #[error("serialization error: {0}")]
     Serialization(String),
+
+    #[error("authorization hold rejected: {0}")]
+    HoldRejected(String),
+
+    #[error("mandate rejected: {0}")]
+    MandateRejected(String),
+
+    #[error("unjail rejected: {0}")]
+    UnjailRejected(String),
+
+    #[error("treasury spend rejected: {0}")]
+    TreasuryRejected(String),
+
+    #[error("batch rejected: {0}")]
+    BatchRejected(String),
+
+    #[error("confidential transfer rejected: {0}")]
+    ConfidentialRejected(String),
+
+    #[error("network key rotation rejected: {0}")]
+    NetworkKeyRejected(String),
+
+    #[error("spending policy rejected: {0}")]
+    PolicyRejected(String),
+
+    #[error("spending policy violated: {0}")]
+    PolicyViolated(String),
+
+    #[error("unknown token: {0} is not in the token registry")]
+    UnknownToken(String),
+
+    #[error("insufficient {token} balance: have {have}, need {need}")]
+    InsufficientTokenBalance { token: String, have: u64, need: u64 },
+
+    #[error(
+        "transfer would create {address} with {amount}, below the existential deposit of {minimum}"
+    )]
+    BelowExistentialDeposit {
+        address: String,
+        amount: u64,
+        minimum: u64,
+    },
+
+    #[error(
+        "value not conserved: debited {debited}, minted {minted}, credited {credited}, burned {burned}"
+    )]
+    ConservationViolated {
+        debited: u128,
+        minted: u64,
+        credited: u128,
+        burned: u64,
+    },
+
+    #[error("supply invariant broken: {0}")]
+    SupplyMismatch(String),
 }
 
 // ---------------------------------------------------------------------------
@@ -266,6 +406,33 @@ fn leaf_hash(key: &[u8; 32], value_bytes: &[u8]) -> [u8; 32] {
     blake3_hash(&preimage)
 }
 
+/// Queue a node write, or its removal when it is back to the default hash
+/// for its level — empty subtrees are never materialized.
+fn store_node(writes: &mut RawWrites, skey: &[u8], hash: &[u8; 32], default: &[u8; 32]) {
+    let value = (hash != default).then(|| hash.to_vec());
+    writes.insert(skey.to_vec(), value);
+}
+
+/// Read `skey` from the `smt_nodes` tree, looking in `staged` first.
+fn load(tree: &sled::Tree, staged: Option<&Staged>, skey: &[u8]) -> Option<Vec<u8>> {
+    if let Some(value) = staged.and_then(|staged| staged.writes.get(skey)) {
+        return value.clone();
+    }
+    tree.get(skey).ok().flatten().map(|bytes| bytes.to_vec())
+}
+
+/// Write `writes` to the `smt_nodes` tree in a single batch.
+fn persist(tree: &sled::Tree, writes: RawWrites) {
+    let mut batch = sled::Batch::default();
+    for (skey, value) in writes {
+        match value {
+            Some(value) => batch.insert(skey, value),
+            None => batch.remove(skey),
+        }
+    }
+    tree.apply_batch(batch).expect("sled write should not fail");
+}
+
 /// Combine two child hashes into a parent hash: BLAKE3(left || right).
 fn combine_hashes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
     let mut combined = [0u8; 64];
@@ -278,6 +445,35 @@ fn combine_hashes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
 // StateTree (Sparse Merkle Tree)
 // ---------------------------------------------------------------------------
 
+/// Root bookkeeping for deferred rehashing.
+struct PendingRoot {
+    /// Root covering every leaf not in `dirty`.
+    root: [u8; 32],
+    /// Leaf keys written since `root` was computed.
+    dirty: BTreeSet<[u8; 32]>,
+}
+
+impl PendingRoot {
+    fn clean(root: [u8; 32]) -> Self {
+        Self {
+            root,
+            dirty: BTreeSet::new(),
+        }
+    }
+}
+
+/// Raw `smt_nodes` writes: storage key to value, `None` for a removal.
+type RawWrites = BTreeMap<Vec<u8>, Option<Vec<u8>>>;
+
+/// Writes held in memory between [`StateTree::stage`] and
+/// [`StateTree::apply_staged`] or [`StateTree::discard_staged`].
+struct Staged {
+    /// Root when staging began, restored on discard.
+    base_root: [u8; 32],
+    /// Leaf values and the nodes rehashed over them.
+    writes: RawWrites,
+}
+
 /// Sparse Merkle Tree backed by sled for persistent state storage.
 ///
 /// The tree has a fixed depth of 256 levels, matching the BLAKE3 output
@@ -293,7 +489,18 @@ fn combine_hashes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
 /// At level `l`, the branching decision uses bit index `256 - l` of the key.
 pub struct StateTree {
     db: NovaDB,
-    root: [u8; 32],
+    /// Root as of the last rehash, plus the leaves written since.
+    hashing: Mutex<PendingRoot>,
+    /// Read view for API queries, if one is attached (see [`super::snapshot`]).
+    committed: Option<Arc<CommittedState>>,
+    /// Value each key held before its first write since
+    /// [`track_writes`](Self::track_writes), while tracking.
+    tracked: Option<BTreeMap<String, Option<Vec<u8>>>>,
+    /// Value each key held before its first write since
+    /// [`savepoint`](Self::savepoint), while one is open.
+    savepoint: Option<BTreeMap<String, Option<Vec<u8>>>>,
+    /// Writes held back from sled, while [staging](Self::stage).
+    staged: Mutex<Option<Staged>>,
 }
 
 impl StateTree {
@@ -305,7 +512,11 @@ impl StateTree {
         let defaults = default_hashes();
         Self {
             db,
-            root: defaults[TREE_DEPTH],
+            hashing: Mutex::new(PendingRoot::clean(defaults[TREE_DEPTH])),
+            committed: None,
+            tracked: None,
+            savepoint: None,
+            staged: Mutex::new(None),
         }
     }
 
@@ -315,95 +526,344 @@ impl StateTree {
     /// the corresponding nodes exist in the database. Used when resuming
     /// from a persisted state after a node restart.
     pub fn from_root(db: NovaDB, root: [u8; 32]) -> Self {
-        Self { db, root }
+        Self {
+            db,
+            hashing: Mutex::new(PendingRoot::clean(root)),
+            committed: None,
+            tracked: None,
+            savepoint: None,
+            staged: Mutex::new(None),
+        }
+    }
+
+    /// Journal writes into `committed` so readers of that handle keep
+    /// seeing the last committed block until [`commit`](Self::commit).
+    pub fn with_committed_state(mut self, committed: Arc<CommittedState>) -> Self {
+        self.committed = Some(committed);
+        self
+    }
+
+    /// The attached committed-state handle, if any.
+    pub fn committed_state(&self) -> Option<&Arc<CommittedState>> {
+        self.committed.as_ref()
+    }
+
+    /// Start remembering the value each key held before its first write,
+    /// for [`take_writes`](Self::take_writes). Restarts tracking if it was
+    /// already on.
+    pub fn track_writes(&mut self) {
+        self.tracked = Some(BTreeMap::new());
+    }
+
+    /// Stop tracking and return every key written since
+    /// [`track_writes`](Self::track_writes) with the value it held before,
+    /// in key order. Keys written back to their old value are included.
+    pub fn take_writes(&mut self) -> Vec<(String, Option<Vec<u8>>)> {
+        self.tracked
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
+
+    /// Open a savepoint: every write from now on can be undone with
+    /// [`rollback_to_savepoint`](Self::rollback_to_savepoint) until
+    /// [`release_savepoint`](Self::release_savepoint). Replaces a savepoint
+    /// already open. Independent of [`track_writes`](Self::track_writes).
+    pub fn savepoint(&mut self) {
+        self.savepoint = Some(BTreeMap::new());
+    }
+
+    /// Close the open savepoint, keeping its writes.
+    pub fn release_savepoint(&mut self) {
+        self.savepoint = None;
+    }
+
+    /// Write every key changed since [`savepoint`](Self::savepoint) back to
+    /// its value then, and close the savepoint.
+    pub fn rollback_to_savepoint(&mut self) {
+        for (key, before) in self.savepoint.take().unwrap_or_default() {
+            match before {
+                Some(value) => self.put_raw(&key, &value),
+                None => self.delete_raw(&key),
+            }
+        }
+    }
+
+    /// Hold every write from now on in memory instead of sled — leaf
+    /// values and the nodes rehashed over them alike — until
+    /// [`apply_staged`](Self::apply_staged) persists them or
+    /// [`discard_staged`](Self::discard_staged) drops them. Reads, roots
+    /// and proofs see the staged writes. Does nothing if already staging.
+    pub fn stage(&mut self) {
+        if self.staged.get_mut().is_some() {
+            return;
+        }
+        let base_root = self.root();
+        *self.staged.get_mut() = Some(Staged {
+            base_root,
+            writes: RawWrites::new(),
+        });
+    }
+
+    /// Whether writes are being staged (see [`stage`](Self::stage)).
+    pub fn is_staging(&self) -> bool {
+        self.staged.lock().is_some()
+    }
+
+    /// Write everything staged to sled in a single batch and stop staging.
+    /// A no-op when not staging.
+    pub fn apply_staged(&mut self) {
+        // Rehash first, so the staged nodes cover every staged leaf.
+        self.root();
+        if let Some(staged) = self.staged.get_mut().take() {
+            persist(&self.smt_tree(), staged.writes);
+        }
+    }
+
+    /// Drop everything staged and stop staging, leaving the tree at the
+    /// root it had when staging began. A no-op when not staging.
+    pub fn discard_staged(&mut self) {
+        if let Some(staged) = self.staged.get_mut().take() {
+            *self.hashing.get_mut() = PendingRoot::clean(staged.base_root);
+        }
+    }
+
+    /// Publish the current root as the committed state at `height`.
+    ///
+    /// Call once the block at `height` is durable. A no-op when no
+    /// committed-state handle is attached.
+    pub fn commit(&self, height: u64) {
+        if let Some(committed) = &self.committed {
+            committed.commit(height, self.root());
+        }
     }
 
     /// Return the current state root hash.
+    ///
+    /// Rehashes the paths of any leaves written since the last call first
+    /// (see [`rehash_dirty`](Self::rehash_dirty)).
     pub fn root(&self) -> [u8; 32] {
-        self.root
+        let mut hashing = self.hashing.lock();
+        if !hashing.dirty.is_empty() {
+            let dirty = std::mem::take(&mut hashing.dirty);
+            hashing.root = self.rehash_dirty(&dirty);
+        }
+        hashing.root
     }
 
     /// Retrieve the account state for an address.
     ///
     /// Returns `None` if the address has never been written to the tree.
     pub fn get(&self, address: &str) -> Option<AccountState> {
-        let key = address_to_key(address);
-        let tree = self.smt_tree();
-        let vkey = leaf_value_key(&key);
-        match tree.get(vkey).ok()? {
-            Some(bytes) => AccountState::from_bytes(&bytes),
-            None => None,
-        }
+        self.get_raw(address)
+            .and_then(|bytes| AccountState::from_bytes(&bytes))
     }
 
-    /// Insert or update an account state, recomputing the root hash.
+    /// Retrieve the raw leaf value stored under `key`.
     ///
-    /// The algorithm:
-    /// 1. Walk from root to leaf, collecting the current sibling hash at
-    ///    each level. Siblings are read from sled, falling back to default
-    ///    hashes for empty subtrees.
-    /// 2. Compute the new leaf hash.
-    /// 3. Walk from leaf to root, combining the new hash with each collected
-    ///    sibling, and writing every updated node to sled.
-    pub fn put(&mut self, address: &str, state: &AccountState) {
-        let key = address_to_key(address);
-        let tree = self.smt_tree();
-        let defaults = default_hashes();
+    /// Used for protocol-level records (e.g., the validator set) that live
+    /// in the state tree alongside accounts and are covered by the same root.
+    pub fn get_raw(&self, key: &str) -> Option<Vec<u8>> {
+        let key = address_to_key(key);
+        let staged = self.staged.lock();
+        load(&self.smt_tree(), staged.as_ref(), &leaf_value_key(&key))
+    }
 
-        // Step 1: Collect sibling hashes top-down (root to leaf).
-        // We iterate from level TREE_DEPTH down to level 1.
-        // At each level, the sibling is the child of the parent on the
-        // opposite side of our path.
-        let mut siblings_top_down = Vec::with_capacity(TREE_DEPTH);
-        for level in (1..=TREE_DEPTH).rev() {
-            let sib_key = storage_key_for_sibling(&key, level);
-            let sib_hash = match tree.get(&sib_key).ok().flatten() {
-                Some(bytes) if bytes.len() == 32 => {
-                    let mut h = [0u8; 32];
-                    h.copy_from_slice(&bytes);
-                    h
+    /// Every account in the tree, in key order, skipping the protocol
+    /// records stored under `records` with [`put_raw`](Self::put_raw).
+    ///
+    /// Leaves are keyed by the hash of their address, so accounts come
+    /// back without one. A full scan of the leaf values.
+    pub fn accounts(&self, records: &[&str]) -> Vec<AccountState> {
+        let skip: Vec<Vec<u8>> = records
+            .iter()
+            .map(|key| leaf_value_key(&address_to_key(key)))
+            .collect();
+        let staged = self.staged.lock();
+        let stored = self
+            .smt_tree()
+            .scan_prefix(b"v:")
+            .filter_map(|entry| entry.ok())
+            .map(|(key, value)| (key.to_vec(), value.to_vec()));
+        let leaves: Vec<(Vec<u8>, Vec<u8>)> = match staged.as_ref() {
+            None => stored.collect(),
+            Some(staged) => {
+                let mut merged: BTreeMap<Vec<u8>, Vec<u8>> = stored.collect();
+                let leaf_writes = staged
+                    .writes
+                    .range(b"v:".to_vec()..)
+                    .take_while(|(key, _)| key.starts_with(b"v:"));
+                for (key, value) in leaf_writes {
+                    match value {
+                        Some(value) => merged.insert(key.clone(), value.clone()),
+                        None => merged.remove(key),
+                    };
                 }
-                _ => defaults[level - 1],
-            };
-            siblings_top_down.push(sib_hash);
-        }
-        // siblings_top_down[0] = sibling at level TREE_DEPTH
-        // siblings_top_down[TREE_DEPTH - 1] = sibling at level 1
-        // Reverse so index 0 = level 1, index i = level i+1
-        siblings_top_down.reverse();
-        // Now: siblings_top_down[i] = sibling at level (i+1)
+                merged.into_iter().collect()
+            }
+        };
+        leaves
+            .into_iter()
+            .filter(|(key, _)| !skip.contains(key))
+            .filter_map(|(_, value)| AccountState::from_bytes(&value))
+            .collect()
+    }
 
-        // Step 2: Store the leaf value.
-        let value_bytes = state.to_bytes();
-        let vkey = leaf_value_key(&key);
-        tree.insert(vkey, value_bytes.as_slice())
-            .expect("sled write should not fail");
+    /// Insert or update an account state, recomputing the root hash.
+    pub fn put(&mut self, address: &str, state: &AccountState) {
+        self.put_raw(address, &state.to_bytes());
+    }
 
-        // Step 3: Compute new leaf hash (level 0) and store it.
-        let mut current_hash = leaf_hash(&key, &value_bytes);
-        let leaf_skey = storage_key_for_node(&key, 0);
-        tree.insert(leaf_skey, &current_hash)
-            .expect("sled write should not fail");
+    /// Insert or update a raw leaf value under `key`.
+    ///
+    /// The value is stored immediately (in memory while
+    /// [staging](Self::stage)), so reads see it at once, but the
+    /// path up to the root is only rehashed on the next [`root`](Self::root)
+    /// or [`get_proof`](Self::get_proof). A block touching the same account
+    /// many times pays for one path, and paths shared between accounts are
+    /// hashed once.
+    pub fn put_raw(&mut self, key: &str, value_bytes: &[u8]) {
+        self.track(key);
+        match self.committed.clone() {
+            Some(committed) => {
+                let _journal = committed.begin_write(key, || self.get_raw(key));
+                self.set_leaf(key, Some(value_bytes));
+            }
+            None => self.set_leaf(key, Some(value_bytes)),
+        }
+    }
 
-        // Step 4: Walk from level 1 to TREE_DEPTH, recomputing parent hashes.
-        for level in 1..=TREE_DEPTH {
-            let bit = bit_at_level(&key, level);
-            let sibling = siblings_top_down[level - 1];
+    /// Remove an account from the tree.
+    ///
+    /// The leaf reverts to the empty default, so exclusion proofs for the
+    /// address verify again once the root is recomputed, and nodes left
+    /// covering only empty subtrees are pruned from storage.
+    pub fn delete(&mut self, address: &str) {
+        self.delete_raw(address);
+    }
 
-            let (left, right) = if bit {
-                (sibling, current_hash)
-            } else {
-                (current_hash, sibling)
-            };
+    /// Remove the raw leaf value under `key`. See [`delete`](Self::delete).
+    pub fn delete_raw(&mut self, key: &str) {
+        self.track(key);
+        match self.committed.clone() {
+            Some(committed) => {
+                let _journal = committed.begin_write(key, || self.get_raw(key));
+                self.set_leaf(key, None);
+            }
+            None => self.set_leaf(key, None),
+        }
+    }
 
-            current_hash = combine_hashes(&left, &right);
+    /// Remember `key`'s current value for write tracking and the open
+    /// savepoint, for whichever this is the first write of `key`.
+    fn track(&mut self, key: &str) {
+        let first = |log: &Option<BTreeMap<String, Option<Vec<u8>>>>| {
+            log.as_ref().is_some_and(|log| !log.contains_key(key))
+        };
+        if first(&self.tracked) || first(&self.savepoint) {
+            let before = self.get_raw(key);
+            for log in [&mut self.tracked, &mut self.savepoint]
+                .into_iter()
+                .flatten()
+            {
+                log.entry(key.to_string()).or_insert_with(|| before.clone());
+            }
+        }
+    }
 
-            let node_skey = storage_key_for_node(&key, level);
-            tree.insert(node_skey, &current_hash)
+    /// Store the leaf value under `key`, or remove it for `None` — in
+    /// memory while staging, in sled otherwise — and queue its path for
+    /// rehashing.
+    fn set_leaf(&mut self, key: &str, value: Option<&[u8]>) {
+        let key = address_to_key(key);
+        let vkey = leaf_value_key(&key);
+        match self.staged.get_mut() {
+            Some(staged) => {
+                staged.writes.insert(vkey, value.map(<[u8]>::to_vec));
+            }
+            None => {
+                let tree = self.smt_tree();
+                match value {
+                    Some(value) => tree.insert(vkey, value).map(drop),
+                    None => tree.remove(vkey).map(drop),
+                }
                 .expect("sled write should not fail");
+            }
+        }
+        self.hashing.get_mut().dirty.insert(key);
+    }
+
+    /// Recompute every node on the paths from `dirty` leaves to the root,
+    /// returning the new root.
+    ///
+    /// Works one level at a time from the leaves up. At each level the set
+    /// of updated nodes is the set of distinct parents of the level below;
+    /// a parent's other child comes from that set if it was updated too,
+    /// otherwise from sled (or the default hash for an empty subtree). A
+    /// deleted leaf hashes to the empty default, and any node that ends up
+    /// equal to its level's default is removed rather than stored. All node
+    /// writes go to sled in a single batch, or join the staged writes.
+    fn rehash_dirty(&self, dirty: &BTreeSet<[u8; 32]>) -> [u8; 32] {
+        let tree = self.smt_tree();
+        let defaults = default_hashes();
+        let mut staged = self.staged.lock();
+        let mut writes = RawWrites::new();
+
+        // Updated nodes at the current level: storage key -> (a leaf key
+        // whose path runs through the node, node hash).
+        let mut updated: BTreeMap<Vec<u8>, ([u8; 32], [u8; 32])> = BTreeMap::new();
+        for key in dirty {
+            let value = load(&tree, staged.as_ref(), &leaf_value_key(key));
+            let hash = value.map_or(defaults[0], |value| leaf_hash(key, &value));
+            let skey = storage_key_for_node(key, 0);
+            store_node(&mut writes, &skey, &hash, &defaults[0]);
+            updated.insert(skey, (*key, hash));
+        }
+
+        for level in 1..=TREE_DEPTH {
+            let mut parents: BTreeMap<Vec<u8>, ([u8; 32], [u8; 32])> = BTreeMap::new();
+            for (key, hash) in updated.values() {
+                let parent_skey = storage_key_for_node(key, level);
+                if parents.contains_key(&parent_skey) {
+                    // Already combined while visiting the sibling.
+                    continue;
+                }
+                let sib_skey = storage_key_for_sibling(key, level);
+                let sibling = match updated.get(&sib_skey) {
+                    Some((_, h)) => *h,
+                    None => match load(&tree, staged.as_ref(), &sib_skey) {
+                        Some(bytes) if bytes.len() == 32 => {
+                            let mut h = [0u8; 32];
+                            h.copy_from_slice(&bytes);
+                            h
+                        }
+                        _ => defaults[level - 1],
+                    },
+                };
+
+                let (left, right) = if bit_at_level(key, level) {
+                    (sibling, *hash)
+                } else {
+                    (*hash, sibling)
+                };
+                let parent = combine_hashes(&left, &right);
+                store_node(&mut writes, &parent_skey, &parent, &defaults[level]);
+                parents.insert(parent_skey, (*key, parent));
+            }
+            updated = parents;
+        }
+
+        match staged.as_mut() {
+            Some(staged) => staged.writes.extend(writes),
+            None => persist(&tree, writes),
         }
 
-        self.root = current_hash;
+        let (_, root) = updated
+            .into_values()
+            .next()
+            .expect("at least one dirty leaf reaches the root");
+        root
     }
 
     /// Generate a Merkle proof for the given address.
@@ -411,8 +871,11 @@ impl StateTree {
     /// Works for both existing accounts (inclusion proof) and non-existent
     /// accounts (exclusion proof). The proof contains 256 sibling hashes.
     pub fn get_proof(&self, address: &str) -> MerkleProof {
+        // Siblings must reflect every pending write.
+        self.root();
         let key = address_to_key(address);
         let tree = self.smt_tree();
+        let staged = self.staged.lock();
         let defaults = default_hashes();
 
         let mut siblings = Vec::with_capacity(TREE_DEPTH);
@@ -423,7 +886,7 @@ impl StateTree {
             path_bits.push(bit);
 
             let sib_key = storage_key_for_sibling(&key, level);
-            let sib_hash = match tree.get(&sib_key).ok().flatten() {
+            let sib_hash = match load(&tree, staged.as_ref(), &sib_key) {
                 Some(bytes) if bytes.len() == 32 => {
                     let mut h = [0u8; 32];
                     h.copy_from_slice(&bytes);
@@ -497,8 +960,22 @@ impl StateTree {
 
 /// Apply a balance transfer between two accounts in the state tree.
 ///
-/// Validates that the sender has sufficient balance, decrements sender balance,
-/// increments sender nonce, and credits the receiver.
+/// Validates that `nonce` is exactly the sender's next nonce and that the
+/// sender's *available* balance (funds locked by authorization holds do not
+/// count) covers `amount + fee`, then debits both from the sender,
+/// increments the sender nonce in the same write, and credits the receiver
+/// with `amount`. The fee is left for the block to settle — see
+/// [`settle_fees`](super::fee::settle_fees).
+///
+/// Existential deposit: a transfer may not create the receiver with less
+/// than [`EXISTENTIAL_DEPOSIT_PHOTONS`], and a sender left below it is
+/// reaped — its remaining balance is swept into [`DUST_POOL_STATE_KEY`]
+/// and the account deleted (see [`AccountState::is_reapable`]).
+///
+/// Strict nonce equality is what stops a transaction from being replayed
+/// into a later block: once it executes, its nonce is behind the account.
+/// A reaped account starts again from nonce 0, so block execution also
+/// refuses transaction IDs that are already on chain.
 ///
 /// This is the fundamental state transition for NOVA transfers. Higher-level
 /// transaction types (credit requests, token mints, etc.) build on top of this
@@ -508,28 +985,211 @@ pub fn apply_transfer(
     sender: &str,
     receiver: &str,
     amount: u64,
+    fee: u64,
+    nonce: u64,
+) -> Result<(), StateError> {
+    transfer(tree, sender, receiver, None, amount, fee, nonce)
+}
+
+/// Apply a sponsored transfer: [`apply_transfer`], except that `fee` is
+/// debited from `fee_payer` and the sender only needs to cover `amount`.
+///
+/// The fee payer must not be frozen and its available balance must cover
+/// `fee`. Its nonce is left alone (the sender's nonce already stops the
+/// transaction from replaying), and it is reaped like the sender if the
+/// fee leaves it below the existential deposit. A `fee_payer` equal to
+/// the sender is an ordinary transfer.
+pub fn apply_sponsored_transfer(
+    tree: &mut StateTree,
+    sender: &str,
+    receiver: &str,
+    fee_payer: &str,
+    amount: u64,
+    fee: u64,
+    nonce: u64,
+) -> Result<(), StateError> {
+    let fee_payer = Some(fee_payer).filter(|payer| *payer != sender);
+    transfer(tree, sender, receiver, fee_payer, None, amount, fee, nonce)
+}
+
+/// Execute a `Transfer` transaction in the token its amount is
+/// denominated in.
+///
+/// The amount's currency must resolve to a token in `registry`, or the
+/// transfer is refused with [`StateError::UnknownToken`]. NOVA transfers
+/// are [`apply_transfer`] or, with a fee payer, [`apply_sponsored_transfer`].
+/// Any other token moves the amount from the sender's balance of that
+/// token to the receiver's, while the fee is still paid in NOVA by the
+/// sender or fee payer. Token balances keep an account from being reaped,
+/// so a receiver may be created holding only tokens.
+pub fn apply_transfer_transaction(
+    tree: &mut StateTree,
+    tx: &Transaction,
+    registry: &TokenRegistry,
+) -> Result<(), StateError> {
+    let token = registry
+        .resolve(&tx.amount.currency)
+        .ok_or_else(|| StateError::UnknownToken(tx.amount.currency.to_string()))?;
+    let token = match token.token_type {
+        TokenType::Native => None,
+        _ => Some(token.id),
+    };
+    let fee_payer = tx.fee_payer.as_deref().filter(|payer| *payer != tx.sender);
+    transfer(
+        tree,
+        &tx.sender,
+        &tx.receiver,
+        fee_payer,
+        token,
+        tx.amount.value,
+        tx.fee,
+        tx.nonce,
+    )
+}
+
+/// Moves `amount` of `token` (NOVA when `None`) from `sender` to
+/// `receiver`, with `fee` paid in NOVA by `fee_payer` or the sender.
+#[allow(clippy::too_many_arguments)]
+fn transfer(
+    tree: &mut StateTree,
+    sender: &str,
+    receiver: &str,
+    fee_payer: Option<&str>,
+    token: Option<TokenId>,
+    amount: u64,
+    fee: u64,
+    nonce: u64,
 ) -> Result<(), StateError> {
     let mut sender_state = tree.get(sender).unwrap_or_default();
 
     if sender_state.frozen {
         return Err(StateError::AccountFrozen(sender.to_string()));
     }
-
-    if sender_state.balance < amount {
+    expect_nonce(&sender_state, sender, nonce)?;
+
+    // NOVA the sender must cover: the amount of a NOVA transfer, plus the
+    // fee unless sponsored.
+    let native_amount = if token.is_none() { amount } else { 0 };
+    let available = sender_state.available_balance();
+    let need = match fee_payer {
+        Some(_) => native_amount,
+        None => native_amount.saturating_add(fee),
+    };
+    if available < need {
         return Err(StateError::InsufficientBalance {
-            have: sender_state.balance,
-            need: amount,
+            have: available,
+            need,
+        });
+    }
+    if let Some(id) = token {
+        let have = sender_state.token_balance(&id);
+        if have < amount {
+            return Err(StateError::InsufficientTokenBalance {
+                token: id.to_hex(),
+                have,
+                need: amount,
+            });
+        }
+    }
+
+    if let Some(payer) = fee_payer {
+        let payer_state = tree.get(payer).unwrap_or_default();
+        if payer_state.frozen {
+            return Err(StateError::AccountFrozen(payer.to_string()));
+        }
+        if payer_state.available_balance() < fee {
+            return Err(StateError::InsufficientBalance {
+                have: payer_state.available_balance(),
+                need: fee,
+            });
+        }
+    }
+
+    let receiver_exists = receiver == sender || tree.get(receiver).is_some();
+    if token.is_none() && !receiver_exists && amount < EXISTENTIAL_DEPOSIT_PHOTONS {
+        return Err(StateError::BelowExistentialDeposit {
+            address: receiver.to_string(),
+            amount,
+            minimum: EXISTENTIAL_DEPOSIT_PHOTONS,
         });
     }
 
-    sender_state.balance -= amount;
+    sender_state.balance -= need;
     sender_state.nonce += 1;
+    if let Some(id) = token {
+        let left = sender_state.token_balance(&id) - amount;
+        if left == 0 {
+            sender_state.token_balances.remove(&id);
+        } else {
+            sender_state.token_balances.insert(id, left);
+        }
+    }
     tree.put(sender, &sender_state);
 
+    if let Some(payer) = fee_payer {
+        let mut payer_state = tree.get(payer).unwrap_or_default();
+        payer_state.balance -= fee;
+        tree.put(payer, &payer_state);
+    }
+
     let mut receiver_state = tree.get(receiver).unwrap_or_default();
-    receiver_state.balance += amount;
+    match token {
+        Some(id) if amount > 0 => *receiver_state.token_balances.entry(id).or_insert(0) += amount,
+        Some(_) => {}
+        None => receiver_state.balance += amount,
+    }
     tree.put(receiver, &receiver_state);
 
+    reap_if_dust(tree, sender);
+    if let Some(payer) = fee_payer {
+        reap_if_dust(tree, payer);
+    }
+    Ok(())
+}
+
+/// Delete `address` if it is reapable and below the existential deposit,
+/// sweeping its balance into the dust pool. Returns the amount swept, or
+/// `None` if the account was kept.
+pub fn reap_if_dust(tree: &mut StateTree, address: &str) -> Option<u64> {
+    let state = tree.get(address)?;
+    if state.balance >= EXISTENTIAL_DEPOSIT_PHOTONS || !state.is_reapable() {
+        return None;
+    }
+    if state.balance > 0 {
+        let mut pool = tree.get(DUST_POOL_STATE_KEY).unwrap_or_default();
+        pool.balance += state.balance;
+        tree.put(DUST_POOL_STATE_KEY, &pool);
+    }
+    tree.delete(address);
+    Some(state.balance)
+}
+
+/// Check that `nonce` is the next nonce for `address`, without changing
+/// state. Execution paths call this before any state transition that bumps
+/// the signer's nonce.
+pub fn check_nonce(tree: &StateTree, address: &str, nonce: u64) -> Result<(), StateError> {
+    expect_nonce(&tree.get(address).unwrap_or_default(), address, nonce)
+}
+
+/// Check `nonce` and consume it, for transactions that carry no other
+/// state transition yet.
+pub fn bump_nonce(tree: &mut StateTree, address: &str, nonce: u64) -> Result<(), StateError> {
+    let mut state = tree.get(address).unwrap_or_default();
+    expect_nonce(&state, address, nonce)?;
+    state.nonce += 1;
+    tree.put(address, &state);
+    Ok(())
+}
+
+fn expect_nonce(state: &AccountState, address: &str, nonce: u64) -> Result<(), StateError> {
+    let expected = state.next_nonce();
+    if nonce != expected {
+        return Err(StateError::NonceMismatch {
+            address: address.to_string(),
+            expected,
+            got: nonce,
+        });
+    }
     Ok(())
 }
 
@@ -549,6 +1209,8 @@ fn address_to_key(address: &str) -> [u8; 32] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
 
     fn temp_tree() -> StateTree {
         let db = NovaDB::open_temporary().expect("should create temp db");
@@ -589,6 +1251,7 @@ mod tests {
             balance_commitments: HashMap::new(),
             credit_lines: vec!["credit_001".to_string()],
             frozen: false,
+            ..Default::default()
         };
 
         tree.put("nova1bob", &state);
@@ -684,7 +1347,7 @@ mod tests {
         let alice = AccountState::with_balance(10_000);
         tree.put("nova1alice", &alice);
 
-        apply_transfer(&mut tree, "nova1alice", "nova1bob", 3_000).unwrap();
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 3_000, 0, 1).unwrap();
 
         let alice_after = tree.get("nova1alice").unwrap();
         let bob_after = tree.get("nova1bob").unwrap();
@@ -702,7 +1365,7 @@ mod tests {
         let alice = AccountState::with_balance(500);
         tree.put("nova1alice", &alice);
 
-        let result = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000);
+        let result = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1);
         assert!(result.is_err());
 
         match result.unwrap_err() {
@@ -723,13 +1386,13 @@ mod tests {
         let alice = AccountState::with_balance(10_000);
         tree.put("nova1alice", &alice);
 
-        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000).unwrap();
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap();
         assert_eq!(tree.get("nova1alice").unwrap().nonce, 1);
 
-        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000).unwrap();
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 2).unwrap();
         assert_eq!(tree.get("nova1alice").unwrap().nonce, 2);
 
-        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000).unwrap();
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 3).unwrap();
         assert_eq!(tree.get("nova1alice").unwrap().nonce, 3);
     }
 
@@ -878,7 +1541,7 @@ mod tests {
         };
         tree.put("nova1alice", &alice);
 
-        let result = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000);
+        let result = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1);
         assert!(result.is_err());
         match result.unwrap_err() {
             StateError::AccountFrozen(addr) => assert_eq!(addr, "nova1alice"),
@@ -930,10 +1593,500 @@ mod tests {
         let alice = AccountState::with_balance(5_000);
         tree.put("nova1alice", &alice);
 
-        apply_transfer(&mut tree, "nova1alice", "nova1alice", 1_000).unwrap();
+        apply_transfer(&mut tree, "nova1alice", "nova1alice", 1_000, 0, 1).unwrap();
         let after = tree.get("nova1alice").unwrap();
         // Sender debit: balance=4000, nonce=1. Receiver credit: balance=5000, nonce=1.
         assert_eq!(after.balance, 5_000);
         assert_eq!(after.nonce, 1);
     }
+
+    // -- 22. Incremental root equals a from-scratch recomputation ------------
+
+    /// Root of a tree holding exactly `entries`, computed recursively from
+    /// the leaves without touching sled.
+    fn reference_root(entries: &BTreeMap<String, Vec<u8>>) -> [u8; 32] {
+        fn subtree(level: usize, leaves: &[([u8; 32], Vec<u8>)]) -> [u8; 32] {
+            if leaves.is_empty() {
+                return default_hashes()[level];
+            }
+            if level == 0 {
+                return leaf_hash(&leaves[0].0, &leaves[0].1);
+            }
+            let (right, left): (Vec<_>, Vec<_>) = leaves
+                .iter()
+                .cloned()
+                .partition(|(key, _)| bit_at_level(key, level));
+            combine_hashes(&subtree(level - 1, &left), &subtree(level - 1, &right))
+        }
+
+        let leaves: Vec<_> = entries
+            .iter()
+            .map(|(address, value)| (address_to_key(address), value.clone()))
+            .collect();
+        subtree(TREE_DEPTH, &leaves)
+    }
+
+    #[test]
+    fn incremental_root_matches_from_scratch() {
+        let db = NovaDB::open_temporary().unwrap();
+        let mut tree = StateTree::new(db.clone());
+        let mut expected = BTreeMap::new();
+
+        for round in 0..6u64 {
+            // Overlapping account sets, some accounts written twice per round.
+            for i in (round * 5)..(round * 5 + 20) {
+                for bump in 0..=(i % 2) {
+                    let address = format!("nova1acct{i}");
+                    let state = AccountState::with_balance(round * 1_000 + i + bump);
+                    tree.put(&address, &state);
+                    expected.insert(address, state.to_bytes());
+                }
+            }
+            // Only sample the root on some rounds so dirty sets accumulate.
+            if round % 2 == 1 {
+                assert_eq!(tree.root(), reference_root(&expected), "round {round}");
+            }
+            if round == 3 {
+                // Resume from the persisted nodes, as after a restart.
+                tree = StateTree::from_root(db.clone(), tree.root());
+            }
+        }
+        assert_eq!(tree.root(), reference_root(&expected));
+
+        // Same final contents, inserted in a different order, in one batch.
+        let mut fresh = temp_tree();
+        for (address, value) in expected.iter().rev() {
+            fresh.put_raw(address, value);
+        }
+        assert_eq!(fresh.root(), tree.root());
+    }
+
+    // -- 23. Proofs see writes made since the last root ----------------------
+
+    #[test]
+    fn proofs_reflect_pending_writes() {
+        let mut tree = temp_tree();
+        for i in 0..8 {
+            tree.put(&format!("nova1acct{i}"), &AccountState::with_balance(i));
+        }
+        let root_before = tree.root();
+
+        let state = AccountState::with_balance(99);
+        tree.put("nova1acct3", &state);
+        let proof = tree.get_proof("nova1acct3");
+
+        assert_ne!(tree.root(), root_before);
+        assert!(StateTree::verify_proof(
+            &tree.root(),
+            "nova1acct3",
+            Some(&state),
+            &proof
+        ));
+    }
+
+    // -- 24. Replayed and out-of-order nonces rejected -----------------------
+
+    #[test]
+    fn apply_transfer_rejects_replayed_nonce() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap();
+        let root = tree.root();
+
+        let err = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            StateError::NonceMismatch {
+                expected: 2,
+                got: 1,
+                ..
+            }
+        ));
+        // Nothing moved.
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.get("nova1bob").unwrap().balance, 1_000);
+    }
+
+    #[test]
+    fn apply_transfer_rejects_nonce_gap() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+
+        let err = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 2).unwrap_err();
+        assert!(matches!(
+            err,
+            StateError::NonceMismatch {
+                expected: 1,
+                got: 2,
+                ..
+            }
+        ));
+        assert_eq!(tree.get("nova1alice").unwrap().nonce, 0);
+        assert!(tree.get("nova1bob").is_none());
+    }
+
+    #[test]
+    fn bump_nonce_consumes_exactly_one() {
+        let mut tree = temp_tree();
+        bump_nonce(&mut tree, "nova1alice", 1).unwrap();
+        assert!(check_nonce(&tree, "nova1alice", 2).is_ok());
+        assert!(bump_nonce(&mut tree, "nova1alice", 1).is_err());
+        assert_eq!(tree.get("nova1alice").unwrap().nonce, 1);
+    }
+
+    // -- 25. Fee debited alongside the amount --------------------------------
+
+    #[test]
+    fn apply_transfer_debits_fee() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(1_200));
+
+        // The amount alone fits; amount plus fee does not.
+        let err = apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 201, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            StateError::InsufficientBalance {
+                have: 1_200,
+                need: 1_201
+            }
+        ));
+
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 100, 1).unwrap();
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 100);
+        assert_eq!(tree.get("nova1bob").unwrap().balance, 1_000);
+    }
+
+    // -- 26. Deletion restores exclusion proofs and prunes nodes -------------
+
+    #[test]
+    fn delete_restores_empty_root_and_prunes_nodes() {
+        let mut tree = temp_tree();
+        let empty_root = tree.root();
+
+        tree.put("nova1alice", &AccountState::with_balance(1_000));
+        tree.put("nova1bob", &AccountState::with_balance(2_000));
+        let with_both = tree.root();
+
+        tree.delete("nova1bob");
+        let root = tree.root();
+        assert_ne!(root, with_both);
+        let proof = tree.get_proof("nova1bob");
+        assert!(StateTree::verify_proof(&root, "nova1bob", None, &proof));
+        let proof = tree.get_proof("nova1alice");
+        let alice = tree.get("nova1alice").unwrap();
+        assert!(StateTree::verify_proof(
+            &root,
+            "nova1alice",
+            Some(&alice),
+            &proof
+        ));
+
+        tree.delete("nova1alice");
+        assert_eq!(tree.root(), empty_root);
+        assert_eq!(tree.smt_tree().len(), 0);
+    }
+
+    #[test]
+    fn incremental_root_with_deletes_matches_from_scratch() {
+        let mut tree = temp_tree();
+        let mut expected = BTreeMap::new();
+
+        for i in 0..30u64 {
+            let address = format!("nova1acct{i}");
+            let state = AccountState::with_balance(i + 1);
+            tree.put(&address, &state);
+            expected.insert(address, state.to_bytes());
+        }
+        assert_eq!(tree.root(), reference_root(&expected));
+
+        // Delete every third account, and re-create one of them in the
+        // same batch of writes.
+        for i in (0..30u64).step_by(3) {
+            let address = format!("nova1acct{i}");
+            tree.delete(&address);
+            expected.remove(&address);
+        }
+        let revived = AccountState::with_balance(777);
+        tree.put("nova1acct3", &revived);
+        expected.insert("nova1acct3".to_string(), revived.to_bytes());
+
+        assert_eq!(tree.root(), reference_root(&expected));
+    }
+
+    // -- 27. Existential deposit ---------------------------------------------
+
+    #[test]
+    fn transfer_cannot_create_dust_account() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+
+        let small = EXISTENTIAL_DEPOSIT_PHOTONS - 1;
+        let err = apply_transfer(&mut tree, "nova1alice", "nova1bob", small, 0, 1).unwrap_err();
+        assert!(matches!(err, StateError::BelowExistentialDeposit { .. }));
+        assert!(tree.get("nova1bob").is_none());
+        assert_eq!(tree.get("nova1alice").unwrap().nonce, 0);
+
+        // An existing account may receive any amount.
+        tree.put(
+            "nova1bob",
+            &AccountState::with_balance(EXISTENTIAL_DEPOSIT_PHOTONS),
+        );
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1, 0, 1).unwrap();
+        assert_eq!(
+            tree.get("nova1bob").unwrap().balance,
+            EXISTENTIAL_DEPOSIT_PHOTONS + 1
+        );
+    }
+
+    #[test]
+    fn sender_below_deposit_is_reaped_into_dust_pool() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(1_050));
+
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap();
+
+        assert!(tree.get("nova1alice").is_none());
+        assert_eq!(tree.get(DUST_POOL_STATE_KEY).unwrap().balance, 50);
+        let root = tree.root();
+        let proof = tree.get_proof("nova1alice");
+        assert!(StateTree::verify_proof(&root, "nova1alice", None, &proof));
+    }
+
+    #[test]
+    fn account_with_other_state_is_not_reaped() {
+        let mut tree = temp_tree();
+        let mut alice = AccountState::with_balance(1_050);
+        alice.credit_lines.push("line-1".to_string());
+        tree.put("nova1alice", &alice);
+
+        apply_transfer(&mut tree, "nova1alice", "nova1bob", 1_000, 0, 1).unwrap();
+
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 50);
+        assert!(tree.get(DUST_POOL_STATE_KEY).is_none());
+    }
+
+    // -- 28. Sponsored transfers ---------------------------------------------
+
+    #[test]
+    fn sponsored_transfer_debits_fee_from_fee_payer() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+        tree.put("nova1app", &AccountState::with_balance(5_000));
+
+        // Alice covers the amount but not amount plus fee; the app pays.
+        apply_sponsored_transfer(
+            &mut tree,
+            "nova1alice",
+            "nova1bob",
+            "nova1app",
+            10_000,
+            300,
+            1,
+        )
+        .unwrap_err();
+        apply_sponsored_transfer(
+            &mut tree,
+            "nova1alice",
+            "nova1bob",
+            "nova1app",
+            9_000,
+            300,
+            1,
+        )
+        .unwrap();
+
+        let alice = tree.get("nova1alice").unwrap();
+        assert_eq!((alice.balance, alice.nonce), (1_000, 1));
+        let app = tree.get("nova1app").unwrap();
+        assert_eq!((app.balance, app.nonce), (4_700, 0));
+        assert_eq!(tree.get("nova1bob").unwrap().balance, 9_000);
+
+        // A sponsor that cannot cover the fee fails the whole transfer.
+        let err = apply_sponsored_transfer(
+            &mut tree,
+            "nova1alice",
+            "nova1bob",
+            "nova1app",
+            100,
+            5_000,
+            2,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            StateError::InsufficientBalance {
+                have: 4_700,
+                need: 5_000
+            }
+        ));
+        assert_eq!(tree.get("nova1alice").unwrap().nonce, 1);
+    }
+
+    // -- 29. Token transfers ---------------------------------------------------
+
+    fn token_transfer(currency: Currency, amount: u64, fee: u64, nonce: u64) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(amount, currency))
+            .fee(fee)
+            .nonce(nonce)
+            .timestamp(1_700_000_000_000)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn token_transfer_moves_the_token_balance() {
+        let registry = TokenRegistry::default();
+        let brl = crate::vault::token::brl_token_id();
+        let mut tree = temp_tree();
+        let mut alice = AccountState::with_balance(10_000);
+        alice.token_balances.insert(brl, 5_000);
+        tree.put("nova1alice", &alice);
+
+        let tx = token_transfer(Currency::BRL, 2_000, 100, 1);
+        apply_transfer_transaction(&mut tree, &tx, &registry).unwrap();
+
+        // The amount moves in BRL, the fee in NOVA, and Bob is created
+        // holding only BRL.
+        let alice = tree.get("nova1alice").unwrap();
+        assert_eq!((alice.balance, alice.nonce), (9_900, 1));
+        assert_eq!(alice.token_balance(&brl), 3_000);
+        let bob = tree.get("nova1bob").unwrap();
+        assert_eq!((bob.balance, bob.token_balance(&brl)), (0, 2_000));
+        assert!(!bob.is_reapable());
+
+        // Spending the rest drops the entry.
+        let tx = token_transfer(Currency::BRL, 3_000, 100, 2);
+        apply_transfer_transaction(&mut tree, &tx, &registry).unwrap();
+        assert!(tree.get("nova1alice").unwrap().token_balances.is_empty());
+
+        // NOVA transfers still move the native balance.
+        let tx = token_transfer(Currency::NOVA, 1_000, 100, 3);
+        apply_transfer_transaction(&mut tree, &tx, &registry).unwrap();
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 8_600);
+        assert_eq!(tree.get("nova1bob").unwrap().balance, 1_000);
+    }
+
+    #[test]
+    fn token_transfer_rejects_unknown_tokens_and_shortfalls() {
+        let registry = TokenRegistry::default();
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(10_000));
+
+        let tx = token_transfer(Currency::Custom("DOGE".into()), 1_000, 100, 1);
+        let err = apply_transfer_transaction(&mut tree, &tx, &registry).unwrap_err();
+        assert!(matches!(err, StateError::UnknownToken(ref t) if t == "DOGE"));
+
+        // A NOVA balance does not cover a USD transfer.
+        let tx = token_transfer(Currency::USD, 1_000, 100, 1);
+        let err = apply_transfer_transaction(&mut tree, &tx, &registry).unwrap_err();
+        assert!(matches!(
+            err,
+            StateError::InsufficientTokenBalance {
+                have: 0,
+                need: 1_000,
+                ..
+            }
+        ));
+        let alice = tree.get("nova1alice").unwrap();
+        assert_eq!((alice.balance, alice.nonce), (10_000, 0));
+        assert!(tree.get("nova1bob").is_none());
+    }
+
+    // -- 30. Write tracking ----------------------------------------------------
+
+    #[test]
+    fn tracked_writes_remember_first_values() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(100));
+        // Untracked writes are not remembered.
+        assert!(tree.take_writes().is_empty());
+
+        tree.track_writes();
+        tree.put("nova1alice", &AccountState::with_balance(60));
+        tree.put("nova1alice", &AccountState::with_balance(40));
+        tree.put("nova1bob", &AccountState::with_balance(60));
+        tree.delete("nova1bob");
+
+        let writes = tree.take_writes();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].0, "nova1alice");
+        let before = AccountState::from_bytes(writes[0].1.as_deref().unwrap()).unwrap();
+        assert_eq!(before.balance, 100);
+        assert_eq!(writes[1], ("nova1bob".to_string(), None));
+
+        tree.put("nova1carol", &AccountState::with_balance(1));
+        assert!(tree.take_writes().is_empty());
+    }
+
+    #[test]
+    fn savepoint_rolls_back_only_its_own_writes() {
+        let mut tree = temp_tree();
+        tree.put("nova1alice", &AccountState::with_balance(100));
+        tree.track_writes();
+        tree.put("nova1alice", &AccountState::with_balance(90));
+        let root = tree.root();
+
+        tree.savepoint();
+        tree.put("nova1alice", &AccountState::with_balance(50));
+        tree.put("nova1bob", &AccountState::with_balance(50));
+        tree.rollback_to_savepoint();
+        assert_eq!(tree.get("nova1alice").unwrap().balance, 90);
+        assert!(tree.get("nova1bob").is_none());
+        assert_eq!(tree.root(), root);
+
+        // Released writes stay, and block-level tracking saw them all.
+        tree.savepoint();
+        tree.put("nova1carol", &AccountState::with_balance(1));
+        tree.release_savepoint();
+        tree.rollback_to_savepoint();
+        assert!(tree.get("nova1carol").is_some());
+        let keys: Vec<String> = tree.take_writes().into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["nova1alice", "nova1bob", "nova1carol"]);
+    }
+
+    // -- 31. Staged writes -------------------------------------------------------
+
+    #[test]
+    fn staged_writes_reach_sled_only_when_applied() {
+        let db = NovaDB::open_temporary().unwrap();
+        let mut tree = StateTree::new(db.clone());
+        tree.put("nova1alice", &AccountState::with_balance(100));
+        let base = tree.root();
+
+        tree.stage();
+        tree.put("nova1alice", &AccountState::with_balance(40));
+        tree.put("nova1bob", &AccountState::with_balance(60));
+        let staged_root = tree.root();
+        assert_ne!(staged_root, base);
+        assert_eq!(tree.accounts(&[]).len(), 2);
+
+        // Nothing reached sled: a fresh reader sees the base state.
+        let reader = StateTree::from_root(db.clone(), base);
+        assert_eq!(reader.get("nova1alice").unwrap().balance, 100);
+        assert!(reader.get("nova1bob").is_none());
+
+        tree.discard_staged();
+        assert!(!tree.is_staging());
+        assert_eq!(tree.root(), base);
+        assert!(tree.get("nova1bob").is_none());
+
+        // Applied, the leaves and nodes are on disk and prove the new root.
+        tree.stage();
+        tree.put("nova1alice", &AccountState::with_balance(40));
+        tree.put("nova1bob", &AccountState::with_balance(60));
+        tree.apply_staged();
+        assert_eq!(tree.root(), staged_root);
+        let reader = StateTree::from_root(db, staged_root);
+        let bob = reader.get("nova1bob").unwrap();
+        let proof = reader.get_proof("nova1bob");
+        assert!(StateTree::verify_proof(
+            &staged_root,
+            "nova1bob",
+            Some(&bob),
+            &proof
+        ));
+    }
 }
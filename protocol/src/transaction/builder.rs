@@ -2,16 +2,31 @@
 //!
 //! The [`TransactionBuilder`] enforces a disciplined construction flow:
 //! set the required fields, call `.build()`, and get back an unsigned
-//! [`Transaction`] with a deterministic ID derived from its contents.
+//! [`Transaction`] with a deterministic ID derived from its contents -- or a
+//! [`BuildError`] naming the field that is missing or out of bounds.
 //!
 //! The builder does not sign -- that happens in [`super::signing`]. This
 //! separation keeps construction testable without key material.
 
+#[cfg(feature = "std")]
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-
+use thiserror::Error;
+
+use super::canonical::{self, CANONICAL_TX_VERSION};
+#[cfg(feature = "std")]
+use super::envelope::{self, EnvelopeError};
+use super::limits;
+#[cfg(feature = "std")]
+use super::payload::{self, TransactionPayload};
 use super::types::{Amount, Currency, TransactionType};
+use super::verification::TransactionError;
+use crate::alloc_prelude::*;
 use crate::crypto::hash::double_sha256;
+#[cfg(feature = "std")]
+use crate::crypto::keys::NovaPublicKey;
+use crate::encoding;
+use crate::identity::nova_id::AddressPolicy;
 
 // ---------------------------------------------------------------------------
 // Transaction
@@ -31,15 +46,19 @@ use crate::crypto::hash::double_sha256;
 ///
 /// The signing and ID computation use [`Transaction::signable_bytes`], which
 /// deterministically serializes: version, tx_type, sender, receiver, amount
-/// value, amount currency, fee, nonce, timestamp, payload. Signature,
-/// sender_public_key, and ZKP proof are excluded.
+/// value, amount currency, fee, nonce, timestamp, payload, and the optional
+/// expiry, memo and fee payer. Signatures, public keys, and ZKP proof are
+/// excluded. The layout depends on `version` and is specified in
+/// [`super::canonical`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Transaction {
     /// Transaction ID: `hex(double_sha256(signable_bytes))`.
+    #[serde(with = "encoding::hex_string")]
     pub id: String,
 
     /// Protocol version at the time of creation. Allows validators to
-    /// apply the correct rule set during verification.
+    /// apply the correct rule set during verification, and selects the
+    /// canonical encoding (see [`super::canonical`]).
     pub version: u16,
 
     /// The operation this transaction represents.
@@ -55,6 +74,7 @@ pub struct Transaction {
     pub amount: Amount,
 
     /// Fee paid to validators, in photons (NOVA smallest unit).
+    #[serde(with = "encoding::amount")]
     pub fee: u64,
 
     /// Monotonically increasing per-sender sequence number.
@@ -66,83 +86,77 @@ pub struct Transaction {
 
     /// Optional application-specific payload (smart contract calls,
     /// binary memos, etc.). For human-readable memos, encode as UTF-8.
+    #[serde(with = "encoding::hex_bytes_opt")]
     pub payload: Option<Vec<u8>>,
 
     /// Hex-encoded sender public key. Embedded in the transaction so that
     /// validators can verify the signature without a separate key lookup.
     /// Set during signing via [`super::signing::sign_transaction`].
+    #[serde(with = "encoding::hex_string_opt")]
     pub sender_public_key: Option<String>,
 
     /// Ed25519 signature over [`Transaction::signable_bytes`], hex-encoded.
     /// `None` for unsigned transactions fresh from the builder.
+    #[serde(with = "encoding::hex_string_opt")]
     pub signature: Option<String>,
 
     /// Optional zero-knowledge proof bytes (e.g., a Groth16 balance proof).
     /// Attached for shielded transactions; `None` for transparent ones.
+    #[serde(with = "encoding::hex_bytes_opt")]
     pub zkp_proof: Option<Vec<u8>>,
 
     /// Serialized Groth16 proof for confidential transfers. Validators
     /// deserialize and verify this using the balance-proof verifier.
+    #[serde(with = "encoding::hex_bytes_opt")]
     pub proof: Option<Vec<u8>>,
 
     /// Serialized Pedersen commitment binding the hidden transfer amount.
     /// Required when `proof` is present; together they enable amount-hiding
     /// transfers that are still publicly verifiable.
+    #[serde(with = "encoding::hex_bytes_opt")]
     pub amount_commitment: Option<Vec<u8>>,
+
+    /// Unix timestamp in milliseconds after which the transaction can no
+    /// longer be included in a block. `None` never expires. Lets a sender
+    /// re-sign a payment that did not confirm without the stale copy
+    /// landing later.
+    #[serde(default)]
+    pub valid_until: Option<u64>,
+
+    /// Merchant-facing reference such as an order or invoice number, at
+    /// most [`MAX_TX_MEMO_BYTES`](super::limits::MAX_TX_MEMO_BYTES) bytes.
+    /// Signed, and copied into receipts. Unlike a payload memo it is
+    /// available on every transaction type.
+    #[serde(default)]
+    pub memo: Option<String>,
+
+    /// Address that pays the fee instead of the sender, for applications
+    /// sponsoring their users' transactions. Signed by the sender, so a
+    /// sponsor cannot be swapped in; the sponsor co-signs the same bytes.
+    /// Only transfers can be sponsored.
+    #[serde(default)]
+    pub fee_payer: Option<String>,
+
+    /// Hex-encoded public key of the `fee_payer`. Set by
+    /// [`super::signing::sign_as_fee_payer`].
+    #[serde(default, with = "encoding::hex_string_opt")]
+    pub fee_payer_public_key: Option<String>,
+
+    /// The fee payer's Ed25519 signature over
+    /// [`Transaction::signable_bytes`], hex-encoded.
+    #[serde(default, with = "encoding::hex_string_opt")]
+    pub fee_payer_signature: Option<String>,
 }
 
 impl Transaction {
-    /// Returns the canonical byte representation used for signing and ID
-    /// computation.
-    ///
-    /// The format is a deterministic concatenation of fields with null-byte
-    /// separators and fixed-width little-endian integers. JSON/serde is
-    /// intentionally avoided because field ordering is not guaranteed across
-    /// serialization formats.
+    /// Returns the bytes signed by the sender and hashed into the ID: the
+    /// [`canonical_bytes`](Self::canonical_bytes) for this transaction's
+    /// `version`.
     ///
-    /// Excluded fields: `id`, `sender_public_key`, `signature`, `zkp_proof`.
+    /// JSON/serde is intentionally avoided because field ordering is not
+    /// guaranteed across serialization formats.
     pub fn signable_bytes(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(256);
-
-        // Protocol version (2 bytes, LE).
-        buf.extend_from_slice(&self.version.to_le_bytes());
-
-        // Transaction type discriminant.
-        buf.extend_from_slice(format!("{}", self.tx_type).as_bytes());
-        buf.push(0x00);
-
-        // Sender address.
-        buf.extend_from_slice(self.sender.as_bytes());
-        buf.push(0x00);
-
-        // Receiver address.
-        buf.extend_from_slice(self.receiver.as_bytes());
-        buf.push(0x00);
-
-        // Amount: value as little-endian u64, then currency string.
-        buf.extend_from_slice(&self.amount.value.to_le_bytes());
-        buf.extend_from_slice(format!("{}", self.amount.currency).as_bytes());
-        buf.push(0x00);
-
-        // Fee as little-endian u64.
-        buf.extend_from_slice(&self.fee.to_le_bytes());
-
-        // Nonce as little-endian u64.
-        buf.extend_from_slice(&self.nonce.to_le_bytes());
-
-        // Timestamp as little-endian u64.
-        buf.extend_from_slice(&self.timestamp.to_le_bytes());
-
-        // Payload (length-prefixed if present).
-        if let Some(ref payload) = self.payload {
-            buf.push(0x01); // payload-present flag
-            buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
-            buf.extend_from_slice(payload);
-        } else {
-            buf.push(0x00); // no-payload flag
-        }
-
-        buf
+        self.canonical_bytes()
     }
 
     /// Computes the transaction ID from the current field values.
@@ -160,14 +174,10 @@ impl Transaction {
         // modifying them invalidates the hash. These are appended after
         // signing, so they are NOT part of signable_bytes().
         if let Some(ref proof) = self.proof {
-            buf.push(0x02); // proof-present tag
-            buf.extend_from_slice(&(proof.len() as u32).to_le_bytes());
-            buf.extend_from_slice(proof);
+            canonical::put_extension(&mut buf, canonical::TAG_PROOF, proof);
         }
         if let Some(ref commitment) = self.amount_commitment {
-            buf.push(0x03); // commitment-present tag
-            buf.extend_from_slice(&(commitment.len() as u32).to_le_bytes());
-            buf.extend_from_slice(commitment);
+            canonical::put_extension(&mut buf, canonical::TAG_COMMITMENT, commitment);
         }
 
         let hash = double_sha256(&buf);
@@ -196,6 +206,17 @@ impl Transaction {
         self.signature.is_some()
     }
 
+    /// Returns `true` if a `fee_payer` other than the sender pays the fee.
+    pub fn is_sponsored(&self) -> bool {
+        self.fee_payer.is_some()
+    }
+
+    /// The address the fee is debited from: the `fee_payer` if set,
+    /// otherwise the sender.
+    pub fn fee_source(&self) -> &str {
+        self.fee_payer.as_deref().unwrap_or(&self.sender)
+    }
+
     /// Returns `true` if the transaction includes a zero-knowledge proof.
     pub fn is_shielded(&self) -> bool {
         self.zkp_proof.is_some()
@@ -233,6 +254,13 @@ impl Transaction {
     pub fn has_commitment(&self) -> bool {
         self.amount_commitment.is_some()
     }
+
+    /// Returns `true` if the transaction's `valid_until` lies before
+    /// `now_ms`. Transactions without an expiry never expire.
+    pub fn is_expired_at(&self, now_ms: u64) -> bool {
+        self.valid_until
+            .is_some_and(|valid_until| now_ms > valid_until)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -253,7 +281,8 @@ impl Transaction {
 ///     .amount(Amount::new(50_000_000, Currency::NOVA))
 ///     .fee(1_000)
 ///     .nonce(1)
-///     .build();
+///     .build()?;
+/// # Ok::<(), nova_protocol::transaction::BuildError>(())
 /// ```
 ///
 /// The builder sets `version` to the current protocol version and `timestamp`
@@ -263,32 +292,42 @@ pub struct TransactionBuilder {
     tx_type: TransactionType,
     sender: String,
     receiver: String,
-    amount: Amount,
+    amount: Option<Amount>,
     fee: u64,
     nonce: u64,
     timestamp: Option<u64>,
     payload: Option<Vec<u8>>,
+    valid_until: Option<u64>,
+    memo: Option<String>,
+    fee_payer: Option<String>,
+    #[cfg(feature = "std")]
+    encrypt_for: Option<[u8; 32]>,
 }
 
 impl TransactionBuilder {
     /// Creates a new builder for the given transaction type.
     ///
     /// Defaults:
-    /// - `version`: 1 (current protocol version)
+    /// - `version`: [`CANONICAL_TX_VERSION`] (current protocol version)
     /// - `fee`: 0 (caller should set an appropriate fee)
     /// - `nonce`: 0
     /// - `timestamp`: set automatically at build time
     pub fn new(tx_type: TransactionType) -> Self {
         Self {
-            version: 1,
+            version: CANONICAL_TX_VERSION,
             tx_type,
             sender: String::new(),
             receiver: String::new(),
-            amount: Amount::new(0, Currency::NOVA),
+            amount: None,
             fee: 0,
             nonce: 0,
             timestamp: None,
             payload: None,
+            valid_until: None,
+            memo: None,
+            fee_payer: None,
+            #[cfg(feature = "std")]
+            encrypt_for: None,
         }
     }
 
@@ -312,7 +351,7 @@ impl TransactionBuilder {
 
     /// Sets the transfer amount.
     pub fn amount(mut self, amount: Amount) -> Self {
-        self.amount = amount;
+        self.amount = Some(amount);
         self
     }
 
@@ -330,7 +369,8 @@ impl TransactionBuilder {
 
     /// Sets the timestamp explicitly (Unix milliseconds).
     ///
-    /// If not called, `build()` will use the current UTC time.
+    /// If not called, `build()` will use the current UTC time, or fail
+    /// without `std`.
     pub fn timestamp(mut self, timestamp: u64) -> Self {
         self.timestamp = Some(timestamp);
         self
@@ -342,14 +382,103 @@ impl TransactionBuilder {
         self
     }
 
+    /// Attaches a typed payload for a token or credit transaction (see
+    /// [`payload`](super::payload)).
+    #[cfg(feature = "std")]
+    pub fn payload_typed(mut self, payload: &TransactionPayload) -> Self {
+        self.payload = Some(payload.encode());
+        self
+    }
+
+    /// Sets the expiry (Unix milliseconds). Past it, the transaction is
+    /// rejected by verification and evicted from mempools.
+    pub fn valid_until(mut self, valid_until: u64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    /// Attaches a reference such as an order number.
+    pub fn memo(mut self, memo: &str) -> Self {
+        self.memo = Some(memo.to_string());
+        self
+    }
+
+    /// Has `address` pay the fee instead of the sender. The sponsor must
+    /// co-sign with [`sign_as_fee_payer`](super::signing::sign_as_fee_payer).
+    pub fn fee_payer(mut self, address: &str) -> Self {
+        self.fee_payer = Some(address.to_string());
+        self
+    }
+
+    /// Encrypts the payload at build time so only the holder of `receiver`
+    /// can read it (see [`envelope`](super::envelope)). Fails if the key
+    /// cannot be used for encryption.
+    #[cfg(feature = "std")]
+    pub fn encrypt_payload_for(mut self, receiver: &NovaPublicKey) -> Result<Self, EnvelopeError> {
+        self.encrypt_for = Some(envelope::receiver_key(receiver)?);
+        Ok(self)
+    }
+
     /// Consumes the builder and produces an unsigned [`Transaction`].
     ///
     /// The transaction ID is computed automatically from the signable bytes.
     /// The `signature`, `sender_public_key`, and `zkp_proof` fields are `None`.
-    pub fn build(self) -> Transaction {
+    ///
+    /// Fails if the sender or receiver was never set, if the amount was
+    /// never set on a type that moves value, if the transaction violates the
+    /// protocol field limits (payload and memo sizes, address format,
+    /// per-type amount rule) or, with `std`, if it carries a payload its
+    /// type does not accept. An unset timestamp defaults to the current
+    /// time; without `std` there is no clock and it is an error instead.
+    pub fn build(self) -> Result<Transaction, BuildError> {
+        if self.sender.is_empty() {
+            return Err(BuildError::MissingSender);
+        }
+        if self.receiver.is_empty() {
+            return Err(BuildError::MissingReceiver);
+        }
+        if self.amount.is_none() && !limits::allows_zero_amount(self.tx_type) {
+            return Err(BuildError::MissingAmount(self.tx_type));
+        }
+        if cfg!(not(feature = "std")) && self.timestamp.is_none() {
+            return Err(BuildError::MissingTimestamp);
+        }
+
+        let tx = self.build_unchecked();
+        limits::check_fields(&tx, AddressPolicy::Strict)?;
+        #[cfg(feature = "std")]
+        payload::check(&tx).map_err(|e| TransactionError::InvalidPayload {
+            tx_type: tx.tx_type,
+            reason: e.to_string(),
+        })?;
+        Ok(tx)
+    }
+
+    /// Like [`build`](Self::build), but skips every check: unset fields
+    /// stay empty, an unset amount is zero NOVA and, without `std`, an
+    /// unset timestamp is 0.
+    ///
+    /// For tests and tooling that construct deliberately invalid
+    /// transactions. Anything headed for the network should come through
+    /// `build`.
+    pub fn build_unchecked(self) -> Transaction {
+        #[cfg(feature = "std")]
         let timestamp = self
             .timestamp
             .unwrap_or_else(|| Utc::now().timestamp_millis() as u64);
+        #[cfg(not(feature = "std"))]
+        let timestamp = self.timestamp.unwrap_or(0);
+
+        #[cfg(feature = "std")]
+        let payload = match (self.payload, self.encrypt_for) {
+            (Some(plaintext), Some(key)) => Some(
+                envelope::seal(&key, &self.sender, &self.receiver, self.nonce, &plaintext)
+                    .expect("receiver key was checked when set"),
+            ),
+            (payload, _) => payload,
+        };
+        #[cfg(not(feature = "std"))]
+        let payload = self.payload;
 
         let mut tx = Transaction {
             id: String::new(),
@@ -357,16 +486,23 @@ impl TransactionBuilder {
             tx_type: self.tx_type,
             sender: self.sender,
             receiver: self.receiver,
-            amount: self.amount,
+            amount: self
+                .amount
+                .unwrap_or_else(|| Amount::new(0, Currency::NOVA)),
             fee: self.fee,
             nonce: self.nonce,
             timestamp,
-            payload: self.payload,
+            payload,
             sender_public_key: None,
             signature: None,
             zkp_proof: None,
             proof: None,
             amount_commitment: None,
+            valid_until: self.valid_until,
+            memo: self.memo,
+            fee_payer: self.fee_payer,
+            fee_payer_public_key: None,
+            fee_payer_signature: None,
         };
 
         tx.id = tx.compute_id();
@@ -374,6 +510,26 @@ impl TransactionBuilder {
     }
 }
 
+/// Why [`TransactionBuilder::build`] refused to produce a transaction.
+#[derive(Debug, Error)]
+pub enum BuildError {
+    /// No sender address was set.
+    #[error("transaction has no sender")]
+    MissingSender,
+    /// No receiver address was set.
+    #[error("transaction has no receiver")]
+    MissingReceiver,
+    /// No amount was set on a type that moves value.
+    #[error("{0} transaction has no amount")]
+    MissingAmount(TransactionType),
+    /// No timestamp was set, and there is no clock to default to.
+    #[error("transaction has no timestamp")]
+    MissingTimestamp,
+    /// The fields are set but the transaction is invalid.
+    #[error(transparent)]
+    Invalid(#[from] TransactionError),
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -390,7 +546,64 @@ mod tests {
             .fee(100)
             .nonce(1)
             .timestamp(1_700_000_000_000)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn build_refuses_missing_fields() {
+        use crate::crypto::keys::NovaKeypair;
+        use crate::identity::nova_id::NovaId;
+
+        let address =
+            || NovaId::from_public_key(&NovaKeypair::generate().public_key()).to_address();
+        let (alice, bob) = (address(), address());
+        let transfer = || {
+            TransactionBuilder::new(TransactionType::Transfer)
+                .nonce(1)
+                .timestamp(1_700_000_000_000)
+        };
+
+        assert!(matches!(
+            transfer().receiver(&bob).build(),
+            Err(BuildError::MissingSender)
+        ));
+        assert!(matches!(
+            transfer().sender(&alice).build(),
+            Err(BuildError::MissingReceiver)
+        ));
+        assert!(matches!(
+            transfer().sender(&alice).receiver(&bob).build(),
+            Err(BuildError::MissingAmount(TransactionType::Transfer))
+        ));
+        assert!(matches!(
+            transfer()
+                .sender(&alice)
+                .receiver("nova1bob")
+                .amount(Amount::new(1, Currency::NOVA))
+                .build(),
+            Err(BuildError::Invalid(TransactionError::InvalidAddress(_)))
+        ));
+
+        let tx = transfer()
+            .sender(&alice)
+            .receiver(&bob)
+            .amount(Amount::new(1, Currency::NOVA))
+            .build()
+            .unwrap();
+        assert_eq!(tx.id, tx.compute_id());
+
+        // Types that move no value may leave the amount unset.
+        let unjail = TransactionBuilder::new(TransactionType::Unjail)
+            .sender(&alice)
+            .receiver(&alice)
             .build()
+            .unwrap();
+        assert_eq!(unjail.amount.value, 0);
+
+        // The escape hatch builds whatever it is given.
+        let empty = TransactionBuilder::new(TransactionType::Transfer).build_unchecked();
+        assert!(empty.sender.is_empty());
+        assert_eq!(empty.amount.value, 0);
     }
 
     #[test]
@@ -423,7 +636,7 @@ mod tests {
             .amount(Amount::new(1000, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let tx2 = TransactionBuilder::new(TransactionType::Transfer)
             .sender("nova1aaaa")
@@ -431,7 +644,7 @@ mod tests {
             .amount(Amount::new(1000, Currency::NOVA))
             .nonce(2)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         assert_ne!(tx1.id, tx2.id);
     }
@@ -457,7 +670,7 @@ mod tests {
             .receiver("nova1bbbb")
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
         let after = Utc::now().timestamp_millis() as u64;
 
         assert!(tx.timestamp >= before);
@@ -472,6 +685,24 @@ mod tests {
         assert_eq!(tx, recovered);
     }
 
+    #[test]
+    fn transaction_json_follows_wire_policy() {
+        let mut tx = sample_tx();
+        tx.payload = Some(vec![0xca, 0xfe]);
+        tx.fee = u64::MAX;
+        tx.id = tx.compute_id();
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["id"], format!("0x{}", tx.id));
+        assert_eq!(json["payload"], "0xcafe");
+        assert_eq!(json["fee"], u64::MAX.to_string());
+        assert_eq!(json["amount"]["value"], 1_000_000);
+
+        let recovered: Transaction = serde_json::from_value(json).unwrap();
+        assert_eq!(recovered, tx, "in-memory ID stays unprefixed");
+        assert_eq!(recovered.compute_id(), tx.id);
+    }
+
     #[test]
     fn signable_bytes_exclude_signature() {
         let mut tx = sample_tx();
@@ -522,7 +753,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let tx_with_payload = TransactionBuilder::new(TransactionType::Transfer)
             .sender("nova1aaaa")
@@ -531,7 +762,7 @@ mod tests {
             .nonce(1)
             .timestamp(1_700_000_000_000)
             .payload(b"hello world".to_vec())
-            .build();
+            .build_unchecked();
 
         assert_ne!(
             tx_no_payload.signable_bytes(),
@@ -549,7 +780,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let tx_v2 = TransactionBuilder::new(TransactionType::Transfer)
             .version(2)
@@ -558,7 +789,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         assert_ne!(
             tx_v1.id, tx_v2.id,
@@ -567,9 +798,77 @@ mod tests {
     }
 
     #[test]
-    fn default_version_is_one() {
+    fn expiry_is_signed_only_when_set() {
+        let builder = || {
+            TransactionBuilder::new(TransactionType::Transfer)
+                .sender("nova1aaaa")
+                .receiver("nova1bbbb")
+                .amount(Amount::new(100, Currency::NOVA))
+                .nonce(1)
+                .timestamp(1_700_000_000_000)
+        };
+        let without = builder().build_unchecked();
+        let with = builder().valid_until(1_700_000_060_000).build_unchecked();
+
+        // Appended after the existing fields, so old transactions keep
+        // their IDs.
+        assert!(with.signable_bytes().starts_with(&without.signable_bytes()));
+        assert_ne!(with.id, without.id);
+
+        assert!(!with.is_expired_at(1_700_000_060_000));
+        assert!(with.is_expired_at(1_700_000_060_001));
+        assert!(!without.is_expired_at(u64::MAX));
+    }
+
+    #[test]
+    fn memo_is_signed_only_when_set() {
+        let builder = || {
+            TransactionBuilder::new(TransactionType::Transfer)
+                .sender("nova1aaaa")
+                .receiver("nova1bbbb")
+                .amount(Amount::new(100, Currency::NOVA))
+                .nonce(1)
+                .timestamp(1_700_000_000_000)
+        };
+        let without = builder().build_unchecked();
+        let order_1 = builder().memo("order-1").build_unchecked();
+        let order_2 = builder().memo("order-2").build_unchecked();
+
+        assert!(order_1
+            .signable_bytes()
+            .starts_with(&without.signable_bytes()));
+        assert_ne!(order_1.id, without.id);
+        assert_ne!(order_1.id, order_2.id);
+    }
+
+    #[test]
+    fn fee_payer_is_signed_only_when_set() {
+        let builder = || {
+            TransactionBuilder::new(TransactionType::Transfer)
+                .sender("nova1aaaa")
+                .receiver("nova1bbbb")
+                .amount(Amount::new(100, Currency::NOVA))
+                .fee(10)
+                .nonce(1)
+                .timestamp(1_700_000_000_000)
+        };
+        let own = builder().build_unchecked();
+        let sponsored = builder().fee_payer("nova1app").build_unchecked();
+
+        assert!(!own.is_sponsored());
+        assert_eq!(own.fee_source(), "nova1aaaa");
+        assert!(sponsored.is_sponsored());
+        assert_eq!(sponsored.fee_source(), "nova1app");
+        assert!(sponsored
+            .signable_bytes()
+            .starts_with(&own.signable_bytes()));
+        assert_ne!(sponsored.id, own.id);
+    }
+
+    #[test]
+    fn default_version_is_canonical() {
         let tx = sample_tx();
-        assert_eq!(tx.version, 1);
+        assert_eq!(tx.version, CANONICAL_TX_VERSION);
     }
 
     #[test]
@@ -581,7 +880,7 @@ mod tests {
             .fee(10_000)
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         assert!(tx.fee_per_byte() > 0);
     }
@@ -0,0 +1,501 @@
+//! Offline signing bundles for cold-storage keys.
+//!
+//! Treasury keys live on air-gapped machines. A [`TransactionBundle`] carries
+//! an unsigned transaction from the online machine to the signer and the
+//! signed one back, as a JSON file or as a run of QR codes:
+//!
+//! ```text
+//! online:   build tx ──► TransactionBundle::export ──► file / QR chunks
+//! offline:  TransactionBundle::verify ──► show summary ──► sign
+//! online:   TransactionBundle::signed_transaction ──► nova_sendTransaction
+//! ```
+//!
+//! Each bundle repeats the transaction's canonical signing bytes
+//! ([`Transaction::signable_bytes`]), their SHA-256 digest and a
+//! human-readable summary. [`TransactionBundle::verify`] recomputes all three
+//! from the transaction, so a bundle edited in transit fails on whichever
+//! machine reads it next, and the summary the signer reads is the one that
+//! describes what it signs. Operators should compare the digest shown on
+//! both machines before signing.
+//!
+//! ## QR Chunks
+//!
+//! A bundle's compact JSON rarely fits one QR code, so
+//! [`TransactionBundle::to_qr_chunks`] splits it into strings of the form
+//!
+//! ```text
+//! NOVATX/<index>/<total>/<digest prefix>/<data>
+//! ```
+//!
+//! with 1-based indices. The scanner may read them in any order;
+//! [`TransactionBundle::from_qr_chunks`] refuses sets that are incomplete,
+//! mix two bundles, or reassemble into a bundle whose digest does not match
+//! the prefix.
+//!
+//! ## Files
+//!
+//! [`TransactionBundle::to_file`] and [`TransactionBundle::from_file`] (with
+//! `std`) move bundles through the filesystem, so any program that builds
+//! transactions — not just `nova-node tx export` — can hand one to the
+//! offline signer. Readers accept either form: a JSON file or QR chunk
+//! strings one per line ([`TransactionBundle::parse`]).
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::builder::Transaction;
+use super::limits;
+use super::signing::sign_transaction;
+use super::types::{Amount, Currency};
+use super::verification::{verify_transaction_with_policy, TransactionError};
+use crate::alloc_prelude::*;
+use crate::crypto::hash::sha256;
+use crate::crypto::keys::NovaKeypair;
+use crate::encoding;
+use crate::identity::nova_id::{AddressPolicy, NovaId};
+
+/// Bundle format version written by [`TransactionBundle::export`].
+pub const BUNDLE_VERSION: u8 = 1;
+
+/// Leading tag of every QR chunk.
+pub const QR_CHUNK_PREFIX: &str = "NOVATX";
+
+/// Default data characters per QR chunk. With the header this stays inside
+/// a version 25 code at medium error correction.
+pub const DEFAULT_QR_CHUNK_LEN: usize = 800;
+
+/// Hex characters of the digest repeated in every QR chunk header.
+const QR_DIGEST_PREFIX_LEN: usize = 16;
+
+/// Why a bundle was refused.
+#[derive(Debug, Error)]
+pub enum BundleError {
+    /// The bundle was written by a newer, unknown format.
+    #[error("unsupported bundle version {0}")]
+    UnsupportedVersion(u8),
+
+    /// `canonical` is not the transaction's signing bytes.
+    #[error("canonical bytes do not match the transaction")]
+    CanonicalMismatch,
+
+    /// `digest` is not the SHA-256 of the canonical bytes.
+    #[error("digest mismatch: bundle says {expected}, transaction hashes to {actual}")]
+    DigestMismatch { expected: String, actual: String },
+
+    /// `summary` does not describe the transaction.
+    #[error("summary does not match the transaction")]
+    SummaryMismatch,
+
+    /// Only unsigned transactions can be exported, and signed bundles
+    /// cannot be signed again.
+    #[error("transaction is already signed")]
+    AlreadySigned,
+
+    /// The bundle has not been through the offline signer yet.
+    #[error("bundle is not signed")]
+    NotSigned,
+
+    /// The signing key does not own the sender address.
+    #[error("key address {key_address} is not the sender {sender}")]
+    KeyMismatch { sender: String, key_address: String },
+
+    /// The transaction inside failed verification.
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+
+    /// The bundle or its chunks could not be parsed.
+    #[error("malformed bundle: {0}")]
+    Malformed(String),
+
+    /// A bundle file could not be read or written.
+    #[error("bundle file: {0}")]
+    File(String),
+}
+
+/// An unsigned or signed transaction in transit between an online machine
+/// and an offline signer (see the module docs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionBundle {
+    /// Format version, [`BUNDLE_VERSION`].
+    pub version: u8,
+    /// The transaction; unsigned until the offline signer returns it.
+    pub transaction: Transaction,
+    /// [`Transaction::signable_bytes`] — exactly what the key signs.
+    #[serde(with = "encoding::hex_bytes")]
+    pub canonical: Vec<u8>,
+    /// Hex SHA-256 of `canonical`, for operators to compare across machines.
+    pub digest: String,
+    /// Human-readable description, see [`summarize`].
+    pub summary: String,
+}
+
+impl TransactionBundle {
+    /// Wraps an unsigned transaction for the offline signer.
+    pub fn export(tx: Transaction) -> Result<Self, BundleError> {
+        if tx.is_signed() {
+            return Err(BundleError::AlreadySigned);
+        }
+        let canonical = tx.signable_bytes();
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            digest: hex::encode(sha256(&canonical)),
+            summary: summarize(&tx),
+            canonical,
+            transaction: tx,
+        })
+    }
+
+    /// Recomputes the canonical bytes, digest and summary from the
+    /// transaction and checks the bundle's copies against them. A signed
+    /// bundle must also carry a valid signature by the sender.
+    pub fn verify(&self) -> Result<(), BundleError> {
+        if self.version != BUNDLE_VERSION {
+            return Err(BundleError::UnsupportedVersion(self.version));
+        }
+        let canonical = self.transaction.signable_bytes();
+        if canonical != self.canonical {
+            return Err(BundleError::CanonicalMismatch);
+        }
+        let digest = hex::encode(sha256(&canonical));
+        if digest != self.digest {
+            return Err(BundleError::DigestMismatch {
+                expected: self.digest.clone(),
+                actual: digest,
+            });
+        }
+        if summarize(&self.transaction) != self.summary {
+            return Err(BundleError::SummaryMismatch);
+        }
+        let id = self.transaction.compute_id();
+        if id != self.transaction.id {
+            return Err(TransactionError::IdMismatch {
+                expected: id,
+                actual: self.transaction.id.clone(),
+            }
+            .into());
+        }
+        if self.is_signed() {
+            // Addresses were checked when the transaction was built; the
+            // node applies its own policy again on broadcast.
+            verify_transaction_with_policy(&self.transaction, AddressPolicy::Permissive)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` once the offline signer has signed the transaction.
+    pub fn is_signed(&self) -> bool {
+        self.transaction.is_signed()
+    }
+
+    /// Verifies the bundle and signs its transaction with `keypair`, which
+    /// must own the sender address. Touches nothing but the bundle.
+    pub fn sign(mut self, keypair: &NovaKeypair) -> Result<Self, BundleError> {
+        self.verify()?;
+        if self.is_signed() {
+            return Err(BundleError::AlreadySigned);
+        }
+        let key_address = NovaId::from_public_key(&keypair.public_key()).to_address();
+        if key_address != self.transaction.sender {
+            return Err(BundleError::KeyMismatch {
+                sender: self.transaction.sender.clone(),
+                key_address,
+            });
+        }
+        sign_transaction(&mut self.transaction, keypair);
+        Ok(self)
+    }
+
+    /// Verifies a signed bundle and returns its transaction for broadcast.
+    pub fn signed_transaction(&self) -> Result<Transaction, BundleError> {
+        if !self.is_signed() {
+            return Err(BundleError::NotSigned);
+        }
+        self.verify()?;
+        Ok(self.transaction.clone())
+    }
+
+    /// Pretty-printed JSON, the file format.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("bundle serialization is infallible")
+    }
+
+    /// Parses and verifies a bundle file.
+    pub fn from_json(json: &str) -> Result<Self, BundleError> {
+        let bundle: Self =
+            serde_json::from_str(json).map_err(|e| BundleError::Malformed(e.to_string()))?;
+        bundle.verify()?;
+        Ok(bundle)
+    }
+
+    /// Parses and verifies a bundle written as JSON or as QR chunk strings
+    /// one per line, the two forms a bundle file may take.
+    pub fn parse(text: &str) -> Result<Self, BundleError> {
+        if text.trim_start().starts_with(QR_CHUNK_PREFIX) {
+            let chunks: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+            return Self::from_qr_chunks(&chunks);
+        }
+        Self::from_json(text)
+    }
+
+    /// Writes the bundle to `path` as JSON.
+    #[cfg(feature = "std")]
+    pub fn to_file(&self, path: impl AsRef<std::path::Path>) -> Result<(), BundleError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json() + "\n")
+            .map_err(|e| BundleError::File(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Reads and verifies a bundle file in either form (see [`parse`](Self::parse)).
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, BundleError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| BundleError::File(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text)
+    }
+
+    /// Splits the bundle's compact JSON into QR chunk strings carrying at
+    /// most `max_len` data characters each (see the module docs).
+    pub fn to_qr_chunks(&self, max_len: usize) -> Vec<String> {
+        let json = serde_json::to_string(self).expect("bundle serialization is infallible");
+        // Compact JSON of a bundle is ASCII, so byte chunks are char chunks.
+        let parts: Vec<&[u8]> = json.as_bytes().chunks(max_len.max(1)).collect();
+        let prefix = &self.digest[..QR_DIGEST_PREFIX_LEN.min(self.digest.len())];
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                format!(
+                    "{}/{}/{}/{}/{}",
+                    QR_CHUNK_PREFIX,
+                    i + 1,
+                    parts.len(),
+                    prefix,
+                    String::from_utf8_lossy(part)
+                )
+            })
+            .collect()
+    }
+
+    /// Reassembles and verifies a bundle from scanned QR chunks, in any
+    /// order.
+    pub fn from_qr_chunks<S: AsRef<str>>(chunks: &[S]) -> Result<Self, BundleError> {
+        let malformed = |reason: &str| BundleError::Malformed(reason.to_string());
+        let mut header: Option<(usize, String)> = None;
+        let mut parts: Vec<Option<&str>> = Vec::new();
+
+        for chunk in chunks {
+            let mut fields = chunk.as_ref().trim().splitn(5, '/');
+            if fields.next() != Some(QR_CHUNK_PREFIX) {
+                return Err(malformed("not a NOVATX chunk"));
+            }
+            let mut number = || {
+                fields
+                    .next()
+                    .and_then(|f| f.parse::<usize>().ok())
+                    .ok_or_else(|| malformed("bad chunk header"))
+            };
+            let (index, total) = (number()?, number()?);
+            let prefix = fields.next().ok_or_else(|| malformed("bad chunk header"))?;
+            let data = fields
+                .next()
+                .ok_or_else(|| malformed("chunk has no data"))?;
+            if total == 0 || total > chunks.len() {
+                return Err(malformed("incomplete chunk set"));
+            }
+
+            match &header {
+                None => {
+                    header = Some((total, prefix.to_string()));
+                    parts = vec![None; total];
+                }
+                Some((t, p)) if *t != total || p != prefix => {
+                    return Err(malformed("chunks belong to different bundles"));
+                }
+                Some(_) => {}
+            }
+            if index == 0 || index > total {
+                return Err(malformed("chunk index out of range"));
+            }
+            parts[index - 1] = Some(data);
+        }
+
+        let (_, prefix) = header.ok_or_else(|| malformed("no chunks"))?;
+        let mut json = String::new();
+        for (i, part) in parts.iter().enumerate() {
+            let part = part.ok_or_else(|| {
+                BundleError::Malformed(format!("missing chunk {} of {}", i + 1, parts.len()))
+            })?;
+            json.push_str(part);
+        }
+        let bundle = Self::from_json(&json)?;
+        if !bundle.digest.starts_with(&prefix) {
+            return Err(BundleError::DigestMismatch {
+                expected: prefix,
+                actual: bundle.digest,
+            });
+        }
+        Ok(bundle)
+    }
+}
+
+/// The human-readable description of `tx` an offline signer shows before
+/// signing. Deterministic, so [`TransactionBundle::verify`] can recompute it.
+pub fn summarize(tx: &Transaction) -> String {
+    let mut out = format!(
+        "{} {}\n  From : {}\n  To   : {}\n  Fee  : {}\n  Nonce: {}\n  Time : {} ms",
+        tx.tx_type,
+        tx.amount.display_decimal(),
+        tx.sender,
+        tx.receiver,
+        Amount::new(tx.fee, Currency::NOVA).display_decimal(),
+        tx.nonce,
+        tx.timestamp,
+    );
+    match &tx.payload {
+        Some(payload) if limits::payload_is_memo(tx.tx_type) => {
+            match core::str::from_utf8(payload) {
+                Ok(memo) => out.push_str(&format!("\n  Memo : {:?}", memo)),
+                Err(_) => out.push_str(&format!("\n  Memo : {} bytes", payload.len())),
+            }
+        }
+        Some(payload) => out.push_str(&format!("\n  Data : 0x{}", hex::encode(payload))),
+        None => {}
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::TransactionType;
+
+    fn address(kp: &NovaKeypair) -> String {
+        NovaId::from_public_key(&kp.public_key()).to_address()
+    }
+
+    fn unsigned(sender: &NovaKeypair) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender(&address(sender))
+            .receiver(&address(&NovaKeypair::from_seed(&[9u8; 32])))
+            .amount(Amount::new(250_000_000, Currency::NOVA))
+            .fee(1_000)
+            .nonce(4)
+            .timestamp(1_700_000_000_000)
+            .payload(b"invoice 42".to_vec())
+            .build_unchecked()
+    }
+
+    #[test]
+    fn export_sign_and_broadcast_round_trip() {
+        let kp = NovaKeypair::from_seed(&[3u8; 32]);
+        let bundle = TransactionBundle::export(unsigned(&kp)).unwrap();
+        assert!(bundle.summary.contains("Transfer 2.50000000 NOVA"));
+        assert!(bundle.summary.contains("\"invoice 42\""));
+        assert!(matches!(
+            bundle.signed_transaction(),
+            Err(BundleError::NotSigned)
+        ));
+
+        // Through a file on the way out, through QR codes on the way back.
+        let offline = TransactionBundle::from_json(&bundle.to_json()).unwrap();
+        let signed = offline.sign(&kp).unwrap();
+        assert_eq!(signed.digest, bundle.digest);
+
+        let mut chunks = signed.to_qr_chunks(120);
+        assert!(chunks.len() > 1);
+        chunks.reverse();
+        let returned = TransactionBundle::from_qr_chunks(&chunks).unwrap();
+        let tx = returned.signed_transaction().unwrap();
+        assert!(verify_transaction_with_policy(&tx, AddressPolicy::Strict).is_ok());
+        assert!(matches!(
+            returned.sign(&kp),
+            Err(BundleError::AlreadySigned)
+        ));
+    }
+
+    #[test]
+    fn tampering_is_caught() {
+        let kp = NovaKeypair::from_seed(&[3u8; 32]);
+        let bundle = TransactionBundle::export(unsigned(&kp)).unwrap();
+
+        // Edited amount, with a recomputed ID to get past the ID check.
+        let mut edited = bundle.clone();
+        edited.transaction.amount.value = 1;
+        edited.transaction.id = edited.transaction.compute_id();
+        assert!(matches!(
+            edited.verify(),
+            Err(BundleError::CanonicalMismatch)
+        ));
+
+        let mut edited = bundle.clone();
+        edited.digest = "00".repeat(32);
+        assert!(matches!(
+            edited.verify(),
+            Err(BundleError::DigestMismatch { .. })
+        ));
+
+        let mut edited = bundle.clone();
+        edited.summary = edited.summary.replace("2.50000000", "0.00000001");
+        assert!(matches!(edited.verify(), Err(BundleError::SummaryMismatch)));
+
+        assert!(matches!(
+            bundle.clone().sign(&NovaKeypair::from_seed(&[4u8; 32])),
+            Err(BundleError::KeyMismatch { .. })
+        ));
+
+        let mut signed = bundle.sign(&kp).unwrap();
+        signed.transaction.signature = Some("00".repeat(64));
+        assert!(matches!(
+            signed.signed_transaction(),
+            Err(BundleError::Transaction(_))
+        ));
+    }
+
+    #[test]
+    fn qr_chunk_sets_must_be_whole_and_consistent() {
+        let kp = NovaKeypair::from_seed(&[3u8; 32]);
+        let a = TransactionBundle::export(unsigned(&kp)).unwrap();
+        let mut other = unsigned(&kp);
+        other.nonce = 5;
+        other.id = other.compute_id();
+        let b = TransactionBundle::export(other).unwrap();
+
+        let chunks = a.to_qr_chunks(100);
+        assert!(chunks.iter().all(|c| c.starts_with("NOVATX/")));
+        assert!(TransactionBundle::from_qr_chunks(&chunks[1..]).is_err());
+
+        let mut mixed = chunks.clone();
+        mixed[0] = b.to_qr_chunks(100)[0].clone();
+        assert!(TransactionBundle::from_qr_chunks(&mixed).is_err());
+
+        assert!(TransactionBundle::from_qr_chunks::<&str>(&[]).is_err());
+        assert_eq!(TransactionBundle::from_qr_chunks(&chunks).unwrap(), a);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn bundles_travel_as_files_in_either_form() {
+        let kp = NovaKeypair::from_seed(&[3u8; 32]);
+        let bundle = TransactionBundle::export(unsigned(&kp)).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = dir.path().join("unsigned.json");
+        bundle.to_file(&path).unwrap();
+        assert_eq!(TransactionBundle::from_file(&path).unwrap(), bundle);
+
+        let qr = dir.path().join("unsigned.qr");
+        std::fs::write(&qr, bundle.to_qr_chunks(64).join("\n") + "\n\n").unwrap();
+        assert_eq!(TransactionBundle::from_file(&qr).unwrap(), bundle);
+
+        assert!(matches!(
+            TransactionBundle::from_file(dir.path().join("missing.json")),
+            Err(BundleError::File(_))
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+//! Canonical byte encoding of transactions.
+//!
+//! A transaction is signed over, and identified by, the bytes returned by
+//! [`Transaction::canonical_bytes`]. Which layout those bytes follow is
+//! decided by the transaction's `version`:
+//!
+//! - **Version 1** (legacy) — the original layout: null-terminated strings,
+//!   a payload flag byte and tagged optional fields. Kept unchanged so
+//!   transactions already on chain keep their IDs.
+//! - **Version 2** — every field in a fixed order, fixed-width
+//!   little-endian integers and `u32` length prefixes, so the bytes can be
+//!   rebuilt from this description alone. [`TransactionBuilder`] produces
+//!   version 2 unless told otherwise, and later versions use this layout
+//!   until one defines its own.
+//!
+//! ## Version 2 layout
+//!
+//! | # | Field             | Encoding                                  |
+//! |---|-------------------|-------------------------------------------|
+//! | 1 | magic             | the 6 ASCII bytes `NOVATX`                |
+//! | 2 | `version`         | `u16`                                     |
+//! | 3 | `tx_type`         | string, the variant name (`Transfer`)     |
+//! | 4 | `sender`          | string                                    |
+//! | 5 | `receiver`        | string                                    |
+//! | 6 | `amount.value`    | `u64`                                     |
+//! | 7 | `amount.currency` | string, the ticker (`NOVA`)               |
+//! | 8 | `fee`             | `u64`                                     |
+//! | 9 | `nonce`           | `u64`                                     |
+//! | 10 | `timestamp`      | `u64`                                     |
+//! | 11 | extensions       | zero or more, in ascending tag order      |
+//!
+//! Integers are little-endian. A string is its UTF-8 byte length as a `u32`
+//! followed by the bytes. An extension is a one-byte tag, the value's byte
+//! length as a `u32`, then the value, and appears only when its field is
+//! set:
+//!
+//! | Tag    | Field         | Value        |
+//! |--------|---------------|--------------|
+//! | `0x01` | `payload`     | raw bytes    |
+//! | `0x04` | `valid_until` | `u64`        |
+//! | `0x05` | `memo`        | UTF-8 bytes  |
+//! | `0x06` | `fee_payer`   | UTF-8 bytes  |
+//!
+//! Tags `0x02` and `0x03` belong to the ID: it is `double_sha256` of the
+//! canonical bytes followed by the `proof` (`0x02`) and `amount_commitment`
+//! (`0x03`) extensions, when attached (see [`Transaction::compute_id`]).
+//!
+//! The golden vectors in this module's tests are the reference for other
+//! implementations.
+//!
+//! [`TransactionBuilder`]: super::builder::TransactionBuilder
+
+use super::builder::Transaction;
+use crate::alloc_prelude::*;
+
+/// The original transaction encoding, kept for existing transactions.
+pub const LEGACY_TX_VERSION: u16 = 1;
+
+/// The length-prefixed encoding described in the module docs. New
+/// transactions use it by default.
+pub const CANONICAL_TX_VERSION: u16 = 2;
+
+/// Domain prefix of version 2 encodings, so the signed bytes cannot be
+/// mistaken for any other message signed with the same key.
+pub const CANONICAL_MAGIC: &[u8; 6] = b"NOVATX";
+
+/// Extension tags, shared by the version 1 and version 2 layouts.
+pub(crate) const TAG_PAYLOAD: u8 = 0x01;
+pub(crate) const TAG_PROOF: u8 = 0x02;
+pub(crate) const TAG_COMMITMENT: u8 = 0x03;
+const TAG_VALID_UNTIL: u8 = 0x04;
+const TAG_MEMO: u8 = 0x05;
+const TAG_FEE_PAYER: u8 = 0x06;
+
+impl Transaction {
+    /// Returns the canonical encoding of the signed fields, in the layout
+    /// selected by `version` (see the [module docs](self)).
+    ///
+    /// Excluded fields: `id`, `sender_public_key`, `signature`, `zkp_proof`,
+    /// `proof`, `amount_commitment`, `fee_payer_public_key`,
+    /// `fee_payer_signature`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        if self.version <= LEGACY_TX_VERSION {
+            legacy_bytes(self)
+        } else {
+            canonical_v2_bytes(self)
+        }
+    }
+}
+
+/// Appends `value` behind a `u32` little-endian length prefix.
+pub(crate) fn put_bytes(buf: &mut Vec<u8>, value: &[u8]) {
+    buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    buf.extend_from_slice(value);
+}
+
+/// Appends an extension: `tag`, then `value` behind its length prefix.
+pub(crate) fn put_extension(buf: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    buf.push(tag);
+    put_bytes(buf, value);
+}
+
+fn canonical_v2_bytes(tx: &Transaction) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(256);
+
+    buf.extend_from_slice(CANONICAL_MAGIC);
+    buf.extend_from_slice(&tx.version.to_le_bytes());
+    put_bytes(&mut buf, format!("{}", tx.tx_type).as_bytes());
+    put_bytes(&mut buf, tx.sender.as_bytes());
+    put_bytes(&mut buf, tx.receiver.as_bytes());
+    buf.extend_from_slice(&tx.amount.value.to_le_bytes());
+    put_bytes(&mut buf, format!("{}", tx.amount.currency).as_bytes());
+    buf.extend_from_slice(&tx.fee.to_le_bytes());
+    buf.extend_from_slice(&tx.nonce.to_le_bytes());
+    buf.extend_from_slice(&tx.timestamp.to_le_bytes());
+
+    if let Some(ref payload) = tx.payload {
+        put_extension(&mut buf, TAG_PAYLOAD, payload);
+    }
+    if let Some(valid_until) = tx.valid_until {
+        put_extension(&mut buf, TAG_VALID_UNTIL, &valid_until.to_le_bytes());
+    }
+    if let Some(ref memo) = tx.memo {
+        put_extension(&mut buf, TAG_MEMO, memo.as_bytes());
+    }
+    if let Some(ref fee_payer) = tx.fee_payer {
+        put_extension(&mut buf, TAG_FEE_PAYER, fee_payer.as_bytes());
+    }
+
+    buf
+}
+
+/// The version 1 layout: a concatenation of fields with null-byte
+/// separators and fixed-width little-endian integers, then the optional
+/// fields, each only when set.
+fn legacy_bytes(tx: &Transaction) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(256);
+
+    // Protocol version (2 bytes, LE).
+    buf.extend_from_slice(&tx.version.to_le_bytes());
+
+    // Transaction type discriminant.
+    buf.extend_from_slice(format!("{}", tx.tx_type).as_bytes());
+    buf.push(0x00);
+
+    // Sender address.
+    buf.extend_from_slice(tx.sender.as_bytes());
+    buf.push(0x00);
+
+    // Receiver address.
+    buf.extend_from_slice(tx.receiver.as_bytes());
+    buf.push(0x00);
+
+    // Amount: value as little-endian u64, then currency string.
+    buf.extend_from_slice(&tx.amount.value.to_le_bytes());
+    buf.extend_from_slice(format!("{}", tx.amount.currency).as_bytes());
+    buf.push(0x00);
+
+    // Fee as little-endian u64.
+    buf.extend_from_slice(&tx.fee.to_le_bytes());
+
+    // Nonce as little-endian u64.
+    buf.extend_from_slice(&tx.nonce.to_le_bytes());
+
+    // Timestamp as little-endian u64.
+    buf.extend_from_slice(&tx.timestamp.to_le_bytes());
+
+    // Payload (length-prefixed if present).
+    if let Some(ref payload) = tx.payload {
+        buf.push(0x01); // payload-present flag
+        put_bytes(&mut buf, payload);
+    } else {
+        buf.push(0x00); // no-payload flag
+    }
+
+    // Expiry, only when set so transactions without one keep their IDs.
+    if let Some(valid_until) = tx.valid_until {
+        buf.push(TAG_VALID_UNTIL);
+        buf.extend_from_slice(&valid_until.to_le_bytes());
+    }
+
+    // Memo (length-prefixed), likewise only when set.
+    if let Some(ref memo) = tx.memo {
+        put_extension(&mut buf, TAG_MEMO, memo.as_bytes());
+    }
+
+    // Fee payer (length-prefixed), likewise only when set.
+    if let Some(ref fee_payer) = tx.fee_payer {
+        put_extension(&mut buf, TAG_FEE_PAYER, fee_payer.as_bytes());
+    }
+
+    buf
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    /// The fields of the cross-language vector in `signing.rs`.
+    fn vector() -> TransactionBuilder {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1sender_test_vector")
+            .receiver("nova1receiver_test_vector")
+            .amount(Amount::new(1_000_000, Currency::NOVA))
+            .fee(100)
+            .nonce(42)
+            .timestamp(1_700_000_000_000)
+    }
+
+    #[test]
+    fn builder_defaults_to_the_canonical_layout() {
+        let tx = vector().build_unchecked();
+        assert_eq!(tx.version, CANONICAL_TX_VERSION);
+        assert!(tx.canonical_bytes().starts_with(CANONICAL_MAGIC));
+        assert_eq!(tx.signable_bytes(), tx.canonical_bytes());
+    }
+
+    #[test]
+    fn golden_vector_plain_transfer() {
+        let tx = vector().build_unchecked();
+
+        // "NOVATX" | 2 (u16) | len "Transfer" | len sender | len receiver |
+        // 1000000 (u64) | len "NOVA" | 100 | 42 | 1700000000000, no extensions
+        assert_eq!(
+            hex::encode(tx.canonical_bytes()),
+            "4e4f564154580200080000005472616e73666572170000006e6f76613173656e6465725f746573745f766563746f72190000006e6f76613172656365697665725f746573745f766563746f7240420f0000000000040000004e4f564164000000000000002a000000000000000068e5cf8b010000",
+        );
+        assert_eq!(
+            tx.id,
+            "456ba57f8572b1655bb34dce62ceccb38736aa331d0f6d0218b93683bf0988b1"
+        );
+    }
+
+    #[test]
+    fn golden_vector_with_extensions() {
+        let tx = vector()
+            .payload(vec![0xca, 0xfe])
+            .valid_until(1_700_000_060_000)
+            .memo("invoice 42")
+            .fee_payer("nova1sponsor_test_vector")
+            .build_unchecked();
+
+        // The plain transfer, then 0x01 payload, 0x04 expiry, 0x05 memo and
+        // 0x06 fee payer, each tag | len (u32) | value.
+        assert_eq!(
+            hex::encode(tx.canonical_bytes()),
+            "4e4f564154580200080000005472616e73666572170000006e6f76613173656e6465725f746573745f766563746f72190000006e6f76613172656365697665725f746573745f766563746f7240420f0000000000040000004e4f564164000000000000002a000000000000000068e5cf8b0100000102000000cafe04080000006052e6cf8b010000050a000000696e766f69636520343206180000006e6f76613173706f6e736f725f746573745f766563746f72",
+        );
+        assert_eq!(
+            tx.id,
+            "0f1617cc851663a3a4f668c649d00e662bd7d9fae42970ffc8ba7b63bf423833"
+        );
+    }
+
+    #[test]
+    fn legacy_version_keeps_its_ids() {
+        let tx = vector().version(LEGACY_TX_VERSION).build_unchecked();
+        assert!(!tx.canonical_bytes().starts_with(CANONICAL_MAGIC));
+        assert_eq!(
+            tx.id,
+            "a8c099ee823f352281802881bf6b55008b4a0f8813808426fe83017e20a5d147"
+        );
+    }
+
+    #[test]
+    fn length_prefixes_keep_fields_apart() {
+        // A separator inside a field shifts bytes between the fields in the
+        // legacy layout; the length prefixes keep them apart.
+        let split = |sender: &str, receiver: &str, version| {
+            vector()
+                .version(version)
+                .sender(sender)
+                .receiver(receiver)
+                .build_unchecked()
+        };
+
+        let a = split("nova1a\0nova1b", "nova1c", LEGACY_TX_VERSION);
+        let b = split("nova1a", "nova1b\0nova1c", LEGACY_TX_VERSION);
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+
+        let a = split("nova1a\0nova1b", "nova1c", CANONICAL_TX_VERSION);
+        let b = split("nova1a", "nova1b\0nova1c", CANONICAL_TX_VERSION);
+        assert_ne!(a.canonical_bytes(), b.canonical_bytes());
+        assert_ne!(a.id, b.id);
+    }
+}
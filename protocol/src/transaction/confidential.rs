@@ -78,7 +78,7 @@ pub fn create_confidential_transfer(
         .amount(Amount::new(amount, Currency::NOVA))
         .fee(0)
         .nonce(1)
-        .build()
+        .build()?
         .with_proof(proof_bytes)
         .with_commitment(commitment_bytes);
 
@@ -150,6 +150,10 @@ mod tests {
         (prover, verifier, rng)
     }
 
+    fn address() -> String {
+        NovaId::from_public_key(&NovaKeypair::generate().public_key()).to_address()
+    }
+
     // ------------------------------------------------------------------
     // 1. Regular transaction still works (no proof)
     // ------------------------------------------------------------------
@@ -166,7 +170,7 @@ mod tests {
             .amount(Amount::new(1_000, Currency::NOVA))
             .fee(10)
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx, &kp);
         assert!(tx.proof.is_none());
@@ -185,7 +189,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let fake_proof = vec![0xDE, 0xAD, 0xBE, 0xEF];
         let fake_commitment = vec![0xCA, 0xFE, 0xBA, 0xBE];
@@ -209,9 +213,8 @@ mod tests {
         let (prover, _verifier, mut rng) = setup_zkp();
         let blinding = Fr::rand(&mut rng);
 
-        let tx =
-            create_confidential_transfer("nova1sender", "nova1receiver", 500, blinding, &prover)
-                .expect("confidential transfer must succeed");
+        let tx = create_confidential_transfer(&address(), &address(), 500, blinding, &prover)
+            .expect("confidential transfer must succeed");
 
         assert!(tx.proof.is_some());
         assert!(tx.amount_commitment.is_some());
@@ -228,9 +231,8 @@ mod tests {
         let (prover, verifier, mut rng) = setup_zkp();
         let blinding = Fr::rand(&mut rng);
 
-        let tx =
-            create_confidential_transfer("nova1sender", "nova1receiver", 500, blinding, &prover)
-                .expect("confidential transfer must succeed");
+        let tx = create_confidential_transfer(&address(), &address(), 500, blinding, &prover)
+            .expect("confidential transfer must succeed");
 
         let result =
             verify_confidential_proof(&tx, &verifier).expect("verification must not error");
@@ -248,7 +250,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         // Attach garbage bytes that cannot be deserialized as a Groth16 proof.
         tx.proof = Some(vec![0xFF; 32]);
@@ -276,7 +278,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .fee(10)
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx, &kp);
 
@@ -302,7 +304,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .fee(10)
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         // Attach proof but NOT commitment.
         // Use realistic-length bytes but still garbage for structural purposes —
@@ -333,7 +335,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .fee(10)
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         // Attach a corrupt proof to a regular Transfer — should fail structural check.
         tx.proof = Some(vec![0xFF; 32]);
@@ -357,7 +359,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let bytes_without = tx_base.signable_bytes();
 
@@ -385,7 +387,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let id_without = tx_base.id.clone();
 
@@ -429,7 +431,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         // JSON round-trip
         let json = serde_json::to_string(&tx).unwrap();
@@ -459,7 +461,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build()
+            .build_unchecked()
             .with_proof(proof_bytes.clone())
             .with_commitment(commitment_bytes.clone());
 
@@ -469,7 +471,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build()
+            .build_unchecked()
             .with_proof(proof_bytes)
             .with_commitment(commitment_bytes);
 
@@ -495,7 +497,7 @@ mod tests {
             .amount(Amount::new(500, Currency::NOVA))
             .fee(10)
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx, &kp);
         assert!(
@@ -0,0 +1,311 @@
+//! # Encrypted Payload Envelope
+//!
+//! Some payloads — a shipping address, invoice lines — should be readable
+//! by the receiver and nobody else, while the transaction itself stays
+//! public. An encrypted envelope carries such data in the ordinary
+//! `payload` field.
+//!
+//! ## Scheme
+//!
+//! 1. The receiver's Ed25519 identity key is mapped to its X25519 form
+//!    (Edwards to Montgomery), so no separate encryption key has to be
+//!    published.
+//! 2. The sender draws an ephemeral X25519 key and runs Diffie-Hellman
+//!    against it. BLAKE3 `derive_key` turns the shared secret, bound to
+//!    both public keys, into an AES-256-GCM key.
+//! 3. The plaintext is encrypted with the transaction's sender, receiver
+//!    and nonce as associated data. The transaction ID covers the payload
+//!    and so cannot sit inside it, but `(sender, nonce)` names the same
+//!    transaction and is signed with it: an envelope copied into another
+//!    transaction fails authentication.
+//!
+//! The receiver reverses the exchange with the secret half of its identity
+//! key; see [`Transaction::open_payload`].
+//!
+//! ## Wire Format
+//!
+//! ```text
+//! "NVE1" | ephemeral X25519 key (32) | AES-GCM nonce (12) | ciphertext + tag
+//! ```
+//!
+//! Envelopes ride in value-transfer payloads and are bounded by
+//! [`MAX_ENCRYPTED_PAYLOAD_BYTES`](super::limits::MAX_ENCRYPTED_PAYLOAD_BYTES)
+//! instead of the memo limit. Mempools may charge for every envelope byte
+//! on top of their minimum fee.
+//!
+//! Parsing is alloc-only; sealing and opening need `std`.
+
+use thiserror::Error;
+
+#[cfg(feature = "std")]
+use curve25519_dalek::edwards::CompressedEdwardsY;
+#[cfg(feature = "std")]
+use curve25519_dalek::montgomery::MontgomeryPoint;
+#[cfg(feature = "std")]
+use rand::RngCore;
+#[cfg(feature = "std")]
+use sha2::{Digest, Sha512};
+
+#[cfg(feature = "std")]
+use super::builder::Transaction;
+#[cfg(feature = "std")]
+use crate::config::AES_KEY_LENGTH;
+#[cfg(feature = "std")]
+use crate::crypto::encryption::{decrypt_with_aad, encrypt_with_aad};
+#[cfg(feature = "std")]
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey};
+
+/// Leading bytes that mark a payload as an envelope.
+pub const ENVELOPE_MAGIC: &[u8; 4] = b"NVE1";
+
+/// Length of the ephemeral X25519 public key.
+const EPHEMERAL_KEY_LEN: usize = 32;
+
+/// Length of the AES-GCM nonce.
+const NONCE_LEN: usize = 12;
+
+/// Length of the AES-GCM authentication tag.
+const TAG_LEN: usize = 16;
+
+/// Size of an envelope around an empty plaintext.
+pub const ENVELOPE_OVERHEAD: usize = ENVELOPE_MAGIC.len() + EPHEMERAL_KEY_LEN + NONCE_LEN + TAG_LEN;
+
+/// Errors from sealing or opening an envelope.
+///
+/// Like [`EncryptionError`](crate::crypto::encryption::EncryptionError),
+/// a failed open does not say whether the key or the data was wrong.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The receiver key is not a usable Curve25519 point.
+    #[error("receiver key cannot be used for encryption")]
+    InvalidReceiverKey,
+    /// The payload does not carry an envelope.
+    #[error("payload is not an encrypted envelope")]
+    NotAnEnvelope,
+    /// Encryption failed.
+    #[error("payload encryption failed")]
+    SealFailed,
+    /// Wrong key, tampered envelope, or an envelope from another transaction.
+    #[error("payload decryption failed")]
+    OpenFailed,
+}
+
+/// Returns `true` if `payload` is framed as an envelope.
+pub fn is_envelope(payload: &[u8]) -> bool {
+    payload.len() >= ENVELOPE_OVERHEAD && payload.starts_with(ENVELOPE_MAGIC)
+}
+
+/// An envelope's parts, borrowed from the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Envelope<'a> {
+    /// The sender's ephemeral X25519 public key.
+    pub ephemeral_key: &'a [u8; 32],
+    /// The AES-GCM nonce.
+    pub nonce: &'a [u8; 12],
+    /// Ciphertext with the authentication tag appended.
+    pub ciphertext: &'a [u8],
+}
+
+impl<'a> Envelope<'a> {
+    /// Splits `payload` into its parts.
+    pub fn parse(payload: &'a [u8]) -> Result<Self, EnvelopeError> {
+        if !is_envelope(payload) {
+            return Err(EnvelopeError::NotAnEnvelope);
+        }
+        let rest = &payload[ENVELOPE_MAGIC.len()..];
+        let (ephemeral_key, rest) = rest.split_at(EPHEMERAL_KEY_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        Ok(Self {
+            ephemeral_key: ephemeral_key.try_into().expect("32-byte key"),
+            nonce: nonce.try_into().expect("12-byte nonce"),
+            ciphertext,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sealing and Opening
+// ---------------------------------------------------------------------------
+
+/// The X25519 form of an Ed25519 identity key.
+#[cfg(feature = "std")]
+pub fn receiver_key(public_key: &NovaPublicKey) -> Result<[u8; 32], EnvelopeError> {
+    let point = CompressedEdwardsY(*public_key.as_bytes())
+        .decompress()
+        .filter(|p| !p.is_small_order())
+        .ok_or(EnvelopeError::InvalidReceiverKey)?;
+    Ok(point.to_montgomery().to_bytes())
+}
+
+/// Encrypts `plaintext` for the holder of `receiver_key` (from
+/// [`receiver_key`]), bound to the transaction `sender` sends to
+/// `receiver` with `nonce`. Returns the envelope bytes.
+#[cfg(feature = "std")]
+pub fn seal(
+    receiver_key: &[u8; 32],
+    sender: &str,
+    receiver: &str,
+    nonce: u64,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, EnvelopeError> {
+    let mut ephemeral_secret = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut ephemeral_secret);
+    let ephemeral_key = MontgomeryPoint::mul_base_clamped(ephemeral_secret).to_bytes();
+    let shared = MontgomeryPoint(*receiver_key).mul_clamped(ephemeral_secret);
+    if shared.to_bytes() == [0u8; 32] {
+        return Err(EnvelopeError::InvalidReceiverKey);
+    }
+
+    let key = derive_key(&shared.to_bytes(), &ephemeral_key, receiver_key);
+    let aad = associated_data(sender, receiver, nonce, &ephemeral_key);
+    let (aes_nonce, ciphertext) =
+        encrypt_with_aad(&key, plaintext, &aad).map_err(|_| EnvelopeError::SealFailed)?;
+
+    let mut envelope = Vec::with_capacity(ENVELOPE_OVERHEAD + plaintext.len());
+    envelope.extend_from_slice(ENVELOPE_MAGIC);
+    envelope.extend_from_slice(&ephemeral_key);
+    envelope.extend_from_slice(&aes_nonce);
+    envelope.extend_from_slice(&ciphertext);
+    Ok(envelope)
+}
+
+/// Decrypts `envelope` with `keypair`, the receiver's identity key, for
+/// the transaction `sender` sent to `receiver` with `nonce`.
+#[cfg(feature = "std")]
+pub fn open(
+    keypair: &NovaKeypair,
+    sender: &str,
+    receiver: &str,
+    nonce: u64,
+    envelope: &[u8],
+) -> Result<Vec<u8>, EnvelopeError> {
+    let envelope = Envelope::parse(envelope)?;
+
+    // The X25519 secret of an Ed25519 key is the lower half of the
+    // SHA-512 of its seed, clamped — the scalar Ed25519 itself signs with.
+    let digest = Sha512::digest(keypair.secret_key_bytes());
+    let secret: [u8; 32] = digest[..32].try_into().expect("32-byte half");
+    let own_key = MontgomeryPoint::mul_base_clamped(secret).to_bytes();
+    let shared = MontgomeryPoint(*envelope.ephemeral_key).mul_clamped(secret);
+    if shared.to_bytes() == [0u8; 32] {
+        return Err(EnvelopeError::OpenFailed);
+    }
+
+    let key = derive_key(&shared.to_bytes(), envelope.ephemeral_key, &own_key);
+    let aad = associated_data(sender, receiver, nonce, envelope.ephemeral_key);
+    decrypt_with_aad(&key, envelope.nonce, envelope.ciphertext, &aad)
+        .map_err(|_| EnvelopeError::OpenFailed)
+}
+
+#[cfg(feature = "std")]
+fn derive_key(shared: &[u8; 32], ephemeral_key: &[u8; 32], receiver_key: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_derive_key("nova-protocol v1 encrypted payload key");
+    hasher.update(shared);
+    hasher.update(ephemeral_key);
+    hasher.update(receiver_key);
+    let mut key = [0u8; AES_KEY_LENGTH];
+    hasher.finalize_xof().fill(&mut key);
+    key
+}
+
+#[cfg(feature = "std")]
+fn associated_data(sender: &str, receiver: &str, nonce: u64, ephemeral_key: &[u8; 32]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(sender.len() + receiver.len() + 42);
+    aad.extend_from_slice(sender.as_bytes());
+    aad.push(0x00);
+    aad.extend_from_slice(receiver.as_bytes());
+    aad.push(0x00);
+    aad.extend_from_slice(&nonce.to_le_bytes());
+    aad.extend_from_slice(ephemeral_key);
+    aad
+}
+
+#[cfg(feature = "std")]
+impl Transaction {
+    /// Returns `true` if the payload is an encrypted envelope.
+    pub fn has_encrypted_payload(&self) -> bool {
+        self.payload.as_deref().is_some_and(is_envelope)
+    }
+
+    /// Decrypts the payload with the receiver's `keypair`. `Ok(None)` if
+    /// the payload is absent or not an envelope, so a wallet can call this
+    /// on every incoming transaction.
+    pub fn open_payload(&self, keypair: &NovaKeypair) -> Result<Option<Vec<u8>>, EnvelopeError> {
+        match self.payload.as_deref() {
+            Some(payload) if is_envelope(payload) => {
+                open(keypair, &self.sender, &self.receiver, self.nonce, payload).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    fn sealed_transfer(receiver: &NovaKeypair, plaintext: &[u8]) -> Transaction {
+        TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .nonce(7)
+            .timestamp(1_700_000_000_000)
+            .payload(plaintext.to_vec())
+            .encrypt_payload_for(&receiver.public_key())
+            .unwrap()
+            .build_unchecked()
+    }
+
+    #[test]
+    fn only_the_receiver_can_open() {
+        let bob = NovaKeypair::from_seed(&[2; 32]);
+        let tx = sealed_transfer(&bob, b"221B Baker Street");
+
+        let payload = tx.payload.as_deref().unwrap();
+        assert!(tx.has_encrypted_payload());
+        assert_eq!(payload.len(), ENVELOPE_OVERHEAD + 17);
+        assert!(!payload.windows(6).any(|w| w == b"Baker "));
+
+        assert_eq!(
+            tx.open_payload(&bob).unwrap().as_deref(),
+            Some(&b"221B Baker Street"[..])
+        );
+        let eve = NovaKeypair::from_seed(&[3; 32]);
+        assert_eq!(tx.open_payload(&eve), Err(EnvelopeError::OpenFailed));
+    }
+
+    #[test]
+    fn envelope_is_bound_to_its_transaction() {
+        let bob = NovaKeypair::from_seed(&[2; 32]);
+        let tx = sealed_transfer(&bob, b"invoice 42");
+
+        let mut replayed = tx.clone();
+        replayed.nonce = 8;
+        assert_eq!(replayed.open_payload(&bob), Err(EnvelopeError::OpenFailed));
+
+        let mut tampered = tx.clone();
+        let last = tampered.payload.as_mut().unwrap().last_mut().unwrap();
+        *last ^= 1;
+        assert_eq!(tampered.open_payload(&bob), Err(EnvelopeError::OpenFailed));
+    }
+
+    #[test]
+    fn plain_payloads_are_left_alone() {
+        let bob = NovaKeypair::from_seed(&[2; 32]);
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova1alice")
+            .receiver("nova1bob")
+            .payload(b"NVE1 but far too short".to_vec())
+            .build_unchecked();
+        assert!(!tx.has_encrypted_payload());
+        assert_eq!(tx.open_payload(&bob), Ok(None));
+        assert_eq!(Envelope::parse(b"hello"), Err(EnvelopeError::NotAnEnvelope));
+    }
+}
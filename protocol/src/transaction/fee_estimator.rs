@@ -0,0 +1,417 @@
+//! Fee estimation from recently committed blocks.
+//!
+//! A [`FeeEstimator`] keeps a [`BlockFeeRecord`] — fee percentiles and
+//! fullness — for each of the last few committed blocks, fed by the block
+//! producer on commit, and answers "what should I pay, and how long will
+//! it take?" in three tiers.
+//!
+//! ## Method
+//!
+//! A block with room left took every fee at the floor. A full one (90% of
+//! capacity or more) took only fees at or above its cheapest transaction,
+//! since the mempool fills blocks best-paying first. A fee's inclusion
+//! probability is the share of blocks in the window that would have taken
+//! it; treating blocks as independent draws, the expected wait is
+//! `1 / probability` blocks.
+//!
+//! Each tier quotes the lowest fee whose probability reaches its target:
+//!
+//! | Tier       | Priority | Probability | Expected wait  |
+//! |------------|----------|-------------|----------------|
+//! | `economy`  | `Low`    | 20%         | 5 blocks       |
+//! | `standard` | `Normal` | 50%         | 2 blocks       |
+//! | `fast`     | `High`   | 90%         | about 1 block  |
+//!
+//! The wait comes with a 90% confidence interval (a Wilson score interval
+//! on the probability), so a short or erratic history shows as a wide
+//! range rather than false precision. Estimates never drop below the
+//! floor, normally the mempool's `min_fee`, and with no history the floor
+//! is all there is.
+//!
+//! Fees are whole-transaction fees in photons, matching `Transaction::fee`.
+//! The mempool actually orders by fee per byte, so quotes are exact for
+//! typical transfers and approximate for unusually large transactions.
+
+use std::collections::VecDeque;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::builder::Transaction;
+
+/// Default number of blocks an estimate looks back over.
+pub const DEFAULT_FEE_WINDOW: usize = 20;
+
+/// Fullness, in percent of block capacity, from which a block counts as
+/// full.
+const FULL_PERCENT: u64 = 90;
+
+/// z-score of a two-sided 90% confidence interval.
+const CONFIDENCE_Z: f64 = 1.645;
+
+/// How soon a transaction should be included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePriority {
+    /// Cheapest fee likely to be included eventually (`economy`).
+    #[serde(alias = "economy")]
+    Low,
+    /// Fee likely to be included within a few blocks (`standard`).
+    #[default]
+    #[serde(alias = "standard")]
+    Normal,
+    /// Fee likely to be included in the next block (`fast`).
+    #[serde(alias = "fast")]
+    High,
+}
+
+impl FeePriority {
+    /// Inclusion probability per block the quoted fee must reach.
+    fn inclusion_target(self) -> f64 {
+        match self {
+            FeePriority::Low => 0.2,
+            FeePriority::Normal => 0.5,
+            FeePriority::High => 0.9,
+        }
+    }
+}
+
+/// Fee statistics of one committed block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFeeRecord {
+    /// Transactions in the block.
+    pub transactions: u64,
+    /// Transactions as a percentage of block capacity.
+    pub fullness_percent: u64,
+    /// Cheapest fee included; 0 for an empty block.
+    pub min_fee: u64,
+    /// 25th percentile fee.
+    pub p25_fee: u64,
+    /// Median fee.
+    pub p50_fee: u64,
+    /// 90th percentile fee.
+    pub p90_fee: u64,
+    /// Highest fee included.
+    pub max_fee: u64,
+}
+
+impl BlockFeeRecord {
+    /// Summarises the fees of a block that could hold `capacity`
+    /// transactions.
+    fn new(mut fees: Vec<u64>, capacity: usize) -> Self {
+        fees.sort_unstable();
+        let percentile = |p: usize| match fees.len() {
+            0 => 0,
+            n => fees[(n - 1) * p / 100],
+        };
+        Self {
+            transactions: fees.len() as u64,
+            fullness_percent: (fees.len() * 100 / capacity) as u64,
+            min_fee: percentile(0),
+            p25_fee: percentile(25),
+            p50_fee: percentile(50),
+            p90_fee: percentile(90),
+            max_fee: percentile(100),
+        }
+    }
+
+    /// Returns `true` if the block had no room for more transactions.
+    pub fn is_full(&self) -> bool {
+        self.fullness_percent >= FULL_PERCENT
+    }
+
+    /// Returns `true` if a transaction paying `fee` would have made it into
+    /// this block.
+    pub fn admits(&self, fee: u64) -> bool {
+        !self.is_full() || fee >= self.min_fee
+    }
+}
+
+/// A suggested fee and how soon it should be included.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// Fee in photons.
+    #[serde(with = "crate::encoding::amount")]
+    pub fee: u64,
+    /// Share of recent blocks that would have included the fee.
+    pub inclusion_probability: f64,
+    /// Expected blocks until inclusion.
+    pub expected_blocks: f64,
+    /// Lower bound of the 90% confidence interval of `expected_blocks`.
+    pub blocks_low: f64,
+    /// Upper bound of that interval; `None` when the history cannot rule
+    /// out the fee never being included.
+    pub blocks_high: Option<f64>,
+}
+
+/// Suggestions for each priority, from one reading of the history.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeTiers {
+    /// Suggestion for [`FeePriority::Low`].
+    pub economy: FeeTier,
+    /// Suggestion for [`FeePriority::Normal`].
+    pub standard: FeeTier,
+    /// Suggestion for [`FeePriority::High`].
+    pub fast: FeeTier,
+}
+
+impl FeeTiers {
+    /// The suggestion for `priority`.
+    pub fn get(&self, priority: FeePriority) -> &FeeTier {
+        match priority {
+            FeePriority::Low => &self.economy,
+            FeePriority::Normal => &self.standard,
+            FeePriority::High => &self.fast,
+        }
+    }
+}
+
+/// Rolling fee history of the last `window` committed blocks.
+#[derive(Debug)]
+pub struct FeeEstimator {
+    /// Transactions a block may hold; fullness is measured against it.
+    block_capacity: usize,
+    /// Number of blocks kept.
+    window: usize,
+    /// Lowest fee ever quoted.
+    floor: u64,
+    /// Records of the blocks in the window, oldest first.
+    blocks: Mutex<VecDeque<BlockFeeRecord>>,
+}
+
+impl FeeEstimator {
+    /// Creates an estimator for blocks of up to `block_capacity`
+    /// transactions, looking back [`DEFAULT_FEE_WINDOW`] blocks.
+    pub fn new(block_capacity: usize) -> Self {
+        Self {
+            block_capacity: block_capacity.max(1),
+            window: DEFAULT_FEE_WINDOW,
+            floor: 0,
+            blocks: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Overrides the number of blocks looked back over.
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Sets the lowest fee quoted, normally the mempool's `min_fee`.
+    pub fn with_floor(mut self, floor: u64) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Records the transactions of a committed block. Empty blocks count
+    /// too: they are what an uncongested chain looks like.
+    pub fn record_block(&self, transactions: &[Transaction]) {
+        let fees = transactions.iter().map(|tx| tx.fee).collect();
+        let record = BlockFeeRecord::new(fees, self.block_capacity);
+
+        let mut blocks = self.blocks.lock();
+        if blocks.len() == self.window {
+            blocks.pop_front();
+        }
+        blocks.push_back(record);
+    }
+
+    /// Suggestions for every priority.
+    pub fn tiers(&self) -> FeeTiers {
+        let blocks = self.blocks.lock();
+        // Only the floor and the cheapest fees of full blocks change which
+        // blocks a fee gets into, so they are the only fees worth quoting.
+        let mut candidates: Vec<u64> = blocks
+            .iter()
+            .filter(|block| block.is_full())
+            .map(|block| block.min_fee.max(self.floor))
+            .chain([self.floor])
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let tier = |priority: FeePriority| {
+            candidates
+                .iter()
+                .map(|&fee| tier_at(&blocks, fee))
+                .find(|tier| tier.inclusion_probability >= priority.inclusion_target())
+                .expect("the highest candidate is admitted by every block")
+        };
+        FeeTiers {
+            economy: tier(FeePriority::Low),
+            standard: tier(FeePriority::Normal),
+            fast: tier(FeePriority::High),
+        }
+    }
+
+    /// Suggested fee for `priority`, in photons.
+    pub fn estimate(&self, priority: FeePriority) -> u64 {
+        self.tiers().get(priority).fee
+    }
+
+    /// Records of the blocks in the window, oldest first.
+    pub fn history(&self) -> Vec<BlockFeeRecord> {
+        self.blocks.lock().iter().copied().collect()
+    }
+
+    /// Number of blocks currently in the window.
+    pub fn sample_blocks(&self) -> usize {
+        self.blocks.lock().len()
+    }
+}
+
+/// How a transaction paying `fee` would have fared over `blocks`. With no
+/// history a fee is assumed to be included at once.
+fn tier_at(blocks: &VecDeque<BlockFeeRecord>, fee: u64) -> FeeTier {
+    let admitted = blocks.iter().filter(|block| block.admits(fee)).count();
+    let probability = match blocks.len() {
+        0 => 1.0,
+        n => admitted as f64 / n as f64,
+    };
+    let (low, high) = wilson_interval(admitted, blocks.len());
+    FeeTier {
+        fee,
+        inclusion_probability: probability,
+        expected_blocks: round2(1.0 / probability),
+        blocks_low: round2(1.0 / high),
+        blocks_high: (low > 0.0).then(|| round2(1.0 / low)),
+    }
+}
+
+/// Wilson score interval of a probability seen `successes` times in
+/// `trials`.
+fn wilson_interval(successes: usize, trials: usize) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = CONFIDENCE_Z * CONFIDENCE_Z;
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let margin = CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Rounds to two decimals, which is all the precision a block count needs.
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency, TransactionType};
+
+    fn txs(fees: impl IntoIterator<Item = u64>) -> Vec<Transaction> {
+        fees.into_iter()
+            .enumerate()
+            .map(|(nonce, fee)| {
+                TransactionBuilder::new(TransactionType::Transfer)
+                    .sender("nova1alice")
+                    .receiver("nova1bob")
+                    .amount(Amount::new(1, Currency::NOVA))
+                    .fee(fee)
+                    .nonce(nonce as u64)
+                    .timestamp(1_700_000_000_000)
+                    .build_unchecked()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_history_quotes_the_floor() {
+        let estimator = FeeEstimator::new(10).with_floor(7);
+        assert_eq!(estimator.estimate(FeePriority::High), 7);
+        let fast = estimator.tiers().fast;
+        assert_eq!(fast.expected_blocks, 1.0);
+        assert_eq!(fast.blocks_high, None);
+
+        estimator.record_block(&[]);
+        assert_eq!(estimator.estimate(FeePriority::High), 7);
+        assert_eq!(estimator.sample_blocks(), 1);
+    }
+
+    #[test]
+    fn blocks_with_room_take_the_floor() {
+        let estimator = FeeEstimator::new(10).with_floor(5);
+        estimator.record_block(&txs([10, 20, 30]));
+
+        assert_eq!(estimator.estimate(FeePriority::Low), 5);
+        assert_eq!(estimator.estimate(FeePriority::Normal), 5);
+        assert_eq!(estimator.estimate(FeePriority::High), 5);
+    }
+
+    #[test]
+    fn records_keep_percentiles_and_fullness() {
+        let estimator = FeeEstimator::new(20);
+        estimator.record_block(&txs((1..=10).map(|i| i * 100)));
+
+        let record = estimator.history()[0];
+        assert_eq!(record.transactions, 10);
+        assert_eq!(record.fullness_percent, 50);
+        assert!(!record.is_full());
+        assert_eq!(
+            (
+                record.min_fee,
+                record.p25_fee,
+                record.p50_fee,
+                record.p90_fee,
+                record.max_fee
+            ),
+            (100, 300, 500, 900, 1_000)
+        );
+    }
+
+    #[test]
+    fn tiers_follow_inclusion_history() {
+        // Ten full blocks whose cheapest fees are 100, 200, ..., 1000.
+        let estimator = FeeEstimator::new(4);
+        for i in 1..=10 {
+            estimator.record_block(&txs([i * 100; 4]));
+        }
+
+        let tiers = estimator.tiers();
+        assert_eq!(tiers.economy.fee, 200);
+        assert_eq!(tiers.standard.fee, 500);
+        assert_eq!(tiers.standard.expected_blocks, 2.0);
+        assert_eq!(tiers.fast.fee, 900);
+        assert_eq!(tiers.fast.expected_blocks, 1.11);
+        assert_eq!(estimator.estimate(FeePriority::High), 900);
+
+        // The interval brackets the expectation and narrows with history.
+        for tier in [tiers.economy, tiers.standard, tiers.fast] {
+            assert!(tier.blocks_low <= tier.expected_blocks);
+            assert!(tier.expected_blocks <= tier.blocks_high.unwrap());
+        }
+        let short = FeeEstimator::new(4);
+        short.record_block(&txs([100; 4]));
+        assert!(short.tiers().fast.blocks_high.unwrap() > tiers.fast.blocks_high.unwrap());
+    }
+
+    #[test]
+    fn old_blocks_leave_the_window() {
+        let estimator = FeeEstimator::new(4).with_window(2);
+        estimator.record_block(&txs([1_000; 4]));
+        assert_eq!(estimator.estimate(FeePriority::Normal), 1_000);
+
+        estimator.record_block(&[]);
+        estimator.record_block(&[]);
+        assert_eq!(estimator.sample_blocks(), 2);
+        assert_eq!(estimator.estimate(FeePriority::High), 0);
+    }
+
+    #[test]
+    fn priorities_parse_lowercase() {
+        let priority: FeePriority = serde_json::from_str("\"high\"").unwrap();
+        assert_eq!(priority, FeePriority::High);
+        assert_eq!(FeePriority::default(), FeePriority::Normal);
+        let priority: FeePriority = serde_json::from_str("\"economy\"").unwrap();
+        assert_eq!(priority, FeePriority::Low);
+    }
+}
@@ -0,0 +1,403 @@
+//! Protocol-level bounds on transaction fields.
+//!
+//! A transaction is a few hundred bytes of fixed fields plus a handful of
+//! variable-length blobs (payload, proofs, commitment, addresses). Without
+//! limits, any of those can be inflated to megabytes and ride along through
+//! gossip, the mempool and block bodies before anything looks at them.
+//!
+//! The checks here are stateless and cheap — length comparisons and a
+//! Bech32 decode — so they run at every entry point:
+//!
+//! - [`TransactionBuilder::build`](super::TransactionBuilder::build)
+//!   refuses to produce an out-of-bounds transaction in the first place.
+//! - [`GossipService::publish_transaction`](crate::network::gossip::GossipService::publish_transaction)
+//!   and [`Mempool::add`](crate::network::mempool::Mempool::add) apply the
+//!   size bounds before queueing anything.
+//! - [`verify_transaction`](super::verify_transaction) applies all of them
+//!   before touching the signature.
+//!
+//! ## Payload vs. memo
+//!
+//! For value transfers the payload is a free-form memo and is held to
+//! [`MAX_MEMO_BYTES`]. Types whose payload carries structured terms (holds,
+//! mandates, credit, batch payouts) get the larger [`MAX_PAYLOAD_BYTES`].
+//! A transfer payload sealed in an [encrypted envelope](super::envelope) is
+//! held to [`MAX_ENCRYPTED_PAYLOAD_BYTES`] instead.
+//!
+//! The `memo` field is separate: a UTF-8 reference (an order or invoice
+//! number) that any transaction type may carry, held to
+//! [`MAX_TX_MEMO_BYTES`].
+//!
+//! ## Weight
+//!
+//! On top of the per-field bounds, a transaction's [`tx_weight`] — the
+//! bytes it takes up in a block — is held to [`MAX_TX_BYTES`]. Mempools
+//! may set a lower cap, and block producers fill each block up to a byte
+//! budget rather than a transaction count alone.
+
+use super::builder::Transaction;
+use super::envelope::is_envelope;
+use super::types::TransactionType;
+use super::verification::TransactionError;
+use crate::config::{MAX_MEMO_LENGTH, MAX_TX_SIZE_BYTES};
+use crate::identity::nova_id::{AddressPolicy, MAX_ADDRESS_LENGTH};
+
+// ---------------------------------------------------------------------------
+// Limits
+// ---------------------------------------------------------------------------
+
+/// Maximum payload size for types that carry structured terms.
+pub const MAX_PAYLOAD_BYTES: usize = 16 * 1024;
+
+/// Maximum memo size for value transfers.
+pub const MAX_MEMO_BYTES: usize = MAX_MEMO_LENGTH;
+
+/// Maximum size of the `memo` field, in UTF-8 bytes.
+pub const MAX_TX_MEMO_BYTES: usize = 256;
+
+/// Maximum size of an encrypted envelope in a transfer payload, overhead
+/// included.
+pub const MAX_ENCRYPTED_PAYLOAD_BYTES: usize = 2 * 1024;
+
+/// Maximum size of a serialized proof (`proof` or `zkp_proof`). A compressed
+/// Groth16 proof over BN254 is 128 bytes; this leaves room for framing and
+/// future proof systems without admitting junk.
+pub const MAX_PROOF_BYTES: usize = 4 * 1024;
+
+/// Maximum size of a serialized amount commitment.
+pub const MAX_COMMITMENT_BYTES: usize = 256;
+
+/// Maximum length of a sender or receiver address string.
+pub const MAX_ADDRESS_BYTES: usize = MAX_ADDRESS_LENGTH;
+
+/// Maximum length of the hex-encoded sender public key (32 bytes).
+pub const MAX_PUBLIC_KEY_HEX_BYTES: usize = 64;
+
+/// Maximum length of the hex-encoded signature (64 bytes).
+pub const MAX_SIGNATURE_HEX_BYTES: usize = 128;
+
+/// Maximum [`tx_weight`] of a transaction.
+pub const MAX_TX_BYTES: usize = MAX_TX_SIZE_BYTES;
+
+// ---------------------------------------------------------------------------
+// Per-type rules
+// ---------------------------------------------------------------------------
+
+/// Returns `true` if the payload of this type is a human-readable memo and
+/// subject to [`MAX_MEMO_BYTES`].
+pub fn payload_is_memo(tx_type: TransactionType) -> bool {
+    matches!(
+        tx_type,
+        TransactionType::Transfer | TransactionType::ConfidentialTransfer
+    )
+}
+
+/// Size of the encrypted envelope `tx` carries, or 0. Mempools charge for
+/// these bytes on top of the minimum fee.
+pub fn encrypted_payload_len(tx: &Transaction) -> usize {
+    match tx.payload.as_deref() {
+        Some(payload) if payload_is_memo(tx.tx_type) && is_envelope(payload) => payload.len(),
+        _ => 0,
+    }
+}
+
+/// Returns `true` if a zero `amount` is meaningful for this type.
+///
+/// Mandate grants and revocations move no funds — the ceiling lives in the
+/// payload — so their amount field is unused, as is an unjail's, a
+/// network key rotation's or a spending policy update's. Everything else
+/// moves value and must move some.
+pub fn allows_zero_amount(tx_type: TransactionType) -> bool {
+    matches!(
+        tx_type,
+        TransactionType::MandateGrant
+            | TransactionType::MandateRevoke
+            | TransactionType::Unjail
+            | TransactionType::NetworkKeyRotation
+            | TransactionType::SpendingPolicyUpdate
+    )
+}
+
+/// Bytes `tx` takes up in a block: its canonical encoding plus every
+/// attached field the encoding leaves out (proofs, commitment, public keys
+/// and signatures).
+pub fn tx_weight(tx: &Transaction) -> usize {
+    let attached = [
+        tx.proof.as_ref().map(|v| v.len()),
+        tx.amount_commitment.as_ref().map(|v| v.len()),
+        tx.zkp_proof.as_ref().map(|v| v.len()),
+        tx.sender_public_key.as_ref().map(|v| v.len()),
+        tx.signature.as_ref().map(|v| v.len()),
+        tx.fee_payer_public_key.as_ref().map(|v| v.len()),
+        tx.fee_payer_signature.as_ref().map(|v| v.len()),
+    ];
+    tx.canonical_bytes().len() + attached.into_iter().flatten().sum::<usize>()
+}
+
+// ---------------------------------------------------------------------------
+// Checks
+// ---------------------------------------------------------------------------
+
+/// Checks every variable-length field against its bound, then the whole
+/// transaction's [`tx_weight`] against [`MAX_TX_BYTES`].
+///
+/// Length comparisons, and one encoding of fields already found to be in
+/// bounds, so it is safe to call on untrusted input before anything else.
+pub fn check_sizes(tx: &Transaction) -> Result<(), TransactionError> {
+    check_len("sender", tx.sender.len(), MAX_ADDRESS_BYTES)?;
+    check_len("receiver", tx.receiver.len(), MAX_ADDRESS_BYTES)?;
+
+    if let Some(ref payload) = tx.payload {
+        if payload_is_memo(tx.tx_type) && is_envelope(payload) {
+            check_len(
+                "encrypted_payload",
+                payload.len(),
+                MAX_ENCRYPTED_PAYLOAD_BYTES,
+            )?;
+        } else if payload_is_memo(tx.tx_type) {
+            check_len("memo", payload.len(), MAX_MEMO_BYTES)?;
+        } else {
+            check_len("payload", payload.len(), MAX_PAYLOAD_BYTES)?;
+        }
+    }
+    if let Some(ref memo) = tx.memo {
+        check_len("memo", memo.len(), MAX_TX_MEMO_BYTES)?;
+    }
+    if let Some(ref proof) = tx.proof {
+        check_len("proof", proof.len(), MAX_PROOF_BYTES)?;
+    }
+    if let Some(ref proof) = tx.zkp_proof {
+        check_len("zkp_proof", proof.len(), MAX_PROOF_BYTES)?;
+    }
+    if let Some(ref commitment) = tx.amount_commitment {
+        check_len("amount_commitment", commitment.len(), MAX_COMMITMENT_BYTES)?;
+    }
+    if let Some(ref pk) = tx.sender_public_key {
+        check_len("sender_public_key", pk.len(), MAX_PUBLIC_KEY_HEX_BYTES)?;
+    }
+    if let Some(ref sig) = tx.signature {
+        check_len("signature", sig.len(), MAX_SIGNATURE_HEX_BYTES)?;
+    }
+    if let Some(ref fee_payer) = tx.fee_payer {
+        check_len("fee_payer", fee_payer.len(), MAX_ADDRESS_BYTES)?;
+    }
+    if let Some(ref pk) = tx.fee_payer_public_key {
+        check_len("fee_payer_public_key", pk.len(), MAX_PUBLIC_KEY_HEX_BYTES)?;
+    }
+    if let Some(ref sig) = tx.fee_payer_signature {
+        check_len("fee_payer_signature", sig.len(), MAX_SIGNATURE_HEX_BYTES)?;
+    }
+    check_len("transaction", tx_weight(tx), MAX_TX_BYTES)
+}
+
+/// Full field sanity: sizes, per-type amount rule and address format.
+///
+/// Addresses are checked under `policy`; a sender failure is reported as
+/// [`TransactionError::InvalidSenderAddress`], a receiver failure as
+/// [`TransactionError::InvalidAddress`], a fee payer failure as
+/// [`TransactionError::InvalidFeePayer`]. Does not look at the signature,
+/// nonce or timestamp — those belong to
+/// [`verify_transaction`](super::verify_transaction).
+pub fn check_fields(tx: &Transaction, policy: AddressPolicy) -> Result<(), TransactionError> {
+    check_sizes(tx)?;
+
+    if tx.amount.value == 0 && !allows_zero_amount(tx.tx_type) {
+        return Err(TransactionError::ZeroAmount);
+    }
+
+    policy
+        .check(&tx.sender)
+        .map_err(|_| TransactionError::InvalidSenderAddress {
+            address: tx.sender.clone(),
+        })?;
+    policy.check(&tx.receiver)?;
+    if let Some(ref fee_payer) = tx.fee_payer {
+        policy
+            .check(fee_payer)
+            .map_err(|_| TransactionError::InvalidFeePayer {
+                address: fee_payer.clone(),
+            })?;
+    }
+    Ok(())
+}
+
+fn check_len(field: &'static str, size: usize, max: usize) -> Result<(), TransactionError> {
+    if size > max {
+        return Err(TransactionError::FieldTooLarge { field, size, max });
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::NovaKeypair;
+    use crate::identity::nova_id::NovaId;
+    use crate::transaction::builder::{BuildError, TransactionBuilder};
+    use crate::transaction::types::{Amount, Currency};
+
+    fn address() -> String {
+        NovaId::from_public_key(&NovaKeypair::generate().public_key()).to_address()
+    }
+
+    fn builder(tx_type: TransactionType) -> TransactionBuilder {
+        TransactionBuilder::new(tx_type)
+            .sender(&address())
+            .receiver(&address())
+            .amount(Amount::new(1_000, Currency::NOVA))
+            .fee(100)
+            .nonce(1)
+    }
+
+    #[test]
+    fn memo_limit_applies_to_transfers_only() {
+        let big = vec![b'x'; MAX_MEMO_BYTES + 1];
+
+        match builder(TransactionType::Transfer)
+            .payload(big.clone())
+            .build()
+        {
+            Err(BuildError::Invalid(TransactionError::FieldTooLarge { field: "memo", .. })) => {}
+            other => panic!("expected memo FieldTooLarge, got {:?}", other),
+        }
+
+        let hold = builder(TransactionType::AuthorizationHold)
+            .payload(big)
+            .build();
+        assert!(hold.is_ok());
+    }
+
+    #[test]
+    fn memo_field_limit_applies_to_every_type() {
+        for tx_type in [TransactionType::Transfer, TransactionType::HoldCapture] {
+            let at_limit = builder(tx_type)
+                .memo(&"r".repeat(MAX_TX_MEMO_BYTES))
+                .build();
+            assert!(at_limit.is_ok());
+
+            match builder(tx_type)
+                .memo(&"r".repeat(MAX_TX_MEMO_BYTES + 1))
+                .build()
+            {
+                Err(BuildError::Invalid(TransactionError::FieldTooLarge {
+                    field: "memo",
+                    max: MAX_TX_MEMO_BYTES,
+                    ..
+                })) => {}
+                other => panic!("expected memo FieldTooLarge, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn envelopes_get_their_own_bound() {
+        let envelope = |len: usize| {
+            let mut payload = crate::transaction::envelope::ENVELOPE_MAGIC.to_vec();
+            payload.resize(len, 0);
+            payload
+        };
+
+        let tx = builder(TransactionType::Transfer)
+            .payload(envelope(MAX_MEMO_BYTES * 2))
+            .build()
+            .unwrap();
+        assert_eq!(encrypted_payload_len(&tx), MAX_MEMO_BYTES * 2);
+
+        match builder(TransactionType::Transfer)
+            .payload(envelope(MAX_ENCRYPTED_PAYLOAD_BYTES + 1))
+            .build()
+        {
+            Err(BuildError::Invalid(TransactionError::FieldTooLarge {
+                field: "encrypted_payload",
+                ..
+            })) => {}
+            other => panic!("expected encrypted_payload FieldTooLarge, got {:?}", other),
+        }
+
+        let plain = builder(TransactionType::Transfer)
+            .payload(vec![b'x'; 8])
+            .build_unchecked();
+        assert_eq!(encrypted_payload_len(&plain), 0);
+    }
+
+    #[test]
+    fn oversized_payload_and_proof_rejected() {
+        match builder(TransactionType::MandateGrant)
+            .payload(vec![0; MAX_PAYLOAD_BYTES + 1])
+            .build()
+        {
+            Err(BuildError::Invalid(TransactionError::FieldTooLarge {
+                field: "payload", ..
+            })) => {}
+            other => panic!("expected payload FieldTooLarge, got {:?}", other),
+        }
+
+        let tx = builder(TransactionType::ConfidentialTransfer)
+            .build_unchecked()
+            .with_proof(vec![0; MAX_PROOF_BYTES + 1]);
+        match check_sizes(&tx) {
+            Err(TransactionError::FieldTooLarge { field: "proof", .. }) => {}
+            other => panic!("expected proof FieldTooLarge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn weight_counts_attached_fields() {
+        let tx = builder(TransactionType::ConfidentialTransfer).build_unchecked();
+        let base = tx.canonical_bytes().len();
+        assert_eq!(tx_weight(&tx), base);
+
+        let tx = tx.with_proof(vec![0; 128]).with_commitment(vec![0; 64]);
+        assert_eq!(tx_weight(&tx), base + 192);
+        assert!(check_sizes(&tx).is_ok());
+    }
+
+    #[test]
+    fn zero_amount_allowed_only_where_unused() {
+        for tx_type in [
+            TransactionType::MandateGrant,
+            TransactionType::MandateRevoke,
+            TransactionType::Unjail,
+            TransactionType::NetworkKeyRotation,
+            TransactionType::SpendingPolicyUpdate,
+        ] {
+            let tx = builder(tx_type)
+                .amount(Amount::new(0, Currency::NOVA))
+                .build();
+            assert!(tx.is_ok(), "{} should allow a zero amount", tx_type);
+        }
+
+        match builder(TransactionType::HoldCapture)
+            .amount(Amount::new(0, Currency::NOVA))
+            .build()
+        {
+            Err(BuildError::Invalid(TransactionError::ZeroAmount)) => {}
+            other => panic!("expected ZeroAmount, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn malformed_addresses_rejected() {
+        match builder(TransactionType::Transfer)
+            .receiver("nova1bob")
+            .build()
+        {
+            Err(BuildError::Invalid(TransactionError::InvalidAddress(e))) => {
+                assert_eq!(e.address, "nova1bob")
+            }
+            other => panic!("expected InvalidAddress, got {:?}", other),
+        }
+
+        let long = "n".repeat(MAX_ADDRESS_BYTES + 1);
+        match builder(TransactionType::Transfer).sender(&long).build() {
+            Err(BuildError::Invalid(TransactionError::FieldTooLarge {
+                field: "sender", ..
+            })) => {}
+            other => panic!("expected sender FieldTooLarge, got {:?}", other),
+        }
+    }
+}
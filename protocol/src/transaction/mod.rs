@@ -9,15 +9,23 @@
 //! ```text
 //! types.rs        — Core enums and value types (TransactionType, Amount, Currency)
 //! builder.rs      — Fluent TransactionBuilder for constructing unsigned transactions
-//! signing.rs      — Transaction signing with Ed25519 keypairs
+//! canonical.rs    — Versioned canonical encoding that IDs and signatures cover
+//! limits.rs       — Protocol bounds on payload, memo, proof and address fields
+//! envelope.rs     — Receiver-only encrypted payloads (X25519 + AES-256-GCM)
+//! payload.rs      — Typed payloads of token and credit transactions
+//! signing.rs      — Transaction signing and signed cancellations with Ed25519 keypairs
 //! verification.rs — Structural and cryptographic verification of signed transactions
+//! batch_verification.rs — Parallel, batched signature checks for whole blocks
 //! receipt.rs      — Immutable post-confirmation receipts for audit trails
+//! fee_estimator.rs — Fee tiers from the fees and fullness of recent blocks
+//! bundle.rs       — Offline signing bundles for air-gapped keys (file and QR)
 //! ```
 //!
 //! ## Transaction Lifecycle
 //!
 //! 1. **Build** — Use [`TransactionBuilder`] to assemble the transaction fields.
-//! 2. **Sign** — Call [`sign_transaction`] with the sender's keypair.
+//! 2. **Sign** — Call [`sign_transaction`] with the sender's keypair, or
+//!    carry a [`TransactionBundle`] to an offline signer and back.
 //! 3. **Broadcast** — Submit the signed transaction to the mempool.
 //! 4. **Verify** — Validators run [`verify_transaction`] before inclusion.
 //! 5. **Receipt** — After block confirmation, a [`TransactionReceipt`] is generated.
@@ -26,24 +34,58 @@
 //!
 //! - Transaction IDs are `double_sha256` of the canonical byte representation
 //!   (excluding signature and ZKP proof), matching Bitcoin's approach to
-//!   prevent length-extension attacks on the hash.
+//!   prevent length-extension attacks on the hash. The encoding is versioned
+//!   and specified in [`canonical`], so other implementations can reproduce
+//!   IDs byte for byte.
 //! - All amounts are `u64` in the smallest denomination. No floating point
 //!   anywhere near monetary values.
 //! - The `payload` and `zkp_proof` fields are optional byte vectors, keeping
-//!   the base transaction lean while supporting extensibility.
+//!   the base transaction lean while supporting extensibility. Token and
+//!   credit types put a typed [`TransactionPayload`] in those bytes.
+//! - Every variable-length field has a protocol bound ([`limits`]), and so
+//!   does the transaction's total weight, checked at build time, on mempool
+//!   admission and during verification.
 //! - Timestamps are checked against a 5-minute future window to prevent
 //!   clock-skew attacks without rejecting legitimate transactions.
+//! - Everything except `confidential` (needs `zkp`), `receipt`, `payload`,
+//!   `fee_estimator` and `batch_verification` (need `std`) builds without `std`, so terminals can
+//!   build and sign with the same code the validators verify with.
 
+#[cfg(feature = "std")]
+pub mod batch_verification;
 pub mod builder;
+pub mod bundle;
+pub mod canonical;
+#[cfg(feature = "zkp")]
 pub mod confidential;
+pub mod envelope;
+#[cfg(feature = "std")]
+pub mod fee_estimator;
+pub mod limits;
+#[cfg(feature = "std")]
+pub mod payload;
+#[cfg(feature = "std")]
 pub mod receipt;
 pub mod signing;
 pub mod types;
 pub mod verification;
 
-pub use builder::{Transaction, TransactionBuilder};
+#[cfg(feature = "std")]
+pub use batch_verification::{verify_block_signatures, BlockSignatureError};
+pub use builder::{BuildError, Transaction, TransactionBuilder};
+pub use bundle::{BundleError, TransactionBundle};
+pub use canonical::{CANONICAL_TX_VERSION, LEGACY_TX_VERSION};
+#[cfg(feature = "zkp")]
 pub use confidential::{create_confidential_transfer, verify_confidential_proof};
-pub use receipt::TransactionReceipt;
-pub use signing::sign_transaction;
+pub use envelope::EnvelopeError;
+#[cfg(feature = "std")]
+pub use fee_estimator::{BlockFeeRecord, FeeEstimator, FeePriority, FeeTier, FeeTiers};
+#[cfg(feature = "std")]
+pub use payload::{PayloadError, TransactionPayload};
+#[cfg(feature = "std")]
+pub use receipt::{LogKind, ReceiptLog, TransactionReceipt};
+pub use signing::{sign_as_fee_payer, sign_transaction, TransactionCancellation};
 pub use types::{Amount, Currency, TransactionStatus, TransactionType};
-pub use verification::{verify_transaction, TransactionError};
+pub use verification::{
+    verify_transaction, verify_transaction_at, verify_transaction_with_policy, TransactionError,
+};
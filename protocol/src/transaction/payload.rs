@@ -0,0 +1,320 @@
+//! # Typed Payloads
+//!
+//! `Transaction.payload` is raw bytes. Types with a defined payload of
+//! their own — hold and mandate terms, batches, treasury spends, network
+//! key bindings — carry JSON records owned by their modules, and transfers
+//! carry memos or encrypted envelopes. The token and credit types carry a
+//! [`TransactionPayload`] instead:
+//!
+//! | Transaction type   | Accepted variants                  |
+//! |--------------------|------------------------------------|
+//! | `TokenMint`        | `TokenMint`                        |
+//! | `TokenBurn`        | `TokenBurn`                        |
+//! | `CreditRequest`    | `CreditRequest`, `EscrowCreate`    |
+//! | `CreditSettlement` | `CreditSettlement`                 |
+//!
+//! A payload on one of these types must decode to an accepted variant and
+//! pass its checks ([`check`]); verification and
+//! [`TransactionBuilder::build`](super::TransactionBuilder::build)
+//! run them. The payload itself stays optional.
+//!
+//! ## Wire Format
+//!
+//! ```text
+//! "NVP1" | bincode(TransactionPayload)
+//! ```
+//!
+//! Decoding re-encodes the value and requires the same bytes back, so each
+//! payload has exactly one valid encoding.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::builder::Transaction;
+use super::types::TransactionType;
+
+/// Leading bytes that mark a payload as a [`TransactionPayload`].
+pub const PAYLOAD_MAGIC: &[u8; 4] = b"NVP1";
+
+/// Why a payload was refused.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PayloadError {
+    /// The bytes are not a typed payload.
+    #[error("payload is not a typed payload")]
+    NotTyped,
+    /// The bytes carry the marker but do not decode canonically.
+    #[error("malformed payload: {0}")]
+    Malformed(String),
+    /// The variant does not belong to the transaction's type.
+    #[error("{variant} payload on a {tx_type} transaction")]
+    WrongType {
+        variant: &'static str,
+        tx_type: TransactionType,
+    },
+    /// The payload decodes but its contents are invalid.
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// Structured payload of the token and credit transaction types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionPayload {
+    /// Issues the transaction's amount of `token` to the receiver.
+    TokenMint {
+        /// Token being minted; must match the amount's currency.
+        token: String,
+        /// Issuer's reference for the backing deposit, for reserve audits.
+        reference: String,
+    },
+    /// Destroys the transaction's amount of `token` from the sender.
+    TokenBurn {
+        /// Token being burned; must match the amount's currency.
+        token: String,
+        /// Issuer's reference for the redemption it settles.
+        reference: String,
+    },
+    /// Draws the transaction's amount against a credit offer from the
+    /// receiver.
+    CreditRequest {
+        /// The lender's offer being accepted.
+        offer_id: String,
+        /// Repayment term in days.
+        term_days: u32,
+        /// Highest annual rate the borrower accepts, in basis points.
+        max_rate_bps: u32,
+    },
+    /// Repays the transaction's amount towards an open credit.
+    CreditSettlement {
+        /// The credit being repaid.
+        credit_id: String,
+    },
+    /// Opens a credit escrow for the transaction's amount with the
+    /// receiver as lender.
+    EscrowCreate {
+        /// Annual interest rate in basis points.
+        interest_rate_bps: u32,
+        /// Unix milliseconds by which the credit must be repaid.
+        repayment_deadline: u64,
+        /// Seconds past the deadline before the escrow defaults.
+        grace_period_secs: u64,
+    },
+}
+
+impl TransactionPayload {
+    /// The variant's name, as used in errors.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::TokenMint { .. } => "TokenMint",
+            Self::TokenBurn { .. } => "TokenBurn",
+            Self::CreditRequest { .. } => "CreditRequest",
+            Self::CreditSettlement { .. } => "CreditSettlement",
+            Self::EscrowCreate { .. } => "EscrowCreate",
+        }
+    }
+
+    /// The transaction type that carries this variant.
+    pub fn tx_type(&self) -> TransactionType {
+        match self {
+            Self::TokenMint { .. } => TransactionType::TokenMint,
+            Self::TokenBurn { .. } => TransactionType::TokenBurn,
+            Self::CreditRequest { .. } | Self::EscrowCreate { .. } => {
+                TransactionType::CreditRequest
+            }
+            Self::CreditSettlement { .. } => TransactionType::CreditSettlement,
+        }
+    }
+
+    /// Encodes the payload as `"NVP1" | bincode`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = PAYLOAD_MAGIC.to_vec();
+        bytes.extend(bincode::serialize(self).expect("payload serialization cannot fail"));
+        bytes
+    }
+
+    /// Decodes bytes written by [`encode`](Self::encode).
+    pub fn decode(bytes: &[u8]) -> Result<Self, PayloadError> {
+        let body = bytes
+            .strip_prefix(PAYLOAD_MAGIC.as_slice())
+            .ok_or(PayloadError::NotTyped)?;
+        let payload: Self =
+            bincode::deserialize(body).map_err(|e| PayloadError::Malformed(e.to_string()))?;
+        if payload.encode() != bytes {
+            return Err(PayloadError::Malformed("non-canonical encoding".into()));
+        }
+        Ok(payload)
+    }
+}
+
+/// Returns `true` if `tx_type` carries a [`TransactionPayload`].
+pub fn is_typed(tx_type: TransactionType) -> bool {
+    matches!(
+        tx_type,
+        TransactionType::TokenMint
+            | TransactionType::TokenBurn
+            | TransactionType::CreditRequest
+            | TransactionType::CreditSettlement
+    )
+}
+
+/// Checks `tx`'s payload against its type. Types without typed payloads,
+/// and transactions without a payload, always pass.
+pub fn check(tx: &Transaction) -> Result<(), PayloadError> {
+    let Some(bytes) = tx.payload.as_deref() else {
+        return Ok(());
+    };
+    if !is_typed(tx.tx_type) {
+        return Ok(());
+    }
+    let payload = TransactionPayload::decode(bytes)?;
+    if payload.tx_type() != tx.tx_type {
+        return Err(PayloadError::WrongType {
+            variant: payload.kind(),
+            tx_type: tx.tx_type,
+        });
+    }
+
+    let currency = tx.amount.currency.to_string();
+    match &payload {
+        TransactionPayload::TokenMint { token, .. }
+        | TransactionPayload::TokenBurn { token, .. }
+            if *token != currency =>
+        {
+            Err(PayloadError::Invalid(format!(
+                "payload names token {} but the amount is in {}",
+                token, currency
+            )))
+        }
+        TransactionPayload::CreditRequest { offer_id, .. } if offer_id.is_empty() => Err(
+            PayloadError::Invalid("credit request names no offer".into()),
+        ),
+        TransactionPayload::CreditRequest { term_days: 0, .. } => Err(PayloadError::Invalid(
+            "credit term must be at least a day".into(),
+        )),
+        TransactionPayload::CreditSettlement { credit_id } if credit_id.is_empty() => {
+            Err(PayloadError::Invalid("settlement names no credit".into()))
+        }
+        TransactionPayload::EscrowCreate {
+            repayment_deadline, ..
+        } if *repayment_deadline <= tx.timestamp => Err(PayloadError::Invalid(
+            "escrow repayment deadline has already passed".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl Transaction {
+    /// Decodes the payload of a token or credit transaction. `Ok(None)` if
+    /// there is no payload or the type does not carry typed payloads.
+    pub fn typed_payload(&self) -> Result<Option<TransactionPayload>, PayloadError> {
+        match self.payload.as_deref() {
+            Some(bytes) if is_typed(self.tx_type) => TransactionPayload::decode(bytes).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::builder::TransactionBuilder;
+    use crate::transaction::types::{Amount, Currency};
+
+    fn tx(tx_type: TransactionType, payload: &TransactionPayload) -> Transaction {
+        TransactionBuilder::new(tx_type)
+            .sender("nova1issuer")
+            .receiver("nova1holder")
+            .amount(Amount::new(5_000, Currency::Custom("nBRL".into())))
+            .nonce(1)
+            .timestamp(1_700_000_000_000)
+            .payload_typed(payload)
+            .build_unchecked()
+    }
+
+    #[test]
+    fn payloads_round_trip_through_bincode() {
+        let payloads = [
+            TransactionPayload::TokenMint {
+                token: "nBRL".into(),
+                reference: "deposit-7".into(),
+            },
+            TransactionPayload::TokenBurn {
+                token: "nBRL".into(),
+                reference: "redeem-3".into(),
+            },
+            TransactionPayload::CreditRequest {
+                offer_id: "offer-1".into(),
+                term_days: 30,
+                max_rate_bps: 900,
+            },
+            TransactionPayload::CreditSettlement {
+                credit_id: "credit-1".into(),
+            },
+            TransactionPayload::EscrowCreate {
+                interest_rate_bps: 500,
+                repayment_deadline: 1_800_000_000_000,
+                grace_period_secs: 86_400,
+            },
+        ];
+        for payload in payloads {
+            let bytes = payload.encode();
+            assert!(bytes.starts_with(PAYLOAD_MAGIC));
+            assert_eq!(TransactionPayload::decode(&bytes).unwrap(), payload);
+
+            let tx = tx(payload.tx_type(), &payload);
+            assert_eq!(tx.typed_payload().unwrap(), Some(payload));
+            assert_eq!(check(&tx), Ok(()));
+        }
+
+        let mut trailing = TransactionPayload::CreditSettlement {
+            credit_id: "c".into(),
+        }
+        .encode();
+        trailing.push(0);
+        assert!(matches!(
+            TransactionPayload::decode(&trailing),
+            Err(PayloadError::Malformed(_))
+        ));
+        assert_eq!(
+            TransactionPayload::decode(b"memo"),
+            Err(PayloadError::NotTyped)
+        );
+    }
+
+    #[test]
+    fn payload_must_fit_the_transaction_type() {
+        let mint = TransactionPayload::TokenMint {
+            token: "nBRL".into(),
+            reference: "deposit-7".into(),
+        };
+        let burn = tx(TransactionType::TokenBurn, &mint);
+        assert!(matches!(check(&burn), Err(PayloadError::WrongType { .. })));
+
+        let other_token = TransactionPayload::TokenMint {
+            token: "nUSD".into(),
+            reference: "deposit-8".into(),
+        };
+        let mismatched = tx(TransactionType::TokenMint, &other_token);
+        assert!(matches!(check(&mismatched), Err(PayloadError::Invalid(_))));
+
+        let lapsed = TransactionPayload::EscrowCreate {
+            interest_rate_bps: 500,
+            repayment_deadline: 1_000,
+            grace_period_secs: 0,
+        };
+        let escrow = tx(TransactionType::CreditRequest, &lapsed);
+        assert!(matches!(check(&escrow), Err(PayloadError::Invalid(_))));
+
+        let mut raw = tx(TransactionType::TokenMint, &mint);
+        raw.payload = Some(b"mint please".to_vec());
+        assert_eq!(check(&raw), Err(PayloadError::NotTyped));
+
+        // Other types keep their own payload formats.
+        raw.tx_type = TransactionType::Transfer;
+        assert_eq!(check(&raw), Ok(()));
+        assert_eq!(raw.typed_payload(), Ok(None));
+    }
+}
@@ -5,6 +5,10 @@
 //! block metadata, forming an immutable proof-of-execution that can be
 //! verified independently.
 //!
+//! The block producer generates a receipt for every transaction of a
+//! block it commits, with the fee charged and the balance changes made
+//! ([`ReceiptLog`]), and persists them in NovaDB's `receipts` tree.
+//!
 //! Receipt hashes use BLAKE3 (not SHA-256) because receipts are not
 //! consensus-critical — they are derived artifacts. BLAKE3 gives us
 //! faster hashing without any security trade-off for this use case.
@@ -14,6 +18,7 @@ use serde::{Deserialize, Serialize};
 use super::builder::Transaction;
 use super::types::{Amount, TransactionStatus};
 use crate::crypto::hash::blake3_hash;
+use crate::encoding;
 
 // ---------------------------------------------------------------------------
 // BlockInfo
@@ -34,6 +39,44 @@ pub struct BlockInfo {
     pub timestamp: u64,
 }
 
+// ---------------------------------------------------------------------------
+// ReceiptLog
+// ---------------------------------------------------------------------------
+
+/// What a [`ReceiptLog`] entry did to its account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogKind {
+    /// Value left the account.
+    Debit,
+    /// Value arrived in the account.
+    Credit,
+    /// The account paid the transaction fee.
+    Fee,
+}
+
+/// One balance change made by a transaction's execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiptLog {
+    /// Kind of change.
+    pub kind: LogKind,
+    /// NOVA address whose balance changed.
+    pub account: String,
+    /// Photons moved.
+    pub amount: u64,
+}
+
+impl ReceiptLog {
+    /// Creates a log entry.
+    pub fn new(kind: LogKind, account: impl Into<String>, amount: u64) -> Self {
+        Self {
+            kind,
+            account: account.into(),
+            amount,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // TransactionReceipt
 // ---------------------------------------------------------------------------
@@ -52,12 +95,14 @@ pub struct BlockInfo {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionReceipt {
     /// The transaction ID (double-SHA-256 hex digest).
+    #[serde(with = "encoding::hex_string")]
     pub tx_id: String,
 
     /// Height of the block that included this transaction.
     pub block_height: u64,
 
     /// Hex-encoded hash of the block that included this transaction.
+    #[serde(with = "encoding::hex_string")]
     pub block_hash: String,
 
     /// Unix timestamp in milliseconds of the block.
@@ -79,8 +124,21 @@ pub struct TransactionReceipt {
     /// Increases over time as the chain grows.
     pub confirmations: u64,
 
+    /// The transaction's memo, such as a merchant order reference.
+    #[serde(default)]
+    pub memo: Option<String>,
+
+    /// Fee charged for executing the transaction, in photons.
+    #[serde(default)]
+    pub fee: u64,
+
+    /// Balance changes made by the transaction, in execution order.
+    #[serde(default)]
+    pub logs: Vec<ReceiptLog>,
+
     /// BLAKE3 hash of all other receipt fields, hex-encoded.
     /// Proves the receipt has not been modified after creation.
+    #[serde(with = "encoding::hex_string")]
     pub receipt_hash: String,
 }
 
@@ -126,6 +184,9 @@ impl TransactionReceipt {
             amount: tx.amount.clone(),
             status,
             confirmations: 1,
+            memo: tx.memo.clone(),
+            fee: 0,
+            logs: Vec::new(),
             receipt_hash: String::new(), // computed below
         };
 
@@ -133,6 +194,15 @@ impl TransactionReceipt {
         receipt
     }
 
+    /// Records the fee charged and the balance changes made by executing
+    /// the transaction, and rehashes the receipt.
+    pub fn with_execution(mut self, fee: u64, logs: Vec<ReceiptLog>) -> Self {
+        self.fee = fee;
+        self.logs = logs;
+        self.receipt_hash = self.compute_hash();
+        self
+    }
+
     /// Computes the BLAKE3 hash of all receipt fields (excluding `receipt_hash`).
     ///
     /// Used both at creation time and for verification. If the returned hash
@@ -149,8 +219,23 @@ impl TransactionReceipt {
             status: &self.status,
             confirmations: self.confirmations,
         };
-        let bytes =
+        let mut bytes =
             bincode::serialize(&data).expect("receipt hash data serialization must not fail");
+        // Appended only when present so receipts without a memo keep
+        // their hashes.
+        if let Some(ref memo) = self.memo {
+            bytes.extend_from_slice(&(memo.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(memo.as_bytes());
+        }
+        // Execution results likewise, tagged so they cannot be read as a
+        // memo.
+        if self.fee > 0 || !self.logs.is_empty() {
+            bytes.push(0x01);
+            bytes.extend_from_slice(&self.fee.to_le_bytes());
+            bytes.extend_from_slice(
+                &bincode::serialize(&self.logs).expect("receipt logs serialization must not fail"),
+            );
+        }
         hex::encode(blake3_hash(&bytes))
     }
 
@@ -213,7 +298,7 @@ mod tests {
             .fee(100)
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build()
+            .build_unchecked()
     }
 
     #[test]
@@ -284,6 +369,67 @@ mod tests {
         assert!(!receipt.verify_integrity());
     }
 
+    #[test]
+    fn memo_is_carried_and_hashed() {
+        let plain = TransactionReceipt::from_transaction(
+            &sample_tx(),
+            &sample_block_info(),
+            TransactionStatus::Confirmed,
+        );
+        assert_eq!(plain.memo, None);
+
+        let tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova:aaaa")
+            .receiver("nova:bbbb")
+            .amount(Amount::new(5_000, Currency::NOVA))
+            .fee(100)
+            .nonce(1)
+            .timestamp(1_700_000_000_000)
+            .memo("INV-2024-0042")
+            .build_unchecked();
+        let mut receipt = TransactionReceipt::from_transaction(
+            &tx,
+            &sample_block_info(),
+            TransactionStatus::Confirmed,
+        );
+        assert_eq!(receipt.memo.as_deref(), Some("INV-2024-0042"));
+        assert!(receipt.verify_integrity());
+
+        receipt.memo = Some("INV-2024-0043".to_string());
+        assert!(!receipt.verify_integrity());
+    }
+
+    #[test]
+    fn execution_results_are_hashed() {
+        let plain = TransactionReceipt::from_transaction(
+            &sample_tx(),
+            &sample_block_info(),
+            TransactionStatus::Confirmed,
+        );
+        let executed = plain.clone().with_execution(
+            100,
+            vec![
+                ReceiptLog::new(LogKind::Debit, "nova:aaaa", 5_000),
+                ReceiptLog::new(LogKind::Credit, "nova:bbbb", 5_000),
+                ReceiptLog::new(LogKind::Fee, "nova:aaaa", 100),
+            ],
+        );
+        assert!(executed.verify_integrity());
+        assert_ne!(executed.receipt_hash, plain.receipt_hash);
+
+        let mut tampered = executed.clone();
+        tampered.logs[1].amount = 50_000;
+        assert!(!tampered.verify_integrity());
+
+        let json = executed.to_json();
+        assert!(json.contains("\"kind\": \"credit\""));
+        assert_eq!(TransactionReceipt::from_json(&json).unwrap(), executed);
+        assert_eq!(
+            TransactionReceipt::from_binary(&executed.to_binary()).unwrap(),
+            executed
+        );
+    }
+
     #[test]
     fn receipt_json_roundtrip() {
         let tx = sample_tx();
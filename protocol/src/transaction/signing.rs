@@ -4,9 +4,20 @@
 //! be available at construction time (e.g., hardware wallet, remote signer).
 //! The signing data is the canonical [`Transaction::signable_bytes`] output,
 //! which deterministically excludes the signature and ZKP proof fields.
+//!
+//! A sender can also withdraw a transaction that is still pending by
+//! signing a [`TransactionCancellation`] over its ID with the same key.
+
+use serde::{Deserialize, Serialize};
 
 use super::builder::Transaction;
-use crate::crypto::keys::NovaKeypair;
+use crate::alloc_prelude::*;
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use crate::encoding;
+
+/// Domain prefix of cancellation messages, so a cancellation signature can
+/// never pass for a transaction signature or the other way round.
+pub const CANCELLATION_DOMAIN: &[u8] = b"nova-cancel-tx";
 
 /// Signs a transaction in place using the provided keypair.
 ///
@@ -43,10 +54,11 @@ use crate::crypto::keys::NovaKeypair;
 ///     .receiver("nova:ccdd...")
 ///     .amount(Amount::new(1_000, Currency::NOVA))
 ///     .nonce(1)
-///     .build();
+///     .build()?;
 ///
 /// sign_transaction(&mut tx, &keypair);
 /// assert!(tx.is_signed());
+/// # Ok::<(), nova_protocol::transaction::BuildError>(())
 /// ```
 pub fn sign_transaction<'a>(tx: &'a mut Transaction, keypair: &NovaKeypair) -> &'a Transaction {
     let signable = tx.signable_bytes();
@@ -56,6 +68,70 @@ pub fn sign_transaction<'a>(tx: &'a mut Transaction, keypair: &NovaKeypair) -> &
     tx
 }
 
+/// Co-signs a sponsored transaction as its `fee_payer`.
+///
+/// Signs the same [`Transaction::signable_bytes`] as the sender, which
+/// include the `fee_payer` address, and stores the signature and public
+/// key in `fee_payer_signature` and `fee_payer_public_key`. Sender and
+/// sponsor can sign in either order.
+///
+/// The caller is responsible for ensuring `keypair` matches
+/// `tx.fee_payer`; verification rejects the transaction otherwise.
+pub fn sign_as_fee_payer<'a>(tx: &'a mut Transaction, keypair: &NovaKeypair) -> &'a Transaction {
+    let signature = keypair.sign(&tx.signable_bytes());
+    tx.fee_payer_signature = Some(signature.to_hex());
+    tx.fee_payer_public_key = Some(keypair.public_key().to_hex());
+    tx
+}
+
+/// A sender's signed request to drop one of their pending transactions.
+///
+/// The signature covers [`CANCELLATION_DOMAIN`] followed by the
+/// transaction ID, and is checked against the `sender_public_key` of the
+/// transaction it names. Only transactions still in a mempool can be
+/// cancelled; once included in a block, a cancellation has no effect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionCancellation {
+    /// ID of the transaction to cancel.
+    #[serde(with = "encoding::hex_string")]
+    pub tx_id: String,
+
+    /// Ed25519 signature over [`signing_bytes`](Self::signing_bytes),
+    /// hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub signature: String,
+}
+
+impl TransactionCancellation {
+    /// Signs a cancellation of `tx_id` with the sender's keypair.
+    pub fn sign(tx_id: &str, keypair: &NovaKeypair) -> Self {
+        let tx_id = encoding::strip_hex_prefix(tx_id).to_string();
+        let signature = keypair.sign(&Self::signing_bytes(&tx_id)).to_hex();
+        Self { tx_id, signature }
+    }
+
+    /// The bytes a cancellation of `tx_id` signs.
+    pub fn signing_bytes(tx_id: &str) -> Vec<u8> {
+        let mut buf = CANCELLATION_DOMAIN.to_vec();
+        buf.extend_from_slice(encoding::strip_hex_prefix(tx_id).as_bytes());
+        buf
+    }
+
+    /// Returns `true` if this cancels `tx` and is signed by its sender.
+    pub fn verify(&self, tx: &Transaction) -> bool {
+        if encoding::strip_hex_prefix(&self.tx_id) != tx.id {
+            return false;
+        }
+        let (Some(Ok(key)), Ok(signature)) = (
+            tx.sender_public_key.as_deref().map(NovaPublicKey::from_hex),
+            NovaSignature::from_hex(encoding::strip_hex_prefix(&self.signature)),
+        ) else {
+            return false;
+        };
+        key.verify(&Self::signing_bytes(&self.tx_id), &signature)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -75,7 +151,7 @@ mod tests {
             .receiver("nova:bbbb")
             .amount(Amount::new(500, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         assert!(!tx.is_signed());
         sign_transaction(&mut tx, &kp);
@@ -91,7 +167,7 @@ mod tests {
             .receiver("nova:bbbb")
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx, &kp);
         let sig = tx.signature.as_ref().unwrap();
@@ -99,6 +175,28 @@ mod tests {
         assert!(sig.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn seed_derived_key_with_explicit_timestamp_verifies() {
+        // The terminal flow: no OS RNG, no clock — only what `no_std` offers.
+        let kp = NovaKeypair::from_seed(&[7u8; 32]);
+        let sender = crate::identity::NovaId::from_public_key(&kp.public_key()).to_address();
+        let receiver = crate::identity::NovaId::from_public_key(
+            &NovaKeypair::from_seed(&[8u8; 32]).public_key(),
+        )
+        .to_address();
+        let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender(&sender)
+            .receiver(&receiver)
+            .amount(Amount::new(100, Currency::NOVA))
+            .nonce(1)
+            .timestamp(1_700_000_000_000)
+            .build()
+            .unwrap();
+
+        sign_transaction(&mut tx, &kp);
+        assert!(crate::transaction::verify_transaction(&tx).is_ok());
+    }
+
     #[test]
     fn signing_does_not_change_id() {
         let kp = NovaKeypair::generate();
@@ -107,7 +205,7 @@ mod tests {
             .receiver("nova:bbbb")
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         let id_before = tx.id.clone();
         sign_transaction(&mut tx, &kp);
@@ -127,7 +225,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let mut tx2 = TransactionBuilder::new(TransactionType::Transfer)
             .sender("nova:aaaa")
@@ -135,7 +233,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx1, &kp);
         sign_transaction(&mut tx2, &kp);
@@ -158,7 +256,7 @@ mod tests {
                 .amount(Amount::new(100, Currency::NOVA))
                 .nonce(1)
                 .timestamp(1_700_000_000_000)
-                .build()
+                .build_unchecked()
         };
 
         let mut tx1 = build();
@@ -181,7 +279,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx, &kp1);
         let sig1 = tx.signature.clone();
@@ -214,7 +312,7 @@ mod tests {
             .fee(100)
             .nonce(42)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
 
         let signable = tx.signable_bytes();
         let signable_hex = hex::encode(&signable);
@@ -263,7 +361,7 @@ mod tests {
             .fee(100)
             .nonce(42)
             .timestamp(1_700_000_000_000)
-            .build();
+            .build_unchecked();
         assert_eq!(tx.id, tx2.id, "identical inputs must produce identical IDs");
         assert_eq!(
             tx.signable_bytes(),
@@ -288,4 +386,30 @@ mod tests {
         let sig_hex = tx_signed.signature.as_ref().unwrap();
         eprintln!("signature_hex: {}", sig_hex);
     }
+
+    #[test]
+    fn cancellation_verifies_only_for_the_sender() {
+        let sender = NovaKeypair::generate();
+        let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+            .sender("nova:aaaa")
+            .receiver("nova:bbbb")
+            .amount(Amount::new(100, Currency::NOVA))
+            .nonce(1)
+            .build_unchecked();
+        sign_transaction(&mut tx, &sender);
+
+        let cancellation = TransactionCancellation::sign(&format!("0x{}", tx.id), &sender);
+        assert_eq!(cancellation.tx_id, tx.id);
+        assert!(cancellation.verify(&tx));
+
+        let stranger = TransactionCancellation::sign(&tx.id, &NovaKeypair::generate());
+        assert!(!stranger.verify(&tx));
+
+        // A transaction signature is not a cancellation signature.
+        let reused = TransactionCancellation {
+            tx_id: tx.id.clone(),
+            signature: tx.signature.clone().unwrap(),
+        };
+        assert!(!reused.verify(&tx));
+    }
 }
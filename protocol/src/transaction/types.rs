@@ -4,8 +4,11 @@
 //! They are intentionally kept small and `Copy`-friendly where possible
 //! to avoid heap allocations on the hot validation path.
 
+use core::fmt;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+
+use crate::alloc_prelude::*;
+use crate::encoding;
 
 // ---------------------------------------------------------------------------
 // TransactionType
@@ -31,6 +34,37 @@ pub enum TransactionType {
     /// Privacy-preserving value transfer using a Groth16 zero-knowledge proof.
     /// Requires both a ZKP proof and a Pedersen commitment on the transaction.
     ConfidentialTransfer,
+    /// Lock part of the sender's balance for the receiver (a merchant), who
+    /// may capture it later. The payload carries the hold terms.
+    AuthorizationHold,
+    /// Merchant capture against an existing authorization hold. The sender
+    /// is the merchant; the receiver is the account holding the funds.
+    HoldCapture,
+    /// Grant the receiver a standing mandate to pull from the sender's
+    /// account, bounded per period. The payload carries the mandate terms.
+    MandateGrant,
+    /// Payee pull under an existing mandate. The sender is the payee; the
+    /// receiver is the payer.
+    MandatePull,
+    /// Payer revokes a mandate, effective immediately.
+    MandateRevoke,
+    /// A jailed validator asks to rejoin the active set once its cooldown
+    /// has passed. Signed with the validator's own key.
+    Unjail,
+    /// Pays out of the treasury under a validator-supermajority spend
+    /// proposal carried in the payload.
+    TreasurySpend,
+    /// Pays every recipient listed in the payload, all-or-nothing. The
+    /// receiver is the first recipient and the amount is the total.
+    Batch,
+    /// Binds a new network (transport) key to a validator. Signed with the
+    /// validator's consensus key; the payload is the binding signed with
+    /// the network key.
+    NetworkKeyRotation,
+    /// Replaces the sender's spending policy (daily limits, allow-listed
+    /// recipients). Addressed to the sender itself; the payload carries
+    /// the policy.
+    SpendingPolicyUpdate,
 }
 
 impl fmt::Display for TransactionType {
@@ -42,6 +76,16 @@ impl fmt::Display for TransactionType {
             Self::TokenMint => write!(f, "TokenMint"),
             Self::TokenBurn => write!(f, "TokenBurn"),
             Self::ConfidentialTransfer => write!(f, "ConfidentialTransfer"),
+            Self::AuthorizationHold => write!(f, "AuthorizationHold"),
+            Self::HoldCapture => write!(f, "HoldCapture"),
+            Self::MandateGrant => write!(f, "MandateGrant"),
+            Self::MandatePull => write!(f, "MandatePull"),
+            Self::MandateRevoke => write!(f, "MandateRevoke"),
+            Self::Unjail => write!(f, "Unjail"),
+            Self::TreasurySpend => write!(f, "TreasurySpend"),
+            Self::Batch => write!(f, "Batch"),
+            Self::NetworkKeyRotation => write!(f, "NetworkKeyRotation"),
+            Self::SpendingPolicyUpdate => write!(f, "SpendingPolicyUpdate"),
         }
     }
 }
@@ -161,6 +205,7 @@ impl Currency {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Amount {
     /// Value in the smallest indivisible unit of the currency.
+    #[serde(with = "encoding::amount")]
     pub value: u64,
     /// The currency denomination.
     pub currency: Currency,
@@ -284,6 +329,16 @@ mod tests {
             TransactionType::TokenMint,
             TransactionType::TokenBurn,
             TransactionType::ConfidentialTransfer,
+            TransactionType::AuthorizationHold,
+            TransactionType::HoldCapture,
+            TransactionType::MandateGrant,
+            TransactionType::MandatePull,
+            TransactionType::MandateRevoke,
+            TransactionType::Unjail,
+            TransactionType::TreasurySpend,
+            TransactionType::Batch,
+            TransactionType::NetworkKeyRotation,
+            TransactionType::SpendingPolicyUpdate,
         ];
         for t in types {
             let json = serde_json::to_string(&t).unwrap();
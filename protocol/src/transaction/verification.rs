@@ -5,13 +5,18 @@
 //! expensive (string comparisons before signature verification) to fail
 //! fast and waste minimal CPU on invalid transactions.
 
+#[cfg(feature = "std")]
 use chrono::Utc;
 use thiserror::Error;
 
 use super::builder::Transaction;
+use super::canonical::{CANONICAL_TX_VERSION, LEGACY_TX_VERSION};
+use super::limits;
 use super::types::TransactionType;
+use crate::alloc_prelude::*;
 use crate::crypto::keys::{NovaPublicKey, NovaSignature};
-use crate::identity::nova_id::NovaId;
+use crate::identity::nova_id::{AddressPolicy, InvalidAddress, NovaId};
+#[cfg(feature = "zkp")]
 use crate::zkp::prover::BalanceProof;
 
 // ---------------------------------------------------------------------------
@@ -24,6 +29,10 @@ use crate::zkp::prover::BalanceProof;
 /// includes enough context for debugging without leaking internal state.
 #[derive(Debug, Error)]
 pub enum TransactionError {
+    /// The version has no canonical encoding defined for it.
+    #[error("unsupported transaction version {version}")]
+    UnsupportedVersion { version: u16 },
+
     /// The transaction ID does not match the double-SHA-256 of its signable bytes.
     #[error("transaction ID mismatch: expected {expected}, got {actual}")]
     IdMismatch { expected: String, actual: String },
@@ -44,11 +53,42 @@ pub enum TransactionError {
     #[error("invalid sender address: {address}")]
     InvalidSenderAddress { address: String },
 
+    /// The fee payer is malformed, is the sender itself, or its public key
+    /// does not belong to it.
+    #[error("invalid fee payer: {address}")]
+    InvalidFeePayer { address: String },
+
+    /// A sponsored transaction lacks the fee payer's signature.
+    #[error("sponsored transaction is missing the fee payer's signature")]
+    MissingFeePayerSignature,
+
+    /// The fee payer's signature does not verify.
+    #[error("invalid signature: does not verify against fee payer {fee_payer}")]
+    InvalidFeePayerSignature { fee_payer: String },
+
+    /// Only transfers can have their fee paid by a sponsor.
+    #[error("{tx_type} transactions cannot be sponsored")]
+    SponsorshipNotSupported { tx_type: TransactionType },
+
+    /// The receiver address is not acceptable under the active
+    /// [`AddressPolicy`].
+    #[error(transparent)]
+    InvalidAddress(#[from] InvalidAddress),
+
+    /// A variable-length field exceeds its protocol bound
+    /// (see [`super::limits`]).
+    #[error("{field} is {size} bytes (max {max})")]
+    FieldTooLarge {
+        field: &'static str,
+        size: usize,
+        max: usize,
+    },
+
     /// The nonce is zero, which is reserved. Valid nonces start at 1.
     #[error("invalid nonce: must be > 0, got {nonce}")]
     InvalidNonce { nonce: u64 },
 
-    /// The transaction amount is zero.
+    /// The transaction amount is zero for a type that moves value.
     #[error("amount must be > 0")]
     ZeroAmount,
 
@@ -64,6 +104,10 @@ pub enum TransactionError {
         max_secs: i64,
     },
 
+    /// The transaction's `valid_until` has passed.
+    #[error("transaction expired at {valid_until} (now {now_ms})")]
+    Expired { valid_until: u64, now_ms: u64 },
+
     /// A `ConfidentialTransfer` is missing its required ZKP proof.
     #[error("confidential transfer requires a Groth16 proof")]
     MissingProof,
@@ -75,6 +119,14 @@ pub enum TransactionError {
     /// The attached ZKP proof could not be deserialized.
     #[error("invalid ZKP proof: {reason}")]
     InvalidProof { reason: String },
+
+    /// The payload is not one the transaction's type accepts (see
+    /// [`super::payload`]).
+    #[error("invalid {tx_type} payload: {reason}")]
+    InvalidPayload {
+        tx_type: TransactionType,
+        reason: String,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -91,16 +143,33 @@ const MAX_FUTURE_SECONDS: i64 = 300;
 ///
 /// The checks, in order:
 ///
-/// 1. **Nonce** — must be > 0.
-/// 2. **Amount** — must be > 0.
+/// 1. **Version and nonce** — the version must have a canonical encoding
+///    and the nonce must be > 0.
+/// 2. **Field limits** — sizes, total weight (at most
+///    [`limits::MAX_TX_BYTES`]), per-type amount rule and address format
+///    (see [`limits::check_fields`]), and the typed payload of token and
+///    credit types (see [`super::payload::check`]).
 /// 3. **Self-transfer** — sender must differ from receiver.
-/// 4. **Timestamp** — must not be more than 5 minutes in the future.
+/// 4. **Timestamp** — must not be more than 5 minutes in the future, and
+///    `valid_until`, if set, must not have passed.
 /// 5. **Transaction ID** — must equal `double_sha256(signable_bytes)`.
 /// 6. **Signature present** — the transaction must be signed.
-/// 7. **Sender address valid** — must parse as a `nova:<hex>` address.
+/// 7. **Signature well-formed** — 64 bytes of valid hex.
 /// 8. **Signature valid** — Ed25519 verification against the sender's public key.
-/// 9. **ConfidentialTransfer fields** — proof and commitment required.
-/// 10. **ZKP structural validity** — if proof attached, must deserialize.
+/// 9. **Fee payer** — a sponsored transfer needs a distinct fee payer
+///    whose signature over the same bytes verifies.
+/// 10. **ConfidentialTransfer fields** — proof and commitment required.
+/// 11. **ZKP structural validity** — if proof attached, must deserialize.
+///
+/// Step 4 needs a clock and only runs with the `std` feature or through
+/// [`verify_transaction_at`]; the payload check in step 2 also needs
+/// `std`, and step 11 only runs with `zkp`. A `no_std`
+/// terminal checking its own transactions gets everything else — the node
+/// repeats the full set on admission.
+///
+/// Addresses are checked under [`AddressPolicy::Strict`]; use
+/// [`verify_transaction_with_policy`] on devnets that still carry
+/// placeholder addresses.
 ///
 /// # Errors
 ///
@@ -108,15 +177,56 @@ const MAX_FUTURE_SECONDS: i64 = 300;
 /// ordered from cheapest to most expensive to minimize wasted computation
 /// on clearly invalid transactions.
 pub fn verify_transaction(tx: &Transaction) -> Result<(), TransactionError> {
-    // 1. Nonce must be positive (0 is reserved for genesis/system txs).
+    verify_transaction_with_policy(tx, AddressPolicy::Strict)
+}
+
+/// [`verify_transaction`] with an explicit address policy for step 2.
+pub fn verify_transaction_with_policy(
+    tx: &Transaction,
+    policy: AddressPolicy,
+) -> Result<(), TransactionError> {
+    #[cfg(feature = "std")]
+    let now_ms = Some(Utc::now().timestamp_millis() as u64);
+    #[cfg(not(feature = "std"))]
+    let now_ms = None;
+    verify_with_clock(tx, policy, now_ms)
+}
+
+/// [`verify_transaction_with_policy`] with step 4 judged at `now_ms` (Unix
+/// milliseconds) instead of the clock. For replaying a transaction as of
+/// when it arrived; also gives `no_std` callers the time checks.
+pub fn verify_transaction_at(
+    tx: &Transaction,
+    policy: AddressPolicy,
+    now_ms: u64,
+) -> Result<(), TransactionError> {
+    verify_with_clock(tx, policy, Some(now_ms))
+}
+
+fn verify_with_clock(
+    tx: &Transaction,
+    policy: AddressPolicy,
+    now_ms: Option<u64>,
+) -> Result<(), TransactionError> {
+    // 1. Known version; nonce must be positive (0 is reserved for
+    //    genesis/system txs).
+    if !(LEGACY_TX_VERSION..=CANONICAL_TX_VERSION).contains(&tx.version) {
+        return Err(TransactionError::UnsupportedVersion {
+            version: tx.version,
+        });
+    }
     if tx.nonce == 0 {
         return Err(TransactionError::InvalidNonce { nonce: tx.nonce });
     }
 
-    // 2. Amount must be non-zero.
-    if tx.amount.value == 0 {
-        return Err(TransactionError::ZeroAmount);
-    }
+    // 2. Field sizes and total weight, amount rule for the type,
+    //    sender/receiver addresses.
+    limits::check_fields(tx, policy)?;
+    #[cfg(feature = "std")]
+    super::payload::check(tx).map_err(|e| TransactionError::InvalidPayload {
+        tx_type: tx.tx_type,
+        reason: e.to_string(),
+    })?;
 
     // 3. No self-transfers.
     if tx.sender == tx.receiver {
@@ -125,16 +235,26 @@ pub fn verify_transaction(tx: &Transaction) -> Result<(), TransactionError> {
         });
     }
 
-    // 4. Timestamp must not be unreasonably far in the future.
-    let now_ms = Utc::now().timestamp_millis() as u64;
-    let max_future_ms = now_ms + (MAX_FUTURE_SECONDS as u64 * 1_000);
-    if tx.timestamp > max_future_ms {
-        let delta_secs = (tx.timestamp as i64 - now_ms as i64) / 1_000;
-        return Err(TransactionError::TimestampTooFarInFuture {
-            timestamp_ms: tx.timestamp,
-            delta_secs,
-            max_secs: MAX_FUTURE_SECONDS,
-        });
+    // 4. Timestamp must not be unreasonably far in the future, and the
+    //    transaction must not have expired.
+    if let Some(now_ms) = now_ms {
+        let max_future_ms = now_ms.saturating_add(MAX_FUTURE_SECONDS as u64 * 1_000);
+        if tx.timestamp > max_future_ms {
+            let delta_secs = (tx.timestamp as i64 - now_ms as i64) / 1_000;
+            return Err(TransactionError::TimestampTooFarInFuture {
+                timestamp_ms: tx.timestamp,
+                delta_secs,
+                max_secs: MAX_FUTURE_SECONDS,
+            });
+        }
+        if let Some(valid_until) = tx.valid_until {
+            if now_ms > valid_until {
+                return Err(TransactionError::Expired {
+                    valid_until,
+                    now_ms,
+                });
+            }
+        }
     }
 
     // 5. Transaction ID integrity check.
@@ -146,6 +266,70 @@ pub fn verify_transaction(tx: &Transaction) -> Result<(), TransactionError> {
         });
     }
 
+    // 6–9. The sender's signature, and a fee payer's, over the signable
+    //      bytes.
+    let signable = tx.signable_bytes();
+    for required in required_signatures(tx)? {
+        if !required.public_key.verify(&signable, &required.signature) {
+            return Err(required.invalid(tx));
+        }
+    }
+
+    // 10. ConfidentialTransfer type REQUIRES both a proof and commitment.
+    if tx.tx_type == TransactionType::ConfidentialTransfer {
+        if tx.proof.is_none() {
+            return Err(TransactionError::MissingProof);
+        }
+        if tx.amount_commitment.is_none() {
+            return Err(TransactionError::MissingCommitment);
+        }
+    }
+
+    // 11. ZKP proof verification — if a proof is attached, validate that
+    //     it is at least well-formed (deserializable as a Groth16 proof).
+    //     Full semantic verification (against a specific commitment and
+    //     required amount) requires the BalanceVerifier, which lives at the
+    //     node layer. Here we perform structural validation only.
+    #[cfg(feature = "zkp")]
+    if let Some(ref proof_bytes) = tx.proof {
+        BalanceProof::from_bytes(proof_bytes).map_err(|e| TransactionError::InvalidProof {
+            reason: e.to_string(),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// An Ed25519 signature a transaction must carry over its signable bytes.
+pub(crate) struct RequiredSignature {
+    /// Key that must have signed, already matched to its address.
+    pub(crate) public_key: NovaPublicKey,
+    /// The signature carried.
+    pub(crate) signature: NovaSignature,
+    /// `None` for the sender, the fee payer's address for a co-signature.
+    fee_payer: Option<String>,
+}
+
+impl RequiredSignature {
+    /// The error for this signature not verifying on `tx`.
+    pub(crate) fn invalid(&self, tx: &Transaction) -> TransactionError {
+        match &self.fee_payer {
+            None => TransactionError::InvalidSignature {
+                sender: tx.sender.clone(),
+            },
+            Some(fee_payer) => TransactionError::InvalidFeePayerSignature {
+                fee_payer: fee_payer.clone(),
+            },
+        }
+    }
+}
+
+/// Steps 6–9 short of the curve arithmetic: decodes the signatures `tx`
+/// must carry and checks that each key belongs to the address it signs
+/// for. Callers verify the returned signatures one by one or in a batch.
+pub(crate) fn required_signatures(
+    tx: &Transaction,
+) -> Result<Vec<RequiredSignature>, TransactionError> {
     // 6. Signature must be present.
     let sig_hex = tx
         .signature
@@ -153,30 +337,11 @@ pub fn verify_transaction(tx: &Transaction) -> Result<(), TransactionError> {
         .ok_or(TransactionError::MissingSignature)?;
 
     // 7. Decode signature from hex.
-    let sig_bytes = hex::decode(sig_hex).map_err(|e| TransactionError::MalformedSignature {
-        reason: format!("hex decode failed: {}", e),
-    })?;
-
-    if sig_bytes.len() != 64 {
-        return Err(TransactionError::MalformedSignature {
-            reason: format!("expected 64 bytes, got {}", sig_bytes.len()),
-        });
-    }
-
-    let mut sig_arr = [0u8; 64];
-    sig_arr.copy_from_slice(&sig_bytes);
-    let signature = NovaSignature::from_bytes(sig_arr);
-
-    // 8. Parse sender address to validate it's a well-formed NOVA address.
-    let _sender_id =
-        NovaId::from_address(&tx.sender).map_err(|_| TransactionError::InvalidSenderAddress {
-            address: tx.sender.clone(),
-        })?;
+    let signature = decode_signature(sig_hex)?;
 
-    // 9. Verify the signature against the sender's public key.
-    //    The sender's public key is extracted from the `sender_pubkey` field
-    //    on the transaction. We also verify that the public key hashes to the
-    //    sender address to prevent key substitution attacks.
+    // 8. The sender's public key is extracted from the `sender_pubkey`
+    //    field on the transaction. It must hash to the sender address to
+    //    prevent key substitution attacks.
     let sender_pk_hex =
         tx.sender_public_key
             .as_ref()
@@ -188,44 +353,74 @@ pub fn verify_transaction(tx: &Transaction) -> Result<(), TransactionError> {
             address: tx.sender.clone(),
         }
     })?;
-
-    // Verify the public key maps to the claimed sender address.
-    let derived_id = NovaId::from_public_key(&sender_pk);
-    if derived_id.to_address() != tx.sender {
+    if NovaId::from_public_key(&sender_pk).to_address() != tx.sender {
         return Err(TransactionError::InvalidSenderAddress {
             address: tx.sender.clone(),
         });
     }
-
-    let signable = tx.signable_bytes();
-    if !sender_pk.verify(&signable, &signature) {
-        return Err(TransactionError::InvalidSignature {
-            sender: tx.sender.clone(),
-        });
-    }
-
-    // 10. ConfidentialTransfer type REQUIRES both a proof and commitment.
-    if tx.tx_type == TransactionType::ConfidentialTransfer {
-        if tx.proof.is_none() {
-            return Err(TransactionError::MissingProof);
+    let mut required = vec![RequiredSignature {
+        public_key: sender_pk,
+        signature,
+        fee_payer: None,
+    }];
+
+    // 9. A sponsor must be a different account and co-sign the same bytes.
+    //    Its key must hash to the fee payer address, as for the sender.
+    match tx.fee_payer {
+        Some(ref fee_payer) => {
+            if tx.tx_type != TransactionType::Transfer {
+                return Err(TransactionError::SponsorshipNotSupported {
+                    tx_type: tx.tx_type,
+                });
+            }
+            let invalid = || TransactionError::InvalidFeePayer {
+                address: fee_payer.clone(),
+            };
+            if *fee_payer == tx.sender {
+                return Err(invalid());
+            }
+            let sig_hex = tx
+                .fee_payer_signature
+                .as_ref()
+                .ok_or(TransactionError::MissingFeePayerSignature)?;
+            let signature = decode_signature(sig_hex)?;
+            let fee_payer_pk = tx
+                .fee_payer_public_key
+                .as_ref()
+                .and_then(|pk| NovaPublicKey::from_hex(pk).ok())
+                .filter(|pk| NovaId::from_public_key(pk).to_address() == *fee_payer)
+                .ok_or_else(invalid)?;
+            required.push(RequiredSignature {
+                public_key: fee_payer_pk,
+                signature,
+                fee_payer: Some(fee_payer.clone()),
+            });
         }
-        if tx.amount_commitment.is_none() {
-            return Err(TransactionError::MissingCommitment);
+        None if tx.fee_payer_signature.is_some() || tx.fee_payer_public_key.is_some() => {
+            return Err(TransactionError::MalformedSignature {
+                reason: "fee payer signature without a fee payer".to_string(),
+            });
         }
+        None => {}
     }
+    Ok(required)
+}
 
-    // 11. ZKP proof verification — if a proof is attached, validate that
-    //     it is at least well-formed (deserializable as a Groth16 proof).
-    //     Full semantic verification (against a specific commitment and
-    //     required amount) requires the BalanceVerifier, which lives at the
-    //     node layer. Here we perform structural validation only.
-    if let Some(ref proof_bytes) = tx.proof {
-        BalanceProof::from_bytes(proof_bytes).map_err(|e| TransactionError::InvalidProof {
-            reason: e.to_string(),
-        })?;
+/// Decodes a hex Ed25519 signature (steps 7 and 9).
+fn decode_signature(sig_hex: &str) -> Result<NovaSignature, TransactionError> {
+    let sig_bytes = hex::decode(sig_hex).map_err(|e| TransactionError::MalformedSignature {
+        reason: format!("hex decode failed: {}", e),
+    })?;
+
+    if sig_bytes.len() != 64 {
+        return Err(TransactionError::MalformedSignature {
+            reason: format!("expected 64 bytes, got {}", sig_bytes.len()),
+        });
     }
 
-    Ok(())
+    let mut sig_arr = [0u8; 64];
+    sig_arr.copy_from_slice(&sig_bytes);
+    Ok(NovaSignature::from_bytes(sig_arr))
 }
 
 // ---------------------------------------------------------------------------
@@ -254,7 +449,7 @@ mod tests {
             .amount(Amount::new(1_000, Currency::NOVA))
             .fee(100)
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         sign_transaction(&mut tx, &kp);
         (tx, kp)
@@ -279,6 +474,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejects_unknown_version() {
+        let (mut tx, kp) = valid_signed_tx();
+        tx.version = CANONICAL_TX_VERSION + 1;
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+
+        match verify_transaction(&tx) {
+            Err(TransactionError::UnsupportedVersion { version }) => {
+                assert_eq!(version, CANONICAL_TX_VERSION + 1)
+            }
+            other => panic!("expected UnsupportedVersion, got {:?}", other),
+        }
+
+        // Both defined versions verify.
+        let (mut tx, kp) = valid_signed_tx();
+        tx.version = LEGACY_TX_VERSION;
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+        assert!(verify_transaction(&tx).is_ok());
+    }
+
     #[test]
     fn rejects_zero_amount() {
         let kp = NovaKeypair::generate();
@@ -291,7 +508,7 @@ mod tests {
             .receiver(&receiver_addr)
             .amount(Amount::new(0, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
         sign_transaction(&mut tx, &kp);
 
         match verify_transaction(&tx) {
@@ -300,6 +517,34 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn rejects_payload_the_type_does_not_accept() {
+        use crate::transaction::payload::TransactionPayload;
+
+        let (mut tx, kp) = valid_signed_tx();
+        tx.tx_type = TransactionType::CreditSettlement;
+        tx.payload = Some(
+            TransactionPayload::CreditSettlement {
+                credit_id: "credit-9".into(),
+            }
+            .encode(),
+        );
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+        assert!(verify_transaction(&tx).is_ok());
+
+        tx.payload = Some(b"settle credit-9".to_vec());
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+        match verify_transaction(&tx) {
+            Err(TransactionError::InvalidPayload { tx_type, .. }) => {
+                assert_eq!(tx_type, TransactionType::CreditSettlement)
+            }
+            other => panic!("expected InvalidPayload, got {:?}", other),
+        }
+    }
+
     #[test]
     fn rejects_self_transfer() {
         let kp = NovaKeypair::generate();
@@ -310,7 +555,7 @@ mod tests {
             .receiver(&addr)
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
         sign_transaction(&mut tx, &kp);
 
         match verify_transaction(&tx) {
@@ -334,7 +579,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(far_future)
-            .build();
+            .build_unchecked();
         sign_transaction(&mut tx, &kp);
 
         match verify_transaction(&tx) {
@@ -343,6 +588,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejects_expired_transaction() {
+        let kp = NovaKeypair::generate();
+        let sender_addr = NovaId::from_public_key(&kp.public_key()).to_address();
+        let receiver_kp = NovaKeypair::generate();
+        let receiver_addr = NovaId::from_public_key(&receiver_kp.public_key()).to_address();
+
+        let now = Utc::now().timestamp_millis() as u64;
+        let build = |valid_until| {
+            let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+                .sender(&sender_addr)
+                .receiver(&receiver_addr)
+                .amount(Amount::new(100, Currency::NOVA))
+                .nonce(1)
+                .timestamp(now - 120_000)
+                .valid_until(valid_until)
+                .build_unchecked();
+            sign_transaction(&mut tx, &kp);
+            tx
+        };
+
+        assert!(verify_transaction(&build(now + 60_000)).is_ok());
+        match verify_transaction(&build(now - 60_000)) {
+            Err(TransactionError::Expired { valid_until, .. }) => {
+                assert_eq!(valid_until, now - 60_000)
+            }
+            other => panic!("expected Expired, got {:?}", other),
+        }
+    }
+
     #[test]
     fn rejects_tampered_id() {
         let (mut tx, _) = valid_signed_tx();
@@ -366,7 +641,7 @@ mod tests {
             .receiver(&receiver_addr)
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         match verify_transaction(&tx) {
             Err(TransactionError::MissingSignature) => {}
@@ -387,7 +662,7 @@ mod tests {
             .receiver(&receiver_addr)
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
-            .build();
+            .build_unchecked();
 
         // Sign with the WRONG keypair (sets sender_public_key to kp_wrong's key).
         sign_transaction(&mut tx, &kp_wrong);
@@ -416,6 +691,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sponsored_transfer_needs_the_fee_payer_signature() {
+        let (mut tx, kp) = valid_signed_tx();
+        let sponsor = NovaKeypair::generate();
+        tx.fee_payer = Some(NovaId::from_public_key(&sponsor.public_key()).to_address());
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+
+        match verify_transaction(&tx) {
+            Err(TransactionError::MissingFeePayerSignature) => {}
+            other => panic!("expected MissingFeePayerSignature, got {:?}", other),
+        }
+
+        // A key that is not the fee payer's.
+        let mut forged = tx.clone();
+        crate::transaction::signing::sign_as_fee_payer(&mut forged, &NovaKeypair::generate());
+        match verify_transaction(&forged) {
+            Err(TransactionError::InvalidFeePayer { .. }) => {}
+            other => panic!("expected InvalidFeePayer, got {:?}", other),
+        }
+
+        crate::transaction::signing::sign_as_fee_payer(&mut tx, &sponsor);
+        assert!(verify_transaction(&tx).is_ok());
+
+        // The sender's signature covers the sponsor.
+        let mut swapped = tx.clone();
+        swapped.fee_payer = Some(NovaId::from_public_key(&kp.public_key()).to_address());
+        swapped.id = swapped.compute_id();
+        assert!(verify_transaction(&swapped).is_err());
+
+        // Only transfers can be sponsored.
+        let mut hold = tx.clone();
+        hold.tx_type = TransactionType::AuthorizationHold;
+        hold.id = hold.compute_id();
+        sign_transaction(&mut hold, &kp);
+        crate::transaction::signing::sign_as_fee_payer(&mut hold, &sponsor);
+        match verify_transaction(&hold) {
+            Err(TransactionError::SponsorshipNotSupported { .. }) => {}
+            other => panic!("expected SponsorshipNotSupported, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_memo_and_bad_receiver() {
+        let (mut tx, kp) = valid_signed_tx();
+        tx.payload = Some(vec![b'm'; limits::MAX_MEMO_BYTES + 1]);
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+
+        match verify_transaction(&tx) {
+            Err(TransactionError::FieldTooLarge { field: "memo", .. }) => {}
+            other => panic!("expected memo FieldTooLarge, got {:?}", other),
+        }
+
+        let (mut tx, kp) = valid_signed_tx();
+        tx.receiver = "nova1bob".to_string();
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+
+        match verify_transaction(&tx) {
+            Err(TransactionError::InvalidAddress(_)) => {}
+            other => panic!("expected InvalidAddress, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn memo_is_bounded_and_covered_by_the_signature() {
+        let (mut tx, kp) = valid_signed_tx();
+        tx.memo = Some("order-1042".to_string());
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+        assert!(verify_transaction(&tx).is_ok());
+
+        // Rewriting the memo after signing breaks the ID.
+        let mut tampered = tx.clone();
+        tampered.memo = Some("order-9999".to_string());
+        assert!(verify_transaction(&tampered).is_err());
+
+        tx.memo = Some("r".repeat(limits::MAX_TX_MEMO_BYTES + 1));
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+        match verify_transaction(&tx) {
+            Err(TransactionError::FieldTooLarge { field: "memo", .. }) => {}
+            other => panic!("expected memo FieldTooLarge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn permissive_policy_accepts_dev_receiver() {
+        let (mut tx, kp) = valid_signed_tx();
+        tx.receiver = "nova1bob".to_string();
+        tx.id = tx.compute_id();
+        sign_transaction(&mut tx, &kp);
+
+        assert!(verify_transaction(&tx).is_err());
+        assert!(verify_transaction_with_policy(&tx, AddressPolicy::Permissive).is_ok());
+    }
+
     #[test]
     fn accepts_near_future_timestamp() {
         let kp = NovaKeypair::generate();
@@ -432,7 +805,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(near_future)
-            .build();
+            .build_unchecked();
         sign_transaction(&mut tx, &kp);
 
         assert!(verify_transaction(&tx).is_ok());
@@ -454,7 +827,7 @@ mod tests {
             .amount(Amount::new(100, Currency::NOVA))
             .nonce(1)
             .timestamp(past)
-            .build();
+            .build_unchecked();
         sign_transaction(&mut tx, &kp);
 
         assert!(verify_transaction(&tx).is_ok());
@@ -8,10 +8,13 @@
 //! ## Architecture
 //!
 //! ```text
-//! token.rs    — Token standard: identifiers, metadata, pre-defined tokens
+//! token.rs    — Token standard: identifiers, metadata, pre-defined tokens,
+//!               the token registry
 //! balance.rs  — Per-wallet balance tracking with Pedersen commitments
 //! wallet.rs   — Multi-asset wallet: deposits, withdrawals, transfers
 //! credit.rs   — Credit line management: limits, draws, repayments
+//! reserves.rs — Proof of reserves for custodial operators: Merkle-sum
+//!               liabilities, signed address ownership
 //! ```
 //!
 //! ## Design Principles
@@ -33,10 +36,15 @@
 
 pub mod balance;
 pub mod credit;
+pub mod reserves;
 pub mod token;
 pub mod wallet;
 
 pub use balance::{Balance, BalanceError, BalanceSheet};
 pub use credit::{CreditError, CreditLine, CreditLineManager, CreditLineStatus};
-pub use token::{Token, TokenId, TokenInfo, TokenType};
+pub use reserves::{
+    AddressOwnershipProof, CustomerBalance, InclusionProof, LiabilityTree, ReservesCommitment,
+    ReservesError, ReservesReport, Solvency, SumNode,
+};
+pub use token::{Token, TokenId, TokenInfo, TokenRegistry, TokenType};
 pub use wallet::{Wallet, WalletError};
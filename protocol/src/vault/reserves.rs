@@ -0,0 +1,561 @@
+//! # Proof of Reserves for Custodial Operators
+//!
+//! An exchange holding NOVA for its customers shows it is solvent in two
+//! halves:
+//!
+//! - **Liabilities.** Customer balances go into a Merkle-sum tree: every
+//!   node carries a hash *and* the sum of the balances below it, so the
+//!   root commits to the total owed. Each customer gets an
+//!   [`InclusionProof`] that their balance is counted, and since sums can
+//!   only add up, no balance can be hidden by netting it against another.
+//!   Leaves hash a per-customer nonce with the customer ID, so the proofs
+//!   reveal neither identities nor anyone else's balance.
+//! - **Reserves.** For each on-chain address it controls, the operator signs
+//!   the published [`ReservesCommitment`] with the address key
+//!   ([`AddressOwnershipProof`]). Anyone can read those addresses' balances
+//!   from the chain and add them up.
+//!
+//! A [`ReservesReport`] verifies when every ownership proof checks out and
+//! the reserves it sums to are at least the committed liabilities.
+//!
+//! The commitment is published by placing [`ReservesCommitment::to_payload`]
+//! in the payload of a transaction from the operator, so it is timestamped
+//! by the chain and cannot be swapped afterwards for a tree with smaller
+//! balances.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::crypto::hash::domain_separated_hash;
+use crate::crypto::keys::{NovaKeypair, NovaPublicKey, NovaSignature};
+use crate::encoding;
+use crate::identity::NovaId;
+
+/// Domain tag of the ownership signature message and the on-chain payload.
+pub const RESERVES_DOMAIN: &[u8] = b"nova-proof-of-reserves-v1";
+
+const LEAF_CONTEXT: &str = "nova reserves leaf v1";
+const NODE_CONTEXT: &str = "nova reserves node v1";
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Errors building or verifying a proof of reserves.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReservesError {
+    /// The customer balances sum past `u64::MAX`.
+    #[error("total liabilities overflow u64")]
+    LiabilityOverflow,
+
+    /// The on-chain balances of the operator's addresses sum past `u64::MAX`.
+    #[error("total reserves overflow u64")]
+    ReserveOverflow,
+
+    /// No leaf exists at the requested index.
+    #[error("no customer at leaf index {0}")]
+    UnknownLeaf(usize),
+
+    /// An ownership proof has a bad key, a key that does not derive the
+    /// address, or a signature that does not verify.
+    #[error("invalid ownership proof for {0}")]
+    InvalidOwnership(String),
+
+    /// The same address is claimed twice, which would count it twice.
+    #[error("address {0} claimed more than once")]
+    DuplicateAddress(String),
+
+    /// The chain has no account for a claimed address.
+    #[error("no on-chain account for {0}")]
+    UnknownAddress(String),
+
+    /// Reserves fall short of liabilities.
+    #[error("insolvent: reserves {reserves} < liabilities {liabilities}")]
+    Insolvent {
+        /// Sum of the proven on-chain balances.
+        reserves: u64,
+        /// Total committed by the liabilities root.
+        liabilities: u64,
+    },
+
+    /// The bytes are not a reserves commitment payload.
+    #[error("malformed reserves payload")]
+    MalformedPayload,
+}
+
+// ---------------------------------------------------------------------------
+// Merkle-sum tree
+// ---------------------------------------------------------------------------
+
+/// A node of the Merkle-sum tree: a hash and the balance total beneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SumNode {
+    /// Hash binding the node's children and their sums.
+    pub hash: [u8; 32],
+    /// Sum of the customer balances in this subtree.
+    pub sum: u64,
+}
+
+impl SumNode {
+    /// Padding for odd levels. It adds nothing to the sum, where
+    /// duplicating the last node (as [`merkle_root`] does) would count a
+    /// balance twice.
+    ///
+    /// [`merkle_root`]: crate::crypto::hash::merkle_root
+    pub const EMPTY: SumNode = SumNode {
+        hash: [0u8; 32],
+        sum: 0,
+    };
+
+    /// The leaf for a customer's balance.
+    ///
+    /// `nonce` is a secret the operator hands to the customer along with
+    /// the proof; without it a leaf hash cannot be linked to a customer ID.
+    pub fn leaf(customer_id: &str, nonce: &[u8; 32], balance: u64) -> Self {
+        let mut data = Vec::with_capacity(32 + customer_id.len() + 8);
+        data.extend_from_slice(nonce);
+        data.extend_from_slice(customer_id.as_bytes());
+        data.extend_from_slice(&balance.to_le_bytes());
+        Self {
+            hash: domain_separated_hash(LEAF_CONTEXT, &data),
+            sum: balance,
+        }
+    }
+
+    /// The parent of `left` and `right`, or `None` if the sum overflows.
+    pub fn parent(left: &SumNode, right: &SumNode) -> Option<Self> {
+        let sum = left.sum.checked_add(right.sum)?;
+        let mut data = [0u8; 80];
+        data[..32].copy_from_slice(&left.hash);
+        data[32..40].copy_from_slice(&left.sum.to_le_bytes());
+        data[40..72].copy_from_slice(&right.hash);
+        data[72..].copy_from_slice(&right.sum.to_le_bytes());
+        Some(Self {
+            hash: domain_separated_hash(NODE_CONTEXT, &data),
+            sum,
+        })
+    }
+}
+
+/// One customer's entry in the liabilities tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomerBalance {
+    /// The operator's identifier for the customer (account number, email).
+    pub customer_id: String,
+    /// Per-customer secret blinding the leaf hash.
+    pub nonce: [u8; 32],
+    /// NOVA owed to the customer, in base units.
+    pub balance: u64,
+}
+
+/// The operator's Merkle-sum tree over all customer balances.
+#[derive(Debug, Clone)]
+pub struct LiabilityTree {
+    /// `levels[0]` are the leaves, the last level is the root alone.
+    levels: Vec<Vec<SumNode>>,
+}
+
+impl LiabilityTree {
+    /// Builds the tree with one leaf per customer, in the given order.
+    ///
+    /// # Errors
+    ///
+    /// [`ReservesError::LiabilityOverflow`] if the balances sum past
+    /// `u64::MAX`.
+    pub fn build(customers: &[CustomerBalance]) -> Result<Self, ReservesError> {
+        let leaves: Vec<SumNode> = customers
+            .iter()
+            .map(|c| SumNode::leaf(&c.customer_id, &c.nonce, c.balance))
+            .collect();
+        let mut levels = vec![leaves];
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let level = levels.last().expect("at least one level");
+            let next = level
+                .chunks(2)
+                .map(|pair| SumNode::parent(&pair[0], pair.get(1).unwrap_or(&SumNode::EMPTY)))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(ReservesError::LiabilityOverflow)?;
+            levels.push(next);
+        }
+        Ok(Self { levels })
+    }
+
+    /// The root; [`SumNode::EMPTY`] for an operator with no customers.
+    pub fn root(&self) -> SumNode {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or(SumNode::EMPTY)
+    }
+
+    /// Total owed to customers.
+    pub fn total_liabilities(&self) -> u64 {
+        self.root().sum
+    }
+
+    /// Number of customers in the tree.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns `true` if the tree has no customers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The inclusion proof for the customer at `index`.
+    ///
+    /// # Errors
+    ///
+    /// [`ReservesError::UnknownLeaf`] if `index` is out of range.
+    pub fn prove(&self, index: usize) -> Result<InclusionProof, ReservesError> {
+        if index >= self.len() {
+            return Err(ReservesError::UnknownLeaf(index));
+        }
+        let siblings = self.levels[..self.levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(depth, level)| {
+                let position = index >> depth;
+                level.get(position ^ 1).copied().unwrap_or(SumNode::EMPTY)
+            })
+            .collect();
+        Ok(InclusionProof { index, siblings })
+    }
+}
+
+/// Proof that one customer's balance is counted under a liabilities root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// Position of the customer's leaf.
+    pub index: usize,
+    /// Sibling nodes from the leaf level up to just below the root.
+    pub siblings: Vec<SumNode>,
+}
+
+impl InclusionProof {
+    /// Returns `true` if the customer's leaf, hashed up with the siblings,
+    /// reproduces `root` — hash and sum.
+    pub fn verify(&self, root: &SumNode, customer: &CustomerBalance) -> bool {
+        let mut node = SumNode::leaf(&customer.customer_id, &customer.nonce, customer.balance);
+        for (depth, sibling) in self.siblings.iter().enumerate() {
+            let parent = if (self.index >> depth) & 1 == 0 {
+                SumNode::parent(&node, sibling)
+            } else {
+                SumNode::parent(sibling, &node)
+            };
+            match parent {
+                Some(parent) => node = parent,
+                None => return false,
+            }
+        }
+        node == *root
+    }
+}
+
+// ---------------------------------------------------------------------------
+// On-chain commitment and address ownership
+// ---------------------------------------------------------------------------
+
+/// What the operator publishes on-chain for one reserves attestation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservesCommitment {
+    /// Root of the liabilities tree; `root.sum` is the total owed.
+    pub root: SumNode,
+    /// Block height the reserves are measured at.
+    pub height: u64,
+}
+
+impl ReservesCommitment {
+    /// Length of [`to_payload`](Self::to_payload) output.
+    pub const PAYLOAD_LEN: usize = RESERVES_DOMAIN.len() + 32 + 8 + 8;
+
+    /// Commits to `tree` with reserves measured at `height`.
+    pub fn new(tree: &LiabilityTree, height: u64) -> Self {
+        Self {
+            root: tree.root(),
+            height,
+        }
+    }
+
+    /// Transaction payload publishing this commitment:
+    /// `RESERVES_DOMAIN || root hash || root sum (LE) || height (LE)`.
+    /// The same bytes are what address keys sign.
+    pub fn to_payload(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::PAYLOAD_LEN);
+        buf.extend_from_slice(RESERVES_DOMAIN);
+        buf.extend_from_slice(&self.root.hash);
+        buf.extend_from_slice(&self.root.sum.to_le_bytes());
+        buf.extend_from_slice(&self.height.to_le_bytes());
+        buf
+    }
+
+    /// Parses a transaction payload written by
+    /// [`to_payload`](Self::to_payload).
+    ///
+    /// # Errors
+    ///
+    /// [`ReservesError::MalformedPayload`] on a wrong length or domain tag.
+    pub fn from_payload(payload: &[u8]) -> Result<Self, ReservesError> {
+        if payload.len() != Self::PAYLOAD_LEN || !payload.starts_with(RESERVES_DOMAIN) {
+            return Err(ReservesError::MalformedPayload);
+        }
+        let body = &payload[RESERVES_DOMAIN.len()..];
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&body[..32]);
+        let word = |at: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&body[at..at + 8]);
+            u64::from_le_bytes(bytes)
+        };
+        Ok(Self {
+            root: SumNode {
+                hash,
+                sum: word(32),
+            },
+            height: word(40),
+        })
+    }
+}
+
+/// A signature by an address's key over a [`ReservesCommitment`], proving
+/// the operator controls the address's funds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressOwnershipProof {
+    /// The on-chain address holding reserves.
+    pub address: String,
+    /// Public key the address derives from, hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub public_key: String,
+    /// Ed25519 signature over the commitment payload, hex-encoded.
+    #[serde(with = "encoding::hex_string")]
+    pub signature: String,
+}
+
+impl AddressOwnershipProof {
+    /// Signs `commitment` with the key of the address it proves.
+    pub fn sign(commitment: &ReservesCommitment, keypair: &NovaKeypair) -> Self {
+        let public_key = keypair.public_key();
+        Self {
+            address: NovaId::from_public_key(&public_key).to_address(),
+            public_key: public_key.to_hex(),
+            signature: keypair.sign(&commitment.to_payload()).to_hex(),
+        }
+    }
+
+    /// Returns `true` if the key derives `address` and signed `commitment`.
+    pub fn verify(&self, commitment: &ReservesCommitment) -> bool {
+        let (Ok(key), Ok(signature)) = (
+            NovaPublicKey::from_hex(encoding::strip_hex_prefix(&self.public_key)),
+            NovaSignature::from_hex(encoding::strip_hex_prefix(&self.signature)),
+        ) else {
+            return false;
+        };
+        NovaId::from_public_key(&key).to_address() == self.address
+            && key.verify(&commitment.to_payload(), &signature)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Everything a third party needs to check an operator's solvency.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservesReport {
+    /// The published commitment.
+    pub commitment: ReservesCommitment,
+    /// One proof per address counted as reserves.
+    pub ownership: Vec<AddressOwnershipProof>,
+}
+
+/// Outcome of a successful [`ReservesReport::verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solvency {
+    /// Sum of the proven addresses' on-chain balances.
+    pub reserves: u64,
+    /// Total owed to customers.
+    pub liabilities: u64,
+}
+
+impl Solvency {
+    /// Reserves held beyond what is owed.
+    pub fn surplus(&self) -> u64 {
+        self.reserves - self.liabilities
+    }
+}
+
+impl ReservesReport {
+    /// Signs `commitment` with the key of every reserve address.
+    pub fn new(commitment: ReservesCommitment, keys: &[&NovaKeypair]) -> Self {
+        let ownership = keys
+            .iter()
+            .map(|kp| AddressOwnershipProof::sign(&commitment, kp))
+            .collect();
+        Self {
+            commitment,
+            ownership,
+        }
+    }
+
+    /// Checks that reserves cover liabilities.
+    ///
+    /// `balance_of` reads an address's NOVA balance at
+    /// `commitment.height`, e.g. from a `StateTree` or an RPC node.
+    ///
+    /// # Errors
+    ///
+    /// The first invalid or duplicate ownership proof, an address unknown
+    /// to `balance_of`, an overflowing total, or
+    /// [`ReservesError::Insolvent`].
+    pub fn verify(
+        &self,
+        balance_of: impl Fn(&str) -> Option<u64>,
+    ) -> Result<Solvency, ReservesError> {
+        let mut seen = HashSet::new();
+        let mut reserves: u64 = 0;
+        for proof in &self.ownership {
+            if !proof.verify(&self.commitment) {
+                return Err(ReservesError::InvalidOwnership(proof.address.clone()));
+            }
+            if !seen.insert(proof.address.as_str()) {
+                return Err(ReservesError::DuplicateAddress(proof.address.clone()));
+            }
+            let balance = balance_of(&proof.address)
+                .ok_or_else(|| ReservesError::UnknownAddress(proof.address.clone()))?;
+            reserves = reserves
+                .checked_add(balance)
+                .ok_or(ReservesError::ReserveOverflow)?;
+        }
+
+        let liabilities = self.commitment.root.sum;
+        if reserves < liabilities {
+            return Err(ReservesError::Insolvent {
+                reserves,
+                liabilities,
+            });
+        }
+        Ok(Solvency {
+            reserves,
+            liabilities,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn customers(balances: &[u64]) -> Vec<CustomerBalance> {
+        balances
+            .iter()
+            .enumerate()
+            .map(|(i, &balance)| CustomerBalance {
+                customer_id: format!("customer-{}", i),
+                nonce: [i as u8; 32],
+                balance,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_customer_proves_inclusion_under_the_root() {
+        let list = customers(&[100, 250, 0, 75, 1_000]);
+        let tree = LiabilityTree::build(&list).unwrap();
+        let root = tree.root();
+        assert_eq!(root.sum, 1_425);
+
+        for (i, customer) in list.iter().enumerate() {
+            let proof = tree.prove(i).unwrap();
+            assert!(proof.verify(&root, customer));
+        }
+
+        // A customer checking a balance other than the one committed fails,
+        // as does one holding another customer's proof.
+        let mut understated = list[1].clone();
+        understated.balance = 200;
+        assert!(!tree.prove(1).unwrap().verify(&root, &understated));
+        assert!(!tree.prove(0).unwrap().verify(&root, &list[1]));
+        assert_eq!(tree.prove(5), Err(ReservesError::UnknownLeaf(5)));
+    }
+
+    #[test]
+    fn overflowing_liabilities_are_rejected() {
+        let list = customers(&[u64::MAX, 1]);
+        assert!(matches!(
+            LiabilityTree::build(&list),
+            Err(ReservesError::LiabilityOverflow)
+        ));
+    }
+
+    #[test]
+    fn payload_round_trips() {
+        let tree = LiabilityTree::build(&customers(&[10, 20, 30])).unwrap();
+        let commitment = ReservesCommitment::new(&tree, 42);
+        let payload = commitment.to_payload();
+        assert_eq!(payload.len(), ReservesCommitment::PAYLOAD_LEN);
+        assert_eq!(ReservesCommitment::from_payload(&payload), Ok(commitment));
+        assert_eq!(
+            ReservesCommitment::from_payload(&payload[1..]),
+            Err(ReservesError::MalformedPayload)
+        );
+    }
+
+    #[test]
+    fn report_checks_ownership_and_solvency() {
+        let tree = LiabilityTree::build(&customers(&[400, 600])).unwrap();
+        let commitment = ReservesCommitment::new(&tree, 7);
+        let hot = NovaKeypair::generate();
+        let cold = NovaKeypair::generate();
+        let report = ReservesReport::new(commitment.clone(), &[&hot, &cold]);
+
+        let mut chain = HashMap::new();
+        chain.insert(report.ownership[0].address.clone(), 300);
+        chain.insert(report.ownership[1].address.clone(), 800);
+        let solvency = report.verify(|a| chain.get(a).copied()).unwrap();
+        assert_eq!((solvency.reserves, solvency.surplus()), (1_100, 100));
+
+        // Cold wallet drained below what is owed.
+        chain.insert(report.ownership[1].address.clone(), 500);
+        assert_eq!(
+            report.verify(|a| chain.get(a).copied()),
+            Err(ReservesError::Insolvent {
+                reserves: 800,
+                liabilities: 1_000
+            })
+        );
+
+        // Claiming someone else's address with one's own key fails.
+        let mut stolen = report.clone();
+        stolen.ownership[0].address =
+            NovaId::from_public_key(&NovaKeypair::generate().public_key()).to_address();
+        assert!(matches!(
+            stolen.verify(|_| Some(u64::MAX / 4)),
+            Err(ReservesError::InvalidOwnership(_))
+        ));
+
+        // Counting the same address twice fails.
+        let mut doubled = report.clone();
+        doubled.ownership[1] = doubled.ownership[0].clone();
+        assert!(matches!(
+            doubled.verify(|_| Some(1_000)),
+            Err(ReservesError::DuplicateAddress(_))
+        ));
+
+        // Signatures over an older commitment don't carry over.
+        let mut stale = report;
+        stale.commitment.height = 8;
+        assert!(matches!(
+            stale.verify(|_| Some(1_000)),
+            Err(ReservesError::InvalidOwnership(_))
+        ));
+    }
+}
@@ -15,6 +15,12 @@
 //! The protocol ships with a set of well-known token constants for the
 //! assets we expect to see on day one: `nova_brl()`, `nova_usd()`,
 //! `nova_btc()`, etc.
+//!
+//! ## Registry
+//!
+//! A [`TokenRegistry`] lists the tokens a chain accepts in transfers and
+//! resolves a transaction's [`Currency`] to one of them. The default
+//! registry holds the pre-defined tokens.
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -31,7 +37,7 @@ use crate::transaction::types::Currency;
 /// Computed as `BLAKE3(name || symbol || token_type_tag || issuer_address)`.
 /// Two tokens with identical properties will always produce the same ID,
 /// making this a natural deduplication key across the network.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TokenId([u8; 32]);
 
 impl TokenId {
@@ -163,6 +169,43 @@ pub mod token_id_map {
     }
 }
 
+/// [`token_id_map`] for `BTreeMap<TokenId, V>`, whose ordered entries
+/// serialize to the same bytes on every node.
+pub mod token_id_btree_map {
+    use super::TokenId;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<V, S>(map: &BTreeMap<TokenId, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        V: Serialize,
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut ser_map = serializer.serialize_map(Some(map.len()))?;
+        for (key, value) in map {
+            ser_map.serialize_entry(&key.to_hex(), value)?;
+        }
+        ser_map.end()
+    }
+
+    pub fn deserialize<'de, V, D>(deserializer: D) -> Result<BTreeMap<TokenId, V>, D::Error>
+    where
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let string_map: BTreeMap<String, V> = BTreeMap::deserialize(deserializer)?;
+        string_map
+            .into_iter()
+            .map(|(key, value)| {
+                TokenId::from_hex(&key)
+                    .map(|id| (id, value))
+                    .map_err(serde::de::Error::custom)
+            })
+            .collect()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // TokenType
 // ---------------------------------------------------------------------------
@@ -454,7 +497,7 @@ pub fn nova_native() -> TokenInfo {
         TokenType::Native,
         SYSTEM_ISSUER,
         // Genesis supply: 1 billion NOVA = 10^17 photons.
-        100_000_000_000_000_000,
+        crate::config::GENESIS_SUPPLY_PHOTONS,
     )
 }
 
@@ -477,6 +520,90 @@ pub fn usd_token_id() -> TokenId {
     nova_usd().id
 }
 
+// ---------------------------------------------------------------------------
+// TokenRegistry
+// ---------------------------------------------------------------------------
+
+/// The tokens a chain accepts in transfers, in registration order.
+///
+/// Resolution walks the tokens in that order, so every node configured
+/// with the same registry resolves a currency to the same token.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenRegistry {
+    tokens: Vec<TokenInfo>,
+}
+
+impl TokenRegistry {
+    /// A registry holding no tokens, not even NOVA.
+    pub fn empty() -> Self {
+        Self { tokens: Vec::new() }
+    }
+
+    /// Adds `token`. Returns `false`, leaving the registry unchanged, if a
+    /// token with the same ID or symbol is already registered.
+    pub fn register(&mut self, token: TokenInfo) -> bool {
+        let taken = self
+            .tokens
+            .iter()
+            .any(|t| t.id == token.id || t.symbol == token.symbol);
+        if !taken {
+            self.tokens.push(token);
+        }
+        !taken
+    }
+
+    /// The registered token with ID `id`.
+    pub fn get(&self, id: &TokenId) -> Option<&TokenInfo> {
+        self.tokens.iter().find(|t| t.id == *id)
+    }
+
+    /// The token a transaction denominated in `currency` moves.
+    ///
+    /// `NOVA` is the native token. A standard currency is the first token
+    /// backed by or wrapping it; a custom ticker matches a token's symbol
+    /// or hex ID, or the asset a token backs.
+    pub fn resolve(&self, currency: &Currency) -> Option<&TokenInfo> {
+        self.tokens
+            .iter()
+            .find(|t| match (&t.token_type, currency) {
+                (TokenType::Native, Currency::NOVA) => true,
+                (
+                    TokenType::FiatBacked(backing)
+                    | TokenType::Crypto(backing)
+                    | TokenType::Stablecoin(backing),
+                    _,
+                ) if backing == currency => true,
+                (_, Currency::Custom(ticker)) => t.symbol == *ticker || t.id.to_hex() == *ticker,
+                _ => false,
+            })
+    }
+
+    /// The registered tokens, in registration order.
+    pub fn tokens(&self) -> &[TokenInfo] {
+        &self.tokens
+    }
+}
+
+impl Default for TokenRegistry {
+    /// The native token and the pre-defined tokens.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for token in [
+            nova_native(),
+            nova_brl(),
+            nova_usd(),
+            nova_eur(),
+            nova_btc(),
+            nova_eth(),
+            nova_usdc(),
+            nova_usdt(),
+        ] {
+            registry.register(token);
+        }
+        registry
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -613,4 +740,45 @@ mod tests {
         let recovered: TokenInfo = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(token, recovered);
     }
+
+    #[test]
+    fn registry_resolves_currencies_to_tokens() {
+        let registry = TokenRegistry::default();
+        assert_eq!(
+            registry.resolve(&Currency::NOVA).unwrap().id,
+            native_token_id()
+        );
+        assert_eq!(registry.resolve(&Currency::BRL).unwrap().id, brl_token_id());
+        assert_eq!(
+            registry
+                .resolve(&Currency::Custom("nUSD".into()))
+                .unwrap()
+                .id,
+            usd_token_id()
+        );
+        assert_eq!(
+            registry
+                .resolve(&Currency::Custom("USDT".into()))
+                .unwrap()
+                .symbol,
+            "nUSDT"
+        );
+        let by_id = Currency::Custom(brl_token_id().to_hex());
+        assert_eq!(registry.resolve(&by_id).unwrap().id, brl_token_id());
+        assert!(registry.resolve(&Currency::Custom("DOGE".into())).is_none());
+        assert!(TokenRegistry::empty().resolve(&Currency::NOVA).is_none());
+    }
+
+    #[test]
+    fn registry_refuses_duplicates() {
+        let mut registry = TokenRegistry::empty();
+        assert!(registry.register(nova_brl()));
+        assert!(!registry.register(nova_brl()));
+
+        let mut impostor = nova_usd();
+        impostor.symbol = "nBRL".into();
+        assert!(!registry.register(impostor));
+        assert_eq!(registry.tokens().len(), 1);
+        assert!(registry.get(&brl_token_id()).is_some());
+    }
 }
@@ -29,6 +29,7 @@ use thiserror::Error;
 
 use super::balance::{BalanceError, BalanceSheet};
 use super::token::TokenId;
+use crate::identity::nova_id::{AddressPolicy, InvalidAddress};
 
 // ---------------------------------------------------------------------------
 // Errors
@@ -53,6 +54,10 @@ pub enum WalletError {
         /// Human-readable explanation for the freeze.
         reason: String,
     },
+
+    /// The owner address was rejected by the address policy.
+    #[error(transparent)]
+    InvalidAddress(#[from] InvalidAddress),
 }
 
 // ---------------------------------------------------------------------------
@@ -126,6 +131,13 @@ impl Wallet {
         }
     }
 
+    /// Like [`new`](Self::new), but validates `owner_address` under
+    /// `policy` first. Use this for addresses that come from user input.
+    pub fn try_new(owner_address: &str, policy: AddressPolicy) -> Result<Self, WalletError> {
+        policy.check(owner_address)?;
+        Ok(Self::new(owner_address))
+    }
+
     /// Returns the owner's NOVA address.
     pub fn owner(&self) -> &str {
         &self.owner
@@ -609,4 +621,15 @@ mod tests {
         assert_eq!(recovered.remaining_balance, 7000);
         assert_eq!(recovered.nonce, 1);
     }
+
+    #[test]
+    fn try_new_validates_owner_address() {
+        let kp = crate::crypto::keys::NovaKeypair::generate();
+        let addr = crate::identity::NovaId::from_public_key(&kp.public_key()).to_address();
+        assert!(Wallet::try_new(&addr, AddressPolicy::Strict).is_ok());
+
+        let result = Wallet::try_new("garbage", AddressPolicy::Strict);
+        assert!(matches!(result, Err(WalletError::InvalidAddress(_))));
+        assert!(Wallet::try_new(TEST_OWNER, AddressPolicy::Permissive).is_ok());
+    }
 }
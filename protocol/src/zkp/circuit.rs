@@ -34,7 +34,18 @@
 //! constraint system implies `0 <= delta < 2^64` in the integers, which
 //! in turn implies `balance >= required_amount` (assuming both fit in u64).
 //!
-//! Total constraint count: ~2 (commitment) + 64 (boolean) + 1 (sum) = ~67.
+//! ### Freshness binding
+//!
+//! A [`ProofContext`] — the receiver's challenge nonce and a recent block
+//! hash — enters as two more public inputs. They take no part in the
+//! statement; each is squared once so it owns a constraint and the
+//! verifier's input commitment cannot be malleated. A proof therefore
+//! verifies only for the exact context it was generated under, which is
+//! what stops an old proof from being replayed against a new challenge.
+//! Proofs with no freshness requirement use [`ProofContext::UNBOUND`].
+//!
+//! Total constraint count: ~2 (commitment) + 64 (boolean) + 1 (sum) +
+//! 2 (context) = ~69.
 //!
 //! ## Public inputs (in order)
 //!
@@ -42,6 +53,8 @@
 //! |-------|-------|
 //! | 0     | scalar commitment `c` (Fr element) |
 //! | 1     | Fr::from(required_amount) |
+//! | 2     | challenge nonce, reduced mod r |
+//! | 3     | anchor block hash, reduced mod r |
 
 use ark_bn254::Fr;
 use ark_ff::PrimeField;
@@ -56,6 +69,49 @@ use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisE
 use super::commitment::PedersenParams;
 use super::RANGE_BITS;
 
+// ---------------------------------------------------------------------------
+// Proof context
+// ---------------------------------------------------------------------------
+
+/// The freshness binding of a proof: who asked for it and when.
+///
+/// Both values are 32 bytes reduced into the scalar field, which loses
+/// about two bits — far too few for a prover to steer a stale proof onto a
+/// fresh challenge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofContext {
+    /// Random nonce issued by the verifying party.
+    pub challenge: [u8; 32],
+    /// Hash of a recent block the proof is anchored to.
+    pub anchor_block: [u8; 32],
+}
+
+impl ProofContext {
+    /// No freshness binding (confidential transfers, tests).
+    pub const UNBOUND: Self = Self {
+        challenge: [0u8; 32],
+        anchor_block: [0u8; 32],
+    };
+
+    /// A context binding `challenge` and `anchor_block`.
+    pub fn new(challenge: [u8; 32], anchor_block: [u8; 32]) -> Self {
+        Self {
+            challenge,
+            anchor_block,
+        }
+    }
+
+    /// The challenge as a field element.
+    pub fn challenge_scalar(&self) -> Fr {
+        Fr::from_le_bytes_mod_order(&self.challenge)
+    }
+
+    /// The anchor block hash as a field element.
+    pub fn anchor_scalar(&self) -> Fr {
+        Fr::from_le_bytes_mod_order(&self.anchor_block)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Circuit definition
 // ---------------------------------------------------------------------------
@@ -84,10 +140,15 @@ pub struct BalanceProofCircuit {
     pub commitment_scalar: Option<Fr>,
     /// Minimum amount the balance must cover.
     pub required_amount: Option<Fr>,
+    /// Verifier-issued challenge (see [`ProofContext`]).
+    pub challenge: Option<Fr>,
+    /// Recent block hash the proof is anchored to.
+    pub anchor_block: Option<Fr>,
 }
 
 impl BalanceProofCircuit {
-    /// Construct a fully-populated circuit for proof generation.
+    /// Construct a fully-populated circuit for proof generation, with no
+    /// freshness binding (see [`BalanceProofCircuit::with_context`]).
     pub fn new(
         params: &PedersenParams,
         balance: u64,
@@ -102,9 +163,18 @@ impl BalanceProofCircuit {
             blinding: Some(blinding),
             commitment_scalar: Some(commitment.scalar),
             required_amount: Some(Fr::from(required_amount)),
+            challenge: Some(ProofContext::UNBOUND.challenge_scalar()),
+            anchor_block: Some(ProofContext::UNBOUND.anchor_scalar()),
         }
     }
 
+    /// Binds the proof to `context`.
+    pub fn with_context(mut self, context: &ProofContext) -> Self {
+        self.challenge = Some(context.challenge_scalar());
+        self.anchor_block = Some(context.anchor_scalar());
+        self
+    }
+
     /// Construct a blank circuit (for CRS generation). The constraint
     /// topology is identical — only the witness slots are empty.
     pub fn blank(params: &PedersenParams) -> Self {
@@ -115,6 +185,8 @@ impl BalanceProofCircuit {
             blinding: None,
             commitment_scalar: None,
             required_amount: None,
+            challenge: None,
+            anchor_block: None,
         }
     }
 }
@@ -145,6 +217,15 @@ impl ConstraintSynthesizer<Fr> for BalanceProofCircuit {
                     .ok_or(SynthesisError::AssignmentMissing)
             })?;
 
+        // Freshness binding — public so the verifier supplies its own
+        // challenge and anchor instead of trusting the prover's.
+        let challenge_var = FpVar::<Fr>::new_input(ark_relations::ns!(cs, "challenge"), || {
+            self.challenge.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let anchor_var = FpVar::<Fr>::new_input(ark_relations::ns!(cs, "anchor_block"), || {
+            self.anchor_block.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
         // ===================================================================
         // 2. Allocate private witnesses
         // ===================================================================
@@ -208,6 +289,13 @@ impl ConstraintSynthesizer<Fr> for BalanceProofCircuit {
         //   (b) delta < 2^64 (only 64 bits are used)
         reconstructed.enforce_equal(&delta_var)?;
 
+        // ===================================================================
+        // 6. Context binding: give each context input a constraint
+        // ===================================================================
+
+        let _ = challenge_var.square()?;
+        let _ = anchor_var.square()?;
+
         Ok(())
     }
 }
@@ -238,8 +326,17 @@ fn delta_to_bits(balance: Option<Fr>, amount: Option<Fr>) -> Option<Vec<bool>> {
 ///
 /// The ordering MUST match `generate_constraints` — the first `new_input`
 /// allocation becomes public_inputs[0], the second becomes [1], etc.
-pub fn public_inputs(commitment: &super::commitment::Commitment, required_amount: u64) -> Vec<Fr> {
-    vec![commitment.scalar, Fr::from(required_amount)]
+pub fn public_inputs(
+    commitment: &super::commitment::Commitment,
+    required_amount: u64,
+    context: &ProofContext,
+) -> Vec<Fr> {
+    vec![
+        commitment.scalar,
+        Fr::from(required_amount),
+        context.challenge_scalar(),
+        context.anchor_scalar(),
+    ]
 }
 
 // ---------------------------------------------------------------------------
@@ -367,9 +464,11 @@ mod tests {
         let amount = 100u64;
 
         // Verify that public_inputs() produces the correct values matching
-        // the circuit's new_input allocation order: [commitment_scalar, required_amount].
-        let inputs = public_inputs(&c, amount);
-        assert_eq!(inputs.len(), 2, "circuit expects exactly 2 public inputs");
+        // the circuit's new_input allocation order:
+        // [commitment_scalar, required_amount, challenge, anchor_block].
+        let context = ProofContext::new([7u8; 32], [9u8; 32]);
+        let inputs = public_inputs(&c, amount, &context);
+        assert_eq!(inputs.len(), 4, "circuit expects exactly 4 public inputs");
         assert_eq!(
             inputs[0], c.scalar,
             "first public input must be the commitment scalar"
@@ -379,9 +478,12 @@ mod tests {
             Fr::from(amount),
             "second public input must be the required amount"
         );
+        assert_eq!(inputs[2], context.challenge_scalar());
+        assert_eq!(inputs[3], context.anchor_scalar());
 
         // Also verify the circuit is satisfied with these inputs.
-        let circuit = BalanceProofCircuit::new(&params, balance, blinding, &c, amount);
+        let circuit =
+            BalanceProofCircuit::new(&params, balance, blinding, &c, amount).with_context(&context);
         let cs = ConstraintSystem::<Fr>::new_ref();
         circuit.generate_constraints(cs.clone()).unwrap();
         assert!(cs.is_satisfied().unwrap(), "circuit must be satisfied");
@@ -401,7 +503,7 @@ mod tests {
         circuit.generate_constraints(cs.clone()).unwrap();
 
         let n = cs.num_constraints();
-        // Expected: ~66-70 constraints (depends on arkworks internals).
+        // Expected: ~68-72 constraints (depends on arkworks internals).
         // We assert a sane upper bound to catch regressions.
         assert!(n > 50, "too few constraints ({}), something is wrong", n);
         assert!(n < 200, "too many constraints ({}), circuit bloat", n);
@@ -33,7 +33,7 @@ use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::UniformRand;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::rand::Rng;
-use std::ops::Mul;
+use std::ops::{Add, Mul, Sub};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -163,6 +163,41 @@ impl Commitment {
     pub fn from_bytes(data: &[u8]) -> Result<Self, ark_serialize::SerializationError> {
         Self::deserialize_compressed(data)
     }
+
+    /// The commitment to zero with a zero blinding factor — the identity
+    /// for [`Add`] and [`Sub`].
+    pub fn zero() -> Self {
+        Self {
+            point: G1Affine::zero(),
+            scalar: Fr::from(0u64),
+        }
+    }
+}
+
+/// Commitments are additively homomorphic: `commit(a, r) + commit(b, s)`
+/// equals `commit(a + b, r + s)` in both forms.
+impl Add for &Commitment {
+    type Output = Commitment;
+
+    fn add(self, other: &Commitment) -> Commitment {
+        Commitment {
+            point: (self.point.into_group() + other.point).into_affine(),
+            scalar: self.scalar + other.scalar,
+        }
+    }
+}
+
+/// `commit(a, r) - commit(b, s)` equals `commit(a - b, r - s)`, where
+/// `a - b` is taken in the field: only a range proof shows it did not wrap.
+impl Sub for &Commitment {
+    type Output = Commitment;
+
+    fn sub(self, other: &Commitment) -> Commitment {
+        Commitment {
+            point: (self.point.into_group() - other.point).into_affine(),
+            scalar: self.scalar - other.scalar,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -243,6 +278,19 @@ mod tests {
         assert!(!verify_commitment(&params, &c, 42, r2));
     }
 
+    #[test]
+    fn commitments_are_additively_homomorphic() {
+        let mut rng = test_rng();
+        let params = PedersenParams::setup(&mut rng);
+        let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let c1 = commit(&params, 700, r1);
+        let c2 = commit(&params, 300, r2);
+
+        assert_eq!(&c1 + &c2, commit(&params, 1_000, r1 + r2));
+        assert_eq!(&c1 - &c2, commit(&params, 400, r1 - r2));
+        assert_eq!(&c1 + &Commitment::zero(), c1);
+    }
+
     #[test]
     fn params_serialization_round_trip() {
         let mut rng = test_rng();
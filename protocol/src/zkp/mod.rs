@@ -22,6 +22,8 @@
 //! - **Soundness**: Groth16 knowledge-soundness in the generic group model.
 //! - **Range check**: bit-decomposition to 64 bits with boolean enforcement
 //!   on every limb — no overflow, no wrap-around.
+//! - **Freshness**: proofs can be bound to a verifier's challenge and a
+//!   recent block hash ([`ProofContext`]) so they cannot be replayed.
 //!
 //! The trusted setup is per-circuit. In production, replace the local
 //! ceremony with an MPC-generated SRS (see `prover::BalanceProver::setup`).
@@ -32,7 +34,7 @@ pub mod prover;
 pub mod verifier;
 
 // Re-export the public API so callers can do `use nova_protocol::zkp::*`.
-pub use circuit::BalanceProofCircuit;
+pub use circuit::{BalanceProofCircuit, ProofContext};
 pub use commitment::{Commitment, PedersenParams};
 pub use prover::{BalanceProof, BalanceProver};
 pub use verifier::BalanceVerifier;
@@ -21,7 +21,7 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 use ark_std::rand::{CryptoRng, Rng};
 
-use super::circuit::BalanceProofCircuit;
+use super::circuit::{BalanceProofCircuit, ProofContext};
 use super::commitment::{Commitment, PedersenParams};
 use super::verifier::BalanceVerifier;
 
@@ -96,9 +96,31 @@ impl BalanceProver {
         required_amount: u64,
         params: &PedersenParams,
         commitment: &Commitment,
+    ) -> Result<BalanceProof> {
+        self.prove_with_context(
+            balance,
+            blinding,
+            required_amount,
+            params,
+            commitment,
+            &ProofContext::UNBOUND,
+        )
+    }
+
+    /// Like [`BalanceProver::prove`], but the proof verifies only under
+    /// `context` (see [`ProofContext`]).
+    pub fn prove_with_context(
+        &self,
+        balance: u64,
+        blinding: Fr,
+        required_amount: u64,
+        params: &PedersenParams,
+        commitment: &Commitment,
+        context: &ProofContext,
     ) -> Result<BalanceProof> {
         let circuit =
-            BalanceProofCircuit::new(params, balance, blinding, commitment, required_amount);
+            BalanceProofCircuit::new(params, balance, blinding, commitment, required_amount)
+                .with_context(context);
 
         let mut rng = ark_std::rand::thread_rng();
 
@@ -15,7 +15,7 @@ use ark_groth16::{Groth16, VerifyingKey};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 
-use super::circuit;
+use super::circuit::{self, ProofContext};
 use super::commitment::{Commitment, PedersenParams};
 use super::prover::BalanceProof;
 
@@ -59,17 +59,36 @@ impl BalanceVerifier {
     /// `Ok(true)` if the proof verifies, `Ok(false)` if it does not, or
     /// `Err(...)` if deserialization or the verification algorithm itself fails.
     pub fn verify(
+        &self,
+        proof: &BalanceProof,
+        commitment: &Commitment,
+        required_amount: u64,
+        params: &PedersenParams,
+    ) -> Result<bool> {
+        self.verify_with_context(
+            proof,
+            commitment,
+            required_amount,
+            params,
+            &ProofContext::UNBOUND,
+        )
+    }
+
+    /// Like [`BalanceVerifier::verify`], but for a proof bound to `context`.
+    /// A proof generated under any other context does not verify.
+    pub fn verify_with_context(
         &self,
         proof: &BalanceProof,
         commitment: &Commitment,
         required_amount: u64,
         _params: &PedersenParams,
+        context: &ProofContext,
     ) -> Result<bool> {
         let ark_proof = proof
             .to_ark_proof()
             .context("failed to deserialize proof")?;
 
-        let public_inputs = circuit::public_inputs(commitment, required_amount);
+        let public_inputs = circuit::public_inputs(commitment, required_amount, context);
 
         let valid = Groth16::<Bn254>::verify(&self.vk, &public_inputs, &ark_proof)
             .context("Groth16 verification algorithm failed")?;
@@ -195,4 +214,30 @@ mod tests {
         let ok = restored.verify(&proof, &c, 50, &params).unwrap();
         assert!(ok, "restored VK must verify valid proofs");
     }
+
+    #[test]
+    fn reject_proof_under_another_context() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let (prover, verifier) = BalanceProver::setup(&mut rng);
+        let params = prover.pedersen_params();
+
+        let blinding = Fr::rand(&mut rng);
+        let c = commitment::commit(params, 1000, blinding);
+        let context = ProofContext::new([1u8; 32], [2u8; 32]);
+
+        let proof = prover
+            .prove_with_context(1000, blinding, 200, params, &c, &context)
+            .unwrap();
+        assert!(verifier
+            .verify_with_context(&proof, &c, 200, params, &context)
+            .unwrap());
+
+        let new_challenge = ProofContext::new([3u8; 32], [2u8; 32]);
+        let new_anchor = ProofContext::new([1u8; 32], [4u8; 32]);
+        for other in [new_challenge, new_anchor, ProofContext::UNBOUND] {
+            assert!(!verifier
+                .verify_with_context(&proof, &c, 200, params, &other)
+                .unwrap());
+        }
+    }
 }
@@ -0,0 +1,539 @@
+//! Byzantine fault simulation for the consensus engine.
+//!
+//! Runs a handful of in-process validators against each other over a
+//! simulated network and lets some of them misbehave: equivocate, withhold
+//! votes, propose garbage, or deliver their messages late. The assertions
+//! are the two properties BFT consensus promises:
+//!
+//! - **Safety** — honest validators never commit different blocks at the
+//!   same height, no matter what the faulty ones do.
+//! - **Liveness** — while faulty stake stays below one third, honest
+//!   validators keep committing. Past that threshold, halting is allowed;
+//!   forking still is not.
+//!
+//! The network is synchronous within a round for honest traffic, which keeps
+//! the runs deterministic enough to assert on. Each round is: the proposer
+//! broadcasts, everyone reacts until the queue drains, then validators that
+//! did not commit time out and advance.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use nova_protocol::crypto::keys::NovaKeypair;
+use nova_protocol::crypto::wire::BlockHash;
+use nova_protocol::identity::NovaId;
+use nova_protocol::network::consensus::{
+    ConsensusConfig, ConsensusEngine, Proposal, ValidatorSet, Vote, VoteKind,
+};
+use nova_protocol::storage::block::Block;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+// ---------------------------------------------------------------------------
+// Simulation
+// ---------------------------------------------------------------------------
+
+/// How a simulated validator behaves on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Behavior {
+    /// Follows the protocol.
+    Honest,
+    /// Sends conflicting proposals and votes to different halves of the network.
+    Equivocate,
+    /// Never sends anything.
+    Withhold,
+    /// Proposes blocks that do not extend the chain; votes honestly.
+    InvalidProposals,
+    /// Every message arrives this many rounds late.
+    Delay(u64),
+}
+
+#[derive(Debug, Clone)]
+enum Msg {
+    Proposal(Proposal),
+    Prevote(Vote),
+    Precommit(Vote),
+    /// A block with the precommits that finalized it, for validators that
+    /// missed some of the precommits.
+    Commit(Block, Vec<Vote>),
+}
+
+struct Envelope {
+    deliver_at: u64,
+    to: usize,
+    msg: Msg,
+}
+
+struct SimNode {
+    keypair: NovaKeypair,
+    address: String,
+    behavior: Behavior,
+    engine: ConsensusEngine,
+    chain: Vec<Block>,
+    /// Candidate blocks seen at the current height, by hash.
+    blocks: HashMap<BlockHash, Block>,
+    /// Precommits at the current height, by (round, block hash).
+    precommits: HashMap<(u64, BlockHash), Vec<Vote>>,
+    /// (round, validator) pairs we already counted a precommit from.
+    precommitters: HashSet<(u64, String)>,
+    /// Rounds in which we already precommitted.
+    precommitted: HashSet<u64>,
+}
+
+impl SimNode {
+    fn height(&self) -> u64 {
+        self.chain.last().unwrap().header.height
+    }
+
+    fn tip(&self) -> &Block {
+        self.chain.last().unwrap()
+    }
+
+    fn is_honest(&self) -> bool {
+        self.behavior == Behavior::Honest
+    }
+}
+
+struct Network {
+    nodes: Vec<SimNode>,
+    set: ValidatorSet,
+    queue: VecDeque<Envelope>,
+    round: u64,
+}
+
+impl Network {
+    /// Builds a network from (behavior, stake) pairs.
+    fn new(spec: &[(Behavior, u64)]) -> Self {
+        let keypairs: Vec<NovaKeypair> = spec.iter().map(|_| NovaKeypair::generate()).collect();
+        let mut set = ValidatorSet::new();
+        for (kp, (_, stake)) in keypairs.iter().zip(spec) {
+            set.add_validator(kp.public_key().to_hex(), *stake);
+        }
+
+        let genesis = Block::genesis();
+        let nodes = keypairs
+            .into_iter()
+            .zip(spec)
+            .map(|(keypair, (behavior, _))| {
+                let config = ConsensusConfig {
+                    min_validators: 1,
+                    ..ConsensusConfig::default()
+                };
+                let mut engine = ConsensusEngine::new(config, set.clone());
+                engine.set_chain_state(1, genesis.header.hash);
+                SimNode {
+                    address: keypair.public_key().to_hex(),
+                    keypair,
+                    behavior: *behavior,
+                    engine,
+                    chain: vec![genesis.clone()],
+                    blocks: HashMap::new(),
+                    precommits: HashMap::new(),
+                    precommitters: HashSet::new(),
+                    precommitted: HashSet::new(),
+                }
+            })
+            .collect();
+
+        Self {
+            nodes,
+            set,
+            queue: VecDeque::new(),
+            round: 0,
+        }
+    }
+
+    fn run(&mut self, rounds: u64) {
+        for _ in 0..rounds {
+            self.step();
+        }
+    }
+
+    /// One consensus round: propose, drain deliverable messages, time out.
+    fn step(&mut self) {
+        let round = self.round;
+        let proposer = self.set.proposer_for_round(round).unwrap().address.clone();
+        let idx = self
+            .nodes
+            .iter()
+            .position(|n| n.address == proposer)
+            .unwrap();
+        self.propose(idx);
+
+        while let Some(pos) = self.queue.iter().position(|e| e.deliver_at <= round) {
+            let env = self.queue.remove(pos).unwrap();
+            self.deliver(env.to, env.msg);
+        }
+
+        for node in &mut self.nodes {
+            if node.engine.current_round() == round {
+                node.engine.advance_round();
+            }
+        }
+        self.round += 1;
+    }
+
+    fn propose(&mut self, idx: usize) {
+        let node = &self.nodes[idx];
+        if node.engine.current_round() != self.round {
+            return;
+        }
+
+        // A locked proposer must re-propose its locked block; if it never
+        // saw that block it has nothing valid to offer and sits the round out.
+        let block = match node.engine.locked() {
+            Some(lock) => match node.blocks.get(&lock.block_hash) {
+                Some(block) => block.clone(),
+                None => return,
+            },
+            None => node
+                .engine
+                .propose_block(vec![], &node.keypair)
+                .expect("scheduled proposer can build a block"),
+        };
+        let proposal = node
+            .engine
+            .create_proposal(block, &node.keypair)
+            .expect("proposal for locked or fresh block");
+        self.send(idx, Msg::Proposal(proposal));
+    }
+
+    /// Broadcasts `msg` from `from`, filtered through its behavior.
+    fn send(&mut self, from: usize, msg: Msg) {
+        let n = self.nodes.len();
+        let node = &self.nodes[from];
+        let mut deliver_at = self.round;
+
+        let per_recipient: Vec<Msg> = match node.behavior {
+            Behavior::Honest => vec![msg; n],
+            Behavior::Withhold => return,
+            Behavior::Delay(k) => {
+                deliver_at += k;
+                vec![msg; n]
+            }
+            Behavior::InvalidProposals => match msg {
+                Msg::Proposal(p) => {
+                    // Unsigned, and its parent is not our tip.
+                    let mut bogus =
+                        Block::new(&Block::genesis(), vec![], node.address.clone(), [0xBA; 32]);
+                    bogus.header.parent_hash = BlockHash([0xFF; 32]);
+                    let bad = Proposal::new(&node.keypair, bogus, p.round, None);
+                    vec![Msg::Proposal(bad); n]
+                }
+                other => vec![other; n],
+            },
+            Behavior::Equivocate => {
+                let twin = match &msg {
+                    Msg::Proposal(p) => {
+                        let alt = node
+                            .engine
+                            .propose_block(vec![filler_tx()], &node.keypair)
+                            .unwrap_or_else(|_| p.block.clone());
+                        Msg::Proposal(Proposal::new(&node.keypair, alt, p.round, None))
+                    }
+                    Msg::Prevote(v) => Msg::Prevote(conflicting(&node.keypair, v)),
+                    Msg::Precommit(v) => Msg::Precommit(conflicting(&node.keypair, v)),
+                    Msg::Commit(..) => msg.clone(),
+                };
+                (0..n)
+                    .map(|i| {
+                        if i % 2 == 0 {
+                            msg.clone()
+                        } else {
+                            twin.clone()
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        for (to, msg) in per_recipient.into_iter().enumerate() {
+            self.queue.push_back(Envelope {
+                deliver_at,
+                to,
+                msg,
+            });
+        }
+    }
+
+    fn deliver(&mut self, to: usize, msg: Msg) {
+        let round = self.round;
+        let node = &mut self.nodes[to];
+        let mut outbox = Vec::new();
+
+        match msg {
+            Msg::Proposal(p) => {
+                if p.round != round || node.engine.current_round() != round {
+                    return;
+                }
+                if node.engine.validate_block(&p.block).is_err() {
+                    return;
+                }
+                node.blocks.insert(p.block_hash(), p.block.clone());
+                if node.precommitted.contains(&round) {
+                    return;
+                }
+                if let Ok(vote) = node.engine.prevote(&p, &node.keypair) {
+                    outbox.push(Msg::Prevote(vote));
+                }
+            }
+            Msg::Prevote(v) => {
+                if v.round != round || node.engine.current_round() != round {
+                    return;
+                }
+                let polka = match node.engine.record_prevote(v) {
+                    Ok(Some(lock)) if lock.round == round => Some(lock.block_hash),
+                    _ => None,
+                };
+                if let Some(hash) = polka {
+                    if node.blocks.contains_key(&hash) && node.precommitted.insert(round) {
+                        outbox.push(Msg::Precommit(Vote::precommit(&node.keypair, hash, round)));
+                    }
+                }
+            }
+            Msg::Precommit(v) => {
+                if v.round != round || node.engine.current_round() != round {
+                    return;
+                }
+                if !v.verify() || !self.set.contains(&v.validator) {
+                    return;
+                }
+                if !node.precommitters.insert((v.round, v.validator.clone())) {
+                    return;
+                }
+                let key = (v.round, v.block_hash);
+                let votes = node.precommits.entry(key).or_default();
+                votes.push(v);
+                let stake: u64 = votes.iter().map(|v| self.set.stake_of(&v.validator)).sum();
+                if self.set.has_quorum(stake, votes.len()) {
+                    if let Some(block) = node.blocks.get(&key.1).cloned() {
+                        let votes = votes.clone();
+                        if commit(node, block.clone(), votes.clone()) {
+                            outbox.push(Msg::Commit(block, votes));
+                        }
+                    }
+                }
+            }
+            Msg::Commit(block, votes) => {
+                commit(node, block, votes);
+            }
+        }
+
+        for msg in outbox {
+            self.send(to, msg);
+        }
+    }
+}
+
+/// Finalizes `block` on `node` if it extends the node's tip.
+fn commit(node: &mut SimNode, block: Block, votes: Vec<Vote>) -> bool {
+    if block.header.height != node.height() + 1
+        || block.header.parent_hash != node.tip().header.hash
+    {
+        return false;
+    }
+    match node.engine.finalize_block(block, votes) {
+        Ok(finalized) => {
+            node.chain.push(finalized.block);
+            node.blocks.clear();
+            node.precommits.clear();
+            node.precommitters.clear();
+            node.precommitted.clear();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// A vote for a different block in the same round.
+fn conflicting(keypair: &NovaKeypair, vote: &Vote) -> Vote {
+    let mut hash = vote.block_hash;
+    hash[0] ^= 0xFF;
+    match vote.kind {
+        VoteKind::Prevote => Vote::new(keypair, hash, vote.round),
+        VoteKind::Precommit => Vote::precommit(keypair, hash, vote.round),
+    }
+}
+
+/// A transaction that only exists to make an equivocating block differ.
+/// Signed, so the twin block passes validation and splits the votes.
+fn filler_tx() -> nova_protocol::transaction::Transaction {
+    let keypair = NovaKeypair::from_seed(&[0xE0; 32]);
+    let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+        .sender(&NovaId::from_public_key(&keypair.public_key()).to_address())
+        .receiver("nova1nobody")
+        .amount(Amount::new(1, Currency::NOVA))
+        .fee(100)
+        .nonce(0)
+        .build_unchecked();
+    sign_transaction(&mut tx, &keypair);
+    tx
+}
+
+// ---------------------------------------------------------------------------
+// Assertions
+// ---------------------------------------------------------------------------
+
+/// Every pair of honest chains agrees at every height both have reached.
+fn assert_no_fork(net: &Network) {
+    let honest: Vec<&SimNode> = net.nodes.iter().filter(|n| n.is_honest()).collect();
+    for a in &honest {
+        for b in &honest {
+            let common = a.chain.len().min(b.chain.len());
+            for h in 0..common {
+                assert_eq!(
+                    a.chain[h].header.hash, b.chain[h].header.hash,
+                    "honest validators forked at height {}",
+                    h
+                );
+            }
+        }
+    }
+}
+
+/// Lowest committed height across honest validators.
+fn honest_height(net: &Network) -> u64 {
+    net.nodes
+        .iter()
+        .filter(|n| n.is_honest())
+        .map(|n| n.height())
+        .min()
+        .unwrap()
+}
+
+/// Number of rounds in `0..rounds` whose proposer behaves honestly.
+fn honest_proposer_rounds(net: &Network, rounds: u64) -> u64 {
+    (0..rounds)
+        .filter(|r| {
+            let addr = &net.set.proposer_for_round(*r).unwrap().address;
+            net.nodes
+                .iter()
+                .any(|n| &n.address == addr && n.is_honest())
+        })
+        .count() as u64
+}
+
+// ---------------------------------------------------------------------------
+// 1. Baseline
+// ---------------------------------------------------------------------------
+
+#[test]
+fn honest_network_commits_every_round() {
+    let mut net = Network::new(&[(Behavior::Honest, 1_000); 4]);
+    net.run(8);
+
+    assert_no_fork(&net);
+    assert_eq!(honest_height(&net), 8);
+}
+
+// ---------------------------------------------------------------------------
+// 2. Single Faults (below one third of stake)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn equivocating_validator_cannot_fork() {
+    let mut spec = [(Behavior::Honest, 1_000); 4];
+    spec[0].0 = Behavior::Equivocate;
+    let mut net = Network::new(&spec);
+    net.run(12);
+
+    assert_no_fork(&net);
+    assert!(honest_height(&net) >= honest_proposer_rounds(&net, 12));
+}
+
+#[test]
+fn withheld_votes_slow_but_do_not_halt() {
+    let mut spec = [(Behavior::Honest, 1_000); 4];
+    spec[3].0 = Behavior::Withhold;
+    let mut net = Network::new(&spec);
+    net.run(12);
+
+    assert_no_fork(&net);
+    assert_eq!(honest_height(&net), honest_proposer_rounds(&net, 12));
+}
+
+#[test]
+fn invalid_proposals_never_commit() {
+    let mut spec = [(Behavior::Honest, 1_000); 4];
+    spec[1].0 = Behavior::InvalidProposals;
+    let mut net = Network::new(&spec);
+    net.run(12);
+
+    assert_no_fork(&net);
+    let bad = &net.nodes[1].address;
+    for node in net.nodes.iter().filter(|n| n.is_honest()) {
+        assert!(node.chain.iter().all(|b| &b.header.validator != bad));
+    }
+    assert_eq!(honest_height(&net), honest_proposer_rounds(&net, 12));
+}
+
+#[test]
+fn delayed_messages_are_tolerated() {
+    let mut spec = [(Behavior::Honest, 1_000); 4];
+    spec[2].0 = Behavior::Delay(2);
+    let mut net = Network::new(&spec);
+    net.run(12);
+
+    assert_no_fork(&net);
+    assert!(honest_height(&net) >= honest_proposer_rounds(&net, 12));
+}
+
+// ---------------------------------------------------------------------------
+// 3. Mixed Faults and Thresholds
+// ---------------------------------------------------------------------------
+
+#[test]
+fn mixed_faults_below_one_third_keep_progressing() {
+    let mut spec = [(Behavior::Honest, 1_000); 7];
+    spec[0].0 = Behavior::Equivocate;
+    spec[4].0 = Behavior::Delay(1);
+    let mut net = Network::new(&spec);
+    net.run(21);
+
+    assert_no_fork(&net);
+    assert!(honest_height(&net) >= honest_proposer_rounds(&net, 21));
+}
+
+#[test]
+fn exactly_one_third_faulty_stake_still_progresses() {
+    // Honest validators hold exactly 2/3 of the stake — the quorum boundary.
+    let spec = [
+        (Behavior::Honest, 1_000),
+        (Behavior::Honest, 1_000),
+        (Behavior::Withhold, 1_000),
+    ];
+    let mut net = Network::new(&spec);
+    net.run(9);
+
+    assert_no_fork(&net);
+    assert_eq!(honest_height(&net), honest_proposer_rounds(&net, 9));
+}
+
+#[test]
+fn faulty_majority_halts_without_forking() {
+    let mut spec = [(Behavior::Honest, 1_000); 4];
+    spec[0].0 = Behavior::Withhold;
+    spec[1].0 = Behavior::Withhold;
+    let mut net = Network::new(&spec);
+    net.run(12);
+
+    // Honest stake is 1/2: no quorum, so no progress — but never a fork.
+    assert_no_fork(&net);
+    assert_eq!(honest_height(&net), 0);
+}
+
+#[test]
+fn stake_outweighs_headcount_under_faults() {
+    // One honest whale, three withholding minnows: 70% honest stake.
+    let spec = [
+        (Behavior::Honest, 7_000),
+        (Behavior::Withhold, 1_000),
+        (Behavior::Withhold, 1_000),
+        (Behavior::Withhold, 1_000),
+    ];
+    let mut net = Network::new(&spec);
+    net.run(8);
+
+    assert_no_fork(&net);
+    assert_eq!(honest_height(&net), honest_proposer_rounds(&net, 8));
+}
@@ -75,7 +75,7 @@ fn build_signed_transfer(
         .amount(Amount::new(amount, Currency::NOVA))
         .fee(fee)
         .nonce(nonce)
-        .build();
+        .build_unchecked();
     sign_transaction(&mut tx, sender_kp);
     tx
 }
@@ -119,7 +119,7 @@ fn full_transfer_lifecycle() {
     let t = tree.read();
     let alice_state = t.get(&alice_addr).unwrap();
     let bob_state = t.get(&bob_addr).unwrap();
-    assert_eq!(alice_state.balance, 9_500);
+    assert_eq!(alice_state.balance, 9_400); // 10000 - 500 - 100 fee
     assert_eq!(bob_state.balance, 500);
 
     // Verify block is persisted in the database.
@@ -167,7 +167,7 @@ fn multiple_transfers_single_block() {
     let t = tree.read();
     let alice = t.get(&alice_addr).unwrap();
     let bob = t.get(&bob_addr).unwrap();
-    assert_eq!(alice.balance, 9_500);
+    assert_eq!(alice.balance, 8_985); // 10000 - 5 * 100 - fees 101..=105
     assert_eq!(bob.balance, 500);
 }
 
@@ -219,9 +219,9 @@ fn chain_of_blocks() {
     let alice = t.get(&alice_addr).unwrap();
     let bob = t.get(&bob_addr).unwrap();
     let charlie = t.get(&charlie_addr).unwrap();
-    assert_eq!(alice.balance, 9_200); // 10000 - 1000 + 200
-    assert_eq!(bob.balance, 500); // 1000 - 500
-    assert_eq!(charlie.balance, 300); // 500 - 200
+    assert_eq!(alice.balance, 9_100); // 10000 - 1000 - 100 fee + 200
+    assert_eq!(bob.balance, 400); // 1000 - 500 - 100 fee
+    assert_eq!(charlie.balance, 200); // 500 - 200 - 100 fee
 
     // Verify we have 4 blocks (genesis + 3).
     let chain = db.get_block_range(0, 3).unwrap();
@@ -309,6 +309,17 @@ fn nonce_enforcement() {
         let alice = t.get(&alice_addr).unwrap();
         assert_eq!(alice.nonce, 2);
     }
+
+    // Block 3: replaying nonce 2 must not execute.
+    let replay = build_signed_transfer(&alice_kp, &alice_addr, &bob_addr, 500, 100, 2);
+    mempool.add(replay).unwrap();
+    let p3 = producer.produce_block(&p2.block, 100).unwrap();
+    assert!(p3.block.transactions.is_empty());
+    assert!(p3.tx_results.iter().all(|r| !r.success));
+
+    let t = tree.read();
+    assert_eq!(t.get(&alice_addr).unwrap().balance, 9_600);
+    assert_eq!(t.get(&bob_addr).unwrap().balance, 200);
 }
 
 // ---------------------------------------------------------------------------
@@ -452,10 +463,10 @@ fn concurrent_mempool_and_production() {
         let tx = TransactionBuilder::new(TransactionType::Transfer)
             .sender("nova1alice_concurrent")
             .receiver("nova1bob_concurrent")
-            .amount(Amount::new(10, Currency::NOVA))
+            .amount(Amount::new(100, Currency::NOVA))
             .fee((i + 1) * 100)
-            .nonce(i)
-            .build();
+            .nonce(i + 1)
+            .build_unchecked();
         mempool.add(tx).unwrap();
     }
 
@@ -468,8 +479,8 @@ fn concurrent_mempool_and_production() {
                 .receiver("nova1receiver_concurrent")
                 .amount(Amount::new(1, Currency::NOVA))
                 .fee(50)
-                .nonce(i)
-                .build();
+                .nonce(1)
+                .build_unchecked();
             let _ = mempool_clone.add(tx);
         }
     });
@@ -500,7 +511,7 @@ fn genesis_block_in_db() {
     assert_eq!(retrieved.header.height, 0);
     assert_eq!(retrieved.header.parent_hash, [0u8; 32]);
     assert!(retrieved.transactions.is_empty());
-    assert!(retrieved.header.signature.is_empty());
+    assert!(retrieved.header.signature.is_none());
     assert_eq!(retrieved.header.hash, genesis.header.hash);
 
     // Genesis should pass structural verification.
@@ -536,7 +547,7 @@ fn state_tree_merkle_proof_after_transfer() {
     // Get the current state and generate a Merkle proof.
     let t = tree.read();
     let alice_state = t.get(&alice_addr).expect("alice should exist");
-    assert_eq!(alice_state.balance, 7_000);
+    assert_eq!(alice_state.balance, 6_900);
 
     let proof = t.get_proof(&alice_addr);
     assert_eq!(proof.siblings.len(), 256);
@@ -583,7 +594,7 @@ fn multiple_currencies_if_supported() {
         .amount(Amount::new(5_000, Currency::USD))
         .fee(100)
         .nonce(2)
-        .build();
+        .build_unchecked();
     sign_transaction(&mut tx_usd, &alice_kp);
     assert!(verify_transaction(&tx_usd).is_ok());
     assert_eq!(tx_usd.amount.currency, Currency::USD);
@@ -595,7 +606,7 @@ fn multiple_currencies_if_supported() {
         .amount(Amount::new(100, Currency::Custom("DOGE".to_string())))
         .fee(100)
         .nonce(3)
-        .build();
+        .build_unchecked();
     sign_transaction(&mut tx_custom, &alice_kp);
     assert!(verify_transaction(&tx_custom).is_ok());
     assert_eq!(
@@ -654,7 +665,7 @@ fn large_block_stress_test() {
     // Verify each sender was debited.
     for addr in &addresses {
         let state = t.get(addr).unwrap();
-        assert_eq!(state.balance, 9_900); // 10_000 - 100
+        assert_eq!(state.balance, 9_850); // 10_000 - 100 - 50 fee
     }
 }
 
@@ -722,7 +733,8 @@ fn db_persistence_survives_reopen() {
 #[test]
 fn non_transfer_transaction_types_accepted() {
     // CreditRequest, CreditSettlement, TokenMint, TokenBurn are accepted
-    // by the block producer as no-ops (no state change, but included in block).
+    // by the block producer as no-ops: they move no value, but like every
+    // transaction they consume the nonce and pay their fee.
     let (producer, genesis, tree, mempool, db, _) = setup();
     db.put_block(&genesis).unwrap();
 
@@ -733,13 +745,18 @@ fn non_transfer_transaction_types_accepted() {
         .receiver("nova1credit_receiver")
         .amount(Amount::new(1_000, Currency::NOVA))
         .fee(100)
-        .nonce(0)
-        .build();
+        .nonce(1)
+        .build_unchecked();
     mempool.add(tx).unwrap();
 
     let produced = producer.produce_block(&genesis, 100).unwrap();
     assert_eq!(produced.block.transactions.len(), 1);
     assert!(produced.tx_results.iter().all(|r| r.success));
+    assert_eq!(produced.block.header.fees, 100);
+
+    let t = tree.read();
+    assert_eq!(t.get("nova1credit_sender").unwrap().balance, 49_900);
+    assert!(t.get("nova1credit_receiver").is_none());
 }
 
 // ---------------------------------------------------------------------------
@@ -876,7 +893,7 @@ fn full_pipeline_identity_through_state_proof() {
     // Step 5: State tree verification with Merkle proof.
     let t = tree.read();
     let alice_state = t.get(&alice_addr).unwrap();
-    assert_eq!(alice_state.balance, 35_000);
+    assert_eq!(alice_state.balance, 34_800);
 
     let bob_state = t.get(&bob_addr).unwrap();
     assert_eq!(bob_state.balance, 15_000);
@@ -66,8 +66,14 @@ def signable_bytes(tx: Transaction) -> bytes:
         payload        — if present: 0x01 + 4-byte LE u32 length + raw bytes
                          if absent:  0x00
 
+    Version 2 and later use the length-prefixed layout of
+    :func:`canonical_v2_bytes` instead.
+
     Fields excluded: id, signature, sender_public_key, zkp_proof.
     """
+    if tx.version >= 2:
+        return canonical_v2_bytes(tx)
+
     buf = bytearray()
 
     # Protocol version (2 bytes, LE u16).
@@ -112,6 +118,47 @@ def signable_bytes(tx: Transaction) -> bytes:
     return bytes(buf)
 
 
+def _length_prefixed(data: bytes) -> bytes:
+    return struct.pack("<I", len(data)) + data
+
+
+def canonical_v2_bytes(tx: Transaction) -> bytes:
+    """Version 2 canonical encoding — matches the Rust
+    ``transaction::canonical`` module. Every string is length-prefixed, so
+    no field can run into the next.
+
+    Layout::
+
+        magic          — the 6 ASCII bytes "NOVATX"
+        version        — 2 bytes, little-endian u16
+        tx_type        — 4-byte LE u32 length + UTF-8 PascalCase string
+        sender         — 4-byte LE u32 length + UTF-8 address string
+        receiver       — 4-byte LE u32 length + UTF-8 address string
+        amount.value   — 8 bytes, little-endian u64
+        amount.currency— 4-byte LE u32 length + UTF-8 string
+        fee            — 8 bytes, little-endian u64
+        nonce          — 8 bytes, little-endian u64
+        timestamp      — 8 bytes, little-endian u64
+        payload        — if present: 0x01 + 4-byte LE u32 length + raw bytes
+                         if absent:  nothing
+    """
+    buf = bytearray(b"NOVATX")
+    buf += struct.pack("<H", tx.version)
+    buf += _length_prefixed(_TX_TYPE_WIRE[tx.tx_type].encode("utf-8"))
+    buf += _length_prefixed(str(tx.sender).encode("utf-8"))
+    buf += _length_prefixed(str(tx.receiver).encode("utf-8"))
+    buf += struct.pack("<Q", tx.amount.value)
+    buf += _length_prefixed(tx.amount.currency.encode("utf-8"))
+    buf += struct.pack("<QQQ", tx.fee, tx.nonce, tx.timestamp)
+
+    # Extensions: tag + length-prefixed value, only when set.
+    if tx.payload:
+        buf += b"\x01"
+        buf += _length_prefixed(tx.payload)
+
+    return bytes(buf)
+
+
 def compute_transaction_id(tx: Transaction) -> str:
     """Compute the canonical transaction ID: ``hex(double_sha256(signable_bytes))``.
 
@@ -847,6 +847,32 @@ class TestCrossLanguageVector:
         print(f"signable_bytes_hex: {canonical_hex}")
         print(f"tx_id: {tx_id}")
 
+    def test_version_2_vector(self) -> None:
+        from nova_sdk.types import Transaction as TxModel
+
+        tx = TxModel.model_construct(
+            version=2,
+            tx_type=TransactionType.TRANSFER,
+            sender="nova1sender_test_vector",
+            receiver="nova1receiver_test_vector",
+            amount=Amount(value=1_000_000, currency="NOVA"),
+            fee=100,
+            nonce=42,
+            timestamp=1_700_000_000_000,
+            payload=b"",
+        )
+
+        # Pinned in the Rust transaction::canonical golden_vector_plain_transfer test.
+        assert signable_bytes(tx).hex() == (
+            "4e4f564154580200080000005472616e73666572170000006e6f76613173656e6465"
+            "725f746573745f766563746f72190000006e6f76613172656365697665725f746573"
+            "745f766563746f7240420f0000000000040000004e4f564164000000000000002a00"
+            "0000000000000068e5cf8b010000"
+        )
+        assert compute_transaction_id(tx) == (
+            "456ba57f8572b1655bb34dce62ceccb38736aa331d0f6d0218b93683bf0988b1"
+        )
+
     def test_signing_roundtrip_with_deterministic_keypair(self) -> None:
         """Verify sign + verify round-trips with real bech32 addresses."""
         sender_seed = b"\x01" + b"\x00" * 31
@@ -3,8 +3,8 @@
  *
  * Wire format is aligned with the Rust protocol crate so that transactions
  * built in any SDK can be verified by the Rust validator node. The canonical
- * binary serialization (signable bytes) uses little-endian integers and null-
- * byte separators — see {@link signableBytes} for the exact layout.
+ * binary serialization (signable bytes) is selected by the transaction
+ * version — see {@link signableBytes} for the exact layouts.
  */
 
 import { sha256 } from '@noble/hashes/sha256';
@@ -61,9 +61,16 @@ const TX_TYPE_WIRE: Record<TransactionType, string> = {
  *   payload        — if present: 0x01 + 4-byte LE u32 length + raw bytes
  *                    if absent:  0x00
  *
+ * Version 2 and later use the length-prefixed layout of
+ * {@link canonicalV2Bytes} instead.
+ *
  * Fields excluded: id, signature, sender_public_key, zkp_proof.
  */
 export function signableBytes(tx: Omit<Transaction, 'id'>): Uint8Array {
+  if (tx.version >= 2) {
+    return canonicalV2Bytes(tx);
+  }
+
   const encoder = new TextEncoder();
   const parts: Uint8Array[] = [];
 
@@ -119,7 +126,68 @@ export function signableBytes(tx: Omit<Transaction, 'id'>): Uint8Array {
     parts.push(new Uint8Array([0x00]));
   }
 
-  // Concatenate all segments into a single buffer.
+  return concat(parts);
+}
+
+/**
+ * Version 2 canonical encoding — matches the Rust `transaction::canonical`
+ * module. Every string is length-prefixed, so no field can run into the
+ * next.
+ *
+ * Layout:
+ *   magic          — the 6 ASCII bytes "NOVATX"
+ *   version        — 2 bytes, little-endian u16
+ *   tx_type        — 4-byte LE u32 length + UTF-8 PascalCase string
+ *   sender         — 4-byte LE u32 length + UTF-8 address string
+ *   receiver       — 4-byte LE u32 length + UTF-8 address string
+ *   amount.value   — 8 bytes, little-endian u64
+ *   amount.currency— 4-byte LE u32 length + UTF-8 string
+ *   fee            — 8 bytes, little-endian u64
+ *   nonce          — 8 bytes, little-endian u64
+ *   timestamp      — 8 bytes, little-endian u64
+ *   payload        — if present: 0x01 + 4-byte LE u32 length + raw bytes
+ *                    if absent:  nothing
+ */
+export function canonicalV2Bytes(tx: Omit<Transaction, 'id'>): Uint8Array {
+  const encoder = new TextEncoder();
+  const parts: Uint8Array[] = [encoder.encode('NOVATX')];
+
+  const versionBuf = new Uint8Array(2);
+  new DataView(versionBuf.buffer).setUint16(0, tx.version, true);
+  parts.push(versionBuf);
+
+  parts.push(...lengthPrefixed(encoder.encode(TX_TYPE_WIRE[tx.type])));
+  parts.push(...lengthPrefixed(encoder.encode(tx.sender)));
+  parts.push(...lengthPrefixed(encoder.encode(tx.receiver)));
+  parts.push(u64(BigInt(tx.amount.value)));
+  parts.push(...lengthPrefixed(encoder.encode(tx.amount.currency)));
+  parts.push(u64(BigInt(tx.fee)));
+  parts.push(u64(BigInt(tx.nonce)));
+  parts.push(u64(BigInt(tx.timestamp)));
+
+  // Extensions: tag + length-prefixed value, only when set.
+  if (tx.payload.length > 0) {
+    parts.push(new Uint8Array([0x01]));
+    parts.push(...lengthPrefixed(tx.payload));
+  }
+
+  return concat(parts);
+}
+
+function u64(value: bigint): Uint8Array {
+  const buf = new Uint8Array(8);
+  new DataView(buf.buffer).setBigUint64(0, value, true);
+  return buf;
+}
+
+function lengthPrefixed(bytes: Uint8Array): Uint8Array[] {
+  const lenBuf = new Uint8Array(4);
+  new DataView(lenBuf.buffer).setUint32(0, bytes.length, true);
+  return [lenBuf, bytes];
+}
+
+/** Concatenate all segments into a single buffer. */
+function concat(parts: Uint8Array[]): Uint8Array {
   const totalLength = parts.reduce((sum, p) => sum + p.length, 0);
   const buf = new Uint8Array(totalLength);
   let offset = 0;
@@ -127,7 +195,6 @@ export function signableBytes(tx: Omit<Transaction, 'id'>): Uint8Array {
     buf.set(part, offset);
     offset += part.length;
   }
-
   return buf;
 }
 
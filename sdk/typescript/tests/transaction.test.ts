@@ -443,6 +443,29 @@ describe('cross-language test vector', () => {
     console.log('tx_id:', tx.id);
   });
 
+  it('matches the Rust version 2 golden vector', () => {
+    const tx = new TransactionBuilder()
+      .version(2)
+      .type('transfer')
+      .sender('nova1sender_test_vector' as NovaId)
+      .receiver('nova1receiver_test_vector' as NovaId)
+      .amount(1_000_000n, 'NOVA')
+      .fee(100n)
+      .nonce(42)
+      .timestamp(1_700_000_000_000)
+      .build();
+
+    const { id: _id, ...body } = tx;
+
+    // Pinned in the Rust transaction::canonical golden_vector_plain_transfer test.
+    expect(bytesToHex(signableBytes(body))).toBe(
+      '4e4f564154580200080000005472616e73666572170000006e6f76613173656e6465725f746573745f766563746f72190000006e6f76613172656365697665725f746573745f766563746f7240420f0000000000040000004e4f564164000000000000002a000000000000000068e5cf8b010000'
+    );
+    expect(tx.id).toBe(
+      '456ba57f8572b1655bb34dce62ceccb38736aa331d0f6d0218b93683bf0988b1'
+    );
+  });
+
   it('signing round-trips with a deterministic keypair', () => {
     const seed = new Uint8Array(32);
     seed[0] = 0x01;
@@ -0,0 +1,719 @@
+//! # Soak Test
+//!
+//! `nova-soak` runs a single dev validator under sustained synthetic load
+//! for hours and checks, every `--check-interval-secs`, the invariants that
+//! only break slowly:
+//!
+//! | Invariant     | Check                                                                  |
+//! |---------------|------------------------------------------------------------------------|
+//! | Supply        | `Σ balances + burned == genesis + minted`, holds equal `locked`        |
+//! | Replay        | a second state tree replaying the stored blocks reaches every root     |
+//! | Memory        | jemalloc's allocated bytes grow at most `--max-memory-growth-mb`       |
+//! | Bounds        | mempool count and bytes, and the gossip seen-cache, within their caps  |
+//! | Deduplication | a re-delivered gossip message is dropped                               |
+//!
+//! The first violation writes a JSON diagnostics dump to
+//! `--diagnostics-dir` and exits non-zero. A clean run prints a summary.
+//!
+//! ## Load
+//!
+//! Funded accounts send transfers to each other at `--tps`. Every transfer
+//! arrives as a gossip message from a synthetic peer and goes through the
+//! same dedup → mempool → producer → commit path as on a live validator;
+//! every committed block is broadcast back through gossip. Each sender keeps
+//! at most a few transfers in flight, numbered from its committed nonce, so
+//! the pool churns instead of filling with nonce gaps.
+//!
+//! ## Memory
+//!
+//! The baseline is taken at the first check after `--warmup-secs`. Both
+//! temporary databases keep a sled page cache of up to 1 GiB each, so the
+//! budget must leave room for them to fill; a leak shows up as growth past
+//! that plateau.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use parking_lot::RwLock;
+use serde::Serialize;
+use tikv_jemalloc_ctl::{epoch, stats};
+
+use nova_protocol::config::MIN_TX_FEE_PHOTONS;
+use nova_protocol::crypto::keys::NovaKeypair;
+use nova_protocol::identity::NovaId;
+use nova_protocol::network::gossip::{
+    GossipAction, GossipConfig, GossipMessage, GossipProtocol, PeerInfo, PeerTransport,
+};
+use nova_protocol::network::mempool::{Mempool, MempoolConfig};
+use nova_protocol::network::producer::BlockProducer;
+use nova_protocol::network::validation::BlockValidator;
+use nova_protocol::network::STATE_RECORD_KEYS;
+use nova_protocol::storage::block::Block;
+use nova_protocol::storage::db::NovaDB;
+use nova_protocol::storage::emission::{fund_genesis_account, SupplyAudit, SupplyRecord};
+use nova_protocol::storage::state::StateTree;
+use nova_protocol::transaction::builder::TransactionBuilder;
+use nova_protocol::transaction::signing::sign_transaction;
+use nova_protocol::transaction::types::{Amount, Currency, TransactionType};
+
+#[global_allocator]
+static ALLOCATOR: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+/// Photons moved by each transfer.
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+/// Balance of each account (1M NOVA) — enough for days of transfers.
+const ACCOUNT_BALANCE: u64 = 100_000_000_000_000;
+
+/// Most transfers a sender keeps pending at once.
+const SENDER_WINDOW: usize = 4;
+
+/// Every this many transfers, the same gossip message is delivered twice.
+const DUPLICATE_EVERY: u64 = 10;
+
+/// Hops a synthetic gossip message starts with.
+const GOSSIP_TTL: u8 = 5;
+
+/// Pause between iterations of the load loop.
+const TICK: Duration = Duration::from_millis(5);
+
+/// The synthetic peer all load arrives from.
+const LOAD_PEER: &str = "soak-load";
+
+// ---------------------------------------------------------------------------
+// Arguments
+// ---------------------------------------------------------------------------
+
+/// Runs a dev validator under sustained load and checks its invariants.
+#[derive(Debug, Clone, Parser)]
+#[command(name = "nova-soak", version)]
+pub struct SoakArgs {
+    /// How long to run, in seconds.
+    #[arg(long, env = "NOVA_SOAK_DURATION_SECS", default_value_t = 3600)]
+    pub duration_secs: u64,
+
+    /// Transfers offered per second.
+    #[arg(long, env = "NOVA_SOAK_TPS", default_value_t = 200)]
+    pub tps: u64,
+
+    /// Funded accounts the transfers rotate through.
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u64).range(2..))]
+    pub accounts: u64,
+
+    /// Milliseconds between blocks.
+    #[arg(long, default_value_t = 500)]
+    pub block_interval_ms: u64,
+
+    /// Most transactions per block.
+    #[arg(long, default_value_t = 500)]
+    pub block_size: usize,
+
+    /// Capacity of the gossip seen-cache. Small, so eviction runs often.
+    #[arg(long, default_value_t = 10_000)]
+    pub seen_cache_size: usize,
+
+    /// Seconds between invariant checks.
+    #[arg(long, env = "NOVA_SOAK_CHECK_INTERVAL_SECS", default_value_t = 30)]
+    pub check_interval_secs: u64,
+
+    /// Seconds of load before the memory baseline is taken.
+    #[arg(long, default_value_t = 60)]
+    pub warmup_secs: u64,
+
+    /// Most the allocated heap may grow past its baseline, in MiB.
+    #[arg(long, env = "NOVA_SOAK_MAX_MEMORY_GROWTH_MB", default_value_t = 3072)]
+    pub max_memory_growth_mb: u64,
+
+    /// Where the diagnostics dump is written on a violation.
+    #[arg(long, env = "NOVA_SOAK_DIAGNOSTICS_DIR", default_value = ".")]
+    pub diagnostics_dir: PathBuf,
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Counters of the load offered so far.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LoadStats {
+    /// Transfers delivered through gossip.
+    pub offered: u64,
+    /// Transfers the mempool accepted.
+    pub admitted: u64,
+    /// Transfers the mempool refused.
+    pub rejected: u64,
+    /// Re-delivered gossip messages dropped as duplicates.
+    pub duplicates_dropped: u64,
+    /// Offers skipped because the sender's window was full.
+    pub throttled: u64,
+    /// Blocks produced and committed.
+    pub blocks: u64,
+    /// Transactions included in those blocks.
+    pub included: u64,
+}
+
+/// jemalloc's view of the heap, in bytes.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct MemoryStats {
+    /// Bytes allocated by the application.
+    pub allocated: usize,
+    /// Bytes in active pages.
+    pub active: usize,
+    /// Bytes in physically resident pages.
+    pub resident: usize,
+    /// Bytes in mapped chunks.
+    pub mapped: usize,
+}
+
+impl MemoryStats {
+    /// Reads fresh statistics.
+    fn read() -> Result<Self> {
+        let ctl = |name: &str, e: tikv_jemalloc_ctl::Error| anyhow!("jemalloc {}: {}", name, e);
+        // Statistics are cached until the epoch advances.
+        epoch::advance().map_err(|e| ctl("epoch", e))?;
+        Ok(Self {
+            allocated: stats::allocated::read().map_err(|e| ctl("stats.allocated", e))?,
+            active: stats::active::read().map_err(|e| ctl("stats.active", e))?,
+            resident: stats::resident::read().map_err(|e| ctl("stats.resident", e))?,
+            mapped: stats::mapped::read().map_err(|e| ctl("stats.mapped", e))?,
+        })
+    }
+}
+
+/// Everything known about the validator when a check ran, written out on a
+/// violation.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostics {
+    /// The violated invariant, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violation: Option<Violation>,
+    /// Seconds since the soak started.
+    pub elapsed_secs: u64,
+    /// Height of the committed tip.
+    pub height: u64,
+    /// Load counters.
+    pub load: LoadStats,
+    /// Transactions pending in the mempool.
+    pub mempool_size: usize,
+    /// Bytes pending in the mempool.
+    pub mempool_bytes: usize,
+    /// Message hashes in the gossip seen-cache.
+    pub seen_count: usize,
+    /// Heap statistics.
+    pub memory: MemoryStats,
+    /// Allocated bytes at the memory baseline, once taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_baseline: Option<usize>,
+    /// Accounts in the state tree.
+    pub accounts: u64,
+    /// Sum of all account balances.
+    pub balances: u128,
+    /// The supply record.
+    pub supply: SupplyRecord,
+}
+
+/// A broken invariant.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    /// Which invariant: `supply`, `replay`, `memory`, `bounds` or `dedup`.
+    pub invariant: &'static str,
+    /// What was observed.
+    pub detail: String,
+}
+
+impl Violation {
+    fn new(invariant: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            invariant,
+            detail: detail.into(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+/// A second state tree that replays every stored block from genesis, one
+/// check at a time, and must reach each header's state root.
+struct Replica {
+    tree: StateTree,
+    validator: BlockValidator,
+    height: u64,
+    parent_validator: Option<String>,
+}
+
+impl Replica {
+    /// A replica funded like the live chain's genesis.
+    fn new(accounts: &[(NovaKeypair, String)]) -> Result<Self> {
+        let db = NovaDB::open_temporary().context("failed to open replica database")?;
+        let mut tree = StateTree::new(db);
+        for (_, address) in accounts {
+            fund_genesis_account(&mut tree, address, ACCOUNT_BALANCE);
+        }
+        Ok(Self {
+            tree,
+            validator: BlockValidator::new(),
+            height: 0,
+            parent_validator: None,
+        })
+    }
+
+    /// Replays the blocks stored in `db` after the replica's height, up to
+    /// `tip`.
+    fn catch_up(&mut self, db: &NovaDB, tip: u64) -> Result<(), Violation> {
+        for height in self.height + 1..=tip {
+            let block = db
+                .get_block(height)
+                .map_err(|e| Violation::new("replay", e.to_string()))?
+                .ok_or_else(|| Violation::new("replay", format!("block {} is missing", height)))?;
+            let applied = self
+                .validator
+                .transition()
+                .apply_block(&mut self.tree, &block, self.parent_validator.as_deref())
+                .map_err(|e| Violation::new("replay", format!("block {}: {}", height, e)))?;
+            self.validator
+                .check_state_root(&block, applied.state_root)
+                .map_err(|e| Violation::new("replay", format!("block {}: {}", height, e)))?;
+            self.height = height;
+            self.parent_validator = Some(block.header.validator.clone());
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Soak
+// ---------------------------------------------------------------------------
+
+/// The dev validator under test and the load driving it.
+struct Soak {
+    args: SoakArgs,
+    db: Arc<NovaDB>,
+    state_tree: Arc<RwLock<StateTree>>,
+    mempool: Arc<Mempool>,
+    producer: BlockProducer,
+    gossip: GossipProtocol,
+    mempool_config: MempoolConfig,
+    accounts: Vec<(NovaKeypair, String)>,
+    replica: Replica,
+    parent: Block,
+    load: LoadStats,
+    started: Instant,
+    memory_baseline: Option<usize>,
+}
+
+impl Soak {
+    /// Builds a dev chain in a temporary database and funds the accounts.
+    fn new(args: SoakArgs) -> Result<Self> {
+        let db = Arc::new(NovaDB::open_temporary().context("failed to open temporary database")?);
+        let state_tree = Arc::new(RwLock::new(StateTree::new((*db).clone())));
+        let mempool_config = MempoolConfig::default();
+        let mempool = Arc::new(Mempool::new(mempool_config.clone()));
+        let producer = BlockProducer::new(
+            Arc::clone(&db),
+            Arc::clone(&state_tree),
+            Arc::clone(&mempool),
+            NovaKeypair::generate(),
+        );
+
+        let gossip = GossipProtocol::new(GossipConfig {
+            seen_cache_size: args.seen_cache_size,
+            ..GossipConfig::default()
+        });
+        gossip.add_peer(PeerInfo {
+            peer_id: LOAD_PEER.to_string(),
+            address: "/ip4/127.0.0.1/tcp/9740".to_string(),
+            connected_at: 0,
+            last_seen: 0,
+            transport: PeerTransport::Tcp,
+        });
+
+        let accounts: Vec<(NovaKeypair, String)> = (0..args.accounts)
+            .map(|_| {
+                let kp = NovaKeypair::generate();
+                let address = NovaId::from_public_key(&kp.public_key()).to_address();
+                (kp, address)
+            })
+            .collect();
+        {
+            let mut tree = state_tree.write();
+            for (_, address) in &accounts {
+                fund_genesis_account(&mut tree, address, ACCOUNT_BALANCE);
+            }
+        }
+        let genesis = Block::genesis();
+        db.put_block(&genesis)?;
+        state_tree.read().commit(0);
+        let replica = Replica::new(&accounts)?;
+
+        Ok(Self {
+            args,
+            db,
+            state_tree,
+            mempool,
+            producer,
+            gossip,
+            mempool_config,
+            accounts,
+            replica,
+            parent: genesis,
+            load: LoadStats::default(),
+            started: Instant::now(),
+            memory_baseline: None,
+        })
+    }
+
+    /// Runs until `--duration-secs` is up or an invariant breaks. Errors
+    /// are infrastructure failures; a broken invariant is returned in the
+    /// diagnostics.
+    fn run(&mut self) -> Result<Diagnostics> {
+        let duration = Duration::from_secs(self.args.duration_secs);
+        let block_interval = Duration::from_millis(self.args.block_interval_ms.max(1));
+        let check_interval = Duration::from_secs(self.args.check_interval_secs.max(1));
+        let mut next_block = block_interval;
+        let mut next_check = check_interval;
+
+        loop {
+            let elapsed = self.started.elapsed();
+            let due = (elapsed.as_millis() as u64).saturating_mul(self.args.tps) / 1000;
+            if let Err(violation) = self.offer_until(due) {
+                return self.diagnose(Some(violation));
+            }
+
+            if elapsed >= next_block {
+                self.produce()?;
+                next_block = elapsed + block_interval;
+            }
+
+            let finished = elapsed >= duration;
+            if elapsed >= next_check || finished {
+                let diagnostics = self.check()?;
+                if diagnostics.violation.is_some() || finished {
+                    return Ok(diagnostics);
+                }
+                print_progress(&diagnostics);
+                next_check = elapsed + check_interval;
+            }
+
+            std::thread::sleep(TICK);
+        }
+    }
+
+    /// Offers transfers until `due` have been offered in total, or every
+    /// sender's window is full.
+    fn offer_until(&mut self, due: u64) -> Result<(), Violation> {
+        let mut skipped = 0;
+        while self.load.offered + self.load.throttled < due && skipped < self.accounts.len() {
+            let k = self.load.offered + self.load.throttled;
+            let sender = (k % self.accounts.len() as u64) as usize;
+            let receiver = (sender + 1) % self.accounts.len();
+            let (kp, address) = &self.accounts[sender];
+
+            let pending = self.mempool.pending_for_sender(address).len();
+            if pending >= SENDER_WINDOW {
+                self.load.throttled += 1;
+                skipped += 1;
+                continue;
+            }
+            skipped = 0;
+
+            let account_nonce = self
+                .state_tree
+                .read()
+                .get(address)
+                .map_or(1, |a| a.next_nonce());
+            let mut tx = TransactionBuilder::new(TransactionType::Transfer)
+                .sender(address)
+                .receiver(&self.accounts[receiver].1)
+                .amount(Amount::new(TRANSFER_AMOUNT, Currency::NOVA))
+                .fee(MIN_TX_FEE_PHOTONS + k % 1_000)
+                .nonce(account_nonce + pending as u64)
+                .build()
+                .expect("soak transfers are well-formed");
+            sign_transaction(&mut tx, kp);
+
+            let message = GossipMessage::NewTransaction {
+                transaction: tx,
+                ttl: GOSSIP_TTL,
+            };
+            let duplicate = (k % DUPLICATE_EVERY == 0).then(|| message.clone());
+            self.load.offered += 1;
+            for action in self.gossip.handle_message(LOAD_PEER, message) {
+                if let GossipAction::AddToMempool(tx) = action {
+                    match self.mempool.add(tx) {
+                        Ok(()) => self.load.admitted += 1,
+                        Err(_) => self.load.rejected += 1,
+                    }
+                }
+            }
+
+            if let Some(message) = duplicate {
+                let actions = self.gossip.handle_message(LOAD_PEER, message);
+                if !matches!(actions.as_slice(), [GossipAction::Drop]) {
+                    return Err(Violation::new(
+                        "dedup",
+                        format!("a re-delivered transfer produced {:?}", actions),
+                    ));
+                }
+                self.load.duplicates_dropped += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Produces, commits and broadcasts the next block.
+    fn produce(&mut self) -> Result<()> {
+        self.mempool.expire_old();
+        let produced = self
+            .producer
+            .produce_block(&self.parent, self.args.block_size)?;
+        self.producer.commit_block(&produced.block)?;
+        self.load.blocks += 1;
+        self.load.included += produced.block.transactions.len() as u64;
+        self.gossip.broadcast(GossipMessage::NewBlock {
+            block: produced.block.clone(),
+            ttl: GOSSIP_TTL,
+        });
+        self.parent = produced.block;
+        Ok(())
+    }
+
+    /// Checks every invariant and gathers the diagnostics.
+    fn check(&mut self) -> Result<Diagnostics> {
+        let violation = self.check_invariants()?.err();
+        self.diagnose(violation)
+    }
+
+    /// Checks the invariants in the order of the module docs.
+    fn check_invariants(&mut self) -> Result<Result<(), Violation>> {
+        let audit = SupplyAudit::take(&self.state_tree.read(), STATE_RECORD_KEYS);
+        if let Err(e) = audit.check() {
+            return Ok(Err(Violation::new("supply", e.to_string())));
+        }
+
+        let tip = self.parent.header.height;
+        if let Err(violation) = self.replica.catch_up(&self.db, tip) {
+            return Ok(Err(violation));
+        }
+        let live_root = self.state_tree.read().root();
+        if self.replica.tree.root() != live_root {
+            return Ok(Err(Violation::new(
+                "replay",
+                format!("replica diverged from the live state at height {}", tip),
+            )));
+        }
+
+        let memory = MemoryStats::read()?;
+        if let Some(baseline) = self.memory_baseline {
+            let growth = memory.allocated.saturating_sub(baseline);
+            let budget = self.args.max_memory_growth_mb as usize * 1024 * 1024;
+            if growth > budget {
+                return Ok(Err(Violation::new(
+                    "memory",
+                    format!(
+                        "allocated grew {} bytes past its baseline of {} (budget {})",
+                        growth, baseline, budget
+                    ),
+                )));
+            }
+        } else if self.started.elapsed() >= Duration::from_secs(self.args.warmup_secs) {
+            self.memory_baseline = Some(memory.allocated);
+        }
+
+        Ok(self.check_bounds())
+    }
+
+    /// The mempool and the seen-cache stay within their configured caps.
+    fn check_bounds(&self) -> Result<(), Violation> {
+        let size = self.mempool.size();
+        if size > self.mempool_config.max_size {
+            return Err(Violation::new(
+                "bounds",
+                format!(
+                    "mempool holds {} transactions, cap {}",
+                    size, self.mempool_config.max_size
+                ),
+            ));
+        }
+        let bytes = self.mempool.total_bytes();
+        if bytes > self.mempool_config.max_bytes {
+            return Err(Violation::new(
+                "bounds",
+                format!(
+                    "mempool holds {} bytes, cap {}",
+                    bytes, self.mempool_config.max_bytes
+                ),
+            ));
+        }
+        let seen = self.gossip.seen_count();
+        if seen > self.args.seen_cache_size {
+            return Err(Violation::new(
+                "bounds",
+                format!(
+                    "seen-cache holds {} hashes, cap {}",
+                    seen, self.args.seen_cache_size
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Snapshots the validator's state.
+    fn diagnose(&self, violation: Option<Violation>) -> Result<Diagnostics> {
+        let audit = SupplyAudit::take(&self.state_tree.read(), STATE_RECORD_KEYS);
+        Ok(Diagnostics {
+            violation,
+            elapsed_secs: self.started.elapsed().as_secs(),
+            height: self.parent.header.height,
+            load: self.load.clone(),
+            mempool_size: self.mempool.size(),
+            mempool_bytes: self.mempool.total_bytes(),
+            seen_count: self.gossip.seen_count(),
+            memory: MemoryStats::read()?,
+            memory_baseline: self.memory_baseline,
+            accounts: audit.accounts,
+            balances: audit.balances,
+            supply: audit.record,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+/// One line per check.
+fn print_progress(d: &Diagnostics) {
+    println!(
+        "[{:>6}s] height {} | {} offered, {} admitted, {} included | mempool {} | seen {} | allocated {:.1} MiB",
+        d.elapsed_secs,
+        d.height,
+        d.load.offered,
+        d.load.admitted,
+        d.load.included,
+        d.mempool_size,
+        d.seen_count,
+        d.memory.allocated as f64 / (1024.0 * 1024.0)
+    );
+}
+
+/// Writes `diagnostics` to a timestamped file in `dir` and returns its path.
+fn dump_diagnostics(dir: &Path, diagnostics: &Diagnostics) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = dir.join(format!("soak-failure-{}.json", stamp));
+    fs::write(&path, serde_json::to_vec_pretty(diagnostics)?)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn main() -> Result<()> {
+    let args = SoakArgs::parse();
+    println!(
+        "Soaking a dev validator for {} s at {} tx/s across {} accounts...",
+        args.duration_secs, args.tps, args.accounts
+    );
+
+    let diagnostics_dir = args.diagnostics_dir.clone();
+    let diagnostics = Soak::new(args)?.run()?;
+    if let Some(violation) = &diagnostics.violation {
+        let path = dump_diagnostics(&diagnostics_dir, &diagnostics)?;
+        bail!(
+            "{} invariant violated after {} s: {} (diagnostics in {})",
+            violation.invariant,
+            diagnostics.elapsed_secs,
+            violation.detail,
+            path.display()
+        );
+    }
+
+    print_progress(&diagnostics);
+    println!(
+        "Soak OK: {} blocks, {} transfers included, every invariant held",
+        diagnostics.load.blocks, diagnostics.load.included
+    );
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_protocol::storage::state::AccountState;
+
+    fn short_args() -> SoakArgs {
+        SoakArgs::parse_from([
+            "nova-soak",
+            "--duration-secs",
+            "2",
+            "--tps",
+            "100",
+            "--accounts",
+            "8",
+            "--block-interval-ms",
+            "100",
+            "--seen-cache-size",
+            "50",
+            "--check-interval-secs",
+            "1",
+            "--warmup-secs",
+            "0",
+        ])
+    }
+
+    #[test]
+    fn short_soak_holds_every_invariant() {
+        let diagnostics = Soak::new(short_args()).unwrap().run().unwrap();
+        assert!(
+            diagnostics.violation.is_none(),
+            "{:?}",
+            diagnostics.violation
+        );
+        assert!(diagnostics.height > 0);
+        assert!(diagnostics.load.included > 0);
+        assert!(diagnostics.load.duplicates_dropped > 0);
+        assert!(diagnostics.seen_count <= 50);
+        assert!(diagnostics.memory_baseline.is_some());
+    }
+
+    #[test]
+    fn unbooked_balance_breaks_supply() {
+        let mut soak = Soak::new(short_args()).unwrap();
+        let address = soak.accounts[0].1.clone();
+        soak.state_tree
+            .write()
+            .put(&address, &AccountState::with_balance(1));
+
+        let diagnostics = soak.check().unwrap();
+        let violation = diagnostics.violation.expect("supply is off");
+        assert_eq!(violation.invariant, "supply");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dump_diagnostics(dir.path(), &diagnostics).unwrap();
+        let dumped: serde_json::Value = serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+        assert_eq!(dumped["violation"]["invariant"], "supply");
+    }
+
+    #[test]
+    fn replica_catches_a_forged_state_root() {
+        let mut soak = Soak::new(short_args()).unwrap();
+        soak.produce().unwrap();
+        let mut forged = soak.parent.clone();
+        forged.header.state_root = [7u8; 32].into();
+        soak.db.put_block(&forged).unwrap();
+
+        let violation = soak.replica.catch_up(&soak.db, 1).unwrap_err();
+        assert_eq!(violation.invariant, "replay");
+    }
+}
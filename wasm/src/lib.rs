@@ -0,0 +1,311 @@
+//! # NOVA WASM Bindings
+//!
+//! `wasm-bindgen` wrappers that let a browser wallet generate keys, derive
+//! addresses, build and sign transactions, hand transactions to an offline
+//! signer, open encrypted payloads, and check confidential-transfer proofs
+//! entirely client-side. Secret keys never leave the page.
+//!
+//! The crate links `nova-protocol` with only the `std` and `zkp` features,
+//! so libp2p, sled and tokio stay out of the module. Build it with:
+//!
+//! ```text
+//! wasm-pack build wasm --target web
+//! ```
+//!
+//! ## Wire Format
+//!
+//! Transactions cross the JS boundary as JSON strings in the same serde
+//! shape the node and validators use, so a signed transaction needs no
+//! re-encoding before it is submitted. Errors surface as JS `Error` objects
+//! carrying the protocol error message.
+//!
+//! ## Example (JavaScript)
+//!
+//! ```text
+//! import init, { Keypair, buildTransaction, signTransaction } from "nova-wasm";
+//!
+//! await init();
+//! const kp = new Keypair();
+//! const unsigned = buildTransaction(JSON.stringify({
+//!   txType: "Transfer",
+//!   sender: kp.address,
+//!   receiver: "nova1...",
+//!   amount: 1_000_000,
+//!   fee: 1_000,
+//!   nonce: 1,
+//! }));
+//! const signed = signTransaction(unsigned, kp);
+//! ```
+
+use nova_protocol::crypto::{NovaKeypair, NovaPublicKey, NovaSignature};
+use nova_protocol::identity::{self, AddressPolicy, NovaId};
+use nova_protocol::transaction::bundle::{DEFAULT_QR_CHUNK_LEN, QR_CHUNK_PREFIX};
+use nova_protocol::transaction::{
+    self, Amount, Currency, Transaction, TransactionBuilder, TransactionBundle, TransactionType,
+};
+use nova_protocol::zkp::{BalanceVerifier, PedersenParams};
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+// ---------------------------------------------------------------------------
+// Keys
+// ---------------------------------------------------------------------------
+
+/// An Ed25519 signing keypair held in WASM memory.
+#[wasm_bindgen]
+pub struct Keypair {
+    inner: NovaKeypair,
+}
+
+#[wasm_bindgen]
+impl Keypair {
+    /// Generates a fresh keypair from `crypto.getRandomValues`.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Keypair {
+        Keypair {
+            inner: NovaKeypair::generate(),
+        }
+    }
+
+    /// Derives a keypair from a 32-byte seed (e.g. a BIP-39 / KDF output).
+    #[wasm_bindgen(js_name = fromSeed)]
+    pub fn from_seed(seed: &[u8]) -> Result<Keypair, JsError> {
+        let seed: &[u8; 32] = seed
+            .try_into()
+            .map_err(|_| JsError::new(&format!("seed must be 32 bytes, got {}", seed.len())))?;
+        Ok(Keypair {
+            inner: NovaKeypair::from_seed(seed),
+        })
+    }
+
+    /// Restores a keypair from its hex-encoded secret key.
+    #[wasm_bindgen(js_name = fromSecretKeyHex)]
+    pub fn from_secret_key_hex(secret_hex: &str) -> Result<Keypair, JsError> {
+        let inner = NovaKeypair::from_hex(secret_hex).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(Keypair { inner })
+    }
+
+    /// Hex-encoded secret key, for the wallet's own encrypted storage.
+    #[wasm_bindgen(js_name = secretKeyHex)]
+    pub fn secret_key_hex(&self) -> String {
+        hex::encode(self.inner.secret_key_bytes())
+    }
+
+    /// Hex-encoded 32-byte public key.
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> String {
+        self.inner.public_key_hex()
+    }
+
+    /// The Bech32 `nova1...` address for this key.
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        NovaId::from_public_key(&self.inner.public_key()).to_address()
+    }
+
+    /// Signs an arbitrary message, returning the 64-byte signature.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.inner.sign(message).as_bytes().to_vec()
+    }
+
+    /// Signs `message` for "sign in with NOVA", returning the signed
+    /// message as JSON (`address`, `message`, `public_key`, `signature`) —
+    /// the shape `nova_verifyMessage` accepts.
+    #[wasm_bindgen(js_name = signMessage)]
+    pub fn sign_message(&self, message: &str) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&identity::SignedMessage::sign(
+            &self.inner,
+            message,
+        ))?)
+    }
+}
+
+/// Derives the Bech32 address for a hex-encoded public key.
+#[wasm_bindgen(js_name = deriveAddress)]
+pub fn derive_address(public_key_hex: &str) -> Result<String, JsError> {
+    let pk = parse_public_key(public_key_hex)?;
+    Ok(NovaId::from_public_key(&pk).to_address())
+}
+
+/// Returns `true` if `address` is a well-formed, checksummed NOVA address.
+#[wasm_bindgen(js_name = isValidAddress)]
+pub fn is_valid_address(address: &str) -> bool {
+    AddressPolicy::Strict.check(address).is_ok()
+}
+
+/// Checks a detached signature. Malformed keys or signatures yield `false`.
+#[wasm_bindgen(js_name = verifySignature)]
+pub fn verify_signature(public_key_hex: &str, message: &[u8], signature: &[u8]) -> bool {
+    let Ok(pk) = NovaPublicKey::from_hex(public_key_hex) else {
+        return false;
+    };
+    let Ok(sig) = <[u8; 64]>::try_from(signature) else {
+        return false;
+    };
+    pk.verify(message, &NovaSignature::from_bytes(sig))
+}
+
+/// Verifies a signed message produced by `Keypair.signMessage` or
+/// `nova-node keys sign-message`. Throws with the reason if it does not
+/// verify.
+#[wasm_bindgen(js_name = verifyMessage)]
+pub fn verify_message(signed_json: &str) -> Result<(), JsError> {
+    let signed: identity::SignedMessage = serde_json::from_str(signed_json)
+        .map_err(|e| JsError::new(&format!("invalid signed message: {e}")))?;
+    signed.verify().map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn parse_public_key(public_key_hex: &str) -> Result<NovaPublicKey, JsError> {
+    NovaPublicKey::from_hex(public_key_hex)
+        .map_err(|e| JsError::new(&format!("invalid public key: {e}")))
+}
+
+// ---------------------------------------------------------------------------
+// Transactions
+// ---------------------------------------------------------------------------
+
+/// JSON accepted by [`build_transaction`].
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionRequest {
+    tx_type: TransactionType,
+    sender: String,
+    receiver: String,
+    amount: u64,
+    #[serde(default = "default_currency")]
+    currency: Currency,
+    fee: u64,
+    nonce: u64,
+    /// Unix milliseconds; defaults to `Date.now()`.
+    #[serde(default)]
+    timestamp: Option<u64>,
+    /// Hex-encoded payload bytes.
+    #[serde(default)]
+    payload: Option<String>,
+    /// Hex-encoded public key of the receiver; when set, the payload is
+    /// sealed so only they can read it.
+    #[serde(default)]
+    encrypt_to: Option<String>,
+}
+
+fn default_currency() -> Currency {
+    Currency::NOVA
+}
+
+/// Builds an unsigned transaction from a JSON request, enforcing the same
+/// field limits the mempool does. Returns the transaction as JSON.
+#[wasm_bindgen(js_name = buildTransaction)]
+pub fn build_transaction(request_json: &str) -> Result<String, JsError> {
+    let req: TransactionRequest = serde_json::from_str(request_json)?;
+
+    let mut builder = TransactionBuilder::new(req.tx_type)
+        .sender(&req.sender)
+        .receiver(&req.receiver)
+        .amount(Amount::new(req.amount, req.currency))
+        .fee(req.fee)
+        .nonce(req.nonce);
+    if let Some(ts) = req.timestamp {
+        builder = builder.timestamp(ts);
+    }
+    if let Some(payload) = req.payload {
+        let bytes = hex::decode(&payload)
+            .map_err(|e| JsError::new(&format!("invalid payload hex: {e}")))?;
+        builder = builder.payload(bytes);
+    }
+    if let Some(receiver_key) = req.encrypt_to {
+        builder = builder
+            .encrypt_payload_for(&parse_public_key(&receiver_key)?)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+    }
+
+    let tx = builder.build().map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(serde_json::to_string(&tx)?)
+}
+
+/// Signs a transaction JSON with `keypair` and returns the signed JSON.
+#[wasm_bindgen(js_name = signTransaction)]
+pub fn sign_transaction(tx_json: &str, keypair: &Keypair) -> Result<String, JsError> {
+    let mut tx: Transaction = serde_json::from_str(tx_json)?;
+    transaction::sign_transaction(&mut tx, &keypair.inner);
+    Ok(serde_json::to_string(&tx)?)
+}
+
+/// Runs the stateless validator checks (ID, signature, field limits) on a
+/// transaction JSON. Throws with the rejection reason if any fail.
+#[wasm_bindgen(js_name = verifyTransaction)]
+pub fn verify_transaction(tx_json: &str) -> Result<(), JsError> {
+    let tx: Transaction = serde_json::from_str(tx_json)?;
+    transaction::verify_transaction(&tx).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Opens the encrypted payload of a transaction JSON addressed to
+/// `keypair`. Returns `undefined` if the payload is not encrypted; throws
+/// if it is but does not open with this key.
+#[wasm_bindgen(js_name = decryptPayload)]
+pub fn decrypt_payload(tx_json: &str, keypair: &Keypair) -> Result<Option<Vec<u8>>, JsError> {
+    let tx: Transaction = serde_json::from_str(tx_json)?;
+    tx.open_payload(&keypair.inner)
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Offline signing bundles
+// ---------------------------------------------------------------------------
+
+/// Wraps an unsigned transaction JSON in an offline signing bundle (see
+/// `nova-node tx sign`). Returns the bundle as JSON.
+#[wasm_bindgen(js_name = exportBundle)]
+pub fn export_bundle(tx_json: &str) -> Result<String, JsError> {
+    let tx: Transaction = serde_json::from_str(tx_json)?;
+    let bundle = TransactionBundle::export(tx).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(bundle.to_json())
+}
+
+/// Splits a bundle JSON into `NOVATX/...` strings, one per QR code.
+#[wasm_bindgen(js_name = bundleQrChunks)]
+pub fn bundle_qr_chunks(bundle_json: &str) -> Result<Vec<String>, JsError> {
+    let bundle =
+        TransactionBundle::from_json(bundle_json).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(bundle.to_qr_chunks(DEFAULT_QR_CHUNK_LEN))
+}
+
+/// Checks a signed bundle — JSON, or scanned QR chunks one per line — and
+/// returns its transaction JSON, ready for `nova_sendTransaction`.
+#[wasm_bindgen(js_name = importSignedBundle)]
+pub fn import_signed_bundle(bundle: &str) -> Result<String, JsError> {
+    let parsed = if bundle.trim_start().starts_with(QR_CHUNK_PREFIX) {
+        let chunks: Vec<&str> = bundle.lines().filter(|l| !l.trim().is_empty()).collect();
+        TransactionBundle::from_qr_chunks(&chunks)
+    } else {
+        TransactionBundle::from_json(bundle)
+    };
+    let tx = parsed
+        .and_then(|b| b.signed_transaction())
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(serde_json::to_string(&tx)?)
+}
+
+// ---------------------------------------------------------------------------
+// Proofs
+// ---------------------------------------------------------------------------
+
+/// Verifies the Groth16 balance proof on a confidential transfer.
+///
+/// `verifying_key` and `pedersen_params` are the compressed bytes published
+/// with the network's trusted setup. Returns `false` for a well-formed but
+/// invalid proof; throws if anything fails to deserialize.
+#[wasm_bindgen(js_name = verifyConfidentialProof)]
+pub fn verify_confidential_proof(
+    tx_json: &str,
+    verifying_key: &[u8],
+    pedersen_params: &[u8],
+) -> Result<bool, JsError> {
+    let tx: Transaction = serde_json::from_str(tx_json)?;
+    let params = PedersenParams::from_bytes(pedersen_params)
+        .map_err(|e| JsError::new(&format!("invalid Pedersen parameters: {e}")))?;
+    let verifier = BalanceVerifier::vk_from_bytes(verifying_key, params)
+        .map_err(|e| JsError::new(&format!("{e:#}")))?;
+    transaction::verify_confidential_proof(&tx, &verifier)
+        .map_err(|e| JsError::new(&format!("{e:#}")))
+}
@@ -0,0 +1,113 @@
+//! Runs under `wasm-pack test --node wasm`; compiled out on native targets.
+
+#![cfg(target_arch = "wasm32")]
+
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use nova_protocol::transaction::create_confidential_transfer;
+use nova_protocol::zkp::BalanceProver;
+use nova_wasm::*;
+use wasm_bindgen_test::*;
+
+fn transfer_request(sender: &str, receiver: &str) -> String {
+    format!(
+        r#"{{"txType":"Transfer","sender":"{sender}","receiver":"{receiver}","amount":1000000,"fee":1000,"nonce":0,"timestamp":1700000000000}}"#
+    )
+}
+
+#[wasm_bindgen_test]
+fn keypair_address_matches_derived_address() {
+    let kp = Keypair::new();
+    assert!(is_valid_address(&kp.address()));
+    assert_eq!(derive_address(&kp.public_key()).unwrap(), kp.address());
+
+    let restored = Keypair::from_secret_key_hex(&kp.secret_key_hex()).unwrap();
+    assert_eq!(restored.public_key(), kp.public_key());
+}
+
+#[wasm_bindgen_test]
+fn seeded_keys_are_deterministic() {
+    let a = Keypair::from_seed(&[7u8; 32]).unwrap();
+    let b = Keypair::from_seed(&[7u8; 32]).unwrap();
+    assert_eq!(a.address(), b.address());
+    assert!(Keypair::from_seed(&[7u8; 31]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn detached_signatures_verify() {
+    let kp = Keypair::new();
+    let sig = kp.sign(b"hello");
+    assert!(verify_signature(&kp.public_key(), b"hello", &sig));
+    assert!(!verify_signature(&kp.public_key(), b"hellp", &sig));
+    assert!(!verify_signature("zz", b"hello", &sig));
+}
+
+#[wasm_bindgen_test]
+fn build_sign_verify_round_trip() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+
+    let unsigned =
+        build_transaction(&transfer_request(&sender.address(), &receiver.address())).unwrap();
+    assert!(
+        verify_transaction(&unsigned).is_err(),
+        "unsigned must not verify"
+    );
+
+    let signed = sign_transaction(&unsigned, &sender).unwrap();
+    assert!(verify_transaction(&signed).is_ok());
+
+    let tampered = signed.replace("1000000", "9000000");
+    assert!(verify_transaction(&tampered).is_err());
+}
+
+#[wasm_bindgen_test]
+fn encrypted_payload_opens_for_the_receiver_only() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let request = format!(
+        r#"{{"txType":"Transfer","sender":"{}","receiver":"{}","amount":1,"fee":1000,"nonce":0,"payload":"{}","encryptTo":"{}"}}"#,
+        sender.address(),
+        receiver.address(),
+        hex::encode(b"invoice 42"),
+        receiver.public_key()
+    );
+    let signed = sign_transaction(&build_transaction(&request).unwrap(), &sender).unwrap();
+    assert!(verify_transaction(&signed).is_ok());
+
+    assert_eq!(
+        decrypt_payload(&signed, &receiver).unwrap(),
+        Some(b"invoice 42".to_vec())
+    );
+    assert!(decrypt_payload(&signed, &sender).is_err());
+}
+
+#[wasm_bindgen_test]
+fn build_rejects_malformed_addresses() {
+    let receiver = Keypair::new();
+    assert!(build_transaction(&transfer_request("nova1alice", &receiver.address())).is_err());
+}
+
+#[wasm_bindgen_test]
+fn confidential_proof_verifies_in_wasm() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let (prover, verifier) = BalanceProver::setup(&mut rng);
+
+    let kp = Keypair::from_seed(&[1u8; 32]).unwrap();
+    let receiver = Keypair::new();
+    let tx = create_confidential_transfer(
+        &kp.address(),
+        &receiver.address(),
+        500,
+        Fr::rand(&mut rng),
+        &prover,
+    )
+    .unwrap();
+    let tx_json = sign_transaction(&serde_json::to_string(&tx).unwrap(), &kp).unwrap();
+
+    let vk = verifier.vk_to_bytes();
+    let params = verifier.pedersen_params().to_bytes();
+    assert!(verify_confidential_proof(&tx_json, &vk, &params).unwrap());
+    assert!(verify_confidential_proof(&tx_json, &vk[..vk.len() - 1], &params).is_err());
+}